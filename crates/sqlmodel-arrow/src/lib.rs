@@ -0,0 +1,133 @@
+//! Apache Arrow interop for SQLModel Rust.
+//!
+//! `sqlmodel-arrow` moves query results and bulk loads between SQLModel and
+//! Arrow `RecordBatch`es, so data can flow into Polars, DataFusion and other
+//! columnar tools without going through model structs.
+//!
+//! # Role In The Architecture
+//!
+//! - **Reads**: [`SelectArrowExt::to_arrow`] runs a [`Select`](sqlmodel_query::Select)
+//!   and builds each Arrow column in one pass over the result rows.
+//! - **Writes**: [`bulk_insert_arrow`] maps the columns of a batch onto a
+//!   model's table by name and inserts them with multi-row `INSERT`s.
+//! - **Types**: [`arrow_type`] maps each [`SqlType`] to the Arrow type used for
+//!   its column; [`model_schema`] applies it to a whole model.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use sqlmodel::prelude::*;
+//! use sqlmodel_arrow::{SelectArrowExt, bulk_insert_arrow};
+//!
+//! let batches = select!(Hero).filter(Expr::col("age").gt(30)).to_arrow(&cx, &conn).await?;
+//! bulk_insert_arrow::<Hero, _>(&cx, &archive_conn, &batches[0]).await?;
+//! ```
+
+mod read;
+mod write;
+
+use std::sync::Arc;
+
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef, TimeUnit};
+use sqlmodel_core::{Error, FieldInfo, Model, SqlType};
+
+pub use arrow_array::RecordBatch;
+pub use read::{DEFAULT_BATCH_ROWS, SelectArrowExt, rows_to_record_batches};
+pub use write::bulk_insert_arrow;
+
+/// Arrow type used for columns of `sql_type`.
+///
+/// JSON, arrays, enums and custom types are carried as UTF-8 text; decimals
+/// outside Arrow's 1..=38 precision range fall back to text as well.
+pub fn arrow_type(sql_type: &SqlType) -> DataType {
+    match sql_type {
+        SqlType::Boolean => DataType::Boolean,
+        SqlType::TinyInt => DataType::Int8,
+        SqlType::SmallInt => DataType::Int16,
+        SqlType::Integer => DataType::Int32,
+        SqlType::BigInt => DataType::Int64,
+        SqlType::Real => DataType::Float32,
+        SqlType::Double => DataType::Float64,
+        SqlType::Numeric { precision, scale } | SqlType::Decimal { precision, scale } => {
+            match i8::try_from(*scale) {
+                Ok(scale) if (1..=38).contains(precision) => {
+                    DataType::Decimal128(*precision, scale)
+                }
+                _ => DataType::Utf8,
+            }
+        }
+        SqlType::Binary(_) | SqlType::VarBinary(_) | SqlType::Blob => DataType::Binary,
+        SqlType::Date => DataType::Date32,
+        SqlType::Time => DataType::Time64(TimeUnit::Microsecond),
+        SqlType::DateTime | SqlType::Timestamp => DataType::Timestamp(TimeUnit::Microsecond, None),
+        SqlType::TimestampTz => DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+        SqlType::Uuid => DataType::FixedSizeBinary(16),
+        SqlType::Char(_)
+        | SqlType::VarChar(_)
+        | SqlType::Text
+        | SqlType::CiText
+        | SqlType::Json
+        | SqlType::JsonB
+        | SqlType::Array(_)
+        | SqlType::Enum(_)
+        | SqlType::Custom(_) => DataType::Utf8,
+    }
+}
+
+/// Arrow field for one model column.
+pub fn arrow_field(field: &FieldInfo) -> Field {
+    Field::new(
+        field.column_name,
+        arrow_type(&field.sql_type),
+        field.nullable,
+    )
+}
+
+/// Arrow schema with one field per column `M` selects by default.
+pub fn model_schema<M: Model>() -> SchemaRef {
+    let fields: Vec<Field> = M::select_columns()
+        .into_iter()
+        .filter_map(|column| model_field::<M>(column))
+        .map(arrow_field)
+        .collect();
+    Arc::new(Schema::new(fields))
+}
+
+fn model_field<M: Model>(column: &str) -> Option<&'static FieldInfo> {
+    M::fields().iter().find(|f| f.column_name == column)
+}
+
+#[allow(clippy::needless_pass_by_value)] // Used as a `map_err` adapter
+fn arrow_error(e: ArrowError) -> Error {
+    Error::Custom(format!("arrow: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arrow_type_mapping() {
+        assert_eq!(arrow_type(&SqlType::BigInt), DataType::Int64);
+        assert_eq!(arrow_type(&SqlType::VarChar(20)), DataType::Utf8);
+        assert_eq!(
+            arrow_type(&SqlType::Decimal {
+                precision: 10,
+                scale: 2
+            }),
+            DataType::Decimal128(10, 2)
+        );
+        assert_eq!(
+            arrow_type(&SqlType::Numeric {
+                precision: 0,
+                scale: 0
+            }),
+            DataType::Utf8
+        );
+        assert_eq!(
+            arrow_type(&SqlType::TimestampTz),
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+        );
+        assert_eq!(arrow_type(&SqlType::Uuid), DataType::FixedSizeBinary(16));
+    }
+}
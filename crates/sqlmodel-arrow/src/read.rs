@@ -0,0 +1,404 @@
+//! Query results to Arrow record batches.
+
+use std::future::Future;
+use std::sync::Arc;
+
+use arrow_array::{
+    ArrayRef, BinaryArray, BooleanArray, Date32Array, Decimal128Array, FixedSizeBinaryArray,
+    Float32Array, Float64Array, Int8Array, Int16Array, Int32Array, Int64Array, RecordBatch,
+    StringArray, Time64MicrosecondArray, TimestampMicrosecondArray,
+};
+use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use sqlmodel_core::{Connection, Cx, Error, Model, Outcome, Row, Value};
+use sqlmodel_query::Select;
+
+use crate::{arrow_error, arrow_field, model_field, model_schema};
+
+/// Rows per [`RecordBatch`] produced by [`SelectArrowExt::to_arrow`].
+pub const DEFAULT_BATCH_ROWS: usize = 8192;
+
+/// Run a query and collect the result as Arrow record batches.
+pub trait SelectArrowExt {
+    /// Execute the query and return its rows as batches of at most
+    /// [`DEFAULT_BATCH_ROWS`] rows.
+    ///
+    /// Always returns at least one batch, so an empty result still carries
+    /// its schema.
+    fn to_arrow<C: Connection>(
+        self,
+        cx: &Cx,
+        conn: &C,
+    ) -> impl Future<Output = Outcome<Vec<RecordBatch>, Error>> + Send;
+}
+
+impl<M: Model> SelectArrowExt for Select<M> {
+    fn to_arrow<C: Connection>(
+        self,
+        cx: &Cx,
+        conn: &C,
+    ) -> impl Future<Output = Outcome<Vec<RecordBatch>, Error>> + Send {
+        let (sql, params) = self.build_with_dialect(conn.sql_dialect());
+        async move {
+            match conn.query(cx, &sql, &params).await {
+                Outcome::Ok(rows) => match rows_to_record_batches::<M>(&rows, DEFAULT_BATCH_ROWS) {
+                    Ok(batches) => Outcome::Ok(batches),
+                    Err(e) => Outcome::Err(e),
+                },
+                Outcome::Err(e) => Outcome::Err(e),
+                Outcome::Cancelled(r) => Outcome::Cancelled(r),
+                Outcome::Panicked(p) => Outcome::Panicked(p),
+            }
+        }
+    }
+}
+
+/// Convert rows of `M`'s table into record batches of at most `batch_rows` rows.
+///
+/// Columns are typed from `M::fields()`; result columns that are not model
+/// fields (expressions, aliases) are typed from their first non-NULL value.
+#[allow(clippy::result_large_err)]
+pub fn rows_to_record_batches<M: Model>(
+    rows: &[Row],
+    batch_rows: usize,
+) -> Result<Vec<RecordBatch>, Error> {
+    let Some(first) = rows.first() else {
+        return Ok(vec![RecordBatch::new_empty(model_schema::<M>())]);
+    };
+
+    let fields: Vec<Field> = first
+        .column_names()
+        .enumerate()
+        .map(|(index, name)| match model_field::<M>(name) {
+            Some(field) => arrow_field(field),
+            None => Field::new(name, inferred_type(rows, index), true),
+        })
+        .collect();
+    let schema: SchemaRef = Arc::new(Schema::new(fields));
+
+    rows.chunks(batch_rows.max(1))
+        .map(|chunk| record_batch(&schema, chunk))
+        .collect()
+}
+
+#[allow(clippy::result_large_err)]
+fn record_batch(schema: &SchemaRef, rows: &[Row]) -> Result<RecordBatch, Error> {
+    let columns = schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(index, field)| column_array(field, rows, index))
+        .collect::<Result<Vec<ArrayRef>, Error>>()?;
+    RecordBatch::try_new(Arc::clone(schema), columns).map_err(arrow_error)
+}
+
+/// Arrow type for a column with no model field, from its first non-NULL value.
+fn inferred_type(rows: &[Row], index: usize) -> DataType {
+    let value = rows
+        .iter()
+        .filter_map(|row| row.get(index))
+        .find(|value| !value.is_null());
+    match value {
+        Some(Value::Bool(_)) => DataType::Boolean,
+        Some(Value::TinyInt(_) | Value::SmallInt(_) | Value::Int(_) | Value::BigInt(_)) => {
+            DataType::Int64
+        }
+        Some(Value::Float(_) | Value::Double(_)) => DataType::Float64,
+        Some(Value::Bytes(_)) => DataType::Binary,
+        Some(Value::Date(_)) => DataType::Date32,
+        Some(Value::Time(_)) => DataType::Time64(TimeUnit::Microsecond),
+        Some(Value::Timestamp(_)) => DataType::Timestamp(TimeUnit::Microsecond, None),
+        Some(Value::TimestampTz(_)) => {
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+        }
+        Some(Value::Uuid(_)) => DataType::FixedSizeBinary(16),
+        _ => DataType::Utf8,
+    }
+}
+
+/// The `index`-th value of every row, converted for one Arrow column.
+#[allow(clippy::result_large_err)]
+fn collect<T>(
+    field: &Field,
+    rows: &[Row],
+    index: usize,
+    convert: impl Fn(&Value) -> Option<T>,
+) -> Result<Vec<Option<T>>, Error> {
+    rows.iter()
+        .map(|row| match row.get(index).unwrap_or(&Value::Null) {
+            Value::Null => Ok(None),
+            value => convert(value).map(Some).ok_or_else(|| {
+                Error::Custom(format!(
+                    "arrow: column `{}` cannot hold {value:?} as {}",
+                    field.name(),
+                    field.data_type()
+                ))
+            }),
+        })
+        .collect()
+}
+
+/// Build one column from the `index`-th value of every row.
+#[allow(clippy::result_large_err)]
+fn column_array(field: &Field, rows: &[Row], index: usize) -> Result<ArrayRef, Error> {
+    let array: ArrayRef =
+        match field.data_type() {
+            DataType::Boolean => Arc::new(BooleanArray::from(collect(field, rows, index, |v| {
+                as_i64(v).map(|n| n != 0)
+            })?)),
+            DataType::Int8 => Arc::new(Int8Array::from(collect(field, rows, index, |v| {
+                as_i64(v).and_then(|n| i8::try_from(n).ok())
+            })?)),
+            DataType::Int16 => Arc::new(Int16Array::from(collect(field, rows, index, |v| {
+                as_i64(v).and_then(|n| i16::try_from(n).ok())
+            })?)),
+            DataType::Int32 => Arc::new(Int32Array::from(collect(field, rows, index, |v| {
+                as_i64(v).and_then(|n| i32::try_from(n).ok())
+            })?)),
+            DataType::Int64 => Arc::new(Int64Array::from(collect(field, rows, index, as_i64)?)),
+            #[allow(clippy::cast_possible_truncation)]
+            DataType::Float32 => Arc::new(Float32Array::from(collect(field, rows, index, |v| {
+                as_f64(v).map(|f| f as f32)
+            })?)),
+            DataType::Float64 => Arc::new(Float64Array::from(collect(field, rows, index, as_f64)?)),
+            DataType::Binary => Arc::new(BinaryArray::from_iter(collect(
+                field, rows, index, as_bytes,
+            )?)),
+            DataType::Date32 => Arc::new(Date32Array::from(collect(
+                field,
+                rows,
+                index,
+                |v| match v {
+                    Value::Date(d) => Some(*d),
+                    _ => None,
+                },
+            )?)),
+            DataType::Time64(TimeUnit::Microsecond) => Arc::new(Time64MicrosecondArray::from(
+                collect(field, rows, index, |v| match v {
+                    Value::Time(t) => Some(*t),
+                    _ => None,
+                })?,
+            )),
+            DataType::Timestamp(TimeUnit::Microsecond, tz) => Arc::new(
+                TimestampMicrosecondArray::from(collect(field, rows, index, |v| match v {
+                    Value::Timestamp(t) | Value::TimestampTz(t) => Some(*t),
+                    _ => None,
+                })?)
+                .with_timezone_opt(tz.clone()),
+            ),
+            DataType::Decimal128(precision, scale) => Arc::new(
+                Decimal128Array::from(collect(field, rows, index, |v| as_decimal(v, *scale))?)
+                    .with_precision_and_scale(*precision, *scale)
+                    .map_err(arrow_error)?,
+            ),
+            DataType::FixedSizeBinary(16) => Arc::new(
+                FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+                    collect(field, rows, index, |v| match v {
+                        Value::Uuid(u) => Some(u.to_vec()),
+                        Value::Bytes(b) if b.len() == 16 => Some(b.clone()),
+                        _ => None,
+                    })?
+                    .into_iter(),
+                    16,
+                )
+                .map_err(arrow_error)?,
+            ),
+            DataType::Utf8 => Arc::new(StringArray::from(collect(field, rows, index, |v| {
+                Some(as_text(v))
+            })?)),
+            other => {
+                return Err(Error::Custom(format!(
+                    "arrow: unsupported column type {other} for `{}`",
+                    field.name()
+                )));
+            }
+        };
+    Ok(array)
+}
+
+fn as_i64(value: &Value) -> Option<i64> {
+    match value {
+        Value::Bool(b) => Some(i64::from(*b)),
+        Value::TinyInt(n) => Some(i64::from(*n)),
+        Value::SmallInt(n) => Some(i64::from(*n)),
+        Value::Int(n) => Some(i64::from(*n)),
+        Value::BigInt(n) => Some(*n),
+        _ => None,
+    }
+}
+
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Float(f) => Some(f64::from(*f)),
+        Value::Double(f) => Some(*f),
+        Value::Decimal(s) => s.parse().ok(),
+        other => as_i64(other).map(|n| n as f64),
+    }
+}
+
+fn as_bytes(value: &Value) -> Option<Vec<u8>> {
+    match value {
+        Value::Bytes(b) => Some(b.clone()),
+        Value::Text(s) => Some(s.as_bytes().to_vec()),
+        Value::Uuid(u) => Some(u.to_vec()),
+        _ => None,
+    }
+}
+
+/// Text for a UTF-8 column; non-text values use their JSON encoding.
+fn as_text(value: &Value) -> String {
+    match value {
+        Value::Text(s) | Value::Decimal(s) => s.clone(),
+        Value::Json(j) => j.to_string(),
+        other => serde_json::to_value(other)
+            .map(|j| match j {
+                serde_json::Value::String(s) => s,
+                j => j.to_string(),
+            })
+            .unwrap_or_default(),
+    }
+}
+
+/// Scaled integer for a Decimal128 column with `scale` fractional digits.
+fn as_decimal(value: &Value, scale: i8) -> Option<i128> {
+    match value {
+        Value::Decimal(s) | Value::Text(s) => parse_decimal(s, scale),
+        Value::Float(_) | Value::Double(_) =>
+        {
+            #[allow(clippy::cast_possible_truncation)]
+            as_f64(value).map(|f| (f * 10f64.powi(i32::from(scale))).round() as i128)
+        }
+        other => as_i64(other).and_then(|n| i128::from(n).checked_mul(pow10(scale)?)),
+    }
+}
+
+fn pow10(scale: i8) -> Option<i128> {
+    10i128.checked_pow(u32::try_from(scale).ok()?)
+}
+
+/// Parse `"-12.345"` into an integer scaled by `10^scale`, rounding half away
+/// from zero.
+fn parse_decimal(text: &str, scale: i8) -> Option<i128> {
+    let text = text.trim();
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    let scale = usize::try_from(scale).ok()?;
+
+    let mut scaled: i128 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().ok()?
+    };
+    let mut fraction_digits = fraction.chars();
+    for _ in 0..scale {
+        let digit = fraction_digits.next().map_or(Some(0), |c| c.to_digit(10))?;
+        scaled = scaled.checked_mul(10)?.checked_add(i128::from(digit))?;
+    }
+    if fraction_digits
+        .next()
+        .and_then(|c| c.to_digit(10))
+        .is_some_and(|d| d >= 5)
+    {
+        scaled = scaled.checked_add(1)?;
+    }
+    Some(if negative { -scaled } else { scaled })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::Array;
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Decimal128Type, Int64Type};
+    use sqlmodel_testing::{MockConnection, block_on, matching, rows};
+
+    #[derive(sqlmodel_macros::Model, Debug, Clone)]
+    #[sqlmodel(table = "heroes")]
+    struct Hero {
+        #[sqlmodel(primary_key, auto_increment)]
+        id: Option<i64>,
+        name: String,
+        #[sqlmodel(nullable)]
+        age: Option<i32>,
+    }
+
+    #[test]
+    fn test_parse_decimal() {
+        assert_eq!(parse_decimal("12.345", 2), Some(1235));
+        assert_eq!(parse_decimal("-0.5", 0), Some(-1));
+        assert_eq!(parse_decimal("7", 3), Some(7000));
+        assert_eq!(parse_decimal(".25", 2), Some(25));
+        assert_eq!(parse_decimal("abc", 2), None);
+    }
+
+    #[test]
+    fn test_rows_to_record_batches() {
+        let rows = rows![
+            {"id" => 1_i64, "name" => "Deadpond", "age" => Value::Null},
+            {"id" => 2_i64, "name" => "Spider-Boy", "age" => 16_i64},
+            {"id" => 3_i64, "name" => "Rusty-Man", "age" => 48_i64},
+        ];
+        let batches = rows_to_record_batches::<Hero>(&rows, 2).unwrap();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].num_rows(), 2);
+
+        let schema = batches[0].schema();
+        assert_eq!(schema.field(0).data_type(), &DataType::Int64);
+        assert_eq!(schema.field(2).data_type(), &DataType::Int32);
+
+        let ids = batches[1].column(0).as_primitive::<Int64Type>();
+        assert_eq!(ids.value(0), 3);
+        let ages = batches[0].column(2);
+        assert!(ages.is_null(0));
+        assert_eq!(
+            batches[0].column(1).as_string::<i32>().value(1),
+            "Spider-Boy"
+        );
+    }
+
+    #[test]
+    fn test_decimal_column_and_inferred_types() {
+        let field = Field::new("price", DataType::Decimal128(10, 2), true);
+        let rows = rows![{"price" => Value::Decimal("19.99".into())}, {"price" => 3_i64}];
+        let array = column_array(&field, &rows, 0).unwrap();
+        let prices = array.as_primitive::<Decimal128Type>();
+        assert_eq!(prices.value(0), 1999);
+        assert_eq!(prices.value(1), 300);
+
+        let rows = rows![{"total" => 2.5_f64}];
+        assert_eq!(inferred_type(&rows, 0), DataType::Float64);
+
+        let field = Field::new("n", DataType::Int8, true);
+        let rows = rows![{"n" => 1000_i64}];
+        assert!(column_array(&field, &rows, 0).is_err());
+    }
+
+    #[test]
+    fn test_select_to_arrow() {
+        let conn = MockConnection::new();
+        conn.expect_query(matching("SELECT%FROM%heroes%"))
+            .returning(rows![{"id" => 1_i64, "name" => "Deadpond", "age" => 30_i64}]);
+        conn.expect_query(matching("SELECT%"));
+
+        block_on(async {
+            let cx = Cx::for_testing();
+            let batches = Select::<Hero>::new().to_arrow(&cx, &conn).await;
+            let Outcome::Ok(batches) = batches else {
+                panic!("to_arrow failed: {batches:?}")
+            };
+            assert_eq!(batches.len(), 1);
+            assert_eq!(batches[0].num_rows(), 1);
+
+            let Outcome::Ok(empty) = Select::<Hero>::new().to_arrow(&cx, &conn).await else {
+                panic!("empty to_arrow failed")
+            };
+            assert_eq!(empty[0].num_rows(), 0);
+            assert_eq!(empty[0].schema().fields().len(), 3);
+        });
+    }
+}
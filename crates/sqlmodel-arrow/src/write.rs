@@ -0,0 +1,353 @@
+//! Arrow record batches into model tables.
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Date32Type, Decimal128Type, Float32Type, Float64Type, Int8Type, Int16Type, Int32Type,
+    Int64Type, Time64MicrosecondType, Time64NanosecondType, TimestampMicrosecondType,
+    TimestampMillisecondType, TimestampNanosecondType, TimestampSecondType, UInt8Type, UInt16Type,
+    UInt32Type, UInt64Type,
+};
+use arrow_array::{Array, ArrayRef, RecordBatch};
+use arrow_schema::{DataType, TimeUnit};
+use sqlmodel_core::dialect::SqlDialect;
+use sqlmodel_core::{Connection, Cx, Error, FieldInfo, Model, Outcome, SqlType, Value};
+
+use crate::model_field;
+
+/// Upper bound on bind parameters per `INSERT` (SQLite's historical limit).
+const MAX_PARAMS: usize = 999;
+
+/// Insert every row of `batch` into `M`'s table.
+///
+/// Batch columns are matched to model columns by name; a column the model
+/// does not have is an error, model columns missing from the batch get their
+/// database defaults. Rows are sent as multi-row `INSERT` statements sized to
+/// stay under the driver's parameter limit. Returns the number of rows
+/// inserted.
+pub async fn bulk_insert_arrow<M: Model, C: Connection>(
+    cx: &Cx,
+    conn: &C,
+    batch: &RecordBatch,
+) -> Outcome<u64, Error> {
+    let columns = match batch_columns::<M>(batch) {
+        Ok(columns) => columns,
+        Err(e) => return Outcome::Err(e),
+    };
+
+    let dialect = conn.sql_dialect();
+    let rows_per_statement = (MAX_PARAMS / columns.len()).max(1);
+    let mut inserted = 0;
+    let mut start = 0;
+    while start < batch.num_rows() {
+        let end = (start + rows_per_statement).min(batch.num_rows());
+        let (sql, params) = match insert_statement(dialect, M::TABLE_NAME, &columns, start..end) {
+            Ok(statement) => statement,
+            Err(e) => return Outcome::Err(e),
+        };
+        match conn.execute(cx, &sql, &params).await {
+            Outcome::Ok(count) => inserted += count,
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        }
+        start = end;
+    }
+    Outcome::Ok(inserted)
+}
+
+/// Pair each batch column with the model field it fills.
+#[allow(clippy::result_large_err)]
+fn batch_columns<M: Model>(
+    batch: &RecordBatch,
+) -> Result<Vec<(&'static FieldInfo, &ArrayRef)>, Error> {
+    if batch.num_columns() == 0 {
+        return Err(Error::Custom(
+            "arrow: record batch has no columns".to_string(),
+        ));
+    }
+    batch
+        .schema_ref()
+        .fields()
+        .iter()
+        .zip(batch.columns())
+        .map(
+            |(arrow_field, array)| match model_field::<M>(arrow_field.name()) {
+                Some(field) => Ok((field, array)),
+                None => Err(Error::Custom(format!(
+                    "arrow: column `{}` is not a column of {}",
+                    arrow_field.name(),
+                    M::TABLE_NAME
+                ))),
+            },
+        )
+        .collect()
+}
+
+/// A multi-row `INSERT` for rows `rows` of the batch.
+#[allow(clippy::result_large_err)]
+fn insert_statement(
+    dialect: &'static dyn SqlDialect,
+    table: &str,
+    columns: &[(&'static FieldInfo, &ArrayRef)],
+    rows: std::ops::Range<usize>,
+) -> Result<(String, Vec<Value>), Error> {
+    let mut params = Vec::with_capacity(rows.len() * columns.len());
+    let mut tuples = Vec::with_capacity(rows.len());
+    for row in rows {
+        let mut placeholders = Vec::with_capacity(columns.len());
+        for (field, array) in columns {
+            params.push(value_at(array, row, &field.sql_type)?);
+            placeholders.push(dialect.placeholder(params.len()));
+        }
+        tuples.push(format!("({})", placeholders.join(", ")));
+    }
+
+    let column_list: Vec<String> = columns
+        .iter()
+        .map(|(field, _)| dialect.quote_identifier(field.column_name))
+        .collect();
+    let sql = format!(
+        "INSERT INTO {} ({}) VALUES {}",
+        dialect.quote_identifier(table),
+        column_list.join(", "),
+        tuples.join(", ")
+    );
+    Ok((sql, params))
+}
+
+/// The value at `row` of `array`, adjusted for a column of `target` type.
+#[allow(clippy::result_large_err)]
+fn value_at(array: &ArrayRef, row: usize, target: &SqlType) -> Result<Value, Error> {
+    if array.is_null(row) {
+        return Ok(Value::Null);
+    }
+
+    let value = match array.data_type() {
+        DataType::Boolean => Value::Bool(array.as_boolean().value(row)),
+        DataType::Int8 => Value::TinyInt(array.as_primitive::<Int8Type>().value(row)),
+        DataType::Int16 => Value::SmallInt(array.as_primitive::<Int16Type>().value(row)),
+        DataType::Int32 => Value::Int(array.as_primitive::<Int32Type>().value(row)),
+        DataType::Int64 => Value::BigInt(array.as_primitive::<Int64Type>().value(row)),
+        DataType::UInt8 => Value::SmallInt(i16::from(array.as_primitive::<UInt8Type>().value(row))),
+        DataType::UInt16 => Value::Int(i32::from(array.as_primitive::<UInt16Type>().value(row))),
+        DataType::UInt32 => Value::BigInt(i64::from(array.as_primitive::<UInt32Type>().value(row))),
+        DataType::UInt64 => {
+            let n = array.as_primitive::<UInt64Type>().value(row);
+            Value::BigInt(i64::try_from(n).map_err(|_| {
+                Error::Custom(format!("arrow: UInt64 value {n} does not fit in BIGINT"))
+            })?)
+        }
+        DataType::Float32 => Value::Float(array.as_primitive::<Float32Type>().value(row)),
+        DataType::Float64 => Value::Double(array.as_primitive::<Float64Type>().value(row)),
+        DataType::Utf8 => Value::Text(array.as_string::<i32>().value(row).to_string()),
+        DataType::LargeUtf8 => Value::Text(array.as_string::<i64>().value(row).to_string()),
+        DataType::Binary => Value::Bytes(array.as_binary::<i32>().value(row).to_vec()),
+        DataType::LargeBinary => Value::Bytes(array.as_binary::<i64>().value(row).to_vec()),
+        DataType::FixedSizeBinary(_) => {
+            let bytes = array.as_fixed_size_binary().value(row);
+            match <[u8; 16]>::try_from(bytes) {
+                Ok(uuid) if matches!(target, SqlType::Uuid) => Value::Uuid(uuid),
+                _ => Value::Bytes(bytes.to_vec()),
+            }
+        }
+        DataType::Date32 => Value::Date(array.as_primitive::<Date32Type>().value(row)),
+        DataType::Time64(TimeUnit::Microsecond) => {
+            Value::Time(array.as_primitive::<Time64MicrosecondType>().value(row))
+        }
+        DataType::Time64(TimeUnit::Nanosecond) => {
+            Value::Time(array.as_primitive::<Time64NanosecondType>().value(row) / 1_000)
+        }
+        DataType::Timestamp(unit, tz) => {
+            let micros = match unit {
+                TimeUnit::Second => array
+                    .as_primitive::<TimestampSecondType>()
+                    .value(row)
+                    .saturating_mul(1_000_000),
+                TimeUnit::Millisecond => array
+                    .as_primitive::<TimestampMillisecondType>()
+                    .value(row)
+                    .saturating_mul(1_000),
+                TimeUnit::Microsecond => {
+                    array.as_primitive::<TimestampMicrosecondType>().value(row)
+                }
+                TimeUnit::Nanosecond => {
+                    array.as_primitive::<TimestampNanosecondType>().value(row) / 1_000
+                }
+            };
+            if tz.is_some() {
+                Value::TimestampTz(micros)
+            } else {
+                Value::Timestamp(micros)
+            }
+        }
+        DataType::Decimal128(_, scale) => Value::Decimal(decimal_to_string(
+            array.as_primitive::<Decimal128Type>().value(row),
+            *scale,
+        )),
+        other => {
+            return Err(Error::Custom(format!(
+                "arrow: cannot insert values of type {other}"
+            )));
+        }
+    };
+    Ok(coerce(value, target))
+}
+
+/// Adjust an Arrow-derived value to the model column's SQL type where the
+/// Arrow representation is ambiguous.
+fn coerce(value: Value, target: &SqlType) -> Value {
+    match (value, target) {
+        (Value::Text(text), SqlType::Json | SqlType::JsonB) => match serde_json::from_str(&text) {
+            Ok(json) => Value::Json(json),
+            Err(_) => Value::Text(text),
+        },
+        (Value::Timestamp(t), SqlType::TimestampTz) => Value::TimestampTz(t),
+        (Value::TimestampTz(t), SqlType::Timestamp | SqlType::DateTime) => Value::Timestamp(t),
+        (value, _) => value,
+    }
+}
+
+/// Render a Decimal128 value with `scale` fractional digits.
+fn decimal_to_string(value: i128, scale: i8) -> String {
+    let Ok(scale) = usize::try_from(scale) else {
+        // Negative scale: the value counts units of 10^-scale.
+        return format!("{value}{}", "0".repeat(usize::from(scale.unsigned_abs())));
+    };
+    if scale == 0 {
+        return value.to_string();
+    }
+    let digits = format!("{:0>width$}", value.unsigned_abs(), width = scale + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - scale);
+    let sign = if value < 0 { "-" } else { "" };
+    format!("{sign}{whole}.{fraction}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use arrow_array::{Decimal128Array, Int64Array, StringArray, TimestampMillisecondArray};
+    use arrow_schema::{Field, Schema};
+    use sqlmodel_testing::{MockConnection, SqlMatcher, block_on, exact, matching};
+
+    #[derive(sqlmodel_macros::Model, Debug, Clone)]
+    #[sqlmodel(table = "heroes")]
+    struct Hero {
+        #[sqlmodel(primary_key, auto_increment)]
+        id: Option<i64>,
+        name: String,
+        #[sqlmodel(nullable)]
+        age: Option<i32>,
+    }
+
+    fn heroes_batch(rows: usize) -> RecordBatch {
+        let names: Vec<String> = (0..rows).map(|i| format!("hero-{i}")).collect();
+        let ages: Vec<Option<i64>> = (0..rows)
+            .map(|i| (i % 2 == 0).then_some(i as i64))
+            .collect();
+        RecordBatch::try_new(
+            Arc::new(Schema::new(vec![
+                Field::new("name", DataType::Utf8, false),
+                Field::new("age", DataType::Int64, true),
+            ])),
+            vec![
+                Arc::new(StringArray::from(names)),
+                Arc::new(Int64Array::from(ages)),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_decimal_to_string() {
+        assert_eq!(decimal_to_string(1999, 2), "19.99");
+        assert_eq!(decimal_to_string(-5, 3), "-0.005");
+        assert_eq!(decimal_to_string(42, 0), "42");
+        assert_eq!(decimal_to_string(7, -2), "700");
+    }
+
+    #[test]
+    fn test_value_at_conversions() {
+        let millis: ArrayRef =
+            Arc::new(TimestampMillisecondArray::from(vec![Some(1_500)]).with_timezone("UTC"));
+        assert_eq!(
+            value_at(&millis, 0, &SqlType::TimestampTz).unwrap(),
+            Value::TimestampTz(1_500_000)
+        );
+
+        let decimals: ArrayRef = Arc::new(
+            Decimal128Array::from(vec![Some(1999), None])
+                .with_precision_and_scale(10, 2)
+                .unwrap(),
+        );
+        let target = SqlType::Decimal {
+            precision: 10,
+            scale: 2,
+        };
+        assert_eq!(
+            value_at(&decimals, 0, &target).unwrap(),
+            Value::Decimal("19.99".to_string())
+        );
+        assert_eq!(value_at(&decimals, 1, &target).unwrap(), Value::Null);
+
+        let json: ArrayRef = Arc::new(StringArray::from(vec!["{\"a\":1}"]));
+        assert_eq!(
+            value_at(&json, 0, &SqlType::Json).unwrap(),
+            Value::Json(serde_json::json!({"a": 1}))
+        );
+    }
+
+    #[test]
+    fn test_bulk_insert_arrow_chunks_statements() {
+        let conn = MockConnection::new();
+        conn.expect_execute(exact(
+            "INSERT INTO \"heroes\" (\"name\", \"age\") VALUES ($1, $2), ($3, $4)",
+        ))
+        .with_params(vec![
+            Value::from("hero-0"),
+            Value::BigInt(0),
+            Value::from("hero-1"),
+            Value::Null,
+        ])
+        .affecting(2);
+
+        block_on(async {
+            let cx = Cx::for_testing();
+            let inserted = bulk_insert_arrow::<Hero, _>(&cx, &conn, &heroes_batch(2)).await;
+            assert!(matches!(inserted, Outcome::Ok(2)));
+        });
+
+        // Two columns per row: 499 rows fit under MAX_PARAMS, the 500th spills over.
+        let conn = MockConnection::new();
+        conn.expect_execute(any_insert()).affecting(499);
+        conn.expect_execute(any_insert()).affecting(1);
+        block_on(async {
+            let cx = Cx::for_testing();
+            let inserted = bulk_insert_arrow::<Hero, _>(&cx, &conn, &heroes_batch(500)).await;
+            assert!(matches!(inserted, Outcome::Ok(500)));
+        });
+        assert_eq!(conn.calls()[1].params.len(), 2);
+    }
+
+    fn any_insert() -> SqlMatcher {
+        matching("INSERT INTO \"heroes\"%")
+    }
+
+    #[test]
+    fn test_bulk_insert_arrow_rejects_unknown_columns() {
+        let batch = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![Field::new("power", DataType::Utf8, true)])),
+            vec![Arc::new(StringArray::from(vec!["flight"]))],
+        )
+        .unwrap();
+        let conn = MockConnection::new();
+        block_on(async {
+            let cx = Cx::for_testing();
+            let outcome = bulk_insert_arrow::<Hero, _>(&cx, &conn, &batch).await;
+            assert!(
+                matches!(outcome, Outcome::Err(Error::Custom(ref m)) if m.contains("`power` is not a column of heroes"))
+            );
+        });
+    }
+}
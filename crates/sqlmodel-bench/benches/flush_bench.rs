@@ -0,0 +1,67 @@
+//! Flush serialization benchmarks: turning dirty models into statements.
+//!
+//! Measures the per-object work a flush does before touching the
+//! connection: `to_row` plus rendering SQL and binding parameters into a
+//! reused [`SqlWriter`].
+//!
+//! # Running Benchmarks
+//!
+//! ```bash
+//! cargo bench -p sqlmodel-bench --bench flush_bench
+//! ```
+
+#![feature(test)]
+
+extern crate test;
+
+use test::{Bencher, black_box};
+
+use sqlmodel_bench::{Order, ROWS, orders};
+use sqlmodel_core::{Dialect, Model};
+use sqlmodel_query::{InsertBuilder, ModelStatements, SqlWriter, UpdateBuilder};
+
+#[bench]
+fn bench_insert_build_into_reused_writer(b: &mut Bencher) {
+    let orders = orders(ROWS);
+    let mut w = SqlWriter::with_capacity(Dialect::Postgres, 256);
+    b.iter(|| {
+        let mut bytes = 0;
+        for order in black_box(&orders) {
+            w.clear();
+            InsertBuilder::new(order).build_into(&mut w);
+            bytes += w.sql().len() + w.params().len();
+        }
+        black_box(bytes)
+    });
+}
+
+#[bench]
+fn bench_update_build_into_reused_writer(b: &mut Bencher) {
+    let orders = orders(ROWS);
+    let mut w = SqlWriter::with_capacity(Dialect::Postgres, 256);
+    b.iter(|| {
+        let mut bytes = 0;
+        for order in black_box(&orders) {
+            w.clear();
+            UpdateBuilder::new(order).build_into(&mut w);
+            bytes += w.sql().len() + w.params().len();
+        }
+        black_box(bytes)
+    });
+}
+
+#[bench]
+fn bench_cached_insert_template(b: &mut Bencher) {
+    let orders = orders(ROWS);
+    let statements = ModelStatements::for_model::<Order>(Dialect::Postgres);
+    b.iter(|| {
+        let mut params = Vec::with_capacity(statements.columns.len());
+        let mut bound = 0;
+        for order in black_box(&orders) {
+            params.clear();
+            params.extend(order.to_row().into_iter().map(|(_, v)| v));
+            bound += params.len();
+        }
+        black_box((statements.insert.len(), bound))
+    });
+}
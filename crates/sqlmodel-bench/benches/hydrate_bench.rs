@@ -0,0 +1,48 @@
+//! Model hydration benchmarks: `Model::from_row` over decoded rows.
+//!
+//! # Running Benchmarks
+//!
+//! ```bash
+//! cargo bench -p sqlmodel-bench --bench hydrate_bench
+//! ```
+
+#![feature(test)]
+
+extern crate test;
+
+use test::{Bencher, black_box};
+
+use sqlmodel_bench::{Order, ROWS, order_rows};
+use sqlmodel_core::Model;
+
+#[bench]
+fn bench_from_row_shared_columns(b: &mut Bencher) {
+    let rows = order_rows(ROWS);
+    b.iter(|| {
+        let orders: Vec<Order> = black_box(&rows)
+            .iter()
+            .map(|row| Order::from_row(row).unwrap())
+            .collect();
+        black_box(orders)
+    });
+}
+
+#[bench]
+fn bench_from_row_single(b: &mut Bencher) {
+    let rows = order_rows(1);
+    b.iter(|| black_box(Order::from_row(black_box(&rows[0])).unwrap()));
+}
+
+#[bench]
+fn bench_named_column_lookup(b: &mut Bencher) {
+    let rows = order_rows(ROWS);
+    b.iter(|| {
+        black_box(&rows)
+            .iter()
+            .map(|row| {
+                let reference: &str = row.try_get_ref("reference").unwrap();
+                reference.len()
+            })
+            .sum::<usize>()
+    });
+}
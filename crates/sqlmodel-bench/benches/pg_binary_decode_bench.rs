@@ -0,0 +1,77 @@
+//! Postgres binary-format decoding benchmarks.
+//!
+//! Decodes a result set of fixed-width (int8, float8, uuid) and text columns
+//! sent in binary format, the path used by extended-protocol queries.
+//!
+//! # Running Benchmarks
+//!
+//! ```bash
+//! cargo bench -p sqlmodel-bench --bench pg_binary_decode_bench
+//! ```
+
+#![feature(test)]
+#![allow(clippy::cast_possible_truncation)] // Row indices are far below i32::MAX
+
+extern crate test;
+
+use test::{Bencher, black_box};
+
+use sqlmodel_bench::{ROWS, uuid_bytes};
+use sqlmodel_postgres::types::{Format, decode_value, oid};
+
+const OIDS: [u32; 5] = [oid::INT8, oid::INT4, oid::FLOAT8, oid::UUID, oid::TEXT];
+
+fn binary_rows() -> Vec<Vec<Vec<u8>>> {
+    (0..ROWS)
+        .map(|i| {
+            vec![
+                (i as i64).to_be_bytes().to_vec(),
+                (i as i32).to_be_bytes().to_vec(),
+                (i as f64 * 0.5).to_be_bytes().to_vec(),
+                uuid_bytes(i).to_vec(),
+                format!("customer name number {i} with some padding").into_bytes(),
+            ]
+        })
+        .collect()
+}
+
+fn decode_rows(rows: &[Vec<Vec<u8>>], oids: &[u32]) -> usize {
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(oids)
+                .map(|(data, oid)| {
+                    black_box(decode_value(*oid, Some(data), Format::Binary).unwrap());
+                    1
+                })
+                .sum::<usize>()
+        })
+        .sum()
+}
+
+#[bench]
+fn bench_binary_decode_mixed(b: &mut Bencher) {
+    let rows = binary_rows();
+    b.iter(|| black_box(decode_rows(black_box(&rows), &OIDS)));
+}
+
+#[bench]
+fn bench_binary_decode_fixed_width(b: &mut Bencher) {
+    let rows: Vec<Vec<Vec<u8>>> = binary_rows()
+        .into_iter()
+        .map(|mut r| {
+            r.truncate(4);
+            r
+        })
+        .collect();
+    b.iter(|| black_box(decode_rows(black_box(&rows), &OIDS[..4])));
+}
+
+#[bench]
+fn bench_binary_decode_text(b: &mut Bencher) {
+    let rows: Vec<Vec<Vec<u8>>> = binary_rows()
+        .into_iter()
+        .map(|mut r| r.split_off(4))
+        .collect();
+    b.iter(|| black_box(decode_rows(black_box(&rows), &OIDS[4..])));
+}
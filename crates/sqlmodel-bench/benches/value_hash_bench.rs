@@ -0,0 +1,53 @@
+//! Identity-map key benchmarks: hashing primary key values.
+//!
+//! Every `get`, `add` and flush goes through [`ObjectKey`], so a slowdown in
+//! `Value` hashing shows up across the whole session.
+//!
+//! # Running Benchmarks
+//!
+//! ```bash
+//! cargo bench -p sqlmodel-bench --bench value_hash_bench
+//! ```
+
+#![feature(test)]
+
+extern crate test;
+
+use test::{Bencher, black_box};
+
+use sqlmodel_bench::{Order, ROWS, orders, pk_values};
+use sqlmodel_core::Value;
+use sqlmodel_session::ObjectKey;
+
+#[bench]
+fn bench_object_key_mixed_pks(b: &mut Bencher) {
+    let pks = pk_values(ROWS);
+    b.iter(|| {
+        black_box(&pks)
+            .iter()
+            .map(|pk| ObjectKey::from_pk::<Order>(pk).pk_hash())
+            .fold(0u64, u64::wrapping_add)
+    });
+}
+
+#[bench]
+fn bench_object_key_bigint_pk(b: &mut Bencher) {
+    let pks: Vec<Vec<Value>> = (0..ROWS as i64).map(|i| vec![Value::BigInt(i)]).collect();
+    b.iter(|| {
+        black_box(&pks)
+            .iter()
+            .map(|pk| ObjectKey::from_pk::<Order>(pk).pk_hash())
+            .fold(0u64, u64::wrapping_add)
+    });
+}
+
+#[bench]
+fn bench_object_key_from_model(b: &mut Bencher) {
+    let orders = orders(ROWS);
+    b.iter(|| {
+        black_box(&orders)
+            .iter()
+            .map(|o| ObjectKey::from_model(o).pk_hash())
+            .fold(0u64, u64::wrapping_add)
+    });
+}
@@ -0,0 +1,127 @@
+//! Shared fixtures for the SQLModel Rust benchmark suite.
+//!
+//! The benchmarks themselves live in `benches/` and run on the nightly
+//! `test` harness, like the driver and console benchmarks:
+//!
+//! ```bash
+//! cargo bench -p sqlmodel-bench
+//! cargo bench -p sqlmodel-bench --bench hydrate_bench
+//! ```
+//!
+//! | Bench | Guards |
+//! |-------|--------|
+//! | `hydrate_bench` | `Model::from_row` over eager rows sharing column metadata |
+//! | `value_hash_bench` | identity-map key hashing of primary key values |
+//! | `flush_bench` | `to_row` + INSERT/UPDATE serialization into a reused `SqlWriter` |
+//! | `pg_binary_decode_bench` | Postgres binary-format decoding of int/float/uuid/text columns |
+//!
+//! Fixtures are deterministic so numbers are comparable between runs.
+
+#![allow(clippy::cast_possible_truncation)] // Fixture indices are far below i32::MAX
+
+use std::sync::Arc;
+
+use sqlmodel_core::Value;
+use sqlmodel_core::row::{ColumnInfo, Row};
+use sqlmodel_macros::Model;
+
+/// Rows per iteration used by every benchmark.
+pub const ROWS: usize = 1_000;
+
+/// A moderately wide model mixing the common column types.
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(table = "orders")]
+pub struct Order {
+    #[sqlmodel(primary_key)]
+    pub id: i64,
+    pub customer_id: i64,
+    pub status: String,
+    pub total: f64,
+    pub quantity: i32,
+    pub paid: bool,
+    pub note: Option<String>,
+    pub reference: String,
+}
+
+/// Build `n` orders with stable contents.
+pub fn orders(n: usize) -> Vec<Order> {
+    (0..n)
+        .map(|i| {
+            let id = i as i64 + 1;
+            Order {
+                id,
+                customer_id: id % 97,
+                status: ["pending", "paid", "shipped"][i % 3].to_string(),
+                total: id as f64 * 1.25,
+                quantity: (i % 10) as i32 + 1,
+                paid: i % 2 == 0,
+                note: (i % 4 == 0).then(|| format!("gift wrap order {id}")),
+                reference: format!("ORD-{id:08}"),
+            }
+        })
+        .collect()
+}
+
+/// Column metadata matching [`Order`], shared by every fixture row.
+pub fn order_columns() -> Arc<ColumnInfo> {
+    Arc::new(ColumnInfo::new(
+        [
+            "id",
+            "customer_id",
+            "status",
+            "total",
+            "quantity",
+            "paid",
+            "note",
+            "reference",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect(),
+    ))
+}
+
+/// Build `n` decoded result rows for [`Order`], as a driver would return them.
+pub fn order_rows(n: usize) -> Vec<Row> {
+    let columns = order_columns();
+    orders(n)
+        .into_iter()
+        .map(|o| {
+            Row::with_columns(
+                Arc::clone(&columns),
+                vec![
+                    Value::BigInt(o.id),
+                    Value::BigInt(o.customer_id),
+                    Value::Text(o.status),
+                    Value::Double(o.total),
+                    Value::Int(o.quantity),
+                    Value::Bool(o.paid),
+                    o.note.map_or(Value::Null, Value::Text),
+                    Value::Text(o.reference),
+                ],
+            )
+        })
+        .collect()
+}
+
+/// Primary key tuples of mixed shapes, as fed to identity-map hashing.
+pub fn pk_values(n: usize) -> Vec<Vec<Value>> {
+    (0..n)
+        .map(|i| match i % 3 {
+            0 => vec![Value::BigInt(i as i64)],
+            1 => vec![
+                Value::Text(format!("tenant-{}", i % 13)),
+                Value::Int(i as i32),
+            ],
+            _ => vec![Value::Uuid(uuid_bytes(i))],
+        })
+        .collect()
+}
+
+/// Deterministic 16-byte UUID payload for row `i`.
+pub fn uuid_bytes(i: usize) -> [u8; 16] {
+    let mut bytes = [0u8; 16];
+    bytes[..8].copy_from_slice(&(i as u64).to_be_bytes());
+    bytes[8..].copy_from_slice(&(!(i as u64)).to_be_bytes());
+    bytes
+}
@@ -0,0 +1,214 @@
+//! `sqlmodel` command-line tool.
+//!
+//! ```text
+//! sqlmodel generate models --url <database-url> [--table <name>]... [--no-relationships] [--output <file>]
+//! ```
+//!
+//! `generate models` reflects an existing database and prints one
+//! `#[derive(Model)]` struct per table (see `sqlmodel_schema::codegen`).
+
+mod url;
+
+use std::process::ExitCode;
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+use sqlmodel_core::{Connection, Error};
+use sqlmodel_mysql::SharedMySqlConnection;
+use sqlmodel_postgres::SharedPgConnection;
+use sqlmodel_schema::codegen::{self, CodegenOptions};
+use sqlmodel_sqlite::SqliteConnection;
+
+use crate::url::DatabaseUrl;
+
+const USAGE: &str = "\
+Usage: sqlmodel generate models --url <database-url> [options]
+
+Reflect an existing database and emit #[derive(Model)] structs.
+
+Options:
+  --url <url>           postgres://, mysql:// or sqlite:// database URL
+  --table <name>        Only generate this table (repeatable)
+  --no-relationships    Do not emit Related/RelatedMany fields
+  --output <file>       Write to <file> instead of stdout
+  -h, --help            Print this help";
+
+/// Parsed `generate models` arguments.
+#[derive(Debug)]
+struct GenerateModels {
+    url: String,
+    output: Option<String>,
+    options: CodegenOptions,
+}
+
+/// Parse the command line (without the program name).
+fn parse_args(args: &[String]) -> Result<Option<GenerateModels>, String> {
+    let mut args = args.iter().map(String::as_str);
+    match (args.next(), args.next()) {
+        (None | Some("-h" | "--help"), _) => return Ok(None),
+        (Some("generate"), Some("models")) => {}
+        (Some("generate"), other) => {
+            return Err(format!(
+                "unknown generate target '{}' (expected 'models')",
+                other.unwrap_or_default()
+            ));
+        }
+        (Some(command), _) => return Err(format!("unknown command '{command}'")),
+    }
+
+    let mut url = None;
+    let mut output = None;
+    let mut options = CodegenOptions::default();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .map(str::to_string)
+                .ok_or_else(|| format!("{arg} requires a value"))
+        };
+        match arg {
+            "--url" => url = Some(value()?),
+            "--table" => options.tables.push(value()?),
+            "--output" | "-o" => output = Some(value()?),
+            "--no-relationships" => options.relationships = false,
+            "-h" | "--help" => return Ok(None),
+            other => return Err(format!("unknown option '{other}'")),
+        }
+    }
+    let url = url
+        .or_else(|| std::env::var("DATABASE_URL").ok())
+        .ok_or("--url is required (or set DATABASE_URL)")?;
+    Ok(Some(GenerateModels {
+        url,
+        output,
+        options,
+    }))
+}
+
+async fn generate(
+    cx: &Cx,
+    target: DatabaseUrl,
+    options: &CodegenOptions,
+) -> Outcome<String, Error> {
+    async fn run<C: Connection>(
+        cx: &Cx,
+        conn: Outcome<C, Error>,
+        options: &CodegenOptions,
+    ) -> Outcome<String, Error> {
+        match conn {
+            Outcome::Ok(conn) => codegen::generate_models_from_db(cx, &conn, options).await,
+            Outcome::Err(e) => Outcome::Err(e),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
+    match target {
+        DatabaseUrl::Postgres(config) => {
+            run(cx, SharedPgConnection::connect(cx, config).await, options).await
+        }
+        DatabaseUrl::Mysql(config) => {
+            run(
+                cx,
+                SharedMySqlConnection::connect(cx, config).await,
+                options,
+            )
+            .await
+        }
+        DatabaseUrl::Sqlite(path) => {
+            let conn = match SqliteConnection::open_file(path) {
+                Ok(conn) => Outcome::Ok(conn),
+                Err(e) => Outcome::Err(e),
+            };
+            run(cx, conn, options).await
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match parse_args(&args) {
+        Ok(Some(command)) => command,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    let target = match DatabaseUrl::parse(&command.url) {
+        Ok(target) => target,
+        Err(message) => {
+            eprintln!("error: {message}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let rt = match RuntimeBuilder::current_thread().build() {
+        Ok(rt) => rt,
+        Err(e) => {
+            eprintln!("error: failed to start runtime: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let cx = Cx::for_testing();
+    let source = match rt.block_on(generate(&cx, target, &command.options)) {
+        Outcome::Ok(source) => source,
+        Outcome::Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+        Outcome::Cancelled(r) => {
+            eprintln!("error: cancelled: {r:?}");
+            return ExitCode::FAILURE;
+        }
+        Outcome::Panicked(p) => {
+            eprintln!("error: panicked: {p:?}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match command.output {
+        Some(path) => {
+            if let Err(e) = std::fs::write(&path, source) {
+                eprintln!("error: failed to write {path}: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print!("{source}"),
+    }
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_parse_generate_models_args() {
+        let command = parse_args(&args(
+            "generate models --url sqlite://app.db --table heroes --table teams --no-relationships -o models.rs",
+        ))
+        .expect("valid arguments")
+        .expect("a command");
+        assert_eq!(command.url, "sqlite://app.db");
+        assert_eq!(command.output.as_deref(), Some("models.rs"));
+        assert_eq!(command.options.tables, ["heroes", "teams"]);
+        assert!(!command.options.relationships);
+    }
+
+    #[test]
+    fn test_parse_args_help_and_errors() {
+        assert!(matches!(parse_args(&[]), Ok(None)));
+        assert!(matches!(parse_args(&args("--help")), Ok(None)));
+        assert!(parse_args(&args("migrate")).is_err());
+        assert!(parse_args(&args("generate schema")).is_err());
+        assert!(parse_args(&args("generate models --url")).is_err());
+        assert!(parse_args(&args("generate models --url x --bogus")).is_err());
+    }
+}
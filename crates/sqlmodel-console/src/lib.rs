@@ -10,6 +10,7 @@
 //!   in driver crates.
 //! - **Agent-safe output**: auto-detects AI coding tools and switches to plain text.
 //! - **Diagnostics**: provides structured renderables for tables, errors, and status.
+//! - **REPL**: input handling and rendering for the interactive `sqlmodel repl` mode.
 //!
 //! This crate provides styled console output that automatically adapts to
 //! the terminal environment. When running under an AI coding agent, output
@@ -56,6 +57,7 @@ pub mod console;
 pub mod logging;
 pub mod mode;
 pub mod renderables;
+pub mod repl;
 pub mod theme;
 pub mod traits;
 pub mod widgets;
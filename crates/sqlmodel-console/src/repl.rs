@@ -0,0 +1,406 @@
+//! Interactive REPL support for ad-hoc queries.
+//!
+//! This module holds the database-independent half of `sqlmodel repl`:
+//! line input with multi-line SQL statements, backslash meta-commands, and
+//! rendering of results in the active [`OutputMode`]. The loop that executes
+//! commands against a connection lives in the `sqlmodel` crate
+//! (`sqlmodel::repl::run_repl`), which has access to drivers and introspection.
+//!
+//! # Commands
+//!
+//! | Input | Meaning |
+//! |-------|---------|
+//! | `SELECT ...;` | Run SQL (may span lines, ends with `;`) |
+//! | `\d` / `\dt` | List tables |
+//! | `\d <table>` | Describe a table |
+//! | `\m` / `\models` | List registered models |
+//! | `\m <table>` | Describe a registered model's expected schema |
+//! | `\mode plain\|rich\|json` | Switch output mode |
+//! | `\?` | Show help |
+//! | `\q` | Quit |
+//!
+//! # Example
+//!
+//! ```rust
+//! use sqlmodel_console::repl::{ReplCommand, ReplInput};
+//!
+//! let mut input = ReplInput::new();
+//! assert_eq!(input.push_line("SELECT id"), None);
+//! assert_eq!(
+//!     input.push_line("FROM heroes;"),
+//!     Some(ReplCommand::Sql("SELECT id\nFROM heroes".to_string()))
+//! );
+//! assert_eq!(input.push_line("\\d heroes"), Some(ReplCommand::Describe("heroes".to_string())));
+//! ```
+
+use crate::mode::OutputMode;
+use crate::renderables::{Cell, QueryResultTable, TableInfo};
+use crate::theme::Theme;
+use std::time::Duration;
+
+/// Help text listing the REPL commands.
+pub const HELP: &str = "\
+SQL statements end with ';' and may span several lines.
+
+  \\d, \\dt             list tables
+  \\d <table>          describe a table
+  \\m, \\models         list registered models
+  \\m <table>          describe a registered model
+  \\mode <mode>        switch output (plain, rich, json)
+  \\?                  show this help
+  \\q                  quit";
+
+/// A complete unit of REPL input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplCommand {
+    /// A SQL statement, without the terminating `;`.
+    Sql(String),
+    /// `\d` / `\dt`: list tables.
+    ListTables,
+    /// `\d <table>`: describe one table.
+    Describe(String),
+    /// `\m` / `\models`: list the models registered with the session.
+    ListModels,
+    /// `\m <table>`: describe the schema a registered model expects.
+    DescribeModel(String),
+    /// `\mode <mode>`: switch the output mode.
+    Mode(OutputMode),
+    /// `\?` / `\h`: show help.
+    Help,
+    /// `\q`: leave the REPL.
+    Quit,
+    /// A backslash command that was not understood (message for the user).
+    Invalid(String),
+}
+
+impl ReplCommand {
+    /// Parse a backslash meta-command (the leading `\` included).
+    pub fn parse_meta(line: &str) -> Self {
+        let line = line.trim();
+        let mut parts = line.split_whitespace();
+        let command = parts.next().unwrap_or_default();
+        let argument = parts.next();
+
+        match (command, argument) {
+            ("\\d" | "\\dt", None) => ReplCommand::ListTables,
+            ("\\d" | "\\dt", Some(table)) => ReplCommand::Describe(table.to_string()),
+            ("\\m" | "\\models", None) => ReplCommand::ListModels,
+            ("\\m" | "\\models", Some(table)) => ReplCommand::DescribeModel(table.to_string()),
+            ("\\mode", Some(mode)) => match mode.to_ascii_lowercase().as_str() {
+                "plain" => ReplCommand::Mode(OutputMode::Plain),
+                "rich" => ReplCommand::Mode(OutputMode::Rich),
+                "json" => ReplCommand::Mode(OutputMode::Json),
+                other => ReplCommand::Invalid(format!(
+                    "unknown output mode '{other}' (expected plain, rich or json)"
+                )),
+            },
+            ("\\mode", None) => {
+                ReplCommand::Invalid("\\mode needs an argument: plain, rich or json".to_string())
+            }
+            ("\\?" | "\\h" | "\\help", _) => ReplCommand::Help,
+            ("\\q" | "\\quit", _) => ReplCommand::Quit,
+            _ => ReplCommand::Invalid(format!("unknown command '{command}' (try \\?)")),
+        }
+    }
+}
+
+/// Line buffer turning raw input lines into [`ReplCommand`]s.
+///
+/// Backslash commands are complete on their own line. SQL accumulates until
+/// a line ends with `;`.
+#[derive(Debug, Clone, Default)]
+pub struct ReplInput {
+    buffer: String,
+}
+
+impl ReplInput {
+    /// Create an empty input buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one line; returns a command once one is complete.
+    pub fn push_line(&mut self, line: &str) -> Option<ReplCommand> {
+        let trimmed = line.trim();
+
+        if self.buffer.is_empty() {
+            if trimmed.is_empty() {
+                return None;
+            }
+            if trimmed.starts_with('\\') {
+                return Some(ReplCommand::parse_meta(trimmed));
+            }
+        } else {
+            self.buffer.push('\n');
+        }
+        self.buffer.push_str(line.trim_end());
+
+        if trimmed.ends_with(';') {
+            let sql = std::mem::take(&mut self.buffer);
+            let sql = sql.trim().trim_end_matches(';').trim_end().to_string();
+            if sql.is_empty() {
+                return None;
+            }
+            return Some(ReplCommand::Sql(sql));
+        }
+        None
+    }
+
+    /// Whether a statement is partially entered.
+    pub fn is_continuation(&self) -> bool {
+        !self.buffer.is_empty()
+    }
+
+    /// Prompt to show before the next line.
+    pub fn prompt(&self) -> &'static str {
+        if self.is_continuation() {
+            "     ...> "
+        } else {
+            "sqlmodel> "
+        }
+    }
+
+    /// Take whatever partial statement is buffered (e.g. at end of input).
+    pub fn take_pending(&mut self) -> Option<ReplCommand> {
+        let sql = std::mem::take(&mut self.buffer);
+        let sql = sql.trim().trim_end_matches(';').trim_end();
+        (!sql.is_empty()).then(|| ReplCommand::Sql(sql.to_string()))
+    }
+}
+
+/// Renders REPL output in the active output mode.
+#[derive(Debug, Clone)]
+pub struct ReplRenderer {
+    mode: OutputMode,
+    theme: Theme,
+    max_width: Option<usize>,
+}
+
+impl ReplRenderer {
+    /// Create a renderer for `mode` with the default theme.
+    pub fn new(mode: OutputMode) -> Self {
+        Self {
+            mode,
+            theme: Theme::default(),
+            max_width: None,
+        }
+    }
+
+    /// Set the theme used in rich mode.
+    #[must_use]
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Limit rendered tables to `width` columns.
+    #[must_use]
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Current output mode.
+    pub fn mode(&self) -> OutputMode {
+        self.mode
+    }
+
+    /// Switch the output mode.
+    pub fn set_mode(&mut self, mode: OutputMode) {
+        self.mode = mode;
+    }
+
+    /// Render a result set. `None` values are shown as NULL.
+    pub fn render_rows(
+        &self,
+        columns: Vec<String>,
+        rows: Vec<Vec<Option<String>>>,
+        elapsed: Duration,
+    ) -> String {
+        let mut table = QueryResultTable::new()
+            .columns(columns)
+            .timing(elapsed)
+            .theme(self.theme.clone());
+        if let Some(width) = self.max_width {
+            table = table.max_width(width);
+        }
+        for row in rows {
+            table = table.row_cells(
+                row.into_iter()
+                    .map(|v| v.map_or_else(Cell::null, Cell::new))
+                    .collect(),
+            );
+        }
+        self.render_table(&table)
+    }
+
+    /// Render the outcome of a statement that returns no rows.
+    pub fn render_affected(&self, rows: u64, elapsed: Duration) -> String {
+        let ms = elapsed.as_secs_f64() * 1000.0;
+        match self.mode {
+            OutputMode::Json => {
+                serde_json::json!({ "rows_affected": rows, "elapsed_ms": ms }).to_string()
+            }
+            OutputMode::Plain | OutputMode::Rich => {
+                let noun = if rows == 1 { "row" } else { "rows" };
+                format!("{rows} {noun} affected ({ms:.2}ms)")
+            }
+        }
+    }
+
+    /// Render the table list produced by `\d`.
+    pub fn render_table_names(&self, names: &[String]) -> String {
+        let mut table = QueryResultTable::new()
+            .title("Tables")
+            .columns(["table"])
+            .theme(self.theme.clone());
+        for name in names {
+            table = table.row([name.as_str()]);
+        }
+        self.render_table(&table)
+    }
+
+    /// Render the model list produced by `\m`, as `(table, type name)` pairs.
+    pub fn render_model_names(&self, models: &[(&str, &str)]) -> String {
+        let mut table = QueryResultTable::new()
+            .title("Models")
+            .columns(["table", "model"])
+            .theme(self.theme.clone());
+        for (name, model) in models {
+            table = table.row([*name, *model]);
+        }
+        self.render_table(&table)
+    }
+
+    /// Render a table description produced by `\d <table>`.
+    pub fn render_table_info(&self, info: TableInfo) -> String {
+        let mut info = info.theme(self.theme.clone());
+        if let Some(width) = self.max_width {
+            info = info.width(width);
+        }
+        match self.mode {
+            OutputMode::Plain => info.render_plain(),
+            OutputMode::Rich => info.render_styled(),
+            OutputMode::Json => info.to_json().to_string(),
+        }
+    }
+
+    /// Render an error message.
+    pub fn render_error(&self, message: &str) -> String {
+        match self.mode {
+            OutputMode::Json => serde_json::json!({ "error": message }).to_string(),
+            OutputMode::Plain => format!("ERROR: {message}"),
+            OutputMode::Rich => format!(
+                "{}ERROR:{} {message}",
+                self.theme.error.color_code(),
+                "\x1b[0m"
+            ),
+        }
+    }
+
+    fn render_table(&self, table: &QueryResultTable) -> String {
+        match self.mode {
+            OutputMode::Plain => table.render_plain(),
+            OutputMode::Rich => table.render_styled(),
+            OutputMode::Json => table.to_json().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meta_commands() {
+        assert_eq!(ReplCommand::parse_meta("\\d"), ReplCommand::ListTables);
+        assert_eq!(ReplCommand::parse_meta("\\dt"), ReplCommand::ListTables);
+        assert_eq!(
+            ReplCommand::parse_meta("\\d heroes"),
+            ReplCommand::Describe("heroes".to_string())
+        );
+        assert_eq!(ReplCommand::parse_meta("\\m"), ReplCommand::ListModels);
+        assert_eq!(ReplCommand::parse_meta("\\models"), ReplCommand::ListModels);
+        assert_eq!(
+            ReplCommand::parse_meta("\\m heroes"),
+            ReplCommand::DescribeModel("heroes".to_string())
+        );
+        assert_eq!(
+            ReplCommand::parse_meta("\\mode JSON"),
+            ReplCommand::Mode(OutputMode::Json)
+        );
+        assert_eq!(ReplCommand::parse_meta("\\q"), ReplCommand::Quit);
+        assert_eq!(ReplCommand::parse_meta("\\?"), ReplCommand::Help);
+        assert!(matches!(
+            ReplCommand::parse_meta("\\mode loud"),
+            ReplCommand::Invalid(_)
+        ));
+        assert!(matches!(
+            ReplCommand::parse_meta("\\x"),
+            ReplCommand::Invalid(_)
+        ));
+    }
+
+    #[test]
+    fn test_multiline_sql() {
+        let mut input = ReplInput::new();
+        assert_eq!(input.prompt(), "sqlmodel> ");
+        assert_eq!(input.push_line("SELECT *"), None);
+        assert!(input.is_continuation());
+        assert_eq!(input.prompt(), "     ...> ");
+        assert_eq!(input.push_line("  FROM heroes"), None);
+        assert_eq!(
+            input.push_line("WHERE id = 1 ;"),
+            Some(ReplCommand::Sql(
+                "SELECT *\n  FROM heroes\nWHERE id = 1".to_string()
+            ))
+        );
+        assert!(!input.is_continuation());
+        assert_eq!(input.push_line(""), None);
+        assert_eq!(input.push_line(";"), None);
+    }
+
+    #[test]
+    fn test_take_pending() {
+        let mut input = ReplInput::new();
+        input.push_line("SELECT 1");
+        assert_eq!(
+            input.take_pending(),
+            Some(ReplCommand::Sql("SELECT 1".to_string()))
+        );
+        assert_eq!(input.take_pending(), None);
+    }
+
+    #[test]
+    fn test_render_rows_plain_and_json() {
+        let renderer = ReplRenderer::new(OutputMode::Plain);
+        let out = renderer.render_rows(
+            vec!["id".to_string(), "name".to_string()],
+            vec![vec![Some("1".to_string()), None]],
+            Duration::from_millis(2),
+        );
+        assert!(out.contains("id"));
+        assert!(out.contains("NULL"));
+
+        let renderer = ReplRenderer::new(OutputMode::Json);
+        let out = renderer.render_rows(
+            vec!["id".to_string()],
+            vec![vec![Some("1".to_string())]],
+            Duration::from_millis(2),
+        );
+        assert!(out.starts_with('{') || out.starts_with('['));
+    }
+
+    #[test]
+    fn test_render_affected_and_error() {
+        let renderer = ReplRenderer::new(OutputMode::Plain);
+        assert_eq!(
+            renderer.render_affected(1, Duration::from_millis(3)),
+            "1 row affected (3.00ms)"
+        );
+        assert_eq!(renderer.render_error("boom"), "ERROR: boom");
+
+        let renderer = ReplRenderer::new(OutputMode::Json);
+        assert_eq!(renderer.render_error("boom"), "{\"error\":\"boom\"}");
+    }
+}
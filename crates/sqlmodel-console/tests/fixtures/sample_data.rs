@@ -25,6 +25,8 @@ fn col(
         primary_key: pk,
         auto_increment: auto,
         comment: None,
+        collation: None,
+        charset: None,
     }
 }
 
@@ -0,0 +1,411 @@
+//! Budget-aware query admission.
+//!
+//! Every statement is checked against the caller's [`Cx`] budget *before* it
+//! is sent, so an exhausted budget surfaces as a clean
+//! [`Outcome::Cancelled`] instead of a statement the server starts and the
+//! client abandons mid-stream. Statements are weighed by a [`QueryCost`]
+//! class: a statement is refused when the budget's cost quota is below the
+//! class's units or its deadline is closer than the class's expected time.
+//!
+//! [`BudgetedConnection`] applies the check to every statement of a
+//! connection (`Session::with_budget` uses it). A statement's class is the
+//! connection's default unless the SQL carries a hint comment:
+//!
+//! ```rust,ignore
+//! use sqlmodel_core::budget::{BudgetedConnection, QueryCost};
+//!
+//! let conn = BudgetedConnection::new(conn, QueryCost::Light);
+//! let sql = QueryCost::Heavy.annotate("SELECT region, sum(total) FROM orders GROUP BY region");
+//! conn.query(&cx, &sql, &[]).await?; // refused up front if < 1s of budget remains
+//! ```
+//!
+//! `COMMIT` and `ROLLBACK` are always admitted so exhausted work can still
+//! be finished or undone.
+
+use std::future::Future;
+use std::time::Duration;
+
+use asupersync::{Budget, CancelReason};
+
+use crate::connection::{Connection, IsolationLevel, PreparedStatement, QueryResult};
+use crate::error::Error;
+use crate::interceptor::Statement;
+use crate::row::Row;
+use crate::value::Value;
+use crate::{Cx, Dialect, Outcome, SqlDialect};
+
+/// Estimated cost class of a statement, from cheapest to most expensive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum QueryCost {
+    /// Point lookups by primary key, `SELECT 1`
+    Trivial,
+    /// Indexed reads and single-row writes
+    #[default]
+    Light,
+    /// Joins, range scans and multi-row writes
+    Moderate,
+    /// Aggregations, reports and bulk operations
+    Heavy,
+}
+
+impl QueryCost {
+    /// Cost-quota units the statement needs.
+    #[must_use]
+    pub fn units(self) -> u64 {
+        match self {
+            Self::Trivial => 1,
+            Self::Light => 10,
+            Self::Moderate => 100,
+            Self::Heavy => 1000,
+        }
+    }
+
+    /// Time the statement is expected to take.
+    #[must_use]
+    pub fn expected_time(self) -> Duration {
+        match self {
+            Self::Trivial => Duration::from_millis(1),
+            Self::Light => Duration::from_millis(10),
+            Self::Moderate => Duration::from_millis(100),
+            Self::Heavy => Duration::from_secs(1),
+        }
+    }
+
+    /// The class name, e.g. `"heavy"`.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Trivial => "trivial",
+            Self::Light => "light",
+            Self::Moderate => "moderate",
+            Self::Heavy => "heavy",
+        }
+    }
+
+    /// Prefix `sql` with a `/* cost=... */` hint comment.
+    #[must_use]
+    pub fn annotate(self, sql: &str) -> String {
+        format!("/* cost={} */ {}", self.as_str(), sql)
+    }
+
+    /// The class named by a `/* cost=... */` hint in `sql`, if any.
+    #[must_use]
+    pub fn from_hint(sql: &str) -> Option<Self> {
+        let start = sql.find("/* cost=")? + "/* cost=".len();
+        let name = sql[start..]
+            .split(|c: char| c.is_whitespace() || c == '*')
+            .next()?;
+        match name {
+            "trivial" => Some(Self::Trivial),
+            "light" => Some(Self::Light),
+            "moderate" => Some(Self::Moderate),
+            "heavy" => Some(Self::Heavy),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for QueryCost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Check that `cx` can afford a statement of class `cost`.
+///
+/// Returns [`Outcome::Cancelled`] when cancellation was requested or the
+/// budget is exhausted, too small for `cost`, or past its deadline.
+pub fn admit(cx: &Cx, cost: QueryCost) -> Outcome<(), Error> {
+    if cx.is_cancel_requested() {
+        return Outcome::Cancelled(CancelReason::user("cancelled before statement was sent"));
+    }
+    let budget = cx.budget();
+    if budget.is_exhausted() {
+        return Outcome::Cancelled(CancelReason::user("budget exhausted before statement"));
+    }
+    if budget.cost_quota.is_some_and(|quota| quota < cost.units()) {
+        return Outcome::Cancelled(CancelReason::user("cost quota too small for statement"));
+    }
+    if remaining_time(&budget).is_some_and(|remaining| remaining < cost.expected_time()) {
+        return Outcome::Cancelled(CancelReason::user("deadline too close for statement"));
+    }
+    Outcome::Ok(())
+}
+
+/// Time left before the budget's deadline; `None` without a deadline.
+fn remaining_time(budget: &Budget) -> Option<Duration> {
+    budget.deadline?;
+    Some(
+        budget
+            .remaining_time(asupersync::time::wall_now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// Whether `sql` ends a transaction and must run even without budget.
+fn is_transaction_end(dialect: Dialect, sql: &str) -> bool {
+    Statement::new(dialect, sql, &[])
+        .keyword()
+        .is_some_and(|keyword| matches!(keyword.as_str(), "COMMIT" | "ROLLBACK" | "END"))
+}
+
+/// A connection that checks the [`Cx`] budget before each statement.
+///
+/// Prepared statements are weighed when run, using the hint in their SQL.
+/// Statements issued on a transaction object returned by
+/// [`begin`](Connection::begin) are not checked.
+#[derive(Debug)]
+pub struct BudgetedConnection<C> {
+    inner: C,
+    default_cost: QueryCost,
+}
+
+impl<C: Connection> BudgetedConnection<C> {
+    /// Wrap `inner`, weighing statements without a hint as `default_cost`.
+    pub fn new(inner: C, default_cost: QueryCost) -> Self {
+        Self {
+            inner,
+            default_cost,
+        }
+    }
+
+    /// The wrapped connection.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// The class of statements without a hint.
+    pub fn default_cost(&self) -> QueryCost {
+        self.default_cost
+    }
+
+    /// Unwrap the inner connection.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    /// The class `sql` is weighed as.
+    pub fn cost_of(&self, sql: &str) -> QueryCost {
+        QueryCost::from_hint(sql).unwrap_or(self.default_cost)
+    }
+
+    fn admit_sql(&self, cx: &Cx, sql: &str) -> Outcome<(), Error> {
+        if is_transaction_end(self.inner.dialect(), sql) {
+            return Outcome::Ok(());
+        }
+        admit(cx, self.cost_of(sql))
+    }
+}
+
+#[allow(clippy::manual_async_fn)] // Trait methods return `impl Future + Send`
+impl<C: Connection> Connection for BudgetedConnection<C> {
+    type Tx<'conn>
+        = C::Tx<'conn>
+    where
+        Self: 'conn;
+
+    fn dialect(&self) -> Dialect {
+        self.inner.dialect()
+    }
+
+    fn sql_dialect(&self) -> &'static dyn SqlDialect {
+        self.inner.sql_dialect()
+    }
+
+    fn query(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<Vec<Row>, Error>> + Send {
+        async move {
+            match self.admit_sql(cx, sql) {
+                Outcome::Ok(()) => self.inner.query(cx, sql, params).await,
+                Outcome::Err(e) => Outcome::Err(e),
+                Outcome::Cancelled(r) => Outcome::Cancelled(r),
+                Outcome::Panicked(p) => Outcome::Panicked(p),
+            }
+        }
+    }
+
+    fn query_raw(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<QueryResult, Error>> + Send {
+        async move {
+            match self.admit_sql(cx, sql) {
+                Outcome::Ok(()) => self.inner.query_raw(cx, sql, params).await,
+                Outcome::Err(e) => Outcome::Err(e),
+                Outcome::Cancelled(r) => Outcome::Cancelled(r),
+                Outcome::Panicked(p) => Outcome::Panicked(p),
+            }
+        }
+    }
+
+    fn query_one(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<Option<Row>, Error>> + Send {
+        async move {
+            match self.admit_sql(cx, sql) {
+                Outcome::Ok(()) => self.inner.query_one(cx, sql, params).await,
+                Outcome::Err(e) => Outcome::Err(e),
+                Outcome::Cancelled(r) => Outcome::Cancelled(r),
+                Outcome::Panicked(p) => Outcome::Panicked(p),
+            }
+        }
+    }
+
+    fn execute(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<u64, Error>> + Send {
+        async move {
+            match self.admit_sql(cx, sql) {
+                Outcome::Ok(()) => self.inner.execute(cx, sql, params).await,
+                Outcome::Err(e) => Outcome::Err(e),
+                Outcome::Cancelled(r) => Outcome::Cancelled(r),
+                Outcome::Panicked(p) => Outcome::Panicked(p),
+            }
+        }
+    }
+
+    fn insert(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<i64, Error>> + Send {
+        async move {
+            match self.admit_sql(cx, sql) {
+                Outcome::Ok(()) => self.inner.insert(cx, sql, params).await,
+                Outcome::Err(e) => Outcome::Err(e),
+                Outcome::Cancelled(r) => Outcome::Cancelled(r),
+                Outcome::Panicked(p) => Outcome::Panicked(p),
+            }
+        }
+    }
+
+    fn batch(
+        &self,
+        cx: &Cx,
+        statements: &[(String, Vec<Value>)],
+    ) -> impl Future<Output = Outcome<Vec<u64>, Error>> + Send {
+        // A batch costs as much as its most expensive statement.
+        let cost = statements
+            .iter()
+            .map(|(sql, _)| self.cost_of(sql))
+            .max()
+            .unwrap_or(QueryCost::Trivial);
+        async move {
+            match admit(cx, cost) {
+                Outcome::Ok(()) => self.inner.batch(cx, statements).await,
+                Outcome::Err(e) => Outcome::Err(e),
+                Outcome::Cancelled(r) => Outcome::Cancelled(r),
+                Outcome::Panicked(p) => Outcome::Panicked(p),
+            }
+        }
+    }
+
+    fn begin(&self, cx: &Cx) -> impl Future<Output = Outcome<Self::Tx<'_>, Error>> + Send {
+        self.inner.begin(cx)
+    }
+
+    fn begin_with(
+        &self,
+        cx: &Cx,
+        isolation: IsolationLevel,
+    ) -> impl Future<Output = Outcome<Self::Tx<'_>, Error>> + Send {
+        self.inner.begin_with(cx, isolation)
+    }
+
+    fn prepare(
+        &self,
+        cx: &Cx,
+        sql: &str,
+    ) -> impl Future<Output = Outcome<PreparedStatement, Error>> + Send {
+        self.inner.prepare(cx, sql)
+    }
+
+    fn query_prepared(
+        &self,
+        cx: &Cx,
+        stmt: &PreparedStatement,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<Vec<Row>, Error>> + Send {
+        async move {
+            match self.admit_sql(cx, stmt.sql()) {
+                Outcome::Ok(()) => self.inner.query_prepared(cx, stmt, params).await,
+                Outcome::Err(e) => Outcome::Err(e),
+                Outcome::Cancelled(r) => Outcome::Cancelled(r),
+                Outcome::Panicked(p) => Outcome::Panicked(p),
+            }
+        }
+    }
+
+    fn execute_prepared(
+        &self,
+        cx: &Cx,
+        stmt: &PreparedStatement,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<u64, Error>> + Send {
+        async move {
+            match self.admit_sql(cx, stmt.sql()) {
+                Outcome::Ok(()) => self.inner.execute_prepared(cx, stmt, params).await,
+                Outcome::Err(e) => Outcome::Err(e),
+                Outcome::Cancelled(r) => Outcome::Cancelled(r),
+                Outcome::Panicked(p) => Outcome::Panicked(p),
+            }
+        }
+    }
+
+    fn ping(&self, cx: &Cx) -> impl Future<Output = Outcome<(), Error>> + Send {
+        self.inner.ping(cx)
+    }
+
+    fn close(self, cx: &Cx) -> impl Future<Output = crate::Result<()>> + Send {
+        self.inner.close(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cost_hints() {
+        let sql = QueryCost::Heavy.annotate("SELECT count(*) FROM orders");
+        assert_eq!(sql, "/* cost=heavy */ SELECT count(*) FROM orders");
+        assert_eq!(QueryCost::from_hint(&sql), Some(QueryCost::Heavy));
+        assert_eq!(
+            QueryCost::from_hint("SELECT 1 /* cost=trivial*/"),
+            Some(QueryCost::Trivial)
+        );
+        assert_eq!(QueryCost::from_hint("SELECT 1 /* cost=huge */"), None);
+        assert_eq!(QueryCost::from_hint("SELECT 1"), None);
+        assert!(QueryCost::Trivial < QueryCost::Heavy);
+    }
+
+    #[test]
+    fn test_transaction_end_is_always_admitted() {
+        assert!(is_transaction_end(Dialect::Postgres, "COMMIT"));
+        assert!(is_transaction_end(
+            Dialect::Sqlite,
+            "  rollback to savepoint sp1"
+        ));
+        assert!(!is_transaction_end(Dialect::Postgres, "BEGIN"));
+        assert!(!is_transaction_end(Dialect::Postgres, "SELECT 1"));
+    }
+
+    #[test]
+    fn test_admit_unbounded_budget() {
+        let cx = Cx::for_testing();
+        assert!(matches!(admit(&cx, QueryCost::Heavy), Outcome::Ok(())));
+    }
+}
@@ -6,11 +6,16 @@
 //! - [`Transaction`] - Trait for transactional operations with savepoint support
 //! - [`IsolationLevel`] - SQL transaction isolation levels
 //! - [`PreparedStatement`] - Pre-compiled statement for efficient repeated execution
+//! - [`QueryResult`] - Rows together with their column metadata
 //!
 //! All operations integrate with asupersync's structured concurrency via `Cx` context
 //! for proper cancellation and timeout handling.
 
+pub use crate::dialect::Dialect;
+use crate::dialect::SqlDialect;
 use crate::error::Result;
+use crate::params::{ToParams, ValueRef};
+use crate::query_options::{QueryOptions, with_statement_timeout};
 use crate::row::Row;
 use crate::value::Value;
 use asupersync::{Cx, Outcome};
@@ -129,6 +134,80 @@ impl PreparedStatement {
     }
 }
 
+/// Metadata for one column of a result set.
+///
+/// Fields a driver cannot report are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResultColumn {
+    /// Column name (or alias) as returned by the database.
+    pub name: String,
+    /// Database type name, e.g. `int4` (PostgreSQL), `VARCHAR` (MySQL) or the
+    /// declared type of an SQLite column.
+    pub sql_type: Option<String>,
+    /// Driver-specific type id: the PostgreSQL type OID or the MySQL column type code.
+    pub type_id: Option<u32>,
+    /// OID of the source table (PostgreSQL).
+    pub table_oid: Option<u32>,
+    /// Name of the source table (MySQL).
+    pub table: Option<String>,
+    /// Whether the column can hold NULL.
+    pub nullable: Option<bool>,
+}
+
+impl ResultColumn {
+    /// A column with only its name known.
+    #[must_use]
+    pub fn named(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Rows returned by [`Connection::query_raw`], with their column metadata.
+///
+/// Lets generic tools (REPLs, exporters, admin UIs) render a result without
+/// a [`Model`](crate::Model).
+#[derive(Debug, Clone, Default)]
+pub struct QueryResult {
+    /// Columns in result order.
+    pub columns: Vec<ResultColumn>,
+    /// The rows.
+    pub rows: Vec<Row>,
+}
+
+impl QueryResult {
+    /// Build a result whose columns are known only by the names on `rows`.
+    ///
+    /// An empty `rows` yields no columns.
+    #[must_use]
+    pub fn from_rows(rows: Vec<Row>) -> Self {
+        let columns = rows
+            .first()
+            .map(|row| row.column_names().map(ResultColumn::named).collect())
+            .unwrap_or_default();
+        Self { columns, rows }
+    }
+
+    /// Column names in result order.
+    pub fn column_names(&self) -> impl Iterator<Item = &str> {
+        self.columns.iter().map(|c| c.name.as_str())
+    }
+
+    /// Number of rows.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Whether the result has no rows.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
 /// A database connection capable of executing queries.
 ///
 /// All operations are async and take a `Cx` context for cancellation/timeout support.
@@ -150,60 +229,6 @@ impl PreparedStatement {
 /// tx.execute(&cx, "INSERT INTO logs (msg) VALUES ($1)", &[Value::Text("action".into())]).await?;
 /// tx.commit(&cx).await?;
 /// ```
-/// SQL dialect enumeration for cross-database compatibility.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub enum Dialect {
-    /// PostgreSQL dialect (uses $1, $2 placeholders)
-    #[default]
-    Postgres,
-    /// SQLite dialect (uses ?1, ?2 placeholders)
-    Sqlite,
-    /// MySQL dialect (uses ? placeholders)
-    Mysql,
-}
-
-impl Dialect {
-    /// Generate a placeholder for the given parameter index (1-based).
-    pub fn placeholder(self, index: usize) -> String {
-        match self {
-            Dialect::Postgres => format!("${index}"),
-            Dialect::Sqlite => format!("?{index}"),
-            Dialect::Mysql => "?".to_string(),
-        }
-    }
-
-    /// Get the string concatenation operator for this dialect.
-    pub const fn concat_op(self) -> &'static str {
-        match self {
-            Dialect::Postgres | Dialect::Sqlite => "||",
-            Dialect::Mysql => "", // MySQL uses CONCAT() function
-        }
-    }
-
-    /// Check if this dialect supports ILIKE.
-    pub const fn supports_ilike(self) -> bool {
-        matches!(self, Dialect::Postgres)
-    }
-
-    /// Quote an identifier for this dialect.
-    ///
-    /// Properly escapes embedded quote characters by doubling them:
-    /// - For Postgres/SQLite: `"` becomes `""`
-    /// - For MySQL: `` ` `` becomes ``` `` ```
-    pub fn quote_identifier(self, name: &str) -> String {
-        match self {
-            Dialect::Postgres | Dialect::Sqlite => {
-                let escaped = name.replace('"', "\"\"");
-                format!("\"{escaped}\"")
-            }
-            Dialect::Mysql => {
-                let escaped = name.replace('`', "``");
-                format!("`{escaped}`")
-            }
-        }
-    }
-}
-
 pub trait Connection: Send + Sync {
     /// The transaction type returned by this connection.
     type Tx<'conn>: TransactionOps
@@ -218,6 +243,15 @@ pub trait Connection: Send + Sync {
         Dialect::Postgres
     }
 
+    /// Get the pluggable dialect implementation for this connection.
+    ///
+    /// Built-in drivers return the static implementation for [`dialect`](Connection::dialect).
+    /// Third-party drivers override this to expose their own [`SqlDialect`];
+    /// sessions, query builders and DDL generation render through it.
+    fn sql_dialect(&self) -> &'static dyn SqlDialect {
+        self.dialect().as_dyn()
+    }
+
     /// Execute a query and return all rows.
     fn query(
         &self,
@@ -234,6 +268,27 @@ pub trait Connection: Send + Sync {
         params: &[Value],
     ) -> impl Future<Output = Outcome<Option<Row>, crate::Error>> + Send;
 
+    /// Execute a query and return its rows together with column metadata.
+    ///
+    /// The default implementation runs [`query`](Connection::query) and takes
+    /// the column names from the first row; drivers override it to report
+    /// types, source tables and nullability, including for empty results.
+    fn query_raw(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<QueryResult, crate::Error>> + Send {
+        async move {
+            match self.query(cx, sql, params).await {
+                Outcome::Ok(rows) => Outcome::Ok(QueryResult::from_rows(rows)),
+                Outcome::Err(e) => Outcome::Err(e),
+                Outcome::Cancelled(r) => Outcome::Cancelled(r),
+                Outcome::Panicked(p) => Outcome::Panicked(p),
+            }
+        }
+    }
+
     /// Execute a statement (INSERT, UPDATE, DELETE) and return rows affected.
     fn execute(
         &self,
@@ -242,6 +297,122 @@ pub trait Connection: Send + Sync {
         params: &[Value],
     ) -> impl Future<Output = Outcome<u64, crate::Error>> + Send;
 
+    /// Execute a query with borrowed parameters and return all rows.
+    ///
+    /// Accepts any [`ToParams`], such as `&[ValueRef]`, so large text or blob
+    /// parameters need not be cloned into owned [`Value`]s. Drivers that bind
+    /// borrowed data override this; the default copies the parameters and
+    /// calls [`query`](Connection::query).
+    fn query_params<P: ToParams + ?Sized>(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &P,
+    ) -> impl Future<Output = Outcome<Vec<Row>, crate::Error>> + Send {
+        let params: Vec<Value> = params
+            .to_params()
+            .into_iter()
+            .map(ValueRef::to_value)
+            .collect();
+        async move { self.query(cx, sql, &params).await }
+    }
+
+    /// Execute a statement with borrowed parameters and return rows affected.
+    ///
+    /// The borrowed counterpart of [`execute`](Connection::execute); see
+    /// [`query_params`](Connection::query_params).
+    fn execute_params<P: ToParams + ?Sized>(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &P,
+    ) -> impl Future<Output = Outcome<u64, crate::Error>> + Send {
+        let params: Vec<Value> = params
+            .to_params()
+            .into_iter()
+            .map(ValueRef::to_value)
+            .collect();
+        async move { self.execute(cx, sql, &params).await }
+    }
+
+    /// Execute a query with per-statement [`QueryOptions`] and return all rows.
+    ///
+    /// The default implementation tags the SQL and maps the timeout to
+    /// server settings, see [`query_options`](crate::query_options); drivers
+    /// without such settings override it.
+    fn query_with(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+        options: &QueryOptions,
+    ) -> impl Future<Output = Outcome<Vec<Row>, crate::Error>> + Send {
+        async move {
+            let sql = options.statement_sql(self.dialect(), sql);
+            with_statement_timeout(self, cx, options, || self.query(cx, &sql, params)).await
+        }
+    }
+
+    /// Execute a statement with per-statement [`QueryOptions`] and return
+    /// rows affected.
+    ///
+    /// See [`query_with`](Connection::query_with).
+    fn execute_with(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+        options: &QueryOptions,
+    ) -> impl Future<Output = Outcome<u64, crate::Error>> + Send {
+        async move {
+            let sql = options.statement_sql(self.dialect(), sql);
+            with_statement_timeout(self, cx, options, || self.execute(cx, &sql, params)).await
+        }
+    }
+
+    /// Execute a multi-statement SQL script, such as a `.sql` migration file.
+    ///
+    /// The script is split with [`split_script`](crate::script::split_script)
+    /// for this connection's dialect, so semicolons inside literals, comments,
+    /// dollar-quoted bodies and trigger/routine `BEGIN ... END` blocks are
+    /// kept. Statements run in order through [`execute`](Connection::execute);
+    /// the first failure stops the script, and a query error's detail then
+    /// names the failing statement and its line. Returns the total rows affected.
+    fn execute_script(
+        &self,
+        cx: &Cx,
+        script: &str,
+    ) -> impl Future<Output = Outcome<u64, crate::Error>> + Send {
+        async move {
+            let statements = crate::script::split_script(self.dialect(), script);
+            let count = statements.len();
+            let mut affected = 0u64;
+            for (index, statement) in statements.into_iter().enumerate() {
+                match self.execute(cx, statement.sql, &[]).await {
+                    Outcome::Ok(n) => affected += n,
+                    Outcome::Err(mut e) => {
+                        if let Some(q) = e.query_error_mut() {
+                            let at = format!(
+                                "statement {} of {count} in script, line {}",
+                                index + 1,
+                                statement.line
+                            );
+                            q.sql.get_or_insert_with(|| statement.sql.to_string());
+                            q.detail = Some(match q.detail.take() {
+                                Some(detail) => format!("{detail}; {at}"),
+                                None => at,
+                            });
+                        }
+                        return Outcome::Err(e);
+                    }
+                    Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                    Outcome::Panicked(p) => return Outcome::Panicked(p),
+                }
+            }
+            Outcome::Ok(affected)
+        }
+    }
+
     /// Execute an INSERT and return the last inserted ID.
     ///
     /// For PostgreSQL, this typically uses RETURNING to get the inserted ID.
@@ -649,6 +820,21 @@ impl ConnectionConfig {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_query_result_from_rows_takes_names_from_first_row() {
+        let columns = std::sync::Arc::new(crate::row::ColumnInfo::new(vec![
+            "id".to_string(),
+            "name".to_string(),
+        ]));
+        let row = Row::with_columns(columns, vec![Value::Int(1), Value::Text("a".into())]);
+        let result = QueryResult::from_rows(vec![row]);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.column_names().collect::<Vec<_>>(), ["id", "name"]);
+        assert_eq!(result.columns[0], ResultColumn::named("id"));
+
+        assert!(QueryResult::from_rows(Vec::new()).columns.is_empty());
+    }
+
     #[test]
     fn test_isolation_level_default() {
         let level = IsolationLevel::default();
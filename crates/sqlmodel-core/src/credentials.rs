@@ -0,0 +1,324 @@
+//! Dynamic database credentials.
+//!
+//! A [`CredentialsProvider`] is asked for [`Credentials`] every time a new
+//! connection is opened, so short-lived secrets (AWS RDS IAM tokens, Vault
+//! leases) never have to be baked into a config. Drivers expose
+//! `connect_with_credentials`, which fits straight into a pool's factory:
+//!
+//! ```rust,ignore
+//! let provider: Arc<dyn CredentialsProvider> =
+//!     Arc::new(EnvCredentials::new("PGUSER", "PGPASSWORD"));
+//!
+//! let conn = pool
+//!     .acquire(&cx, || {
+//!         PgAsyncConnection::connect_with_credentials(&cx, config.clone(), &*provider)
+//!     })
+//!     .await?;
+//! ```
+//!
+//! Token-based schemes plug in as closures. Wrapping them in
+//! [`CachedCredentials`] reuses a token until shortly before it expires;
+//! a pool `max_lifetime` below the token lifetime then rotates connections
+//! onto fresh tokens:
+//!
+//! ```rust,ignore
+//! let iam = CachedCredentials::new(
+//!     credentials::from_fn(|cx| {
+//!         Box::pin(async move {
+//!             let token = generate_rds_auth_token(cx, "db.example.com", 5432, "app").await?;
+//!             Outcome::Ok(Credentials::new("app", token).expires_in(Duration::from_secs(900)))
+//!         })
+//!     }),
+//!     Duration::from_secs(60),
+//! );
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use asupersync::{Cx, Outcome};
+
+use crate::error::{ConfigError, Error};
+
+/// A user name and secret to authenticate a connection with.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credentials {
+    /// User name
+    pub user: String,
+    /// Password or token; `None` for passwordless authentication
+    pub password: Option<String>,
+    /// When the secret stops being accepted by the server
+    pub expires_at: Option<Instant>,
+}
+
+impl Credentials {
+    /// Credentials that do not expire.
+    pub fn new(user: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            user: user.into(),
+            password: Some(password.into()),
+            expires_at: None,
+        }
+    }
+
+    /// Credentials without a password.
+    pub fn user(user: impl Into<String>) -> Self {
+        Self {
+            user: user.into(),
+            password: None,
+            expires_at: None,
+        }
+    }
+
+    /// Mark the secret as valid until `expires_at`.
+    #[must_use]
+    pub fn expires_at(mut self, expires_at: Instant) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Mark the secret as valid for `ttl` from now.
+    #[must_use]
+    pub fn expires_in(self, ttl: Duration) -> Self {
+        self.expires_at(Instant::now() + ttl)
+    }
+
+    /// Whether the secret expires within `margin` from now.
+    #[must_use]
+    pub fn expires_within(&self, margin: Duration) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= Instant::now() + margin)
+    }
+
+    /// Whether the secret has expired.
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        self.expires_within(Duration::ZERO)
+    }
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("user", &self.user)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+/// Future returned by [`CredentialsProvider::fetch`].
+pub type CredentialsFuture<'a> =
+    Pin<Box<dyn Future<Output = Outcome<Credentials, Error>> + Send + 'a>>;
+
+/// A source of [`Credentials`], consulted for every new connection.
+///
+/// Implemented for closures `Fn(&Cx) -> CredentialsFuture<'_>`; see
+/// [`from_fn`].
+pub trait CredentialsProvider: Send + Sync {
+    /// Fetch the credentials to open the next connection with.
+    fn fetch<'a>(&'a self, cx: &'a Cx) -> CredentialsFuture<'a>;
+}
+
+impl<F> CredentialsProvider for F
+where
+    F: for<'a> Fn(&'a Cx) -> CredentialsFuture<'a> + Send + Sync,
+{
+    fn fetch<'a>(&'a self, cx: &'a Cx) -> CredentialsFuture<'a> {
+        self(cx)
+    }
+}
+
+/// Use a closure as a [`CredentialsProvider`].
+///
+/// Passing the closure through here lets its signature be inferred.
+pub fn from_fn<F>(fetch: F) -> F
+where
+    F: for<'a> Fn(&'a Cx) -> CredentialsFuture<'a> + Send + Sync,
+{
+    fetch
+}
+
+/// Always returns the same credentials.
+#[derive(Debug, Clone)]
+pub struct StaticCredentials(pub Credentials);
+
+impl StaticCredentials {
+    /// Provide `user` and `password` to every connection.
+    pub fn new(user: impl Into<String>, password: impl Into<String>) -> Self {
+        Self(Credentials::new(user, password))
+    }
+}
+
+impl CredentialsProvider for StaticCredentials {
+    fn fetch<'a>(&'a self, _cx: &'a Cx) -> CredentialsFuture<'a> {
+        Box::pin(async move { Outcome::Ok(self.0.clone()) })
+    }
+}
+
+/// Reads credentials from environment variables on every fetch, so a
+/// rotated secret is picked up by the next connection.
+#[derive(Debug, Clone)]
+pub struct EnvCredentials {
+    user_var: String,
+    password_var: String,
+}
+
+impl EnvCredentials {
+    /// Read the user from `user_var` and the password from `password_var`.
+    ///
+    /// A missing password variable means no password; a missing user
+    /// variable fails the fetch.
+    pub fn new(user_var: impl Into<String>, password_var: impl Into<String>) -> Self {
+        Self {
+            user_var: user_var.into(),
+            password_var: password_var.into(),
+        }
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn read(&self) -> Result<Credentials, Error> {
+        let user = std::env::var(&self.user_var).map_err(|e| {
+            Error::Config(ConfigError {
+                message: format!("credentials: cannot read ${}: {}", self.user_var, e),
+                source: Some(Box::new(e)),
+            })
+        })?;
+        Ok(Credentials {
+            user,
+            password: std::env::var(&self.password_var).ok(),
+            expires_at: None,
+        })
+    }
+}
+
+impl CredentialsProvider for EnvCredentials {
+    fn fetch<'a>(&'a self, _cx: &'a Cx) -> CredentialsFuture<'a> {
+        Box::pin(async move {
+            match self.read() {
+                Ok(credentials) => Outcome::Ok(credentials),
+                Err(e) => Outcome::Err(e),
+            }
+        })
+    }
+}
+
+/// Reuses another provider's credentials until they are about to expire.
+///
+/// Credentials without an expiry are cached for good. Concurrent fetches
+/// that find the cache stale may each ask the inner provider.
+pub struct CachedCredentials<P> {
+    inner: P,
+    refresh_before: Duration,
+    cached: Mutex<Option<Credentials>>,
+}
+
+impl<P: CredentialsProvider> CachedCredentials<P> {
+    /// Cache `inner`'s credentials, refreshing them `refresh_before` their
+    /// expiry.
+    pub fn new(inner: P, refresh_before: Duration) -> Self {
+        Self {
+            inner,
+            refresh_before,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Drop the cached credentials, e.g. after an authentication failure.
+    pub fn invalidate(&self) {
+        *self.cached.lock().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+
+    fn cached(&self) -> Option<Credentials> {
+        self.cached
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .filter(|credentials| !credentials.expires_within(self.refresh_before))
+            .cloned()
+    }
+}
+
+impl<P: CredentialsProvider> CredentialsProvider for CachedCredentials<P> {
+    fn fetch<'a>(&'a self, cx: &'a Cx) -> CredentialsFuture<'a> {
+        Box::pin(async move {
+            if let Some(credentials) = self.cached() {
+                return Outcome::Ok(credentials);
+            }
+            match self.inner.fetch(cx).await {
+                Outcome::Ok(credentials) => {
+                    *self.cached.lock().unwrap_or_else(PoisonError::into_inner) =
+                        Some(credentials.clone());
+                    Outcome::Ok(credentials)
+                }
+                Outcome::Err(e) => Outcome::Err(e),
+                Outcome::Cancelled(r) => Outcome::Cancelled(r),
+                Outcome::Panicked(p) => Outcome::Panicked(p),
+            }
+        })
+    }
+}
+
+impl<P> std::fmt::Debug for CachedCredentials<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedCredentials")
+            .field("refresh_before", &self.refresh_before)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn test_credentials_expiry_and_redaction() {
+        let creds = Credentials::new("app", "hunter2");
+        assert!(!creds.is_expired());
+        assert!(!format!("{creds:?}").contains("hunter2"));
+
+        let token = Credentials::new("app", "token").expires_in(Duration::from_secs(30));
+        assert!(!token.is_expired());
+        assert!(token.expires_within(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_cached_credentials_refresh_near_expiry() {
+        use asupersync::runtime::RuntimeBuilder;
+
+        let calls = AtomicU32::new(0);
+        let provider = CachedCredentials::new(
+            from_fn(|_cx| {
+                let n = calls.fetch_add(1, Ordering::Relaxed);
+                Box::pin(async move {
+                    // The first token is already inside the refresh window.
+                    let ttl = if n == 0 { 10 } else { 900 };
+                    Outcome::Ok(
+                        Credentials::new("app", format!("token-{n}"))
+                            .expires_in(Duration::from_secs(ttl)),
+                    )
+                })
+            }),
+            Duration::from_secs(60),
+        );
+        let cx = Cx::for_testing();
+        let rt = RuntimeBuilder::current_thread()
+            .build()
+            .expect("create asupersync runtime");
+        rt.block_on(async {
+            for expected in ["token-0", "token-1", "token-1"] {
+                let Outcome::Ok(creds) = provider.fetch(&cx).await else {
+                    panic!("fetch failed");
+                };
+                assert_eq!(creds.password.as_deref(), Some(expected));
+            }
+        });
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+
+        provider.invalidate();
+        assert!(provider.cached().is_none());
+    }
+}
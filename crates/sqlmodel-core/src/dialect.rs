@@ -0,0 +1,411 @@
+//! SQL dialect abstraction.
+//!
+//! SQL generation differs between databases in a handful of well-defined places:
+//! identifier quoting, parameter placeholders, `LIMIT`/`OFFSET` syntax, `RETURNING`
+//! support, upsert syntax, and type names. This module captures those differences
+//! behind the object-safe [`SqlDialect`] trait so that external crates can add new
+//! backends without forking `sqlmodel-core`.
+//!
+//! The built-in [`Dialect`] enum implements [`SqlDialect`] for PostgreSQL, SQLite,
+//! and MySQL. Custom dialects declare which built-in family they are closest to via
+//! [`SqlDialect::base_dialect`]; query builders fall back to that family for any
+//! construct the trait does not cover.
+//!
+//! A connection reports its dialect through
+//! [`Connection::sql_dialect`](crate::Connection::sql_dialect). Sessions render
+//! their statements through it, and the `build_with_dialect` methods of the
+//! query builders accept any [`IntoSqlDialect`].
+//!
+//! # Example
+//!
+//! ```
+//! use sqlmodel_core::dialect::{Dialect, SqlDialect, UpsertSyntax};
+//!
+//! #[derive(Debug)]
+//! struct CockroachDialect;
+//!
+//! impl SqlDialect for CockroachDialect {
+//!     fn name(&self) -> &str {
+//!         "cockroach"
+//!     }
+//!
+//!     fn base_dialect(&self) -> Dialect {
+//!         Dialect::Postgres
+//!     }
+//! }
+//!
+//! let d: &dyn SqlDialect = &CockroachDialect;
+//! assert_eq!(d.placeholder(2), "$2");
+//! assert_eq!(d.quote_identifier("users"), "\"users\"");
+//! assert_eq!(d.upsert_syntax(), UpsertSyntax::OnConflict);
+//! ```
+
+use crate::types::SqlType;
+use std::fmt::Debug;
+
+/// Upsert syntax family supported by a dialect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpsertSyntax {
+    /// `INSERT ... ON CONFLICT (...) DO UPDATE SET ...` (PostgreSQL, SQLite)
+    OnConflict,
+    /// `INSERT ... ON DUPLICATE KEY UPDATE ...` (MySQL)
+    OnDuplicateKeyUpdate,
+    /// The dialect has no single-statement upsert.
+    Unsupported,
+}
+
+/// Pluggable SQL dialect.
+///
+/// Every method except [`name`](SqlDialect::name) and
+/// [`base_dialect`](SqlDialect::base_dialect) has a default that delegates to the
+/// base dialect, so implementors only override what actually differs.
+pub trait SqlDialect: Send + Sync + Debug {
+    /// Short, stable name of the dialect (e.g. `"postgres"`).
+    fn name(&self) -> &str;
+
+    /// The built-in dialect family this dialect is closest to.
+    ///
+    /// Query builders use this family for constructs not covered by this trait.
+    fn base_dialect(&self) -> Dialect;
+
+    /// Generate a placeholder for the given parameter index (1-based).
+    fn placeholder(&self, index: usize) -> String {
+        self.base_dialect().placeholder(index)
+    }
+
+    /// Quote an identifier, escaping embedded quote characters.
+    fn quote_identifier(&self, name: &str) -> String {
+        self.base_dialect().quote_identifier(name)
+    }
+
+    /// Get the string concatenation operator (empty if a function is required).
+    fn concat_op(&self) -> &'static str {
+        self.base_dialect().concat_op()
+    }
+
+    /// Check if this dialect supports `ILIKE`.
+    fn supports_ilike(&self) -> bool {
+        self.base_dialect().supports_ilike()
+    }
+
+    /// Check if this dialect supports `RETURNING` on INSERT/UPDATE/DELETE.
+    fn supports_returning(&self) -> bool {
+        self.base_dialect().supports_returning()
+    }
+
+    /// Most bind parameters a single statement may carry.
+    fn max_bind_params(&self) -> usize {
+        self.base_dialect().max_bind_params()
+    }
+
+    /// The upsert syntax family this dialect uses.
+    fn upsert_syntax(&self) -> UpsertSyntax {
+        self.base_dialect().upsert_syntax()
+    }
+
+    /// Check if this dialect supports the SQL:2003 `MERGE` statement.
+    fn supports_merge(&self) -> bool {
+        self.base_dialect().supports_merge()
+    }
+
+    /// Render the `LIMIT`/`OFFSET` suffix (including a leading space), or an empty
+    /// string when neither is set.
+    fn limit_offset(&self, limit: Option<u64>, offset: Option<u64>) -> String {
+        self.base_dialect().limit_offset(limit, offset)
+    }
+
+    /// Get the column type name for `ty` in this dialect.
+    fn type_name(&self, ty: &SqlType) -> String {
+        self.base_dialect().type_name(ty)
+    }
+}
+
+/// Conversion into the [`SqlDialect`] that SQL generation renders with.
+///
+/// Query builders and DDL generators accept a built-in [`Dialect`] or a
+/// third-party `&'static dyn SqlDialect`, such as the one returned by
+/// [`Connection::sql_dialect`](crate::Connection::sql_dialect).
+pub trait IntoSqlDialect {
+    /// The dialect as a trait object.
+    fn into_sql_dialect(self) -> &'static dyn SqlDialect;
+}
+
+impl IntoSqlDialect for Dialect {
+    fn into_sql_dialect(self) -> &'static dyn SqlDialect {
+        self.as_dyn()
+    }
+}
+
+impl IntoSqlDialect for &'static dyn SqlDialect {
+    fn into_sql_dialect(self) -> &'static dyn SqlDialect {
+        self
+    }
+}
+
+impl<D: SqlDialect> IntoSqlDialect for &'static D {
+    fn into_sql_dialect(self) -> &'static dyn SqlDialect {
+        self
+    }
+}
+
+/// SQL dialect enumeration for cross-database compatibility.
+///
+/// These are the built-in [`SqlDialect`] implementations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Dialect {
+    /// PostgreSQL dialect (uses $1, $2 placeholders)
+    #[default]
+    Postgres,
+    /// SQLite dialect (uses ?1, ?2 placeholders)
+    Sqlite,
+    /// MySQL dialect (uses ? placeholders)
+    Mysql,
+}
+
+static POSTGRES: Dialect = Dialect::Postgres;
+static SQLITE: Dialect = Dialect::Sqlite;
+static MYSQL: Dialect = Dialect::Mysql;
+
+impl Dialect {
+    /// Get a `'static` trait object for this built-in dialect.
+    #[must_use]
+    pub fn as_dyn(self) -> &'static dyn SqlDialect {
+        match self {
+            Dialect::Postgres => &POSTGRES,
+            Dialect::Sqlite => &SQLITE,
+            Dialect::Mysql => &MYSQL,
+        }
+    }
+
+    /// Short, stable name of the dialect.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Dialect::Postgres => "postgres",
+            Dialect::Sqlite => "sqlite",
+            Dialect::Mysql => "mysql",
+        }
+    }
+
+    /// Generate a placeholder for the given parameter index (1-based).
+    pub fn placeholder(self, index: usize) -> String {
+        match self {
+            Dialect::Postgres => format!("${index}"),
+            Dialect::Sqlite => format!("?{index}"),
+            Dialect::Mysql => "?".to_string(),
+        }
+    }
+
+    /// Get the string concatenation operator for this dialect.
+    pub const fn concat_op(self) -> &'static str {
+        match self {
+            Dialect::Postgres | Dialect::Sqlite => "||",
+            Dialect::Mysql => "", // MySQL uses CONCAT() function
+        }
+    }
+
+    /// Check if this dialect supports ILIKE.
+    pub const fn supports_ilike(self) -> bool {
+        matches!(self, Dialect::Postgres)
+    }
+
+    /// Check if this dialect supports `RETURNING`.
+    ///
+    /// SQLite has supported `RETURNING` since 3.35.
+    pub const fn supports_returning(self) -> bool {
+        matches!(self, Dialect::Postgres | Dialect::Sqlite)
+    }
+
+    /// Most bind parameters a single statement may carry.
+    ///
+    /// Postgres and MySQL cap a statement at 65535; SQLite builds before 3.32
+    /// cap it at 999.
+    pub const fn max_bind_params(self) -> usize {
+        match self {
+            Dialect::Sqlite => 999,
+            Dialect::Postgres | Dialect::Mysql => 65_535,
+        }
+    }
+
+    /// The upsert syntax family for this dialect.
+    pub const fn upsert_syntax(self) -> UpsertSyntax {
+        match self {
+            Dialect::Postgres | Dialect::Sqlite => UpsertSyntax::OnConflict,
+            Dialect::Mysql => UpsertSyntax::OnDuplicateKeyUpdate,
+        }
+    }
+
+    /// Check if this dialect supports the SQL:2003 `MERGE` statement.
+    ///
+    /// PostgreSQL gained `MERGE` in version 15; older servers need the upsert
+    /// emulation. SQLite and MySQL have no `MERGE`.
+    pub const fn supports_merge(self) -> bool {
+        matches!(self, Dialect::Postgres)
+    }
+
+    /// Render the `LIMIT`/`OFFSET` suffix for this dialect.
+    ///
+    /// SQLite and MySQL require a `LIMIT` whenever `OFFSET` is present, so an
+    /// offset without a limit emits the dialect's "no limit" sentinel.
+    pub fn limit_offset(self, limit: Option<u64>, offset: Option<u64>) -> String {
+        let mut sql = String::new();
+        match (limit, offset) {
+            (Some(limit), _) => sql.push_str(&format!(" LIMIT {limit}")),
+            (None, Some(_)) => match self {
+                Dialect::Postgres => {}
+                Dialect::Sqlite => sql.push_str(" LIMIT -1"),
+                Dialect::Mysql => sql.push_str(" LIMIT 18446744073709551615"),
+            },
+            (None, None) => {}
+        }
+        if let Some(offset) = offset {
+            sql.push_str(&format!(" OFFSET {offset}"));
+        }
+        sql
+    }
+
+    /// Get the column type name for `ty` in this dialect.
+    pub fn type_name(self, ty: &SqlType) -> String {
+        match (self, ty) {
+            (
+                Dialect::Sqlite,
+                SqlType::TinyInt | SqlType::SmallInt | SqlType::Integer | SqlType::BigInt,
+            ) => "INTEGER".to_string(),
+            (Dialect::Sqlite, SqlType::Numeric { .. } | SqlType::Decimal { .. }) => {
+                "NUMERIC".to_string()
+            }
+            (Dialect::Sqlite, SqlType::Real | SqlType::Double) => "REAL".to_string(),
+            (Dialect::Sqlite, SqlType::Boolean) => "INTEGER".to_string(),
+            (Dialect::Sqlite, t) if t.is_text() => "TEXT".to_string(),
+            (
+                Dialect::Sqlite,
+                SqlType::Binary(_) | SqlType::VarBinary(_) | SqlType::Blob | SqlType::Uuid,
+            ) => "BLOB".to_string(),
+            (Dialect::Sqlite, SqlType::Json | SqlType::JsonB | SqlType::Array(_)) => {
+                "TEXT".to_string()
+            }
+            (Dialect::Sqlite, t) if t.is_temporal() => "TEXT".to_string(),
+            (Dialect::Postgres, SqlType::TinyInt) => "SMALLINT".to_string(),
+            (Dialect::Postgres, SqlType::DateTime) => "TIMESTAMP".to_string(),
+            (Dialect::Postgres, SqlType::Blob | SqlType::Binary(_) | SqlType::VarBinary(_)) => {
+                "BYTEA".to_string()
+            }
+            (Dialect::Mysql, SqlType::Double) => "DOUBLE".to_string(),
+            (Dialect::Mysql, SqlType::Boolean) => "TINYINT(1)".to_string(),
+            (Dialect::Mysql, SqlType::TimestampTz) => "TIMESTAMP".to_string(),
+            (Dialect::Mysql, SqlType::Uuid) => "CHAR(36)".to_string(),
+            (Dialect::Mysql, SqlType::JsonB) => "JSON".to_string(),
+            (Dialect::Mysql, SqlType::CiText) => "TEXT".to_string(),
+            (Dialect::Mysql, SqlType::Array(_)) => "JSON".to_string(),
+            (_, t) => t.sql_name(),
+        }
+    }
+
+    /// Quote an identifier for this dialect.
+    ///
+    /// Properly escapes embedded quote characters by doubling them:
+    /// - For Postgres/SQLite: `"` becomes `""`
+    /// - For MySQL: `` ` `` becomes ``` `` ```
+    pub fn quote_identifier(self, name: &str) -> String {
+        match self {
+            Dialect::Postgres | Dialect::Sqlite => {
+                let escaped = name.replace('"', "\"\"");
+                format!("\"{escaped}\"")
+            }
+            Dialect::Mysql => {
+                let escaped = name.replace('`', "``");
+                format!("`{escaped}`")
+            }
+        }
+    }
+}
+
+impl SqlDialect for Dialect {
+    fn name(&self) -> &str {
+        Dialect::name(*self)
+    }
+
+    fn base_dialect(&self) -> Dialect {
+        *self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Custom;
+
+    impl SqlDialect for Custom {
+        fn name(&self) -> &'static str {
+            "custom"
+        }
+
+        fn base_dialect(&self) -> Dialect {
+            Dialect::Sqlite
+        }
+
+        fn placeholder(&self, index: usize) -> String {
+            format!(":p{index}")
+        }
+
+        fn supports_returning(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn test_builtin_as_dyn_roundtrip() {
+        for d in [Dialect::Postgres, Dialect::Sqlite, Dialect::Mysql] {
+            let dyn_d = d.as_dyn();
+            assert_eq!(dyn_d.base_dialect(), d);
+            assert_eq!(dyn_d.name(), d.name());
+            assert_eq!(dyn_d.placeholder(3), d.placeholder(3));
+        }
+    }
+
+    #[test]
+    fn test_custom_dialect_overrides_and_defaults() {
+        let d: &dyn SqlDialect = &Custom;
+        assert_eq!(d.placeholder(1), ":p1");
+        assert!(!d.supports_returning());
+        // Falls back to the SQLite family.
+        assert_eq!(d.quote_identifier("a\"b"), "\"a\"\"b\"");
+        assert_eq!(d.upsert_syntax(), UpsertSyntax::OnConflict);
+        assert_eq!(d.type_name(&SqlType::Boolean), "INTEGER");
+    }
+
+    #[test]
+    fn test_limit_offset() {
+        assert_eq!(
+            Dialect::Postgres.limit_offset(Some(10), Some(5)),
+            " LIMIT 10 OFFSET 5"
+        );
+        assert_eq!(Dialect::Postgres.limit_offset(None, Some(5)), " OFFSET 5");
+        assert_eq!(
+            Dialect::Sqlite.limit_offset(None, Some(5)),
+            " LIMIT -1 OFFSET 5"
+        );
+        assert_eq!(Dialect::Mysql.limit_offset(None, None), "");
+    }
+
+    #[test]
+    fn test_upsert_syntax() {
+        assert_eq!(
+            Dialect::Mysql.upsert_syntax(),
+            UpsertSyntax::OnDuplicateKeyUpdate
+        );
+        assert_eq!(Dialect::Postgres.upsert_syntax(), UpsertSyntax::OnConflict);
+    }
+
+    #[test]
+    fn test_type_names() {
+        assert_eq!(Dialect::Postgres.type_name(&SqlType::Blob), "BYTEA");
+        assert_eq!(Dialect::Mysql.type_name(&SqlType::Boolean), "TINYINT(1)");
+        assert_eq!(Dialect::Sqlite.type_name(&SqlType::VarChar(10)), "TEXT");
+        assert_eq!(Dialect::Postgres.type_name(&SqlType::Text), "TEXT");
+        assert_eq!(Dialect::Postgres.type_name(&SqlType::CiText), "CITEXT");
+        assert_eq!(Dialect::Mysql.type_name(&SqlType::CiText), "TEXT");
+        assert_eq!(Dialect::Sqlite.type_name(&SqlType::CiText), "TEXT");
+    }
+}
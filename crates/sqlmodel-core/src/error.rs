@@ -9,8 +9,54 @@ pub enum Error {
     Connection(ConnectionError),
     /// Query execution errors
     Query(QueryError),
+    /// Unique or primary key constraint violated
+    UniqueViolation {
+        /// Name of the violated constraint or index, if reported
+        constraint: Option<String>,
+        /// Columns covered by the constraint, if reported
+        columns: Vec<String>,
+        /// The underlying database error
+        query: QueryError,
+    },
+    /// Foreign key constraint violated
+    ForeignKeyViolation {
+        /// Name of the violated constraint, if reported
+        constraint: Option<String>,
+        /// Referencing columns, if reported
+        columns: Vec<String>,
+        /// The underlying database error
+        query: QueryError,
+    },
+    /// CHECK constraint violated
+    CheckViolation {
+        /// Name of the violated constraint, if reported
+        constraint: Option<String>,
+        /// Columns involved, if reported
+        columns: Vec<String>,
+        /// The underlying database error
+        query: QueryError,
+    },
+    /// NOT NULL constraint violated
+    NotNullViolation {
+        /// Name of the violated constraint, if reported
+        constraint: Option<String>,
+        /// The column that received NULL, if reported
+        columns: Vec<String>,
+        /// The underlying database error
+        query: QueryError,
+    },
     /// Type conversion errors
     Type(TypeError),
+    /// A bound parameter does not match the type the statement expects,
+    /// detected before the statement is sent
+    ParamTypeMismatch {
+        /// One-based parameter position (`$1` is 1)
+        index: usize,
+        /// The parameter type the server reported, e.g. `int4`
+        expected: String,
+        /// The type of the bound value, e.g. `TEXT`
+        got: String,
+    },
     /// Transaction errors
     Transaction(TransactionError),
     /// Protocol errors (wire-level)
@@ -23,12 +69,23 @@ pub enum Error {
     Config(ConfigError),
     /// Validation errors
     Validation(ValidationError),
+    /// A session object failed validation before being written
+    InvalidObject {
+        /// Table of the offending object
+        table: String,
+        /// Its primary key values (NULL while an auto-generated key is unassigned)
+        primary_key: Vec<crate::Value>,
+        /// The failed rules
+        errors: ValidationError,
+    },
     /// I/O errors
     Io(std::io::Error),
     /// Operation timed out
     Timeout,
     /// Operation was cancelled via asupersync
     Cancelled,
+    /// Shed by admission control: the concurrency limit and its queue are full
+    Overloaded,
     /// Serialization/deserialization errors
     Serde(String),
     /// Custom error with message
@@ -167,6 +224,9 @@ pub enum PoolErrorKind {
     /// This indicates a serious internal error. The pool may still be usable
     /// for read-only operations, but mutation operations will fail.
     Poisoned,
+    /// The acquiring task already holds every connection of the exhausted
+    /// pool, so waiting could only end in a timeout
+    ReentrantAcquire,
 }
 
 #[derive(Debug)]
@@ -231,6 +291,8 @@ pub enum ValidationErrorKind {
     Pattern,
     /// Required field is missing/null
     Required,
+    /// Field is missing while the field it depends on is set
+    RequiredIf,
     /// Custom validation failed
     Custom,
     /// Model-level validation failed
@@ -385,6 +447,17 @@ impl ValidationError {
         );
     }
 
+    /// Add a conditional required error.
+    ///
+    /// Used when `field` is missing although `other` is set.
+    pub fn add_required_if(&mut self, field: impl Into<String>, other: &str) {
+        self.add(
+            field,
+            ValidationErrorKind::RequiredIf,
+            format!("is required when '{other}' is set"),
+        );
+    }
+
     /// Add a custom validation error.
     pub fn add_custom(&mut self, field: impl Into<String>, message: impl Into<String>) {
         self.add(field, ValidationErrorKind::Custom, message);
@@ -407,6 +480,11 @@ impl ValidationError {
         );
     }
 
+    /// Append every error from `other`.
+    pub fn merge(&mut self, other: ValidationError) {
+        self.errors.extend(other.errors);
+    }
+
     /// Convert to Result, returning Ok(()) if no errors, Err(self) otherwise.
     pub fn into_result(self) -> std::result::Result<(), Self> {
         if self.is_empty() { Ok(()) } else { Err(self) }
@@ -419,6 +497,93 @@ impl Default for ValidationError {
     }
 }
 
+/// Validation failures grouped by field path.
+///
+/// Paths are field names, `"__model__"` for model-level errors, or dotted
+/// paths (`"address.city"`) for errors nested with [`ValidationErrors::nest`].
+/// Returned by the async `validate_async()` generated by `#[derive(Validate)]`.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationErrors {
+    by_path: std::collections::BTreeMap<String, Vec<FieldValidationError>>,
+}
+
+impl ValidationErrors {
+    /// Create an empty set of errors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check if there are any validation errors.
+    pub fn is_empty(&self) -> bool {
+        self.by_path.is_empty()
+    }
+
+    /// Total number of errors across all paths.
+    pub fn len(&self) -> usize {
+        self.by_path.values().map(Vec::len).sum()
+    }
+
+    /// Record an error under its field path.
+    pub fn push(&mut self, error: FieldValidationError) {
+        self.by_path
+            .entry(error.field.clone())
+            .or_default()
+            .push(error);
+    }
+
+    /// Errors recorded for `path` (empty if none).
+    pub fn get(&self, path: &str) -> &[FieldValidationError] {
+        self.by_path.get(path).map_or(&[][..], Vec::as_slice)
+    }
+
+    /// Paths with at least one error, sorted.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.by_path.keys().map(String::as_str)
+    }
+
+    /// Iterate `(path, errors)` pairs, sorted by path.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[FieldValidationError])> {
+        self.by_path
+            .iter()
+            .map(|(path, errors)| (path.as_str(), errors.as_slice()))
+    }
+
+    /// Merge a nested value's errors under `prefix`, e.g. `city` becomes
+    /// `address.city`.
+    pub fn nest(&mut self, prefix: &str, other: impl Into<ValidationErrors>) {
+        for (path, errors) in other.into().by_path {
+            let path = format!("{prefix}.{path}");
+            for mut error in errors {
+                error.field.clone_from(&path);
+                self.push(error);
+            }
+        }
+    }
+
+    /// Convert to Result, returning Ok(()) if no errors, Err(self) otherwise.
+    pub fn into_result(self) -> std::result::Result<(), Self> {
+        if self.is_empty() { Ok(()) } else { Err(self) }
+    }
+}
+
+impl From<ValidationError> for ValidationErrors {
+    fn from(err: ValidationError) -> Self {
+        let mut errors = Self::new();
+        for error in err.errors {
+            errors.push(error);
+        }
+        errors
+    }
+}
+
+impl From<ValidationErrors> for ValidationError {
+    fn from(errors: ValidationErrors) -> Self {
+        Self {
+            errors: errors.by_path.into_values().flatten().collect(),
+        }
+    }
+}
+
 impl Error {
     /// Is this a retryable error (deadlock, serialization, pool exhausted, timeouts)?
     pub fn is_retryable(&self) -> bool {
@@ -459,35 +624,298 @@ impl Error {
         }
     }
 
-    /// Get SQLSTATE if available (e.g., "23505" for unique violation)
-    pub fn sqlstate(&self) -> Option<&str> {
+    /// Does this error suggest the server failed over: a write rejected by a
+    /// read-only server (SQLSTATE `25006`), an administrator shutdown
+    /// (`57P01`), or a dropped connection?
+    pub fn is_failover(&self) -> bool {
+        match self {
+            Error::Query(q) => matches!(q.sqlstate.as_deref(), Some("25006" | "57P01")),
+            Error::Connection(c) => c.kind == ConnectionErrorKind::Disconnected,
+            _ => false,
+        }
+    }
+
+    /// Is this a unique constraint violation (any backend)?
+    pub fn is_unique_violation(&self) -> bool {
         match self {
-            Error::Query(q) => q.sqlstate.as_deref(),
+            Error::UniqueViolation { .. } => true,
+            Error::Query(q) => q.is_unique_violation(),
+            _ => false,
+        }
+    }
+
+    /// Is this any integrity constraint violation (unique, foreign key, check, not-null)?
+    pub fn is_constraint_violation(&self) -> bool {
+        match self {
+            Error::UniqueViolation { .. }
+            | Error::ForeignKeyViolation { .. }
+            | Error::CheckViolation { .. }
+            | Error::NotNullViolation { .. } => true,
+            Error::Query(q) => q.kind == QueryErrorKind::Constraint,
+            _ => false,
+        }
+    }
+
+    /// Build an error from a driver-level query error, mapping integrity
+    /// constraint failures to the structured violation variants.
+    ///
+    /// Drivers call this instead of wrapping in [`Error::Query`] directly so
+    /// applications can match on [`Error::UniqueViolation`] and friends rather
+    /// than inspecting backend-specific codes or message text.
+    pub fn from_query_error(query: QueryError) -> Error {
+        let Some(kind) = query.violation_kind() else {
+            return Error::Query(query);
+        };
+        let constraint = query.constraint_name().map(str::to_string);
+        let columns = query.constraint_columns();
+        match kind {
+            ViolationKind::Unique => Error::UniqueViolation {
+                constraint,
+                columns,
+                query,
+            },
+            ViolationKind::ForeignKey => Error::ForeignKeyViolation {
+                constraint,
+                columns,
+                query,
+            },
+            ViolationKind::Check => Error::CheckViolation {
+                constraint,
+                columns,
+                query,
+            },
+            ViolationKind::NotNull => Error::NotNullViolation {
+                constraint,
+                columns,
+                query,
+            },
+        }
+    }
+
+    /// HTTP status code a web handler should answer with for this error.
+    ///
+    /// Unique and foreign key violations map to `409 Conflict`, check/not-null
+    /// violations and validation failures to `422 Unprocessable Entity`,
+    /// transient failures ([`Error::is_retryable`], cancellation, load
+    /// shedding) to `503 Service Unavailable`, timeouts to `504 Gateway
+    /// Timeout`, and everything else to `500 Internal Server Error`. Framework
+    /// adapters turn the code into their own response type.
+    pub fn http_status(&self) -> u16 {
+        match self {
+            Error::UniqueViolation { .. } | Error::ForeignKeyViolation { .. } => 409,
+            Error::CheckViolation { .. }
+            | Error::NotNullViolation { .. }
+            | Error::Validation(_)
+            | Error::InvalidObject { .. } => 422,
+            Error::Query(q) if q.kind == QueryErrorKind::Constraint => 409,
+            Error::Query(q) if q.kind == QueryErrorKind::Timeout => 504,
+            Error::Timeout => 504,
+            Error::Cancelled | Error::Overloaded => 503,
+            _ if self.is_retryable() => 503,
+            _ => 500,
+        }
+    }
+
+    /// The underlying query error, for [`Error::Query`] and the violation variants.
+    pub fn query_error(&self) -> Option<&QueryError> {
+        match self {
+            Error::Query(q)
+            | Error::UniqueViolation { query: q, .. }
+            | Error::ForeignKeyViolation { query: q, .. }
+            | Error::CheckViolation { query: q, .. }
+            | Error::NotNullViolation { query: q, .. } => Some(q),
             _ => None,
         }
     }
 
-    /// Get the SQL that caused this error, if available
-    pub fn sql(&self) -> Option<&str> {
+    /// Mutable access to the underlying query error, e.g. to extend its detail.
+    pub fn query_error_mut(&mut self) -> Option<&mut QueryError> {
+        match self {
+            Error::Query(q)
+            | Error::UniqueViolation { query: q, .. }
+            | Error::ForeignKeyViolation { query: q, .. }
+            | Error::CheckViolation { query: q, .. }
+            | Error::NotNullViolation { query: q, .. } => Some(q),
+            _ => None,
+        }
+    }
+
+    /// Name of the violated constraint, if the backend reported one.
+    ///
+    /// See [`QueryError::constraint_name`].
+    pub fn constraint_name(&self) -> Option<&str> {
         match self {
-            Error::Query(q) => q.sql.as_deref(),
+            Error::UniqueViolation { constraint, .. }
+            | Error::ForeignKeyViolation { constraint, .. }
+            | Error::CheckViolation { constraint, .. }
+            | Error::NotNullViolation { constraint, .. } => constraint.as_deref(),
+            Error::Query(q) => q.constraint_name(),
             _ => None,
         }
     }
+
+    /// Get SQLSTATE if available (e.g., "23505" for unique violation)
+    pub fn sqlstate(&self) -> Option<&str> {
+        self.query_error().and_then(|q| q.sqlstate.as_deref())
+    }
+
+    /// Get the SQL that caused this error, if available
+    pub fn sql(&self) -> Option<&str> {
+        self.query_error().and_then(|q| q.sql.as_deref())
+    }
+}
+
+/// Integrity constraint categories recognised by [`Error::from_query_error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ViolationKind {
+    Unique,
+    ForeignKey,
+    Check,
+    NotNull,
 }
 
 impl QueryError {
     /// Is this a unique constraint violation?
+    ///
+    /// Recognizes PostgreSQL's SQLSTATE 23505 as well as the MySQL
+    /// (`Duplicate entry`) and SQLite (`UNIQUE constraint failed`) messages,
+    /// which only report a generic integrity-constraint state.
     pub fn is_unique_violation(&self) -> bool {
-        self.sqlstate.as_deref() == Some("23505")
+        if self.sqlstate.as_deref() == Some("23505") {
+            return true;
+        }
+        self.kind == QueryErrorKind::Constraint
+            && (self.message.contains("Duplicate entry")
+                || self.message.contains("UNIQUE constraint failed"))
     }
 
     /// Is this a foreign key violation?
     pub fn is_foreign_key_violation(&self) -> bool {
-        self.sqlstate.as_deref() == Some("23503")
+        self.violation_kind() == Some(ViolationKind::ForeignKey)
+    }
+
+    /// Classify an integrity constraint failure.
+    ///
+    /// PostgreSQL reports a precise SQLSTATE; MySQL and SQLite share a generic
+    /// state (or none), so their messages are inspected instead.
+    fn violation_kind(&self) -> Option<ViolationKind> {
+        match self.sqlstate.as_deref() {
+            Some("23505") => return Some(ViolationKind::Unique),
+            Some("23503") => return Some(ViolationKind::ForeignKey),
+            Some("23514") => return Some(ViolationKind::Check),
+            Some("23502") => return Some(ViolationKind::NotNull),
+            _ => {}
+        }
+        if self.kind != QueryErrorKind::Constraint {
+            return None;
+        }
+        let message = self.message.as_str();
+        if self.is_unique_violation() {
+            Some(ViolationKind::Unique)
+        } else if message.contains("FOREIGN KEY constraint failed")
+            || message.contains("a foreign key constraint fails")
+        {
+            Some(ViolationKind::ForeignKey)
+        } else if message.contains("CHECK constraint failed")
+            || message.starts_with("Check constraint")
+        {
+            Some(ViolationKind::Check)
+        } else if message.contains("NOT NULL constraint failed")
+            || message.contains("cannot be null")
+        {
+            Some(ViolationKind::NotNull)
+        } else {
+            None
+        }
+    }
+
+    /// Name of the violated constraint, extracted from the server message.
+    ///
+    /// Handles PostgreSQL (`violates unique constraint "name"`), MySQL
+    /// (`for key 'table.name'`, `` CONSTRAINT `name` ``, `Check constraint 'name'`)
+    /// and SQLite CHECK failures. SQLite only reports column names for other
+    /// constraints, so this returns `None` there.
+    pub fn constraint_name(&self) -> Option<&str> {
+        let message = self.message.as_str();
+        if let Some(rest) = after(message, " constraint \"") {
+            return rest.find('"').map(|end| &rest[..end]);
+        }
+        if let Some(rest) = after(message, " for key '") {
+            let key = &rest[..rest.find('\'')?];
+            // MySQL 8 prefixes the key with the table name.
+            return Some(key.rsplit_once('.').map_or(key, |(_, name)| name));
+        }
+        if let Some(rest) = after(message, "CONSTRAINT `") {
+            return rest.find('`').map(|end| &rest[..end]);
+        }
+        if let Some(rest) = after(message, "Check constraint '") {
+            return rest.find('\'').map(|end| &rest[..end]);
+        }
+        if let Some(rest) = after(message, "CHECK constraint failed: ") {
+            return Some(rest.trim());
+        }
+        None
+    }
+
+    /// Columns involved in a constraint violation, extracted from the server
+    /// message or detail. Empty when the backend does not report them.
+    pub fn constraint_columns(&self) -> Vec<String> {
+        let split = |list: &str| -> Vec<String> {
+            list.split(',')
+                .map(|c| {
+                    let c = c.trim().trim_matches(|ch| ch == '`' || ch == '"');
+                    // SQLite reports `table.column`.
+                    c.rsplit_once('.').map_or(c, |(_, col)| col).to_string()
+                })
+                .filter(|c| !c.is_empty())
+                .collect()
+        };
+
+        // PostgreSQL: DETAIL "Key (a, b)=(1, x) already exists."
+        if let Some(rest) = self.detail.as_deref().and_then(|d| after(d, "Key ("))
+            && let Some(end) = rest.find(")=(")
+        {
+            return split(&rest[..end]);
+        }
+        let message = self.message.as_str();
+        // PostgreSQL: null value in column "email" ...
+        if let Some(rest) = after(message, "null value in column \"") {
+            return rest
+                .find('"')
+                .map(|end| split(&rest[..end]))
+                .unwrap_or_default();
+        }
+        // MySQL: Column 'email' cannot be null
+        if let Some(rest) = after(message, "Column '") {
+            return rest
+                .find('\'')
+                .map(|end| split(&rest[..end]))
+                .unwrap_or_default();
+        }
+        // MySQL: ... FOREIGN KEY (`team_id`) REFERENCES ...
+        if let Some(rest) = after(message, "FOREIGN KEY (") {
+            return rest
+                .find(')')
+                .map(|end| split(&rest[..end]))
+                .unwrap_or_default();
+        }
+        // SQLite: UNIQUE constraint failed: t.a, t.b / NOT NULL constraint failed: t.a
+        for prefix in ["UNIQUE constraint failed: ", "NOT NULL constraint failed: "] {
+            if let Some(rest) = after(message, prefix) {
+                return split(rest);
+            }
+        }
+        Vec::new()
     }
 }
 
+/// The remainder of `haystack` after the first occurrence of `needle`.
+fn after<'a>(haystack: &'a str, needle: &str) -> Option<&'a str> {
+    haystack
+        .find(needle)
+        .map(|idx| &haystack[idx + needle.len()..])
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -499,6 +927,18 @@ impl fmt::Display for Error {
                     write!(f, "Query error: {}", e.message)
                 }
             }
+            Error::UniqueViolation { query, .. } => {
+                write!(f, "Unique constraint violation: {}", query.message)
+            }
+            Error::ForeignKeyViolation { query, .. } => {
+                write!(f, "Foreign key constraint violation: {}", query.message)
+            }
+            Error::CheckViolation { query, .. } => {
+                write!(f, "Check constraint violation: {}", query.message)
+            }
+            Error::NotNullViolation { query, .. } => {
+                write!(f, "Not-null constraint violation: {}", query.message)
+            }
             Error::Type(e) => {
                 if let Some(col) = &e.column {
                     write!(
@@ -510,15 +950,34 @@ impl fmt::Display for Error {
                     write!(f, "Type error: expected {}, found {}", e.expected, e.actual)
                 }
             }
+            Error::ParamTypeMismatch {
+                index,
+                expected,
+                got,
+            } => write!(
+                f,
+                "Parameter ${} expects {}, but a {} value was bound",
+                index, expected, got
+            ),
             Error::Transaction(e) => write!(f, "Transaction error: {}", e.message),
             Error::Protocol(e) => write!(f, "Protocol error: {}", e.message),
             Error::Pool(e) => write!(f, "Pool error: {}", e.message),
             Error::Schema(e) => write!(f, "Schema error: {}", e.message),
             Error::Config(e) => write!(f, "Configuration error: {}", e.message),
             Error::Validation(e) => write!(f, "Validation error: {}", e),
+            Error::InvalidObject {
+                table,
+                primary_key,
+                errors,
+            } => write!(
+                f,
+                "Validation error in {} {:?}: {}",
+                table, primary_key, errors
+            ),
             Error::Io(e) => write!(f, "I/O error: {}", e),
             Error::Timeout => write!(f, "Operation timed out"),
             Error::Cancelled => write!(f, "Operation cancelled"),
+            Error::Overloaded => write!(f, "Overloaded: query limit and queue are full"),
             Error::Serde(msg) => write!(f, "Serialization error: {}", msg),
             Error::Custom(msg) => write!(f, "{}", msg),
         }
@@ -532,7 +991,11 @@ impl std::error::Error for Error {
                 .source
                 .as_deref()
                 .map(|err| err as &(dyn std::error::Error + 'static)),
-            Error::Query(e) => e
+            Error::Query(e)
+            | Error::UniqueViolation { query: e, .. }
+            | Error::ForeignKeyViolation { query: e, .. }
+            | Error::CheckViolation { query: e, .. }
+            | Error::NotNullViolation { query: e, .. } => e
                 .source
                 .as_deref()
                 .map(|err| err as &(dyn std::error::Error + 'static)),
@@ -637,6 +1100,23 @@ impl fmt::Display for ValidationError {
 
 impl std::error::Error for ValidationError {}
 
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "validation passed");
+        }
+        writeln!(f, "validation errors:")?;
+        for (path, errors) in self.iter() {
+            for err in errors {
+                writeln!(f, "  - {path}: {}", err.message)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Self {
         Error::Io(err)
@@ -697,6 +1177,12 @@ impl From<ValidationError> for Error {
     }
 }
 
+impl From<ValidationErrors> for Error {
+    fn from(errors: ValidationErrors) -> Self {
+        Error::Validation(errors.into())
+    }
+}
+
 /// Result type alias for SQLModel operations.
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -725,6 +1211,144 @@ mod tests {
         assert_eq!(err.sql(), Some("SELECT 1"));
     }
 
+    #[test]
+    fn unique_violation_across_backends() {
+        let make = |sqlstate: &str, message: &str| QueryError {
+            kind: QueryErrorKind::Constraint,
+            sql: None,
+            sqlstate: Some(sqlstate.to_string()),
+            message: message.to_string(),
+            detail: None,
+            hint: None,
+            position: None,
+            source: None,
+        };
+
+        let mysql = make("23000", "Duplicate entry 'a@b.c' for key 'users.email'");
+        let sqlite = make("", "UNIQUE constraint failed: users.email");
+        let fk = make("23000", "Cannot add or update a child row");
+
+        assert!(mysql.is_unique_violation());
+        assert!(sqlite.is_unique_violation());
+        assert!(!fk.is_unique_violation());
+        assert!(Error::Query(mysql).is_unique_violation());
+    }
+
+    #[test]
+    fn structured_constraint_violations() {
+        let make = |sqlstate: Option<&str>, message: &str, detail: Option<&str>| QueryError {
+            kind: QueryErrorKind::Constraint,
+            sql: Some("INSERT ...".to_string()),
+            sqlstate: sqlstate.map(str::to_string),
+            message: message.to_string(),
+            detail: detail.map(str::to_string),
+            hint: None,
+            position: None,
+            source: None,
+        };
+
+        // PostgreSQL
+        let err = Error::from_query_error(make(
+            Some("23505"),
+            "duplicate key value violates unique constraint \"uk_members_org_id_email\"",
+            Some("Key (org_id, email)=(1, a@b.c) already exists."),
+        ));
+        assert!(matches!(
+            &err,
+            Error::UniqueViolation { constraint: Some(c), columns, .. }
+                if c == "uk_members_org_id_email" && columns == &["org_id", "email"]
+        ));
+        assert!(err.is_unique_violation());
+        assert_eq!(err.sqlstate(), Some("23505"));
+        assert_eq!(err.sql(), Some("INSERT ..."));
+
+        let err = Error::from_query_error(make(
+            Some("23502"),
+            "null value in column \"email\" of relation \"members\" violates not-null constraint",
+            None,
+        ));
+        assert!(matches!(&err, Error::NotNullViolation { columns, .. } if columns == &["email"]));
+
+        let err = Error::from_query_error(make(
+            Some("23514"),
+            "new row for relation \"members\" violates check constraint \"ck_age\"",
+            None,
+        ));
+        assert!(matches!(&err, Error::CheckViolation { constraint: Some(c), .. } if c == "ck_age"));
+
+        // MySQL
+        let err = Error::from_query_error(make(
+            Some("23000"),
+            "Cannot add or update a child row: a foreign key constraint fails (`db`.`heroes`, \
+             CONSTRAINT `fk_heroes_team_id` FOREIGN KEY (`team_id`) REFERENCES `teams` (`id`))",
+            None,
+        ));
+        assert!(matches!(
+            &err,
+            Error::ForeignKeyViolation { constraint: Some(c), columns, .. }
+                if c == "fk_heroes_team_id" && columns == &["team_id"]
+        ));
+        let err =
+            Error::from_query_error(make(Some("23000"), "Column 'email' cannot be null", None));
+        assert!(matches!(&err, Error::NotNullViolation { columns, .. } if columns == &["email"]));
+
+        // SQLite
+        let err = Error::from_query_error(make(
+            None,
+            "UNIQUE constraint failed: members.org_id, members.email",
+            None,
+        ));
+        assert!(matches!(
+            &err,
+            Error::UniqueViolation { constraint: None, columns, .. }
+                if columns == &["org_id", "email"]
+        ));
+        let err = Error::from_query_error(make(None, "CHECK constraint failed: ck_age", None));
+        assert!(matches!(&err, Error::CheckViolation { constraint: Some(c), .. } if c == "ck_age"));
+        let err = Error::from_query_error(make(None, "FOREIGN KEY constraint failed", None));
+        assert!(matches!(
+            &err,
+            Error::ForeignKeyViolation {
+                constraint: None,
+                ..
+            }
+        ));
+        assert!(err.is_constraint_violation());
+
+        // Non-constraint errors stay generic.
+        let mut syntax = make(Some("42601"), "syntax error", None);
+        syntax.kind = QueryErrorKind::Syntax;
+        assert!(matches!(Error::from_query_error(syntax), Error::Query(_)));
+    }
+
+    #[test]
+    fn constraint_name_from_backend_messages() {
+        let make = |message: &str| QueryError {
+            kind: QueryErrorKind::Constraint,
+            sql: None,
+            sqlstate: None,
+            message: message.to_string(),
+            detail: None,
+            hint: None,
+            position: None,
+            source: None,
+        };
+
+        let pg = make("duplicate key value violates unique constraint \"uk_members_org_id_email\"");
+        let mysql = make("Duplicate entry '1-a@b.c' for key 'members.uk_members_org_id_email'");
+        let mysql57 = make("Duplicate entry '1-a@b.c' for key 'uk_members_org_id_email'");
+        let sqlite = make("UNIQUE constraint failed: members.org_id, members.email");
+
+        assert_eq!(pg.constraint_name(), Some("uk_members_org_id_email"));
+        assert_eq!(mysql.constraint_name(), Some("uk_members_org_id_email"));
+        assert_eq!(mysql57.constraint_name(), Some("uk_members_org_id_email"));
+        assert_eq!(sqlite.constraint_name(), None);
+        assert_eq!(
+            Error::Query(pg).constraint_name(),
+            Some("uk_members_org_id_email")
+        );
+    }
+
     #[test]
     fn retryable_and_connection_flags() {
         let retryable_query = Error::Query(QueryError {
@@ -760,6 +1384,8 @@ mod tests {
             source: None,
         });
         assert!(conn_error.is_connection_error());
+        assert!(conn_error.is_failover());
+        assert!(!retryable_query.is_failover());
     }
 
     #[test]
@@ -803,4 +1429,118 @@ mod tests {
         assert!(exhausted.is_retryable());
         assert!(timeout.is_retryable());
     }
+
+    #[test]
+    fn http_status_mapping() {
+        let query = |kind: QueryErrorKind| QueryError {
+            kind,
+            sql: None,
+            sqlstate: None,
+            message: "boom".to_string(),
+            detail: None,
+            hint: None,
+            position: None,
+            source: None,
+        };
+
+        let unique = Error::UniqueViolation {
+            constraint: None,
+            columns: vec!["email".to_string()],
+            query: query(QueryErrorKind::Constraint),
+        };
+        let not_null = Error::NotNullViolation {
+            constraint: None,
+            columns: vec![],
+            query: query(QueryErrorKind::Constraint),
+        };
+        let pool_timeout = Error::Pool(PoolError {
+            kind: PoolErrorKind::Timeout,
+            message: "timed out".to_string(),
+            source: None,
+        });
+
+        assert_eq!(unique.http_status(), 409);
+        assert_eq!(
+            Error::Query(query(QueryErrorKind::Constraint)).http_status(),
+            409
+        );
+        assert_eq!(not_null.http_status(), 422);
+        assert_eq!(Error::Validation(ValidationError::new()).http_status(), 422);
+        assert_eq!(
+            Error::Query(query(QueryErrorKind::Deadlock)).http_status(),
+            503
+        );
+        assert_eq!(pool_timeout.http_status(), 503);
+        assert_eq!(Error::Overloaded.http_status(), 503);
+        assert!(!Error::Overloaded.is_retryable());
+        assert_eq!(
+            Error::Query(query(QueryErrorKind::Timeout)).http_status(),
+            504
+        );
+        assert_eq!(
+            Error::Query(query(QueryErrorKind::Syntax)).http_status(),
+            500
+        );
+        assert_eq!(Error::Custom("x".to_string()).http_status(), 500);
+    }
+
+    #[test]
+    fn param_type_mismatch_display() {
+        let err = Error::ParamTypeMismatch {
+            index: 1,
+            expected: "int8".to_string(),
+            got: "TEXT".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Parameter $1 expects int8, but a TEXT value was bound"
+        );
+        assert_eq!(err.http_status(), 500);
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn validation_errors_group_by_path() {
+        let mut flat = ValidationError::new();
+        flat.add_required_if("end_date", "start_date");
+        flat.add_min_length("name", 1, 0);
+        flat.add_custom("end_date", "must be after start_date");
+
+        let mut errors = ValidationErrors::from(flat);
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors.paths().collect::<Vec<_>>(), ["end_date", "name"]);
+        assert_eq!(
+            errors.get("end_date")[0].kind,
+            ValidationErrorKind::RequiredIf
+        );
+        assert!(errors.get("missing").is_empty());
+
+        let mut address = ValidationError::new();
+        address.add_required("city");
+        errors.nest("address", address);
+        assert_eq!(errors.get("address.city")[0].field, "address.city");
+
+        let err = Error::from(errors);
+        let Error::Validation(flat) = err else {
+            panic!("expected a validation error");
+        };
+        assert_eq!(flat.errors.len(), 4);
+        assert_eq!(flat.errors[0].field, "address.city");
+    }
+
+    #[test]
+    fn invalid_object_display() {
+        let mut errors = ValidationError::new();
+        errors.add_required("name");
+        let err = Error::InvalidObject {
+            table: "heroes".to_string(),
+            primary_key: vec![crate::Value::BigInt(7)],
+            errors,
+        };
+        assert_eq!(
+            err.to_string(),
+            "Validation error in heroes [BigInt(7)]: validation error on 'name': is required"
+        );
+        assert_eq!(err.http_status(), 422);
+    }
 }
@@ -1,6 +1,7 @@
 //! Field and column definitions.
 
 use crate::types::SqlType;
+use crate::value::Value;
 
 /// Referential action for foreign key constraints (ON DELETE / ON UPDATE).
 ///
@@ -98,9 +99,18 @@ pub struct FieldInfo {
     /// Computed fields are excluded from database operations but included
     /// in serialization (model_dump) unless exclude_computed_fields is set.
     pub computed: bool,
+    /// Whether this column is deferred (not loaded by default SELECTs).
+    /// Deferred columns are fetched on demand, e.g. via `Session::load_deferred`.
+    pub deferred: bool,
     /// Whether to exclude this field from serialization (model_dump).
     /// When true, the field will never appear in serialized output.
     pub exclude: bool,
+    /// Whether this field is left out of API shapes: generated DTOs and JSON
+    /// Schema. Unlike `exclude`, it is still serialized by model_dump.
+    pub api_exclude: bool,
+    /// Whether this column is stored but never leaves the database layer:
+    /// neither serialized nor part of any API shape (e.g. password hashes).
+    pub db_only: bool,
     /// Schema title for JSON Schema generation.
     /// Used as the "title" property in the generated JSON Schema.
     pub title: Option<&'static str>,
@@ -127,6 +137,12 @@ pub struct FieldInfo {
     /// SQL comment for the column (used in DDL generation).
     /// This maps to the COMMENT ON COLUMN or inline COMMENT clause depending on the database.
     pub column_comment: Option<&'static str>,
+    /// Collation for text columns (e.g. `"und-x-icu"`, `"NOCASE"`, `"utf8mb4_bin"`).
+    /// Emitted as a `COLLATE` clause in the column definition.
+    pub collation: Option<&'static str>,
+    /// Character set for text columns (e.g. `"utf8mb4"`).
+    /// Only MySQL stores a per-column character set; other dialects ignore it.
+    pub charset: Option<&'static str>,
     /// Extra metadata as JSON string (for custom extensions/info).
     /// This can be used to store additional information that doesn't fit in other fields.
     pub column_info: Option<&'static str>,
@@ -184,7 +200,10 @@ impl FieldInfo {
             validation_alias: None,
             serialization_alias: None,
             computed: false,
+            deferred: false,
             exclude: false,
+            api_exclude: false,
+            db_only: false,
             title: None,
             description: None,
             schema_extra: None,
@@ -193,6 +212,8 @@ impl FieldInfo {
             const_field: false,
             column_constraints: &[],
             column_comment: None,
+            collation: None,
+            charset: None,
             column_info: None,
             hybrid_sql: None,
             discriminator: None,
@@ -453,6 +474,19 @@ impl FieldInfo {
         self
     }
 
+    /// Mark this column as deferred (lazy-loaded).
+    ///
+    /// Deferred columns are:
+    /// - Skipped by default SELECTs generated from model metadata
+    /// - Filled with a placeholder and tracked as expired when loading
+    /// - Fetched on demand by primary key (`Session::load_deferred`)
+    ///
+    /// Use this for heavy columns such as blobs or large text bodies.
+    pub const fn deferred(mut self, value: bool) -> Self {
+        self.deferred = value;
+        self
+    }
+
     /// Mark this field as excluded from serialization (model_dump).
     ///
     /// Excluded fields will never appear in serialized output, regardless
@@ -474,6 +508,43 @@ impl FieldInfo {
         self
     }
 
+    /// Leave this field out of API shapes (generated DTOs and JSON Schema)
+    /// while still serializing it with model_dump.
+    pub const fn api_exclude(mut self, value: bool) -> Self {
+        self.api_exclude = value;
+        self
+    }
+
+    /// Mark this column as database-only.
+    ///
+    /// Database-only columns are loaded and saved like any other, but never
+    /// serialized and never part of an API shape.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// #[derive(Model)]
+    /// struct User {
+    ///     id: i64,
+    ///     #[sqlmodel(db_only)]
+    ///     password_hash: String,  // Stored, never exposed
+    /// }
+    /// ```
+    pub const fn db_only(mut self, value: bool) -> Self {
+        self.db_only = value;
+        self
+    }
+
+    /// Whether model_dump output includes this field.
+    pub const fn is_serialized(&self) -> bool {
+        !self.exclude && !self.db_only
+    }
+
+    /// Whether this field appears in API shapes (DTOs and JSON Schema).
+    pub const fn in_api(&self) -> bool {
+        !self.api_exclude && !self.db_only
+    }
+
     /// Set the schema title for JSON Schema generation.
     ///
     /// The title appears as the "title" property in the field's JSON Schema.
@@ -600,6 +671,30 @@ impl FieldInfo {
         self
     }
 
+    /// Set the column collation.
+    pub const fn collation(mut self, collation: &'static str) -> Self {
+        self.collation = Some(collation);
+        self
+    }
+
+    /// Set column collation from optional.
+    pub const fn collation_opt(mut self, collation: Option<&'static str>) -> Self {
+        self.collation = collation;
+        self
+    }
+
+    /// Set the column character set (MySQL).
+    pub const fn charset(mut self, charset: &'static str) -> Self {
+        self.charset = Some(charset);
+        self
+    }
+
+    /// Set column character set from optional.
+    pub const fn charset_opt(mut self, charset: Option<&'static str>) -> Self {
+        self.charset = charset;
+        self
+    }
+
     /// Set extra metadata as JSON string.
     ///
     /// This can be used for custom extensions or information
@@ -685,6 +780,137 @@ impl FieldInfo {
             || self.validation_alias.is_some()
             || self.serialization_alias.is_some()
     }
+
+    /// A neutral stand-in value for this column when it was not loaded.
+    ///
+    /// Used for partial hydration: nullable columns become NULL, enums take their
+    /// first variant, and everything else becomes the zero/empty value of its
+    /// SQL type so `from_row` can succeed.
+    /// The attribute must be treated as expired until it is actually loaded.
+    #[must_use]
+    pub fn placeholder_value(&self) -> Value {
+        if self.nullable {
+            return Value::Null;
+        }
+        match &self.sql_type {
+            SqlType::Boolean => Value::Bool(false),
+            SqlType::TinyInt => Value::TinyInt(0),
+            SqlType::SmallInt => Value::SmallInt(0),
+            SqlType::Integer => Value::Int(0),
+            SqlType::BigInt => Value::BigInt(0),
+            SqlType::Real => Value::Float(0.0),
+            SqlType::Double => Value::Double(0.0),
+            SqlType::Numeric { .. } | SqlType::Decimal { .. } => Value::Decimal("0".to_string()),
+            SqlType::Binary(_) | SqlType::VarBinary(_) | SqlType::Blob => Value::Bytes(Vec::new()),
+            SqlType::Date => Value::Date(0),
+            SqlType::Time => Value::Time(0),
+            SqlType::DateTime | SqlType::Timestamp => Value::Timestamp(0),
+            SqlType::TimestampTz => Value::TimestampTz(0),
+            SqlType::Uuid => Value::Uuid([0; 16]),
+            SqlType::Json | SqlType::JsonB => Value::Json(serde_json::Value::Null),
+            SqlType::Array(_) => Value::Array(Vec::new()),
+            SqlType::Enum(variants) => Value::Text(
+                variants
+                    .first()
+                    .map_or_else(String::new, |v| (*v).to_string()),
+            ),
+            SqlType::Char(_)
+            | SqlType::VarChar(_)
+            | SqlType::Text
+            | SqlType::CiText
+            | SqlType::Custom(_) => Value::Text(String::new()),
+        }
+    }
+}
+
+/// Table-level unique constraint spanning one or more columns.
+///
+/// Declared with `#[sqlmodel(unique_together("org_id", "email"))]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UniqueConstraintDef {
+    /// Explicit constraint name (generated from table and columns when `None`).
+    pub name: Option<&'static str>,
+    /// Column names covered by the constraint, in order.
+    pub columns: &'static [&'static str],
+}
+
+impl UniqueConstraintDef {
+    /// Create a unique constraint over the given columns.
+    pub const fn new(columns: &'static [&'static str]) -> Self {
+        Self {
+            name: None,
+            columns,
+        }
+    }
+
+    /// Set an explicit constraint name.
+    pub const fn name(mut self, name: &'static str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// The constraint name used in DDL (`uk_<table>_<col1>_<col2>` by default).
+    #[must_use]
+    pub fn constraint_name(&self, table: &str) -> String {
+        self.name.map_or_else(
+            || format!("uk_{}_{}", table, self.columns.join("_")),
+            str::to_string,
+        )
+    }
+}
+
+/// Table-level index, possibly composite, unique, or partial.
+///
+/// Declared with `#[sqlmodel(index(columns("a", "b"), unique, where = "..."))]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexDef {
+    /// Explicit index name (generated from table and columns when `None`).
+    pub name: Option<&'static str>,
+    /// Column names covered by the index, in order.
+    pub columns: &'static [&'static str],
+    /// Whether this is a UNIQUE index.
+    pub unique: bool,
+    /// Predicate for a partial index (PostgreSQL and SQLite only).
+    pub where_clause: Option<&'static str>,
+}
+
+impl IndexDef {
+    /// Create a non-unique index over the given columns.
+    pub const fn new(columns: &'static [&'static str]) -> Self {
+        Self {
+            name: None,
+            columns,
+            unique: false,
+            where_clause: None,
+        }
+    }
+
+    /// Set an explicit index name.
+    pub const fn name(mut self, name: &'static str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Set whether the index is unique.
+    pub const fn unique(mut self, value: bool) -> Self {
+        self.unique = value;
+        self
+    }
+
+    /// Set the partial index predicate.
+    pub const fn where_clause(mut self, predicate: &'static str) -> Self {
+        self.where_clause = Some(predicate);
+        self
+    }
+
+    /// The index name used in DDL (`idx_<table>_<col1>_<col2>` by default).
+    #[must_use]
+    pub fn index_name(&self, table: &str) -> String {
+        self.name.map_or_else(
+            || format!("idx_{}_{}", table, self.columns.join("_")),
+            str::to_string,
+        )
+    }
 }
 
 /// A column reference used in queries.
@@ -1264,6 +1490,21 @@ mod tests {
         assert!(!field3.exclude);
     }
 
+    #[test]
+    fn test_field_info_api_exclude_and_db_only() {
+        let plain = FieldInfo::new("name", "name", SqlType::Text);
+        assert!(plain.is_serialized() && plain.in_api());
+
+        let internal = FieldInfo::new("score", "score", SqlType::Integer).api_exclude(true);
+        assert!(internal.is_serialized());
+        assert!(!internal.in_api());
+
+        let hash = FieldInfo::new("password_hash", "password_hash", SqlType::Text).db_only(true);
+        assert!(!hash.is_serialized());
+        assert!(!hash.in_api());
+        assert!(!hash.computed);
+    }
+
     #[test]
     fn test_field_info_exclude_combined_with_other_attrs() {
         // Exclude can be combined with other attributes
@@ -1363,6 +1604,19 @@ mod tests {
         assert_eq!(field2.column_comment, Some("User's display name"));
     }
 
+    #[test]
+    fn test_field_info_collation_and_charset() {
+        let field1 = FieldInfo::new("name", "name", SqlType::Text);
+        assert_eq!(field1.collation, None);
+        assert_eq!(field1.charset, None);
+
+        let field2 = FieldInfo::new("name", "name", SqlType::Text)
+            .collation("utf8mb4_bin")
+            .charset("utf8mb4");
+        assert_eq!(field2.collation, Some("utf8mb4_bin"));
+        assert_eq!(field2.charset, Some("utf8mb4"));
+    }
+
     #[test]
     fn test_field_info_column_info() {
         // Default should be None
@@ -1495,4 +1749,12 @@ mod tests {
         assert_eq!(info.discriminator_column, Some("type"));
         assert!(info.is_base());
     }
+
+    #[test]
+    fn test_placeholder_value_enum_uses_first_variant() {
+        let field = FieldInfo::new("status", "status", SqlType::Enum(vec!["active", "banned"]));
+        assert_eq!(field.placeholder_value(), Value::Text("active".to_string()));
+        let nullable = field.clone().nullable(true);
+        assert_eq!(nullable.placeholder_value(), Value::Null);
+    }
 }
@@ -0,0 +1,637 @@
+//! Statement interceptors.
+//!
+//! A [`StatementInterceptor`] sees every statement (SQL and parameters) just
+//! before it is sent to the database and may rewrite it or reject it. This is
+//! where org-wide policies live: tagging SQL with a trace comment, appending a
+//! mandatory tenant predicate, or refusing a `DELETE` without a `WHERE`.
+//!
+//! Interceptors are registered on a connection pool
+//! (`Pool::with_interceptor`) or wrapped around any single connection with
+//! [`InterceptedConnection`], which is what `Session::with_interceptors` uses.
+//! They run in registration order, each seeing the previous one's output; the
+//! first error aborts the statement without running it.
+//!
+//! Built-in interceptors:
+//!
+//! - [`CommentInjector`] prefixes statements with a `/* ... */` comment
+//! - [`RejectUnfilteredWrites`] refuses `DELETE` (and optionally `UPDATE`)
+//!   statements that have no `WHERE` clause
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use sqlmodel_core::interceptor::{CommentInjector, RejectUnfilteredWrites};
+//!
+//! let pool = Pool::new(config)
+//!     .with_interceptor(Arc::new(CommentInjector::new(|| {
+//!         current_trace_id().map(|id| format!("trace_id={id}"))
+//!     })))
+//!     .with_interceptor(Arc::new(RejectUnfilteredWrites::new()));
+//! ```
+
+use std::borrow::Cow;
+use std::future::Future;
+use std::sync::Arc;
+
+use crate::connection::{Connection, IsolationLevel, PreparedStatement, QueryResult};
+use crate::error::Error;
+use crate::row::Row;
+use crate::value::Value;
+use crate::{Cx, Dialect, Outcome, SqlDialect};
+
+/// A statement on its way to the database.
+///
+/// SQL and parameters are borrowed from the caller until an interceptor
+/// changes them (`statement.sql.to_mut()`), so untouched statements are not
+/// copied.
+#[derive(Debug, Clone)]
+pub struct Statement<'a> {
+    /// The SQL text.
+    pub sql: Cow<'a, str>,
+    /// Bound parameters.
+    pub params: Cow<'a, [Value]>,
+    /// Dialect of the connection, for interceptors that add placeholders.
+    pub dialect: Dialect,
+}
+
+impl<'a> Statement<'a> {
+    /// A statement borrowing `sql` and `params`.
+    pub fn new(dialect: Dialect, sql: &'a str, params: &'a [Value]) -> Self {
+        Self {
+            sql: Cow::Borrowed(sql),
+            params: Cow::Borrowed(params),
+            dialect,
+        }
+    }
+
+    /// The leading SQL keyword in upper case (`SELECT`, `DELETE`, ...),
+    /// skipping whitespace and comments.
+    pub fn keyword(&self) -> Option<String> {
+        words(&self.sql)
+            .next()
+            .map(|word| word.to_ascii_uppercase())
+    }
+
+    /// Whether the statement contains a `WHERE` keyword outside string
+    /// literals, quoted identifiers, and comments.
+    ///
+    /// This is a lexical check: a `WHERE` inside a subquery counts.
+    pub fn has_where_clause(&self) -> bool {
+        words(&self.sql).any(|word| word.eq_ignore_ascii_case("WHERE"))
+    }
+
+    /// Prefix the SQL with `/* comment */`.
+    ///
+    /// Any `*/` in `comment` is broken up so it cannot end the comment early.
+    pub fn prepend_comment(&mut self, comment: &str) {
+        let comment = comment.replace("*/", "* /");
+        self.sql = Cow::Owned(format!("/* {comment} */ {}", self.sql));
+    }
+}
+
+/// Iterate over the bare words of `sql`, skipping quoted text and comments.
+fn words(sql: &str) -> impl Iterator<Item = &str> {
+    let bytes = sql.as_bytes();
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        while pos < bytes.len() {
+            let b = bytes[pos];
+            match b {
+                b'\'' | b'"' | b'`' => {
+                    pos += 1;
+                    while pos < bytes.len() && bytes[pos] != b {
+                        pos += 1;
+                    }
+                    pos += 1;
+                }
+                b'-' if bytes.get(pos + 1) == Some(&b'-') => {
+                    while pos < bytes.len() && bytes[pos] != b'\n' {
+                        pos += 1;
+                    }
+                }
+                b'/' if bytes.get(pos + 1) == Some(&b'*') => {
+                    pos += 2;
+                    while pos < bytes.len() && !bytes[pos..].starts_with(b"*/") {
+                        pos += 1;
+                    }
+                    pos += 2;
+                }
+                _ if b.is_ascii_alphabetic() || b == b'_' => {
+                    let start = pos;
+                    while pos < bytes.len()
+                        && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_')
+                    {
+                        pos += 1;
+                    }
+                    return Some(&sql[start..pos]);
+                }
+                _ => pos += 1,
+            }
+        }
+        None
+    })
+}
+
+/// Inspects or rewrites statements before they run.
+///
+/// Return an error to stop the statement; it is reported to the caller as the
+/// statement's outcome.
+pub trait StatementInterceptor: Send + Sync {
+    /// Inspect or modify `statement`.
+    #[allow(clippy::result_large_err)]
+    fn intercept(&self, statement: &mut Statement<'_>) -> crate::Result<()>;
+}
+
+impl<F> StatementInterceptor for F
+where
+    F: Fn(&mut Statement<'_>) -> crate::Result<()> + Send + Sync,
+{
+    fn intercept(&self, statement: &mut Statement<'_>) -> crate::Result<()> {
+        self(statement)
+    }
+}
+
+/// An ordered list of interceptors. Cloning is cheap.
+#[derive(Clone, Default)]
+pub struct InterceptorChain {
+    interceptors: Vec<Arc<dyn StatementInterceptor>>,
+}
+
+impl InterceptorChain {
+    /// An empty chain, which passes statements through unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append `interceptor`; it runs after those already in the chain.
+    pub fn with(mut self, interceptor: Arc<dyn StatementInterceptor>) -> Self {
+        self.push(interceptor);
+        self
+    }
+
+    /// Append `interceptor` in place.
+    pub fn push(&mut self, interceptor: Arc<dyn StatementInterceptor>) {
+        self.interceptors.push(interceptor);
+    }
+
+    /// Number of interceptors in the chain.
+    pub fn len(&self) -> usize {
+        self.interceptors.len()
+    }
+
+    /// Whether the chain has no interceptors.
+    pub fn is_empty(&self) -> bool {
+        self.interceptors.is_empty()
+    }
+
+    /// Run every interceptor over `sql` and `params`, in order.
+    #[allow(clippy::result_large_err)]
+    pub fn apply<'a>(
+        &self,
+        dialect: Dialect,
+        sql: &'a str,
+        params: &'a [Value],
+    ) -> crate::Result<Statement<'a>> {
+        let mut statement = Statement::new(dialect, sql, params);
+        for interceptor in &self.interceptors {
+            interceptor.intercept(&mut statement)?;
+        }
+        Ok(statement)
+    }
+
+    /// Run the chain over each statement of a batch.
+    ///
+    /// Returns `None` when the chain is empty and the batch can be sent as is.
+    #[allow(clippy::result_large_err, clippy::type_complexity)]
+    pub fn apply_batch(
+        &self,
+        dialect: Dialect,
+        statements: &[(String, Vec<Value>)],
+    ) -> crate::Result<Option<Vec<(String, Vec<Value>)>>> {
+        if self.is_empty() {
+            return Ok(None);
+        }
+        statements
+            .iter()
+            .map(|(sql, params)| {
+                let statement = self.apply(dialect, sql, params)?;
+                Ok((statement.sql.into_owned(), statement.params.into_owned()))
+            })
+            .collect::<crate::Result<Vec<_>>>()
+            .map(Some)
+    }
+}
+
+impl std::fmt::Debug for InterceptorChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InterceptorChain")
+            .field("len", &self.interceptors.len())
+            .finish()
+    }
+}
+
+/// Prefixes every statement with a `/* ... */` comment.
+///
+/// The closure runs per statement, so it can pick up request-scoped values
+/// such as a trace id; returning `None` leaves the statement alone.
+pub struct CommentInjector<F> {
+    comment: F,
+}
+
+impl<F> CommentInjector<F>
+where
+    F: Fn() -> Option<String> + Send + Sync,
+{
+    /// Inject the comment produced by `comment`.
+    pub fn new(comment: F) -> Self {
+        Self { comment }
+    }
+}
+
+impl<F> std::fmt::Debug for CommentInjector<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommentInjector").finish_non_exhaustive()
+    }
+}
+
+impl<F> StatementInterceptor for CommentInjector<F>
+where
+    F: Fn() -> Option<String> + Send + Sync,
+{
+    fn intercept(&self, statement: &mut Statement<'_>) -> crate::Result<()> {
+        if let Some(comment) = (self.comment)() {
+            statement.prepend_comment(&comment);
+        }
+        Ok(())
+    }
+}
+
+/// Rejects `DELETE` statements without a `WHERE` clause, and optionally
+/// `UPDATE` statements too.
+///
+/// Uses [`Statement::has_where_clause`], so a filter that only appears in a
+/// subquery is accepted.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RejectUnfilteredWrites {
+    updates: bool,
+}
+
+impl RejectUnfilteredWrites {
+    /// Reject unfiltered `DELETE`s.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also reject unfiltered `UPDATE`s.
+    pub fn include_updates(mut self) -> Self {
+        self.updates = true;
+        self
+    }
+}
+
+impl StatementInterceptor for RejectUnfilteredWrites {
+    fn intercept(&self, statement: &mut Statement<'_>) -> crate::Result<()> {
+        let Some(keyword) = statement.keyword() else {
+            return Ok(());
+        };
+        let guarded = keyword == "DELETE" || (self.updates && keyword == "UPDATE");
+        if guarded && !statement.has_where_clause() {
+            return Err(Error::Custom(format!(
+                "statement rejected: {keyword} without a WHERE clause"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A connection that passes every statement through an [`InterceptorChain`].
+///
+/// Prepared statements are intercepted once, when prepared, with no
+/// parameters. Statements issued on a transaction object returned by
+/// [`begin`](Connection::begin) go straight to the inner connection's
+/// transaction and are not intercepted.
+pub struct InterceptedConnection<C> {
+    inner: C,
+    interceptors: InterceptorChain,
+}
+
+impl<C> InterceptedConnection<C> {
+    /// Wrap `inner`, running its statements through `interceptors`.
+    pub fn new(inner: C, interceptors: InterceptorChain) -> Self {
+        Self {
+            inner,
+            interceptors,
+        }
+    }
+
+    /// The wrapped connection.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// The interceptors statements pass through.
+    pub fn interceptors(&self) -> &InterceptorChain {
+        &self.interceptors
+    }
+
+    /// Unwrap the inner connection.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<C: std::fmt::Debug> std::fmt::Debug for InterceptedConnection<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InterceptedConnection")
+            .field("inner", &self.inner)
+            .field("interceptors", &self.interceptors)
+            .finish()
+    }
+}
+
+#[allow(clippy::manual_async_fn)] // Trait methods return `impl Future + Send`
+impl<C: Connection> Connection for InterceptedConnection<C> {
+    type Tx<'conn>
+        = C::Tx<'conn>
+    where
+        Self: 'conn;
+
+    fn dialect(&self) -> Dialect {
+        self.inner.dialect()
+    }
+
+    fn sql_dialect(&self) -> &'static dyn SqlDialect {
+        self.inner.sql_dialect()
+    }
+
+    fn query(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<Vec<Row>, Error>> + Send {
+        async move {
+            let statement = match self.interceptors.apply(self.dialect(), sql, params) {
+                Ok(statement) => statement,
+                Err(e) => return Outcome::Err(e),
+            };
+            self.inner
+                .query(cx, &statement.sql, &statement.params)
+                .await
+        }
+    }
+
+    fn query_raw(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<QueryResult, Error>> + Send {
+        async move {
+            let statement = match self.interceptors.apply(self.dialect(), sql, params) {
+                Ok(statement) => statement,
+                Err(e) => return Outcome::Err(e),
+            };
+            self.inner
+                .query_raw(cx, &statement.sql, &statement.params)
+                .await
+        }
+    }
+
+    fn query_one(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<Option<Row>, Error>> + Send {
+        async move {
+            let statement = match self.interceptors.apply(self.dialect(), sql, params) {
+                Ok(statement) => statement,
+                Err(e) => return Outcome::Err(e),
+            };
+            self.inner
+                .query_one(cx, &statement.sql, &statement.params)
+                .await
+        }
+    }
+
+    fn execute(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<u64, Error>> + Send {
+        async move {
+            let statement = match self.interceptors.apply(self.dialect(), sql, params) {
+                Ok(statement) => statement,
+                Err(e) => return Outcome::Err(e),
+            };
+            self.inner
+                .execute(cx, &statement.sql, &statement.params)
+                .await
+        }
+    }
+
+    fn insert(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<i64, Error>> + Send {
+        async move {
+            let statement = match self.interceptors.apply(self.dialect(), sql, params) {
+                Ok(statement) => statement,
+                Err(e) => return Outcome::Err(e),
+            };
+            self.inner
+                .insert(cx, &statement.sql, &statement.params)
+                .await
+        }
+    }
+
+    fn batch(
+        &self,
+        cx: &Cx,
+        statements: &[(String, Vec<Value>)],
+    ) -> impl Future<Output = Outcome<Vec<u64>, Error>> + Send {
+        async move {
+            match self.interceptors.apply_batch(self.dialect(), statements) {
+                Ok(Some(rewritten)) => self.inner.batch(cx, &rewritten).await,
+                Ok(None) => self.inner.batch(cx, statements).await,
+                Err(e) => Outcome::Err(e),
+            }
+        }
+    }
+
+    fn begin(&self, cx: &Cx) -> impl Future<Output = Outcome<Self::Tx<'_>, Error>> + Send {
+        self.inner.begin(cx)
+    }
+
+    fn begin_with(
+        &self,
+        cx: &Cx,
+        isolation: IsolationLevel,
+    ) -> impl Future<Output = Outcome<Self::Tx<'_>, Error>> + Send {
+        self.inner.begin_with(cx, isolation)
+    }
+
+    fn prepare(
+        &self,
+        cx: &Cx,
+        sql: &str,
+    ) -> impl Future<Output = Outcome<PreparedStatement, Error>> + Send {
+        async move {
+            let statement = match self.interceptors.apply(self.dialect(), sql, &[]) {
+                Ok(statement) => statement,
+                Err(e) => return Outcome::Err(e),
+            };
+            self.inner.prepare(cx, &statement.sql).await
+        }
+    }
+
+    fn query_prepared(
+        &self,
+        cx: &Cx,
+        stmt: &PreparedStatement,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<Vec<Row>, Error>> + Send {
+        self.inner.query_prepared(cx, stmt, params)
+    }
+
+    fn execute_prepared(
+        &self,
+        cx: &Cx,
+        stmt: &PreparedStatement,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<u64, Error>> + Send {
+        self.inner.execute_prepared(cx, stmt, params)
+    }
+
+    fn ping(&self, cx: &Cx) -> impl Future<Output = Outcome<(), Error>> + Send {
+        self.inner.ping(cx)
+    }
+
+    fn close(self, cx: &Cx) -> impl Future<Output = crate::Result<()>> + Send {
+        self.inner.close(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(clippy::result_large_err)]
+    fn run(chain: &InterceptorChain, sql: &str) -> crate::Result<String> {
+        chain
+            .apply(Dialect::Postgres, sql, &[])
+            .map(|statement| statement.sql.into_owned())
+    }
+
+    #[test]
+    fn test_keyword_skips_comments_and_whitespace() {
+        let statement =
+            Statement::new(Dialect::Sqlite, "  -- note\n /* hint */ delete FROM t", &[]);
+        assert_eq!(statement.keyword().as_deref(), Some("DELETE"));
+        assert_eq!(Statement::new(Dialect::Sqlite, "  ", &[]).keyword(), None);
+    }
+
+    #[test]
+    fn test_has_where_clause_ignores_quoted_text() {
+        let has_where = |sql| Statement::new(Dialect::Postgres, sql, &[]).has_where_clause();
+        assert!(has_where("DELETE FROM t WHERE id = $1"));
+        assert!(has_where("delete from t\nwhere id = 1"));
+        assert!(!has_where("DELETE FROM t"));
+        assert!(!has_where("DELETE FROM \"where\""));
+        assert!(!has_where("UPDATE t SET note = 'where'"));
+        assert!(!has_where("DELETE FROM t -- WHERE id = 1"));
+        assert!(!has_where("DELETE FROM t_where"));
+    }
+
+    #[test]
+    fn test_empty_chain_borrows_statement() {
+        let params = [Value::Int(1)];
+        let statement = InterceptorChain::new()
+            .apply(Dialect::Postgres, "SELECT $1", &params)
+            .unwrap();
+        assert!(matches!(statement.sql, Cow::Borrowed(_)));
+        assert!(matches!(statement.params, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_chain_runs_in_order_and_stops_at_first_error() {
+        let chain = InterceptorChain::new()
+            .with(Arc::new(CommentInjector::new(|| {
+                Some("trace_id=abc */ DROP".to_string())
+            })))
+            .with(Arc::new(RejectUnfilteredWrites::new()));
+
+        assert_eq!(
+            run(&chain, "SELECT 1").unwrap(),
+            "/* trace_id=abc * / DROP */ SELECT 1"
+        );
+        assert_eq!(
+            run(&chain, "DELETE FROM t WHERE id = 1").unwrap(),
+            "/* trace_id=abc * / DROP */ DELETE FROM t WHERE id = 1"
+        );
+        let err = run(&chain, "DELETE FROM t").unwrap_err();
+        assert!(err.to_string().contains("DELETE without a WHERE clause"));
+        assert!(run(&chain, "UPDATE t SET x = 1").is_ok());
+    }
+
+    #[test]
+    fn test_reject_unfiltered_updates() {
+        let chain =
+            InterceptorChain::new().with(Arc::new(RejectUnfilteredWrites::new().include_updates()));
+        assert!(run(&chain, "UPDATE t SET x = 1").is_err());
+        assert!(run(&chain, "UPDATE t SET x = 1 WHERE id = 2").is_ok());
+    }
+
+    #[test]
+    #[allow(clippy::result_large_err)]
+    fn test_closure_interceptor_can_append_params() {
+        let tenant = |statement: &mut Statement<'_>| -> crate::Result<()> {
+            if statement.keyword().as_deref() == Some("SELECT") {
+                let index = statement.params.len() + 1;
+                let predicate =
+                    format!(" AND tenant_id = {}", statement.dialect.placeholder(index));
+                statement.sql.to_mut().push_str(&predicate);
+                statement.params.to_mut().push(Value::BigInt(42));
+            }
+            Ok(())
+        };
+        let chain = InterceptorChain::new().with(Arc::new(tenant));
+        let params = [Value::Int(1)];
+        let statement = chain
+            .apply(Dialect::Postgres, "SELECT * FROM t WHERE id = $1", &params)
+            .unwrap();
+        assert_eq!(
+            statement.sql,
+            "SELECT * FROM t WHERE id = $1 AND tenant_id = $2"
+        );
+        assert_eq!(
+            statement.params.as_ref(),
+            &[Value::Int(1), Value::BigInt(42)]
+        );
+    }
+
+    #[test]
+    fn test_apply_batch() {
+        let chain = InterceptorChain::new();
+        let batch = vec![("DELETE FROM t".to_string(), Vec::new())];
+        assert!(
+            chain
+                .apply_batch(Dialect::Sqlite, &batch)
+                .unwrap()
+                .is_none()
+        );
+
+        let chain = chain.with(Arc::new(RejectUnfilteredWrites::new()));
+        assert!(chain.apply_batch(Dialect::Sqlite, &batch).is_err());
+        let ok = vec![("SELECT 1".to_string(), Vec::new())];
+        assert_eq!(
+            chain.apply_batch(Dialect::Sqlite, &ok).unwrap(),
+            Some(ok.clone())
+        );
+    }
+}
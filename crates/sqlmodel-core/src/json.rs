@@ -0,0 +1,636 @@
+//! Bridging between [`Value`] and `serde_json::Value`.
+//!
+//! HTTP layers receive untyped JSON payloads and need to bind them as typed query
+//! parameters, then render result rows back as JSON. [`Value::from_json`] uses the
+//! target column's [`SqlType`] to pick the right variant, and [`Value::to_json`] /
+//! [`Row::to_json`] produce the matching JSON shapes, so the two round-trip:
+//!
+//! | SQL type | JSON |
+//! |----------|------|
+//! | integers, floats, booleans | numbers / booleans |
+//! | `NUMERIC` / `DECIMAL` | string (exact digits preserved) |
+//! | `DATE`, `TIME`, `TIMESTAMP` | ISO-8601 strings (`2024-03-01`, `13:45:00.250000`, `2024-03-01T13:45:00`) |
+//! | `TIMESTAMPTZ` | ISO-8601 string with `Z` (offsets are accepted on input) |
+//! | `UUID` | hyphenated lowercase string |
+//! | binary | standard base64 string |
+//! | `JSON` / `JSONB` | passed through unchanged |
+//! | arrays | JSON arrays of the element type |
+//!
+//! JSON `null` binds as `Value::Null` for every type.
+
+use crate::Result;
+use crate::error::{Error, TypeError};
+use crate::row::Row;
+use crate::types::SqlType;
+use crate::value::Value;
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const MICROS_PER_DAY: i64 = 86_400 * MICROS_PER_SECOND;
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+impl Value {
+    /// Convert a JSON payload into a value for a column of type `sql_type`.
+    ///
+    /// Numeric strings are accepted for numeric columns, since form-encoded and
+    /// query-string inputs usually arrive as text. Integers are range-checked
+    /// against the column width.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sqlmodel_core::{SqlType, Value};
+    ///
+    /// let v = Value::from_json(serde_json::json!("2024-03-01"), &SqlType::Date).unwrap();
+    /// assert_eq!(v, Value::Date(19_783));
+    /// assert_eq!(v.to_json(), serde_json::json!("2024-03-01"));
+    /// ```
+    #[allow(clippy::result_large_err)]
+    pub fn from_json(json: serde_json::Value, sql_type: &SqlType) -> Result<Value> {
+        if json.is_null() {
+            return Ok(Value::Null);
+        }
+        match sql_type {
+            SqlType::TinyInt => json_int(&json, "TINYINT").and_then(|i| {
+                i8::try_from(i)
+                    .map(Value::TinyInt)
+                    .map_err(|_| mismatch("TINYINT", &json))
+            }),
+            SqlType::SmallInt => json_int(&json, "SMALLINT").and_then(|i| {
+                i16::try_from(i)
+                    .map(Value::SmallInt)
+                    .map_err(|_| mismatch("SMALLINT", &json))
+            }),
+            SqlType::Integer => json_int(&json, "INTEGER").and_then(|i| {
+                i32::try_from(i)
+                    .map(Value::Int)
+                    .map_err(|_| mismatch("INTEGER", &json))
+            }),
+            SqlType::BigInt => json_int(&json, "BIGINT").map(Value::BigInt),
+            #[allow(clippy::cast_possible_truncation)]
+            SqlType::Real => json_float(&json, "REAL").map(|f| Value::Float(f as f32)),
+            SqlType::Double => json_float(&json, "DOUBLE").map(Value::Double),
+            SqlType::Numeric { .. } | SqlType::Decimal { .. } => match &json {
+                serde_json::Value::Number(n) => Ok(Value::Decimal(n.to_string())),
+                serde_json::Value::String(s) if s.trim().parse::<f64>().is_ok() => {
+                    Ok(Value::Decimal(s.trim().to_string()))
+                }
+                _ => Err(mismatch("decimal number or numeric string", &json)),
+            },
+            SqlType::Boolean => match json {
+                serde_json::Value::Bool(b) => Ok(Value::Bool(b)),
+                other => Err(mismatch("boolean", &other)),
+            },
+            SqlType::Char(len) | SqlType::VarChar(len) => {
+                let s = json_str(json, "string")?;
+                if s.chars().count() > *len as usize {
+                    return Err(type_error(
+                        "string within column length",
+                        format!("string of {} characters (limit {len})", s.chars().count()),
+                    ));
+                }
+                Ok(Value::Text(s))
+            }
+            SqlType::Text | SqlType::CiText => json_str(json, "string").map(Value::Text),
+            SqlType::Enum(variants) => {
+                let s = json_str(json, "enum string")?;
+                if variants.contains(&s.as_str()) {
+                    Ok(Value::Text(s))
+                } else {
+                    Err(type_error(
+                        "enum variant",
+                        format!("'{s}' (allowed: {})", variants.join(", ")),
+                    ))
+                }
+            }
+            SqlType::Binary(_) | SqlType::VarBinary(_) | SqlType::Blob => {
+                let s = json_str(json, "base64 string")?;
+                base64_decode(&s)
+                    .map(Value::Bytes)
+                    .ok_or_else(|| type_error("base64 string", format!("'{s}'")))
+            }
+            SqlType::Date => {
+                let s = json_str(json, "ISO-8601 date")?;
+                parse_date(&s)
+                    .map(Value::Date)
+                    .ok_or_else(|| type_error("ISO-8601 date", format!("'{s}'")))
+            }
+            SqlType::Time => {
+                let s = json_str(json, "ISO-8601 time")?;
+                parse_time(&s)
+                    .map(Value::Time)
+                    .ok_or_else(|| type_error("ISO-8601 time", format!("'{s}'")))
+            }
+            SqlType::DateTime | SqlType::Timestamp => {
+                let s = json_str(json, "ISO-8601 timestamp")?;
+                parse_timestamp(&s)
+                    .map(Value::Timestamp)
+                    .ok_or_else(|| type_error("ISO-8601 timestamp", format!("'{s}'")))
+            }
+            SqlType::TimestampTz => {
+                let s = json_str(json, "ISO-8601 timestamp")?;
+                parse_timestamp(&s)
+                    .map(Value::TimestampTz)
+                    .ok_or_else(|| type_error("ISO-8601 timestamp", format!("'{s}'")))
+            }
+            SqlType::Uuid => {
+                let s = json_str(json, "UUID string")?;
+                parse_uuid(&s)
+                    .map(Value::Uuid)
+                    .ok_or_else(|| type_error("UUID string", format!("'{s}'")))
+            }
+            SqlType::Json | SqlType::JsonB => Ok(Value::Json(json)),
+            SqlType::Array(inner) => match json {
+                serde_json::Value::Array(items) => items
+                    .into_iter()
+                    .map(|item| Value::from_json(item, inner))
+                    .collect::<Result<Vec<_>>>()
+                    .map(Value::Array),
+                other => Err(mismatch("array", &other)),
+            },
+            SqlType::Custom(_) => Ok(untyped(json)),
+        }
+    }
+
+    /// Render this value as JSON.
+    ///
+    /// The output is the shape [`Value::from_json`] accepts for the value's SQL
+    /// type. Non-finite floats become `null`; `Default` renders as `null`.
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Value::Null | Value::Default => serde_json::Value::Null,
+            Value::Bool(b) => serde_json::Value::Bool(*b),
+            Value::TinyInt(i) => (*i).into(),
+            Value::SmallInt(i) => (*i).into(),
+            Value::Int(i) => (*i).into(),
+            Value::BigInt(i) => (*i).into(),
+            Value::Float(f) => serde_json::Number::from_f64(f64::from(*f))
+                .map_or(serde_json::Value::Null, serde_json::Value::Number),
+            Value::Double(f) => serde_json::Number::from_f64(*f)
+                .map_or(serde_json::Value::Null, serde_json::Value::Number),
+            Value::Decimal(s) | Value::Text(s) => serde_json::Value::String(s.clone()),
+            Value::Bytes(b) => serde_json::Value::String(base64_encode(b)),
+            Value::Date(days) => serde_json::Value::String(format_date(*days)),
+            Value::Time(micros) => serde_json::Value::String(format_time(*micros)),
+            Value::Timestamp(micros) => serde_json::Value::String(format_timestamp(*micros)),
+            Value::TimestampTz(micros) => {
+                serde_json::Value::String(format!("{}Z", format_timestamp(*micros)))
+            }
+            Value::Uuid(bytes) => serde_json::Value::String(format_uuid(bytes)),
+            Value::Json(j) => j.clone(),
+            Value::Array(items) => {
+                serde_json::Value::Array(items.iter().map(Value::to_json).collect())
+            }
+        }
+    }
+}
+
+impl Row {
+    /// Render this row as a JSON object keyed by column name.
+    ///
+    /// Values are converted with [`Value::to_json`]. If a column name repeats,
+    /// the last column wins.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::Value::Object(
+            self.iter()
+                .map(|(name, value)| (name.to_string(), value.to_json()))
+                .collect(),
+        )
+    }
+}
+
+/// Best-effort conversion for columns whose SQL type gives no guidance.
+fn untyped(json: serde_json::Value) -> Value {
+    match json {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(b) => Value::Bool(b),
+        serde_json::Value::Number(n) => match (n.as_i64(), n.as_f64()) {
+            (Some(i), _) => Value::BigInt(i),
+            (None, Some(f)) => Value::Double(f),
+            (None, None) => Value::Decimal(n.to_string()),
+        },
+        serde_json::Value::String(s) => Value::Text(s),
+        other => Value::Json(other),
+    }
+}
+
+fn type_error(expected: &'static str, actual: String) -> Error {
+    Error::Type(TypeError {
+        expected,
+        actual,
+        column: None,
+        rust_type: None,
+    })
+}
+
+fn mismatch(expected: &'static str, json: &serde_json::Value) -> Error {
+    type_error(expected, json.to_string())
+}
+
+#[allow(clippy::result_large_err)]
+fn json_int(json: &serde_json::Value, expected: &'static str) -> Result<i64> {
+    match json {
+        serde_json::Value::Number(n) => n.as_i64(),
+        serde_json::Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| mismatch(expected, json))
+}
+
+#[allow(clippy::result_large_err)]
+fn json_float(json: &serde_json::Value, expected: &'static str) -> Result<f64> {
+    match json {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| mismatch(expected, json))
+}
+
+#[allow(clippy::result_large_err)]
+fn json_str(json: serde_json::Value, expected: &'static str) -> Result<String> {
+    match json {
+        serde_json::Value::String(s) => Ok(s),
+        other => Err(mismatch(expected, &other)),
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Proleptic Gregorian (year, month, day) for days since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Parse a fixed-width run of ASCII digits.
+fn digits(s: &str) -> Option<i64> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+/// Parse `YYYY-MM-DD` into days since the Unix epoch.
+fn parse_date(s: &str) -> Option<i32> {
+    let (negative, rest) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let mut parts = rest.splitn(3, '-');
+    let year = digits(parts.next()?)?;
+    let year = if negative { -year } else { year };
+    let month = parts.next().filter(|p| p.len() == 2).and_then(digits)?;
+    let day = parts.next().filter(|p| p.len() == 2).and_then(digits)?;
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+    i32::try_from(days_from_civil(year, month, day)).ok()
+}
+
+/// Parse `HH:MM[:SS[.fraction]]` into microseconds since midnight.
+fn parse_time(s: &str) -> Option<i64> {
+    let (clock, fraction) = match s.split_once('.') {
+        Some((clock, fraction)) => (clock, Some(fraction)),
+        None => (s, None),
+    };
+    let mut parts = clock.split(':');
+    let hour = parts.next().filter(|p| p.len() == 2).and_then(digits)?;
+    let minute = parts.next().filter(|p| p.len() == 2).and_then(digits)?;
+    let second = match parts.next() {
+        Some(p) if p.len() == 2 => digits(p)?,
+        Some(_) => return None,
+        None if fraction.is_none() => 0,
+        None => return None,
+    };
+    if parts.next().is_some() || hour > 23 || minute > 59 || second > 59 {
+        return None;
+    }
+    let micros = match fraction {
+        Some(f) if (1..=9).contains(&f.len()) => {
+            let padded = format!("{f:0<6}");
+            digits(&padded[..6])?
+        }
+        Some(_) => return None,
+        None => 0,
+    };
+    Some(((hour * 60 + minute) * 60 + second) * MICROS_PER_SECOND + micros)
+}
+
+/// Parse `YYYY-MM-DD[T| ]HH:MM[:SS[.fraction]][Z|±HH:MM]` into microseconds since
+/// the Unix epoch, normalised to UTC.
+fn parse_timestamp(s: &str) -> Option<i64> {
+    let split = s.rfind(['T', 't', ' ']).filter(|&i| i >= 10)?;
+    let (date, time) = (&s[..split], &s[split + 1..]);
+    let (time, offset_secs) = if let Some(t) = time.strip_suffix(['Z', 'z']) {
+        (t, 0)
+    } else if let Some(i) = time.rfind(['+', '-']) {
+        let (t, offset) = time.split_at(i);
+        let sign = if offset.starts_with('-') { -1 } else { 1 };
+        let offset = offset[1..].replace(':', "");
+        if offset.len() != 4 {
+            return None;
+        }
+        let hours = digits(&offset[..2])?;
+        let minutes = digits(&offset[2..])?;
+        (t, sign * (hours * 3600 + minutes * 60))
+    } else {
+        (time, 0)
+    };
+    let days = i64::from(parse_date(date)?);
+    let time = parse_time(time)?;
+    Some(days * MICROS_PER_DAY + time - offset_secs * MICROS_PER_SECOND)
+}
+
+fn parse_uuid(s: &str) -> Option<[u8; 16]> {
+    let hex: String = s.chars().filter(|&c| c != '-').collect();
+    let dashed = s.len() == 36
+        && [8, 13, 18, 23]
+            .iter()
+            .all(|&i| s.as_bytes().get(i) == Some(&b'-'));
+    if hex.len() != 32 || !(dashed || s.len() == 32) {
+        return None;
+    }
+    let mut bytes = [0u8; 16];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(bytes)
+}
+
+fn format_date(days: i32) -> String {
+    let (year, month, day) = civil_from_days(i64::from(days));
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// `HH:MM:SS`, with six fractional digits when the value has sub-second precision.
+fn format_time(micros: i64) -> String {
+    let micros = micros.rem_euclid(MICROS_PER_DAY);
+    let secs = micros / MICROS_PER_SECOND;
+    let fraction = micros % MICROS_PER_SECOND;
+    let clock = format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60);
+    if fraction == 0 {
+        clock
+    } else {
+        format!("{clock}.{fraction:06}")
+    }
+}
+
+fn format_timestamp(micros: i64) -> String {
+    let days = micros.div_euclid(MICROS_PER_DAY);
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{}",
+        format_time(micros.rem_euclid(MICROS_PER_DAY))
+    )
+}
+
+fn format_uuid(bytes: &[u8; 16]) -> String {
+    use std::fmt::Write;
+    let hex = bytes.iter().fold(String::with_capacity(32), |mut acc, b| {
+        let _ = write!(acc, "{b:02x}");
+        acc
+    });
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+fn base64_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(char::from(
+                    BASE64_ALPHABET[(n >> (18 - 6 * i)) as usize & 63],
+                ));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Decode standard base64; trailing padding is optional.
+#[allow(clippy::cast_possible_truncation)]
+fn base64_decode(s: &str) -> Option<Vec<u8>> {
+    let s = s.trim_end_matches('=');
+    if s.len() % 4 == 1 {
+        return None;
+    }
+    let mut out = Vec::with_capacity(s.len() * 3 / 4);
+    let mut acc = 0u32;
+    let mut bits = 0;
+    for c in s.bytes() {
+        let sextet = BASE64_ALPHABET.iter().position(|&a| a == c)?;
+        acc = (acc << 6) | sextet as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn round_trip(value: &Value, sql_type: &SqlType) {
+        let json = value.to_json();
+        assert_eq!(
+            &Value::from_json(json.clone(), sql_type).unwrap(),
+            value,
+            "{sql_type:?} via {json}"
+        );
+    }
+
+    #[test]
+    fn test_round_trips_per_sql_type() {
+        round_trip(&Value::TinyInt(-8), &SqlType::TinyInt);
+        round_trip(&Value::SmallInt(300), &SqlType::SmallInt);
+        round_trip(&Value::Int(-70_000), &SqlType::Integer);
+        round_trip(&Value::BigInt(i64::MAX), &SqlType::BigInt);
+        round_trip(&Value::Float(1.5), &SqlType::Real);
+        round_trip(&Value::Double(-0.125), &SqlType::Double);
+        round_trip(
+            &Value::Decimal("12345678901234567890.0001".into()),
+            &SqlType::Numeric {
+                precision: 38,
+                scale: 4,
+            },
+        );
+        round_trip(&Value::Bool(true), &SqlType::Boolean);
+        round_trip(&Value::Text("héllo".into()), &SqlType::VarChar(5));
+        round_trip(&Value::Bytes(vec![0, 1, 2, 250, 251]), &SqlType::Blob);
+        round_trip(&Value::Bytes(vec![]), &SqlType::Blob);
+        round_trip(&Value::Date(0), &SqlType::Date);
+        round_trip(&Value::Date(-719_468), &SqlType::Date);
+        round_trip(&Value::Time(45_296_000_001), &SqlType::Time);
+        round_trip(
+            &Value::Timestamp(1_709_300_700_250_000),
+            &SqlType::Timestamp,
+        );
+        round_trip(&Value::Timestamp(-1), &SqlType::DateTime);
+        round_trip(
+            &Value::TimestampTz(1_709_300_700_000_000),
+            &SqlType::TimestampTz,
+        );
+        round_trip(&Value::Uuid([0xab; 16]), &SqlType::Uuid);
+        round_trip(&Value::Json(json!({"a": [1, null]})), &SqlType::JsonB);
+        round_trip(
+            &Value::Array(vec![Value::Int(1), Value::Null]),
+            &SqlType::Array(Box::new(SqlType::Integer)),
+        );
+        round_trip(&Value::Null, &SqlType::Uuid);
+    }
+
+    #[test]
+    fn test_to_json_shapes() {
+        assert_eq!(Value::Date(19_783).to_json(), json!("2024-03-01"));
+        assert_eq!(
+            Value::Time(49_500_250_000).to_json(),
+            json!("13:45:00.250000")
+        );
+        assert_eq!(
+            Value::TimestampTz(1_709_300_700_000_000).to_json(),
+            json!("2024-03-01T13:45:00Z")
+        );
+        assert_eq!(Value::Bytes(b"hi!?".to_vec()).to_json(), json!("aGkhPw=="));
+        assert_eq!(
+            Value::Uuid([
+                0x55, 0x0e, 0x84, 0x00, 0xe2, 0x9b, 0x41, 0xd4, 0xa7, 0x16, 0x44, 0x66, 0x55, 0x44,
+                0x00, 0x00
+            ])
+            .to_json(),
+            json!("550e8400-e29b-41d4-a716-446655440000")
+        );
+        assert_eq!(Value::Double(f64::NAN).to_json(), json!(null));
+        assert_eq!(Value::Default.to_json(), json!(null));
+    }
+
+    #[test]
+    fn test_from_json_accepts_common_inputs() {
+        assert_eq!(
+            Value::from_json(json!("42"), &SqlType::Integer).unwrap(),
+            Value::Int(42)
+        );
+        assert_eq!(
+            Value::from_json(
+                json!(2.5),
+                &SqlType::Decimal {
+                    precision: 10,
+                    scale: 2
+                }
+            )
+            .unwrap(),
+            Value::Decimal("2.5".into())
+        );
+        assert_eq!(
+            Value::from_json(json!("2024-03-01 15:45+02:00"), &SqlType::TimestampTz).unwrap(),
+            Value::TimestampTz(1_709_300_700_000_000)
+        );
+        assert_eq!(
+            Value::from_json(json!("550E8400E29B41D4A716446655440000"), &SqlType::Uuid)
+                .unwrap()
+                .to_json(),
+            json!("550e8400-e29b-41d4-a716-446655440000")
+        );
+        assert_eq!(
+            Value::from_json(json!("aGk"), &SqlType::Blob).unwrap(),
+            Value::Bytes(b"hi".to_vec())
+        );
+        assert_eq!(
+            Value::from_json(json!(7), &SqlType::Custom("CITEXT")).unwrap(),
+            Value::BigInt(7)
+        );
+    }
+
+    #[test]
+    fn test_from_json_rejects_mismatches() {
+        let rejects = |json: serde_json::Value, sql_type: SqlType| {
+            assert!(
+                matches!(
+                    Value::from_json(json.clone(), &sql_type),
+                    Err(Error::Type(_))
+                ),
+                "{json} as {sql_type:?}"
+            );
+        };
+        rejects(json!(128), SqlType::TinyInt);
+        rejects(json!(1.5), SqlType::Integer);
+        rejects(json!("yes"), SqlType::Boolean);
+        rejects(json!("abcdef"), SqlType::VarChar(3));
+        rejects(json!("purple"), SqlType::Enum(vec!["red", "green"]));
+        rejects(json!("2024-02-30"), SqlType::Date);
+        rejects(json!("24:00:00"), SqlType::Time);
+        rejects(json!("2024-03-01"), SqlType::Timestamp);
+        rejects(json!("not-a-uuid"), SqlType::Uuid);
+        rejects(json!("a*b"), SqlType::Blob);
+        rejects(json!({"a": 1}), SqlType::Text);
+        rejects(json!([1, "x"]), SqlType::Array(Box::new(SqlType::Integer)));
+    }
+
+    #[test]
+    fn test_row_to_json() {
+        let row = Row::new(
+            vec![
+                "id".into(),
+                "name".into(),
+                "born".into(),
+                "deleted_at".into(),
+            ],
+            vec![
+                Value::BigInt(1),
+                Value::Text("Ada".into()),
+                Value::Date(-56_433),
+                Value::Null,
+            ],
+        );
+        assert_eq!(
+            row.to_json(),
+            json!({"id": 1, "name": "Ada", "born": "1815-06-30", "deleted_at": null})
+        );
+    }
+}
@@ -27,46 +27,88 @@
 // Re-export asupersync primitives for structured concurrency
 pub use asupersync::{Budget, Cx, Outcome, RegionId, TaskId};
 
+// Used by `#[sqlmodel(register)]` expansions.
+#[doc(hidden)]
+pub use inventory;
+
+pub mod budget;
 pub mod connection;
+pub mod credentials;
+pub mod dialect;
 pub mod dynamic;
 pub mod error;
 pub mod field;
 pub mod fields_set;
 pub mod hybrid;
 pub mod identifiers;
+pub mod interceptor;
+pub mod json;
+pub mod lock_watch;
 pub mod model;
+pub mod namespace;
+pub mod params;
+pub mod query_log;
+pub mod query_options;
+pub mod registry;
 pub mod relationship;
 pub mod row;
+pub mod script;
+pub mod sqlcommenter;
 pub mod tracked;
 pub mod types;
 pub mod validate;
 pub mod value;
 
+pub use budget::{BudgetedConnection, QueryCost};
 pub use connection::{
-    Connection, Dialect, IsolationLevel, PreparedStatement, Transaction, TransactionInternal,
-    TransactionOps,
+    Connection, IsolationLevel, PreparedStatement, QueryResult, ResultColumn, Transaction,
+    TransactionInternal, TransactionOps,
+};
+pub use credentials::{
+    CachedCredentials, Credentials, CredentialsFuture, CredentialsProvider, EnvCredentials,
+    StaticCredentials,
+};
+pub use dialect::{Dialect, IntoSqlDialect, SqlDialect, UpsertSyntax};
+pub use error::{
+    Error, FieldValidationError, Result, ValidationError, ValidationErrorKind, ValidationErrors,
 };
-pub use error::{Error, FieldValidationError, Result, ValidationError, ValidationErrorKind};
 pub use field::{
-    Column, Field, FieldInfo, InheritanceInfo, InheritanceStrategy, ReferentialAction,
+    Column, Field, FieldInfo, IndexDef, InheritanceInfo, InheritanceStrategy, ReferentialAction,
+    UniqueConstraintDef,
 };
 pub use fields_set::FieldsSet;
 pub use hybrid::Hybrid;
 pub use identifiers::{quote_ident, quote_ident_mysql, sanitize_identifier};
+pub use interceptor::{
+    CommentInjector, InterceptedConnection, InterceptorChain, RejectUnfilteredWrites, Statement,
+    StatementInterceptor,
+};
+pub use lock_watch::{LockBlocker, LockWaitReport, LockWatchConnection};
 pub use model::{
-    AttributeChange, AutoIncrement, ExtraFieldsBehavior, Model, ModelConfig, ModelEvents,
-    SoftDelete, Timestamps,
+    AttributeChange, AutoIncrement, CachePolicy, ExtraFieldsBehavior, HISTORY_OPERATION_COLUMN,
+    HISTORY_VALID_TO_COLUMN, Model, ModelConfig, ModelEvents, OrderByColumn, SoftDelete,
+    Timestamps,
+};
+pub use namespace::TableNamespace;
+pub use params::{ToParams, ValueRef};
+pub use query_log::{
+    JsonLinesQueryLogger, LoggedConnection, QueryLogEntry, QueryLogger, QueryStatus,
+    RedactionPolicy, TracingQueryLogger,
 };
+pub use query_options::QueryOptions;
+pub use registry::{ModelDescriptor, ModelDyn, ModelRegistry, RegisteredModel};
 pub use relationship::{
     Lazy, LazyLoader, LinkTableInfo, PassiveDeletes, Related, RelatedMany, RelationshipInfo,
     RelationshipKind, find_back_relationship, find_relationship, validate_back_populates,
 };
-pub use row::Row;
+pub use row::{ColumnDecoder, FromValueRef, Row};
+pub use script::{ScriptStatement, split_script};
+pub use sqlcommenter::SqlCommenter;
 pub use tracked::TrackedModel;
-pub use types::{SqlEnum, SqlType, TypeInfo};
+pub use types::{SqlEnum, SqlScalar, SqlType, TypeInfo};
 pub use validate::{
     DumpMode, DumpOptions, DumpResult, ModelDump, ModelValidate, SqlModelDump, SqlModelValidate,
-    ValidateInput, ValidateOptions, ValidateResult, apply_serialization_aliases,
+    Validate, ValidateInput, ValidateOptions, ValidateResult, apply_serialization_aliases,
     apply_validation_aliases,
 };
 pub use value::Value;
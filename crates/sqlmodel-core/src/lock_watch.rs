@@ -0,0 +1,588 @@
+//! Lock-wait diagnostics.
+//!
+//! [`LockWatchConnection`] wraps a connection and watches every statement it
+//! runs. When a statement is still running after a configured threshold, it
+//! asks the server, over a second *side* connection, which sessions block it:
+//!
+//! - PostgreSQL: `pg_blocking_pids()` joined with `pg_stat_activity`
+//! - MySQL: the `sys.innodb_lock_waits` view
+//!
+//! The blockers are logged with `tracing::warn!`, kept as the connection's
+//! [`last_lock_wait`](LockWatchConnection::last_lock_wait), and appended to the
+//! detail of the statement's error if it eventually fails (lock timeout,
+//! deadlock, cancellation by the server). SQLite has no lock introspection, so
+//! its statements pass through unwatched.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//! use sqlmodel_core::lock_watch::LockWatchConnection;
+//!
+//! let conn = LockWatchConnection::new(conn, side_conn, Duration::from_secs(2));
+//! ```
+
+use std::future::Future;
+use std::pin::pin;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Mutex, OnceLock};
+use std::task::{Poll, Waker};
+use std::time::{Duration, Instant};
+
+use asupersync::{Cx, Outcome};
+
+use crate::connection::{Connection, IsolationLevel, PreparedStatement, QueryResult};
+use crate::dialect::{Dialect, SqlDialect};
+use crate::error::Error;
+use crate::params::ToParams;
+use crate::row::Row;
+use crate::value::Value;
+
+/// A session holding a lock that a watched statement waits for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockBlocker {
+    /// Server process / connection id of the blocking session.
+    pub pid: i64,
+    /// The blocking session's current (or last) statement, if reported.
+    pub query: Option<String>,
+}
+
+impl LockBlocker {
+    fn from_row(row: &Row) -> Option<Self> {
+        Some(Self {
+            pid: row.get_as::<i64>(0).ok()?,
+            query: row.get_as::<Option<String>>(1).ok().flatten(),
+        })
+    }
+}
+
+/// What the watchdog found about one slow statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockWaitReport {
+    /// The statement that waited.
+    pub sql: String,
+    /// How long it had been running when the blockers were read.
+    pub waited: Duration,
+    /// Sessions blocking it at that moment (empty if none were found).
+    pub blockers: Vec<LockBlocker>,
+}
+
+impl LockWaitReport {
+    /// One-line summary, e.g. `blocked by pid 42 (UPDATE accounts ...)`.
+    pub fn describe(&self) -> String {
+        if self.blockers.is_empty() {
+            return format!(
+                "waited {}ms, no blocking sessions found",
+                self.waited.as_millis()
+            );
+        }
+        let blockers = self
+            .blockers
+            .iter()
+            .map(|b| match &b.query {
+                Some(q) => format!("pid {} ({})", b.pid, q),
+                None => format!("pid {}", b.pid),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "waited {}ms, blocked by {}",
+            self.waited.as_millis(),
+            blockers
+        )
+    }
+}
+
+/// SQL returning the server id of the current connection.
+fn backend_pid_sql(dialect: Dialect) -> Option<&'static str> {
+    match dialect {
+        Dialect::Postgres => Some("SELECT pg_backend_pid()"),
+        Dialect::Mysql => Some("SELECT CONNECTION_ID()"),
+        Dialect::Sqlite => None,
+    }
+}
+
+/// SQL returning `(pid, query)` for every session blocking the given id.
+fn blockers_sql(dialect: Dialect) -> Option<&'static str> {
+    match dialect {
+        Dialect::Postgres => {
+            Some("SELECT pid, query FROM pg_stat_activity WHERE pid = ANY(pg_blocking_pids($1))")
+        }
+        Dialect::Mysql => Some(
+            "SELECT blocking_pid, blocking_query FROM sys.innodb_lock_waits WHERE waiting_pid = ?",
+        ),
+        Dialect::Sqlite => None,
+    }
+}
+
+/// Wake `waker` at `deadline` from one shared background thread.
+fn wake_at(deadline: Instant, waker: Waker) {
+    static TIMER: OnceLock<Option<mpsc::Sender<(Instant, Waker)>>> = OnceLock::new();
+    let timer = TIMER.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<(Instant, Waker)>();
+        std::thread::Builder::new()
+            .name("sqlmodel-lock-watch".to_string())
+            .spawn(move || {
+                let mut pending: Vec<(Instant, Waker)> = Vec::new();
+                loop {
+                    let now = Instant::now();
+                    pending.retain(|(at, waker)| {
+                        if *at <= now {
+                            waker.wake_by_ref();
+                            false
+                        } else {
+                            true
+                        }
+                    });
+                    let next = pending.iter().map(|(at, _)| *at).min();
+                    let received = match next {
+                        Some(at) => match rx.recv_timeout(at.saturating_duration_since(now)) {
+                            Ok(entry) => Some(entry),
+                            Err(RecvTimeoutError::Timeout) => None,
+                            Err(RecvTimeoutError::Disconnected) => return,
+                        },
+                        None => match rx.recv() {
+                            Ok(entry) => Some(entry),
+                            Err(_) => return,
+                        },
+                    };
+                    pending.extend(received);
+                }
+            })
+            .ok()
+            .map(|_| tx)
+    });
+    match timer {
+        Some(tx) => {
+            let _ = tx.send((deadline, waker));
+        }
+        // No timer thread: poll again right away rather than never
+        None => waker.wake(),
+    }
+}
+
+/// Run `fut`; if it is still pending after `threshold`, run `probe`
+/// alongside it and return what the probe found.
+async fn watch_statement<T, F, P, PF>(
+    threshold: Duration,
+    fut: F,
+    probe: P,
+) -> (Outcome<T, Error>, Option<(Duration, Vec<LockBlocker>)>)
+where
+    F: Future<Output = Outcome<T, Error>>,
+    P: FnOnce() -> PF,
+    PF: Future<Output = Vec<LockBlocker>>,
+{
+    let started = Instant::now();
+    let deadline = started + threshold;
+    let mut fut = pin!(fut);
+    let mut probe = Some(probe);
+    let mut probing = pin!(None::<PF>);
+    let mut found: Option<(Duration, Vec<LockBlocker>)> = None;
+    let mut armed = false;
+
+    let outcome = std::future::poll_fn(|task| {
+        if let Poll::Ready(outcome) = fut.as_mut().poll(task) {
+            return Poll::Ready(outcome);
+        }
+        if found.is_some() {
+            return Poll::Pending;
+        }
+        if let Some(start_probe) = probe.take_if(|_| Instant::now() >= deadline) {
+            probing.set(Some(start_probe()));
+        }
+        match probing.as_mut().as_pin_mut() {
+            Some(running) => {
+                if let Poll::Ready(blockers) = running.poll(task) {
+                    found = Some((started.elapsed(), blockers));
+                    probing.set(None);
+                }
+            }
+            None if !armed => {
+                wake_at(deadline, task.waker().clone());
+                armed = true;
+            }
+            None => {}
+        }
+        Poll::Pending
+    })
+    .await;
+
+    (outcome, found)
+}
+
+/// A connection that reports which sessions block its slow statements.
+///
+/// Statements issued on a transaction object returned by
+/// [`begin`](Connection::begin) go straight to the inner connection's
+/// transaction and are not watched.
+pub struct LockWatchConnection<C> {
+    inner: C,
+    side: C,
+    threshold: Duration,
+    /// Server id of `inner`, read before its first watched statement.
+    backend_pid: OnceLock<Value>,
+    last_report: Mutex<Option<LockWaitReport>>,
+}
+
+impl<C> LockWatchConnection<C> {
+    /// Watch the statements of `inner`, reading lock information over `side`
+    /// once a statement has been running for `threshold`.
+    ///
+    /// `side` must be a separate connection to the same server; it is only
+    /// used for the diagnostic queries.
+    pub fn new(inner: C, side: C, threshold: Duration) -> Self {
+        Self {
+            inner,
+            side,
+            threshold,
+            backend_pid: OnceLock::new(),
+            last_report: Mutex::new(None),
+        }
+    }
+
+    /// The watched connection.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// How long a statement may run before its blockers are looked up.
+    pub fn threshold(&self) -> Duration {
+        self.threshold
+    }
+
+    /// The most recent lock wait the watchdog reported, if any.
+    pub fn last_lock_wait(&self) -> Option<LockWaitReport> {
+        self.last_report.lock().ok().and_then(|r| r.clone())
+    }
+
+    /// Unwrap the watched and side connections.
+    pub fn into_parts(self) -> (C, C) {
+        (self.inner, self.side)
+    }
+}
+
+impl<C: std::fmt::Debug> std::fmt::Debug for LockWatchConnection<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LockWatchConnection")
+            .field("inner", &self.inner)
+            .field("threshold", &self.threshold)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: Connection> LockWatchConnection<C> {
+    async fn watch<T, F>(&self, cx: &Cx, sql: &str, fut: F) -> Outcome<T, Error>
+    where
+        F: Future<Output = Outcome<T, Error>> + Send,
+    {
+        let dialect = self.inner.dialect();
+        let (Some(pid_query), Some(blockers_query)) =
+            (backend_pid_sql(dialect), blockers_sql(dialect))
+        else {
+            return fut.await;
+        };
+        let pid = match self.backend_pid.get() {
+            Some(pid) => pid.clone(),
+            None => match self.inner.query_one(cx, pid_query, &[]).await {
+                Outcome::Ok(Some(row)) => match row.get(0) {
+                    Some(pid) => self.backend_pid.get_or_init(|| pid.clone()).clone(),
+                    None => return fut.await,
+                },
+                Outcome::Ok(None) | Outcome::Err(_) => return fut.await,
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            },
+        };
+
+        let probe = || async {
+            match self
+                .side
+                .query(cx, blockers_query, std::slice::from_ref(&pid))
+                .await
+            {
+                Outcome::Ok(rows) => rows.iter().filter_map(LockBlocker::from_row).collect(),
+                _ => Vec::new(),
+            }
+        };
+        let (outcome, found) = watch_statement(self.threshold, fut, probe).await;
+        let Some((waited, blockers)) = found else {
+            return outcome;
+        };
+
+        let report = LockWaitReport {
+            sql: sql.to_string(),
+            waited,
+            blockers,
+        };
+        let summary = report.describe();
+        tracing::warn!(sql = %report.sql, "statement waited on a lock: {summary}");
+        let has_blockers = !report.blockers.is_empty();
+        if let Ok(mut last) = self.last_report.lock() {
+            *last = Some(report);
+        }
+
+        match outcome {
+            Outcome::Err(mut e) => {
+                if has_blockers && let Some(q) = e.query_error_mut() {
+                    q.detail = Some(match q.detail.take() {
+                        Some(detail) => format!("{detail}; {summary}"),
+                        None => summary,
+                    });
+                }
+                Outcome::Err(e)
+            }
+            other => other,
+        }
+    }
+}
+
+impl<C: Connection> Connection for LockWatchConnection<C> {
+    type Tx<'conn>
+        = C::Tx<'conn>
+    where
+        Self: 'conn;
+
+    fn dialect(&self) -> Dialect {
+        self.inner.dialect()
+    }
+
+    fn sql_dialect(&self) -> &'static dyn SqlDialect {
+        self.inner.sql_dialect()
+    }
+
+    fn query(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<Vec<Row>, Error>> + Send {
+        self.watch(cx, sql, self.inner.query(cx, sql, params))
+    }
+
+    fn query_raw(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<QueryResult, Error>> + Send {
+        self.watch(cx, sql, self.inner.query_raw(cx, sql, params))
+    }
+
+    fn query_one(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<Option<Row>, Error>> + Send {
+        self.watch(cx, sql, self.inner.query_one(cx, sql, params))
+    }
+
+    fn execute(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<u64, Error>> + Send {
+        self.watch(cx, sql, self.inner.execute(cx, sql, params))
+    }
+
+    fn query_params<P: ToParams + ?Sized>(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &P,
+    ) -> impl Future<Output = Outcome<Vec<Row>, Error>> + Send {
+        self.watch(cx, sql, self.inner.query_params(cx, sql, params))
+    }
+
+    fn execute_params<P: ToParams + ?Sized>(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &P,
+    ) -> impl Future<Output = Outcome<u64, Error>> + Send {
+        self.watch(cx, sql, self.inner.execute_params(cx, sql, params))
+    }
+
+    fn insert(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<i64, Error>> + Send {
+        self.watch(cx, sql, self.inner.insert(cx, sql, params))
+    }
+
+    fn batch(
+        &self,
+        cx: &Cx,
+        statements: &[(String, Vec<Value>)],
+    ) -> impl Future<Output = Outcome<Vec<u64>, Error>> + Send {
+        let sql = statements
+            .iter()
+            .map(|(sql, _)| sql.as_str())
+            .collect::<Vec<_>>()
+            .join(";\n");
+        let fut = self.inner.batch(cx, statements);
+        async move { self.watch(cx, &sql, fut).await }
+    }
+
+    fn begin(&self, cx: &Cx) -> impl Future<Output = Outcome<Self::Tx<'_>, Error>> + Send {
+        self.inner.begin(cx)
+    }
+
+    fn begin_with(
+        &self,
+        cx: &Cx,
+        isolation: IsolationLevel,
+    ) -> impl Future<Output = Outcome<Self::Tx<'_>, Error>> + Send {
+        self.inner.begin_with(cx, isolation)
+    }
+
+    fn prepare(
+        &self,
+        cx: &Cx,
+        sql: &str,
+    ) -> impl Future<Output = Outcome<PreparedStatement, Error>> + Send {
+        self.inner.prepare(cx, sql)
+    }
+
+    fn query_prepared(
+        &self,
+        cx: &Cx,
+        stmt: &PreparedStatement,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<Vec<Row>, Error>> + Send {
+        self.watch(cx, stmt.sql(), self.inner.query_prepared(cx, stmt, params))
+    }
+
+    fn execute_prepared(
+        &self,
+        cx: &Cx,
+        stmt: &PreparedStatement,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<u64, Error>> + Send {
+        self.watch(
+            cx,
+            stmt.sql(),
+            self.inner.execute_prepared(cx, stmt, params),
+        )
+    }
+
+    fn ping(&self, cx: &Cx) -> impl Future<Output = Outcome<(), Error>> + Send {
+        self.inner.ping(cx)
+    }
+
+    fn close(self, cx: &Cx) -> impl Future<Output = crate::Result<()>> + Send {
+        self.inner.close(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{QueryError, QueryErrorKind};
+    use asupersync::runtime::RuntimeBuilder;
+    use std::sync::Arc;
+
+    /// Shared flag the probe sets to release the "locked" statement.
+    #[derive(Default)]
+    struct Gate {
+        open: bool,
+        waker: Option<Waker>,
+    }
+
+    fn lock_timeout() -> Error {
+        Error::Query(QueryError {
+            kind: QueryErrorKind::Timeout,
+            sql: None,
+            sqlstate: Some("55P03".to_string()),
+            message: "canceling statement due to lock timeout".to_string(),
+            detail: None,
+            hint: None,
+            position: None,
+            source: None,
+        })
+    }
+
+    #[test]
+    fn test_slow_statement_reports_blockers() {
+        let rt = RuntimeBuilder::current_thread()
+            .build()
+            .expect("create asupersync runtime");
+        let gate = Arc::new(Mutex::new(Gate::default()));
+
+        let (outcome, found) = rt.block_on(async {
+            let statement = std::future::poll_fn(|task| {
+                let mut gate = gate.lock().unwrap();
+                if gate.open {
+                    Poll::Ready(Outcome::<u64, Error>::Err(lock_timeout()))
+                } else {
+                    gate.waker = Some(task.waker().clone());
+                    Poll::Pending
+                }
+            });
+            let probe = || async {
+                let mut gate = gate.lock().unwrap();
+                gate.open = true;
+                if let Some(waker) = gate.waker.take() {
+                    waker.wake();
+                }
+                vec![LockBlocker {
+                    pid: 42,
+                    query: Some("LOCK TABLE accounts".to_string()),
+                }]
+            };
+            watch_statement(Duration::from_millis(10), statement, probe).await
+        });
+
+        assert!(matches!(outcome, Outcome::Err(_)));
+        let (waited, blockers) = found.expect("probe ran");
+        assert!(waited >= Duration::from_millis(10));
+        assert_eq!(blockers[0].pid, 42);
+
+        let report = LockWaitReport {
+            sql: "UPDATE accounts SET balance = 0".to_string(),
+            waited: Duration::from_millis(15),
+            blockers,
+        };
+        assert_eq!(
+            report.describe(),
+            "waited 15ms, blocked by pid 42 (LOCK TABLE accounts)"
+        );
+    }
+
+    #[test]
+    fn test_fast_statement_skips_probe() {
+        let rt = RuntimeBuilder::current_thread()
+            .build()
+            .expect("create asupersync runtime");
+        let (outcome, found) = rt.block_on(async {
+            watch_statement(
+                Duration::from_secs(60),
+                async { Outcome::<u64, Error>::Ok(1) },
+                || async { Vec::new() },
+            )
+            .await
+        });
+        assert!(matches!(outcome, Outcome::Ok(1)));
+        assert!(found.is_none());
+    }
+
+    #[test]
+    fn test_lock_queries_per_dialect() {
+        assert!(
+            blockers_sql(Dialect::Postgres)
+                .unwrap()
+                .contains("pg_blocking_pids($1)")
+        );
+        assert!(
+            blockers_sql(Dialect::Mysql)
+                .unwrap()
+                .contains("sys.innodb_lock_waits")
+        );
+        assert_eq!(blockers_sql(Dialect::Sqlite), None);
+        assert_eq!(backend_pid_sql(Dialect::Sqlite), None);
+    }
+}
@@ -5,7 +5,8 @@
 //! `#[derive(Model)]` macro from `sqlmodel-macros`.
 
 use crate::Result;
-use crate::field::{FieldInfo, InheritanceInfo};
+use crate::error::ValidationError;
+use crate::field::{FieldInfo, IndexDef, InheritanceInfo, UniqueConstraintDef};
 use crate::relationship::RelationshipInfo;
 use crate::row::Row;
 use crate::value::Value;
@@ -22,6 +23,22 @@ pub enum ExtraFieldsBehavior {
     Allow,
 }
 
+/// How a model participates in the session's read-through entity cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CachePolicy {
+    /// How long a cached row stays valid; `None` defers to the cache backend.
+    pub ttl: Option<std::time::Duration>,
+}
+
+/// One column of a model's default ordering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrderByColumn {
+    /// Column name.
+    pub column: &'static str,
+    /// Sort descending instead of ascending.
+    pub descending: bool,
+}
+
 /// Model-level configuration matching Pydantic's model_config.
 ///
 /// This struct holds configuration options that affect model behavior
@@ -169,6 +186,46 @@ pub trait Model: Sized + Send + Sync {
     /// no relationships can rely on the default empty slice.
     const RELATIONSHIPS: &'static [RelationshipInfo] = &[];
 
+    /// Table-level (multi-column) unique constraints.
+    ///
+    /// Populated from `#[sqlmodel(unique_together(...))]`. Single-column
+    /// uniqueness stays on [`FieldInfo::unique`].
+    const UNIQUE_CONSTRAINTS: &'static [UniqueConstraintDef] = &[];
+
+    /// Table-level indexes, including composite, unique and partial ones.
+    ///
+    /// Populated from `#[sqlmodel(index(columns(...), ...))]`.
+    const INDEXES: &'static [IndexDef] = &[];
+
+    /// Table comment, from `#[sqlmodel(comment = "...")]`.
+    ///
+    /// Emitted as `COMMENT ON TABLE` (PostgreSQL) or a `COMMENT=` table option
+    /// (MySQL) when the table is created or migrated. Column comments live on
+    /// [`FieldInfo::column_comment`].
+    const TABLE_COMMENT: Option<&'static str> = None;
+
+    /// History table receiving a before-image of each updated or deleted row.
+    ///
+    /// Populated from `#[sqlmodel(history_table = "...")]`. When set, session
+    /// flush copies the current row into this table before changing it. The
+    /// history table has the model's columns plus [`HISTORY_VALID_TO_COLUMN`]
+    /// and [`HISTORY_OPERATION_COLUMN`].
+    const HISTORY_TABLE: Option<&'static str> = None;
+
+    /// Whether the database itself versions this table (MariaDB
+    /// `WITH SYSTEM VERSIONING`), from `#[sqlmodel(system_versioned)]`.
+    const SYSTEM_VERSIONED: bool = false;
+
+    /// Entity cache settings, from `#[sqlmodel(cache)]` or
+    /// `#[sqlmodel(cache(ttl = "60s"))]`.
+    ///
+    /// `None` keeps the model out of the session's entity cache entirely.
+    const CACHE: Option<CachePolicy> = None;
+
+    /// Ordering applied by `select!` when the query sets none, from
+    /// `#[sqlmodel(order_by = "created_at DESC, id")]`.
+    const DEFAULT_ORDER_BY: &'static [OrderByColumn] = &[];
+
     /// Inheritance metadata for this model.
     ///
     /// Returns information about table inheritance if this model participates
@@ -181,6 +238,29 @@ pub trait Model: Sized + Send + Sync {
     /// Get field metadata for all columns.
     fn fields() -> &'static [FieldInfo];
 
+    /// Column names to load for this model.
+    ///
+    /// Excludes computed fields, which have no backing column, and deferred
+    /// columns, which are loaded on demand. Loaders use this to emit explicit
+    /// column lists instead of `SELECT *`, so columns added to the table
+    /// concurrently are ignored and wide tables are not over-fetched.
+    fn select_columns() -> Vec<&'static str> {
+        Self::fields()
+            .iter()
+            .filter(|f| !f.computed && !f.deferred)
+            .map(|f| f.column_name)
+            .collect()
+    }
+
+    /// Column names marked `#[sqlmodel(deferred)]`.
+    fn deferred_columns() -> Vec<&'static str> {
+        Self::fields()
+            .iter()
+            .filter(|f| !f.computed && f.deferred)
+            .map(|f| f.column_name)
+            .collect()
+    }
+
     /// Convert this model instance to a row of values.
     fn to_row(&self) -> Vec<(&'static str, Value)>;
 
@@ -214,6 +294,16 @@ pub trait Model: Sized + Send + Sync {
         ModelConfig::new()
     }
 
+    /// Run this instance's validation rules.
+    ///
+    /// `#[derive(Model)]` forwards to [`Validate`](crate::validate::Validate)
+    /// when the struct carries `#[validate(...)]` attributes; otherwise every
+    /// instance is valid. The session calls this before writing when
+    /// `SessionConfig::validate_on_flush` is set.
+    fn validate_instance(&self) -> std::result::Result<(), ValidationError> {
+        Ok(())
+    }
+
     /// The shard key field name for horizontal sharding.
     ///
     /// Returns `None` if the model doesn't use sharding. When set,
@@ -231,6 +321,16 @@ pub trait Model: Sized + Send + Sync {
     }
 }
 
+/// History table column holding the time a row image stopped being current.
+pub const HISTORY_VALID_TO_COLUMN: &str = "history_valid_to";
+
+/// History table column holding the operation that ended the row image
+/// (`"INSERT"`, `"UPDATE"` or `"DELETE"`).
+///
+/// `"INSERT"` rows mark the moment a row started to exist; their image is the
+/// inserted row.
+pub const HISTORY_OPERATION_COLUMN: &str = "history_operation";
+
 /// Marker trait for models that support automatic ID generation.
 pub trait AutoIncrement: Model {
     /// Set the auto-generated ID after insert.
@@ -0,0 +1,336 @@
+//! Runtime table prefixes and schema qualification.
+//!
+//! A [`TableNamespace`] maps the table names baked into models
+//! (`#[sqlmodel(table = "heroes")]`) to the tables a deployment actually
+//! uses: `test_heroes` with a prefix, `"analytics"."heroes"` with a schema, or
+//! both. Model code does not change.
+//!
+//! The namespace rewrites SQL text, so it applies to everything that produces
+//! SQL: install it as a [`StatementInterceptor`] on a pool
+//! (`Pool::with_interceptor`) or session (`Session::with_table_namespace`) for
+//! queries, pass it to `SchemaBuilder::namespace` for DDL, and to
+//! `Introspector::with_namespace` for introspection.
+//!
+//! Only registered tables are rewritten ([`model`](TableNamespace::model),
+//! [`table`](TableNamespace::table)), wherever they appear as an identifier
+//! outside string literals and comments, except as the column part of a
+//! qualified name. A column that has the same name as a registered table is
+//! rewritten too, so avoid registering such tables. With a prefix, index names
+//! in `CREATE INDEX` / `DROP INDEX` are prefixed as well so that prefixed
+//! copies of a schema do not collide.
+//!
+//! On Postgres the schema can instead be selected with `search_path`; leave
+//! the namespace without a schema and run [`TableNamespace::search_path_sql`]
+//! on each connection.
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use crate::interceptor::{Statement, StatementInterceptor};
+use crate::model::Model;
+
+/// A table prefix and/or schema applied to registered tables.
+#[derive(Debug, Clone, Default)]
+pub struct TableNamespace {
+    prefix: Option<String>,
+    schema: Option<String>,
+    tables: HashSet<String>,
+}
+
+impl TableNamespace {
+    /// A namespace that changes nothing until a prefix or schema is set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prefix every registered table name with `prefix`.
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Qualify every registered table with `schema`.
+    pub fn schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = Some(schema.into());
+        self
+    }
+
+    /// Register the table of `M`, and its history table if it has one.
+    pub fn model<M: Model>(mut self) -> Self {
+        self.tables.insert(M::TABLE_NAME.to_string());
+        if let Some(history) = M::HISTORY_TABLE {
+            self.tables.insert(history.to_string());
+        }
+        self
+    }
+
+    /// Register a table by name.
+    pub fn table(mut self, name: impl Into<String>) -> Self {
+        self.tables.insert(name.into());
+        self
+    }
+
+    /// Register a table by name, in place.
+    pub fn register(&mut self, name: impl Into<String>) {
+        self.tables.insert(name.into());
+    }
+
+    /// The configured prefix, if any.
+    pub fn table_prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    /// The configured schema, if any.
+    pub fn schema_name(&self) -> Option<&str> {
+        self.schema.as_deref()
+    }
+
+    /// Whether `name` is a registered table.
+    pub fn contains(&self, name: &str) -> bool {
+        self.tables.contains(name)
+    }
+
+    /// Whether the namespace leaves SQL unchanged.
+    pub fn is_identity(&self) -> bool {
+        self.prefix.is_none() && self.schema.is_none()
+    }
+
+    /// The physical (prefixed, unqualified) name of table `name`.
+    pub fn table_name(&self, name: &str) -> String {
+        match &self.prefix {
+            Some(prefix) => format!("{prefix}{name}"),
+            None => name.to_string(),
+        }
+    }
+
+    /// The model table name for physical table `physical`, or `None` if it
+    /// does not carry the prefix.
+    pub fn logical_name<'a>(&self, physical: &'a str) -> Option<&'a str> {
+        match &self.prefix {
+            Some(prefix) => physical.strip_prefix(prefix.as_str()),
+            None => Some(physical),
+        }
+    }
+
+    /// `SET search_path TO "schema", public` for Postgres connections, if a
+    /// schema is set.
+    pub fn search_path_sql(&self) -> Option<String> {
+        self.schema
+            .as_deref()
+            .map(|schema| format!("SET search_path TO {}, public", quote(schema, b'"')))
+    }
+
+    /// Rewrite registered table names in `sql`.
+    pub fn rewrite<'a>(&self, sql: &'a str) -> Cow<'a, str> {
+        if self.is_identity() || (self.tables.is_empty() && self.prefix.is_none()) {
+            return Cow::Borrowed(sql);
+        }
+
+        let bytes = sql.as_bytes();
+        let mut out = String::with_capacity(sql.len() + 32);
+        let mut copied = 0;
+        let mut pos = 0;
+        let mut after_dot = false;
+        let mut index_name_next = false;
+        let mut changed = false;
+
+        while pos < bytes.len() {
+            let b = bytes[pos];
+            let start = pos;
+            let (ident, quote_char) = match b {
+                b'\'' => {
+                    pos = skip_past(bytes, pos + 1, b"'");
+                    after_dot = false;
+                    continue;
+                }
+                b'-' if bytes.get(pos + 1) == Some(&b'-') => {
+                    pos = skip_past(bytes, pos, b"\n");
+                    continue;
+                }
+                b'/' if bytes.get(pos + 1) == Some(&b'*') => {
+                    pos = skip_past(bytes, pos + 2, b"*/");
+                    continue;
+                }
+                b'"' | b'`' => {
+                    pos = skip_past(bytes, pos + 1, &[b]);
+                    let end = pos.saturating_sub(1).max(start + 1);
+                    (&sql[start + 1..end], Some(b))
+                }
+                _ if b.is_ascii_alphabetic() || b == b'_' => {
+                    while pos < bytes.len()
+                        && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_')
+                    {
+                        pos += 1;
+                    }
+                    (&sql[start..pos], None)
+                }
+                b'.' => {
+                    after_dot = true;
+                    pos += 1;
+                    continue;
+                }
+                _ => {
+                    if !b.is_ascii_whitespace() {
+                        after_dot = false;
+                        index_name_next = false;
+                    }
+                    pos += 1;
+                    continue;
+                }
+            };
+
+            let replacement = if quote_char.is_none() && ident.eq_ignore_ascii_case("INDEX") {
+                index_name_next = true;
+                None
+            } else if quote_char.is_none()
+                && index_name_next
+                && ["IF", "NOT", "EXISTS", "CONCURRENTLY"]
+                    .iter()
+                    .any(|kw| ident.eq_ignore_ascii_case(kw))
+            {
+                None
+            } else if std::mem::take(&mut index_name_next) && !after_dot {
+                self.prefix
+                    .as_ref()
+                    .map(|prefix| requote(&format!("{prefix}{ident}"), quote_char))
+            } else if !after_dot && self.tables.contains(ident) {
+                let name = requote(&self.table_name(ident), quote_char);
+                Some(match &self.schema {
+                    Some(schema) => format!("{}.{name}", requote(schema, quote_char)),
+                    None => name,
+                })
+            } else {
+                None
+            };
+            after_dot = false;
+
+            if let Some(replacement) = replacement {
+                out.push_str(&sql[copied..start]);
+                out.push_str(&replacement);
+                copied = pos;
+                changed = true;
+            }
+        }
+
+        if !changed {
+            return Cow::Borrowed(sql);
+        }
+        out.push_str(&sql[copied..]);
+        Cow::Owned(out)
+    }
+}
+
+impl StatementInterceptor for TableNamespace {
+    fn intercept(&self, statement: &mut Statement<'_>) -> crate::Result<()> {
+        if let Cow::Owned(sql) = self.rewrite(&statement.sql) {
+            statement.sql = Cow::Owned(sql);
+        }
+        Ok(())
+    }
+}
+
+/// Position just past the next `terminator` at or after `pos`, or the end.
+fn skip_past(bytes: &[u8], mut pos: usize, terminator: &[u8]) -> usize {
+    while pos < bytes.len() {
+        if bytes[pos..].starts_with(terminator) {
+            return pos + terminator.len();
+        }
+        pos += 1;
+    }
+    bytes.len()
+}
+
+fn quote(name: &str, quote_char: u8) -> String {
+    let q = char::from(quote_char);
+    let escaped = name.replace(q, &format!("{q}{q}"));
+    format!("{q}{escaped}{q}")
+}
+
+/// Quote `name` the way the identifier it replaces was quoted.
+fn requote(name: &str, quote_char: Option<u8>) -> String {
+    match quote_char {
+        Some(q) => quote(name, q),
+        None => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ns() -> TableNamespace {
+        TableNamespace::new().table("heroes").table("teams")
+    }
+
+    #[test]
+    fn test_identity_namespace_borrows() {
+        let sql = "SELECT * FROM heroes";
+        assert!(matches!(ns().rewrite(sql), Cow::Borrowed(_)));
+        let prefixed = ns().prefix("t1_");
+        assert!(matches!(prefixed.rewrite("SELECT 1"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_prefix_rewrites_quoted_and_bare_tables() {
+        let ns = ns().prefix("t1_");
+        assert_eq!(
+            ns.rewrite(
+                "SELECT \"heroes\".\"name\", teams.id FROM heroes \
+                 JOIN \"teams\" ON \"heroes\".\"team_id\" = teams.id \
+                 WHERE \"heroes\".\"name\" = 'heroes'"
+            ),
+            "SELECT \"t1_heroes\".\"name\", t1_teams.id FROM t1_heroes \
+             JOIN \"t1_teams\" ON \"t1_heroes\".\"team_id\" = t1_teams.id \
+             WHERE \"t1_heroes\".\"name\" = 'heroes'"
+        );
+        assert_eq!(
+            ns.rewrite("SELECT villains.heroes FROM villains"),
+            "SELECT villains.heroes FROM villains"
+        );
+    }
+
+    #[test]
+    fn test_schema_qualifies_tables() {
+        let ns = ns().schema("analytics");
+        assert_eq!(
+            ns.rewrite("INSERT INTO \"heroes\" (\"name\") VALUES ($1)"),
+            "INSERT INTO \"analytics\".\"heroes\" (\"name\") VALUES ($1)"
+        );
+        assert_eq!(
+            ns.rewrite("DELETE FROM `teams` WHERE `teams`.`id` = ?"),
+            "DELETE FROM `analytics`.`teams` WHERE `analytics`.`teams`.`id` = ?"
+        );
+        assert_eq!(
+            ns.rewrite("SELECT * FROM public.heroes"),
+            "SELECT * FROM public.heroes"
+        );
+    }
+
+    #[test]
+    fn test_prefix_applies_to_index_names() {
+        let ns = ns().prefix("t1_").schema("app");
+        assert_eq!(
+            ns.rewrite(
+                "CREATE UNIQUE INDEX IF NOT EXISTS \"idx_heroes_name\" ON \"heroes\" (\"name\")"
+            ),
+            "CREATE UNIQUE INDEX IF NOT EXISTS \"t1_idx_heroes_name\" ON \"app\".\"t1_heroes\" (\"name\")"
+        );
+        assert_eq!(
+            ns.rewrite("DROP INDEX idx_heroes_name"),
+            "DROP INDEX t1_idx_heroes_name"
+        );
+    }
+
+    #[test]
+    fn test_logical_name_and_search_path() {
+        let ns = ns().prefix("t1_").schema("my\"schema");
+        assert_eq!(ns.table_name("heroes"), "t1_heroes");
+        assert_eq!(ns.logical_name("t1_heroes"), Some("heroes"));
+        assert_eq!(ns.logical_name("heroes"), None);
+        assert_eq!(
+            ns.search_path_sql().as_deref(),
+            Some("SET search_path TO \"my\"\"schema\", public")
+        );
+        assert_eq!(TableNamespace::new().search_path_sql(), None);
+    }
+}
@@ -0,0 +1,313 @@
+//! Borrowed query parameters.
+//!
+//! [`Connection`](crate::Connection) methods take `&[Value]`, so binding a large
+//! string or blob that the caller already owns means cloning it into a
+//! [`Value`] first. [`ValueRef`] is the borrowed counterpart of [`Value`], and
+//! [`ToParams`] turns slices of either into `ValueRef`s without copying their
+//! payloads. [`Connection::query_params`](crate::Connection::query_params) and
+//! [`Connection::execute_params`](crate::Connection::execute_params) accept any
+//! `ToParams`; drivers that can bind borrowed data override them, and the
+//! default implementations fall back to owned values.
+//!
+//! ```ignore
+//! let body: String = load_document();
+//! conn.execute_params(
+//!     &cx,
+//!     "UPDATE docs SET body = $1 WHERE id = $2",
+//!     &[ValueRef::from(&body), ValueRef::from(7_i64)],
+//! )
+//! .await?;
+//! ```
+
+use crate::value::Value;
+
+/// A borrowed SQL value.
+///
+/// Mirrors [`Value`] variant for variant; text, bytes, JSON and arrays borrow
+/// from the caller instead of owning their data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValueRef<'a> {
+    /// NULL value
+    Null,
+    /// Boolean value
+    Bool(bool),
+    /// 8-bit signed integer
+    TinyInt(i8),
+    /// 16-bit signed integer
+    SmallInt(i16),
+    /// 32-bit signed integer
+    Int(i32),
+    /// 64-bit signed integer
+    BigInt(i64),
+    /// 32-bit floating point
+    Float(f32),
+    /// 64-bit floating point
+    Double(f64),
+    /// Arbitrary precision decimal (as string)
+    Decimal(&'a str),
+    /// Text string
+    Text(&'a str),
+    /// Binary data
+    Bytes(&'a [u8]),
+    /// Date (days since epoch)
+    Date(i32),
+    /// Time (microseconds since midnight)
+    Time(i64),
+    /// Timestamp (microseconds since epoch)
+    Timestamp(i64),
+    /// Timestamp with timezone (microseconds since epoch, UTC)
+    TimestampTz(i64),
+    /// UUID (as 16 bytes)
+    Uuid([u8; 16]),
+    /// JSON value
+    Json(&'a serde_json::Value),
+    /// Array of values
+    Array(&'a [Value]),
+    /// SQL DEFAULT keyword
+    Default,
+}
+
+impl ValueRef<'_> {
+    /// Copy the borrowed data into an owned [`Value`].
+    pub fn to_value(self) -> Value {
+        match self {
+            ValueRef::Null => Value::Null,
+            ValueRef::Bool(v) => Value::Bool(v),
+            ValueRef::TinyInt(v) => Value::TinyInt(v),
+            ValueRef::SmallInt(v) => Value::SmallInt(v),
+            ValueRef::Int(v) => Value::Int(v),
+            ValueRef::BigInt(v) => Value::BigInt(v),
+            ValueRef::Float(v) => Value::Float(v),
+            ValueRef::Double(v) => Value::Double(v),
+            ValueRef::Decimal(s) => Value::Decimal(s.to_string()),
+            ValueRef::Text(s) => Value::Text(s.to_string()),
+            ValueRef::Bytes(b) => Value::Bytes(b.to_vec()),
+            ValueRef::Date(v) => Value::Date(v),
+            ValueRef::Time(v) => Value::Time(v),
+            ValueRef::Timestamp(v) => Value::Timestamp(v),
+            ValueRef::TimestampTz(v) => Value::TimestampTz(v),
+            ValueRef::Uuid(v) => Value::Uuid(v),
+            ValueRef::Json(j) => Value::Json(j.clone()),
+            ValueRef::Array(items) => Value::Array(items.to_vec()),
+            ValueRef::Default => Value::Default,
+        }
+    }
+}
+
+impl Value {
+    /// Borrow this value as a [`ValueRef`] without copying its data.
+    pub fn as_value_ref(&self) -> ValueRef<'_> {
+        match self {
+            Value::Null => ValueRef::Null,
+            Value::Bool(v) => ValueRef::Bool(*v),
+            Value::TinyInt(v) => ValueRef::TinyInt(*v),
+            Value::SmallInt(v) => ValueRef::SmallInt(*v),
+            Value::Int(v) => ValueRef::Int(*v),
+            Value::BigInt(v) => ValueRef::BigInt(*v),
+            Value::Float(v) => ValueRef::Float(*v),
+            Value::Double(v) => ValueRef::Double(*v),
+            Value::Decimal(s) => ValueRef::Decimal(s),
+            Value::Text(s) => ValueRef::Text(s),
+            Value::Bytes(b) => ValueRef::Bytes(b),
+            Value::Date(v) => ValueRef::Date(*v),
+            Value::Time(v) => ValueRef::Time(*v),
+            Value::Timestamp(v) => ValueRef::Timestamp(*v),
+            Value::TimestampTz(v) => ValueRef::TimestampTz(*v),
+            Value::Uuid(v) => ValueRef::Uuid(*v),
+            Value::Json(j) => ValueRef::Json(j),
+            Value::Array(items) => ValueRef::Array(items),
+            Value::Default => ValueRef::Default,
+        }
+    }
+}
+
+impl From<ValueRef<'_>> for Value {
+    fn from(v: ValueRef<'_>) -> Self {
+        v.to_value()
+    }
+}
+
+impl<'a> From<&'a Value> for ValueRef<'a> {
+    fn from(v: &'a Value) -> Self {
+        v.as_value_ref()
+    }
+}
+
+impl<'a> From<&'a str> for ValueRef<'a> {
+    fn from(v: &'a str) -> Self {
+        ValueRef::Text(v)
+    }
+}
+
+impl<'a> From<&'a String> for ValueRef<'a> {
+    fn from(v: &'a String) -> Self {
+        ValueRef::Text(v)
+    }
+}
+
+impl<'a> From<&'a [u8]> for ValueRef<'a> {
+    fn from(v: &'a [u8]) -> Self {
+        ValueRef::Bytes(v)
+    }
+}
+
+impl<'a> From<&'a Vec<u8>> for ValueRef<'a> {
+    fn from(v: &'a Vec<u8>) -> Self {
+        ValueRef::Bytes(v)
+    }
+}
+
+impl<'a> From<&'a serde_json::Value> for ValueRef<'a> {
+    fn from(v: &'a serde_json::Value) -> Self {
+        ValueRef::Json(v)
+    }
+}
+
+impl<'a, T> From<Option<T>> for ValueRef<'a>
+where
+    T: Into<ValueRef<'a>>,
+{
+    fn from(v: Option<T>) -> Self {
+        v.map_or(ValueRef::Null, Into::into)
+    }
+}
+
+macro_rules! value_ref_from_scalar {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$ty> for ValueRef<'_> {
+                fn from(v: $ty) -> Self {
+                    ValueRef::$variant(v)
+                }
+            }
+        )*
+    };
+}
+
+value_ref_from_scalar! {
+    bool => Bool,
+    i8 => TinyInt,
+    i16 => SmallInt,
+    i32 => Int,
+    i64 => BigInt,
+    f32 => Float,
+    f64 => Double,
+    [u8; 16] => Uuid,
+}
+
+/// A parameter list that can be bound without copying its data.
+///
+/// Implemented for slices, arrays and vectors of [`Value`] and [`ValueRef`].
+pub trait ToParams {
+    /// Borrow the parameters in bind order.
+    fn to_params(&self) -> Vec<ValueRef<'_>>;
+}
+
+impl ToParams for [Value] {
+    fn to_params(&self) -> Vec<ValueRef<'_>> {
+        self.iter().map(Value::as_value_ref).collect()
+    }
+}
+
+impl ToParams for [ValueRef<'_>] {
+    fn to_params(&self) -> Vec<ValueRef<'_>> {
+        self.to_vec()
+    }
+}
+
+impl<T> ToParams for Vec<T>
+where
+    [T]: ToParams,
+{
+    fn to_params(&self) -> Vec<ValueRef<'_>> {
+        self.as_slice().to_params()
+    }
+}
+
+impl<T, const N: usize> ToParams for [T; N]
+where
+    [T]: ToParams,
+{
+    fn to_params(&self) -> Vec<ValueRef<'_>> {
+        self.as_slice().to_params()
+    }
+}
+
+impl<T: ToParams + ?Sized> ToParams for &T {
+    fn to_params(&self) -> Vec<ValueRef<'_>> {
+        (**self).to_params()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_ref_borrows_payloads() {
+        let value = Value::Text("x".repeat(1024));
+        let Value::Text(owned) = &value else {
+            unreachable!()
+        };
+        let ValueRef::Text(borrowed) = value.as_value_ref() else {
+            panic!("expected text");
+        };
+        assert!(std::ptr::eq(owned.as_str(), borrowed));
+        assert_eq!(value.as_value_ref().to_value(), value);
+    }
+
+    #[test]
+    fn test_value_ref_round_trips_every_variant() {
+        let values = vec![
+            Value::Null,
+            Value::Bool(true),
+            Value::TinyInt(1),
+            Value::SmallInt(2),
+            Value::Int(3),
+            Value::BigInt(4),
+            Value::Float(1.5),
+            Value::Double(2.5),
+            Value::Decimal("1.10".into()),
+            Value::Text("t".into()),
+            Value::Bytes(vec![1, 2]),
+            Value::Date(5),
+            Value::Time(6),
+            Value::Timestamp(7),
+            Value::TimestampTz(8),
+            Value::Uuid([9; 16]),
+            Value::Json(serde_json::json!({"k": [1]})),
+            Value::Array(vec![Value::Int(1)]),
+            Value::Default,
+        ];
+        let refs = values.to_params();
+        assert_eq!(refs.len(), values.len());
+        let back: Vec<Value> = refs.into_iter().map(Value::from).collect();
+        assert_eq!(back, values);
+    }
+
+    #[test]
+    fn test_conversions() {
+        let name = String::from("Ada");
+        let blob = vec![0_u8, 1];
+        let missing: Option<&str> = None;
+        let params = [
+            ValueRef::from(&name),
+            ValueRef::from(&blob),
+            ValueRef::from(42_i64),
+            ValueRef::from(Some("x")),
+            ValueRef::from(missing),
+        ];
+        assert_eq!(
+            params.to_params(),
+            [
+                ValueRef::Text("Ada"),
+                ValueRef::Bytes(&[0, 1]),
+                ValueRef::BigInt(42),
+                ValueRef::Text("x"),
+                ValueRef::Null,
+            ]
+        );
+        let owned: &[Value] = &[Value::Int(1)];
+        assert_eq!(owned.to_params(), [ValueRef::Int(1)]);
+    }
+}
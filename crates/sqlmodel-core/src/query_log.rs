@@ -0,0 +1,650 @@
+//! Structured query logging.
+//!
+//! A [`QueryLogger`] is called once per statement with the SQL, its
+//! parameters, how long it took, and how it ended. Loggers are plugged into a
+//! connection pool (`Pool::with_query_logger`) or wrapped around any single
+//! connection with [`LoggedConnection`], which is what a `Session` uses.
+//!
+//! Parameters frequently carry personal data, so every built-in sink applies a
+//! [`RedactionPolicy`] before anything is written:
+//!
+//! - [`RedactionPolicy::Raw`] logs parameter values as-is
+//! - [`RedactionPolicy::Hashed`] logs a stable fingerprint per value, so equal
+//!   values can be correlated without being revealed
+//! - [`RedactionPolicy::Omitted`] (the default) drops parameters entirely
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use sqlmodel_core::query_log::{JsonLinesQueryLogger, LoggedConnection, RedactionPolicy};
+//!
+//! let logger = Arc::new(JsonLinesQueryLogger::create("queries.jsonl", RedactionPolicy::Hashed)?);
+//! let conn = LoggedConnection::new(conn, logger);
+//! ```
+
+use std::fs::OpenOptions;
+use std::future::Future;
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::connection::{Connection, IsolationLevel, PreparedStatement, QueryResult};
+use crate::error::Error;
+use crate::row::Row;
+use crate::value::Value;
+use crate::{Cx, Dialect, Outcome, SqlDialect};
+
+/// How a logged statement finished.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryStatus {
+    /// The statement succeeded, returning or affecting `rows` rows.
+    Ok {
+        /// Rows returned (queries) or affected (statements).
+        rows: u64,
+    },
+    /// The statement failed with the given error message.
+    Error(String),
+    /// The statement was cancelled.
+    Cancelled,
+    /// The statement panicked.
+    Panicked,
+}
+
+impl QueryStatus {
+    /// Short name of the status (`ok`, `error`, `cancelled`, `panicked`).
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            QueryStatus::Ok { .. } => "ok",
+            QueryStatus::Error(_) => "error",
+            QueryStatus::Cancelled => "cancelled",
+            QueryStatus::Panicked => "panicked",
+        }
+    }
+}
+
+/// One executed statement, as handed to a [`QueryLogger`].
+#[derive(Debug, Clone)]
+pub struct QueryLogEntry<'a> {
+    /// The SQL text.
+    pub sql: &'a str,
+    /// Bound parameters, unredacted. Sinks apply their [`RedactionPolicy`].
+    pub params: &'a [Value],
+    /// Wall-clock time spent executing the statement.
+    pub duration: Duration,
+    /// How the statement finished.
+    pub status: QueryStatus,
+}
+
+impl QueryLogEntry<'_> {
+    /// Render the entry as a JSON object with parameters redacted by `policy`.
+    pub fn to_json(&self, policy: RedactionPolicy) -> serde_json::Value {
+        let mut object = serde_json::Map::new();
+        object.insert("sql".to_string(), self.sql.into());
+        if let Some(params) = policy.redact(self.params) {
+            object.insert("params".to_string(), params.into());
+        }
+        object.insert(
+            "duration_ms".to_string(),
+            (self.duration.as_secs_f64() * 1000.0).into(),
+        );
+        object.insert("status".to_string(), self.status.as_str().into());
+        match &self.status {
+            QueryStatus::Ok { rows } => {
+                object.insert("rows".to_string(), (*rows).into());
+            }
+            QueryStatus::Error(message) => {
+                object.insert("error".to_string(), message.as_str().into());
+            }
+            QueryStatus::Cancelled | QueryStatus::Panicked => {}
+        }
+        serde_json::Value::Object(object)
+    }
+}
+
+/// Controls how statement parameters appear in logs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RedactionPolicy {
+    /// Log parameter values unchanged.
+    Raw,
+    /// Replace each value with a stable 64-bit FNV-1a fingerprint.
+    ///
+    /// Fingerprints make equal values recognisable across log lines. They are
+    /// not a cryptographic hash; low-entropy values can be recovered by
+    /// guessing, so use [`Omitted`](Self::Omitted) where that matters.
+    Hashed,
+    /// Do not log parameters at all.
+    #[default]
+    Omitted,
+}
+
+impl RedactionPolicy {
+    /// Apply the policy to `params`; `None` means parameters are omitted.
+    pub fn redact(self, params: &[Value]) -> Option<Vec<serde_json::Value>> {
+        match self {
+            RedactionPolicy::Raw => Some(
+                params
+                    .iter()
+                    .map(|value| serde_json::to_value(value).unwrap_or(serde_json::Value::Null))
+                    .collect(),
+            ),
+            RedactionPolicy::Hashed => Some(
+                params
+                    .iter()
+                    .map(|value| fingerprint(value).into())
+                    .collect(),
+            ),
+            RedactionPolicy::Omitted => None,
+        }
+    }
+}
+
+/// FNV-1a fingerprint of a value's JSON encoding.
+fn fingerprint(value: &Value) -> String {
+    let encoded = serde_json::to_string(value).unwrap_or_default();
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in encoded.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("fnv1a:{hash:016x}")
+}
+
+/// Receives every statement executed through a logged connection or pool.
+///
+/// `log` runs inline on the query path, so implementations should be cheap
+/// and must never panic.
+pub trait QueryLogger: Send + Sync {
+    /// Record one executed statement.
+    fn log(&self, entry: &QueryLogEntry<'_>);
+}
+
+/// Emits query entries as `tracing` events under the `sqlmodel::query` target.
+///
+/// Successful statements are logged at `DEBUG`, failures at `WARN`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingQueryLogger {
+    policy: RedactionPolicy,
+}
+
+impl TracingQueryLogger {
+    /// Create a tracing sink with the given redaction policy.
+    pub const fn new(policy: RedactionPolicy) -> Self {
+        Self { policy }
+    }
+}
+
+impl QueryLogger for TracingQueryLogger {
+    fn log(&self, entry: &QueryLogEntry<'_>) {
+        let params = self
+            .policy
+            .redact(entry.params)
+            .map(|params| serde_json::Value::Array(params).to_string());
+        let duration_ms = entry.duration.as_secs_f64() * 1000.0;
+        match &entry.status {
+            QueryStatus::Ok { rows } => tracing::debug!(
+                target: "sqlmodel::query",
+                sql = entry.sql,
+                params = params.as_deref(),
+                duration_ms,
+                rows,
+                "query executed"
+            ),
+            QueryStatus::Error(error) => tracing::warn!(
+                target: "sqlmodel::query",
+                sql = entry.sql,
+                params = params.as_deref(),
+                duration_ms,
+                error = error.as_str(),
+                "query failed"
+            ),
+            QueryStatus::Cancelled | QueryStatus::Panicked => tracing::warn!(
+                target: "sqlmodel::query",
+                sql = entry.sql,
+                params = params.as_deref(),
+                duration_ms,
+                status = entry.status.as_str(),
+                "query interrupted"
+            ),
+        }
+    }
+}
+
+/// Writes one JSON object per statement to a file or any other writer.
+///
+/// Write failures are reported through `tracing` and otherwise ignored, so a
+/// full disk never fails the query being logged.
+pub struct JsonLinesQueryLogger {
+    writer: Mutex<Box<dyn Write + Send>>,
+    policy: RedactionPolicy,
+}
+
+impl JsonLinesQueryLogger {
+    /// Log to an arbitrary writer.
+    pub fn new<W: Write + Send + 'static>(writer: W, policy: RedactionPolicy) -> Self {
+        Self {
+            writer: Mutex::new(Box::new(writer)),
+            policy,
+        }
+    }
+
+    /// Log to `path`, creating the file or appending to it.
+    pub fn create(path: impl AsRef<Path>, policy: RedactionPolicy) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::new(file, policy))
+    }
+}
+
+impl std::fmt::Debug for JsonLinesQueryLogger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsonLinesQueryLogger")
+            .field("policy", &self.policy)
+            .finish_non_exhaustive()
+    }
+}
+
+impl QueryLogger for JsonLinesQueryLogger {
+    fn log(&self, entry: &QueryLogEntry<'_>) {
+        let line = entry.to_json(self.policy).to_string();
+        let mut writer = match self.writer.lock() {
+            Ok(writer) => writer,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if let Err(e) = writeln!(writer, "{line}").and_then(|()| writer.flush()) {
+            tracing::warn!(target: "sqlmodel::query", error = %e, "failed to write query log");
+        }
+    }
+}
+
+/// Await `fut` and report the statement to `logger`, if there is one.
+///
+/// `rows` extracts the row count from a successful result. Used by
+/// [`LoggedConnection`] and by pooled connections.
+pub async fn log_query<T, R, F>(
+    logger: Option<&dyn QueryLogger>,
+    sql: &str,
+    params: &[Value],
+    rows: R,
+    fut: F,
+) -> Outcome<T, Error>
+where
+    R: FnOnce(&T) -> u64,
+    F: Future<Output = Outcome<T, Error>>,
+{
+    let Some(logger) = logger else {
+        return fut.await;
+    };
+
+    let started = Instant::now();
+    let outcome = fut.await;
+    let status = match &outcome {
+        Outcome::Ok(value) => QueryStatus::Ok { rows: rows(value) },
+        Outcome::Err(e) => QueryStatus::Error(e.to_string()),
+        Outcome::Cancelled(_) => QueryStatus::Cancelled,
+        Outcome::Panicked(_) => QueryStatus::Panicked,
+    };
+    logger.log(&QueryLogEntry {
+        sql,
+        params,
+        duration: started.elapsed(),
+        status,
+    });
+    outcome
+}
+
+/// Flatten a batch into one loggable statement: SQL joined with `;\n` and all
+/// parameters in order.
+pub fn batch_entry(statements: &[(String, Vec<Value>)]) -> (String, Vec<Value>) {
+    let sql = statements
+        .iter()
+        .map(|(sql, _)| sql.as_str())
+        .collect::<Vec<_>>()
+        .join(";\n");
+    let params = statements
+        .iter()
+        .flat_map(|(_, params)| params.iter().cloned())
+        .collect();
+    (sql, params)
+}
+
+/// A connection that reports every statement to a [`QueryLogger`].
+///
+/// Statements issued on a transaction object returned by
+/// [`begin`](Connection::begin) go straight to the inner connection's
+/// transaction and are not logged.
+pub struct LoggedConnection<C> {
+    inner: C,
+    logger: Arc<dyn QueryLogger>,
+}
+
+impl<C> LoggedConnection<C> {
+    /// Wrap `inner`, logging its statements to `logger`.
+    pub fn new(inner: C, logger: Arc<dyn QueryLogger>) -> Self {
+        Self { inner, logger }
+    }
+
+    /// The wrapped connection.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// The logger statements are reported to.
+    pub fn logger(&self) -> &Arc<dyn QueryLogger> {
+        &self.logger
+    }
+
+    /// Unwrap the inner connection.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<C: std::fmt::Debug> std::fmt::Debug for LoggedConnection<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoggedConnection")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: Connection> Connection for LoggedConnection<C> {
+    type Tx<'conn>
+        = C::Tx<'conn>
+    where
+        Self: 'conn;
+
+    fn dialect(&self) -> Dialect {
+        self.inner.dialect()
+    }
+
+    fn sql_dialect(&self) -> &'static dyn SqlDialect {
+        self.inner.sql_dialect()
+    }
+
+    fn query(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<Vec<Row>, Error>> + Send {
+        log_query(
+            Some(self.logger.as_ref()),
+            sql,
+            params,
+            |rows: &Vec<Row>| rows.len() as u64,
+            self.inner.query(cx, sql, params),
+        )
+    }
+
+    fn query_raw(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<QueryResult, Error>> + Send {
+        log_query(
+            Some(self.logger.as_ref()),
+            sql,
+            params,
+            |result: &QueryResult| result.len() as u64,
+            self.inner.query_raw(cx, sql, params),
+        )
+    }
+
+    fn query_one(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<Option<Row>, Error>> + Send {
+        log_query(
+            Some(self.logger.as_ref()),
+            sql,
+            params,
+            |row: &Option<Row>| u64::from(row.is_some()),
+            self.inner.query_one(cx, sql, params),
+        )
+    }
+
+    fn execute(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<u64, Error>> + Send {
+        log_query(
+            Some(self.logger.as_ref()),
+            sql,
+            params,
+            |count: &u64| *count,
+            self.inner.execute(cx, sql, params),
+        )
+    }
+
+    fn insert(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<i64, Error>> + Send {
+        log_query(
+            Some(self.logger.as_ref()),
+            sql,
+            params,
+            |_: &i64| 1,
+            self.inner.insert(cx, sql, params),
+        )
+    }
+
+    fn batch(
+        &self,
+        cx: &Cx,
+        statements: &[(String, Vec<Value>)],
+    ) -> impl Future<Output = Outcome<Vec<u64>, Error>> + Send {
+        let (sql, params) = batch_entry(statements);
+        let fut = self.inner.batch(cx, statements);
+        async move {
+            log_query(
+                Some(self.logger.as_ref()),
+                &sql,
+                &params,
+                |counts: &Vec<u64>| counts.iter().sum(),
+                fut,
+            )
+            .await
+        }
+    }
+
+    fn begin(&self, cx: &Cx) -> impl Future<Output = Outcome<Self::Tx<'_>, Error>> + Send {
+        self.inner.begin(cx)
+    }
+
+    fn begin_with(
+        &self,
+        cx: &Cx,
+        isolation: IsolationLevel,
+    ) -> impl Future<Output = Outcome<Self::Tx<'_>, Error>> + Send {
+        self.inner.begin_with(cx, isolation)
+    }
+
+    fn prepare(
+        &self,
+        cx: &Cx,
+        sql: &str,
+    ) -> impl Future<Output = Outcome<PreparedStatement, Error>> + Send {
+        self.inner.prepare(cx, sql)
+    }
+
+    fn query_prepared(
+        &self,
+        cx: &Cx,
+        stmt: &PreparedStatement,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<Vec<Row>, Error>> + Send {
+        log_query(
+            Some(self.logger.as_ref()),
+            stmt.sql(),
+            params,
+            |rows: &Vec<Row>| rows.len() as u64,
+            self.inner.query_prepared(cx, stmt, params),
+        )
+    }
+
+    fn execute_prepared(
+        &self,
+        cx: &Cx,
+        stmt: &PreparedStatement,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<u64, Error>> + Send {
+        log_query(
+            Some(self.logger.as_ref()),
+            stmt.sql(),
+            params,
+            |count: &u64| *count,
+            self.inner.execute_prepared(cx, stmt, params),
+        )
+    }
+
+    fn ping(&self, cx: &Cx) -> impl Future<Output = Outcome<(), Error>> + Send {
+        self.inner.ping(cx)
+    }
+
+    fn close(self, cx: &Cx) -> impl Future<Output = crate::Result<()>> + Send {
+        self.inner.close(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Collect(Mutex<Vec<String>>);
+
+    impl QueryLogger for Collect {
+        fn log(&self, entry: &QueryLogEntry<'_>) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(entry.to_json(RedactionPolicy::Raw).to_string());
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn entry<'a>(params: &'a [Value], status: QueryStatus) -> QueryLogEntry<'a> {
+        QueryLogEntry {
+            sql: "SELECT * FROM users WHERE email = $1",
+            params,
+            duration: Duration::from_millis(3),
+            status,
+        }
+    }
+
+    #[test]
+    fn test_redaction_policies() {
+        let params = [Value::Text("alice@example.com".to_string()), Value::Int(7)];
+
+        let raw = RedactionPolicy::Raw.redact(&params).unwrap();
+        assert!(raw[0].to_string().contains("alice@example.com"));
+
+        let hashed = RedactionPolicy::Hashed.redact(&params).unwrap();
+        assert_eq!(hashed.len(), 2);
+        assert!(!hashed[0].to_string().contains("alice"));
+        assert!(hashed[0].as_str().unwrap().starts_with("fnv1a:"));
+        assert_eq!(hashed, RedactionPolicy::Hashed.redact(&params).unwrap());
+        assert_ne!(hashed[0], hashed[1]);
+
+        assert_eq!(RedactionPolicy::Omitted.redact(&params), None);
+        assert_eq!(RedactionPolicy::default(), RedactionPolicy::Omitted);
+    }
+
+    #[test]
+    fn test_entry_to_json() {
+        let params = [Value::Text("alice@example.com".to_string())];
+
+        let json = entry(&params, QueryStatus::Ok { rows: 2 }).to_json(RedactionPolicy::Omitted);
+        assert_eq!(json["status"], "ok");
+        assert_eq!(json["rows"], 2);
+        assert!(json.get("params").is_none());
+        assert!(json["duration_ms"].as_f64().unwrap() >= 3.0);
+
+        let json =
+            entry(&params, QueryStatus::Error("boom".to_string())).to_json(RedactionPolicy::Hashed);
+        assert_eq!(json["status"], "error");
+        assert_eq!(json["error"], "boom");
+        assert_eq!(json["params"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_json_lines_logger_writes_one_line_per_entry() {
+        let buffer = SharedBuffer::default();
+        let logger = JsonLinesQueryLogger::new(buffer.clone(), RedactionPolicy::Hashed);
+        let params = [Value::Text("alice@example.com".to_string())];
+
+        logger.log(&entry(&params, QueryStatus::Ok { rows: 1 }));
+        logger.log(&entry(&params, QueryStatus::Cancelled));
+
+        let written = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = written.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(!written.contains("alice"));
+        let second: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(second["status"], "cancelled");
+    }
+
+    #[test]
+    fn test_log_query_reports_outcome() {
+        use asupersync::runtime::RuntimeBuilder;
+
+        let collect = Collect(Mutex::new(Vec::new()));
+        let logger: &dyn QueryLogger = &collect;
+        let rt = RuntimeBuilder::current_thread()
+            .build()
+            .expect("create asupersync runtime");
+        rt.block_on(async {
+            let params = [Value::Int(1)];
+            let outcome = log_query(
+                Some(logger),
+                "UPDATE t SET x = 1",
+                &params,
+                |count: &u64| *count,
+                async { Outcome::Ok(3) },
+            )
+            .await;
+            assert!(matches!(outcome, Outcome::Ok(3)));
+
+            let outcome: Outcome<u64, Error> = log_query(
+                Some(logger),
+                "UPDATE t SET x = 2",
+                &params,
+                |count: &u64| *count,
+                async { Outcome::Err(Error::Custom("boom".to_string())) },
+            )
+            .await;
+            assert!(matches!(outcome, Outcome::Err(_)));
+
+            let outcome = log_query(None, "SELECT 1", &[], |count: &u64| *count, async {
+                Outcome::Ok(1)
+            })
+            .await;
+            assert!(matches!(outcome, Outcome::Ok(1)));
+        });
+
+        let lines = collect.0.lock().unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("\"rows\":3"));
+        assert!(lines[1].contains("\"error\""));
+    }
+}
@@ -0,0 +1,191 @@
+//! Per-statement execution options.
+//!
+//! [`QueryOptions`] bound a single statement without touching the `Cx`
+//! budget, which covers the whole task, or the pool's `acquire_timeout`,
+//! which only covers waiting for a connection. Pass them to
+//! [`Connection::query_with`] and [`Connection::execute_with`], or attach
+//! them to a `Select`:
+//!
+//! ```rust,ignore
+//! let options = QueryOptions::new()
+//!     .timeout(Duration::from_secs(2))
+//!     .tag("monthly-report");
+//! let rows = conn.query_with(&cx, "SELECT ...", &[], &options).await?;
+//! let heroes = select!(Hero).options(options).all(&cx, &conn).await?;
+//! ```
+//!
+//! The timeout is enforced by the server where it has a setting for it:
+//!
+//! - PostgreSQL: `statement_timeout` is set for the statement and restored
+//!   afterwards
+//! - MySQL: `SELECT`s get a `MAX_EXECUTION_TIME` optimizer hint; MySQL has
+//!   no timeout for other statements
+//! - SQLite: the driver interrupts the statement once the timeout passes
+//!
+//! Connection wrappers use the default implementation, which issues the
+//! PostgreSQL and MySQL settings through the wrapper.
+
+use std::borrow::Cow;
+use std::future::Future;
+use std::time::Duration;
+
+use asupersync::{Cx, Outcome};
+
+use crate::connection::Connection;
+use crate::dialect::Dialect;
+use crate::error::Error;
+use crate::interceptor::Statement;
+use crate::value::Value;
+
+/// Options for running one statement.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryOptions {
+    /// Longest the statement may run on the server
+    pub timeout: Option<Duration>,
+    /// Rows to fetch per round trip, for drivers that fetch incrementally;
+    /// drivers that buffer whole results ignore it
+    pub fetch_size: Option<usize>,
+    /// Label prepended to the SQL as a comment, so the statement can be
+    /// found in `pg_stat_activity`, slow query logs and `SHOW PROCESSLIST`
+    pub tag: Option<String>,
+}
+
+impl QueryOptions {
+    /// Options that change nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bound the statement's run time.
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Fetch `rows` rows per round trip.
+    #[must_use]
+    pub fn fetch_size(mut self, rows: usize) -> Self {
+        self.fetch_size = Some(rows);
+        self
+    }
+
+    /// Label the statement with `tag`.
+    #[must_use]
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// The timeout in whole milliseconds, at least 1 so a tiny timeout is
+    /// not read as "no timeout" by the server.
+    pub fn timeout_millis(&self) -> Option<u64> {
+        self.timeout.map(|timeout| {
+            u64::try_from(timeout.as_millis())
+                .unwrap_or(u64::MAX)
+                .max(1)
+        })
+    }
+
+    /// `sql` with the tag comment and, on MySQL, the execution time hint.
+    pub fn statement_sql<'a>(&self, dialect: Dialect, sql: &'a str) -> Cow<'a, str> {
+        let mut statement = Statement::new(dialect, sql, &[]);
+        if dialect == Dialect::Mysql
+            && let Some(ms) = self.timeout_millis()
+            && statement.keyword().as_deref() == Some("SELECT")
+        {
+            let trimmed = sql.trim_start();
+            if trimmed.len() >= 6 && trimmed[..6].eq_ignore_ascii_case("SELECT") {
+                let at = sql.len() - trimmed.len() + 6;
+                statement.sql = Cow::Owned(format!(
+                    "{} /*+ MAX_EXECUTION_TIME({ms}) */{}",
+                    &sql[..at],
+                    &sql[at..]
+                ));
+            }
+        }
+        if let Some(tag) = &self.tag {
+            statement.prepend_comment(tag);
+        }
+        statement.sql
+    }
+}
+
+/// Run `statement` on `conn` with the PostgreSQL `statement_timeout` set to
+/// `options.timeout`, restoring the previous setting afterwards.
+///
+/// Other dialects run `statement` unchanged.
+pub async fn with_statement_timeout<C, T, F, Fut>(
+    conn: &C,
+    cx: &Cx,
+    options: &QueryOptions,
+    statement: F,
+) -> Outcome<T, Error>
+where
+    C: Connection + ?Sized,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Outcome<T, Error>>,
+{
+    let Some(ms) = options.timeout_millis() else {
+        return statement().await;
+    };
+    if conn.dialect() != Dialect::Postgres {
+        return statement().await;
+    }
+
+    let set = "SELECT current_setting('statement_timeout'), \
+               set_config('statement_timeout', $1, false)";
+    let previous = match conn
+        .query_one(cx, set, &[Value::Text(ms.to_string())])
+        .await
+    {
+        Outcome::Ok(row) => row
+            .and_then(|row| row.get(0).cloned())
+            .unwrap_or_else(|| Value::Text("0".to_string())),
+        Outcome::Err(e) => return Outcome::Err(e),
+        Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+        Outcome::Panicked(p) => return Outcome::Panicked(p),
+    };
+
+    let outcome = statement().await;
+
+    // After a failure inside a transaction the restore fails too, but the
+    // rollback then reverts the setting.
+    let restore = "SELECT set_config('statement_timeout', $1, false)";
+    match (outcome, conn.query_one(cx, restore, &[previous]).await) {
+        (Outcome::Ok(_), Outcome::Err(e)) => Outcome::Err(e),
+        (Outcome::Ok(_), Outcome::Cancelled(r)) => Outcome::Cancelled(r),
+        (Outcome::Ok(_), Outcome::Panicked(p)) => Outcome::Panicked(p),
+        (outcome, _) => outcome,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statement_sql_tags_and_hints() {
+        let options = QueryOptions::new()
+            .timeout(Duration::from_micros(10))
+            .tag("report */ x");
+        assert_eq!(options.timeout_millis(), Some(1));
+
+        assert_eq!(
+            options.statement_sql(Dialect::Mysql, "select * from t"),
+            "/* report * / x */ select /*+ MAX_EXECUTION_TIME(1) */ * from t"
+        );
+        assert_eq!(
+            options.statement_sql(Dialect::Postgres, "SELECT 1"),
+            "/* report * / x */ SELECT 1"
+        );
+        assert_eq!(
+            options.statement_sql(Dialect::Mysql, "UPDATE t SET a = 1"),
+            "/* report * / x */ UPDATE t SET a = 1"
+        );
+        assert!(matches!(
+            QueryOptions::new().statement_sql(Dialect::Mysql, "SELECT 1"),
+            Cow::Borrowed("SELECT 1")
+        ));
+    }
+}
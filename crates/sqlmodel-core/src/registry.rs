@@ -0,0 +1,402 @@
+//! Type-erased models for generic tooling.
+//!
+//! [`Model`] has associated constants and static methods, so it cannot be
+//! used as a trait object. [`ModelDyn`] is its object-safe counterpart,
+//! implemented for every model, which lets admin panels and generic
+//! repositories hold `Vec<Box<dyn ModelDyn>>` of mixed model types.
+//!
+//! [`ModelDescriptor`] carries the same metadata without an instance, and
+//! [`ModelRegistry`] maps table names to descriptors so tools such as
+//! `create_all` and the REPL can work from a list of models chosen at
+//! runtime:
+//!
+//! ```ignore
+//! let registry = ModelRegistry::new().register::<Hero>().register::<Team>();
+//! let hero = registry.get("heroes").unwrap().from_row(&row)?;
+//! println!("{} {:?}", hero.table_name(), hero.pk_values());
+//! ```
+//!
+//! Models declared with `#[sqlmodel(register)]` are collected at link time
+//! into [`ModelRegistry::global`], so schema tooling can discover every
+//! model in the binary without a hand-maintained list.
+//!
+//! `ModelDyn` method names overlap with [`Model`]'s, so it is not part of
+//! the prelude or the `sqlmodel` crate root; import it from this module only
+//! where models are handled as trait objects.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use crate::Result;
+use crate::field::{FieldInfo, IndexDef, UniqueConstraintDef};
+use crate::model::Model;
+use crate::relationship::RelationshipInfo;
+use crate::row::Row;
+use crate::value::Value;
+
+/// Object-safe view of a [`Model`] instance.
+pub trait ModelDyn: Send + Sync {
+    /// The model's table name.
+    fn table_name(&self) -> &'static str;
+
+    /// Field metadata, in declaration order.
+    fn fields(&self) -> &'static [FieldInfo];
+
+    /// Column/value pairs for this instance.
+    fn to_row(&self) -> Vec<(&'static str, Value)>;
+
+    /// Primary key values, in [`ModelDescriptor::primary_key`] order.
+    fn pk_values(&self) -> Vec<Value>;
+
+    /// Whether the instance has not been persisted yet.
+    fn is_new(&self) -> bool;
+
+    /// Metadata for the model type.
+    fn descriptor(&self) -> ModelDescriptor;
+
+    /// The instance as [`Any`], for downcasting back to the model type.
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<M: Model + 'static> ModelDyn for M {
+    fn table_name(&self) -> &'static str {
+        M::TABLE_NAME
+    }
+
+    fn fields(&self) -> &'static [FieldInfo] {
+        <M as Model>::fields()
+    }
+
+    fn to_row(&self) -> Vec<(&'static str, Value)> {
+        Model::to_row(self)
+    }
+
+    fn pk_values(&self) -> Vec<Value> {
+        self.primary_key_value()
+    }
+
+    fn is_new(&self) -> bool {
+        Model::is_new(self)
+    }
+
+    fn descriptor(&self) -> ModelDescriptor {
+        ModelDescriptor::of::<M>()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl dyn ModelDyn {
+    /// Downcast to the concrete model type.
+    pub fn downcast_ref<M: Model + 'static>(&self) -> Option<&M> {
+        self.as_any().downcast_ref()
+    }
+}
+
+impl std::fmt::Debug for dyn ModelDyn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ModelDyn")
+            .field("table", &self.table_name())
+            .field("pk", &self.pk_values())
+            .finish()
+    }
+}
+
+type FromRowFn = fn(&Row) -> Result<Box<dyn ModelDyn>>;
+
+/// Static metadata of a model type, usable without an instance.
+#[derive(Debug, Clone, Copy)]
+pub struct ModelDescriptor {
+    /// Rust type name, for display.
+    pub type_name: &'static str,
+    /// See [`Model::TABLE_NAME`].
+    pub table_name: &'static str,
+    /// See [`Model::PRIMARY_KEY`].
+    pub primary_key: &'static [&'static str],
+    /// See [`Model::fields`].
+    pub fields: &'static [FieldInfo],
+    /// See [`Model::RELATIONSHIPS`].
+    pub relationships: &'static [RelationshipInfo],
+    /// See [`Model::UNIQUE_CONSTRAINTS`].
+    pub unique_constraints: &'static [UniqueConstraintDef],
+    /// See [`Model::INDEXES`].
+    pub indexes: &'static [IndexDef],
+    /// See [`Model::TABLE_COMMENT`].
+    pub table_comment: Option<&'static str>,
+    from_row: FromRowFn,
+}
+
+impl ModelDescriptor {
+    /// The descriptor of model `M`.
+    #[allow(clippy::result_large_err)]
+    pub fn of<M: Model + 'static>() -> Self {
+        Self {
+            type_name: std::any::type_name::<M>(),
+            table_name: M::TABLE_NAME,
+            primary_key: M::PRIMARY_KEY,
+            fields: <M as Model>::fields(),
+            relationships: M::RELATIONSHIPS,
+            unique_constraints: M::UNIQUE_CONSTRAINTS,
+            indexes: M::INDEXES,
+            table_comment: M::TABLE_COMMENT,
+            from_row: |row| M::from_row(row).map(|m| Box::new(m) as Box<dyn ModelDyn>),
+        }
+    }
+
+    /// Build an instance from a result row.
+    #[allow(clippy::result_large_err)]
+    pub fn from_row(&self, row: &Row) -> Result<Box<dyn ModelDyn>> {
+        (self.from_row)(row)
+    }
+
+    /// Selectable column names (non-computed, non-deferred).
+    pub fn select_columns(&self) -> Vec<&'static str> {
+        self.fields
+            .iter()
+            .filter(|f| !f.computed && !f.deferred)
+            .map(|f| f.column_name)
+            .collect()
+    }
+}
+
+/// A model submitted to [`ModelRegistry::global`].
+///
+/// `#[sqlmodel(register)]` emits one per model; hand-written [`Model`]
+/// impls can submit their own:
+///
+/// ```ignore
+/// sqlmodel_core::inventory::submit! {
+///     RegisteredModel::new(ModelDescriptor::of::<Hero>)
+/// }
+/// ```
+pub struct RegisteredModel {
+    descriptor: fn() -> ModelDescriptor,
+}
+
+impl RegisteredModel {
+    /// Register the model whose descriptor `descriptor` returns.
+    pub const fn new(descriptor: fn() -> ModelDescriptor) -> Self {
+        Self { descriptor }
+    }
+
+    /// The registered model's descriptor.
+    pub fn descriptor(&self) -> ModelDescriptor {
+        (self.descriptor)()
+    }
+}
+
+inventory::collect!(RegisteredModel);
+
+/// Table name → [`ModelDescriptor`] lookup.
+#[derive(Debug, Clone, Default)]
+pub struct ModelRegistry {
+    models: BTreeMap<&'static str, ModelDescriptor>,
+}
+
+impl ModelRegistry {
+    /// An empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every model in the binary declared with `#[sqlmodel(register)]`.
+    ///
+    /// Built on first use. If two registered models share a table, one of
+    /// them is kept and which one is unspecified.
+    pub fn global() -> &'static ModelRegistry {
+        static GLOBAL: OnceLock<ModelRegistry> = OnceLock::new();
+        GLOBAL.get_or_init(|| {
+            let mut registry = Self::new();
+            for model in inventory::iter::<RegisteredModel> {
+                registry.insert(model.descriptor());
+            }
+            registry
+        })
+    }
+
+    /// Register model `M` under its table name, replacing any model already
+    /// registered for that table.
+    #[must_use]
+    pub fn register<M: Model + 'static>(mut self) -> Self {
+        self.insert(ModelDescriptor::of::<M>());
+        self
+    }
+
+    /// Register a descriptor, returning the one it replaced.
+    pub fn insert(&mut self, descriptor: ModelDescriptor) -> Option<ModelDescriptor> {
+        self.models.insert(descriptor.table_name, descriptor)
+    }
+
+    /// The model registered for `table`.
+    pub fn get(&self, table: &str) -> Option<&ModelDescriptor> {
+        self.models.get(table)
+    }
+
+    /// Whether a model is registered for `table`.
+    pub fn contains(&self, table: &str) -> bool {
+        self.models.contains_key(table)
+    }
+
+    /// Registered table names, sorted.
+    pub fn table_names(&self) -> Vec<&'static str> {
+        self.models.keys().copied().collect()
+    }
+
+    /// Registered descriptors, sorted by table name.
+    pub fn iter(&self) -> impl Iterator<Item = &ModelDescriptor> {
+        self.models.values()
+    }
+
+    /// Number of registered models.
+    pub fn len(&self) -> usize {
+        self.models.len()
+    }
+
+    /// Whether no models are registered.
+    pub fn is_empty(&self) -> bool {
+        self.models.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SqlType;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Hero {
+        id: Option<i64>,
+        name: String,
+    }
+
+    impl Model for Hero {
+        const TABLE_NAME: &'static str = "heroes";
+        const PRIMARY_KEY: &'static [&'static str] = &["id"];
+
+        fn fields() -> &'static [FieldInfo] {
+            static FIELDS: &[FieldInfo] = &[
+                FieldInfo::new("id", "id", SqlType::BigInt)
+                    .primary_key(true)
+                    .nullable(true),
+                FieldInfo::new("name", "name", SqlType::Text),
+            ];
+            FIELDS
+        }
+
+        fn to_row(&self) -> Vec<(&'static str, Value)> {
+            vec![
+                ("id", self.id.map_or(Value::Null, Value::BigInt)),
+                ("name", Value::Text(self.name.clone())),
+            ]
+        }
+
+        fn from_row(row: &Row) -> Result<Self> {
+            Ok(Self {
+                id: row.get_named("id")?,
+                name: row.get_named("name")?,
+            })
+        }
+
+        fn primary_key_value(&self) -> Vec<Value> {
+            vec![self.id.map_or(Value::Null, Value::BigInt)]
+        }
+
+        fn is_new(&self) -> bool {
+            self.id.is_none()
+        }
+    }
+
+    inventory::submit! {
+        RegisteredModel::new(ModelDescriptor::of::<Hero>)
+    }
+
+    #[derive(Debug, Clone)]
+    struct Team;
+
+    impl Model for Team {
+        const TABLE_NAME: &'static str = "teams";
+        const PRIMARY_KEY: &'static [&'static str] = &["id"];
+
+        fn fields() -> &'static [FieldInfo] {
+            &[]
+        }
+
+        fn to_row(&self) -> Vec<(&'static str, Value)> {
+            Vec::new()
+        }
+
+        fn from_row(_row: &Row) -> Result<Self> {
+            Ok(Team)
+        }
+
+        fn primary_key_value(&self) -> Vec<Value> {
+            vec![Value::BigInt(1)]
+        }
+
+        fn is_new(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn heterogeneous_models_as_trait_objects() {
+        let models: Vec<Box<dyn ModelDyn>> = vec![
+            Box::new(Hero {
+                id: Some(7),
+                name: "Deadpond".to_string(),
+            }),
+            Box::new(Team),
+        ];
+        let tables: Vec<_> = models.iter().map(|m| m.table_name()).collect();
+        assert_eq!(tables, ["heroes", "teams"]);
+        assert_eq!(models[0].pk_values(), vec![Value::BigInt(7)]);
+        assert_eq!(models[0].to_row()[1].1, Value::Text("Deadpond".to_string()));
+        assert_eq!(models[0].fields().len(), 2);
+        assert!(!models[1].is_new());
+        assert_eq!(models[1].descriptor().table_name, "teams");
+
+        assert_eq!(
+            models[0].downcast_ref::<Hero>().map(|h| h.id),
+            Some(Some(7))
+        );
+        assert!(models[0].downcast_ref::<Team>().is_none());
+    }
+
+    #[test]
+    fn registry_looks_up_descriptors_by_table() {
+        let registry = ModelRegistry::new().register::<Team>().register::<Hero>();
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.table_names(), ["heroes", "teams"]);
+        assert!(registry.contains("teams"));
+        assert!(registry.get("villains").is_none());
+
+        let heroes = registry.get("heroes").unwrap();
+        assert!(heroes.type_name.ends_with("Hero"));
+        assert_eq!(heroes.primary_key, ["id"]);
+        assert_eq!(heroes.select_columns(), ["id", "name"]);
+
+        let row = Row::new(
+            vec!["id".to_string(), "name".to_string()],
+            vec![Value::BigInt(3), Value::Text("Rusty-Man".to_string())],
+        );
+        let hero = heroes.from_row(&row).unwrap();
+        assert_eq!(
+            hero.downcast_ref::<Hero>(),
+            Some(&Hero {
+                id: Some(3),
+                name: "Rusty-Man".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn global_registry_collects_submitted_models() {
+        let global = ModelRegistry::global();
+        assert!(global.contains("heroes"));
+        assert!(!global.contains("teams"));
+        assert!(std::ptr::eq(global, ModelRegistry::global()));
+    }
+}
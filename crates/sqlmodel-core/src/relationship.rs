@@ -11,6 +11,7 @@ use asupersync::{Cx, Outcome};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use std::future::Future;
+use std::panic::Location;
 use std::sync::OnceLock;
 
 /// The type of relationship between two models.
@@ -563,6 +564,19 @@ pub trait LazyLoader<M: Model> {
     /// Load an object by primary key.
     fn get(&mut self, cx: &Cx, pk: Value)
     -> impl Future<Output = Outcome<Option<M>, Error>> + Send;
+
+    /// Load an object for a lazy relationship requested at `caller`.
+    ///
+    /// Sessions override this to feed N+1 detection; the default just
+    /// delegates to [`get`](Self::get).
+    fn get_lazy(
+        &mut self,
+        cx: &Cx,
+        pk: Value,
+        _caller: &'static Location<'static>,
+    ) -> impl Future<Output = Outcome<Option<M>, Error>> + Send {
+        self.get(cx, pk)
+    }
 }
 
 /// A related single object (many-to-one or one-to-one).
@@ -612,6 +626,11 @@ impl<T: Model> Related<T> {
         self.loaded.get().and_then(|o| o.as_ref())
     }
 
+    /// Get the loaded object mutably (None if not loaded or loaded as null).
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.loaded.get_mut().and_then(Option::as_mut)
+    }
+
     /// Check if the relationship has been loaded (including loaded-null).
     #[must_use]
     pub fn is_loaded(&self) -> bool {
@@ -634,6 +653,54 @@ impl<T: Model> Related<T> {
     pub fn set_loaded(&self, obj: Option<T>) -> Result<(), Option<T>> {
         self.loaded.set(obj)
     }
+
+    /// Return the cached object, loading it through `loader` by foreign key
+    /// first if this relationship has not been loaded yet.
+    ///
+    /// A null FK caches `None` without a query. Loader failures leave the
+    /// relationship unloaded so the call can be retried. Loads through a
+    /// `Session` are recorded for N+1 detection.
+    #[track_caller]
+    pub fn get_or_load<L>(
+        &self,
+        cx: &Cx,
+        loader: &mut L,
+    ) -> impl Future<Output = Outcome<Option<&T>, Error>>
+    where
+        L: LazyLoader<T> + ?Sized,
+    {
+        let caller = Location::caller();
+        self.get_or_load_at(cx, loader, caller)
+    }
+
+    async fn get_or_load_at<L>(
+        &self,
+        cx: &Cx,
+        loader: &mut L,
+        caller: &'static Location<'static>,
+    ) -> Outcome<Option<&T>, Error>
+    where
+        L: LazyLoader<T> + ?Sized,
+    {
+        if self.is_loaded() {
+            return Outcome::Ok(self.get());
+        }
+
+        let Some(fk) = self.fk_value.clone() else {
+            let _ = self.set_loaded(None);
+            return Outcome::Ok(None);
+        };
+
+        match loader.get_lazy(cx, fk, caller).await {
+            Outcome::Ok(obj) => {
+                let _ = self.set_loaded(obj);
+                Outcome::Ok(self.get())
+            }
+            Outcome::Err(e) => Outcome::Err(e),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
 }
 
 impl<T: Model> Default for Related<T> {
@@ -785,6 +852,11 @@ impl<T: Model> RelatedMany<T> {
         self.loaded.get().map(Vec::as_slice)
     }
 
+    /// Get the loaded objects mutably (None if not loaded).
+    pub fn get_mut(&mut self) -> Option<&mut Vec<T>> {
+        self.loaded.get_mut()
+    }
+
     /// Get the number of loaded items (0 if not loaded).
     #[must_use]
     pub fn len(&self) -> usize {
@@ -1118,7 +1190,47 @@ impl<T: Model> Lazy<T> {
     /// - If the FK is NULL, this caches `None` and returns `Ok(None)`.
     /// - If the loader errors/cancels/panics, this does **not** mark the
     ///   relationship as loaded, allowing retries.
-    pub async fn load<L>(&mut self, cx: &Cx, loader: &mut L) -> Outcome<Option<&T>, Error>
+    ///
+    /// The caller's location is passed to [`LazyLoader::get_lazy`] so N+1
+    /// warnings point at the code that triggered the load.
+    #[track_caller]
+    pub fn load<L>(
+        &mut self,
+        cx: &Cx,
+        loader: &mut L,
+    ) -> impl Future<Output = Outcome<Option<&T>, Error>>
+    where
+        L: LazyLoader<T> + ?Sized,
+    {
+        let caller = Location::caller();
+        self.load_at(cx, loader, caller)
+    }
+
+    /// Return the cached object, loading it through `loader` first if this
+    /// relationship has not been loaded yet.
+    ///
+    /// Unlike [`load`](Self::load) this only needs a shared borrow, so it
+    /// works while iterating over `&[Parent]`. Loads through a `Session` are
+    /// recorded for N+1 detection.
+    #[track_caller]
+    pub fn get_or_load<L>(
+        &self,
+        cx: &Cx,
+        loader: &mut L,
+    ) -> impl Future<Output = Outcome<Option<&T>, Error>>
+    where
+        L: LazyLoader<T> + ?Sized,
+    {
+        let caller = Location::caller();
+        self.load_at(cx, loader, caller)
+    }
+
+    async fn load_at<L>(
+        &self,
+        cx: &Cx,
+        loader: &mut L,
+        caller: &'static Location<'static>,
+    ) -> Outcome<Option<&T>, Error>
     where
         L: LazyLoader<T> + ?Sized,
     {
@@ -1131,7 +1243,7 @@ impl<T: Model> Lazy<T> {
             return Outcome::Ok(None);
         };
 
-        match loader.get(cx, fk).await {
+        match loader.get_lazy(cx, fk, caller).await {
             Outcome::Ok(obj) => {
                 let _ = self.set_loaded(obj);
                 Outcome::Ok(self.get())
@@ -1148,6 +1260,11 @@ impl<T: Model> Lazy<T> {
         self.loaded.get().and_then(|o| o.as_ref())
     }
 
+    /// Get the loaded object mutably (None if not loaded or FK is null).
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.loaded.get_mut().and_then(Option::as_mut)
+    }
+
     /// Check if load() has been called.
     #[must_use]
     pub fn is_loaded(&self) -> bool {
@@ -2075,6 +2192,51 @@ mod tests {
         assert!(lazy.set_loaded(None).is_err());
     }
 
+    #[test]
+    fn test_get_or_load_loads_once_through_shared_borrows() {
+        #[derive(Default)]
+        struct Loader {
+            calls: usize,
+        }
+
+        impl LazyLoader<Team> for Loader {
+            fn get(
+                &mut self,
+                _cx: &Cx,
+                pk: Value,
+            ) -> impl Future<Output = Outcome<Option<Team>, Error>> + Send {
+                self.calls += 1;
+                let team = (pk == Value::BigInt(1)).then(|| Team {
+                    id: Some(1),
+                    name: "Avengers".to_string(),
+                });
+                async move { Outcome::Ok(team) }
+            }
+        }
+
+        let rt = RuntimeBuilder::current_thread()
+            .build()
+            .expect("create asupersync runtime");
+        let cx = Cx::for_testing();
+
+        rt.block_on(async {
+            let related = [Related::<Team>::from_fk(1_i64), Related::empty()];
+            let lazy = Lazy::<Team>::from_fk(1_i64);
+            let mut loader = Loader::default();
+
+            for _ in 0..2 {
+                let team = related[0].get_or_load(&cx, &mut loader).await;
+                assert!(matches!(team, Outcome::Ok(Some(t)) if t.name == "Avengers"));
+                let team = lazy.get_or_load(&cx, &mut loader).await;
+                assert!(matches!(team, Outcome::Ok(Some(_))));
+            }
+            let none = related[1].get_or_load(&cx, &mut loader).await;
+            assert!(matches!(none, Outcome::Ok(None)));
+            assert!(related[1].is_loaded());
+            assert_eq!(loader.calls, 2);
+        });
+    }
+
     // ========================================================================
     // Relationship Lookup Helper Tests
     // ========================================================================
@@ -2,9 +2,11 @@
 
 use crate::Result;
 use crate::error::{Error, TypeError};
+use crate::field::FieldInfo;
 use crate::value::Value;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::fmt;
+use std::sync::{Arc, OnceLock};
 
 /// Column metadata shared across all rows in a result set.
 ///
@@ -63,14 +65,120 @@ impl ColumnInfo {
     }
 }
 
+/// Decodes a single raw column from the driver's wire format into a [`Value`].
+///
+/// Drivers that support lazy rows implement this once per result set and share
+/// it across all rows via `Arc`. The decoder is only invoked for columns that are
+/// actually read.
+pub trait ColumnDecoder: Send + Sync {
+    /// Decode the raw bytes of column `index`.
+    #[allow(clippy::result_large_err)]
+    fn decode(&self, index: usize, raw: &[u8]) -> Result<Value>;
+}
+
+/// Raw column bytes that are decoded on first access.
+#[derive(Clone)]
+struct LazyValues {
+    /// Raw wire bytes per column (`None` = SQL NULL)
+    raw: Vec<Option<Vec<u8>>>,
+    /// Decoded values, filled in on first access
+    decoded: Vec<OnceLock<Value>>,
+    /// Shared result-set decoder
+    decoder: Arc<dyn ColumnDecoder>,
+}
+
+impl LazyValues {
+    #[allow(clippy::result_large_err)]
+    fn try_get(&self, index: usize) -> Option<Result<&Value>> {
+        let slot = self.decoded.get(index)?;
+        if let Some(value) = slot.get() {
+            return Some(Ok(value));
+        }
+        let value = match &self.raw[index] {
+            None => Value::Null,
+            Some(bytes) => match self.decoder.decode(index, bytes) {
+                Ok(value) => value,
+                Err(e) => return Some(Err(e)),
+            },
+        };
+        Some(Ok(slot.get_or_init(|| value)))
+    }
+
+    fn get(&self, index: usize) -> Option<&Value> {
+        match self.try_get(index)? {
+            Ok(value) => Some(value),
+            Err(e) => {
+                // Infallible accessors cannot surface the error; keep the raw bytes so
+                // the data is not silently lost and the typed accessors report a mismatch.
+                tracing::warn!(column = index, error = %e, "lazy column decode failed");
+                let raw = self.raw[index].clone().unwrap_or_default();
+                Some(self.decoded[index].get_or_init(|| Value::Bytes(raw)))
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+enum RowValues {
+    /// All values decoded up front
+    Eager(Vec<Value>),
+    /// Values decoded per column on first access
+    Lazy(LazyValues),
+}
+
+impl RowValues {
+    fn len(&self) -> usize {
+        match self {
+            RowValues::Eager(values) => values.len(),
+            RowValues::Lazy(lazy) => lazy.raw.len(),
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<&Value> {
+        match self {
+            RowValues::Eager(values) => values.get(index),
+            RowValues::Lazy(lazy) => lazy.get(index),
+        }
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn try_get(&self, index: usize) -> Option<Result<&Value>> {
+        match self {
+            RowValues::Eager(values) => values.get(index).map(Ok),
+            RowValues::Lazy(lazy) => lazy.try_get(index),
+        }
+    }
+}
+
+impl fmt::Debug for RowValues {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RowValues::Eager(values) => values.fmt(f),
+            RowValues::Lazy(lazy) => f
+                .debug_struct("Lazy")
+                .field("columns", &lazy.raw.len())
+                .field(
+                    "decoded",
+                    &lazy.decoded.iter().filter(|v| v.get().is_some()).count(),
+                )
+                .finish(),
+        }
+    }
+}
+
 /// A single row returned from a database query.
 ///
 /// Rows provide both index-based and name-based access to column values.
 /// Column metadata is shared via `Arc` for memory efficiency.
+///
+/// Rows are either *eager* (every column decoded into a [`Value`] up front) or
+/// *lazy* (see [`Row::lazy`]), where each column is decoded from the driver's wire
+/// buffer the first time it is read. Reading only a few columns of a wide lazy
+/// row avoids allocating values for the rest.
 #[derive(Debug, Clone)]
 pub struct Row {
     /// Column values in order
-    values: Vec<Value>,
+    values: RowValues,
     /// Shared column metadata
     columns: Arc<ColumnInfo>,
 }
@@ -82,7 +190,10 @@ impl Row {
     /// to share the column metadata.
     pub fn new(column_names: Vec<String>, values: Vec<Value>) -> Self {
         let columns = Arc::new(ColumnInfo::new(column_names));
-        Self { values, columns }
+        Self {
+            values: RowValues::Eager(values),
+            columns,
+        }
     }
 
     /// Extract a subset of columns with a given prefix.
@@ -117,6 +228,31 @@ impl Row {
         Self::new(names, values)
     }
 
+    /// Return a copy of this row with every field column that is absent added with
+    /// its [`placeholder_value`](crate::FieldInfo::placeholder_value).
+    ///
+    /// This supports partial hydration: a query that selected only some columns
+    /// can still be turned into a full model, with the missing attributes treated
+    /// as expired by the caller.
+    #[must_use]
+    pub fn fill_missing(&self, fields: &[FieldInfo]) -> Self {
+        let missing: Vec<&FieldInfo> = fields
+            .iter()
+            .filter(|f| !f.computed && !self.contains_column(f.column_name))
+            .collect();
+        if missing.is_empty() {
+            return self.clone();
+        }
+
+        let mut names: Vec<String> = self.column_names().map(str::to_string).collect();
+        let mut values: Vec<Value> = self.values().cloned().collect();
+        for field in missing {
+            names.push(field.column_name.to_string());
+            values.push(field.placeholder_value());
+        }
+        Self::new(names, values)
+    }
+
     /// Check if this row has any columns with the given prefix.
     ///
     /// Useful for checking if a LEFT JOIN returned NULL (no matching rows).
@@ -147,7 +283,36 @@ impl Row {
     ///
     /// This is more efficient for creating multiple rows from the same query.
     pub fn with_columns(columns: Arc<ColumnInfo>, values: Vec<Value>) -> Self {
-        Self { values, columns }
+        Self {
+            values: RowValues::Eager(values),
+            columns,
+        }
+    }
+
+    /// Create a lazily-decoded row from raw column bytes.
+    ///
+    /// `raw[i]` is the wire representation of column `i` (`None` for SQL NULL).
+    /// Columns are decoded with `decoder` the first time they are accessed and
+    /// cached for subsequent reads.
+    pub fn lazy(
+        columns: Arc<ColumnInfo>,
+        raw: Vec<Option<Vec<u8>>>,
+        decoder: Arc<dyn ColumnDecoder>,
+    ) -> Self {
+        let cache = (0..raw.len()).map(|_| OnceLock::new()).collect();
+        Self {
+            values: RowValues::Lazy(LazyValues {
+                raw,
+                decoded: cache,
+                decoder,
+            }),
+            columns,
+        }
+    }
+
+    /// Check if this row decodes its columns lazily.
+    pub fn is_lazy(&self) -> bool {
+        matches!(self.values, RowValues::Lazy(_))
     }
 
     /// Get the shared column metadata.
@@ -164,7 +329,7 @@ impl Row {
 
     /// Check if this row is empty.
     pub fn is_empty(&self) -> bool {
-        self.values.is_empty()
+        self.values.len() == 0
     }
 
     /// Get a value by column index. O(1) operation.
@@ -177,6 +342,59 @@ impl Row {
         self.columns.index_of(name).and_then(|i| self.values.get(i))
     }
 
+    /// Get a borrowed, typed value by column name without cloning.
+    ///
+    /// Unlike [`get_named`](Row::get_named), this borrows directly from the row,
+    /// so `&str` and `&[u8]` columns are returned without allocating. On lazy rows
+    /// only the requested column is decoded, and decode errors are reported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sqlmodel_core::{Row, Value};
+    ///
+    /// let row = Row::new(vec!["name".into()], vec![Value::Text("Alice".into())]);
+    /// let name: &str = row.try_get_ref("name").unwrap();
+    /// assert_eq!(name, "Alice");
+    /// ```
+    #[allow(clippy::result_large_err)]
+    pub fn try_get_ref<'a, T: FromValueRef<'a>>(&'a self, name: &str) -> Result<T> {
+        let Some(index) = self.columns.index_of(name) else {
+            return Err(Error::Type(TypeError {
+                expected: std::any::type_name::<T>(),
+                actual: format!("column '{}' not found", name),
+                column: Some(name.to_string()),
+                rust_type: None,
+            }));
+        };
+        self.try_get_ref_at(index).map_err(|e| match e {
+            Error::Type(mut te) => {
+                te.column = Some(name.to_string());
+                Error::Type(te)
+            }
+            e => e,
+        })
+    }
+
+    /// Get a borrowed, typed value by column index without cloning.
+    #[allow(clippy::result_large_err)]
+    pub fn try_get_ref_at<'a, T: FromValueRef<'a>>(&'a self, index: usize) -> Result<T> {
+        match self.values.try_get(index) {
+            Some(Ok(value)) => T::from_value_ref(value),
+            Some(Err(e)) => Err(e),
+            None => Err(Error::Type(TypeError {
+                expected: std::any::type_name::<T>(),
+                actual: format!(
+                    "index {} out of bounds (row has {} columns)",
+                    index,
+                    self.len()
+                ),
+                column: None,
+                rust_type: None,
+            })),
+        }
+    }
+
     /// Check if a column exists by name.
     pub fn contains_column(&self, name: &str) -> bool {
         self.columns.contains(name)
@@ -220,14 +438,43 @@ impl Row {
         })
     }
 
+    /// Get a value by column name, decoding it with `decode` instead of [`FromValue`].
+    ///
+    /// Used by `#[sqlmodel(deserialize_with = "...")]` fields. Errors are tagged
+    /// with the column name like [`get_named`](Row::get_named).
+    #[allow(clippy::result_large_err)]
+    pub fn get_named_with<T>(
+        &self,
+        name: &str,
+        decode: impl FnOnce(&Value) -> Result<T>,
+    ) -> Result<T> {
+        let value = self.get_by_name(name).ok_or_else(|| {
+            Error::Type(TypeError {
+                expected: std::any::type_name::<T>(),
+                actual: format!("column '{}' not found", name),
+                column: Some(name.to_string()),
+                rust_type: None,
+            })
+        })?;
+        decode(value).map_err(|e| match e {
+            Error::Type(mut te) => {
+                te.column = Some(name.to_string());
+                Error::Type(te)
+            }
+            e => e,
+        })
+    }
+
     /// Get all column names.
     pub fn column_names(&self) -> impl Iterator<Item = &str> {
         self.columns.names().iter().map(String::as_str)
     }
 
     /// Iterate over all values.
+    ///
+    /// On lazy rows this decodes every column.
     pub fn values(&self) -> impl Iterator<Item = &Value> {
-        self.values.iter()
+        (0..self.values.len()).filter_map(|i| self.values.get(i))
     }
 
     /// Iterate over (column_name, value) pairs.
@@ -236,7 +483,7 @@ impl Row {
             .names()
             .iter()
             .map(String::as_str)
-            .zip(self.values.iter())
+            .zip(self.values())
     }
 }
 
@@ -579,6 +826,90 @@ impl FromValue for serde_json::Value {
     }
 }
 
+/// Trait for borrowing a typed view of a `Value` without cloning.
+///
+/// This is the zero-copy counterpart of [`FromValue`], used by
+/// [`Row::try_get_ref`].
+pub trait FromValueRef<'a>: Sized {
+    /// Borrow from a Value, returning an error if the conversion fails.
+    #[allow(clippy::result_large_err)]
+    fn from_value_ref(value: &'a Value) -> Result<Self>;
+}
+
+impl<'a> FromValueRef<'a> for &'a str {
+    fn from_value_ref(value: &'a Value) -> Result<Self> {
+        match value {
+            Value::Text(s) | Value::Decimal(s) => Ok(s.as_str()),
+            _ => Err(Error::Type(TypeError {
+                expected: "&str",
+                actual: value.type_name().to_string(),
+                column: None,
+                rust_type: None,
+            })),
+        }
+    }
+}
+
+impl<'a> FromValueRef<'a> for &'a [u8] {
+    fn from_value_ref(value: &'a Value) -> Result<Self> {
+        match value {
+            Value::Bytes(b) => Ok(b.as_slice()),
+            Value::Text(s) => Ok(s.as_bytes()),
+            Value::Uuid(u) => Ok(u.as_slice()),
+            _ => Err(Error::Type(TypeError {
+                expected: "&[u8]",
+                actual: value.type_name().to_string(),
+                column: None,
+                rust_type: None,
+            })),
+        }
+    }
+}
+
+impl<'a> FromValueRef<'a> for &'a Value {
+    fn from_value_ref(value: &'a Value) -> Result<Self> {
+        Ok(value)
+    }
+}
+
+impl<'a> FromValueRef<'a> for &'a serde_json::Value {
+    fn from_value_ref(value: &'a Value) -> Result<Self> {
+        match value {
+            Value::Json(v) => Ok(v),
+            _ => Err(Error::Type(TypeError {
+                expected: "JSON",
+                actual: value.type_name().to_string(),
+                column: None,
+                rust_type: None,
+            })),
+        }
+    }
+}
+
+impl<'a, T: FromValueRef<'a>> FromValueRef<'a> for Option<T> {
+    fn from_value_ref(value: &'a Value) -> Result<Self> {
+        if value.is_null() {
+            Ok(None)
+        } else {
+            T::from_value_ref(value).map(Some)
+        }
+    }
+}
+
+macro_rules! impl_from_value_ref_copy {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl<'a> FromValueRef<'a> for $t {
+                fn from_value_ref(value: &'a Value) -> Result<Self> {
+                    <$t as FromValue>::from_value(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_value_ref_copy!(bool, i8, i16, i32, i64, u8, u16, u32, u64, f32, f64);
+
 impl FromValue for [u8; 16] {
     fn from_value(value: &Value) -> Result<Self> {
         match value {
@@ -664,6 +995,27 @@ mod tests {
         assert!(row.get_as::<i32>(99).is_err());
     }
 
+    #[test]
+    #[allow(clippy::result_large_err)]
+    fn test_row_get_named_with() {
+        let row = Row::new(
+            vec!["tags".to_string()],
+            vec![Value::Text("a,b".to_string())],
+        );
+        let split = |v: &Value| -> Result<Vec<String>> {
+            let s = String::from_value(v)?;
+            Ok(s.split(',').map(str::to_string).collect())
+        };
+        assert_eq!(row.get_named_with("tags", split).unwrap(), ["a", "b"]);
+        assert!(row.get_named_with("missing", split).is_err());
+
+        let err = row.get_named_with("tags", i32::from_value).unwrap_err();
+        match err {
+            Error::Type(te) => assert_eq!(te.column.as_deref(), Some("tags")),
+            other => panic!("expected type error, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_row_null_handling() {
         let row = Row::new(vec!["nullable".to_string()], vec![Value::Null]);
@@ -953,4 +1305,124 @@ mod tests {
         assert!((f64::from_value(&Value::Bool(true)).unwrap() - 1.0).abs() < f64::EPSILON);
         assert!((f64::from_value(&Value::Bool(false)).unwrap() - 0.0).abs() < f64::EPSILON);
     }
+
+    /// Text decoder that counts invocations and rejects the byte `0xFF`.
+    struct CountingDecoder(std::sync::atomic::AtomicUsize);
+
+    impl ColumnDecoder for CountingDecoder {
+        fn decode(&self, _index: usize, raw: &[u8]) -> Result<Value> {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            std::str::from_utf8(raw)
+                .map(|s| Value::Text(s.to_string()))
+                .map_err(|e| {
+                    Error::Type(TypeError {
+                        expected: "utf-8",
+                        actual: e.to_string(),
+                        column: None,
+                        rust_type: None,
+                    })
+                })
+        }
+    }
+
+    fn lazy_row() -> (Row, Arc<CountingDecoder>) {
+        let decoder = Arc::new(CountingDecoder(std::sync::atomic::AtomicUsize::new(0)));
+        let columns = Arc::new(ColumnInfo::new(vec![
+            "a".to_string(),
+            "b".to_string(),
+            "c".to_string(),
+            "d".to_string(),
+        ]));
+        let row = Row::lazy(
+            columns,
+            vec![
+                Some(b"alpha".to_vec()),
+                Some(b"beta".to_vec()),
+                None,
+                Some(vec![0xFF]),
+            ],
+            Arc::clone(&decoder) as Arc<dyn ColumnDecoder>,
+        );
+        (row, decoder)
+    }
+
+    #[test]
+    fn test_try_get_ref_borrows() {
+        let row = Row::new(
+            vec!["name".to_string(), "data".to_string(), "n".to_string()],
+            vec![
+                Value::Text("Alice".to_string()),
+                Value::Bytes(vec![1, 2, 3]),
+                Value::Null,
+            ],
+        );
+        let name: &str = row.try_get_ref("name").unwrap();
+        assert_eq!(name, "Alice");
+        let data: &[u8] = row.try_get_ref("data").unwrap();
+        assert_eq!(data, &[1, 2, 3]);
+        let n: Option<&str> = row.try_get_ref("n").unwrap();
+        assert_eq!(n, None);
+        assert!(row.try_get_ref::<&str>("data").is_err());
+        assert!(row.try_get_ref::<&str>("missing").is_err());
+    }
+
+    #[test]
+    fn test_lazy_row_decodes_on_demand() {
+        let (row, decoder) = lazy_row();
+        assert!(row.is_lazy());
+        assert_eq!(row.len(), 4);
+        assert_eq!(decoder.0.load(std::sync::atomic::Ordering::Relaxed), 0);
+
+        let b: &str = row.try_get_ref("b").unwrap();
+        assert_eq!(b, "beta");
+        let _: &str = row.try_get_ref("b").unwrap();
+        assert_eq!(decoder.0.load(std::sync::atomic::Ordering::Relaxed), 1);
+
+        // NULL columns never reach the decoder.
+        assert_eq!(row.get_by_name("c"), Some(&Value::Null));
+        assert_eq!(decoder.0.load(std::sync::atomic::Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_lazy_row_decode_error() {
+        let (row, _) = lazy_row();
+        let err = row.try_get_ref::<&str>("d").unwrap_err();
+        match err {
+            Error::Type(te) => assert_eq!(te.column.as_deref(), Some("d")),
+            other => panic!("unexpected error: {other:?}"),
+        }
+        // Infallible access falls back to the raw bytes.
+        assert_eq!(row.get(3), Some(&Value::Bytes(vec![0xFF])));
+    }
+
+    #[test]
+    fn test_lazy_row_iter_and_clone() {
+        let (row, _) = lazy_row();
+        let cloned = row.clone();
+        assert_eq!(cloned.get_named::<String>("a").unwrap(), "alpha");
+        let values: Vec<_> = row.values().collect();
+        assert_eq!(values.len(), 4);
+        assert_eq!(values[0], &Value::Text("alpha".to_string()));
+    }
+
+    #[test]
+    fn test_fill_missing_adds_placeholders() {
+        use crate::types::SqlType;
+
+        let fields = [
+            FieldInfo::new("id", "id", SqlType::BigInt).primary_key(true),
+            FieldInfo::new("name", "name", SqlType::Text),
+            FieldInfo::new("bio", "bio", SqlType::Text).nullable(true),
+        ];
+        let row = Row::new(vec!["id".to_string()], vec![Value::BigInt(7)]);
+        let filled = row.fill_missing(&fields);
+
+        assert_eq!(filled.len(), 3);
+        assert_eq!(filled.get_by_name("id"), Some(&Value::BigInt(7)));
+        assert_eq!(
+            filled.get_by_name("name"),
+            Some(&Value::Text(String::new()))
+        );
+        assert_eq!(filled.get_by_name("bio"), Some(&Value::Null));
+    }
 }
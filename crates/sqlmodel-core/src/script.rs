@@ -0,0 +1,370 @@
+//! Splitting SQL scripts into statements.
+//!
+//! [`split_script`] cuts a multi-statement script (a `.sql` migration file,
+//! a schema dump) at its top-level statement delimiters. It is
+//! dialect-aware, so a `;` only ends a statement when it is outside:
+//!
+//! - string literals, quoted identifiers and comments
+//! - PostgreSQL dollar-quoted bodies (`$$ ... $$`, `$fn$ ... $fn$`)
+//! - `BEGIN ... END` bodies of triggers, procedures, functions and events
+//!   (SQLite triggers, MySQL routines, PostgreSQL `BEGIN ATOMIC`)
+//!
+//! For MySQL the client-side `DELIMITER` directive is honoured as well.
+//!
+//! [`Connection::execute_script`](crate::Connection::execute_script) runs the
+//! statements one by one and reports which statement of the script failed.
+
+use crate::dialect::Dialect;
+
+/// One statement of a script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScriptStatement<'a> {
+    /// The statement text, without its delimiter or leading comments.
+    pub sql: &'a str,
+    /// Byte offset of the statement in the script.
+    pub offset: usize,
+    /// 1-based line of the statement's first character.
+    pub line: usize,
+}
+
+/// Words that may introduce a `BEGIN ... END` body after `CREATE`.
+const ROUTINE_KINDS: &[&str] = &["TRIGGER", "PROCEDURE", "FUNCTION", "EVENT"];
+
+/// MySQL control-flow blocks closed by `END <word>`; their openers are not counted.
+const END_SUFFIXES: &[&str] = &["IF", "LOOP", "WHILE", "REPEAT"];
+
+/// How many leading words of a statement are searched for a routine kind.
+const ROUTINE_LOOKAHEAD: usize = 8;
+
+/// Split `script` into its top-level statements.
+///
+/// Empty statements and statements made only of comments are dropped.
+pub fn split_script(dialect: Dialect, script: &str) -> Vec<ScriptStatement<'_>> {
+    let bytes = script.as_bytes();
+    let mut spans: Vec<(usize, usize)> = Vec::new();
+    let mut delimiter = ";".to_string();
+
+    let mut i = 0;
+    // First content byte of the current statement, if it has any content.
+    let mut content_start: Option<usize> = None;
+    let mut words = 0usize;
+    let mut creates = false;
+    // Whether a `(` was seen; a routine kind must come before it
+    let mut opened = false;
+    let mut routine = false;
+    let mut depth = 0usize;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+
+        if depth == 0 && bytes[i..].starts_with(delimiter.as_bytes()) {
+            spans.extend(content_start.map(|start| (start, i)));
+            i += delimiter.len();
+            content_start = None;
+            words = 0;
+            creates = false;
+            opened = false;
+            routine = false;
+            continue;
+        }
+
+        match b {
+            b if b.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = line_end(bytes, i);
+                continue;
+            }
+            b'#' if dialect == Dialect::Mysql => {
+                i = line_end(bytes, i);
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = block_comment_end(bytes, i, dialect == Dialect::Postgres);
+                continue;
+            }
+            _ => {}
+        }
+
+        // MySQL client directive: `DELIMITER //` on its own line.
+        if dialect == Dialect::Mysql
+            && content_start.is_none()
+            && word_at(bytes, i).eq_ignore_ascii_case(b"DELIMITER")
+        {
+            let end = line_end(bytes, i);
+            let new = script[i + "DELIMITER".len()..end].trim();
+            if !new.is_empty() {
+                delimiter = new.to_string();
+            }
+            i = end;
+            continue;
+        }
+
+        content_start.get_or_insert(i);
+
+        i = match b {
+            b'\'' => {
+                let backslash = dialect == Dialect::Mysql
+                    || (dialect == Dialect::Postgres
+                        && i > 0
+                        && bytes[i - 1].eq_ignore_ascii_case(&b'e')
+                        && !(i > 1 && is_ident_byte(bytes[i - 2])));
+                quoted_end(bytes, i, b'\'', backslash)
+            }
+            b'"' => quoted_end(bytes, i, b'"', dialect == Dialect::Mysql),
+            b'`' => quoted_end(bytes, i, b'`', false),
+            b'[' if dialect == Dialect::Sqlite => quoted_end(bytes, i, b']', false),
+            b'$' if dialect == Dialect::Postgres && !(i > 0 && is_ident_byte(bytes[i - 1])) => {
+                dollar_quoted_end(bytes, i).unwrap_or(i + 1)
+            }
+            b if is_ident_start(b) && !(i > 0 && is_ident_byte(bytes[i - 1])) => {
+                let word = word_at(bytes, i);
+                let mut next = i + word.len();
+                words += 1;
+                if words == 1 {
+                    creates = word.eq_ignore_ascii_case(b"CREATE");
+                } else if creates
+                    && !opened
+                    && !routine
+                    && words <= ROUTINE_LOOKAHEAD
+                    && ROUTINE_KINDS
+                        .iter()
+                        .any(|k| word.eq_ignore_ascii_case(k.as_bytes()))
+                {
+                    routine = true;
+                }
+                if routine {
+                    if word.eq_ignore_ascii_case(b"BEGIN") || word.eq_ignore_ascii_case(b"CASE") {
+                        depth += 1;
+                    } else if word.eq_ignore_ascii_case(b"END") {
+                        let mut j = next;
+                        while j < bytes.len() && bytes[j].is_ascii_whitespace() {
+                            j += 1;
+                        }
+                        let suffix = word_at(bytes, j);
+                        if END_SUFFIXES
+                            .iter()
+                            .any(|s| suffix.eq_ignore_ascii_case(s.as_bytes()))
+                        {
+                            next = j + suffix.len();
+                        } else {
+                            if suffix.eq_ignore_ascii_case(b"CASE") {
+                                next = j + suffix.len();
+                            }
+                            depth = depth.saturating_sub(1);
+                        }
+                    }
+                }
+                next
+            }
+            b'(' => {
+                opened = true;
+                i + 1
+            }
+            _ => i + 1,
+        };
+    }
+    spans.extend(content_start.map(|start| (start, bytes.len())));
+
+    let mut line = 1;
+    let mut line_pos = 0;
+    spans
+        .into_iter()
+        .map(|(start, end)| {
+            line += script[line_pos..start].matches('\n').count();
+            line_pos = start;
+            ScriptStatement {
+                sql: script[start..end].trim_end(),
+                offset: start,
+                line,
+            }
+        })
+        .collect()
+}
+
+fn is_ident_start(b: u8) -> bool {
+    b.is_ascii_alphabetic() || b == b'_'
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'$'
+}
+
+/// The identifier-like word starting at `i` (empty if there is none).
+fn word_at(bytes: &[u8], i: usize) -> &[u8] {
+    if i >= bytes.len() || !is_ident_start(bytes[i]) {
+        return &[];
+    }
+    let len = bytes[i..]
+        .iter()
+        .take_while(|&&b| b.is_ascii_alphanumeric() || b == b'_')
+        .count();
+    &bytes[i..i + len]
+}
+
+/// Index of the newline ending the line that contains `i` (or the end).
+fn line_end(bytes: &[u8], i: usize) -> usize {
+    bytes[i..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(bytes.len(), |p| i + p)
+}
+
+/// Index just past the `*/` closing the comment opened at `i`.
+fn block_comment_end(bytes: &[u8], i: usize, nested: bool) -> usize {
+    let mut depth = 0usize;
+    let mut j = i;
+    while j + 1 < bytes.len() {
+        match (bytes[j], bytes[j + 1]) {
+            (b'/', b'*') if nested || depth == 0 => {
+                depth += 1;
+                j += 2;
+            }
+            (b'*', b'/') => {
+                depth -= 1;
+                j += 2;
+                if depth == 0 {
+                    return j;
+                }
+            }
+            _ => j += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Index just past the literal opened by `quote` at `i`.
+///
+/// A doubled closing quote is an escaped quote; with `backslash`, so is `\`
+/// followed by any byte.
+fn quoted_end(bytes: &[u8], i: usize, quote: u8, backslash: bool) -> usize {
+    let mut j = i + 1;
+    while j < bytes.len() {
+        match bytes[j] {
+            b'\\' if backslash => j += 2,
+            b if b == quote => {
+                if quote != b']' && bytes.get(j + 1) == Some(&quote) {
+                    j += 2;
+                } else {
+                    return j + 1;
+                }
+            }
+            _ => j += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Index just past the dollar-quoted body opened at `i`, or `None` if `i`
+/// does not start a `$tag$` opener (e.g. a `$1` placeholder).
+fn dollar_quoted_end(bytes: &[u8], i: usize) -> Option<usize> {
+    let tag_len = bytes[i + 1..]
+        .iter()
+        .take_while(|&&b| b.is_ascii_alphanumeric() || b == b'_')
+        .count();
+    let close = i + 1 + tag_len;
+    if bytes.get(close) != Some(&b'$') || bytes.get(i + 1).is_some_and(u8::is_ascii_digit) {
+        return None;
+    }
+    let tag = &bytes[i..=close];
+    let body = close + 1;
+    Some(
+        bytes[body..]
+            .windows(tag.len())
+            .position(|w| w == tag)
+            .map_or(bytes.len(), |p| body + p + tag.len()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sqls(dialect: Dialect, script: &str) -> Vec<&str> {
+        split_script(dialect, script)
+            .into_iter()
+            .map(|s| s.sql)
+            .collect()
+    }
+
+    #[test]
+    fn test_split_simple_statements_with_positions() {
+        let script = "-- setup\nCREATE TABLE a (x INT);\n\nINSERT INTO a VALUES (1);  \n";
+        let statements = split_script(Dialect::Sqlite, script);
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[0].sql, "CREATE TABLE a (x INT)");
+        assert_eq!(statements[0].line, 2);
+        assert_eq!(statements[1].sql, "INSERT INTO a VALUES (1)");
+        assert_eq!(statements[1].line, 4);
+        assert_eq!(&script[statements[1].offset..][..6], "INSERT");
+    }
+
+    #[test]
+    fn test_split_ignores_delimiters_in_literals_and_comments() {
+        let script = "INSERT INTO t VALUES ('a;b', \"c;d\"); /* x; y */ SELECT 1 -- z;\n;";
+        assert_eq!(
+            sqls(Dialect::Postgres, script),
+            ["INSERT INTO t VALUES ('a;b', \"c;d\")", "SELECT 1 -- z;"]
+        );
+        assert_eq!(
+            sqls(Dialect::Mysql, r"SELECT 'it\'s;'; SELECT 2"),
+            [r"SELECT 'it\'s;'", "SELECT 2"]
+        );
+    }
+
+    #[test]
+    fn test_split_postgres_dollar_quoted_function() {
+        let script = "CREATE FUNCTION bump() RETURNS trigger AS $body$\n\
+                      BEGIN\n  NEW.n := NEW.n + 1;\n  RETURN NEW;\nEND;\n\
+                      $body$ LANGUAGE plpgsql;\n\
+                      SELECT $1::int;";
+        let statements = sqls(Dialect::Postgres, script);
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].ends_with("LANGUAGE plpgsql"));
+        assert_eq!(statements[1], "SELECT $1::int");
+    }
+
+    #[test]
+    fn test_split_sqlite_trigger_body() {
+        let script = "CREATE TRIGGER t AFTER INSERT ON a BEGIN\n\
+                      UPDATE b SET n = CASE WHEN n > 0 THEN n + 1 ELSE 1 END;\n\
+                      DELETE FROM c;\n\
+                      END;\n\
+                      BEGIN;\nCOMMIT;";
+        let statements = sqls(Dialect::Sqlite, script);
+        assert_eq!(statements.len(), 3);
+        assert!(statements[0].ends_with("END"));
+        assert_eq!(statements[1..], ["BEGIN", "COMMIT"]);
+    }
+
+    #[test]
+    fn test_split_mysql_procedure_with_delimiter_directive() {
+        let script = "DELIMITER //\n\
+                      CREATE PROCEDURE p()\nBEGIN\n\
+                      IF 1 THEN SELECT 1; END IF;\n\
+                      END //\n\
+                      DELIMITER ;\n\
+                      CALL p();";
+        let statements = split_script(Dialect::Mysql, script);
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].sql.starts_with("CREATE PROCEDURE"));
+        assert!(statements[0].sql.ends_with("END"));
+        assert_eq!(statements[1].sql, "CALL p()");
+        assert_eq!(statements[1].line, 7);
+
+        // Without DELIMITER, the BEGIN ... END body still keeps its semicolons.
+        let script = "CREATE PROCEDURE p() BEGIN SELECT 1; SELECT 2; END; CALL p();";
+        assert_eq!(sqls(Dialect::Mysql, script).len(), 2);
+    }
+
+    #[test]
+    fn test_split_drops_comment_only_tail() {
+        assert_eq!(
+            sqls(Dialect::Postgres, "SELECT 1;\n-- done\n"),
+            ["SELECT 1"]
+        );
+        assert!(split_script(Dialect::Postgres, " ; ;\n").is_empty());
+    }
+}
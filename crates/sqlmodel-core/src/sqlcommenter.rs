@@ -0,0 +1,216 @@
+//! [sqlcommenter](https://google.github.io/sqlcommenter/) comments.
+//!
+//! [`SqlCommenter`] is a [`StatementInterceptor`] that appends
+//! `/*application='billing',traceparent='00-...-01'*/` to each statement, so
+//! `pg_stat_statements`, slow query logs and `SHOW PROCESSLIST` can be traced
+//! back to the service and request that issued the query.
+//!
+//! Keys are sorted and keys and values are URL-encoded, as the sqlcommenter
+//! spec requires. Statements that already contain a comment are left alone.
+//!
+//! `tracing` spans carry no W3C trace context of their own, so the
+//! `traceparent` is read through a closure. With `tracing-opentelemetry` that
+//! is the current span's OpenTelemetry context:
+//!
+//! ```rust,ignore
+//! use opentelemetry::trace::TraceContextExt;
+//! use tracing_opentelemetry::OpenTelemetrySpanExt;
+//!
+//! let commenter = SqlCommenter::from_config(&config).traceparent(|| {
+//!     let context = tracing::Span::current().context();
+//!     let span = context.span().span_context().clone();
+//!     span.is_valid().then(|| {
+//!         format!("00-{}-{}-{:02x}", span.trace_id(), span.span_id(), span.trace_flags())
+//!     })
+//! });
+//! let pool = Pool::new(pool_config).with_sqlcommenter(commenter);
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use crate::connection::ConnectionConfig;
+use crate::interceptor::{Statement, StatementInterceptor};
+
+type TraceparentSource = Arc<dyn Fn() -> Option<String> + Send + Sync>;
+
+/// Appends a sqlcommenter comment to every statement.
+#[derive(Clone, Default)]
+pub struct SqlCommenter {
+    tags: BTreeMap<String, String>,
+    traceparent: Option<TraceparentSource>,
+}
+
+impl SqlCommenter {
+    /// A commenter with no tags.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A commenter tagging statements with the config's `application_name`.
+    pub fn from_config(config: &ConnectionConfig) -> Self {
+        match &config.application_name {
+            Some(name) => Self::new().application(name.clone()),
+            None => Self::new(),
+        }
+    }
+
+    /// Set the `application` tag.
+    pub fn application(self, name: impl Into<String>) -> Self {
+        self.tag("application", name)
+    }
+
+    /// Set a static tag, e.g. `tag("framework", "sqlmodel")`.
+    pub fn tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.tags.insert(key.into(), value.into());
+        self
+    }
+
+    /// Read the `traceparent` tag from `source` for each statement; `None`
+    /// omits it.
+    pub fn traceparent<F>(mut self, source: F) -> Self
+    where
+        F: Fn() -> Option<String> + Send + Sync + 'static,
+    {
+        self.traceparent = Some(Arc::new(source));
+        self
+    }
+
+    /// The comment for the current statement, without the `/* */`, or `None`
+    /// if there is nothing to tag.
+    pub fn comment(&self) -> Option<String> {
+        let traceparent = self.traceparent.as_ref().and_then(|source| source());
+        let mut tags: Vec<(&str, &str)> = self
+            .tags
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        if let Some(traceparent) = traceparent.as_deref() {
+            tags.push(("traceparent", traceparent));
+            tags.sort_by_key(|(key, _)| *key);
+        }
+        if tags.is_empty() {
+            return None;
+        }
+
+        let mut comment = String::new();
+        for (i, (key, value)) in tags.into_iter().enumerate() {
+            if i > 0 {
+                comment.push(',');
+            }
+            comment.push_str(&encode(key));
+            comment.push_str("='");
+            comment.push_str(&encode(value));
+            comment.push('\'');
+        }
+        Some(comment)
+    }
+}
+
+impl std::fmt::Debug for SqlCommenter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqlCommenter")
+            .field("tags", &self.tags)
+            .field("traceparent", &self.traceparent.is_some())
+            .finish()
+    }
+}
+
+impl StatementInterceptor for SqlCommenter {
+    fn intercept(&self, statement: &mut Statement<'_>) -> crate::Result<()> {
+        if statement.sql.contains("/*") || statement.sql.contains("--") {
+            return Ok(());
+        }
+        let Some(comment) = self.comment() else {
+            return Ok(());
+        };
+
+        let sql = statement.sql.trim_end();
+        let (body, terminator) = match sql.strip_suffix(';') {
+            Some(body) => (body.trim_end(), ";"),
+            None => (sql, ""),
+        };
+        statement.sql = format!("{body} /*{comment}*/{terminator}").into();
+        Ok(())
+    }
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters.
+fn encode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            out.push(char::from(byte));
+        } else {
+            let _ = write!(out, "%{byte:02X}");
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Dialect;
+
+    fn apply(commenter: &SqlCommenter, sql: &str) -> String {
+        let mut statement = Statement::new(Dialect::Postgres, sql, &[]);
+        commenter.intercept(&mut statement).unwrap();
+        statement.sql.into_owned()
+    }
+
+    #[test]
+    fn test_comment_sorts_and_encodes_tags() {
+        let commenter = SqlCommenter::new()
+            .application("billing api")
+            .tag("route", "/invoices/{id}")
+            .traceparent(|| Some("00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01".into()));
+        assert_eq!(
+            commenter.comment().as_deref(),
+            Some(
+                "application='billing%20api',route='%2Finvoices%2F%7Bid%7D',\
+                 traceparent='00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01'"
+            )
+        );
+    }
+
+    #[test]
+    fn test_appends_comment_before_terminator() {
+        let commenter = SqlCommenter::new().application("app");
+        assert_eq!(
+            apply(&commenter, "SELECT * FROM t"),
+            "SELECT * FROM t /*application='app'*/"
+        );
+        assert_eq!(
+            apply(&commenter, "SELECT 1 ; "),
+            "SELECT 1 /*application='app'*/;"
+        );
+    }
+
+    #[test]
+    fn test_leaves_commented_and_untagged_statements_alone() {
+        let commenter = SqlCommenter::new().application("app");
+        assert_eq!(
+            apply(&commenter, "/* hint */ SELECT 1"),
+            "/* hint */ SELECT 1"
+        );
+        assert_eq!(apply(&SqlCommenter::new(), "SELECT 1"), "SELECT 1");
+
+        let no_trace = SqlCommenter::new().traceparent(|| None);
+        assert_eq!(apply(&no_trace, "SELECT 1"), "SELECT 1");
+    }
+
+    #[test]
+    fn test_from_config_uses_application_name() {
+        let config = ConnectionConfig::new("postgres://localhost/db").application_name("it's");
+        assert_eq!(
+            SqlCommenter::from_config(&config).comment().as_deref(),
+            Some("application='it%27s'")
+        );
+        assert_eq!(
+            SqlCommenter::from_config(&ConnectionConfig::default()).comment(),
+            None
+        );
+    }
+}
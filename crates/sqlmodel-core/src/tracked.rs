@@ -79,9 +79,9 @@ impl<T: Model> TrackedModel<T> {
         exclude_computed_fields: bool,
         exclude_defaults: bool,
     ) {
-        // Always honor per-field exclude flag (Pydantic Field(exclude=True) semantics).
+        // Always honor per-field exclude/db_only flags (Pydantic Field(exclude=True) semantics).
         for field in fields {
-            if field.exclude {
+            if !field.is_serialized() {
                 map.remove(field.name);
             }
         }
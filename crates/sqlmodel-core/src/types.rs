@@ -1,5 +1,8 @@
 //! SQL type definitions and mapping.
 
+use crate::Value;
+use crate::row::FromValue;
+
 /// SQL data types supported by SQLModel.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SqlType {
@@ -14,8 +17,14 @@ pub enum SqlType {
     Double,
 
     // Fixed precision
-    Numeric { precision: u8, scale: u8 },
-    Decimal { precision: u8, scale: u8 },
+    Numeric {
+        precision: u8,
+        scale: u8,
+    },
+    Decimal {
+        precision: u8,
+        scale: u8,
+    },
 
     // Boolean
     Boolean,
@@ -24,6 +33,9 @@ pub enum SqlType {
     Char(u32),
     VarChar(u32),
     Text,
+    /// Case-insensitive text: `CITEXT` on PostgreSQL, `TEXT` with a
+    /// case-insensitive collation on MySQL and SQLite (`NOCASE`)
+    CiText,
 
     // Binary types
     Binary(u32),
@@ -70,6 +82,7 @@ impl SqlType {
             SqlType::Char(len) => format!("CHAR({})", len),
             SqlType::VarChar(len) => format!("VARCHAR({})", len),
             SqlType::Text => "TEXT".to_string(),
+            SqlType::CiText => "CITEXT".to_string(),
             SqlType::Binary(len) => format!("BINARY({})", len),
             SqlType::VarBinary(len) => format!("VARBINARY({})", len),
             SqlType::Blob => "BLOB".to_string(),
@@ -107,7 +120,10 @@ impl SqlType {
 
     /// Check if this type is text-based.
     pub const fn is_text(&self) -> bool {
-        matches!(self, SqlType::Char(_) | SqlType::VarChar(_) | SqlType::Text)
+        matches!(
+            self,
+            SqlType::Char(_) | SqlType::VarChar(_) | SqlType::Text | SqlType::CiText
+        )
     }
 
     /// Check if this type is a date/time type.
@@ -179,6 +195,18 @@ impl TypeInfo for i64 {
     const SQL_TYPE: SqlType = SqlType::BigInt;
 }
 
+impl TypeInfo for u8 {
+    const SQL_TYPE: SqlType = SqlType::SmallInt;
+}
+
+impl TypeInfo for u16 {
+    const SQL_TYPE: SqlType = SqlType::Integer;
+}
+
+impl TypeInfo for u32 {
+    const SQL_TYPE: SqlType = SqlType::BigInt;
+}
+
 impl TypeInfo for f32 {
     const SQL_TYPE: SqlType = SqlType::Real;
 }
@@ -203,3 +231,26 @@ impl<T: TypeInfo> TypeInfo for Option<T> {
     const SQL_TYPE: SqlType = T::SQL_TYPE;
     const NULLABLE: bool = true;
 }
+
+/// A type stored in a single column, usable as a generic model field.
+///
+/// `#[derive(Model)]` infers column types from how a field's type is written,
+/// which can't work for a type parameter. For fields typed `T` or `Option<T>`
+/// the derive instead adds a `T: SqlScalar` bound and reads the column type and
+/// nullability from [`TypeInfo`].
+///
+/// Implemented automatically for every type with [`TypeInfo`], [`FromValue`] and
+/// `Into<Value>`, including `Option<T>`.
+///
+/// ```ignore
+/// #[derive(Model)]
+/// #[sqlmodel(table = "audit_events")]
+/// struct AuditEvent<K: SqlScalar> {
+///     #[sqlmodel(primary_key)]
+///     id: K,
+///     action: String,
+/// }
+/// ```
+pub trait SqlScalar: TypeInfo + FromValue + Into<Value> + Clone + Send + Sync + 'static {}
+
+impl<T> SqlScalar for T where T: TypeInfo + FromValue + Into<Value> + Clone + Send + Sync + 'static {}
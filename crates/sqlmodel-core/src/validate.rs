@@ -177,6 +177,57 @@ pub fn is_valid_credit_card(value: &str) -> bool {
     sum % 10 == 0
 }
 
+/// Field and model validation rules, implemented by `#[derive(Validate)]`.
+pub trait Validate {
+    /// Check every rule, collecting all failures.
+    fn validate(&self) -> std::result::Result<(), ValidationError>;
+}
+
+/// Whether a field counts as "set" for `#[validate(required_if = "...")]`.
+///
+/// `Option` is set when `Some`, `bool` when `true`, and strings and
+/// collections when non-empty.
+pub trait Presence {
+    /// `true` if the value is set.
+    fn is_present(&self) -> bool;
+}
+
+impl<T> Presence for Option<T> {
+    fn is_present(&self) -> bool {
+        self.is_some()
+    }
+}
+
+impl Presence for bool {
+    fn is_present(&self) -> bool {
+        *self
+    }
+}
+
+impl Presence for str {
+    fn is_present(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+impl Presence for String {
+    fn is_present(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+impl<T> Presence for Vec<T> {
+    fn is_present(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+impl<T: Presence + ?Sized> Presence for &T {
+    fn is_present(&self) -> bool {
+        (**self).is_present()
+    }
+}
+
 // ============================================================================
 // Model Validation (model_validate)
 // ============================================================================
@@ -986,9 +1037,9 @@ pub trait SqlModelDump: Model + serde::Serialize {
 
         // Apply options that work on original field names BEFORE alias renaming
         if let serde_json::Value::Object(ref mut map) = value {
-            // Always honor per-field exclude flag (Pydantic Field(exclude=True) semantics).
+            // Always honor per-field exclude/db_only flags (Pydantic Field(exclude=True) semantics).
             for field in Self::fields() {
-                if field.exclude {
+                if !field.is_serialized() {
                     map.remove(field.name);
                 }
             }
@@ -2981,6 +3032,17 @@ mod tests {
         assert!(is_valid_credit_card("6011111111111117"));
     }
 
+    #[test]
+    fn test_presence() {
+        assert!(Some(0).is_present());
+        assert!(!None::<i32>.is_present());
+        assert!(true.is_present());
+        assert!(!false.is_present());
+        assert!("x".is_present());
+        assert!(!String::new().is_present());
+        assert!(!Vec::<i32>::new().is_present());
+    }
+
     // =========================================================================
     // Nested Model Tests
     // =========================================================================
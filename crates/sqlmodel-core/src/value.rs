@@ -1,5 +1,7 @@
 //! Dynamic SQL values.
 
+use std::hash::Hasher;
+
 use serde::{Deserialize, Serialize};
 
 /// A dynamically-typed SQL value.
@@ -266,6 +268,210 @@ impl Value {
             })),
         }
     }
+
+    /// Feed a variant-tagged encoding of this value into `hasher`.
+    ///
+    /// Every variant writes a tag byte followed by its contents as
+    /// little-endian integers and length-prefixed bytes, so the bytes fed are
+    /// the same on every platform. Floats are written by bit pattern. JSON and
+    /// arrays are encoded structurally, without serializing; JSON object
+    /// entries are combined order-independently so equal objects hash equally
+    /// regardless of key order.
+    pub fn hash_into<H: Hasher>(&self, hasher: &mut H) {
+        match self {
+            Value::Null => hasher.write(&[0]),
+            Value::Bool(b) => hasher.write(&[1, u8::from(*b)]),
+            Value::TinyInt(i) => {
+                hasher.write(&[2]);
+                hasher.write(&i.to_le_bytes());
+            }
+            Value::SmallInt(i) => {
+                hasher.write(&[3]);
+                hasher.write(&i.to_le_bytes());
+            }
+            Value::Int(i) => {
+                hasher.write(&[4]);
+                hasher.write(&i.to_le_bytes());
+            }
+            Value::BigInt(i) => {
+                hasher.write(&[5]);
+                hasher.write(&i.to_le_bytes());
+            }
+            Value::Float(f) => {
+                hasher.write(&[6]);
+                hasher.write(&f.to_bits().to_le_bytes());
+            }
+            Value::Double(f) => {
+                hasher.write(&[7]);
+                hasher.write(&f.to_bits().to_le_bytes());
+            }
+            Value::Decimal(s) => {
+                hasher.write(&[8]);
+                write_len_prefixed(hasher, s.as_bytes());
+            }
+            Value::Text(s) => {
+                hasher.write(&[9]);
+                write_len_prefixed(hasher, s.as_bytes());
+            }
+            Value::Bytes(b) => {
+                hasher.write(&[10]);
+                write_len_prefixed(hasher, b);
+            }
+            Value::Date(d) => {
+                hasher.write(&[11]);
+                hasher.write(&d.to_le_bytes());
+            }
+            Value::Time(t) => {
+                hasher.write(&[12]);
+                hasher.write(&t.to_le_bytes());
+            }
+            Value::Timestamp(ts) => {
+                hasher.write(&[13]);
+                hasher.write(&ts.to_le_bytes());
+            }
+            Value::TimestampTz(ts) => {
+                hasher.write(&[14]);
+                hasher.write(&ts.to_le_bytes());
+            }
+            Value::Uuid(u) => {
+                hasher.write(&[15]);
+                hasher.write(u);
+            }
+            Value::Json(j) => {
+                hasher.write(&[16]);
+                hash_json(j, hasher);
+            }
+            Value::Array(arr) => {
+                hasher.write(&[17]);
+                hasher.write(&(arr.len() as u64).to_le_bytes());
+                for item in arr {
+                    item.hash_into(hasher);
+                }
+            }
+            Value::Default => hasher.write(&[18]),
+        }
+    }
+
+    /// Hash this value with 64-bit FNV-1a (plus an `fmix64` finalizer) over
+    /// [`hash_into`](Self::hash_into).
+    ///
+    /// The algorithm and encoding are fixed, so the result is the same across
+    /// processes, platforms and toolchains; it keys identity maps and routes
+    /// shards, and must not change.
+    pub fn stable_hash(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        self.hash_into(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Hash a tuple of values (e.g. a composite primary key) with the same
+    /// hasher as [`stable_hash`](Self::stable_hash).
+    pub fn stable_hash_slice(values: &[Value]) -> u64 {
+        let mut hasher = StableHasher::new();
+        for v in values {
+            v.hash_into(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// Like [`stable_hash_slice`](Self::stable_hash_slice), but integers hash
+    /// alike whatever their width, so a key given as `Int(1)` matches one
+    /// read back as `BigInt(1)`.
+    pub fn stable_key_hash(values: &[Value]) -> u64 {
+        let mut hasher = StableHasher::new();
+        for v in values {
+            match v {
+                Value::TinyInt(_) | Value::SmallInt(_) | Value::Int(_) => {
+                    Value::BigInt(v.as_i64().unwrap_or_default()).hash_into(&mut hasher);
+                }
+                other => other.hash_into(&mut hasher),
+            }
+        }
+        hasher.finish()
+    }
+}
+
+/// 64-bit FNV-1a with an `fmix64` finalizer, the fixed hash behind
+/// [`Value::stable_hash`].
+struct StableHasher(u64);
+
+impl StableHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    /// The FNV-1a state passed through MurmurHash3's `fmix64`, so the low
+    /// bits spread well enough for `hash % n` routing.
+    fn finish(&self) -> u64 {
+        let mut h = self.0;
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+        h ^ (h >> 33)
+    }
+}
+
+/// Write `bytes` preceded by their length as a little-endian `u64`.
+fn write_len_prefixed<H: Hasher>(hasher: &mut H, bytes: &[u8]) {
+    hasher.write(&(bytes.len() as u64).to_le_bytes());
+    hasher.write(bytes);
+}
+
+/// Structural JSON encoding; see [`Value::hash_into`].
+fn hash_json<H: Hasher>(json: &serde_json::Value, hasher: &mut H) {
+    match json {
+        serde_json::Value::Null => hasher.write(&[0]),
+        serde_json::Value::Bool(b) => hasher.write(&[1, u8::from(*b)]),
+        serde_json::Value::Number(n) => {
+            hasher.write(&[2]);
+            if let Some(i) = n.as_i64() {
+                hasher.write(&[0]);
+                hasher.write(&i.to_le_bytes());
+            } else if let Some(u) = n.as_u64() {
+                hasher.write(&[1]);
+                hasher.write(&u.to_le_bytes());
+            } else if let Some(f) = n.as_f64() {
+                hasher.write(&[2]);
+                hasher.write(&f.to_bits().to_le_bytes());
+            }
+        }
+        serde_json::Value::String(s) => {
+            hasher.write(&[3]);
+            write_len_prefixed(hasher, s.as_bytes());
+        }
+        serde_json::Value::Array(items) => {
+            hasher.write(&[4]);
+            hasher.write(&(items.len() as u64).to_le_bytes());
+            for item in items {
+                hash_json(item, hasher);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            hasher.write(&[5]);
+            hasher.write(&(map.len() as u64).to_le_bytes());
+            let mut combined = 0u64;
+            for (key, value) in map {
+                let mut entry = StableHasher::new();
+                write_len_prefixed(&mut entry, key.as_bytes());
+                hash_json(value, &mut entry);
+                combined = combined.wrapping_add(entry.finish());
+            }
+            hasher.write(&combined.to_le_bytes());
+        }
+    }
 }
 
 // Conversion implementations
@@ -1413,4 +1619,89 @@ mod tests {
         let v: f32 = Value::Float(original).try_into().unwrap();
         assert!((v - original).abs() < f32::EPSILON);
     }
+
+    #[test]
+    fn test_stable_hash_distinguishes_variants() {
+        assert_eq!(Value::Int(1).stable_hash(), Value::Int(1).stable_hash());
+        assert_ne!(Value::Int(1).stable_hash(), Value::BigInt(1).stable_hash());
+        assert_ne!(
+            Value::Text("1".into()).stable_hash(),
+            Value::Decimal("1".into()).stable_hash()
+        );
+        assert_eq!(
+            Value::stable_hash_slice(&[Value::Int(1), Value::Text("a".into())]),
+            Value::stable_hash_slice(&[Value::Int(1), Value::Text("a".into())])
+        );
+        assert_ne!(
+            Value::stable_hash_slice(&[Value::Int(1), Value::Int(2)]),
+            Value::stable_hash_slice(&[Value::Int(2), Value::Int(1)])
+        );
+    }
+
+    #[test]
+    fn test_stable_key_hash_ignores_integer_width() {
+        let key = Value::stable_key_hash(&[Value::BigInt(1), Value::Text("a".into())]);
+        assert_eq!(
+            Value::stable_key_hash(&[Value::Int(1), Value::Text("a".into())]),
+            key
+        );
+        assert_eq!(
+            Value::stable_key_hash(&[Value::TinyInt(1), Value::Text("a".into())]),
+            key
+        );
+        assert_eq!(
+            key,
+            Value::stable_hash_slice(&[Value::BigInt(1), Value::Text("a".into())])
+        );
+        assert_ne!(
+            Value::stable_key_hash(&[Value::Int(1)]),
+            Value::stable_key_hash(&[Value::Int(2)])
+        );
+    }
+
+    #[test]
+    fn test_stable_hash_is_pinned() {
+        // These values route shards; a change here moves rows.
+        assert_eq!(Value::BigInt(1).stable_hash(), 0x5d58_26c1_67f2_48c0);
+        assert_eq!(Value::Text("a".into()).stable_hash(), 0xe1e1_bc92_d5a1_300c);
+    }
+
+    #[test]
+    fn test_stable_hash_json_is_structural() {
+        let a: serde_json::Value = serde_json::json!({"a": 1, "b": [true, null, "x"]});
+        let b: serde_json::Value =
+            serde_json::from_str(r#"{"b": [true, null, "x"], "a": 1}"#).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(
+            Value::Json(a.clone()).stable_hash(),
+            Value::Json(b).stable_hash()
+        );
+
+        let c = serde_json::json!({"a": 2, "b": [true, null, "x"]});
+        assert_ne!(
+            Value::Json(a.clone()).stable_hash(),
+            Value::Json(c).stable_hash()
+        );
+        // Swapping a key and its value must not collide.
+        assert_ne!(
+            Value::Json(serde_json::json!({"k": "v"})).stable_hash(),
+            Value::Json(serde_json::json!({"v": "k"})).stable_hash()
+        );
+        assert_ne!(
+            Value::Json(serde_json::json!([1, 2])).stable_hash(),
+            Value::Json(serde_json::json!([2, 1])).stable_hash()
+        );
+        assert_ne!(
+            Value::Json(serde_json::json!(1)).stable_hash(),
+            Value::Json(serde_json::json!(1.0)).stable_hash()
+        );
+    }
+
+    #[test]
+    fn test_stable_hash_array_nesting() {
+        let flat = Value::Array(vec![Value::Int(1), Value::Int(2)]);
+        let nested = Value::Array(vec![Value::Array(vec![Value::Int(1)]), Value::Int(2)]);
+        assert_ne!(flat.stable_hash(), nested.stable_hash());
+        assert_eq!(flat.stable_hash(), flat.clone().stable_hash());
+    }
 }
@@ -155,9 +155,8 @@ impl FrankenConnection {
 
         // Query PRAGMA table_info to get column names
         let pragma_sql = format!("PRAGMA table_info({})", table_name);
-        let pragma_rows = match conn.query(&pragma_sql) {
-            Ok(rows) => rows,
-            Err(_) => return None,
+        let Ok(pragma_rows) = conn.query(&pragma_sql) else {
+            return None;
         };
 
         // PRAGMA table_info returns: cid, name, type, notnull, dflt_value, pk
@@ -758,9 +757,7 @@ fn infer_returning_columns(sql: &str) -> Vec<String> {
     let upper = sql.to_uppercase();
 
     // Find RETURNING keyword
-    let returning_pos = if let Some(pos) = find_keyword_at_depth_zero(&upper, "RETURNING") {
-        pos
-    } else {
+    let Some(returning_pos) = find_keyword_at_depth_zero(&upper, "RETURNING") else {
         return Vec::new();
     };
 
@@ -843,9 +840,9 @@ fn extract_identifier(s: &str) -> String {
     }
 
     // Quoted identifier
-    if trimmed.starts_with('"') {
-        if let Some(end) = trimmed[1..].find('"') {
-            return trimmed[1..end + 1].to_string();
+    if let Some(quoted) = trimmed.strip_prefix('"') {
+        if let Some(end) = quoted.find('"') {
+            return quoted[..end].to_string();
         }
         return String::new();
     }
@@ -1037,7 +1034,7 @@ fn franken_to_query_error(e: &fsqlite_error::FrankenError, sql: &str) -> Error {
         FrankenError::UniqueViolation { .. } | FrankenError::NotNullViolation { .. } => {
             QueryErrorKind::Constraint
         }
-        FrankenError::ForeignKeyViolation { .. } | FrankenError::CheckViolation { .. } => {
+        FrankenError::ForeignKeyViolation | FrankenError::CheckViolation { .. } => {
             QueryErrorKind::Constraint
         }
         FrankenError::WriteConflict { .. } | FrankenError::SerializationFailure { .. } => {
@@ -1048,7 +1045,7 @@ fn franken_to_query_error(e: &fsqlite_error::FrankenError, sql: &str) -> Error {
         _ => QueryErrorKind::Database,
     };
 
-    Error::Query(QueryError {
+    Error::from_query_error(QueryError {
         kind,
         sql: Some(sql.to_string()),
         sqlstate: None,
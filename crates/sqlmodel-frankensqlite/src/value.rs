@@ -89,12 +89,12 @@ mod tests {
 
     #[test]
     fn float_variants() {
-        let sv = value_to_sqlite(&Value::Float(3.14));
+        let sv = value_to_sqlite(&Value::Float(1.5));
         assert!(matches!(sv, SqliteValue::Float(_)));
 
         assert_eq!(
-            value_to_sqlite(&Value::Double(2.718)),
-            SqliteValue::Float(2.718)
+            value_to_sqlite(&Value::Double(2.5)),
+            SqliteValue::Float(2.5)
         );
     }
 
@@ -163,7 +163,7 @@ mod tests {
 
     #[test]
     fn sqlite_float_to_double() {
-        let v = sqlite_to_value(&SqliteValue::Float(3.14));
-        assert_eq!(v, Value::Double(3.14));
+        let v = sqlite_to_value(&SqliteValue::Float(1.5));
+        assert_eq!(v, Value::Double(1.5));
     }
 }
@@ -0,0 +1,298 @@
+//! Filter input objects and their translation into [`Expr`] trees.
+//!
+//! Every model gets a `{Model}Filter` input with one operator object per
+//! filterable field plus `and`, `or` and `not` combinators:
+//!
+//! ```graphql
+//! { heroes(filter: { or: [{ age: { gt: 30 } }, { name: { like: "Spider%" } }] }) { name } }
+//! ```
+
+use async_graphql::dynamic::{InputObject, InputValue, TypeRef};
+use async_graphql::{Name, Value as Gql};
+use sqlmodel_core::{FieldInfo, Model, Value};
+use sqlmodel_query::Expr;
+use sqlmodel_schema::openapi::model_name;
+
+use crate::{exposed, graphql_type};
+
+const INT_FILTER: &str = "IntFilter";
+const FLOAT_FILTER: &str = "FloatFilter";
+const STRING_FILTER: &str = "StringFilter";
+const BOOLEAN_FILTER: &str = "BooleanFilter";
+
+/// Name of the filter input declared for `M`.
+pub(crate) fn filter_name<M: Model>() -> String {
+    format!("{}Filter", model_name::<M>())
+}
+
+/// The operator input for `field`, or `None` if its column cannot be
+/// filtered on (JSON, arrays and custom types).
+fn scalar_filter(field: &FieldInfo) -> Option<&'static str> {
+    match graphql_type(&field.sql_type) {
+        TypeRef::BOOLEAN => Some(BOOLEAN_FILTER),
+        TypeRef::INT => Some(INT_FILTER),
+        TypeRef::FLOAT => Some(FLOAT_FILTER),
+        TypeRef::STRING => Some(STRING_FILTER),
+        _ => None,
+    }
+}
+
+fn operators(name: &str, scalar: &str, ordered: bool) -> InputObject {
+    let mut input = InputObject::new(name)
+        .field(InputValue::new("eq", TypeRef::named(scalar)))
+        .field(InputValue::new("ne", TypeRef::named(scalar)));
+    if ordered {
+        for op in ["gt", "gte", "lt", "lte"] {
+            input = input.field(InputValue::new(op, TypeRef::named(scalar)));
+        }
+    }
+    input
+        .field(InputValue::new("in", TypeRef::named_nn_list(scalar)))
+        .field(InputValue::new("isNull", TypeRef::named(TypeRef::BOOLEAN)))
+}
+
+/// The operator inputs shared by every model filter: `IntFilter`,
+/// `FloatFilter`, `StringFilter` (with `like` and `ilike`) and
+/// `BooleanFilter`.
+pub fn scalar_filter_inputs() -> Vec<InputObject> {
+    vec![
+        operators(INT_FILTER, TypeRef::INT, true),
+        operators(FLOAT_FILTER, TypeRef::FLOAT, true),
+        operators(STRING_FILTER, TypeRef::STRING, true)
+            .field(InputValue::new("like", TypeRef::named(TypeRef::STRING)))
+            .field(InputValue::new("ilike", TypeRef::named(TypeRef::STRING))),
+        operators(BOOLEAN_FILTER, TypeRef::BOOLEAN, false),
+    ]
+}
+
+/// The `{Model}Filter` input object for `M`.
+pub fn filter_input<M: Model>() -> InputObject {
+    let name = filter_name::<M>();
+    let mut input = InputObject::new(&name)
+        .field(InputValue::new("and", TypeRef::named_nn_list(&name)))
+        .field(InputValue::new("or", TypeRef::named_nn_list(&name)))
+        .field(InputValue::new("not", TypeRef::named(&name)));
+    for field in M::fields().iter().filter(|field| exposed(field)) {
+        if let Some(scalar) = scalar_filter(field) {
+            input = input.field(InputValue::new(field.output_name(), TypeRef::named(scalar)));
+        }
+    }
+    input
+}
+
+/// Translate a `{Model}Filter` value into a `WHERE` expression.
+///
+/// Returns `None` for a null or empty filter. Fields are addressed by their
+/// GraphQL (serialization) names and compared against their columns.
+pub fn filter_expr<M: Model>(filter: &Gql) -> async_graphql::Result<Option<Expr>> {
+    let entries = match filter {
+        Gql::Null => return Ok(None),
+        Gql::Object(entries) => entries,
+        other => {
+            return Err(async_graphql::Error::new(format!(
+                "{} must be an object, got {other}",
+                filter_name::<M>()
+            )));
+        }
+    };
+
+    let mut terms = Vec::new();
+    for (key, value) in entries {
+        if matches!(value, Gql::Null) {
+            continue;
+        }
+        let term = match key.as_str() {
+            "and" => join(list::<M>(key, value)?, Expr::and),
+            "or" => join(list::<M>(key, value)?, Expr::or),
+            "not" => filter_expr::<M>(value)?.map(|expr| expr.paren().not()),
+            name => {
+                let field = M::fields()
+                    .iter()
+                    .find(|field| exposed(field) && field.output_name() == name)
+                    .ok_or_else(|| {
+                        async_graphql::Error::new(format!(
+                            "{} has no field `{name}`",
+                            filter_name::<M>()
+                        ))
+                    })?;
+                field_expr(field, value)?
+            }
+        };
+        terms.extend(term);
+    }
+    Ok(join(terms, Expr::and))
+}
+
+/// The expressions of an `and`/`or` list, skipping empty entries.
+fn list<M: Model>(key: &Name, value: &Gql) -> async_graphql::Result<Vec<Expr>> {
+    let Gql::List(items) = value else {
+        return Err(async_graphql::Error::new(format!(
+            "`{key}` expects a list of {}",
+            filter_name::<M>()
+        )));
+    };
+    let mut exprs = Vec::with_capacity(items.len());
+    for item in items {
+        exprs.extend(filter_expr::<M>(item)?);
+    }
+    Ok(exprs)
+}
+
+/// Combine `terms` with `op`, parenthesising each so nested `OR`s keep
+/// their meaning.
+fn join(terms: Vec<Expr>, op: fn(Expr, Expr) -> Expr) -> Option<Expr> {
+    let single = terms.len() == 1;
+    terms
+        .into_iter()
+        .map(|term| if single { term } else { term.paren() })
+        .reduce(op)
+}
+
+fn field_expr(field: &FieldInfo, ops: &Gql) -> async_graphql::Result<Option<Expr>> {
+    let Gql::Object(ops) = ops else {
+        return Err(async_graphql::Error::new(format!(
+            "filter on `{}` must be an object",
+            field.output_name()
+        )));
+    };
+    let column = || Expr::col(field.column_name);
+    let mut terms = Vec::new();
+    for (op, operand) in ops {
+        if matches!(operand, Gql::Null) {
+            continue;
+        }
+        let term = match op.as_str() {
+            "eq" => column().eq(sql_value(field, operand)?),
+            "ne" => column().ne(sql_value(field, operand)?),
+            "gt" => column().gt(sql_value(field, operand)?),
+            "gte" => column().ge(sql_value(field, operand)?),
+            "lt" => column().lt(sql_value(field, operand)?),
+            "lte" => column().le(sql_value(field, operand)?),
+            "in" => {
+                let Gql::List(items) = operand else {
+                    return Err(async_graphql::Error::new("`in` expects a list"));
+                };
+                let values = items
+                    .iter()
+                    .map(|item| sql_value(field, item))
+                    .collect::<async_graphql::Result<Vec<Value>>>()?;
+                column().in_list(values)
+            }
+            "isNull" => match operand {
+                Gql::Boolean(true) => column().is_null(),
+                Gql::Boolean(false) => column().is_not_null(),
+                _ => return Err(async_graphql::Error::new("`isNull` expects a boolean")),
+            },
+            "like" | "ilike" => {
+                let Gql::String(pattern) = operand else {
+                    return Err(async_graphql::Error::new(format!(
+                        "`{op}` expects a string"
+                    )));
+                };
+                if op.as_str() == "like" {
+                    column().like(pattern.clone())
+                } else {
+                    column().ilike(pattern.clone())
+                }
+            }
+            other => {
+                return Err(async_graphql::Error::new(format!(
+                    "unknown filter operator `{other}` on `{}`",
+                    field.output_name()
+                )));
+            }
+        };
+        terms.push(term);
+    }
+    Ok(join(terms, Expr::and))
+}
+
+/// Convert a filter operand into a parameter for `field`'s column.
+fn sql_value(field: &FieldInfo, operand: &Gql) -> async_graphql::Result<Value> {
+    let json = operand.clone().into_json()?;
+    Value::from_json(json, &field.sql_type).map_err(|e| {
+        async_graphql::Error::new(format!("invalid value for `{}`: {e}", field.output_name()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlmodel_core::{Row, SqlType};
+
+    struct Hero;
+
+    impl Model for Hero {
+        const TABLE_NAME: &'static str = "heroes";
+        const PRIMARY_KEY: &'static [&'static str] = &["id"];
+
+        fn fields() -> &'static [FieldInfo] {
+            static FIELDS: &[FieldInfo] = &[
+                FieldInfo::new("id", "id", SqlType::BigInt).primary_key(true),
+                FieldInfo::new("name", "hero_name", SqlType::Text).serialization_alias("heroName"),
+                FieldInfo::new("age", "age", SqlType::Integer).nullable(true),
+                FieldInfo::new("secret", "secret", SqlType::Text).exclude(true),
+            ];
+            FIELDS
+        }
+
+        fn to_row(&self) -> Vec<(&'static str, Value)> {
+            Vec::new()
+        }
+
+        fn from_row(_row: &Row) -> sqlmodel_core::Result<Self> {
+            Ok(Self)
+        }
+
+        fn primary_key_value(&self) -> Vec<Value> {
+            Vec::new()
+        }
+
+        fn is_new(&self) -> bool {
+            true
+        }
+    }
+
+    // serde_json maps are ordered by key, so inputs list keys alphabetically.
+    fn parse(input: &str) -> Gql {
+        let json: serde_json::Value = serde_json::from_str(input).unwrap();
+        Gql::from_json(json).unwrap()
+    }
+
+    fn render(input: &str) -> (String, Vec<Value>) {
+        let expr = filter_expr::<Hero>(&parse(input)).unwrap().unwrap();
+        let mut params = Vec::new();
+        let sql = expr.build(&mut params, 0);
+        (sql, params)
+    }
+
+    #[test]
+    fn test_filter_expr_uses_columns_behind_aliases() {
+        let (sql, params) = render(r#"{"age": {"gte": 30}, "heroName": {"eq": "Deadpond"}}"#);
+        assert_eq!(sql, "(\"age\" >= $1) AND (\"hero_name\" = $2)");
+        assert_eq!(params, vec![Value::Int(30), Value::Text("Deadpond".into())]);
+    }
+
+    #[test]
+    fn test_filter_expr_combinators() {
+        let (sql, params) = render(
+            r#"{"not": {"heroName": {"like": "Spider%"}}, "or": [{"age": {"isNull": true}}, {"age": {"in": [1, 2]}}]}"#,
+        );
+        assert_eq!(
+            sql,
+            "(NOT (\"hero_name\" LIKE $1)) AND ((\"age\" IS NULL) OR (\"age\" IN ($2, $3)))"
+        );
+        assert_eq!(
+            params,
+            vec![Value::Text("Spider%".into()), Value::Int(1), Value::Int(2)]
+        );
+    }
+
+    #[test]
+    fn test_filter_expr_rejects_hidden_and_unknown_fields() {
+        assert!(filter_expr::<Hero>(&parse(r#"{"secret": {"eq": "x"}}"#)).is_err());
+        assert!(filter_expr::<Hero>(&parse(r#"{"hero_name": {"eq": "x"}}"#)).is_err());
+        assert!(filter_expr::<Hero>(&parse(r#"{"age": {"eq": "old"}}"#)).is_err());
+        assert!(filter_expr::<Hero>(&Gql::Null).unwrap().is_none());
+    }
+}
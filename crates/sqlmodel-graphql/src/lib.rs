@@ -0,0 +1,169 @@
+//! async-graphql bridge for SQLModel Rust.
+//!
+//! `sqlmodel-graphql` builds [`async_graphql::dynamic`] schema types straight
+//! from [`Model`] metadata, so a model becomes a GraphQL object without a
+//! second, hand-written type.
+//!
+//! # Role In The Architecture
+//!
+//! - **Object types**: [`ModelObject`] exposes every field that reaches API
+//!   shapes (not `exclude`, `api_exclude` or `db_only`) under its
+//!   serialization name.
+//! - **Relationships**: [`ModelObject::relation`] resolves a foreign key
+//!   through a session [`Loader`](sqlmodel_session::Loader) in the request
+//!   data, so sibling resolvers share one `IN` query.
+//! - **Filters**: [`filter_input`] declares a `HeroFilter` input type and
+//!   [`filter_expr`] turns its values into an [`Expr`](sqlmodel_query::Expr)
+//!   for [`list_field`] queries.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use async_graphql::dynamic::{Object, Schema};
+//! use sqlmodel_graphql::{ModelObject, SharedSession, list_field, register_scalars};
+//!
+//! let query = Object::new("Query").field(list_field::<Hero, PgConnection>("heroes"));
+//! let schema = Schema::build("Query", None, None).register(query);
+//! let schema = ModelObject::<Hero>::new()
+//!     .relation::<Team, PgConnection>("team", |hero| hero.team_id.map(Value::BigInt))
+//!     .register(schema);
+//! let schema = register_scalars(ModelObject::<Team>::new().register(schema)).finish()?;
+//!
+//! // Per request: one shared session, one loader per related model.
+//! let session: SharedSession<PgConnection> = Arc::new(Mutex::new(Session::new(conn)));
+//! let request = async_graphql::Request::new("{ heroes(filter: { age: { gt: 30 } }) { name team { name } } }")
+//!     .data(cx.clone())
+//!     .data(Loader::<Value, Team, _>::shared(Arc::clone(&session)))
+//!     .data(session);
+//! let response = schema.execute(request).await;
+//! ```
+//!
+//! # Runtime Exception
+//!
+//! async-graphql unconditionally depends on `async-io` (smol's reactor),
+//! `multer` and `http`, which the asupersync-only rule otherwise forbids.
+//! The bridge is therefore compiled only with the opt-in `async-graphql`
+//! feature (enabled by `sqlmodel`'s `graphql` feature); without it this
+//! crate is empty.
+
+#![cfg(feature = "async-graphql")]
+
+mod filter;
+mod object;
+
+use std::sync::Arc;
+
+use asupersync::Outcome;
+use async_graphql::dynamic::{Scalar, SchemaBuilder, TypeRef};
+use sqlmodel_core::{Error, FieldInfo, SqlType, Value};
+use sqlmodel_session::Session;
+
+pub use filter::{filter_expr, filter_input, scalar_filter_inputs};
+pub use object::{ModelObject, list_field};
+
+/// Name of the custom scalar carrying JSON, array and custom-typed columns.
+pub const JSON_SCALAR: &str = "JSON";
+
+/// A session shared by the resolvers of one request, as stored in its data.
+pub type SharedSession<C> = Arc<asupersync::sync::Mutex<Session<C>>>;
+
+/// Register the types every model schema shares: the [`JSON_SCALAR`] and
+/// the operator inputs from [`scalar_filter_inputs`].
+pub fn register_scalars(schema: SchemaBuilder) -> SchemaBuilder {
+    scalar_filter_inputs().into_iter().fold(
+        schema.register(Scalar::new(JSON_SCALAR)),
+        SchemaBuilder::register,
+    )
+}
+
+/// Whether `field` appears in GraphQL types.
+pub fn exposed(field: &FieldInfo) -> bool {
+    field.in_api() && field.is_serialized() && !field.computed
+}
+
+/// GraphQL scalar used for columns of `sql_type`.
+///
+/// 64-bit integers use `Int`, as async-graphql does for `i64`. Decimals,
+/// temporal types, UUIDs and bytes are strings in the shapes
+/// [`Value::to_json`] writes.
+pub fn graphql_type(sql_type: &SqlType) -> &'static str {
+    match sql_type {
+        SqlType::Boolean => TypeRef::BOOLEAN,
+        SqlType::TinyInt | SqlType::SmallInt | SqlType::Integer | SqlType::BigInt => TypeRef::INT,
+        SqlType::Real | SqlType::Double => TypeRef::FLOAT,
+        SqlType::Json | SqlType::JsonB | SqlType::Array(_) | SqlType::Custom(_) => JSON_SCALAR,
+        _ => TypeRef::STRING,
+    }
+}
+
+/// Field type of `field`: its scalar, non-null unless the column is nullable.
+pub fn field_type(field: &FieldInfo) -> TypeRef {
+    let name = graphql_type(&field.sql_type);
+    if field.nullable {
+        TypeRef::named(name)
+    } else {
+        TypeRef::named_nn(name)
+    }
+}
+
+/// Convert a column value to its GraphQL form, via [`Value::to_json`].
+pub fn graphql_value(value: &Value) -> async_graphql::Value {
+    async_graphql::Value::from_json(value.to_json()).unwrap_or(async_graphql::Value::Null)
+}
+
+/// Turn a database outcome into a resolver result.
+pub fn into_result<T>(outcome: Outcome<T, Error>) -> async_graphql::Result<T> {
+    match outcome {
+        Outcome::Ok(value) => Ok(value),
+        Outcome::Err(e) => Err(async_graphql::Error::new(e.to_string())),
+        Outcome::Cancelled(r) => Err(async_graphql::Error::new(format!(
+            "request cancelled: {r:?}"
+        ))),
+        Outcome::Panicked(p) => Err(async_graphql::Error::new(format!(
+            "resolver panicked: {p:?}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graphql_value_matches_json_bridge() {
+        assert_eq!(
+            graphql_value(&Value::BigInt(7)),
+            async_graphql::Value::from(7)
+        );
+        assert_eq!(
+            graphql_value(&Value::Text("Deadpond".into())),
+            async_graphql::Value::from("Deadpond")
+        );
+        assert_eq!(
+            graphql_value(&Value::Bytes(vec![0xca, 0xfe])),
+            async_graphql::Value::from("yv4=")
+        );
+        assert_eq!(
+            graphql_value(&Value::Date(19_783)),
+            async_graphql::Value::from("2024-03-01")
+        );
+        assert_eq!(
+            graphql_value(&Value::Uuid([0x11; 16])),
+            async_graphql::Value::from("11111111-1111-1111-1111-111111111111")
+        );
+        assert_eq!(
+            graphql_value(&Value::Json(serde_json::json!({"a": [1]}))),
+            async_graphql::Value::from_json(serde_json::json!({"a": [1]})).unwrap()
+        );
+        assert_eq!(graphql_value(&Value::Null), async_graphql::Value::Null);
+    }
+
+    #[test]
+    fn test_field_type_follows_nullability() {
+        let id = FieldInfo::new("id", "id", SqlType::BigInt);
+        let age = FieldInfo::new("age", "age", SqlType::Integer).nullable(true);
+        assert_eq!(field_type(&id).to_string(), "Int!");
+        assert_eq!(field_type(&age).to_string(), "Int");
+        assert_eq!(graphql_type(&SqlType::JsonB), JSON_SCALAR);
+    }
+}
@@ -0,0 +1,206 @@
+//! GraphQL object types and list queries derived from models.
+
+use std::marker::PhantomData;
+
+use asupersync::Cx;
+use async_graphql::dynamic::{
+    Field, FieldFuture, FieldValue, InputValue, Object, SchemaBuilder, TypeRef,
+};
+use serde::{Deserialize, Serialize};
+use sqlmodel_core::{Connection, Model, Value};
+use sqlmodel_query::Select;
+use sqlmodel_schema::openapi::model_name;
+use sqlmodel_session::Loader;
+
+use crate::filter::{filter_expr, filter_input, filter_name};
+use crate::{SharedSession, exposed, field_type, graphql_value, into_result};
+
+/// The GraphQL object type of a model.
+///
+/// Resolvers expect the parent value to be an owned `M`, as produced by
+/// [`list_field`] and relationship fields.
+pub struct ModelObject<M> {
+    object: Object,
+    _model: PhantomData<fn() -> M>,
+}
+
+impl<M: Model + Send + Sync + 'static> ModelObject<M> {
+    /// Build the object with one field per column exposed to APIs, named by
+    /// its serialization alias.
+    pub fn new() -> Self {
+        let mut object = Object::new(model_name::<M>());
+        for field in M::fields().iter().filter(|field| exposed(field)) {
+            let column = field.column_name;
+            let mut resolver = Field::new(field.output_name(), field_type(field), move |ctx| {
+                FieldFuture::new(async move {
+                    let model = ctx.parent_value.try_downcast_ref::<M>()?;
+                    let value = model
+                        .to_row()
+                        .into_iter()
+                        .find(|(name, _)| *name == column)
+                        .map_or(Value::Null, |(_, value)| value);
+                    Ok(Some(FieldValue::value(graphql_value(&value))))
+                })
+            });
+            if let Some(description) = field.description {
+                resolver = resolver.description(description);
+            }
+            object = object.field(resolver);
+        }
+        Self {
+            object,
+            _model: PhantomData,
+        }
+    }
+
+    /// Add a to-one relationship field resolved through the request's
+    /// `Loader<'static, Value, T, C>`.
+    ///
+    /// `fk` reads the related primary key off the parent; `None` resolves
+    /// to `null` without a query. Loads of sibling objects are batched into
+    /// one `IN` query.
+    #[must_use]
+    pub fn relation<T, C>(mut self, name: &str, fk: fn(&M) -> Option<Value>) -> Self
+    where
+        T: Model + Clone + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
+        C: Connection + 'static,
+    {
+        let field = Field::new(name, TypeRef::named(model_name::<T>()), move |ctx| {
+            FieldFuture::new(async move {
+                let parent = ctx.parent_value.try_downcast_ref::<M>()?;
+                let Some(key) = fk(parent) else {
+                    return Ok(None);
+                };
+                let cx = ctx.data::<Cx>()?;
+                let loader = ctx.data::<Loader<'static, Value, T, C>>()?;
+                let related = into_result(loader.load(cx, key).await)?;
+                Ok(related.map(FieldValue::owned_any))
+            })
+        });
+        self.object = self.object.field(field);
+        self
+    }
+
+    /// The finished object type.
+    pub fn into_object(self) -> Object {
+        self.object
+    }
+
+    /// Register the object and its `{Model}Filter` input with `schema`.
+    pub fn register(self, schema: SchemaBuilder) -> SchemaBuilder {
+        schema.register(self.object).register(filter_input::<M>())
+    }
+}
+
+impl<M: Model + Send + Sync + 'static> Default for ModelObject<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A root query field listing `M`, with optional `filter`, `limit` and
+/// `offset` arguments.
+///
+/// Runs on the request's [`SharedSession`]; the request data must also hold
+/// the [`Cx`].
+pub fn list_field<M, C>(name: &str) -> Field
+where
+    M: Model + Send + Sync + 'static,
+    C: Connection + 'static,
+{
+    Field::new(name, TypeRef::named_nn_list_nn(model_name::<M>()), |ctx| {
+        FieldFuture::new(async move {
+            let mut query = Select::<M>::new();
+            if let Some(filter) = ctx.args.get("filter") {
+                if let Some(expr) = filter_expr::<M>(filter.as_value())? {
+                    query = query.filter(expr);
+                }
+            }
+            if let Some(limit) = ctx.args.get("limit") {
+                query = query.limit(limit.u64()?);
+            }
+            if let Some(offset) = ctx.args.get("offset") {
+                query = query.offset(offset.u64()?);
+            }
+
+            let cx = ctx.data::<Cx>()?;
+            let session = ctx.data::<SharedSession<C>>()?;
+            let Ok(session) = session.lock(cx).await else {
+                return Err(async_graphql::Error::new(
+                    "failed to acquire the request session",
+                ));
+            };
+            let models = into_result(query.all(cx, session.connection()).await)?;
+            Ok(Some(FieldValue::list(
+                models.into_iter().map(FieldValue::owned_any),
+            )))
+        })
+    })
+    .argument(InputValue::new(
+        "filter",
+        TypeRef::named(filter_name::<M>()),
+    ))
+    .argument(InputValue::new("limit", TypeRef::named(TypeRef::INT)))
+    .argument(InputValue::new("offset", TypeRef::named(TypeRef::INT)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::register_scalars;
+    use async_graphql::dynamic::Schema;
+    use sqlmodel_core::{FieldInfo, Row, SqlType};
+
+    struct Hero;
+
+    impl Model for Hero {
+        const TABLE_NAME: &'static str = "heroes";
+        const PRIMARY_KEY: &'static [&'static str] = &["id"];
+
+        fn fields() -> &'static [FieldInfo] {
+            static FIELDS: &[FieldInfo] = &[
+                FieldInfo::new("id", "id", SqlType::BigInt).primary_key(true),
+                FieldInfo::new("name", "hero_name", SqlType::Text).serialization_alias("heroName"),
+                FieldInfo::new("age", "age", SqlType::Integer).nullable(true),
+                FieldInfo::new("secret", "secret", SqlType::Text).api_exclude(true),
+            ];
+            FIELDS
+        }
+
+        fn to_row(&self) -> Vec<(&'static str, Value)> {
+            Vec::new()
+        }
+
+        fn from_row(_row: &Row) -> sqlmodel_core::Result<Self> {
+            Ok(Self)
+        }
+
+        fn primary_key_value(&self) -> Vec<Value> {
+            Vec::new()
+        }
+
+        fn is_new(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_model_object_sdl_respects_aliases_and_exclusions() {
+        let query = Object::new("Query").field(Field::new("hero", TypeRef::named("Hero"), |_| {
+            FieldFuture::new(async { Ok(None::<FieldValue>) })
+        }));
+        let schema = ModelObject::<Hero>::new()
+            .register(register_scalars(
+                Schema::build("Query", None, None).register(query),
+            ))
+            .finish()
+            .unwrap();
+        let sdl = schema.sdl();
+
+        assert!(sdl.contains("type Hero"));
+        assert!(sdl.contains("heroName: String!"));
+        assert!(sdl.contains("age: Int"));
+        assert!(!sdl.contains("secret"));
+        assert!(sdl.contains("input HeroFilter"));
+    }
+}
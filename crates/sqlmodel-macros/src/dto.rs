@@ -0,0 +1,146 @@
+//! Companion DTO structs for `#[sqlmodel(generate = "...")]`.
+//!
+//! Each requested struct copies the model's fields (minus `exclude(...)`,
+//! `api_exclude`/`db_only` and relationship fields) and gets conversions back
+//! and forth:
+//!
+//! - `From<Model> for Dto` always.
+//! - `From<Dto> for Model` unless `all_optional`; excluded fields are filled
+//!   with `Default::default()`.
+//! - `Dto::apply_to(self, &mut Model)` copies the DTO's fields onto an existing
+//!   model; with `all_optional`, `None` leaves the model's value unchanged.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::parse::{DtoAttr, FieldDef, ModelDef, is_option_type};
+
+/// Generate every companion struct requested on `model`.
+pub fn generate_dtos(model: &ModelDef) -> TokenStream {
+    let dtos = model
+        .config
+        .generate
+        .iter()
+        .map(|dto| generate_dto(model, dto));
+    quote! { #(#dtos)* }
+}
+
+fn generate_dto(model: &ModelDef, dto: &DtoAttr) -> TokenStream {
+    let model_name = &model.name;
+    let name = &dto.name;
+    let vis = &model.vis;
+
+    let (kept, dropped): (Vec<&FieldDef>, Vec<&FieldDef>) = model.fields.iter().partition(|f| {
+        f.relationship.is_none() && !f.api_exclude && !f.db_only && !dto.exclude.contains(&f.name)
+    });
+    // Fields whose type gains an `Option` wrapper in this DTO.
+    let wrapped = |f: &FieldDef| dto.all_optional && !is_option_type(&f.ty);
+
+    let field_decls = kept.iter().map(|f| {
+        let field_name = &f.name;
+        let ty = &f.ty;
+        if wrapped(f) {
+            quote! { #vis #field_name: ::core::option::Option<#ty> }
+        } else {
+            quote! { #vis #field_name: #ty }
+        }
+    });
+
+    let from_model_fields = kept.iter().map(|f| {
+        let field_name = &f.name;
+        if wrapped(f) {
+            quote! { #field_name: ::core::option::Option::Some(model.#field_name) }
+        } else {
+            quote! { #field_name: model.#field_name }
+        }
+    });
+
+    let apply_fields = kept.iter().map(|f| {
+        let field_name = &f.name;
+        if !dto.all_optional {
+            quote! { model.#field_name = self.#field_name; }
+        } else if wrapped(f) {
+            quote! {
+                if let ::core::option::Option::Some(value) = self.#field_name {
+                    model.#field_name = value;
+                }
+            }
+        } else {
+            quote! {
+                if self.#field_name.is_some() {
+                    model.#field_name = self.#field_name;
+                }
+            }
+        }
+    });
+
+    let into_model = if dto.all_optional {
+        quote! {}
+    } else {
+        let kept_fields = kept.iter().map(|f| {
+            let field_name = &f.name;
+            quote! { #field_name: dto.#field_name }
+        });
+        let dropped_fields = dropped.iter().map(|f| {
+            let field_name = &f.name;
+            quote! { #field_name: ::core::default::Default::default() }
+        });
+        quote! {
+            impl ::core::convert::From<#name> for #model_name {
+                fn from(dto: #name) -> Self {
+                    Self {
+                        #(#kept_fields,)*
+                        #(#dropped_fields,)*
+                    }
+                }
+            }
+        }
+    };
+
+    let mut derives: Vec<TokenStream> = vec![
+        quote! { ::core::fmt::Debug },
+        quote! { ::core::clone::Clone },
+    ];
+    let user_derives_default = dto
+        .derives
+        .iter()
+        .any(|p| p.segments.last().is_some_and(|s| s.ident == "Default"));
+    if dto.all_optional && !user_derives_default {
+        derives.push(quote! { ::core::default::Default });
+    }
+    derives.extend(dto.derives.iter().map(|p| quote! { #p }));
+
+    let doc = if dto.all_optional {
+        format!(
+            "Partial-update shape of [`{model_name}`], generated by `#[sqlmodel(generate)]`.\n\n\
+             Every field is optional; [`{name}::apply_to`] only overwrites the fields that are `Some`."
+        )
+    } else {
+        format!("Companion shape of [`{model_name}`], generated by `#[sqlmodel(generate)]`.")
+    };
+
+    quote! {
+        #[doc = #doc]
+        #[derive(#(#derives),*)]
+        #vis struct #name {
+            #(#field_decls,)*
+        }
+
+        impl #name {
+            /// Copy this value's fields onto `model`.
+            pub fn apply_to(self, model: &mut #model_name) {
+                #(#apply_fields)*
+            }
+        }
+
+        impl ::core::convert::From<#model_name> for #name {
+            fn from(model: #model_name) -> Self {
+                Self {
+                    #(#from_model_fields,)*
+                }
+            }
+        }
+
+        #into_model
+    }
+}
@@ -5,7 +5,7 @@
 
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{GenericArgument, PathArguments, Type};
+use syn::{GenericArgument, Generics, Ident, PathArguments, Type};
 
 /// Infer the SQL type from a Rust type, returning a TokenStream that
 /// constructs the appropriate SqlType variant.
@@ -93,6 +93,25 @@ pub fn infer_sql_type(ty: &Type) -> TokenStream {
     }
 }
 
+/// Return the model type parameter a field is declared as, if any.
+///
+/// Matches a bare `T` or `Option<T>` where `T` is one of `generics`' type
+/// parameters. Such fields can't be inferred from their spelling, so the derive
+/// reads their SQL type from `<T as TypeInfo>` instead.
+pub fn generic_param<'a>(ty: &Type, generics: &'a Generics) -> Option<&'a Ident> {
+    let Type::Path(type_path) = unwrap_option_type(ty) else {
+        return None;
+    };
+    if type_path.qself.is_some() {
+        return None;
+    }
+    let ident = type_path.path.get_ident()?;
+    generics
+        .type_params()
+        .map(|param| &param.ident)
+        .find(|param| *param == ident)
+}
+
 /// Parse an explicit sql_type attribute string into a SqlType TokenStream.
 ///
 /// Supports common SQL type names:
@@ -185,6 +204,7 @@ pub fn parse_sql_type_attr(sql_type: &str) -> TokenStream {
 
         // String types
         "TEXT" => quote! { sqlmodel_core::SqlType::Text },
+        "CITEXT" => quote! { sqlmodel_core::SqlType::CiText },
         "VARCHAR" => quote! { sqlmodel_core::SqlType::VarChar(255) }, // Default length
         "CHAR" => quote! { sqlmodel_core::SqlType::Char(1) },
 
@@ -273,6 +293,24 @@ mod tests {
         assert!(result.contains("Integer"));
     }
 
+    #[test]
+    fn test_generic_param() {
+        let generics: Generics = parse_quote!(<K: SqlScalar, V>);
+        let ty: Type = parse_quote!(K);
+        assert_eq!(generic_param(&ty, &generics).unwrap(), "K");
+        let ty: Type = parse_quote!(Option<V>);
+        assert_eq!(generic_param(&ty, &generics).unwrap(), "V");
+
+        for ty in [
+            parse_quote!(i64),
+            parse_quote!(Vec<K>),
+            parse_quote!(self::K),
+            parse_quote!(<K as Trait>::Id),
+        ] {
+            assert!(generic_param(&ty, &generics).is_none());
+        }
+    }
+
     #[test]
     fn test_parse_sql_type_varchar() {
         let result = parse_sql_type_attr("VARCHAR(100)").to_string();
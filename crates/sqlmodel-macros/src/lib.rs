@@ -8,20 +8,26 @@
 //! - **Model metadata**: `#[derive(Model)]` produces a `Model` implementation with
 //!   table/column metadata consumed by query, schema, and session layers.
 //! - **Validation**: `#[derive(Validate)]` generates field validation glue.
-//! - **Schema export**: `#[derive(JsonSchema)]` enables JSON schema generation for
-//!   API documentation or tooling.
+//! - **Repositories**: `#[derive(Repository)]` implements `Repository<M>` over a session field
+//!   and adds `find_by_<field>` finders.
+//! - **Schema export**: the generated field metadata (aliases, titles, descriptions)
+//!   feeds `sqlmodel_schema::openapi` for JSON Schema / OpenAPI generation.
+//! - **Migrations**: `embed_migrations!` compiles a migrations directory into the binary.
 //!
 //! These macros are used by application crates via the `sqlmodel` facade.
 
 use proc_macro::TokenStream;
 use syn::ext::IdentExt;
 
+mod dto;
 mod infer;
+mod migrations;
 mod parse;
+mod repository;
 mod validate;
 mod validate_derive;
 
-use parse::{InheritanceStrategy, ModelDef, RelationshipKindAttr, parse_model};
+use parse::{FieldDef, InheritanceStrategy, ModelDef, RelationshipKindAttr, parse_model};
 
 /// Derive macro for the `Model` trait.
 ///
@@ -34,6 +40,29 @@ use parse::{InheritanceStrategy, ModelDef, RelationshipKindAttr, parse_model};
 /// # Attributes
 ///
 /// - `#[sqlmodel(table = "name")]` - Override table name (defaults to snake_case struct name)
+/// - `#[sqlmodel(unique_together("a", "b"))]` - Struct-level composite UNIQUE constraint
+/// - `#[sqlmodel(index(columns("a", "b"), unique, name = "..", where = ".."))]` - Struct-level
+///   composite (optionally unique or partial) index
+/// - `#[sqlmodel(comment = "...")]` - Table comment, emitted with the CREATE TABLE DDL and
+///   compared by schema diffs; column comments use `column_comment = "..."`
+/// - `#[sqlmodel(history_table = "name")]` - Write row before-images to a history table on
+///   update/delete (queried with `Session::as_of`)
+/// - `#[sqlmodel(system_versioned)]` - Use database system versioning (MariaDB)
+/// - `#[sqlmodel(cache(ttl = "60s"))]` - Serve `Session::get` from the session's entity cache,
+///   if one is set; bare `cache` uses the backend's default expiry
+/// - `#[sqlmodel(order_by = "created_at DESC, id")]` - Default ordering for `select!(Model)`
+///   when the query sets no `order_by` of its own
+/// - `#[sqlmodel(scope(active = "deleted_at IS NULL"))]` - Named filter exposed as
+///   `Model::scope_active() -> Expr`
+/// - `#[sqlmodel(generate = "HeroCreate(exclude(id)), HeroUpdate(all_optional)")]` - Emit
+///   companion structs with `From` conversions and an `apply_to(&mut Model)` method;
+///   options are `exclude(field, ..)`, `all_optional` and `derive(Trait, ..)`
+/// - `#[sqlmodel(register)]` - Add the model to `ModelRegistry::global()`, which schema
+///   tooling such as `create_all_registered` reads instead of a hand-maintained model list
+/// - `#[sqlmodel(rename_all = "camelCase")]` - Default column naming rule (serde spellings:
+///   `lowercase`, `UPPERCASE`, `PascalCase`, `camelCase`, `snake_case`,
+///   `SCREAMING_SNAKE_CASE`, `kebab-case`, `SCREAMING-KEBAB-CASE`); a field's explicit
+///   `column = "..."` is used as written, and struct-level attributes may still name fields
 /// - `#[sqlmodel(primary_key)]` - Mark field as primary key
 /// - `#[sqlmodel(auto_increment)]` - Mark field as auto-incrementing
 /// - `#[sqlmodel(column = "name")]` - Override column name
@@ -42,7 +71,21 @@ use parse::{InheritanceStrategy, ModelDef, RelationshipKindAttr, parse_model};
 /// - `#[sqlmodel(default = "expr")]` - Set default SQL expression
 /// - `#[sqlmodel(foreign_key = "table.column")]` - Add foreign key reference
 /// - `#[sqlmodel(index = "name")]` - Add to named index
+/// - `#[sqlmodel(collation = "und-x-icu", charset = "utf8mb4")]` - Column collation
+///   (`COLLATE`) and, on MySQL, character set (`CHARACTER SET`) for text columns
+/// - `#[sqlmodel(case_insensitive)]` - Case-insensitive text column (`SqlType::CiText`):
+///   `CITEXT` on PostgreSQL, a case-insensitive collation on MySQL and SQLite
 /// - `#[sqlmodel(skip)]` - Skip this field in database operations
+/// - `#[sqlmodel(deferred)]` - Exclude a heavy column from default SELECTs (load on demand)
+/// - `#[sqlmodel(exclude)]` - Leave the field out of `model_dump` output
+/// - `#[sqlmodel(api_exclude)]` - Leave the field out of generated DTOs and JSON Schema, but
+///   keep it in `model_dump`
+/// - `#[sqlmodel(db_only)]` - Store the column but never serialize it or expose it in DTOs
+///   or JSON Schema (e.g. password hashes); API-only values use `computed` instead
+/// - `#[sqlmodel(serialize_with = "path", deserialize_with = "path")]` - Convert a column
+///   whose stored form differs from the field type; `fn(&T) -> Value` replaces
+///   `Into<Value>` in `to_row` and `fn(&Value) -> Result<T>` replaces `FromValue` in
+///   `from_row` (`T` is the full field type, including any `Option`)
 ///
 /// # Example
 ///
@@ -67,6 +110,22 @@ use parse::{InheritanceStrategy, ModelDef, RelationshipKindAttr, parse_model};
 ///     team_id: Option<i64>,
 /// }
 /// ```
+///
+/// # Generic models
+///
+/// A field typed as a type parameter `T` (or `Option<T>`) takes its SQL type and
+/// nullability from `<T as TypeInfo>`, and the generated impls gain a
+/// `T: SqlScalar` bound alongside the struct's own where clause:
+///
+/// ```ignore
+/// #[derive(Model)]
+/// #[sqlmodel(table = "audit_events")]
+/// struct AuditEvent<K: SqlScalar> {
+///     #[sqlmodel(primary_key)]
+///     id: K,
+///     action: String,
+/// }
+/// ```
 #[proc_macro_derive(Model, attributes(sqlmodel))]
 pub fn derive_model(input: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
@@ -90,7 +149,8 @@ pub fn derive_model(input: TokenStream) -> TokenStream {
 fn generate_model_impl(model: &ModelDef) -> proc_macro2::TokenStream {
     let name = &model.name;
     let table_name_lit = &model.table_name;
-    let (impl_generics, ty_generics, where_clause) = model.generics.split_for_impl();
+    let model_generics = scalar_bounded_generics(model);
+    let (impl_generics, ty_generics, where_clause) = model_generics.split_for_impl();
 
     // If this is a single-table-inheritance child (inherits + discriminator_value),
     // its effective table is the parent table.
@@ -137,6 +197,23 @@ fn generate_model_impl(model: &ModelDef) -> proc_macro2::TokenStream {
     // Generate static FieldInfo array for fields()
     let field_infos = generate_field_infos(model);
 
+    // A `static` can't name the model's type parameters; an inline const is
+    // evaluated per instantiation instead.
+    let fields_body = if model.generics.type_params().next().is_some()
+        || model.generics.const_params().next().is_some()
+    {
+        quote::quote! {
+            const { &[#field_infos] }
+        }
+    } else {
+        quote::quote! {
+            static FIELDS: &[sqlmodel_core::FieldInfo] = &[
+                #field_infos
+            ];
+            FIELDS
+        }
+    };
+
     // Generate RELATIONSHIPS constant
     let relationships = generate_relationships(model);
 
@@ -161,6 +238,42 @@ fn generate_model_impl(model: &ModelDef) -> proc_macro2::TokenStream {
     // Generate shard_key implementation
     let (shard_key_const, shard_key_value_body) = generate_shard_key(model);
 
+    // Generate UNIQUE_CONSTRAINTS and INDEXES constants
+    let (unique_constraints, indexes) = generate_table_constraints(model);
+
+    let table_comment = if let Some(comment) = &model.config.comment {
+        quote::quote! { Some(#comment) }
+    } else {
+        quote::quote! { None }
+    };
+
+    // Generate temporal history constants
+    let history_table = if let Some(table) = &model.config.history_table {
+        quote::quote! { Some(#table) }
+    } else {
+        quote::quote! { None }
+    };
+    let system_versioned = model.config.system_versioned;
+
+    // Generate entity cache policy
+    let cache_policy = if let Some(cache) = model.config.cache {
+        let ttl = if let Some(ms) = cache.ttl_ms {
+            quote::quote! { Some(::core::time::Duration::from_millis(#ms)) }
+        } else {
+            quote::quote! { None }
+        };
+        quote::quote! { Some(sqlmodel_core::CachePolicy { ttl: #ttl }) }
+    } else {
+        quote::quote! { None }
+    };
+
+    // Generate default ordering
+    let default_order_by = model.config.order_by.iter().map(|(column, descending)| {
+        quote::quote! {
+            sqlmodel_core::OrderByColumn { column: #column, descending: #descending }
+        }
+    });
+
     // Generate joined-parent extraction for joined-table inheritance child models.
     let joined_parent_row_body = generate_joined_parent_row(model);
 
@@ -170,18 +283,50 @@ fn generate_model_impl(model: &ModelDef) -> proc_macro2::TokenStream {
     // Generate hybrid property expr methods
     let hybrid_impl = generate_hybrid_methods(model);
 
+    // Generate named scope constructors
+    let scope_impl = generate_scope_methods(model);
+
+    // Generate companion DTO structs
+    let dto_impls = dto::generate_dtos(model);
+
+    // Run `#[derive(Validate)]` rules when the session validates on flush
+    let validate_instance = if model.has_validation {
+        quote::quote! {
+            fn validate_instance(&self) -> std::result::Result<(), sqlmodel_core::ValidationError> {
+                <Self as sqlmodel_core::Validate>::validate(self)
+            }
+        }
+    } else {
+        quote::quote! {}
+    };
+
+    // Submit to the global model registry
+    let register = if model.config.register {
+        quote::quote! {
+            sqlmodel_core::inventory::submit! {
+                sqlmodel_core::RegisteredModel::new(sqlmodel_core::ModelDescriptor::of::<#name>)
+            }
+        }
+    } else {
+        quote::quote! {}
+    };
+
     quote::quote! {
         impl #impl_generics sqlmodel_core::Model for #name #ty_generics #where_clause {
             const TABLE_NAME: &'static str = #table_name_ts;
             const PRIMARY_KEY: &'static [&'static str] = #pk_slice;
             const RELATIONSHIPS: &'static [sqlmodel_core::RelationshipInfo] = #relationships;
             const SHARD_KEY: Option<&'static str> = #shard_key_const;
+            const UNIQUE_CONSTRAINTS: &'static [sqlmodel_core::UniqueConstraintDef] = #unique_constraints;
+            const INDEXES: &'static [sqlmodel_core::IndexDef] = #indexes;
+            const TABLE_COMMENT: Option<&'static str> = #table_comment;
+            const HISTORY_TABLE: Option<&'static str> = #history_table;
+            const SYSTEM_VERSIONED: bool = #system_versioned;
+            const CACHE: Option<sqlmodel_core::CachePolicy> = #cache_policy;
+            const DEFAULT_ORDER_BY: &'static [sqlmodel_core::OrderByColumn] = &[#(#default_order_by),*];
 
             fn fields() -> &'static [sqlmodel_core::FieldInfo] {
-                static FIELDS: &[sqlmodel_core::FieldInfo] = &[
-                    #field_infos
-                ];
-                FIELDS
+                #fields_body
             }
 
             fn to_row(&self) -> Vec<(&'static str, sqlmodel_core::Value)> {
@@ -212,20 +357,82 @@ fn generate_model_impl(model: &ModelDef) -> proc_macro2::TokenStream {
                 #shard_key_value_body
             }
 
+            #validate_instance
+
             #joined_parent_row_body
         }
 
         #debug_impl
 
         #hybrid_impl
+
+        #scope_impl
+
+        #dto_impls
+
+        #register
     }
 }
 
+/// The model's generics with `T: SqlScalar` added for every type parameter
+/// used directly as a column type (`T` or `Option<T>`).
+fn scalar_bounded_generics(model: &ModelDef) -> syn::Generics {
+    let mut generics = model.generics.clone();
+    let mut bounded: Vec<&syn::Ident> = Vec::new();
+    for field in model.data_fields() {
+        if let Some(param) = infer::generic_param(&field.ty, &model.generics) {
+            if !bounded.contains(&param) {
+                bounded.push(param);
+            }
+        }
+    }
+    if !bounded.is_empty() {
+        let where_clause = generics.make_where_clause();
+        for param in bounded {
+            where_clause
+                .predicates
+                .push(syn::parse_quote! { #param: sqlmodel_core::SqlScalar });
+        }
+    }
+    generics
+}
+
 /// Generate associated functions for hybrid properties.
 ///
 /// For each field with `#[sqlmodel(hybrid, sql = "...")]`, generates
 /// a `pub fn {field}_expr() -> sqlmodel_query::Expr` method that returns
 /// `Expr::raw(sql)`.
+/// Generate `scope_{name}()` constructors for `#[sqlmodel(scope(name = "..."))]`.
+///
+/// Each returns the scope's SQL as a parenthesized raw expression, so it
+/// combines safely with `.and()` / `.or()`.
+fn generate_scope_methods(model: &ModelDef) -> proc_macro2::TokenStream {
+    if model.config.scopes.is_empty() {
+        return quote::quote! {};
+    }
+
+    let name = &model.name;
+    let (impl_generics, ty_generics, where_clause) = model.generics.split_for_impl();
+
+    let methods = model.config.scopes.iter().map(|(scope, sql)| {
+        let method_name = quote::format_ident!("scope_{}", scope);
+        let doc = format!("The `{scope}` scope: `{sql}`.");
+        let sql = format!("({sql})");
+        quote::quote! {
+            #[doc = #doc]
+            pub fn #method_name() -> sqlmodel_query::Expr {
+                sqlmodel_query::Expr::raw(#sql)
+            }
+        }
+    });
+
+    quote::quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#methods)*
+        }
+    }
+}
+
 fn generate_hybrid_methods(model: &ModelDef) -> proc_macro2::TokenStream {
     let hybrid_fields: Vec<_> = model
         .fields
@@ -284,7 +491,7 @@ fn generate_joined_parent_row(model: &ModelDef) -> proc_macro2::TokenStream {
 
     quote::quote! {
         fn joined_parent_row(&self) -> Option<Vec<(&'static str, sqlmodel_core::Value)>> {
-            Some(self.#parent_ident.to_row())
+            Some(<_ as sqlmodel_core::Model>::to_row(&self.#parent_ident))
         }
     }
 }
@@ -321,19 +528,32 @@ fn generate_field_infos(model: &ModelDef) -> proc_macro2::TokenStream {
         // Check if sa_column override is present
         let sa_col = field.sa_column.as_ref();
 
+        // Type-parameter fields take their SQL type and nullability from `TypeInfo`
+        let generic_param = infer::generic_param(&field.ty, &model.generics);
+
         // Nullable: sa_column.nullable takes precedence over field.nullable
         let nullable = sa_col.and_then(|sc| sc.nullable).unwrap_or(field.nullable);
+        let nullable_ts = match generic_param {
+            Some(param) if !nullable && sa_col.and_then(|sc| sc.nullable).is_none() => {
+                quote::quote! { <#param as sqlmodel_core::TypeInfo>::NULLABLE }
+            }
+            _ => quote::quote! { #nullable },
+        };
 
         // Unique: sa_column.unique takes precedence over field.unique
         let unique = sa_col.and_then(|sc| sc.unique).unwrap_or(field.unique);
 
-        // Determine SQL type: sa_column.sql_type > field.sql_type > inferred
+        // Determine SQL type: sa_column.sql_type > field.sql_type > case_insensitive > inferred
         let effective_sql_type = sa_col
             .and_then(|sc| sc.sql_type.as_ref())
             .or(field.sql_type.as_ref());
         let sql_type_ts = if let Some(sql_type_str) = effective_sql_type {
             // Parse the explicit SQL type attribute string
             infer::parse_sql_type_attr(sql_type_str)
+        } else if field.case_insensitive {
+            quote::quote! { sqlmodel_core::SqlType::CiText }
+        } else if let Some(param) = generic_param {
+            quote::quote! { <#param as sqlmodel_core::TypeInfo>::SQL_TYPE }
         } else {
             // Infer from Rust type (handles primitives, Option<T>, common library types)
             infer::infer_sql_type(&field.ty)
@@ -409,7 +629,10 @@ fn generate_field_infos(model: &ModelDef) -> proc_macro2::TokenStream {
         };
 
         let computed = field.computed;
+        let deferred = field.deferred;
         let exclude = field.exclude;
+        let api_exclude = field.api_exclude;
+        let db_only = field.db_only;
 
         // Schema metadata tokens
         let title_ts = if let Some(ref title) = field.title {
@@ -464,6 +687,18 @@ fn generate_field_infos(model: &ModelDef) -> proc_macro2::TokenStream {
             quote::quote! { None }
         };
 
+        // Collation and character set
+        let collation_ts = if let Some(collation) = &field.collation {
+            quote::quote! { Some(#collation) }
+        } else {
+            quote::quote! { None }
+        };
+        let charset_ts = if let Some(charset) = &field.charset {
+            quote::quote! { Some(#charset) }
+        } else {
+            quote::quote! { None }
+        };
+
         // Column info
         let column_info_ts = if let Some(ref info) = field.column_info {
             quote::quote! { Some(#info) }
@@ -503,7 +738,7 @@ fn generate_field_infos(model: &ModelDef) -> proc_macro2::TokenStream {
                 .sql_type_override_opt(#sql_type_override_ts)
                 .precision_opt(#precision_ts)
                 .scale_opt(#scale_ts)
-                .nullable(#nullable)
+                .nullable(#nullable_ts)
                 .primary_key(#primary_key)
                 .auto_increment(#auto_increment)
                 .unique(#unique)
@@ -516,7 +751,10 @@ fn generate_field_infos(model: &ModelDef) -> proc_macro2::TokenStream {
                 .validation_alias_opt(#validation_alias_ts)
                 .serialization_alias_opt(#serialization_alias_ts)
                 .computed(#computed)
+                .deferred(#deferred)
                 .exclude(#exclude)
+                .api_exclude(#api_exclude)
+                .db_only(#db_only)
                 .title_opt(#title_ts)
                 .description_opt(#description_ts)
                 .schema_extra_opt(#schema_extra_ts)
@@ -524,6 +762,8 @@ fn generate_field_infos(model: &ModelDef) -> proc_macro2::TokenStream {
                 .const_field(#const_field)
                 .column_constraints(#column_constraints_ts)
                 .column_comment_opt(#column_comment_ts)
+                .collation_opt(#collation_ts)
+                .charset_opt(#charset_ts)
                 .column_info_opt(#column_info_ts)
                 .hybrid_sql_opt(#hybrid_sql_ts)
                 .discriminator_opt(#discriminator_ts)
@@ -533,27 +773,36 @@ fn generate_field_infos(model: &ModelDef) -> proc_macro2::TokenStream {
     quote::quote! { #(#field_ts),* }
 }
 
+/// Expression converting `self.<field>` into its stored `Value`.
+///
+/// Uses the field's `serialize_with` hook when set, otherwise `Into<Value>`
+/// (mapping `None` to NULL for `Option<T>` fields).
+fn column_value(field: &FieldDef) -> proc_macro2::TokenStream {
+    let field_name = &field.name;
+    if let Some(ref hook) = field.serialize_with {
+        quote::quote! { #hook(&self.#field_name) }
+    } else if parse::is_option_type(&field.ty) {
+        quote::quote! {
+            match &self.#field_name {
+                Some(v) => ::core::convert::Into::<sqlmodel_core::Value>::into(v.clone()),
+                None => sqlmodel_core::Value::Null,
+            }
+        }
+    } else {
+        quote::quote! {
+            ::core::convert::Into::<sqlmodel_core::Value>::into(self.#field_name.clone())
+        }
+    }
+}
+
 /// Generate the to_row method body.
 fn generate_to_row(model: &ModelDef) -> proc_macro2::TokenStream {
     let mut conversions = Vec::new();
 
     for field in model.select_fields() {
-        let field_name = &field.name;
         let column_name = &field.column_name;
-
-        // Convert field to Value
-        if parse::is_option_type(&field.ty) {
-            conversions.push(quote::quote! {
-                (#column_name, match &self.#field_name {
-                    Some(v) => sqlmodel_core::Value::from(v.clone()),
-                    None => sqlmodel_core::Value::Null,
-                })
-            });
-        } else {
-            conversions.push(quote::quote! {
-                (#column_name, sqlmodel_core::Value::from(self.#field_name.clone()))
-            });
-        }
+        let value = column_value(field);
+        conversions.push(quote::quote! { (#column_name, #value) });
     }
 
     quote::quote! {
@@ -586,7 +835,22 @@ fn generate_from_row(model: &ModelDef) -> proc_macro2::TokenStream {
         let field_name = &field.name;
         let column_name = &field.column_name;
 
-        if parse::is_option_type(&field.ty) {
+        if let Some(ref hook) = field.deserialize_with {
+            // The hook decodes NULL itself; a missing column still means `None` for Option<T>
+            if parse::is_option_type(&field.ty) {
+                field_extractions.push(quote::quote! {
+                    #field_name: if #row_ident.contains_column(#column_name) {
+                        #row_ident.get_named_with(#column_name, #hook)?
+                    } else {
+                        None
+                    }
+                });
+            } else {
+                field_extractions.push(quote::quote! {
+                    #field_name: #row_ident.get_named_with(#column_name, #hook)?
+                });
+            }
+        } else if parse::is_option_type(&field.ty) {
             // For Option<T> fields, handle NULL gracefully
             field_extractions.push(quote::quote! {
                 #field_name: #row_ident.get_named(#column_name).ok()
@@ -685,38 +949,13 @@ fn generate_primary_key_value(model: &ModelDef) -> proc_macro2::TokenStream {
         // Try to use "id" field if it exists
         let id_field = model.fields.iter().find(|f| f.name == "id");
         if let Some(field) = id_field {
-            let field_name = &field.name;
-            if parse::is_option_type(&field.ty) {
-                return quote::quote! {
-                    match &self.#field_name {
-                        Some(v) => vec![sqlmodel_core::Value::from(v.clone())],
-                        None => vec![sqlmodel_core::Value::Null],
-                    }
-                };
-            }
-            return quote::quote! {
-                vec![sqlmodel_core::Value::from(self.#field_name.clone())]
-            };
+            let value = column_value(field);
+            return quote::quote! { vec![#value] };
         }
         return quote::quote! { vec![] };
     }
 
-    let mut value_exprs = Vec::new();
-    for field in pk_fields {
-        let field_name = &field.name;
-        if parse::is_option_type(&field.ty) {
-            value_exprs.push(quote::quote! {
-                match &self.#field_name {
-                    Some(v) => sqlmodel_core::Value::from(v.clone()),
-                    None => sqlmodel_core::Value::Null,
-                }
-            });
-        } else {
-            value_exprs.push(quote::quote! {
-                sqlmodel_core::Value::from(self.#field_name.clone())
-            });
-        }
-    }
+    let value_exprs: Vec<_> = pk_fields.into_iter().map(column_value).collect();
 
     quote::quote! {
         vec![#(#value_exprs),*]
@@ -902,18 +1141,30 @@ fn generate_shard_key(model: &ModelDef) -> (proc_macro2::TokenStream, proc_macro
         // Generate the method body based on whether the field exists and its type
         let value_body = if let Some(field) = shard_field {
             let field_ident = &field.name;
-            if parse::is_option_type(&field.ty) {
+            if let Some(ref hook) = field.serialize_with {
+                // Custom conversion: a NULL result on an Option<T> field means no key
+                if parse::is_option_type(&field.ty) {
+                    quote::quote! {
+                        match #hook(&self.#field_ident) {
+                            sqlmodel_core::Value::Null => None,
+                            v => Some(v),
+                        }
+                    }
+                } else {
+                    quote::quote! { Some(#hook(&self.#field_ident)) }
+                }
+            } else if parse::is_option_type(&field.ty) {
                 // Option<T> field: return Some(value) if Some, None if None
                 quote::quote! {
                     match &self.#field_ident {
-                        Some(v) => Some(sqlmodel_core::Value::from(v.clone())),
+                        Some(v) => Some(::core::convert::Into::<sqlmodel_core::Value>::into(v.clone())),
                         None => None,
                     }
                 }
             } else {
                 // Non-optional field: always has a value
                 quote::quote! {
-                    Some(sqlmodel_core::Value::from(self.#field_ident.clone()))
+                    Some(::core::convert::Into::<sqlmodel_core::Value>::into(self.#field_ident.clone()))
                 }
             }
         } else {
@@ -931,6 +1182,46 @@ fn generate_shard_key(model: &ModelDef) -> (proc_macro2::TokenStream, proc_macro
     }
 }
 
+/// Generate the UNIQUE_CONSTRAINTS and INDEXES constants.
+///
+/// Column names in `unique_together(...)` / `index(columns(...))` may refer to
+/// either the Rust field name or the database column name; both resolve to the
+/// column name here.
+fn generate_table_constraints(
+    model: &ModelDef,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let resolve = |col: &String| -> String {
+        model
+            .fields
+            .iter()
+            .find(|f| f.name == col.as_str())
+            .map_or_else(|| col.clone(), |f| f.column_name.clone())
+    };
+
+    let uniques = model.config.unique_together.iter().map(|cols| {
+        let cols: Vec<String> = cols.iter().map(resolve).collect();
+        quote::quote! { sqlmodel_core::UniqueConstraintDef::new(&[#(#cols),*]) }
+    });
+
+    let indexes = model.config.indexes.iter().map(|index| {
+        let cols: Vec<String> = index.columns.iter().map(resolve).collect();
+        let unique = index.unique;
+        let name = index.name.as_ref().map(|n| quote::quote! { .name(#n) });
+        let where_clause = index
+            .where_clause
+            .as_ref()
+            .map(|w| quote::quote! { .where_clause(#w) });
+        quote::quote! {
+            sqlmodel_core::IndexDef::new(&[#(#cols),*]).unique(#unique) #name #where_clause
+        }
+    });
+
+    (
+        quote::quote! { &[#(#uniques),*] },
+        quote::quote! { &[#(#indexes),*] },
+    )
+}
+
 /// Generate a custom Debug implementation if any field has repr=false.
 ///
 /// This generates a Debug impl that excludes fields marked with `repr = false`,
@@ -1178,6 +1469,16 @@ fn generate_relationships(model: &ModelDef) -> proc_macro2::TokenStream {
 /// - `#[validate(url)]` - URL format validation
 /// - `#[validate(required)]` - Mark an Option<T> field as required
 /// - `#[validate(custom = "fn_name")]` - Custom validation function
+/// - `#[validate(required_if = "other_field")]` - Required whenever `other_field` is set
+///   (`Some`, `true`, or non-empty)
+/// - `#[validate(custom_async = "fn_name")]` - Async validator
+///   `fn_name(&self, cx, conn, &value) -> Outcome<Result<(), String>, Error>`, run by the
+///   generated `validate_async(cx, conn)`
+/// - `#[validate(schema = "fn_name")]` - On the struct: `fn_name(&self) -> Result<(), ValidationError>`
+///   for cross-field checks that report errors against specific fields
+///
+/// `validate_async` returns every failure (sync and async) as a `ValidationErrors`
+/// grouped by field path.
 ///
 /// # Example
 ///
@@ -1224,6 +1525,45 @@ pub fn derive_validate(input: TokenStream) -> TokenStream {
     validate_derive::generate_validate_impl(&def).into()
 }
 
+/// Derive macro for the `Repository` trait.
+///
+/// Implements `sqlmodel_session::Repository<M>` for a struct holding a
+/// `Session<C>` (owned or `&mut`), so it gets `find`, `find_all`, `save`,
+/// `delete` and `paginate`, and generates finders for the listed model fields.
+///
+/// # Attributes
+///
+/// - `#[repository(model = User)]` - The model type (required)
+/// - `#[repository(find_by(email, name))]` - Generate `find_by_email(cx, value)` returning
+///   every matching row and `find_one_by_email(cx, value)` returning the first one
+/// - `#[repository(session)]` - On a field: the session field, when it is not named `session`
+///
+/// # Example
+///
+/// ```ignore
+/// use sqlmodel::prelude::*;
+///
+/// #[derive(Repository)]
+/// #[repository(model = User, find_by(email))]
+/// struct UserRepo<'s, C: Connection> {
+///     session: &'s mut Session<C>,
+/// }
+///
+/// let mut users = UserRepo { session: &mut session };
+/// let alice = users.find_one_by_email(&cx, "alice@example.com").await?;
+/// ```
+#[proc_macro_derive(Repository, attributes(repository))]
+pub fn derive_repository(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    let def = match repository::parse_repository(&input) {
+        Ok(d) => d,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    repository::generate_repository_impl(&def).into()
+}
+
 /// Derive macro for SQL enum types.
 ///
 /// Generates `SqlEnum` trait implementation, `From<EnumType> for Value`,
@@ -1501,3 +1841,131 @@ fn is_cx_path(ty: &syn::Type) -> bool {
     };
     p.path.segments.last().is_some_and(|seg| seg.ident == "Cx")
 }
+
+/// Attribute macro for database tests with transactional rollback.
+///
+/// The annotated `async fn` receives a `Cx` and a `Session` that has already
+/// begun a transaction; the transaction is rolled back when the body returns.
+/// Without arguments each test gets a fresh in-memory SQLite database; pass
+/// `db = <expr>` to use a shared `sqlmodel_testing::TestDatabase` (for example
+/// a `static` `LazyLock`). Requires `sqlmodel-testing` as a dev-dependency.
+///
+/// The body may return `()`, a `Result<(), E>`, or an `Outcome<(), Error>`.
+///
+/// # Example
+///
+/// ```ignore
+/// #[sqlmodel::testing::test(db = DB)]
+/// async fn inserts_hero(cx: &Cx, session: &mut Session<SqliteConnection>) {
+///     session.add(&Hero::new("Deadpond"));
+///     session.flush(cx).await.unwrap();
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn test(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut db: Option<syn::Expr> = None;
+    let args_parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("db") {
+            db = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta
+                .error("unsupported #[sqlmodel::testing::test] argument; expected `db = <expr>`"))
+        }
+    });
+    if let Err(e) = syn::parse::Parser::parse(args_parser, attr) {
+        return e.to_compile_error().into();
+    }
+
+    let func: syn::ItemFn = match syn::parse(item) {
+        Ok(f) => f,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    if func.sig.asyncness.is_none() {
+        return syn::Error::new_spanned(
+            func.sig.fn_token,
+            "#[sqlmodel::testing::test] requires an async fn",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    if func.sig.inputs.len() != 2 {
+        return syn::Error::new_spanned(
+            &func.sig.inputs,
+            "#[sqlmodel::testing::test] requires parameters `(cx: &Cx, session: &mut Session<C>)`",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let attrs = &func.attrs;
+    let vis = &func.vis;
+    let name = &func.sig.ident;
+
+    let mut body_fn = func.clone();
+    body_fn.attrs.clear();
+    body_fn.vis = syn::Visibility::Inherited;
+    body_fn.sig.ident = quote::format_ident!("__sqlmodel_test_body");
+
+    let db_expr = if let Some(expr) = db {
+        quote::quote!(&#expr)
+    } else {
+        quote::quote!(&::sqlmodel_testing::TestDatabase::sqlite())
+    };
+
+    quote::quote! {
+        #[test]
+        #(#attrs)*
+        #vis fn #name() {
+            #body_fn
+
+            let __sqlmodel_db = #db_expr;
+            ::sqlmodel_testing::block_on(async {
+                let __sqlmodel_cx = ::sqlmodel_testing::Cx::for_testing();
+                let mut __sqlmodel_session = ::sqlmodel_testing::expect_outcome(
+                    __sqlmodel_db.session(&__sqlmodel_cx).await,
+                    "open test session",
+                );
+                let __sqlmodel_result =
+                    __sqlmodel_test_body(&__sqlmodel_cx, &mut __sqlmodel_session).await;
+                ::sqlmodel_testing::finish_test(
+                    __sqlmodel_session,
+                    &__sqlmodel_cx,
+                    __sqlmodel_result,
+                )
+                .await;
+            });
+        }
+    }
+    .into()
+}
+
+/// Embed a directory of migration files into the binary.
+///
+/// Expands to a `Vec<sqlmodel_schema::Migration>` ordered by version, built
+/// from the `<version>_<description>.sql` and `.rs` files in the directory
+/// (relative to the crate's `Cargo.toml`), the layout `MigrationWriter`
+/// writes. `.sql` files are embedded with `include_str!` and read with
+/// `Migration::from_sql`; `.rs` files are compiled as modules and their
+/// `migration()` function is called. Requires `sqlmodel-schema` as a dependency.
+///
+/// Cargo rebuilds when an embedded file changes, but not when a file is added;
+/// add `println!("cargo:rerun-if-changed=migrations");` to a build script to
+/// pick up new migrations.
+///
+/// # Example
+///
+/// ```ignore
+/// let runner = MigrationRunner::new(sqlmodel::embed_migrations!("migrations"));
+/// runner.migrate(&cx, &conn).await?;
+/// ```
+#[proc_macro]
+pub fn embed_migrations(input: TokenStream) -> TokenStream {
+    let dir = syn::parse_macro_input!(input as syn::LitStr);
+    match migrations::expand(&dir) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,106 @@
+//! `embed_migrations!` expansion.
+
+use std::path::{Path, PathBuf};
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+/// A migration file found in the embedded directory.
+struct MigrationFile {
+    version: String,
+    description: String,
+    path: PathBuf,
+    rust: bool,
+}
+
+/// Split `<version>_<description>` into its parts; the version is all digits.
+fn parse_stem(stem: &str) -> Option<(String, String)> {
+    let digits = stem.bytes().take_while(u8::is_ascii_digit).count();
+    if digits == 0 {
+        return None;
+    }
+    let (version, rest) = stem.split_at(digits);
+    Some((
+        version.to_string(),
+        rest.trim_start_matches('_').replace('_', " "),
+    ))
+}
+
+fn scan(dir: &Path, span: proc_macro2::Span) -> syn::Result<Vec<MigrationFile>> {
+    let entries = std::fs::read_dir(dir).map_err(|e| {
+        syn::Error::new(
+            span,
+            format!("cannot read migrations directory {}: {e}", dir.display()),
+        )
+    })?;
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|e| syn::Error::new(span, format!("cannot read {}: {e}", dir.display())))?
+            .path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let Some((stem, ext)) = name.rsplit_once('.') else {
+            continue;
+        };
+        if !matches!(ext, "sql" | "rs") || name == "mod.rs" {
+            continue;
+        }
+        let Some((version, description)) = parse_stem(stem) else {
+            return Err(syn::Error::new(
+                span,
+                format!("migration file {name} does not start with a version number"),
+            ));
+        };
+        files.push(MigrationFile {
+            version,
+            description,
+            rust: ext == "rs",
+            path,
+        });
+    }
+
+    files.sort_by(|a, b| a.version.cmp(&b.version));
+    if let Some(pair) = files.windows(2).find(|w| w[0].version == w[1].version) {
+        return Err(syn::Error::new(
+            span,
+            format!("duplicate migration version {}", pair[0].version),
+        ));
+    }
+    Ok(files)
+}
+
+/// Expand `embed_migrations!("dir")` into a `Vec<sqlmodel_schema::Migration>`.
+pub fn expand(dir: &syn::LitStr) -> syn::Result<TokenStream> {
+    let root =
+        std::env::var("CARGO_MANIFEST_DIR").map_or_else(|_| PathBuf::from("."), PathBuf::from);
+    let files = scan(&root.join(dir.value()), dir.span())?;
+
+    let migrations = files.iter().enumerate().map(|(i, file)| {
+        let path = file.path.to_string_lossy().into_owned();
+        if file.rust {
+            let module = format_ident!("__sqlmodel_migration_{}", i);
+            quote! {
+                {
+                    // The migration writer always emits `r#"..."#` literals.
+                    #[allow(clippy::needless_raw_string_hashes)]
+                    #[path = #path]
+                    mod #module;
+                    #module::migration()
+                }
+            }
+        } else {
+            let version = &file.version;
+            let description = &file.description;
+            quote! {
+                sqlmodel_schema::Migration::from_sql(#version, #description, include_str!(#path))
+            }
+        }
+    });
+
+    Ok(quote! {
+        ::std::vec![#(#migrations),*]
+    })
+}
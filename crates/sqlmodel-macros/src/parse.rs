@@ -59,6 +59,199 @@ pub struct ModelConfigParsed {
     pub discriminator_value: Option<String>,
     /// Shard key field name for horizontal sharding.
     pub shard_key: Option<String>,
+    /// Composite unique constraints from `unique_together(...)`.
+    pub unique_together: Vec<Vec<String>>,
+    /// Composite indexes from `index(columns(...), ...)`.
+    pub indexes: Vec<IndexAttr>,
+    /// Table comment from `comment = "..."`.
+    pub comment: Option<String>,
+    /// History table receiving row before-images (`history_table = "..."`).
+    pub history_table: Option<String>,
+    /// Database-managed system versioning (`system_versioned`).
+    pub system_versioned: bool,
+    /// Companion DTO structs from `generate = "..."`.
+    pub generate: Vec<DtoAttr>,
+    /// Entity cache opt-in from `cache` or `cache(ttl = "...")`.
+    pub cache: Option<CacheAttr>,
+    /// Default ordering from `order_by = "..."`, as (column, descending).
+    pub order_by: Vec<(String, bool)>,
+    /// Named filter expressions from `scope(name = "...")`.
+    pub scopes: Vec<(Ident, String)>,
+    /// Submit the model to the global registry (`register`).
+    pub register: bool,
+    /// Naming rule for columns without an explicit `column = "..."`.
+    pub rename_all: Option<RenameRule>,
+}
+
+/// Column naming rule from `#[sqlmodel(rename_all = "...")]`, with serde's
+/// spellings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameRule {
+    /// `lowercase`
+    Lower,
+    /// `UPPERCASE`
+    Upper,
+    /// `PascalCase`
+    Pascal,
+    /// `camelCase`
+    Camel,
+    /// `snake_case`
+    Snake,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnake,
+    /// `kebab-case`
+    Kebab,
+    /// `SCREAMING-KEBAB-CASE`
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    /// Parse a rule name; `SCREAMING_SNAKE` is accepted as a short form.
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "lowercase" => Self::Lower,
+            "UPPERCASE" => Self::Upper,
+            "PascalCase" => Self::Pascal,
+            "camelCase" => Self::Camel,
+            "snake_case" => Self::Snake,
+            "SCREAMING_SNAKE_CASE" | "SCREAMING_SNAKE" => Self::ScreamingSnake,
+            "kebab-case" => Self::Kebab,
+            "SCREAMING-KEBAB-CASE" => Self::ScreamingKebab,
+            _ => return None,
+        })
+    }
+
+    /// Column name for a snake_case Rust field name.
+    pub fn apply(self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_string(),
+            Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+            Self::Pascal | Self::Camel => {
+                let mut out = String::with_capacity(field.len());
+                let mut capitalize = self == Self::Pascal;
+                for c in field.chars() {
+                    if c == '_' {
+                        capitalize = !out.is_empty();
+                    } else if capitalize {
+                        out.push(c.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        out.push(c);
+                    }
+                }
+                out
+            }
+        }
+    }
+}
+
+/// Entity cache settings from `#[sqlmodel(cache(ttl = "60s"))]`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheAttr {
+    /// Time to live in milliseconds; `None` uses the backend default.
+    pub ttl_ms: Option<u64>,
+}
+
+/// A companion struct requested through `#[sqlmodel(generate = "...")]`.
+///
+/// ```ignore
+/// #[sqlmodel(generate = "HeroCreate(exclude(id)), HeroUpdate(all_optional)")]
+/// ```
+#[derive(Debug, Clone)]
+pub struct DtoAttr {
+    /// Name of the generated struct.
+    pub name: Ident,
+    /// Rust field names left out of the struct.
+    pub exclude: Vec<Ident>,
+    /// Wrap every field in `Option` (fields already `Option<T>` stay as they are).
+    pub all_optional: bool,
+    /// Extra derives on top of `Debug` and `Clone`.
+    pub derives: Vec<syn::Path>,
+}
+
+impl syn::parse::Parse for DtoAttr {
+    fn parse(input: syn::parse::ParseStream<'_>) -> Result<Self> {
+        let mut dto = DtoAttr {
+            name: input.parse()?,
+            exclude: Vec::new(),
+            all_optional: false,
+            derives: Vec::new(),
+        };
+        if !input.peek(syn::token::Paren) {
+            return Ok(dto);
+        }
+
+        let content;
+        syn::parenthesized!(content in input);
+        while !content.is_empty() {
+            let option: Ident = content.parse()?;
+            if option == "exclude" {
+                let names;
+                syn::parenthesized!(names in content);
+                dto.exclude.extend(
+                    names.parse_terminated(<Ident as syn::parse::Parse>::parse, syn::Token![,])?,
+                );
+            } else if option == "derive" {
+                let paths;
+                syn::parenthesized!(paths in content);
+                dto.derives
+                    .extend(paths.parse_terminated(syn::Path::parse_mod_style, syn::Token![,])?);
+            } else if option == "all_optional" {
+                dto.all_optional = true;
+            } else {
+                return Err(Error::new(
+                    option.span(),
+                    format!(
+                        "unknown generate option `{option}` (supported: exclude(...), all_optional, derive(...))"
+                    ),
+                ));
+            }
+            if !content.is_empty() {
+                content.parse::<syn::Token![,]>()?;
+            }
+        }
+        Ok(dto)
+    }
+}
+
+/// Parsed struct-level index from `#[sqlmodel(index(...))]`.
+///
+/// ```ignore
+/// #[sqlmodel(index(columns("org_id", "email"), unique, where = "deleted_at IS NULL"))]
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IndexAttr {
+    /// Explicit index name (derived from table and columns when absent).
+    pub name: Option<String>,
+    /// Indexed columns, in order.
+    pub columns: Vec<String>,
+    /// Whether this is a UNIQUE index.
+    pub unique: bool,
+    /// Optional partial index predicate.
+    pub where_clause: Option<String>,
+}
+
+/// Where each `#[sqlmodel(...)]` key was written, so checks that run after
+/// parsing can point at the offending attribute instead of the whole item.
+#[derive(Debug, Clone, Default)]
+pub struct AttrSpans(Vec<(String, Span)>);
+
+impl AttrSpans {
+    fn record(&mut self, path: &syn::Path) {
+        if let Some(ident) = path.get_ident() {
+            self.0.push((ident.to_string(), ident.span()));
+        }
+    }
+
+    /// Span of the first `key` attribute, or `fallback` if it wasn't written.
+    pub fn get(&self, key: &str, fallback: Span) -> Span {
+        self.0
+            .iter()
+            .find(|(k, _)| k == key)
+            .map_or(fallback, |(_, span)| *span)
+    }
 }
 
 /// Parsed model definition from a struct with `#[derive(Model)]`.
@@ -77,6 +270,12 @@ pub struct ModelDef {
     pub generics: Generics,
     /// Model-level configuration.
     pub config: ModelConfigParsed,
+    /// Visibility of the struct, reused for generated companion structs.
+    pub vis: syn::Visibility,
+    /// Whether the struct carries `#[validate(...)]` rules from `#[derive(Validate)]`.
+    pub has_validation: bool,
+    /// Spans of the struct-level `#[sqlmodel(...)]` keys.
+    pub attr_spans: AttrSpans,
 }
 
 /// Parsed field definition from a struct field.
@@ -131,6 +330,8 @@ pub struct FieldDef {
     pub serialization_alias: Option<String>,
     /// Whether this is a computed field (not stored in database).
     pub computed: bool,
+    /// Whether this column is deferred (skipped by default SELECTs).
+    pub deferred: bool,
     /// Total number of digits for Decimal/Numeric types (precision).
     /// Maps to DECIMAL(max_digits, decimal_places) in SQL.
     pub max_digits: Option<u8>,
@@ -139,6 +340,14 @@ pub struct FieldDef {
     pub decimal_places: Option<u8>,
     /// Exclude this field from serialization (model_dump).
     pub exclude: bool,
+    /// Leave this field out of generated DTOs and JSON Schema.
+    pub api_exclude: bool,
+    /// Store this column but never serialize it or expose it in API shapes.
+    pub db_only: bool,
+    /// Function converting `&FieldType` into the stored `Value` (replaces `Into<Value>`).
+    pub serialize_with: Option<syn::Path>,
+    /// Function decoding the stored `&Value` into the field type (replaces `FromValue`).
+    pub deserialize_with: Option<syn::Path>,
     /// Schema title for JSON Schema generation.
     pub title: Option<String>,
     /// Schema description for JSON Schema generation.
@@ -158,6 +367,12 @@ pub struct FieldDef {
     pub column_constraints: Vec<String>,
     /// SQL comment for the column.
     pub column_comment: Option<String>,
+    /// Column collation (`collation = "..."`).
+    pub collation: Option<String>,
+    /// Case-insensitive text column (`case_insensitive`), stored as `SqlType::CiText`.
+    pub case_insensitive: bool,
+    /// Column character set (`charset = "..."`).
+    pub charset: Option<String>,
     /// Extra metadata as JSON string.
     pub column_info: Option<String>,
     /// Complete column specification override (sa_column).
@@ -170,6 +385,8 @@ pub struct FieldDef {
     /// Discriminator field name for union types.
     /// Used to identify which field in a union determines the concrete type.
     pub discriminator: Option<String>,
+    /// Spans of this field's `#[sqlmodel(...)]` keys.
+    pub attr_spans: AttrSpans,
 }
 
 /// Parsed relationship attribute from `#[sqlmodel(relationship(...))]`.
@@ -426,29 +643,142 @@ pub fn parse_model(input: &DeriveInput) -> Result<ModelDef> {
     let name = input.ident.clone();
     let generics = input.generics.clone();
 
+    // Keep going past bad attributes so every problem is reported at once
+    let mut errors = Vec::new();
+
     // Parse struct-level attributes
-    let StructAttrs {
-        table_name,
-        table_alias,
-        config,
-    } = parse_struct_sqlmodel_attrs(&input.attrs, &name)?;
+    let struct_attrs = parse_struct_sqlmodel_attrs(&input.attrs, &name)
+        .map_err(|e| errors.push(e))
+        .ok();
+    let rename_all = struct_attrs.as_ref().and_then(|a| a.config.rename_all);
 
     // Get struct fields
     let fields = match &input.data {
-        Data::Struct(data) => parse_fields(&data.fields)?,
-        Data::Enum(_) => {
-            return Err(Error::new_spanned(
-                input,
-                "Model can only be derived for structs, not enums",
+        Data::Struct(data) => parse_fields(&data.fields, rename_all),
+        Data::Enum(_) => Err(Error::new_spanned(
+            input,
+            "Model can only be derived for structs, not enums",
+        )),
+        Data::Union(_) => Err(Error::new_spanned(
+            input,
+            "Model can only be derived for structs, not unions",
+        )),
+    }
+    .map_err(|e| errors.push(e))
+    .ok();
+
+    let (
+        Some(StructAttrs {
+            table_name,
+            table_alias,
+            mut config,
+            attr_spans,
+        }),
+        Some(fields),
+    ) = (struct_attrs, fields)
+    else {
+        combine_errors(errors)?;
+        unreachable!("failed parse steps record their errors");
+    };
+    let span_of = |key: &str| attr_spans.get(key, name.span());
+
+    // Validate: composite constraint/index columns must name a field or column
+    let known_column = |col: &str| {
+        fields
+            .iter()
+            .any(|f| !f.skip && !f.computed && (f.column_name == col || f.name == col))
+    };
+    let constraint_columns = config
+        .unique_together
+        .iter()
+        .flatten()
+        .map(|col| (col, "unique_together"))
+        .chain(
+            config
+                .indexes
+                .iter()
+                .flat_map(|i| i.columns.iter().map(|col| (col, "index"))),
+        );
+    for (col, key) in constraint_columns {
+        if !known_column(col) {
+            errors.push(Error::new(
+                span_of(key),
+                format!("unknown column `{col}` in unique_together/index attribute"),
             ));
         }
-        Data::Union(_) => {
-            return Err(Error::new_spanned(
-                input,
-                "Model can only be derived for structs, not unions",
+    }
+
+    // Resolve default ordering to column names
+    for (col, _) in &mut config.order_by {
+        let Some(field) = fields
+            .iter()
+            .find(|f| !f.skip && !f.computed && (f.column_name == *col || f.name == col))
+        else {
+            errors.push(Error::new(
+                span_of("order_by"),
+                format!("unknown column `{col}` in order_by attribute"),
+            ));
+            continue;
+        };
+        col.clone_from(&field.column_name);
+    }
+
+    // Validate: a model keeps history either itself or through the database
+    if config.history_table.is_some() && config.system_versioned {
+        errors.push(Error::new(
+            span_of("system_versioned"),
+            "history_table and system_versioned cannot be combined",
+        ));
+    }
+
+    // Validate: registered models are concrete and own their table
+    if config.register && !generics.params.is_empty() {
+        let mut err = Error::new(
+            span_of("register"),
+            "register is not supported on generic models",
+        );
+        err.combine(Error::new_spanned(
+            &generics,
+            "generic parameters declared here",
+        ));
+        errors.push(err);
+    }
+    if config.register && config.inherits.is_some() && config.discriminator_value.is_some() {
+        errors.push(Error::new(
+            span_of("register"),
+            "single table inheritance children share their parent's table; register the parent instead",
+        ));
+    }
+
+    // Validate: generated DTOs exclude real fields and have distinct names
+    if !config.generate.is_empty() && !generics.params.is_empty() {
+        let mut err = Error::new(
+            span_of("generate"),
+            "generate is not supported on generic models",
+        );
+        err.combine(Error::new_spanned(
+            &generics,
+            "generic parameters declared here",
+        ));
+        errors.push(err);
+    }
+    for (i, dto) in config.generate.iter().enumerate() {
+        if dto.name == name || config.generate[..i].iter().any(|d| d.name == dto.name) {
+            errors.push(Error::new_spanned(
+                &dto.name,
+                format!("duplicate generated struct `{}`", dto.name),
             ));
         }
-    };
+        for excluded in &dto.exclude {
+            if !fields.iter().any(|f| f.name == *excluded) {
+                errors.push(Error::new_spanned(
+                    excluded,
+                    format!("unknown field `{excluded}` in generate exclude(...)"),
+                ));
+            }
+        }
+    }
+    combine_errors(errors)?;
 
     // Validate: at least one field should be a primary key, or warn
     // (we don't error because some use cases may not need a PK)
@@ -458,6 +788,16 @@ pub fn parse_model(input: &DeriveInput) -> Result<ModelDef> {
         // For now, just allow it - the generate phase will handle defaults
     }
 
+    // `#[validate(...)]` attributes only compile alongside `#[derive(Validate)]`
+    let has_validation = match &input.data {
+        Data::Struct(data) => input
+            .attrs
+            .iter()
+            .chain(data.fields.iter().flat_map(|f| &f.attrs))
+            .any(|attr| attr.path().is_ident("validate")),
+        _ => false,
+    };
+
     Ok(ModelDef {
         name,
         table_name,
@@ -465,6 +805,9 @@ pub fn parse_model(input: &DeriveInput) -> Result<ModelDef> {
         fields,
         generics,
         config,
+        vis: input.vis.clone(),
+        has_validation,
+        attr_spans,
     })
 }
 
@@ -473,6 +816,7 @@ struct StructAttrs {
     table_name: String,
     table_alias: Option<String>,
     config: ModelConfigParsed,
+    attr_spans: AttrSpans,
 }
 
 /// Parse struct-level `#[sqlmodel(...)]` attributes.
@@ -485,13 +829,16 @@ fn parse_struct_sqlmodel_attrs(attrs: &[Attribute], struct_name: &Ident) -> Resu
     let mut table_name: Option<String> = None;
     let mut table_alias: Option<String> = None;
     let mut config = ModelConfigParsed::default();
+    let mut attr_spans = AttrSpans::default();
+    let mut errors = Vec::new();
 
     for attr in attrs {
         if !attr.path().is_ident("sqlmodel") {
             continue;
         }
 
-        attr.parse_nested_meta(|meta| {
+        let parsed = attr.parse_nested_meta(|meta| {
+            attr_spans.record(&meta.path);
             if meta.path.is_ident("table") {
                 // Check if it's a flag (no value) or has a value
                 if meta.input.peek(syn::Token![=]) {
@@ -593,7 +940,10 @@ fn parse_struct_sqlmodel_attrs(attrs: &[Attribute], struct_name: &Ident) -> Resu
                     config.title = Some(lit_str.value());
                     Ok(())
                 } else {
-                    Err(Error::new_spanned(value, "expected string literal for title"))
+                    Err(Error::new_spanned(
+                        value,
+                        "expected string literal for title",
+                    ))
                 }
             // Table inheritance attributes
             } else if meta.path.is_ident("inheritance") {
@@ -664,17 +1014,144 @@ fn parse_struct_sqlmodel_attrs(attrs: &[Attribute], struct_name: &Ident) -> Resu
                         "expected string literal for shard_key",
                     ))
                 }
+            } else if meta.path.is_ident("unique_together") {
+                let columns = parse_column_list(&meta)?;
+                if columns.len() < 2 {
+                    return Err(Error::new_spanned(
+                        meta.path,
+                        "unique_together requires at least two columns",
+                    ));
+                }
+                config.unique_together.push(columns);
+                Ok(())
+            } else if meta.path.is_ident("index") {
+                config.indexes.push(parse_index_content(&meta)?);
+                Ok(())
+            } else if meta.path.is_ident("comment") {
+                let value: Lit = meta.value()?.parse()?;
+                if let Lit::Str(lit_str) = value {
+                    config.comment = Some(lit_str.value());
+                    Ok(())
+                } else {
+                    Err(Error::new_spanned(
+                        value,
+                        "expected string literal for comment",
+                    ))
+                }
+            // Temporal history attributes
+            } else if meta.path.is_ident("history_table") {
+                let value: Lit = meta.value()?.parse()?;
+                if let Lit::Str(lit_str) = value {
+                    config.history_table = Some(lit_str.value());
+                    Ok(())
+                } else {
+                    Err(Error::new_spanned(
+                        value,
+                        "expected string literal for history_table",
+                    ))
+                }
+            } else if meta.path.is_ident("system_versioned") {
+                config.system_versioned = true;
+                Ok(())
+            // Global model registry
+            } else if meta.path.is_ident("register") {
+                config.register = true;
+                Ok(())
+            } else if meta.path.is_ident("rename_all") {
+                let value: Lit = meta.value()?.parse()?;
+                if let Lit::Str(lit_str) = value {
+                    config.rename_all = Some(RenameRule::parse(&lit_str.value()).ok_or_else(|| {
+                        Error::new_spanned(
+                            &lit_str,
+                            "rename_all must be one of: 'lowercase', 'UPPERCASE', 'PascalCase', \
+                             'camelCase', 'snake_case', 'SCREAMING_SNAKE_CASE', 'kebab-case', \
+                             'SCREAMING-KEBAB-CASE'",
+                        )
+                    })?);
+                    Ok(())
+                } else {
+                    Err(Error::new_spanned(
+                        value,
+                        "expected string literal for rename_all",
+                    ))
+                }
+            // Entity cache
+            } else if meta.path.is_ident("cache") {
+                config.cache = Some(parse_cache_content(&meta)?);
+                Ok(())
+            // Default ordering and named scopes
+            } else if meta.path.is_ident("order_by") {
+                let value: Lit = meta.value()?.parse()?;
+                if let Lit::Str(lit_str) = value {
+                    config.order_by = parse_order_by(&lit_str.value())
+                        .map_err(|msg| Error::new_spanned(&lit_str, msg))?;
+                    Ok(())
+                } else {
+                    Err(Error::new_spanned(
+                        value,
+                        "expected string literal for order_by",
+                    ))
+                }
+            } else if meta.path.is_ident("scope") {
+                meta.parse_nested_meta(|nested| {
+                    let Some(scope) = nested.path.get_ident().cloned() else {
+                        return Err(Error::new_spanned(&nested.path, "expected scope name"));
+                    };
+                    let value: Lit = nested.value()?.parse()?;
+                    let Lit::Str(lit_str) = value else {
+                        return Err(Error::new_spanned(
+                            value,
+                            "expected string literal for scope expression",
+                        ));
+                    };
+                    if config.scopes.iter().any(|(existing, _)| *existing == scope) {
+                        return Err(Error::new_spanned(
+                            &scope,
+                            format!("duplicate scope `{scope}`"),
+                        ));
+                    }
+                    if lit_str.value().trim().is_empty() {
+                        return Err(Error::new_spanned(&lit_str, "scope expression is empty"));
+                    }
+                    config.scopes.push((scope, lit_str.value()));
+                    Ok(())
+                })
+            // Companion DTO structs
+            } else if meta.path.is_ident("generate") {
+                let value: Lit = meta.value()?.parse()?;
+                if let Lit::Str(lit_str) = value {
+                    let parser =
+                        syn::punctuated::Punctuated::<DtoAttr, syn::Token![,]>::parse_terminated;
+                    let dtos =
+                        syn::parse::Parser::parse_str(parser, &lit_str.value()).map_err(|e| {
+                            Error::new_spanned(&lit_str, format!("invalid generate spec: {e}"))
+                        })?;
+                    config.generate.extend(dtos);
+                    Ok(())
+                } else {
+                    Err(Error::new_spanned(
+                        value,
+                        "expected string literal for generate",
+                    ))
+                }
             } else {
-                Err(Error::new_spanned(
-                    meta.path,
-                    "unknown sqlmodel struct attribute (supported: table, table_alias, from_attributes, \
-                     validate_assignment, extra, strict, populate_by_name, use_enum_values, \
-                     arbitrary_types_allowed, defer_build, revalidate_instances, json_schema_extra, title, \
-                     inheritance, inherits, discriminator, discriminator_value, shard_key)",
-                ))
+                let key = meta.path.to_token_stream().to_string();
+                errors.push(Error::new_spanned(
+                    &meta.path,
+                    format!(
+                        "unknown sqlmodel struct attribute `{key}`{} (supported: {})",
+                        did_you_mean(&key, STRUCT_ATTRS),
+                        STRUCT_ATTRS.join(", ")
+                    ),
+                ));
+                skip_meta_value(&meta)
             }
-        })?;
+        });
+        if let Err(e) = parsed {
+            errors.push(e);
+        }
     }
+    combine_errors(errors)?;
 
     // Derive table name from struct name unless explicitly overridden.
     let table_name = table_name.unwrap_or_else(|| derive_table_name(&struct_name.to_string()));
@@ -696,9 +1173,291 @@ fn parse_struct_sqlmodel_attrs(attrs: &[Attribute], struct_name: &Ident) -> Resu
         table_name,
         table_alias,
         config,
+        attr_spans,
     })
 }
 
+/// Struct-level `#[sqlmodel(...)]` keys.
+const STRUCT_ATTRS: &[&str] = &[
+    "table",
+    "table_alias",
+    "from_attributes",
+    "validate_assignment",
+    "extra",
+    "strict",
+    "populate_by_name",
+    "use_enum_values",
+    "arbitrary_types_allowed",
+    "defer_build",
+    "revalidate_instances",
+    "json_schema_extra",
+    "title",
+    "inheritance",
+    "inherits",
+    "discriminator",
+    "discriminator_value",
+    "shard_key",
+    "unique_together",
+    "index",
+    "comment",
+    "history_table",
+    "system_versioned",
+    "generate",
+    "cache",
+    "order_by",
+    "scope",
+    "register",
+    "rename_all",
+];
+
+/// Field-level `#[sqlmodel(...)]` keys.
+const FIELD_ATTRS: &[&str] = &[
+    "primary_key",
+    "auto_increment",
+    "column",
+    "nullable",
+    "unique",
+    "foreign_key",
+    "on_delete",
+    "on_update",
+    "default",
+    "sql_type",
+    "index",
+    "skip",
+    "skip_insert",
+    "skip_update",
+    "relationship",
+    "alias",
+    "validation_alias",
+    "serialization_alias",
+    "computed",
+    "deferred",
+    "exclude",
+    "api_exclude",
+    "db_only",
+    "serialize_with",
+    "deserialize_with",
+    "title",
+    "description",
+    "schema_extra",
+    "max_digits",
+    "decimal_places",
+    "default_json",
+    "repr",
+    "const_field",
+    "column_constraints",
+    "column_comment",
+    "collation",
+    "charset",
+    "case_insensitive",
+    "column_info",
+    "sa_column",
+    "hybrid",
+    "sql",
+    "discriminator",
+    "parent",
+];
+
+/// A `; did you mean `...`?` hint naming the known key closest to a
+/// misspelled one, or nothing if none is close.
+fn did_you_mean(key: &str, known: &[&str]) -> String {
+    let max_distance = (key.len() / 3).max(1);
+    known
+        .iter()
+        .map(|candidate| (edit_distance(key, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map_or_else(String::new, |(_, candidate)| {
+            format!("; did you mean `{candidate}`?")
+        })
+}
+
+/// Edit distance between two keys, counting a swap of adjacent characters
+/// (`tabel` for `table`) as a single edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// Consume an unknown key's `= value` or `(...)` so the keys after it are
+/// still checked.
+fn skip_meta_value(meta: &syn::meta::ParseNestedMeta<'_>) -> Result<()> {
+    if meta.input.peek(syn::Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.input.parse::<proc_macro2::TokenTree>()?;
+    }
+    Ok(())
+}
+
+/// Fold collected errors into one so the compiler reports each of them.
+pub(crate) fn combine_errors(errors: Vec<Error>) -> Result<()> {
+    let mut errors = errors.into_iter();
+    let Some(mut combined) = errors.next() else {
+        return Ok(());
+    };
+    for err in errors {
+        combined.combine(err);
+    }
+    Err(combined)
+}
+
+/// Parse a parenthesized list of column name string literals, e.g. `("a", "b")`.
+fn parse_column_list(meta: &syn::meta::ParseNestedMeta<'_>) -> Result<Vec<String>> {
+    let content;
+    syn::parenthesized!(content in meta.input);
+    let lits =
+        content.parse_terminated(<syn::LitStr as syn::parse::Parse>::parse, syn::Token![,])?;
+    if lits.is_empty() {
+        return Err(Error::new_spanned(
+            &meta.path,
+            "expected at least one column",
+        ));
+    }
+    Ok(lits.iter().map(syn::LitStr::value).collect())
+}
+
+/// Parse the content of a struct-level `index(...)` attribute.
+///
+/// Supported keys: `columns("a", "b")` (required), `unique`, `name = "..."`,
+/// `where = "..."`.
+/// Parse `"created_at DESC, id"` into (column, descending) pairs.
+fn parse_order_by(s: &str) -> std::result::Result<Vec<(String, bool)>, String> {
+    s.split(',')
+        .map(|part| {
+            let words: Vec<&str> = part.split_whitespace().collect();
+            match words.as_slice() {
+                [col] => Ok(((*col).to_string(), false)),
+                [col, dir] if dir.eq_ignore_ascii_case("asc") => Ok(((*col).to_string(), false)),
+                [col, dir] if dir.eq_ignore_ascii_case("desc") => Ok(((*col).to_string(), true)),
+                _ => Err(format!(
+                    "invalid order_by term `{}` (expected `column [ASC|DESC]`)",
+                    part.trim()
+                )),
+            }
+        })
+        .collect()
+}
+
+/// Parse `cache` or `cache(ttl = "...")`.
+fn parse_cache_content(meta: &syn::meta::ParseNestedMeta<'_>) -> Result<CacheAttr> {
+    let mut cache = CacheAttr::default();
+    if !meta.input.peek(syn::token::Paren) {
+        return Ok(cache);
+    }
+
+    meta.parse_nested_meta(|nested| {
+        if nested.path.is_ident("ttl") {
+            let value: Lit = nested.value()?.parse()?;
+            let Lit::Str(lit_str) = value else {
+                return Err(Error::new_spanned(
+                    value,
+                    "expected string literal for cache ttl",
+                ));
+            };
+            let ttl = parse_duration_ms(&lit_str.value()).ok_or_else(|| {
+                Error::new_spanned(
+                    &lit_str,
+                    "invalid cache ttl (expected e.g. \"500ms\", \"60s\", \"5m\", \"1h\", \"1d\")",
+                )
+            })?;
+            cache.ttl_ms = Some(ttl);
+            Ok(())
+        } else {
+            Err(Error::new_spanned(
+                &nested.path,
+                "unknown cache attribute (supported: ttl)",
+            ))
+        }
+    })?;
+
+    Ok(cache)
+}
+
+/// Parse a duration such as `"500ms"`, `"60s"`, `"5m"`, `"1h"` or `"1d"` into
+/// milliseconds.
+fn parse_duration_ms(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit())?;
+    let (digits, unit) = s.split_at(split);
+    let n: u64 = digits.parse().ok()?;
+    let scale = match unit.trim() {
+        "ms" => 1,
+        "s" => 1_000,
+        "m" => 60_000,
+        "h" => 3_600_000,
+        "d" => 86_400_000,
+        _ => return None,
+    };
+    n.checked_mul(scale)
+}
+
+fn parse_index_content(meta: &syn::meta::ParseNestedMeta<'_>) -> Result<IndexAttr> {
+    let mut index = IndexAttr::default();
+
+    meta.parse_nested_meta(|nested| {
+        if nested.path.is_ident("columns") {
+            index.columns = parse_column_list(&nested)?;
+        } else if nested.path.is_ident("unique") {
+            index.unique = true;
+        } else if nested.path.is_ident("name") {
+            let value: Lit = nested.value()?.parse()?;
+            if let Lit::Str(lit_str) = value {
+                index.name = Some(lit_str.value());
+            } else {
+                return Err(Error::new_spanned(
+                    value,
+                    "expected string literal for index name",
+                ));
+            }
+        } else if nested.path.is_ident("where") {
+            let value: Lit = nested.value()?.parse()?;
+            if let Lit::Str(lit_str) = value {
+                index.where_clause = Some(lit_str.value());
+            } else {
+                return Err(Error::new_spanned(
+                    value,
+                    "expected string literal for index where clause",
+                ));
+            }
+        } else {
+            return Err(Error::new_spanned(
+                &nested.path,
+                "unknown index attribute (supported: columns, unique, name, where)",
+            ));
+        }
+        Ok(())
+    })?;
+
+    if index.columns.is_empty() {
+        return Err(Error::new_spanned(
+            &meta.path,
+            "index requires columns(\"...\")",
+        ));
+    }
+
+    Ok(index)
+}
+
 /// Derive table name from struct name: convert to snake_case and pluralize.
 ///
 /// Examples:
@@ -840,9 +1599,23 @@ fn pluralize(word: &str) -> String {
 }
 
 /// Parse all fields from a struct.
-fn parse_fields(fields: &Fields) -> Result<Vec<FieldDef>> {
+fn parse_fields(fields: &Fields, rename_all: Option<RenameRule>) -> Result<Vec<FieldDef>> {
     match fields {
-        Fields::Named(named) => named.named.iter().map(parse_field).collect(),
+        Fields::Named(named) => {
+            // Report problems in every field, not just the first bad one
+            let mut errors = Vec::new();
+            let parsed: Vec<FieldDef> = named
+                .named
+                .iter()
+                .filter_map(|field| {
+                    parse_field(field, rename_all)
+                        .map_err(|e| errors.push(e))
+                        .ok()
+                })
+                .collect();
+            combine_errors(errors)?;
+            Ok(parsed)
+        }
         Fields::Unnamed(_) => Err(Error::new(
             Span::call_site(),
             "Model requires a struct with named fields, not a tuple struct",
@@ -855,7 +1628,7 @@ fn parse_fields(fields: &Fields) -> Result<Vec<FieldDef>> {
 }
 
 /// Parse a single field and its attributes.
-fn parse_field(field: &Field) -> Result<FieldDef> {
+fn parse_field(field: &Field, rename_all: Option<RenameRule>) -> Result<FieldDef> {
     let name = field
         .ident
         .clone()
@@ -869,8 +1642,12 @@ fn parse_field(field: &Field) -> Result<FieldDef> {
     // Parse field attributes
     let attrs = parse_field_attrs(&field.attrs, &name, &ty)?;
 
-    // Column name defaults to field name
-    let column_name = attrs.column.unwrap_or_else(|| name.to_string());
+    // Column name defaults to the field name, renamed by `rename_all`; an
+    // explicit `column = "..."` is used verbatim
+    let column_name = attrs.column.unwrap_or_else(|| match rename_all {
+        Some(rule) => rule.apply(&name.to_string()),
+        None => name.to_string(),
+    });
 
     Ok(FieldDef {
         name,
@@ -895,9 +1672,14 @@ fn parse_field(field: &Field) -> Result<FieldDef> {
         validation_alias: attrs.validation_alias,
         serialization_alias: attrs.serialization_alias,
         computed: attrs.computed,
+        deferred: attrs.deferred,
         max_digits: attrs.max_digits,
         decimal_places: attrs.decimal_places,
         exclude: attrs.exclude,
+        api_exclude: attrs.api_exclude,
+        db_only: attrs.db_only,
+        serialize_with: attrs.serialize_with,
+        deserialize_with: attrs.deserialize_with,
         title: attrs.title,
         description: attrs.description,
         schema_extra: attrs.schema_extra,
@@ -906,11 +1688,15 @@ fn parse_field(field: &Field) -> Result<FieldDef> {
         const_field: attrs.const_field,
         column_constraints: attrs.column_constraints,
         column_comment: attrs.column_comment,
+        collation: attrs.collation,
+        case_insensitive: attrs.case_insensitive,
+        charset: attrs.charset,
         column_info: attrs.column_info,
         sa_column: attrs.sa_column,
         hybrid: attrs.hybrid,
         hybrid_sql: attrs.hybrid_sql,
         discriminator: attrs.discriminator,
+        attr_spans: attrs.attr_spans,
     })
 }
 
@@ -936,12 +1722,22 @@ struct FieldAttrs {
     validation_alias: Option<String>,
     serialization_alias: Option<String>,
     computed: bool,
+    /// Load this column on demand instead of in default SELECTs.
+    deferred: bool,
     /// Total number of digits for Decimal/Numeric types (precision).
     max_digits: Option<u8>,
     /// Number of digits after decimal point for Decimal/Numeric types (scale).
     decimal_places: Option<u8>,
     /// Exclude this field from serialization.
     exclude: bool,
+    /// Leave this field out of generated DTOs and JSON Schema.
+    api_exclude: bool,
+    /// Stored column kept out of serialization and API shapes.
+    db_only: bool,
+    /// Custom `&FieldType -> Value` conversion for to_row.
+    serialize_with: Option<syn::Path>,
+    /// Custom `&Value -> Result<FieldType>` conversion for from_row.
+    deserialize_with: Option<syn::Path>,
     /// Schema title for JSON Schema generation.
     title: Option<String>,
     /// Schema description for JSON Schema generation.
@@ -959,6 +1755,12 @@ struct FieldAttrs {
     column_constraints: Vec<String>,
     /// SQL comment for the column.
     column_comment: Option<String>,
+    /// Column collation.
+    collation: Option<String>,
+    /// Case-insensitive text column.
+    case_insensitive: bool,
+    /// Column character set.
+    charset: Option<String>,
     /// Extra metadata as JSON string.
     column_info: Option<String>,
     /// Complete column specification override (sa_column).
@@ -971,6 +1773,8 @@ struct FieldAttrs {
     discriminator: Option<String>,
     /// Joined-table inheritance parent field (embedded parent model).
     parent: bool,
+    /// Where each key was written.
+    attr_spans: AttrSpans,
 }
 
 /// Detect the relationship kind from a field's Rust type.
@@ -1004,14 +1808,16 @@ fn parse_field_attrs(
     field_type: &Type,
 ) -> Result<FieldAttrs> {
     let mut result = FieldAttrs::default();
+    let mut errors = Vec::new();
 
     for attr in attrs {
         if !attr.path().is_ident("sqlmodel") {
             continue;
         }
 
-        attr.parse_nested_meta(|meta| {
+        let parsed = attr.parse_nested_meta(|meta| {
             let path = &meta.path;
+            result.attr_spans.record(path);
 
             if path.is_ident("primary_key") {
                 result.primary_key = true;
@@ -1166,8 +1972,28 @@ fn parse_field_attrs(
                 }
             } else if path.is_ident("computed") {
                 result.computed = true;
+            } else if path.is_ident("deferred") {
+                result.deferred = true;
             } else if path.is_ident("exclude") {
                 result.exclude = true;
+            } else if path.is_ident("api_exclude") {
+                result.api_exclude = true;
+            } else if path.is_ident("db_only") {
+                result.db_only = true;
+            } else if path.is_ident("serialize_with") || path.is_ident("deserialize_with") {
+                let value: Lit = meta.value()?.parse()?;
+                let Lit::Str(lit_str) = value else {
+                    return Err(Error::new_spanned(
+                        value,
+                        "expected string literal function path, e.g. \"codec::join_tags\"",
+                    ));
+                };
+                let hook = Some(lit_str.parse::<syn::Path>()?);
+                if path.is_ident("serialize_with") {
+                    result.serialize_with = hook;
+                } else {
+                    result.deserialize_with = hook;
+                }
             } else if path.is_ident("title") {
                 let value: Lit = meta.value()?.parse()?;
                 if let Lit::Str(lit_str) = value {
@@ -1275,6 +2101,28 @@ fn parse_field_attrs(
                         "expected string literal for column_comment",
                     ));
                 }
+            } else if path.is_ident("collation") {
+                let value: Lit = meta.value()?.parse()?;
+                if let Lit::Str(lit_str) = value {
+                    result.collation = Some(lit_str.value());
+                } else {
+                    return Err(Error::new_spanned(
+                        value,
+                        "expected string literal for collation",
+                    ));
+                }
+            } else if path.is_ident("case_insensitive") {
+                result.case_insensitive = true;
+            } else if path.is_ident("charset") {
+                let value: Lit = meta.value()?.parse()?;
+                if let Lit::Str(lit_str) = value {
+                    result.charset = Some(lit_str.value());
+                } else {
+                    return Err(Error::new_spanned(
+                        value,
+                        "expected string literal for charset",
+                    ));
+                }
             } else if path.is_ident("column_info") {
                 let value: Lit = meta.value()?.parse()?;
                 if let Lit::Str(lit_str) = value {
@@ -1320,26 +2168,27 @@ fn parse_field_attrs(
             } else {
                 // Unknown attribute
                 let attr_name = path.to_token_stream().to_string();
-                return Err(Error::new_spanned(
+                errors.push(Error::new_spanned(
                     path,
                     format!(
-                        "unknown sqlmodel attribute `{attr_name}`. \
-                         Valid attributes are: primary_key, auto_increment, column, nullable, \
-                         unique, foreign_key, on_delete, on_update, default, sql_type, index, \
-                         skip, skip_insert, skip_update, relationship, alias, validation_alias, \
-                         serialization_alias, computed, max_digits, decimal_places, default_json, repr, \
-                         const_field, column_constraints, column_comment, column_info, sa_column, \
-                         hybrid, sql, discriminator, parent"
+                        "unknown sqlmodel attribute `{attr_name}`{} (supported: {})",
+                        did_you_mean(&attr_name, FIELD_ATTRS),
+                        FIELD_ATTRS.join(", ")
                     ),
                 ));
+                return skip_meta_value(&meta);
             }
 
             Ok(())
-        })?;
+        });
+        if let Err(e) = parsed {
+            errors.push(e);
+        }
     }
 
     // Validate attribute combinations
-    validate_field_attrs(&result, field_name, field_type)?;
+    validate_field_attrs(&result, field_name, field_type, &mut errors);
+    combine_errors(errors)?;
 
     Ok(result)
 }
@@ -1756,19 +2605,68 @@ fn parse_sa_column_content(meta: &syn::meta::ParseNestedMeta<'_>) -> Result<SaCo
 }
 
 /// Validate that attribute combinations make sense.
-fn validate_field_attrs(attrs: &FieldAttrs, field_name: &Ident, field_type: &Type) -> Result<()> {
+fn validate_field_attrs(
+    attrs: &FieldAttrs,
+    field_name: &Ident,
+    field_type: &Type,
+    errors: &mut Vec<Error>,
+) {
+    let span_of = |key: &str| attrs.attr_spans.get(key, field_name.span());
+
     // Cannot use skip with primary_key
     if attrs.skip && attrs.primary_key {
-        return Err(Error::new_spanned(
-            field_name,
+        errors.push(Error::new(
+            span_of("skip"),
             "cannot use both `skip` and `primary_key` on the same field",
         ));
     }
 
+    // Primary keys are needed to load deferred columns, so they cannot be deferred
+    if attrs.deferred && (attrs.primary_key || attrs.computed || attrs.skip) {
+        errors.push(Error::new(
+            span_of("deferred"),
+            "`deferred` cannot be combined with `primary_key`, `computed`, or `skip`",
+        ));
+    }
+
+    // case_insensitive picks the column type itself
+    if attrs.case_insensitive && attrs.sql_type.is_some() {
+        errors.push(Error::new(
+            span_of("case_insensitive"),
+            "`case_insensitive` selects the CITEXT column type; it cannot be combined with `sql_type`",
+        ));
+    }
+
+    // A database-only field has to be stored somewhere
+    if attrs.db_only && (attrs.computed || attrs.skip) {
+        errors.push(Error::new(
+            span_of("db_only"),
+            "`db_only` fields are stored columns; it cannot be combined with `computed` or `skip`",
+        ));
+    }
+
+    // Conversion hooks only apply to columns read and written through the row
+    for key in ["serialize_with", "deserialize_with"] {
+        let set = if key == "serialize_with" {
+            attrs.serialize_with.is_some()
+        } else {
+            attrs.deserialize_with.is_some()
+        };
+        if set && (attrs.skip || attrs.computed || attrs.parent || attrs.relationship.is_some()) {
+            errors.push(Error::new(
+                span_of(key),
+                format!(
+                    "`{key}` only applies to stored columns; it cannot be combined with \
+                     `skip`, `computed`, `parent`, or `relationship`"
+                ),
+            ));
+        }
+    }
+
     // Cannot use skip with skip_insert or skip_update (redundant)
     if attrs.skip && (attrs.skip_insert || attrs.skip_update) {
-        return Err(Error::new_spanned(
-            field_name,
+        errors.push(Error::new(
+            span_of("skip"),
             "`skip` already excludes the field from all operations; \
              `skip_insert` and `skip_update` are redundant",
         ));
@@ -1778,8 +2676,8 @@ fn validate_field_attrs(attrs: &FieldAttrs, field_name: &Ident, field_type: &Typ
     if attrs.relationship.is_some() {
         let detected = detect_relationship_kind(field_type);
         if detected.is_none() {
-            return Err(Error::new_spanned(
-                field_name,
+            errors.push(Error::new(
+                span_of("relationship"),
                 "relationship attribute can only be used on Related<T>, RelatedMany<T>, or Lazy<T> fields",
             ));
         }
@@ -1803,8 +2701,8 @@ fn validate_field_attrs(attrs: &FieldAttrs, field_name: &Ident, field_type: &Typ
             || attrs.hybrid
             || attrs.hybrid_sql.is_some()
         {
-            return Err(Error::new_spanned(
-                field_name,
+            errors.push(Error::new(
+                span_of("parent"),
                 "`parent` cannot be combined with column/relationship attributes; it is an embedded joined-inheritance parent model field",
             ));
         }
@@ -1816,8 +2714,8 @@ fn validate_field_attrs(attrs: &FieldAttrs, field_name: &Ident, field_type: &Typ
     // Validate decimal precision constraints
     if let (Some(max_digits), Some(decimal_places)) = (attrs.max_digits, attrs.decimal_places) {
         if decimal_places > max_digits {
-            return Err(Error::new_spanned(
-                field_name,
+            errors.push(Error::new(
+                span_of("decimal_places"),
                 format!(
                     "decimal_places ({}) cannot be greater than max_digits ({})",
                     decimal_places, max_digits
@@ -1866,8 +2764,8 @@ fn validate_field_attrs(attrs: &FieldAttrs, field_name: &Ident, field_type: &Typ
         }
 
         if !conflicts.is_empty() {
-            return Err(Error::new_spanned(
-                field_name,
+            errors.push(Error::new(
+                span_of("sa_column"),
                 format!(
                     "`sa_column` provides full column override and cannot be combined with: {}. \
                      Use sa_column sub-attributes instead (e.g., sa_column(sql_type = \"...\"))",
@@ -1879,19 +2777,17 @@ fn validate_field_attrs(attrs: &FieldAttrs, field_name: &Ident, field_type: &Typ
 
     // Validate hybrid property: `sql` requires `hybrid`, and `hybrid` requires `sql`
     if attrs.hybrid && attrs.hybrid_sql.is_none() {
-        return Err(Error::new_spanned(
-            field_name,
+        errors.push(Error::new(
+            span_of("hybrid"),
             "`hybrid` attribute requires `sql = \"...\"` to specify the SQL expression",
         ));
     }
     if attrs.hybrid_sql.is_some() && !attrs.hybrid {
-        return Err(Error::new_spanned(
-            field_name,
+        errors.push(Error::new(
+            span_of("sql"),
             "`sql` attribute is only valid on hybrid fields; add `hybrid` attribute",
         ));
     }
-
-    Ok(())
 }
 
 /// Check if a type is `Option<T>`.
@@ -2017,6 +2913,92 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_unknown_attrs_suggest_closest_key() {
+        let input: DeriveInput = parse_quote! {
+            #[sqlmodel(tabel = "events")]
+            struct Event {
+                #[sqlmodel(primay_key)]
+                id: i64,
+                #[sqlmodel(frobnicate)]
+                name: String,
+            }
+        };
+
+        let messages: Vec<String> = parse_model(&input)
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(messages.len(), 3, "{messages:?}");
+        assert!(
+            messages[0]
+                .starts_with("unknown sqlmodel struct attribute `tabel`; did you mean `table`?")
+        );
+        assert!(
+            messages[1].starts_with(
+                "unknown sqlmodel attribute `primay_key`; did you mean `primary_key`?"
+            )
+        );
+        assert!(messages[2].starts_with("unknown sqlmodel attribute `frobnicate` (supported:"));
+    }
+
+    #[test]
+    fn test_parse_model_reports_every_error() {
+        let input: DeriveInput = parse_quote! {
+            struct Event {
+                #[sqlmodel(primary_key, skip)]
+                id: i64,
+                #[sqlmodel(hybrid, nullable = "yes")]
+                name: String,
+            }
+        };
+
+        // A malformed value ends its own attribute, but the other fields and
+        // attribute combinations are still checked
+        let messages: Vec<String> = parse_model(&input)
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(messages.len(), 3, "{messages:?}");
+        assert!(messages[0].contains("`skip` and `primary_key`"));
+        assert!(messages[1].starts_with("expected"));
+        assert!(messages[2].contains("`hybrid` attribute requires"));
+
+        let input: DeriveInput = parse_quote! {
+            #[sqlmodel(order_by = "missing", history_table = "h", system_versioned)]
+            struct Event {
+                #[sqlmodel(primary_key)]
+                id: i64,
+            }
+        };
+        let messages: Vec<String> = parse_model(&input)
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "unknown column `missing` in order_by attribute",
+                "history_table and system_versioned cannot be combined",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_did_you_mean() {
+        assert_eq!(edit_distance("primay_key", "primary_key"), 1);
+        assert_eq!(edit_distance("tabel", "table"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(
+            did_you_mean("nulable", FIELD_ATTRS),
+            "; did you mean `nullable`?"
+        );
+        assert_eq!(did_you_mean("rename", STRUCT_ATTRS), "");
+    }
+
     // ========================================================================
     // Relationship attribute parsing tests
     // ========================================================================
@@ -2933,6 +3915,54 @@ mod tests {
         assert!(!id_field.exclude);
     }
 
+    #[test]
+    fn test_parse_serialize_with_hooks() {
+        let input: DeriveInput = parse_quote! {
+            struct Post {
+                #[sqlmodel(primary_key)]
+                id: i64,
+                #[sqlmodel(serialize_with = "codec::join_tags", deserialize_with = "codec::split_tags")]
+                tags: Vec<String>,
+            }
+        };
+
+        let def = parse_model(&input).unwrap();
+        let tags = def.fields.iter().find(|f| f.name == "tags").unwrap();
+        let path = |p: &Option<syn::Path>| p.as_ref().map(|p| quote::quote!(#p).to_string());
+        assert_eq!(
+            path(&tags.serialize_with).as_deref(),
+            Some("codec :: join_tags")
+        );
+        assert_eq!(
+            path(&tags.deserialize_with).as_deref(),
+            Some("codec :: split_tags")
+        );
+        let id = def.fields.iter().find(|f| f.name == "id").unwrap();
+        assert!(id.serialize_with.is_none() && id.deserialize_with.is_none());
+
+        let input: DeriveInput = parse_quote! {
+            struct Post {
+                #[sqlmodel(primary_key)]
+                id: i64,
+                #[sqlmodel(skip, deserialize_with = "codec::split_tags")]
+                tags: Vec<String>,
+            }
+        };
+        let err = parse_model(&input).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("`deserialize_with` only applies to stored columns")
+        );
+
+        let input: DeriveInput = parse_quote! {
+            struct Post {
+                #[sqlmodel(serialize_with = "not a path")]
+                tags: Vec<String>,
+            }
+        };
+        assert!(parse_model(&input).is_err());
+    }
+
     #[test]
     fn test_exclude_combined_with_other_attrs() {
         // exclude can be combined with other attributes
@@ -2958,6 +3988,50 @@ mod tests {
         assert_eq!(notes_field.column_name, "internal_notes");
     }
 
+    #[test]
+    fn test_parse_api_exclude_and_db_only() {
+        let input: DeriveInput = parse_quote! {
+            struct User {
+                #[sqlmodel(primary_key)]
+                id: i64,
+                #[sqlmodel(db_only)]
+                password_hash: String,
+                #[sqlmodel(api_exclude)]
+                risk_score: i32,
+            }
+        };
+
+        let def = parse_model(&input).unwrap();
+        let hash = def
+            .fields
+            .iter()
+            .find(|f| f.name == "password_hash")
+            .unwrap();
+        assert!(hash.db_only && !hash.api_exclude && !hash.exclude);
+        // Still a stored column
+        assert!(
+            def.select_fields()
+                .iter()
+                .any(|f| f.name == "password_hash")
+        );
+        let score = def.fields.iter().find(|f| f.name == "risk_score").unwrap();
+        assert!(score.api_exclude && !score.db_only);
+
+        let input: DeriveInput = parse_quote! {
+            struct User {
+                #[sqlmodel(primary_key)]
+                id: i64,
+                #[sqlmodel(db_only, computed)]
+                password_hash: String,
+            }
+        };
+        let err = parse_model(&input).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("`db_only` fields are stored columns")
+        );
+    }
+
     // =========================================================================
     // Decimal Precision Tests (max_digits, decimal_places)
     // =========================================================================
@@ -3072,6 +4146,409 @@ mod tests {
         assert!(def.data_fields().iter().any(|f| f.computed));
     }
 
+    #[test]
+    fn test_deferred_field_attribute() {
+        let input: DeriveInput = parse_quote! {
+            struct Post {
+                #[sqlmodel(primary_key)]
+                id: i64,
+                #[sqlmodel(deferred)]
+                body: String,
+            }
+        };
+
+        let def = parse_model(&input).unwrap();
+        let body = def.fields.iter().find(|f| f.name == "body").unwrap();
+        assert!(body.deferred);
+        // Deferred columns are still real columns.
+        assert_eq!(def.select_fields().len(), 2);
+    }
+
+    #[test]
+    fn test_deferred_primary_key_rejected() {
+        let input: DeriveInput = parse_quote! {
+            struct Post {
+                #[sqlmodel(primary_key, deferred)]
+                id: i64,
+            }
+        };
+
+        assert!(parse_model(&input).is_err());
+    }
+
+    #[test]
+    fn test_unique_together_and_index_attributes() {
+        let input: DeriveInput = parse_quote! {
+            #[sqlmodel(
+                table,
+                unique_together("org_id", "email"),
+                index(columns("org_id", "created_at"), unique, where = "deleted_at IS NULL"),
+                index(columns("email"), name = "idx_email")
+            )]
+            struct Member {
+                #[sqlmodel(primary_key)]
+                id: i64,
+                org_id: i64,
+                email: String,
+                created_at: i64,
+                deleted_at: Option<i64>,
+            }
+        };
+
+        let def = parse_model(&input).unwrap();
+        assert_eq!(
+            def.config.unique_together,
+            vec![vec!["org_id".to_string(), "email".to_string()]]
+        );
+        assert_eq!(def.config.indexes.len(), 2);
+        let first = &def.config.indexes[0];
+        assert_eq!(first.columns, vec!["org_id", "created_at"]);
+        assert!(first.unique);
+        assert_eq!(first.where_clause.as_deref(), Some("deleted_at IS NULL"));
+        assert_eq!(def.config.indexes[1].name.as_deref(), Some("idx_email"));
+        assert!(!def.config.indexes[1].unique);
+    }
+
+    #[test]
+    fn test_unique_together_unknown_column_rejected() {
+        let input: DeriveInput = parse_quote! {
+            #[sqlmodel(unique_together("org_id", "missing"))]
+            struct Member {
+                #[sqlmodel(primary_key)]
+                id: i64,
+                org_id: i64,
+            }
+        };
+
+        assert!(parse_model(&input).is_err());
+    }
+
+    #[test]
+    fn test_collation_and_charset_attributes() {
+        let input: DeriveInput = parse_quote! {
+            struct Tag {
+                #[sqlmodel(primary_key)]
+                id: i64,
+                #[sqlmodel(collation = "utf8mb4_bin", charset = "utf8mb4")]
+                name: String,
+                slug: String,
+            }
+        };
+        let def = parse_model(&input).unwrap();
+        let name = def.fields.iter().find(|f| f.name == "name").unwrap();
+        assert_eq!(name.collation.as_deref(), Some("utf8mb4_bin"));
+        assert_eq!(name.charset.as_deref(), Some("utf8mb4"));
+        let slug = def.fields.iter().find(|f| f.name == "slug").unwrap();
+        assert_eq!(slug.collation, None);
+
+        let input: DeriveInput = parse_quote! {
+            struct Tag {
+                #[sqlmodel(primary_key)]
+                id: i64,
+                #[sqlmodel(collation = true)]
+                name: String,
+            }
+        };
+        assert!(parse_model(&input).is_err());
+    }
+
+    #[test]
+    fn test_case_insensitive_attribute() {
+        let input: DeriveInput = parse_quote! {
+            struct Account {
+                #[sqlmodel(primary_key)]
+                id: i64,
+                #[sqlmodel(case_insensitive, unique)]
+                email: String,
+                name: String,
+            }
+        };
+        let def = parse_model(&input).unwrap();
+        assert!(
+            def.fields
+                .iter()
+                .find(|f| f.name == "email")
+                .unwrap()
+                .case_insensitive
+        );
+        assert!(
+            !def.fields
+                .iter()
+                .find(|f| f.name == "name")
+                .unwrap()
+                .case_insensitive
+        );
+
+        let input: DeriveInput = parse_quote! {
+            struct Account {
+                #[sqlmodel(primary_key)]
+                id: i64,
+                #[sqlmodel(case_insensitive, sql_type = "VARCHAR(320)")]
+                email: String,
+            }
+        };
+        assert!(parse_model(&input).is_err());
+    }
+
+    #[test]
+    fn test_table_comment_attribute() {
+        let input: DeriveInput = parse_quote! {
+            #[sqlmodel(table = "heroes", comment = "Registered heroes")]
+            struct Hero {
+                #[sqlmodel(primary_key)]
+                id: i64,
+                #[sqlmodel(column_comment = "Public alias")]
+                name: String,
+            }
+        };
+        let def = parse_model(&input).unwrap();
+        assert_eq!(def.config.comment.as_deref(), Some("Registered heroes"));
+
+        let input: DeriveInput = parse_quote! {
+            #[sqlmodel(comment = 1)]
+            struct Hero {
+                #[sqlmodel(primary_key)]
+                id: i64,
+            }
+        };
+        assert!(parse_model(&input).is_err());
+    }
+
+    #[test]
+    fn test_history_attributes() {
+        let input: DeriveInput = parse_quote! {
+            #[sqlmodel(table = "heroes", history_table = "heroes_history")]
+            struct Hero {
+                #[sqlmodel(primary_key)]
+                id: i64,
+                name: String,
+            }
+        };
+        let def = parse_model(&input).unwrap();
+        assert_eq!(def.config.history_table.as_deref(), Some("heroes_history"));
+        assert!(!def.config.system_versioned);
+
+        let input: DeriveInput = parse_quote! {
+            #[sqlmodel(history_table = "heroes_history", system_versioned)]
+            struct Hero {
+                #[sqlmodel(primary_key)]
+                id: i64,
+            }
+        };
+        assert!(parse_model(&input).is_err());
+    }
+
+    #[test]
+    fn test_cache_attribute() {
+        let input: DeriveInput = parse_quote! {
+            #[sqlmodel(table, cache(ttl = "5m"))]
+            struct Hero {
+                #[sqlmodel(primary_key)]
+                id: i64,
+            }
+        };
+        let def = parse_model(&input).unwrap();
+        assert_eq!(
+            def.config.cache,
+            Some(CacheAttr {
+                ttl_ms: Some(300_000)
+            })
+        );
+
+        let input: DeriveInput = parse_quote! {
+            #[sqlmodel(table, cache)]
+            struct Hero {
+                #[sqlmodel(primary_key)]
+                id: i64,
+            }
+        };
+        let def = parse_model(&input).unwrap();
+        assert_eq!(def.config.cache, Some(CacheAttr { ttl_ms: None }));
+
+        let input: DeriveInput = parse_quote! {
+            #[sqlmodel(table, cache(ttl = "soon"))]
+            struct Hero {
+                #[sqlmodel(primary_key)]
+                id: i64,
+            }
+        };
+        assert!(parse_model(&input).is_err());
+
+        assert_eq!(parse_duration_ms("250ms"), Some(250));
+        assert_eq!(parse_duration_ms("60s"), Some(60_000));
+        assert_eq!(parse_duration_ms("2h"), Some(7_200_000));
+        assert_eq!(parse_duration_ms("s"), None);
+        assert_eq!(parse_duration_ms("10"), None);
+    }
+
+    #[test]
+    fn test_register_attribute() {
+        let input: DeriveInput = parse_quote! {
+            #[sqlmodel(table, register)]
+            struct Hero {
+                #[sqlmodel(primary_key)]
+                id: i64,
+            }
+        };
+        assert!(parse_model(&input).unwrap().config.register);
+
+        let generic: DeriveInput = parse_quote! {
+            #[sqlmodel(register)]
+            struct Wrapper<T> {
+                #[sqlmodel(primary_key)]
+                id: i64,
+                inner: T,
+            }
+        };
+        let err = parse_model(&generic).unwrap_err();
+        assert!(err.to_string().contains("not supported on generic models"));
+
+        let sti_child: DeriveInput = parse_quote! {
+            #[sqlmodel(register, inherits = "Employee", discriminator_value = "manager")]
+            struct Manager {
+                department: String,
+            }
+        };
+        let err = parse_model(&sti_child).unwrap_err();
+        assert!(err.to_string().contains("register the parent"));
+    }
+
+    #[test]
+    fn test_rename_rule_apply() {
+        let cases = [
+            (RenameRule::Lower, "first_name"),
+            (RenameRule::Upper, "FIRST_NAME"),
+            (RenameRule::Pascal, "FirstName"),
+            (RenameRule::Camel, "firstName"),
+            (RenameRule::Snake, "first_name"),
+            (RenameRule::ScreamingSnake, "FIRST_NAME"),
+            (RenameRule::Kebab, "first-name"),
+            (RenameRule::ScreamingKebab, "FIRST-NAME"),
+        ];
+        for (rule, expected) in cases {
+            assert_eq!(rule.apply("first_name"), expected, "{rule:?}");
+        }
+        assert_eq!(RenameRule::Camel.apply("id"), "id");
+        assert_eq!(RenameRule::Pascal.apply("user_id_2"), "UserId2");
+        assert_eq!(
+            RenameRule::parse("SCREAMING_SNAKE"),
+            Some(RenameRule::ScreamingSnake)
+        );
+    }
+
+    #[test]
+    fn test_rename_all_sets_default_column_names() {
+        let input: DeriveInput = parse_quote! {
+            #[sqlmodel(table = "legacy_users", rename_all = "camelCase", order_by = "last_name")]
+            #[sqlmodel(unique_together("first_name", "last_name"))]
+            struct User {
+                #[sqlmodel(primary_key)]
+                user_id: i64,
+                first_name: String,
+                last_name: String,
+                #[sqlmodel(column = "EMAIL_ADDR")]
+                email: String,
+            }
+        };
+        let def = parse_model(&input).unwrap();
+        let columns: Vec<&str> = def.fields.iter().map(|f| f.column_name.as_str()).collect();
+        assert_eq!(columns, ["userId", "firstName", "lastName", "EMAIL_ADDR"]);
+        // Field names in struct-level attributes resolve to the renamed columns
+        assert_eq!(def.config.order_by, [("lastName".to_string(), false)]);
+
+        let bad: DeriveInput = parse_quote! {
+            #[sqlmodel(rename_all = "Train-Case")]
+            struct User {
+                #[sqlmodel(primary_key)]
+                id: i64,
+            }
+        };
+        let err = parse_model(&bad).unwrap_err();
+        assert!(err.to_string().contains("rename_all must be one of"));
+    }
+
+    #[test]
+    fn test_has_validation_detects_validate_attrs() {
+        let validated: DeriveInput = parse_quote! {
+            struct Hero {
+                #[sqlmodel(primary_key)]
+                id: i64,
+                #[validate(min_length = 1)]
+                name: String,
+            }
+        };
+        assert!(parse_model(&validated).unwrap().has_validation);
+
+        let schema_only: DeriveInput = parse_quote! {
+            #[validate(schema = "check")]
+            struct Hero {
+                #[sqlmodel(primary_key)]
+                id: i64,
+            }
+        };
+        assert!(parse_model(&schema_only).unwrap().has_validation);
+
+        let plain: DeriveInput = parse_quote! {
+            struct Hero {
+                #[sqlmodel(primary_key)]
+                id: i64,
+            }
+        };
+        assert!(!parse_model(&plain).unwrap().has_validation);
+    }
+
+    #[test]
+    fn test_order_by_and_scope_attributes() {
+        let input: DeriveInput = parse_quote! {
+            #[sqlmodel(
+                table,
+                order_by = "created DESC, id",
+                scope(active = "deleted_at IS NULL", named = "name <> ''"),
+                scope(recent = "created > 0")
+            )]
+            struct Hero {
+                #[sqlmodel(primary_key)]
+                id: i64,
+                name: String,
+                #[sqlmodel(column = "created_at")]
+                created: i64,
+            }
+        };
+        let def = parse_model(&input).unwrap();
+        assert_eq!(
+            def.config.order_by,
+            vec![("created_at".to_string(), true), ("id".to_string(), false)]
+        );
+        let scopes: Vec<String> = def
+            .config
+            .scopes
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .collect();
+        assert_eq!(scopes, ["active", "named", "recent"]);
+
+        let input: DeriveInput = parse_quote! {
+            #[sqlmodel(order_by = "missing DESC")]
+            struct Hero {
+                #[sqlmodel(primary_key)]
+                id: i64,
+            }
+        };
+        assert!(parse_model(&input).is_err());
+
+        let input: DeriveInput = parse_quote! {
+            #[sqlmodel(scope(active = "a = 1"), scope(active = "b = 1"))]
+            struct Hero {
+                #[sqlmodel(primary_key)]
+                id: i64,
+            }
+        };
+        assert!(parse_model(&input).is_err());
+
+        assert!(parse_order_by("id SIDEWAYS").is_err());
+        assert!(parse_order_by("").is_err());
+    }
+
     // ==================== Hybrid Property Tests ====================
 
     #[test]
@@ -3336,6 +4813,65 @@ mod tests {
         assert_eq!(def.config.title, Some("User Model".to_string()));
     }
 
+    #[test]
+    fn test_generate_dto_specs() {
+        let input: DeriveInput = parse_quote! {
+            #[sqlmodel(generate = "HeroCreate(exclude(id), derive(serde::Deserialize)), HeroUpdate(all_optional), HeroRead")]
+            struct Hero {
+                #[sqlmodel(primary_key)]
+                id: Option<i64>,
+                name: String,
+            }
+        };
+
+        let def = parse_model(&input).unwrap();
+        let dtos = &def.config.generate;
+        assert_eq!(dtos.len(), 3);
+        assert_eq!(dtos[0].name, "HeroCreate");
+        assert_eq!(dtos[0].exclude, vec!["id"]);
+        assert_eq!(dtos[0].derives.len(), 1);
+        assert!(!dtos[0].all_optional);
+        assert_eq!(dtos[1].name, "HeroUpdate");
+        assert!(dtos[1].all_optional);
+        assert!(dtos[2].exclude.is_empty());
+    }
+
+    #[test]
+    fn test_generate_dto_errors() {
+        let unknown_field: DeriveInput = parse_quote! {
+            #[sqlmodel(generate = "HeroCreate(exclude(power))")]
+            struct Hero {
+                id: i64,
+            }
+        };
+        let err = parse_model(&unknown_field).unwrap_err();
+        assert!(err.to_string().contains("unknown field `power`"));
+
+        let unknown_option: DeriveInput = parse_quote! {
+            #[sqlmodel(generate = "HeroCreate(partial)")]
+            struct Hero {
+                id: i64,
+            }
+        };
+        let err = parse_model(&unknown_option).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("unknown generate option `partial`")
+        );
+
+        let duplicate: DeriveInput = parse_quote! {
+            #[sqlmodel(generate = "HeroRead, HeroRead")]
+            struct Hero {
+                id: i64,
+            }
+        };
+        let err = parse_model(&duplicate).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("duplicate generated struct `HeroRead`")
+        );
+    }
+
     #[test]
     fn test_model_config_json_schema_extra() {
         let input: DeriveInput = parse_quote! {
@@ -0,0 +1,310 @@
+//! Implementation of the Repository derive macro.
+//!
+//! Implements `sqlmodel_session::Repository<M>` for a struct holding a
+//! session and generates `find_by_<field>` / `find_one_by_<field>` finders
+//! from `#[repository(find_by(...))]`.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Error, Fields, GenericArgument, Ident, PathArguments, Result, Type};
+
+/// Parsed `#[derive(Repository)]` struct.
+#[derive(Debug)]
+pub struct RepositoryDef {
+    /// The repository struct name.
+    pub name: Ident,
+    /// Generics from the struct.
+    pub generics: syn::Generics,
+    /// The model type from `model = ...`.
+    pub model: Type,
+    /// Model fields to generate finders for.
+    pub find_by: Vec<Ident>,
+    /// The field holding the session.
+    pub session_field: Ident,
+    /// Whether the session field is a `&mut Session<C>` rather than an owned one.
+    pub session_borrowed: bool,
+    /// The connection type `C` of the session.
+    pub conn: Type,
+}
+
+/// Parse a struct with `#[derive(Repository)]`.
+pub fn parse_repository(input: &DeriveInput) -> Result<RepositoryDef> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            input,
+            "Repository can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            &data.fields,
+            "Repository requires a struct with named fields",
+        ));
+    };
+
+    let mut model = None;
+    let mut find_by = Vec::new();
+    for attr in &input.attrs {
+        if !attr.path().is_ident("repository") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("model") {
+                model = Some(meta.value()?.parse::<Type>()?);
+                Ok(())
+            } else if meta.path.is_ident("find_by") {
+                meta.parse_nested_meta(|field| {
+                    let Some(ident) = field.path.get_ident() else {
+                        return Err(Error::new_spanned(&field.path, "expected a field name"));
+                    };
+                    if find_by.contains(ident) {
+                        return Err(Error::new_spanned(
+                            ident,
+                            format!("duplicate finder field `{ident}`"),
+                        ));
+                    }
+                    find_by.push(ident.clone());
+                    Ok(())
+                })
+            } else {
+                Err(Error::new_spanned(
+                    meta.path,
+                    "unknown repository attribute (supported: model, find_by)",
+                ))
+            }
+        })?;
+    }
+    let Some(model) = model else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "Repository requires #[repository(model = ModelType)]",
+        ));
+    };
+
+    // The session field is the one marked `#[repository(session)]`, or the
+    // one named `session`.
+    let mut marked = None;
+    for field in &fields.named {
+        for attr in &field.attrs {
+            if attr.path().is_ident("repository") {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("session") {
+                        marked = Some(field);
+                        Ok(())
+                    } else {
+                        Err(Error::new_spanned(
+                            meta.path,
+                            "unknown repository field attribute (supported: session)",
+                        ))
+                    }
+                })?;
+            }
+        }
+    }
+    let Some(field) = marked.or_else(|| {
+        fields
+            .named
+            .iter()
+            .find(|f| f.ident.as_ref().is_some_and(|i| i == "session"))
+    }) else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "Repository needs a `session` field (or one marked #[repository(session)])",
+        ));
+    };
+
+    let (session_borrowed, session_ty) = match &field.ty {
+        Type::Reference(reference) if reference.mutability.is_some() => (true, &*reference.elem),
+        other => (false, other),
+    };
+    let conn = session_connection_type(session_ty).ok_or_else(|| {
+        Error::new_spanned(
+            &field.ty,
+            "session field must be `Session<C>` or `&mut Session<C>`",
+        )
+    })?;
+
+    Ok(RepositoryDef {
+        name: input.ident.clone(),
+        generics: input.generics.clone(),
+        model,
+        find_by,
+        session_field: field.ident.clone().expect("named field"),
+        session_borrowed,
+        conn,
+    })
+}
+
+/// `C` from a `Session<C>` type path.
+fn session_connection_type(ty: &Type) -> Option<Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Session" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    args.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty.clone()),
+        _ => None,
+    })
+}
+
+/// Generate the `Repository` impl and finder methods.
+pub fn generate_repository_impl(def: &RepositoryDef) -> TokenStream {
+    let name = &def.name;
+    let model = &def.model;
+    let conn = &def.conn;
+    let field = &def.session_field;
+    let (impl_generics, ty_generics, where_clause) = def.generics.split_for_impl();
+
+    let session_expr = if def.session_borrowed {
+        quote! { &mut *self.#field }
+    } else {
+        quote! { &mut self.#field }
+    };
+
+    let finders = def.find_by.iter().map(|field| {
+        let field_name = field.to_string();
+        let find_by = format_ident!("find_by_{}", field);
+        let find_one_by = format_ident!("find_one_by_{}", field);
+        let find_by_doc = format!("Rows whose `{field_name}` equals `value`.");
+        let find_one_by_doc = format!("The first row whose `{field_name}` equals `value`.");
+        quote! {
+            #[doc = #find_by_doc]
+            pub async fn #find_by(
+                &mut self,
+                cx: &sqlmodel_core::Cx,
+                value: impl Into<sqlmodel_core::Value>,
+            ) -> sqlmodel_core::Outcome<Vec<#model>, sqlmodel_core::Error> {
+                let filter = Self::__repository_filter(#field_name, value.into());
+                <Self as sqlmodel_session::Repository<#model>>::find_by(self, cx, filter).await
+            }
+
+            #[doc = #find_one_by_doc]
+            pub async fn #find_one_by(
+                &mut self,
+                cx: &sqlmodel_core::Cx,
+                value: impl Into<sqlmodel_core::Value>,
+            ) -> sqlmodel_core::Outcome<Option<#model>, sqlmodel_core::Error> {
+                let filter = Self::__repository_filter(#field_name, value.into());
+                <Self as sqlmodel_session::Repository<#model>>::find_one_by(self, cx, filter).await
+            }
+        }
+    });
+
+    // Referencing each field rejects unknown finder fields at compile time.
+    let field_checks = def.find_by.iter().map(|field| {
+        quote! { let _ = |m: &#model| { let _ = &m.#field; }; }
+    });
+
+    let finder_impl = if def.find_by.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                #(#finders)*
+
+                fn __repository_filter(field: &str, value: sqlmodel_core::Value) -> sqlmodel_query::Expr {
+                    #(#field_checks)*
+                    let column = <#model as sqlmodel_core::Model>::fields()
+                        .iter()
+                        .find(|f| f.name == field)
+                        .map_or(field, |f| f.column_name);
+                    sqlmodel_query::Expr::col(column).eq(value)
+                }
+            }
+        }
+    };
+
+    quote! {
+        impl #impl_generics sqlmodel_session::Repository<#model> for #name #ty_generics #where_clause {
+            type Conn = #conn;
+
+            fn session(&mut self) -> &mut sqlmodel_session::Session<#conn> {
+                #session_expr
+            }
+        }
+
+        #finder_impl
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_parse_borrowed_session() {
+        let input: DeriveInput = parse_quote! {
+            #[repository(model = User, find_by(email, name))]
+            struct UserRepo<'s, C: Connection> {
+                session: &'s mut Session<C>,
+            }
+        };
+        let def = parse_repository(&input).unwrap();
+        assert_eq!(def.find_by, ["email", "name"]);
+        assert!(def.session_borrowed);
+        assert_eq!(def.session_field, "session");
+        let conn = &def.conn;
+        assert_eq!(quote!(#conn).to_string(), "C");
+    }
+
+    #[test]
+    fn test_parse_marked_owned_session() {
+        let input: DeriveInput = parse_quote! {
+            #[repository(model = crate::models::User)]
+            struct UserRepo {
+                #[repository(session)]
+                db: sqlmodel::Session<PgConnection>,
+                audit: bool,
+            }
+        };
+        let def = parse_repository(&input).unwrap();
+        assert!(def.find_by.is_empty());
+        assert!(!def.session_borrowed);
+        assert_eq!(def.session_field, "db");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let no_model: DeriveInput = parse_quote! {
+            struct UserRepo<C> {
+                session: Session<C>,
+            }
+        };
+        let err = parse_repository(&no_model).unwrap_err();
+        assert!(err.to_string().contains("model = ModelType"));
+
+        let no_session: DeriveInput = parse_quote! {
+            #[repository(model = User)]
+            struct UserRepo<C> {
+                conn: C,
+            }
+        };
+        let err = parse_repository(&no_session).unwrap_err();
+        assert!(err.to_string().contains("needs a `session` field"));
+
+        let wrong_type: DeriveInput = parse_quote! {
+            #[repository(model = User)]
+            struct UserRepo<C> {
+                session: Vec<C>,
+            }
+        };
+        let err = parse_repository(&wrong_type).unwrap_err();
+        assert!(err.to_string().contains("Session<C>"));
+
+        let duplicate: DeriveInput = parse_quote! {
+            #[repository(model = User, find_by(email, email))]
+            struct UserRepo<C> {
+                session: Session<C>,
+            }
+        };
+        let err = parse_repository(&duplicate).unwrap_err();
+        assert!(err.to_string().contains("duplicate finder field `email`"));
+    }
+}
@@ -9,7 +9,7 @@ use proc_macro2::Span;
 use quote::ToTokens;
 use syn::{Error, GenericArgument, PathArguments, Type};
 
-use crate::parse::{FieldDef, ModelDef};
+use crate::parse::{FieldDef, ModelDef, combine_errors};
 
 /// Validate a parsed model definition.
 ///
@@ -20,7 +20,11 @@ pub fn validate_model(model: &ModelDef) -> Result<(), Error> {
 
     // Struct-level validations
     validate_has_fields(model, &mut errors);
-    validate_table_name(&model.table_name, model.name.span(), &mut errors);
+    validate_table_name(
+        &model.table_name,
+        model.attr_spans.get("table", model.name.span()),
+        &mut errors,
+    );
     validate_no_duplicate_columns(model, &mut errors);
 
     // Field-level validations
@@ -33,15 +37,7 @@ pub fn validate_model(model: &ModelDef) -> Result<(), Error> {
     validate_joined_inheritance_parent_field(model, &mut errors);
 
     // Combine all errors
-    if errors.is_empty() {
-        Ok(())
-    } else {
-        let mut combined = errors.remove(0);
-        for err in errors {
-            combined.combine(err);
-        }
-        Err(combined)
-    }
+    combine_errors(errors)
 }
 
 fn validate_joined_inheritance_parent_field(model: &ModelDef, errors: &mut Vec<Error>) {
@@ -63,7 +59,7 @@ fn validate_joined_inheritance_parent_field(model: &ModelDef, errors: &mut Vec<E
     let parent_fields: Vec<_> = model.fields.iter().filter(|f| f.parent).collect();
     if parent_fields.len() != 1 {
         errors.push(Error::new(
-            model.name.span(),
+            model.attr_spans.get("inherits", model.name.span()),
             "joined-table inheritance child models must include exactly one `#[sqlmodel(parent)]` field to embed the parent model",
         ));
     }
@@ -124,7 +120,7 @@ fn validate_no_duplicate_columns(model: &ModelDef, errors: &mut Vec<Error>) {
 
         if !seen_columns.insert(&field.column_name) {
             errors.push(Error::new(
-                field.name.span(),
+                field.attr_spans.get("column", field.name.span()),
                 format!(
                     "duplicate column name '{}'; another field already maps to this column",
                     field.column_name
@@ -139,7 +135,7 @@ fn validate_auto_increment_has_pk(model: &ModelDef, errors: &mut Vec<Error>) {
     for field in &model.fields {
         if field.auto_increment && !field.primary_key {
             errors.push(Error::new(
-                field.name.span(),
+                field.attr_spans.get("auto_increment", field.name.span()),
                 "auto_increment requires primary_key; add #[sqlmodel(primary_key)] to this field",
             ));
         }
@@ -187,7 +183,7 @@ fn validate_skip_conflicts(field: &FieldDef, errors: &mut Vec<Error>) {
 
     if field.skip && field.unique {
         errors.push(Error::new(
-            field.name.span(),
+            field.attr_spans.get("unique", field.name.span()),
             "cannot use both #[sqlmodel(skip)] and #[sqlmodel(unique)] on the same field; \
              skipped fields are excluded from database operations",
         ));
@@ -195,7 +191,7 @@ fn validate_skip_conflicts(field: &FieldDef, errors: &mut Vec<Error>) {
 
     if field.skip && field.foreign_key.is_some() {
         errors.push(Error::new(
-            field.name.span(),
+            field.attr_spans.get("foreign_key", field.name.span()),
             "cannot use both #[sqlmodel(skip)] and #[sqlmodel(foreign_key)] on the same field; \
              skipped fields are excluded from database operations",
         ));
@@ -203,7 +199,7 @@ fn validate_skip_conflicts(field: &FieldDef, errors: &mut Vec<Error>) {
 
     if field.skip && field.index.is_some() {
         errors.push(Error::new(
-            field.name.span(),
+            field.attr_spans.get("index", field.name.span()),
             "cannot use both #[sqlmodel(skip)] and #[sqlmodel(index)] on the same field; \
              skipped fields are excluded from database operations",
         ));
@@ -38,6 +38,8 @@ pub struct ValidateDef {
     pub fields: Vec<ValidateFieldDef>,
     /// Model-level validators.
     pub model_validators: Vec<ModelValidator>,
+    /// Struct-level schema validators returning field-keyed errors.
+    pub schema_validators: Vec<String>,
     /// Generics from the struct.
     pub generics: syn::Generics,
 }
@@ -73,6 +75,10 @@ pub struct ValidateFieldDef {
     pub unique_items: bool,
     /// Whether to validate as a credit card number (Luhn check).
     pub credit_card: bool,
+    /// Field that makes this one required when set.
+    pub required_if: Option<Ident>,
+    /// Async custom validation function name.
+    pub custom_async: Option<String>,
 }
 
 /// Parse a `DeriveInput` into a `ValidateDef`.
@@ -80,8 +86,8 @@ pub fn parse_validate(input: &DeriveInput) -> Result<ValidateDef> {
     let name = input.ident.clone();
     let generics = input.generics.clone();
 
-    // Parse struct-level attributes for model validators
-    let model_validators = parse_model_validators(&input.attrs)?;
+    // Parse struct-level attributes for model and schema validators
+    let (model_validators, schema_validators) = parse_model_validators(&input.attrs)?;
 
     let fields = match &input.data {
         Data::Struct(data) => parse_validate_fields(&data.fields)?,
@@ -103,13 +109,16 @@ pub fn parse_validate(input: &DeriveInput) -> Result<ValidateDef> {
         name,
         fields,
         model_validators,
+        schema_validators,
         generics,
     })
 }
 
-/// Parse struct-level `#[validate(...)]` attributes for model validators.
-fn parse_model_validators(attrs: &[syn::Attribute]) -> Result<Vec<ModelValidator>> {
+/// Parse struct-level `#[validate(...)]` attributes for model and schema
+/// validators.
+fn parse_model_validators(attrs: &[syn::Attribute]) -> Result<(Vec<ModelValidator>, Vec<String>)> {
     let mut validators = Vec::new();
+    let mut schema_validators = Vec::new();
 
     for attr in attrs {
         if !attr.path().is_ident("validate") {
@@ -195,16 +204,28 @@ fn parse_model_validators(attrs: &[syn::Attribute]) -> Result<Vec<ModelValidator
                     ));
                 }
                 Ok(())
+            } else if meta.path.is_ident("schema") {
+                // Parse: #[validate(schema = "fn_name")]
+                let value: Lit = meta.value()?.parse()?;
+                if let Lit::Str(lit_str) = value {
+                    schema_validators.push(lit_str.value());
+                    Ok(())
+                } else {
+                    Err(Error::new_spanned(
+                        value,
+                        "expected string literal for schema validator function name",
+                    ))
+                }
             } else {
                 Err(Error::new_spanned(
                     meta.path,
-                    "unknown struct-level validate attribute, expected 'model'",
+                    "unknown struct-level validate attribute, expected 'model' or 'schema'",
                 ))
             }
         })?;
     }
 
-    Ok(validators)
+    Ok((validators, schema_validators))
 }
 
 /// Parse all fields from a struct for validation.
@@ -241,6 +262,8 @@ fn parse_validate_field(field: &Field) -> Result<ValidateFieldDef> {
     let mut max_items = None;
     let mut unique_items = false;
     let mut credit_card = false;
+    let mut required_if = None;
+    let mut custom_async = None;
 
     // Parse #[validate(...)] attributes
     for attr in &field.attrs {
@@ -283,6 +306,16 @@ fn parse_validate_field(field: &Field) -> Result<ValidateFieldDef> {
                 }
             } else if path.is_ident("required") {
                 required = true;
+            } else if path.is_ident("required_if") {
+                let value: Lit = meta.value()?.parse()?;
+                if let Lit::Str(lit_str) = value {
+                    required_if = Some(lit_str.parse::<Ident>()?);
+                } else {
+                    return Err(Error::new_spanned(
+                        value,
+                        "expected string literal for required_if field name",
+                    ));
+                }
             } else if path.is_ident("custom") {
                 let value: Lit = meta.value()?.parse()?;
                 if let Lit::Str(lit_str) = value {
@@ -293,6 +326,16 @@ fn parse_validate_field(field: &Field) -> Result<ValidateFieldDef> {
                         "expected string literal for custom function name",
                     ));
                 }
+            } else if path.is_ident("custom_async") {
+                let value: Lit = meta.value()?.parse()?;
+                if let Lit::Str(lit_str) = value {
+                    custom_async = Some(lit_str.value());
+                } else {
+                    return Err(Error::new_spanned(
+                        value,
+                        "expected string literal for custom_async function name",
+                    ));
+                }
             } else if path.is_ident("email") {
                 // Email validation is a common pattern
                 pattern = Some(r"^[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}$".to_string());
@@ -364,8 +407,9 @@ fn parse_validate_field(field: &Field) -> Result<ValidateFieldDef> {
                     format!(
                         "unknown validate attribute `{attr_name}`. \
                          Valid attributes are: min, max, min_length, max_length, pattern, \
-                         required, custom, email, url, uuid, ipv4, ipv6, mac_address, slug, \
-                         hex_color, phone, credit_card, multiple_of, min_items, max_items, unique_items"
+                         required, required_if, custom, custom_async, email, url, uuid, ipv4, \
+                         ipv6, mac_address, slug, hex_color, phone, credit_card, multiple_of, \
+                         min_items, max_items, unique_items"
                     ),
                 ));
             }
@@ -400,6 +444,8 @@ fn parse_validate_field(field: &Field) -> Result<ValidateFieldDef> {
         max_items,
         unique_items,
         credit_card,
+        required_if,
+        custom_async,
     })
 }
 
@@ -497,8 +543,36 @@ pub fn generate_validate_impl(def: &ValidateDef) -> TokenStream {
         })
         .collect();
 
+    // Generate schema validator calls (merge their field-keyed errors)
+    let schema_validators: Vec<TokenStream> = def
+        .schema_validators
+        .iter()
+        .map(|function| {
+            let fn_name = syn::Ident::new(function, proc_macro2::Span::call_site());
+            quote! {
+                if let Err(schema_errors) = self.#fn_name() {
+                    errors.merge(schema_errors);
+                }
+            }
+        })
+        .collect();
+
+    let validate_async = generate_validate_async(def);
+
+    // The trait impl lets the session run these rules on flush
+    let validate_trait_impl = quote! {
+        impl #impl_generics sqlmodel_core::Validate for #name #ty_generics #where_clause {
+            fn validate(&self) -> std::result::Result<(), sqlmodel_core::ValidationError> {
+                Self::validate(self)
+            }
+        }
+    };
+
     // If no validations and no model validators, generate a trivial impl
-    if field_validations.is_empty() && def.model_validators.is_empty() {
+    if field_validations.is_empty()
+        && def.model_validators.is_empty()
+        && def.schema_validators.is_empty()
+    {
         return quote! {
             impl #impl_generics #name #ty_generics #where_clause {
                 /// Validate this model's fields.
@@ -508,7 +582,11 @@ pub fn generate_validate_impl(def: &ValidateDef) -> TokenStream {
                 pub fn validate(&self) -> std::result::Result<(), sqlmodel_core::ValidationError> {
                     Ok(())
                 }
+
+                #validate_async
             }
+
+            #validate_trait_impl
         };
     }
 
@@ -523,6 +601,7 @@ pub fn generate_validate_impl(def: &ValidateDef) -> TokenStream {
             /// 1. Model validators with mode="before"
             /// 2. Field-level validations
             /// 3. Model validators with mode="after" (default)
+            /// 4. Schema validators
             pub fn validate(&self) -> std::result::Result<(), sqlmodel_core::ValidationError> {
                 let mut errors = sqlmodel_core::ValidationError::new();
 
@@ -535,8 +614,85 @@ pub fn generate_validate_impl(def: &ValidateDef) -> TokenStream {
                 // 3. After validators (run after field validation, default mode)
                 #(#after_validators)*
 
+                // 4. Schema validators (cross-field, errors keyed by field)
+                #(#schema_validators)*
+
                 errors.into_result()
             }
+
+            #validate_async
+        }
+
+        #validate_trait_impl
+    }
+}
+
+/// Generate `validate_async()` when any field has a `custom_async` validator.
+fn generate_validate_async(def: &ValidateDef) -> TokenStream {
+    let checks: Vec<TokenStream> = def
+        .fields
+        .iter()
+        .filter_map(|field| {
+            let custom_async = field.custom_async.as_ref()?;
+            let field_name = &field.name;
+            let field_name_str = field_name.to_string();
+            let fn_name = syn::Ident::new(custom_async, field_name.span());
+            let check = quote! {
+                match self.#fn_name(cx, conn, value).await {
+                    sqlmodel_core::Outcome::Ok(Ok(())) => {}
+                    sqlmodel_core::Outcome::Ok(Err(msg)) => errors.add_custom(#field_name_str, msg),
+                    sqlmodel_core::Outcome::Err(e) => return sqlmodel_core::Outcome::Err(e),
+                    sqlmodel_core::Outcome::Cancelled(r) => {
+                        return sqlmodel_core::Outcome::Cancelled(r);
+                    }
+                    sqlmodel_core::Outcome::Panicked(p) => {
+                        return sqlmodel_core::Outcome::Panicked(p);
+                    }
+                }
+            };
+            Some(if is_option_type(&field.ty) {
+                quote! {
+                    if let Some(ref value) = self.#field_name {
+                        #check
+                    }
+                }
+            } else {
+                quote! {
+                    {
+                        let value = &self.#field_name;
+                        #check
+                    }
+                }
+            })
+        })
+        .collect();
+
+    if checks.is_empty() {
+        return quote! {};
+    }
+
+    quote! {
+        /// Run `validate()` and then the async (`custom_async`) validators,
+        /// which may query `conn`.
+        ///
+        /// Returns `Ok(Err(errors))` with every failure grouped by field path;
+        /// `Err` is reserved for database errors raised by a validator.
+        pub async fn validate_async<C: sqlmodel_core::Connection>(
+            &self,
+            cx: &sqlmodel_core::Cx,
+            conn: &C,
+        ) -> sqlmodel_core::Outcome<
+            std::result::Result<(), sqlmodel_core::ValidationErrors>,
+            sqlmodel_core::Error,
+        > {
+            let mut errors = match self.validate() {
+                Ok(()) => sqlmodel_core::ValidationError::new(),
+                Err(errors) => errors,
+            };
+
+            #(#checks)*
+
+            sqlmodel_core::Outcome::Ok(sqlmodel_core::ValidationErrors::from(errors).into_result())
         }
     }
 }
@@ -555,6 +711,7 @@ fn has_validation(field: &ValidateFieldDef) -> bool {
         || field.max_items.is_some()
         || field.unique_items
         || field.credit_card
+        || field.required_if.is_some()
 }
 
 /// Generate validation code for a single field.
@@ -574,6 +731,18 @@ fn generate_field_validation(field: &ValidateFieldDef) -> TokenStream {
         });
     }
 
+    // Conditional required check: this field must be set when `other` is
+    if let Some(ref other) = field.required_if {
+        let other_str = other.to_string();
+        checks.push(quote! {
+            if sqlmodel_core::validate::Presence::is_present(&self.#other)
+                && !sqlmodel_core::validate::Presence::is_present(&self.#field_name)
+            {
+                errors.add_required_if(#field_name_str, #other_str);
+            }
+        });
+    }
+
     // Min value check
     if let Some(min) = field.min {
         if is_optional {
@@ -851,6 +1020,8 @@ mod tests {
             max_items: None,
             unique_items: false,
             credit_card: false,
+            required_if: None,
+            custom_async: None,
         };
         assert!(has_validation(&field));
 
@@ -869,6 +1040,8 @@ mod tests {
             max_items: None,
             unique_items: false,
             credit_card: false,
+            required_if: None,
+            custom_async: None,
         };
         assert!(!has_validation(&field));
     }
@@ -982,6 +1155,53 @@ mod tests {
         assert!(def.fields[1].min_length.is_some());
     }
 
+    #[test]
+    fn test_parse_schema_required_if_and_custom_async() {
+        let input: syn::DeriveInput = parse_quote! {
+            #[validate(schema = "check_dates")]
+            struct Event {
+                start_date: Option<String>,
+                #[validate(required_if = "start_date")]
+                end_date: Option<String>,
+                #[validate(custom_async = "slug_is_unique")]
+                slug: String,
+            }
+        };
+
+        let def = parse_validate(&input).unwrap();
+        assert_eq!(def.schema_validators, ["check_dates"]);
+        assert!(def.model_validators.is_empty());
+        assert_eq!(def.fields[1].required_if.as_ref().unwrap(), "start_date");
+        assert!(has_validation(&def.fields[1]));
+        assert_eq!(
+            def.fields[2].custom_async.as_deref(),
+            Some("slug_is_unique")
+        );
+        assert!(!has_validation(&def.fields[2]));
+
+        let generated = generate_validate_impl(&def).to_string();
+        assert!(generated.contains("add_required_if"));
+        assert!(generated.contains("check_dates"));
+        assert!(generated.contains("validate_async"));
+    }
+
+    #[test]
+    fn test_validate_async_only_with_async_validators() {
+        let input: syn::DeriveInput = parse_quote! {
+            struct Plain {
+                #[validate(min_length = 1)]
+                name: String,
+            }
+        };
+
+        let def = parse_validate(&input).unwrap();
+        assert!(
+            !generate_validate_impl(&def)
+                .to_string()
+                .contains("validate_async")
+        );
+    }
+
     // ========================================================================
     // Multiple Of Validation Tests
     // ========================================================================
@@ -1063,6 +1283,8 @@ mod tests {
             max_items: None,
             unique_items: false,
             credit_card: false,
+            required_if: None,
+            custom_async: None,
         };
         assert!(has_validation(&field_with_multiple_of));
 
@@ -1081,6 +1303,8 @@ mod tests {
             max_items: None,
             unique_items: false,
             credit_card: false,
+            required_if: None,
+            custom_async: None,
         };
         assert!(!has_validation(&field_without_validation));
     }
@@ -1164,6 +1388,8 @@ mod tests {
             max_items: None,
             unique_items: false,
             credit_card: false,
+            required_if: None,
+            custom_async: None,
         };
         assert!(has_validation(&field_with_min_items));
 
@@ -1182,6 +1408,8 @@ mod tests {
             max_items: None,
             unique_items: true,
             credit_card: false,
+            required_if: None,
+            custom_async: None,
         };
         assert!(has_validation(&field_with_unique));
     }
@@ -1368,6 +1596,8 @@ mod tests {
             max_items: None,
             unique_items: false,
             credit_card: true,
+            required_if: None,
+            custom_async: None,
         };
         assert!(has_validation(&field_with_credit_card));
     }
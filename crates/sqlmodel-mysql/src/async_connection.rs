@@ -19,7 +19,10 @@ use asupersync::net::TcpStream;
 use asupersync::sync::Mutex;
 use asupersync::{Cx, Outcome};
 
-use sqlmodel_core::connection::{Connection, IsolationLevel, PreparedStatement, TransactionOps};
+use sqlmodel_core::connection::{
+    Connection, IsolationLevel, PreparedStatement, QueryResult, ResultColumn, TransactionOps,
+};
+use sqlmodel_core::credentials::CredentialsProvider;
 use sqlmodel_core::error::{
     ConnectionError, ConnectionErrorKind, ProtocolError, QueryError, QueryErrorKind,
 };
@@ -301,17 +304,21 @@ impl MySqlAsyncConnection {
     pub async fn connect(_cx: &Cx, config: MySqlConfig) -> Outcome<Self, Error> {
         // Use async TCP connect
         let addr = config.socket_addr();
-        let socket_addr = match addr.parse() {
-            Ok(a) => a,
-            Err(e) => {
-                return Outcome::Err(Error::Connection(ConnectionError {
-                    kind: ConnectionErrorKind::Connect,
-                    message: format!("Invalid socket address: {}", e),
-                    source: None,
-                }));
-            }
+        let socket_addrs = match config.socket_addrs() {
+            Ok(addrs) => addrs,
+            Err(e) => return Outcome::Err(e),
         };
-        let stream = match TcpStream::connect_timeout(socket_addr, config.connect_timeout).await {
+        let mut connected = Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "host resolved to no addresses",
+        ));
+        for socket_addr in socket_addrs {
+            connected = TcpStream::connect_timeout(socket_addr, config.connect_timeout).await;
+            if connected.is_ok() {
+                break;
+            }
+        }
+        let stream = match connected {
             Ok(s) => s,
             Err(e) => {
                 let kind = if e.kind() == io::ErrorKind::ConnectionRefused {
@@ -372,6 +379,23 @@ impl MySqlAsyncConnection {
         Outcome::Ok(conn)
     }
 
+    /// Fetch credentials from `provider`, then [`connect`](Self::connect).
+    ///
+    /// Use this in a pool's connection factory so every new connection
+    /// authenticates with current credentials (e.g. a fresh IAM token).
+    pub async fn connect_with_credentials(
+        cx: &Cx,
+        config: MySqlConfig,
+        provider: &dyn CredentialsProvider,
+    ) -> Outcome<Self, Error> {
+        match provider.fetch(cx).await {
+            Outcome::Ok(credentials) => Self::connect(cx, config.credentials(credentials)).await,
+            Outcome::Err(e) => Outcome::Err(e),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
     /// Get the current connection state.
     pub fn state(&self) -> ConnectionState {
         self.state
@@ -398,6 +422,70 @@ impl MySqlAsyncConnection {
         self.connection_id
     }
 
+    /// A token that kills whatever statement this connection is running,
+    /// usable from another task.
+    pub fn cancel_token(&self) -> MySqlCancelToken {
+        MySqlCancelToken {
+            config: self.config.clone(),
+            connection_id: self.connection_id,
+        }
+    }
+
+    /// Kill the statement just sent. Its response is left unread, so the
+    /// connection is unusable afterwards.
+    async fn cancel_in_flight(&mut self) {
+        let _ = self.cancel_token().send().await;
+        self.state = ConnectionState::Error;
+    }
+
+    /// Read the next packet of a statement's response, killing the statement
+    /// if `cx` is cancelled while waiting for it.
+    async fn read_response_packet(&mut self, cx: &Cx) -> Outcome<(Vec<u8>, u8), Error> {
+        let read = {
+            let mut packet = std::pin::pin!(self.read_packet_async());
+            std::future::poll_fn(|task| {
+                if let Some(reason) = cx.cancel_reason() {
+                    return std::task::Poll::Ready(Err(reason));
+                }
+                packet.as_mut().poll(task).map(Ok)
+            })
+            .await
+        };
+        match read {
+            Ok(packet) => packet,
+            Err(reason) => {
+                self.cancel_in_flight().await;
+                Outcome::Cancelled(reason)
+            }
+        }
+    }
+
+    /// Run `KILL QUERY connection_id` without watching for cancellation.
+    async fn kill_query(&mut self, connection_id: u32) -> Outcome<(), Error> {
+        self.sequence_id = 0;
+        let mut writer = PacketWriter::new();
+        writer.write_u8(Command::Query as u8);
+        writer.write_bytes(format!("KILL QUERY {connection_id}").as_bytes());
+        if let Outcome::Err(e) = self.write_packet_async(writer.as_bytes()).await {
+            return Outcome::Err(e);
+        }
+
+        let (payload, _) = match self.read_packet_async().await {
+            Outcome::Ok(p) => p,
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        };
+        if payload.first() == Some(&0xFF) {
+            let mut reader = PacketReader::new(&payload);
+            return match reader.parse_err_packet() {
+                Some(err) => Outcome::Err(query_error(&err)),
+                None => Outcome::Err(protocol_error("Invalid error packet")),
+            };
+        }
+        Outcome::Ok(())
+    }
+
     /// Get the server version.
     pub fn server_version(&self) -> Option<&str> {
         self.server_caps
@@ -1225,10 +1313,25 @@ impl MySqlAsyncConnection {
     /// Execute a text protocol query asynchronously.
     pub async fn query_async(
         &mut self,
-        _cx: &Cx,
+        cx: &Cx,
         sql: &str,
         params: &[Value],
     ) -> Outcome<Vec<Row>, Error> {
+        match self.query_raw_async(cx, sql, params).await {
+            Outcome::Ok(result) => Outcome::Ok(result.rows),
+            Outcome::Err(e) => Outcome::Err(e),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
+    /// Execute a text protocol query and return its rows with column metadata.
+    pub async fn query_raw_async(
+        &mut self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> Outcome<QueryResult, Error> {
         let sql = interpolate_params(sql, params);
         if !self.is_ready() && self.state != ConnectionState::InTransaction {
             return Outcome::Err(connection_error("Connection not ready for queries"));
@@ -1247,7 +1350,7 @@ impl MySqlAsyncConnection {
         }
 
         // Read response
-        let (payload, _) = match self.read_packet_async().await {
+        let (payload, _) = match self.read_response_packet(cx).await {
             Outcome::Ok(p) => p,
             Outcome::Err(e) => return Outcome::Err(e),
             Outcome::Cancelled(r) => return Outcome::Cancelled(r),
@@ -1277,7 +1380,7 @@ impl MySqlAsyncConnection {
                 } else {
                     ConnectionState::Ready
                 };
-                Outcome::Ok(vec![])
+                Outcome::Ok(QueryResult::default())
             }
             PacketType::Error => {
                 self.state = ConnectionState::Ready;
@@ -1291,12 +1394,16 @@ impl MySqlAsyncConnection {
                 self.state = ConnectionState::Ready;
                 Outcome::Err(query_error_msg("LOCAL INFILE not supported"))
             }
-            _ => self.read_result_set_async(&payload).await,
+            _ => self.read_result_set_async(cx, &payload).await,
         }
     }
 
     /// Read a result set asynchronously.
-    async fn read_result_set_async(&mut self, first_packet: &[u8]) -> Outcome<Vec<Row>, Error> {
+    async fn read_result_set_async(
+        &mut self,
+        cx: &Cx,
+        first_packet: &[u8],
+    ) -> Outcome<QueryResult, Error> {
         let mut reader = PacketReader::new(first_packet);
         #[allow(clippy::cast_possible_truncation)] // Column count fits in usize
         let Some(column_count) = reader.read_lenenc_int().map(|c| c as usize) else {
@@ -1306,7 +1413,7 @@ impl MySqlAsyncConnection {
         // Read column definitions
         let mut columns = Vec::with_capacity(column_count);
         for _ in 0..column_count {
-            let (payload, _) = match self.read_packet_async().await {
+            let (payload, _) = match self.read_response_packet(cx).await {
                 Outcome::Ok(p) => p,
                 Outcome::Err(e) => return Outcome::Err(e),
                 Outcome::Cancelled(r) => return Outcome::Cancelled(r),
@@ -1321,7 +1428,7 @@ impl MySqlAsyncConnection {
         // Check for EOF packet
         let server_caps = self.server_caps.as_ref().map_or(0, |c| c.capabilities);
         if server_caps & capabilities::CLIENT_DEPRECATE_EOF == 0 {
-            let (payload, _) = match self.read_packet_async().await {
+            let (payload, _) = match self.read_response_packet(cx).await {
                 Outcome::Ok(p) => p,
                 Outcome::Err(e) => return Outcome::Err(e),
                 Outcome::Cancelled(r) => return Outcome::Cancelled(r),
@@ -1335,7 +1442,7 @@ impl MySqlAsyncConnection {
         // Read rows until EOF or OK
         let mut rows = Vec::new();
         loop {
-            let (payload, _) = match self.read_packet_async().await {
+            let (payload, _) = match self.read_response_packet(cx).await {
                 Outcome::Ok(p) => p,
                 Outcome::Err(e) => return Outcome::Err(e),
                 Outcome::Cancelled(r) => return Outcome::Cancelled(r),
@@ -1385,7 +1492,10 @@ impl MySqlAsyncConnection {
                 ConnectionState::Ready
             };
 
-        Outcome::Ok(rows)
+        Outcome::Ok(QueryResult {
+            columns: columns.iter().map(result_column).collect(),
+            rows,
+        })
     }
 
     /// Parse a column definition packet.
@@ -1608,7 +1718,7 @@ impl MySqlAsyncConnection {
     /// Execute a prepared statement and return result rows (binary protocol).
     pub async fn query_prepared_async(
         &mut self,
-        _cx: &Cx,
+        cx: &Cx,
         stmt: &PreparedStatement,
         params: &[Value],
     ) -> Outcome<Vec<Row>, Error> {
@@ -1649,7 +1759,7 @@ impl MySqlAsyncConnection {
         }
 
         // Read response
-        let (payload, _) = match self.read_packet_async().await {
+        let (payload, _) = match self.read_response_packet(cx).await {
             Outcome::Ok(p) => p,
             Outcome::Err(e) => return Outcome::Err(e),
             Outcome::Cancelled(r) => return Outcome::Cancelled(r),
@@ -1685,7 +1795,7 @@ impl MySqlAsyncConnection {
             }
             _ => {
                 // Result set - read binary protocol rows
-                self.read_binary_result_set_async(&payload, &meta.columns)
+                self.read_binary_result_set_async(cx, &payload, &meta.columns)
                     .await
             }
         }
@@ -1721,6 +1831,7 @@ impl MySqlAsyncConnection {
     /// Read a binary protocol result set.
     async fn read_binary_result_set_async(
         &mut self,
+        cx: &Cx,
         first_packet: &[u8],
         columns: &[ColumnDef],
     ) -> Outcome<Vec<Row>, Error> {
@@ -1735,7 +1846,7 @@ impl MySqlAsyncConnection {
         // But server sends them again in binary result set - we need to read them
         let mut result_columns = Vec::with_capacity(column_count);
         for _ in 0..column_count {
-            let (payload, _) = match self.read_packet_async().await {
+            let (payload, _) = match self.read_response_packet(cx).await {
                 Outcome::Ok(p) => p,
                 Outcome::Err(e) => return Outcome::Err(e),
                 Outcome::Cancelled(r) => return Outcome::Cancelled(r),
@@ -1757,7 +1868,7 @@ impl MySqlAsyncConnection {
         // Check for EOF packet
         let server_caps = self.server_caps.as_ref().map_or(0, |c| c.capabilities);
         if server_caps & capabilities::CLIENT_DEPRECATE_EOF == 0 {
-            let (payload, _) = match self.read_packet_async().await {
+            let (payload, _) = match self.read_response_packet(cx).await {
                 Outcome::Ok(p) => p,
                 Outcome::Err(e) => return Outcome::Err(e),
                 Outcome::Cancelled(r) => return Outcome::Cancelled(r),
@@ -1771,7 +1882,7 @@ impl MySqlAsyncConnection {
         // Read binary rows until EOF or OK
         let mut rows = Vec::new();
         loop {
-            let (payload, _) = match self.read_packet_async().await {
+            let (payload, _) = match self.read_response_packet(cx).await {
                 Outcome::Ok(p) => p,
                 Outcome::Err(e) => return Outcome::Err(e),
                 Outcome::Cancelled(r) => return Outcome::Cancelled(r),
@@ -2007,6 +2118,23 @@ fn auth_error(msg: impl Into<String>) -> Error {
     })
 }
 
+/// Column metadata from a column definition packet.
+fn result_column(col: &ColumnDef) -> ResultColumn {
+    let table = if col.org_table.is_empty() {
+        &col.table
+    } else {
+        &col.org_table
+    };
+    ResultColumn {
+        name: col.name.clone(),
+        sql_type: Some(col.column_type.name().to_string()),
+        type_id: Some(u32::from(col.column_type as u8)),
+        table_oid: None,
+        table: (!table.is_empty()).then(|| table.clone()),
+        nullable: Some(!col.is_not_null()),
+    }
+}
+
 fn connection_error(msg: impl Into<String>) -> Error {
     Error::Connection(ConnectionError {
         kind: ConnectionErrorKind::Connect,
@@ -2034,13 +2162,13 @@ fn mysql_server_uses_oaep(server_version: &str) -> bool {
 }
 
 fn query_error(err: &ErrPacket) -> Error {
-    let kind = if err.is_duplicate_key() || err.is_foreign_key_violation() {
+    let kind = if err.is_constraint_violation() {
         QueryErrorKind::Constraint
     } else {
         QueryErrorKind::Syntax
     };
 
-    Error::Query(QueryError {
+    Error::from_query_error(QueryError {
         kind,
         message: err.error_message.clone(),
         sqlstate: Some(err.sql_state.clone()),
@@ -2102,6 +2230,50 @@ fn validate_savepoint_name(name: &str) -> Result<(), Error> {
     Ok(())
 }
 
+/// Kills the statement running on a [`MySqlAsyncConnection`].
+///
+/// Runs `KILL QUERY <connection id>` over a separate connection, so the
+/// server stops the statement and frees its resources. The running
+/// statement then fails with error 1317 (query execution was interrupted).
+#[derive(Clone)]
+pub struct MySqlCancelToken {
+    config: MySqlConfig,
+    connection_id: u32,
+}
+
+impl MySqlCancelToken {
+    /// Kill the statement currently running on the connection, if any.
+    ///
+    /// Works from a task that is itself being cancelled: the kill runs on
+    /// its own context, not `cx`.
+    pub async fn cancel(&self, _cx: &Cx) -> Outcome<(), Error> {
+        self.send().await
+    }
+
+    async fn send(&self) -> Outcome<(), Error> {
+        let cx = Cx::for_request();
+        let mut conn = match MySqlAsyncConnection::connect(&cx, self.config.clone()).await {
+            Outcome::Ok(conn) => conn,
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        };
+        let killed = conn.kill_query(self.connection_id).await;
+        let _ = conn.close_async(&cx).await;
+        killed
+    }
+}
+
+impl std::fmt::Debug for MySqlCancelToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MySqlCancelToken")
+            .field("connection_id", &self.connection_id)
+            .field("host", &self.config.host)
+            .field("port", &self.config.port)
+            .finish_non_exhaustive()
+    }
+}
+
 // === Shared connection wrapper ===
 
 /// A thread-safe, shared MySQL connection with interior mutability.
@@ -2120,12 +2292,14 @@ fn validate_savepoint_name(name: &str) -> Result<(), Error> {
 /// ```
 pub struct SharedMySqlConnection {
     inner: Arc<Mutex<MySqlAsyncConnection>>,
+    cancel: MySqlCancelToken,
 }
 
 impl SharedMySqlConnection {
     /// Create a new shared connection from a raw connection.
     pub fn new(conn: MySqlAsyncConnection) -> Self {
         Self {
+            cancel: conn.cancel_token(),
             inner: Arc::new(Mutex::new(conn)),
         }
     }
@@ -2144,12 +2318,19 @@ impl SharedMySqlConnection {
     pub fn inner(&self) -> &Arc<Mutex<MySqlAsyncConnection>> {
         &self.inner
     }
+
+    /// A token that kills the running statement without waiting for the
+    /// connection lock.
+    pub fn cancel_token(&self) -> &MySqlCancelToken {
+        &self.cancel
+    }
 }
 
 impl Clone for SharedMySqlConnection {
     fn clone(&self) -> Self {
         Self {
             inner: Arc::clone(&self.inner),
+            cancel: self.cancel.clone(),
         }
     }
 }
@@ -2256,6 +2437,23 @@ impl Connection for SharedMySqlConnection {
         }
     }
 
+    fn query_raw(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<QueryResult, Error>> + Send {
+        let inner = Arc::clone(&self.inner);
+        let sql = sql.to_string();
+        let params = params.to_vec();
+        async move {
+            let Ok(mut guard) = inner.lock(cx).await else {
+                return Outcome::Err(connection_error("Failed to acquire connection lock"));
+            };
+            guard.query_raw_async(cx, &sql, &params).await
+        }
+    }
+
     fn query_one(
         &self,
         cx: &Cx,
@@ -2660,4 +2858,27 @@ mod tests {
         let long_name = "a".repeat(65);
         assert!(validate_savepoint_name(&long_name).is_err());
     }
+
+    #[test]
+    fn test_result_column_from_column_def() {
+        let col = ColumnDef {
+            catalog: "def".to_string(),
+            schema: "shop".to_string(),
+            table: "o".to_string(),
+            org_table: "orders".to_string(),
+            name: "total".to_string(),
+            org_name: "total".to_string(),
+            charset: 63,
+            column_length: 10,
+            column_type: FieldType::NewDecimal,
+            flags: crate::types::column_flags::NOT_NULL,
+            decimals: 2,
+        };
+        let meta = result_column(&col);
+        assert_eq!(meta.name, "total");
+        assert_eq!(meta.sql_type.as_deref(), Some(FieldType::NewDecimal.name()));
+        assert_eq!(meta.type_id, Some(0xF6));
+        assert_eq!(meta.table.as_deref(), Some("orders"));
+        assert_eq!(meta.nullable, Some(false));
+    }
 }
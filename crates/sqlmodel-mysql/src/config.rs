@@ -4,9 +4,14 @@
 //! including authentication, SSL, and connection options.
 
 use std::collections::HashMap;
+use std::net::{SocketAddr, ToSocketAddrs};
 use std::path::PathBuf;
 use std::time::Duration;
 
+use sqlmodel_core::Error;
+use sqlmodel_core::credentials::Credentials;
+use sqlmodel_core::error::{ConnectionError, ConnectionErrorKind};
+
 /// TLS/SSL configuration for MySQL connections.
 ///
 /// This struct holds the certificate and key paths for TLS connections.
@@ -193,6 +198,13 @@ impl MySqlConfig {
         self
     }
 
+    /// Use `credentials` for the user and password.
+    pub fn credentials(mut self, credentials: Credentials) -> Self {
+        self.user = credentials.user;
+        self.password = credentials.password;
+        self
+    }
+
     /// Internal helper for auth code: return configured password as `&str` (or empty).
     ///
     /// This keeps password handling centralized in config so callers don't need
@@ -293,6 +305,22 @@ impl MySqlConfig {
         format!("{}:{}", self.host, self.port)
     }
 
+    /// Resolve the host to socket addresses.
+    ///
+    /// Resolution happens on every call, so each new connection follows
+    /// DNS changes (e.g. after a failover or `Pool::reload_config`).
+    #[allow(clippy::result_large_err)]
+    pub fn socket_addrs(&self) -> Result<Vec<SocketAddr>, Error> {
+        match (self.host.as_str(), self.port).to_socket_addrs() {
+            Ok(addrs) => Ok(addrs.collect()),
+            Err(e) => Err(Error::Connection(ConnectionError {
+                kind: ConnectionErrorKind::DnsResolution,
+                message: format!("Failed to resolve {}: {}", self.host, e),
+                source: Some(Box::new(e)),
+            })),
+        }
+    }
+
     /// Build capability flags based on configuration.
     pub fn capability_flags(&self) -> u32 {
         use crate::protocol::capabilities::{
@@ -361,6 +389,12 @@ mod tests {
     fn test_socket_addr() {
         let config = MySqlConfig::new().host("db.example.com").port(3307);
         assert_eq!(config.socket_addr(), "db.example.com:3307");
+
+        let local = MySqlConfig::new().host("127.0.0.1").port(3307);
+        assert_eq!(
+            local.socket_addrs().unwrap(),
+            vec!["127.0.0.1:3307".parse::<SocketAddr>().unwrap()]
+        );
     }
 
     #[test]
@@ -138,17 +138,17 @@ impl MySqlConnection {
     #[allow(clippy::result_large_err)]
     pub fn connect(config: MySqlConfig) -> Result<Self, Error> {
         // 1. TCP connection with timeout
-        let stream = TcpStream::connect_timeout(
-            &config.socket_addr().parse().map_err(|e| {
-                Error::Connection(ConnectionError {
-                    kind: ConnectionErrorKind::Connect,
-                    message: format!("Invalid socket address: {}", e),
-                    source: None,
-                })
-            })?,
-            config.connect_timeout,
-        )
-        .map_err(|e| {
+        let mut connected = Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "host resolved to no addresses",
+        ));
+        for socket_addr in config.socket_addrs()? {
+            connected = TcpStream::connect_timeout(&socket_addr, config.connect_timeout);
+            if connected.is_ok() {
+                break;
+            }
+        }
+        let stream = connected.map_err(|e| {
             let kind = if e.kind() == std::io::ErrorKind::ConnectionRefused {
                 ConnectionErrorKind::Refused
             } else {
@@ -1344,13 +1344,13 @@ fn mysql_server_uses_oaep(server_version: &str) -> bool {
 }
 
 fn query_error(err: &ErrPacket) -> Error {
-    let kind = if err.is_duplicate_key() || err.is_foreign_key_violation() {
+    let kind = if err.is_constraint_violation() {
         QueryErrorKind::Constraint
     } else {
         QueryErrorKind::Syntax
     };
 
-    Error::Query(QueryError {
+    Error::from_query_error(QueryError {
         kind,
         message: err.error_message.clone(),
         sqlstate: Some(err.sql_state.clone()),
@@ -1405,11 +1405,31 @@ mod tests {
         };
 
         let err = query_error(&err_packet);
-        assert!(matches!(err, Error::Query(_)), "Expected query error");
-        let Error::Query(q) = err else { return };
+        assert!(
+            matches!(err, Error::UniqueViolation { .. }),
+            "Expected unique violation"
+        );
+        let Error::UniqueViolation { query: q, .. } = err else {
+            return;
+        };
         assert_eq!(q.kind, QueryErrorKind::Constraint);
     }
 
+    #[test]
+    fn test_query_error_not_null() {
+        let err_packet = ErrPacket {
+            error_code: 1048,
+            sql_state: "23000".to_string(),
+            error_message: "Column 'email' cannot be null".to_string(),
+        };
+
+        let err = query_error(&err_packet);
+        assert!(
+            matches!(&err, Error::NotNullViolation { columns, .. } if columns == &["email"]),
+            "Expected not-null violation, got {err:?}"
+        );
+    }
+
     /// Console integration tests (only run when console feature is enabled).
     #[cfg(feature = "console")]
     mod console_tests {
@@ -49,7 +49,7 @@ pub mod protocol;
 pub mod tls;
 pub mod types;
 
-pub use async_connection::{MySqlAsyncConnection, SharedMySqlConnection};
+pub use async_connection::{MySqlAsyncConnection, MySqlCancelToken, SharedMySqlConnection};
 pub use config::{MySqlConfig, SslMode, TlsConfig};
 pub use connection::{ConnectionState, MySqlConnection};
 
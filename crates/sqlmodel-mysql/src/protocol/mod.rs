@@ -272,6 +272,26 @@ impl ErrPacket {
         // MySQL error codes 1451, 1452 = foreign key violations
         self.error_code == 1451 || self.error_code == 1452
     }
+
+    /// Check if this is a NOT NULL constraint violation.
+    pub fn is_not_null_violation(&self) -> bool {
+        // MySQL error code 1048 = ER_BAD_NULL_ERROR
+        self.error_code == 1048
+    }
+
+    /// Check if this is a CHECK constraint violation.
+    pub fn is_check_violation(&self) -> bool {
+        // MySQL error code 3819 = ER_CHECK_CONSTRAINT_VIOLATED
+        self.error_code == 3819
+    }
+
+    /// Check if this is any integrity constraint violation.
+    pub fn is_constraint_violation(&self) -> bool {
+        self.is_duplicate_key()
+            || self.is_foreign_key_violation()
+            || self.is_not_null_violation()
+            || self.is_check_violation()
+    }
 }
 
 /// Parsed EOF packet (deprecated in newer MySQL versions).
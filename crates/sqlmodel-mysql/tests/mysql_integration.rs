@@ -265,7 +265,11 @@ fn mysql_unique_violation_maps_to_constraint() {
             .execute(&cx, &insert_sql, &[Value::Text("dup".into())])
             .await;
         assert!(
-            matches!(&outcome, Outcome::Err(Error::Query(q)) if q.kind == QueryErrorKind::Constraint),
+            matches!(
+                &outcome,
+                Outcome::Err(Error::UniqueViolation { query: q, .. })
+                    if q.kind == QueryErrorKind::Constraint
+            ),
             "expected constraint violation, got outcome: {outcome:?}"
         );
 
@@ -0,0 +1,338 @@
+//! Structured health reports for `/healthz` endpoints.
+//!
+//! [`Pool::health`] pings one idle connection and summarizes saturation,
+//! the recent acquire error rate and whether the pool is admitting work:
+//!
+//! ```rust,ignore
+//! let report = pool.health(&cx).await;
+//! Response::json(report.http_status(), serde_json::to_string(&report)?)
+//! ```
+//!
+//! [`ReplicaPool::health`] reports the primary with one entry per replica.
+//! Status names match the console's `PoolHealth` renderable.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use asupersync::{Cx, Outcome};
+use serde::Serialize;
+use sqlmodel_core::Connection;
+
+use crate::{Pool, PooledConnection, ReplicaPool};
+
+/// Saturation at or above which a pool is reported [`HealthStatus::Busy`].
+pub const BUSY_SATURATION: f64 = 0.8;
+
+/// Recent acquire error rate above which a pool is reported
+/// [`HealthStatus::Degraded`].
+pub const DEGRADED_ERROR_RATE: f64 = 0.1;
+
+/// Overall health of a pool, from best to worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HealthStatus {
+    /// Low utilization, no waiting requests
+    Healthy,
+    /// High utilization but no waiting requests
+    Busy,
+    /// Requests are waiting, acquires are failing, or a replica is down
+    Degraded,
+    /// At capacity with a waiting queue as long as the pool
+    Exhausted,
+    /// Closed, shedding load, or the ping failed
+    Unhealthy,
+}
+
+impl HealthStatus {
+    /// The status name, e.g. `"HEALTHY"`.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Healthy => "HEALTHY",
+            Self::Busy => "BUSY",
+            Self::Degraded => "DEGRADED",
+            Self::Exhausted => "EXHAUSTED",
+            Self::Unhealthy => "UNHEALTHY",
+        }
+    }
+}
+
+impl std::fmt::Display for HealthStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Whether the pool is admitting work.
+///
+/// Pools have no breaker of their own: the circuit reads open while the
+/// pool is closed or its [`QueryLimits`](crate::QueryLimits) queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Acquires and statements are admitted
+    Closed,
+    /// New work is rejected
+    Open,
+}
+
+/// The result of pinging an idle connection.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PingReport {
+    /// Whether the ping succeeded
+    pub ok: bool,
+    /// Round-trip time in milliseconds
+    pub latency_ms: f64,
+    /// Why the ping failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A replica's entry in a [`ReplicaPool`] report.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReplicaHealth {
+    /// Position of the replica in the pool
+    pub index: usize,
+    /// Last measured replication lag in milliseconds, under
+    /// [`ReplicaStrategy::MaxLag`](crate::ReplicaStrategy::MaxLag)
+    pub lag_ms: Option<u64>,
+    /// Whether reads currently skip this replica as unreachable
+    pub evicted: bool,
+    /// The replica pool's own report
+    pub health: HealthReport,
+}
+
+/// A point-in-time health summary of a pool.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HealthReport {
+    /// Overall status
+    pub status: HealthStatus,
+    /// Ping of one idle connection; `None` when none was idle
+    pub ping: Option<PingReport>,
+    /// Active connections over `max_connections`, from 0.0 to 1.0
+    pub saturation: f64,
+    /// Number of connections currently in use
+    pub active_connections: usize,
+    /// Number of idle connections
+    pub idle_connections: usize,
+    /// Maximum connections allowed
+    pub max_connections: usize,
+    /// Number of requests waiting for a connection
+    pub pending_requests: usize,
+    /// Share of the recent acquires that failed, from 0.0 to 1.0
+    pub error_rate: f64,
+    /// Number of acquires `error_rate` is computed over
+    pub recent_acquires: usize,
+    /// Whether the pool is admitting work
+    pub circuit: CircuitState,
+    /// Replica reports, for [`ReplicaPool`]s
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub replicas: Vec<ReplicaHealth>,
+}
+
+impl HealthReport {
+    /// Whether the pool can serve requests (anything but
+    /// [`HealthStatus::Unhealthy`]).
+    #[must_use]
+    pub fn is_ready(&self) -> bool {
+        self.status != HealthStatus::Unhealthy
+    }
+
+    /// HTTP status for a health endpoint: 200 when ready, 503 otherwise.
+    #[must_use]
+    pub fn http_status(&self) -> u16 {
+        if self.is_ready() { 200 } else { 503 }
+    }
+
+    fn classify(&self) -> HealthStatus {
+        let ping_failed = self.ping.as_ref().is_some_and(|ping| !ping.ok);
+        if self.circuit == CircuitState::Open || ping_failed {
+            HealthStatus::Unhealthy
+        } else if self.pending_requests > 0
+            && (self.pending_requests >= self.max_connections
+                || self.active_connections >= self.max_connections)
+        {
+            HealthStatus::Exhausted
+        } else if self.pending_requests > 0 || self.error_rate > DEGRADED_ERROR_RATE {
+            HealthStatus::Degraded
+        } else if self.saturation >= BUSY_SATURATION {
+            HealthStatus::Busy
+        } else {
+            HealthStatus::Healthy
+        }
+    }
+}
+
+impl<C: Connection> Pool<C> {
+    /// Build a [`HealthReport`], pinging one idle connection if there is one.
+    ///
+    /// No connection is opened for the check. A connection that fails the
+    /// ping is closed instead of being returned to the pool.
+    pub async fn health(&self, cx: &Cx) -> HealthReport {
+        let ping = match self.take_idle() {
+            Some(conn) => Some(ping(cx, conn).await),
+            None => None,
+        };
+
+        let stats = self.stats();
+        let max_connections = self.config().max_connections;
+        let (error_rate, recent_acquires) = self.shared.error_rate();
+        let shedding = self
+            .shared
+            .limiter()
+            .is_some_and(|limiter| limiter.is_saturated());
+        #[allow(clippy::cast_precision_loss)]
+        let saturation = if max_connections == 0 {
+            1.0
+        } else {
+            stats.active_connections as f64 / max_connections as f64
+        };
+        let mut report = HealthReport {
+            status: HealthStatus::Healthy,
+            ping,
+            saturation,
+            active_connections: stats.active_connections,
+            idle_connections: stats.idle_connections,
+            max_connections,
+            pending_requests: stats.pending_requests,
+            error_rate,
+            recent_acquires,
+            circuit: if self.is_closed() || shedding {
+                CircuitState::Open
+            } else {
+                CircuitState::Closed
+            },
+            replicas: Vec::new(),
+        };
+        report.status = report.classify();
+        report
+    }
+
+    /// Check out the oldest idle connection, skipping validation and hooks.
+    fn take_idle(&self) -> Option<PooledConnection<C>> {
+        let mut inner = self.shared.lock_or_recover();
+        if inner.closed {
+            return None;
+        }
+        let meta = inner.idle.pop_front()?;
+        inner.active_count += 1;
+        drop(inner);
+        Some(PooledConnection::new(meta, Arc::downgrade(&self.shared)))
+    }
+}
+
+async fn ping<C: Connection>(cx: &Cx, conn: PooledConnection<C>) -> PingReport {
+    let started = Instant::now();
+    let outcome = conn.ping(cx).await;
+    let latency_ms = started.elapsed().as_secs_f64() * 1000.0;
+    let error = match outcome {
+        Outcome::Ok(()) => None,
+        Outcome::Err(e) => Some(e.to_string()),
+        Outcome::Cancelled(_) => Some("ping cancelled".to_string()),
+        Outcome::Panicked(_) => Some("ping panicked".to_string()),
+    };
+    if error.is_some() {
+        drop(conn.detach());
+    }
+    PingReport {
+        ok: error.is_none(),
+        latency_ms,
+        error,
+    }
+}
+
+impl<C: Connection> ReplicaPool<C> {
+    /// Build the primary's [`HealthReport`] with one [`ReplicaHealth`] per
+    /// replica.
+    ///
+    /// An unhealthy or evicted replica degrades the overall status; reads
+    /// fall back to the remaining replicas.
+    pub async fn health(&self, cx: &Cx) -> HealthReport {
+        let mut report = self.primary().health(cx).await;
+        for (index, replica) in self.replicas().iter().enumerate() {
+            report.replicas.push(ReplicaHealth {
+                index,
+                lag_ms: self
+                    .replica_lag(index)
+                    .map(|lag| u64::try_from(lag.as_millis()).unwrap_or(u64::MAX)),
+                evicted: self.is_evicted(index),
+                health: replica.health(cx).await,
+            });
+        }
+        let replica_down = report
+            .replicas
+            .iter()
+            .any(|replica| replica.evicted || !replica.health.is_ready());
+        if replica_down {
+            report.status = report.status.max(HealthStatus::Degraded);
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> HealthReport {
+        HealthReport {
+            status: HealthStatus::Healthy,
+            ping: None,
+            saturation: 0.2,
+            active_connections: 2,
+            idle_connections: 3,
+            max_connections: 10,
+            pending_requests: 0,
+            error_rate: 0.0,
+            recent_acquires: 50,
+            circuit: CircuitState::Closed,
+            replicas: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(report().classify(), HealthStatus::Healthy);
+        let busy = HealthReport {
+            saturation: 0.9,
+            active_connections: 9,
+            ..report()
+        };
+        assert_eq!(busy.classify(), HealthStatus::Busy);
+        let failing = HealthReport {
+            error_rate: 0.5,
+            ..report()
+        };
+        assert_eq!(failing.classify(), HealthStatus::Degraded);
+        let exhausted = HealthReport {
+            active_connections: 10,
+            pending_requests: 4,
+            ..report()
+        };
+        assert_eq!(exhausted.classify(), HealthStatus::Exhausted);
+        let down = HealthReport {
+            ping: Some(PingReport {
+                ok: false,
+                latency_ms: 1.0,
+                error: Some("connection reset".to_string()),
+            }),
+            ..report()
+        };
+        assert_eq!(down.classify(), HealthStatus::Unhealthy);
+        let down = HealthReport {
+            status: down.classify(),
+            ..down
+        };
+        assert!(!down.is_ready());
+        assert_eq!(down.http_status(), 503);
+    }
+
+    #[test]
+    fn test_report_serializes() {
+        let json = serde_json::to_value(report()).unwrap();
+        assert_eq!(json["status"], "HEALTHY");
+        assert_eq!(json["circuit"], "closed");
+        assert!(json["ping"].is_null());
+        assert!(json.get("replicas").is_none());
+    }
+}
@@ -17,8 +17,11 @@
 //! - RAII-based connection return (connections returned on drop)
 //! - Timeout support via `Cx` context
 //! - Connection health validation
+//! - Setup and reset hooks (`after_connect`, `before_checkout`)
+//! - Per-query admission control (`with_limits`)
 //! - Idle and max lifetime tracking
-//! - Pool statistics
+//! - Pool statistics and health reports (`health`)
+//! - Live config reloads with gradual draining (`reload_config`)
 //!
 //! # Example
 //!
@@ -42,21 +45,41 @@
 //! conn.query(&cx, "SELECT 1", &[]).await?;
 //! ```
 
+pub mod health;
+pub use health::{CircuitState, HealthReport, HealthStatus, PingReport, ReplicaHealth};
+
+pub mod limits;
+pub use limits::QueryLimits;
+
 pub mod replica;
-pub use replica::{ReplicaPool, ReplicaStrategy};
+pub use replica::{DEFAULT_PROBE_INTERVAL, ReplicaPool, ReplicaStrategy};
 
 pub mod sharding;
 pub use sharding::{ModuloShardChooser, QueryHints, ShardChooser, ShardedPool, ShardedPoolStats};
 
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::future::Future;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Condvar, Mutex, Weak};
 use std::time::{Duration, Instant};
 
-use asupersync::{CancelReason, Cx, Outcome};
+use asupersync::{CancelReason, Cx, Outcome, TaskId};
+use sqlmodel_core::connection::{IsolationLevel, PreparedStatement, QueryResult};
 use sqlmodel_core::error::{ConnectionError, ConnectionErrorKind, PoolError, PoolErrorKind};
-use sqlmodel_core::{Connection, Error};
+use sqlmodel_core::interceptor::{InterceptorChain, StatementInterceptor};
+use sqlmodel_core::query_log::{QueryLogger, batch_entry, log_query};
+use sqlmodel_core::sqlcommenter::SqlCommenter;
+use sqlmodel_core::{Connection, Dialect, Error, QueryOptions, Row, SqlDialect, Value};
+
+use crate::limits::QueryLimiter;
+
+/// Future returned by a [`ConnectionHook`].
+pub type HookFuture<'a> = Pin<Box<dyn Future<Output = Outcome<(), Error>> + Send + 'a>>;
+
+/// A hook run on a pooled connection, registered with
+/// [`Pool::after_connect`] or [`Pool::before_checkout`].
+pub type ConnectionHook<C> = Arc<dyn for<'a> Fn(&'a C, &'a Cx) -> HookFuture<'a> + Send + Sync>;
 
 /// Connection pool configuration.
 #[derive(Debug, Clone)]
@@ -163,6 +186,47 @@ pub struct PoolStats {
     pub acquires: u64,
     /// Total number of acquire timeouts
     pub timeouts: u64,
+    /// Statements running under the pool's [`QueryLimits`]
+    pub queries_running: usize,
+    /// Statements waiting for a [`QueryLimits`] slot
+    pub queries_queued: usize,
+    /// Total number of statements shed with [`Error::Overloaded`]
+    pub queries_rejected: u64,
+}
+
+/// How quickly connections opened before [`Pool::reload_config`] are
+/// replaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrainPolicy {
+    /// Maximum number of old connections closed per interval
+    pub max_per_interval: usize,
+    /// Length of an interval
+    pub interval: Duration,
+}
+
+impl DrainPolicy {
+    /// Close at most `max_per_interval` old connections every `interval`.
+    #[must_use]
+    pub fn new(max_per_interval: usize, interval: Duration) -> Self {
+        Self {
+            max_per_interval,
+            interval,
+        }
+    }
+
+    /// Close every old connection as soon as it is idle.
+    #[must_use]
+    pub fn immediate() -> Self {
+        Self::new(usize::MAX, Duration::ZERO)
+    }
+}
+
+/// Progress of a [`DrainPolicy`] through its current interval.
+#[derive(Debug)]
+struct DrainState {
+    policy: DrainPolicy,
+    window_start: Instant,
+    closed_in_window: usize,
 }
 
 /// Metadata about a pooled connection.
@@ -174,6 +238,8 @@ struct ConnectionMeta<C> {
     created_at: Instant,
     /// When this connection was last used
     last_used: Instant,
+    /// Config generation the connection was opened under
+    generation: u64,
 }
 
 impl<C> ConnectionMeta<C> {
@@ -183,6 +249,7 @@ impl<C> ConnectionMeta<C> {
             conn,
             created_at: now,
             last_used: now,
+            generation: 0,
         }
     }
 
@@ -213,6 +280,12 @@ struct PoolInner<C> {
     waiter_count: usize,
     /// Whether the pool has been closed
     closed: bool,
+    /// Bumped by every config reload
+    generation: u64,
+    /// Replacement of connections from earlier generations, if requested
+    drain: Option<DrainState>,
+    /// Connections checked out by each task
+    holders: HashMap<TaskId, usize>,
 }
 
 impl<C> PoolInner<C> {
@@ -224,6 +297,9 @@ impl<C> PoolInner<C> {
             total_count: 0,
             waiter_count: 0,
             closed: false,
+            generation: 0,
+            drain: None,
+            holders: HashMap::new(),
         }
     }
 
@@ -231,6 +307,40 @@ impl<C> PoolInner<C> {
         !self.closed && self.total_count < self.config.max_connections
     }
 
+    fn hold(&mut self, task: TaskId) {
+        *self.holders.entry(task).or_insert(0) += 1;
+    }
+
+    fn release(&mut self, task: TaskId) {
+        if let Some(held) = self.holders.get_mut(&task) {
+            *held -= 1;
+            if *held == 0 {
+                self.holders.remove(&task);
+            }
+        }
+    }
+
+    /// Whether `meta` is from an earlier generation and the drain policy
+    /// allows closing it now; counts it against the policy if so.
+    fn should_drain(&mut self, meta: &ConnectionMeta<C>) -> bool {
+        if meta.generation == self.generation {
+            return false;
+        }
+        let Some(drain) = &mut self.drain else {
+            return false;
+        };
+        let now = Instant::now();
+        if now.duration_since(drain.window_start) >= drain.policy.interval {
+            drain.window_start = now;
+            drain.closed_in_window = 0;
+        }
+        if drain.closed_in_window >= drain.policy.max_per_interval {
+            return false;
+        }
+        drain.closed_in_window += 1;
+        true
+    }
+
     fn stats(&self) -> PoolStats {
         PoolStats {
             total_connections: self.total_count,
@@ -242,6 +352,9 @@ impl<C> PoolInner<C> {
     }
 }
 
+/// Number of recent acquires the health report's error rate covers.
+const RECENT_ACQUIRES: usize = 100;
+
 /// Shared state wrapper with condition variable for notification.
 struct PoolShared<C> {
     /// Protected pool state
@@ -253,6 +366,18 @@ struct PoolShared<C> {
     connections_closed: AtomicU64,
     acquires: AtomicU64,
     timeouts: AtomicU64,
+    /// Logger handed to every connection checked out after it is set
+    query_logger: Mutex<Option<Arc<dyn QueryLogger>>>,
+    /// Interceptors handed to every connection checked out after they are added
+    interceptors: Mutex<InterceptorChain>,
+    /// Run once on every connection the pool opens
+    after_connect: Mutex<Option<ConnectionHook<C>>>,
+    /// Run on every idle connection before it is handed out again
+    before_checkout: Mutex<Option<ConnectionHook<C>>>,
+    /// Admission control shared by every connection checked out after it is set
+    limiter: Mutex<Option<Arc<QueryLimiter>>>,
+    /// Whether each of the last `RECENT_ACQUIRES` acquires failed
+    recent_acquires: Mutex<VecDeque<bool>>,
 }
 
 impl<C> PoolShared<C> {
@@ -264,6 +389,77 @@ impl<C> PoolShared<C> {
             connections_closed: AtomicU64::new(0),
             acquires: AtomicU64::new(0),
             timeouts: AtomicU64::new(0),
+            query_logger: Mutex::new(None),
+            interceptors: Mutex::new(InterceptorChain::new()),
+            after_connect: Mutex::new(None),
+            before_checkout: Mutex::new(None),
+            limiter: Mutex::new(None),
+            recent_acquires: Mutex::new(VecDeque::with_capacity(RECENT_ACQUIRES)),
+        }
+    }
+
+    fn hook(slot: &Mutex<Option<ConnectionHook<C>>>) -> Option<ConnectionHook<C>> {
+        match slot.lock() {
+            Ok(hook) => hook.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    fn query_logger(&self) -> Option<Arc<dyn QueryLogger>> {
+        match self.query_logger.lock() {
+            Ok(logger) => logger.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    fn limiter(&self) -> Option<Arc<QueryLimiter>> {
+        match self.limiter.lock() {
+            Ok(limiter) => limiter.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    fn clear_idle(&self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            let idle_count = inner.idle.len();
+            inner.idle.clear();
+            inner.total_count -= idle_count;
+            self.connections_closed
+                .fetch_add(idle_count as u64, Ordering::Relaxed);
+        }
+    }
+
+    fn record_acquire(&self, failed: bool) {
+        let mut recent = self
+            .recent_acquires
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if recent.len() == RECENT_ACQUIRES {
+            recent.pop_front();
+        }
+        recent.push_back(failed);
+    }
+
+    /// Share of the recent acquires that failed, and how many there were.
+    fn error_rate(&self) -> (f64, usize) {
+        let recent = self
+            .recent_acquires
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let failed = recent.iter().filter(|failed| **failed).count();
+        #[allow(clippy::cast_precision_loss)]
+        let rate = if recent.is_empty() {
+            0.0
+        } else {
+            failed as f64 / recent.len() as f64
+        };
+        (rate, recent.len())
+    }
+
+    fn interceptors(&self) -> InterceptorChain {
+        match self.interceptors.lock() {
+            Ok(chain) => chain.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
         }
     }
 
@@ -328,6 +524,128 @@ impl<C: Connection> Pool<C> {
         }
     }
 
+    /// Log every statement run on connections from this pool.
+    ///
+    /// Applies to connections checked out after the call; see
+    /// [`sqlmodel_core::query_log`] for the built-in sinks and redaction.
+    #[must_use]
+    pub fn with_query_logger(self, logger: Arc<dyn QueryLogger>) -> Self {
+        match self.shared.query_logger.lock() {
+            Ok(mut slot) => *slot = Some(logger),
+            Err(poisoned) => *poisoned.into_inner() = Some(logger),
+        }
+        self
+    }
+
+    /// Run every statement on connections from this pool through
+    /// `interceptor`, after any interceptors added before it.
+    ///
+    /// Applies to connections checked out after the call; see
+    /// [`sqlmodel_core::interceptor`] for the built-in interceptors. The query
+    /// logger sees statements as rewritten by the interceptors.
+    #[must_use]
+    pub fn with_interceptor(self, interceptor: Arc<dyn StatementInterceptor>) -> Self {
+        match self.shared.interceptors.lock() {
+            Ok(mut chain) => chain.push(interceptor),
+            Err(poisoned) => poisoned.into_inner().push(interceptor),
+        }
+        self
+    }
+
+    /// Tag every statement on this pool's connections with a sqlcommenter
+    /// comment (`/*application='...',traceparent='...'*/`).
+    ///
+    /// Pools without this call send statements unchanged. Add it last so the
+    /// comment describes the statement other interceptors produced.
+    #[must_use]
+    pub fn with_sqlcommenter(self, commenter: SqlCommenter) -> Self {
+        self.with_interceptor(Arc::new(commenter))
+    }
+
+    /// Run `hook` once on every connection the pool opens, before its first
+    /// checkout: set the time zone, `search_path` or `statement_timeout`,
+    /// load extensions.
+    ///
+    /// A failing hook discards the connection and fails the acquire.
+    ///
+    /// ```rust,ignore
+    /// let pool = Pool::new(config).after_connect(|conn, cx| {
+    ///     Box::pin(async move {
+    ///         conn.execute(cx, "SET TIME ZONE 'UTC'", &[]).await.map(|_| ())
+    ///     })
+    /// });
+    /// ```
+    #[must_use]
+    pub fn after_connect<F>(self, hook: F) -> Self
+    where
+        F: for<'a> Fn(&'a C, &'a Cx) -> HookFuture<'a> + Send + Sync + 'static,
+    {
+        let hook: ConnectionHook<C> = Arc::new(hook);
+        match self.shared.after_connect.lock() {
+            Ok(mut slot) => *slot = Some(hook),
+            Err(poisoned) => *poisoned.into_inner() = Some(hook),
+        }
+        self
+    }
+
+    /// Run `hook` on every idle connection before handing it out again,
+    /// after the `test_on_checkout` ping: `ROLLBACK` a transaction a previous
+    /// holder leaked, or `DISCARD ALL`.
+    ///
+    /// Fresh connections skip it. A failing hook discards the connection and
+    /// fails the acquire, like a failed ping. Note that `DISCARD ALL` also
+    /// resets session settings made by [`after_connect`](Self::after_connect).
+    #[must_use]
+    pub fn before_checkout<F>(self, hook: F) -> Self
+    where
+        F: for<'a> Fn(&'a C, &'a Cx) -> HookFuture<'a> + Send + Sync + 'static,
+    {
+        let hook: ConnectionHook<C> = Arc::new(hook);
+        match self.shared.before_checkout.lock() {
+            Ok(mut slot) => *slot = Some(hook),
+            Err(poisoned) => *poisoned.into_inner() = Some(hook),
+        }
+        self
+    }
+
+    /// Cap how many statements run at once on this pool's connections,
+    /// independently of `max_connections`.
+    ///
+    /// Statements over the cap wait in a FIFO queue of `max_queue`; beyond
+    /// that they fail immediately with [`Error::Overloaded`]. Applies to
+    /// connections checked out after the call; see [`limits`] for details.
+    #[must_use]
+    pub fn with_limits(self, limits: QueryLimits) -> Self {
+        let limiter = Some(Arc::new(QueryLimiter::new(limits)));
+        match self.shared.limiter.lock() {
+            Ok(mut slot) => *slot = limiter,
+            Err(poisoned) => *poisoned.into_inner() = limiter,
+        }
+        self
+    }
+
+    /// Apply `config` and start a new connection generation.
+    ///
+    /// Sizes and timeouts take effect immediately. Connections opened from
+    /// now on belong to the new generation, so pass `acquire` a factory that
+    /// reads the current credentials and hosts (for example a config behind
+    /// an `Arc<RwLock<_>>` updated alongside this call). Older connections
+    /// are closed as they are checked in or out, at most as fast as `drain`
+    /// allows; with `None` they are kept until `max_lifetime`.
+    pub fn reload_config(&self, config: PoolConfig, drain: Option<DrainPolicy>) {
+        let mut inner = self.shared.lock_or_recover();
+        inner.config = config;
+        inner.generation += 1;
+        inner.drain = drain.map(|policy| DrainState {
+            policy,
+            window_start: Instant::now(),
+            closed_in_window: 0,
+        });
+        drop(inner);
+        // A larger pool may let waiters create connections now.
+        self.shared.conn_available.notify_all();
+    }
+
     /// Get the pool configuration.
     #[must_use]
     pub fn config(&self) -> PoolConfig {
@@ -344,6 +662,13 @@ impl<C: Connection> Pool<C> {
         stats.connections_closed = self.shared.connections_closed.load(Ordering::Relaxed);
         stats.acquires = self.shared.acquires.load(Ordering::Relaxed);
         stats.timeouts = self.shared.timeouts.load(Ordering::Relaxed);
+        if let Some(limiter) = self.shared.limiter() {
+            (
+                stats.queries_running,
+                stats.queries_queued,
+                stats.queries_rejected,
+            ) = limiter.counts();
+        }
         stats
     }
 
@@ -376,6 +701,29 @@ impl<C: Connection> Pool<C> {
     /// - Cancellation is requested via the `Cx` context
     /// - Connection validation fails (if `test_on_checkout` is enabled)
     pub async fn acquire<F, Fut>(&self, cx: &Cx, factory: F) -> Outcome<PooledConnection<C>, Error>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Outcome<C, Error>>,
+    {
+        let task = cx.task_id();
+        let mut outcome = self.acquire_connection(cx, task, factory).await;
+        match &mut outcome {
+            Outcome::Ok(conn) => {
+                self.shared.record_acquire(false);
+                conn.hold(task);
+            }
+            Outcome::Err(_) | Outcome::Panicked(_) => self.shared.record_acquire(true),
+            Outcome::Cancelled(_) => {}
+        }
+        outcome
+    }
+
+    async fn acquire_connection<F, Fut>(
+        &self,
+        cx: &Cx,
+        task: TaskId,
+        factory: F,
+    ) -> Outcome<PooledConnection<C>, Error>
     where
         F: Fn() -> Fut,
         Fut: Future<Output = Outcome<C, Error>>,
@@ -423,8 +771,9 @@ impl<C: Connection> Pool<C> {
                             continue;
                         }
 
-                        // Check if connection has been idle too long
-                        if meta.idle_time() > idle_timeout {
+                        // Check if connection has been idle too long, or
+                        // predates a config reload that is being drained
+                        if meta.idle_time() > idle_timeout || inner.should_drain(&meta) {
                             inner.total_count -= 1;
                             self.shared
                                 .connections_closed
@@ -446,6 +795,13 @@ impl<C: Connection> Pool<C> {
                         inner.total_count += 1;
                         inner.active_count += 1;
                         AcquireAction::CreateNew
+                    } else if let Some(&held) = inner
+                        .holders
+                        .get(&task)
+                        .filter(|&&held| held >= inner.config.max_connections)
+                    {
+                        // Every connection is ours, so waiting would deadlock
+                        AcquireAction::Reentrant(held)
                     } else {
                         // Must wait
                         inner.waiter_count += 1;
@@ -462,19 +818,40 @@ impl<C: Connection> Pool<C> {
                         source: None,
                     }));
                 }
+                AcquireAction::Reentrant(held) => {
+                    return Outcome::Err(Error::Pool(PoolError {
+                        kind: PoolErrorKind::ReentrantAcquire,
+                        message: format!(
+                            "task already holds all {held} of this pool's connection(s) and \
+                             would wait on itself; pass the held connection down instead of \
+                             acquiring again, drop it before acquiring, or raise max_connections"
+                        ),
+                        source: None,
+                    }));
+                }
                 AcquireAction::ValidateExisting(meta) => {
                     // Validate and wrap the connection (lock is released)
                     return self.validate_and_wrap(cx, meta, test_on_checkout).await;
                 }
                 AcquireAction::CreateNew => {
                     // Create new connection outside of lock
+                    let generation = self.shared.lock_or_recover().generation;
                     match factory().await {
                         Outcome::Ok(conn) => {
                             self.shared
                                 .connections_created
                                 .fetch_add(1, Ordering::Relaxed);
+                            if let Some(hook) = PoolShared::hook(&self.shared.after_connect) {
+                                match self.run_hook(cx, &conn, &hook).await {
+                                    Outcome::Ok(()) => {}
+                                    Outcome::Err(e) => return Outcome::Err(e),
+                                    Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                                    Outcome::Panicked(p) => return Outcome::Panicked(p),
+                                }
+                            }
                             self.shared.acquires.fetch_add(1, Ordering::Relaxed);
-                            let meta = ConnectionMeta::new(conn);
+                            let mut meta = ConnectionMeta::new(conn);
+                            meta.generation = generation;
                             return Outcome::Ok(PooledConnection::new(
                                 meta,
                                 Arc::downgrade(&self.shared),
@@ -560,45 +937,57 @@ impl<C: Connection> Pool<C> {
         if test_on_checkout {
             // Validate the connection
             match meta.conn.ping(cx).await {
-                Outcome::Ok(()) => {
-                    self.shared.acquires.fetch_add(1, Ordering::Relaxed);
-                    Outcome::Ok(PooledConnection::new(meta, Arc::downgrade(&self.shared)))
-                }
+                Outcome::Ok(()) => {}
                 Outcome::Err(_) | Outcome::Cancelled(_) | Outcome::Panicked(_) => {
                     // Connection is invalid, decrement counts and try again
-                    {
-                        if let Ok(mut inner) = self.shared.lock_or_error("validate_cleanup") {
-                            inner.total_count -= 1;
-                            inner.active_count -= 1;
-                        }
-                    }
-                    self.shared
-                        .connections_closed
-                        .fetch_add(1, Ordering::Relaxed);
+                    self.discard_slot("validate_cleanup");
                     // Return error - caller should retry
-                    Outcome::Err(Error::Connection(ConnectionError {
+                    return Outcome::Err(Error::Connection(ConnectionError {
                         kind: ConnectionErrorKind::Disconnected,
                         message: "connection validation failed".to_string(),
                         source: None,
-                    }))
+                    }));
                 }
             }
-        } else {
-            self.shared.acquires.fetch_add(1, Ordering::Relaxed);
-            Outcome::Ok(PooledConnection::new(meta, Arc::downgrade(&self.shared)))
         }
+        if let Some(hook) = PoolShared::hook(&self.shared.before_checkout) {
+            match self.run_hook(cx, &meta.conn, &hook).await {
+                Outcome::Ok(()) => {}
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            }
+        }
+        self.shared.acquires.fetch_add(1, Ordering::Relaxed);
+        Outcome::Ok(PooledConnection::new(meta, Arc::downgrade(&self.shared)))
+    }
+
+    /// Run a connection hook, giving up the connection's slot if it fails.
+    async fn run_hook(&self, cx: &Cx, conn: &C, hook: &ConnectionHook<C>) -> Outcome<(), Error> {
+        let outcome = hook(conn, cx).await;
+        if !matches!(outcome, Outcome::Ok(())) {
+            self.discard_slot("hook_cleanup");
+        }
+        outcome
+    }
+
+    /// Forget a checked-out connection that is being dropped instead of
+    /// returned.
+    fn discard_slot(&self, operation: &'static str) {
+        if let Ok(mut inner) = self.shared.lock_or_error(operation) {
+            inner.total_count -= 1;
+            inner.active_count -= 1;
+        }
+        self.shared
+            .connections_closed
+            .fetch_add(1, Ordering::Relaxed);
     }
 
     /// Close the pool, preventing new connections and closing all idle connections.
     ///
     /// If the pool mutex is poisoned, this logs an error but still wakes waiters.
     pub fn clear_idle(&self) {
-        if let Ok(mut inner) = self.shared.inner.lock() {
-            let idle_count = inner.idle.len();
-            inner.idle.clear();
-            inner.total_count -= idle_count;
-            self.shared.connections_closed.fetch_add(idle_count as u64, Ordering::Relaxed);
-        }
+        self.shared.clear_idle();
     }
 
     pub fn close(&self) {
@@ -669,25 +1058,83 @@ enum AcquireAction<C> {
     CreateNew,
     /// Wait for a connection to become available
     Wait,
+    /// The task already holds every connection of the exhausted pool
+    Reentrant(usize),
 }
 
 /// A connection borrowed from the pool.
 ///
 /// When dropped, the connection is automatically returned to the pool.
-/// The connection can be used via `Deref` and `DerefMut`.
+/// The connection can be used via `Deref` and `DerefMut`, or as a
+/// [`Connection`] itself, in which case statements pass through the pool's
+/// interceptors and are reported to its query logger (if any).
 pub struct PooledConnection<C: Connection> {
     /// The connection metadata (Some while held, None after return)
     meta: Option<ConnectionMeta<C>>,
     /// Weak reference to pool for returning
     pool: Weak<PoolShared<C>>,
+    /// Query logger captured from the pool at checkout
+    query_logger: Option<Arc<dyn QueryLogger>>,
+    /// Interceptors captured from the pool at checkout
+    interceptors: InterceptorChain,
+    /// Admission control captured from the pool at checkout
+    limiter: Option<Arc<QueryLimiter>>,
+    /// Set when a statement failed in a way that suggests a failover
+    failed_over: AtomicBool,
+    /// The task that checked the connection out
+    holder: Option<TaskId>,
 }
 
 impl<C: Connection> PooledConnection<C> {
     fn new(meta: ConnectionMeta<C>, pool: Weak<PoolShared<C>>) -> Self {
+        let shared = pool.upgrade();
+        let query_logger = shared.as_ref().and_then(|shared| shared.query_logger());
+        let limiter = shared.as_ref().and_then(|shared| shared.limiter());
+        let interceptors = shared
+            .map(|shared| shared.interceptors())
+            .unwrap_or_default();
         Self {
             meta: Some(meta),
             pool,
+            query_logger,
+            interceptors,
+            limiter,
+            failed_over: AtomicBool::new(false),
+            holder: None,
+        }
+    }
+
+    /// Count this connection as held by `task` until it is returned.
+    fn hold(&mut self, task: TaskId) {
+        if let Some(pool) = self.pool.upgrade() {
+            pool.lock_or_recover().hold(task);
+            self.holder = Some(task);
+        }
+    }
+
+    /// Run `statement` under the pool's [`QueryLimits`], if any.
+    ///
+    /// An error that suggests a failover ([`Error::is_failover`]) drops the
+    /// pool's idle connections and this one on return, so the next acquires
+    /// reconnect and re-resolve which host is the primary.
+    async fn dispatch<T>(
+        &self,
+        cx: &Cx,
+        statement: impl Future<Output = Outcome<T, Error>>,
+    ) -> Outcome<T, Error> {
+        let outcome = match &self.limiter {
+            Some(limiter) => limiter.run(cx, statement).await,
+            None => statement.await,
+        };
+        if let Outcome::Err(e) = &outcome {
+            if e.is_failover() && !self.failed_over.swap(true, Ordering::Relaxed) {
+                tracing::warn!(error = %e, "possible failover; dropping idle pool connections");
+                if let Some(pool) = self.pool.upgrade() {
+                    pool.clear_idle();
+                }
+            }
         }
+        outcome
     }
 
     /// Detach this connection from the pool.
@@ -702,6 +1149,9 @@ impl<C: Connection> PooledConnection<C> {
                 Ok(mut inner) => {
                     inner.total_count -= 1;
                     inner.active_count -= 1;
+                    if let Some(task) = self.holder.take() {
+                        inner.release(task);
+                    }
                     pool.connections_closed.fetch_add(1, Ordering::Relaxed);
                 }
                 Err(_poisoned) => {
@@ -751,6 +1201,301 @@ impl<C: Connection> std::ops::DerefMut for PooledConnection<C> {
     }
 }
 
+#[allow(clippy::manual_async_fn)] // Trait methods return `impl Future + Send`
+impl<C: Connection> Connection for PooledConnection<C> {
+    type Tx<'conn>
+        = C::Tx<'conn>
+    where
+        Self: 'conn;
+
+    fn dialect(&self) -> Dialect {
+        (**self).dialect()
+    }
+
+    fn sql_dialect(&self) -> &'static dyn SqlDialect {
+        (**self).sql_dialect()
+    }
+
+    fn query(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<Vec<Row>, Error>> + Send {
+        async move {
+            let statement = match self.interceptors.apply(self.dialect(), sql, params) {
+                Ok(statement) => statement,
+                Err(e) => return Outcome::Err(e),
+            };
+            self.dispatch(
+                cx,
+                log_query(
+                    self.query_logger.as_deref(),
+                    &statement.sql,
+                    &statement.params,
+                    |rows: &Vec<Row>| rows.len() as u64,
+                    (**self).query(cx, &statement.sql, &statement.params),
+                ),
+            )
+            .await
+        }
+    }
+
+    fn query_raw(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<QueryResult, Error>> + Send {
+        async move {
+            let statement = match self.interceptors.apply(self.dialect(), sql, params) {
+                Ok(statement) => statement,
+                Err(e) => return Outcome::Err(e),
+            };
+            self.dispatch(
+                cx,
+                log_query(
+                    self.query_logger.as_deref(),
+                    &statement.sql,
+                    &statement.params,
+                    |result: &QueryResult| result.len() as u64,
+                    (**self).query_raw(cx, &statement.sql, &statement.params),
+                ),
+            )
+            .await
+        }
+    }
+
+    fn query_one(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<Option<Row>, Error>> + Send {
+        async move {
+            let statement = match self.interceptors.apply(self.dialect(), sql, params) {
+                Ok(statement) => statement,
+                Err(e) => return Outcome::Err(e),
+            };
+            self.dispatch(
+                cx,
+                log_query(
+                    self.query_logger.as_deref(),
+                    &statement.sql,
+                    &statement.params,
+                    |row: &Option<Row>| u64::from(row.is_some()),
+                    (**self).query_one(cx, &statement.sql, &statement.params),
+                ),
+            )
+            .await
+        }
+    }
+
+    fn execute(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<u64, Error>> + Send {
+        async move {
+            let statement = match self.interceptors.apply(self.dialect(), sql, params) {
+                Ok(statement) => statement,
+                Err(e) => return Outcome::Err(e),
+            };
+            self.dispatch(
+                cx,
+                log_query(
+                    self.query_logger.as_deref(),
+                    &statement.sql,
+                    &statement.params,
+                    |count: &u64| *count,
+                    (**self).execute(cx, &statement.sql, &statement.params),
+                ),
+            )
+            .await
+        }
+    }
+
+    fn query_with(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+        options: &QueryOptions,
+    ) -> impl Future<Output = Outcome<Vec<Row>, Error>> + Send {
+        async move {
+            let statement = match self.interceptors.apply(self.dialect(), sql, params) {
+                Ok(statement) => statement,
+                Err(e) => return Outcome::Err(e),
+            };
+            self.dispatch(
+                cx,
+                log_query(
+                    self.query_logger.as_deref(),
+                    &statement.sql,
+                    &statement.params,
+                    |rows: &Vec<Row>| rows.len() as u64,
+                    (**self).query_with(cx, &statement.sql, &statement.params, options),
+                ),
+            )
+            .await
+        }
+    }
+
+    fn execute_with(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+        options: &QueryOptions,
+    ) -> impl Future<Output = Outcome<u64, Error>> + Send {
+        async move {
+            let statement = match self.interceptors.apply(self.dialect(), sql, params) {
+                Ok(statement) => statement,
+                Err(e) => return Outcome::Err(e),
+            };
+            self.dispatch(
+                cx,
+                log_query(
+                    self.query_logger.as_deref(),
+                    &statement.sql,
+                    &statement.params,
+                    |count: &u64| *count,
+                    (**self).execute_with(cx, &statement.sql, &statement.params, options),
+                ),
+            )
+            .await
+        }
+    }
+
+    fn insert(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<i64, Error>> + Send {
+        async move {
+            let statement = match self.interceptors.apply(self.dialect(), sql, params) {
+                Ok(statement) => statement,
+                Err(e) => return Outcome::Err(e),
+            };
+            self.dispatch(
+                cx,
+                log_query(
+                    self.query_logger.as_deref(),
+                    &statement.sql,
+                    &statement.params,
+                    |_: &i64| 1,
+                    (**self).insert(cx, &statement.sql, &statement.params),
+                ),
+            )
+            .await
+        }
+    }
+
+    fn batch(
+        &self,
+        cx: &Cx,
+        statements: &[(String, Vec<Value>)],
+    ) -> impl Future<Output = Outcome<Vec<u64>, Error>> + Send {
+        async move {
+            let rewritten = match self.interceptors.apply_batch(self.dialect(), statements) {
+                Ok(rewritten) => rewritten,
+                Err(e) => return Outcome::Err(e),
+            };
+            let statements = rewritten.as_deref().unwrap_or(statements);
+            let logged = if self.query_logger.is_some() {
+                batch_entry(statements)
+            } else {
+                (String::new(), Vec::new())
+            };
+            let fut = (**self).batch(cx, statements);
+            self.dispatch(
+                cx,
+                log_query(
+                    self.query_logger.as_deref(),
+                    &logged.0,
+                    &logged.1,
+                    |counts: &Vec<u64>| counts.iter().sum(),
+                    fut,
+                ),
+            )
+            .await
+        }
+    }
+
+    fn begin(&self, cx: &Cx) -> impl Future<Output = Outcome<Self::Tx<'_>, Error>> + Send {
+        (**self).begin(cx)
+    }
+
+    fn begin_with(
+        &self,
+        cx: &Cx,
+        isolation: IsolationLevel,
+    ) -> impl Future<Output = Outcome<Self::Tx<'_>, Error>> + Send {
+        (**self).begin_with(cx, isolation)
+    }
+
+    fn prepare(
+        &self,
+        cx: &Cx,
+        sql: &str,
+    ) -> impl Future<Output = Outcome<PreparedStatement, Error>> + Send {
+        async move {
+            let statement = match self.interceptors.apply(self.dialect(), sql, &[]) {
+                Ok(statement) => statement,
+                Err(e) => return Outcome::Err(e),
+            };
+            (**self).prepare(cx, &statement.sql).await
+        }
+    }
+
+    fn query_prepared(
+        &self,
+        cx: &Cx,
+        stmt: &PreparedStatement,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<Vec<Row>, Error>> + Send {
+        self.dispatch(
+            cx,
+            log_query(
+                self.query_logger.as_deref(),
+                stmt.sql(),
+                params,
+                |rows: &Vec<Row>| rows.len() as u64,
+                (**self).query_prepared(cx, stmt, params),
+            ),
+        )
+    }
+
+    fn execute_prepared(
+        &self,
+        cx: &Cx,
+        stmt: &PreparedStatement,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<u64, Error>> + Send {
+        self.dispatch(
+            cx,
+            log_query(
+                self.query_logger.as_deref(),
+                stmt.sql(),
+                params,
+                |count: &u64| *count,
+                (**self).execute_prepared(cx, stmt, params),
+            ),
+        )
+    }
+
+    fn ping(&self, cx: &Cx) -> impl Future<Output = Outcome<(), Error>> + Send {
+        (**self).ping(cx)
+    }
+
+    /// Close the underlying connection instead of returning it to the pool.
+    fn close(self, cx: &Cx) -> impl Future<Output = sqlmodel_core::Result<()>> + Send {
+        self.detach().close(cx)
+    }
+}
+
 impl<C: Connection> Drop for PooledConnection<C> {
     fn drop(&mut self) {
         if let Some(mut meta) = self.meta.take() {
@@ -770,17 +1515,20 @@ impl<C: Connection> Drop for PooledConnection<C> {
                         return;
                     }
                 };
+                if let Some(task) = self.holder.take() {
+                    inner.release(task);
+                }
 
-                if inner.closed {
+                if inner.closed || *self.failed_over.get_mut() {
                     inner.total_count -= 1;
                     inner.active_count -= 1;
                     pool.connections_closed.fetch_add(1, Ordering::Relaxed);
                     return;
                 }
 
-                // Check max lifetime
+                // Check max lifetime and config generation
                 let max_lifetime = Duration::from_millis(inner.config.max_lifetime_ms);
-                if meta.age() > max_lifetime {
+                if meta.age() > max_lifetime || inner.should_drain(&meta) {
                     inner.total_count -= 1;
                     inner.active_count -= 1;
                     pool.connections_closed.fetch_add(1, Ordering::Relaxed);
@@ -813,7 +1561,6 @@ mod tests {
     use super::*;
     use sqlmodel_core::connection::{IsolationLevel, PreparedStatement, TransactionOps};
     use sqlmodel_core::{Row, Value};
-    use std::sync::atomic::AtomicBool;
 
     /// A mock connection for testing pool behavior.
     #[derive(Debug)]
@@ -1209,6 +1956,402 @@ mod tests {
         assert_eq!(stats.connections_closed, 1);
     }
 
+    #[test]
+    fn test_pooled_connection_logs_queries() {
+        use asupersync::runtime::RuntimeBuilder;
+        use sqlmodel_core::query_log::{QueryLogEntry, QueryStatus};
+
+        #[derive(Default)]
+        struct Collect(Mutex<Vec<(String, QueryStatus)>>);
+
+        impl QueryLogger for Collect {
+            fn log(&self, entry: &QueryLogEntry<'_>) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((entry.sql.to_string(), entry.status.clone()));
+            }
+        }
+
+        let logger = Arc::new(Collect::default());
+        let pool: Pool<MockConnection> =
+            Pool::new(PoolConfig::new(5)).with_query_logger(logger.clone());
+        {
+            let mut inner = pool.shared.inner.lock().unwrap();
+            inner.total_count = 1;
+            inner.active_count = 1;
+        }
+
+        let meta = ConnectionMeta::new(MockConnection::new(1));
+        let pooled = PooledConnection::new(meta, Arc::downgrade(&pool.shared));
+        let cx = Cx::for_testing();
+        let rt = RuntimeBuilder::current_thread()
+            .build()
+            .expect("create asupersync runtime");
+        rt.block_on(async {
+            let outcome = Connection::execute(&pooled, &cx, "DELETE FROM t", &[]).await;
+            assert!(matches!(outcome, Outcome::Ok(0)));
+            let outcome = Connection::query(&pooled, &cx, "SELECT 1", &[]).await;
+            assert!(matches!(outcome, Outcome::Ok(_)));
+        });
+
+        let entries = logger.0.lock().unwrap();
+        assert_eq!(
+            *entries,
+            vec![
+                ("DELETE FROM t".to_string(), QueryStatus::Ok { rows: 0 }),
+                ("SELECT 1".to_string(), QueryStatus::Ok { rows: 0 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pooled_connection_runs_interceptors_before_logging() {
+        use asupersync::runtime::RuntimeBuilder;
+        use sqlmodel_core::interceptor::{CommentInjector, RejectUnfilteredWrites};
+        use sqlmodel_core::query_log::QueryLogEntry;
+
+        #[derive(Default)]
+        struct Collect(Mutex<Vec<String>>);
+
+        impl QueryLogger for Collect {
+            fn log(&self, entry: &QueryLogEntry<'_>) {
+                self.0.lock().unwrap().push(entry.sql.to_string());
+            }
+        }
+
+        let logger = Arc::new(Collect::default());
+        let pool: Pool<MockConnection> = Pool::new(PoolConfig::new(5))
+            .with_query_logger(logger.clone())
+            .with_interceptor(Arc::new(CommentInjector::new(|| {
+                Some("app=test".to_string())
+            })))
+            .with_interceptor(Arc::new(RejectUnfilteredWrites::new()));
+        {
+            let mut inner = pool.shared.inner.lock().unwrap();
+            inner.total_count = 1;
+            inner.active_count = 1;
+        }
+
+        let meta = ConnectionMeta::new(MockConnection::new(1));
+        let pooled = PooledConnection::new(meta, Arc::downgrade(&pool.shared));
+        let cx = Cx::for_testing();
+        let rt = RuntimeBuilder::current_thread()
+            .build()
+            .expect("create asupersync runtime");
+        rt.block_on(async {
+            let outcome = Connection::execute(&pooled, &cx, "DELETE FROM t", &[]).await;
+            assert!(matches!(outcome, Outcome::Err(_)));
+            let outcome =
+                Connection::execute(&pooled, &cx, "DELETE FROM t WHERE id = 1", &[]).await;
+            assert!(matches!(outcome, Outcome::Ok(0)));
+        });
+
+        let entries = logger.0.lock().unwrap();
+        assert_eq!(
+            *entries,
+            vec!["/* app=test */ DELETE FROM t WHERE id = 1".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_connection_hooks_run_on_connect_and_reuse() {
+        use asupersync::runtime::RuntimeBuilder;
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let fail_checkout = Arc::new(AtomicBool::new(false));
+        let (on_connect, on_checkout, fail) = (calls.clone(), calls.clone(), fail_checkout.clone());
+        let pool: Pool<MockConnection> = Pool::new(PoolConfig::new(5).test_on_checkout(false))
+            .after_connect(move |conn: &MockConnection, _cx| {
+                on_connect
+                    .lock()
+                    .unwrap()
+                    .push(format!("connect {}", conn.id));
+                Box::pin(async { Outcome::Ok(()) })
+            })
+            .before_checkout(move |conn: &MockConnection, _cx| {
+                on_checkout
+                    .lock()
+                    .unwrap()
+                    .push(format!("checkout {}", conn.id));
+                let fail = fail.load(Ordering::Relaxed);
+                Box::pin(async move {
+                    if fail {
+                        Outcome::Err(Error::Custom("reset failed".to_string()))
+                    } else {
+                        Outcome::Ok(())
+                    }
+                })
+            });
+
+        let cx = Cx::for_testing();
+        let rt = RuntimeBuilder::current_thread()
+            .build()
+            .expect("create asupersync runtime");
+        rt.block_on(async {
+            let factory = || async { Outcome::Ok(MockConnection::new(7)) };
+            let first = pool.acquire(&cx, factory).await;
+            assert!(matches!(first, Outcome::Ok(_)));
+            drop(first);
+            let second = pool.acquire(&cx, factory).await;
+            assert!(matches!(second, Outcome::Ok(_)));
+            drop(second);
+
+            fail_checkout.store(true, Ordering::Relaxed);
+            let third = pool.acquire(&cx, factory).await;
+            assert!(matches!(third, Outcome::Err(Error::Custom(_))));
+        });
+
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec!["connect 7", "checkout 7", "checkout 7"]
+        );
+        assert_eq!(pool.total_count(), 0);
+        assert_eq!(pool.stats().connections_closed, 1);
+    }
+
+    #[test]
+    fn test_pool_with_limits_admits_statements() {
+        use asupersync::runtime::RuntimeBuilder;
+
+        let pool: Pool<MockConnection> =
+            Pool::new(PoolConfig::new(5)).with_limits(QueryLimits::new(1, 0));
+        let cx = Cx::for_testing();
+        let rt = RuntimeBuilder::current_thread()
+            .build()
+            .expect("create asupersync runtime");
+        rt.block_on(async {
+            let factory = || async { Outcome::Ok(MockConnection::new(1)) };
+            let Outcome::Ok(conn) = pool.acquire(&cx, factory).await else {
+                panic!("acquire failed");
+            };
+            for _ in 0..3 {
+                assert!(matches!(
+                    conn.execute(&cx, "UPDATE t SET x = 1", &[]).await,
+                    Outcome::Ok(_)
+                ));
+            }
+        });
+
+        let stats = pool.stats();
+        assert_eq!(stats.queries_running, 0);
+        assert_eq!(stats.queries_queued, 0);
+        assert_eq!(stats.queries_rejected, 0);
+    }
+
+    #[test]
+    fn test_pool_health_pings_idle_connection() {
+        use asupersync::runtime::RuntimeBuilder;
+
+        let pool: Pool<MockConnection> = Pool::new(PoolConfig::new(4));
+        let ping_fails = Arc::new(AtomicBool::new(false));
+        let cx = Cx::for_testing();
+        let rt = RuntimeBuilder::current_thread()
+            .build()
+            .expect("create asupersync runtime");
+        rt.block_on(async {
+            let report = pool.health(&cx).await;
+            assert_eq!(report.status, HealthStatus::Healthy);
+            assert!(report.ping.is_none());
+
+            let factory =
+                || async { Outcome::Ok(MockConnection::with_ping_behavior(1, ping_fails.clone())) };
+            drop(pool.acquire(&cx, factory).await);
+            let report = pool.health(&cx).await;
+            assert!(report.ping.as_ref().is_some_and(|ping| ping.ok));
+            assert_eq!(report.recent_acquires, 1);
+            assert_eq!(pool.idle_count(), 1);
+
+            ping_fails.store(true, Ordering::Relaxed);
+            let report = pool.health(&cx).await;
+            assert_eq!(report.status, HealthStatus::Unhealthy);
+            assert_eq!(report.http_status(), 503);
+            assert_eq!(pool.total_count(), 0);
+        });
+    }
+
+    #[test]
+    fn test_replica_pool_max_lag_evicts_and_reprobes() {
+        use asupersync::runtime::RuntimeBuilder;
+        use std::sync::atomic::AtomicU32;
+
+        let pool = ReplicaPool::with_strategy(
+            Pool::new(PoolConfig::new(2)),
+            vec![Pool::new(PoolConfig::new(2))],
+            ReplicaStrategy::MaxLag(Duration::from_secs(1)),
+        )
+        .with_probe_interval(Duration::ZERO);
+        let attempts = AtomicU32::new(0);
+        let cx = Cx::for_testing();
+        let rt = RuntimeBuilder::current_thread()
+            .build()
+            .expect("create asupersync runtime");
+        rt.block_on(async {
+            // The replica's first connection attempt fails.
+            let factory = || async {
+                match attempts.fetch_add(1, Ordering::Relaxed) {
+                    0 => Outcome::Err(Error::Custom("replica down".to_string())),
+                    n => Outcome::Ok(MockConnection::new(n)),
+                }
+            };
+            let first = pool.acquire_read(&cx, factory).await;
+            assert!(matches!(first, Outcome::Ok(_)));
+            assert_eq!(pool.primary().total_count(), 1);
+            assert_eq!(pool.replicas()[0].total_count(), 0);
+
+            let second = pool.acquire_read(&cx, factory).await;
+            assert!(matches!(second, Outcome::Ok(_)));
+            assert_eq!(pool.replicas()[0].total_count(), 1);
+            assert_eq!(pool.replica_lag(0), Some(Duration::ZERO));
+        });
+    }
+
+    #[test]
+    fn test_reentrant_acquire_fails_fast() {
+        use asupersync::runtime::RuntimeBuilder;
+
+        let pool: Pool<MockConnection> = Pool::new(
+            PoolConfig::new(1)
+                .acquire_timeout(30_000)
+                .test_on_checkout(false),
+        );
+        let cx = Cx::for_testing();
+        let rt = RuntimeBuilder::current_thread()
+            .build()
+            .expect("create asupersync runtime");
+        rt.block_on(async {
+            let factory = || async { Outcome::Ok(MockConnection::new(1)) };
+            let held = pool.acquire(&cx, factory).await;
+            assert!(matches!(held, Outcome::Ok(_)));
+
+            let started = Instant::now();
+            match pool.acquire(&cx, factory).await {
+                Outcome::Err(Error::Pool(e)) => {
+                    assert_eq!(e.kind, PoolErrorKind::ReentrantAcquire);
+                    assert!(e.message.contains("holds all 1"));
+                }
+                _ => panic!("expected ReentrantAcquire"),
+            }
+            assert!(started.elapsed() < Duration::from_secs(1));
+            assert_eq!(pool.stats().pending_requests, 0);
+
+            drop(held);
+            assert!(matches!(pool.acquire(&cx, factory).await, Outcome::Ok(_)));
+        });
+        assert!(pool.shared.lock_or_recover().holders.is_empty());
+    }
+
+    #[test]
+    fn test_acquire_waits_when_other_tasks_hold_connections() {
+        use asupersync::runtime::RuntimeBuilder;
+
+        let pool: Pool<MockConnection> = Pool::new(
+            PoolConfig::new(2)
+                .acquire_timeout(5_000)
+                .test_on_checkout(false),
+        );
+        let cx = Cx::for_testing();
+        let other = Cx::for_request();
+        let rt = RuntimeBuilder::current_thread()
+            .build()
+            .expect("create asupersync runtime");
+        rt.block_on(async {
+            let factory = || async { Outcome::Ok(MockConnection::new(1)) };
+            let held = pool.acquire(&cx, factory).await;
+            assert!(matches!(held, Outcome::Ok(_)));
+            let Outcome::Ok(theirs) = pool.acquire(&other, factory).await else {
+                panic!("expected a connection");
+            };
+
+            // The other task's connection comes back, so this is a wait, not an error
+            let release = std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(50));
+                drop(theirs);
+            });
+            assert!(matches!(pool.acquire(&cx, factory).await, Outcome::Ok(_)));
+            release.join().expect("release thread");
+        });
+    }
+
+    #[test]
+    fn test_reload_config_drains_old_connections_gradually() {
+        use asupersync::runtime::RuntimeBuilder;
+
+        let pool: Pool<MockConnection> = Pool::new(PoolConfig::new(4).test_on_checkout(false));
+        let cx = Cx::for_testing();
+        let rt = RuntimeBuilder::current_thread()
+            .build()
+            .expect("create asupersync runtime");
+        rt.block_on(async {
+            let factory = || async { Outcome::Ok(MockConnection::new(1)) };
+            let a = pool.acquire(&cx, factory).await;
+            let b = pool.acquire(&cx, factory).await;
+            drop((a, b));
+            assert_eq!(pool.idle_count(), 2);
+
+            pool.reload_config(
+                PoolConfig::new(8).test_on_checkout(false),
+                Some(DrainPolicy::new(1, Duration::from_secs(3600))),
+            );
+            assert_eq!(pool.config().max_connections, 8);
+
+            // One old connection is closed; the budget keeps the other.
+            let reused = pool.acquire(&cx, factory).await;
+            assert!(matches!(reused, Outcome::Ok(_)));
+            assert_eq!(pool.total_count(), 1);
+            drop(reused);
+            assert_eq!(pool.idle_count(), 1);
+        });
+        assert_eq!(pool.stats().connections_closed, 1);
+    }
+
+    #[test]
+    fn test_pool_with_sqlcommenter_tags_statements() {
+        use asupersync::runtime::RuntimeBuilder;
+        use sqlmodel_core::connection::ConnectionConfig;
+        use sqlmodel_core::query_log::QueryLogEntry;
+
+        #[derive(Default)]
+        struct Collect(Mutex<Vec<String>>);
+
+        impl QueryLogger for Collect {
+            fn log(&self, entry: &QueryLogEntry<'_>) {
+                self.0.lock().unwrap().push(entry.sql.to_string());
+            }
+        }
+
+        let config = ConnectionConfig::new("postgres://localhost/app").application_name("billing");
+        let logger = Arc::new(Collect::default());
+        let pool: Pool<MockConnection> = Pool::new(PoolConfig::new(5))
+            .with_query_logger(logger.clone())
+            .with_sqlcommenter(
+                SqlCommenter::from_config(&config)
+                    .traceparent(|| Some("00-abc-def-01".to_string())),
+            );
+        {
+            let mut inner = pool.shared.inner.lock().unwrap();
+            inner.total_count = 1;
+            inner.active_count = 1;
+        }
+
+        let meta = ConnectionMeta::new(MockConnection::new(1));
+        let pooled = PooledConnection::new(meta, Arc::downgrade(&pool.shared));
+        let cx = Cx::for_testing();
+        let rt = RuntimeBuilder::current_thread()
+            .build()
+            .expect("create asupersync runtime");
+        rt.block_on(async {
+            let outcome = Connection::query(&pooled, &cx, "SELECT 1", &[]).await;
+            assert!(matches!(outcome, Outcome::Ok(_)));
+        });
+
+        let entries = logger.0.lock().unwrap();
+        assert_eq!(
+            *entries,
+            vec!["SELECT 1 /*application='billing',traceparent='00-abc-def-01'*/".to_string()]
+        );
+    }
+
     #[test]
     fn test_pooled_connection_drop_returns_to_pool() {
         let pool: Pool<MockConnection> = Pool::new(PoolConfig::new(5));
@@ -0,0 +1,266 @@
+//! Per-pool query concurrency limits and admission control.
+//!
+//! [`Pool::with_limits`](crate::Pool::with_limits) caps how many statements
+//! run at once on the pool's connections, independently of
+//! `max_connections`. Statements over the cap wait in a bounded FIFO queue;
+//! once that is full they fail fast with [`Error::Overloaded`] so services
+//! shed load instead of piling up latency:
+//!
+//! ```rust,ignore
+//! let pool = Pool::new(PoolConfig::new(20)).with_limits(QueryLimits::new(8, 32));
+//!
+//! match conn.query(&cx, sql, &params).await {
+//!     Outcome::Err(Error::Overloaded) => return Response::status(503),
+//!     other => other,
+//! }
+//! ```
+//!
+//! Statements run through a connection's transaction handle (`Tx`) are not
+//! counted.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::task::{Poll, Waker};
+
+use asupersync::{CancelReason, Cx, Outcome};
+use sqlmodel_core::Error;
+
+/// Concurrency limits for the statements of one pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryLimits {
+    /// Statements allowed to run at once
+    pub max_concurrent_queries: usize,
+    /// Statements allowed to wait for a slot before new ones are rejected
+    pub max_queue: usize,
+}
+
+impl QueryLimits {
+    /// Allow `max_concurrent_queries` statements at once and `max_queue`
+    /// more waiting.
+    #[must_use]
+    pub fn new(max_concurrent_queries: usize, max_queue: usize) -> Self {
+        Self {
+            max_concurrent_queries: max_concurrent_queries.max(1),
+            max_queue,
+        }
+    }
+}
+
+struct LimiterState {
+    running: usize,
+    /// Tickets of queued statements with their wakers, oldest first.
+    queue: VecDeque<(u64, Waker)>,
+    next_ticket: u64,
+}
+
+/// A FIFO semaphore over statements, shared by a pool's connections.
+pub(crate) struct QueryLimiter {
+    limits: QueryLimits,
+    state: Mutex<LimiterState>,
+    rejected: AtomicU64,
+}
+
+impl QueryLimiter {
+    pub(crate) fn new(limits: QueryLimits) -> Self {
+        Self {
+            limits,
+            state: Mutex::new(LimiterState {
+                running: 0,
+                queue: VecDeque::new(),
+                next_ticket: 0,
+            }),
+            rejected: AtomicU64::new(0),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, LimiterState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Statements running, statements queued, statements rejected so far.
+    pub(crate) fn counts(&self) -> (usize, usize, u64) {
+        let state = self.lock();
+        (
+            state.running,
+            state.queue.len(),
+            self.rejected.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Whether new statements are being rejected right now.
+    pub(crate) fn is_saturated(&self) -> bool {
+        let state = self.lock();
+        state.running >= self.limits.max_concurrent_queries
+            && state.queue.len() >= self.limits.max_queue
+    }
+
+    /// Run `statement` once a slot is free.
+    pub(crate) async fn run<T>(
+        &self,
+        cx: &Cx,
+        statement: impl Future<Output = Outcome<T, Error>>,
+    ) -> Outcome<T, Error> {
+        let _permit = match (Admission {
+            limiter: self,
+            cx,
+            ticket: None,
+        })
+        .await
+        {
+            Outcome::Ok(permit) => permit,
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        };
+        statement.await
+    }
+
+    fn wake_front(state: &LimiterState) {
+        if let Some((_, waker)) = state.queue.front() {
+            waker.wake_by_ref();
+        }
+    }
+}
+
+/// A running statement's slot, released on drop.
+struct Permit<'a> {
+    limiter: &'a QueryLimiter,
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        let mut state = self.limiter.lock();
+        state.running -= 1;
+        QueryLimiter::wake_front(&state);
+    }
+}
+
+/// Waits for a slot; dropping it while queued gives up its place.
+struct Admission<'a> {
+    limiter: &'a QueryLimiter,
+    cx: &'a Cx,
+    ticket: Option<u64>,
+}
+
+impl<'a> Future for Admission<'a> {
+    type Output = Outcome<Permit<'a>, Error>;
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        task: &mut std::task::Context<'_>,
+    ) -> Poll<Self::Output> {
+        let limiter = self.limiter;
+        let limits = limiter.limits;
+        let mut state = limiter.lock();
+
+        let Some(ticket) = self.ticket else {
+            if state.running < limits.max_concurrent_queries && state.queue.is_empty() {
+                state.running += 1;
+                return Poll::Ready(Outcome::Ok(Permit { limiter }));
+            }
+            if state.queue.len() >= limits.max_queue {
+                limiter.rejected.fetch_add(1, Ordering::Relaxed);
+                return Poll::Ready(Outcome::Err(Error::Overloaded));
+            }
+            let ticket = state.next_ticket;
+            state.next_ticket += 1;
+            state.queue.push_back((ticket, task.waker().clone()));
+            drop(state);
+            self.ticket = Some(ticket);
+            return Poll::Pending;
+        };
+
+        if self.cx.is_cancel_requested() {
+            state.queue.retain(|(queued, _)| *queued != ticket);
+            QueryLimiter::wake_front(&state);
+            drop(state);
+            self.ticket = None;
+            return Poll::Ready(Outcome::Cancelled(CancelReason::user(
+                "query admission cancelled",
+            )));
+        }
+        let at_front = state
+            .queue
+            .front()
+            .is_some_and(|(front, _)| *front == ticket);
+        if at_front && state.running < limits.max_concurrent_queries {
+            state.queue.pop_front();
+            state.running += 1;
+            // Another slot may still be free for the next in line.
+            if state.running < limits.max_concurrent_queries {
+                QueryLimiter::wake_front(&state);
+            }
+            drop(state);
+            self.ticket = None;
+            return Poll::Ready(Outcome::Ok(Permit { limiter }));
+        }
+        if let Some(entry) = state.queue.iter_mut().find(|(queued, _)| *queued == ticket) {
+            entry.1.clone_from(task.waker());
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for Admission<'_> {
+    fn drop(&mut self) {
+        if let Some(ticket) = self.ticket {
+            let mut state = self.limiter.lock();
+            state.queue.retain(|(queued, _)| *queued != ticket);
+            QueryLimiter::wake_front(&state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::pin::pin;
+    use std::sync::Arc;
+    use std::task::{Context, Wake};
+
+    struct Flag(std::sync::atomic::AtomicBool);
+
+    impl Wake for Flag {
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_limiter_queues_then_sheds() {
+        let limiter = QueryLimiter::new(QueryLimits::new(1, 1));
+        let cx = Cx::for_testing();
+        let flag = Arc::new(Flag(std::sync::atomic::AtomicBool::new(false)));
+        let waker = Waker::from(flag.clone());
+        let mut task = Context::from_waker(&waker);
+
+        let admit = || Admission {
+            limiter: &limiter,
+            cx: &cx,
+            ticket: None,
+        };
+        let mut first = pin!(admit());
+        let Poll::Ready(Outcome::Ok(permit)) = first.as_mut().poll(&mut task) else {
+            panic!("first statement should run immediately");
+        };
+
+        let mut second = pin!(admit());
+        assert!(second.as_mut().poll(&mut task).is_pending());
+        let mut third = pin!(admit());
+        assert!(matches!(
+            third.as_mut().poll(&mut task),
+            Poll::Ready(Outcome::Err(Error::Overloaded))
+        ));
+        assert_eq!(limiter.counts(), (1, 1, 1));
+
+        drop(permit);
+        assert!(flag.0.load(Ordering::Relaxed));
+        assert!(matches!(
+            second.as_mut().poll(&mut task),
+            Poll::Ready(Outcome::Ok(_))
+        ));
+        assert_eq!(limiter.counts(), (0, 0, 1));
+    }
+}
@@ -5,12 +5,23 @@
 
 use std::future::Future;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
 
 use asupersync::{Cx, Outcome};
-use sqlmodel_core::{Connection, Error};
+use sqlmodel_core::{Connection, Dialect, Error, Value};
 
 use crate::{Pool, PooledConnection};
 
+/// Default interval between lag measurements of a replica, and how long an
+/// unreachable replica stays evicted before it is probed again.
+pub const DEFAULT_PROBE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Replication lag in seconds; 0 on a server that is not replaying WAL.
+const POSTGRES_LAG_SQL: &str = "SELECT CASE \
+    WHEN NOT pg_is_in_recovery() OR pg_last_wal_receive_lsn() = pg_last_wal_replay_lsn() THEN 0 \
+    ELSE EXTRACT(EPOCH FROM now() - pg_last_xact_replay_timestamp()) END";
+
 /// Strategy for selecting which replica to use for reads.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ReplicaStrategy {
@@ -18,6 +29,23 @@ pub enum ReplicaStrategy {
     RoundRobin,
     /// Pick a random replica each time.
     Random,
+    /// Rotate through the replicas trailing the primary by at most this
+    /// much, falling back to the primary when none does.
+    ///
+    /// Lag is measured at most once per probe interval per replica
+    /// (`pg_last_xact_replay_timestamp()` on Postgres, `Seconds_Behind_Master`
+    /// from `SHOW SLAVE STATUS` on MySQL, always zero on SQLite). Replicas
+    /// that cannot be reached or measured are evicted for one interval and
+    /// then probed again by the next read.
+    MaxLag(Duration),
+}
+
+/// What the pool last learned about one replica.
+#[derive(Debug, Clone, Copy, Default)]
+struct ReplicaState {
+    lag: Option<Duration>,
+    measured_at: Option<Instant>,
+    evicted_until: Option<Instant>,
 }
 
 /// A pool that routes reads to replicas and writes to a primary.
@@ -46,17 +74,16 @@ pub struct ReplicaPool<C: Connection> {
     strategy: ReplicaStrategy,
     /// Counter for round-robin selection.
     round_robin_counter: AtomicUsize,
+    /// How often lag is re-measured under [`ReplicaStrategy::MaxLag`].
+    probe_interval: Duration,
+    /// Lag and eviction state, one entry per replica.
+    states: Mutex<Vec<ReplicaState>>,
 }
 
 impl<C: Connection> ReplicaPool<C> {
     /// Create a new replica pool with round-robin strategy.
     pub fn new(primary: Pool<C>, replicas: Vec<Pool<C>>) -> Self {
-        Self {
-            primary,
-            replicas,
-            strategy: ReplicaStrategy::RoundRobin,
-            round_robin_counter: AtomicUsize::new(0),
-        }
+        Self::with_strategy(primary, replicas, ReplicaStrategy::RoundRobin)
     }
 
     /// Create a new replica pool with a specific strategy.
@@ -65,14 +92,25 @@ impl<C: Connection> ReplicaPool<C> {
         replicas: Vec<Pool<C>>,
         strategy: ReplicaStrategy,
     ) -> Self {
+        let states = vec![ReplicaState::default(); replicas.len()];
         Self {
             primary,
             replicas,
             strategy,
             round_robin_counter: AtomicUsize::new(0),
+            probe_interval: DEFAULT_PROBE_INTERVAL,
+            states: Mutex::new(states),
         }
     }
 
+    /// Set how often replica lag is re-measured and how long unreachable
+    /// replicas stay evicted (default [`DEFAULT_PROBE_INTERVAL`]).
+    #[must_use]
+    pub fn with_probe_interval(mut self, interval: Duration) -> Self {
+        self.probe_interval = interval;
+        self
+    }
+
     /// Acquire a connection for read operations.
     ///
     /// If replicas are available, selects one based on the configured strategy.
@@ -89,11 +127,81 @@ impl<C: Connection> ReplicaPool<C> {
         if self.replicas.is_empty() {
             return self.primary.acquire(cx, factory).await;
         }
+        if let ReplicaStrategy::MaxLag(max_lag) = self.strategy {
+            return self.acquire_within_lag(cx, factory, max_lag).await;
+        }
 
         let idx = self.select_replica();
         self.replicas[idx].acquire(cx, factory).await
     }
 
+    /// Acquire from the next replica within `max_lag`, or from the primary.
+    async fn acquire_within_lag<F, Fut>(
+        &self,
+        cx: &Cx,
+        factory: F,
+        max_lag: Duration,
+    ) -> Outcome<PooledConnection<C>, Error>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Outcome<C, Error>>,
+    {
+        let start = self.round_robin_counter.fetch_add(1, Ordering::Relaxed);
+        for offset in 0..self.replicas.len() {
+            let idx = (start + offset) % self.replicas.len();
+            let now = Instant::now();
+            let state = self.states()[idx];
+            if state.evicted_until.is_some_and(|until| now < until) {
+                continue;
+            }
+            // Skip a replica already known to lag without checking it out.
+            let fresh = state
+                .measured_at
+                .is_some_and(|at| now.duration_since(at) < self.probe_interval);
+            if fresh && state.lag.is_some_and(|lag| lag > max_lag) {
+                continue;
+            }
+
+            let conn = match self.replicas[idx].acquire(cx, &factory).await {
+                Outcome::Ok(conn) => conn,
+                Outcome::Err(e) => {
+                    tracing::warn!(replica = idx, error = %e, "evicting unreachable replica");
+                    self.evict(idx, now);
+                    continue;
+                }
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            };
+            if fresh {
+                return Outcome::Ok(conn);
+            }
+            let lag = match measure_lag(cx, &conn).await {
+                Outcome::Ok(lag) => lag,
+                Outcome::Err(e) => {
+                    tracing::warn!(
+                        replica = idx,
+                        error = %e,
+                        "evicting replica after failed lag probe"
+                    );
+                    drop(conn.detach());
+                    self.evict(idx, now);
+                    continue;
+                }
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            };
+            self.states()[idx] = ReplicaState {
+                lag: Some(lag),
+                measured_at: Some(now),
+                evicted_until: None,
+            };
+            if lag <= max_lag {
+                return Outcome::Ok(conn);
+            }
+        }
+        self.primary.acquire(cx, factory).await
+    }
+
     /// Acquire a connection for write operations (always uses primary).
     pub async fn acquire_write<F, Fut>(
         &self,
@@ -140,9 +248,30 @@ impl<C: Connection> ReplicaPool<C> {
         self.strategy
     }
 
+    /// The last measured replication lag of replica `index`, if any.
+    pub fn replica_lag(&self, index: usize) -> Option<Duration> {
+        self.states().get(index).and_then(|state| state.lag)
+    }
+
+    /// Whether replica `index` is currently evicted from read routing.
+    pub fn is_evicted(&self, index: usize) -> bool {
+        self.states()
+            .get(index)
+            .and_then(|state| state.evicted_until)
+            .is_some_and(|until| Instant::now() < until)
+    }
+
+    fn states(&self) -> MutexGuard<'_, Vec<ReplicaState>> {
+        self.states.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn evict(&self, idx: usize, now: Instant) {
+        self.states()[idx].evicted_until = Some(now + self.probe_interval);
+    }
+
     fn select_replica(&self) -> usize {
         match self.strategy {
-            ReplicaStrategy::RoundRobin => {
+            ReplicaStrategy::RoundRobin | ReplicaStrategy::MaxLag(_) => {
                 let idx = self.round_robin_counter.fetch_add(1, Ordering::Relaxed);
                 idx % self.replicas.len()
             }
@@ -167,10 +296,50 @@ impl<C: Connection> std::fmt::Debug for ReplicaPool<C> {
             .field("primary", &"Pool { .. }")
             .field("replicas", &self.replicas.len())
             .field("strategy", &self.strategy)
+            .field("probe_interval", &self.probe_interval)
             .field(
                 "round_robin_counter",
                 &self.round_robin_counter.load(Ordering::Relaxed),
             )
-            .finish()
+            .finish_non_exhaustive()
+    }
+}
+
+/// Measure how far the server behind `conn` trails its primary.
+///
+/// A replica whose replication is stopped reports [`Duration::MAX`].
+async fn measure_lag<C: Connection>(cx: &Cx, conn: &C) -> Outcome<Duration, Error> {
+    let (sql, column) = match conn.dialect() {
+        Dialect::Postgres => (POSTGRES_LAG_SQL, None),
+        Dialect::Mysql => ("SHOW SLAVE STATUS", Some("Seconds_Behind_Master")),
+        Dialect::Sqlite => return Outcome::Ok(Duration::ZERO),
+    };
+    let row = match conn.query_one(cx, sql, &[]).await {
+        Outcome::Ok(row) => row,
+        Outcome::Err(e) => return Outcome::Err(e),
+        Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+        Outcome::Panicked(p) => return Outcome::Panicked(p),
+    };
+    // No replication status means the server is not a replica.
+    let Some(row) = row else {
+        return Outcome::Ok(Duration::ZERO);
+    };
+    let value = match column {
+        Some(name) => row.get_by_name(name),
+        None => row.get(0),
+    };
+    let seconds = match value {
+        None | Some(Value::Null) => return Outcome::Ok(Duration::MAX),
+        Some(value) => value
+            .as_f64()
+            .or_else(|| value.as_str().and_then(|text| text.parse().ok())),
+    };
+    match seconds {
+        Some(seconds) => {
+            Outcome::Ok(Duration::try_from_secs_f64(seconds.max(0.0)).unwrap_or(Duration::MAX))
+        }
+        None => Outcome::Err(Error::Custom(format!(
+            "unexpected replication lag value: {value:?}"
+        ))),
     }
 }
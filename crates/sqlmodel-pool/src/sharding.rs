@@ -202,8 +202,8 @@ pub trait ShardChooser: Send + Sync {
 /// `shard_0`, `shard_1`, etc.
 ///
 /// This is suitable for evenly distributed numeric keys (e.g., auto-increment IDs).
-/// Not suitable for sequential inserts (hotspotting on latest shard) or
-/// non-numeric keys.
+/// Not suitable for sequential inserts (hotspotting on latest shard).
+/// Non-numeric keys are routed by [`Value::stable_hash`].
 #[derive(Debug, Clone)]
 pub struct ModuloShardChooser {
     shard_count: usize,
@@ -251,14 +251,10 @@ impl ModuloShardChooser {
             Value::BigInt(n) => (*n).unsigned_abs() as usize,
             Value::Int(n) => (*n).unsigned_abs() as usize,
             Value::SmallInt(n) => (*n).unsigned_abs() as usize,
-            Value::Text(s) => {
-                // Hash the string for non-numeric keys
-                use std::hash::{Hash, Hasher};
-                let mut hasher = std::collections::hash_map::DefaultHasher::new();
-                s.hash(&mut hasher);
-                hasher.finish() as usize
-            }
-            _ => 0,
+            Value::Null => 0,
+            // Other keys (text, UUIDs, bytes, ...) route by the fixed FNV-1a
+            // hash the session uses for identity-map keys.
+            other => other.stable_hash() as usize,
         }
     }
 }
@@ -655,6 +651,23 @@ mod tests {
 
         // Different strings may hash to same or different shards
         let _ = chooser.choose_for_model(&Value::Text("user_xyz".to_string()));
+
+        let key = Value::Text("user_abc".to_string());
+        assert_eq!(shard1, format!("shard_{}", key.stable_hash() % 3));
+    }
+
+    #[test]
+    fn test_modulo_shard_chooser_uuid_spreads_across_shards() {
+        let chooser = ModuloShardChooser::new(4);
+
+        let shards: std::collections::HashSet<String> = (0u8..32)
+            .map(|i| chooser.choose_for_model(&Value::Uuid([i; 16])))
+            .collect();
+        assert!(shards.len() > 1);
+        assert_eq!(
+            chooser.choose_for_model(&Value::Uuid([7; 16])),
+            format!("shard_{}", Value::Uuid([7; 16]).stable_hash() % 4)
+        );
     }
 
     #[test]
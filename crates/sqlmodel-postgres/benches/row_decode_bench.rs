@@ -0,0 +1,177 @@
+//! Row decoding benchmarks: eager vs lazy per-column decoding.
+//!
+//! Simulates a wide (32 column) text-format result set and reads only two
+//! columns per row, which is the common case for list endpoints that select
+//! `*` but render a couple of fields.
+//!
+//! # Running Benchmarks
+//!
+//! ```bash
+//! cargo bench -p sqlmodel-postgres --bench row_decode_bench
+//! ```
+//!
+//! Besides timings, each benchmark prints the number of heap allocations
+//! performed per row (counted with a wrapping global allocator), which is the
+//! metric lazy decoding is designed to reduce.
+
+#![feature(test)]
+#![allow(unsafe_code)]
+
+extern crate test;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use test::{Bencher, black_box};
+
+use sqlmodel_core::Row;
+use sqlmodel_core::row::{ColumnDecoder, ColumnInfo};
+use sqlmodel_postgres::types::{Format, PgColumnDecoder, decode_value, oid};
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        // SAFETY: forwarded unchanged to the system allocator.
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: forwarded unchanged to the system allocator.
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const COLUMNS: usize = 32;
+const ROWS: usize = 100;
+
+fn column_oids() -> Vec<u32> {
+    (0..COLUMNS)
+        .map(|i| match i % 3 {
+            0 => oid::INT8,
+            1 => oid::TEXT,
+            _ => oid::FLOAT8,
+        })
+        .collect()
+}
+
+fn raw_rows() -> Vec<Vec<Option<Vec<u8>>>> {
+    let oids = column_oids();
+    (0..ROWS)
+        .map(|r| {
+            oids.iter()
+                .enumerate()
+                .map(|(c, oid)| {
+                    let text = match *oid {
+                        oid::INT8 => format!("{}", r * COLUMNS + c),
+                        oid::TEXT => format!("some moderately long text value {r}/{c}"),
+                        _ => format!("{}.5", r + c),
+                    };
+                    Some(text.into_bytes())
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn column_info() -> Arc<ColumnInfo> {
+    Arc::new(ColumnInfo::new(
+        (0..COLUMNS).map(|i| format!("col_{i}")).collect(),
+    ))
+}
+
+fn eager_rows(columns: &Arc<ColumnInfo>, raw: Vec<Vec<Option<Vec<u8>>>>) -> Vec<Row> {
+    let oids = column_oids();
+    raw.into_iter()
+        .map(|values| {
+            let decoded = values
+                .iter()
+                .zip(&oids)
+                .map(|(v, oid)| decode_value(*oid, v.as_deref(), Format::Text).unwrap())
+                .collect();
+            Row::with_columns(Arc::clone(columns), decoded)
+        })
+        .collect()
+}
+
+fn lazy_rows(columns: &Arc<ColumnInfo>, raw: Vec<Vec<Option<Vec<u8>>>>) -> Vec<Row> {
+    let decoder: Arc<dyn ColumnDecoder> = Arc::new(PgColumnDecoder::new(
+        column_oids()
+            .into_iter()
+            .map(|o| (o, Format::Text))
+            .collect(),
+    ));
+    raw.into_iter()
+        .map(|values| Row::lazy(Arc::clone(columns), values, Arc::clone(&decoder)))
+        .collect()
+}
+
+fn read_two_columns(rows: &[Row]) -> usize {
+    rows.iter()
+        .map(|row| {
+            let id: i64 = row.try_get_ref("col_0").unwrap();
+            let name: &str = row.try_get_ref("col_1").unwrap();
+            usize::try_from(id).unwrap() + name.len()
+        })
+        .sum()
+}
+
+fn report(label: &str, f: impl Fn() -> usize) {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(f());
+    let after = ALLOCATIONS.load(Ordering::Relaxed);
+    eprintln!(
+        "{label}: {:.1} allocations/row",
+        (after - before) as f64 / ROWS as f64
+    );
+}
+
+// ============================================================================
+// Wide Row, Narrow Read
+// ============================================================================
+
+#[bench]
+fn bench_eager_decode_read_two_columns(b: &mut Bencher) {
+    let columns = column_info();
+    let template = raw_rows();
+    report("eager", || {
+        read_two_columns(&eager_rows(&columns, template.clone()))
+    });
+    b.iter(|| {
+        let rows = eager_rows(&columns, black_box(template.clone()));
+        black_box(read_two_columns(&rows))
+    });
+}
+
+#[bench]
+fn bench_lazy_decode_read_two_columns(b: &mut Bencher) {
+    let columns = column_info();
+    let template = raw_rows();
+    report("lazy", || {
+        read_two_columns(&lazy_rows(&columns, template.clone()))
+    });
+    b.iter(|| {
+        let rows = lazy_rows(&columns, black_box(template.clone()));
+        black_box(read_two_columns(&rows))
+    });
+}
+
+// ============================================================================
+// Wide Row, Full Read
+// ============================================================================
+
+#[bench]
+fn bench_lazy_decode_read_all_columns(b: &mut Bencher) {
+    let columns = column_info();
+    let template = raw_rows();
+    b.iter(|| {
+        let rows = lazy_rows(&columns, black_box(template.clone()));
+        black_box(rows.iter().map(|r| r.values().count()).sum::<usize>())
+    });
+}
@@ -26,21 +26,27 @@ use asupersync::net::TcpStream;
 use asupersync::sync::Mutex;
 use asupersync::{Cx, Outcome};
 
-use sqlmodel_core::connection::{Connection, IsolationLevel, PreparedStatement, TransactionOps};
+use sqlmodel_core::connection::{
+    Connection, IsolationLevel, PreparedStatement, QueryResult, ResultColumn, TransactionOps,
+};
+use sqlmodel_core::credentials::CredentialsProvider;
 use sqlmodel_core::error::{
     ConnectionError, ConnectionErrorKind, ProtocolError, QueryError, QueryErrorKind,
 };
-use sqlmodel_core::row::ColumnInfo;
+use sqlmodel_core::row::{ColumnDecoder, ColumnInfo};
 use sqlmodel_core::{Error, Row, Value};
 
 use crate::auth::ScramClient;
-use crate::config::{PgConfig, SslMode};
+use crate::config::{PgConfig, SslMode, TargetSessionAttrs};
 use crate::connection::{ConnectionState, TransactionStatusState};
 use crate::protocol::{
     BackendMessage, DescribeKind, ErrorFields, FrontendMessage, MessageReader, MessageWriter,
     PROTOCOL_VERSION,
 };
-use crate::types::{Format, decode_value, encode_value};
+use crate::types::{
+    Format, PgColumnDecoder, coerce_param, decode_value, encode_value, oid, param_format,
+    supports_binary,
+};
 
 #[cfg(feature = "tls")]
 use crate::tls;
@@ -302,6 +308,66 @@ pub struct PgAsyncConnection {
 struct PgPreparedMeta {
     name: String,
     param_type_oids: Vec<u32>,
+    /// Per-column result format codes for Bind; empty means all text.
+    result_formats: Vec<i16>,
+}
+
+/// Parameters encoded for a Bind message.
+struct EncodedParams {
+    type_oids: Vec<u32>,
+    values: Vec<Option<Vec<u8>>>,
+    formats: Vec<i16>,
+}
+
+/// Encode `params`, sending each in binary where [`param_format`] allows
+/// and `binary` is set.
+fn encode_params(params: &[Value], binary: bool) -> Result<EncodedParams, Error> {
+    let mut encoded = EncodedParams {
+        type_oids: Vec::with_capacity(params.len()),
+        values: Vec::with_capacity(params.len()),
+        formats: Vec::with_capacity(params.len()),
+    };
+    for v in params {
+        if matches!(v, Value::Null) {
+            encoded.type_oids.push(0);
+            encoded.values.push(None);
+            encoded.formats.push(Format::Text.code());
+            continue;
+        }
+        let format = if binary {
+            param_format(v)
+        } else {
+            Format::Text
+        };
+        let (bytes, oid) = encode_value(v, format)?;
+        encoded.type_oids.push(oid);
+        encoded.values.push(Some(bytes));
+        encoded.formats.push(format.code());
+    }
+    // A single format code applies to every parameter.
+    if encoded.formats.iter().all(|&f| f == Format::Text.code()) {
+        encoded.formats.truncate(1);
+    }
+    Ok(encoded)
+}
+
+/// Per-column result formats: binary where [`supports_binary`] allows.
+fn result_formats_for(desc: &[crate::protocol::FieldDescription]) -> Vec<i16> {
+    let formats: Vec<i16> = desc
+        .iter()
+        .map(|f| {
+            if supports_binary(f.type_oid) {
+                Format::Binary.code()
+            } else {
+                Format::Text.code()
+            }
+        })
+        .collect();
+    if formats.iter().all(|&f| f == Format::Text.code()) {
+        Vec::new()
+    } else {
+        formats
+    }
 }
 
 impl std::fmt::Debug for PgAsyncConnection {
@@ -318,20 +384,120 @@ impl std::fmt::Debug for PgAsyncConnection {
 
 impl PgAsyncConnection {
     /// Establish a new async connection to the PostgreSQL server.
-    pub async fn connect(_cx: &Cx, config: PgConfig) -> Outcome<Self, Error> {
-        let addr = config.socket_addr();
-        let socket_addr = match addr.parse() {
-            Ok(a) => a,
-            Err(e) => {
-                return Outcome::Err(Error::Connection(ConnectionError {
-                    kind: ConnectionErrorKind::Connect,
-                    message: format!("Invalid socket address: {}", e),
-                    source: None,
-                }));
+    ///
+    /// With several hosts configured they are tried in order until one
+    /// accepts the connection and satisfies
+    /// [`target_session_attrs`](PgConfig::target_session_attrs), checked with
+    /// `SHOW transaction_read_only`. Pools calling this from their factory
+    /// therefore follow the primary after a failover.
+    pub async fn connect(cx: &Cx, config: PgConfig) -> Outcome<Self, Error> {
+        let hosts = config.hosts();
+        if hosts.len() <= 1 && config.target_session_attrs == TargetSessionAttrs::Any {
+            return Self::connect_host(cx, config).await;
+        }
+
+        let mut last_error = None;
+        for (host, port) in &hosts {
+            let mut conn = match Self::connect_host(cx, config.for_host(host, *port)).await {
+                Outcome::Ok(conn) => conn,
+                Outcome::Err(e) => {
+                    last_error = Some(e);
+                    continue;
+                }
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            };
+            let read_only = match conn.is_read_only(cx).await {
+                Outcome::Ok(read_only) => read_only,
+                Outcome::Err(e) => {
+                    last_error = Some(e);
+                    continue;
+                }
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            };
+            if config.target_session_attrs.accepts(read_only) {
+                return Outcome::Ok(conn);
             }
+            let _ = conn.close_async(cx).await;
+            last_error = Some(Error::Connection(ConnectionError {
+                kind: ConnectionErrorKind::Connect,
+                message: format!(
+                    "{host}:{port} is {}, not {}",
+                    if read_only { "read-only" } else { "read-write" },
+                    config.target_session_attrs.as_str()
+                ),
+                source: None,
+            }));
+        }
+
+        let message = format!(
+            "no host of {} accepted a {} session",
+            config.host,
+            config.target_session_attrs.as_str()
+        );
+        Outcome::Err(Error::Connection(ConnectionError {
+            kind: ConnectionErrorKind::Connect,
+            message: match &last_error {
+                Some(e) => format!("{message}; last error: {e}"),
+                None => message,
+            },
+            source: last_error.map(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>),
+        }))
+    }
+
+    /// Whether the server only accepts read-only transactions, as a hot
+    /// standby does.
+    async fn is_read_only(&mut self, cx: &Cx) -> Outcome<bool, Error> {
+        let rows = match self
+            .query_async(cx, "SHOW transaction_read_only", &[])
+            .await
+        {
+            Outcome::Ok(rows) => rows,
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        };
+        let value = rows.first().and_then(|row| row.get(0));
+        Outcome::Ok(value.and_then(Value::as_str) == Some("on"))
+    }
+
+    /// Fetch credentials from `provider`, then [`connect`](Self::connect).
+    ///
+    /// Use this in a pool's connection factory so every new connection
+    /// authenticates with current credentials (e.g. a fresh IAM token).
+    pub async fn connect_with_credentials(
+        cx: &Cx,
+        config: PgConfig,
+        provider: &dyn CredentialsProvider,
+    ) -> Outcome<Self, Error> {
+        match provider.fetch(cx).await {
+            Outcome::Ok(credentials) => Self::connect(cx, config.credentials(credentials)).await,
+            Outcome::Err(e) => Outcome::Err(e),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
+    /// Connect to the single host of `config`.
+    async fn connect_host(_cx: &Cx, config: PgConfig) -> Outcome<Self, Error> {
+        let addr = config.socket_addr();
+        let socket_addrs = match config.socket_addrs() {
+            Ok(addrs) => addrs,
+            Err(e) => return Outcome::Err(e),
         };
 
-        let stream = match TcpStream::connect_timeout(socket_addr, config.connect_timeout).await {
+        let mut connected = Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "host resolved to no addresses",
+        ));
+        for socket_addr in socket_addrs {
+            connected = TcpStream::connect_timeout(socket_addr, config.connect_timeout).await;
+            if connected.is_ok() {
+                break;
+            }
+        }
+        let stream = match connected {
             Ok(s) => s,
             Err(e) => {
                 let kind = if e.kind() == std::io::ErrorKind::ConnectionRefused {
@@ -417,6 +583,24 @@ impl PgAsyncConnection {
         }
     }
 
+    /// Execute a query and return its rows with column metadata.
+    pub async fn query_raw_async(
+        &mut self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> Outcome<QueryResult, Error> {
+        match self.run_extended(cx, sql, params).await {
+            Outcome::Ok(result) => Outcome::Ok(QueryResult {
+                columns: result.columns,
+                rows: result.rows,
+            }),
+            Outcome::Err(e) => Outcome::Err(e),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
     /// Execute a statement and return rows affected.
     pub async fn execute_async(
         &mut self,
@@ -532,6 +716,7 @@ impl PgAsyncConnection {
 
         let mut param_type_oids: Option<Vec<u32>> = None;
         let mut columns: Option<Vec<String>> = None;
+        let mut result_formats = Vec::new();
 
         loop {
             let msg = match self.receive_message(cx).await {
@@ -552,6 +737,9 @@ impl PgAsyncConnection {
                 }
                 BackendMessage::RowDescription(desc) => {
                     columns = Some(desc.iter().map(|f| f.name.clone()).collect());
+                    if self.config.binary_protocol {
+                        result_formats = result_formats_for(&desc);
+                    }
                 }
                 BackendMessage::ReadyForQuery(status) => {
                     self.state = ConnectionState::Ready(TransactionStatusState::from(status));
@@ -576,6 +764,7 @@ impl PgAsyncConnection {
             PgPreparedMeta {
                 name: stmt_name,
                 param_type_oids: param_type_oids.clone(),
+                result_formats,
             },
         );
 
@@ -666,12 +855,229 @@ impl PgAsyncConnection {
         }
     }
 
+    // ==================== COPY ====================
+
+    /// Start a `COPY ... TO STDOUT` statement.
+    ///
+    /// Read the data with [`copy_out_next`](Self::copy_out_next) until it
+    /// returns `None`; the connection is busy until then.
+    pub async fn copy_out_start(&mut self, cx: &Cx, sql: &str) -> Outcome<(), Error> {
+        match self.start_copy(cx, sql, CopyDirection::Out).await {
+            Outcome::Ok(()) => Outcome::Ok(()),
+            Outcome::Err(e) => Outcome::Err(with_sql(e, sql)),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
+    /// Next chunk of `COPY ... TO STDOUT` data, or `None` once the copy is done.
+    ///
+    /// In text and CSV formats each chunk is one row.
+    pub async fn copy_out_next(&mut self, cx: &Cx) -> Outcome<Option<Vec<u8>>, Error> {
+        loop {
+            let msg = match self.receive_message(cx).await {
+                Outcome::Ok(m) => m,
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            };
+            match msg {
+                BackendMessage::CopyData(data) => return Outcome::Ok(Some(data)),
+                BackendMessage::CopyDone => {
+                    return match self.read_copy_result(cx).await {
+                        Outcome::Ok(_) => Outcome::Ok(None),
+                        Outcome::Err(e) => Outcome::Err(e),
+                        Outcome::Cancelled(r) => Outcome::Cancelled(r),
+                        Outcome::Panicked(p) => Outcome::Panicked(p),
+                    };
+                }
+                BackendMessage::ErrorResponse(e) => {
+                    let error = error_from_fields(&e);
+                    return match self.read_copy_result(cx).await {
+                        Outcome::Cancelled(r) => Outcome::Cancelled(r),
+                        Outcome::Panicked(p) => Outcome::Panicked(p),
+                        Outcome::Ok(_) | Outcome::Err(_) => Outcome::Err(error),
+                    };
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Start a `COPY ... FROM STDIN` statement.
+    ///
+    /// Send the data with [`copy_in_send`](Self::copy_in_send), then end it
+    /// with [`copy_in_finish`](Self::copy_in_finish) or
+    /// [`copy_in_abort`](Self::copy_in_abort).
+    pub async fn copy_in_start(&mut self, cx: &Cx, sql: &str) -> Outcome<(), Error> {
+        match self.start_copy(cx, sql, CopyDirection::In).await {
+            Outcome::Ok(()) => Outcome::Ok(()),
+            Outcome::Err(e) => Outcome::Err(with_sql(e, sql)),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
+    /// Send a chunk of `COPY ... FROM STDIN` data.
+    ///
+    /// Chunks need not line up with rows; the server reassembles the stream.
+    pub async fn copy_in_send(&mut self, cx: &Cx, data: Vec<u8>) -> Outcome<(), Error> {
+        self.send_message(cx, &FrontendMessage::CopyData(data))
+            .await
+    }
+
+    /// Finish `COPY ... FROM STDIN` and return the number of rows copied.
+    pub async fn copy_in_finish(&mut self, cx: &Cx) -> Outcome<u64, Error> {
+        match self.send_message(cx, &FrontendMessage::CopyDone).await {
+            Outcome::Ok(()) => {}
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        }
+        self.read_copy_result(cx)
+            .await
+            .map(|tag| parse_rows_affected(tag.as_deref()).unwrap_or(0))
+    }
+
+    /// Abort `COPY ... FROM STDIN`; the server discards every row sent so far.
+    pub async fn copy_in_abort(&mut self, cx: &Cx, reason: &str) -> Outcome<(), Error> {
+        match self
+            .send_message(cx, &FrontendMessage::CopyFail(reason.to_string()))
+            .await
+        {
+            Outcome::Ok(()) => {}
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        }
+        // The server answers CopyFail with an error; that error is the point.
+        match self.read_copy_result(cx).await {
+            Outcome::Ok(_) | Outcome::Err(_) => Outcome::Ok(()),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
+    /// Send a simple-protocol COPY and wait for the matching copy response.
+    pub(crate) async fn start_copy(
+        &mut self,
+        cx: &Cx,
+        sql: &str,
+        direction: CopyDirection,
+    ) -> Outcome<(), Error> {
+        match self
+            .send_message(cx, &FrontendMessage::Query(sql.to_string()))
+            .await
+        {
+            Outcome::Ok(()) => {}
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        }
+        loop {
+            let msg = match self.receive_message(cx).await {
+                Outcome::Ok(m) => m,
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            };
+            match msg {
+                BackendMessage::CopyInResponse { .. } if direction == CopyDirection::In => {
+                    return Outcome::Ok(());
+                }
+                BackendMessage::CopyOutResponse { .. } if direction == CopyDirection::Out => {
+                    return Outcome::Ok(());
+                }
+                BackendMessage::CopyBothResponse { .. } if direction == CopyDirection::Both => {
+                    return Outcome::Ok(());
+                }
+                BackendMessage::ErrorResponse(e) => {
+                    let error = error_from_fields(&e);
+                    return match self.read_copy_result(cx).await {
+                        Outcome::Cancelled(r) => Outcome::Cancelled(r),
+                        Outcome::Panicked(p) => Outcome::Panicked(p),
+                        Outcome::Ok(_) | Outcome::Err(_) => Outcome::Err(error),
+                    };
+                }
+                BackendMessage::ReadyForQuery(status) => {
+                    self.state = ConnectionState::Ready(TransactionStatusState::from(status));
+                    return Outcome::Err(protocol_error(match direction {
+                        CopyDirection::In => "statement is not a COPY ... FROM STDIN",
+                        CopyDirection::Out => "statement is not a COPY ... TO STDOUT",
+                        CopyDirection::Both => "statement did not start a replication stream",
+                    }));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Run `sql` over the simple query protocol and return its command tag.
+    ///
+    /// Result rows are discarded. Replication commands only work over this
+    /// protocol.
+    #[cfg_attr(not(feature = "cdc"), allow(dead_code))]
+    pub(crate) async fn simple_query(
+        &mut self,
+        cx: &Cx,
+        sql: &str,
+    ) -> Outcome<Option<String>, Error> {
+        match self
+            .send_message(cx, &FrontendMessage::Query(sql.to_string()))
+            .await
+        {
+            Outcome::Ok(()) => {}
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        }
+        match self.read_copy_result(cx).await {
+            Outcome::Ok(tag) => Outcome::Ok(tag),
+            Outcome::Err(e) => Outcome::Err(with_sql(e, sql)),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
+    /// Read the rest of a simple-protocol or COPY exchange up to ReadyForQuery.
+    ///
+    /// Returns the command tag, or the first error the server reported once
+    /// the connection is ready for the next query.
+    async fn read_copy_result(&mut self, cx: &Cx) -> Outcome<Option<String>, Error> {
+        let mut command_tag = None;
+        let mut error = None;
+        loop {
+            let msg = match self.receive_message(cx).await {
+                Outcome::Ok(m) => m,
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            };
+            match msg {
+                BackendMessage::CommandComplete(tag) => command_tag = Some(tag),
+                BackendMessage::ErrorResponse(e) => {
+                    error.get_or_insert_with(|| error_from_fields(&e));
+                }
+                BackendMessage::ReadyForQuery(status) => {
+                    self.state = ConnectionState::Ready(TransactionStatusState::from(status));
+                    return match error {
+                        Some(e) => Outcome::Err(e),
+                        None => Outcome::Ok(command_tag),
+                    };
+                }
+                _ => {}
+            }
+        }
+    }
+
     // ==================== Protocol: extended query ====================
 
     async fn read_extended_result(&mut self, cx: &Cx) -> Outcome<PgQueryResult, Error> {
         // Read responses until ReadyForQuery
         let mut field_descs: Option<Vec<crate::protocol::FieldDescription>> = None;
         let mut columns: Option<Arc<ColumnInfo>> = None;
+        let mut result_columns: Vec<ResultColumn> = Vec::new();
+        let mut lazy_decoder: Option<Arc<PgColumnDecoder>> = None;
         let mut rows: Vec<Row> = Vec::new();
         let mut command_tag: Option<String> = None;
 
@@ -694,6 +1100,8 @@ impl PgAsyncConnection {
                 BackendMessage::RowDescription(desc) => {
                     let names: Vec<String> = desc.iter().map(|f| f.name.clone()).collect();
                     columns = Some(Arc::new(ColumnInfo::new(names)));
+                    result_columns = desc.iter().map(result_column).collect();
+                    lazy_decoder = None;
                     field_descs = Some(desc);
                 }
                 BackendMessage::DataRow(raw_values) => {
@@ -709,6 +1117,22 @@ impl PgAsyncConnection {
                         return Outcome::Err(protocol_error("DataRow field count mismatch"));
                     }
 
+                    if self.config.lazy_decoding {
+                        let decoder = lazy_decoder.get_or_insert_with(|| {
+                            Arc::new(PgColumnDecoder::new(
+                                desc.iter()
+                                    .map(|f| (f.type_oid, Format::from_code(f.format)))
+                                    .collect(),
+                            ))
+                        });
+                        rows.push(Row::lazy(
+                            Arc::clone(cols),
+                            raw_values,
+                            Arc::clone(decoder) as Arc<dyn ColumnDecoder>,
+                        ));
+                        continue;
+                    }
+
                     let mut values = Vec::with_capacity(raw_values.len());
                     for (i, raw) in raw_values.into_iter().enumerate() {
                         match raw {
@@ -746,7 +1170,11 @@ impl PgAsyncConnection {
             }
         }
 
-        Outcome::Ok(PgQueryResult { rows, command_tag })
+        Outcome::Ok(PgQueryResult {
+            columns: result_columns,
+            rows,
+            command_tag,
+        })
     }
 
     async fn run_extended(
@@ -755,24 +1183,10 @@ impl PgAsyncConnection {
         sql: &str,
         params: &[Value],
     ) -> Outcome<PgQueryResult, Error> {
-        // Encode parameters
-        let mut param_types = Vec::with_capacity(params.len());
-        let mut param_values = Vec::with_capacity(params.len());
-
-        for v in params {
-            if matches!(v, Value::Null) {
-                param_types.push(0);
-                param_values.push(None);
-                continue;
-            }
-            match encode_value(v, Format::Text) {
-                Ok((bytes, oid)) => {
-                    param_types.push(oid);
-                    param_values.push(Some(bytes));
-                }
-                Err(e) => return Outcome::Err(e),
-            }
-        }
+        let encoded = match encode_params(params, self.config.binary_protocol) {
+            Ok(encoded) => encoded,
+            Err(e) => return Outcome::Err(e),
+        };
 
         // Parse + bind unnamed statement/portal
         if let Outcome::Err(e) = self
@@ -781,7 +1195,7 @@ impl PgAsyncConnection {
                 &FrontendMessage::Parse {
                     name: String::new(),
                     query: sql.to_string(),
-                    param_types,
+                    param_types: encoded.type_oids,
                 },
             )
             .await
@@ -789,20 +1203,16 @@ impl PgAsyncConnection {
             return Outcome::Err(e);
         }
 
-        let param_formats = if params.is_empty() {
-            Vec::new()
-        } else {
-            vec![Format::Text.code()]
-        };
         if let Outcome::Err(e) = self
             .send_message(
                 cx,
                 &FrontendMessage::Bind {
                     portal: String::new(),
                     statement: String::new(),
-                    param_formats,
-                    params: param_values,
-                    // Default result formats (text) when empty.
+                    param_formats: encoded.formats,
+                    params: encoded.values,
+                    // Result types are unknown before execution, so results
+                    // of unprepared queries stay text.
                     result_formats: Vec::new(),
                 },
             )
@@ -849,37 +1259,19 @@ impl PgAsyncConnection {
         meta: &PgPreparedMeta,
         params: &[Value],
     ) -> Outcome<PgQueryResult, Error> {
-        let mut param_values = Vec::with_capacity(params.len());
-
+        // Check parameters against the statement's ParameterDescription so
+        // mismatches fail here instead of as an opaque server error.
+        let mut coerced = Vec::with_capacity(params.len());
         for (i, v) in params.iter().enumerate() {
-            if matches!(v, Value::Null) {
-                param_values.push(None);
-                continue;
-            }
-            match encode_value(v, Format::Text) {
-                Ok((bytes, oid)) => {
-                    let expected = meta.param_type_oids.get(i).copied().unwrap_or(0);
-                    if expected != 0 && expected != oid {
-                        return Outcome::Err(query_error_msg(
-                            format!(
-                                "Prepared statement param {} expects type OID {}, got {}",
-                                i + 1,
-                                expected,
-                                oid
-                            ),
-                            QueryErrorKind::Database,
-                        ));
-                    }
-                    param_values.push(Some(bytes));
-                }
+            let expected = meta.param_type_oids.get(i).copied().unwrap_or(0);
+            match coerce_param(i + 1, expected, v) {
+                Ok(v) => coerced.push(v),
                 Err(e) => return Outcome::Err(e),
             }
         }
-
-        let param_formats = if params.is_empty() {
-            Vec::new()
-        } else {
-            vec![Format::Text.code()]
+        let encoded = match encode_params(&coerced, self.config.binary_protocol) {
+            Ok(encoded) => encoded,
+            Err(e) => return Outcome::Err(e),
         };
 
         if let Outcome::Err(e) = self
@@ -888,9 +1280,9 @@ impl PgAsyncConnection {
                 &FrontendMessage::Bind {
                     portal: String::new(),
                     statement: meta.name.clone(),
-                    param_formats,
-                    params: param_values,
-                    result_formats: Vec::new(),
+                    param_formats: encoded.formats,
+                    params: encoded.values,
+                    result_formats: meta.result_formats.clone(),
                 },
             )
             .await
@@ -1234,7 +1626,11 @@ impl PgAsyncConnection {
 
     // ==================== I/O ====================
 
-    async fn send_message(&mut self, cx: &Cx, msg: &FrontendMessage) -> Outcome<(), Error> {
+    pub(crate) async fn send_message(
+        &mut self,
+        cx: &Cx,
+        msg: &FrontendMessage,
+    ) -> Outcome<(), Error> {
         // If cancelled, propagate early.
         if let Some(reason) = cx.cancel_reason() {
             return Outcome::Cancelled(reason);
@@ -1242,11 +1638,61 @@ impl PgAsyncConnection {
         self.send_message_no_cx(msg).await
     }
 
-    async fn receive_message(&mut self, cx: &Cx) -> Outcome<BackendMessage, Error> {
-        if let Some(reason) = cx.cancel_reason() {
-            return Outcome::Cancelled(reason);
+    /// Receive the next message, cancelling the running statement on the
+    /// server if `cx` is cancelled while waiting for it.
+    pub(crate) async fn receive_message(&mut self, cx: &Cx) -> Outcome<BackendMessage, Error> {
+        let received = {
+            let mut message = std::pin::pin!(self.receive_message_no_cx());
+            std::future::poll_fn(|task| {
+                if let Some(reason) = cx.cancel_reason() {
+                    return std::task::Poll::Ready(Err(reason));
+                }
+                message.as_mut().poll(task).map(Ok)
+            })
+            .await
+        };
+        match received {
+            Ok(message) => message,
+            Err(reason) => {
+                self.cancel_in_flight().await;
+                Outcome::Cancelled(reason)
+            }
+        }
+    }
+
+    /// A token that cancels whatever statement this connection is running,
+    /// usable from another task.
+    pub fn cancel_token(&self) -> PgCancelToken {
+        PgCancelToken {
+            config: self.config.clone(),
+            process_id: self.process_id,
+            secret_key: self.secret_key,
+        }
+    }
+
+    /// Ask the server to stop the statement we are waiting on, then read up
+    /// to its `ReadyForQuery` so the connection can be reused.
+    async fn cancel_in_flight(&mut self) {
+        if self.process_id == 0 || !matches!(self.state, ConnectionState::Ready(_)) {
+            return;
+        }
+        if let Outcome::Err(_) = self.cancel_token().send().await {
+            self.state = ConnectionState::Error;
+            return;
+        }
+        loop {
+            match self.receive_message_no_cx().await {
+                Outcome::Ok(BackendMessage::ReadyForQuery(status)) => {
+                    self.state = ConnectionState::Ready(TransactionStatusState::from(status));
+                    return;
+                }
+                Outcome::Ok(_) => {}
+                _ => {
+                    self.state = ConnectionState::Error;
+                    return;
+                }
+            }
         }
-        self.receive_message_no_cx().await
     }
 
     async fn send_message_no_cx(&mut self, msg: &FrontendMessage) -> Outcome<(), Error> {
@@ -1322,14 +1768,98 @@ impl PgAsyncConnection {
     }
 }
 
+/// Cancels the statement running on a [`PgAsyncConnection`].
+///
+/// Sends a protocol-level `CancelRequest` over a separate connection, so
+/// the server stops the statement and frees its resources. The running
+/// statement then fails with SQLSTATE `57014`. A cancel that arrives after
+/// the statement finished is ignored by the server.
+#[derive(Clone)]
+pub struct PgCancelToken {
+    config: PgConfig,
+    process_id: i32,
+    secret_key: i32,
+}
+
+impl PgCancelToken {
+    /// Cancel the statement currently running on the connection, if any.
+    ///
+    /// Works from a task that is itself being cancelled; `cx` is not
+    /// checked.
+    pub async fn cancel(&self, _cx: &Cx) -> Outcome<(), Error> {
+        self.send().await
+    }
+
+    async fn send(&self) -> Outcome<(), Error> {
+        let addr = self.config.socket_addr();
+        let socket_addrs = match self.config.socket_addrs() {
+            Ok(addrs) => addrs,
+            Err(e) => return Outcome::Err(e),
+        };
+
+        let mut connected = Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "host resolved to no addresses",
+        ));
+        for socket_addr in socket_addrs {
+            connected = TcpStream::connect_timeout(socket_addr, self.config.connect_timeout).await;
+            if connected.is_ok() {
+                break;
+            }
+        }
+        let mut stream = match connected {
+            Ok(s) => s,
+            Err(e) => {
+                return Outcome::Err(Error::Connection(ConnectionError {
+                    kind: ConnectionErrorKind::Connect,
+                    message: format!("Failed to connect to {} to cancel query: {}", addr, e),
+                    source: Some(Box::new(e)),
+                }));
+            }
+        };
+
+        let mut writer = MessageWriter::new();
+        let request = writer
+            .write(&FrontendMessage::CancelRequest {
+                process_id: self.process_id,
+                secret_key: self.secret_key,
+            })
+            .to_vec();
+        let sent = match write_all_plain_async(&mut stream, &request).await {
+            Ok(()) => flush_plain_async(&mut stream).await,
+            Err(e) => Err(e),
+        };
+        match sent {
+            Ok(()) => Outcome::Ok(()),
+            Err(e) => Outcome::Err(Error::Connection(ConnectionError {
+                kind: ConnectionErrorKind::Disconnected,
+                message: format!("Failed to send cancel request: {}", e),
+                source: Some(Box::new(e)),
+            })),
+        }
+    }
+}
+
+impl std::fmt::Debug for PgCancelToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PgCancelToken")
+            .field("process_id", &self.process_id)
+            .field("host", &self.config.host)
+            .field("port", &self.config.port)
+            .finish_non_exhaustive()
+    }
+}
+
 /// Shared, cloneable PostgreSQL connection with interior mutability.
 pub struct SharedPgConnection {
     inner: Arc<Mutex<PgAsyncConnection>>,
+    cancel: PgCancelToken,
 }
 
 impl SharedPgConnection {
     pub fn new(conn: PgAsyncConnection) -> Self {
         Self {
+            cancel: conn.cancel_token(),
             inner: Arc::new(Mutex::new(conn)),
         }
     }
@@ -1347,6 +1877,12 @@ impl SharedPgConnection {
         &self.inner
     }
 
+    /// A token that cancels the running statement without waiting for the
+    /// connection lock.
+    pub fn cancel_token(&self) -> &PgCancelToken {
+        &self.cancel
+    }
+
     async fn begin_transaction_impl(
         &self,
         cx: &Cx,
@@ -1387,6 +1923,7 @@ impl Clone for SharedPgConnection {
     fn clone(&self) -> Self {
         Self {
             inner: Arc::clone(&self.inner),
+            cancel: self.cancel.clone(),
         }
     }
 }
@@ -1448,6 +1985,23 @@ impl Connection for SharedPgConnection {
         }
     }
 
+    fn query_raw(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<QueryResult, Error>> + Send {
+        let inner = Arc::clone(&self.inner);
+        let sql = sql.to_string();
+        let params = params.to_vec();
+        async move {
+            let Ok(mut guard) = inner.lock(cx).await else {
+                return Outcome::Err(connection_error("Failed to acquire connection lock"));
+            };
+            guard.query_raw_async(cx, &sql, &params).await
+        }
+    }
+
     fn query_one(
         &self,
         cx: &Cx,
@@ -1763,12 +2317,40 @@ impl<'conn> TransactionOps for SharedPgTransaction<'conn> {
 
 // ==================== Helpers ====================
 
+/// Which copy response a COPY statement should produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CopyDirection {
+    /// `COPY ... FROM STDIN`
+    In,
+    /// `COPY ... TO STDOUT`
+    Out,
+    /// `START_REPLICATION`
+    #[cfg_attr(not(feature = "cdc"), allow(dead_code))]
+    Both,
+}
+
 struct PgQueryResult {
+    columns: Vec<ResultColumn>,
     rows: Vec<Row>,
     command_tag: Option<String>,
 }
 
-fn connection_error(msg: impl Into<String>) -> Error {
+/// Column metadata from a RowDescription field.
+///
+/// RowDescription carries no nullability; resolving it would need a catalog query.
+fn result_column(field: &crate::protocol::FieldDescription) -> ResultColumn {
+    let sql_type = oid::type_name(field.type_oid);
+    ResultColumn {
+        name: field.name.clone(),
+        sql_type: (sql_type != "unknown").then(|| sql_type.to_string()),
+        type_id: Some(field.type_oid),
+        table_oid: (field.table_oid != 0).then_some(field.table_oid),
+        table: None,
+        nullable: None,
+    }
+}
+
+pub(crate) fn connection_error(msg: impl Into<String>) -> Error {
     Error::Connection(ConnectionError {
         kind: ConnectionErrorKind::Connect,
         message: msg.into(),
@@ -1784,7 +2366,7 @@ fn auth_error(msg: impl Into<String>) -> Error {
     })
 }
 
-fn protocol_error(msg: impl Into<String>) -> Error {
+pub(crate) fn protocol_error(msg: impl Into<String>) -> Error {
     Error::Protocol(ProtocolError {
         message: msg.into(),
         raw_data: None,
@@ -1805,7 +2387,7 @@ fn query_error_msg(msg: impl Into<String>, kind: QueryErrorKind) -> Error {
     })
 }
 
-fn error_from_fields(fields: &ErrorFields) -> Error {
+pub(crate) fn error_from_fields(fields: &ErrorFields) -> Error {
     let kind = match fields.code.get(..2) {
         Some("08") => {
             return Error::Connection(ConnectionError {
@@ -1840,7 +2422,7 @@ fn error_from_fields(fields: &ErrorFields) -> Error {
         _ => QueryErrorKind::Database,
     };
 
-    Error::Query(QueryError {
+    Error::from_query_error(QueryError {
         kind,
         sql: None,
         sqlstate: Some(fields.code.clone()),
@@ -1852,6 +2434,14 @@ fn error_from_fields(fields: &ErrorFields) -> Error {
     })
 }
 
+/// Attach the statement text to a query error.
+fn with_sql(mut error: Error, sql: &str) -> Error {
+    if let Some(q) = error.query_error_mut() {
+        q.sql.get_or_insert_with(|| sql.to_string());
+    }
+    error
+}
+
 fn parse_rows_affected(tag: Option<&str>) -> Option<u64> {
     let tag = tag?;
     let mut parts = tag.split_whitespace().collect::<Vec<_>>();
@@ -0,0 +1,487 @@
+//! Streaming access to large binary values.
+//!
+//! Two ways to move big payloads without holding them in one
+//! [`Value::Bytes`]:
+//!
+//! - [`SharedPgConnection::read_blob`] streams a `bytea` column in chunks as
+//!   an [`AsyncRead`], fetching each chunk with `substring()`.
+//! - The large-object API ([`PgAsyncConnection::lo_create`],
+//!   [`PgAsyncConnection::lo_open`], [`LargeObject`]) reads, writes and
+//!   seeks `pg_largeobject` data through the server-side `lo_*` functions.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use sqlmodel_postgres::LoMode;
+//!
+//! // Stream a bytea column to a file.
+//! let mut reader = conn.read_blob(&cx, "files", "data", ("id", Value::BigInt(7))).await?;
+//!
+//! // Write a large object in 1 MiB chunks; descriptors need a transaction.
+//! let mut guard = conn.inner().lock(&cx).await?;
+//! guard.execute_async(&cx, "BEGIN", &[]).await?;
+//! let oid = guard.lo_create(&cx).await?;
+//! let mut lo = guard.lo_open(&cx, oid, LoMode::Write).await?;
+//! for chunk in payload.chunks(1 << 20) {
+//!     lo.write(&cx, chunk).await?;
+//! }
+//! lo.close(&cx).await?;
+//! guard.execute_async(&cx, "COMMIT", &[]).await?;
+//! ```
+
+// Offsets and lengths cross between Rust sizes and int4/int8 SQL arguments.
+#![allow(clippy::cast_possible_truncation)]
+
+use std::future::Future;
+use std::io::SeekFrom;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use asupersync::io::{AsyncRead, ReadBuf};
+use asupersync::{Cx, Outcome};
+use sqlmodel_core::{Error, Value, quote_ident};
+
+use crate::async_connection::{PgAsyncConnection, SharedPgConnection, connection_error};
+use crate::copy::quote_table;
+
+/// Bytes fetched per round trip by [`BlobReader`] unless overridden.
+pub const DEFAULT_BLOB_CHUNK: usize = 1 << 20;
+
+const INV_WRITE: i32 = 0x0002_0000;
+const INV_READ: i32 = 0x0004_0000;
+
+type ChunkFuture = Pin<Box<dyn Future<Output = Outcome<Vec<u8>, Error>> + Send>>;
+
+/// An [`AsyncRead`] over one `bytea` value, fetched a chunk at a time.
+///
+/// Each chunk is a separate `substring()` query, so other tasks may use the
+/// connection between chunks and a concurrent update can be observed
+/// mid-stream; read inside a `REPEATABLE READ` transaction for a consistent
+/// snapshot. Slicing only avoids reading the whole value when the column is
+/// stored uncompressed (`ALTER TABLE ... ALTER COLUMN ... SET STORAGE
+/// EXTERNAL`).
+pub struct BlobReader {
+    conn: SharedPgConnection,
+    cx: Cx,
+    sql: String,
+    key: Value,
+    len: u64,
+    offset: u64,
+    chunk_size: usize,
+    buf: Vec<u8>,
+    pos: usize,
+    pending: Option<ChunkFuture>,
+}
+
+impl BlobReader {
+    /// Total length of the value in bytes.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Whether the value is empty (or NULL).
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Fetch `size` bytes per round trip instead of [`DEFAULT_BLOB_CHUNK`].
+    #[must_use]
+    pub fn chunk_size(mut self, size: usize) -> Self {
+        self.chunk_size = size.max(1);
+        self
+    }
+
+    /// The next chunk, or `None` once the whole value has been read.
+    pub async fn next_chunk(&mut self, cx: &Cx) -> Outcome<Option<Vec<u8>>, Error> {
+        if self.pos < self.buf.len() {
+            let rest = self.buf.split_off(self.pos);
+            self.buf.clear();
+            self.pos = 0;
+            return Outcome::Ok(Some(rest));
+        }
+        if self.offset >= self.len {
+            return Outcome::Ok(None);
+        }
+        match self.fetch(cx.clone()).await {
+            Outcome::Ok(chunk) if chunk.is_empty() => Outcome::Err(Error::Custom(
+                "blob shrank while it was being read".to_string(),
+            )),
+            Outcome::Ok(chunk) => {
+                self.offset += chunk.len() as u64;
+                Outcome::Ok(Some(chunk))
+            }
+            Outcome::Err(e) => Outcome::Err(e),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
+    /// Start fetching the chunk at the current offset.
+    fn fetch(&self, cx: Cx) -> ChunkFuture {
+        let len = (self.len - self.offset).min(self.chunk_size as u64);
+        Box::pin(fetch_chunk(
+            self.conn.clone(),
+            cx,
+            self.sql.clone(),
+            self.key.clone(),
+            self.offset,
+            len,
+        ))
+    }
+}
+
+impl std::fmt::Debug for BlobReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlobReader")
+            .field("sql", &self.sql)
+            .field("len", &self.len)
+            .field("offset", &self.offset)
+            .field("chunk_size", &self.chunk_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AsyncRead for BlobReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        task: &mut Context<'_>,
+        out: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            if this.pos < this.buf.len() {
+                let n = out.remaining().min(this.buf.len() - this.pos);
+                out.put_slice(&this.buf[this.pos..this.pos + n]);
+                this.pos += n;
+                return Poll::Ready(Ok(()));
+            }
+            if this.offset >= this.len {
+                return Poll::Ready(Ok(()));
+            }
+            let mut pending = this
+                .pending
+                .take()
+                .unwrap_or_else(|| this.fetch(this.cx.clone()));
+            let outcome = match pending.as_mut().poll(task) {
+                Poll::Ready(outcome) => outcome,
+                Poll::Pending => {
+                    this.pending = Some(pending);
+                    return Poll::Pending;
+                }
+            };
+            match outcome {
+                Outcome::Ok(chunk) if chunk.is_empty() => {
+                    return Poll::Ready(Err(std::io::Error::new(
+                        std::io::ErrorKind::UnexpectedEof,
+                        "blob shrank while it was being read",
+                    )));
+                }
+                Outcome::Ok(chunk) => {
+                    this.offset += chunk.len() as u64;
+                    this.buf = chunk;
+                    this.pos = 0;
+                }
+                Outcome::Err(e) => return Poll::Ready(Err(std::io::Error::other(e.to_string()))),
+                Outcome::Cancelled(_) => {
+                    return Poll::Ready(Err(std::io::Error::new(
+                        std::io::ErrorKind::Interrupted,
+                        "blob read cancelled",
+                    )));
+                }
+                Outcome::Panicked(_) => {
+                    return Poll::Ready(Err(std::io::Error::other("blob read panicked")));
+                }
+            }
+        }
+    }
+}
+
+/// Fetch `len` bytes of the value starting at zero-based `offset`.
+async fn fetch_chunk(
+    conn: SharedPgConnection,
+    cx: Cx,
+    sql: String,
+    key: Value,
+    offset: u64,
+    len: u64,
+) -> Outcome<Vec<u8>, Error> {
+    let Ok(mut guard) = conn.inner().lock(&cx).await else {
+        return Outcome::Err(connection_error("Failed to acquire connection lock"));
+    };
+    // bytea values are capped at 1 GB, so int4 positions suffice.
+    let params = [key, Value::Int(offset as i32 + 1), Value::Int(len as i32)];
+    let rows = match guard.query_async(&cx, &sql, &params).await {
+        Outcome::Ok(rows) => rows,
+        Outcome::Err(e) => return Outcome::Err(e),
+        Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+        Outcome::Panicked(p) => return Outcome::Panicked(p),
+    };
+    match rows.first().and_then(|row| row.get(0)) {
+        Some(Value::Bytes(bytes)) => Outcome::Ok(bytes.clone()),
+        Some(Value::Null) => Outcome::Ok(Vec::new()),
+        _ => Outcome::Err(Error::Custom(
+            "blob row disappeared while it was being read".to_string(),
+        )),
+    }
+}
+
+impl SharedPgConnection {
+    /// Stream the `bytea` value of `column` in the row of `table` whose
+    /// `key.0` column equals `key.1`.
+    ///
+    /// A NULL value reads as empty; a missing row is an error.
+    pub async fn read_blob(
+        &self,
+        cx: &Cx,
+        table: &str,
+        column: &str,
+        key: (&str, Value),
+    ) -> Outcome<BlobReader, Error> {
+        let (key_column, key) = key;
+        let from = format!(
+            "FROM {} WHERE {} = $1",
+            quote_table(table),
+            quote_ident(key_column)
+        );
+        let column = quote_ident(column);
+
+        let Ok(mut guard) = self.inner().lock(cx).await else {
+            return Outcome::Err(connection_error("Failed to acquire connection lock"));
+        };
+        let rows = match guard
+            .query_async(
+                cx,
+                &format!("SELECT octet_length({column}) {from}"),
+                std::slice::from_ref(&key),
+            )
+            .await
+        {
+            Outcome::Ok(rows) => rows,
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        };
+        drop(guard);
+        let len = match rows.first().and_then(|row| row.get(0)) {
+            Some(Value::Null) => 0,
+            Some(value) => value.as_i64().unwrap_or(0) as u64,
+            None => {
+                return Outcome::Err(Error::Custom(format!(
+                    "read_blob: no row in {table} where {key_column} = {key:?}"
+                )));
+            }
+        };
+
+        Outcome::Ok(BlobReader {
+            conn: self.clone(),
+            cx: cx.clone(),
+            sql: format!("SELECT substring({column} FROM $2 FOR $3) {from}"),
+            key,
+            len,
+            offset: 0,
+            chunk_size: DEFAULT_BLOB_CHUNK,
+            buf: Vec::new(),
+            pos: 0,
+            pending: None,
+        })
+    }
+}
+
+/// Access mode for [`PgAsyncConnection::lo_open`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoMode {
+    /// Read only; sees the object as of the transaction snapshot
+    Read,
+    /// Write only
+    Write,
+    /// Read and write; reads see the object's latest contents
+    ReadWrite,
+}
+
+impl LoMode {
+    /// The `INV_READ`/`INV_WRITE` flags passed to `lo_open`.
+    pub const fn flags(self) -> i32 {
+        match self {
+            LoMode::Read => INV_READ,
+            LoMode::Write => INV_WRITE,
+            LoMode::ReadWrite => INV_READ | INV_WRITE,
+        }
+    }
+}
+
+/// An open large-object descriptor.
+///
+/// Descriptors only live until the end of the transaction that opened
+/// them, so open one inside `BEGIN ... COMMIT`; in autocommit mode it is
+/// already closed by the next call.
+pub struct LargeObject<'a> {
+    conn: &'a mut PgAsyncConnection,
+    oid: u32,
+    fd: i32,
+}
+
+impl std::fmt::Debug for LargeObject<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LargeObject")
+            .field("oid", &self.oid)
+            .field("fd", &self.fd)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PgAsyncConnection {
+    /// Create an empty large object and return its OID.
+    pub async fn lo_create(&mut self, cx: &Cx) -> Outcome<u32, Error> {
+        self.lo_call(cx, "SELECT lo_create(0)", &[])
+            .await
+            .map(|oid| oid as u32)
+    }
+
+    /// Open large object `oid`.
+    pub async fn lo_open(
+        &mut self,
+        cx: &Cx,
+        oid: u32,
+        mode: LoMode,
+    ) -> Outcome<LargeObject<'_>, Error> {
+        let params = [Value::BigInt(i64::from(oid)), Value::Int(mode.flags())];
+        match self
+            .lo_call(cx, "SELECT lo_open($1::oid, $2)", &params)
+            .await
+        {
+            Outcome::Ok(fd) => Outcome::Ok(LargeObject {
+                conn: self,
+                oid,
+                fd: fd as i32,
+            }),
+            Outcome::Err(e) => Outcome::Err(e),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
+    /// Delete large object `oid`.
+    pub async fn lo_unlink(&mut self, cx: &Cx, oid: u32) -> Outcome<(), Error> {
+        let params = [Value::BigInt(i64::from(oid))];
+        self.lo_call(cx, "SELECT lo_unlink($1::oid)", &params)
+            .await
+            .map(|_| ())
+    }
+
+    /// Run a single-value `lo_*` query and return its integer result.
+    async fn lo_call(&mut self, cx: &Cx, sql: &str, params: &[Value]) -> Outcome<i64, Error> {
+        let rows = match self.query_async(cx, sql, params).await {
+            Outcome::Ok(rows) => rows,
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        };
+        match rows.first().and_then(|row| row.get(0)) {
+            // OIDs decode as `Int` holding the unsigned bit pattern.
+            Some(Value::Int(v)) => Outcome::Ok(i64::from(*v as u32)),
+            Some(value) => match value.as_i64() {
+                Some(v) => Outcome::Ok(v),
+                None => Outcome::Err(Error::Custom(format!(
+                    "{sql} returned non-integer {value:?}"
+                ))),
+            },
+            None => Outcome::Err(Error::Custom(format!("{sql} returned no value"))),
+        }
+    }
+}
+
+impl LargeObject<'_> {
+    /// OID of the object.
+    pub fn oid(&self) -> u32 {
+        self.oid
+    }
+
+    /// Read up to `len` bytes from the current position; empty at the end.
+    pub async fn read(&mut self, cx: &Cx, len: usize) -> Outcome<Vec<u8>, Error> {
+        let len = i32::try_from(len).unwrap_or(i32::MAX);
+        let params = [Value::Int(self.fd), Value::Int(len)];
+        let rows = match self
+            .conn
+            .query_async(cx, "SELECT loread($1, $2)", &params)
+            .await
+        {
+            Outcome::Ok(rows) => rows,
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        };
+        match rows.into_iter().next().and_then(|row| row.get(0).cloned()) {
+            Some(Value::Bytes(bytes)) => Outcome::Ok(bytes),
+            other => Outcome::Err(Error::Custom(format!(
+                "loread returned {other:?} instead of bytea"
+            ))),
+        }
+    }
+
+    /// Write `data` at the current position; returns the bytes written.
+    pub async fn write(&mut self, cx: &Cx, data: &[u8]) -> Outcome<usize, Error> {
+        let params = [Value::Int(self.fd), Value::Bytes(data.to_vec())];
+        self.conn
+            .lo_call(cx, "SELECT lowrite($1, $2)", &params)
+            .await
+            .map(|n| n as usize)
+    }
+
+    /// Move the current position; returns the new offset from the start.
+    pub async fn seek(&mut self, cx: &Cx, pos: SeekFrom) -> Outcome<u64, Error> {
+        // SEEK_SET, SEEK_CUR and SEEK_END.
+        let (offset, whence) = match pos {
+            SeekFrom::Start(offset) => (offset as i64, 0),
+            SeekFrom::Current(offset) => (offset, 1),
+            SeekFrom::End(offset) => (offset, 2),
+        };
+        let params = [
+            Value::Int(self.fd),
+            Value::BigInt(offset),
+            Value::Int(whence),
+        ];
+        self.conn
+            .lo_call(cx, "SELECT lo_lseek64($1, $2, $3)", &params)
+            .await
+            .map(|pos| pos as u64)
+    }
+
+    /// The current offset from the start.
+    pub async fn tell(&mut self, cx: &Cx) -> Outcome<u64, Error> {
+        let params = [Value::Int(self.fd)];
+        self.conn
+            .lo_call(cx, "SELECT lo_tell64($1)", &params)
+            .await
+            .map(|pos| pos as u64)
+    }
+
+    /// Cut or zero-extend the object to `len` bytes.
+    pub async fn truncate(&mut self, cx: &Cx, len: u64) -> Outcome<(), Error> {
+        let params = [Value::Int(self.fd), Value::BigInt(len as i64)];
+        self.conn
+            .lo_call(cx, "SELECT lo_truncate64($1, $2)", &params)
+            .await
+            .map(|_| ())
+    }
+
+    /// Close the descriptor.
+    pub async fn close(self, cx: &Cx) -> Outcome<(), Error> {
+        let params = [Value::Int(self.fd)];
+        self.conn
+            .lo_call(cx, "SELECT lo_close($1)", &params)
+            .await
+            .map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lo_mode_flags() {
+        assert_eq!(LoMode::Read.flags(), 0x40000);
+        assert_eq!(LoMode::Write.flags(), 0x20000);
+        assert_eq!(LoMode::ReadWrite.flags(), 0x60000);
+    }
+}
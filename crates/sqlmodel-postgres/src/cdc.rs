@@ -0,0 +1,736 @@
+//! Change data capture from a logical replication slot.
+//!
+//! [`ReplicationStream`] opens a replication connection, starts streaming a
+//! `pgoutput` slot for a publication, and decodes the stream into
+//! [`Change`]s. Changes are delivered one committed transaction at a time, so
+//! a consumer never sees a change that is later rolled back.
+//!
+//! Every change carries the end LSN of its transaction. Once a change has
+//! been handled, [`ack`](ReplicationStream::ack) its LSN so the server can
+//! discard the WAL; unacknowledged transactions are replayed after a
+//! reconnect.
+//!
+//! The server needs `wal_level = logical` and a publication:
+//!
+//! ```sql
+//! CREATE PUBLICATION app_changes FOR TABLE heroes, teams;
+//! ```
+//!
+//! # Example
+//!
+//! ```ignore
+//! use sqlmodel_postgres::cdc::{ChangeEvent, CdcOptions, ReplicationStream};
+//!
+//! let options = CdcOptions::new("app_cache", "app_changes").create_slot();
+//! let mut stream = ReplicationStream::connect(&cx, config, &options).await?;
+//! while let Some(change) = stream.next(&cx).await? {
+//!     if let Some(event) = change.decode::<Hero>() {
+//!         match event? {
+//!             ChangeEvent::Insert { new, .. } | ChangeEvent::Update { new, .. } => {
+//!                 cache.put(new)
+//!             }
+//!             ChangeEvent::Delete { old_key, .. } => cache.evict(&old_key),
+//!             ChangeEvent::Truncate { .. } => cache.clear(),
+//!         }
+//!     }
+//!     stream.ack(&cx, change.lsn).await?;
+//! }
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use asupersync::{Cx, Outcome};
+use sqlmodel_core::row::ColumnInfo;
+use sqlmodel_core::{Error, Model, Row, Value, quote_ident};
+
+use crate::async_connection::{
+    CopyDirection, PgAsyncConnection, error_from_fields, protocol_error,
+};
+use crate::config::PgConfig;
+use crate::protocol::{BackendMessage, FrontendMessage};
+use crate::types::{Format, decode_value};
+
+/// PostgreSQL `duplicate_object`, returned when the slot already exists.
+const DUPLICATE_OBJECT: &str = "42710";
+
+/// Seconds between the Unix epoch and the PostgreSQL epoch (2000-01-01).
+const PG_EPOCH_OFFSET: Duration = Duration::from_secs(946_684_800);
+
+/// A write-ahead log position.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lsn(pub u64);
+
+impl fmt::Display for Lsn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:X}/{:X}", self.0 >> 32, self.0 & 0xFFFF_FFFF)
+    }
+}
+
+impl FromStr for Lsn {
+    type Err = Error;
+
+    /// Parse the `XXX/XXX` form used by `pg_current_wal_lsn()`.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let parse = |part: &str| {
+            u64::from_str_radix(part, 16)
+                .ok()
+                .filter(|v| *v <= 0xFFFF_FFFF)
+        };
+        match s.split_once('/') {
+            Some((high, low)) => match (parse(high), parse(low)) {
+                (Some(high), Some(low)) => Ok(Lsn((high << 32) | low)),
+                _ => Err(Error::Custom(format!("invalid LSN: {s}"))),
+            },
+            None => Err(Error::Custom(format!("invalid LSN: {s}"))),
+        }
+    }
+}
+
+/// Which slot and publication a [`ReplicationStream`] reads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CdcOptions {
+    slot: String,
+    publication: String,
+    start: Lsn,
+    create_slot: bool,
+}
+
+impl CdcOptions {
+    /// Stream slot `slot` for publication `publication`.
+    pub fn new(slot: impl Into<String>, publication: impl Into<String>) -> Self {
+        Self {
+            slot: slot.into(),
+            publication: publication.into(),
+            start: Lsn::default(),
+            create_slot: false,
+        }
+    }
+
+    /// Resume at `lsn` instead of the slot's confirmed position.
+    ///
+    /// The server never goes back past the confirmed position.
+    #[must_use]
+    pub fn start_at(mut self, lsn: Lsn) -> Self {
+        self.start = lsn;
+        self
+    }
+
+    /// Create the slot (with the `pgoutput` plugin) if it does not exist.
+    #[must_use]
+    pub fn create_slot(mut self) -> Self {
+        self.create_slot = true;
+        self
+    }
+}
+
+/// The kind of row change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeOp {
+    /// A row was inserted.
+    Insert,
+    /// A row was updated.
+    Update,
+    /// A row was deleted.
+    Delete,
+    /// The table was truncated.
+    Truncate,
+}
+
+/// One decoded row change.
+///
+/// Values arrive in text format and are decoded by column type. Unchanged
+/// TOASTed values of an update are not sent by the server and read as NULL.
+#[derive(Debug, Clone)]
+pub struct Change {
+    /// End LSN of the transaction; acknowledge it once handled.
+    pub lsn: Lsn,
+    /// Schema of the changed table.
+    pub schema: String,
+    /// Name of the changed table.
+    pub table: String,
+    /// What happened to the row.
+    pub op: ChangeOp,
+    /// The replica identity of the old row (its key columns, or the whole row
+    /// with `REPLICA IDENTITY FULL`) for deletes, and for updates that
+    /// changed the key or use `REPLICA IDENTITY FULL`.
+    pub old: Option<Row>,
+    /// The new row, for inserts and updates.
+    pub new: Option<Row>,
+}
+
+/// A [`Change`] to a model's table, with the new row decoded as the model.
+#[derive(Debug, Clone)]
+pub enum ChangeEvent<M> {
+    /// A row was inserted.
+    Insert {
+        /// End LSN of the transaction.
+        lsn: Lsn,
+        /// The inserted row.
+        new: M,
+    },
+    /// A row was updated.
+    Update {
+        /// End LSN of the transaction.
+        lsn: Lsn,
+        /// The old replica identity, if the server sent it.
+        old_key: Option<Row>,
+        /// The updated row.
+        new: M,
+    },
+    /// A row was deleted.
+    Delete {
+        /// End LSN of the transaction.
+        lsn: Lsn,
+        /// The replica identity of the deleted row.
+        old_key: Row,
+    },
+    /// The table was truncated.
+    Truncate {
+        /// End LSN of the transaction.
+        lsn: Lsn,
+    },
+}
+
+impl Change {
+    /// Decode this change as a change to `M`'s table.
+    ///
+    /// Returns `None` if the change is to another table.
+    #[allow(clippy::result_large_err)]
+    pub fn decode<M: Model>(&self) -> Option<Result<ChangeEvent<M>, Error>> {
+        if self.table != M::TABLE_NAME {
+            return None;
+        }
+        let lsn = self.lsn;
+        Some(match (self.op, &self.old, &self.new) {
+            (ChangeOp::Insert, _, Some(new)) => {
+                M::from_row(new).map(|new| ChangeEvent::Insert { lsn, new })
+            }
+            (ChangeOp::Update, old, Some(new)) => M::from_row(new).map(|new| ChangeEvent::Update {
+                lsn,
+                old_key: old.clone(),
+                new,
+            }),
+            (ChangeOp::Delete, Some(old), _) => Ok(ChangeEvent::Delete {
+                lsn,
+                old_key: old.clone(),
+            }),
+            (ChangeOp::Truncate, ..) => Ok(ChangeEvent::Truncate { lsn }),
+            (op, ..) => Err(protocol_error(format!(
+                "{op:?} change to {} is missing its row",
+                self.table
+            ))),
+        })
+    }
+}
+
+/// A logical replication stream decoded into [`Change`]s.
+pub struct ReplicationStream {
+    conn: PgAsyncConnection,
+    decoder: Decoder,
+    ready: VecDeque<Change>,
+    received: Lsn,
+    acknowledged: Lsn,
+}
+
+impl fmt::Debug for ReplicationStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReplicationStream")
+            .field("received", &self.received)
+            .field("acknowledged", &self.acknowledged)
+            .field("buffered", &self.ready.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl ReplicationStream {
+    /// Open a replication connection and start streaming.
+    pub async fn connect(cx: &Cx, config: PgConfig, options: &CdcOptions) -> Outcome<Self, Error> {
+        let config = config.option("replication", "database");
+        let mut conn = match PgAsyncConnection::connect(cx, config).await {
+            Outcome::Ok(conn) => conn,
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        };
+
+        if options.create_slot {
+            let sql = format!(
+                "CREATE_REPLICATION_SLOT {} LOGICAL pgoutput",
+                quote_ident(&options.slot)
+            );
+            match conn.simple_query(cx, &sql).await {
+                Outcome::Ok(_) => {}
+                Outcome::Err(e) if e.sqlstate() == Some(DUPLICATE_OBJECT) => {}
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            }
+        }
+
+        let sql = format!(
+            "START_REPLICATION SLOT {} LOGICAL {} (proto_version '1', publication_names '{}')",
+            quote_ident(&options.slot),
+            options.start,
+            quote_ident(&options.publication).replace('\'', "''"),
+        );
+        match conn.start_copy(cx, &sql, CopyDirection::Both).await {
+            Outcome::Ok(()) => {}
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        }
+
+        Outcome::Ok(Self {
+            conn,
+            decoder: Decoder::default(),
+            ready: VecDeque::new(),
+            received: options.start,
+            acknowledged: options.start,
+        })
+    }
+
+    /// Wait for the next change; `None` once the server ends the stream.
+    ///
+    /// Keepalive requests from the server are answered while waiting.
+    pub async fn next(&mut self, cx: &Cx) -> Outcome<Option<Change>, Error> {
+        loop {
+            if let Some(change) = self.ready.pop_front() {
+                return Outcome::Ok(Some(change));
+            }
+            let msg = match self.conn.receive_message(cx).await {
+                Outcome::Ok(msg) => msg,
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            };
+            let data = match msg {
+                BackendMessage::CopyData(data) => data,
+                BackendMessage::CopyDone => return Outcome::Ok(None),
+                BackendMessage::ErrorResponse(e) => return Outcome::Err(error_from_fields(&e)),
+                _ => continue,
+            };
+            let mut r = Reader::new(&data);
+            let kind = match r.u8() {
+                Ok(kind @ (b'w' | b'k')) => kind,
+                Ok(_) => continue,
+                Err(e) => return Outcome::Err(e),
+            };
+            let (end, reply) = match read_header(&mut r, kind) {
+                Ok(header) => header,
+                Err(e) => return Outcome::Err(e),
+            };
+            self.received = self.received.max(Lsn(end));
+            if kind == b'w' {
+                match self.decoder.decode(r.rest()) {
+                    Ok(Some(transaction)) => self.ready.extend(transaction),
+                    Ok(None) => {}
+                    Err(e) => return Outcome::Err(e),
+                }
+            } else if reply {
+                match self.send_status(cx).await {
+                    Outcome::Ok(()) => {}
+                    Outcome::Err(e) => return Outcome::Err(e),
+                    Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                    Outcome::Panicked(p) => return Outcome::Panicked(p),
+                }
+            }
+        }
+    }
+
+    /// Wait for the next change to `M`'s table, skipping other tables.
+    ///
+    /// Skipped changes are not acknowledged; use this on a stream dedicated
+    /// to one model.
+    pub async fn next_event<M: Model>(
+        &mut self,
+        cx: &Cx,
+    ) -> Outcome<Option<ChangeEvent<M>>, Error> {
+        loop {
+            let change = match self.next(cx).await {
+                Outcome::Ok(Some(change)) => change,
+                Outcome::Ok(None) => return Outcome::Ok(None),
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            };
+            match change.decode::<M>() {
+                Some(Ok(event)) => return Outcome::Ok(Some(event)),
+                Some(Err(e)) => return Outcome::Err(e),
+                None => {}
+            }
+        }
+    }
+
+    /// Confirm that every change up to `lsn` has been handled.
+    pub async fn ack(&mut self, cx: &Cx, lsn: Lsn) -> Outcome<(), Error> {
+        if lsn <= self.acknowledged {
+            return Outcome::Ok(());
+        }
+        self.acknowledged = lsn;
+        self.send_status(cx).await
+    }
+
+    /// The last acknowledged LSN.
+    pub fn acknowledged(&self) -> Lsn {
+        self.acknowledged
+    }
+
+    /// Stop streaming and close the connection.
+    pub async fn close(mut self, cx: &Cx) -> Outcome<(), Error> {
+        self.conn.close_async(cx).await
+    }
+
+    /// Send a standby status update with the received and acknowledged LSNs.
+    async fn send_status(&mut self, cx: &Cx) -> Outcome<(), Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .saturating_sub(PG_EPOCH_OFFSET);
+        let mut msg = Vec::with_capacity(34);
+        msg.push(b'r');
+        msg.extend_from_slice(&self.received.0.to_be_bytes());
+        msg.extend_from_slice(&self.acknowledged.0.to_be_bytes());
+        msg.extend_from_slice(&self.acknowledged.0.to_be_bytes());
+        msg.extend_from_slice(&(now.as_micros() as i64).to_be_bytes());
+        msg.push(0);
+        self.conn
+            .send_message(cx, &FrontendMessage::CopyData(msg))
+            .await
+    }
+}
+
+/// Parse the header of an XLogData (`w`) or keepalive (`k`) message.
+///
+/// Returns the server's WAL end and whether it asked for a status update.
+#[allow(clippy::result_large_err)]
+fn read_header(r: &mut Reader<'_>, kind: u8) -> Result<(u64, bool), Error> {
+    if kind == b'w' {
+        r.u64()?;
+        let end = r.u64()?;
+        r.u64()?;
+        Ok((end, false))
+    } else {
+        let end = r.u64()?;
+        r.u64()?;
+        Ok((end, r.u8()? == 1))
+    }
+}
+
+/// A table described by a pgoutput Relation message.
+#[derive(Debug)]
+struct Relation {
+    schema: String,
+    name: String,
+    columns: Arc<ColumnInfo>,
+    type_oids: Vec<u32>,
+}
+
+/// pgoutput message decoder; buffers each transaction until its commit.
+#[derive(Debug, Default)]
+struct Decoder {
+    relations: HashMap<u32, Relation>,
+    pending: Vec<Change>,
+}
+
+impl Decoder {
+    /// Decode one pgoutput message; returns the transaction it committed.
+    #[allow(clippy::result_large_err)]
+    fn decode(&mut self, msg: &[u8]) -> Result<Option<Vec<Change>>, Error> {
+        let mut r = Reader::new(msg);
+        match r.u8()? {
+            b'B' => self.pending.clear(),
+            b'C' => {
+                r.u8()?;
+                r.u64()?;
+                let end = Lsn(r.u64()?);
+                let mut transaction = std::mem::take(&mut self.pending);
+                for change in &mut transaction {
+                    change.lsn = end;
+                }
+                return Ok(Some(transaction));
+            }
+            b'R' => {
+                let id = r.u32()?;
+                let schema = r.cstr()?;
+                let name = r.cstr()?;
+                r.u8()?;
+                let count = r.u16()?;
+                let mut names = Vec::with_capacity(count.into());
+                let mut type_oids = Vec::with_capacity(count.into());
+                for _ in 0..count {
+                    r.u8()?;
+                    names.push(r.cstr()?);
+                    type_oids.push(r.u32()?);
+                    r.u32()?;
+                }
+                self.relations.insert(
+                    id,
+                    Relation {
+                        schema,
+                        name,
+                        columns: Arc::new(ColumnInfo::new(names)),
+                        type_oids,
+                    },
+                );
+            }
+            op @ (b'I' | b'U' | b'D') => {
+                let relation = relation(&self.relations, r.u32()?)?;
+                let mut tag = r.u8()?;
+                let old = if op != b'I' && matches!(tag, b'K' | b'O') {
+                    let old = read_tuple(&mut r, relation)?;
+                    if op == b'U' {
+                        tag = r.u8()?;
+                    }
+                    Some(old)
+                } else {
+                    None
+                };
+                let new = match (op, tag) {
+                    (b'D', _) if old.is_some() => None,
+                    (b'I' | b'U', b'N') => Some(read_tuple(&mut r, relation)?),
+                    _ => return Err(protocol_error("pgoutput: malformed row change")),
+                };
+                let op = match op {
+                    b'I' => ChangeOp::Insert,
+                    b'U' => ChangeOp::Update,
+                    _ => ChangeOp::Delete,
+                };
+                self.pending.push(change(relation, op, old, new));
+            }
+            b'T' => {
+                let count = r.u32()?;
+                r.u8()?;
+                for _ in 0..count {
+                    let relation = relation(&self.relations, r.u32()?)?;
+                    self.pending
+                        .push(change(relation, ChangeOp::Truncate, None, None));
+                }
+            }
+            // Origin, Type and logical decoding messages carry no row changes.
+            _ => {}
+        }
+        Ok(None)
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn relation(relations: &HashMap<u32, Relation>, id: u32) -> Result<&Relation, Error> {
+    relations
+        .get(&id)
+        .ok_or_else(|| protocol_error(format!("pgoutput: unknown relation {id}")))
+}
+
+fn change(relation: &Relation, op: ChangeOp, old: Option<Row>, new: Option<Row>) -> Change {
+    Change {
+        lsn: Lsn::default(),
+        schema: relation.schema.clone(),
+        table: relation.name.clone(),
+        op,
+        old,
+        new,
+    }
+}
+
+/// Decode a pgoutput TupleData into a row of `relation`.
+#[allow(clippy::result_large_err)]
+fn read_tuple(r: &mut Reader<'_>, relation: &Relation) -> Result<Row, Error> {
+    if usize::from(r.u16()?) != relation.type_oids.len() {
+        return Err(protocol_error("pgoutput: tuple column count mismatch"));
+    }
+    let mut values = Vec::with_capacity(relation.type_oids.len());
+    for &type_oid in &relation.type_oids {
+        let value = match r.u8()? {
+            b'n' | b'u' => Value::Null,
+            b't' => {
+                let len = r.u32()? as usize;
+                decode_value(type_oid, Some(r.bytes(len)?), Format::Text)?
+            }
+            b'b' => {
+                let len = r.u32()? as usize;
+                decode_value(type_oid, Some(r.bytes(len)?), Format::Binary)?
+            }
+            other => {
+                return Err(protocol_error(format!(
+                    "pgoutput: unknown tuple value kind {other:#x}"
+                )));
+            }
+        };
+        values.push(value);
+    }
+    Ok(Row::with_columns(Arc::clone(&relation.columns), values))
+}
+
+/// Big-endian cursor over a replication message.
+struct Reader<'a> {
+    buf: &'a [u8],
+}
+
+#[allow(clippy::result_large_err)]
+impl<'a> Reader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.buf.len() < len {
+            return Err(protocol_error("pgoutput: truncated message"));
+        }
+        let (head, tail) = self.buf.split_at(len);
+        self.buf = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let mut out = [0; N];
+        out.copy_from_slice(self.bytes(N)?);
+        Ok(out)
+    }
+
+    fn u8(&mut self) -> Result<u8, Error> {
+        self.array::<1>().map(|b| b[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, Error> {
+        self.array().map(u16::from_be_bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        self.array().map(u32::from_be_bytes)
+    }
+
+    fn u64(&mut self) -> Result<u64, Error> {
+        self.array().map(u64::from_be_bytes)
+    }
+
+    fn cstr(&mut self) -> Result<String, Error> {
+        let Some(end) = self.buf.iter().position(|&b| b == 0) else {
+            return Err(protocol_error("pgoutput: unterminated string"));
+        };
+        let s = String::from_utf8_lossy(&self.buf[..end]).into_owned();
+        self.buf = &self.buf[end + 1..];
+        Ok(s)
+    }
+
+    fn rest(&self) -> &'a [u8] {
+        self.buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relation_msg() -> Vec<u8> {
+        let mut msg = vec![b'R'];
+        msg.extend_from_slice(&16_384_u32.to_be_bytes());
+        msg.extend_from_slice(b"public\0heroes\0d");
+        msg.extend_from_slice(&2_u16.to_be_bytes());
+        for (key, name, oid) in [(1_u8, "id", 20_u32), (0, "name", 25)] {
+            msg.push(key);
+            msg.extend_from_slice(name.as_bytes());
+            msg.push(0);
+            msg.extend_from_slice(&oid.to_be_bytes());
+            msg.extend_from_slice(&(-1_i32).to_be_bytes());
+        }
+        msg
+    }
+
+    fn tuple(values: &[Option<&str>]) -> Vec<u8> {
+        let mut msg = (values.len() as u16).to_be_bytes().to_vec();
+        for value in values {
+            match value {
+                Some(text) => {
+                    msg.push(b't');
+                    msg.extend_from_slice(&(text.len() as u32).to_be_bytes());
+                    msg.extend_from_slice(text.as_bytes());
+                }
+                None => msg.push(b'n'),
+            }
+        }
+        msg
+    }
+
+    fn row_msg(op: u8, parts: &[(u8, &[Option<&str>])]) -> Vec<u8> {
+        let mut msg = vec![op];
+        msg.extend_from_slice(&16_384_u32.to_be_bytes());
+        for (tag, values) in parts {
+            msg.push(*tag);
+            msg.extend(tuple(values));
+        }
+        msg
+    }
+
+    fn commit_msg(end: u64) -> Vec<u8> {
+        let mut msg = vec![b'C', 0];
+        msg.extend_from_slice(&(end - 8).to_be_bytes());
+        msg.extend_from_slice(&end.to_be_bytes());
+        msg.extend_from_slice(&0_u64.to_be_bytes());
+        msg
+    }
+
+    fn begin_msg() -> Vec<u8> {
+        let mut msg = vec![b'B'];
+        msg.extend_from_slice(&[0; 20]);
+        msg
+    }
+
+    #[test]
+    fn test_lsn_round_trip() {
+        let lsn: Lsn = "16/B374D848".parse().unwrap();
+        assert_eq!(lsn, Lsn(0x16_B374_D848));
+        assert_eq!(lsn.to_string(), "16/B374D848");
+        assert!("16".parse::<Lsn>().is_err());
+        assert!("1/100000000".parse::<Lsn>().is_err());
+    }
+
+    #[test]
+    fn test_decoder_buffers_transaction_until_commit() {
+        let mut decoder = Decoder::default();
+        assert!(decoder.decode(&begin_msg()).unwrap().is_none());
+        assert!(decoder.decode(&relation_msg()).unwrap().is_none());
+        let insert = row_msg(b'I', &[(b'N', &[Some("1"), Some("Thor")])]);
+        assert!(decoder.decode(&insert).unwrap().is_none());
+        let update = row_msg(
+            b'U',
+            &[(b'K', &[Some("1"), None]), (b'N', &[Some("2"), None])],
+        );
+        assert!(decoder.decode(&update).unwrap().is_none());
+        let delete = row_msg(b'D', &[(b'K', &[Some("2"), None])]);
+        assert!(decoder.decode(&delete).unwrap().is_none());
+
+        let changes = decoder.decode(&commit_msg(0x100)).unwrap().unwrap();
+        let ops: Vec<ChangeOp> = changes.iter().map(|c| c.op).collect();
+        assert_eq!(ops, [ChangeOp::Insert, ChangeOp::Update, ChangeOp::Delete]);
+        assert!(
+            changes
+                .iter()
+                .all(|c| c.lsn == Lsn(0x100) && c.table == "heroes")
+        );
+
+        let inserted = changes[0].new.as_ref().unwrap();
+        assert_eq!(inserted.get_named::<i64>("id").unwrap(), 1);
+        assert_eq!(inserted.get_named::<String>("name").unwrap(), "Thor");
+        assert_eq!(
+            changes[1]
+                .old
+                .as_ref()
+                .unwrap()
+                .get_named::<i64>("id")
+                .unwrap(),
+            1
+        );
+        assert_eq!(changes[1].new.as_ref().unwrap().get(1), Some(&Value::Null));
+        assert!(changes[2].new.is_none());
+    }
+
+    #[test]
+    fn test_decoder_rejects_unknown_relation() {
+        let mut decoder = Decoder::default();
+        let insert = row_msg(b'I', &[(b'N', &[Some("1"), Some("Thor")])]);
+        assert!(decoder.decode(&insert).is_err());
+    }
+}
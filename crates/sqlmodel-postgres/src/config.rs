@@ -4,8 +4,13 @@
 //! including authentication, SSL, and connection options.
 
 use std::collections::HashMap;
+use std::net::{SocketAddr, ToSocketAddrs};
 use std::time::Duration;
 
+use sqlmodel_core::Error;
+use sqlmodel_core::credentials::Credentials;
+use sqlmodel_core::error::{ConfigError, ConnectionError, ConnectionErrorKind};
+
 /// SSL mode for PostgreSQL connections.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum SslMode {
@@ -37,10 +42,44 @@ impl SslMode {
     }
 }
 
+/// Which server of a multi-host configuration a connection may settle on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TargetSessionAttrs {
+    /// Any server that accepts the connection
+    #[default]
+    Any,
+    /// Only a server accepting writes (the primary)
+    ReadWrite,
+    /// Only a read-only server (a standby)
+    ReadOnly,
+}
+
+impl TargetSessionAttrs {
+    /// The libpq keyword value, e.g. `read-write`.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Any => "any",
+            Self::ReadWrite => "read-write",
+            Self::ReadOnly => "read-only",
+        }
+    }
+
+    /// Whether a server reporting `transaction_read_only = read_only`
+    /// satisfies these attributes.
+    pub const fn accepts(self, read_only: bool) -> bool {
+        match self {
+            Self::Any => true,
+            Self::ReadWrite => !read_only,
+            Self::ReadOnly => read_only,
+        }
+    }
+}
+
 /// PostgreSQL connection configuration.
 #[derive(Debug, Clone)]
 pub struct PgConfig {
-    /// Hostname or IP address
+    /// Hostname or IP address, or a comma-separated list of hosts (each
+    /// optionally `host:port`) tried in order
     pub host: String,
     /// Port number (default: 5432)
     pub port: u16,
@@ -56,8 +95,23 @@ pub struct PgConfig {
     pub connect_timeout: Duration,
     /// SSL mode
     pub ssl_mode: SslMode,
+    /// Which of several hosts to settle on
+    pub target_session_attrs: TargetSessionAttrs,
     /// Additional connection parameters
     pub options: HashMap<String, String>,
+    /// Decode row columns lazily on first access instead of eagerly.
+    ///
+    /// Reduces allocations when only a few columns of wide rows are read.
+    /// Decode errors surface from typed accessors rather than from the query.
+    pub lazy_decoding: bool,
+    /// Use the binary wire format where both sides support it.
+    ///
+    /// Parameters are sent in binary per value, and prepared statements
+    /// fetch result columns in binary per column; see
+    /// [`param_format`](crate::types::param_format) and
+    /// [`supports_binary`](crate::types::supports_binary) for the types
+    /// involved. Disable to force text everywhere.
+    pub binary_protocol: bool,
 }
 
 impl Default for PgConfig {
@@ -71,7 +125,10 @@ impl Default for PgConfig {
             application_name: None,
             connect_timeout: Duration::from_secs(30),
             ssl_mode: SslMode::default(),
+            target_session_attrs: TargetSessionAttrs::default(),
             options: HashMap::new(),
+            lazy_decoding: false,
+            binary_protocol: true,
         }
     }
 }
@@ -103,6 +160,13 @@ impl PgConfig {
         self
     }
 
+    /// Use `credentials` for the user and password.
+    pub fn credentials(mut self, credentials: Credentials) -> Self {
+        self.user = credentials.user;
+        self.password = credentials.password;
+        self
+    }
+
     /// Set the application name.
     pub fn application_name(mut self, name: impl Into<String>) -> Self {
         self.application_name = Some(name.into());
@@ -121,6 +185,24 @@ impl PgConfig {
         self
     }
 
+    /// Set which of several hosts a connection may settle on.
+    pub fn target_session_attrs(mut self, attrs: TargetSessionAttrs) -> Self {
+        self.target_session_attrs = attrs;
+        self
+    }
+
+    /// Enable or disable lazy per-column row decoding.
+    pub fn lazy_decoding(mut self, enabled: bool) -> Self {
+        self.lazy_decoding = enabled;
+        self
+    }
+
+    /// Enable or disable the binary wire format.
+    pub fn binary_protocol(mut self, enabled: bool) -> Self {
+        self.binary_protocol = enabled;
+        self
+    }
+
     /// Set an additional connection option.
     pub fn option(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         self.options.insert(key.into(), value.into());
@@ -148,8 +230,158 @@ impl PgConfig {
 
     /// Get the socket address string for connection.
     pub fn socket_addr(&self) -> String {
-        format!("{}:{}", self.host, self.port)
+        if self.host.contains(':') {
+            format!("[{}]:{}", self.host, self.port)
+        } else {
+            format!("{}:{}", self.host, self.port)
+        }
     }
+
+    /// Resolve the host to socket addresses.
+    ///
+    /// Resolution happens on every call, so each new connection follows
+    /// DNS changes (e.g. after a failover or `Pool::reload_config`).
+    #[allow(clippy::result_large_err)]
+    pub fn socket_addrs(&self) -> Result<Vec<SocketAddr>, Error> {
+        match (self.host.as_str(), self.port).to_socket_addrs() {
+            Ok(addrs) => Ok(addrs.collect()),
+            Err(e) => Err(Error::Connection(ConnectionError {
+                kind: ConnectionErrorKind::DnsResolution,
+                message: format!("Failed to resolve {}: {}", self.host, e),
+                source: Some(Box::new(e)),
+            })),
+        }
+    }
+
+    /// The hosts to try, in order, with their ports.
+    ///
+    /// Entries of [`host`](Self::host) without an explicit `:port` (or
+    /// `[addr]:port` for IPv6) use [`port`](Self::port).
+    pub fn hosts(&self) -> Vec<(String, u16)> {
+        self.host
+            .split(',')
+            .map(str::trim)
+            .filter(|host| !host.is_empty())
+            .map(|entry| {
+                let (host, port) = match entry.strip_prefix('[') {
+                    Some(rest) => match rest.split_once(']') {
+                        Some((addr, port)) => (addr, port.strip_prefix(':')),
+                        None => (entry, None),
+                    },
+                    None => match entry.split_once(':') {
+                        Some((host, port)) if !port.contains(':') => (host, Some(port)),
+                        _ => (entry, None),
+                    },
+                };
+                let port = port.and_then(|port| port.parse().ok()).unwrap_or(self.port);
+                (host.to_string(), port)
+            })
+            .collect()
+    }
+
+    /// A copy of this configuration connecting to `host:port` only.
+    pub fn for_host(&self, host: &str, port: u16) -> Self {
+        Self {
+            host: host.to_string(),
+            port,
+            ..self.clone()
+        }
+    }
+
+    /// Parse a libpq keyword/value connection string, e.g.
+    /// `host=a,b,c port=5432 user=app dbname=app target_session_attrs=read-write`.
+    ///
+    /// A `port` list pairs up with the `host` list. Unrecognized keywords are
+    /// sent to the server as startup options.
+    #[allow(clippy::result_large_err)]
+    pub fn parse(conninfo: &str) -> Result<Self, Error> {
+        let mut config = Self::default();
+        let mut ports: Vec<u16> = Vec::new();
+        for pair in conninfo.split_whitespace() {
+            let Some((key, value)) = pair.split_once('=') else {
+                return Err(config_error(format!(
+                    "expected keyword=value, got '{pair}'"
+                )));
+            };
+            match key {
+                "host" | "hostaddr" => config.host = value.to_string(),
+                "port" => {
+                    ports = value
+                        .split(',')
+                        .map(|port| {
+                            port.trim()
+                                .parse()
+                                .map_err(|_| config_error(format!("invalid port '{port}'")))
+                        })
+                        .collect::<Result<_, _>>()?;
+                }
+                "user" => config.user = value.to_string(),
+                "password" => config.password = Some(value.to_string()),
+                "dbname" => config.database = value.to_string(),
+                "application_name" => config.application_name = Some(value.to_string()),
+                "connect_timeout" => {
+                    let seconds = value
+                        .parse()
+                        .map_err(|_| config_error(format!("invalid connect_timeout '{value}'")))?;
+                    config.connect_timeout = Duration::from_secs(seconds);
+                }
+                "sslmode" => {
+                    config.ssl_mode = match value {
+                        "disable" => SslMode::Disable,
+                        "prefer" | "allow" => SslMode::Prefer,
+                        "require" => SslMode::Require,
+                        "verify-ca" => SslMode::VerifyCa,
+                        "verify-full" => SslMode::VerifyFull,
+                        _ => return Err(config_error(format!("invalid sslmode '{value}'"))),
+                    };
+                }
+                "target_session_attrs" => {
+                    config.target_session_attrs = match value {
+                        "any" => TargetSessionAttrs::Any,
+                        "read-write" | "primary" => TargetSessionAttrs::ReadWrite,
+                        "read-only" | "standby" => TargetSessionAttrs::ReadOnly,
+                        _ => {
+                            return Err(config_error(format!(
+                                "invalid target_session_attrs '{value}'"
+                            )));
+                        }
+                    };
+                }
+                _ => {
+                    config.options.insert(key.to_string(), value.to_string());
+                }
+            }
+        }
+
+        match ports.as_slice() {
+            [] => {}
+            [port] => config.port = *port,
+            ports => {
+                let hosts = config.hosts();
+                if hosts.len() != ports.len() {
+                    return Err(config_error(format!(
+                        "{} ports given for {} hosts",
+                        ports.len(),
+                        hosts.len()
+                    )));
+                }
+                config.host = hosts
+                    .iter()
+                    .zip(ports)
+                    .map(|((host, _), port)| format!("{host}:{port}"))
+                    .collect::<Vec<_>>()
+                    .join(",");
+            }
+        }
+        Ok(config)
+    }
+}
+
+fn config_error(message: String) -> Error {
+    Error::Config(ConfigError {
+        message,
+        source: None,
+    })
 }
 
 #[cfg(test)]
@@ -200,10 +432,54 @@ mod tests {
         assert!(params.iter().any(|(k, v)| k == "timezone" && v == "UTC"));
     }
 
+    #[test]
+    fn test_parse_multi_host_conninfo() {
+        let config = PgConfig::parse(
+            "host=a,b,c port=5432,5433,5434 user=app dbname=shop target_session_attrs=read-write",
+        )
+        .unwrap();
+        assert_eq!(
+            config.hosts(),
+            vec![
+                ("a".to_string(), 5432),
+                ("b".to_string(), 5433),
+                ("c".to_string(), 5434)
+            ]
+        );
+        assert_eq!(config.user, "app");
+        assert_eq!(config.database, "shop");
+        assert_eq!(config.target_session_attrs, TargetSessionAttrs::ReadWrite);
+        assert_eq!(config.for_host("b", 5433).socket_addr(), "b:5433");
+
+        assert!(PgConfig::parse("host=a,b port=1,2,3").is_err());
+        assert!(PgConfig::parse("target_session_attrs=sometimes").is_err());
+    }
+
+    #[test]
+    fn test_hosts_default_port() {
+        let config = PgConfig::new("10.0.0.1, 10.0.0.2:6432,[::1]:5433", "u", "d");
+        assert_eq!(
+            config.hosts(),
+            vec![
+                ("10.0.0.1".to_string(), 5432),
+                ("10.0.0.2".to_string(), 6432),
+                ("::1".to_string(), 5433)
+            ]
+        );
+        assert!(TargetSessionAttrs::ReadOnly.accepts(true));
+        assert!(!TargetSessionAttrs::ReadWrite.accepts(true));
+    }
+
     #[test]
     fn test_socket_addr() {
         let config = PgConfig::new("db.example.com", "user", "db").port(5433);
         assert_eq!(config.socket_addr(), "db.example.com:5433");
+
+        let local = PgConfig::new("::1", "user", "db").port(5433);
+        assert_eq!(
+            local.socket_addrs().unwrap(),
+            vec!["[::1]:5433".parse::<SocketAddr>().unwrap()]
+        );
     }
 
     #[test]
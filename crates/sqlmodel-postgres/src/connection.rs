@@ -206,20 +206,41 @@ impl PgConnection {
     /// 3. Startup message
     /// 4. Authentication
     /// 5. Receive server parameters and ReadyForQuery
+    ///
+    /// With several hosts configured the first one that accepts the
+    /// connection is used; `target_session_attrs` is only checked by
+    /// [`PgAsyncConnection`](crate::PgAsyncConnection).
     #[allow(clippy::result_large_err)]
     pub fn connect(config: PgConfig) -> Result<Self, Error> {
+        let hosts = config.hosts();
+        if hosts.len() <= 1 {
+            return Self::connect_host(config);
+        }
+        let mut last_error = None;
+        for (host, port) in &hosts {
+            match Self::connect_host(config.for_host(host, *port)) {
+                Ok(conn) => return Ok(conn),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error.expect("at least two hosts were tried"))
+    }
+
+    /// Connect to the single host of `config`.
+    #[allow(clippy::result_large_err)]
+    fn connect_host(config: PgConfig) -> Result<Self, Error> {
         // 1. TCP connection with timeout
-        let stream = TcpStream::connect_timeout(
-            &config.socket_addr().parse().map_err(|e| {
-                Error::Connection(ConnectionError {
-                    kind: ConnectionErrorKind::Connect,
-                    message: format!("Invalid socket address: {}", e),
-                    source: None,
-                })
-            })?,
-            config.connect_timeout,
-        )
-        .map_err(|e| {
+        let mut connected = Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "host resolved to no addresses",
+        ));
+        for socket_addr in config.socket_addrs()? {
+            connected = TcpStream::connect_timeout(&socket_addr, config.connect_timeout);
+            if connected.is_ok() {
+                break;
+            }
+        }
+        let stream = connected.map_err(|e| {
             let kind = if e.kind() == std::io::ErrorKind::ConnectionRefused {
                 ConnectionErrorKind::Refused
             } else {
@@ -843,7 +864,7 @@ fn error_from_fields(fields: &ErrorFields) -> Error {
         _ => QueryErrorKind::Database,
     };
 
-    Error::Query(QueryError {
+    Error::from_query_error(QueryError {
         kind,
         sql: None,
         sqlstate: Some(fields.code.clone()),
@@ -892,7 +913,10 @@ mod tests {
             ..Default::default()
         };
         let err = error_from_fields(&fields);
-        assert!(matches!(err, Error::Query(q) if q.kind == QueryErrorKind::Constraint));
+        assert!(matches!(
+            err,
+            Error::UniqueViolation { query: q, .. } if q.kind == QueryErrorKind::Constraint
+        ));
 
         let fields = ErrorFields {
             severity: "FATAL".to_string(),
@@ -0,0 +1,256 @@
+//! Table-to-table replication over COPY.
+//!
+//! [`sync_table`] streams rows from `COPY ... TO STDOUT` on one connection
+//! straight into `COPY ... FROM STDIN` on another, one chunk at a time, so
+//! the table is never held in memory. Use it to clone environments or to
+//! backfill a table from another database.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use sqlmodel_postgres::{SyncOptions, sync_table};
+//!
+//! let copied = sync_table(
+//!     &cx,
+//!     &prod,
+//!     &staging,
+//!     "heroes",
+//!     &SyncOptions::new()
+//!         .column("id")
+//!         .map_column("secret_name", "alias")
+//!         .filter("created_at > now() - interval '30 days'")
+//!         .truncate(),
+//! )
+//! .await?;
+//! ```
+
+use std::sync::Arc;
+
+use asupersync::{Cx, Outcome};
+use sqlmodel_core::{Error, quote_ident};
+
+use crate::async_connection::{PgAsyncConnection, SharedPgConnection, connection_error};
+
+/// What [`sync_table`] copies and where to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncOptions {
+    /// (source, target) column pairs; empty copies every column.
+    columns: Vec<(String, String)>,
+    filter: Option<String>,
+    target: Option<String>,
+    truncate: bool,
+}
+
+impl SyncOptions {
+    /// Copy every column of every row into the same-named table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy column `name` into the column of the same name.
+    #[must_use]
+    pub fn column(self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        self.map_column(name.clone(), name)
+    }
+
+    /// Copy source column `source` into target column `target`.
+    #[must_use]
+    pub fn map_column(mut self, source: impl Into<String>, target: impl Into<String>) -> Self {
+        self.columns.push((source.into(), target.into()));
+        self
+    }
+
+    /// Only copy source rows matching `predicate`.
+    #[must_use]
+    pub fn filter(mut self, predicate: impl Into<String>) -> Self {
+        self.filter = Some(predicate.into());
+        self
+    }
+
+    /// Copy into `table` instead of the same-named table.
+    #[must_use]
+    pub fn into_table(mut self, table: impl Into<String>) -> Self {
+        self.target = Some(table.into());
+        self
+    }
+
+    /// Empty the target table before copying.
+    ///
+    /// Run inside a transaction on the target connection to replace the
+    /// contents atomically.
+    #[must_use]
+    pub fn truncate(mut self) -> Self {
+        self.truncate = true;
+        self
+    }
+
+    /// The `COPY ... TO STDOUT` statement run on the source.
+    pub fn source_sql(&self, table: &str) -> String {
+        let table = quote_table(table);
+        match (&self.filter, self.columns.is_empty()) {
+            (None, true) => format!("COPY {table} TO STDOUT"),
+            (None, false) => format!("COPY {table} ({}) TO STDOUT", self.column_list(false)),
+            (Some(predicate), all) => format!(
+                "COPY (SELECT {} FROM {table} WHERE {predicate}) TO STDOUT",
+                if all {
+                    "*".to_string()
+                } else {
+                    self.column_list(false)
+                }
+            ),
+        }
+    }
+
+    /// The `COPY ... FROM STDIN` statement run on the target.
+    pub fn target_sql(&self, table: &str) -> String {
+        let table = quote_table(self.target.as_deref().unwrap_or(table));
+        if self.columns.is_empty() {
+            format!("COPY {table} FROM STDIN")
+        } else {
+            format!("COPY {table} ({}) FROM STDIN", self.column_list(true))
+        }
+    }
+
+    fn column_list(&self, target: bool) -> String {
+        self.columns
+            .iter()
+            .map(|(source, dest)| quote_ident(if target { dest } else { source }))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Quote a possibly schema-qualified table name.
+pub(crate) fn quote_table(table: &str) -> String {
+    table
+        .split('.')
+        .map(quote_ident)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Copy rows of `table` from `src` into `dst`; returns the rows copied.
+///
+/// Rows travel in COPY text format, so target columns only need to accept
+/// the source values' text form. If either side fails, the target COPY is
+/// aborted and none of its rows are kept.
+pub async fn sync_table(
+    cx: &Cx,
+    src: &SharedPgConnection,
+    dst: &SharedPgConnection,
+    table: &str,
+    options: &SyncOptions,
+) -> Outcome<u64, Error> {
+    if Arc::ptr_eq(src.inner(), dst.inner()) {
+        return Outcome::Err(Error::Custom(
+            "sync_table needs two distinct connections".to_string(),
+        ));
+    }
+    let Ok(mut source) = src.inner().lock(cx).await else {
+        return Outcome::Err(connection_error("Failed to acquire connection lock"));
+    };
+    let Ok(mut target) = dst.inner().lock(cx).await else {
+        return Outcome::Err(connection_error("Failed to acquire connection lock"));
+    };
+
+    if options.truncate {
+        let name = options.target.as_deref().unwrap_or(table);
+        let sql = format!("TRUNCATE TABLE {}", quote_table(name));
+        match target.execute_async(cx, &sql, &[]).await {
+            Outcome::Ok(_) => {}
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        }
+    }
+
+    match target.copy_in_start(cx, &options.target_sql(table)).await {
+        Outcome::Ok(()) => {}
+        Outcome::Err(e) => return Outcome::Err(e),
+        Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+        Outcome::Panicked(p) => return Outcome::Panicked(p),
+    }
+    match stream(cx, &mut source, &mut target, &options.source_sql(table)).await {
+        Outcome::Ok(()) => target.copy_in_finish(cx).await,
+        failed => {
+            let _ = target
+                .copy_in_abort(cx, "sync_table: source COPY failed")
+                .await;
+            failed.map(|()| 0)
+        }
+    }
+}
+
+/// Forward every COPY OUT chunk from `src` to the COPY IN on `dst`.
+async fn stream(
+    cx: &Cx,
+    src: &mut PgAsyncConnection,
+    dst: &mut PgAsyncConnection,
+    sql: &str,
+) -> Outcome<(), Error> {
+    match src.copy_out_start(cx, sql).await {
+        Outcome::Ok(()) => {}
+        Outcome::Err(e) => return Outcome::Err(e),
+        Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+        Outcome::Panicked(p) => return Outcome::Panicked(p),
+    }
+    loop {
+        let chunk = match src.copy_out_next(cx).await {
+            Outcome::Ok(Some(chunk)) => chunk,
+            Outcome::Ok(None) => return Outcome::Ok(()),
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        };
+        match dst.copy_in_send(cx, chunk).await {
+            Outcome::Ok(()) => {}
+            Outcome::Err(e) => {
+                // Drain the source so its connection stays usable.
+                while let Outcome::Ok(Some(_)) = src.copy_out_next(cx).await {}
+                return Outcome::Err(e);
+            }
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_sql_copies_whole_table_by_default() {
+        let options = SyncOptions::new();
+        assert_eq!(options.source_sql("heroes"), "COPY \"heroes\" TO STDOUT");
+        assert_eq!(options.target_sql("heroes"), "COPY \"heroes\" FROM STDIN");
+    }
+
+    #[test]
+    fn test_sync_sql_with_columns_filter_and_target() {
+        let options = SyncOptions::new()
+            .column("id")
+            .map_column("secret_name", "alias")
+            .into_table("archive.heroes");
+        assert_eq!(
+            options.source_sql("heroes"),
+            "COPY \"heroes\" (\"id\", \"secret_name\") TO STDOUT"
+        );
+        assert_eq!(
+            options.target_sql("heroes"),
+            "COPY \"archive\".\"heroes\" (\"id\", \"alias\") FROM STDIN"
+        );
+
+        let filtered = options.filter("age > 30");
+        assert_eq!(
+            filtered.source_sql("public.heroes"),
+            "COPY (SELECT \"id\", \"secret_name\" FROM \"public\".\"heroes\" WHERE age > 30) \
+             TO STDOUT"
+        );
+        assert_eq!(
+            SyncOptions::new().filter("age > 30").source_sql("heroes"),
+            "COPY (SELECT * FROM \"heroes\" WHERE age > 30) TO STDOUT"
+        );
+    }
+}
@@ -44,15 +44,23 @@
 
 pub mod async_connection;
 pub mod auth;
+pub mod blob;
+#[cfg(feature = "cdc")]
+pub mod cdc;
 pub mod config;
 pub mod connection;
+pub mod copy;
 pub mod protocol;
 pub mod tls;
 pub mod types;
 
-pub use async_connection::{PgAsyncConnection, SharedPgConnection, SharedPgTransaction};
-pub use config::{PgConfig, SslMode};
+pub use async_connection::{
+    PgAsyncConnection, PgCancelToken, SharedPgConnection, SharedPgTransaction,
+};
+pub use blob::{BlobReader, LargeObject, LoMode};
+pub use config::{PgConfig, SslMode, TargetSessionAttrs};
 pub use connection::{ConnectionState, PgConnection, TransactionStatusState};
+pub use copy::{SyncOptions, sync_table};
 pub use types::{Format, TypeCategory, TypeInfo, TypeRegistry};
 
 // Console integration (feature-gated)
@@ -13,7 +13,7 @@ use sqlmodel_core::Error;
 use sqlmodel_core::error::TypeError;
 use sqlmodel_core::value::Value;
 
-use super::encode::Format;
+use super::encode::{Format, NUMERIC_NAN, NUMERIC_NEG, NUMERIC_NINF, NUMERIC_PINF};
 use super::oid;
 
 /// Decode a value from PostgreSQL text format.
@@ -98,11 +98,9 @@ impl TextDecode for i16 {
 }
 
 impl BinaryDecode for i16 {
+    #[inline]
     fn decode_binary(data: &[u8]) -> Result<Self, Error> {
-        if data.len() != 2 {
-            return Err(binary_length_error("int2", 2, data.len()));
-        }
-        Ok(i16::from_be_bytes([data[0], data[1]]))
+        Ok(i16::from_be_bytes(fixed_width("int2", data)?))
     }
 }
 
@@ -115,11 +113,9 @@ impl TextDecode for i32 {
 }
 
 impl BinaryDecode for i32 {
+    #[inline]
     fn decode_binary(data: &[u8]) -> Result<Self, Error> {
-        if data.len() != 4 {
-            return Err(binary_length_error("int4", 4, data.len()));
-        }
-        Ok(i32::from_be_bytes([data[0], data[1], data[2], data[3]]))
+        Ok(i32::from_be_bytes(fixed_width("int4", data)?))
     }
 }
 
@@ -132,13 +128,9 @@ impl TextDecode for i64 {
 }
 
 impl BinaryDecode for i64 {
+    #[inline]
     fn decode_binary(data: &[u8]) -> Result<Self, Error> {
-        if data.len() != 8 {
-            return Err(binary_length_error("int8", 8, data.len()));
-        }
-        Ok(i64::from_be_bytes([
-            data[0], data[1], data[2], data[3], data[4], data[5], data[6], data[7],
-        ]))
+        Ok(i64::from_be_bytes(fixed_width("int8", data)?))
     }
 }
 
@@ -154,11 +146,9 @@ impl TextDecode for u32 {
 }
 
 impl BinaryDecode for u32 {
+    #[inline]
     fn decode_binary(data: &[u8]) -> Result<Self, Error> {
-        if data.len() != 4 {
-            return Err(binary_length_error("oid", 4, data.len()));
-        }
-        Ok(u32::from_be_bytes([data[0], data[1], data[2], data[3]]))
+        Ok(u32::from_be_bytes(fixed_width("oid", data)?))
     }
 }
 
@@ -178,11 +168,9 @@ impl TextDecode for f32 {
 }
 
 impl BinaryDecode for f32 {
+    #[inline]
     fn decode_binary(data: &[u8]) -> Result<Self, Error> {
-        if data.len() != 4 {
-            return Err(binary_length_error("float4", 4, data.len()));
-        }
-        Ok(f32::from_be_bytes([data[0], data[1], data[2], data[3]]))
+        Ok(f32::from_be_bytes(fixed_width("float4", data)?))
     }
 }
 
@@ -200,13 +188,9 @@ impl TextDecode for f64 {
 }
 
 impl BinaryDecode for f64 {
+    #[inline]
     fn decode_binary(data: &[u8]) -> Result<Self, Error> {
-        if data.len() != 8 {
-            return Err(binary_length_error("float8", 8, data.len()));
-        }
-        Ok(f64::from_be_bytes([
-            data[0], data[1], data[2], data[3], data[4], data[5], data[6], data[7],
-        ]))
+        Ok(f64::from_be_bytes(fixed_width("float8", data)?))
     }
 }
 
@@ -221,8 +205,10 @@ impl TextDecode for String {
 }
 
 impl BinaryDecode for String {
+    #[inline]
     fn decode_binary(data: &[u8]) -> Result<Self, Error> {
-        String::from_utf8(data.to_vec()).map_err(|_| {
+        // Validate in place, then copy the whole buffer once.
+        std::str::from_utf8(data).map(str::to_owned).map_err(|_| {
             Error::Type(TypeError {
                 expected: "valid UTF-8",
                 actual: format!("invalid bytes: {:?}", &data[..data.len().min(20)]),
@@ -262,28 +248,28 @@ impl Decode for Vec<u8> {}
 impl TextDecode for [u8; 16] {
     fn decode_text(s: &str) -> Result<Self, Error> {
         // Parse UUID string: xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx
-        let s = s.replace('-', "");
-        if s.len() != 32 {
-            return Err(type_error("uuid", s));
-        }
-
+        let mut digits = s.bytes().filter(|&b| b != b'-');
         let mut bytes = [0u8; 16];
-        for (i, byte) in bytes.iter_mut().enumerate() {
-            *byte =
-                u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).map_err(|_| type_error("uuid", &s))?;
+        for byte in &mut bytes {
+            let (Some(hi), Some(lo)) = (
+                digits.next().and_then(hex_nibble),
+                digits.next().and_then(hex_nibble),
+            ) else {
+                return Err(type_error("uuid", s));
+            };
+            *byte = (hi << 4) | lo;
+        }
+        if digits.next().is_some() {
+            return Err(type_error("uuid", s));
         }
         Ok(bytes)
     }
 }
 
 impl BinaryDecode for [u8; 16] {
+    #[inline]
     fn decode_binary(data: &[u8]) -> Result<Self, Error> {
-        if data.len() != 16 {
-            return Err(binary_length_error("uuid", 16, data.len()));
-        }
-        let mut bytes = [0u8; 16];
-        bytes.copy_from_slice(data);
-        Ok(bytes)
+        fixed_width("uuid", data)
     }
 }
 
@@ -451,7 +437,8 @@ pub fn decode_value(type_oid: u32, data: Option<&[u8]>, format: Format) -> Resul
         }
 
         // Numeric (decimal)
-        (oid::NUMERIC, _) => {
+        (oid::NUMERIC, Format::Binary) => Ok(Value::Decimal(decode_numeric_binary(data)?)),
+        (oid::NUMERIC, Format::Text) => {
             let s = std::str::from_utf8(data).map_err(utf8_error)?;
             Ok(Value::Decimal(s.to_string()))
         }
@@ -557,11 +544,174 @@ pub fn decode_value(type_oid: u32, data: Option<&[u8]>, format: Format) -> Resul
             Ok(Value::Int(v as i32))
         }
 
+        // Arrays
+        (array, Format::Binary) if oid::is_array(array) => decode_array_binary(data),
+
         // Unknown type - return as text
         (_, _) => Ok(Value::Text(String::decode_binary(data)?)),
     }
 }
 
+/// Whether result columns of `type_oid` can be requested in binary format.
+///
+/// This is the result side of the format matrix: types [`decode_value`]
+/// understands in binary, and arrays of them. Everything else (intervals,
+/// network types, ranges, `timetz`, user-defined types) stays text.
+pub const fn supports_binary(type_oid: u32) -> bool {
+    let scalar = match oid::element_oid(type_oid) {
+        Some(element) => element,
+        None => type_oid,
+    };
+    matches!(
+        scalar,
+        oid::BOOL
+            | oid::INT2
+            | oid::INT4
+            | oid::INT8
+            | oid::FLOAT4
+            | oid::FLOAT8
+            | oid::NUMERIC
+            | oid::TEXT
+            | oid::VARCHAR
+            | oid::BPCHAR
+            | oid::NAME
+            | oid::CHAR
+            | oid::BYTEA
+            | oid::DATE
+            | oid::TIME
+            | oid::TIMESTAMP
+            | oid::TIMESTAMPTZ
+            | oid::UUID
+            | oid::JSON
+            | oid::JSONB
+            | oid::OID
+            | oid::XID
+            | oid::CID
+    )
+}
+
+/// Cursor over a binary value, reading big-endian fields.
+struct BinaryReader<'a> {
+    data: &'a [u8],
+    type_name: &'static str,
+}
+
+impl<'a> BinaryReader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], Error> {
+        if self.data.len() < n {
+            return Err(binary_length_error(self.type_name, n, self.data.len()));
+        }
+        let (head, tail) = self.data.split_at(n);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn i16(&mut self) -> Result<i16, Error> {
+        Ok(i16::from_be_bytes(fixed_width(
+            self.type_name,
+            self.take(2)?,
+        )?))
+    }
+
+    fn i32(&mut self) -> Result<i32, Error> {
+        Ok(i32::from_be_bytes(fixed_width(
+            self.type_name,
+            self.take(4)?,
+        )?))
+    }
+}
+
+/// Decode a binary NUMERIC into its decimal string form.
+fn decode_numeric_binary(data: &[u8]) -> Result<String, Error> {
+    let mut r = BinaryReader {
+        data,
+        type_name: "numeric",
+    };
+    let ndigits = r.i16()?;
+    let weight = i32::from(r.i16()?);
+    let sign = r.i16()? as u16;
+    let dscale = r.i16()? as u16 as usize;
+    let digits = (0..ndigits.max(0))
+        .map(|_| r.i16())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match sign {
+        NUMERIC_NAN => return Ok("NaN".to_string()),
+        NUMERIC_PINF => return Ok("Infinity".to_string()),
+        NUMERIC_NINF => return Ok("-Infinity".to_string()),
+        _ => {}
+    }
+    // Group `g` holds the digits for 10000^(weight - g).
+    let group = |g: i32| {
+        usize::try_from(g)
+            .ok()
+            .and_then(|g| digits.get(g).copied())
+            .unwrap_or(0)
+    };
+
+    let mut out = String::new();
+    if sign == NUMERIC_NEG && !digits.is_empty() {
+        out.push('-');
+    }
+    if weight < 0 {
+        out.push('0');
+    } else {
+        out.push_str(&group(0).to_string());
+        for g in 1..=weight {
+            out.push_str(&format!("{:04}", group(g)));
+        }
+    }
+    if dscale > 0 {
+        let mut frac = String::with_capacity(dscale + 4);
+        let mut g = weight + 1;
+        while frac.len() < dscale {
+            frac.push_str(&format!("{:04}", group(g)));
+            g += 1;
+        }
+        frac.truncate(dscale);
+        out.push('.');
+        out.push_str(&frac);
+    }
+    Ok(out)
+}
+
+/// Decode a binary array into `Value::Array`.
+///
+/// Only one-dimensional arrays map onto `Value::Array`; empty arrays of any
+/// dimension decode as empty.
+fn decode_array_binary(data: &[u8]) -> Result<Value, Error> {
+    let mut r = BinaryReader {
+        data,
+        type_name: "array",
+    };
+    let ndim = r.i32()?;
+    let _has_null = r.i32()?;
+    let element_oid = u32::from_be_bytes(fixed_width("array", r.take(4)?)?);
+    if ndim == 0 {
+        return Ok(Value::Array(Vec::new()));
+    }
+    if ndim != 1 {
+        return Err(type_error(
+            "one-dimensional array",
+            format!("{ndim} dimensions"),
+        ));
+    }
+    let len = r.i32()?;
+    let _lower_bound = r.i32()?;
+
+    let mut values = Vec::with_capacity(len.max(0) as usize);
+    for _ in 0..len {
+        let elem_len = r.i32()?;
+        let element = if elem_len < 0 {
+            None
+        } else {
+            Some(r.take(elem_len as usize)?)
+        };
+        values.push(decode_value(element_oid, element, Format::Binary)?);
+    }
+    Ok(Value::Array(values))
+}
+
 // ==================== Helper Functions ====================
 
 fn type_error(expected: &'static str, value: impl std::fmt::Display) -> Error {
@@ -586,6 +736,24 @@ fn type_error_with_source<E: std::error::Error>(
     })
 }
 
+/// Read a fixed-width binary value as an array, checking its length once.
+#[inline]
+fn fixed_width<const N: usize>(type_name: &'static str, data: &[u8]) -> Result<[u8; N], Error> {
+    data.try_into()
+        .map_err(|_| binary_length_error(type_name, N, data.len()))
+}
+
+/// Value of one ASCII hex digit.
+#[inline]
+fn hex_nibble(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
+
 fn binary_length_error(type_name: &'static str, expected: usize, actual: usize) -> Error {
     Error::Type(TypeError {
         expected: type_name,
@@ -672,6 +840,37 @@ fn date_to_days(year: i32, month: u32, day: u32) -> i32 {
     era * 146_097 + doe as i32 - 719_468
 }
 
+/// Per-result-set column decoder used for lazily decoded rows.
+///
+/// Captures the type OID and wire format of every column from the
+/// `RowDescription` so that [`Row::lazy`](sqlmodel_core::Row::lazy) can decode
+/// individual columns from the raw `DataRow` buffer on first access.
+#[derive(Debug, Clone)]
+pub struct PgColumnDecoder {
+    columns: Vec<(u32, Format)>,
+}
+
+impl PgColumnDecoder {
+    /// Create a decoder from `(type_oid, format)` pairs, one per column.
+    pub fn new(columns: Vec<(u32, Format)>) -> Self {
+        Self { columns }
+    }
+}
+
+impl sqlmodel_core::row::ColumnDecoder for PgColumnDecoder {
+    fn decode(&self, index: usize, raw: &[u8]) -> Result<Value, Error> {
+        let (type_oid, format) = self.columns.get(index).copied().ok_or_else(|| {
+            Error::Type(TypeError {
+                expected: "column description",
+                actual: format!("no description for column {index}"),
+                column: None,
+                rust_type: None,
+            })
+        })?;
+        decode_value(type_oid, Some(raw), format)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -725,6 +924,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_uuid_decoding_rejects_malformed_text() {
+        assert!(<[u8; 16]>::decode_text("55069c47-868b-4a08-a47f-3653262bce").is_err());
+        assert!(<[u8; 16]>::decode_text("55069c47-868b-4a08-a47f-3653262bce3500").is_err());
+        assert!(<[u8; 16]>::decode_text("zz069c47-868b-4a08-a47f-3653262bce35").is_err());
+        assert_eq!(
+            <[u8; 16]>::decode_text("55069C47868B4A08A47F3653262BCE35").unwrap()[0],
+            0x55
+        );
+    }
+
+    #[test]
+    fn test_fixed_width_binary_decoding() {
+        assert_eq!(i64::decode_binary(&(-7i64).to_be_bytes()).unwrap(), -7);
+        assert_eq!(i16::decode_binary(&300i16.to_be_bytes()).unwrap(), 300);
+        assert_eq!(
+            f64::decode_binary(&2.5f64.to_be_bytes()).unwrap().to_bits(),
+            2.5f64.to_bits()
+        );
+        let uuid: [u8; 16] = std::array::from_fn(|i| i as u8);
+        assert_eq!(<[u8; 16]>::decode_binary(&uuid).unwrap(), uuid);
+
+        let err = i64::decode_binary(&[0, 0, 0, 1]).unwrap_err();
+        assert!(err.to_string().contains("expected 8 bytes, got 4"));
+        assert!(<[u8; 16]>::decode_binary(&uuid[..15]).is_err());
+    }
+
+    #[test]
+    fn test_text_binary_decoding() {
+        assert_eq!(String::decode_binary(b"hello").unwrap(), "hello");
+        assert!(String::decode_binary(&[0xff, 0xfe]).is_err());
+        let value = decode_value(oid::TEXT, Some(b"caf\xc3\xa9"), Format::Binary).unwrap();
+        assert!(matches!(value, Value::Text(ref s) if s == "caf\u{e9}"));
+    }
+
     #[test]
     fn test_date_parsing() {
         // 2000-01-01 is day 10957 since Unix epoch
@@ -754,4 +988,163 @@ mod tests {
         let value = decode_value(oid::INT4, Some(&[0, 0, 0, 42]), Format::Binary).unwrap();
         assert!(matches!(value, Value::Int(42)));
     }
+
+    fn binary_round_trip(value: &Value) -> Value {
+        let (bytes, type_oid) = crate::types::encode_value(value, Format::Binary).unwrap();
+        decode_value(type_oid, Some(&bytes), Format::Binary).unwrap()
+    }
+
+    /// Deterministic xorshift generator for property-style loops.
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn digits(&mut self, len: u64) -> String {
+            (0..len)
+                .map(|_| char::from(b'0' + (self.next() % 10) as u8))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_binary_round_trip_scalars() {
+        for value in [
+            Value::Bool(true),
+            Value::SmallInt(-12),
+            Value::Int(i32::MIN),
+            Value::BigInt(i64::MAX),
+            Value::Float(1.5),
+            Value::Double(-2.25e300),
+            Value::Bytes(vec![0, 1, 0xff]),
+            Value::Date(19_000),
+            Value::Time(45_045_123_456),
+            Value::Timestamp(1_700_000_000_123_456),
+            Value::TimestampTz(-86_400_000_000),
+            Value::Uuid([7; 16]),
+        ] {
+            assert_eq!(binary_round_trip(&value), value);
+        }
+    }
+
+    #[test]
+    fn test_binary_round_trip_numeric() {
+        for s in [
+            "0",
+            "1",
+            "-1",
+            "9999",
+            "10000",
+            "12345.6789",
+            "0.0001",
+            "0.00001",
+            "-0.5",
+            "1.50",
+            "100000000",
+            "0.000",
+            "123456789012345678901234567890.000000001",
+            "NaN",
+            "Infinity",
+            "-Infinity",
+        ] {
+            let value = Value::Decimal(s.to_string());
+            assert_eq!(binary_round_trip(&value), value, "numeric {s}");
+        }
+        // Non-canonical input decodes to its canonical form.
+        assert_eq!(
+            binary_round_trip(&Value::Decimal("+007.10".to_string())),
+            Value::Decimal("7.10".to_string())
+        );
+        assert_eq!(
+            binary_round_trip(&Value::Decimal("-0.00".to_string())),
+            Value::Decimal("0.00".to_string())
+        );
+    }
+
+    #[test]
+    fn test_binary_round_trip_numeric_random() {
+        let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..2_000 {
+            let int_len = rng.next() % 24;
+            let frac_len = rng.next() % 20;
+            let mut int = rng.digits(int_len);
+            int = int.trim_start_matches('0').to_string();
+            if int.is_empty() {
+                int.push('0');
+            }
+            let frac = rng.digits(frac_len);
+            let mut s = if frac.is_empty() {
+                int
+            } else {
+                format!("{int}.{frac}")
+            };
+            if rng.next() % 2 == 0 && s.bytes().any(|b| b.is_ascii_digit() && b != b'0') {
+                s.insert(0, '-');
+            }
+            let value = Value::Decimal(s.clone());
+            assert_eq!(binary_round_trip(&value), value, "numeric {s}");
+        }
+    }
+
+    #[test]
+    fn test_binary_round_trip_integers_random() {
+        let mut rng = XorShift(42);
+        for _ in 0..1_000 {
+            let bits = rng.next();
+            for value in [
+                Value::SmallInt(bits as i16),
+                Value::Int(bits as i32),
+                Value::BigInt(bits as i64),
+                Value::Double(f64::from_bits(bits & !(0x7ff << 52))),
+            ] {
+                assert_eq!(binary_round_trip(&value), value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_binary_round_trip_arrays() {
+        for value in [
+            Value::Array(vec![Value::Int(1), Value::Null, Value::Int(3)]),
+            Value::Array(vec![Value::Decimal("1.5".to_string())]),
+            Value::Array(vec![Value::Uuid([1; 16]), Value::Uuid([2; 16])]),
+            Value::Array(vec![Value::Bool(false), Value::Bool(true)]),
+        ] {
+            assert_eq!(binary_round_trip(&value), value);
+        }
+        let empty = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 23];
+        assert_eq!(
+            decode_value(oid::INT4_ARRAY, Some(&empty), Format::Binary).unwrap(),
+            Value::Array(Vec::new())
+        );
+    }
+
+    #[test]
+    fn test_binary_numeric_rejects_truncated_data() {
+        assert!(decode_value(oid::NUMERIC, Some(&[0, 2, 0, 0]), Format::Binary).is_err());
+        assert!(decode_value(oid::INT4_ARRAY, Some(&[0, 0, 0, 1]), Format::Binary).is_err());
+    }
+
+    #[test]
+    fn test_supports_binary_matrix() {
+        for type_oid in [
+            oid::INT4,
+            oid::NUMERIC,
+            oid::UUID,
+            oid::TIMESTAMPTZ,
+            oid::BYTEA,
+            oid::INT8_ARRAY,
+            oid::TEXT_ARRAY,
+        ] {
+            assert!(supports_binary(type_oid), "oid {type_oid}");
+        }
+        for type_oid in [oid::INTERVAL, oid::TIMETZ, oid::INET, oid::UNKNOWN, 999_999] {
+            assert!(!supports_binary(type_oid), "oid {type_oid}");
+        }
+    }
 }
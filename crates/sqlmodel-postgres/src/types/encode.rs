@@ -461,7 +461,10 @@ pub fn encode_value(value: &Value, format: Format) -> Result<(Vec<u8>, u32), Err
             oid::FLOAT8
         }
         Value::Decimal(v) => {
-            buf.extend(v.as_bytes());
+            match format {
+                Format::Text => buf.extend(v.as_bytes()),
+                Format::Binary => encode_numeric_binary(v, &mut buf)?,
+            }
             oid::NUMERIC
         }
         Value::Text(v) => {
@@ -537,20 +540,337 @@ pub fn encode_value(value: &Value, format: Format) -> Result<(Vec<u8>, u32), Err
             oid::JSON
         }
         Value::Array(values) => {
+            return match format {
+                Format::Text => encode_array_text(values),
+                Format::Binary => encode_array_binary(values),
+            };
+        }
+        Value::Default => return Ok((vec![], oid::UNKNOWN)),
+    };
+
+    Ok((buf, type_oid))
+}
+
+/// The wire format to send `value` in as a query parameter.
+///
+/// Binary is used for every type with a fixed binary layout. Text, JSON and
+/// NULL go as text (their binary form is the same bytes), as do decimals in
+/// exponent notation and arrays without a typed, binary-encodable element.
+pub fn param_format(value: &Value) -> Format {
+    match value {
+        Value::Null | Value::Default | Value::Text(_) | Value::Json(_) => Format::Text,
+        Value::Decimal(v) if parse_plain_decimal(v).is_none() => Format::Text,
+        Value::Array(values) => {
+            let typed = values.iter().any(|v| !v.is_null());
+            let binary = values.iter().all(|v| match v {
+                Value::Null | Value::Text(_) | Value::Json(_) => true,
+                Value::Array(_) => false,
+                other => param_format(other) == Format::Binary,
+            });
+            if typed && binary {
+                Format::Binary
+            } else {
+                Format::Text
+            }
+        }
+        _ => Format::Binary,
+    }
+}
+
+/// Check a value bound to prepared-statement parameter `index` (one-based)
+/// against the parameter type `expected` the server described, and convert
+/// it to exactly that type.
+///
+/// Integers widen or narrow when the value fits, integers and floats convert
+/// to floating-point and numeric parameters, and timestamps convert between
+/// `timestamp` and `timestamptz`. Text is accepted for text, JSON and the
+/// date/time and UUID types (the server parses it) but not for numeric,
+/// boolean or bytea parameters. Unknown and user-defined types accept any
+/// value. Anything else is an [`Error::ParamTypeMismatch`].
+pub fn coerce_param(index: usize, expected: u32, value: &Value) -> Result<Value, Error> {
+    let int = match value {
+        Value::TinyInt(v) => Some(i64::from(*v)),
+        Value::SmallInt(v) => Some(i64::from(*v)),
+        Value::Int(v) => Some(i64::from(*v)),
+        Value::BigInt(v) => Some(*v),
+        _ => None,
+    };
+    let text = matches!(value, Value::Text(_));
+
+    let coerced = match (expected, value) {
+        (_, Value::Null | Value::Default) | (0 | oid::UNKNOWN, _) => Some(value.clone()),
+        (oid::INT2, _) => int.and_then(|v| i16::try_from(v).ok()).map(Value::SmallInt),
+        (oid::INT4, _) => int.and_then(|v| i32::try_from(v).ok()).map(Value::Int),
+        (oid::INT8, _) => int.map(Value::BigInt),
+        (oid::FLOAT4, Value::Float(v)) => Some(Value::Float(*v)),
+        (oid::FLOAT4, Value::Double(v)) => Some(Value::Float(*v as f32)),
+        (oid::FLOAT4, _) => int.map(|v| Value::Float(v as f32)),
+        (oid::FLOAT8, Value::Float(v)) => Some(Value::Double(f64::from(*v))),
+        (oid::FLOAT8, Value::Double(v)) => Some(Value::Double(*v)),
+        (oid::FLOAT8, _) => int.map(|v| Value::Double(v as f64)),
+        (oid::NUMERIC, Value::Decimal(_)) => Some(value.clone()),
+        (oid::NUMERIC, Value::Float(v)) if v.is_finite() => Some(Value::Decimal(v.to_string())),
+        (oid::NUMERIC, Value::Double(v)) if v.is_finite() => Some(Value::Decimal(v.to_string())),
+        (oid::NUMERIC, _) => int.map(|v| Value::Decimal(v.to_string())),
+        (oid::BOOL, Value::Bool(_))
+        | (oid::BYTEA, Value::Bytes(_))
+        | (oid::UUID, Value::Uuid(_))
+        | (oid::DATE, Value::Date(_))
+        | (oid::TIME, Value::Time(_))
+        | (oid::JSON | oid::JSONB, Value::Json(_)) => Some(value.clone()),
+        (oid::TIMESTAMP, Value::Timestamp(v) | Value::TimestampTz(v)) => Some(Value::Timestamp(*v)),
+        (oid::TIMESTAMPTZ, Value::Timestamp(v) | Value::TimestampTz(v)) => {
+            Some(Value::TimestampTz(*v))
+        }
+        (
+            oid::TEXT
+            | oid::VARCHAR
+            | oid::BPCHAR
+            | oid::NAME
+            | oid::JSON
+            | oid::JSONB
+            | oid::UUID
+            | oid::DATE
+            | oid::TIME
+            | oid::TIMETZ
+            | oid::TIMESTAMP
+            | oid::TIMESTAMPTZ
+            | oid::INTERVAL,
+            _,
+        ) => text.then(|| value.clone()),
+        (array, Value::Array(values)) if oid::is_array(array) => {
+            let element = oid::element_oid(array).unwrap_or(0);
+            let values = values
+                .iter()
+                .map(|v| coerce_param(index, element, v))
+                .collect::<Result<Vec<_>, _>>()?;
+            Some(Value::Array(values))
+        }
+        (array, _) if oid::is_array(array) => text.then(|| value.clone()),
+        (oid::BOOL | oid::BYTEA, _) => None,
+        _ => Some(value.clone()),
+    };
+
+    coerced.ok_or_else(|| Error::ParamTypeMismatch {
+        index,
+        expected: oid::type_name(expected).to_string(),
+        got: value.type_name().to_string(),
+    })
+}
+
+// ==================== Helper Functions ====================
+
+/// NUMERIC sign words.
+pub(super) const NUMERIC_POS: u16 = 0x0000;
+pub(super) const NUMERIC_NEG: u16 = 0x4000;
+pub(super) const NUMERIC_NAN: u16 = 0xC000;
+pub(super) const NUMERIC_PINF: u16 = 0xD000;
+pub(super) const NUMERIC_NINF: u16 = 0xF000;
+
+/// Split a plain decimal literal (`-12.340`) into sign, integer digits and
+/// fraction digits; `None` for anything else, including exponents.
+fn parse_plain_decimal(s: &str) -> Option<(bool, &str, &str)> {
+    let (negative, unsigned) = match s.as_bytes().first()? {
+        b'-' => (true, &s[1..]),
+        b'+' => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let (int, frac) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    let digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if (int.is_empty() && frac.is_empty()) || !digits(int) || !digits(frac) {
+        return None;
+    }
+    Some((negative, int, frac))
+}
+
+/// Encode a decimal string as binary NUMERIC: a header of digit count,
+/// weight, sign and display scale, then base-10000 digits.
+fn encode_numeric_binary(s: &str, buf: &mut Vec<u8>) -> Result<(), Error> {
+    let special = match s {
+        "NaN" => Some(NUMERIC_NAN),
+        "Infinity" => Some(NUMERIC_PINF),
+        "-Infinity" => Some(NUMERIC_NINF),
+        _ => None,
+    };
+    if let Some(sign) = special {
+        for word in [0_u16, 0, sign, 0] {
+            buf.extend_from_slice(&word.to_be_bytes());
+        }
+        return Ok(());
+    }
+
+    let Some((negative, int, frac)) = parse_plain_decimal(s) else {
+        return Err(Error::Type(TypeError {
+            expected: "plain decimal for binary numeric",
+            actual: s.to_string(),
+            column: None,
+            rust_type: None,
+        }));
+    };
+
+    // Pad both parts to whole base-10000 groups around the decimal point.
+    let int_pad = (4 - int.len() % 4) % 4;
+    let frac_pad = (4 - frac.len() % 4) % 4;
+    let padded: Vec<u8> = std::iter::repeat_n(b'0', int_pad)
+        .chain(int.bytes())
+        .chain(frac.bytes())
+        .chain(std::iter::repeat_n(b'0', frac_pad))
+        .collect();
+    let mut groups: Vec<i16> = padded
+        .chunks(4)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .fold(0_i16, |acc, b| acc * 10 + i16::from(b - b'0'))
+        })
+        .collect();
+    let mut weight = ((int.len() + int_pad) / 4) as i32 - 1;
+
+    let leading = groups.iter().take_while(|&&g| g == 0).count();
+    groups.drain(..leading);
+    weight -= leading as i32;
+    while groups.last() == Some(&0) {
+        groups.pop();
+    }
+    let (sign, weight) = if groups.is_empty() {
+        (NUMERIC_POS, 0)
+    } else if negative {
+        (NUMERIC_NEG, weight)
+    } else {
+        (NUMERIC_POS, weight)
+    };
+
+    buf.extend_from_slice(&(groups.len() as i16).to_be_bytes());
+    buf.extend_from_slice(&(weight as i16).to_be_bytes());
+    buf.extend_from_slice(&sign.to_be_bytes());
+    buf.extend_from_slice(&(frac.len() as u16).to_be_bytes());
+    for group in groups {
+        buf.extend_from_slice(&group.to_be_bytes());
+    }
+    Ok(())
+}
+
+/// Encode a one-dimensional array in binary format, typed by its
+/// (non-null) elements.
+fn encode_array_binary(values: &[Value]) -> Result<(Vec<u8>, u32), Error> {
+    let mut element_oid = None;
+    let mut elements = Vec::with_capacity(values.len());
+    for value in values {
+        if value.is_null() {
+            elements.push(None);
+            continue;
+        }
+        if matches!(value, Value::Array(_)) {
             return Err(Error::Type(TypeError {
-                expected: "scalar value",
-                actual: format!("array with {} elements", values.len()),
+                expected: "one-dimensional array",
+                actual: "nested array".to_string(),
                 column: None,
                 rust_type: None,
             }));
         }
-        Value::Default => return Ok((vec![], oid::UNKNOWN)),
+        let (bytes, elem_oid) = encode_value(value, Format::Binary)?;
+        match element_oid {
+            None => element_oid = Some(elem_oid),
+            Some(existing) if existing != elem_oid => {
+                return Err(Error::Type(TypeError {
+                    expected: "array elements of one type",
+                    actual: format!("elements with type oids {existing} and {elem_oid}"),
+                    column: None,
+                    rust_type: None,
+                }));
+            }
+            Some(_) => {}
+        }
+        elements.push(Some(bytes));
+    }
+
+    let (Some(element_oid), Some(array_oid)) = (element_oid, element_oid.and_then(oid::array_oid))
+    else {
+        return Err(Error::Type(TypeError {
+            expected: "binary array with a typed element",
+            actual: match element_oid {
+                Some(elem_oid) => format!("elements with type oid {elem_oid}"),
+                None => format!("{} untyped elements", values.len()),
+            },
+            column: None,
+            rust_type: None,
+        }));
     };
 
-    Ok((buf, type_oid))
+    let mut buf = Vec::new();
+    buf.extend_from_slice(&1_i32.to_be_bytes()); // dimensions
+    buf.extend_from_slice(&i32::from(elements.iter().any(Option::is_none)).to_be_bytes());
+    buf.extend_from_slice(&element_oid.to_be_bytes());
+    buf.extend_from_slice(&(elements.len() as i32).to_be_bytes());
+    buf.extend_from_slice(&1_i32.to_be_bytes()); // lower bound
+    for element in elements {
+        match element {
+            None => buf.extend_from_slice(&(-1_i32).to_be_bytes()),
+            Some(bytes) => {
+                buf.extend_from_slice(&(bytes.len() as i32).to_be_bytes());
+                buf.extend(bytes);
+            }
+        }
+    }
+    Ok((buf, array_oid))
 }
 
-// ==================== Helper Functions ====================
+/// Encode a one-dimensional array as a text-format array literal
+/// (`{"1","2"}`), typed by its (non-null) elements.
+fn encode_array_text(values: &[Value]) -> Result<(Vec<u8>, u32), Error> {
+    let mut element_oid = None;
+    let mut buf = vec![b'{'];
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            buf.push(b',');
+        }
+        if value.is_null() {
+            buf.extend(b"NULL");
+            continue;
+        }
+        if matches!(value, Value::Array(_)) {
+            return Err(Error::Type(TypeError {
+                expected: "one-dimensional array",
+                actual: "nested array".to_string(),
+                column: None,
+                rust_type: None,
+            }));
+        }
+        let (bytes, elem_oid) = encode_value(value, Format::Text)?;
+        match element_oid {
+            None => element_oid = Some(elem_oid),
+            Some(existing) if existing != elem_oid => {
+                return Err(Error::Type(TypeError {
+                    expected: "array elements of one type",
+                    actual: format!("elements with type oids {existing} and {elem_oid}"),
+                    column: None,
+                    rust_type: None,
+                }));
+            }
+            Some(_) => {}
+        }
+        buf.push(b'"');
+        for byte in bytes {
+            if byte == b'"' || byte == b'\\' {
+                buf.push(b'\\');
+            }
+            buf.push(byte);
+        }
+        buf.push(b'"');
+    }
+    buf.push(b'}');
+
+    let array_oid = element_oid.map_or(Some(oid::UNKNOWN), oid::array_oid);
+    let Some(array_oid) = array_oid else {
+        return Err(Error::Type(TypeError {
+            expected: "array of a type with an array type",
+            actual: format!("elements with type oid {}", element_oid.unwrap_or_default()),
+            column: None,
+            rust_type: None,
+        }));
+    };
+    Ok((buf, array_oid))
+}
 
 /// Convert days since Unix epoch to YYYY-MM-DD string.
 #[allow(clippy::many_single_char_names)]
@@ -605,6 +925,29 @@ fn micros_to_timestamp_string(micros: i64) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_array_encoding() {
+        let (bytes, type_oid) = encode_value(
+            &Value::Array(vec![Value::BigInt(1), Value::Null, Value::BigInt(-3)]),
+            Format::Text,
+        )
+        .unwrap();
+        assert_eq!(bytes, b"{\"1\",NULL,\"-3\"}");
+        assert_eq!(type_oid, oid::INT8_ARRAY);
+
+        let (bytes, type_oid) = encode_value(
+            &Value::Array(vec![Value::Text("a\"b".into()), Value::Text("c\\d".into())]),
+            Format::Text,
+        )
+        .unwrap();
+        assert_eq!(bytes, br#"{"a\"b","c\\d"}"#);
+        assert_eq!(type_oid, oid::TEXT_ARRAY);
+
+        let mixed = Value::Array(vec![Value::BigInt(1), Value::Text("x".into())]);
+        assert!(encode_value(&mixed, Format::Text).is_err());
+        assert!(encode_value(&Value::Array(vec![]), Format::Binary).is_err());
+    }
+
     #[test]
     fn test_bool_encoding() {
         assert_eq!(true.encode_text(), "t");
@@ -662,4 +1005,124 @@ mod tests {
         assert_eq!(Format::from_code(0), Format::Text);
         assert_eq!(Format::from_code(1), Format::Binary);
     }
+
+    #[test]
+    fn test_numeric_binary_encoding() {
+        let (bytes, type_oid) =
+            encode_value(&Value::Decimal("-12345.6789".into()), Format::Binary).unwrap();
+        assert_eq!(type_oid, oid::NUMERIC);
+        let words: Vec<u16> = bytes
+            .chunks(2)
+            .map(|w| u16::from_be_bytes([w[0], w[1]]))
+            .collect();
+        // ndigits, weight, sign, dscale, then base-10000 digits.
+        assert_eq!(words, [3, 1, NUMERIC_NEG, 4, 1, 2345, 6789]);
+
+        let (bytes, _) = encode_value(&Value::Decimal("NaN".into()), Format::Binary).unwrap();
+        assert_eq!(bytes, [0, 0, 0, 0, 0xc0, 0, 0, 0]);
+        assert!(encode_value(&Value::Decimal("1e5".into()), Format::Binary).is_err());
+        assert!(encode_value(&Value::Decimal("1.2.3".into()), Format::Binary).is_err());
+    }
+
+    #[test]
+    fn test_param_format_matrix() {
+        for value in [
+            Value::Bool(true),
+            Value::TinyInt(1),
+            Value::Int(1),
+            Value::BigInt(1),
+            Value::Double(1.0),
+            Value::Decimal("1.25".into()),
+            Value::Bytes(vec![1]),
+            Value::Date(1),
+            Value::Timestamp(1),
+            Value::TimestampTz(1),
+            Value::Uuid([0; 16]),
+            Value::Array(vec![Value::Int(1), Value::Null]),
+            Value::Array(vec![Value::Text("x".into())]),
+        ] {
+            assert_eq!(param_format(&value), Format::Binary, "{value:?}");
+        }
+        for value in [
+            Value::Null,
+            Value::Default,
+            Value::Text("x".into()),
+            Value::Json(serde_json::json!({"a": 1})),
+            Value::Decimal("1e10".into()),
+            Value::Array(vec![]),
+            Value::Array(vec![Value::Null]),
+            Value::Array(vec![Value::Array(vec![Value::Int(1)])]),
+        ] {
+            assert_eq!(param_format(&value), Format::Text, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn test_coerce_param_converts_compatible_values() {
+        let cases = [
+            (oid::INT8, Value::Int(7), Value::BigInt(7)),
+            (oid::INT2, Value::BigInt(-3), Value::SmallInt(-3)),
+            (oid::FLOAT8, Value::Int(2), Value::Double(2.0)),
+            (oid::NUMERIC, Value::BigInt(10), Value::Decimal("10".into())),
+            (
+                oid::NUMERIC,
+                Value::Double(0.5),
+                Value::Decimal("0.5".into()),
+            ),
+            (oid::TIMESTAMPTZ, Value::Timestamp(5), Value::TimestampTz(5)),
+            (
+                oid::DATE,
+                Value::Text("2024-01-31".into()),
+                Value::Text("2024-01-31".into()),
+            ),
+            (
+                oid::VARCHAR,
+                Value::Text("x".into()),
+                Value::Text("x".into()),
+            ),
+            (oid::INT4, Value::Null, Value::Null),
+            (0, Value::Bool(true), Value::Bool(true)),
+            (99_999, Value::Int(1), Value::Int(1)),
+            (
+                oid::INT8_ARRAY,
+                Value::Array(vec![Value::Int(1), Value::Null]),
+                Value::Array(vec![Value::BigInt(1), Value::Null]),
+            ),
+        ];
+        for (expected, value, coerced) in cases {
+            assert_eq!(coerce_param(1, expected, &value).unwrap(), coerced);
+        }
+    }
+
+    #[test]
+    fn test_coerce_param_reports_mismatches() {
+        let err = coerce_param(2, oid::INT4, &Value::Text("42".into())).unwrap_err();
+        assert!(matches!(
+            &err,
+            Error::ParamTypeMismatch { index: 2, expected, got }
+                if expected == "int4" && got == "TEXT"
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Parameter $2 expects int4, but a TEXT value was bound"
+        );
+
+        for (expected, value) in [
+            (oid::INT2, Value::Int(70_000)),
+            (oid::BOOL, Value::Int(1)),
+            (oid::BYTEA, Value::Text("x".into())),
+            (oid::TEXT, Value::Int(1)),
+            (oid::UUID, Value::Bytes(vec![0; 16])),
+            (oid::NUMERIC, Value::Double(f64::NAN)),
+            (oid::INT4_ARRAY, Value::Array(vec![Value::Text("x".into())])),
+        ] {
+            assert!(
+                matches!(
+                    coerce_param(1, expected, &value),
+                    Err(Error::ParamTypeMismatch { index: 1, .. })
+                ),
+                "{expected} {value:?}"
+            );
+        }
+    }
 }
@@ -21,8 +21,12 @@ pub mod oid;
 
 use std::collections::HashMap;
 
-pub use decode::{BinaryDecode, Decode, TextDecode, decode_value};
-pub use encode::{BinaryEncode, Encode, Format, TextEncode, encode_value};
+pub use decode::{
+    BinaryDecode, Decode, PgColumnDecoder, TextDecode, decode_value, supports_binary,
+};
+pub use encode::{
+    BinaryEncode, Encode, Format, TextEncode, coerce_param, encode_value, param_format,
+};
 
 /// Category of a PostgreSQL type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
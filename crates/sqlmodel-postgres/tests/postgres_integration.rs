@@ -6,7 +6,7 @@ use asupersync::{Cx, Outcome};
 use sqlmodel_core::error::QueryErrorKind;
 use sqlmodel_core::{Connection, Error, TransactionOps, Value};
 
-use sqlmodel_postgres::{PgConfig, SharedPgConnection, SslMode};
+use sqlmodel_postgres::{LoMode, PgConfig, SharedPgConnection, SslMode, SyncOptions, sync_table};
 use sqlmodel_schema::introspect::{Dialect, Introspector};
 
 const POSTGRES_URL_ENV: &str = "SQLMODEL_TEST_POSTGRES_URL";
@@ -262,7 +262,11 @@ fn postgres_unique_violation_maps_to_constraint() {
             .execute(&cx, &insert_sql, &[Value::Text("x".into())])
             .await;
         assert!(
-            matches!(&outcome, Outcome::Err(Error::Query(q)) if q.kind == QueryErrorKind::Constraint),
+            matches!(
+                &outcome,
+                Outcome::Err(Error::UniqueViolation { query: q, .. })
+                    if q.kind == QueryErrorKind::Constraint
+            ),
             "expected constraint error, got: {outcome:?}"
         );
 
@@ -438,3 +442,326 @@ fn postgres_introspection_preserves_composite_index_column_order() {
         let _ = conn.execute(&cx, &drop_sql, &[]).await;
     });
 }
+
+#[test]
+fn postgres_sync_table_copies_filtered_mapped_rows() {
+    let Some(cfg) = postgres_test_config() else {
+        eprintln!("skipping Postgres integration tests: set {POSTGRES_URL_ENV}");
+        return;
+    };
+
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let src = unwrap_outcome(SharedPgConnection::connect(&cx, cfg.clone()).await);
+        let dst = unwrap_outcome(SharedPgConnection::connect(&cx, cfg).await);
+        let source = test_table_name("sync_src");
+        let target = test_table_name("sync_dst");
+
+        unwrap_outcome(
+            src.execute(
+                &cx,
+                &format!(
+                    "CREATE TABLE \"{source}\" (id INTEGER PRIMARY KEY, name TEXT, age INTEGER)"
+                ),
+                &[],
+            )
+            .await,
+        );
+        unwrap_outcome(
+            src.execute(
+                &cx,
+                &format!(
+                    "INSERT INTO \"{source}\" VALUES (1, 'Thor', 1500), (2, 'Peter', 17), \
+                     (3, 'Tab\tand\\backslash', 40), (4, NULL, 99)"
+                ),
+                &[],
+            )
+            .await,
+        );
+        unwrap_outcome(
+            dst.execute(
+                &cx,
+                &format!(
+                    "CREATE TABLE \"{target}\" (id BIGINT, alias TEXT, extra TEXT DEFAULT 'x')"
+                ),
+                &[],
+            )
+            .await,
+        );
+        unwrap_outcome(
+            dst.execute(
+                &cx,
+                &format!("INSERT INTO \"{target}\" (id) VALUES (99)"),
+                &[],
+            )
+            .await,
+        );
+
+        let options = SyncOptions::new()
+            .column("id")
+            .map_column("name", "alias")
+            .filter("age >= 18")
+            .into_table(target.clone())
+            .truncate();
+        let copied = unwrap_outcome(sync_table(&cx, &src, &dst, &source, &options).await);
+        assert_eq!(copied, 3);
+
+        let rows = unwrap_outcome(
+            dst.query(
+                &cx,
+                &format!("SELECT id, alias, extra FROM \"{target}\" ORDER BY id"),
+                &[],
+            )
+            .await,
+        );
+        let copied: Vec<(i64, Option<String>, String)> = rows
+            .iter()
+            .map(|r| {
+                (
+                    r.get_as(0).expect("id"),
+                    r.get_as(1).expect("alias"),
+                    r.get_as(2).expect("extra"),
+                )
+            })
+            .collect();
+        assert_eq!(
+            copied,
+            [
+                (1, Some("Thor".to_string()), "x".to_string()),
+                (3, Some("Tab\tand\\backslash".to_string()), "x".to_string()),
+                (4, None, "x".to_string()),
+            ]
+        );
+
+        // A failing source leaves both connections usable.
+        let bad = SyncOptions::new()
+            .filter("no_such_column = 1")
+            .into_table(target.clone());
+        assert!(matches!(
+            sync_table(&cx, &src, &dst, &source, &bad).await,
+            Outcome::Err(_)
+        ));
+        unwrap_outcome(src.ping(&cx).await);
+        unwrap_outcome(dst.ping(&cx).await);
+
+        let _ = src
+            .execute(&cx, &format!("DROP TABLE IF EXISTS \"{source}\""), &[])
+            .await;
+        let _ = dst
+            .execute(&cx, &format!("DROP TABLE IF EXISTS \"{target}\""), &[])
+            .await;
+    });
+}
+
+#[test]
+fn postgres_binary_protocol_round_trips_typed_values() {
+    let Some(cfg) = postgres_test_config() else {
+        eprintln!("skipping Postgres integration tests: set {POSTGRES_URL_ENV}");
+        return;
+    };
+
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = unwrap_outcome(SharedPgConnection::connect(&cx, cfg.clone()).await);
+        let text_conn =
+            unwrap_outcome(SharedPgConnection::connect(&cx, cfg.binary_protocol(false)).await);
+        let table = test_table_name("binary_protocol");
+
+        unwrap_outcome(
+            conn.execute(
+                &cx,
+                &format!(
+                    "CREATE TABLE \"{table}\" (id INTEGER PRIMARY KEY, flag BOOLEAN, \
+                     big BIGINT, ratio DOUBLE PRECISION, amount NUMERIC(20, 4), uid UUID, \
+                     seen TIMESTAMP, blob BYTEA, tags INTEGER[])"
+                ),
+                &[],
+            )
+            .await,
+        );
+        let params = vec![
+            Value::Int(1),
+            Value::Bool(true),
+            Value::BigInt(-9_000_000_000),
+            Value::Double(0.125),
+            Value::Decimal("-12345.6700".into()),
+            Value::Uuid([0xab; 16]),
+            Value::Timestamp(1_700_000_000_123_456),
+            Value::Bytes(vec![0, 0xff, b'\\']),
+            Value::Array(vec![Value::Int(3), Value::Null, Value::Int(-1)]),
+        ];
+        unwrap_outcome(
+            conn.execute(
+                &cx,
+                &format!("INSERT INTO \"{table}\" VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"),
+                &params,
+            )
+            .await,
+        );
+
+        // Prepared statements fetch every column in binary.
+        let select_sql = format!("SELECT * FROM \"{table}\" WHERE id = $1");
+        let stmt = unwrap_outcome(conn.prepare(&cx, &select_sql).await);
+        let rows = unwrap_outcome(conn.query_prepared(&cx, &stmt, &[Value::Int(1)]).await);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].values().cloned().collect::<Vec<_>>(), params);
+
+        // Text-only connections decode the same scalars.
+        let rows = unwrap_outcome(
+            text_conn
+                .query(
+                    &cx,
+                    &select_sql.replace('*', "id, big, amount, uid, blob"),
+                    &[Value::Int(1)],
+                )
+                .await,
+        );
+        assert_eq!(
+            rows[0].values().cloned().collect::<Vec<_>>(),
+            [0, 2, 4, 5, 7].map(|i| params[i].clone())
+        );
+
+        let _ = conn
+            .execute(&cx, &format!("DROP TABLE IF EXISTS \"{table}\""), &[])
+            .await;
+    });
+}
+
+#[test]
+fn postgres_blob_streaming_and_large_objects() {
+    let Some(cfg) = postgres_test_config() else {
+        eprintln!("skipping Postgres integration tests: set {POSTGRES_URL_ENV}");
+        return;
+    };
+
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = unwrap_outcome(SharedPgConnection::connect(&cx, cfg).await);
+        let table = test_table_name("blobs");
+        let payload: Vec<u8> = (0..10_000_u32).map(|i| (i % 251) as u8).collect();
+
+        unwrap_outcome(
+            conn.execute(
+                &cx,
+                &format!("CREATE TABLE \"{table}\" (id BIGINT PRIMARY KEY, data BYTEA)"),
+                &[],
+            )
+            .await,
+        );
+        unwrap_outcome(
+            conn.execute(
+                &cx,
+                &format!("INSERT INTO \"{table}\" VALUES (1, $1), (2, NULL)"),
+                &[Value::Bytes(payload.clone())],
+            )
+            .await,
+        );
+
+        let mut reader = unwrap_outcome(
+            conn.read_blob(&cx, &table, "data", ("id", Value::BigInt(1)))
+                .await,
+        )
+        .chunk_size(4096);
+        assert_eq!(reader.len(), payload.len() as u64);
+        let mut streamed = Vec::new();
+        let mut chunks = 0;
+        while let Some(chunk) = unwrap_outcome(reader.next_chunk(&cx).await) {
+            streamed.extend(chunk);
+            chunks += 1;
+        }
+        assert_eq!(streamed, payload);
+        assert_eq!(chunks, 3);
+
+        let empty = unwrap_outcome(
+            conn.read_blob(&cx, &table, "data", ("id", Value::BigInt(2)))
+                .await,
+        );
+        assert!(empty.is_empty());
+        assert!(matches!(
+            conn.read_blob(&cx, &table, "data", ("id", Value::BigInt(3)))
+                .await,
+            Outcome::Err(_)
+        ));
+
+        let mut guard = conn.inner().lock(&cx).await.expect("lock connection");
+        unwrap_outcome(guard.execute_async(&cx, "BEGIN", &[]).await);
+        let oid = unwrap_outcome(guard.lo_create(&cx).await);
+        let mut lo = unwrap_outcome(guard.lo_open(&cx, oid, LoMode::ReadWrite).await);
+        for chunk in payload.chunks(3000) {
+            assert_eq!(unwrap_outcome(lo.write(&cx, chunk).await), chunk.len());
+        }
+        assert_eq!(unwrap_outcome(lo.tell(&cx).await), payload.len() as u64);
+        unwrap_outcome(lo.seek(&cx, std::io::SeekFrom::Start(9_000)).await);
+        assert_eq!(unwrap_outcome(lo.read(&cx, 5_000).await), payload[9_000..]);
+        unwrap_outcome(lo.truncate(&cx, 10).await);
+        unwrap_outcome(lo.seek(&cx, std::io::SeekFrom::Start(0)).await);
+        assert_eq!(unwrap_outcome(lo.read(&cx, 100).await), payload[..10]);
+        unwrap_outcome(lo.close(&cx).await);
+        unwrap_outcome(guard.lo_unlink(&cx, oid).await);
+        unwrap_outcome(guard.execute_async(&cx, "COMMIT", &[]).await);
+        drop(guard);
+
+        let _ = conn
+            .execute(&cx, &format!("DROP TABLE IF EXISTS \"{table}\""), &[])
+            .await;
+    });
+}
+
+#[test]
+fn postgres_prepared_param_type_mismatch_is_reported_client_side() {
+    let Some(cfg) = postgres_test_config() else {
+        eprintln!("skipping Postgres integration tests: set {POSTGRES_URL_ENV}");
+        return;
+    };
+
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = unwrap_outcome(SharedPgConnection::connect(&cx, cfg).await);
+        let stmt = unwrap_outcome(
+            conn.prepare(&cx, "SELECT $1::int8 + 1 AS next, $2::text AS label")
+                .await,
+        );
+
+        // Compatible values are converted to the described types.
+        let rows = unwrap_outcome(
+            conn.query_prepared(&cx, &stmt, &[Value::Int(41), Value::Text("x".into())])
+                .await,
+        );
+        assert_eq!(rows[0].get_as::<i64>(0).expect("next"), 42);
+
+        match conn
+            .query_prepared(&cx, &stmt, &[Value::Text("41".into()), Value::Null])
+            .await
+        {
+            Outcome::Err(Error::ParamTypeMismatch {
+                index,
+                expected,
+                got,
+            }) => {
+                assert_eq!(index, 1);
+                assert_eq!(expected, "int8");
+                assert_eq!(got, "TEXT");
+            }
+            other => panic!("expected ParamTypeMismatch, got {other:?}"),
+        }
+
+        // The connection is still usable afterwards.
+        unwrap_outcome(conn.query(&cx, "SELECT 1", &[]).await);
+    });
+}
@@ -0,0 +1,786 @@
+//! Public query AST, visitors, and pluggable SQL renderers.
+//!
+//! Builders such as [`Select`](crate::Select) lower themselves into a [`QueryAst`]
+//! before any SQL text is produced. The AST is a plain, non-generic data structure
+//! that tools can inspect or rewrite with a [`Visitor`] / [`VisitorMut`] (for example
+//! to inject a tenant filter or rewrite table names for sharding), and then render
+//! with any [`SqlRenderer`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! use sqlmodel_query::ast::VisitorMut;
+//!
+//! struct Shard(u32);
+//!
+//! impl VisitorMut for Shard {
+//!     fn visit_table_mut(&mut self, table: &mut String) {
+//!         *table = format!("{table}_{}", self.0);
+//!     }
+//! }
+//!
+//! let mut ast = Select::<Hero>::new().filter(Expr::col("age").gt(18)).to_ast();
+//! ast.accept_mut(&mut Shard(3));
+//! let (sql, params) = ast.render(Dialect::Postgres)?;
+//! // SELECT * FROM heroes_3 WHERE "age" > $1
+//! ```
+//!
+//! Rendering fails when the query uses a clause the renderer cannot express,
+//! such as `FOR UPDATE` with [`SqliteRenderer`].
+
+use crate::clause::{Limit, Offset, OrderBy, Where};
+use crate::expr::{Dialect, Expr};
+use crate::join::Join;
+use crate::subquery::SelectQuery;
+use sqlmodel_core::dialect::{IntoSqlDialect, SqlDialect};
+use sqlmodel_core::{Error, Value};
+
+/// A query statement in AST form.
+#[derive(Debug, Clone)]
+pub enum QueryAst {
+    /// A SELECT statement.
+    Select(SelectQuery),
+}
+
+impl QueryAst {
+    /// Walk the AST with a read-only visitor.
+    pub fn accept<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        match self {
+            QueryAst::Select(query) => visitor.visit_select(query),
+        }
+    }
+
+    /// Walk the AST with a mutating visitor.
+    pub fn accept_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        match self {
+            QueryAst::Select(query) => visitor.visit_select_mut(query),
+        }
+    }
+
+    /// Render the AST for `dialect` with a [`DialectRenderer`].
+    #[allow(clippy::result_large_err)]
+    pub fn render(&self, dialect: impl IntoSqlDialect) -> Result<(String, Vec<Value>), Error> {
+        DialectRenderer(dialect.into_sql_dialect()).render(self)
+    }
+
+    /// Render the AST with a custom renderer.
+    #[allow(clippy::result_large_err)]
+    pub fn render_with(&self, renderer: &dyn SqlRenderer) -> Result<(String, Vec<Value>), Error> {
+        renderer.render(self)
+    }
+
+    /// Render for the infallible builder APIs (`build_with_dialect`).
+    ///
+    /// Clauses the dialect rejects are emitted as written, so the database
+    /// reports them when the statement runs instead of the clause vanishing.
+    pub(crate) fn render_lenient(&self, dialect: &'static dyn SqlDialect) -> (String, Vec<Value>) {
+        match LenientRenderer(dialect).render(self) {
+            Ok(rendered) => rendered,
+            Err(_) => unreachable!("LenientRenderer accepts every clause"),
+        }
+    }
+}
+
+impl From<SelectQuery> for QueryAst {
+    fn from(query: SelectQuery) -> Self {
+        QueryAst::Select(query)
+    }
+}
+
+// ==================== Visitors ====================
+
+/// Read-only AST visitor.
+///
+/// Every method has a default that recurses into child nodes via the matching
+/// `walk_*` function; override only the nodes you care about and call the
+/// `walk_*` function yourself if you still want to descend.
+pub trait Visitor {
+    /// Visit a SELECT statement.
+    fn visit_select(&mut self, query: &SelectQuery) {
+        walk_select(self, query);
+    }
+
+    /// Visit a table reference (FROM target or JOIN target).
+    fn visit_table(&mut self, _table: &str) {}
+
+    /// Visit a column reference.
+    ///
+    /// The default passes a table qualifier to [`visit_table`](Self::visit_table).
+    /// Qualifiers may also name a JOIN alias; override this to tell them apart.
+    fn visit_column(&mut self, table: Option<&str>, _name: &str) {
+        if let Some(table) = table {
+            self.visit_table(table);
+        }
+    }
+
+    /// Visit the raw SQL body of a subquery (`Expr::Subquery`, `Expr::Exists`
+    /// or a raw subquery JOIN). Builder-based subqueries go through
+    /// [`visit_select`](Self::visit_select) instead.
+    fn visit_subquery_sql(&mut self, _sql: &str) {}
+
+    /// Visit a JOIN clause.
+    fn visit_join(&mut self, join: &Join) {
+        walk_join(self, join);
+    }
+
+    /// Visit an ORDER BY item.
+    fn visit_order_by(&mut self, order: &OrderBy) {
+        self.visit_expr(&order.expr);
+    }
+
+    /// Visit an expression.
+    fn visit_expr(&mut self, expr: &Expr) {
+        walk_expr(self, expr);
+    }
+}
+
+/// Mutating AST visitor.
+///
+/// The mutable counterpart of [`Visitor`], used for query rewriting.
+pub trait VisitorMut {
+    /// Visit a SELECT statement.
+    fn visit_select_mut(&mut self, query: &mut SelectQuery) {
+        walk_select_mut(self, query);
+    }
+
+    /// Visit a table reference (FROM target or JOIN target).
+    fn visit_table_mut(&mut self, _table: &mut String) {}
+
+    /// Visit a column reference.
+    ///
+    /// The default passes a table qualifier to
+    /// [`visit_table_mut`](Self::visit_table_mut). Qualifiers may also name a
+    /// JOIN alias; override this to tell them apart.
+    fn visit_column_mut(&mut self, table: Option<&mut String>, _name: &mut String) {
+        if let Some(table) = table {
+            self.visit_table_mut(table);
+        }
+    }
+
+    /// Visit the raw SQL body of a subquery (`Expr::Subquery`, `Expr::Exists`
+    /// or a raw subquery JOIN). Builder-based subqueries go through
+    /// [`visit_select_mut`](Self::visit_select_mut) instead.
+    fn visit_subquery_sql_mut(&mut self, _sql: &mut String) {}
+
+    /// Visit a JOIN clause.
+    fn visit_join_mut(&mut self, join: &mut Join) {
+        walk_join_mut(self, join);
+    }
+
+    /// Visit an ORDER BY item.
+    fn visit_order_by_mut(&mut self, order: &mut OrderBy) {
+        self.visit_expr_mut(&mut order.expr);
+    }
+
+    /// Visit an expression.
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        walk_expr_mut(self, expr);
+    }
+}
+
+/// Recurse into the children of a SELECT statement.
+pub fn walk_select<V: Visitor + ?Sized>(visitor: &mut V, query: &SelectQuery) {
+    visitor.visit_table(&query.table);
+    for join in &query.joins {
+        visitor.visit_join(join);
+    }
+    if let Some(where_clause) = &query.where_clause {
+        visitor.visit_expr(where_clause.expr());
+    }
+    if let Some(having) = &query.having {
+        visitor.visit_expr(having.expr());
+    }
+    for order in &query.order_by {
+        visitor.visit_order_by(order);
+    }
+}
+
+/// Recurse into the children of a SELECT statement (mutable).
+pub fn walk_select_mut<V: VisitorMut + ?Sized>(visitor: &mut V, query: &mut SelectQuery) {
+    visitor.visit_table_mut(&mut query.table);
+    for join in &mut query.joins {
+        visitor.visit_join_mut(join);
+    }
+    if let Some(where_clause) = &mut query.where_clause {
+        visitor.visit_expr_mut(where_clause.expr_mut());
+    }
+    if let Some(having) = &mut query.having {
+        visitor.visit_expr_mut(having.expr_mut());
+    }
+    for order in &mut query.order_by {
+        visitor.visit_order_by_mut(order);
+    }
+}
+
+/// Recurse into the children of a JOIN clause.
+pub fn walk_join<V: Visitor + ?Sized>(visitor: &mut V, join: &Join) {
+    if let Some(subquery) = &join.subquery {
+        visitor.visit_select(subquery);
+    } else if join.is_subquery {
+        visitor.visit_subquery_sql(&join.table);
+    } else {
+        visitor.visit_table(&join.table);
+    }
+    visitor.visit_expr(&join.on);
+}
+
+/// Recurse into the children of a JOIN clause (mutable).
+pub fn walk_join_mut<V: VisitorMut + ?Sized>(visitor: &mut V, join: &mut Join) {
+    if let Some(subquery) = &mut join.subquery {
+        visitor.visit_select_mut(subquery);
+    } else if join.is_subquery {
+        visitor.visit_subquery_sql_mut(&mut join.table);
+    } else {
+        visitor.visit_table_mut(&mut join.table);
+    }
+    visitor.visit_expr_mut(&mut join.on);
+}
+
+/// Recurse into the child expressions of `expr`.
+pub fn walk_expr<V: Visitor + ?Sized>(visitor: &mut V, expr: &Expr) {
+    match expr {
+        Expr::Column { table, name } => visitor.visit_column(table.as_deref(), name),
+        Expr::Subquery(sql) | Expr::Exists { subquery: sql, .. } => {
+            visitor.visit_subquery_sql(sql);
+        }
+        Expr::Literal(_) | Expr::Placeholder(_) | Expr::Raw(_) | Expr::CountStar => {}
+        Expr::Binary { left, right, .. }
+        | Expr::IsDistinctFrom { left, right, .. }
+        | Expr::TrigramSimilarity {
+            expr: left,
+            other: right,
+        }
+        | Expr::JsonContains {
+            expr: left,
+            other: right,
+        }
+        | Expr::JsonContainedBy {
+            expr: left,
+            other: right,
+        } => {
+            visitor.visit_expr(left);
+            visitor.visit_expr(right);
+        }
+        Expr::Unary { expr, .. }
+        | Expr::IsNull { expr, .. }
+        | Expr::Cast { expr, .. }
+        | Expr::Collate { expr, .. }
+        | Expr::Like { expr, .. }
+        | Expr::Paren(expr)
+        | Expr::JsonExtract { expr, .. }
+        | Expr::JsonExtractText { expr, .. }
+        | Expr::JsonExtractPath { expr, .. }
+        | Expr::JsonExtractPathText { expr, .. }
+        | Expr::JsonHasKey { expr, .. }
+        | Expr::JsonHasAnyKey { expr, .. }
+        | Expr::JsonHasAllKeys { expr, .. }
+        | Expr::JsonArrayLength { expr }
+        | Expr::JsonTypeof { expr } => visitor.visit_expr(expr),
+        Expr::Function { args, .. } => {
+            for arg in args {
+                visitor.visit_expr(arg);
+            }
+        }
+        Expr::Case {
+            when_clauses,
+            else_clause,
+        } => {
+            for (when, then) in when_clauses {
+                visitor.visit_expr(when);
+                visitor.visit_expr(then);
+            }
+            if let Some(else_clause) = else_clause {
+                visitor.visit_expr(else_clause);
+            }
+        }
+        Expr::In { expr, values, .. } => {
+            visitor.visit_expr(expr);
+            for value in values {
+                visitor.visit_expr(value);
+            }
+        }
+        Expr::Between {
+            expr, low, high, ..
+        } => {
+            visitor.visit_expr(expr);
+            visitor.visit_expr(low);
+            visitor.visit_expr(high);
+        }
+        Expr::ExistsQuery { subquery, .. } => visitor.visit_select(subquery),
+        Expr::Window {
+            function,
+            partition_by,
+            order_by,
+            ..
+        } => {
+            visitor.visit_expr(function);
+            for expr in partition_by {
+                visitor.visit_expr(expr);
+            }
+            for order in order_by {
+                visitor.visit_order_by(order);
+            }
+        }
+    }
+}
+
+/// Recurse into the child expressions of `expr` (mutable).
+pub fn walk_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, expr: &mut Expr) {
+    match expr {
+        Expr::Column { table, name } => visitor.visit_column_mut(table.as_mut(), name),
+        Expr::Subquery(sql) | Expr::Exists { subquery: sql, .. } => {
+            visitor.visit_subquery_sql_mut(sql);
+        }
+        Expr::Literal(_) | Expr::Placeholder(_) | Expr::Raw(_) | Expr::CountStar => {}
+        Expr::Binary { left, right, .. }
+        | Expr::IsDistinctFrom { left, right, .. }
+        | Expr::TrigramSimilarity {
+            expr: left,
+            other: right,
+        }
+        | Expr::JsonContains {
+            expr: left,
+            other: right,
+        }
+        | Expr::JsonContainedBy {
+            expr: left,
+            other: right,
+        } => {
+            visitor.visit_expr_mut(left);
+            visitor.visit_expr_mut(right);
+        }
+        Expr::Unary { expr, .. }
+        | Expr::IsNull { expr, .. }
+        | Expr::Cast { expr, .. }
+        | Expr::Collate { expr, .. }
+        | Expr::Like { expr, .. }
+        | Expr::Paren(expr)
+        | Expr::JsonExtract { expr, .. }
+        | Expr::JsonExtractText { expr, .. }
+        | Expr::JsonExtractPath { expr, .. }
+        | Expr::JsonExtractPathText { expr, .. }
+        | Expr::JsonHasKey { expr, .. }
+        | Expr::JsonHasAnyKey { expr, .. }
+        | Expr::JsonHasAllKeys { expr, .. }
+        | Expr::JsonArrayLength { expr }
+        | Expr::JsonTypeof { expr } => visitor.visit_expr_mut(expr),
+        Expr::Function { args, .. } => {
+            for arg in args {
+                visitor.visit_expr_mut(arg);
+            }
+        }
+        Expr::Case {
+            when_clauses,
+            else_clause,
+        } => {
+            for (when, then) in when_clauses {
+                visitor.visit_expr_mut(when);
+                visitor.visit_expr_mut(then);
+            }
+            if let Some(else_clause) = else_clause {
+                visitor.visit_expr_mut(else_clause);
+            }
+        }
+        Expr::In { expr, values, .. } => {
+            visitor.visit_expr_mut(expr);
+            for value in values {
+                visitor.visit_expr_mut(value);
+            }
+        }
+        Expr::Between {
+            expr, low, high, ..
+        } => {
+            visitor.visit_expr_mut(expr);
+            visitor.visit_expr_mut(low);
+            visitor.visit_expr_mut(high);
+        }
+        Expr::ExistsQuery { subquery, .. } => visitor.visit_select_mut(subquery),
+        Expr::Window {
+            function,
+            partition_by,
+            order_by,
+            ..
+        } => {
+            visitor.visit_expr_mut(function);
+            for expr in partition_by {
+                visitor.visit_expr_mut(expr);
+            }
+            for order in order_by {
+                visitor.visit_order_by_mut(order);
+            }
+        }
+    }
+}
+
+// ==================== Renderers ====================
+
+/// Renders a [`QueryAst`] into SQL text and bound parameters.
+///
+/// The provided methods implement ANSI-style rendering; per-dialect renderers
+/// override only the clauses whose syntax differs.
+pub trait SqlRenderer: Send + Sync {
+    /// The dialect used for identifier quoting, placeholders and LIMIT syntax.
+    fn dialect(&self) -> &'static dyn SqlDialect;
+
+    /// Render a complete statement.
+    #[allow(clippy::result_large_err)]
+    fn render(&self, ast: &QueryAst) -> Result<(String, Vec<Value>), Error> {
+        let mut params = Vec::new();
+        let sql = match ast {
+            QueryAst::Select(query) => self.render_select(query, &mut params)?,
+        };
+        Ok((sql, params))
+    }
+
+    /// Render a SELECT statement, appending bound values to `params`.
+    #[allow(clippy::result_large_err)]
+    fn render_select(&self, query: &SelectQuery, params: &mut Vec<Value>) -> Result<String, Error> {
+        let dialect = self.dialect();
+        let mut sql = String::new();
+
+        // SELECT
+        sql.push_str("SELECT ");
+        if query.distinct {
+            sql.push_str("DISTINCT ");
+        }
+
+        if query.columns.is_empty() {
+            sql.push('*');
+        } else {
+            sql.push_str(&query.columns.join(", "));
+        }
+
+        // FROM
+        sql.push_str(" FROM ");
+        sql.push_str(&query.table);
+
+        // JOINs
+        for join in &query.joins {
+            sql.push_str(&join.build_with_dialect(dialect, params, 0));
+        }
+
+        // WHERE
+        if let Some(where_clause) = &query.where_clause {
+            let (where_sql, where_params) = where_clause.build_with_dialect(dialect, params.len());
+            sql.push_str(" WHERE ");
+            sql.push_str(&where_sql);
+            params.extend(where_params);
+        }
+
+        // GROUP BY
+        if !query.group_by.is_empty() {
+            sql.push_str(" GROUP BY ");
+            sql.push_str(&query.group_by.join(", "));
+        }
+
+        // HAVING
+        if let Some(having) = &query.having {
+            let (having_sql, having_params) = having.build_with_dialect(dialect, params.len());
+            sql.push_str(" HAVING ");
+            sql.push_str(&having_sql);
+            params.extend(having_params);
+        }
+
+        // ORDER BY
+        if !query.order_by.is_empty() {
+            sql.push_str(" ORDER BY ");
+            let order_strs: Vec<_> = query
+                .order_by
+                .iter()
+                .map(|o| o.build(dialect, params, 0))
+                .collect();
+            sql.push_str(&order_strs.join(", "));
+        }
+
+        // LIMIT / OFFSET
+        sql.push_str(&self.render_limit_offset(query.limit, query.offset));
+
+        // FOR UPDATE
+        if query.for_update {
+            sql.push_str(self.render_for_update()?);
+        }
+
+        Ok(sql)
+    }
+
+    /// Render the LIMIT/OFFSET suffix (with a leading space), if any.
+    fn render_limit_offset(&self, limit: Option<Limit>, offset: Option<Offset>) -> String {
+        self.dialect()
+            .limit_offset(limit.map(|Limit(n)| n), offset.map(|Offset(n)| n))
+    }
+
+    /// Render the row-locking suffix used for `SELECT ... FOR UPDATE`.
+    ///
+    /// Renderers for databases without row-level locking return an error.
+    #[allow(clippy::result_large_err)]
+    fn render_for_update(&self) -> Result<&'static str, Error> {
+        Ok(" FOR UPDATE")
+    }
+}
+
+/// Built-in PostgreSQL renderer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PostgresRenderer;
+
+impl SqlRenderer for PostgresRenderer {
+    fn dialect(&self) -> &'static dyn SqlDialect {
+        Dialect::Postgres.as_dyn()
+    }
+}
+
+/// Built-in SQLite renderer.
+///
+/// SQLite has no row-level locking, so rendering a `FOR UPDATE` query fails
+/// rather than silently running it without the lock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SqliteRenderer;
+
+impl SqlRenderer for SqliteRenderer {
+    fn dialect(&self) -> &'static dyn SqlDialect {
+        Dialect::Sqlite.as_dyn()
+    }
+
+    fn render_for_update(&self) -> Result<&'static str, Error> {
+        Err(Error::Custom(
+            "SQLite does not support SELECT ... FOR UPDATE".to_string(),
+        ))
+    }
+}
+
+/// Built-in MySQL renderer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MysqlRenderer;
+
+impl SqlRenderer for MysqlRenderer {
+    fn dialect(&self) -> &'static dyn SqlDialect {
+        Dialect::Mysql.as_dyn()
+    }
+}
+
+/// Get the built-in renderer for `dialect`.
+pub fn renderer_for(dialect: Dialect) -> &'static dyn SqlRenderer {
+    match dialect {
+        Dialect::Postgres => &PostgresRenderer,
+        Dialect::Sqlite => &SqliteRenderer,
+        Dialect::Mysql => &MysqlRenderer,
+    }
+}
+
+/// Renderer for any [`SqlDialect`], including third-party ones.
+///
+/// Quoting, placeholders and LIMIT/OFFSET come from the dialect; clauses the
+/// trait does not cover (row locking) follow the built-in renderer of its
+/// [`base_dialect`](SqlDialect::base_dialect).
+#[derive(Debug, Clone, Copy)]
+pub struct DialectRenderer(pub &'static dyn SqlDialect);
+
+impl SqlRenderer for DialectRenderer {
+    fn dialect(&self) -> &'static dyn SqlDialect {
+        self.0
+    }
+
+    fn render_for_update(&self) -> Result<&'static str, Error> {
+        renderer_for(self.0.base_dialect()).render_for_update()
+    }
+}
+
+/// [`DialectRenderer`] that emits every clause as written; see
+/// [`QueryAst::render_lenient`].
+struct LenientRenderer(&'static dyn SqlDialect);
+
+impl SqlRenderer for LenientRenderer {
+    fn dialect(&self) -> &'static dyn SqlDialect {
+        self.0
+    }
+}
+
+/// Convenience: AND an extra condition into a SELECT's WHERE clause.
+///
+/// Useful from inside a [`VisitorMut`] that injects filters (e.g. tenant scoping).
+pub fn add_filter(query: &mut SelectQuery, expr: Expr) {
+    query.where_clause = Some(match query.where_clause.take() {
+        Some(existing) => existing.and(expr),
+        None => Where::new(expr),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> SelectQuery {
+        SelectQuery {
+            table: "heroes".to_string(),
+            columns: Vec::new(),
+            where_clause: Some(Where::new(Expr::col("age").gt(18))),
+            order_by: vec![OrderBy::asc(Expr::col("name"))],
+            joins: vec![Join::inner(
+                "teams",
+                Expr::qualified("heroes", "team_id").eq(Expr::qualified("teams", "id")),
+            )],
+            limit: Some(Limit(10)),
+            offset: None,
+            group_by: Vec::new(),
+            having: None,
+            distinct: false,
+            for_update: true,
+        }
+    }
+
+    #[derive(Default)]
+    struct TableCollector(Vec<String>);
+
+    impl Visitor for TableCollector {
+        fn visit_table(&mut self, table: &str) {
+            self.0.push(table.to_string());
+        }
+    }
+
+    struct Shard(u32);
+
+    impl VisitorMut for Shard {
+        fn visit_table_mut(&mut self, table: &mut String) {
+            *table = format!("{table}_{}", self.0);
+        }
+
+        fn visit_subquery_sql_mut(&mut self, sql: &mut String) {
+            *sql = sql.replace("FROM powers", &format!("FROM powers_{}", self.0));
+        }
+    }
+
+    struct Tenant(i64);
+
+    impl VisitorMut for Tenant {
+        fn visit_select_mut(&mut self, query: &mut SelectQuery) {
+            add_filter(query, Expr::col("tenant_id").eq(self.0));
+            walk_select_mut(self, query);
+        }
+    }
+
+    #[test]
+    fn test_render_matches_builder_output() {
+        let ast = QueryAst::Select(sample());
+        let (sql, params) = ast.render(Dialect::Postgres).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * FROM heroes INNER JOIN teams ON \"heroes\".\"team_id\" = \"teams\".\"id\" WHERE \"age\" > $1 ORDER BY \"name\" ASC LIMIT 10 FOR UPDATE"
+        );
+        assert_eq!(params.len(), 1);
+    }
+
+    #[test]
+    fn test_visitor_collects_tables() {
+        let ast = QueryAst::Select(sample());
+        let mut collector = TableCollector::default();
+        ast.accept(&mut collector);
+        // FROM, JOIN, then the qualifiers of the ON condition.
+        assert_eq!(collector.0, vec!["heroes", "teams", "heroes", "teams"]);
+    }
+
+    #[test]
+    fn test_visitor_mut_rewrites_tables() {
+        let mut query = sample();
+        add_filter(
+            &mut query,
+            Expr::exists(
+                "SELECT 1 FROM powers WHERE powers.hero_id = heroes.id",
+                Vec::new(),
+            ),
+        );
+        add_filter(
+            &mut query,
+            Expr::col("rank").in_list(vec![Expr::subquery("SELECT rank FROM powers")]),
+        );
+        let mut ast = QueryAst::Select(query);
+        ast.accept_mut(&mut Shard(3));
+        let (sql, _) = ast.render(Dialect::Postgres).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * FROM heroes_3 INNER JOIN teams_3 ON \"heroes_3\".\"team_id\" = \"teams_3\".\"id\" \
+             WHERE \"age\" > $1 AND EXISTS (SELECT 1 FROM powers_3 WHERE powers.hero_id = heroes.id) \
+             AND \"rank\" IN ((SELECT rank FROM powers_3)) ORDER BY \"name\" ASC LIMIT 10 FOR UPDATE"
+        );
+    }
+
+    #[test]
+    fn test_visitor_mut_rewrites_raw_subquery_join() {
+        let mut query = sample();
+        query.joins = vec![Join::lateral(
+            crate::JoinType::Inner,
+            "SELECT hero_id FROM powers",
+            "p",
+            Expr::qualified("p", "hero_id").eq(Expr::qualified("heroes", "id")),
+            Vec::new(),
+        )];
+        let mut ast = QueryAst::Select(query);
+        ast.accept_mut(&mut Shard(3));
+        let QueryAst::Select(query) = &ast;
+        assert_eq!(query.joins[0].table, "SELECT hero_id FROM powers_3");
+    }
+
+    #[test]
+    fn test_visitor_mut_injects_filter() {
+        let mut ast = QueryAst::Select(sample());
+        ast.accept_mut(&mut Tenant(7));
+        let QueryAst::Select(mut query) = ast;
+        query.for_update = false;
+        let (sql, params) = QueryAst::Select(query).render(Dialect::Sqlite).unwrap();
+        assert!(sql.contains("WHERE \"age\" > ?1 AND \"tenant_id\" = ?2"));
+        assert_eq!(params.len(), 2);
+    }
+
+    #[test]
+    fn test_sqlite_renderer_rejects_for_update() {
+        let err = QueryAst::Select(sample())
+            .render(Dialect::Sqlite)
+            .unwrap_err();
+        assert!(err.to_string().contains("FOR UPDATE"));
+        assert!(
+            QueryAst::Select(sample())
+                .render_with(&SqliteRenderer)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_lenient_render_keeps_for_update() {
+        let (sql, _) = QueryAst::Select(sample()).render_lenient(Dialect::Sqlite.as_dyn());
+        assert!(sql.ends_with("LIMIT 10 FOR UPDATE"));
+    }
+
+    #[test]
+    fn test_offset_without_limit_per_dialect() {
+        let mut query = sample();
+        query.limit = None;
+        query.offset = Some(Offset(5));
+        query.for_update = false;
+        let ast = QueryAst::Select(query);
+        assert!(
+            ast.render(Dialect::Postgres)
+                .unwrap()
+                .0
+                .ends_with("ASC OFFSET 5")
+        );
+        assert!(
+            ast.render(Dialect::Sqlite)
+                .unwrap()
+                .0
+                .ends_with("ASC LIMIT -1 OFFSET 5")
+        );
+    }
+
+    #[test]
+    fn test_custom_renderer() {
+        struct NoLock;
+        impl SqlRenderer for NoLock {
+            fn dialect(&self) -> &'static dyn SqlDialect {
+                Dialect::Postgres.as_dyn()
+            }
+            fn render_for_update(&self) -> Result<&'static str, Error> {
+                Ok(" FOR UPDATE SKIP LOCKED")
+            }
+        }
+        let (sql, _) = QueryAst::Select(sample()).render_with(&NoLock).unwrap();
+        assert!(sql.ends_with("FOR UPDATE SKIP LOCKED"));
+    }
+}
@@ -0,0 +1,211 @@
+//! Batched iteration over large tables.
+//!
+//! [`Select::for_each_batch`] walks every row of a query in primary-key
+//! order, `batch_size` rows at a time, and hands each batch to a callback:
+//!
+//! ```ignore
+//! select!(User)
+//!     .filter(Expr::col("locale").is_null())
+//!     .for_each_batch(&cx, &conn, 1_000, |batch| async move {
+//!         backfill_locales(&cx, &conn, batch).await
+//!     })
+//!     .await?;
+//! ```
+//!
+//! Each batch is fetched with a keyset condition on the last primary key
+//! seen (`WHERE id > ?`) rather than an OFFSET, so late batches cost no more
+//! than early ones and rows updated by the callback are never skipped or
+//! seen twice.
+
+use std::future::Future;
+
+use crate::ast::QueryAst;
+use crate::clause::{Limit, OrderBy, Where};
+use crate::expr::Expr;
+use crate::select::Select;
+use asupersync::{Cx, Outcome};
+use sqlmodel_core::{Connection, Error, Model, Value};
+
+/// How far a [`Select::for_each_batch`] walk has got.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchProgress {
+    /// Batches handed to the callback so far.
+    pub batches: u64,
+    /// Rows handed to the callback so far.
+    pub rows: u64,
+}
+
+impl<M: Model> Select<M> {
+    /// Run `f` on every row of this query, `batch_size` rows at a time, in
+    /// primary-key order.
+    ///
+    /// The query's own ORDER BY and LIMIT are replaced; it must not set an
+    /// OFFSET. Stops at the first batch whose callback does not succeed.
+    pub async fn for_each_batch<C, F, Fut>(
+        self,
+        cx: &Cx,
+        conn: &C,
+        batch_size: usize,
+        f: F,
+    ) -> Outcome<BatchProgress, Error>
+    where
+        C: Connection,
+        F: FnMut(Vec<M>) -> Fut,
+        Fut: Future<Output = Outcome<(), Error>>,
+    {
+        self.for_each_batch_with(cx, conn, batch_size, |_| {}, f)
+            .await
+    }
+
+    /// Like [`for_each_batch`](Self::for_each_batch), calling `progress`
+    /// after every batch.
+    pub async fn for_each_batch_with<C, P, F, Fut>(
+        self,
+        cx: &Cx,
+        conn: &C,
+        batch_size: usize,
+        mut progress: P,
+        mut f: F,
+    ) -> Outcome<BatchProgress, Error>
+    where
+        C: Connection,
+        P: FnMut(BatchProgress),
+        F: FnMut(Vec<M>) -> Fut,
+        Fut: Future<Output = Outcome<(), Error>>,
+    {
+        let QueryAst::Select(mut base) = self.to_ast();
+        if base.offset.is_some() {
+            return Outcome::Err(Error::Custom(
+                "for_each_batch pages by primary key; remove offset() from the query".to_string(),
+            ));
+        }
+        if M::PRIMARY_KEY.is_empty() {
+            return Outcome::Err(Error::Custom(format!(
+                "for_each_batch needs a primary key to page by, but {} has none",
+                M::TABLE_NAME
+            )));
+        }
+        let qualify = !base.joins.is_empty();
+        let pk_column = |name: &str| {
+            if qualify {
+                Expr::qualified(M::TABLE_NAME, name)
+            } else {
+                Expr::col(name)
+            }
+        };
+        let batch_size = batch_size.max(1);
+        base.order_by = M::PRIMARY_KEY
+            .iter()
+            .map(|&name| OrderBy::asc(pk_column(name)))
+            .collect();
+        base.limit = Some(Limit(batch_size as u64));
+
+        let mut done = BatchProgress::default();
+        let mut last_key: Option<Vec<Value>> = None;
+        loop {
+            let mut query = base.clone();
+            if let Some(last_key) = &last_key {
+                let Some(after) = after_key(M::PRIMARY_KEY, last_key, &pk_column) else {
+                    return Outcome::Err(Error::Custom(format!(
+                        "for_each_batch could not read the primary key of a {} row",
+                        M::TABLE_NAME
+                    )));
+                };
+                query.where_clause = Some(match query.where_clause {
+                    Some(existing) => Where::new(existing.expr().clone().paren().and(after)),
+                    None => Where::new(after),
+                });
+            }
+            let (sql, params) = match QueryAst::Select(query).render(conn.sql_dialect()) {
+                Ok(rendered) => rendered,
+                Err(e) => return Outcome::Err(e),
+            };
+            let rows = match conn.query(cx, &sql, &params).await {
+                Outcome::Ok(rows) => rows,
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            };
+            let mut batch = Vec::with_capacity(rows.len());
+            for row in &rows {
+                match self.hydrate(row) {
+                    Ok(model) => batch.push(model),
+                    Err(e) => return Outcome::Err(e),
+                }
+            }
+            let Some(last) = batch.last() else {
+                return Outcome::Ok(done);
+            };
+            last_key = Some(last.primary_key_value());
+
+            let fetched = batch.len();
+            match f(batch).await {
+                Outcome::Ok(()) => {}
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            }
+            done.batches += 1;
+            done.rows += fetched as u64;
+            progress(done);
+            if fetched < batch_size {
+                return Outcome::Ok(done);
+            }
+        }
+    }
+}
+
+/// The keyset condition selecting rows after `key` in primary-key order:
+/// `(a > ?) OR (a = ? AND b > ?)` for a composite key `(a, b)`.
+fn after_key(columns: &[&str], key: &[Value], column: &impl Fn(&str) -> Expr) -> Option<Expr> {
+    let mut pairs = columns.iter().zip(key).rev();
+    let (&name, value) = pairs.next()?;
+    let mut expr = column(name).gt(value.clone());
+    for (&name, value) in pairs {
+        expr = column(name)
+            .gt(value.clone())
+            .or(column(name).eq(value.clone()).and(expr.paren()))
+            .paren();
+    }
+    Some(expr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn col(name: &str) -> Expr {
+        Expr::col(name)
+    }
+
+    #[test]
+    fn test_after_single_column_key() {
+        let mut params = Vec::new();
+        let sql = after_key(&["id"], &[Value::BigInt(42)], &col)
+            .unwrap()
+            .build(&mut params, 0);
+        assert_eq!(sql, "\"id\" > $1");
+        assert_eq!(params, vec![Value::BigInt(42)]);
+        assert!(after_key(&["id"], &[], &col).is_none());
+    }
+
+    #[test]
+    fn test_after_composite_key() {
+        let mut params = Vec::new();
+        let sql = after_key(
+            &["tenant_id", "id"],
+            &[Value::BigInt(7), Value::BigInt(42)],
+            &col,
+        )
+        .unwrap()
+        .build(&mut params, 0);
+        assert_eq!(
+            sql,
+            "(\"tenant_id\" > $1 OR \"tenant_id\" = $2 AND (\"id\" > $3))"
+        );
+        assert_eq!(
+            params,
+            vec![Value::BigInt(7), Value::BigInt(7), Value::BigInt(42)]
+        );
+    }
+}
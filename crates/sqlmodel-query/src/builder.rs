@@ -9,9 +9,11 @@
 
 use crate::clause::Where;
 use crate::expr::{Dialect, Expr};
+use crate::writer::SqlWriter;
 use asupersync::{Cx, Outcome};
+use sqlmodel_core::dialect::{IntoSqlDialect, SqlDialect, UpsertSyntax};
 use sqlmodel_core::{
-    Connection, FieldInfo, InheritanceStrategy, Model, Row, TransactionOps, Value,
+    Connection, FieldInfo, InheritanceStrategy, Model, Row, TransactionOps, Value, ValueRef,
 };
 use std::collections::HashSet;
 use std::marker::PhantomData;
@@ -27,7 +29,7 @@ enum JoinedTableTarget {
     Child,
 }
 
-type JoinedSetPairs = Vec<(&'static str, Value)>;
+type JoinedSetPairs = Vec<(&'static str, SetValue)>;
 
 #[allow(clippy::result_large_err)]
 fn joined_parent_meta<M: Model>()
@@ -110,7 +112,7 @@ fn classify_joined_column<M: Model>(
 
 #[allow(clippy::result_large_err)]
 fn build_joined_pk_select_sql<M: Model>(
-    dialect: Dialect,
+    dialect: &'static dyn SqlDialect,
     where_clause: Option<&Where>,
     param_offset: usize,
 ) -> Result<(String, Vec<Value>), sqlmodel_core::Error> {
@@ -187,7 +189,7 @@ fn extract_pk_values_from_rows(
 async fn select_joined_pk_values_in_tx<Tx: TransactionOps, M: Model>(
     tx: &Tx,
     cx: &Cx,
-    dialect: Dialect,
+    dialect: &'static dyn SqlDialect,
     where_clause: Option<&Where>,
 ) -> Outcome<Vec<Vec<Value>>, sqlmodel_core::Error> {
     let pk_cols = M::PRIMARY_KEY;
@@ -232,7 +234,7 @@ fn split_explicit_joined_sets<M: Model>(
 }
 
 fn build_pk_in_where(
-    dialect: Dialect,
+    dialect: &'static dyn SqlDialect,
     pk_cols: &[&'static str],
     pk_values: &[Vec<Value>],
     param_offset: usize,
@@ -275,7 +277,7 @@ fn build_pk_in_where(
 }
 
 fn build_pk_in_where_qualified(
-    dialect: Dialect,
+    dialect: &'static dyn SqlDialect,
     table: &str,
     pk_cols: &[&'static str],
     pk_values: &[Vec<Value>],
@@ -319,21 +321,23 @@ fn build_pk_in_where_qualified(
 }
 
 fn build_update_sql_for_table_pk_in(
-    dialect: Dialect,
+    dialect: &'static dyn SqlDialect,
     table: &str,
     pk_cols: &[&'static str],
     pk_values: &[Vec<Value>],
-    set_pairs: &[(&'static str, Value)],
+    set_pairs: &[(&'static str, SetValue)],
 ) -> (String, Vec<Value>) {
     let mut params = Vec::new();
     let mut set_clauses = Vec::new();
     for (col, value) in set_pairs {
-        set_clauses.push(format!(
-            "{} = {}",
-            col,
-            dialect.placeholder(params.len() + 1)
-        ));
-        params.push(value.clone());
+        let rhs = match value {
+            SetValue::Value(value) => {
+                params.push(value.clone());
+                dialect.placeholder(params.len())
+            }
+            SetValue::Expr(expr) => expr.build_with_dialect(dialect, &mut params, 0),
+        };
+        set_clauses.push(format!("{col} = {rhs}"));
     }
     if set_clauses.is_empty() {
         return (String::new(), Vec::new());
@@ -355,7 +359,7 @@ fn build_update_sql_for_table_pk_in(
 }
 
 fn build_delete_sql_for_table_pk_in(
-    dialect: Dialect,
+    dialect: &'static dyn SqlDialect,
     table: &str,
     pk_cols: &[&'static str],
     pk_values: &[Vec<Value>],
@@ -369,7 +373,7 @@ fn build_delete_sql_for_table_pk_in(
 
 #[allow(clippy::result_large_err)]
 fn build_joined_child_select_sql_by_pk_in<M: Model>(
-    dialect: Dialect,
+    dialect: &'static dyn SqlDialect,
     pk_cols: &[&'static str],
     pk_values: &[Vec<Value>],
 ) -> Result<(String, Vec<Value>), sqlmodel_core::Error> {
@@ -434,13 +438,13 @@ fn rewrite_insert_as_ignore(sql: &mut String) {
 }
 
 fn append_on_conflict_clause(
-    dialect: Dialect,
+    dialect: &'static dyn SqlDialect,
     sql: &mut String,
     pk_cols: &[&'static str],
     insert_columns: &[&'static str],
     on_conflict: &OnConflict,
 ) {
-    if dialect == Dialect::Mysql {
+    if dialect.upsert_syntax() == UpsertSyntax::OnDuplicateKeyUpdate {
         match on_conflict {
             OnConflict::DoNothing => {
                 rewrite_insert_as_ignore(sql);
@@ -525,7 +529,7 @@ fn append_on_conflict_clause(
 }
 
 fn build_insert_sql_for_table_with_columns(
-    dialect: Dialect,
+    dialect: &'static dyn SqlDialect,
     table: &str,
     fields: &[FieldInfo],
     row: &[(&'static str, Value)],
@@ -549,7 +553,7 @@ fn build_insert_sql_for_table_with_columns(
     let mut params = Vec::new();
 
     for (name, value) in insert_fields {
-        if matches!(value, Value::Default) && dialect == Dialect::Sqlite {
+        if matches!(value, Value::Default) && dialect.base_dialect() == Dialect::Sqlite {
             // SQLite doesn't allow DEFAULT in VALUES; omit the column to trigger defaults.
             continue;
         }
@@ -584,7 +588,7 @@ fn build_insert_sql_for_table_with_columns(
 }
 
 fn build_insert_sql_for_table(
-    dialect: Dialect,
+    dialect: &'static dyn SqlDialect,
     table: &str,
     fields: &[FieldInfo],
     row: &[(&'static str, Value)],
@@ -596,7 +600,7 @@ fn build_insert_sql_for_table(
 }
 
 fn build_update_sql_for_table(
-    dialect: Dialect,
+    dialect: &'static dyn SqlDialect,
     table: &str,
     pk_cols: &[&'static str],
     pk_vals: &[Value],
@@ -646,7 +650,7 @@ fn extract_single_pk_i64(pk_vals: &[Value]) -> Option<i64> {
 async fn insert_joined_model_in_tx<Tx: TransactionOps, M: Model>(
     tx: &Tx,
     cx: &Cx,
-    dialect: Dialect,
+    dialect: &'static dyn SqlDialect,
     model: &M,
     parent_table: &'static str,
     parent_fields: &'static [FieldInfo],
@@ -674,7 +678,7 @@ async fn insert_joined_model_in_tx<Tx: TransactionOps, M: Model>(
         && effective_pk_vals[0].is_null();
 
     let mut inserted_id: Option<i64> = None;
-    if dialect == Dialect::Postgres && needs_generated_id {
+    if dialect.base_dialect() == Dialect::Postgres && needs_generated_id {
         let Some(pk_col) = pk_col else {
             return Outcome::Err(sqlmodel_core::Error::Custom(
                 "joined-table inheritance insert requires a primary key column".to_string(),
@@ -717,7 +721,7 @@ async fn insert_joined_model_in_tx<Tx: TransactionOps, M: Model>(
         }
 
         if needs_generated_id {
-            let id_sql = match dialect {
+            let id_sql = match dialect.base_dialect() {
                 Dialect::Sqlite => "SELECT last_insert_rowid()",
                 Dialect::Mysql => "SELECT LAST_INSERT_ID()",
                 Dialect::Postgres => unreachable!(),
@@ -833,6 +837,14 @@ impl<'a, M: Model> InsertBuilder<'a, M> {
         self
     }
 
+    /// Return the inserted row as a model, with database defaults, trigger
+    /// changes and generated columns applied.
+    ///
+    /// Use with [`InsertReturningModel::execute`].
+    pub fn returning_model(self) -> InsertReturningModel<'a, M> {
+        InsertReturningModel { insert: self }
+    }
+
     /// Handle conflicts by doing nothing (PostgreSQL ON CONFLICT DO NOTHING).
     ///
     /// This allows the insert to silently succeed even if it would violate
@@ -883,65 +895,88 @@ impl<'a, M: Model> InsertBuilder<'a, M> {
     }
 
     /// Build the INSERT SQL and parameters with specific dialect.
-    pub fn build_with_dialect(&self, dialect: Dialect) -> (String, Vec<Value>) {
+    pub fn build_with_dialect(&self, dialect: impl IntoSqlDialect) -> (String, Vec<Value>) {
+        let dialect = dialect.into_sql_dialect();
+        let mut w = SqlWriter::new(dialect);
+        self.build_into(&mut w);
+        w.into_parts()
+    }
+
+    /// Append the INSERT SQL and parameters to `w`.
+    ///
+    /// Reuse one cleared writer across statements to avoid allocating a new
+    /// buffer per insert.
+    pub fn build_into(&self, w: &mut SqlWriter) {
+        let dialect = w.dialect();
         let row = self.model.to_row();
         let fields = M::fields();
-
-        let insert_fields: Vec<_> = row
-            .iter()
-            .map(|(name, value)| {
-                let field = fields.iter().find(|f| f.column_name == *name);
-                if let Some(f) = field {
-                    if f.auto_increment && matches!(value, Value::Null) {
-                        return (*name, Value::Default);
-                    }
-                }
-                (*name, value.clone())
-            })
-            .collect();
+        let is_default = |name: &str, value: &Value| {
+            matches!(value, Value::Default)
+                || (matches!(value, Value::Null)
+                    && fields
+                        .iter()
+                        .any(|f| f.column_name == name && f.auto_increment))
+        };
+        // SQLite doesn't allow DEFAULT in VALUES; omit the column to trigger defaults.
+        let included = |name: &str, value: &Value| {
+            dialect.base_dialect() != Dialect::Sqlite || !is_default(name, value)
+        };
 
         let mut columns = Vec::new();
-        let mut placeholders = Vec::new();
-        let mut params = Vec::new();
-
-        for (name, value) in insert_fields {
-            if matches!(value, Value::Default) && dialect == Dialect::Sqlite {
-                // SQLite doesn't allow DEFAULT in VALUES; omit the column to trigger defaults.
-                continue;
-            }
-
-            columns.push(name);
-
-            if matches!(value, Value::Default) {
-                placeholders.push("DEFAULT".to_string());
-            } else {
-                params.push(value);
-                placeholders.push(dialect.placeholder(params.len()));
+        for (name, value) in &row {
+            if included(name, value) {
+                columns.push(*name);
             }
         }
 
-        let mut sql = if columns.is_empty() {
-            format!("INSERT INTO {} DEFAULT VALUES", M::TABLE_NAME)
+        if columns.is_empty() {
+            w.push("INSERT INTO ");
+            w.push(M::TABLE_NAME);
+            w.push(" DEFAULT VALUES");
         } else {
-            format!(
-                "INSERT INTO {} ({}) VALUES ({})",
-                M::TABLE_NAME,
-                columns.join(", "),
-                placeholders.join(", ")
-            )
-        };
+            w.push("INSERT INTO ");
+            w.push(M::TABLE_NAME);
+            w.push(" (");
+            for (i, col) in columns.iter().enumerate() {
+                if i > 0 {
+                    w.push(", ");
+                }
+                w.push(col);
+            }
+            w.push(") VALUES (");
+            let mut first = true;
+            for (name, value) in row {
+                if !included(name, &value) {
+                    continue;
+                }
+                if !first {
+                    w.push(", ");
+                }
+                first = false;
+                if is_default(name, &value) {
+                    w.push("DEFAULT");
+                } else {
+                    w.push_param(value);
+                }
+            }
+            w.push(")");
+        }
 
         // Add ON CONFLICT/UPSERT clause if specified
         if let Some(on_conflict) = &self.on_conflict {
-            append_on_conflict_clause(dialect, &mut sql, M::PRIMARY_KEY, &columns, on_conflict);
+            append_on_conflict_clause(
+                dialect,
+                w.buffer_mut(),
+                M::PRIMARY_KEY,
+                &columns,
+                on_conflict,
+            );
         }
 
         // Add RETURNING clause if requested
         if self.returning {
-            sql.push_str(" RETURNING *");
+            w.push(" RETURNING *");
         }
-
-        (sql, params)
     }
 
     /// Execute the INSERT and return the inserted ID.
@@ -951,7 +986,7 @@ impl<'a, M: Model> InsertBuilder<'a, M> {
         conn: &C,
     ) -> Outcome<i64, sqlmodel_core::Error> {
         if is_joined_inheritance_child::<M>() {
-            let dialect = conn.dialect();
+            let dialect = conn.sql_dialect();
             let on_conflict = self.on_conflict.clone();
             let (parent_table, parent_fields) = match joined_parent_meta::<M>() {
                 Ok(v) => v,
@@ -1052,7 +1087,7 @@ impl<'a, M: Model> InsertBuilder<'a, M> {
 
             // 1) Insert base row (parent table), possibly retrieving the generated PK.
             let mut inserted_id: Option<i64> = None;
-            if dialect == Dialect::Postgres {
+            if dialect.base_dialect() == Dialect::Postgres {
                 let Some(pk_col) = pk_col else {
                     tx_rollback_best_effort(tx, cx).await;
                     return Outcome::Err(sqlmodel_core::Error::Custom(
@@ -1150,7 +1185,7 @@ impl<'a, M: Model> InsertBuilder<'a, M> {
                 }
 
                 if needs_generated_id {
-                    let id_sql = match dialect {
+                    let id_sql = match dialect.base_dialect() {
                         Dialect::Sqlite => "SELECT last_insert_rowid()",
                         Dialect::Mysql => "SELECT LAST_INSERT_ID()",
                         Dialect::Postgres => unreachable!(),
@@ -1243,7 +1278,7 @@ impl<'a, M: Model> InsertBuilder<'a, M> {
             return Outcome::Ok(id);
         }
 
-        let (sql, params) = self.build_with_dialect(conn.dialect());
+        let (sql, params) = self.build_with_dialect(conn.sql_dialect());
         conn.insert(cx, &sql, &params).await
     }
 
@@ -1264,7 +1299,7 @@ impl<'a, M: Model> InsertBuilder<'a, M> {
                 ));
             }
 
-            let dialect = conn.dialect();
+            let dialect = conn.sql_dialect();
             let inh = M::inheritance();
             let Some(parent_table) = inh.parent else {
                 return Outcome::Err(sqlmodel_core::Error::Custom(
@@ -1304,7 +1339,7 @@ impl<'a, M: Model> InsertBuilder<'a, M> {
             };
 
             let mut inserted_id: Option<i64> = None;
-            if dialect == Dialect::Postgres {
+            if dialect.base_dialect() == Dialect::Postgres {
                 let Some(pk_col) = pk_col else {
                     tx_rollback_best_effort(tx, cx).await;
                     return Outcome::Err(sqlmodel_core::Error::Custom(
@@ -1371,7 +1406,7 @@ impl<'a, M: Model> InsertBuilder<'a, M> {
                 }
 
                 if needs_generated_id {
-                    let id_sql = match dialect {
+                    let id_sql = match dialect.base_dialect() {
                         Dialect::Sqlite => "SELECT last_insert_rowid()",
                         Dialect::Mysql => "SELECT LAST_INSERT_ID()",
                         Dialect::Postgres => unreachable!(),
@@ -1456,11 +1491,79 @@ impl<'a, M: Model> InsertBuilder<'a, M> {
             return row_out;
         }
 
-        let (sql, params) = self.build_with_dialect(conn.dialect());
+        let (sql, params) = self.build_with_dialect(conn.sql_dialect());
         conn.query_one(cx, &sql, &params).await
     }
 }
 
+/// INSERT that hydrates the inserted row back into a model.
+///
+/// Dialects with `RETURNING` (Postgres, SQLite) get the row back in the same
+/// statement. Elsewhere, and for joined-table inheritance children, the row
+/// is re-read by primary key after the insert, using the generated id when
+/// the key was left unset.
+///
+/// # Example
+///
+/// ```ignore
+/// let hero = insert!(hero).returning_model().execute(cx, &conn).await?;
+/// assert!(hero.expect("inserted").id.is_some());
+/// ```
+#[derive(Debug)]
+pub struct InsertReturningModel<'a, M: Model> {
+    insert: InsertBuilder<'a, M>,
+}
+
+impl<M: Model> InsertReturningModel<'_, M> {
+    /// Execute the INSERT and return the stored row.
+    ///
+    /// Returns `None` when `ON CONFLICT DO NOTHING` skipped the row on a
+    /// dialect with `RETURNING`. The re-read fallback finds the existing row
+    /// in that case instead.
+    pub async fn execute<C: Connection>(
+        self,
+        cx: &Cx,
+        conn: &C,
+    ) -> Outcome<Option<M>, sqlmodel_core::Error> {
+        if conn.sql_dialect().supports_returning() && !is_joined_inheritance_child::<M>() {
+            return match self.insert.execute_returning(cx, conn).await {
+                Outcome::Ok(Some(row)) => match M::from_row(&row) {
+                    Ok(model) => Outcome::Ok(Some(model)),
+                    Err(e) => Outcome::Err(e),
+                },
+                Outcome::Ok(None) => Outcome::Ok(None),
+                Outcome::Err(e) => Outcome::Err(e),
+                Outcome::Cancelled(r) => Outcome::Cancelled(r),
+                Outcome::Panicked(p) => Outcome::Panicked(p),
+            };
+        }
+
+        let pk_vals = self.insert.model.primary_key_value();
+        let id = match self.insert.execute(cx, conn).await {
+            Outcome::Ok(id) => id,
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        };
+        let pk_vals = if pk_vals.iter().all(|v| !v.is_null()) {
+            pk_vals
+        } else if M::PRIMARY_KEY.len() == 1 && pk_vals.len() == 1 {
+            vec![Value::BigInt(id)]
+        } else {
+            return Outcome::Err(sqlmodel_core::Error::Custom(format!(
+                "cannot re-read inserted row of '{}': primary key is not set",
+                M::TABLE_NAME
+            )));
+        };
+
+        let mut query = crate::Select::<M>::new();
+        for (col, value) in M::PRIMARY_KEY.iter().zip(pk_vals) {
+            query = query.filter(Expr::qualified(M::TABLE_NAME, *col).eq(value));
+        }
+        query.one_or_none(cx, conn).await
+    }
+}
+
 /// Bulk INSERT query builder.
 ///
 /// # Example
@@ -1475,6 +1578,7 @@ pub struct InsertManyBuilder<'a, M: Model> {
     models: &'a [M],
     returning: bool,
     on_conflict: Option<OnConflict>,
+    max_params: Option<usize>,
 }
 
 impl<'a, M: Model> InsertManyBuilder<'a, M> {
@@ -1484,9 +1588,20 @@ impl<'a, M: Model> InsertManyBuilder<'a, M> {
             models,
             returning: false,
             on_conflict: None,
+            max_params: None,
         }
     }
 
+    /// Cap the bind parameters carried by each generated statement.
+    ///
+    /// Rows are split across statements so that no statement exceeds the
+    /// limit. Defaults to [`Dialect::max_bind_params`]; a single row is always
+    /// emitted even if it alone exceeds the limit.
+    pub fn max_params(mut self, max_params: usize) -> Self {
+        self.max_params = Some(max_params);
+        self
+    }
+
     /// Add RETURNING * clause to return the inserted rows.
     pub fn returning(mut self) -> Self {
         self.returning = true;
@@ -1508,13 +1623,24 @@ impl<'a, M: Model> InsertManyBuilder<'a, M> {
         self
     }
 
+    /// Handle conflicts on `target` by updating `columns`; empty `columns`
+    /// update every inserted non-primary-key column.
+    pub fn on_conflict_target_do_update(mut self, target: &[&str], columns: &[&str]) -> Self {
+        self.on_conflict = Some(OnConflict::DoUpdate {
+            columns: columns.iter().map(|s| s.to_string()).collect(),
+            target: target.iter().map(|s| s.to_string()).collect(),
+        });
+        self
+    }
+
     /// Build the bulk INSERT SQL and parameters with default dialect.
     pub fn build(&self) -> (String, Vec<Value>) {
         self.build_with_dialect(Dialect::default())
     }
 
     /// Build the bulk INSERT SQL and parameters with specific dialect.
-    pub fn build_with_dialect(&self, dialect: Dialect) -> (String, Vec<Value>) {
+    pub fn build_with_dialect(&self, dialect: impl IntoSqlDialect) -> (String, Vec<Value>) {
+        let dialect = dialect.into_sql_dialect();
         let batches = self.build_batches_with_dialect(dialect);
         match batches.len() {
             0 => (String::new(), Vec::new()),
@@ -1532,9 +1658,14 @@ impl<'a, M: Model> InsertManyBuilder<'a, M> {
 
     /// Build bulk INSERT statements for the given dialect.
     ///
-    /// SQLite requires column omission when defaults are used, which can
-    /// produce multiple statements to preserve correct semantics.
-    pub fn build_batches_with_dialect(&self, dialect: Dialect) -> Vec<(String, Vec<Value>)> {
+    /// Rows are split into several statements whenever one statement would
+    /// exceed the bind parameter limit (see [`max_params`](Self::max_params)).
+    /// SQLite also requires column omission when defaults are used, which can
+    /// produce further statements to preserve correct semantics.
+    pub fn build_batches_with_dialect(
+        &self,
+        dialect: impl IntoSqlDialect,
+    ) -> Vec<(String, Vec<Value>)> {
         enum Batch {
             Values {
                 columns: Vec<&'static str>,
@@ -1543,6 +1674,8 @@ impl<'a, M: Model> InsertManyBuilder<'a, M> {
             DefaultValues,
         }
 
+        let dialect = dialect.into_sql_dialect();
+
         if self.models.is_empty() {
             return Vec::new();
         }
@@ -1555,8 +1688,13 @@ impl<'a, M: Model> InsertManyBuilder<'a, M> {
             return Vec::new();
         }
 
-        if dialect != Dialect::Sqlite {
-            return vec![self.build_single_with_dialect(dialect)];
+        let max_params = self
+            .max_params
+            .unwrap_or_else(|| dialect.max_bind_params())
+            .max(1);
+
+        if dialect.base_dialect() != Dialect::Sqlite {
+            return self.build_chunked_with_dialect(dialect, max_params);
         }
 
         let fields = M::fields();
@@ -1616,7 +1754,10 @@ impl<'a, M: Model> InsertManyBuilder<'a, M> {
             }
 
             match batches.last_mut() {
-                Some(Batch::Values { columns, rows }) if *columns == columns_for_row => {
+                Some(Batch::Values { columns, rows })
+                    if *columns == columns_for_row
+                        && (rows.len() + 1) * columns.len() <= max_params =>
+                {
                     rows.push(values_for_row);
                 }
                 _ => batches.push(Batch::Values {
@@ -1646,7 +1787,11 @@ impl<'a, M: Model> InsertManyBuilder<'a, M> {
         statements
     }
 
-    fn build_single_with_dialect(&self, dialect: Dialect) -> (String, Vec<Value>) {
+    fn build_chunked_with_dialect(
+        &self,
+        dialect: &'static dyn SqlDialect,
+        max_params: usize,
+    ) -> Vec<(String, Vec<Value>)> {
         let fields = M::fields();
         let rows: Vec<Vec<(&'static str, Value)>> =
             self.models.iter().map(|model| model.to_row()).collect();
@@ -1672,58 +1817,59 @@ impl<'a, M: Model> InsertManyBuilder<'a, M> {
             })
             .collect();
 
-        let mut all_values = Vec::new();
-        let mut value_groups = Vec::new();
-
-        for row in &rows {
-            let values: Vec<_> = insert_columns
-                .iter()
-                .map(|col| {
-                    let val = row
-                        .iter()
-                        .find(|(name, _)| name == col)
-                        .map_or(Value::Null, |(_, v)| v.clone());
-
-                    // Map Null auto-increment fields to DEFAULT
-                    let field = fields.iter().find(|f| f.column_name == *col);
-                    if let Some(f) = field {
-                        if f.auto_increment && matches!(val, Value::Null) {
-                            return Value::Default;
+        let value_rows: Vec<Vec<Value>> = rows
+            .iter()
+            .map(|row| {
+                insert_columns
+                    .iter()
+                    .map(|col| {
+                        let val = row
+                            .iter()
+                            .find(|(name, _)| name == col)
+                            .map_or(Value::Null, |(_, v)| v.clone());
+
+                        // Map Null auto-increment fields to DEFAULT
+                        let field = fields.iter().find(|f| f.column_name == *col);
+                        if let Some(f) = field {
+                            if f.auto_increment && matches!(val, Value::Null) {
+                                return Value::Default;
+                            }
                         }
-                    }
-                    val
-                })
-                .collect();
+                        val
+                    })
+                    .collect()
+            })
+            .collect();
 
-            let mut placeholders = Vec::new();
-            for v in &values {
-                if matches!(v, Value::Default) {
-                    placeholders.push("DEFAULT".to_string());
-                } else {
-                    all_values.push(v.clone());
-                    placeholders.push(dialect.placeholder(all_values.len()));
-                }
+        // Group rows so each statement stays within the parameter limit.
+        let mut statements = Vec::new();
+        let mut start = 0;
+        let mut params = 0;
+        for (idx, row) in value_rows.iter().enumerate() {
+            let row_params = row.iter().filter(|v| !matches!(v, Value::Default)).count();
+            if idx > start && params + row_params > max_params {
+                statements.push(self.build_values_batch_sql(
+                    dialect,
+                    &insert_columns,
+                    &value_rows[start..idx],
+                ));
+                start = idx;
+                params = 0;
             }
-
-            value_groups.push(format!("({})", placeholders.join(", ")));
+            params += row_params;
         }
+        statements.push(self.build_values_batch_sql(
+            dialect,
+            &insert_columns,
+            &value_rows[start..],
+        ));
 
-        let mut sql = format!(
-            "INSERT INTO {} ({}) VALUES {}",
-            M::TABLE_NAME,
-            insert_columns.join(", "),
-            value_groups.join(", ")
-        );
-
-        self.append_on_conflict(dialect, &mut sql, &insert_columns);
-        self.append_returning(&mut sql);
-
-        (sql, all_values)
+        statements
     }
 
     fn build_values_batch_sql(
         &self,
-        dialect: Dialect,
+        dialect: &'static dyn SqlDialect,
         columns: &[&'static str],
         rows: &[Vec<Value>],
     ) -> (String, Vec<Value>) {
@@ -1762,7 +1908,7 @@ impl<'a, M: Model> InsertManyBuilder<'a, M> {
 
     fn append_on_conflict(
         &self,
-        dialect: Dialect,
+        dialect: &'static dyn SqlDialect,
         sql: &mut String,
         insert_columns: &[&'static str],
     ) {
@@ -1783,6 +1929,21 @@ impl<'a, M: Model> InsertManyBuilder<'a, M> {
         cx: &Cx,
         conn: &C,
     ) -> Outcome<u64, sqlmodel_core::Error> {
+        self.execute_chunks(cx, conn)
+            .await
+            .map(|counts| counts.into_iter().sum())
+    }
+
+    /// Execute the bulk INSERT and return rows affected per statement.
+    ///
+    /// One entry is returned for each statement produced by
+    /// [`build_batches_with_dialect`](Self::build_batches_with_dialect), or
+    /// one per model for joined-table inheritance children.
+    pub async fn execute_chunks<C: Connection>(
+        self,
+        cx: &Cx,
+        conn: &C,
+    ) -> Outcome<Vec<u64>, sqlmodel_core::Error> {
         if is_joined_inheritance_child::<M>() {
             if self.on_conflict.is_some() {
                 return Outcome::Err(sqlmodel_core::Error::Custom(
@@ -1791,7 +1952,7 @@ impl<'a, M: Model> InsertManyBuilder<'a, M> {
                 ));
             }
 
-            let dialect = conn.dialect();
+            let dialect = conn.sql_dialect();
             let (parent_table, parent_fields) = match joined_parent_meta::<M>() {
                 Ok(v) => v,
                 Err(e) => return Outcome::Err(e),
@@ -1804,7 +1965,7 @@ impl<'a, M: Model> InsertManyBuilder<'a, M> {
                 Outcome::Panicked(p) => return Outcome::Panicked(p),
             };
 
-            let mut total_inserted: u64 = 0;
+            let mut counts = Vec::with_capacity(self.models.len());
             for model in self.models {
                 match insert_joined_model_in_tx::<_, M>(
                     &tx,
@@ -1816,9 +1977,7 @@ impl<'a, M: Model> InsertManyBuilder<'a, M> {
                 )
                 .await
                 {
-                    Outcome::Ok((count, _)) => {
-                        total_inserted = total_inserted.saturating_add(count);
-                    }
+                    Outcome::Ok((count, _)) => counts.push(count),
                     Outcome::Err(e) => {
                         tx_rollback_best_effort(tx, cx).await;
                         return Outcome::Err(e);
@@ -1835,25 +1994,24 @@ impl<'a, M: Model> InsertManyBuilder<'a, M> {
             }
 
             return match tx.commit(cx).await {
-                Outcome::Ok(()) => Outcome::Ok(total_inserted),
+                Outcome::Ok(()) => Outcome::Ok(counts),
                 Outcome::Err(e) => Outcome::Err(e),
                 Outcome::Cancelled(r) => Outcome::Cancelled(r),
                 Outcome::Panicked(p) => Outcome::Panicked(p),
             };
         }
 
-        let batches = self.build_batches_with_dialect(conn.dialect());
+        let batches = self.build_batches_with_dialect(conn.sql_dialect());
         if batches.is_empty() {
-            return Outcome::Ok(0);
+            return Outcome::Ok(Vec::new());
         }
 
         if batches.len() == 1 {
             let (sql, params) = &batches[0];
-            return conn.execute(cx, sql, params).await;
+            return conn.execute(cx, sql, params).await.map(|count| vec![count]);
         }
 
-        let outcome = conn.batch(cx, &batches).await;
-        outcome.map(|counts| counts.into_iter().sum())
+        conn.batch(cx, &batches).await
     }
 
     /// Execute the bulk INSERT with RETURNING and get the inserted rows.
@@ -1871,7 +2029,7 @@ impl<'a, M: Model> InsertManyBuilder<'a, M> {
                 ));
             }
 
-            let dialect = conn.dialect();
+            let dialect = conn.sql_dialect();
             let (parent_table, parent_fields) = match joined_parent_meta::<M>() {
                 Ok(v) => v,
                 Err(e) => return Outcome::Err(e),
@@ -1976,7 +2134,7 @@ impl<'a, M: Model> InsertManyBuilder<'a, M> {
             };
         }
 
-        let batches = self.build_batches_with_dialect(conn.dialect());
+        let batches = self.build_batches_with_dialect(conn.sql_dialect());
         if batches.is_empty() {
             return Outcome::Ok(Vec::new());
         }
@@ -1995,11 +2153,35 @@ impl<'a, M: Model> InsertManyBuilder<'a, M> {
     }
 }
 
+/// The right-hand side of an explicit UPDATE SET assignment.
+///
+/// Plain values are bound as parameters; expressions are rendered inline so
+/// the database evaluates them, e.g. `counter = counter + 1`.
+#[derive(Debug, Clone)]
+pub enum SetValue {
+    /// A literal value bound as a parameter.
+    Value(Value),
+    /// An SQL expression evaluated by the database.
+    Expr(Expr),
+}
+
+impl<T: Into<Value>> From<T> for SetValue {
+    fn from(value: T) -> Self {
+        SetValue::Value(value.into())
+    }
+}
+
+impl From<Expr> for SetValue {
+    fn from(expr: Expr) -> Self {
+        SetValue::Expr(expr)
+    }
+}
+
 /// A column-value pair for explicit UPDATE SET operations.
 #[derive(Debug, Clone)]
 pub struct SetClause {
     column: String,
-    value: Value,
+    value: SetValue,
 }
 
 /// UPDATE query builder.
@@ -2017,6 +2199,13 @@ pub struct SetClause {
 ///     .filter(Expr::col("id").eq(42))
 ///     .execute(cx, &conn).await?;
 ///
+/// // Update with SQL expressions evaluated by the database
+/// UpdateBuilder::<Hero>::empty()
+///     .set("visits", Expr::col("visits").add(1))
+///     .set_now("updated_at")
+///     .filter(Expr::col("id").eq(42))
+///     .execute(cx, &conn).await?;
+///
 /// // Update with RETURNING
 /// let row = update!(hero).returning().execute_returning(cx, &conn).await?;
 /// ```
@@ -2054,11 +2243,13 @@ impl<'a, M: Model> UpdateBuilder<'a, M> {
         }
     }
 
-    /// Set a column to a specific value.
+    /// Set a column to a specific value or SQL expression.
     ///
+    /// Values are bound as parameters; an [`Expr`] such as
+    /// `Expr::col("counter").add(1)` is rendered inline.
     /// This can be used with or without a model instance.
     /// When used with a model, these explicit sets override the model values.
-    pub fn set<V: Into<Value>>(mut self, column: &str, value: V) -> Self {
+    pub fn set<V: Into<SetValue>>(mut self, column: &str, value: V) -> Self {
         self.explicit_sets.push(SetClause {
             column: column.to_string(),
             value: value.into(),
@@ -2066,6 +2257,11 @@ impl<'a, M: Model> UpdateBuilder<'a, M> {
         self
     }
 
+    /// Set a column to the database's current timestamp (`CURRENT_TIMESTAMP`).
+    pub fn set_now(self, column: &str) -> Self {
+        self.set(column, Expr::raw("CURRENT_TIMESTAMP"))
+    }
+
     /// Only update specific fields from the model.
     pub fn set_only(mut self, fields: &[&'static str]) -> Self {
         self.set_fields = Some(fields.to_vec());
@@ -2093,93 +2289,95 @@ impl<'a, M: Model> UpdateBuilder<'a, M> {
     }
 
     /// Build the UPDATE SQL and parameters with specific dialect.
-    pub fn build_with_dialect(&self, dialect: Dialect) -> (String, Vec<Value>) {
+    pub fn build_with_dialect(&self, dialect: impl IntoSqlDialect) -> (String, Vec<Value>) {
+        let dialect = dialect.into_sql_dialect();
+        let mut w = SqlWriter::new(dialect);
+        self.build_into(&mut w);
+        w.into_parts()
+    }
+
+    /// Append the UPDATE SQL and parameters to `w`.
+    ///
+    /// Nothing is written when there is nothing to update. Reuse one cleared
+    /// writer across statements to avoid allocating a new buffer per update.
+    pub fn build_into(&self, w: &mut SqlWriter) {
+        let dialect = w.dialect();
         let pk = M::PRIMARY_KEY;
-        let mut params = Vec::new();
-        let mut set_clauses = Vec::new();
-
-        // First, add explicit SET clauses
-        for set in &self.explicit_sets {
-            set_clauses.push(format!(
-                "{} = {}",
-                set.column,
-                dialect.placeholder(params.len() + 1)
-            ));
-            params.push(set.value.clone());
-        }
+        let row = self.model.map(M::to_row);
 
-        // Then, add model fields if we have a model
-        if let Some(model) = &self.model {
-            let row = model.to_row();
+        // Determine which model fields to update
+        let update_fields = row.iter().flatten().filter(|(name, _)| {
+            // Skip primary key fields
+            if pk.contains(name) {
+                return false;
+            }
+            // Skip columns that have explicit sets
+            if self.explicit_sets.iter().any(|s| s.column == *name) {
+                return false;
+            }
+            // If set_only specified, only include those fields
+            if let Some(fields) = &self.set_fields {
+                return fields.contains(name);
+            }
+            true
+        });
+        let mut update_fields = update_fields.peekable();
 
-            // Determine which fields to update
-            let update_fields: Vec<_> = row
-                .iter()
-                .filter(|(name, _)| {
-                    // Skip primary key fields
-                    if pk.contains(name) {
-                        return false;
-                    }
-                    // Skip columns that have explicit sets
-                    if self.explicit_sets.iter().any(|s| s.column == *name) {
-                        return false;
-                    }
-                    // If set_only specified, only include those fields
-                    if let Some(fields) = &self.set_fields {
-                        return fields.contains(name);
-                    }
-                    true
-                })
-                .collect();
+        if self.explicit_sets.is_empty() && update_fields.peek().is_none() {
+            // Nothing to update - write no SQL
+            return;
+        }
 
-            for (name, value) in update_fields {
-                set_clauses.push(format!(
-                    "{} = {}",
-                    name,
-                    dialect.placeholder(params.len() + 1)
-                ));
-                params.push(value.clone());
+        w.push("UPDATE ");
+        w.push(M::TABLE_NAME);
+        w.push(" SET ");
+
+        // Explicit SET clauses come first, then model fields
+        for (i, set) in self.explicit_sets.iter().enumerate() {
+            if i > 0 {
+                w.push(", ");
+            }
+            w.push(&set.column);
+            w.push(" = ");
+            match &set.value {
+                SetValue::Value(value) => w.push_param(value.clone()),
+                SetValue::Expr(expr) => w.push_expr(expr),
             }
         }
-
-        if set_clauses.is_empty() {
-            // Nothing to update - return empty SQL
-            return (String::new(), Vec::new());
+        for (i, (name, value)) in update_fields.enumerate() {
+            if i > 0 || !self.explicit_sets.is_empty() {
+                w.push(", ");
+            }
+            w.push(name);
+            w.push(" = ");
+            w.push_param(value.clone());
         }
 
-        let mut sql = format!("UPDATE {} SET {}", M::TABLE_NAME, set_clauses.join(", "));
-
         // Add WHERE clause
         if let Some(where_clause) = &self.where_clause {
-            let (where_sql, where_params) = where_clause.build_with_dialect(dialect, params.len());
-            sql.push_str(" WHERE ");
-            sql.push_str(&where_sql);
-            params.extend(where_params);
+            let (where_sql, where_params) =
+                where_clause.build_with_dialect(dialect, w.placeholder_count());
+            w.push(" WHERE ");
+            w.push(&where_sql);
+            w.skip_placeholders(where_params.len());
+            for value in where_params {
+                w.bind(value);
+            }
         } else if let Some(model) = &self.model {
             // Default to primary key match
             let pk_values = model.primary_key_value();
-            let pk_conditions: Vec<_> = pk
-                .iter()
-                .zip(pk_values.iter())
-                .enumerate()
-                .map(|(i, (col, _))| {
-                    format!("{} = {}", col, dialect.placeholder(params.len() + i + 1))
-                })
-                .collect();
-
-            if !pk_conditions.is_empty() {
-                sql.push_str(" WHERE ");
-                sql.push_str(&pk_conditions.join(" AND "));
-                params.extend(pk_values);
+            for (i, (col, value)) in pk.iter().zip(pk_values).enumerate() {
+                w.push(if i == 0 { " WHERE " } else { " AND " });
+                w.push(col);
+                w.push(" = ");
+                w.push_param(value);
             }
         }
 
         // Add RETURNING clause if requested
         if self.returning {
-            sql.push_str(" RETURNING *");
+            w.push(" RETURNING *");
         }
-
-        (sql, params)
     }
 
     /// Execute the UPDATE and return rows affected.
@@ -2202,7 +2400,7 @@ impl<'a, M: Model> UpdateBuilder<'a, M> {
                     ));
                 }
 
-                let dialect = conn.dialect();
+                let dialect = conn.sql_dialect();
                 let (parent_table, parent_fields) = match joined_parent_meta::<M>() {
                     Ok(v) => v,
                     Err(e) => return Outcome::Err(e),
@@ -2327,7 +2525,7 @@ impl<'a, M: Model> UpdateBuilder<'a, M> {
                 ));
             }
 
-            let dialect = conn.dialect();
+            let dialect = conn.sql_dialect();
             let Some(model) = self.model else {
                 return Outcome::Err(sqlmodel_core::Error::Custom(
                     "update called without model".to_string(),
@@ -2436,7 +2634,7 @@ impl<'a, M: Model> UpdateBuilder<'a, M> {
                 Outcome::Panicked(p) => Outcome::Panicked(p),
             }
         } else {
-            let (sql, params) = self.build_with_dialect(conn.dialect());
+            let (sql, params) = self.build_with_dialect(conn.sql_dialect());
             if sql.is_empty() {
                 return Outcome::Ok(0);
             }
@@ -2460,7 +2658,7 @@ impl<'a, M: Model> UpdateBuilder<'a, M> {
                     ));
                 }
 
-                let dialect = conn.dialect();
+                let dialect = conn.sql_dialect();
                 let (parent_table, parent_fields) = match joined_parent_meta::<M>() {
                     Ok(v) => v,
                     Err(e) => return Outcome::Err(e),
@@ -2614,7 +2812,7 @@ impl<'a, M: Model> UpdateBuilder<'a, M> {
                 ));
             }
 
-            let dialect = conn.dialect();
+            let dialect = conn.sql_dialect();
             let Some(model) = self.model else {
                 return Outcome::Err(sqlmodel_core::Error::Custom(
                     "update_returning called without model".to_string(),
@@ -2725,7 +2923,7 @@ impl<'a, M: Model> UpdateBuilder<'a, M> {
                 Outcome::Panicked(p) => Outcome::Panicked(p),
             }
         } else {
-            let (sql, params) = self.build_with_dialect(conn.dialect());
+            let (sql, params) = self.build_with_dialect(conn.sql_dialect());
             if sql.is_empty() {
                 return Outcome::Ok(Vec::new());
             }
@@ -2806,7 +3004,8 @@ impl<'a, M: Model> DeleteBuilder<'a, M> {
     }
 
     /// Build the DELETE SQL and parameters with specific dialect.
-    pub fn build_with_dialect(&self, dialect: Dialect) -> (String, Vec<Value>) {
+    pub fn build_with_dialect(&self, dialect: impl IntoSqlDialect) -> (String, Vec<Value>) {
+        let dialect = dialect.into_sql_dialect();
         let mut sql = format!("DELETE FROM {}", M::TABLE_NAME);
         let mut params = Vec::new();
 
@@ -2852,7 +3051,7 @@ impl<'a, M: Model> DeleteBuilder<'a, M> {
         conn: &C,
     ) -> Outcome<u64, sqlmodel_core::Error> {
         if is_joined_inheritance_child::<M>() {
-            let dialect = conn.dialect();
+            let dialect = conn.sql_dialect();
             let (parent_table, _parent_fields) = match joined_parent_meta::<M>() {
                 Ok(v) => v,
                 Err(e) => return Outcome::Err(e),
@@ -2968,7 +3167,7 @@ impl<'a, M: Model> DeleteBuilder<'a, M> {
                 Outcome::Panicked(p) => Outcome::Panicked(p),
             }
         } else {
-            let (sql, params) = self.build_with_dialect(conn.dialect());
+            let (sql, params) = self.build_with_dialect(conn.sql_dialect());
             conn.execute(cx, &sql, &params).await
         }
     }
@@ -2984,7 +3183,7 @@ impl<'a, M: Model> DeleteBuilder<'a, M> {
     ) -> Outcome<Vec<Row>, sqlmodel_core::Error> {
         self.returning = true;
         if is_joined_inheritance_child::<M>() {
-            let dialect = conn.dialect();
+            let dialect = conn.sql_dialect();
             let (parent_table, _parent_fields) = match joined_parent_meta::<M>() {
                 Ok(v) => v,
                 Err(e) => return Outcome::Err(e),
@@ -3128,7 +3327,7 @@ impl<'a, M: Model> DeleteBuilder<'a, M> {
                 Outcome::Panicked(p) => Outcome::Panicked(p),
             };
         }
-        let (sql, params) = self.build_with_dialect(conn.dialect());
+        let (sql, params) = self.build_with_dialect(conn.sql_dialect());
         conn.query(cx, &sql, &params).await
     }
 }
@@ -3173,6 +3372,81 @@ impl QueryBuilder {
     }
 }
 
+/// Raw SQL whose parameters borrow from the caller.
+///
+/// Unlike [`QueryBuilder`], binding a `&str`, `&String` or `&[u8]` does not copy
+/// it; the statement runs through [`Connection::query_params`] /
+/// [`Connection::execute_params`], so large text and blob parameters reach the
+/// driver without an owned [`Value`] in between.
+///
+/// ```ignore
+/// let rows = RawQuery::new("SELECT id FROM docs WHERE body = $1")
+///     .bind(&body)
+///     .all(&cx, &conn)
+///     .await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct RawQuery<'a> {
+    sql: &'a str,
+    params: Vec<ValueRef<'a>>,
+}
+
+impl<'a> RawQuery<'a> {
+    /// Create a raw query for `sql`.
+    pub fn new(sql: &'a str) -> Self {
+        Self {
+            sql,
+            params: Vec::new(),
+        }
+    }
+
+    /// Bind a parameter, borrowing it where possible.
+    pub fn bind(mut self, value: impl Into<ValueRef<'a>>) -> Self {
+        self.params.push(value.into());
+        self
+    }
+
+    /// Bind multiple parameters.
+    pub fn bind_all<I>(mut self, values: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<ValueRef<'a>>,
+    {
+        self.params.extend(values.into_iter().map(Into::into));
+        self
+    }
+
+    /// The SQL text.
+    pub fn sql(&self) -> &str {
+        self.sql
+    }
+
+    /// The bound parameters, in order.
+    pub fn params(&self) -> &[ValueRef<'a>] {
+        &self.params
+    }
+
+    /// Run the query and return all rows.
+    pub async fn all<C: Connection>(
+        &self,
+        cx: &Cx,
+        conn: &C,
+    ) -> Outcome<Vec<Row>, sqlmodel_core::Error> {
+        conn.query_params(cx, self.sql, self.params.as_slice())
+            .await
+    }
+
+    /// Run the statement and return the number of rows affected.
+    pub async fn execute<C: Connection>(
+        &self,
+        cx: &Cx,
+        conn: &C,
+    ) -> Outcome<u64, sqlmodel_core::Error> {
+        conn.execute_params(cx, self.sql, self.params.as_slice())
+            .await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3374,6 +3648,23 @@ mod tests {
         assert_eq!(params.len(), 4);
     }
 
+    #[test]
+    fn test_insert_many_on_conflict_target_updates_non_pk_columns() {
+        let heroes = vec![TestHero {
+            id: Some(1),
+            name: "Spider-Man".to_string(),
+            age: 25,
+        }];
+        let (sql, params) = InsertManyBuilder::new(&heroes)
+            .on_conflict_target_do_update(&["name"], &[])
+            .build_with_dialect(Dialect::Postgres);
+
+        assert!(sql.contains("ON CONFLICT (name) DO UPDATE SET"));
+        assert!(sql.contains("age = EXCLUDED.age"));
+        assert!(!sql.contains("id = EXCLUDED.id"));
+        assert_eq!(params.len(), 3);
+    }
+
     #[test]
     fn test_insert_many() {
         let heroes = vec![
@@ -3482,6 +3773,53 @@ mod tests {
         assert!(batches[1].1.is_empty());
     }
 
+    #[test]
+    fn test_insert_many_splits_at_param_limit() {
+        let heroes: Vec<_> = (0..3)
+            .map(|i| TestHero {
+                id: None,
+                name: format!("Hero {i}"),
+                age: 20 + i,
+            })
+            .collect();
+        let batches = InsertManyBuilder::new(&heroes)
+            .max_params(5)
+            .build_batches_with_dialect(Dialect::Postgres);
+
+        assert_eq!(batches.len(), 2);
+        assert_eq!(
+            batches[0].0,
+            "INSERT INTO heroes (id, name, age) VALUES (DEFAULT, $1, $2), (DEFAULT, $3, $4)"
+        );
+        assert_eq!(
+            batches[1].0,
+            "INSERT INTO heroes (id, name, age) VALUES (DEFAULT, $1, $2)"
+        );
+        assert_eq!(batches[0].1.len(), 4);
+        assert_eq!(batches[1].1.len(), 2);
+    }
+
+    #[test]
+    fn test_insert_many_sqlite_respects_dialect_param_limit() {
+        let heroes: Vec<_> = (0..600)
+            .map(|i| TestHero {
+                id: None,
+                name: format!("Hero {i}"),
+                age: i,
+            })
+            .collect();
+        let batches = InsertManyBuilder::new(&heroes).build_batches_with_dialect(Dialect::Sqlite);
+
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].1.len(), 998);
+        assert_eq!(batches[1].1.len(), 202);
+        assert!(
+            batches
+                .iter()
+                .all(|(_, params)| params.len() <= Dialect::Sqlite.max_bind_params())
+        );
+    }
+
     #[test]
     fn test_update_basic() {
         let hero = TestHero {
@@ -3507,6 +3845,47 @@ mod tests {
         assert_eq!(params.len(), 2);
     }
 
+    #[test]
+    fn test_update_expression_set() {
+        let (sql, params) = UpdateBuilder::<TestHero>::empty()
+            .set("name", "Hulk")
+            .set("age", Expr::col("age").add(1))
+            .set_now("updated_at")
+            .filter(Expr::col("id").eq(7))
+            .build_with_dialect(Dialect::Postgres);
+
+        assert_eq!(
+            sql,
+            "UPDATE heroes SET name = $1, age = \"age\" + $2, updated_at = CURRENT_TIMESTAMP WHERE \"id\" = $3"
+        );
+        assert_eq!(
+            params,
+            vec![
+                Value::Text("Hulk".to_string()),
+                Value::Int(1),
+                Value::Int(7)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_model_with_expression_set() {
+        let hero = TestHero {
+            id: Some(1),
+            name: "Spider-Man".to_string(),
+            age: 26,
+        };
+        let (sql, params) = UpdateBuilder::new(&hero)
+            .set("age", Expr::col("age").add(1))
+            .build_with_dialect(Dialect::Sqlite);
+
+        assert_eq!(
+            sql,
+            "UPDATE heroes SET age = \"age\" + ?1, name = ?2 WHERE id = ?3"
+        );
+        assert_eq!(params.len(), 3);
+    }
+
     #[test]
     fn test_update_returning() {
         let hero = TestHero {
@@ -3519,6 +3898,48 @@ mod tests {
         assert!(sql.ends_with(" RETURNING *"));
     }
 
+    #[test]
+    fn test_build_into_reuses_writer() {
+        let heroes = [
+            TestHero {
+                id: None,
+                name: "Spider-Man".to_string(),
+                age: 26,
+            },
+            TestHero {
+                id: Some(2),
+                name: "Iron Man".to_string(),
+                age: 45,
+            },
+        ];
+        let mut w = SqlWriter::new(Dialect::Sqlite);
+
+        InsertBuilder::new(&heroes[0]).build_into(&mut w);
+        assert_eq!(w.sql(), "INSERT INTO heroes (name, age) VALUES (?1, ?2)");
+        assert_eq!(w.params().len(), 2);
+
+        w.clear();
+        UpdateBuilder::new(&heroes[1]).build_into(&mut w);
+        assert_eq!(
+            w.sql(),
+            "UPDATE heroes SET name = ?1, age = ?2 WHERE id = ?3"
+        );
+        assert_eq!(
+            w.params(),
+            &[
+                Value::Text("Iron Man".to_string()),
+                Value::Int(45),
+                Value::BigInt(2)
+            ]
+        );
+
+        w.clear();
+        UpdateBuilder::new(&heroes[1])
+            .set_only(&[])
+            .build_into(&mut w);
+        assert!(w.sql().is_empty());
+    }
+
     #[test]
     fn test_delete_basic() {
         let (sql, _) = DeleteBuilder::<TestHero>::new()
@@ -3577,4 +3998,63 @@ mod tests {
         assert!(sql.contains('?'));
         assert!(!sql.contains("$1"));
     }
+
+    #[test]
+    fn test_custom_dialect_placeholders() {
+        #[derive(Debug)]
+        struct Bracketed;
+
+        impl SqlDialect for Bracketed {
+            fn name(&self) -> &'static str {
+                "bracketed"
+            }
+
+            fn base_dialect(&self) -> Dialect {
+                Dialect::Postgres
+            }
+
+            fn placeholder(&self, index: usize) -> String {
+                format!(":p{index}")
+            }
+        }
+
+        static BRACKETED: Bracketed = Bracketed;
+
+        let hero = TestHero {
+            id: None,
+            name: "Spider-Man".to_string(),
+            age: 25,
+        };
+        let (sql, params) = InsertBuilder::new(&hero).build_with_dialect(&BRACKETED);
+
+        assert_eq!(
+            sql,
+            "INSERT INTO heroes (id, name, age) VALUES (DEFAULT, :p1, :p2)"
+        );
+        assert_eq!(params.len(), 2);
+    }
+
+    #[test]
+    fn test_raw_query_borrows_params() {
+        let body = "x".repeat(4096);
+        let blob = vec![1_u8, 2, 3];
+        let query = RawQuery::new("UPDATE docs SET body = $1, data = $2 WHERE id = $3")
+            .bind(&body)
+            .bind(&blob)
+            .bind(7_i64);
+
+        assert_eq!(
+            query.sql(),
+            "UPDATE docs SET body = $1, data = $2 WHERE id = $3"
+        );
+        let ValueRef::Text(bound) = query.params()[0] else {
+            panic!("expected borrowed text");
+        };
+        assert!(std::ptr::eq(bound, body.as_str()));
+        assert_eq!(query.params()[1], ValueRef::Bytes(&[1, 2, 3]));
+        assert_eq!(query.params()[2], ValueRef::BigInt(7));
+
+        let query = RawQuery::new("SELECT 1").bind_all([Some("a"), None]);
+        assert_eq!(query.params(), [ValueRef::Text("a"), ValueRef::Null]);
+    }
 }
@@ -2,6 +2,7 @@
 
 use crate::expr::{Dialect, Expr};
 use sqlmodel_core::Value;
+use sqlmodel_core::dialect::IntoSqlDialect;
 
 /// WHERE clause.
 #[derive(Debug, Clone)]
@@ -29,6 +30,16 @@ impl Where {
         }
     }
 
+    /// Get the condition expression.
+    pub fn expr(&self) -> &Expr {
+        &self.expr
+    }
+
+    /// Get a mutable reference to the condition expression.
+    pub fn expr_mut(&mut self) -> &mut Expr {
+        &mut self.expr
+    }
+
     /// Build the WHERE clause SQL and parameters with default dialect (Postgres).
     pub fn build(&self) -> (String, Vec<Value>) {
         self.build_with_dialect(Dialect::default(), 0)
@@ -40,7 +51,12 @@ impl Where {
     }
 
     /// Build the WHERE clause with a specific dialect and offset.
-    pub fn build_with_dialect(&self, dialect: Dialect, offset: usize) -> (String, Vec<Value>) {
+    pub fn build_with_dialect(
+        &self,
+        dialect: impl IntoSqlDialect,
+        offset: usize,
+    ) -> (String, Vec<Value>) {
+        let dialect = dialect.into_sql_dialect();
         let mut params = Vec::new();
         let sql = self.expr.build_with_dialect(dialect, &mut params, offset);
         (sql, params)
@@ -102,7 +118,13 @@ impl OrderBy {
     }
 
     /// Build SQL for this ORDER BY clause.
-    pub fn build(&self, dialect: Dialect, params: &mut Vec<Value>, offset: usize) -> String {
+    pub fn build(
+        &self,
+        dialect: impl IntoSqlDialect,
+        params: &mut Vec<Value>,
+        offset: usize,
+    ) -> String {
+        let dialect = dialect.into_sql_dialect();
         let mut sql = self.expr.build_with_dialect(dialect, params, offset);
 
         sql.push_str(match self.direction {
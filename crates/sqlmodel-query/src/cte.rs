@@ -30,6 +30,7 @@
 
 use crate::expr::{Dialect, Expr};
 use sqlmodel_core::Value;
+use sqlmodel_core::dialect::IntoSqlDialect;
 
 /// A Common Table Expression (WITH clause).
 #[derive(Debug, Clone)]
@@ -184,7 +185,8 @@ impl Cte {
     /// Build the CTE definition SQL.
     ///
     /// Returns the SQL for use in a WITH clause and the parameters.
-    pub fn build(&self, dialect: Dialect) -> (String, Vec<Value>) {
+    pub fn build(&self, dialect: impl IntoSqlDialect) -> (String, Vec<Value>) {
+        let dialect = dialect.into_sql_dialect();
         let mut sql = String::new();
         let mut params = Vec::new();
 
@@ -305,7 +307,8 @@ impl WithQuery {
     }
 
     /// Build the complete SQL with a specific dialect.
-    pub fn build_with_dialect(&self, dialect: Dialect) -> (String, Vec<Value>) {
+    pub fn build_with_dialect(&self, dialect: impl IntoSqlDialect) -> (String, Vec<Value>) {
+        let dialect = dialect.into_sql_dialect();
         let mut sql = String::new();
         let mut params = Vec::new();
 
@@ -0,0 +1,215 @@
+//! Server-side cursors for large result sets.
+//!
+//! [`Select::fetch_cursor`] declares a PostgreSQL cursor for the query and
+//! returns a [`Cursor`] that pulls `batch_size` rows per `FETCH`, so only one
+//! batch is held in memory at a time:
+//!
+//! ```ignore
+//! let tx = conn.begin(&cx).await?;
+//! let mut cursor = select!(Event).order_by(OrderBy::asc(Expr::col("id")))
+//!     .fetch_cursor(&cx, &conn, 1_000)
+//!     .await?;
+//! while let Some(batch) = cursor.next_batch(&cx).await? {
+//!     export(&batch)?;
+//! }
+//! cursor.close(&cx).await?;
+//! tx.commit(&cx).await?;
+//! ```
+//!
+//! Unlike `LIMIT`/`OFFSET` pagination, every batch comes from the same
+//! snapshot and later batches cost no more than earlier ones.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::expr::Dialect;
+use crate::select::Select;
+use asupersync::{Cx, Outcome};
+use sqlmodel_core::{Connection, Error, Model, Value};
+
+static NEXT_CURSOR: AtomicU64 = AtomicU64::new(1);
+
+/// A declared server-side cursor over the rows of a [`Select`].
+///
+/// The cursor lives until [`close`](Cursor::close) or the end of the
+/// enclosing transaction, whichever comes first. Dropping it without
+/// closing leaves it open on the server until the transaction ends.
+pub struct Cursor<'c, M: Model, C: Connection> {
+    conn: &'c C,
+    query: Select<M>,
+    name: String,
+    batch_size: usize,
+    buffered: std::vec::IntoIter<M>,
+    exhausted: bool,
+}
+
+impl<M: Model> Select<M> {
+    /// Declare a server-side cursor for this query and fetch it in batches
+    /// of `batch_size` rows.
+    ///
+    /// Requires PostgreSQL and an open transaction on `conn`; cursors are
+    /// only valid inside a transaction block.
+    pub async fn fetch_cursor<'c, C: Connection>(
+        self,
+        cx: &Cx,
+        conn: &'c C,
+        batch_size: usize,
+    ) -> Outcome<Cursor<'c, M, C>, Error> {
+        if conn.dialect() != Dialect::Postgres {
+            return Outcome::Err(Error::Custom(format!(
+                "fetch_cursor needs PostgreSQL server-side cursors; {:?} connections \
+                 should paginate with limit/offset instead",
+                conn.dialect()
+            )));
+        }
+        let name = format!(
+            "sqlmodel_cursor_{}",
+            NEXT_CURSOR.fetch_add(1, Ordering::Relaxed)
+        );
+        let (sql, params) = self.build_declare_cursor(&name);
+        match conn.execute(cx, &sql, &params).await {
+            Outcome::Ok(_) => Outcome::Ok(Cursor {
+                conn,
+                query: self,
+                name,
+                batch_size: batch_size.max(1),
+                buffered: Vec::new().into_iter(),
+                exhausted: false,
+            }),
+            Outcome::Err(e) => Outcome::Err(e),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
+    /// Build the `DECLARE ... CURSOR FOR` statement for this query.
+    pub fn build_declare_cursor(&self, name: &str) -> (String, Vec<Value>) {
+        let (sql, params) = self.build_with_dialect(Dialect::Postgres);
+        (format!("DECLARE {name} NO SCROLL CURSOR FOR {sql}"), params)
+    }
+}
+
+impl<M: Model, C: Connection> Cursor<'_, M, C> {
+    /// The server-side cursor name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Rows requested per `FETCH`.
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Fetch the next batch of up to `batch_size` rows; `None` once the
+    /// result set is exhausted.
+    pub async fn next_batch(&mut self, cx: &Cx) -> Outcome<Option<Vec<M>>, Error> {
+        let buffered: Vec<M> = self.buffered.by_ref().collect();
+        if !buffered.is_empty() {
+            return Outcome::Ok(Some(buffered));
+        }
+        if self.exhausted {
+            return Outcome::Ok(None);
+        }
+
+        let sql = format!("FETCH FORWARD {} FROM {}", self.batch_size, self.name);
+        let rows = match self.conn.query(cx, &sql, &[]).await {
+            Outcome::Ok(rows) => rows,
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        };
+        self.exhausted = rows.len() < self.batch_size;
+        if rows.is_empty() {
+            return Outcome::Ok(None);
+        }
+        let mut models = Vec::with_capacity(rows.len());
+        for row in &rows {
+            match self.query.hydrate(row) {
+                Ok(model) => models.push(model),
+                Err(e) => return Outcome::Err(e),
+            }
+        }
+        Outcome::Ok(Some(models))
+    }
+
+    /// Fetch the next row, pulling a new batch when the current one is used
+    /// up.
+    pub async fn next(&mut self, cx: &Cx) -> Outcome<Option<M>, Error> {
+        if let Some(model) = self.buffered.next() {
+            return Outcome::Ok(Some(model));
+        }
+        match self.next_batch(cx).await {
+            Outcome::Ok(Some(batch)) => {
+                self.buffered = batch.into_iter();
+                Outcome::Ok(self.buffered.next())
+            }
+            Outcome::Ok(None) => Outcome::Ok(None),
+            Outcome::Err(e) => Outcome::Err(e),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
+    /// Close the cursor and release its server resources.
+    pub async fn close(self, cx: &Cx) -> Outcome<(), Error> {
+        let sql = format!("CLOSE {}", self.name);
+        self.conn.execute(cx, &sql, &[]).await.map(|_| ())
+    }
+}
+
+impl<M: Model, C: Connection> std::fmt::Debug for Cursor<'_, M, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cursor")
+            .field("name", &self.name)
+            .field("batch_size", &self.batch_size)
+            .field("exhausted", &self.exhausted)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::expr::Expr;
+    use crate::select::Select;
+    use sqlmodel_core::{FieldInfo, Model, Result, Row, Value};
+
+    #[derive(Debug, Clone)]
+    struct Event;
+
+    impl Model for Event {
+        const TABLE_NAME: &'static str = "events";
+        const PRIMARY_KEY: &'static [&'static str] = &["id"];
+
+        fn fields() -> &'static [FieldInfo] {
+            &[]
+        }
+
+        fn to_row(&self) -> Vec<(&'static str, Value)> {
+            Vec::new()
+        }
+
+        fn from_row(_row: &Row) -> Result<Self> {
+            Ok(Event)
+        }
+
+        fn primary_key_value(&self) -> Vec<Value> {
+            Vec::new()
+        }
+
+        fn is_new(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_declare_cursor_wraps_select() {
+        let (sql, params) = Select::<Event>::new()
+            .filter(Expr::col("kind").eq("login"))
+            .build_declare_cursor("sqlmodel_cursor_7");
+        assert_eq!(
+            sql,
+            "DECLARE sqlmodel_cursor_7 NO SCROLL CURSOR FOR \
+             SELECT * FROM events WHERE \"kind\" = $1"
+        );
+        assert_eq!(params, vec![Value::Text("login".to_string())]);
+    }
+}
@@ -6,6 +6,7 @@
 use crate::clause::{OrderBy, OrderDirection};
 use crate::subquery::SelectQuery;
 use sqlmodel_core::Value;
+use sqlmodel_core::dialect::{IntoSqlDialect, SqlDialect};
 
 /// SQL dialect for generating dialect-specific SQL.
 ///
@@ -78,6 +79,12 @@ pub enum Expr {
     /// CAST(expr AS type)
     Cast { expr: Box<Expr>, type_name: String },
 
+    /// expr COLLATE name (per-expression collation override)
+    Collate { expr: Box<Expr>, collation: String },
+
+    /// Trigram similarity score between 0 and 1 (`similarity(expr, other)`, PostgreSQL pg_trgm)
+    TrigramSimilarity { expr: Box<Expr>, other: Box<Expr> },
+
     /// LIKE / NOT LIKE pattern
     Like {
         expr: Box<Expr>,
@@ -315,6 +322,16 @@ pub enum BinaryOp {
     // String
     /// String concatenation (||)
     Concat,
+    /// POSIX regular expression match (`~`; `REGEXP` on MySQL and SQLite)
+    RegexMatch,
+    /// Negated regular expression match (`!~`; `NOT REGEXP` on MySQL and SQLite)
+    NotRegexMatch,
+    /// SQL regular expression match (SIMILAR TO, PostgreSQL only)
+    SimilarTo,
+    /// Negated SQL regular expression match (NOT SIMILAR TO, PostgreSQL only)
+    NotSimilarTo,
+    /// Trigram similarity above `pg_trgm.similarity_threshold` (%, PostgreSQL only)
+    TrigramSimilar,
 
     // Array (PostgreSQL)
     /// Array contains (@>)
@@ -346,6 +363,11 @@ impl BinaryOp {
             BinaryOp::BitOr => "|",
             BinaryOp::BitXor => "^",
             BinaryOp::Concat => "||",
+            BinaryOp::RegexMatch => "~",
+            BinaryOp::NotRegexMatch => "!~",
+            BinaryOp::SimilarTo => "SIMILAR TO",
+            BinaryOp::NotSimilarTo => "NOT SIMILAR TO",
+            BinaryOp::TrigramSimilar => "%",
             BinaryOp::ArrayContains => "@>",
             BinaryOp::ArrayContainedBy => "<@",
             BinaryOp::ArrayOverlap => "&&",
@@ -365,7 +387,12 @@ impl BinaryOp {
             | BinaryOp::Ge
             | BinaryOp::ArrayContains
             | BinaryOp::ArrayContainedBy
-            | BinaryOp::ArrayOverlap => 3,
+            | BinaryOp::ArrayOverlap
+            | BinaryOp::RegexMatch
+            | BinaryOp::NotRegexMatch
+            | BinaryOp::SimilarTo
+            | BinaryOp::NotSimilarTo
+            | BinaryOp::TrigramSimilar => 3,
             BinaryOp::BitOr => 4,
             BinaryOp::BitXor => 5,
             BinaryOp::BitAnd => 6,
@@ -638,6 +665,20 @@ impl Expr {
         }
     }
 
+    /// Override the collation used to compare or sort this expression.
+    ///
+    /// # Example
+    /// ```ignore
+    /// Expr::col("name").collate("und-x-icu").asc()
+    /// // Generates: "name" COLLATE "und-x-icu" ASC
+    /// ```
+    pub fn collate(self, collation: impl Into<String>) -> Self {
+        Expr::Collate {
+            expr: Box::new(self),
+            collation: collation.into(),
+        }
+    }
+
     // ==================== Pattern Matching ====================
 
     /// LIKE pattern match
@@ -764,6 +805,86 @@ impl Expr {
         }
     }
 
+    // ==================== Regex and Fuzzy Matching ====================
+
+    /// Regular expression match.
+    ///
+    /// PostgreSQL uses `~` (POSIX regex); MySQL and SQLite use `REGEXP`.
+    /// SQLite has no built-in regex engine: register a `regexp(pattern, text)`
+    /// function on the connection first.
+    ///
+    /// # Example
+    /// ```ignore
+    /// Expr::col("sku").regex_match("^[A-Z]{3}-[0-9]+$")
+    /// // PostgreSQL: "sku" ~ $1
+    /// ```
+    pub fn regex_match(self, pattern: impl Into<Expr>) -> Self {
+        Expr::Binary {
+            left: Box::new(self),
+            op: BinaryOp::RegexMatch,
+            right: Box::new(pattern.into()),
+        }
+    }
+
+    /// Negated regular expression match (`!~` / `NOT REGEXP`).
+    pub fn not_regex_match(self, pattern: impl Into<Expr>) -> Self {
+        Expr::Binary {
+            left: Box::new(self),
+            op: BinaryOp::NotRegexMatch,
+            right: Box::new(pattern.into()),
+        }
+    }
+
+    /// SQL-standard regular expression match (SIMILAR TO, PostgreSQL only).
+    ///
+    /// Other dialects render a call to a nonexistent
+    /// `sqlmodel_similar_to_requires_postgres` function, so the database
+    /// rejects the statement with an error naming the problem.
+    pub fn similar_to(self, pattern: impl Into<Expr>) -> Self {
+        Expr::Binary {
+            left: Box::new(self),
+            op: BinaryOp::SimilarTo,
+            right: Box::new(pattern.into()),
+        }
+    }
+
+    /// NOT SIMILAR TO (PostgreSQL only).
+    pub fn not_similar_to(self, pattern: impl Into<Expr>) -> Self {
+        Expr::Binary {
+            left: Box::new(self),
+            op: BinaryOp::NotSimilarTo,
+            right: Box::new(pattern.into()),
+        }
+    }
+
+    /// Trigram fuzzy match (`expr % other`, PostgreSQL with `pg_trgm`).
+    ///
+    /// True when [`similarity`](Self::similarity) exceeds
+    /// `pg_trgm.similarity_threshold` (0.3 by default); a GIN or GiST
+    /// `gin_trgm_ops` index can serve it.
+    pub fn trigram_similar(self, other: impl Into<Expr>) -> Self {
+        Expr::Binary {
+            left: Box::new(self),
+            op: BinaryOp::TrigramSimilar,
+            right: Box::new(other.into()),
+        }
+    }
+
+    /// Trigram similarity score (`similarity(expr, other)`, PostgreSQL with `pg_trgm`).
+    ///
+    /// # Example
+    /// ```ignore
+    /// select!(Hero)
+    ///     .filter(Expr::col("name").trigram_similar("spidrman"))
+    ///     .order_by(Expr::col("name").similarity("spidrman").desc())
+    /// ```
+    pub fn similarity(self, other: impl Into<Expr>) -> Self {
+        Expr::TrigramSimilarity {
+            expr: Box::new(self),
+            other: Box::new(other.into()),
+        }
+    }
+
     // ==================== IN Expressions ====================
 
     /// IN list of values
@@ -1685,10 +1806,11 @@ impl Expr {
     /// Build SQL string with specific dialect.
     pub fn build_with_dialect(
         &self,
-        dialect: Dialect,
+        dialect: impl IntoSqlDialect,
         params: &mut Vec<Value>,
         offset: usize,
     ) -> String {
+        let dialect = dialect.into_sql_dialect();
         match self {
             Expr::Column { table, name } => {
                 if let Some(t) = table {
@@ -1716,10 +1838,24 @@ impl Expr {
             Expr::Binary { left, op, right } => {
                 let left_sql = left.build_with_dialect(dialect, params, offset);
                 let right_sql = right.build_with_dialect(dialect, params, offset);
-                if *op == BinaryOp::Concat && dialect == Dialect::Mysql {
-                    format!("CONCAT({left_sql}, {right_sql})")
-                } else {
-                    format!("{left_sql} {} {right_sql}", op.as_str())
+                match (op, dialect.base_dialect()) {
+                    (BinaryOp::Concat, _) if dialect.concat_op().is_empty() => {
+                        format!("CONCAT({left_sql}, {right_sql})")
+                    }
+                    (BinaryOp::RegexMatch, Dialect::Mysql | Dialect::Sqlite) => {
+                        format!("{left_sql} REGEXP {right_sql}")
+                    }
+                    (BinaryOp::NotRegexMatch, Dialect::Mysql | Dialect::Sqlite) => {
+                        format!("{left_sql} NOT REGEXP {right_sql}")
+                    }
+                    (
+                        BinaryOp::SimilarTo | BinaryOp::NotSimilarTo,
+                        Dialect::Mysql | Dialect::Sqlite,
+                    ) => unsupported_outside_postgres("similar_to", &left_sql, &right_sql),
+                    (BinaryOp::TrigramSimilar, Dialect::Mysql | Dialect::Sqlite) => {
+                        unsupported_outside_postgres("trigram_similar", &left_sql, &right_sql)
+                    }
+                    _ => format!("{left_sql} {} {right_sql}", op.as_str()),
                 }
             }
 
@@ -1808,6 +1944,26 @@ impl Expr {
                 format!("CAST({expr_sql} AS {type_name})")
             }
 
+            Expr::TrigramSimilarity { expr, other } => {
+                let expr_sql = expr.build_with_dialect(dialect, params, offset);
+                let other_sql = other.build_with_dialect(dialect, params, offset);
+                if dialect.base_dialect() == Dialect::Postgres {
+                    format!("similarity({expr_sql}, {other_sql})")
+                } else {
+                    unsupported_outside_postgres("similarity", &expr_sql, &other_sql)
+                }
+            }
+
+            Expr::Collate { expr, collation } => {
+                let expr_sql = expr.build_with_dialect(dialect, params, offset);
+                // COLLATE binds tighter than any operator
+                let expr_sql = match **expr {
+                    Expr::Binary { .. } | Expr::Unary { .. } => format!("({expr_sql})"),
+                    _ => expr_sql,
+                };
+                format!("{expr_sql} COLLATE {}", dialect.quote_identifier(collation))
+            }
+
             Expr::Like {
                 expr,
                 pattern,
@@ -1946,7 +2102,7 @@ impl Expr {
             // ==================== JSON Expressions ====================
             Expr::JsonExtract { expr, path } => {
                 let expr_sql = expr.build_with_dialect(dialect, params, offset);
-                match dialect {
+                match dialect.base_dialect() {
                     Dialect::Postgres => match path {
                         JsonPath::Key(key) => format!("{expr_sql} -> '{key}'"),
                         JsonPath::Index(idx) => format!("{expr_sql} -> {idx}"),
@@ -1970,7 +2126,7 @@ impl Expr {
 
             Expr::JsonExtractText { expr, path } => {
                 let expr_sql = expr.build_with_dialect(dialect, params, offset);
-                match dialect {
+                match dialect.base_dialect() {
                     Dialect::Postgres => match path {
                         JsonPath::Key(key) => format!("{expr_sql} ->> '{key}'"),
                         JsonPath::Index(idx) => format!("{expr_sql} ->> {idx}"),
@@ -1995,14 +2151,14 @@ impl Expr {
 
             Expr::JsonExtractPath { expr, path } => {
                 let expr_sql = expr.build_with_dialect(dialect, params, offset);
-                match dialect {
+                match dialect.base_dialect() {
                     Dialect::Postgres => {
                         let path_array = path.join(", ");
                         format!("{expr_sql} #> '{{{path_array}}}'")
                     }
                     Dialect::Mysql | Dialect::Sqlite => {
                         let json_path = format!("$.{}", path.join("."));
-                        let func = if dialect == Dialect::Mysql {
+                        let func = if dialect.base_dialect() == Dialect::Mysql {
                             "JSON_EXTRACT"
                         } else {
                             "json_extract"
@@ -2014,7 +2170,7 @@ impl Expr {
 
             Expr::JsonExtractPathText { expr, path } => {
                 let expr_sql = expr.build_with_dialect(dialect, params, offset);
-                match dialect {
+                match dialect.base_dialect() {
                     Dialect::Postgres => {
                         let path_array = path.join(", ");
                         format!("{expr_sql} #>> '{{{path_array}}}'")
@@ -2033,7 +2189,7 @@ impl Expr {
             Expr::JsonContains { expr, other } => {
                 let expr_sql = expr.build_with_dialect(dialect, params, offset);
                 let other_sql = other.build_with_dialect(dialect, params, offset);
-                match dialect {
+                match dialect.base_dialect() {
                     Dialect::Postgres => format!("{expr_sql} @> {other_sql}"),
                     Dialect::Mysql => format!("JSON_CONTAINS({expr_sql}, {other_sql})"),
                     Dialect::Sqlite => {
@@ -2048,7 +2204,7 @@ impl Expr {
             Expr::JsonContainedBy { expr, other } => {
                 let expr_sql = expr.build_with_dialect(dialect, params, offset);
                 let other_sql = other.build_with_dialect(dialect, params, offset);
-                match dialect {
+                match dialect.base_dialect() {
                     Dialect::Postgres => format!("{expr_sql} <@ {other_sql}"),
                     Dialect::Mysql => format!("JSON_CONTAINS({other_sql}, {expr_sql})"),
                     Dialect::Sqlite => {
@@ -2061,7 +2217,7 @@ impl Expr {
 
             Expr::JsonHasKey { expr, key } => {
                 let expr_sql = expr.build_with_dialect(dialect, params, offset);
-                match dialect {
+                match dialect.base_dialect() {
                     Dialect::Postgres => format!("{expr_sql} ? '{key}'"),
                     Dialect::Mysql => format!("JSON_CONTAINS_PATH({expr_sql}, 'one', '$.{key}')"),
                     Dialect::Sqlite => format!("json_type({expr_sql}, '$.{key}') IS NOT NULL"),
@@ -2070,7 +2226,7 @@ impl Expr {
 
             Expr::JsonHasAnyKey { expr, keys } => {
                 let expr_sql = expr.build_with_dialect(dialect, params, offset);
-                match dialect {
+                match dialect.base_dialect() {
                     Dialect::Postgres => {
                         let keys_array = keys
                             .iter()
@@ -2100,7 +2256,7 @@ impl Expr {
 
             Expr::JsonHasAllKeys { expr, keys } => {
                 let expr_sql = expr.build_with_dialect(dialect, params, offset);
-                match dialect {
+                match dialect.base_dialect() {
                     Dialect::Postgres => {
                         let keys_array = keys
                             .iter()
@@ -2130,7 +2286,7 @@ impl Expr {
 
             Expr::JsonArrayLength { expr } => {
                 let expr_sql = expr.build_with_dialect(dialect, params, offset);
-                match dialect {
+                match dialect.base_dialect() {
                     Dialect::Postgres => format!("jsonb_array_length({expr_sql})"),
                     Dialect::Mysql => format!("JSON_LENGTH({expr_sql})"),
                     Dialect::Sqlite => format!("json_array_length({expr_sql})"),
@@ -2139,7 +2295,7 @@ impl Expr {
 
             Expr::JsonTypeof { expr } => {
                 let expr_sql = expr.build_with_dialect(dialect, params, offset);
-                match dialect {
+                match dialect.base_dialect() {
                     Dialect::Postgres => format!("jsonb_typeof({expr_sql})"),
                     Dialect::Mysql => format!("JSON_TYPE({expr_sql})"),
                     Dialect::Sqlite => format!("json_type({expr_sql})"),
@@ -2149,6 +2305,16 @@ impl Expr {
     }
 }
 
+/// Render a PostgreSQL-only construct for another dialect.
+///
+/// The result calls a function that does not exist, so the database
+/// rejects the statement with an error naming the feature (e.g.
+/// `no such function: sqlmodel_similar_to_requires_postgres`). The
+/// operands are kept so placeholder numbering stays intact.
+fn unsupported_outside_postgres(feature: &str, left: &str, right: &str) -> String {
+    format!("sqlmodel_{feature}_requires_postgres({left}, {right})")
+}
+
 // ==================== CASE Builder ====================
 
 /// Builder for CASE WHEN expressions.
@@ -2369,12 +2535,16 @@ impl From<f32> for Expr {
 ///
 /// Rewrites $1, $2, etc. to $offset+1, $offset+2, etc. for PostgreSQL,
 /// or ?1, ?2, etc. for SQLite. MySQL always uses ? so no adjustment needed.
-pub(crate) fn adjust_placeholder_indices(sql: &str, offset: usize, dialect: Dialect) -> String {
+pub(crate) fn adjust_placeholder_indices(
+    sql: &str,
+    offset: usize,
+    dialect: &'static dyn SqlDialect,
+) -> String {
     if offset == 0 {
         return sql.to_string();
     }
 
-    match dialect {
+    match dialect.base_dialect() {
         Dialect::Postgres => {
             // Rewrite $N to $(N+offset)
             let mut result = String::with_capacity(sql.len() + 20);
@@ -2710,6 +2880,70 @@ mod tests {
         assert_eq!(sql, "LOWER(\"name\") LIKE LOWER(?1)");
     }
 
+    #[test]
+    fn test_regex_match_per_dialect() {
+        let expr = Expr::col("sku").regex_match("^[A-Z]+$");
+        let mut params = Vec::new();
+        assert_eq!(
+            expr.build_with_dialect(Dialect::Postgres, &mut params, 0),
+            "\"sku\" ~ $1"
+        );
+        assert_eq!(
+            expr.build_with_dialect(Dialect::Mysql, &mut params, 0),
+            "`sku` REGEXP ?"
+        );
+        let negated = Expr::col("sku").not_regex_match("^tmp");
+        assert_eq!(
+            negated.build_with_dialect(Dialect::Postgres, &mut params, 0),
+            "\"sku\" !~ $3"
+        );
+        assert_eq!(
+            negated.build_with_dialect(Dialect::Sqlite, &mut params, 0),
+            "\"sku\" NOT REGEXP ?4"
+        );
+    }
+
+    #[test]
+    fn test_similar_to() {
+        let expr = Expr::col("code").similar_to("(a|b)%");
+        let mut params = Vec::new();
+        assert_eq!(
+            expr.build_with_dialect(Dialect::Postgres, &mut params, 0),
+            "\"code\" SIMILAR TO $1"
+        );
+        let mut params = Vec::new();
+        assert_eq!(
+            Expr::col("code").not_similar_to("x%").build_with_dialect(
+                Dialect::Mysql,
+                &mut params,
+                0
+            ),
+            "sqlmodel_similar_to_requires_postgres(`code`, ?)"
+        );
+        assert_eq!(params.len(), 1);
+    }
+
+    #[test]
+    fn test_trigram_similarity() {
+        let mut params = Vec::new();
+        let filter = Expr::col("name").trigram_similar("spidrman");
+        assert_eq!(
+            filter.build_with_dialect(Dialect::Postgres, &mut params, 0),
+            "\"name\" % $1"
+        );
+        let order = Expr::col("name").similarity("spidrman").desc();
+        assert_eq!(
+            order.build(Dialect::Postgres, &mut params, 0),
+            "similarity(\"name\", $2) DESC"
+        );
+
+        let mut params = Vec::new();
+        assert_eq!(
+            filter.build_with_dialect(Dialect::Sqlite, &mut params, 0),
+            "sqlmodel_trigram_similar_requires_postgres(\"name\", ?1)"
+        );
+    }
+
     // ==================== IN Tests ====================
 
     #[test]
@@ -2916,6 +3150,24 @@ mod tests {
         assert_eq!(sql, "\"name\" DESC NULLS LAST");
     }
 
+    #[test]
+    fn test_collate() {
+        let mut params = Vec::new();
+        let order = Expr::col("name").collate("und-x-icu").asc();
+        let sql = order.build(Dialect::Postgres, &mut params, 0);
+        assert_eq!(sql, "\"name\" COLLATE \"und-x-icu\" ASC");
+
+        let expr = Expr::collate(Expr::col("name"), "utf8mb4_bin").eq("Ann");
+        let sql = expr.build_with_dialect(Dialect::Mysql, &mut params, 0);
+        assert_eq!(sql, "`name` COLLATE `utf8mb4_bin` = ?");
+
+        let expr = Expr::col("first")
+            .concat(Expr::col("last"))
+            .collate("NOCASE");
+        let sql = expr.build_with_dialect(Dialect::Sqlite, &mut params, 0);
+        assert_eq!(sql, "(\"first\" || \"last\") COLLATE \"NOCASE\"");
+    }
+
     // ==================== Dialect Tests ====================
 
     #[test]
@@ -3507,21 +3759,21 @@ mod tests {
     #[test]
     fn test_adjust_placeholder_indices_postgres() {
         let sql = "SELECT * FROM t WHERE a = $1 AND b = $2";
-        let adjusted = super::adjust_placeholder_indices(sql, 3, Dialect::Postgres);
+        let adjusted = super::adjust_placeholder_indices(sql, 3, Dialect::Postgres.as_dyn());
         assert_eq!(adjusted, "SELECT * FROM t WHERE a = $4 AND b = $5");
     }
 
     #[test]
     fn test_adjust_placeholder_indices_sqlite() {
         let sql = "SELECT * FROM t WHERE a = ?1 AND b = ?2";
-        let adjusted = super::adjust_placeholder_indices(sql, 3, Dialect::Sqlite);
+        let adjusted = super::adjust_placeholder_indices(sql, 3, Dialect::Sqlite.as_dyn());
         assert_eq!(adjusted, "SELECT * FROM t WHERE a = ?4 AND b = ?5");
     }
 
     #[test]
     fn test_adjust_placeholder_indices_zero_offset() {
         let sql = "SELECT * FROM t WHERE a = $1";
-        let adjusted = super::adjust_placeholder_indices(sql, 0, Dialect::Postgres);
+        let adjusted = super::adjust_placeholder_indices(sql, 0, Dialect::Postgres.as_dyn());
         assert_eq!(adjusted, sql);
     }
 
@@ -3529,14 +3781,14 @@ mod tests {
     fn test_adjust_placeholder_indices_mysql() {
         // MySQL uses ? without indices, so no adjustment
         let sql = "SELECT * FROM t WHERE a = ? AND b = ?";
-        let adjusted = super::adjust_placeholder_indices(sql, 3, Dialect::Mysql);
+        let adjusted = super::adjust_placeholder_indices(sql, 3, Dialect::Mysql.as_dyn());
         assert_eq!(adjusted, sql);
     }
 
     #[test]
     fn test_adjust_placeholder_indices_postgres_ignores_quoted_literals() {
         let sql = "SELECT '$1' AS s, col FROM t WHERE a = $1 AND note = 'it''s $2'";
-        let adjusted = super::adjust_placeholder_indices(sql, 3, Dialect::Postgres);
+        let adjusted = super::adjust_placeholder_indices(sql, 3, Dialect::Postgres.as_dyn());
         assert_eq!(
             adjusted,
             "SELECT '$1' AS s, col FROM t WHERE a = $4 AND note = 'it''s $2'"
@@ -3546,7 +3798,7 @@ mod tests {
     #[test]
     fn test_adjust_placeholder_indices_sqlite_ignores_quoted_literals() {
         let sql = "SELECT '?1' AS s, col FROM t WHERE a = ?1 AND note = 'keep ?2'";
-        let adjusted = super::adjust_placeholder_indices(sql, 3, Dialect::Sqlite);
+        let adjusted = super::adjust_placeholder_indices(sql, 3, Dialect::Sqlite.as_dyn());
         assert_eq!(
             adjusted,
             "SELECT '?1' AS s, col FROM t WHERE a = ?4 AND note = 'keep ?2'"
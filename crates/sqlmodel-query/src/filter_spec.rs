@@ -0,0 +1,484 @@
+//! Dynamic filters parsed from untrusted JSON.
+//!
+//! REST list endpoints often accept a filter document from the client:
+//!
+//! ```ignore
+//! // GET /heroes?filter={"age": {"gt": 18}, "name": {"ilike": "%man%"}}
+//! let filter = FilterSpec::<Hero>::new()
+//!     .allow(&["name", "age", "team_id"])
+//!     .parse_str(&params.filter)?;
+//! let mut query = select!(Hero);
+//! if let Some(expr) = filter {
+//!     query = query.filter(expr);
+//! }
+//! ```
+//!
+//! Keys are checked against [`Model::fields`] and every operand is converted
+//! with [`Value::from_json`] for the column's type, so the client can only
+//! produce bound parameters against known columns, never SQL text. Invalid
+//! documents fail with [`Error::Validation`] naming the offending path
+//! (`or[1].age.gt`).
+//!
+//! A field maps to an operator object or, as shorthand for `eq`, a bare
+//! value (`{"active": true}`; `null` means `IS NULL`). The operators are
+//! `eq`, `ne`, `gt`, `gte`, `lt`, `lte`, `in`, `not_in`, `like`, `ilike`
+//! and `is_null`; `and` and `or` take lists of filters and `not` takes one.
+
+use std::marker::PhantomData;
+
+use sqlmodel_core::{
+    Error, FieldInfo, Model, Result, SqlType, ValidationError, ValidationErrorKind, Value,
+};
+
+use crate::expr::Expr;
+
+/// Default cap on how deeply `and`/`or`/`not` may nest.
+pub const DEFAULT_MAX_DEPTH: usize = 8;
+
+/// Default cap on the number of values in one `in`/`not_in` list.
+pub const DEFAULT_MAX_LIST_LEN: usize = 1000;
+
+/// Parser for client-supplied filter documents over `M`.
+///
+/// Only fields that reach API output (not `exclude`, `api_exclude`,
+/// `db_only` or computed) can be filtered on, addressed by their Rust name
+/// or serialization alias.
+pub struct FilterSpec<M> {
+    allowed: Option<Vec<&'static str>>,
+    max_depth: usize,
+    max_list_len: usize,
+    _model: PhantomData<fn() -> M>,
+}
+
+impl<M: Model> FilterSpec<M> {
+    /// Create a parser accepting every API-visible field of `M`.
+    pub fn new() -> Self {
+        Self {
+            allowed: None,
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_list_len: DEFAULT_MAX_LIST_LEN,
+            _model: PhantomData,
+        }
+    }
+
+    /// Restrict filtering to the named fields, e.g. the indexed ones.
+    #[must_use]
+    pub fn allow(mut self, fields: &[&'static str]) -> Self {
+        self.allowed = Some(fields.to_vec());
+        self
+    }
+
+    /// Cap how deeply `and`/`or`/`not` may nest (default
+    /// [`DEFAULT_MAX_DEPTH`]).
+    #[must_use]
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Cap the length of `in`/`not_in` lists (default
+    /// [`DEFAULT_MAX_LIST_LEN`]).
+    #[must_use]
+    pub fn max_list_len(mut self, len: usize) -> Self {
+        self.max_list_len = len;
+        self
+    }
+
+    /// Parse a filter document from its JSON text.
+    #[allow(clippy::result_large_err)]
+    pub fn parse_str(&self, filter: &str) -> Result<Option<Expr>> {
+        let json: serde_json::Value = serde_json::from_str(filter)
+            .map_err(|e| invalid("filter", format!("malformed JSON: {e}")))?;
+        self.parse(&json)
+    }
+
+    /// Translate a filter document into a `WHERE` expression.
+    ///
+    /// Returns `None` for `null` or an empty object.
+    #[allow(clippy::result_large_err)]
+    pub fn parse(&self, filter: &serde_json::Value) -> Result<Option<Expr>> {
+        self.filter(filter, "", 0)
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn filter(&self, filter: &serde_json::Value, path: &str, depth: usize) -> Result<Option<Expr>> {
+        let entries = match filter {
+            serde_json::Value::Null => return Ok(None),
+            serde_json::Value::Object(entries) => entries,
+            _ => return Err(invalid(or_root(path), "expected a filter object")),
+        };
+        if depth > self.max_depth {
+            return Err(invalid(
+                or_root(path),
+                format!("filters nest deeper than {} levels", self.max_depth),
+            ));
+        }
+
+        let mut terms = Vec::with_capacity(entries.len());
+        for (key, value) in entries {
+            let at = join_path(path, key);
+            let term = match key.as_str() {
+                "and" | "or" => {
+                    let serde_json::Value::Array(items) = value else {
+                        return Err(invalid(at, "expected a list of filters"));
+                    };
+                    let mut parts = Vec::with_capacity(items.len());
+                    for (i, item) in items.iter().enumerate() {
+                        parts.extend(self.filter(item, &format!("{at}[{i}]"), depth + 1)?);
+                    }
+                    if key == "and" {
+                        combine(parts, Expr::and)
+                    } else {
+                        combine(parts, Expr::or)
+                    }
+                }
+                "not" => self
+                    .filter(value, &at, depth + 1)?
+                    .map(|expr| expr.paren().not()),
+                name => {
+                    let field = api_field::<M>(name, self.allowed.as_deref()).ok_or_else(|| {
+                        invalid(&at, format!("unknown field '{name}' on {}", M::TABLE_NAME))
+                    })?;
+                    self.field_filter(field, value, &at)?
+                }
+            };
+            terms.extend(term);
+        }
+        Ok(combine(terms, Expr::and))
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn field_filter(
+        &self,
+        field: &FieldInfo,
+        ops: &serde_json::Value,
+        path: &str,
+    ) -> Result<Option<Expr>> {
+        let serde_json::Value::Object(ops) = ops else {
+            // Shorthand: a bare value is `eq`, `null` is `is_null`.
+            return self.operator(field, "eq", ops, path).map(Some);
+        };
+        let mut terms = Vec::with_capacity(ops.len());
+        for (op, operand) in ops {
+            terms.push(self.operator(field, op, operand, &join_path(path, op))?);
+        }
+        Ok(combine(terms, Expr::and))
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn operator(
+        &self,
+        field: &FieldInfo,
+        op: &str,
+        operand: &serde_json::Value,
+        path: &str,
+    ) -> Result<Expr> {
+        let column = Expr::col(field.column_name);
+        if op == "is_null" || (matches!(op, "eq" | "ne") && operand.is_null()) {
+            let is_null = match (op, operand) {
+                ("is_null", serde_json::Value::Bool(b)) => *b,
+                ("is_null", _) => return Err(invalid(path, "expected true or false")),
+                (op, _) => op == "eq",
+            };
+            return Ok(if is_null {
+                column.is_null()
+            } else {
+                column.is_not_null()
+            });
+        }
+
+        let sql_type = &field.sql_type;
+        let supported = match op {
+            "eq" | "ne" | "in" | "not_in" => comparable(sql_type),
+            "gt" | "gte" | "lt" | "lte" => ordered(sql_type),
+            "like" | "ilike" => textual(sql_type),
+            _ => return Err(invalid(path, format!("unknown operator '{op}'"))),
+        };
+        if !supported {
+            return Err(invalid(
+                path,
+                format!("'{op}' is not supported on {}", sql_type.sql_name()),
+            ));
+        }
+
+        let expr = match op {
+            "in" | "not_in" => {
+                let serde_json::Value::Array(items) = operand else {
+                    return Err(invalid(path, "expected a list of values"));
+                };
+                if items.is_empty() || items.len() > self.max_list_len {
+                    return Err(invalid(
+                        path,
+                        format!("expected 1 to {} values", self.max_list_len),
+                    ));
+                }
+                let values = items
+                    .iter()
+                    .map(|item| operand_value(field, item, path))
+                    .collect::<Result<Vec<_>>>()?;
+                if op == "in" {
+                    column.in_list(values)
+                } else {
+                    column.not_in_list(values)
+                }
+            }
+            "like" | "ilike" => {
+                let serde_json::Value::String(pattern) = operand else {
+                    return Err(invalid(path, "expected a pattern string"));
+                };
+                if op == "like" {
+                    column.like(pattern.clone())
+                } else {
+                    column.ilike(pattern.clone())
+                }
+            }
+            _ => {
+                let value = operand_value(field, operand, path)?;
+                match op {
+                    "eq" => column.eq(value),
+                    "ne" => column.ne(value),
+                    "gt" => column.gt(value),
+                    "gte" => column.ge(value),
+                    "lt" => column.lt(value),
+                    _ => column.le(value),
+                }
+            }
+        };
+        Ok(expr)
+    }
+}
+
+impl<M: Model> Default for FilterSpec<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The field of `M` a client addresses as `name`, by Rust name or
+/// serialization alias, if it is visible to APIs and in `allowed`.
+pub(crate) fn api_field<M: Model>(
+    name: &str,
+    allowed: Option<&[&'static str]>,
+) -> Option<&'static FieldInfo> {
+    api_fields::<M>(allowed).find(|field| field.name == name || field.output_name() == name)
+}
+
+/// The fields of `M` clients may address, in declaration order.
+pub(crate) fn api_fields<M: Model>(
+    allowed: Option<&[&'static str]>,
+) -> impl Iterator<Item = &'static FieldInfo> {
+    M::fields().iter().filter(move |field| {
+        field.in_api()
+            && field.is_serialized()
+            && !field.computed
+            && allowed.is_none_or(|allowed| allowed.contains(&field.name))
+    })
+}
+
+/// Whether values of `sql_type` can be compared for equality.
+fn comparable(sql_type: &SqlType) -> bool {
+    !matches!(
+        sql_type,
+        SqlType::Json | SqlType::JsonB | SqlType::Array(_) | SqlType::Custom(_)
+    )
+}
+
+/// Whether values of `sql_type` have a meaningful order.
+pub(crate) fn ordered(sql_type: &SqlType) -> bool {
+    comparable(sql_type)
+        && !matches!(
+            sql_type,
+            SqlType::Boolean | SqlType::Binary(_) | SqlType::VarBinary(_) | SqlType::Blob
+        )
+}
+
+/// Whether `sql_type` is a string type `LIKE` applies to.
+fn textual(sql_type: &SqlType) -> bool {
+    matches!(
+        sql_type,
+        SqlType::Char(_) | SqlType::VarChar(_) | SqlType::Text | SqlType::CiText
+    )
+}
+
+#[allow(clippy::result_large_err)]
+fn operand_value(field: &FieldInfo, operand: &serde_json::Value, path: &str) -> Result<Value> {
+    if operand.is_null() {
+        return Err(invalid(
+            path,
+            "null is only allowed with eq, ne and is_null",
+        ));
+    }
+    Value::from_json(operand.clone(), &field.sql_type).map_err(|e| invalid(path, e.to_string()))
+}
+
+fn combine(terms: Vec<Expr>, op: fn(Expr, Expr) -> Expr) -> Option<Expr> {
+    let single = terms.len() == 1;
+    terms
+        .into_iter()
+        .map(|term| if single { term } else { term.paren() })
+        .reduce(op)
+}
+
+fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+fn or_root(path: &str) -> &str {
+    if path.is_empty() { "filter" } else { path }
+}
+
+pub(crate) fn invalid(path: impl Into<String>, message: impl Into<String>) -> Error {
+    let mut errors = ValidationError::new();
+    errors.add(path, ValidationErrorKind::Custom, message);
+    Error::Validation(errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use sqlmodel_core::Row;
+
+    struct Hero;
+
+    impl Model for Hero {
+        const TABLE_NAME: &'static str = "heroes";
+        const PRIMARY_KEY: &'static [&'static str] = &["id"];
+
+        fn fields() -> &'static [FieldInfo] {
+            static FIELDS: &[FieldInfo] = &[
+                FieldInfo::new("id", "id", SqlType::BigInt).primary_key(true),
+                FieldInfo::new("name", "hero_name", SqlType::Text).serialization_alias("heroName"),
+                FieldInfo::new("age", "age", SqlType::Integer).nullable(true),
+                FieldInfo::new("active", "active", SqlType::Boolean),
+                FieldInfo::new("password_hash", "password_hash", SqlType::Text).exclude(true),
+            ];
+            FIELDS
+        }
+
+        fn to_row(&self) -> Vec<(&'static str, Value)> {
+            Vec::new()
+        }
+
+        fn from_row(_row: &Row) -> Result<Self> {
+            Ok(Self)
+        }
+
+        fn primary_key_value(&self) -> Vec<Value> {
+            Vec::new()
+        }
+
+        fn is_new(&self) -> bool {
+            true
+        }
+    }
+
+    fn render(spec: &FilterSpec<Hero>, filter: &serde_json::Value) -> (String, Vec<Value>) {
+        let expr = spec.parse(filter).unwrap().unwrap();
+        let mut params = Vec::new();
+        let sql = expr.build(&mut params, 0);
+        (sql, params)
+    }
+
+    fn error_path(result: Result<Option<Expr>>) -> String {
+        match result {
+            Err(Error::Validation(errors)) => errors.errors[0].field.clone(),
+            other => panic!("expected a validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_binds_typed_operands() {
+        let (sql, params) = render(
+            &FilterSpec::new(),
+            &json!({"age": {"gt": "18"}, "name": {"ilike": "%man%"}}),
+        );
+        assert_eq!(sql, "(\"age\" > $1) AND (\"hero_name\" ILIKE $2)");
+        assert_eq!(params, vec![Value::Int(18), Value::Text("%man%".into())]);
+    }
+
+    #[test]
+    fn test_parse_shorthand_and_combinators() {
+        let (sql, params) = render(
+            &FilterSpec::new(),
+            &json!({
+                "active": true,
+                "or": [{"age": null}, {"heroName": {"in": ["Rusty", "Ferris"]}}],
+            }),
+        );
+        assert_eq!(
+            sql,
+            "(\"active\" = $1) AND ((\"age\" IS NULL) OR (\"hero_name\" IN ($2, $3)))"
+        );
+        assert_eq!(params.len(), 3);
+
+        let (sql, _) = render(&FilterSpec::new(), &json!({"not": {"age": {"lte": 30}}}));
+        assert_eq!(sql, "NOT (\"age\" <= $1)");
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_and_hidden_fields() {
+        let spec = FilterSpec::<Hero>::new();
+        assert_eq!(
+            error_path(spec.parse(&json!({"hero_name": "x"}))),
+            "hero_name"
+        );
+        assert_eq!(
+            error_path(spec.parse(&json!({"password_hash": {"like": "a%"}}))),
+            "password_hash"
+        );
+        assert_eq!(
+            error_path(
+                FilterSpec::<Hero>::new()
+                    .allow(&["age"])
+                    .parse(&json!({"name": "x"}))
+            ),
+            "name"
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_incompatible_operators_and_values() {
+        let spec = FilterSpec::<Hero>::new();
+        assert_eq!(
+            error_path(spec.parse(&json!({"age": {"like": "1%"}}))),
+            "age.like"
+        );
+        assert_eq!(
+            error_path(spec.parse(&json!({"active": {"gt": true}}))),
+            "active.gt"
+        );
+        assert_eq!(
+            error_path(spec.parse(&json!({"age": {"eq": "old"}}))),
+            "age.eq"
+        );
+        assert_eq!(
+            error_path(spec.parse(&json!({"age": {"between": [1, 2]}}))),
+            "age.between"
+        );
+        assert_eq!(
+            error_path(spec.parse(&json!({"or": [{"age": 1}, {"age": {"gt": []}}]}))),
+            "or[1].age.gt"
+        );
+    }
+
+    #[test]
+    fn test_parse_enforces_limits() {
+        let spec = FilterSpec::<Hero>::new().max_depth(1).max_list_len(2);
+        assert!(spec.parse(&json!({"not": {"age": 1}})).is_ok());
+        assert_eq!(
+            error_path(spec.parse(&json!({"not": {"not": {"age": 1}}}))),
+            "not.not"
+        );
+        assert_eq!(
+            error_path(spec.parse(&json!({"age": {"in": [1, 2, 3]}}))),
+            "age.in"
+        );
+        assert!(spec.parse_str("{\"age\":").is_err());
+        assert!(spec.parse(&json!({})).unwrap().is_none());
+    }
+}
@@ -3,6 +3,7 @@
 use crate::expr::{Dialect, Expr, adjust_placeholder_indices};
 use crate::subquery::SelectQuery;
 use sqlmodel_core::Value;
+use sqlmodel_core::dialect::{IntoSqlDialect, SqlDialect};
 
 /// A JOIN clause.
 #[derive(Debug, Clone)]
@@ -229,12 +230,13 @@ impl Join {
     /// literal values that need to be bound as parameters.
     pub fn to_sql(&self) -> (String, Vec<Value>) {
         let mut params = Vec::new();
-        let sql = self.build_sql(Dialect::default(), &mut params, 0);
+        let sql = self.build_sql(Dialect::default().as_dyn(), &mut params, 0);
         (sql, params)
     }
 
     /// Generate SQL for this JOIN clause with a specific dialect.
-    pub fn to_sql_with_dialect(&self, dialect: Dialect) -> (String, Vec<Value>) {
+    pub fn to_sql_with_dialect(&self, dialect: impl IntoSqlDialect) -> (String, Vec<Value>) {
+        let dialect = dialect.into_sql_dialect();
         let mut params = Vec::new();
         let sql = self.build_sql(dialect, &mut params, 0);
         (sql, params)
@@ -242,20 +244,26 @@ impl Join {
 
     /// Generate SQL and collect parameters.
     pub fn build(&self, params: &mut Vec<Value>, offset: usize) -> String {
-        self.build_sql(Dialect::default(), params, offset)
+        self.build_sql(Dialect::default().as_dyn(), params, offset)
     }
 
     /// Generate SQL and collect parameters with a specific dialect.
     pub fn build_with_dialect(
         &self,
-        dialect: Dialect,
+        dialect: impl IntoSqlDialect,
         params: &mut Vec<Value>,
         offset: usize,
     ) -> String {
+        let dialect = dialect.into_sql_dialect();
         self.build_sql(dialect, params, offset)
     }
 
-    fn build_sql(&self, dialect: Dialect, params: &mut Vec<Value>, offset: usize) -> String {
+    fn build_sql(
+        &self,
+        dialect: &'static dyn SqlDialect,
+        params: &mut Vec<Value>,
+        offset: usize,
+    ) -> String {
         let lateral_keyword = if self.lateral { " LATERAL" } else { "" };
 
         let (table_ref, subquery_params) = if let Some(subquery) = &self.subquery {
@@ -5,36 +5,56 @@
 //!
 //! # Role In The Architecture
 //!
-//! - **Query macros**: `select!`, `insert!`, `update!`, `delete!` build typed queries.
+//! - **Query macros**: `select!`, `insert!`, `update!`, `delete!`, `merge!` build typed queries;
+//!   `query!` declares a reusable [`Query`] handle with typed parameters.
 //! - **Expression DSL**: `Expr` and operators build WHERE/HAVING clauses safely.
 //! - **Dialect support**: generates SQL for Postgres, MySQL, and SQLite.
+//! - **Query AST**: `QueryAst` exposes the lowered query for inspection/rewriting via
+//!   visitors before rendering with a per-dialect `SqlRenderer`.
 //!
 //! The resulting queries execute through the `Connection` trait from `sqlmodel-core`.
 //! Most users access these builders via the `sqlmodel` facade crate.
 
+pub mod ast;
+pub mod batch;
 pub mod builder;
 pub mod cache;
 pub mod clause;
 pub mod cte;
+pub mod cursor;
 pub mod eager;
 pub mod expr;
+pub mod filter_spec;
 pub mod join;
+pub mod merge;
+pub mod prepared;
 pub mod select;
 pub mod set_ops;
+pub mod sort_spec;
 pub mod subquery;
+pub mod writer;
 
+pub use ast::{
+    DialectRenderer, MysqlRenderer, PostgresRenderer, QueryAst, SqlRenderer, SqliteRenderer,
+    Visitor, VisitorMut, renderer_for,
+};
+pub use batch::BatchProgress;
 pub use builder::{
-    DeleteBuilder, InsertBuilder, InsertManyBuilder, OnConflict, QueryBuilder, SetClause,
-    UpdateBuilder,
+    DeleteBuilder, InsertBuilder, InsertManyBuilder, InsertReturningModel, OnConflict,
+    QueryBuilder, RawQuery, SetClause, SetValue, UpdateBuilder,
 };
 pub use cache::{StatementCache, cache_key};
 pub use clause::{Limit, Offset, OrderBy, Where};
 pub use cte::{Cte, CteRef, WithQuery};
+pub use cursor::Cursor;
 pub use eager::{EagerLoader, IncludePath};
 pub use expr::{
     BinaryOp, Dialect, Expr, UnaryOp, WindowBuilder, WindowFrame, WindowFrameBound, WindowFrameType,
 };
+pub use filter_spec::FilterSpec;
 pub use join::{Join, JoinType};
+pub use merge::MergeBuilder;
+pub use prepared::{PreparedQuery, Query, QueryParams};
 pub use select::{
     PolymorphicJoined, PolymorphicJoined2, PolymorphicJoined3, PolymorphicJoinedSelect,
     PolymorphicJoinedSelect2, PolymorphicJoinedSelect3, Select,
@@ -42,7 +62,9 @@ pub use select::{
 pub use set_ops::{
     SetOpType, SetOperation, except, except_all, intersect, intersect_all, union, union_all,
 };
+pub use sort_spec::{FieldSet, SortSpec};
 pub use subquery::SelectQuery;
+pub use writer::{ModelStatements, SqlWriter, StatementKind};
 
 use asupersync::{Cx, Outcome};
 use sqlmodel_core::{Connection, Row, Value};
@@ -65,6 +87,26 @@ macro_rules! select {
     };
 }
 
+/// Declare a reusable [`Query`] handle, typically as a `static`.
+///
+/// The query is built on first use; its placeholders are bound from the
+/// handle's parameter tuple.
+///
+/// # Example
+///
+/// ```ignore
+/// static HERO_BY_EMAIL: Query<Hero, (String,)> =
+///     query!(select!(Hero).filter(Expr::col("email").eq(Expr::placeholder(1))));
+///
+/// let hero = HERO_BY_EMAIL.one(cx, &conn, (email,)).await?;
+/// ```
+#[macro_export]
+macro_rules! query {
+    ($select:expr) => {
+        $crate::Query::new(|| $select)
+    };
+}
+
 /// Create an INSERT query for a model.
 ///
 /// # Example
@@ -137,6 +179,26 @@ macro_rules! delete {
     };
 }
 
+/// Create a MERGE statement targeting a model's table.
+///
+/// # Example
+///
+/// ```ignore
+/// merge!(Hero)
+///     .using(select!(StagedHero))
+///     .on(&["id"])
+///     .when_matched_update(&["name"])
+///     .when_not_matched_insert(&["id", "name"])
+///     .execute(cx, &conn)
+///     .await?;
+/// ```
+#[macro_export]
+macro_rules! merge {
+    ($model:ty) => {
+        $crate::merge::MergeBuilder::<$model>::new()
+    };
+}
+
 /// Raw SQL query execution.
 ///
 /// For queries that can't be expressed with the type-safe builder.
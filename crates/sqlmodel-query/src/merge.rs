@@ -0,0 +1,565 @@
+//! MERGE statement builder (SQL:2003).
+//!
+//! [`MergeBuilder`] synchronizes a target table from a source query in one
+//! statement, which is the usual shape of warehouse-style sync jobs:
+//!
+//! ```ignore
+//! merge!(Hero)
+//!     .using(select!(StagedHero).filter(Expr::col("batch").eq(7)))
+//!     .on(&["id"])
+//!     .when_matched_update(&["name", "age"])
+//!     .when_not_matched_insert(&["id", "name", "age"])
+//!     .execute(cx, &conn)
+//!     .await?;
+//! ```
+//!
+//! Dialects that support `MERGE` (PostgreSQL 15+) get a native statement:
+//!
+//! ```sql
+//! MERGE INTO "heroes" AS "target"
+//! USING (SELECT * FROM staged_heroes WHERE "batch" = $1) AS "source"
+//! ON "target"."id" = "source"."id"
+//! WHEN MATCHED THEN UPDATE SET "name" = "source"."name", "age" = "source"."age"
+//! WHEN NOT MATCHED THEN INSERT ("id", "name", "age")
+//!   VALUES ("source"."id", "source"."name", "source"."age")
+//! ```
+//!
+//! Elsewhere (SQLite, MySQL, or PostgreSQL with [`MergeBuilder::emulate`]) the
+//! same request is emulated as `INSERT ... SELECT` plus the dialect's upsert
+//! clause. The emulation relies on a unique index over the [`on`](MergeBuilder::on)
+//! columns and therefore supports a subset of MERGE: an insert branch is
+//! required, the match condition must be plain key columns, and matched rows
+//! can only be updated (not deleted).
+//!
+//! Expressions can refer to either side with [`target`] and [`source`]; the
+//! emulation rewrites those references to the upsert equivalents.
+
+use crate::ast::{QueryAst, VisitorMut, walk_expr_mut};
+use crate::expr::{Dialect, Expr};
+use crate::select::Select;
+use asupersync::{Cx, Outcome};
+use sqlmodel_core::dialect::{IntoSqlDialect, SqlDialect};
+use sqlmodel_core::{Connection, Error, Model, UpsertSyntax, Value};
+use std::marker::PhantomData;
+
+/// Alias of the target table inside a MERGE statement.
+pub const TARGET_ALIAS: &str = "target";
+
+/// Alias of the source query inside a MERGE statement.
+pub const SOURCE_ALIAS: &str = "source";
+
+/// Reference a column of the MERGE target table.
+pub fn target(column: impl Into<String>) -> Expr {
+    Expr::qualified(TARGET_ALIAS, column)
+}
+
+/// Reference a column of the MERGE source query.
+pub fn source(column: impl Into<String>) -> Expr {
+    Expr::qualified(SOURCE_ALIAS, column)
+}
+
+/// Action taken for target rows matched by the source.
+#[derive(Debug, Clone)]
+enum MatchedAction {
+    Update(Vec<(String, Expr)>),
+    Delete,
+}
+
+/// MERGE statement builder.
+///
+/// See the [module documentation](self) for the generated SQL and the
+/// limits of the upsert emulation.
+#[derive(Debug)]
+pub struct MergeBuilder<M: Model> {
+    source: Option<QueryAst>,
+    on_columns: Vec<String>,
+    on_expr: Option<Expr>,
+    matched: Option<MatchedAction>,
+    insert_columns: Option<Vec<String>>,
+    emulate: bool,
+    _marker: PhantomData<M>,
+}
+
+impl<M: Model> MergeBuilder<M> {
+    /// Create a new MERGE builder targeting `M`'s table.
+    pub fn new() -> Self {
+        Self {
+            source: None,
+            on_columns: Vec::new(),
+            on_expr: None,
+            matched: None,
+            insert_columns: None,
+            emulate: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Set the source query rows are merged from.
+    #[allow(clippy::needless_pass_by_value)] // Takes the query by value like other builder steps
+    pub fn using<S: Model>(mut self, source: Select<S>) -> Self {
+        self.source = Some(source.to_ast());
+        self
+    }
+
+    /// Match target and source rows on equality of these columns.
+    ///
+    /// The columns must exist under the same name on both sides. For the
+    /// upsert emulation they must also be covered by a unique index.
+    pub fn on(mut self, columns: &[&str]) -> Self {
+        self.on_columns = columns.iter().map(|c| (*c).to_string()).collect();
+        self
+    }
+
+    /// Add an arbitrary match condition (native MERGE only).
+    ///
+    /// Combined with [`on`](Self::on) columns using AND.
+    pub fn on_expr(mut self, expr: Expr) -> Self {
+        self.on_expr = Some(match self.on_expr {
+            Some(existing) => existing.and(expr),
+            None => expr,
+        });
+        self
+    }
+
+    /// Update matched target rows, copying these columns from the source.
+    pub fn when_matched_update(mut self, columns: &[&str]) -> Self {
+        for column in columns {
+            self = self.when_matched_set(*column, source(*column));
+        }
+        self
+    }
+
+    /// Update one column of matched target rows to an arbitrary expression.
+    pub fn when_matched_set(mut self, column: impl Into<String>, value: Expr) -> Self {
+        let column = column.into();
+        match &mut self.matched {
+            Some(MatchedAction::Update(sets)) => {
+                sets.retain(|(c, _)| *c != column);
+                sets.push((column, value));
+            }
+            _ => self.matched = Some(MatchedAction::Update(vec![(column, value)])),
+        }
+        self
+    }
+
+    /// Delete matched target rows (native MERGE only).
+    pub fn when_matched_delete(mut self) -> Self {
+        self.matched = Some(MatchedAction::Delete);
+        self
+    }
+
+    /// Insert source rows with no match, copying these columns from the source.
+    pub fn when_not_matched_insert(mut self, columns: &[&str]) -> Self {
+        self.insert_columns = Some(columns.iter().map(|c| (*c).to_string()).collect());
+        self
+    }
+
+    /// Force the upsert emulation even if the dialect supports MERGE.
+    ///
+    /// Use this for PostgreSQL servers older than 15.
+    pub fn emulate(mut self) -> Self {
+        self.emulate = true;
+        self
+    }
+
+    /// Build the statement and parameters with default dialect (Postgres).
+    #[allow(clippy::result_large_err)]
+    pub fn build(&self) -> Result<(String, Vec<Value>), Error> {
+        self.build_with_dialect(Dialect::default())
+    }
+
+    /// Build the statement and parameters with a specific dialect.
+    ///
+    /// Returns an error if the builder is incomplete, or if the requested
+    /// MERGE cannot be expressed by the upsert emulation.
+    #[allow(clippy::result_large_err)]
+    pub fn build_with_dialect(
+        &self,
+        dialect: impl IntoSqlDialect,
+    ) -> Result<(String, Vec<Value>), Error> {
+        let dialect = dialect.into_sql_dialect();
+        let Some(source) = &self.source else {
+            return Err(Error::Custom(
+                "MERGE requires a source; call using()".to_string(),
+            ));
+        };
+        if self.on_columns.is_empty() && self.on_expr.is_none() {
+            return Err(Error::Custom(
+                "MERGE requires a match condition; call on() or on_expr()".to_string(),
+            ));
+        }
+        if self.matched.is_none() && self.insert_columns.is_none() {
+            return Err(Error::Custom(
+                "MERGE requires at least one WHEN clause".to_string(),
+            ));
+        }
+
+        let (source_sql, mut params) = source.render(dialect)?;
+        let sql = if dialect.supports_merge() && !self.emulate {
+            self.build_native(dialect, &source_sql, &mut params)
+        } else {
+            self.build_emulated(dialect, &source_sql, &mut params)?
+        };
+        Ok((sql, params))
+    }
+
+    fn build_native(
+        &self,
+        dialect: &'static dyn SqlDialect,
+        source_sql: &str,
+        params: &mut Vec<Value>,
+    ) -> String {
+        let quote = |name: &str| dialect.quote_identifier(name);
+
+        let mut on_parts: Vec<String> = self
+            .on_columns
+            .iter()
+            .map(|c| {
+                format!(
+                    "{}.{} = {}.{}",
+                    quote(TARGET_ALIAS),
+                    quote(c),
+                    quote(SOURCE_ALIAS),
+                    quote(c)
+                )
+            })
+            .collect();
+        if let Some(expr) = &self.on_expr {
+            on_parts.push(expr.build_with_dialect(dialect, params, 0));
+        }
+
+        let mut sql = format!(
+            "MERGE INTO {} AS {} USING ({}) AS {} ON {}",
+            quote(M::TABLE_NAME),
+            quote(TARGET_ALIAS),
+            source_sql,
+            quote(SOURCE_ALIAS),
+            on_parts.join(" AND ")
+        );
+
+        match &self.matched {
+            Some(MatchedAction::Update(sets)) => {
+                let sets: Vec<String> = sets
+                    .iter()
+                    .map(|(c, e)| {
+                        format!(
+                            "{} = {}",
+                            quote(c),
+                            e.build_with_dialect(dialect, params, 0)
+                        )
+                    })
+                    .collect();
+                sql.push_str(" WHEN MATCHED THEN UPDATE SET ");
+                sql.push_str(&sets.join(", "));
+            }
+            Some(MatchedAction::Delete) => sql.push_str(" WHEN MATCHED THEN DELETE"),
+            None => {}
+        }
+
+        if let Some(columns) = &self.insert_columns {
+            let cols: Vec<String> = columns.iter().map(|c| quote(c)).collect();
+            let values: Vec<String> = columns
+                .iter()
+                .map(|c| format!("{}.{}", quote(SOURCE_ALIAS), quote(c)))
+                .collect();
+            sql.push_str(&format!(
+                " WHEN NOT MATCHED THEN INSERT ({}) VALUES ({})",
+                cols.join(", "),
+                values.join(", ")
+            ));
+        }
+
+        sql
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn build_emulated(
+        &self,
+        dialect: &'static dyn SqlDialect,
+        source_sql: &str,
+        params: &mut Vec<Value>,
+    ) -> Result<String, Error> {
+        let unsupported = |what: &str| {
+            Err(Error::Custom(format!(
+                "MERGE emulation on {} does not support {what}",
+                dialect.name()
+            )))
+        };
+        let Some(columns) = &self.insert_columns else {
+            return unsupported("a MERGE without when_not_matched_insert()");
+        };
+        if self.on_columns.is_empty() || self.on_expr.is_some() {
+            return unsupported("on_expr(); match on unique key columns with on()");
+        }
+        if matches!(self.matched, Some(MatchedAction::Delete)) {
+            return unsupported("when_matched_delete()");
+        }
+
+        let quote = |name: &str| dialect.quote_identifier(name);
+        let upsert = dialect.upsert_syntax();
+
+        // INSERT INTO target [AS "target"] (cols) SELECT "source".cols FROM (src) AS "source"
+        let mut sql = format!("INSERT INTO {}", quote(M::TABLE_NAME));
+        if upsert == UpsertSyntax::OnConflict {
+            sql.push_str(&format!(" AS {}", quote(TARGET_ALIAS)));
+        }
+        let cols: Vec<String> = columns.iter().map(|c| quote(c)).collect();
+        let values: Vec<String> = columns
+            .iter()
+            .map(|c| format!("{}.{}", quote(SOURCE_ALIAS), quote(c)))
+            .collect();
+        sql.push_str(&format!(
+            " ({}) SELECT {} FROM ({}) AS {}",
+            cols.join(", "),
+            values.join(", "),
+            source_sql,
+            quote(SOURCE_ALIAS)
+        ));
+
+        // Rewrite `target.x` / `source.x` references to what the upsert clause can see.
+        let mut rewrite = match upsert {
+            UpsertSyntax::OnDuplicateKeyUpdate => RenameQualifier {
+                from: TARGET_ALIAS,
+                to: M::TABLE_NAME,
+            },
+            _ => RenameQualifier {
+                from: SOURCE_ALIAS,
+                to: "excluded",
+            },
+        };
+        let sets: Vec<String> = match &self.matched {
+            Some(MatchedAction::Update(sets)) => sets
+                .iter()
+                .map(|(c, e)| {
+                    let mut e = e.clone();
+                    rewrite.visit_expr_mut(&mut e);
+                    format!(
+                        "{} = {}",
+                        quote(c),
+                        e.build_with_dialect(dialect, params, 0)
+                    )
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        match upsert {
+            UpsertSyntax::OnConflict => {
+                // SQLite needs a WHERE before ON CONFLICT to parse INSERT ... SELECT upserts.
+                let target_cols: Vec<String> = self.on_columns.iter().map(|c| quote(c)).collect();
+                sql.push_str(&format!(
+                    " WHERE 1 = 1 ON CONFLICT ({})",
+                    target_cols.join(", ")
+                ));
+                if sets.is_empty() {
+                    sql.push_str(" DO NOTHING");
+                } else {
+                    sql.push_str(" DO UPDATE SET ");
+                    sql.push_str(&sets.join(", "));
+                }
+            }
+            UpsertSyntax::OnDuplicateKeyUpdate => {
+                sql.push_str(" ON DUPLICATE KEY UPDATE ");
+                if sets.is_empty() {
+                    // No-op assignment: keep the existing row.
+                    let key = quote(&self.on_columns[0]);
+                    sql.push_str(&format!("{key} = {key}"));
+                } else {
+                    sql.push_str(&sets.join(", "));
+                }
+            }
+            UpsertSyntax::Unsupported => {
+                return unsupported("dialects without an upsert statement");
+            }
+        }
+
+        Ok(sql)
+    }
+
+    /// Execute the MERGE and return the number of rows affected.
+    pub async fn execute<C: Connection>(self, cx: &Cx, conn: &C) -> Outcome<u64, Error> {
+        let (sql, params) = match self.build_with_dialect(conn.sql_dialect()) {
+            Ok(built) => built,
+            Err(e) => return Outcome::Err(e),
+        };
+        tracing::debug!(sql = %sql, "Executing MERGE");
+        conn.execute(cx, &sql, &params).await
+    }
+}
+
+impl<M: Model> Default for MergeBuilder<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Rewrites the table qualifier of column references.
+struct RenameQualifier {
+    from: &'static str,
+    to: &'static str,
+}
+
+impl VisitorMut for RenameQualifier {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if let Expr::Column {
+            table: Some(table), ..
+        } = expr
+            && *table == self.from
+        {
+            *table = self.to.to_string();
+        }
+        walk_expr_mut(self, expr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlmodel_core::{FieldInfo, Result, Row};
+
+    #[derive(Debug, Clone)]
+    struct Hero;
+
+    impl Model for Hero {
+        const TABLE_NAME: &'static str = "heroes";
+        const PRIMARY_KEY: &'static [&'static str] = &["id"];
+
+        fn fields() -> &'static [FieldInfo] {
+            &[]
+        }
+
+        fn to_row(&self) -> Vec<(&'static str, Value)> {
+            Vec::new()
+        }
+
+        fn from_row(_row: &Row) -> Result<Self> {
+            Err(Error::Custom("not used in tests".to_string()))
+        }
+
+        fn primary_key_value(&self) -> Vec<Value> {
+            Vec::new()
+        }
+
+        fn is_new(&self) -> bool {
+            true
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct StagedHero;
+
+    impl Model for StagedHero {
+        const TABLE_NAME: &'static str = "staged_heroes";
+        const PRIMARY_KEY: &'static [&'static str] = &["id"];
+
+        fn fields() -> &'static [FieldInfo] {
+            &[]
+        }
+
+        fn to_row(&self) -> Vec<(&'static str, Value)> {
+            Vec::new()
+        }
+
+        fn from_row(_row: &Row) -> Result<Self> {
+            Err(Error::Custom("not used in tests".to_string()))
+        }
+
+        fn primary_key_value(&self) -> Vec<Value> {
+            Vec::new()
+        }
+
+        fn is_new(&self) -> bool {
+            true
+        }
+    }
+
+    fn sync_job() -> MergeBuilder<Hero> {
+        crate::merge!(Hero)
+            .using(Select::<StagedHero>::new().filter(Expr::col("batch").eq(7)))
+            .on(&["id"])
+            .when_matched_update(&["name"])
+            .when_not_matched_insert(&["id", "name"])
+    }
+
+    #[test]
+    fn test_native_merge_postgres() {
+        let (sql, params) = sync_job().build_with_dialect(Dialect::Postgres).unwrap();
+        assert_eq!(
+            sql,
+            "MERGE INTO \"heroes\" AS \"target\" \
+             USING (SELECT * FROM staged_heroes WHERE \"batch\" = $1) AS \"source\" \
+             ON \"target\".\"id\" = \"source\".\"id\" \
+             WHEN MATCHED THEN UPDATE SET \"name\" = \"source\".\"name\" \
+             WHEN NOT MATCHED THEN INSERT (\"id\", \"name\") VALUES (\"source\".\"id\", \"source\".\"name\")"
+        );
+        assert_eq!(params, vec![Value::Int(7)]);
+    }
+
+    #[test]
+    fn test_native_merge_continues_parameter_numbering() {
+        let (sql, params) = sync_job()
+            .on_expr(target("locked").eq(false))
+            .when_matched_set("version", target("version").add(1))
+            .build_with_dialect(Dialect::Postgres)
+            .unwrap();
+        assert!(sql.contains("AND \"target\".\"locked\" = $2"));
+        assert!(sql.contains("\"version\" = \"target\".\"version\" + $3"));
+        assert_eq!(params.len(), 3);
+    }
+
+    #[test]
+    fn test_emulated_merge_sqlite() {
+        let (sql, params) = sync_job().build_with_dialect(Dialect::Sqlite).unwrap();
+        assert_eq!(
+            sql,
+            "INSERT INTO \"heroes\" AS \"target\" (\"id\", \"name\") \
+             SELECT \"source\".\"id\", \"source\".\"name\" \
+             FROM (SELECT * FROM staged_heroes WHERE \"batch\" = ?1) AS \"source\" \
+             WHERE 1 = 1 ON CONFLICT (\"id\") DO UPDATE SET \"name\" = \"excluded\".\"name\""
+        );
+        assert_eq!(params, vec![Value::Int(7)]);
+    }
+
+    #[test]
+    fn test_emulated_merge_mysql() {
+        let (sql, _) = sync_job()
+            .when_matched_set("version", target("version").add(1))
+            .build_with_dialect(Dialect::Mysql)
+            .unwrap();
+        assert!(sql.starts_with("INSERT INTO `heroes` (`id`, `name`) SELECT"));
+        assert!(sql.ends_with(
+            "ON DUPLICATE KEY UPDATE `name` = `source`.`name`, `version` = `heroes`.`version` + ?"
+        ));
+    }
+
+    #[test]
+    fn test_postgres_emulation_and_insert_only() {
+        let (sql, _) = crate::merge!(Hero)
+            .using(Select::<StagedHero>::new())
+            .on(&["id"])
+            .when_not_matched_insert(&["id"])
+            .emulate()
+            .build_with_dialect(Dialect::Postgres)
+            .unwrap();
+        assert!(sql.starts_with("INSERT INTO \"heroes\""));
+        assert!(sql.ends_with("ON CONFLICT (\"id\") DO NOTHING"));
+    }
+
+    #[test]
+    fn test_emulation_rejects_unsupported_shapes() {
+        let delete = sync_job().when_matched_delete();
+        assert!(delete.build_with_dialect(Dialect::Sqlite).is_err());
+        assert!(delete.build_with_dialect(Dialect::Postgres).is_ok());
+
+        let update_only = crate::merge!(Hero)
+            .using(Select::<StagedHero>::new())
+            .on(&["id"])
+            .when_matched_update(&["name"]);
+        assert!(update_only.build_with_dialect(Dialect::Mysql).is_err());
+
+        let no_source = crate::merge!(Hero)
+            .on(&["id"])
+            .when_not_matched_insert(&["id"]);
+        assert!(no_source.build().is_err());
+    }
+}
@@ -0,0 +1,483 @@
+//! Reusable, typed query handles.
+//!
+//! A [`Query`] is a SELECT that is built once and executed many times with
+//! different values. Values are bound through numbered placeholders and
+//! passed as a tuple whose type fixes how many there are and what they are:
+//!
+//! ```ignore
+//! static HERO_BY_EMAIL: Query<Hero, (String,)> =
+//!     query!(select!(Hero).filter(Expr::col("email").eq(Expr::placeholder(1))));
+//!
+//! let hero = HERO_BY_EMAIL.one(&cx, &conn, (email,)).await?;
+//!
+//! // Hot paths prepare the statement once per connection.
+//! let by_email = HERO_BY_EMAIL.prepare(&cx, &conn).await?;
+//! for email in emails {
+//!     let hero = by_email.first(&cx, (email,)).await?;
+//! }
+//! ```
+//!
+//! The SQL for each dialect is rendered on first use, and the query's
+//! placeholders are checked against the parameter tuple at that point: a
+//! handle bound as `(A, B)` must use exactly placeholders 1 and 2. Literal
+//! values written into the query are bound after the tuple's values.
+
+use std::marker::PhantomData;
+use std::sync::OnceLock;
+
+use crate::ast::{QueryAst, Visitor, VisitorMut, walk_expr, walk_expr_mut};
+use crate::expr::{Dialect, Expr};
+use crate::select::Select;
+use asupersync::{Cx, Outcome};
+use sqlmodel_core::dialect::{IntoSqlDialect, SqlDialect};
+use sqlmodel_core::{Connection, Error, Model, PreparedStatement, Row, Value};
+
+/// A tuple of values bound to a [`Query`]'s placeholders, in order.
+pub trait QueryParams {
+    /// Number of values in the tuple.
+    const COUNT: usize;
+
+    /// Convert the tuple into bind values, placeholder 1 first.
+    fn into_values(self) -> Vec<Value>;
+}
+
+impl QueryParams for () {
+    const COUNT: usize = 0;
+
+    fn into_values(self) -> Vec<Value> {
+        Vec::new()
+    }
+}
+
+macro_rules! impl_query_params {
+    ($count:literal; $($ty:ident $idx:tt),+) => {
+        impl<$($ty: Into<Value>),+> QueryParams for ($($ty,)+) {
+            const COUNT: usize = $count;
+
+            fn into_values(self) -> Vec<Value> {
+                vec![$(self.$idx.into()),+]
+            }
+        }
+    };
+}
+
+impl_query_params!(1; A 0);
+impl_query_params!(2; A 0, B 1);
+impl_query_params!(3; A 0, B 1, C 2);
+impl_query_params!(4; A 0, B 1, C 2, D 3);
+impl_query_params!(5; A 0, B 1, C 2, D 3, E 4);
+impl_query_params!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+impl_query_params!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_query_params!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+/// A once-built SELECT executed with typed parameters `P`.
+///
+/// Usually declared as a `static` through [`query!`](crate::query); see the
+/// [module documentation](self).
+pub struct Query<M: Model, P> {
+    build: fn() -> Select<M>,
+    postgres: OnceLock<Result<Plan<M>, String>>,
+    sqlite: OnceLock<Result<Plan<M>, String>>,
+    mysql: OnceLock<Result<Plan<M>, String>>,
+    _params: PhantomData<fn(P)>,
+}
+
+impl<M: Model, P: QueryParams> Query<M, P> {
+    /// Create a handle for the query returned by `build`.
+    ///
+    /// `build` runs at most once per dialect, on first use.
+    pub const fn new(build: fn() -> Select<M>) -> Self {
+        Self {
+            build,
+            postgres: OnceLock::new(),
+            sqlite: OnceLock::new(),
+            mysql: OnceLock::new(),
+            _params: PhantomData,
+        }
+    }
+
+    /// The SQL this handle runs on `dialect`.
+    ///
+    /// Fails if the query's placeholders do not match `P`.
+    #[allow(clippy::result_large_err)]
+    pub fn sql(&self, dialect: impl IntoSqlDialect) -> Result<&str, Error> {
+        let dialect = dialect.into_sql_dialect();
+        self.plan(dialect).map(|plan| plan.sql.as_str())
+    }
+
+    /// The values bound for `params` on `dialect`, in placeholder order.
+    #[allow(clippy::result_large_err)]
+    pub fn bind(&self, dialect: impl IntoSqlDialect, params: P) -> Result<Vec<Value>, Error> {
+        let dialect = dialect.into_sql_dialect();
+        self.plan(dialect).map(|plan| plan.bind(params))
+    }
+
+    /// Execute the query and return all matching rows as models.
+    pub async fn all<C: Connection>(&self, cx: &Cx, conn: &C, params: P) -> Outcome<Vec<M>, Error> {
+        let plan = match self.plan(conn.sql_dialect()) {
+            Ok(plan) => plan,
+            Err(e) => return Outcome::Err(e),
+        };
+        let rows = conn.query(cx, &plan.sql, &plan.bind(params)).await;
+        rows.and_then(|rows| plan.hydrate_all(&rows))
+    }
+
+    /// Execute the query and return the first matching row.
+    pub async fn first<C: Connection>(
+        &self,
+        cx: &Cx,
+        conn: &C,
+        params: P,
+    ) -> Outcome<Option<M>, Error> {
+        let plan = match self.plan(conn.sql_dialect()) {
+            Ok(plan) => plan,
+            Err(e) => return Outcome::Err(e),
+        };
+        let row = conn.query_one(cx, &plan.sql, &plan.bind(params)).await;
+        row.and_then(|row| plan.hydrate_first(row.as_ref()))
+    }
+
+    /// Execute the query and return exactly one row, or error.
+    pub async fn one<C: Connection>(&self, cx: &Cx, conn: &C, params: P) -> Outcome<M, Error> {
+        let plan = match self.plan(conn.sql_dialect()) {
+            Ok(plan) => plan,
+            Err(e) => return Outcome::Err(e),
+        };
+        let rows = conn.query(cx, &plan.sql, &plan.bind(params)).await;
+        rows.and_then(|rows| plan.hydrate_one(&rows))
+    }
+
+    /// Prepare the query on `conn` for repeated execution.
+    pub async fn prepare<'q, 'c, C: Connection>(
+        &'q self,
+        cx: &Cx,
+        conn: &'c C,
+    ) -> Outcome<PreparedQuery<'q, 'c, M, P, C>, Error> {
+        let plan = match self.plan(conn.sql_dialect()) {
+            Ok(plan) => plan,
+            Err(e) => return Outcome::Err(e),
+        };
+        match conn.prepare(cx, &plan.sql).await {
+            Outcome::Ok(stmt) => Outcome::Ok(PreparedQuery {
+                plan,
+                conn,
+                stmt,
+                _params: PhantomData,
+            }),
+            Outcome::Err(e) => Outcome::Err(e),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn plan(&self, dialect: &'static dyn SqlDialect) -> Result<&Plan<M>, Error> {
+        let slot = match dialect.base_dialect() {
+            Dialect::Postgres => &self.postgres,
+            Dialect::Sqlite => &self.sqlite,
+            Dialect::Mysql => &self.mysql,
+        };
+        slot.get_or_init(|| Plan::build((self.build)(), P::COUNT, dialect))
+            .as_ref()
+            .map_err(|message| Error::Custom(message.clone()))
+    }
+}
+
+/// A [`Query`] prepared on one connection.
+///
+/// Created by [`Query::prepare`]; every execution reuses the same prepared
+/// statement.
+pub struct PreparedQuery<'q, 'c, M: Model, P, C: Connection> {
+    plan: &'q Plan<M>,
+    conn: &'c C,
+    stmt: PreparedStatement,
+    _params: PhantomData<fn(P)>,
+}
+
+impl<M: Model, P: QueryParams, C: Connection> PreparedQuery<'_, '_, M, P, C> {
+    /// The underlying prepared statement.
+    pub fn statement(&self) -> &PreparedStatement {
+        &self.stmt
+    }
+
+    /// Execute the statement and return all matching rows as models.
+    pub async fn all(&self, cx: &Cx, params: P) -> Outcome<Vec<M>, Error> {
+        let rows = self.query(cx, params).await;
+        rows.and_then(|rows| self.plan.hydrate_all(&rows))
+    }
+
+    /// Execute the statement and return the first matching row.
+    pub async fn first(&self, cx: &Cx, params: P) -> Outcome<Option<M>, Error> {
+        let rows = self.query(cx, params).await;
+        rows.and_then(|rows| self.plan.hydrate_first(rows.first()))
+    }
+
+    /// Execute the statement and return exactly one row, or error.
+    pub async fn one(&self, cx: &Cx, params: P) -> Outcome<M, Error> {
+        let rows = self.query(cx, params).await;
+        rows.and_then(|rows| self.plan.hydrate_one(&rows))
+    }
+
+    async fn query(&self, cx: &Cx, params: P) -> Outcome<Vec<Row>, Error> {
+        self.conn
+            .query_prepared(cx, &self.stmt, &self.plan.bind(params))
+            .await
+    }
+}
+
+/// A [`Query`] rendered for one dialect.
+struct Plan<M: Model> {
+    /// The built query, kept to hydrate rows.
+    select: Select<M>,
+    sql: String,
+    /// Literal values lifted out of the query, bound after the caller's.
+    fixed: Vec<Value>,
+    /// For `?` placeholders, the value index bound at each position.
+    positional: Option<Vec<usize>>,
+}
+
+impl<M: Model> Plan<M> {
+    fn build(
+        select: Select<M>,
+        count: usize,
+        dialect: &'static dyn SqlDialect,
+    ) -> Result<Self, String> {
+        let QueryAst::Select(mut query) = select.to_ast();
+
+        let mut used = Placeholders::default();
+        used.visit_select(&query);
+        if let Some(index) = used.0.iter().find(|&&index| index == 0 || index > count) {
+            return Err(format!(
+                "query uses placeholder {index} but its parameters bind {count} value(s)"
+            ));
+        }
+        if let Some(missing) = (1..=count).find(|index| !used.0.contains(index)) {
+            return Err(format!(
+                "query parameters bind {count} value(s) but placeholder {missing} is never used"
+            ));
+        }
+
+        let mut lifted = LiftLiterals {
+            next: count,
+            values: Vec::new(),
+        };
+        lifted.visit_select_mut(&mut query);
+        let mut order = Placeholders::default();
+        order.visit_select(&query);
+
+        let (sql, params) = QueryAst::Select(query)
+            .render(dialect)
+            .map_err(|e| e.to_string())?;
+        if !params.is_empty() {
+            return Err(format!(
+                "query binds {} value(s) a handle cannot reorder (LIKE patterns or \
+                 subqueries); compare against placeholders instead",
+                params.len()
+            ));
+        }
+        Ok(Self {
+            select,
+            sql,
+            fixed: lifted.values,
+            positional: (dialect.base_dialect() == Dialect::Mysql)
+                .then(|| order.0.iter().map(|index| index - 1).collect()),
+        })
+    }
+
+    fn bind<P: QueryParams>(&self, params: P) -> Vec<Value> {
+        let mut values = params.into_values();
+        values.extend(self.fixed.iter().cloned());
+        match &self.positional {
+            Some(order) => order.iter().map(|&index| values[index].clone()).collect(),
+            None => values,
+        }
+    }
+
+    fn hydrate_all(&self, rows: &[Row]) -> Outcome<Vec<M>, Error> {
+        let mut models = Vec::with_capacity(rows.len());
+        for row in rows {
+            match self.select.hydrate(row) {
+                Ok(model) => models.push(model),
+                Err(e) => return Outcome::Err(e),
+            }
+        }
+        Outcome::Ok(models)
+    }
+
+    fn hydrate_first(&self, row: Option<&Row>) -> Outcome<Option<M>, Error> {
+        match row {
+            Some(row) => match self.select.hydrate(row) {
+                Ok(model) => Outcome::Ok(Some(model)),
+                Err(e) => Outcome::Err(e),
+            },
+            None => Outcome::Ok(None),
+        }
+    }
+
+    fn hydrate_one(&self, rows: &[Row]) -> Outcome<M, Error> {
+        match rows {
+            [row] => match self.select.hydrate(row) {
+                Ok(model) => Outcome::Ok(model),
+                Err(e) => Outcome::Err(e),
+            },
+            [] => Outcome::Err(Error::Custom("Expected one row, found none".to_string())),
+            _ => Outcome::Err(Error::Custom(format!(
+                "Expected one row, found {}",
+                rows.len()
+            ))),
+        }
+    }
+}
+
+/// Collects placeholder indices in rendering order.
+#[derive(Default)]
+struct Placeholders(Vec<usize>);
+
+impl Visitor for Placeholders {
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let Expr::Placeholder(index) = expr {
+            self.0.push(*index);
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Replaces literal values with placeholders numbered after `next`.
+struct LiftLiterals {
+    next: usize,
+    values: Vec<Value>,
+}
+
+impl VisitorMut for LiftLiterals {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Literal(value) if !matches!(value, Value::Default) => {
+                self.values.push(std::mem::replace(value, Value::Null));
+                self.next += 1;
+                *expr = Expr::Placeholder(self.next);
+            }
+            _ => walk_expr_mut(self, expr),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlmodel_core::{FieldInfo, Result};
+
+    #[derive(Debug, Clone)]
+    struct Event;
+
+    impl Model for Event {
+        const TABLE_NAME: &'static str = "events";
+        const PRIMARY_KEY: &'static [&'static str] = &["id"];
+
+        fn fields() -> &'static [FieldInfo] {
+            &[]
+        }
+
+        fn to_row(&self) -> Vec<(&'static str, Value)> {
+            Vec::new()
+        }
+
+        fn from_row(_row: &Row) -> Result<Self> {
+            Ok(Event)
+        }
+
+        fn primary_key_value(&self) -> Vec<Value> {
+            Vec::new()
+        }
+
+        fn is_new(&self) -> bool {
+            true
+        }
+    }
+
+    static EVENTS_BY_KIND: Query<Event, (String,)> =
+        crate::query!(Select::<Event>::new().filter(Expr::col("kind").eq(Expr::placeholder(1))));
+
+    #[test]
+    fn test_static_query_renders_per_dialect() {
+        assert_eq!(
+            EVENTS_BY_KIND.sql(Dialect::Postgres).unwrap(),
+            "SELECT * FROM events WHERE \"kind\" = $1"
+        );
+        assert_eq!(
+            EVENTS_BY_KIND.sql(Dialect::Mysql).unwrap(),
+            "SELECT * FROM events WHERE `kind` = ?"
+        );
+        assert_eq!(
+            EVENTS_BY_KIND
+                .bind(Dialect::Sqlite, ("login".to_string(),))
+                .unwrap(),
+            vec![Value::Text("login".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_literals_bind_after_parameters() {
+        let query: Query<Event, (&str,)> = Query::new(|| {
+            Select::<Event>::new()
+                .filter(Expr::col("level").gt(3))
+                .filter(Expr::col("kind").eq(Expr::placeholder(1)))
+        });
+        assert_eq!(
+            query.sql(Dialect::Postgres).unwrap(),
+            "SELECT * FROM events WHERE \"level\" > $2 AND \"kind\" = $1"
+        );
+        assert_eq!(
+            query.bind(Dialect::Postgres, ("login",)).unwrap(),
+            vec![Value::Text("login".to_string()), Value::Int(3)]
+        );
+        // MySQL binds `?` by position, so values follow the SQL text.
+        assert_eq!(
+            query.bind(Dialect::Mysql, ("login",)).unwrap(),
+            vec![Value::Int(3), Value::Text("login".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_reused_placeholder_binds_twice_on_mysql() {
+        let query: Query<Event, (i64, &str)> = Query::new(|| {
+            Select::<Event>::new().filter(
+                Expr::col("kind")
+                    .eq(Expr::placeholder(2))
+                    .and(Expr::col("id").gt(Expr::placeholder(1)))
+                    .or(Expr::col("parent_id").eq(Expr::placeholder(1))),
+            )
+        });
+        assert_eq!(
+            query.bind(Dialect::Mysql, (7, "login")).unwrap(),
+            vec![
+                Value::Text("login".to_string()),
+                Value::BigInt(7),
+                Value::BigInt(7),
+            ]
+        );
+        assert_eq!(
+            query.bind(Dialect::Postgres, (7, "login")).unwrap(),
+            vec![Value::BigInt(7), Value::Text("login".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_placeholder_arity_mismatch_is_an_error() {
+        let unused: Query<Event, (i64, i64)> =
+            Query::new(|| Select::<Event>::new().filter(Expr::col("id").eq(Expr::placeholder(1))));
+        let err = unused.sql(Dialect::Postgres).unwrap_err();
+        assert!(err.to_string().contains("placeholder 2 is never used"));
+
+        let out_of_range: Query<Event, (i64,)> =
+            Query::new(|| Select::<Event>::new().filter(Expr::col("id").eq(Expr::placeholder(3))));
+        let err = out_of_range.sql(Dialect::Sqlite).unwrap_err();
+        assert!(err.to_string().contains("uses placeholder 3"));
+    }
+
+    #[test]
+    fn test_like_pattern_is_rejected() {
+        let query: Query<Event, ()> =
+            Query::new(|| Select::<Event>::new().filter(Expr::col("kind").like("log%")));
+        assert!(query.sql(Dialect::Postgres).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 //! SELECT query builder.
 
+use crate::ast::QueryAst;
 use crate::clause::{Limit, Offset, OrderBy, Where};
 use crate::eager::{
     EagerLoader, IncludePath, build_aliased_column_parts, build_join_clause, find_relationship,
@@ -8,7 +9,11 @@ use crate::expr::{Dialect, Expr};
 use crate::join::Join;
 use crate::subquery::SelectQuery;
 use asupersync::{Cx, Outcome};
-use sqlmodel_core::{Connection, Model, RelationshipKind, Value};
+use sqlmodel_core::dialect::{IntoSqlDialect, SqlDialect};
+use sqlmodel_core::row::FromValue;
+use sqlmodel_core::{Connection, Model, QueryOptions, RelationshipKind, Row, Value};
+use std::collections::HashMap;
+use std::hash::Hash;
 use std::marker::PhantomData;
 
 type ParentFieldsFn = fn() -> &'static [sqlmodel_core::FieldInfo];
@@ -60,6 +65,31 @@ fn joined_inheritance_select_columns<M: Model>() -> Option<Vec<String>> {
     Some(parts)
 }
 
+/// The FROM table for `M`, qualified with `schema` if one is set.
+fn from_table<M: Model>(schema: Option<&str>) -> String {
+    match schema {
+        Some(schema) => format!("{schema}.{}", M::TABLE_NAME),
+        None => M::TABLE_NAME.to_string(),
+    }
+}
+
+/// Set the FROM table and add the implicit inheritance filter/join for `M`.
+fn apply_model_defaults<M: Model>(query: &mut SelectQuery, schema: Option<&str>) {
+    query.table = from_table::<M>(schema);
+
+    // Single-table inheritance child models should be implicitly filtered by their discriminator.
+    if let Some(expr) = sti_discriminator_filter::<M>() {
+        query.where_clause = Some(match query.where_clause.take() {
+            Some(existing) => existing.and(expr),
+            None => Where::new(expr),
+        });
+    }
+
+    if let Some(join) = joined_inheritance_join::<M>() {
+        query.joins.insert(0, join);
+    }
+}
+
 /// Information about a JOIN for eager loading.
 ///
 /// Used internally to track which relationships are being eagerly loaded
@@ -105,17 +135,39 @@ pub struct Select<M: Model> {
     for_update: bool,
     /// Eager loading configuration
     eager_loader: Option<EagerLoader<M>>,
+    /// Columns requested via `only()` (None = full hydration)
+    partial: Option<Vec<String>>,
+    /// Whether `order_by` still holds the model's default ordering
+    default_order: bool,
+    /// Schema (or attached database) qualifying the FROM table
+    schema: Option<String>,
+    /// Per-statement timeout and tag
+    options: QueryOptions,
     /// Model type marker
     _marker: PhantomData<M>,
 }
 
 impl<M: Model> Select<M> {
     /// Create a new SELECT query for the model's table.
+    ///
+    /// The query starts out ordered by the model's
+    /// `#[sqlmodel(order_by = "...")]`, if any; the first call to
+    /// [`order_by`](Self::order_by) replaces that ordering.
     pub fn new() -> Self {
+        let order_by = M::DEFAULT_ORDER_BY
+            .iter()
+            .map(|o| {
+                if o.descending {
+                    OrderBy::desc(Expr::col(o.column))
+                } else {
+                    OrderBy::asc(Expr::col(o.column))
+                }
+            })
+            .collect();
         Self {
             columns: Vec::new(),
             where_clause: None,
-            order_by: Vec::new(),
+            order_by,
             joins: Vec::new(),
             limit: None,
             offset: None,
@@ -124,6 +176,10 @@ impl<M: Model> Select<M> {
             distinct: false,
             for_update: false,
             eager_loader: None,
+            partial: None,
+            default_order: true,
+            schema: None,
+            options: QueryOptions::default(),
             _marker: PhantomData,
         }
     }
@@ -134,6 +190,60 @@ impl<M: Model> Select<M> {
         self
     }
 
+    /// Load only the given model columns (partial hydration).
+    ///
+    /// Primary key columns are always selected so the result can be tracked by
+    /// identity. Columns that are not loaded are filled with placeholder values
+    /// when hydrating `M`; a session executing this query marks them expired.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let heroes = select!(Hero).only(&["id", "name"]).all(cx, &conn).await?;
+    /// ```
+    #[must_use]
+    pub fn only(mut self, cols: &[&str]) -> Self {
+        let mut selected: Vec<String> = M::PRIMARY_KEY.iter().map(|&c| c.to_string()).collect();
+        for &col in cols {
+            if !selected.iter().any(|s| s == col) {
+                selected.push(col.to_string());
+            }
+        }
+        self.columns.clone_from(&selected);
+        self.partial = Some(selected);
+        self
+    }
+
+    /// Whether this query hydrates only a subset of the model's columns.
+    pub fn is_partial(&self) -> bool {
+        self.partial.is_some()
+    }
+
+    /// Model columns that this query does not load.
+    ///
+    /// These are the deferred columns, plus any columns left out by
+    /// [`only`](Self::only).
+    pub fn unloaded_columns(&self) -> Vec<&'static str> {
+        match &self.partial {
+            Some(selected) => M::fields()
+                .iter()
+                .filter(|f| !f.computed && !selected.iter().any(|s| s == f.column_name))
+                .map(|f| f.column_name)
+                .collect(),
+            None => M::deferred_columns(),
+        }
+    }
+
+    /// Convert a result row into a model, filling deferred or unselected columns.
+    #[allow(clippy::result_large_err)]
+    pub fn hydrate(&self, row: &Row) -> Result<M, sqlmodel_core::Error> {
+        if self.partial.is_some() || M::fields().iter().any(|f| f.deferred) {
+            M::from_row(&row.fill_missing(M::fields()))
+        } else {
+            M::from_row(row)
+        }
+    }
+
     /// Add a WHERE condition.
     pub fn filter(mut self, expr: Expr) -> Self {
         self.where_clause = Some(match self.where_clause {
@@ -153,17 +263,48 @@ impl<M: Model> Select<M> {
     }
 
     /// Add ORDER BY clause.
+    ///
+    /// The first call drops the model's default ordering.
     pub fn order_by(mut self, order: OrderBy) -> Self {
+        if std::mem::take(&mut self.default_order) {
+            self.order_by.clear();
+        }
         self.order_by.push(order);
         self
     }
 
+    /// Drop all ordering, including the model's default ordering.
+    pub fn unordered(mut self) -> Self {
+        self.order_by.clear();
+        self.default_order = false;
+        self
+    }
+
+    /// Read the model's table from `schema` instead of the default one.
+    ///
+    /// On SQLite `schema` is the name of an attached database, so a query
+    /// can target e.g. one month of a file-per-month layout. Only the FROM
+    /// table is qualified; qualified column references keep using the bare
+    /// table name, which SQL resolves against the qualified table.
+    pub fn from_schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = Some(schema.into());
+        self
+    }
+
     /// Add a JOIN clause.
     pub fn join(mut self, join: Join) -> Self {
         self.joins.push(join);
         self
     }
 
+    /// Names of the tables joined so far, excluding subquery joins.
+    pub fn joined_tables(&self) -> impl Iterator<Item = &str> {
+        self.joins
+            .iter()
+            .filter(|join| !join.is_subquery)
+            .map(|join| join.table.as_str())
+    }
+
     /// Set LIMIT.
     pub fn limit(mut self, n: u64) -> Self {
         self.limit = Some(Limit(n));
@@ -203,6 +344,19 @@ impl<M: Model> Select<M> {
         self
     }
 
+    /// Run the query with per-statement [`QueryOptions`], e.g. a timeout
+    /// that bounds just this query.
+    #[must_use]
+    pub fn options(mut self, options: QueryOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// The options the query runs with.
+    pub fn query_options(&self) -> &QueryOptions {
+        &self.options
+    }
+
     /// Configure eager loading for relationships.
     ///
     /// # Example
@@ -296,7 +450,7 @@ impl<M: Model> Select<M> {
     #[tracing::instrument(level = "trace", skip(self))]
     fn build_eager_with_dialect(
         &self,
-        dialect: Dialect,
+        dialect: &'static dyn SqlDialect,
     ) -> (String, Vec<Value>, Vec<EagerJoinInfo>) {
         let mut sql = String::new();
         let mut params = Vec::new();
@@ -370,7 +524,7 @@ impl<M: Model> Select<M> {
 
         // FROM
         sql.push_str(" FROM ");
-        sql.push_str(M::TABLE_NAME);
+        sql.push_str(&from_table::<M>(self.schema.as_deref()));
 
         // Add JOINs for eager loading
         if let Some(loader) = &self.eager_loader {
@@ -422,15 +576,10 @@ impl<M: Model> Select<M> {
             sql.push_str(&order_strs.join(", "));
         }
 
-        // LIMIT
-        if let Some(Limit(n)) = self.limit {
-            sql.push_str(&format!(" LIMIT {}", n));
-        }
-
-        // OFFSET
-        if let Some(Offset(n)) = self.offset {
-            sql.push_str(&format!(" OFFSET {}", n));
-        }
+        // LIMIT / OFFSET
+        sql.push_str(
+            &dialect.limit_offset(self.limit.map(|Limit(n)| n), self.offset.map(|Offset(n)| n)),
+        );
 
         (sql, params, join_info)
     }
@@ -470,7 +619,7 @@ impl<M: Model> Select<M> {
             return self.all(cx, conn).await;
         }
 
-        let (sql, params, join_info) = self.build_eager_with_dialect(conn.dialect());
+        let (sql, params, join_info) = self.build_eager_with_dialect(conn.sql_dialect());
 
         tracing::debug!(
             table = M::TABLE_NAME,
@@ -479,7 +628,7 @@ impl<M: Model> Select<M> {
         );
         tracing::trace!(sql = %sql, "Eager SQL");
 
-        let rows = conn.query(cx, &sql, &params).await;
+        let rows = conn.query_with(cx, &sql, &params, &self.options).await;
 
         rows.and_then(|rows| {
             tracing::debug!(row_count = rows.len(), "Processing eager query results");
@@ -516,13 +665,7 @@ impl<M: Model> Select<M> {
                     Ok(model) => {
                         // Deduplicate by primary key
                         let pk = model.primary_key_value();
-                        let pk_hash = {
-                            use std::hash::{Hash, Hasher};
-                            let mut hasher = std::collections::hash_map::DefaultHasher::new();
-                            // Hash the debug representation as a simple PK identifier
-                            format!("{:?}", pk).hash(&mut hasher);
-                            hasher.finish()
-                        };
+                        let pk_hash = Value::stable_hash_slice(&pk);
 
                         if seen_pks.insert(pk_hash) {
                             models.push(model);
@@ -549,96 +692,57 @@ impl<M: Model> Select<M> {
     }
 
     /// Build the SQL query and parameters with a specific dialect.
-    pub fn build_with_dialect(&self, dialect: Dialect) -> (String, Vec<Value>) {
-        let mut sql = String::new();
-        let mut params = Vec::new();
-        let mut where_clause = self.where_clause.clone();
-        let mut joins = self.joins.clone();
-
-        // Single-table inheritance child models should be implicitly filtered by their discriminator.
-        if let Some(expr) = sti_discriminator_filter::<M>() {
-            where_clause = Some(match where_clause {
-                Some(existing) => existing.and(expr),
-                None => Where::new(expr),
-            });
-        }
-
-        if let Some(join) = joined_inheritance_join::<M>() {
-            joins.insert(0, join);
-        }
+    ///
+    /// Clauses the dialect rejects (`FOR UPDATE` on SQLite) are kept as written;
+    /// see [`try_build_with_dialect`](Self::try_build_with_dialect).
+    pub fn build_with_dialect(&self, dialect: impl IntoSqlDialect) -> (String, Vec<Value>) {
+        let dialect = dialect.into_sql_dialect();
+        self.to_ast().render_lenient(dialect)
+    }
 
-        // SELECT
-        sql.push_str("SELECT ");
-        if self.distinct {
-            sql.push_str("DISTINCT ");
-        }
+    /// Build the SQL query and parameters, failing on clauses the dialect rejects.
+    ///
+    /// The executing methods ([`all`](Self::all), [`first`](Self::first), ...)
+    /// build through this, so e.g. `for_update()` on SQLite is an error.
+    #[allow(clippy::result_large_err)]
+    pub fn try_build_with_dialect(
+        &self,
+        dialect: impl IntoSqlDialect,
+    ) -> Result<(String, Vec<Value>), sqlmodel_core::Error> {
+        let dialect = dialect.into_sql_dialect();
+        self.to_ast().render(dialect)
+    }
 
+    /// Lower this query into its public AST form.
+    ///
+    /// The AST already includes implicit inheritance filters/joins, so it can be
+    /// inspected or rewritten (see [`crate::ast::VisitorMut`]) and then rendered
+    /// with any [`crate::ast::SqlRenderer`].
+    pub fn to_ast(&self) -> QueryAst {
+        let mut query = SelectQuery {
+            table: String::new(),
+            columns: self.columns.clone(),
+            where_clause: self.where_clause.clone(),
+            order_by: self.order_by.clone(),
+            joins: self.joins.clone(),
+            limit: self.limit,
+            offset: self.offset,
+            group_by: self.group_by.clone(),
+            having: self.having.clone(),
+            distinct: self.distinct,
+            for_update: self.for_update,
+        };
+        apply_model_defaults::<M>(&mut query, self.schema.as_deref());
         if let Some(cols) = joined_inheritance_select_columns::<M>() {
-            sql.push_str(&cols.join(", "));
-        } else if self.columns.is_empty() {
-            sql.push('*');
-        } else {
-            sql.push_str(&self.columns.join(", "));
-        }
-
-        // FROM
-        sql.push_str(" FROM ");
-        sql.push_str(M::TABLE_NAME);
-
-        // JOINs
-        for join in &joins {
-            sql.push_str(&join.build_with_dialect(dialect, &mut params, 0));
-        }
-
-        // WHERE
-        if let Some(where_clause) = &where_clause {
-            let (where_sql, where_params) = where_clause.build_with_dialect(dialect, params.len());
-            sql.push_str(" WHERE ");
-            sql.push_str(&where_sql);
-            params.extend(where_params);
-        }
-
-        // GROUP BY
-        if !self.group_by.is_empty() {
-            sql.push_str(" GROUP BY ");
-            sql.push_str(&self.group_by.join(", "));
-        }
-
-        // HAVING
-        if let Some(having) = &self.having {
-            let (having_sql, having_params) = having.build_with_dialect(dialect, params.len());
-            sql.push_str(" HAVING ");
-            sql.push_str(&having_sql);
-            params.extend(having_params);
-        }
-
-        // ORDER BY
-        if !self.order_by.is_empty() {
-            sql.push_str(" ORDER BY ");
-            let order_strs: Vec<_> = self
-                .order_by
-                .iter()
-                .map(|o| o.build(dialect, &mut params, 0))
+            query.columns = cols;
+        } else if query.columns.is_empty() && M::fields().iter().any(|f| f.deferred) {
+            // Deferred columns are skipped, so the projection must be explicit.
+            query.columns = M::select_columns()
+                .into_iter()
+                .map(str::to_string)
                 .collect();
-            sql.push_str(&order_strs.join(", "));
         }
-
-        // LIMIT
-        if let Some(Limit(n)) = self.limit {
-            sql.push_str(&format!(" LIMIT {}", n));
-        }
-
-        // OFFSET
-        if let Some(Offset(n)) = self.offset {
-            sql.push_str(&format!(" OFFSET {}", n));
-        }
-
-        // FOR UPDATE
-        if self.for_update {
-            sql.push_str(" FOR UPDATE");
-        }
-
-        (sql, params)
+        QueryAst::Select(query)
     }
 
     /// Convert this SELECT query to an EXISTS expression.
@@ -669,7 +773,8 @@ impl<M: Model> Select<M> {
     /// Convert this SELECT query to an EXISTS expression using a specific dialect.
     ///
     /// Use this when embedding the EXISTS in a query for a non-default dialect.
-    pub fn into_exists_with_dialect(self, dialect: Dialect) -> Expr {
+    pub fn into_exists_with_dialect(self, dialect: impl IntoSqlDialect) -> Expr {
+        let dialect = dialect.into_sql_dialect();
         let (sql, params) = self.build_exists_subquery_with_dialect(dialect);
         Expr::exists(sql, params)
     }
@@ -699,7 +804,8 @@ impl<M: Model> Select<M> {
     }
 
     /// Convert this SELECT query to a NOT EXISTS expression using a specific dialect.
-    pub fn into_not_exists_with_dialect(self, dialect: Dialect) -> Expr {
+    pub fn into_not_exists_with_dialect(self, dialect: impl IntoSqlDialect) -> Expr {
+        let dialect = dialect.into_sql_dialect();
         let (sql, params) = self.build_exists_subquery_with_dialect(dialect);
         Expr::not_exists(sql, params)
     }
@@ -744,8 +850,9 @@ impl<M: Model> Select<M> {
         alias: impl Into<String>,
         join_type: crate::JoinType,
         on: Expr,
-        dialect: Dialect,
+        dialect: impl IntoSqlDialect,
     ) -> crate::Join {
+        let dialect = dialect.into_sql_dialect();
         let (sql, params) = self.into_query().build_with_dialect(dialect);
         crate::Join::lateral(join_type, sql, alias, on, params)
     }
@@ -764,24 +871,15 @@ impl<M: Model> Select<M> {
             distinct,
             for_update,
             eager_loader: _,
+            partial: _,
+            default_order: _,
+            schema,
+            options: _,
             _marker: _,
         } = self;
 
-        let mut where_clause = where_clause;
-        if let Some(expr) = sti_discriminator_filter::<M>() {
-            where_clause = Some(match where_clause {
-                Some(existing) => existing.and(expr),
-                None => Where::new(expr),
-            });
-        }
-
-        let mut joins = joins;
-        if let Some(join) = joined_inheritance_join::<M>() {
-            joins.insert(0, join);
-        }
-
-        SelectQuery {
-            table: M::TABLE_NAME.to_string(),
+        let mut query = SelectQuery {
+            table: String::new(),
             columns,
             where_clause,
             order_by,
@@ -792,10 +890,15 @@ impl<M: Model> Select<M> {
             having,
             distinct,
             for_update,
-        }
+        };
+        apply_model_defaults::<M>(&mut query, schema.as_deref());
+        query
     }
 
-    fn build_exists_subquery_with_dialect(&self, dialect: Dialect) -> (String, Vec<Value>) {
+    fn build_exists_subquery_with_dialect(
+        &self,
+        dialect: &'static dyn SqlDialect,
+    ) -> (String, Vec<Value>) {
         let mut sql = String::new();
         let mut params = Vec::new();
         let mut where_clause = self.where_clause.clone();
@@ -814,7 +917,7 @@ impl<M: Model> Select<M> {
 
         // SELECT 1 for optimal EXISTS performance
         sql.push_str("SELECT 1 FROM ");
-        sql.push_str(M::TABLE_NAME);
+        sql.push_str(&from_table::<M>(self.schema.as_deref()));
 
         // JOINs (if any)
         for join in &joins {
@@ -854,13 +957,16 @@ impl<M: Model> Select<M> {
         cx: &Cx,
         conn: &C,
     ) -> Outcome<Vec<M>, sqlmodel_core::Error> {
-        let (sql, params) = self.build_with_dialect(conn.dialect());
-        let rows = conn.query(cx, &sql, &params).await;
+        let (sql, params) = match self.try_build_with_dialect(conn.sql_dialect()) {
+            Ok(built) => built,
+            Err(e) => return Outcome::Err(e),
+        };
+        let rows = conn.query_with(cx, &sql, &params, &self.options).await;
 
         rows.and_then(|rows| {
             let mut models = Vec::with_capacity(rows.len());
             for row in &rows {
-                match M::from_row(row) {
+                match self.hydrate(row) {
                     Ok(model) => models.push(model),
                     Err(e) => return Outcome::Err(e),
                 }
@@ -869,6 +975,84 @@ impl<M: Model> Select<M> {
         })
     }
 
+    /// Execute the query and group the models by `key`.
+    ///
+    /// Each row is decoded straight into its group, so callers do not walk
+    /// the result set a second time. Models within a group keep query order.
+    pub async fn all_grouped_by<C, K, F>(
+        self,
+        cx: &Cx,
+        conn: &C,
+        key: F,
+    ) -> Outcome<HashMap<K, Vec<M>>, sqlmodel_core::Error>
+    where
+        C: Connection,
+        K: Eq + Hash,
+        F: Fn(&M) -> K,
+    {
+        let (sql, params) = match self.try_build_with_dialect(conn.sql_dialect()) {
+            Ok(built) => built,
+            Err(e) => return Outcome::Err(e),
+        };
+        let rows = conn.query_with(cx, &sql, &params, &self.options).await;
+
+        rows.and_then(|rows| {
+            let mut groups: HashMap<K, Vec<M>> = HashMap::new();
+            for row in &rows {
+                match self.hydrate(row) {
+                    Ok(model) => groups.entry(key(&model)).or_default().push(model),
+                    Err(e) => return Outcome::Err(e),
+                }
+            }
+            Outcome::Ok(groups)
+        })
+    }
+
+    /// Execute the query and index the models by primary key.
+    ///
+    /// The model must have a single-column primary key decodable as `K`;
+    /// group composite keys with [`all_grouped_by`](Self::all_grouped_by).
+    pub async fn all_indexed_by_pk<K, C>(
+        self,
+        cx: &Cx,
+        conn: &C,
+    ) -> Outcome<HashMap<K, M>, sqlmodel_core::Error>
+    where
+        K: FromValue + Eq + Hash,
+        C: Connection,
+    {
+        if M::PRIMARY_KEY.len() != 1 {
+            return Outcome::Err(sqlmodel_core::Error::Custom(format!(
+                "all_indexed_by_pk needs a single-column primary key, but {} has {}",
+                M::TABLE_NAME,
+                M::PRIMARY_KEY.len()
+            )));
+        }
+        let (sql, params) = match self.try_build_with_dialect(conn.sql_dialect()) {
+            Ok(built) => built,
+            Err(e) => return Outcome::Err(e),
+        };
+        let rows = conn.query_with(cx, &sql, &params, &self.options).await;
+
+        rows.and_then(|rows| {
+            let mut models = HashMap::with_capacity(rows.len());
+            for row in &rows {
+                let model = match self.hydrate(row) {
+                    Ok(model) => model,
+                    Err(e) => return Outcome::Err(e),
+                };
+                let pk = model.primary_key_value();
+                match K::from_value(pk.first().unwrap_or(&Value::Null)) {
+                    Ok(key) => {
+                        models.insert(key, model);
+                    }
+                    Err(e) => return Outcome::Err(e),
+                }
+            }
+            Outcome::Ok(models)
+        })
+    }
+
     /// Execute the query and return the first matching row.
     pub async fn first<C: Connection>(
         self,
@@ -876,11 +1060,14 @@ impl<M: Model> Select<M> {
         conn: &C,
     ) -> Outcome<Option<M>, sqlmodel_core::Error> {
         let query = self.limit(1);
-        let (sql, params) = query.build_with_dialect(conn.dialect());
-        let row = conn.query_one(cx, &sql, &params).await;
-
-        row.and_then(|opt_row| match opt_row {
-            Some(row) => match M::from_row(&row) {
+        let (sql, params) = match query.try_build_with_dialect(conn.sql_dialect()) {
+            Ok(built) => built,
+            Err(e) => return Outcome::Err(e),
+        };
+        let rows = conn.query_with(cx, &sql, &params, &query.options).await;
+
+        rows.and_then(|rows| match rows.into_iter().next() {
+            Some(row) => match query.hydrate(&row) {
                 Ok(model) => Outcome::Ok(Some(model)),
                 Err(e) => Outcome::Err(e),
             },
@@ -911,12 +1098,15 @@ impl<M: Model> Select<M> {
         // scanning the full result set.
         let mut query = self;
         query.limit = Some(Limit(2));
-        let (sql, params) = query.build_with_dialect(conn.dialect());
-        let rows = conn.query(cx, &sql, &params).await;
+        let (sql, params) = match query.try_build_with_dialect(conn.sql_dialect()) {
+            Ok(built) => built,
+            Err(e) => return Outcome::Err(e),
+        };
+        let rows = conn.query_with(cx, &sql, &params, &query.options).await;
 
         rows.and_then(|rows| match rows.len() {
             0 => Outcome::Ok(None),
-            1 => match M::from_row(&rows[0]) {
+            1 => match query.hydrate(&rows[0]) {
                 Ok(model) => Outcome::Ok(Some(model)),
                 Err(e) => Outcome::Err(e),
             },
@@ -926,22 +1116,47 @@ impl<M: Model> Select<M> {
         })
     }
 
+    /// Build the `SELECT COUNT(*)` form of this query.
+    ///
+    /// ORDER BY, LIMIT and OFFSET are dropped. DISTINCT and GROUP BY queries are
+    /// counted through a derived table so the result is the number of rows the
+    /// original query would return.
+    pub fn build_count_with_dialect(&self, dialect: impl IntoSqlDialect) -> (String, Vec<Value>) {
+        let dialect = dialect.into_sql_dialect();
+        let QueryAst::Select(mut query) = self.to_ast();
+        query.order_by.clear();
+        query.limit = None;
+        query.offset = None;
+
+        if query.distinct || !query.group_by.is_empty() {
+            let (inner, params) = QueryAst::Select(query).render_lenient(dialect);
+            return (
+                format!("SELECT COUNT(*) as count FROM ({inner}) AS _count"),
+                params,
+            );
+        }
+
+        query.columns = vec!["COUNT(*) as count".to_string()];
+        QueryAst::Select(query).render_lenient(dialect)
+    }
+
+    /// Build the `SELECT EXISTS(SELECT 1 ...)` form of this query.
+    pub fn build_exists_with_dialect(&self, dialect: impl IntoSqlDialect) -> (String, Vec<Value>) {
+        let dialect = dialect.into_sql_dialect();
+        let (subquery, params) = self.build_exists_subquery_with_dialect(dialect);
+        (format!("SELECT EXISTS({subquery})"), params)
+    }
+
     /// Execute the query and return the count of matching rows.
     pub async fn count<C: Connection>(
         self,
         cx: &Cx,
         conn: &C,
     ) -> Outcome<u64, sqlmodel_core::Error> {
-        let mut count_query = self;
-        count_query.columns = vec!["COUNT(*) as count".to_string()];
-        count_query.order_by.clear();
-        count_query.limit = None;
-        count_query.offset = None;
+        let (sql, params) = self.build_count_with_dialect(conn.sql_dialect());
+        let rows = conn.query_with(cx, &sql, &params, &self.options).await;
 
-        let (sql, params) = count_query.build_with_dialect(conn.dialect());
-        let row = conn.query_one(cx, &sql, &params).await;
-
-        row.and_then(|opt_row| match opt_row {
+        rows.and_then(|rows| match rows.into_iter().next() {
             Some(row) => match row.get_named::<i64>("count") {
                 Ok(count) => Outcome::Ok(count as u64),
                 Err(e) => Outcome::Err(e),
@@ -951,13 +1166,24 @@ impl<M: Model> Select<M> {
     }
 
     /// Check if any rows match the query.
+    ///
+    /// Compiles to `SELECT EXISTS(SELECT 1 ...)`, so the database can stop at
+    /// the first match and no rows are hydrated.
     pub async fn exists<C: Connection>(
         self,
         cx: &Cx,
         conn: &C,
     ) -> Outcome<bool, sqlmodel_core::Error> {
-        let count = self.count(cx, conn).await;
-        count.map(|n| n > 0)
+        let (sql, params) = self.build_exists_with_dialect(conn.sql_dialect());
+        let rows = conn.query_with(cx, &sql, &params, &self.options).await;
+
+        rows.and_then(|rows| match rows.into_iter().next() {
+            Some(row) => match row.get_as::<bool>(0) {
+                Ok(found) => Outcome::Ok(found),
+                Err(e) => Outcome::Err(e),
+            },
+            None => Outcome::Ok(false),
+        })
     }
 }
 
@@ -1068,7 +1294,8 @@ impl<Base: Model, Child: Model> PolymorphicJoinedSelect<Base, Child> {
     }
 
     /// Build the SQL query and parameters.
-    pub fn build_with_dialect(&self, dialect: Dialect) -> (String, Vec<Value>) {
+    pub fn build_with_dialect(&self, dialect: impl IntoSqlDialect) -> (String, Vec<Value>) {
+        let dialect = dialect.into_sql_dialect();
         self.select.build_with_dialect(dialect)
     }
 
@@ -1112,7 +1339,10 @@ impl<Base: Model, Child: Model> PolymorphicJoinedSelect<Base, Child> {
             )));
         }
 
-        let (sql, params) = self.select.build_with_dialect(conn.dialect());
+        let (sql, params) = match self.select.try_build_with_dialect(conn.sql_dialect()) {
+            Ok(built) => built,
+            Err(e) => return Outcome::Err(e),
+        };
         tracing::debug!(
             sql = %sql,
             base = Base::TABLE_NAME,
@@ -1120,7 +1350,9 @@ impl<Base: Model, Child: Model> PolymorphicJoinedSelect<Base, Child> {
             "Executing polymorphic joined SELECT"
         );
 
-        let rows = conn.query(cx, &sql, &params).await;
+        let rows = conn
+            .query_with(cx, &sql, &params, &self.select.options)
+            .await;
         rows.and_then(|rows| {
             let mut out = Vec::with_capacity(rows.len());
             for row in rows {
@@ -1188,7 +1420,8 @@ impl<Base: Model, C1: Model, C2: Model> PolymorphicJoinedSelect2<Base, C1, C2> {
     }
 
     /// Build the SQL query and parameters.
-    pub fn build_with_dialect(&self, dialect: Dialect) -> (String, Vec<Value>) {
+    pub fn build_with_dialect(&self, dialect: impl IntoSqlDialect) -> (String, Vec<Value>) {
+        let dialect = dialect.into_sql_dialect();
         self.select.build_with_dialect(dialect)
     }
 
@@ -1235,7 +1468,10 @@ impl<Base: Model, C1: Model, C2: Model> PolymorphicJoinedSelect2<Base, C1, C2> {
             )));
         }
 
-        let (sql, params) = self.select.build_with_dialect(conn.dialect());
+        let (sql, params) = match self.select.try_build_with_dialect(conn.sql_dialect()) {
+            Ok(built) => built,
+            Err(e) => return Outcome::Err(e),
+        };
         tracing::debug!(
             sql = %sql,
             base = Base::TABLE_NAME,
@@ -1244,7 +1480,9 @@ impl<Base: Model, C1: Model, C2: Model> PolymorphicJoinedSelect2<Base, C1, C2> {
             "Executing polymorphic joined2 SELECT"
         );
 
-        let rows = conn.query(cx, &sql, &params).await;
+        let rows = conn
+            .query_with(cx, &sql, &params, &self.select.options)
+            .await;
         rows.and_then(|rows| {
             let mut out = Vec::with_capacity(rows.len());
             for row in rows {
@@ -1328,7 +1566,8 @@ impl<Base: Model, C1: Model, C2: Model, C3: Model> PolymorphicJoinedSelect3<Base
     }
 
     /// Build the SQL query and parameters.
-    pub fn build_with_dialect(&self, dialect: Dialect) -> (String, Vec<Value>) {
+    pub fn build_with_dialect(&self, dialect: impl IntoSqlDialect) -> (String, Vec<Value>) {
+        let dialect = dialect.into_sql_dialect();
         self.select.build_with_dialect(dialect)
     }
 
@@ -1376,7 +1615,10 @@ impl<Base: Model, C1: Model, C2: Model, C3: Model> PolymorphicJoinedSelect3<Base
             )));
         }
 
-        let (sql, params) = self.select.build_with_dialect(conn.dialect());
+        let (sql, params) = match self.select.try_build_with_dialect(conn.sql_dialect()) {
+            Ok(built) => built,
+            Err(e) => return Outcome::Err(e),
+        };
         tracing::debug!(
             sql = %sql,
             base = Base::TABLE_NAME,
@@ -1386,7 +1628,9 @@ impl<Base: Model, C1: Model, C2: Model, C3: Model> PolymorphicJoinedSelect3<Base
             "Executing polymorphic joined3 SELECT"
         );
 
-        let rows = conn.query(cx, &sql, &params).await;
+        let rows = conn
+            .query_with(cx, &sql, &params, &self.select.options)
+            .await;
         rows.and_then(|rows| {
             let mut out = Vec::with_capacity(rows.len());
             for row in rows {
@@ -1443,7 +1687,7 @@ mod tests {
     use super::*;
     use crate::JoinType;
     use sqlmodel_core::{
-        Error, FieldInfo, InheritanceInfo, InheritanceStrategy, Result, Row, Value,
+        Error, FieldInfo, InheritanceInfo, InheritanceStrategy, OrderByColumn, Result, Row, Value,
     };
 
     #[derive(Debug, Clone)]
@@ -1474,6 +1718,44 @@ mod tests {
         }
     }
 
+    #[derive(Debug, Clone)]
+    struct Post;
+
+    impl Model for Post {
+        const TABLE_NAME: &'static str = "posts";
+        const PRIMARY_KEY: &'static [&'static str] = &["id"];
+        const DEFAULT_ORDER_BY: &'static [OrderByColumn] = &[
+            OrderByColumn {
+                column: "created_at",
+                descending: true,
+            },
+            OrderByColumn {
+                column: "id",
+                descending: false,
+            },
+        ];
+
+        fn fields() -> &'static [FieldInfo] {
+            &[]
+        }
+
+        fn to_row(&self) -> Vec<(&'static str, Value)> {
+            Vec::new()
+        }
+
+        fn from_row(_row: &Row) -> Result<Self> {
+            Err(Error::Custom("not used in tests".to_string()))
+        }
+
+        fn primary_key_value(&self) -> Vec<Value> {
+            Vec::new()
+        }
+
+        fn is_new(&self) -> bool {
+            true
+        }
+    }
+
     #[derive(Debug, Clone)]
     struct StiManager;
 
@@ -1669,6 +1951,53 @@ mod tests {
         assert!(params.is_empty());
     }
 
+    #[test]
+    fn test_select_only_always_includes_primary_key() {
+        let query = Select::<Hero>::new().only(&["name", "id", "name"]);
+        assert!(query.is_partial());
+        let (sql, _) = query.build();
+
+        assert_eq!(sql, "SELECT id, name FROM heroes");
+    }
+
+    #[test]
+    fn test_select_count_sql() {
+        let query = Select::<Hero>::new()
+            .filter(Expr::col("age").gt(18))
+            .order_by(OrderBy::asc(Expr::col("name")))
+            .limit(10);
+        let (sql, params) = query.build_count_with_dialect(Dialect::Postgres);
+
+        assert_eq!(
+            sql,
+            "SELECT COUNT(*) as count FROM heroes WHERE \"age\" > $1"
+        );
+        assert_eq!(params, vec![Value::Int(18)]);
+    }
+
+    #[test]
+    fn test_select_count_distinct_uses_derived_table() {
+        let query = Select::<Hero>::new().columns(&["team_id"]).distinct();
+        let (sql, _) = query.build_count_with_dialect(Dialect::Postgres);
+
+        assert_eq!(
+            sql,
+            "SELECT COUNT(*) as count FROM (SELECT DISTINCT team_id FROM heroes) AS _count"
+        );
+    }
+
+    #[test]
+    fn test_select_exists_sql() {
+        let query = Select::<Hero>::new().filter(Expr::col("name").eq("Spider-Man"));
+        let (sql, params) = query.build_exists_with_dialect(Dialect::Postgres);
+
+        assert_eq!(
+            sql,
+            "SELECT EXISTS(SELECT 1 FROM heroes WHERE \"name\" = $1)"
+        );
+        assert_eq!(params.len(), 1);
+    }
+
     #[test]
     fn test_select_distinct() {
         let query = Select::<Hero>::new().columns(&["team_id"]).distinct();
@@ -1753,6 +2082,68 @@ mod tests {
         assert!(params.is_empty());
     }
 
+    #[test]
+    fn test_select_joined_tables() {
+        let query = Select::<Hero>::new().join(Join::inner(
+            "teams",
+            Expr::qualified("heroes", "team_id").eq(Expr::qualified("teams", "id")),
+        ));
+        assert_eq!(query.joined_tables().collect::<Vec<_>>(), vec!["teams"]);
+        assert_eq!(Select::<Hero>::new().joined_tables().count(), 0);
+    }
+
+    #[test]
+    fn test_select_from_schema_qualifies_from_table() {
+        let query = Select::<Hero>::new()
+            .from_schema("archive_2024_01")
+            .filter(Expr::qualified("heroes", "age").gt(30));
+        let (sql, params) = query.build();
+        assert_eq!(
+            sql,
+            "SELECT * FROM archive_2024_01.heroes WHERE \"heroes\".\"age\" > $1"
+        );
+        assert_eq!(params, vec![Value::Int(30)]);
+
+        let (sql, _) = query.build_exists_with_dialect(Dialect::Sqlite);
+        assert!(
+            sql.starts_with("SELECT EXISTS(SELECT 1 FROM archive_2024_01.heroes WHERE"),
+            "{sql}"
+        );
+    }
+
+    #[test]
+    fn test_select_applies_model_default_order() {
+        let (sql, _) = Select::<Post>::new().limit(5).build();
+        assert_eq!(
+            sql,
+            "SELECT * FROM posts ORDER BY \"created_at\" DESC, \"id\" ASC LIMIT 5"
+        );
+    }
+
+    #[test]
+    fn test_select_explicit_order_replaces_default_order() {
+        let (sql, _) = Select::<Post>::new()
+            .order_by(OrderBy::asc(Expr::col("title")))
+            .order_by(OrderBy::desc(Expr::col("id")))
+            .build();
+        assert_eq!(
+            sql,
+            "SELECT * FROM posts ORDER BY \"title\" ASC, \"id\" DESC"
+        );
+    }
+
+    #[test]
+    fn test_select_unordered_drops_default_order() {
+        let (sql, _) = Select::<Post>::new().unordered().build();
+        assert_eq!(sql, "SELECT * FROM posts");
+
+        let (sql, _) = Select::<Post>::new()
+            .unordered()
+            .order_by(OrderBy::asc(Expr::col("title")))
+            .build();
+        assert_eq!(sql, "SELECT * FROM posts ORDER BY \"title\" ASC");
+    }
+
     #[test]
     fn test_select_with_limit() {
         let query = Select::<Hero>::new().limit(10);
@@ -2018,7 +2409,7 @@ mod tests {
         let loader = EagerLoader::<EagerHero>::new().include("team");
         let query = Select::<EagerHero>::new().eager(loader);
 
-        let (sql, params, join_info) = query.build_eager_with_dialect(Dialect::default());
+        let (sql, params, join_info) = query.build_eager_with_dialect(Dialect::default().as_dyn());
 
         // Should have LEFT JOIN for team relationship
         assert!(sql.contains("LEFT JOIN teams"));
@@ -2045,7 +2436,7 @@ mod tests {
             .eager(loader)
             .filter(Expr::col("active").eq(true));
 
-        let (sql, params, _) = query.build_eager_with_dialect(Dialect::default());
+        let (sql, params, _) = query.build_eager_with_dialect(Dialect::default().as_dyn());
 
         assert!(sql.contains("LEFT JOIN teams"));
         assert!(sql.contains("WHERE"));
@@ -2062,7 +2453,7 @@ mod tests {
             .limit(10)
             .offset(5);
 
-        let (sql, _, _) = query.build_eager_with_dialect(Dialect::default());
+        let (sql, _, _) = query.build_eager_with_dialect(Dialect::default().as_dyn());
 
         assert!(sql.contains("LEFT JOIN teams"));
         assert!(sql.contains("ORDER BY"));
@@ -2087,7 +2478,7 @@ mod tests {
         let loader = EagerLoader::<EagerHero>::new().include("team");
         let query = Select::<EagerHero>::new().eager(loader).distinct();
 
-        let (sql, _, _) = query.build_eager_with_dialect(Dialect::default());
+        let (sql, _, _) = query.build_eager_with_dialect(Dialect::default().as_dyn());
 
         assert!(sql.starts_with("SELECT DISTINCT"));
     }
@@ -26,6 +26,7 @@
 use crate::clause::OrderBy;
 use crate::expr::Dialect;
 use sqlmodel_core::Value;
+use sqlmodel_core::dialect::IntoSqlDialect;
 
 /// Type of set operation.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -151,7 +152,8 @@ impl SetOperation {
     }
 
     /// Build the SQL query with a specific dialect.
-    pub fn build_with_dialect(&self, dialect: Dialect) -> (String, Vec<Value>) {
+    pub fn build_with_dialect(&self, dialect: impl IntoSqlDialect) -> (String, Vec<Value>) {
+        let dialect = dialect.into_sql_dialect();
         let mut sql = String::new();
         let mut params = Vec::new();
 
@@ -196,17 +198,8 @@ impl SetOperation {
             sql.push_str(&order_strs.join(", "));
         }
 
-        // LIMIT
-        if let Some(limit) = self.limit {
-            sql.push_str(" LIMIT ");
-            sql.push_str(&limit.to_string());
-        }
-
-        // OFFSET
-        if let Some(offset) = self.offset {
-            sql.push_str(" OFFSET ");
-            sql.push_str(&offset.to_string());
-        }
+        // LIMIT / OFFSET
+        sql.push_str(&dialect.limit_offset(self.limit, self.offset));
 
         (sql, params)
     }
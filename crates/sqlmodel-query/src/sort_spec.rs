@@ -0,0 +1,277 @@
+//! Sorting and sparse fieldsets parsed from API query strings.
+//!
+//! The companions of [`FilterSpec`](crate::FilterSpec) for list endpoints:
+//!
+//! ```ignore
+//! // GET /heroes?sort=-created_at,name&fields=id,name
+//! let mut query = select!(Hero).only(&FieldSet::<Hero>::new().columns(&params.fields)?);
+//! for order in SortSpec::<Hero>::new().parse(&params.sort)? {
+//!     query = query.order_by(order);
+//! }
+//! ```
+//!
+//! Names are matched against [`Model::fields`] like filter keys are, so a
+//! field hidden from API output can neither be sorted on nor requested.
+//! Unknown names fail with [`Error::Validation`] listing the names that
+//! are accepted.
+
+use std::marker::PhantomData;
+
+use sqlmodel_core::{Error, FieldInfo, Model, Result};
+
+use crate::clause::OrderBy;
+use crate::expr::Expr;
+use crate::filter_spec::{api_field, api_fields, invalid, ordered};
+
+/// Default cap on the number of sort keys.
+pub const DEFAULT_MAX_SORT_KEYS: usize = 4;
+
+/// Parser for `sort=-created_at,name` parameters over `M`.
+///
+/// Keys are comma-separated field names, each optionally prefixed with `-`
+/// for descending or `+` for ascending order.
+pub struct SortSpec<M> {
+    allowed: Option<Vec<&'static str>>,
+    max_keys: usize,
+    _model: PhantomData<fn() -> M>,
+}
+
+impl<M: Model> SortSpec<M> {
+    /// Create a parser accepting every orderable, API-visible field of `M`.
+    pub fn new() -> Self {
+        Self {
+            allowed: None,
+            max_keys: DEFAULT_MAX_SORT_KEYS,
+            _model: PhantomData,
+        }
+    }
+
+    /// Restrict sorting to the named fields, e.g. the indexed ones.
+    #[must_use]
+    pub fn allow(mut self, fields: &[&'static str]) -> Self {
+        self.allowed = Some(fields.to_vec());
+        self
+    }
+
+    /// Cap the number of sort keys (default [`DEFAULT_MAX_SORT_KEYS`]).
+    #[must_use]
+    pub fn max_keys(mut self, max: usize) -> Self {
+        self.max_keys = max;
+        self
+    }
+
+    /// Parse `sort` into `ORDER BY` terms, in order. An empty string yields
+    /// no terms.
+    #[allow(clippy::result_large_err)]
+    pub fn parse(&self, sort: &str) -> Result<Vec<OrderBy>> {
+        let mut orders = Vec::new();
+        let mut seen: Vec<&'static str> = Vec::new();
+        for key in sort.split(',').map(str::trim).filter(|key| !key.is_empty()) {
+            let (descending, name) = match key.strip_prefix('-') {
+                Some(name) => (true, name),
+                None => (false, key.strip_prefix('+').unwrap_or(key)),
+            };
+            let field = api_field::<M>(name, self.allowed.as_deref())
+                .filter(|field| ordered(&field.sql_type))
+                .ok_or_else(|| self.unknown(name))?;
+            if seen.contains(&field.column_name) {
+                return Err(invalid("sort", format!("'{name}' is sorted on twice")));
+            }
+            seen.push(field.column_name);
+            if seen.len() > self.max_keys {
+                return Err(invalid(
+                    "sort",
+                    format!("at most {} sort keys are allowed", self.max_keys),
+                ));
+            }
+            let column = Expr::col(field.column_name);
+            orders.push(if descending {
+                OrderBy::desc(column)
+            } else {
+                OrderBy::asc(column)
+            });
+        }
+        Ok(orders)
+    }
+
+    fn unknown(&self, name: &str) -> Error {
+        let allowed = api_fields::<M>(self.allowed.as_deref())
+            .filter(|field| ordered(&field.sql_type))
+            .map(FieldInfo::output_name)
+            .collect::<Vec<_>>();
+        invalid(
+            "sort",
+            format!("cannot sort on '{name}'; allowed: {}", allowed.join(", ")),
+        )
+    }
+}
+
+impl<M: Model> Default for SortSpec<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parser for `fields=id,name` sparse fieldset parameters over `M`.
+pub struct FieldSet<M> {
+    allowed: Option<Vec<&'static str>>,
+    _model: PhantomData<fn() -> M>,
+}
+
+impl<M: Model> FieldSet<M> {
+    /// Create a parser accepting every API-visible field of `M`.
+    pub fn new() -> Self {
+        Self {
+            allowed: None,
+            _model: PhantomData,
+        }
+    }
+
+    /// Restrict the fieldset to the named fields.
+    #[must_use]
+    pub fn allow(mut self, fields: &[&'static str]) -> Self {
+        self.allowed = Some(fields.to_vec());
+        self
+    }
+
+    /// Parse `fields` into the requested fields, in order and without
+    /// duplicates. An empty string yields every accepted field.
+    #[allow(clippy::result_large_err)]
+    pub fn parse(&self, fields: &str) -> Result<Vec<&'static FieldInfo>> {
+        let mut selected: Vec<&'static FieldInfo> = Vec::new();
+        for name in fields
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            let field = api_field::<M>(name, self.allowed.as_deref()).ok_or_else(|| {
+                let allowed = api_fields::<M>(self.allowed.as_deref())
+                    .map(FieldInfo::output_name)
+                    .collect::<Vec<_>>();
+                invalid(
+                    "fields",
+                    format!("unknown field '{name}'; allowed: {}", allowed.join(", ")),
+                )
+            })?;
+            if !selected.iter().any(|s| s.column_name == field.column_name) {
+                selected.push(field);
+            }
+        }
+        if selected.is_empty() {
+            selected.extend(api_fields::<M>(self.allowed.as_deref()));
+        }
+        Ok(selected)
+    }
+
+    /// Parse `fields` into the column names to pass to
+    /// [`Select::only`](crate::Select::only).
+    #[allow(clippy::result_large_err)]
+    pub fn columns(&self, fields: &str) -> Result<Vec<&'static str>> {
+        Ok(self
+            .parse(fields)?
+            .into_iter()
+            .map(|field| field.column_name)
+            .collect())
+    }
+}
+
+impl<M: Model> Default for FieldSet<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr::Dialect;
+    use sqlmodel_core::{Row, SqlType, Value};
+
+    struct Hero;
+
+    impl Model for Hero {
+        const TABLE_NAME: &'static str = "heroes";
+        const PRIMARY_KEY: &'static [&'static str] = &["id"];
+
+        fn fields() -> &'static [FieldInfo] {
+            static FIELDS: &[FieldInfo] = &[
+                FieldInfo::new("id", "id", SqlType::BigInt).primary_key(true),
+                FieldInfo::new("name", "hero_name", SqlType::Text).serialization_alias("heroName"),
+                FieldInfo::new("created_at", "created_at", SqlType::Timestamp),
+                FieldInfo::new("tags", "tags", SqlType::JsonB),
+                FieldInfo::new("password_hash", "password_hash", SqlType::Text).exclude(true),
+            ];
+            FIELDS
+        }
+
+        fn to_row(&self) -> Vec<(&'static str, Value)> {
+            Vec::new()
+        }
+
+        fn from_row(_row: &Row) -> Result<Self> {
+            Ok(Self)
+        }
+
+        fn primary_key_value(&self) -> Vec<Value> {
+            Vec::new()
+        }
+
+        fn is_new(&self) -> bool {
+            true
+        }
+    }
+
+    fn message(error: Error) -> String {
+        match error {
+            Error::Validation(errors) => errors.errors[0].message.clone(),
+            other => panic!("expected a validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_sort_spec_parses_directions() {
+        let orders = SortSpec::<Hero>::new()
+            .parse("-created_at, heroName,+id")
+            .unwrap();
+        let sql: Vec<String> = orders
+            .iter()
+            .map(|order| order.build(Dialect::Postgres, &mut Vec::new(), 0))
+            .collect();
+        assert_eq!(
+            sql,
+            vec!["\"created_at\" DESC", "\"hero_name\" ASC", "\"id\" ASC"]
+        );
+        assert!(SortSpec::<Hero>::new().parse("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sort_spec_rejects_unknown_and_unorderable_fields() {
+        let spec = SortSpec::<Hero>::new();
+        assert_eq!(
+            message(spec.parse("-password_hash").unwrap_err()),
+            "cannot sort on 'password_hash'; allowed: id, heroName, created_at"
+        );
+        assert!(spec.parse("tags").is_err());
+        assert!(spec.parse("id,-id").is_err());
+        assert!(spec.max_keys(1).parse("id,name").is_err());
+    }
+
+    #[test]
+    fn test_field_set_resolves_columns() {
+        let set = FieldSet::<Hero>::new();
+        assert_eq!(
+            set.columns("heroName,id,name").unwrap(),
+            vec!["hero_name", "id"]
+        );
+        assert_eq!(set.parse("").unwrap().len(), 4);
+        assert_eq!(
+            message(
+                FieldSet::<Hero>::new()
+                    .allow(&["id", "name"])
+                    .parse("created_at")
+                    .unwrap_err()
+            ),
+            "unknown field 'created_at'; allowed: id, heroName"
+        );
+    }
+}
@@ -0,0 +1,593 @@
+//! Reusable SQL buffers and cached per-model write statements.
+//!
+//! [`SqlWriter`] keeps one `String` and one parameter `Vec` alive across
+//! statements, numbering placeholders as values are bound. Hot write paths
+//! (session flush, builders in loops) [`clear`](SqlWriter::clear) it between
+//! statements instead of formatting fresh strings each time.
+//!
+//! [`ModelStatements`] holds the canonical single-row statements for a model
+//! (select by primary key, insert, full update, delete by primary key), built
+//! once per `(model, dialect)` and shared for the life of the process.
+//! [`StatementKind`] names them, e.g. as the key of a per-connection cache of
+//! prepared statements.
+
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+use crate::expr::Expr;
+use sqlmodel_core::dialect::{IntoSqlDialect, SqlDialect};
+use sqlmodel_core::{Model, Value};
+
+/// A reusable SQL buffer with a placeholder counter.
+///
+/// # Example
+///
+/// ```
+/// use sqlmodel_core::{Dialect, Value};
+/// use sqlmodel_query::SqlWriter;
+///
+/// let mut w = SqlWriter::new(Dialect::Postgres);
+/// w.push("SELECT * FROM ");
+/// w.push_identifier("heroes");
+/// w.push(" WHERE ");
+/// w.push_identifier("id");
+/// w.push(" = ");
+/// w.push_param(Value::BigInt(7));
+/// assert_eq!(w.sql(), "SELECT * FROM \"heroes\" WHERE \"id\" = $1");
+/// assert_eq!(w.params(), &[Value::BigInt(7)]);
+///
+/// w.clear();
+/// assert!(w.sql().is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SqlWriter {
+    dialect: &'static dyn SqlDialect,
+    sql: String,
+    params: Vec<Value>,
+    placeholders: usize,
+}
+
+impl SqlWriter {
+    /// Create an empty writer for the given dialect.
+    pub fn new(dialect: impl IntoSqlDialect) -> Self {
+        Self::with_capacity(dialect, 0)
+    }
+
+    /// Create an empty writer with room for `capacity` bytes of SQL.
+    pub fn with_capacity(dialect: impl IntoSqlDialect, capacity: usize) -> Self {
+        let dialect = dialect.into_sql_dialect();
+        Self {
+            dialect,
+            sql: String::with_capacity(capacity),
+            params: Vec::new(),
+            placeholders: 0,
+        }
+    }
+
+    /// The dialect placeholders and identifiers are rendered for.
+    pub fn dialect(&self) -> &'static dyn SqlDialect {
+        self.dialect
+    }
+
+    /// Reset the SQL, parameters and placeholder counter, keeping the
+    /// allocated capacity for the next statement.
+    pub fn clear(&mut self) {
+        self.sql.clear();
+        self.params.clear();
+        self.placeholders = 0;
+    }
+
+    /// Append raw SQL.
+    pub fn push(&mut self, sql: &str) {
+        self.sql.push_str(sql);
+    }
+
+    /// Append a quoted identifier, escaping embedded quote characters.
+    pub fn push_identifier(&mut self, name: &str) {
+        self.sql.push_str(&self.dialect.quote_identifier(name));
+    }
+
+    /// Append the next placeholder without binding a value.
+    ///
+    /// Use this when the parameters are supplied separately, e.g. with a
+    /// cached template.
+    pub fn push_placeholder(&mut self) {
+        self.placeholders += 1;
+        let index = self.placeholders;
+        self.sql.push_str(&self.dialect.placeholder(index));
+    }
+
+    /// Append the next placeholder and bind `value` to it.
+    pub fn push_param(&mut self, value: Value) {
+        self.push_placeholder();
+        self.params.push(value);
+    }
+
+    /// Bind a value without writing a placeholder (the SQL already has one).
+    pub fn bind(&mut self, value: Value) {
+        self.params.push(value);
+    }
+
+    /// Number of placeholders written so far.
+    pub fn placeholder_count(&self) -> usize {
+        self.placeholders
+    }
+
+    /// Advance the placeholder counter by `n`, for SQL fragments that were
+    /// rendered elsewhere with their own placeholders.
+    pub fn skip_placeholders(&mut self, n: usize) {
+        self.placeholders += n;
+    }
+
+    /// Append a rendered expression and bind its parameters, numbering its
+    /// placeholders after those already written.
+    pub fn push_expr(&mut self, expr: &Expr) {
+        let mut params = Vec::new();
+        let sql = expr.build_with_dialect(self.dialect, &mut params, self.placeholders);
+        self.sql.push_str(&sql);
+        self.skip_placeholders(params.len());
+        self.params.extend(params);
+    }
+
+    /// The SQL written so far.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// The bound parameters so far.
+    pub fn params(&self) -> &[Value] {
+        &self.params
+    }
+
+    /// Mutable access to the SQL buffer for helpers that append to a `String`.
+    pub fn buffer_mut(&mut self) -> &mut String {
+        &mut self.sql
+    }
+
+    /// Consume the writer, returning the SQL and parameters.
+    pub fn into_parts(self) -> (String, Vec<Value>) {
+        (self.sql, self.params)
+    }
+}
+
+/// One of the canonical statements in [`ModelStatements`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatementKind {
+    /// `SELECT ... WHERE pk = ... LIMIT 1`.
+    SelectByPk,
+    /// `INSERT INTO ... VALUES (...)` binding every column.
+    Insert,
+    /// `UPDATE ... SET <all non-pk columns> WHERE pk = ...`.
+    Update,
+    /// `DELETE FROM ... WHERE pk = ...`.
+    Delete,
+}
+
+/// Cached single-row statements for one model and dialect.
+///
+/// Identifiers are quoted. Placeholders follow the column order in
+/// [`columns`](Self::columns):
+///
+/// - `insert` binds every column.
+/// - `update` binds the non-primary-key columns, then the primary key.
+/// - `delete` and [`select_by_pk`](Self::select_by_pk) bind the primary key.
+#[derive(Debug, Clone)]
+pub struct ModelStatements {
+    /// Dialect the statements are rendered for.
+    pub dialect: &'static dyn SqlDialect,
+    /// Table the statements write to.
+    pub table: String,
+    /// Columns the statements were built for, in placeholder order.
+    pub columns: Vec<&'static str>,
+    /// Primary key columns used by the `WHERE` clauses.
+    pub pk_columns: Vec<&'static str>,
+    /// `INSERT INTO t (...) VALUES (...)`.
+    pub insert: String,
+    /// `UPDATE t SET ... WHERE pk = ...`, if there is a primary key and at
+    /// least one other column.
+    pub update: Option<String>,
+    /// `DELETE FROM t WHERE pk = ...`, if there is a primary key.
+    pub delete: Option<String>,
+    /// Built on first use by [`select_by_pk`](Self::select_by_pk).
+    select_by_pk: OnceLock<Option<String>>,
+}
+
+impl ModelStatements {
+    /// Build the statements for a table.
+    pub fn build(
+        dialect: impl IntoSqlDialect,
+        table: &str,
+        columns: &[&'static str],
+        pk_columns: &[&'static str],
+    ) -> Self {
+        let dialect = dialect.into_sql_dialect();
+        let mut w = SqlWriter::new(dialect);
+
+        w.push("INSERT INTO ");
+        w.push_identifier(table);
+        w.push(" (");
+        for (i, col) in columns.iter().enumerate() {
+            if i > 0 {
+                w.push(", ");
+            }
+            w.push_identifier(col);
+        }
+        w.push(") VALUES (");
+        for i in 0..columns.len() {
+            if i > 0 {
+                w.push(", ");
+            }
+            w.push_placeholder();
+        }
+        w.push(")");
+        let insert = w.sql().to_string();
+
+        let pk_where = |w: &mut SqlWriter| {
+            w.push(" WHERE ");
+            for (i, col) in pk_columns.iter().enumerate() {
+                if i > 0 {
+                    w.push(" AND ");
+                }
+                w.push_identifier(col);
+                w.push(" = ");
+                w.push_placeholder();
+            }
+        };
+
+        let set_columns: Vec<_> = columns.iter().filter(|c| !pk_columns.contains(c)).collect();
+        let update = if pk_columns.is_empty() || set_columns.is_empty() {
+            None
+        } else {
+            w.clear();
+            w.push("UPDATE ");
+            w.push_identifier(table);
+            w.push(" SET ");
+            for (i, col) in set_columns.iter().enumerate() {
+                if i > 0 {
+                    w.push(", ");
+                }
+                w.push_identifier(col);
+                w.push(" = ");
+                w.push_placeholder();
+            }
+            pk_where(&mut w);
+            Some(w.sql().to_string())
+        };
+
+        let delete = if pk_columns.is_empty() {
+            None
+        } else {
+            w.clear();
+            w.push("DELETE FROM ");
+            w.push_identifier(table);
+            pk_where(&mut w);
+            Some(w.sql().to_string())
+        };
+
+        Self {
+            dialect,
+            table: table.to_string(),
+            columns: columns.to_vec(),
+            pk_columns: pk_columns.to_vec(),
+            insert,
+            update,
+            delete,
+            select_by_pk: OnceLock::new(),
+        }
+    }
+
+    /// `SELECT <columns of M> FROM t WHERE pk = ... LIMIT 1`, or `None` if
+    /// `M` has no primary key.
+    ///
+    /// Selects [`Model::select_columns`], or `*` if there are none.
+    pub fn select_by_pk<M: Model>(&self) -> Option<&str> {
+        self.select_by_pk
+            .get_or_init(|| {
+                let pk_columns = M::PRIMARY_KEY;
+                if pk_columns.is_empty() {
+                    return None;
+                }
+                let mut w = SqlWriter::new(self.dialect);
+                w.push("SELECT ");
+                let columns = M::select_columns();
+                if columns.is_empty() {
+                    w.push("*");
+                }
+                for (i, col) in columns.iter().enumerate() {
+                    if i > 0 {
+                        w.push(", ");
+                    }
+                    w.push_identifier(col);
+                }
+                w.push(" FROM ");
+                w.push_identifier(M::TABLE_NAME);
+                w.push(" WHERE ");
+                for (i, col) in pk_columns.iter().enumerate() {
+                    if i > 0 {
+                        w.push(" AND ");
+                    }
+                    w.push_identifier(col);
+                    w.push(" = ");
+                    w.push_placeholder();
+                }
+                w.push(" LIMIT 1");
+                Some(w.into_parts().0)
+            })
+            .as_deref()
+    }
+
+    /// The statement of the given kind, if this model has one.
+    ///
+    /// [`StatementKind::SelectByPk`] is only available once
+    /// [`select_by_pk`](Self::select_by_pk) has built it.
+    pub fn get(&self, kind: StatementKind) -> Option<&str> {
+        match kind {
+            StatementKind::SelectByPk => self.select_by_pk.get().and_then(Option::as_deref),
+            StatementKind::Insert => Some(&self.insert),
+            StatementKind::Update => self.update.as_deref(),
+            StatementKind::Delete => self.delete.as_deref(),
+        }
+    }
+
+    /// Statements for `M`, built from its stored (non-computed) fields on
+    /// first use.
+    pub fn for_model<M: Model + 'static>(dialect: impl IntoSqlDialect) -> &'static Self {
+        let dialect = dialect.into_sql_dialect();
+        Self::cached(TypeId::of::<M>(), dialect, || {
+            let columns: Vec<_> = M::fields()
+                .iter()
+                .filter(|f| !f.computed)
+                .map(|f| f.column_name)
+                .collect();
+            Self::build(dialect, M::TABLE_NAME, &columns, M::PRIMARY_KEY)
+        })
+    }
+
+    /// Statements for the model identified by `type_id`, calling `build` on
+    /// first use.
+    ///
+    /// Entries live for the rest of the process; there is one per model type
+    /// and dialect, keyed by [`SqlDialect::name`].
+    pub fn cached(
+        type_id: TypeId,
+        dialect: impl IntoSqlDialect,
+        build: impl FnOnce() -> Self,
+    ) -> &'static Self {
+        let key = (type_id, dialect.into_sql_dialect().name());
+        if let Some(found) = cache().read().ok().and_then(|map| map.get(&key).copied()) {
+            return found;
+        }
+
+        let mut map = match cache().write() {
+            Ok(map) => map,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        map.entry(key)
+            .or_insert_with(|| Box::leak(Box::new(build())))
+    }
+
+    /// Statements for the model identified by `type_id` whose rows have
+    /// exactly these columns.
+    ///
+    /// Unlike [`cached`](Self::cached), an entry built for a different column
+    /// list (e.g. by [`for_model`](Self::for_model) for a model whose rows
+    /// omit some fields) is replaced, so callers that know the row shape
+    /// always get usable statements.
+    pub fn cached_for(
+        type_id: TypeId,
+        dialect: impl IntoSqlDialect,
+        table: &str,
+        columns: &[&'static str],
+        pk_columns: &[&'static str],
+    ) -> &'static Self {
+        let dialect = dialect.into_sql_dialect();
+        let found = Self::cached(type_id, dialect, || {
+            Self::build(dialect, table, columns, pk_columns)
+        });
+        if found.matches(table, columns, pk_columns) {
+            return found;
+        }
+
+        let mut map = match cache().write() {
+            Ok(map) => map,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let entry = map.entry((type_id, dialect.name())).or_insert(found);
+        if !entry.matches(table, columns, pk_columns) {
+            *entry = Box::leak(Box::new(Self::build(dialect, table, columns, pk_columns)));
+        }
+        entry
+    }
+
+    /// Whether these statements apply to a row of `table` with the given
+    /// columns.
+    pub fn matches(
+        &self,
+        table: &str,
+        columns: &[&'static str],
+        pk_columns: &[&'static str],
+    ) -> bool {
+        self.table == table && self.columns == columns && self.pk_columns == pk_columns
+    }
+}
+
+type ModelStatementCache = RwLock<HashMap<(TypeId, &'static str), &'static ModelStatements>>;
+
+fn cache() -> &'static ModelStatementCache {
+    static CACHE: OnceLock<ModelStatementCache> = OnceLock::new();
+    CACHE.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlmodel_core::{Dialect, FieldInfo, Row, SqlType};
+
+    struct Note {
+        id: i64,
+        body: String,
+    }
+
+    impl Model for Note {
+        const TABLE_NAME: &'static str = "notes";
+        const PRIMARY_KEY: &'static [&'static str] = &["id"];
+
+        fn fields() -> &'static [FieldInfo] {
+            static FIELDS: &[FieldInfo] = &[
+                FieldInfo::new("id", "id", SqlType::BigInt).primary_key(true),
+                FieldInfo::new("body", "body", SqlType::Text),
+            ];
+            FIELDS
+        }
+
+        fn to_row(&self) -> Vec<(&'static str, Value)> {
+            vec![
+                ("id", Value::BigInt(self.id)),
+                ("body", Value::Text(self.body.clone())),
+            ]
+        }
+
+        fn from_row(_row: &Row) -> sqlmodel_core::Result<Self> {
+            Err(sqlmodel_core::Error::Custom(
+                "from_row not used in tests".to_string(),
+            ))
+        }
+
+        fn primary_key_value(&self) -> Vec<Value> {
+            vec![Value::BigInt(self.id)]
+        }
+
+        fn is_new(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn test_writer_numbers_placeholders_per_dialect() {
+        let mut w = SqlWriter::new(Dialect::Sqlite);
+        w.push("VALUES (");
+        w.push_param(Value::Int(1));
+        w.push(", ");
+        w.push_param(Value::Int(2));
+        w.push(")");
+        assert_eq!(w.sql(), "VALUES (?1, ?2)");
+        assert_eq!(w.placeholder_count(), 2);
+
+        let capacity = w.buffer_mut().capacity();
+        w.clear();
+        assert_eq!(w.placeholder_count(), 0);
+        assert!(w.params().is_empty());
+        assert_eq!(w.buffer_mut().capacity(), capacity);
+
+        let mut w = SqlWriter::new(Dialect::Mysql);
+        w.push_identifier("we`ird");
+        w.push(" = ");
+        w.push_placeholder();
+        assert_eq!(w.sql(), "`we``ird` = ?");
+    }
+
+    #[test]
+    fn test_model_statements_build() {
+        let stmts =
+            ModelStatements::build(Dialect::Postgres, "heroes", &["id", "name", "age"], &["id"]);
+        assert_eq!(
+            stmts.insert,
+            "INSERT INTO \"heroes\" (\"id\", \"name\", \"age\") VALUES ($1, $2, $3)"
+        );
+        assert_eq!(
+            stmts.update.as_deref(),
+            Some("UPDATE \"heroes\" SET \"name\" = $1, \"age\" = $2 WHERE \"id\" = $3")
+        );
+        assert_eq!(
+            stmts.delete.as_deref(),
+            Some("DELETE FROM \"heroes\" WHERE \"id\" = $1")
+        );
+
+        let keyless = ModelStatements::build(Dialect::Sqlite, "logs", &["line"], &[]);
+        assert_eq!(
+            keyless.insert,
+            "INSERT INTO \"logs\" (\"line\") VALUES (?1)"
+        );
+        assert_eq!(keyless.update, None);
+        assert_eq!(keyless.delete, None);
+    }
+
+    #[test]
+    fn test_model_statements_cached_once_per_dialect() {
+        struct Marker;
+        let build =
+            |dialect| move || ModelStatements::build(dialect, "marks", &["id", "v"], &["id"]);
+
+        let pg = ModelStatements::cached(
+            TypeId::of::<Marker>(),
+            Dialect::Postgres,
+            build(Dialect::Postgres),
+        );
+        let again = ModelStatements::cached(TypeId::of::<Marker>(), Dialect::Postgres, || {
+            panic!("cached entry must be reused")
+        });
+        assert!(std::ptr::eq(pg, again));
+
+        let sqlite = ModelStatements::cached(
+            TypeId::of::<Marker>(),
+            Dialect::Sqlite,
+            build(Dialect::Sqlite),
+        );
+        assert_eq!(
+            sqlite.insert,
+            "INSERT INTO \"marks\" (\"id\", \"v\") VALUES (?1, ?2)"
+        );
+    }
+
+    #[test]
+    fn test_model_statements_for_model_select_by_pk() {
+        let stmts = ModelStatements::for_model::<Note>(Dialect::Mysql);
+        assert_eq!(stmts.get(StatementKind::SelectByPk), None);
+        assert_eq!(
+            stmts.select_by_pk::<Note>(),
+            Some("SELECT `id`, `body` FROM `notes` WHERE `id` = ? LIMIT 1")
+        );
+        assert_eq!(
+            stmts.get(StatementKind::SelectByPk),
+            stmts.select_by_pk::<Note>()
+        );
+        assert_eq!(
+            stmts.get(StatementKind::Update),
+            Some("UPDATE `notes` SET `body` = ? WHERE `id` = ?")
+        );
+
+        // Rows of the model fit the statements built from its fields
+        let note = Note {
+            id: 1,
+            body: "hello".to_string(),
+        };
+        let columns: Vec<_> = note.to_row().into_iter().map(|(c, _)| c).collect();
+        assert!(stmts.matches(Note::TABLE_NAME, &columns, Note::PRIMARY_KEY));
+    }
+
+    #[test]
+    fn test_model_statements_cached_for_replaces_other_shapes() {
+        struct Shape;
+        let type_id = TypeId::of::<Shape>();
+
+        let wide = ModelStatements::cached_for(
+            type_id,
+            Dialect::Postgres,
+            "s",
+            &["id", "a", "b"],
+            &["id"],
+        );
+        let narrow =
+            ModelStatements::cached_for(type_id, Dialect::Postgres, "s", &["id", "a"], &["id"]);
+        assert!(!std::ptr::eq(wide, narrow));
+        assert_eq!(
+            narrow.insert,
+            "INSERT INTO \"s\" (\"id\", \"a\") VALUES ($1, $2)"
+        );
+
+        let again =
+            ModelStatements::cached_for(type_id, Dialect::Postgres, "s", &["id", "a"], &["id"]);
+        assert!(std::ptr::eq(narrow, again));
+    }
+}
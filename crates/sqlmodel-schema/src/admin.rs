@@ -0,0 +1,500 @@
+//! Database create/drop/reset helpers for test harnesses and dev scripts.
+//!
+//! [`create_database`] and [`drop_database`] run against a connection to a
+//! *maintenance* database: `postgres` (or any other database) on PostgreSQL,
+//! any database on MySQL. A connection cannot drop the database it is using.
+//! SQLite databases are plain files, so these two are not supported there.
+//!
+//! [`truncate_all`] and [`reset`] run against the target database and empty
+//! the tables of a [`ModelTuple`], children before parents.
+//! [`create_registered`] creates the missing tables of a runtime
+//! [`ModelRegistry`] instead.
+//!
+//! [`analyze`], [`vacuum`] and [`reindex`] map table maintenance onto each
+//! dialect's statements (see [`maintenance_sql`]).
+//!
+//! # Example
+//!
+//! ```ignore
+//! use sqlmodel_schema::admin;
+//!
+//! admin::create_database(&cx, &maintenance, "app_test").await?;
+//! let conn = PgConnection::connect(&cx, test_config).await?;
+//! admin::reset::<(Team, Hero), _>(&cx, &conn).await?;
+//! ```
+
+use asupersync::{Cx, Outcome};
+use sqlmodel_core::dialect::IntoSqlDialect;
+use sqlmodel_core::{Connection, Error, ModelRegistry, Value};
+
+use crate::ddl::generator_for_dialect;
+use crate::diff::SchemaOperation;
+use crate::expected::{ModelTuple, registry_table_schemas};
+use crate::index::quote;
+use crate::introspect::{Dialect, Introspector, TableInfo};
+
+/// PostgreSQL `duplicate_database`.
+const DUPLICATE_DATABASE: &str = "42P04";
+/// PostgreSQL `unique_violation`, raised on `pg_database` when two sessions
+/// create the same database at once.
+const UNIQUE_VIOLATION: &str = "23505";
+
+fn unsupported_on_sqlite(operation: &str) -> Error {
+    Error::Custom(format!(
+        "{operation} is not supported on SQLite: databases are files, open or remove the file instead"
+    ))
+}
+
+/// Whether database `name` exists on the server.
+pub async fn database_exists<C: Connection>(cx: &Cx, conn: &C, name: &str) -> Outcome<bool, Error> {
+    let sql = match Dialect::from(conn.dialect()) {
+        Dialect::Postgres => "SELECT 1 FROM pg_database WHERE datname = $1",
+        Dialect::Mysql => "SELECT 1 FROM information_schema.schemata WHERE schema_name = ?",
+        Dialect::Sqlite => return Outcome::Err(unsupported_on_sqlite("database_exists")),
+    };
+    conn.query(cx, sql, &[Value::Text(name.to_string())])
+        .await
+        .map(|rows| !rows.is_empty())
+}
+
+/// Create database `name` unless it exists.
+///
+/// Returns `false` if the database already existed, including when another
+/// session created it concurrently.
+pub async fn create_database<C: Connection>(cx: &Cx, conn: &C, name: &str) -> Outcome<bool, Error> {
+    let dialect = Dialect::from(conn.dialect());
+    match dialect {
+        Dialect::Sqlite => Outcome::Err(unsupported_on_sqlite("create_database")),
+        Dialect::Mysql => {
+            let sql = format!("CREATE DATABASE IF NOT EXISTS {}", quote(name, dialect));
+            conn.execute(cx, &sql, &[]).await.map(|created| created > 0)
+        }
+        Dialect::Postgres => {
+            match database_exists(cx, conn, name).await {
+                Outcome::Ok(true) => return Outcome::Ok(false),
+                Outcome::Ok(false) => {}
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            }
+            let sql = format!("CREATE DATABASE {}", quote(name, dialect));
+            match conn.execute(cx, &sql, &[]).await {
+                Outcome::Ok(_) => Outcome::Ok(true),
+                Outcome::Err(e)
+                    if matches!(e.sqlstate(), Some(DUPLICATE_DATABASE | UNIQUE_VIOLATION)) =>
+                {
+                    Outcome::Ok(false)
+                }
+                Outcome::Err(e) => Outcome::Err(e),
+                Outcome::Cancelled(r) => Outcome::Cancelled(r),
+                Outcome::Panicked(p) => Outcome::Panicked(p),
+            }
+        }
+    }
+}
+
+/// Drop database `name` if it exists.
+///
+/// Returns `false` if there was nothing to drop. PostgreSQL refuses to drop a
+/// database other sessions are connected to, so close pools first.
+pub async fn drop_database<C: Connection>(cx: &Cx, conn: &C, name: &str) -> Outcome<bool, Error> {
+    let dialect = Dialect::from(conn.dialect());
+    if dialect == Dialect::Sqlite {
+        return Outcome::Err(unsupported_on_sqlite("drop_database"));
+    }
+    match database_exists(cx, conn, name).await {
+        Outcome::Ok(true) => {}
+        Outcome::Ok(false) => return Outcome::Ok(false),
+        Outcome::Err(e) => return Outcome::Err(e),
+        Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+        Outcome::Panicked(p) => return Outcome::Panicked(p),
+    }
+    // IF EXISTS covers a concurrent drop between the check and here.
+    let sql = format!("DROP DATABASE IF EXISTS {}", quote(name, dialect));
+    conn.execute(cx, &sql, &[]).await.map(|_| true)
+}
+
+/// Order `tables` so every table comes before the tables it references.
+///
+/// Tables in a foreign key cycle keep their relative order at the end.
+pub fn truncate_order(tables: &[TableInfo]) -> Vec<&TableInfo> {
+    fn referenced(name: &str, by: &[&TableInfo]) -> bool {
+        by.iter()
+            .any(|t| t.name != name && t.foreign_keys.iter().any(|fk| fk.foreign_table == name))
+    }
+
+    let mut remaining: Vec<&TableInfo> = tables.iter().collect();
+    let mut order = Vec::with_capacity(tables.len());
+    while !remaining.is_empty() {
+        let next = remaining
+            .iter()
+            .position(|t| !referenced(&t.name, &remaining))
+            .unwrap_or(0);
+        order.push(remaining.remove(next));
+    }
+    order
+}
+
+/// Statements that empty `tables`, children first.
+///
+/// MySQL refuses to TRUNCATE a referenced table, so foreign key checks are
+/// disabled around the batch. SQLite has no TRUNCATE and uses DELETE.
+pub fn truncate_sql(dialect: Dialect, tables: &[TableInfo]) -> Vec<String> {
+    let names: Vec<String> = truncate_order(tables)
+        .iter()
+        .map(|t| quote(&t.name, dialect))
+        .collect();
+    if names.is_empty() {
+        return Vec::new();
+    }
+    match dialect {
+        Dialect::Postgres => vec![format!(
+            "TRUNCATE TABLE {} RESTART IDENTITY",
+            names.join(", ")
+        )],
+        Dialect::Mysql => {
+            let mut sql = vec!["SET FOREIGN_KEY_CHECKS = 0".to_string()];
+            sql.extend(names.iter().map(|n| format!("TRUNCATE TABLE {n}")));
+            sql.push("SET FOREIGN_KEY_CHECKS = 1".to_string());
+            sql
+        }
+        Dialect::Sqlite => names.iter().map(|n| format!("DELETE FROM {n}")).collect(),
+    }
+}
+
+async fn execute_all<C: Connection>(
+    cx: &Cx,
+    conn: &C,
+    statements: &[String],
+) -> Outcome<(), Error> {
+    for sql in statements {
+        match conn.execute(cx, sql, &[]).await {
+            Outcome::Ok(_) => {}
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        }
+    }
+    Outcome::Ok(())
+}
+
+/// Delete every row of the tables of `M`, children first.
+///
+/// Identity/auto-increment counters restart as well.
+pub async fn truncate_all<M: ModelTuple, C: Connection>(cx: &Cx, conn: &C) -> Outcome<(), Error> {
+    let dialect = Dialect::from(conn.dialect());
+    let tables = M::all_table_schemas();
+    let statements = truncate_sql(dialect, &tables);
+
+    let outcome = execute_all(cx, conn, &statements).await;
+    if dialect == Dialect::Mysql && !matches!(outcome, Outcome::Ok(())) {
+        // Don't leave the session with foreign key checks disabled.
+        let _ = conn.execute(cx, "SET FOREIGN_KEY_CHECKS = 1", &[]).await;
+        return outcome;
+    }
+    if dialect != Dialect::Sqlite || !matches!(outcome, Outcome::Ok(())) {
+        return outcome;
+    }
+
+    // SQLite keeps AUTOINCREMENT counters in sqlite_sequence, which only
+    // exists once some table uses AUTOINCREMENT.
+    let has_sequences = match conn
+        .query(
+            cx,
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'sqlite_sequence'",
+            &[],
+        )
+        .await
+    {
+        Outcome::Ok(rows) => !rows.is_empty(),
+        Outcome::Err(e) => return Outcome::Err(e),
+        Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+        Outcome::Panicked(p) => return Outcome::Panicked(p),
+    };
+    if !has_sequences {
+        return Outcome::Ok(());
+    }
+    let names: Vec<Value> = tables.iter().map(|t| Value::Text(t.name.clone())).collect();
+    let placeholders = vec!["?"; names.len()].join(", ");
+    let sql = format!("DELETE FROM sqlite_sequence WHERE name IN ({placeholders})");
+    conn.execute(cx, &sql, &names).await.map(|_| ())
+}
+
+/// A table maintenance operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Maintenance {
+    /// Refresh planner statistics.
+    Analyze,
+    /// Reclaim space left by deleted rows.
+    Vacuum,
+    /// Rebuild indexes.
+    Reindex,
+}
+
+/// The statement performing `op` on `table`, or on the whole database when
+/// `table` is `None`.
+///
+/// | op        | PostgreSQL        | SQLite          | MySQL (table only) |
+/// |-----------|-------------------|-----------------|--------------------|
+/// | `Analyze` | `ANALYZE [t]`     | `ANALYZE [t]`   | `ANALYZE TABLE t`  |
+/// | `Vacuum`  | `VACUUM [t]`      | `VACUUM` (database only) | `OPTIMIZE TABLE t` |
+/// | `Reindex` | `REINDEX TABLE t` (table only) | `REINDEX [t]` | `OPTIMIZE TABLE t` |
+///
+/// MySQL has no REINDEX; `OPTIMIZE TABLE` rebuilds the table and its indexes.
+#[allow(clippy::result_large_err)]
+pub fn maintenance_sql(
+    dialect: Dialect,
+    op: Maintenance,
+    table: Option<&str>,
+) -> Result<String, Error> {
+    let quoted = table.map(|t| quote(t, dialect));
+    let sql = match (op, dialect, quoted) {
+        (Maintenance::Analyze, Dialect::Postgres | Dialect::Sqlite, t) => {
+            t.map_or_else(|| "ANALYZE".to_string(), |t| format!("ANALYZE {t}"))
+        }
+        (Maintenance::Analyze, Dialect::Mysql, Some(t)) => format!("ANALYZE TABLE {t}"),
+        (Maintenance::Vacuum, Dialect::Postgres, t) => {
+            t.map_or_else(|| "VACUUM".to_string(), |t| format!("VACUUM {t}"))
+        }
+        (Maintenance::Vacuum, Dialect::Sqlite, None) => "VACUUM".to_string(),
+        (Maintenance::Vacuum | Maintenance::Reindex, Dialect::Mysql, Some(t)) => {
+            format!("OPTIMIZE TABLE {t}")
+        }
+        (Maintenance::Reindex, Dialect::Postgres, Some(t)) => format!("REINDEX TABLE {t}"),
+        (Maintenance::Reindex, Dialect::Sqlite, t) => {
+            t.map_or_else(|| "REINDEX".to_string(), |t| format!("REINDEX {t}"))
+        }
+        (op, dialect, table) => {
+            let scope = if table.is_some() {
+                "a single table"
+            } else {
+                "the whole database"
+            };
+            return Err(Error::Custom(format!(
+                "{op:?} on {scope} is not supported on {dialect:?}"
+            )));
+        }
+    };
+    Ok(sql)
+}
+
+async fn maintain<C: Connection>(
+    cx: &Cx,
+    conn: &C,
+    op: Maintenance,
+    table: Option<&str>,
+) -> Outcome<(), Error> {
+    match maintenance_sql(Dialect::from(conn.dialect()), op, table) {
+        Ok(sql) => conn.execute(cx, &sql, &[]).await.map(|_| ()),
+        Err(e) => Outcome::Err(e),
+    }
+}
+
+/// Refresh planner statistics for `table`, or every table when `None`.
+pub async fn analyze<C: Connection>(cx: &Cx, conn: &C, table: Option<&str>) -> Outcome<(), Error> {
+    maintain(cx, conn, Maintenance::Analyze, table).await
+}
+
+/// Reclaim space in `table`, or the whole database when `None`.
+///
+/// PostgreSQL and SQLite refuse to `VACUUM` inside a transaction; run this on
+/// a connection without one open.
+pub async fn vacuum<C: Connection>(cx: &Cx, conn: &C, table: Option<&str>) -> Outcome<(), Error> {
+    maintain(cx, conn, Maintenance::Vacuum, table).await
+}
+
+/// Rebuild the indexes of `table`, or of every table when `None`.
+pub async fn reindex<C: Connection>(cx: &Cx, conn: &C, table: Option<&str>) -> Outcome<(), Error> {
+    maintain(cx, conn, Maintenance::Reindex, table).await
+}
+
+/// `CREATE TABLE` statements for the `tables` not in `existing`, parents
+/// first.
+pub fn create_missing_sql(
+    dialect: impl IntoSqlDialect,
+    tables: &[TableInfo],
+    existing: &[String],
+) -> Vec<String> {
+    let generator = generator_for_dialect(dialect);
+    truncate_order(tables)
+        .into_iter()
+        .rev()
+        .filter(|t| !existing.contains(&t.name))
+        .flat_map(|t| generator.generate(&SchemaOperation::CreateTable(t.clone())))
+        .collect()
+}
+
+/// Create the tables of every model in `registry` that do not exist yet,
+/// parents first, and return their names.
+///
+/// This is the runtime counterpart of [`reset`]'s table creation for tools
+/// that pick their models at startup (admin panels, the REPL, plugins).
+pub async fn create_registered<C: Connection>(
+    cx: &Cx,
+    conn: &C,
+    registry: &ModelRegistry,
+) -> Outcome<Vec<String>, Error> {
+    let dialect = Dialect::from(conn.dialect());
+    let existing = match Introspector::new(dialect).table_names(cx, conn).await {
+        Outcome::Ok(names) => names,
+        Outcome::Err(e) => return Outcome::Err(e),
+        Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+        Outcome::Panicked(p) => return Outcome::Panicked(p),
+    };
+
+    let tables = registry_table_schemas(registry);
+    let created: Vec<String> = registry
+        .table_names()
+        .into_iter()
+        .filter(|name| !existing.iter().any(|e| e == name))
+        .map(String::from)
+        .collect();
+    let missing = create_missing_sql(conn.sql_dialect(), &tables, &existing);
+    execute_all(cx, conn, &missing).await.map(|()| created)
+}
+
+/// Bring the database to an empty copy of the tables of `M`.
+///
+/// Missing tables are created (parents first), then every table is emptied
+/// with [`truncate_all`]. Tables that are not part of `M` are left alone.
+pub async fn reset<M: ModelTuple, C: Connection>(cx: &Cx, conn: &C) -> Outcome<(), Error> {
+    let dialect = Dialect::from(conn.dialect());
+    let existing = match Introspector::new(dialect).table_names(cx, conn).await {
+        Outcome::Ok(names) => names,
+        Outcome::Err(e) => return Outcome::Err(e),
+        Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+        Outcome::Panicked(p) => return Outcome::Panicked(p),
+    };
+
+    let missing = create_missing_sql(conn.sql_dialect(), &M::all_table_schemas(), &existing);
+    match execute_all(cx, conn, &missing).await {
+        Outcome::Ok(()) => {}
+        Outcome::Err(e) => return Outcome::Err(e),
+        Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+        Outcome::Panicked(p) => return Outcome::Panicked(p),
+    }
+
+    truncate_all::<M, C>(cx, conn).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::introspect::ForeignKeyInfo;
+
+    fn table(name: &str, references: &[&str]) -> TableInfo {
+        TableInfo {
+            name: name.to_string(),
+            columns: Vec::new(),
+            primary_key: Vec::new(),
+            foreign_keys: references
+                .iter()
+                .map(|parent| ForeignKeyInfo {
+                    name: None,
+                    column: format!("{parent}_id"),
+                    foreign_table: (*parent).to_string(),
+                    foreign_column: "id".to_string(),
+                    on_delete: None,
+                    on_update: None,
+                })
+                .collect(),
+            unique_constraints: Vec::new(),
+            check_constraints: Vec::new(),
+            indexes: Vec::new(),
+            comment: None,
+        }
+    }
+
+    #[test]
+    fn test_truncate_order_children_first() {
+        let tables = vec![
+            table("teams", &[]),
+            table("heroes", &["teams"]),
+            table("powers", &["heroes", "teams"]),
+            table("employees", &["employees"]),
+        ];
+        let order: Vec<&str> = truncate_order(&tables)
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(order, ["powers", "heroes", "teams", "employees"]);
+    }
+
+    #[test]
+    fn test_truncate_order_keeps_cycles() {
+        let tables = vec![table("a", &["b"]), table("b", &["a"]), table("c", &["a"])];
+        let order: Vec<&str> = truncate_order(&tables)
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(order, ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn test_truncate_sql_per_dialect() {
+        let tables = vec![table("teams", &[]), table("heroes", &["teams"])];
+        assert_eq!(
+            truncate_sql(Dialect::Postgres, &tables),
+            ["TRUNCATE TABLE \"heroes\", \"teams\" RESTART IDENTITY"]
+        );
+        assert_eq!(
+            truncate_sql(Dialect::Mysql, &tables),
+            [
+                "SET FOREIGN_KEY_CHECKS = 0",
+                "TRUNCATE TABLE `heroes`",
+                "TRUNCATE TABLE `teams`",
+                "SET FOREIGN_KEY_CHECKS = 1",
+            ]
+        );
+        assert_eq!(
+            truncate_sql(Dialect::Sqlite, &tables),
+            ["DELETE FROM \"heroes\"", "DELETE FROM \"teams\""]
+        );
+        assert!(truncate_sql(Dialect::Sqlite, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_maintenance_sql_per_dialect() {
+        let sql = |dialect, op, table| maintenance_sql(dialect, op, table).ok();
+        assert_eq!(
+            sql(Dialect::Postgres, Maintenance::Analyze, None).as_deref(),
+            Some("ANALYZE")
+        );
+        assert_eq!(
+            sql(Dialect::Postgres, Maintenance::Vacuum, Some("heroes")).as_deref(),
+            Some("VACUUM \"heroes\"")
+        );
+        assert_eq!(
+            sql(Dialect::Postgres, Maintenance::Reindex, Some("heroes")).as_deref(),
+            Some("REINDEX TABLE \"heroes\"")
+        );
+        assert_eq!(
+            sql(Dialect::Sqlite, Maintenance::Reindex, None).as_deref(),
+            Some("REINDEX")
+        );
+        assert_eq!(
+            sql(Dialect::Mysql, Maintenance::Analyze, Some("heroes")).as_deref(),
+            Some("ANALYZE TABLE `heroes`")
+        );
+        assert_eq!(
+            sql(Dialect::Mysql, Maintenance::Reindex, Some("heroes")).as_deref(),
+            Some("OPTIMIZE TABLE `heroes`")
+        );
+        assert!(sql(Dialect::Sqlite, Maintenance::Vacuum, Some("heroes")).is_none());
+        assert!(sql(Dialect::Mysql, Maintenance::Vacuum, None).is_none());
+        assert!(sql(Dialect::Postgres, Maintenance::Reindex, None).is_none());
+    }
+
+    #[test]
+    fn test_create_missing_sql_parents_first_and_skips_existing() {
+        let tables = vec![
+            table("powers", &["heroes"]),
+            table("heroes", &["teams"]),
+            table("teams", &[]),
+        ];
+        let sql = create_missing_sql(Dialect::Sqlite, &tables, &["teams".to_string()]);
+        assert_eq!(sql.len(), 2);
+        assert!(sql[0].contains("\"heroes\""), "{}", sql[0]);
+        assert!(sql[1].contains("\"powers\""), "{}", sql[1]);
+    }
+}
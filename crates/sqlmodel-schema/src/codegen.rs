@@ -0,0 +1,554 @@
+//! Model source generation from an existing database (db-first workflow).
+//!
+//! [`generate_models`] turns an introspected [`DatabaseSchema`] into Rust
+//! source with one `#[derive(Model)]` struct per table: primary keys,
+//! auto-increment, nullability, single-column unique constraints, defaults
+//! and foreign keys become field attributes. With
+//! [`CodegenOptions::relationships`] each foreign key also gets a
+//! `Related<Parent>` field on the child and a `RelatedMany<Child>` field on the
+//! parent.
+//!
+//! Column types map onto the Rust types the drivers decode directly. Types
+//! without a native mapping (dates, times, arrays, vendor types) become
+//! [`Value`](sqlmodel_core::Value) fields that keep their SQL type in
+//! `sql_type`, so the generated code compiles as-is and can be refined by hand.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let source = codegen::generate_models_from_db(&cx, &conn, &CodegenOptions::default()).await?;
+//! std::fs::write("src/models.rs", source)?;
+//! ```
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+
+use asupersync::{Cx, Outcome};
+use sqlmodel_core::{Connection, Error};
+
+use crate::introspect::{ColumnInfo, DatabaseSchema, Dialect, Introspector, TableInfo};
+
+/// Options for [`generate_models`].
+#[derive(Debug, Clone)]
+pub struct CodegenOptions {
+    /// Tables to generate; empty means every table in the database.
+    pub tables: Vec<String>,
+    /// Emit `Related` / `RelatedMany` fields for foreign keys.
+    pub relationships: bool,
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        Self {
+            tables: Vec::new(),
+            relationships: true,
+        }
+    }
+}
+
+/// Introspect the database behind `conn` and generate its models.
+pub async fn generate_models_from_db<C: Connection>(
+    cx: &Cx,
+    conn: &C,
+    options: &CodegenOptions,
+) -> Outcome<String, Error> {
+    let introspector = Introspector::new(Dialect::from(conn.dialect()));
+    match introspector.introspect_all(cx, conn).await {
+        Outcome::Ok(schema) => Outcome::Ok(generate_models(&schema, options)),
+        Outcome::Err(e) => Outcome::Err(e),
+        Outcome::Cancelled(r) => Outcome::Cancelled(r),
+        Outcome::Panicked(p) => Outcome::Panicked(p),
+    }
+}
+
+/// Rust source declaring a model for each selected table of `schema`.
+///
+/// Tables are emitted in name order. Relationship fields only point at tables
+/// that are generated too.
+pub fn generate_models(schema: &DatabaseSchema, options: &CodegenOptions) -> String {
+    let mut tables: Vec<&TableInfo> = schema
+        .tables
+        .values()
+        .filter(|t| options.tables.is_empty() || options.tables.contains(&t.name))
+        .collect();
+    tables.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut taken = HashSet::new();
+    let names: Vec<(&str, String)> = tables
+        .iter()
+        .map(|t| {
+            let mut name = pascal_case(&singular(&t.name));
+            if !taken.insert(name.clone()) {
+                name = pascal_case(&t.name);
+                taken.insert(name.clone());
+            }
+            (t.name.as_str(), name)
+        })
+        .collect();
+    let struct_name = |table: &str| {
+        names
+            .iter()
+            .find(|(t, _)| *t == table)
+            .map(|(_, n)| n.as_str())
+    };
+
+    let uses_relationships = options.relationships
+        && tables.iter().any(|t| {
+            t.foreign_keys
+                .iter()
+                .any(|fk| struct_name(&fk.foreign_table).is_some())
+        });
+
+    let mut out = String::from("//! Models generated from the database schema.\n\n");
+    out.push_str("use sqlmodel::prelude::*;\n");
+    if uses_relationships {
+        out.push_str("use sqlmodel_core::{Related, RelatedMany};\n");
+    }
+
+    for table in &tables {
+        let name = struct_name(&table.name).unwrap_or_default();
+        out.push('\n');
+        write_model(&mut out, schema.dialect, table, name);
+
+        let mut fields: HashSet<String> =
+            table.columns.iter().map(|c| field_name(&c.name)).collect();
+        let mut relationships = String::new();
+        if options.relationships {
+            // Many-to-one: this table's foreign keys
+            for fk in &table.foreign_keys {
+                let Some(parent) = struct_name(&fk.foreign_table) else {
+                    continue;
+                };
+                let base = fk
+                    .column
+                    .strip_suffix("_id")
+                    .map_or_else(|| singular(&fk.foreign_table), str::to_string);
+                let field = unique_field(&mut fields, &field_name(&base), &fk.column);
+                let _ = writeln!(
+                    relationships,
+                    "    #[sqlmodel(relationship(model = {:?}, foreign_key = {:?}))]\n    pub {field}: Related<{parent}>,",
+                    fk.foreign_table, fk.column
+                );
+            }
+            // One-to-many: other tables' foreign keys pointing here
+            for child in &tables {
+                for fk in child
+                    .foreign_keys
+                    .iter()
+                    .filter(|fk| fk.foreign_table == table.name)
+                {
+                    let child_name = struct_name(&child.name).unwrap_or_default();
+                    let field = unique_field(&mut fields, &field_name(&child.name), &fk.column);
+                    let _ = writeln!(
+                        relationships,
+                        "    #[sqlmodel(relationship(model = {:?}, remote_key = {:?}))]\n    pub {field}: RelatedMany<{child_name}>,",
+                        child.name, fk.column
+                    );
+                }
+            }
+        }
+        if !relationships.is_empty() {
+            out.push('\n');
+            out.push_str(&relationships);
+        }
+        out.push_str("}\n");
+    }
+    out
+}
+
+/// Write the struct header and column fields of `table` (without the closing brace).
+fn write_model(out: &mut String, dialect: Dialect, table: &TableInfo, name: &str) {
+    if let Some(comment) = &table.comment {
+        for line in comment.lines() {
+            let _ = writeln!(out, "/// {line}");
+        }
+    }
+    out.push_str("#[derive(Model, Debug, Clone)]\n");
+    let _ = writeln!(out, "#[sqlmodel(table = {:?})]", table.name);
+    let _ = writeln!(out, "pub struct {name} {{");
+
+    for (i, column) in table.columns.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        if let Some(comment) = &column.comment {
+            for line in comment.lines() {
+                let _ = writeln!(out, "    /// {line}");
+            }
+        }
+        let (ty, attrs) = column_field(dialect, table, column);
+        if !attrs.is_empty() {
+            let _ = writeln!(out, "    #[sqlmodel({})]", attrs.join(", "));
+        }
+        let _ = writeln!(out, "    pub {}: {ty},", field_name(&column.name));
+    }
+}
+
+/// Rust type and `#[sqlmodel(...)]` attributes for `column`.
+fn column_field(dialect: Dialect, table: &TableInfo, column: &ColumnInfo) -> (String, Vec<String>) {
+    let (base, keep_sql_type) = rust_type(dialect, column);
+    let primary_key = column.primary_key || table.primary_key.contains(&column.name);
+    let optional = column.nullable || (primary_key && column.auto_increment);
+    let ty = if optional {
+        format!("Option<{base}>")
+    } else {
+        base.to_string()
+    };
+
+    let mut attrs = Vec::new();
+    if primary_key {
+        attrs.push("primary_key".to_string());
+    }
+    if column.auto_increment {
+        attrs.push("auto_increment".to_string());
+    }
+    if field_name(&column.name) != column.name {
+        attrs.push(format!("column = {:?}", column.name));
+    }
+    if column.nullable && !primary_key {
+        attrs.push("nullable".to_string());
+    }
+    if !primary_key && is_unique(table, &column.name) {
+        attrs.push("unique".to_string());
+    }
+    if keep_sql_type {
+        attrs.push(format!("sql_type = {:?}", column.sql_type));
+    }
+    if let Some(default) = column
+        .default
+        .as_deref()
+        .filter(|d| !column.auto_increment && !d.to_ascii_lowercase().starts_with("nextval("))
+    {
+        attrs.push(format!("default = {default:?}"));
+    }
+    if let Some(fk) = table
+        .foreign_keys
+        .iter()
+        .find(|fk| fk.column == column.name)
+    {
+        let target = format!("{}.{}", fk.foreign_table, fk.foreign_column);
+        attrs.push(format!("foreign_key = {target:?}"));
+        for (key, action) in [("on_delete", &fk.on_delete), ("on_update", &fk.on_update)] {
+            if let Some(action) = action
+                .as_deref()
+                .filter(|a| !a.eq_ignore_ascii_case("NO ACTION"))
+            {
+                attrs.push(format!("{key} = {:?}", action.to_uppercase()));
+            }
+        }
+    }
+    (ty, attrs)
+}
+
+/// The Rust type for `column`, and whether the field should keep the
+/// column's SQL type because the Rust type alone does not imply it.
+fn rust_type(dialect: Dialect, column: &ColumnInfo) -> (&'static str, bool) {
+    let parsed = &column.parsed_type;
+    if parsed.array {
+        return ("Value", true);
+    }
+    let unsigned = parsed.unsigned;
+    match parsed.base_type.as_str() {
+        "BOOLEAN" | "BOOL" => ("bool", false),
+        "TINYINT" if dialect == Dialect::Mysql && parsed.length == Some(1) => ("bool", false),
+        "TINYINT" if unsigned => ("u8", false),
+        "TINYINT" => ("i8", false),
+        "SMALLINT" | "INT2" | "SMALLSERIAL" if unsigned => ("u16", false),
+        "SMALLINT" | "INT2" | "SMALLSERIAL" => ("i16", false),
+        // SQLite integers are always 64-bit
+        "INTEGER" | "INT" if dialect == Dialect::Sqlite => ("i64", false),
+        "INTEGER" | "INT" | "INT4" | "MEDIUMINT" | "SERIAL" if unsigned => ("u32", false),
+        "INTEGER" | "INT" | "INT4" | "MEDIUMINT" | "SERIAL" => ("i32", false),
+        "BIGINT" | "INT8" | "BIGSERIAL" if unsigned => ("u64", false),
+        "BIGINT" | "INT8" | "BIGSERIAL" => ("i64", false),
+        "REAL" if dialect == Dialect::Sqlite => ("f64", false),
+        "FLOAT" if dialect != Dialect::Mysql => ("f64", false),
+        "REAL" | "FLOAT4" | "FLOAT" => ("f32", false),
+        "DOUBLE" | "DOUBLE PRECISION" | "FLOAT8" => ("f64", false),
+        "DECIMAL" | "NUMERIC" => ("String", true),
+        "TEXT" | "CLOB" | "TINYTEXT" | "MEDIUMTEXT" | "LONGTEXT" => ("String", false),
+        // Kept as `sql_type = "CITEXT"` so the model stays case-insensitive
+        "CITEXT" => ("String", true),
+        "VARCHAR" | "CHAR" | "CHARACTER" | "CHARACTER VARYING" | "NVARCHAR" | "NCHAR" => {
+            ("String", parsed.length.is_some())
+        }
+        "BYTEA" | "BLOB" | "TINYBLOB" | "MEDIUMBLOB" | "LONGBLOB" | "BINARY" | "VARBINARY" => {
+            ("Vec<u8>", false)
+        }
+        "JSON" | "JSONB" => ("serde_json::Value", false),
+        "UUID" => ("[u8; 16]", true),
+        _ => ("Value", true),
+    }
+}
+
+/// Whether `column` alone is covered by a unique constraint or unique index.
+fn is_unique(table: &TableInfo, column: &str) -> bool {
+    table
+        .unique_constraints
+        .iter()
+        .any(|u| u.columns.len() == 1 && u.columns[0] == column)
+        || table
+            .indexes
+            .iter()
+            .any(|i| i.unique && !i.primary && i.columns.len() == 1 && i.columns[0] == column)
+}
+
+/// Claim a field name not yet used in the struct, qualifying `base` with the
+/// foreign key column on collision.
+fn unique_field(fields: &mut HashSet<String>, base: &str, fk_column: &str) -> String {
+    let mut name = base.to_string();
+    if fields.contains(&name) {
+        name = format!("{base}_by_{}", field_name(fk_column));
+    }
+    while fields.contains(&name) {
+        name.push('_');
+    }
+    fields.insert(name.clone());
+    name
+}
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// A snake_case Rust field name for column `name`.
+fn field_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut prev_lower = false;
+    for ch in name.chars() {
+        if ch.is_ascii_alphanumeric() {
+            if ch.is_ascii_uppercase() && prev_lower {
+                out.push('_');
+            }
+            prev_lower = ch.is_ascii_lowercase() || ch.is_ascii_digit();
+            out.push(ch.to_ascii_lowercase());
+        } else {
+            if !out.ends_with('_') {
+                out.push('_');
+            }
+            prev_lower = false;
+        }
+    }
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert_str(0, "c_");
+    }
+    if KEYWORDS.contains(&out.as_str()) {
+        out.push('_');
+    }
+    out
+}
+
+/// A PascalCase type name for table `name`.
+fn pascal_case(name: &str) -> String {
+    let mut out: String = field_name(name)
+        .trim_end_matches('_')
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_ascii_uppercase().to_string() + chars.as_str()
+            })
+        })
+        .collect();
+    if out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, 'T');
+    }
+    out
+}
+
+/// Best-effort English singular of a table name (`heroes` -> `hero`).
+fn singular(name: &str) -> String {
+    let lower = name.to_ascii_lowercase();
+    if lower.ends_with("ies") && name.len() > 3 {
+        return format!("{}y", &name[..name.len() - 3]);
+    }
+    let cut = if ["ches", "shes", "sses", "xes", "zes", "oes"]
+        .iter()
+        .any(|suffix| lower.ends_with(suffix))
+    {
+        2
+    } else if lower.ends_with('s') && !lower.ends_with("ss") && !lower.ends_with("us") {
+        1
+    } else {
+        0
+    };
+    name[..name.len() - cut].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::introspect::{ForeignKeyInfo, ParsedSqlType, UniqueConstraintInfo};
+
+    fn column(name: &str, sql_type: &str, nullable: bool) -> ColumnInfo {
+        ColumnInfo {
+            name: name.to_string(),
+            sql_type: sql_type.to_string(),
+            parsed_type: ParsedSqlType::parse(sql_type),
+            nullable,
+            default: None,
+            primary_key: false,
+            auto_increment: false,
+            comment: None,
+            collation: None,
+            charset: None,
+        }
+    }
+
+    fn table(name: &str, columns: Vec<ColumnInfo>) -> TableInfo {
+        TableInfo {
+            name: name.to_string(),
+            columns,
+            primary_key: vec!["id".to_string()],
+            foreign_keys: Vec::new(),
+            unique_constraints: Vec::new(),
+            check_constraints: Vec::new(),
+            indexes: Vec::new(),
+            comment: None,
+        }
+    }
+
+    fn id() -> ColumnInfo {
+        ColumnInfo {
+            primary_key: true,
+            auto_increment: true,
+            ..column("id", "bigint", false)
+        }
+    }
+
+    fn heroes_schema() -> DatabaseSchema {
+        let mut teams = table("teams", vec![id(), column("name", "varchar(100)", false)]);
+        teams.unique_constraints.push(UniqueConstraintInfo {
+            name: None,
+            columns: vec!["name".to_string()],
+        });
+        let mut heroes = table(
+            "heroes",
+            vec![
+                id(),
+                column("secretName", "text", false),
+                column("type", "text", false),
+                ColumnInfo {
+                    default: Some("0".to_string()),
+                    ..column("age", "integer", true)
+                },
+                column("team_id", "bigint", true),
+                column("born_at", "timestamp with time zone", true),
+            ],
+        );
+        heroes.foreign_keys.push(ForeignKeyInfo {
+            name: None,
+            column: "team_id".to_string(),
+            foreign_table: "teams".to_string(),
+            foreign_column: "id".to_string(),
+            on_delete: Some("SET NULL".to_string()),
+            on_update: Some("NO ACTION".to_string()),
+        });
+
+        let mut schema = DatabaseSchema::new(Dialect::Postgres);
+        schema.tables.insert("teams".to_string(), teams);
+        schema.tables.insert("heroes".to_string(), heroes);
+        schema
+    }
+
+    #[test]
+    fn test_generate_models_with_relationships() {
+        let source = generate_models(&heroes_schema(), &CodegenOptions::default());
+        assert_eq!(
+            source,
+            r#"//! Models generated from the database schema.
+
+use sqlmodel::prelude::*;
+use sqlmodel_core::{Related, RelatedMany};
+
+#[derive(Model, Debug, Clone)]
+#[sqlmodel(table = "heroes")]
+pub struct Hero {
+    #[sqlmodel(primary_key, auto_increment)]
+    pub id: Option<i64>,
+
+    #[sqlmodel(column = "secretName")]
+    pub secret_name: String,
+
+    #[sqlmodel(column = "type")]
+    pub type_: String,
+
+    #[sqlmodel(nullable, default = "0")]
+    pub age: Option<i32>,
+
+    #[sqlmodel(nullable, foreign_key = "teams.id", on_delete = "SET NULL")]
+    pub team_id: Option<i64>,
+
+    #[sqlmodel(nullable, sql_type = "timestamp with time zone")]
+    pub born_at: Option<Value>,
+
+    #[sqlmodel(relationship(model = "teams", foreign_key = "team_id"))]
+    pub team: Related<Team>,
+}
+
+#[derive(Model, Debug, Clone)]
+#[sqlmodel(table = "teams")]
+pub struct Team {
+    #[sqlmodel(primary_key, auto_increment)]
+    pub id: Option<i64>,
+
+    #[sqlmodel(unique, sql_type = "varchar(100)")]
+    pub name: String,
+
+    #[sqlmodel(relationship(model = "heroes", remote_key = "team_id"))]
+    pub heroes: RelatedMany<Hero>,
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_generate_models_table_filter_drops_dangling_relationships() {
+        let options = CodegenOptions {
+            tables: vec!["heroes".to_string()],
+            relationships: true,
+        };
+        let source = generate_models(&heroes_schema(), &options);
+        assert!(source.contains("pub struct Hero {"));
+        assert!(!source.contains("pub struct Team"));
+        assert!(!source.contains("Related"));
+        assert!(source.contains("foreign_key = \"teams.id\""));
+    }
+
+    #[test]
+    fn test_rust_type_mapping_per_dialect() {
+        let ty = |dialect, sql_type| rust_type(dialect, &column("c", sql_type, false)).0;
+        assert_eq!(ty(Dialect::Sqlite, "INTEGER"), "i64");
+        assert_eq!(ty(Dialect::Postgres, "integer"), "i32");
+        assert_eq!(ty(Dialect::Mysql, "tinyint(1)"), "bool");
+        assert_eq!(ty(Dialect::Mysql, "int unsigned"), "u32");
+        assert_eq!(ty(Dialect::Sqlite, "REAL"), "f64");
+        assert_eq!(ty(Dialect::Postgres, "real"), "f32");
+        assert_eq!(ty(Dialect::Postgres, "numeric(10,2)"), "String");
+        assert_eq!(ty(Dialect::Postgres, "bytea"), "Vec<u8>");
+        assert_eq!(ty(Dialect::Postgres, "jsonb"), "serde_json::Value");
+        assert_eq!(ty(Dialect::Postgres, "uuid"), "[u8; 16]");
+        assert_eq!(ty(Dialect::Postgres, "text[]"), "Value");
+        assert_eq!(ty(Dialect::Postgres, "date"), "Value");
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(singular("heroes"), "hero");
+        assert_eq!(singular("categories"), "category");
+        assert_eq!(singular("boxes"), "box");
+        assert_eq!(singular("status"), "status");
+        assert_eq!(singular("address"), "address");
+        assert_eq!(pascal_case("order_items"), "OrderItems");
+        assert_eq!(pascal_case(&singular("order_items")), "OrderItem");
+        assert_eq!(field_name("firstName"), "first_name");
+        assert_eq!(field_name("Unit Price"), "unit_price");
+        assert_eq!(field_name("2fa"), "c_2fa");
+        assert_eq!(field_name("match"), "match_");
+    }
+}
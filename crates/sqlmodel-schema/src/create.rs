@@ -1,8 +1,16 @@
 //! CREATE TABLE statement builder.
 
-use sqlmodel_core::{FieldInfo, InheritanceStrategy, Model, quote_ident};
+use sqlmodel_core::dialect::IntoSqlDialect;
+use sqlmodel_core::{
+    FieldInfo, HISTORY_OPERATION_COLUMN, HISTORY_VALID_TO_COLUMN, InheritanceStrategy, Model,
+    SqlType, TableNamespace, quote_ident,
+};
 use std::marker::PhantomData;
 
+use crate::ddl::generator_for_dialect;
+use crate::diff::SchemaOperation;
+use crate::expected::table_schema_from_model;
+
 /// Builder for CREATE TABLE statements.
 #[derive(Debug)]
 pub struct CreateTable<M: Model> {
@@ -121,6 +129,16 @@ impl<M: Model> CreateTable<M> {
             }
         }
 
+        // Composite unique constraints from `#[sqlmodel(unique_together(...))]`
+        for unique in M::UNIQUE_CONSTRAINTS {
+            let quoted_cols: Vec<String> = unique.columns.iter().map(|c| quote_ident(c)).collect();
+            constraints.push(format!(
+                "CONSTRAINT {} UNIQUE ({})",
+                quote_ident(&unique.constraint_name(M::TABLE_NAME)),
+                quoted_cols.join(", ")
+            ));
+        }
+
         // For joined table inheritance child models, add FK to parent table
         if inheritance.strategy == InheritanceStrategy::Joined {
             if let Some(parent_table) = inheritance.parent {
@@ -164,9 +182,145 @@ impl<M: Model> CreateTable<M> {
         sql.push_str(&all_parts.join(",\n  "));
         sql.push_str("\n)");
 
+        // MariaDB keeps row history itself for `#[sqlmodel(system_versioned)]`
+        if M::SYSTEM_VERSIONED {
+            sql.push_str(" WITH SYSTEM VERSIONING");
+        }
+
         sql
     }
 
+    /// Build the statements creating the model's history table, if it has one.
+    ///
+    /// The history table from `#[sqlmodel(history_table = "...")]` repeats the
+    /// model's columns without keys or NOT NULL constraints (a row appears once
+    /// per change) and adds the validity and operation columns written by
+    /// session flush. It is indexed on the primary key and validity time, which
+    /// is how `Session::as_of` looks rows up.
+    pub fn build_history(&self) -> Vec<String> {
+        let Some(history_table) = M::HISTORY_TABLE else {
+            return Vec::new();
+        };
+        if Self::should_skip_table_creation() {
+            return Vec::new();
+        }
+
+        let if_not_exists = if self.if_not_exists {
+            "IF NOT EXISTS "
+        } else {
+            ""
+        };
+
+        let mut column_defs: Vec<String> = M::fields()
+            .iter()
+            .map(|field| {
+                format!(
+                    "  {} {}",
+                    quote_ident(field.column_name),
+                    field.effective_sql_type()
+                )
+            })
+            .collect();
+        column_defs.push(format!(
+            "  {} {} NOT NULL",
+            quote_ident(HISTORY_VALID_TO_COLUMN),
+            SqlType::TimestampTz.sql_name()
+        ));
+        column_defs.push(format!(
+            "  {} {} NOT NULL",
+            quote_ident(HISTORY_OPERATION_COLUMN),
+            SqlType::VarChar(16).sql_name()
+        ));
+
+        let mut lookup_cols: Vec<String> = M::PRIMARY_KEY.iter().map(|c| quote_ident(c)).collect();
+        lookup_cols.push(quote_ident(HISTORY_VALID_TO_COLUMN));
+
+        vec![
+            format!(
+                "CREATE TABLE {}{} (\n{}\n)",
+                if_not_exists,
+                quote_ident(history_table),
+                column_defs.join(",\n")
+            ),
+            format!(
+                "CREATE INDEX {}{} ON {} ({})",
+                if_not_exists,
+                quote_ident(&format!("idx_{history_table}_{HISTORY_VALID_TO_COLUMN}")),
+                quote_ident(history_table),
+                lookup_cols.join(", ")
+            ),
+        ]
+    }
+
+    /// Build the CREATE INDEX statements for the model's declared indexes.
+    ///
+    /// Indexes come from `#[sqlmodel(index(...))]` and are emitted as separate
+    /// statements because CREATE TABLE cannot declare (partial) indexes portably.
+    pub fn build_indexes(&self) -> Vec<String> {
+        if Self::should_skip_table_creation() {
+            return Vec::new();
+        }
+
+        M::INDEXES
+            .iter()
+            .map(|index| {
+                let quoted_cols: Vec<String> =
+                    index.columns.iter().map(|c| quote_ident(c)).collect();
+                let mut stmt = format!(
+                    "CREATE {}INDEX {}{} ON {} ({})",
+                    if index.unique { "UNIQUE " } else { "" },
+                    if self.if_not_exists {
+                        "IF NOT EXISTS "
+                    } else {
+                        ""
+                    },
+                    quote_ident(&index.index_name(M::TABLE_NAME)),
+                    quote_ident(M::TABLE_NAME),
+                    quoted_cols.join(", ")
+                );
+                if let Some(predicate) = index.where_clause {
+                    stmt.push_str(" WHERE ");
+                    stmt.push_str(predicate);
+                }
+                stmt
+            })
+            .collect()
+    }
+
+    /// Build the statements that attach the model's table and column comments.
+    ///
+    /// Comments come from `#[sqlmodel(comment = "...")]` and field-level
+    /// `column_comment`. PostgreSQL gets `COMMENT ON` statements and MySQL
+    /// `ALTER TABLE` statements; SQLite has no comments, so nothing is emitted.
+    /// The dialect-aware creation paths (`admin::create_registered`, schema
+    /// diffs) include comments on their own.
+    pub fn build_comments(&self, dialect: impl IntoSqlDialect) -> Vec<String> {
+        if Self::should_skip_table_creation() {
+            return Vec::new();
+        }
+
+        let table = table_schema_from_model::<M>();
+        let mut ops = Vec::new();
+        if table.comment.is_some() {
+            ops.push(SchemaOperation::SetTableComment {
+                table: table.name.clone(),
+                from_comment: None,
+                to_comment: table.comment.clone(),
+            });
+        }
+        for column in &table.columns {
+            if column.comment.is_some() {
+                ops.push(SchemaOperation::SetColumnComment {
+                    table: table.name.clone(),
+                    column: column.clone(),
+                    from_comment: None,
+                    to_comment: column.comment.clone(),
+                });
+            }
+        }
+        generator_for_dialect(dialect).generate_all(&ops)
+    }
+
     /// Check if this model should skip table creation.
     ///
     /// Returns true for single table inheritance child models, which
@@ -191,6 +345,11 @@ impl<M: Model> CreateTable<M> {
         def.push(' ');
         def.push_str(&sql_type);
 
+        if let Some(collation) = field.collation {
+            def.push_str(" COLLATE ");
+            def.push_str(&quote_ident(collation));
+        }
+
         if embed_primary_key {
             def.push_str(" PRIMARY KEY");
         } else if !field.nullable && !field.auto_increment {
@@ -215,7 +374,8 @@ impl<M: Model> Default for CreateTable<M> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use sqlmodel_core::{FieldInfo, Row, SqlType, Value};
+    use crate::introspect::Dialect;
+    use sqlmodel_core::{FieldInfo, IndexDef, Row, SqlType, UniqueConstraintDef, Value};
 
     // Test model for CREATE TABLE generation
     struct TestHero;
@@ -223,6 +383,7 @@ mod tests {
     impl Model for TestHero {
         const TABLE_NAME: &'static str = "heroes";
         const PRIMARY_KEY: &'static [&'static str] = &["id"];
+        const TABLE_COMMENT: Option<&'static str> = Some("Registered heroes");
 
         fn fields() -> &'static [FieldInfo] {
             static FIELDS: &[FieldInfo] = &[
@@ -230,7 +391,9 @@ mod tests {
                     .nullable(true)
                     .primary_key(true)
                     .auto_increment(true),
-                FieldInfo::new("name", "name", SqlType::Text).unique(true),
+                FieldInfo::new("name", "name", SqlType::Text)
+                    .unique(true)
+                    .column_comment("Public alias"),
                 FieldInfo::new("age", "age", SqlType::Integer).nullable(true),
                 FieldInfo::new("team_id", "team_id", SqlType::BigInt)
                     .nullable(true)
@@ -266,6 +429,26 @@ mod tests {
         assert!(sql.contains("\"team_id\" BIGINT"));
     }
 
+    #[test]
+    fn test_create_table_build_comments() {
+        let create = CreateTable::<TestHero>::new();
+        assert!(!create.build().contains("Registered heroes"));
+        assert_eq!(
+            create.build_comments(Dialect::Postgres),
+            [
+                "COMMENT ON TABLE \"heroes\" IS 'Registered heroes'",
+                "COMMENT ON COLUMN \"heroes\".\"name\" IS 'Public alias'",
+            ]
+        );
+        let mysql = create.build_comments(Dialect::Mysql);
+        assert_eq!(
+            mysql[0],
+            "ALTER TABLE `heroes` COMMENT = 'Registered heroes'"
+        );
+        assert!(mysql[1].ends_with("`name` TEXT NOT NULL COMMENT 'Public alias'"));
+        assert!(create.build_comments(Dialect::Sqlite).is_empty());
+    }
+
     #[test]
     fn test_create_table_if_not_exists() {
         let sql = CreateTable::<TestHero>::new().if_not_exists().build();
@@ -324,6 +507,145 @@ mod tests {
         assert!(statements[0].contains("CREATE UNIQUE INDEX"));
     }
 
+    struct TestMember;
+
+    impl Model for TestMember {
+        const TABLE_NAME: &'static str = "members";
+        const PRIMARY_KEY: &'static [&'static str] = &["id"];
+        const UNIQUE_CONSTRAINTS: &'static [UniqueConstraintDef] =
+            &[UniqueConstraintDef::new(&["org_id", "email"])];
+        const INDEXES: &'static [IndexDef] = &[
+            IndexDef::new(&["org_id", "created_at"]),
+            IndexDef::new(&["email"])
+                .unique(true)
+                .name("uq_members_live_email")
+                .where_clause("deleted_at IS NULL"),
+        ];
+
+        fn fields() -> &'static [FieldInfo] {
+            static FIELDS: &[FieldInfo] = &[
+                FieldInfo::new("id", "id", SqlType::BigInt).primary_key(true),
+                FieldInfo::new("org_id", "org_id", SqlType::BigInt),
+                FieldInfo::new("email", "email", SqlType::Text).collation("und-x-icu"),
+                FieldInfo::new("created_at", "created_at", SqlType::BigInt),
+                FieldInfo::new("deleted_at", "deleted_at", SqlType::BigInt).nullable(true),
+            ];
+            FIELDS
+        }
+
+        fn to_row(&self) -> Vec<(&'static str, Value)> {
+            vec![]
+        }
+
+        fn from_row(_row: &Row) -> sqlmodel_core::Result<Self> {
+            Ok(TestMember)
+        }
+
+        fn primary_key_value(&self) -> Vec<Value> {
+            vec![]
+        }
+
+        fn is_new(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_create_table_unique_together() {
+        let sql = CreateTable::<TestMember>::new().build();
+        assert!(
+            sql.contains("CONSTRAINT \"uk_members_org_id_email\" UNIQUE (\"org_id\", \"email\")")
+        );
+    }
+
+    #[test]
+    fn test_create_table_column_collation() {
+        let sql = CreateTable::<TestMember>::new().build();
+        assert!(sql.contains("\"email\" TEXT COLLATE \"und-x-icu\" NOT NULL"));
+    }
+
+    #[test]
+    fn test_schema_builder_emits_model_indexes() {
+        let statements = SchemaBuilder::new().create_table::<TestMember>().build();
+        assert_eq!(statements.len(), 3);
+        assert_eq!(
+            statements[1],
+            "CREATE INDEX IF NOT EXISTS \"idx_members_org_id_created_at\" ON \"members\" (\"org_id\", \"created_at\")"
+        );
+        assert_eq!(
+            statements[2],
+            "CREATE UNIQUE INDEX IF NOT EXISTS \"uq_members_live_email\" ON \"members\" (\"email\") WHERE deleted_at IS NULL"
+        );
+    }
+
+    struct TestAuditedHero;
+
+    impl Model for TestAuditedHero {
+        const TABLE_NAME: &'static str = "heroes";
+        const PRIMARY_KEY: &'static [&'static str] = &["id"];
+        const HISTORY_TABLE: Option<&'static str> = Some("heroes_history");
+
+        fn fields() -> &'static [FieldInfo] {
+            static FIELDS: &[FieldInfo] = &[
+                FieldInfo::new("id", "id", SqlType::BigInt).primary_key(true),
+                FieldInfo::new("name", "name", SqlType::Text).unique(true),
+            ];
+            FIELDS
+        }
+
+        fn to_row(&self) -> Vec<(&'static str, Value)> {
+            vec![]
+        }
+
+        fn from_row(_row: &Row) -> sqlmodel_core::Result<Self> {
+            Ok(TestAuditedHero)
+        }
+
+        fn primary_key_value(&self) -> Vec<Value> {
+            vec![]
+        }
+
+        fn is_new(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_schema_builder_emits_history_table() {
+        let statements = SchemaBuilder::new()
+            .create_table::<TestAuditedHero>()
+            .build();
+        assert_eq!(statements.len(), 3);
+        assert_eq!(
+            statements[1],
+            "CREATE TABLE IF NOT EXISTS \"heroes_history\" (\n  \"id\" BIGINT,\n  \"name\" TEXT,\n  \"history_valid_to\" TIMESTAMPTZ NOT NULL,\n  \"history_operation\" VARCHAR(16) NOT NULL\n)"
+        );
+        assert_eq!(
+            statements[2],
+            "CREATE INDEX IF NOT EXISTS \"idx_heroes_history_history_valid_to\" ON \"heroes_history\" (\"id\", \"history_valid_to\")"
+        );
+        assert!(CreateTable::<TestHero>::new().build_history().is_empty());
+    }
+
+    #[test]
+    fn test_schema_builder_namespace_prefixes_tables_and_indexes() {
+        let statements = SchemaBuilder::new()
+            .create_table::<TestAuditedHero>()
+            .namespace(TableNamespace::new().prefix("t1_").schema("analytics"))
+            .build();
+        assert!(
+            statements[0].starts_with("CREATE TABLE IF NOT EXISTS \"analytics\".\"t1_heroes\" (")
+        );
+        assert!(
+            statements[1]
+                .starts_with("CREATE TABLE IF NOT EXISTS \"analytics\".\"t1_heroes_history\" (")
+        );
+        assert_eq!(
+            statements[2],
+            "CREATE INDEX IF NOT EXISTS \"t1_idx_heroes_history_history_valid_to\" ON \"analytics\".\"t1_heroes_history\" (\"id\", \"history_valid_to\")"
+        );
+    }
+
     #[test]
     fn test_schema_builder_raw_sql() {
         let statements = SchemaBuilder::new()
@@ -1217,6 +1539,8 @@ mod tests {
 #[derive(Debug, Default)]
 pub struct SchemaBuilder {
     statements: Vec<String>,
+    namespace: Option<TableNamespace>,
+    tables: Vec<&'static str>,
 }
 
 impl SchemaBuilder {
@@ -1231,6 +1555,8 @@ impl SchemaBuilder {
     /// statements for the child-specific fields, since the child's logical table is the
     /// parent's physical table.
     pub fn create_table<M: Model>(mut self) -> Self {
+        self.tables.push(M::TABLE_NAME);
+        self.tables.extend(M::HISTORY_TABLE);
         if CreateTable::<M>::should_skip_table_creation() {
             let inheritance = M::inheritance();
             let Some(parent_table) = inheritance.parent else {
@@ -1249,8 +1575,10 @@ impl SchemaBuilder {
             return self;
         }
 
-        self.statements
-            .push(CreateTable::<M>::new().if_not_exists().build());
+        let create = CreateTable::<M>::new().if_not_exists();
+        self.statements.push(create.build());
+        self.statements.extend(create.build_indexes());
+        self.statements.extend(create.build_history());
         self
     }
 
@@ -1275,9 +1603,29 @@ impl SchemaBuilder {
         self
     }
 
+    /// Create tables under `namespace` (a table prefix and/or schema).
+    ///
+    /// Tables added with [`create_table`](Self::create_table) are registered
+    /// with the namespace automatically; tables only referenced by
+    /// [`raw`](Self::raw) or [`create_index`](Self::create_index) must be
+    /// registered on it. The schema itself must already exist.
+    pub fn namespace(mut self, namespace: TableNamespace) -> Self {
+        self.namespace = Some(namespace);
+        self
+    }
+
     /// Get all SQL statements.
     pub fn build(self) -> Vec<String> {
+        let Some(mut namespace) = self.namespace else {
+            return self.statements;
+        };
+        for table in self.tables {
+            namespace.register(table);
+        }
         self.statements
+            .iter()
+            .map(|sql| namespace.rewrite(sql).into_owned())
+            .collect()
     }
 }
 
@@ -1290,6 +1638,11 @@ fn alter_table_add_column(table: &str, field: &FieldInfo) -> String {
         sql_type
     );
 
+    if let Some(collation) = field.collation {
+        stmt.push_str(" COLLATE ");
+        stmt.push_str(&quote_ident(collation));
+    }
+
     if !field.nullable && !field.auto_increment {
         stmt.push_str(" NOT NULL");
     }
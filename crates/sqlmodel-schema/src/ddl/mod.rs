@@ -11,6 +11,8 @@ pub use mysql::MysqlDdlGenerator;
 pub use postgres::PostgresDdlGenerator;
 pub use sqlite::SqliteDdlGenerator;
 
+use sqlmodel_core::dialect::{IntoSqlDialect, SqlDialect};
+
 use crate::diff::SchemaOperation;
 use crate::introspect::{
     ColumnInfo, Dialect, ForeignKeyInfo, IndexInfo, TableInfo, UniqueConstraintInfo,
@@ -45,11 +47,49 @@ pub trait DdlGenerator {
 }
 
 /// Create a DDL generator for the given dialect.
-pub fn generator_for_dialect(dialect: Dialect) -> Box<dyn DdlGenerator> {
-    match dialect {
-        Dialect::Sqlite => Box::new(SqliteDdlGenerator),
-        Dialect::Mysql => Box::new(MysqlDdlGenerator),
-        Dialect::Postgres => Box::new(PostgresDdlGenerator),
+///
+/// Accepts a built-in [`Dialect`] or any [`SqlDialect`], such as the one
+/// returned by [`Connection::sql_dialect`](sqlmodel_core::Connection::sql_dialect).
+/// Statements follow the rules of the dialect's base family, with
+/// identifiers quoted by the dialect itself.
+pub fn generator_for_dialect(dialect: impl IntoSqlDialect) -> Box<dyn DdlGenerator> {
+    Box::new(DialectDdlGenerator::new(dialect))
+}
+
+/// DDL generator driven by a [`SqlDialect`].
+#[derive(Clone, Copy)]
+pub struct DialectDdlGenerator {
+    dialect: &'static dyn SqlDialect,
+}
+
+impl DialectDdlGenerator {
+    /// Create a generator for `dialect`.
+    pub fn new(dialect: impl IntoSqlDialect) -> Self {
+        Self {
+            dialect: dialect.into_sql_dialect(),
+        }
+    }
+}
+
+impl std::fmt::Debug for DialectDdlGenerator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DialectDdlGenerator")
+            .field("dialect", &self.dialect.name())
+            .finish()
+    }
+}
+
+impl DdlGenerator for DialectDdlGenerator {
+    fn dialect(&self) -> &'static str {
+        self.dialect.name()
+    }
+
+    fn generate(&self, op: &SchemaOperation) -> Vec<String> {
+        match family(self.dialect) {
+            Dialect::Sqlite => SqliteDdlGenerator::generate_with(self.dialect, op),
+            Dialect::Mysql => MysqlDdlGenerator::generate_with(self.dialect, op),
+            Dialect::Postgres => PostgresDdlGenerator::generate_with(self.dialect, op),
+        }
     }
 }
 
@@ -57,21 +97,81 @@ pub fn generator_for_dialect(dialect: Dialect) -> Box<dyn DdlGenerator> {
 // Shared Helpers
 // ============================================================================
 
+/// The built-in family whose DDL rules `dialect` follows.
+fn family(dialect: &dyn SqlDialect) -> Dialect {
+    Dialect::from(dialect.base_dialect())
+}
+
 /// Quote an identifier (table/column name) for SQL.
+fn quote_identifier(name: &str, dialect: &dyn SqlDialect) -> String {
+    dialect.quote_identifier(name)
+}
+
+/// Quote a string literal (comment text) for SQL.
 ///
-/// Different dialects use different quote characters:
-/// - SQLite/PostgreSQL: double quotes
-/// - MySQL: backticks
-fn quote_identifier(name: &str, dialect: Dialect) -> String {
-    match dialect {
-        Dialect::Mysql => format!("`{}`", name.replace('`', "``")),
-        Dialect::Sqlite | Dialect::Postgres => format!("\"{}\"", name.replace('"', "\"\"")),
+/// MySQL additionally treats backslashes as escapes inside literals.
+fn quote_literal(value: &str, dialect: &'static dyn SqlDialect) -> String {
+    let escaped = value.replace('\'', "''");
+    match family(dialect) {
+        Dialect::Mysql => format!("'{}'", escaped.replace('\\', "\\\\")),
+        Dialect::Sqlite | Dialect::Postgres => format!("'{}'", escaped),
     }
 }
 
+/// Generate PostgreSQL `COMMENT ON TABLE` / `COMMENT ON COLUMN` SQL.
+///
+/// `None` removes the comment.
+fn generate_comment_on(
+    dialect: &'static dyn SqlDialect,
+    table: &str,
+    column: Option<&str>,
+    comment: Option<&str>,
+) -> String {
+    let target = match column {
+        Some(column) => format!(
+            "COLUMN {}.{}",
+            quote_identifier(table, dialect),
+            quote_identifier(column, dialect)
+        ),
+        None => format!("TABLE {}", quote_identifier(table, dialect)),
+    };
+    let value = comment.map_or_else(|| "NULL".to_string(), |c| quote_literal(c, dialect));
+    format!("COMMENT ON {} IS {}", target, value)
+}
+
+/// Whether `sql_type` is PostgreSQL's case-insensitive `CITEXT`.
+fn is_citext(sql_type: &str) -> bool {
+    sql_type.eq_ignore_ascii_case("CITEXT")
+}
+
 /// Format a column definition for CREATE TABLE or ADD COLUMN.
-fn format_column_def(col: &ColumnInfo, dialect: Dialect) -> String {
-    let mut parts = vec![quote_identifier(&col.name, dialect), col.sql_type.clone()];
+fn format_column_def(col: &ColumnInfo, dialect: &'static dyn SqlDialect) -> String {
+    // Outside PostgreSQL, CITEXT becomes TEXT under a case-insensitive collation
+    let (sql_type, collation) = match family(dialect) {
+        Dialect::Mysql | Dialect::Sqlite if is_citext(&col.sql_type) => {
+            let collation = col
+                .collation
+                .clone()
+                .unwrap_or_else(|| match family(dialect) {
+                    Dialect::Mysql => col.charset.as_ref().map_or_else(
+                        || "utf8mb4_unicode_ci".to_string(),
+                        |charset| format!("{charset}_general_ci"),
+                    ),
+                    _ => "NOCASE".to_string(),
+                });
+            ("TEXT".to_string(), Some(collation))
+        }
+        _ => (col.sql_type.clone(), col.collation.clone()),
+    };
+    let mut parts = vec![quote_identifier(&col.name, dialect), sql_type];
+
+    // Only MySQL has per-column character sets
+    if let (Dialect::Mysql, Some(charset)) = (family(dialect), &col.charset) {
+        parts.push(format!("CHARACTER SET {}", charset));
+    }
+    if let Some(collation) = collation {
+        parts.push(format!("COLLATE {}", quote_identifier(&collation, dialect)));
+    }
 
     if !col.nullable {
         parts.push("NOT NULL".to_string());
@@ -82,7 +182,7 @@ fn format_column_def(col: &ColumnInfo, dialect: Dialect) -> String {
     }
 
     // Auto-increment handling varies by dialect
-    match dialect {
+    match family(dialect) {
         Dialect::Sqlite => {
             // SQLite: INTEGER PRIMARY KEY implies AUTOINCREMENT
             // Explicit AUTOINCREMENT keyword is rarely needed
@@ -91,6 +191,10 @@ fn format_column_def(col: &ColumnInfo, dialect: Dialect) -> String {
             if col.auto_increment {
                 parts.push("AUTO_INCREMENT".to_string());
             }
+            // MySQL keeps column comments in the column definition
+            if let Some(ref comment) = col.comment {
+                parts.push(format!("COMMENT {}", quote_literal(comment, dialect)));
+            }
         }
         Dialect::Postgres => {
             // PostgreSQL uses SERIAL types or GENERATED AS IDENTITY
@@ -113,7 +217,7 @@ fn format_referential_action(action: Option<&String>) -> &str {
 }
 
 /// Format a foreign key constraint clause.
-fn format_fk_constraint(fk: &ForeignKeyInfo, dialect: Dialect) -> String {
+fn format_fk_constraint(fk: &ForeignKeyInfo, dialect: &'static dyn SqlDialect) -> String {
     let mut sql = format!(
         "FOREIGN KEY ({}) REFERENCES {}({})",
         quote_identifier(&fk.column, dialect),
@@ -135,7 +239,10 @@ fn format_fk_constraint(fk: &ForeignKeyInfo, dialect: Dialect) -> String {
 }
 
 /// Format a unique constraint clause.
-fn format_unique_constraint(unique: &UniqueConstraintInfo, dialect: Dialect) -> String {
+fn format_unique_constraint(
+    unique: &UniqueConstraintInfo,
+    dialect: &'static dyn SqlDialect,
+) -> String {
     let cols: Vec<String> = unique
         .columns
         .iter()
@@ -159,15 +266,11 @@ fn format_unique_constraint(unique: &UniqueConstraintInfo, dialect: Dialect) ->
 /// strict create without IF NOT EXISTS for table recreation).
 fn generate_create_table_with_if_not_exists(
     table: &TableInfo,
-    dialect: Dialect,
+    dialect: &'static dyn SqlDialect,
     if_not_exists: bool,
 ) -> String {
     tracing::debug!(
-        dialect = %match dialect {
-            Dialect::Sqlite => "sqlite",
-            Dialect::Mysql => "mysql",
-            Dialect::Postgres => "postgres",
-        },
+        dialect = %dialect.name(),
         table = %table.name,
         columns = table.columns.len(),
         "Generating CREATE TABLE DDL"
@@ -202,24 +305,30 @@ fn generate_create_table_with_if_not_exists(
 
     let table_name = quote_identifier(&table.name, dialect);
     let ine = if if_not_exists { " IF NOT EXISTS" } else { "" };
-    let sql = format!(
+    let mut sql = format!(
         "CREATE TABLE{} {} (\n{}\n)",
         ine,
         table_name,
         parts.join(",\n")
     );
 
+    // MySQL takes the table comment as a table option; PostgreSQL needs
+    // separate COMMENT ON statements (see `generate_comment_on`).
+    if let (Dialect::Mysql, Some(comment)) = (family(dialect), &table.comment) {
+        sql.push_str(&format!(" COMMENT={}", quote_literal(comment, dialect)));
+    }
+
     tracing::trace!(sql = %sql, "Generated CREATE TABLE statement");
     sql
 }
 
 /// Generate CREATE TABLE SQL (defaulting to `IF NOT EXISTS`).
-fn generate_create_table(table: &TableInfo, dialect: Dialect) -> String {
+fn generate_create_table(table: &TableInfo, dialect: &'static dyn SqlDialect) -> String {
     generate_create_table_with_if_not_exists(table, dialect, true)
 }
 
 /// Generate DROP TABLE SQL.
-fn generate_drop_table(table_name: &str, dialect: Dialect) -> String {
+fn generate_drop_table(table_name: &str, dialect: &'static dyn SqlDialect) -> String {
     tracing::debug!(table = %table_name, "Generating DROP TABLE DDL");
     format!(
         "DROP TABLE IF EXISTS {}",
@@ -228,9 +337,9 @@ fn generate_drop_table(table_name: &str, dialect: Dialect) -> String {
 }
 
 /// Generate RENAME TABLE SQL.
-fn generate_rename_table(from: &str, to: &str, dialect: Dialect) -> String {
+fn generate_rename_table(from: &str, to: &str, dialect: &'static dyn SqlDialect) -> String {
     tracing::debug!(from = %from, to = %to, "Generating RENAME TABLE DDL");
-    match dialect {
+    match family(dialect) {
         Dialect::Sqlite => format!(
             "ALTER TABLE {} RENAME TO {}",
             quote_identifier(from, dialect),
@@ -250,7 +359,11 @@ fn generate_rename_table(from: &str, to: &str, dialect: Dialect) -> String {
 }
 
 /// Generate ADD COLUMN SQL.
-fn generate_add_column(table: &str, column: &ColumnInfo, dialect: Dialect) -> String {
+fn generate_add_column(
+    table: &str,
+    column: &ColumnInfo,
+    dialect: &'static dyn SqlDialect,
+) -> String {
     tracing::debug!(table = %table, column = %column.name, "Generating ADD COLUMN DDL");
     format!(
         "ALTER TABLE {} ADD COLUMN {}",
@@ -260,9 +373,14 @@ fn generate_add_column(table: &str, column: &ColumnInfo, dialect: Dialect) -> St
 }
 
 /// Generate RENAME COLUMN SQL.
-fn generate_rename_column(table: &str, from: &str, to: &str, dialect: Dialect) -> String {
+fn generate_rename_column(
+    table: &str,
+    from: &str,
+    to: &str,
+    dialect: &'static dyn SqlDialect,
+) -> String {
     tracing::debug!(table = %table, from = %from, to = %to, "Generating RENAME COLUMN DDL");
-    match dialect {
+    match family(dialect) {
         Dialect::Sqlite => {
             // SQLite 3.25.0+ supports RENAME COLUMN
             format!(
@@ -288,7 +406,11 @@ fn generate_rename_column(table: &str, from: &str, to: &str, dialect: Dialect) -
 }
 
 /// Generate CREATE INDEX SQL.
-fn generate_create_index(table: &str, index: &IndexInfo, dialect: Dialect) -> String {
+fn generate_create_index(
+    table: &str,
+    index: &IndexInfo,
+    dialect: &'static dyn SqlDialect,
+) -> String {
     tracing::debug!(
         table = %table,
         index = %index.name,
@@ -305,7 +427,7 @@ fn generate_create_index(table: &str, index: &IndexInfo, dialect: Dialect) -> St
         .collect();
 
     // Include index type for databases that support it
-    let using = match dialect {
+    let using = match family(dialect) {
         Dialect::Postgres => {
             if let Some(ref idx_type) = index.index_type {
                 format!(" USING {}", idx_type)
@@ -338,9 +460,9 @@ fn generate_create_index(table: &str, index: &IndexInfo, dialect: Dialect) -> St
 }
 
 /// Generate DROP INDEX SQL.
-fn generate_drop_index(table: &str, index_name: &str, dialect: Dialect) -> String {
+fn generate_drop_index(table: &str, index_name: &str, dialect: &'static dyn SqlDialect) -> String {
     tracing::debug!(table = %table, index = %index_name, "Generating DROP INDEX DDL");
-    match dialect {
+    match family(dialect) {
         Dialect::Sqlite => format!(
             "DROP INDEX IF EXISTS {}",
             quote_identifier(index_name, dialect)
@@ -376,6 +498,8 @@ mod tests {
             primary_key: false,
             auto_increment: false,
             comment: None,
+            collation: None,
+            charset: None,
         }
     }
 
@@ -394,25 +518,37 @@ mod tests {
 
     #[test]
     fn test_quote_identifier_sqlite() {
-        assert_eq!(quote_identifier("name", Dialect::Sqlite), "\"name\"");
-        assert_eq!(quote_identifier("table", Dialect::Sqlite), "\"table\"");
         assert_eq!(
-            quote_identifier("col\"name", Dialect::Sqlite),
+            quote_identifier("name", Dialect::Sqlite.as_dyn()),
+            "\"name\""
+        );
+        assert_eq!(
+            quote_identifier("table", Dialect::Sqlite.as_dyn()),
+            "\"table\""
+        );
+        assert_eq!(
+            quote_identifier("col\"name", Dialect::Sqlite.as_dyn()),
             "\"col\"\"name\""
         );
     }
 
     #[test]
     fn test_quote_identifier_mysql() {
-        assert_eq!(quote_identifier("name", Dialect::Mysql), "`name`");
-        assert_eq!(quote_identifier("table", Dialect::Mysql), "`table`");
-        assert_eq!(quote_identifier("col`name", Dialect::Mysql), "`col``name`");
+        assert_eq!(quote_identifier("name", Dialect::Mysql.as_dyn()), "`name`");
+        assert_eq!(
+            quote_identifier("table", Dialect::Mysql.as_dyn()),
+            "`table`"
+        );
+        assert_eq!(
+            quote_identifier("col`name", Dialect::Mysql.as_dyn()),
+            "`col``name`"
+        );
     }
 
     #[test]
     fn test_format_column_def_basic() {
         let col = make_column("name", "TEXT", false);
-        let def = format_column_def(&col, Dialect::Sqlite);
+        let def = format_column_def(&col, Dialect::Sqlite.as_dyn());
         assert!(def.contains("\"name\""));
         assert!(def.contains("TEXT"));
         assert!(def.contains("NOT NULL"));
@@ -421,7 +557,7 @@ mod tests {
     #[test]
     fn test_format_column_def_nullable() {
         let col = make_column("name", "TEXT", true);
-        let def = format_column_def(&col, Dialect::Sqlite);
+        let def = format_column_def(&col, Dialect::Sqlite.as_dyn());
         assert!(!def.contains("NOT NULL"));
     }
 
@@ -429,7 +565,7 @@ mod tests {
     fn test_format_column_def_with_default() {
         let mut col = make_column("status", "TEXT", false);
         col.default = Some("'active'".to_string());
-        let def = format_column_def(&col, Dialect::Sqlite);
+        let def = format_column_def(&col, Dialect::Sqlite.as_dyn());
         assert!(def.contains("DEFAULT 'active'"));
     }
 
@@ -437,10 +573,45 @@ mod tests {
     fn test_format_column_def_auto_increment_mysql() {
         let mut col = make_column("id", "INT", false);
         col.auto_increment = true;
-        let def = format_column_def(&col, Dialect::Mysql);
+        let def = format_column_def(&col, Dialect::Mysql.as_dyn());
         assert!(def.contains("AUTO_INCREMENT"));
     }
 
+    #[test]
+    fn test_format_column_def_collation_and_charset() {
+        let mut col = make_column("name", "VARCHAR(100)", false);
+        col.collation = Some("utf8mb4_bin".to_string());
+        col.charset = Some("utf8mb4".to_string());
+        assert_eq!(
+            format_column_def(&col, Dialect::Mysql.as_dyn()),
+            "`name` VARCHAR(100) CHARACTER SET utf8mb4 COLLATE `utf8mb4_bin` NOT NULL"
+        );
+
+        // Character sets are MySQL-only
+        col.collation = Some("und-x-icu".to_string());
+        assert_eq!(
+            format_column_def(&col, Dialect::Postgres.as_dyn()),
+            "\"name\" VARCHAR(100) COLLATE \"und-x-icu\" NOT NULL"
+        );
+    }
+
+    #[test]
+    fn test_format_column_def_citext() {
+        let col = make_column("email", "CITEXT", false);
+        assert_eq!(
+            format_column_def(&col, Dialect::Postgres.as_dyn()),
+            "\"email\" CITEXT NOT NULL"
+        );
+        assert_eq!(
+            format_column_def(&col, Dialect::Sqlite.as_dyn()),
+            "\"email\" TEXT COLLATE \"NOCASE\" NOT NULL"
+        );
+        assert_eq!(
+            format_column_def(&col, Dialect::Mysql.as_dyn()),
+            "`email` TEXT COLLATE `utf8mb4_unicode_ci` NOT NULL"
+        );
+    }
+
     #[test]
     fn test_generate_create_table_basic() {
         let table = make_table(
@@ -451,7 +622,7 @@ mod tests {
             ],
             vec!["id"],
         );
-        let sql = generate_create_table(&table, Dialect::Sqlite);
+        let sql = generate_create_table(&table, Dialect::Sqlite.as_dyn());
         assert!(sql.contains("CREATE TABLE IF NOT EXISTS"));
         assert!(sql.contains("\"heroes\""));
         assert!(sql.contains("\"id\""));
@@ -478,7 +649,7 @@ mod tests {
             on_update: None,
         });
 
-        let sql = generate_create_table(&table, Dialect::Sqlite);
+        let sql = generate_create_table(&table, Dialect::Sqlite.as_dyn());
         assert!(sql.contains("FOREIGN KEY"));
         assert!(sql.contains("REFERENCES"));
         assert!(sql.contains("ON DELETE CASCADE"));
@@ -486,27 +657,27 @@ mod tests {
 
     #[test]
     fn test_generate_drop_table() {
-        let sql = generate_drop_table("heroes", Dialect::Sqlite);
+        let sql = generate_drop_table("heroes", Dialect::Sqlite.as_dyn());
         assert_eq!(sql, "DROP TABLE IF EXISTS \"heroes\"");
     }
 
     #[test]
     fn test_generate_rename_table_sqlite() {
-        let sql = generate_rename_table("old_name", "new_name", Dialect::Sqlite);
+        let sql = generate_rename_table("old_name", "new_name", Dialect::Sqlite.as_dyn());
         assert!(sql.contains("ALTER TABLE"));
         assert!(sql.contains("RENAME TO"));
     }
 
     #[test]
     fn test_generate_rename_table_mysql() {
-        let sql = generate_rename_table("old_name", "new_name", Dialect::Mysql);
+        let sql = generate_rename_table("old_name", "new_name", Dialect::Mysql.as_dyn());
         assert!(sql.contains("RENAME TABLE"));
     }
 
     #[test]
     fn test_generate_add_column() {
         let col = make_column("age", "INTEGER", true);
-        let sql = generate_add_column("heroes", &col, Dialect::Sqlite);
+        let sql = generate_add_column("heroes", &col, Dialect::Sqlite.as_dyn());
         assert!(sql.contains("ALTER TABLE"));
         assert!(sql.contains("ADD COLUMN"));
         assert!(sql.contains("\"age\""));
@@ -514,7 +685,8 @@ mod tests {
 
     #[test]
     fn test_generate_rename_column() {
-        let sql = generate_rename_column("heroes", "old_name", "new_name", Dialect::Postgres);
+        let sql =
+            generate_rename_column("heroes", "old_name", "new_name", Dialect::Postgres.as_dyn());
         assert!(sql.contains("ALTER TABLE"));
         assert!(sql.contains("RENAME COLUMN"));
     }
@@ -528,7 +700,7 @@ mod tests {
             index_type: None,
             primary: false,
         };
-        let sql = generate_create_index("heroes", &index, Dialect::Sqlite);
+        let sql = generate_create_index("heroes", &index, Dialect::Sqlite.as_dyn());
         assert!(sql.contains("CREATE INDEX"));
         assert!(sql.contains("\"idx_heroes_name\""));
         assert!(sql.contains("ON \"heroes\""));
@@ -543,19 +715,19 @@ mod tests {
             index_type: None,
             primary: false,
         };
-        let sql = generate_create_index("heroes", &index, Dialect::Sqlite);
+        let sql = generate_create_index("heroes", &index, Dialect::Sqlite.as_dyn());
         assert!(sql.contains("CREATE UNIQUE INDEX"));
     }
 
     #[test]
     fn test_generate_drop_index_sqlite() {
-        let sql = generate_drop_index("heroes", "idx_heroes_name", Dialect::Sqlite);
+        let sql = generate_drop_index("heroes", "idx_heroes_name", Dialect::Sqlite.as_dyn());
         assert_eq!(sql, "DROP INDEX IF EXISTS \"idx_heroes_name\"");
     }
 
     #[test]
     fn test_generate_drop_index_mysql() {
-        let sql = generate_drop_index("heroes", "idx_heroes_name", Dialect::Mysql);
+        let sql = generate_drop_index("heroes", "idx_heroes_name", Dialect::Mysql.as_dyn());
         assert!(sql.contains("DROP INDEX"));
         assert!(sql.contains("ON `heroes`"));
     }
@@ -572,6 +744,38 @@ mod tests {
         assert_eq!(postgres.dialect(), "postgres");
     }
 
+    #[test]
+    fn test_generator_for_custom_dialect() {
+        #[derive(Debug)]
+        struct Bracketed;
+
+        impl SqlDialect for Bracketed {
+            fn name(&self) -> &'static str {
+                "bracketed"
+            }
+
+            fn base_dialect(&self) -> sqlmodel_core::Dialect {
+                sqlmodel_core::Dialect::Sqlite
+            }
+
+            fn quote_identifier(&self, name: &str) -> String {
+                format!("[{name}]")
+            }
+        }
+
+        static BRACKETED: Bracketed = Bracketed;
+
+        let generator = generator_for_dialect(&BRACKETED);
+        assert_eq!(generator.dialect(), "bracketed");
+        assert_eq!(
+            generator.generate(&SchemaOperation::RenameTable {
+                from: "heroes".to_string(),
+                to: "champions".to_string(),
+            }),
+            vec!["ALTER TABLE [heroes] RENAME TO [champions]".to_string()]
+        );
+    }
+
     #[test]
     fn test_referential_action_formatting() {
         assert_eq!(
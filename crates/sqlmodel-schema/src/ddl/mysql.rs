@@ -5,10 +5,11 @@
 use super::{
     DdlGenerator, format_column_def, format_fk_constraint, generate_add_column,
     generate_create_index, generate_create_table, generate_drop_index, generate_drop_table,
-    generate_rename_column, generate_rename_table, quote_identifier,
+    generate_rename_column, generate_rename_table, quote_identifier, quote_literal,
 };
 use crate::diff::SchemaOperation;
 use crate::introspect::Dialect;
+use sqlmodel_core::dialect::SqlDialect;
 
 /// DDL generator for MySQL.
 pub struct MysqlDdlGenerator;
@@ -19,36 +20,46 @@ impl DdlGenerator for MysqlDdlGenerator {
     }
 
     fn generate(&self, op: &SchemaOperation) -> Vec<String> {
+        Self::generate_with(Dialect::Mysql.as_dyn(), op)
+    }
+}
+
+impl MysqlDdlGenerator {
+    /// Generate MySQL DDL for `op`, quoting identifiers through `dialect`.
+    pub(super) fn generate_with(
+        dialect: &'static dyn SqlDialect,
+        op: &SchemaOperation,
+    ) -> Vec<String> {
         tracing::debug!(dialect = "mysql", op = ?op, "Generating DDL");
 
         let statements = match op {
             // Tables
             SchemaOperation::CreateTable(table) => {
-                let mut stmts = vec![generate_create_table(table, Dialect::Mysql)];
+                let mut stmts = vec![generate_create_table(table, dialect)];
                 for idx in &table.indexes {
                     if idx.primary {
                         continue;
                     }
-                    stmts.push(generate_create_index(&table.name, idx, Dialect::Mysql));
+                    stmts.push(generate_create_index(&table.name, idx, dialect));
                 }
                 stmts
             }
             SchemaOperation::DropTable(name) => {
-                vec![generate_drop_table(name, Dialect::Mysql)]
+                vec![generate_drop_table(name, dialect)]
             }
             SchemaOperation::RenameTable { from, to } => {
-                vec![generate_rename_table(from, to, Dialect::Mysql)]
+                vec![generate_rename_table(from, to, dialect)]
             }
 
             // Columns
             SchemaOperation::AddColumn { table, column } => {
-                vec![generate_add_column(table, column, Dialect::Mysql)]
+                vec![generate_add_column(table, column, dialect)]
             }
             SchemaOperation::DropColumn { table, column, .. } => {
                 vec![format!(
                     "ALTER TABLE {} DROP COLUMN {}",
-                    quote_identifier(table, Dialect::Mysql),
-                    quote_identifier(column, Dialect::Mysql)
+                    quote_identifier(table, dialect),
+                    quote_identifier(column, dialect)
                 )]
             }
             SchemaOperation::AlterColumnType {
@@ -60,8 +71,8 @@ impl DdlGenerator for MysqlDdlGenerator {
                 // MySQL uses MODIFY COLUMN for type changes
                 vec![format!(
                     "ALTER TABLE {} MODIFY COLUMN {} {}",
-                    quote_identifier(table, Dialect::Mysql),
-                    quote_identifier(column, Dialect::Mysql),
+                    quote_identifier(table, dialect),
+                    quote_identifier(column, dialect),
                     to_type
                 )]
             }
@@ -77,8 +88,8 @@ impl DdlGenerator for MysqlDdlGenerator {
                 col.nullable = *to_nullable;
                 vec![format!(
                     "ALTER TABLE {} MODIFY COLUMN {}",
-                    quote_identifier(table, Dialect::Mysql),
-                    format_column_def(&col, Dialect::Mysql)
+                    quote_identifier(table, dialect),
+                    format_column_def(&col, dialect)
                 )]
             }
             SchemaOperation::AlterColumnDefault {
@@ -91,38 +102,47 @@ impl DdlGenerator for MysqlDdlGenerator {
                 if let Some(default) = to_default {
                     vec![format!(
                         "ALTER TABLE {} ALTER COLUMN {} SET DEFAULT {}",
-                        quote_identifier(table, Dialect::Mysql),
-                        quote_identifier(column, Dialect::Mysql),
+                        quote_identifier(table, dialect),
+                        quote_identifier(column, dialect),
                         default
                     )]
                 } else {
                     vec![format!(
                         "ALTER TABLE {} ALTER COLUMN {} DROP DEFAULT",
-                        quote_identifier(table, Dialect::Mysql),
-                        quote_identifier(column, Dialect::Mysql)
+                        quote_identifier(table, dialect),
+                        quote_identifier(column, dialect)
                     )]
                 }
             }
+            SchemaOperation::AlterColumnCollation { table, column, .. } => {
+                // MODIFY COLUMN restates the whole definition, including
+                // CHARACTER SET / COLLATE.
+                vec![format!(
+                    "ALTER TABLE {} MODIFY COLUMN {}",
+                    quote_identifier(table, dialect),
+                    format_column_def(column, dialect)
+                )]
+            }
             SchemaOperation::RenameColumn { table, from, to } => {
-                vec![generate_rename_column(table, from, to, Dialect::Mysql)]
+                vec![generate_rename_column(table, from, to, dialect)]
             }
 
             // Primary Keys
             SchemaOperation::AddPrimaryKey { table, columns, .. } => {
                 let cols: Vec<String> = columns
                     .iter()
-                    .map(|c| quote_identifier(c, Dialect::Mysql))
+                    .map(|c| quote_identifier(c, dialect))
                     .collect();
                 vec![format!(
                     "ALTER TABLE {} ADD PRIMARY KEY ({})",
-                    quote_identifier(table, Dialect::Mysql),
+                    quote_identifier(table, dialect),
                     cols.join(", ")
                 )]
             }
             SchemaOperation::DropPrimaryKey { table, .. } => {
                 vec![format!(
                     "ALTER TABLE {} DROP PRIMARY KEY",
-                    quote_identifier(table, Dialect::Mysql)
+                    quote_identifier(table, dialect)
                 )]
             }
 
@@ -134,16 +154,16 @@ impl DdlGenerator for MysqlDdlGenerator {
                     .unwrap_or_else(|| format!("fk_{}_{}", table, fk.column));
                 vec![format!(
                     "ALTER TABLE {} ADD CONSTRAINT {} {}",
-                    quote_identifier(table, Dialect::Mysql),
-                    quote_identifier(&constraint_name, Dialect::Mysql),
-                    format_fk_constraint(fk, Dialect::Mysql)
+                    quote_identifier(table, dialect),
+                    quote_identifier(&constraint_name, dialect),
+                    format_fk_constraint(fk, dialect)
                 )]
             }
             SchemaOperation::DropForeignKey { table, name, .. } => {
                 vec![format!(
                     "ALTER TABLE {} DROP FOREIGN KEY {}",
-                    quote_identifier(table, Dialect::Mysql),
-                    quote_identifier(name, Dialect::Mysql)
+                    quote_identifier(table, dialect),
+                    quote_identifier(name, dialect)
                 )]
             }
 
@@ -154,7 +174,7 @@ impl DdlGenerator for MysqlDdlGenerator {
                 let cols: Vec<String> = constraint
                     .columns
                     .iter()
-                    .map(|c| quote_identifier(c, Dialect::Mysql))
+                    .map(|c| quote_identifier(c, dialect))
                     .collect();
                 let name = constraint
                     .name
@@ -162,8 +182,8 @@ impl DdlGenerator for MysqlDdlGenerator {
                     .unwrap_or_else(|| format!("uk_{}_{}", table, constraint.columns.join("_")));
                 vec![format!(
                     "ALTER TABLE {} ADD CONSTRAINT {} UNIQUE ({})",
-                    quote_identifier(table, Dialect::Mysql),
-                    quote_identifier(&name, Dialect::Mysql),
+                    quote_identifier(table, dialect),
+                    quote_identifier(&name, dialect),
                     cols.join(", ")
                 )]
             }
@@ -171,18 +191,42 @@ impl DdlGenerator for MysqlDdlGenerator {
                 // MySQL drops unique constraints via DROP INDEX
                 vec![format!(
                     "ALTER TABLE {} DROP INDEX {}",
-                    quote_identifier(table, Dialect::Mysql),
-                    quote_identifier(name, Dialect::Mysql)
+                    quote_identifier(table, dialect),
+                    quote_identifier(name, dialect)
                 )]
             }
 
             // Indexes
             SchemaOperation::CreateIndex { table, index } => {
-                vec![generate_create_index(table, index, Dialect::Mysql)]
+                vec![generate_create_index(table, index, dialect)]
             }
             SchemaOperation::DropIndex { table, name } => {
-                vec![generate_drop_index(table, name, Dialect::Mysql)]
+                vec![generate_drop_index(table, name, dialect)]
             }
+
+            // Comments
+            SchemaOperation::SetTableComment {
+                table, to_comment, ..
+            } => {
+                // An empty comment is how MySQL removes one
+                vec![format!(
+                    "ALTER TABLE {} COMMENT = {}",
+                    quote_identifier(table, dialect),
+                    quote_literal(to_comment.as_deref().unwrap_or(""), dialect)
+                )]
+            }
+            SchemaOperation::SetColumnComment { table, column, .. } => {
+                // Like nullability, a column comment is part of the full column definition
+                vec![format!(
+                    "ALTER TABLE {} MODIFY COLUMN {}",
+                    quote_identifier(table, dialect),
+                    format_column_def(column, dialect)
+                )]
+            }
+
+            // Triggers
+            SchemaOperation::CreateTrigger(trigger) => trigger.build(dialect),
+            SchemaOperation::DropTrigger(trigger) => trigger.build_drop(dialect),
         };
 
         for stmt in &statements {
@@ -215,6 +259,8 @@ mod tests {
             primary_key: false,
             auto_increment: false,
             comment: None,
+            collation: None,
+            charset: None,
         }
     }
 
@@ -523,6 +569,65 @@ mod tests {
         assert!(stmts[0].contains("ON `heroes`"));
     }
 
+    #[test]
+    fn test_create_table_inlines_comments() {
+        let ddl = MysqlDdlGenerator;
+        let mut name = make_column("name", "VARCHAR(100)", false);
+        name.comment = Some("Hero's alias".to_string());
+        let mut table = make_table("heroes", vec![name], vec![]);
+        table.comment = Some("C:\\heroes".to_string());
+        let stmts = ddl.generate(&SchemaOperation::CreateTable(table));
+
+        assert_eq!(stmts.len(), 1);
+        assert!(stmts[0].contains("`name` VARCHAR(100) NOT NULL COMMENT 'Hero''s alias'"));
+        assert!(stmts[0].ends_with(") COMMENT='C:\\\\heroes'"));
+    }
+
+    #[test]
+    fn test_set_comments() {
+        let ddl = MysqlDdlGenerator;
+        let stmts = ddl.generate(&SchemaOperation::SetTableComment {
+            table: "heroes".to_string(),
+            from_comment: Some("old".to_string()),
+            to_comment: None,
+        });
+        assert_eq!(stmts, ["ALTER TABLE `heroes` COMMENT = ''"]);
+
+        let mut column = make_column("name", "VARCHAR(100)", true);
+        column.comment = Some("alias".to_string());
+        let stmts = ddl.generate(&SchemaOperation::SetColumnComment {
+            table: "heroes".to_string(),
+            column,
+            from_comment: None,
+            to_comment: Some("alias".to_string()),
+        });
+        assert_eq!(
+            stmts,
+            ["ALTER TABLE `heroes` MODIFY COLUMN `name` VARCHAR(100) COMMENT 'alias'"]
+        );
+    }
+
+    #[test]
+    fn test_alter_column_collation() {
+        let ddl = MysqlDdlGenerator;
+        let mut column = make_column("name", "VARCHAR(100)", false);
+        column.charset = Some("utf8mb4".to_string());
+        column.collation = Some("utf8mb4_bin".to_string());
+        let stmts = ddl.generate(&SchemaOperation::AlterColumnCollation {
+            table: "heroes".to_string(),
+            column,
+            from_collation: Some("utf8mb4_0900_ai_ci".to_string()),
+            from_charset: Some("utf8mb4".to_string()),
+            table_info: None,
+        });
+        assert_eq!(
+            stmts,
+            [
+                "ALTER TABLE `heroes` MODIFY COLUMN `name` VARCHAR(100) CHARACTER SET utf8mb4 COLLATE `utf8mb4_bin` NOT NULL"
+            ]
+        );
+    }
+
     #[test]
     fn test_dialect() {
         let ddl = MysqlDdlGenerator;
@@ -3,12 +3,16 @@
 //! PostgreSQL has excellent ALTER TABLE support with fine-grained control over schema changes.
 
 use super::{
-    DdlGenerator, format_fk_constraint, generate_add_column, generate_create_index,
-    generate_create_table, generate_drop_index, generate_drop_table, generate_rename_column,
-    generate_rename_table, quote_identifier,
+    DdlGenerator, format_fk_constraint, generate_add_column, generate_comment_on,
+    generate_create_index, generate_create_table, generate_drop_index, generate_drop_table,
+    generate_rename_column, generate_rename_table, is_citext, quote_identifier,
 };
 use crate::diff::SchemaOperation;
 use crate::introspect::Dialect;
+use sqlmodel_core::dialect::SqlDialect;
+
+/// `CITEXT` lives in an extension that has to be installed before use.
+const CREATE_CITEXT_EXTENSION: &str = "CREATE EXTENSION IF NOT EXISTS citext";
 
 /// DDL generator for PostgreSQL.
 pub struct PostgresDdlGenerator;
@@ -19,36 +23,81 @@ impl DdlGenerator for PostgresDdlGenerator {
     }
 
     fn generate(&self, op: &SchemaOperation) -> Vec<String> {
+        Self::generate_with(Dialect::Postgres.as_dyn(), op)
+    }
+}
+
+impl PostgresDdlGenerator {
+    /// Generate PostgreSQL DDL for `op`, quoting identifiers through `dialect`.
+    pub(super) fn generate_with(
+        dialect: &'static dyn SqlDialect,
+        op: &SchemaOperation,
+    ) -> Vec<String> {
         tracing::debug!(dialect = "postgres", op = ?op, "Generating DDL");
 
         let statements = match op {
             // Tables
             SchemaOperation::CreateTable(table) => {
-                let mut stmts = vec![generate_create_table(table, Dialect::Postgres)];
+                let mut stmts = Vec::new();
+                if table.columns.iter().any(|c| is_citext(&c.sql_type)) {
+                    stmts.push(CREATE_CITEXT_EXTENSION.to_string());
+                }
+                stmts.push(generate_create_table(table, dialect));
                 for idx in &table.indexes {
                     if idx.primary {
                         continue;
                     }
-                    stmts.push(generate_create_index(&table.name, idx, Dialect::Postgres));
+                    stmts.push(generate_create_index(&table.name, idx, dialect));
+                }
+                if let Some(comment) = &table.comment {
+                    stmts.push(generate_comment_on(
+                        dialect,
+                        &table.name,
+                        None,
+                        Some(comment),
+                    ));
+                }
+                for col in &table.columns {
+                    if let Some(comment) = &col.comment {
+                        stmts.push(generate_comment_on(
+                            dialect,
+                            &table.name,
+                            Some(&col.name),
+                            Some(comment),
+                        ));
+                    }
                 }
                 stmts
             }
             SchemaOperation::DropTable(name) => {
-                vec![generate_drop_table(name, Dialect::Postgres)]
+                vec![generate_drop_table(name, dialect)]
             }
             SchemaOperation::RenameTable { from, to } => {
-                vec![generate_rename_table(from, to, Dialect::Postgres)]
+                vec![generate_rename_table(from, to, dialect)]
             }
 
             // Columns
             SchemaOperation::AddColumn { table, column } => {
-                vec![generate_add_column(table, column, Dialect::Postgres)]
+                let mut stmts = Vec::new();
+                if is_citext(&column.sql_type) {
+                    stmts.push(CREATE_CITEXT_EXTENSION.to_string());
+                }
+                stmts.push(generate_add_column(table, column, dialect));
+                if let Some(comment) = &column.comment {
+                    stmts.push(generate_comment_on(
+                        dialect,
+                        table,
+                        Some(&column.name),
+                        Some(comment),
+                    ));
+                }
+                stmts
             }
             SchemaOperation::DropColumn { table, column, .. } => {
                 vec![format!(
                     "ALTER TABLE {} DROP COLUMN {}",
-                    quote_identifier(table, Dialect::Postgres),
-                    quote_identifier(column, Dialect::Postgres)
+                    quote_identifier(table, dialect),
+                    quote_identifier(column, dialect)
                 )]
             }
             SchemaOperation::AlterColumnType {
@@ -61,8 +110,8 @@ impl DdlGenerator for PostgresDdlGenerator {
                 // USING clause may be needed for type conversion
                 vec![format!(
                     "ALTER TABLE {} ALTER COLUMN {} TYPE {}",
-                    quote_identifier(table, Dialect::Postgres),
-                    quote_identifier(column, Dialect::Postgres),
+                    quote_identifier(table, dialect),
+                    quote_identifier(column, dialect),
                     to_type
                 )]
             }
@@ -80,8 +129,8 @@ impl DdlGenerator for PostgresDdlGenerator {
                 };
                 vec![format!(
                     "ALTER TABLE {} ALTER COLUMN {} {}",
-                    quote_identifier(table, Dialect::Postgres),
-                    quote_identifier(&column.name, Dialect::Postgres),
+                    quote_identifier(table, dialect),
+                    quote_identifier(&column.name, dialect),
                     action
                 )]
             }
@@ -95,32 +144,42 @@ impl DdlGenerator for PostgresDdlGenerator {
                 if let Some(default) = to_default {
                     vec![format!(
                         "ALTER TABLE {} ALTER COLUMN {} SET DEFAULT {}",
-                        quote_identifier(table, Dialect::Postgres),
-                        quote_identifier(column, Dialect::Postgres),
+                        quote_identifier(table, dialect),
+                        quote_identifier(column, dialect),
                         default
                     )]
                 } else {
                     vec![format!(
                         "ALTER TABLE {} ALTER COLUMN {} DROP DEFAULT",
-                        quote_identifier(table, Dialect::Postgres),
-                        quote_identifier(column, Dialect::Postgres)
+                        quote_identifier(table, dialect),
+                        quote_identifier(column, dialect)
                     )]
                 }
             }
+            SchemaOperation::AlterColumnCollation { table, column, .. } => {
+                // PostgreSQL changes collation by re-declaring the type.
+                vec![format!(
+                    "ALTER TABLE {} ALTER COLUMN {} TYPE {} COLLATE {}",
+                    quote_identifier(table, dialect),
+                    quote_identifier(&column.name, dialect),
+                    column.sql_type,
+                    quote_identifier(column.collation.as_deref().unwrap_or("default"), dialect)
+                )]
+            }
             SchemaOperation::RenameColumn { table, from, to } => {
-                vec![generate_rename_column(table, from, to, Dialect::Postgres)]
+                vec![generate_rename_column(table, from, to, dialect)]
             }
 
             // Primary Keys
             SchemaOperation::AddPrimaryKey { table, columns, .. } => {
                 let cols: Vec<String> = columns
                     .iter()
-                    .map(|c| quote_identifier(c, Dialect::Postgres))
+                    .map(|c| quote_identifier(c, dialect))
                     .collect();
                 // PostgreSQL auto-generates constraint name as {table}_pkey
                 vec![format!(
                     "ALTER TABLE {} ADD PRIMARY KEY ({})",
-                    quote_identifier(table, Dialect::Postgres),
+                    quote_identifier(table, dialect),
                     cols.join(", ")
                 )]
             }
@@ -129,8 +188,8 @@ impl DdlGenerator for PostgresDdlGenerator {
                 let constraint_name = format!("{}_pkey", table);
                 vec![format!(
                     "ALTER TABLE {} DROP CONSTRAINT {}",
-                    quote_identifier(table, Dialect::Postgres),
-                    quote_identifier(&constraint_name, Dialect::Postgres)
+                    quote_identifier(table, dialect),
+                    quote_identifier(&constraint_name, dialect)
                 )]
             }
 
@@ -142,16 +201,16 @@ impl DdlGenerator for PostgresDdlGenerator {
                     .unwrap_or_else(|| format!("fk_{}_{}", table, fk.column));
                 vec![format!(
                     "ALTER TABLE {} ADD CONSTRAINT {} {}",
-                    quote_identifier(table, Dialect::Postgres),
-                    quote_identifier(&constraint_name, Dialect::Postgres),
-                    format_fk_constraint(fk, Dialect::Postgres)
+                    quote_identifier(table, dialect),
+                    quote_identifier(&constraint_name, dialect),
+                    format_fk_constraint(fk, dialect)
                 )]
             }
             SchemaOperation::DropForeignKey { table, name, .. } => {
                 vec![format!(
                     "ALTER TABLE {} DROP CONSTRAINT {}",
-                    quote_identifier(table, Dialect::Postgres),
-                    quote_identifier(name, Dialect::Postgres)
+                    quote_identifier(table, dialect),
+                    quote_identifier(name, dialect)
                 )]
             }
 
@@ -162,7 +221,7 @@ impl DdlGenerator for PostgresDdlGenerator {
                 let cols: Vec<String> = constraint
                     .columns
                     .iter()
-                    .map(|c| quote_identifier(c, Dialect::Postgres))
+                    .map(|c| quote_identifier(c, dialect))
                     .collect();
                 let name = constraint
                     .name
@@ -170,26 +229,55 @@ impl DdlGenerator for PostgresDdlGenerator {
                     .unwrap_or_else(|| format!("uk_{}_{}", table, constraint.columns.join("_")));
                 vec![format!(
                     "ALTER TABLE {} ADD CONSTRAINT {} UNIQUE ({})",
-                    quote_identifier(table, Dialect::Postgres),
-                    quote_identifier(&name, Dialect::Postgres),
+                    quote_identifier(table, dialect),
+                    quote_identifier(&name, dialect),
                     cols.join(", ")
                 )]
             }
             SchemaOperation::DropUnique { table, name, .. } => {
                 vec![format!(
                     "ALTER TABLE {} DROP CONSTRAINT {}",
-                    quote_identifier(table, Dialect::Postgres),
-                    quote_identifier(name, Dialect::Postgres)
+                    quote_identifier(table, dialect),
+                    quote_identifier(name, dialect)
                 )]
             }
 
             // Indexes
             SchemaOperation::CreateIndex { table, index } => {
-                vec![generate_create_index(table, index, Dialect::Postgres)]
+                vec![generate_create_index(table, index, dialect)]
             }
             SchemaOperation::DropIndex { table, name } => {
-                vec![generate_drop_index(table, name, Dialect::Postgres)]
+                vec![generate_drop_index(table, name, dialect)]
+            }
+
+            // Comments
+            SchemaOperation::SetTableComment {
+                table, to_comment, ..
+            } => {
+                vec![generate_comment_on(
+                    dialect,
+                    table,
+                    None,
+                    to_comment.as_deref(),
+                )]
             }
+            SchemaOperation::SetColumnComment {
+                table,
+                column,
+                to_comment,
+                ..
+            } => {
+                vec![generate_comment_on(
+                    dialect,
+                    table,
+                    Some(&column.name),
+                    to_comment.as_deref(),
+                )]
+            }
+
+            // Triggers
+            SchemaOperation::CreateTrigger(trigger) => trigger.build(dialect),
+            SchemaOperation::DropTrigger(trigger) => trigger.build_drop(dialect),
         };
 
         for stmt in &statements {
@@ -222,6 +310,8 @@ mod tests {
             primary_key: false,
             auto_increment: false,
             comment: None,
+            collation: None,
+            charset: None,
         }
     }
 
@@ -584,6 +674,96 @@ mod tests {
         assert!(stmts[0].contains("DROP INDEX IF EXISTS"));
     }
 
+    #[test]
+    fn test_create_table_emits_comments() {
+        let ddl = PostgresDdlGenerator;
+        let mut name = make_column("name", "TEXT", false);
+        name.comment = Some("Hero's public alias".to_string());
+        let mut table = make_table(
+            "heroes",
+            vec![make_column("id", "SERIAL", false), name],
+            vec!["id"],
+        );
+        table.comment = Some("Registered heroes".to_string());
+        let stmts = ddl.generate(&SchemaOperation::CreateTable(table));
+
+        assert_eq!(stmts.len(), 3);
+        assert_eq!(
+            stmts[1],
+            "COMMENT ON TABLE \"heroes\" IS 'Registered heroes'"
+        );
+        assert_eq!(
+            stmts[2],
+            "COMMENT ON COLUMN \"heroes\".\"name\" IS 'Hero''s public alias'"
+        );
+    }
+
+    #[test]
+    fn test_set_comments() {
+        let ddl = PostgresDdlGenerator;
+        let stmts = ddl.generate(&SchemaOperation::SetTableComment {
+            table: "heroes".to_string(),
+            from_comment: Some("old".to_string()),
+            to_comment: None,
+        });
+        assert_eq!(stmts, ["COMMENT ON TABLE \"heroes\" IS NULL"]);
+
+        let stmts = ddl.generate(&SchemaOperation::SetColumnComment {
+            table: "heroes".to_string(),
+            column: make_column("name", "TEXT", false),
+            from_comment: None,
+            to_comment: Some("alias".to_string()),
+        });
+        assert_eq!(stmts, ["COMMENT ON COLUMN \"heroes\".\"name\" IS 'alias'"]);
+    }
+
+    #[test]
+    fn test_citext_columns_install_extension() {
+        let ddl = PostgresDdlGenerator;
+        let table = make_table(
+            "accounts",
+            vec![
+                make_column("id", "BIGSERIAL", false),
+                make_column("email", "CITEXT", false),
+            ],
+            vec!["id"],
+        );
+        let stmts = ddl.generate(&SchemaOperation::CreateTable(table));
+        assert_eq!(stmts[0], "CREATE EXTENSION IF NOT EXISTS citext");
+        assert!(stmts[1].contains("\"email\" CITEXT NOT NULL"));
+
+        let stmts = ddl.generate(&SchemaOperation::AddColumn {
+            table: "accounts".to_string(),
+            column: make_column("handle", "CITEXT", true),
+        });
+        assert_eq!(stmts.len(), 2);
+        assert_eq!(stmts[0], "CREATE EXTENSION IF NOT EXISTS citext");
+    }
+
+    #[test]
+    fn test_alter_column_collation() {
+        let ddl = PostgresDdlGenerator;
+        let mut column = make_column("name", "TEXT", false);
+        column.collation = Some("und-x-icu".to_string());
+        let op = SchemaOperation::AlterColumnCollation {
+            table: "heroes".to_string(),
+            column,
+            from_collation: None,
+            from_charset: None,
+            table_info: None,
+        };
+        assert_eq!(
+            ddl.generate(&op),
+            ["ALTER TABLE \"heroes\" ALTER COLUMN \"name\" TYPE TEXT COLLATE \"und-x-icu\""]
+        );
+
+        // The inverse goes back to the database default
+        assert_eq!(
+            ddl.generate(&op.inverse().unwrap()),
+            ["ALTER TABLE \"heroes\" ALTER COLUMN \"name\" TYPE TEXT COLLATE \"default\""]
+        );
+    }
+
     #[test]
     fn test_dialect() {
         let ddl = PostgresDdlGenerator;
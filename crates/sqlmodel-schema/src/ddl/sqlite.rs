@@ -9,6 +9,7 @@ use super::{
 };
 use crate::diff::SchemaOperation;
 use crate::introspect::{Dialect, ForeignKeyInfo, TableInfo, UniqueConstraintInfo};
+use sqlmodel_core::dialect::SqlDialect;
 
 /// DDL generator for SQLite.
 pub struct SqliteDdlGenerator;
@@ -19,6 +20,16 @@ impl DdlGenerator for SqliteDdlGenerator {
     }
 
     fn generate(&self, op: &SchemaOperation) -> Vec<String> {
+        Self::generate_with(Dialect::Sqlite.as_dyn(), op)
+    }
+}
+
+impl SqliteDdlGenerator {
+    /// Generate SQLite DDL for `op`, quoting identifiers through `dialect`.
+    pub(super) fn generate_with(
+        dialect: &'static dyn SqlDialect,
+        op: &SchemaOperation,
+    ) -> Vec<String> {
         tracing::debug!(dialect = "sqlite", op = ?op, "Generating DDL");
 
         let statements = match op {
@@ -29,13 +40,13 @@ impl DdlGenerator for SqliteDdlGenerator {
                 let mut base = table.clone();
                 base.unique_constraints.clear();
 
-                let mut stmts = vec![generate_create_table(&base, Dialect::Sqlite)];
+                let mut stmts = vec![generate_create_table(&base, dialect)];
 
                 for uk in &table.unique_constraints {
                     let cols: Vec<String> = uk
                         .columns
                         .iter()
-                        .map(|c| quote_identifier(c, Dialect::Sqlite))
+                        .map(|c| quote_identifier(c, dialect))
                         .collect();
                     let name = uk
                         .name
@@ -43,8 +54,8 @@ impl DdlGenerator for SqliteDdlGenerator {
                         .unwrap_or_else(|| format!("uk_{}_{}", table.name, uk.columns.join("_")));
                     stmts.push(format!(
                         "CREATE UNIQUE INDEX {} ON {}({})",
-                        quote_identifier(&name, Dialect::Sqlite),
-                        quote_identifier(&table.name, Dialect::Sqlite),
+                        quote_identifier(&name, dialect),
+                        quote_identifier(&table.name, dialect),
                         cols.join(", ")
                     ));
                 }
@@ -53,21 +64,21 @@ impl DdlGenerator for SqliteDdlGenerator {
                     if idx.primary {
                         continue;
                     }
-                    stmts.push(generate_create_index(&table.name, idx, Dialect::Sqlite));
+                    stmts.push(generate_create_index(&table.name, idx, dialect));
                 }
 
                 stmts
             }
             SchemaOperation::DropTable(name) => {
-                vec![generate_drop_table(name, Dialect::Sqlite)]
+                vec![generate_drop_table(name, dialect)]
             }
             SchemaOperation::RenameTable { from, to } => {
-                vec![generate_rename_table(from, to, Dialect::Sqlite)]
+                vec![generate_rename_table(from, to, dialect)]
             }
 
             // Columns
             SchemaOperation::AddColumn { table, column } => {
-                vec![generate_add_column(table, column, Dialect::Sqlite)]
+                vec![generate_add_column(table, column, dialect)]
             }
             SchemaOperation::DropColumn {
                 table,
@@ -75,14 +86,14 @@ impl DdlGenerator for SqliteDdlGenerator {
                 table_info,
             } => {
                 if let Some(table_info) = table_info {
-                    sqlite_drop_column_recreate(table_info, column)
+                    sqlite_drop_column_recreate(dialect, table_info, column)
                 } else {
                     vec![
                         "-- SQLite: DROP COLUMN without table_info; using ALTER TABLE DROP COLUMN (requires SQLite >= 3.35.0)".to_string(),
                         format!(
                             "ALTER TABLE {} DROP COLUMN {}",
-                            quote_identifier(table, Dialect::Sqlite),
-                            quote_identifier(column, Dialect::Sqlite)
+                            quote_identifier(table, dialect),
+                            quote_identifier(column, dialect)
                         ),
                     ]
                 }
@@ -95,7 +106,7 @@ impl DdlGenerator for SqliteDdlGenerator {
                 ..
             } => {
                 if let Some(table_info) = table_info {
-                    sqlite_alter_column_type_recreate(table_info, column, to_type)
+                    sqlite_alter_column_type_recreate(dialect, table_info, column, to_type)
                 } else {
                     vec![format!(
                         "SELECT __sqlmodel_error__('SQLite ALTER COLUMN TYPE requires table_info: {}.{} -> {}')",
@@ -113,7 +124,12 @@ impl DdlGenerator for SqliteDdlGenerator {
                 ..
             } => {
                 if let Some(table_info) = table_info {
-                    sqlite_alter_column_nullable_recreate(table_info, &column.name, *to_nullable)
+                    sqlite_alter_column_nullable_recreate(
+                        dialect,
+                        table_info,
+                        &column.name,
+                        *to_nullable,
+                    )
                 } else {
                     vec![format!(
                         "SELECT __sqlmodel_error__('SQLite ALTER COLUMN NULLABILITY requires table_info: {}.{}')",
@@ -130,7 +146,12 @@ impl DdlGenerator for SqliteDdlGenerator {
                 ..
             } => {
                 if let Some(table_info) = table_info {
-                    sqlite_alter_column_default_recreate(table_info, column, to_default.as_deref())
+                    sqlite_alter_column_default_recreate(
+                        dialect,
+                        table_info,
+                        column,
+                        to_default.as_deref(),
+                    )
                 } else {
                     vec![format!(
                         "SELECT __sqlmodel_error__('SQLite ALTER COLUMN DEFAULT requires table_info: {}.{}')",
@@ -139,8 +160,29 @@ impl DdlGenerator for SqliteDdlGenerator {
                     )]
                 }
             }
+            SchemaOperation::AlterColumnCollation {
+                table,
+                column,
+                table_info,
+                ..
+            } => {
+                if let Some(table_info) = table_info {
+                    sqlite_alter_column_collation_recreate(
+                        dialect,
+                        table_info,
+                        &column.name,
+                        column.collation.as_deref(),
+                    )
+                } else {
+                    vec![format!(
+                        "SELECT __sqlmodel_error__('SQLite ALTER COLUMN COLLATION requires table_info: {}.{}')",
+                        sanitize_temp_ident(table),
+                        sanitize_temp_ident(&column.name)
+                    )]
+                }
+            }
             SchemaOperation::RenameColumn { table, from, to } => {
-                vec![generate_rename_column(table, from, to, Dialect::Sqlite)]
+                vec![generate_rename_column(table, from, to, dialect)]
             }
 
             // Primary Keys
@@ -150,7 +192,7 @@ impl DdlGenerator for SqliteDdlGenerator {
                 table_info,
             } => {
                 if let Some(table_info) = table_info {
-                    sqlite_add_primary_key_recreate(table_info, columns)
+                    sqlite_add_primary_key_recreate(dialect, table_info, columns)
                 } else {
                     vec![format!(
                         "SELECT __sqlmodel_error__('SQLite ADD PRIMARY KEY requires table_info: {}')",
@@ -160,7 +202,7 @@ impl DdlGenerator for SqliteDdlGenerator {
             }
             SchemaOperation::DropPrimaryKey { table, table_info } => {
                 if let Some(table_info) = table_info {
-                    sqlite_drop_primary_key_recreate(table_info)
+                    sqlite_drop_primary_key_recreate(dialect, table_info)
                 } else {
                     vec![format!(
                         "SELECT __sqlmodel_error__('SQLite DROP PRIMARY KEY requires table_info: {}')",
@@ -176,7 +218,7 @@ impl DdlGenerator for SqliteDdlGenerator {
                 table_info,
             } => {
                 if let Some(table_info) = table_info {
-                    sqlite_add_foreign_key_recreate(table_info, fk)
+                    sqlite_add_foreign_key_recreate(dialect, table_info, fk)
                 } else {
                     vec![format!(
                         "SELECT __sqlmodel_error__('SQLite ADD FOREIGN KEY requires table_info: {}.{}')",
@@ -191,7 +233,7 @@ impl DdlGenerator for SqliteDdlGenerator {
                 table_info,
             } => {
                 if let Some(table_info) = table_info {
-                    sqlite_drop_foreign_key_recreate(table_info, name)
+                    sqlite_drop_foreign_key_recreate(dialect, table_info, name)
                 } else {
                     vec![format!(
                         "SELECT __sqlmodel_error__('SQLite DROP FOREIGN KEY requires table_info: {}.{}')",
@@ -209,7 +251,7 @@ impl DdlGenerator for SqliteDdlGenerator {
                 let cols: Vec<String> = constraint
                     .columns
                     .iter()
-                    .map(|c| quote_identifier(c, Dialect::Sqlite))
+                    .map(|c| quote_identifier(c, dialect))
                     .collect();
                 let name = constraint
                     .name
@@ -217,8 +259,8 @@ impl DdlGenerator for SqliteDdlGenerator {
                     .unwrap_or_else(|| format!("uk_{}_{}", table, constraint.columns.join("_")));
                 vec![format!(
                     "CREATE UNIQUE INDEX {} ON {}({})",
-                    quote_identifier(&name, Dialect::Sqlite),
-                    quote_identifier(table, Dialect::Sqlite),
+                    quote_identifier(&name, dialect),
+                    quote_identifier(table, dialect),
                     cols.join(", ")
                 )]
             }
@@ -231,7 +273,7 @@ impl DdlGenerator for SqliteDdlGenerator {
                 // In that case we must recreate the table without the unique constraint.
                 if name.starts_with("sqlite_autoindex_") {
                     if let Some(table_info) = table_info {
-                        sqlite_drop_unique_recreate(table_info, name)
+                        sqlite_drop_unique_recreate(dialect, table_info, name)
                     } else {
                         vec![format!(
                             "SELECT __sqlmodel_error__('SQLite DROP UNIQUE autoindex requires table_info: {}.{}')",
@@ -240,17 +282,27 @@ impl DdlGenerator for SqliteDdlGenerator {
                         )]
                     }
                 } else {
-                    vec![generate_drop_index(table, name, Dialect::Sqlite)]
+                    vec![generate_drop_index(table, name, dialect)]
                 }
             }
 
             // Indexes
             SchemaOperation::CreateIndex { table, index } => {
-                vec![generate_create_index(table, index, Dialect::Sqlite)]
+                vec![generate_create_index(table, index, dialect)]
             }
             SchemaOperation::DropIndex { table, name } => {
-                vec![generate_drop_index(table, name, Dialect::Sqlite)]
+                vec![generate_drop_index(table, name, dialect)]
+            }
+
+            // Comments
+            // SQLite has no table or column comments
+            SchemaOperation::SetTableComment { .. } | SchemaOperation::SetColumnComment { .. } => {
+                Vec::new()
             }
+
+            // Triggers
+            SchemaOperation::CreateTrigger(trigger) => trigger.build(dialect),
+            SchemaOperation::DropTrigger(trigger) => trigger.build_drop(dialect),
         };
 
         for stmt in &statements {
@@ -277,6 +329,7 @@ fn sanitize_temp_ident(s: &str) -> String {
 }
 
 fn sqlite_recreate_table(
+    dialect: &'static dyn SqlDialect,
     new_table: &TableInfo,
     tmp_old: &str,
     insert_cols: &[String],
@@ -292,25 +345,25 @@ fn sqlite_recreate_table(
     let mut stmts = vec![
         "PRAGMA foreign_keys=OFF".to_string(),
         "BEGIN".to_string(),
-        generate_rename_table(table_name, tmp_old, Dialect::Sqlite),
-        super::generate_create_table_with_if_not_exists(&create_table, Dialect::Sqlite, false),
+        generate_rename_table(table_name, tmp_old, dialect),
+        super::generate_create_table_with_if_not_exists(&create_table, dialect, false),
     ];
 
     stmts.push(format!(
         "INSERT INTO {} ({}) SELECT {} FROM {}",
-        quote_identifier(table_name, Dialect::Sqlite),
+        quote_identifier(table_name, dialect),
         insert_cols.join(", "),
         select_exprs.join(", "),
-        quote_identifier(tmp_old, Dialect::Sqlite)
+        quote_identifier(tmp_old, dialect)
     ));
 
-    stmts.push(generate_drop_table(tmp_old, Dialect::Sqlite));
+    stmts.push(generate_drop_table(tmp_old, dialect));
 
     for uk in &new_table.unique_constraints {
         let cols: Vec<String> = uk
             .columns
             .iter()
-            .map(|c| quote_identifier(c, Dialect::Sqlite))
+            .map(|c| quote_identifier(c, dialect))
             .collect();
         let name = uk
             .name
@@ -318,8 +371,8 @@ fn sqlite_recreate_table(
             .unwrap_or_else(|| format!("uk_{}_{}", table_name, uk.columns.join("_")));
         stmts.push(format!(
             "CREATE UNIQUE INDEX {} ON {}({})",
-            quote_identifier(&name, Dialect::Sqlite),
-            quote_identifier(table_name, Dialect::Sqlite),
+            quote_identifier(&name, dialect),
+            quote_identifier(table_name, dialect),
             cols.join(", ")
         ));
     }
@@ -328,7 +381,7 @@ fn sqlite_recreate_table(
         if idx.primary {
             continue;
         }
-        stmts.push(generate_create_index(table_name, idx, Dialect::Sqlite));
+        stmts.push(generate_create_index(table_name, idx, dialect));
     }
 
     stmts.push("COMMIT".to_string());
@@ -348,7 +401,11 @@ fn sqlite_unique_effective_name(table: &str, uk: &UniqueConstraintInfo) -> Strin
         .unwrap_or_else(|| format!("uk_{}_{}", table, uk.columns.join("_")))
 }
 
-fn sqlite_add_primary_key_recreate(table: &TableInfo, pk_columns: &[String]) -> Vec<String> {
+fn sqlite_add_primary_key_recreate(
+    dialect: &'static dyn SqlDialect,
+    table: &TableInfo,
+    pk_columns: &[String],
+) -> Vec<String> {
     let table_name = table.name.as_str();
     let tmp_old = format!("__sqlmodel_old_{}_add_pk", sanitize_temp_ident(table_name));
 
@@ -361,13 +418,16 @@ fn sqlite_add_primary_key_recreate(table: &TableInfo, pk_columns: &[String]) ->
     let cols: Vec<String> = new_table
         .columns
         .iter()
-        .map(|c| quote_identifier(&c.name, Dialect::Sqlite))
+        .map(|c| quote_identifier(&c.name, dialect))
         .collect();
 
-    sqlite_recreate_table(&new_table, &tmp_old, &cols, &cols)
+    sqlite_recreate_table(dialect, &new_table, &tmp_old, &cols, &cols)
 }
 
-fn sqlite_drop_primary_key_recreate(table: &TableInfo) -> Vec<String> {
+fn sqlite_drop_primary_key_recreate(
+    dialect: &'static dyn SqlDialect,
+    table: &TableInfo,
+) -> Vec<String> {
     let table_name = table.name.as_str();
     let tmp_old = format!("__sqlmodel_old_{}_drop_pk", sanitize_temp_ident(table_name));
 
@@ -380,13 +440,17 @@ fn sqlite_drop_primary_key_recreate(table: &TableInfo) -> Vec<String> {
     let cols: Vec<String> = new_table
         .columns
         .iter()
-        .map(|c| quote_identifier(&c.name, Dialect::Sqlite))
+        .map(|c| quote_identifier(&c.name, dialect))
         .collect();
 
-    sqlite_recreate_table(&new_table, &tmp_old, &cols, &cols)
+    sqlite_recreate_table(dialect, &new_table, &tmp_old, &cols, &cols)
 }
 
-fn sqlite_add_foreign_key_recreate(table: &TableInfo, fk: &ForeignKeyInfo) -> Vec<String> {
+fn sqlite_add_foreign_key_recreate(
+    dialect: &'static dyn SqlDialect,
+    table: &TableInfo,
+    fk: &ForeignKeyInfo,
+) -> Vec<String> {
     let table_name = table.name.as_str();
     let tmp_old = format!(
         "__sqlmodel_old_{}_add_fk_{}",
@@ -402,13 +466,17 @@ fn sqlite_add_foreign_key_recreate(table: &TableInfo, fk: &ForeignKeyInfo) -> Ve
     let cols: Vec<String> = new_table
         .columns
         .iter()
-        .map(|c| quote_identifier(&c.name, Dialect::Sqlite))
+        .map(|c| quote_identifier(&c.name, dialect))
         .collect();
 
-    sqlite_recreate_table(&new_table, &tmp_old, &cols, &cols)
+    sqlite_recreate_table(dialect, &new_table, &tmp_old, &cols, &cols)
 }
 
-fn sqlite_drop_foreign_key_recreate(table: &TableInfo, name: &str) -> Vec<String> {
+fn sqlite_drop_foreign_key_recreate(
+    dialect: &'static dyn SqlDialect,
+    table: &TableInfo,
+    name: &str,
+) -> Vec<String> {
     let table_name = table.name.as_str();
     let tmp_old = format!(
         "__sqlmodel_old_{}_drop_fk_{}",
@@ -424,13 +492,17 @@ fn sqlite_drop_foreign_key_recreate(table: &TableInfo, name: &str) -> Vec<String
     let cols: Vec<String> = new_table
         .columns
         .iter()
-        .map(|c| quote_identifier(&c.name, Dialect::Sqlite))
+        .map(|c| quote_identifier(&c.name, dialect))
         .collect();
 
-    sqlite_recreate_table(&new_table, &tmp_old, &cols, &cols)
+    sqlite_recreate_table(dialect, &new_table, &tmp_old, &cols, &cols)
 }
 
-fn sqlite_drop_unique_recreate(table: &TableInfo, name: &str) -> Vec<String> {
+fn sqlite_drop_unique_recreate(
+    dialect: &'static dyn SqlDialect,
+    table: &TableInfo,
+    name: &str,
+) -> Vec<String> {
     let table_name = table.name.as_str();
     let tmp_old = format!(
         "__sqlmodel_old_{}_drop_uk_{}",
@@ -446,13 +518,17 @@ fn sqlite_drop_unique_recreate(table: &TableInfo, name: &str) -> Vec<String> {
     let cols: Vec<String> = new_table
         .columns
         .iter()
-        .map(|c| quote_identifier(&c.name, Dialect::Sqlite))
+        .map(|c| quote_identifier(&c.name, dialect))
         .collect();
 
-    sqlite_recreate_table(&new_table, &tmp_old, &cols, &cols)
+    sqlite_recreate_table(dialect, &new_table, &tmp_old, &cols, &cols)
 }
 
-fn sqlite_drop_column_recreate(table: &TableInfo, drop_column: &str) -> Vec<String> {
+fn sqlite_drop_column_recreate(
+    dialect: &'static dyn SqlDialect,
+    table: &TableInfo,
+    drop_column: &str,
+) -> Vec<String> {
     let table_name = table.name.as_str();
     let drop_column = drop_column.to_string();
 
@@ -491,12 +567,13 @@ fn sqlite_drop_column_recreate(table: &TableInfo, drop_column: &str) -> Vec<Stri
     let cols: Vec<String> = new_table
         .columns
         .iter()
-        .map(|c| quote_identifier(&c.name, Dialect::Sqlite))
+        .map(|c| quote_identifier(&c.name, dialect))
         .collect();
-    sqlite_recreate_table(&new_table, &tmp_old, &cols, &cols)
+    sqlite_recreate_table(dialect, &new_table, &tmp_old, &cols, &cols)
 }
 
 fn sqlite_alter_column_type_recreate(
+    dialect: &'static dyn SqlDialect,
     table: &TableInfo,
     column: &str,
     to_type: &str,
@@ -519,14 +596,14 @@ fn sqlite_alter_column_type_recreate(
     let insert_cols: Vec<String> = new_table
         .columns
         .iter()
-        .map(|c| quote_identifier(&c.name, Dialect::Sqlite))
+        .map(|c| quote_identifier(&c.name, dialect))
         .collect();
 
     let select_exprs: Vec<String> = new_table
         .columns
         .iter()
         .map(|c| {
-            let q = quote_identifier(&c.name, Dialect::Sqlite);
+            let q = quote_identifier(&c.name, dialect);
             if c.name == column {
                 format!("CAST({} AS {})", q, to_type)
             } else {
@@ -535,10 +612,11 @@ fn sqlite_alter_column_type_recreate(
         })
         .collect();
 
-    sqlite_recreate_table(&new_table, &tmp_old, &insert_cols, &select_exprs)
+    sqlite_recreate_table(dialect, &new_table, &tmp_old, &insert_cols, &select_exprs)
 }
 
 fn sqlite_alter_column_nullable_recreate(
+    dialect: &'static dyn SqlDialect,
     table: &TableInfo,
     column: &str,
     to_nullable: bool,
@@ -560,13 +638,14 @@ fn sqlite_alter_column_nullable_recreate(
     let cols: Vec<String> = new_table
         .columns
         .iter()
-        .map(|c| quote_identifier(&c.name, Dialect::Sqlite))
+        .map(|c| quote_identifier(&c.name, dialect))
         .collect();
 
-    sqlite_recreate_table(&new_table, &tmp_old, &cols, &cols)
+    sqlite_recreate_table(dialect, &new_table, &tmp_old, &cols, &cols)
 }
 
 fn sqlite_alter_column_default_recreate(
+    dialect: &'static dyn SqlDialect,
     table: &TableInfo,
     column: &str,
     to_default: Option<&str>,
@@ -588,10 +667,39 @@ fn sqlite_alter_column_default_recreate(
     let cols: Vec<String> = new_table
         .columns
         .iter()
-        .map(|c| quote_identifier(&c.name, Dialect::Sqlite))
+        .map(|c| quote_identifier(&c.name, dialect))
+        .collect();
+
+    sqlite_recreate_table(dialect, &new_table, &tmp_old, &cols, &cols)
+}
+
+fn sqlite_alter_column_collation_recreate(
+    dialect: &'static dyn SqlDialect,
+    table: &TableInfo,
+    column: &str,
+    to_collation: Option<&str>,
+) -> Vec<String> {
+    let table_name = table.name.as_str();
+    let tmp_old = format!(
+        "__sqlmodel_old_{}_collation_{}",
+        sanitize_temp_ident(table_name),
+        sanitize_temp_ident(column)
+    );
+
+    let mut new_table = table.clone();
+    for col in &mut new_table.columns {
+        if col.name == column {
+            col.collation = to_collation.map(|s| s.to_string());
+        }
+    }
+
+    let cols: Vec<String> = new_table
+        .columns
+        .iter()
+        .map(|c| quote_identifier(&c.name, dialect))
         .collect();
 
-    sqlite_recreate_table(&new_table, &tmp_old, &cols, &cols)
+    sqlite_recreate_table(dialect, &new_table, &tmp_old, &cols, &cols)
 }
 
 // ============================================================================
@@ -616,6 +724,8 @@ mod tests {
             primary_key: false,
             auto_increment: false,
             comment: None,
+            collation: None,
+            charset: None,
         }
     }
 
@@ -811,6 +921,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_alter_column_collation_recreates_table() {
+        let ddl = SqliteDdlGenerator;
+        let table = make_table(
+            "heroes",
+            vec![
+                make_column("id", "INTEGER", false),
+                make_column("name", "TEXT", false),
+            ],
+            vec!["id"],
+        );
+        let mut column = make_column("name", "TEXT", false);
+        column.collation = Some("NOCASE".to_string());
+        let op = SchemaOperation::AlterColumnCollation {
+            table: "heroes".to_string(),
+            column,
+            from_collation: None,
+            from_charset: None,
+            table_info: Some(table),
+        };
+        let stmts = ddl.generate(&op);
+
+        assert!(stmts.iter().any(|s| s.contains("CREATE TABLE \"heroes\"")
+            && s.contains("\"name\" TEXT COLLATE \"NOCASE\" NOT NULL")));
+        assert!(stmts.iter().any(|s| s.contains("INSERT INTO")));
+    }
+
     #[test]
     fn test_rename_column() {
         let ddl = SqliteDdlGenerator;
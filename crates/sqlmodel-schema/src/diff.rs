@@ -8,6 +8,7 @@ use crate::introspect::{
     ColumnInfo, DatabaseSchema, Dialect, ForeignKeyInfo, IndexInfo, ParsedSqlType, TableInfo,
     UniqueConstraintInfo,
 };
+use crate::trigger::CreateTrigger;
 use std::collections::{HashMap, HashSet};
 
 fn fk_effective_name(table: &str, fk: &ForeignKeyInfo) -> String {
@@ -75,6 +76,17 @@ pub enum SchemaOperation {
         to_default: Option<String>,
         table_info: Option<TableInfo>,
     },
+    /// Change a column's collation and/or character set.
+    ///
+    /// `column` is the full target definition; MySQL restates it and
+    /// PostgreSQL re-declares the type with the new `COLLATE`.
+    AlterColumnCollation {
+        table: String,
+        column: ColumnInfo,
+        from_collation: Option<String>,
+        from_charset: Option<String>,
+        table_info: Option<TableInfo>,
+    },
     /// Rename a column.
     RenameColumn {
         table: String,
@@ -128,6 +140,30 @@ pub enum SchemaOperation {
     CreateIndex { table: String, index: IndexInfo },
     /// Drop an index.
     DropIndex { table: String, name: String },
+
+    // Comments
+    /// Set (or, with `to_comment: None`, remove) a table comment.
+    SetTableComment {
+        table: String,
+        from_comment: Option<String>,
+        to_comment: Option<String>,
+    },
+    /// Set (or remove) a column comment.
+    ///
+    /// `column` carries the full definition with the new comment because
+    /// MySQL can only change a comment by restating the column.
+    SetColumnComment {
+        table: String,
+        column: ColumnInfo,
+        from_comment: Option<String>,
+        to_comment: Option<String>,
+    },
+
+    // Triggers
+    /// Create (or, with `or_replace`, recreate) a trigger.
+    CreateTrigger(CreateTrigger),
+    /// Drop a trigger. Carries the full definition so rollback can recreate it.
+    DropTrigger(CreateTrigger),
 }
 
 impl SchemaOperation {
@@ -204,6 +240,23 @@ impl SchemaOperation {
                 to_default: from_default.clone(),
                 table_info: None,
             }),
+            SchemaOperation::AlterColumnCollation {
+                table,
+                column,
+                from_collation,
+                from_charset,
+                ..
+            } => Some(SchemaOperation::AlterColumnCollation {
+                table: table.clone(),
+                column: ColumnInfo {
+                    collation: from_collation.clone(),
+                    charset: from_charset.clone(),
+                    ..column.clone()
+                },
+                from_collation: column.collation.clone(),
+                from_charset: column.charset.clone(),
+                table_info: None,
+            }),
             SchemaOperation::RenameColumn { table, from, to } => {
                 Some(SchemaOperation::RenameColumn {
                     table: table.clone(),
@@ -237,6 +290,37 @@ impl SchemaOperation {
                 name: index.name.clone(),
             }),
             SchemaOperation::DropIndex { .. } => None,
+            SchemaOperation::SetTableComment {
+                table,
+                from_comment,
+                to_comment,
+            } => Some(SchemaOperation::SetTableComment {
+                table: table.clone(),
+                from_comment: to_comment.clone(),
+                to_comment: from_comment.clone(),
+            }),
+            SchemaOperation::SetColumnComment {
+                table,
+                column,
+                from_comment,
+                to_comment,
+            } => Some(SchemaOperation::SetColumnComment {
+                table: table.clone(),
+                column: ColumnInfo {
+                    comment: from_comment.clone(),
+                    collation: None,
+                    charset: None,
+                    ..column.clone()
+                },
+                from_comment: to_comment.clone(),
+                to_comment: from_comment.clone(),
+            }),
+            SchemaOperation::CreateTrigger(trigger) => {
+                Some(SchemaOperation::DropTrigger(trigger.clone()))
+            }
+            SchemaOperation::DropTrigger(trigger) => {
+                Some(SchemaOperation::CreateTrigger(trigger.clone()))
+            }
         }
     }
 
@@ -251,6 +335,7 @@ impl SchemaOperation {
             | SchemaOperation::AlterColumnType { table, .. }
             | SchemaOperation::AlterColumnNullable { table, .. }
             | SchemaOperation::AlterColumnDefault { table, .. }
+            | SchemaOperation::AlterColumnCollation { table, .. }
             | SchemaOperation::RenameColumn { table, .. }
             | SchemaOperation::AddPrimaryKey { table, .. }
             | SchemaOperation::DropPrimaryKey { table, .. }
@@ -259,13 +344,19 @@ impl SchemaOperation {
             | SchemaOperation::AddUnique { table, .. }
             | SchemaOperation::DropUnique { table, .. }
             | SchemaOperation::CreateIndex { table, .. }
-            | SchemaOperation::DropIndex { table, .. } => Some(table),
+            | SchemaOperation::DropIndex { table, .. }
+            | SchemaOperation::SetTableComment { table, .. }
+            | SchemaOperation::SetColumnComment { table, .. } => Some(table),
+            SchemaOperation::CreateTrigger(trigger) | SchemaOperation::DropTrigger(trigger) => {
+                Some(trigger.table())
+            }
         }
     }
 
     /// Get a priority value for ordering operations.
     fn priority(&self) -> u8 {
         // Order:
+        // 0. Drop triggers (before the columns they reference change)
         // 1. Drop foreign keys (remove constraints before modifying)
         // 2. Drop indexes
         // 3. Drop unique constraints
@@ -278,8 +369,11 @@ impl SchemaOperation {
         // 10. Add unique constraints
         // 11. Add indexes
         // 12. Add foreign keys
-        // 13. Drop tables (last, after FK removal)
+        // 13. Set comments (on the final table and column names)
+        // 14. Create triggers (once their tables and columns exist)
+        // 15. Drop tables (last, after FK removal)
         match self {
+            SchemaOperation::DropTrigger(_) => 0,
             SchemaOperation::DropForeignKey { .. } => 1,
             SchemaOperation::DropIndex { .. } => 2,
             SchemaOperation::DropUnique { .. } => 3,
@@ -287,7 +381,8 @@ impl SchemaOperation {
             SchemaOperation::DropColumn { .. } => 5,
             SchemaOperation::AlterColumnType { .. } => 6,
             SchemaOperation::AlterColumnNullable { .. } => 7,
-            SchemaOperation::AlterColumnDefault { .. } => 8,
+            SchemaOperation::AlterColumnDefault { .. }
+            | SchemaOperation::AlterColumnCollation { .. } => 8,
             SchemaOperation::AddColumn { .. } => 9,
             SchemaOperation::CreateTable(_) => 10,
             SchemaOperation::RenameTable { .. } => 11,
@@ -296,7 +391,11 @@ impl SchemaOperation {
             SchemaOperation::AddUnique { .. } => 14,
             SchemaOperation::CreateIndex { .. } => 15,
             SchemaOperation::AddForeignKey { .. } => 16,
-            SchemaOperation::DropTable(_) => 17,
+            SchemaOperation::SetTableComment { .. } | SchemaOperation::SetColumnComment { .. } => {
+                17
+            }
+            SchemaOperation::CreateTrigger(_) => 18,
+            SchemaOperation::DropTable(_) => 19,
         }
     }
 }
@@ -439,6 +538,7 @@ impl SchemaDiff {
                 | SchemaOperation::AlterColumnType { table_info, .. }
                 | SchemaOperation::AlterColumnNullable { table_info, .. }
                 | SchemaOperation::AlterColumnDefault { table_info, .. }
+                | SchemaOperation::AlterColumnCollation { table_info, .. }
                 | SchemaOperation::AddPrimaryKey { table_info, .. }
                 | SchemaOperation::DropPrimaryKey { table_info, .. }
                 | SchemaOperation::AddForeignKey { table_info, .. }
@@ -723,9 +823,27 @@ fn sqlite_apply_op_to_table_info(table: &mut TableInfo, op: &SchemaOperation) {
         SchemaOperation::DropIndex { name, .. } => {
             table.indexes.retain(|i| i.name != *name);
         }
+        SchemaOperation::AlterColumnCollation { column, .. } => {
+            if let Some(col) = table.columns.iter_mut().find(|c| c.name == column.name) {
+                col.collation.clone_from(&column.collation);
+                col.charset.clone_from(&column.charset);
+            }
+        }
+        SchemaOperation::SetTableComment { to_comment, .. } => {
+            table.comment.clone_from(to_comment);
+        }
+        SchemaOperation::SetColumnComment {
+            column, to_comment, ..
+        } => {
+            if let Some(col) = table.columns.iter_mut().find(|c| c.name == column.name) {
+                col.comment.clone_from(to_comment);
+            }
+        }
         SchemaOperation::CreateTable(_)
         | SchemaOperation::DropTable(_)
-        | SchemaOperation::RenameTable { .. } => {}
+        | SchemaOperation::RenameTable { .. }
+        | SchemaOperation::CreateTrigger(_)
+        | SchemaOperation::DropTrigger(_) => {}
     }
 }
 
@@ -747,6 +865,15 @@ fn diff_table(current: &TableInfo, expected: &TableInfo, dialect: Dialect, diff:
 
     // Diff indexes
     diff_indexes(table, &current.indexes, &expected.indexes, diff);
+
+    // Diff table comment (SQLite has no comments to compare against)
+    if dialect != Dialect::Sqlite && current.comment != expected.comment {
+        diff.add_op(SchemaOperation::SetTableComment {
+            table: table.clone(),
+            from_comment: current.comment.clone(),
+            to_comment: expected.comment.clone(),
+        });
+    }
 }
 
 /// Compare columns between tables.
@@ -890,6 +1017,39 @@ fn diff_column_details(
             table_info: Some(current_table.clone()),
         });
     }
+
+    // Collation / character set change. Only explicitly declared values are
+    // compared: databases report their defaults, and SQLite reports nothing.
+    let differs = |current: &Option<String>, expected: &Option<String>| {
+        expected.as_deref().is_some_and(|e| {
+            !current
+                .as_deref()
+                .is_some_and(|c| c.eq_ignore_ascii_case(e))
+        })
+    };
+    let collation_changed = differs(&current.collation, &expected.collation);
+    let charset_changed = dialect == Dialect::Mysql && differs(&current.charset, &expected.charset);
+    if dialect != Dialect::Sqlite && (collation_changed || charset_changed) {
+        // Whichever of the two is left undeclared is derived by the
+        // database from the other, so the current value is not carried over.
+        diff.add_op(SchemaOperation::AlterColumnCollation {
+            table: table.to_string(),
+            column: expected.clone(),
+            from_collation: current.collation.clone(),
+            from_charset: current.charset.clone(),
+            table_info: Some(current_table.clone()),
+        });
+    }
+
+    // Comment change
+    if dialect != Dialect::Sqlite && current.comment != expected.comment {
+        diff.add_op(SchemaOperation::SetColumnComment {
+            table: table.to_string(),
+            column: (*expected).clone(),
+            from_comment: current.comment.clone(),
+            to_comment: expected.comment.clone(),
+        });
+    }
 }
 
 /// Compare primary keys.
@@ -1301,6 +1461,8 @@ fn normalize_type(sql_type: &str, dialect: Dialect) -> String {
         Dialect::Mysql => match upper.as_str() {
             "INTEGER" => "INT".to_string(),
             "BOOL" | "BOOLEAN" => "TINYINT".to_string(),
+            // CITEXT is emitted as TEXT with a case-insensitive collation
+            "CITEXT" => "TEXT".to_string(),
             _ => upper,
         },
     }
@@ -1325,6 +1487,8 @@ mod tests {
             primary_key: false,
             auto_increment: false,
             comment: None,
+            collation: None,
+            charset: None,
         }
     }
 
@@ -1615,6 +1779,126 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_schema_diff_comments() {
+        let schema = |dialect, table_comment: Option<&str>, column_comment: Option<&str>| {
+            let mut column = make_column("name", "TEXT", false);
+            column.comment = column_comment.map(String::from);
+            let mut table = make_table("heroes", vec![column]);
+            table.comment = table_comment.map(String::from);
+            let mut schema = DatabaseSchema::new(dialect);
+            schema.tables.insert("heroes".to_string(), table);
+            schema
+        };
+
+        let diff = schema_diff(
+            &schema(Dialect::Postgres, Some("old"), None),
+            &schema(Dialect::Postgres, None, Some("alias")),
+        );
+        assert_eq!(diff.operations.len(), 2);
+        assert!(diff.operations.iter().any(|op| matches!(
+            op,
+            SchemaOperation::SetTableComment { table, to_comment: None, .. } if table == "heroes"
+        )));
+        let Some(op) = diff
+            .operations
+            .iter()
+            .find(|op| matches!(op, SchemaOperation::SetColumnComment { .. }))
+        else {
+            panic!("expected a column comment change");
+        };
+        let SchemaOperation::SetColumnComment { column, .. } = op.inverse().unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(column.comment, None);
+
+        // SQLite has nowhere to store comments, so they never produce changes
+        let diff = schema_diff(
+            &schema(Dialect::Sqlite, None, None),
+            &schema(Dialect::Sqlite, Some("heroes"), Some("alias")),
+        );
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn test_schema_diff_collation() {
+        let schema = |dialect, collation: Option<&str>, charset: Option<&str>| {
+            let mut column = make_column("name", "TEXT", false);
+            column.collation = collation.map(String::from);
+            column.charset = charset.map(String::from);
+            let mut schema = DatabaseSchema::new(dialect);
+            schema
+                .tables
+                .insert("heroes".to_string(), make_table("heroes", vec![column]));
+            schema
+        };
+
+        let diff = schema_diff(
+            &schema(Dialect::Postgres, None, None),
+            &schema(Dialect::Postgres, Some("und-x-icu"), None),
+        );
+        assert_eq!(diff.operations.len(), 1);
+        let SchemaOperation::AlterColumnCollation {
+            column,
+            from_collation,
+            ..
+        } = &diff.operations[0]
+        else {
+            panic!("expected a collation change");
+        };
+        assert_eq!(column.collation.as_deref(), Some("und-x-icu"));
+        assert_eq!(*from_collation, None);
+
+        // Undeclared collations are left alone, and names compare case-insensitively
+        assert!(
+            schema_diff(
+                &schema(Dialect::Postgres, Some("C"), None),
+                &schema(Dialect::Postgres, None, None),
+            )
+            .is_empty()
+        );
+        assert!(
+            schema_diff(
+                &schema(Dialect::Mysql, Some("utf8mb4_bin"), Some("utf8mb4")),
+                &schema(Dialect::Mysql, Some("UTF8MB4_BIN"), None),
+            )
+            .is_empty()
+        );
+
+        // Character set changes only matter on MySQL
+        let diff = schema_diff(
+            &schema(Dialect::Mysql, Some("latin1_swedish_ci"), Some("latin1")),
+            &schema(Dialect::Mysql, None, Some("utf8mb4")),
+        );
+        assert_eq!(diff.operations.len(), 1);
+        assert!(
+            schema_diff(
+                &schema(Dialect::Sqlite, None, None),
+                &schema(Dialect::Sqlite, Some("NOCASE"), None),
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_citext_matches_reflected_columns() {
+        for (dialect, reflected) in [
+            (Dialect::Postgres, "CITEXT"),
+            (Dialect::Mysql, "text"),
+            (Dialect::Sqlite, "TEXT"),
+        ] {
+            let schema = |sql_type| {
+                let mut schema = DatabaseSchema::new(dialect);
+                schema.tables.insert(
+                    "accounts".to_string(),
+                    make_table("accounts", vec![make_column("email", sql_type, false)]),
+                );
+                schema
+            };
+            assert!(schema_diff(&schema(reflected), &schema("CITEXT")).is_empty());
+        }
+    }
+
     #[test]
     fn test_schema_diff_empty() {
         let mut current = DatabaseSchema::new(Dialect::Sqlite);
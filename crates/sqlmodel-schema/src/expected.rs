@@ -8,7 +8,9 @@ use crate::introspect::{
     ColumnInfo, DatabaseSchema, Dialect, ForeignKeyInfo, IndexInfo, ParsedSqlType, TableInfo,
     UniqueConstraintInfo,
 };
-use sqlmodel_core::{FieldInfo, Model};
+use sqlmodel_core::{
+    FieldInfo, IndexDef, Model, ModelDescriptor, ModelRegistry, UniqueConstraintDef,
+};
 
 // ============================================================================
 // Extension Trait for Model
@@ -54,7 +56,71 @@ impl<M: Model> ModelSchema for M {}
 
 /// Extract a TableInfo from a Model type.
 pub fn table_schema_from_model<M: Model>() -> TableInfo {
-    table_schema_from_fields(M::TABLE_NAME, M::fields(), M::PRIMARY_KEY)
+    let mut table = table_schema_with_constraints(
+        table_schema_from_fields(M::TABLE_NAME, M::fields(), M::PRIMARY_KEY),
+        M::UNIQUE_CONSTRAINTS,
+        M::INDEXES,
+    );
+    table.comment = M::TABLE_COMMENT.map(String::from);
+    table
+}
+
+/// Extract a TableInfo from a type-erased model descriptor.
+pub fn table_schema_from_descriptor(model: &ModelDescriptor) -> TableInfo {
+    let mut table = table_schema_with_constraints(
+        table_schema_from_fields(model.table_name, model.fields, model.primary_key),
+        model.unique_constraints,
+        model.indexes,
+    );
+    table.comment = model.table_comment.map(String::from);
+    table
+}
+
+/// Add struct-level composite constraints and indexes.
+fn table_schema_with_constraints(
+    mut table: TableInfo,
+    uniques: &[UniqueConstraintDef],
+    indexes: &[IndexDef],
+) -> TableInfo {
+    for unique in uniques {
+        table.unique_constraints.push(UniqueConstraintInfo {
+            name: Some(unique.constraint_name(&table.name)),
+            columns: unique.columns.iter().map(|c| (*c).to_string()).collect(),
+        });
+    }
+    for index in indexes {
+        table.indexes.push(IndexInfo {
+            name: index.index_name(&table.name),
+            columns: index.columns.iter().map(|c| (*c).to_string()).collect(),
+            unique: index.unique,
+            index_type: None,
+            primary: false,
+        });
+    }
+
+    table
+}
+
+/// Table schemas of every model in `registry`, sorted by table name.
+pub fn registry_table_schemas(registry: &ModelRegistry) -> Vec<TableInfo> {
+    registry.iter().map(table_schema_from_descriptor).collect()
+}
+
+/// Build a DatabaseSchema from every model in `registry`.
+///
+/// With [`ModelRegistry::global`] this is the expected schema of every
+/// `#[sqlmodel(register)]` model, ready for [`schema_diff`](crate::diff::schema_diff):
+///
+/// ```ignore
+/// let expected = registry_schema(ModelRegistry::global(), Dialect::Postgres);
+/// let diff = schema_diff(&current, &expected);
+/// ```
+pub fn registry_schema(registry: &ModelRegistry, dialect: Dialect) -> DatabaseSchema {
+    let mut schema = DatabaseSchema::new(dialect);
+    for table in registry_table_schemas(registry) {
+        schema.tables.insert(table.name.clone(), table);
+    }
+    schema
 }
 
 /// Convert field metadata to a TableInfo.
@@ -83,7 +149,9 @@ pub fn table_schema_from_fields(
             default: field.default.map(String::from),
             primary_key: field.primary_key,
             auto_increment: field.auto_increment,
-            comment: None,
+            comment: field.column_comment.map(String::from),
+            collation: field.collation.map(String::from),
+            charset: field.charset.map(String::from),
         });
 
         // Extract foreign key if present
@@ -347,6 +415,7 @@ mod tests {
     impl Model for TestHero {
         const TABLE_NAME: &'static str = "heroes";
         const PRIMARY_KEY: &'static [&'static str] = &["id"];
+        const TABLE_COMMENT: Option<&'static str> = Some("Registered heroes");
 
         fn fields() -> &'static [FieldInfo] {
             static FIELDS: &[FieldInfo] = &[
@@ -356,7 +425,8 @@ mod tests {
                     .auto_increment(true),
                 FieldInfo::new("name", "name", SqlType::Text)
                     .sql_type_override("VARCHAR(100)")
-                    .unique(true),
+                    .unique(true)
+                    .column_comment("Public alias"),
                 FieldInfo::new("age", "age", SqlType::Integer)
                     .nullable(true)
                     .index("idx_heroes_age"),
@@ -444,6 +514,17 @@ mod tests {
         assert!(!idx.unique);
     }
 
+    #[test]
+    fn test_model_schema_comments() {
+        let schema = TestHero::table_schema();
+        assert_eq!(schema.comment.as_deref(), Some("Registered heroes"));
+        assert_eq!(
+            schema.column("name").unwrap().comment.as_deref(),
+            Some("Public alias")
+        );
+        assert_eq!(schema.column("age").unwrap().comment, None);
+    }
+
     #[test]
     fn test_expected_schema() {
         let schema = expected_schema::<TestHero>(Dialect::Sqlite);
@@ -451,6 +532,21 @@ mod tests {
         assert!(schema.table("heroes").is_some());
     }
 
+    #[test]
+    fn test_registry_schema_matches_model_schema() {
+        let registry = ModelRegistry::new().register::<TestHero>();
+        let tables = registry_table_schemas(&registry);
+        assert_eq!(tables.len(), 1);
+        assert_eq!(
+            format!("{:?}", tables[0]),
+            format!("{:?}", TestHero::table_schema())
+        );
+
+        let schema = registry_schema(&registry, Dialect::Mysql);
+        assert_eq!(schema.dialect, Dialect::Mysql);
+        assert!(schema.table("heroes").is_some());
+    }
+
     #[test]
     fn test_model_tuple_two() {
         struct TestTeam;
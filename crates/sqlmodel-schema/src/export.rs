@@ -0,0 +1,974 @@
+//! CSV and JSON Lines export/import for whole tables.
+//!
+//! [`dump_table`] pages through a table and writes each row as it arrives, so
+//! large tables are never held in memory at once. [`import`] reads the same
+//! formats back, maps the header (CSV) or object keys (JSONL) onto a model's
+//! columns, coerces each value to the column's SQL type and inserts the rows
+//! with multi-row `INSERT` statements.
+//!
+//! # Value encoding
+//!
+//! | SQL type | CSV / JSONL text |
+//! |----------|------------------|
+//! | DATE | `2024-03-01` |
+//! | TIME | `13:45:00` or `13:45:00.250000` |
+//! | TIMESTAMP | `2024-03-01 13:45:00` |
+//! | TIMESTAMPTZ | `2024-03-01 13:45:00+00:00` |
+//! | UUID | hyphenated lowercase hex |
+//! | binary | lowercase hex |
+//! | JSON, arrays | compact JSON |
+//!
+//! In CSV an empty unquoted field is NULL and `""` is the empty string. In
+//! JSONL, numbers, booleans, JSON documents and arrays keep their JSON types;
+//! decimals are strings so no precision is lost.
+
+use std::borrow::Cow;
+use std::io::{BufRead, Write};
+
+use asupersync::{Cx, Outcome};
+use sqlmodel_core::{Connection, Dialect, Error, FieldInfo, Model, SqlDialect, SqlType, Value};
+
+/// Rows fetched per page by [`dump_table`].
+pub const DUMP_BATCH_ROWS: usize = 1000;
+
+/// Upper bound on bind parameters per `INSERT` issued by [`import`].
+const MAX_PARAMS: usize = 999;
+
+/// File format for [`dump_table`] and [`import`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Comma-separated values with a header row (RFC 4180 quoting).
+    Csv,
+    /// One JSON object per line, keyed by column name.
+    JsonLines,
+}
+
+/// Write every row of `table` to `writer` in `format`.
+///
+/// Rows are fetched [`DUMP_BATCH_ROWS`] at a time, ordered by the first
+/// column so paging is stable, and written as soon as each page arrives. An
+/// empty table produces no output (there is no row to take a CSV header
+/// from). Returns the number of rows written.
+///
+/// # Example
+///
+/// ```ignore
+/// use sqlmodel_schema::export::{Format, dump_table};
+///
+/// let mut out = std::io::BufWriter::new(std::fs::File::create("heroes.csv")?);
+/// let rows = dump_table(&cx, &conn, "heroes", Format::Csv, &mut out).await?;
+/// ```
+#[allow(clippy::result_large_err)]
+pub async fn dump_table<C: Connection, W: Write>(
+    cx: &Cx,
+    conn: &C,
+    table: &str,
+    format: Format,
+    writer: &mut W,
+) -> Outcome<u64, Error> {
+    let dialect = conn.sql_dialect();
+    let mut written = 0;
+    let mut offset = 0;
+    loop {
+        let sql = format!(
+            "SELECT * FROM {} ORDER BY 1 LIMIT {DUMP_BATCH_ROWS} OFFSET {offset}",
+            dialect.quote_identifier(table)
+        );
+        let rows = match conn.query(cx, &sql, &[]).await {
+            Outcome::Ok(rows) => rows,
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        };
+
+        for row in &rows {
+            let columns: Vec<&str> = row.column_names().collect();
+            let result = match format {
+                Format::Csv => {
+                    let header = if written == 0 {
+                        write_csv_header(writer, &columns)
+                    } else {
+                        Ok(())
+                    };
+                    header.and_then(|()| write_csv_row(writer, row.values()))
+                }
+                Format::JsonLines => write_json_row(writer, &columns, row.values()),
+            };
+            if let Err(e) = result {
+                return Outcome::Err(e);
+            }
+            written += 1;
+        }
+
+        if rows.len() < DUMP_BATCH_ROWS {
+            break;
+        }
+        offset += rows.len();
+    }
+
+    match writer.flush() {
+        Ok(()) => Outcome::Ok(written),
+        Err(e) => Outcome::Err(Error::Io(e)),
+    }
+}
+
+/// Insert the rows read from `reader` into `M`'s table.
+///
+/// CSV input must start with a header row; JSONL takes its columns from the
+/// keys of the first object, and later objects may omit keys (NULL) but not
+/// add new ones. Every column must be a column of `M`; model columns missing
+/// from the input get their database defaults, as do NULL auto-increment
+/// keys. Returns the number of rows inserted.
+///
+/// # Example
+///
+/// ```ignore
+/// use sqlmodel_schema::export::{Format, import};
+///
+/// let mut input = std::io::BufReader::new(std::fs::File::open("heroes.csv")?);
+/// let rows = import::<Hero, _, _>(&cx, &conn, Format::Csv, &mut input).await?;
+/// ```
+pub async fn import<M: Model, C: Connection, R: BufRead>(
+    cx: &Cx,
+    conn: &C,
+    format: Format,
+    reader: &mut R,
+) -> Outcome<u64, Error> {
+    let dialect = conn.sql_dialect();
+    let mut records = Records::new(format, reader, M::TABLE_NAME, M::fields());
+    let mut pending: Vec<Vec<Value>> = Vec::new();
+    let mut imported = 0;
+    loop {
+        let row = match records.next_row() {
+            Ok(row) => row,
+            Err(e) => return Outcome::Err(e),
+        };
+        let done = row.is_none();
+        pending.extend(row);
+
+        let rows_per_statement = (MAX_PARAMS / records.columns.len().max(1)).max(1);
+        if !pending.is_empty() && (done || pending.len() >= rows_per_statement) {
+            let (sql, params) =
+                insert_statement(dialect, M::TABLE_NAME, &records.columns, &pending);
+            match conn.execute(cx, &sql, &params).await {
+                Outcome::Ok(count) => imported += count,
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            }
+            pending.clear();
+        }
+
+        if done {
+            break;
+        }
+    }
+    Outcome::Ok(imported)
+}
+
+// ============================================================================
+// Writing
+// ============================================================================
+
+#[allow(clippy::result_large_err)]
+fn write_csv_header<W: Write>(writer: &mut W, columns: &[&str]) -> Result<(), Error> {
+    let fields: Vec<Cow<'_, str>> = columns.iter().map(|c| csv_field(c)).collect();
+    writeln!(writer, "{}", fields.join(","))?;
+    Ok(())
+}
+
+#[allow(clippy::result_large_err)]
+fn write_csv_row<'a, W: Write>(
+    writer: &mut W,
+    values: impl Iterator<Item = &'a Value>,
+) -> Result<(), Error> {
+    let fields: Vec<String> = values
+        .map(|value| match value_text(value) {
+            None => String::new(),
+            Some(text) => csv_field(&text).into_owned(),
+        })
+        .collect();
+    writeln!(writer, "{}", fields.join(","))?;
+    Ok(())
+}
+
+#[allow(clippy::result_large_err)]
+fn write_json_row<'a, W: Write>(
+    writer: &mut W,
+    columns: &[&str],
+    values: impl Iterator<Item = &'a Value>,
+) -> Result<(), Error> {
+    // Written by hand so keys follow `columns`, whatever map type serde_json uses
+    let json_err = |e: serde_json::Error| Error::Custom(format!("export: {e}"));
+    writer.write_all(b"{")?;
+    for (i, (column, value)) in columns.iter().zip(values).enumerate() {
+        if i > 0 {
+            writer.write_all(b",")?;
+        }
+        serde_json::to_writer(&mut *writer, column).map_err(json_err)?;
+        writer.write_all(b":")?;
+        serde_json::to_writer(&mut *writer, &json_value(value)).map_err(json_err)?;
+    }
+    writeln!(writer, "}}")?;
+    Ok(())
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or line break, or is
+/// empty (so it reads back as the empty string rather than NULL).
+fn csv_field(text: &str) -> Cow<'_, str> {
+    if text.is_empty() || text.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", text.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Text form of a value, `None` for NULL.
+fn value_text(value: &Value) -> Option<String> {
+    let text = match value {
+        Value::Null | Value::Default => return None,
+        Value::Bool(b) => b.to_string(),
+        Value::TinyInt(n) => n.to_string(),
+        Value::SmallInt(n) => n.to_string(),
+        Value::Int(n) => n.to_string(),
+        Value::BigInt(n) => n.to_string(),
+        Value::Float(n) => n.to_string(),
+        Value::Double(n) => n.to_string(),
+        Value::Decimal(s) | Value::Text(s) => s.clone(),
+        Value::Bytes(bytes) => hex_encode(bytes),
+        Value::Date(days) => format_date(i64::from(*days)),
+        Value::Time(micros) => format_time(*micros),
+        Value::Timestamp(micros) => format_timestamp(*micros),
+        Value::TimestampTz(micros) => format!("{}+00:00", format_timestamp(*micros)),
+        Value::Uuid(bytes) => format_uuid(bytes),
+        Value::Json(json) => json.to_string(),
+        Value::Array(_) => json_value(value).to_string(),
+    };
+    Some(text)
+}
+
+/// JSON form of a value; scalars that JSON cannot represent exactly are strings.
+fn json_value(value: &Value) -> serde_json::Value {
+    use serde_json::Value as Json;
+    match value {
+        Value::Null | Value::Default => Json::Null,
+        Value::Bool(b) => Json::Bool(*b),
+        Value::TinyInt(n) => Json::from(*n),
+        Value::SmallInt(n) => Json::from(*n),
+        Value::Int(n) => Json::from(*n),
+        Value::BigInt(n) => Json::from(*n),
+        Value::Float(n) => serde_json::Number::from_f64(f64::from(*n))
+            .map_or_else(|| Json::String(n.to_string()), Json::Number),
+        Value::Double(n) => serde_json::Number::from_f64(*n)
+            .map_or_else(|| Json::String(n.to_string()), Json::Number),
+        Value::Json(json) => json.clone(),
+        Value::Array(items) => Json::Array(items.iter().map(json_value).collect()),
+        other => value_text(other).map_or(Json::Null, Json::String),
+    }
+}
+
+// ============================================================================
+// Reading
+// ============================================================================
+
+/// Rows of an import source, already coerced to their columns' types.
+struct Records<'r, R> {
+    format: Format,
+    reader: &'r mut R,
+    table: &'static str,
+    fields: &'static [FieldInfo],
+    columns: Vec<&'static FieldInfo>,
+    record: usize,
+}
+
+impl<'r, R: BufRead> Records<'r, R> {
+    fn new(
+        format: Format,
+        reader: &'r mut R,
+        table: &'static str,
+        fields: &'static [FieldInfo],
+    ) -> Self {
+        Self {
+            format,
+            reader,
+            table,
+            fields,
+            columns: Vec::new(),
+            record: 0,
+        }
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn next_row(&mut self) -> Result<Option<Vec<Value>>, Error> {
+        match self.format {
+            Format::Csv => self.next_csv_row(),
+            Format::JsonLines => self.next_json_row(),
+        }
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn next_csv_row(&mut self) -> Result<Option<Vec<Value>>, Error> {
+        loop {
+            let Some(record) = read_csv_record(self.reader)? else {
+                return Ok(None);
+            };
+            self.record += 1;
+            if record.len() == 1 && record[0].is_none() {
+                continue;
+            }
+
+            if self.columns.is_empty() {
+                self.columns = record
+                    .iter()
+                    .map(|name| self.column(name.as_deref().unwrap_or_default()))
+                    .collect::<Result<_, _>>()?;
+                continue;
+            }
+
+            if record.len() != self.columns.len() {
+                return Err(Error::Custom(format!(
+                    "import: record {} has {} fields, expected {}",
+                    self.record,
+                    record.len(),
+                    self.columns.len()
+                )));
+            }
+            return self
+                .columns
+                .iter()
+                .zip(record)
+                .map(|(field, text)| match text {
+                    None => Ok(Value::Null),
+                    Some(text) => coerce_text(&field.sql_type, &text)
+                        .ok_or_else(|| self.invalid(field, &text)),
+                })
+                .collect::<Result<_, _>>()
+                .map(Some);
+        }
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn next_json_row(&mut self) -> Result<Option<Vec<Value>>, Error> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            self.record += 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let mut object: serde_json::Map<String, serde_json::Value> =
+                serde_json::from_str(&line)
+                    .map_err(|e| Error::Custom(format!("import: record {}: {e}", self.record)))?;
+            if self.columns.is_empty() {
+                self.columns = object
+                    .keys()
+                    .map(|name| self.column(name))
+                    .collect::<Result<_, _>>()?;
+            }
+
+            let row = self
+                .columns
+                .iter()
+                .map(|field| {
+                    let json = object
+                        .remove(field.column_name)
+                        .unwrap_or(serde_json::Value::Null);
+                    coerce_json(&field.sql_type, &json)
+                        .ok_or_else(|| self.invalid(field, &json.to_string()))
+                })
+                .collect::<Result<_, _>>()?;
+            if let Some(extra) = object.keys().next() {
+                return Err(Error::Custom(format!(
+                    "import: record {}: key `{extra}` does not appear in the first record",
+                    self.record
+                )));
+            }
+            return Ok(Some(row));
+        }
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn column(&self, name: &str) -> Result<&'static FieldInfo, Error> {
+        self.fields
+            .iter()
+            .find(|f| f.column_name == name)
+            .ok_or_else(|| {
+                Error::Custom(format!(
+                    "import: column `{name}` is not a column of {}",
+                    self.table
+                ))
+            })
+    }
+
+    fn invalid(&self, field: &FieldInfo, text: &str) -> Error {
+        Error::Custom(format!(
+            "import: record {}: `{text}` is not a valid {} for column `{}`",
+            self.record,
+            field.sql_type.sql_name(),
+            field.column_name
+        ))
+    }
+}
+
+/// Read one CSV record, which may span several lines inside quotes.
+///
+/// Unquoted empty fields are `None`; quoted fields are always `Some`.
+fn read_csv_record<R: BufRead>(reader: &mut R) -> std::io::Result<Option<Vec<Option<String>>>> {
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut line = String::new();
+    let mut started = false;
+
+    let finish = |field: &mut String, quoted: &mut bool| {
+        let text = std::mem::take(field);
+        let value = if text.is_empty() && !*quoted {
+            None
+        } else {
+            Some(text)
+        };
+        *quoted = false;
+        value
+    };
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            if !started {
+                return Ok(None);
+            }
+            if in_quotes {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "unterminated quoted CSV field",
+                ));
+            }
+            record.push(finish(&mut field, &mut quoted));
+            return Ok(Some(record));
+        }
+        started = true;
+
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            if in_quotes {
+                if c != '"' {
+                    field.push(c);
+                } else if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    in_quotes = false;
+                }
+                continue;
+            }
+            match c {
+                '"' => {
+                    in_quotes = true;
+                    quoted = true;
+                }
+                ',' => record.push(finish(&mut field, &mut quoted)),
+                '\r' if chars.peek() == Some(&'\n') => {}
+                '\n' => {
+                    record.push(finish(&mut field, &mut quoted));
+                    return Ok(Some(record));
+                }
+                _ => field.push(c),
+            }
+        }
+    }
+}
+
+/// Parse the text form of a value of `sql_type`.
+fn coerce_text(sql_type: &SqlType, text: &str) -> Option<Value> {
+    let value = match sql_type {
+        SqlType::Boolean => match text.trim().to_ascii_lowercase().as_str() {
+            "true" | "t" | "1" | "yes" | "y" => Value::Bool(true),
+            "false" | "f" | "0" | "no" | "n" => Value::Bool(false),
+            _ => return None,
+        },
+        SqlType::TinyInt => Value::TinyInt(text.trim().parse().ok()?),
+        SqlType::SmallInt => Value::SmallInt(text.trim().parse().ok()?),
+        SqlType::Integer => Value::Int(text.trim().parse().ok()?),
+        SqlType::BigInt => Value::BigInt(text.trim().parse().ok()?),
+        SqlType::Real => Value::Float(text.trim().parse().ok()?),
+        SqlType::Double => Value::Double(text.trim().parse().ok()?),
+        SqlType::Numeric { .. } | SqlType::Decimal { .. } => {
+            let text = text.trim();
+            text.parse::<f64>().ok()?;
+            Value::Decimal(text.to_string())
+        }
+        SqlType::Char(_)
+        | SqlType::VarChar(_)
+        | SqlType::Text
+        | SqlType::CiText
+        | SqlType::Enum(_)
+        | SqlType::Custom(_) => Value::Text(text.to_string()),
+        SqlType::Binary(_) | SqlType::VarBinary(_) | SqlType::Blob => {
+            Value::Bytes(hex_decode(text.trim().trim_start_matches("\\x"))?)
+        }
+        SqlType::Date => Value::Date(i32::try_from(parse_date(text.trim())?).ok()?),
+        SqlType::Time => Value::Time(parse_time(text.trim())?),
+        SqlType::DateTime | SqlType::Timestamp => Value::Timestamp(parse_timestamp(text.trim())?),
+        SqlType::TimestampTz => Value::TimestampTz(parse_timestamp(text.trim())?),
+        SqlType::Uuid => Value::Uuid(parse_uuid(text.trim())?),
+        SqlType::Json | SqlType::JsonB => Value::Json(serde_json::from_str(text).ok()?),
+        SqlType::Array(_) => coerce_json(sql_type, &serde_json::from_str(text).ok()?)?,
+    };
+    Some(value)
+}
+
+/// Convert a JSON value to a value of `sql_type`.
+fn coerce_json(sql_type: &SqlType, json: &serde_json::Value) -> Option<Value> {
+    use serde_json::Value as Json;
+    match (sql_type, json) {
+        (_, Json::Null) => Some(Value::Null),
+        (SqlType::Json | SqlType::JsonB, json) => Some(Value::Json(json.clone())),
+        (SqlType::Array(inner), Json::Array(items)) => items
+            .iter()
+            .map(|item| coerce_json(inner, item))
+            .collect::<Option<_>>()
+            .map(Value::Array),
+        (_, Json::String(text)) => coerce_text(sql_type, text),
+        (_, Json::Bool(_) | Json::Number(_)) => coerce_text(sql_type, &json.to_string()),
+        (_, Json::Array(_) | Json::Object(_)) => None,
+    }
+}
+
+// ============================================================================
+// Inserting
+// ============================================================================
+
+/// A multi-row `INSERT` of `rows` into `columns` of `table`.
+///
+/// NULL auto-increment keys become `DEFAULT` so the database assigns them;
+/// SQLite has no `DEFAULT` in `VALUES` but assigns a rowid for NULL.
+fn insert_statement(
+    dialect: &'static dyn SqlDialect,
+    table: &str,
+    columns: &[&'static FieldInfo],
+    rows: &[Vec<Value>],
+) -> (String, Vec<Value>) {
+    let mut params = Vec::with_capacity(rows.len() * columns.len());
+    let mut tuples = Vec::with_capacity(rows.len());
+    for row in rows {
+        let mut placeholders = Vec::with_capacity(columns.len());
+        for (field, value) in columns.iter().zip(row) {
+            if field.auto_increment && value.is_null() && dialect.base_dialect() != Dialect::Sqlite
+            {
+                placeholders.push("DEFAULT".to_string());
+            } else {
+                params.push(value.clone());
+                placeholders.push(dialect.placeholder(params.len()));
+            }
+        }
+        tuples.push(format!("({})", placeholders.join(", ")));
+    }
+
+    let column_list: Vec<String> = columns
+        .iter()
+        .map(|field| dialect.quote_identifier(field.column_name))
+        .collect();
+    let sql = format!(
+        "INSERT INTO {} ({}) VALUES {}",
+        dialect.quote_identifier(table),
+        column_list.join(", "),
+        tuples.join(", ")
+    );
+    (sql, params)
+}
+
+// ============================================================================
+// Text encodings
+// ============================================================================
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const MICROS_PER_DAY: i64 = 86_400 * MICROS_PER_SECOND;
+
+/// Civil date for a day count since 1970-01-01 (Howard Hinnant's algorithm).
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Day count since 1970-01-01 for a civil date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn format_date(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+fn format_time(micros: i64) -> String {
+    let seconds = micros.div_euclid(MICROS_PER_SECOND);
+    let fraction = micros.rem_euclid(MICROS_PER_SECOND);
+    let (h, m, s) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if fraction == 0 {
+        format!("{h:02}:{m:02}:{s:02}")
+    } else {
+        format!("{h:02}:{m:02}:{s:02}.{fraction:06}")
+    }
+}
+
+fn format_timestamp(micros: i64) -> String {
+    format!(
+        "{} {}",
+        format_date(micros.div_euclid(MICROS_PER_DAY)),
+        format_time(micros.rem_euclid(MICROS_PER_DAY))
+    )
+}
+
+fn format_uuid(bytes: &[u8; 16]) -> String {
+    let hex = hex_encode(bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+fn parse_date(text: &str) -> Option<i64> {
+    let mut parts = text.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    Some(days_from_civil(year, month, day))
+}
+
+/// `HH:MM[:SS[.ffffff]]` as microseconds since midnight.
+fn parse_time(text: &str) -> Option<i64> {
+    let (clock, fraction) = text.split_once('.').unwrap_or((text, ""));
+    let mut parts = clock.split(':');
+    let hours: i64 = parts.next()?.parse().ok()?;
+    let minutes: i64 = parts.next()?.parse().ok()?;
+    let seconds: i64 = parts.next().map_or(Some(0), |s| s.parse().ok())?;
+    if parts.next().is_some() || minutes > 59 || seconds > 60 || fraction.len() > 6 {
+        return None;
+    }
+    let fraction: i64 = if fraction.is_empty() {
+        0
+    } else {
+        format!("{fraction:0<6}").parse().ok()?
+    };
+    Some((hours * 3600 + minutes * 60 + seconds) * MICROS_PER_SECOND + fraction)
+}
+
+/// `YYYY-MM-DD[ T]HH:MM:SS[.ffffff][Z|±HH:MM]` as microseconds since the epoch (UTC).
+fn parse_timestamp(text: &str) -> Option<i64> {
+    let (date, rest) = match text.find(['T', ' ']) {
+        Some(i) => (&text[..i], &text[i + 1..]),
+        None => (text, "00:00:00"),
+    };
+    let (time, offset_micros) = if let Some(time) = rest.strip_suffix('Z') {
+        (time, 0)
+    } else if let Some(i) = rest.rfind(['+', '-']) {
+        let sign = if rest[i..].starts_with('-') { -1 } else { 1 };
+        (&rest[..i], sign * parse_time(&rest[i + 1..])?)
+    } else {
+        (rest, 0)
+    };
+    Some(parse_date(date)? * MICROS_PER_DAY + parse_time(time)? - offset_micros)
+}
+
+fn parse_uuid(text: &str) -> Option<[u8; 16]> {
+    let hex: String = text.chars().filter(|c| *c != '-').collect();
+    hex_decode(&hex)?.try_into().ok()
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    use std::fmt::Write as _;
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut out, b| {
+            let _ = write!(out, "{b:02x}");
+            out
+        })
+}
+
+fn hex_decode(text: &str) -> Option<Vec<u8>> {
+    if text.len() % 2 != 0 || !text.is_ascii() {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlmodel_core::Row;
+
+    struct Hero;
+
+    impl Model for Hero {
+        const TABLE_NAME: &'static str = "heroes";
+        const PRIMARY_KEY: &'static [&'static str] = &["id"];
+
+        fn fields() -> &'static [FieldInfo] {
+            static FIELDS: &[FieldInfo] = &[
+                FieldInfo::new("id", "id", SqlType::BigInt)
+                    .nullable(true)
+                    .primary_key(true)
+                    .auto_increment(true),
+                FieldInfo::new("name", "name", SqlType::Text),
+                FieldInfo::new("age", "age", SqlType::Integer).nullable(true),
+                FieldInfo::new("born", "born", SqlType::Date).nullable(true),
+            ];
+            FIELDS
+        }
+
+        fn to_row(&self) -> Vec<(&'static str, Value)> {
+            vec![]
+        }
+
+        fn from_row(_row: &Row) -> sqlmodel_core::Result<Self> {
+            Ok(Hero)
+        }
+
+        fn primary_key_value(&self) -> Vec<Value> {
+            vec![]
+        }
+
+        fn is_new(&self) -> bool {
+            true
+        }
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn csv_rows(input: &str) -> Result<Vec<Vec<Value>>, Error> {
+        let mut reader = input.as_bytes();
+        let mut records = Records::new(Format::Csv, &mut reader, Hero::TABLE_NAME, Hero::fields());
+        let mut rows = Vec::new();
+        while let Some(row) = records.next_row()? {
+            rows.push(row);
+        }
+        Ok(rows)
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let row = Row::new(
+            vec!["id".into(), "name".into(), "age".into(), "born".into()],
+            vec![
+                Value::BigInt(1),
+                Value::Text("Deadpond, \"the\"\nmerc".into()),
+                Value::Null,
+                Value::Date(19_783),
+            ],
+        );
+        let mut out = Vec::new();
+        write_csv_header(&mut out, &row.column_names().collect::<Vec<_>>()).unwrap();
+        write_csv_row(&mut out, row.values()).unwrap();
+        write_csv_row(&mut out, [Value::Null, Value::Text(String::new())].iter()).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(
+            text,
+            "id,name,age,born\n1,\"Deadpond, \"\"the\"\"\nmerc\",,2024-03-01\n,\"\"\n"
+        );
+
+        let mut reader = text.as_bytes();
+        assert_eq!(
+            read_csv_record(&mut reader).unwrap().unwrap(),
+            vec![
+                Some("id".to_string()),
+                Some("name".to_string()),
+                Some("age".to_string()),
+                Some("born".to_string())
+            ]
+        );
+        assert_eq!(
+            read_csv_record(&mut reader).unwrap().unwrap(),
+            vec![
+                Some("1".to_string()),
+                Some("Deadpond, \"the\"\nmerc".to_string()),
+                None,
+                Some("2024-03-01".to_string())
+            ]
+        );
+        assert_eq!(
+            read_csv_record(&mut reader).unwrap().unwrap(),
+            vec![None, Some(String::new())]
+        );
+        assert!(read_csv_record(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_csv_import_coerces_to_model_types() {
+        let rows = csv_rows("name,age,born\r\nAlice,30,2024-03-01\r\n\r\nBob,,\r\n").unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                vec![
+                    Value::Text("Alice".into()),
+                    Value::Int(30),
+                    Value::Date(19_783)
+                ],
+                vec![Value::Text("Bob".into()), Value::Null, Value::Null],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_csv_import_errors() {
+        let err = csv_rows("name,power\nAlice,9000\n").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("`power` is not a column of heroes")
+        );
+
+        let err = csv_rows("name,age\nAlice,thirty\n").unwrap_err();
+        assert!(err.to_string().contains("`thirty` is not a valid"));
+
+        let err = csv_rows("name,age\nAlice\n").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("record 2 has 1 fields, expected 2")
+        );
+    }
+
+    #[test]
+    fn test_json_lines_round_trip() {
+        let values = [
+            Value::BigInt(7),
+            Value::Text("Rusty".into()),
+            Value::Null,
+            Value::Date(0),
+        ];
+        let mut out = Vec::new();
+        write_json_row(&mut out, &["id", "name", "age", "born"], values.iter()).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(
+            text,
+            "{\"id\":7,\"name\":\"Rusty\",\"age\":null,\"born\":\"1970-01-01\"}\n"
+        );
+
+        let input = format!("{text}\n{{\"name\":\"Sue\"}}\n");
+        let mut reader = input.as_bytes();
+        let mut records = Records::new(
+            Format::JsonLines,
+            &mut reader,
+            Hero::TABLE_NAME,
+            Hero::fields(),
+        );
+        let first = records.next_row().unwrap().unwrap();
+        assert_eq!(
+            first,
+            vec![
+                Value::BigInt(7),
+                Value::Text("Rusty".into()),
+                Value::Null,
+                Value::Date(0)
+            ]
+        );
+        let second = records.next_row().unwrap().unwrap();
+        assert_eq!(
+            second,
+            vec![
+                Value::Null,
+                Value::Text("Sue".into()),
+                Value::Null,
+                Value::Null
+            ]
+        );
+        assert!(records.next_row().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_value_text_encodings() {
+        assert_eq!(
+            value_text(&Value::Time(49_500_250_000)).unwrap(),
+            "13:45:00.250000"
+        );
+        assert_eq!(
+            value_text(&Value::TimestampTz(1_709_300_700_000_000)).unwrap(),
+            "2024-03-01 13:45:00+00:00"
+        );
+        assert_eq!(value_text(&Value::Bytes(vec![0, 255])).unwrap(), "00ff");
+        assert_eq!(
+            value_text(&Value::Uuid([0xab; 16])).unwrap(),
+            "abababab-abab-abab-abab-abababababab"
+        );
+        assert!(value_text(&Value::Null).is_none());
+    }
+
+    #[test]
+    fn test_coerce_text() {
+        assert_eq!(
+            coerce_text(&SqlType::Timestamp, "2024-03-01T13:45:00Z"),
+            Some(Value::Timestamp(1_709_300_700_000_000))
+        );
+        assert_eq!(
+            coerce_text(&SqlType::TimestampTz, "2024-03-01 15:45:00+02:00"),
+            Some(Value::TimestampTz(1_709_300_700_000_000))
+        );
+        assert_eq!(
+            coerce_text(&SqlType::Uuid, "abababab-abab-abab-abab-abababababab"),
+            Some(Value::Uuid([0xab; 16]))
+        );
+        assert_eq!(
+            coerce_text(&SqlType::Boolean, "Yes"),
+            Some(Value::Bool(true))
+        );
+        assert_eq!(
+            coerce_text(&SqlType::Blob, "\\x00ff"),
+            Some(Value::Bytes(vec![0, 255]))
+        );
+        assert_eq!(
+            coerce_text(&SqlType::Array(Box::new(SqlType::Integer)), "[1,2]"),
+            Some(Value::Array(vec![Value::Int(1), Value::Int(2)]))
+        );
+        assert_eq!(coerce_text(&SqlType::Integer, "12x"), None);
+        assert_eq!(coerce_text(&SqlType::Date, "2024-13-01"), None);
+    }
+
+    #[test]
+    fn test_insert_statement_uses_default_for_null_keys() {
+        let columns: Vec<&FieldInfo> = Hero::fields()[..2].iter().collect();
+        let rows = vec![
+            vec![Value::Null, Value::Text("a".into())],
+            vec![Value::BigInt(5), Value::Text("b".into())],
+        ];
+
+        let (sql, params) = insert_statement(Dialect::Postgres.as_dyn(), "heroes", &columns, &rows);
+        assert_eq!(
+            sql,
+            "INSERT INTO \"heroes\" (\"id\", \"name\") VALUES (DEFAULT, $1), ($2, $3)"
+        );
+        assert_eq!(params.len(), 3);
+
+        let (sql, params) = insert_statement(Dialect::Sqlite.as_dyn(), "heroes", &columns, &rows);
+        assert_eq!(
+            sql,
+            "INSERT INTO \"heroes\" (\"id\", \"name\") VALUES (?1, ?2), (?3, ?4)"
+        );
+        assert_eq!(params[0], Value::Null);
+    }
+}
@@ -4,7 +4,9 @@
 //! It extracts metadata about tables, columns, constraints, and indexes.
 
 use asupersync::{Cx, Outcome};
-use sqlmodel_core::{Connection, Error};
+use sqlmodel_core::dialect::{IntoSqlDialect, SqlDialect};
+use sqlmodel_core::{Connection, Error, TableNamespace};
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 #[cfg(test)]
@@ -235,6 +237,11 @@ pub struct ColumnInfo {
     pub auto_increment: bool,
     /// Column comment (if any)
     pub comment: Option<String>,
+    /// Collation, when the column does not use the database default
+    /// (SQLite does not report it)
+    pub collation: Option<String>,
+    /// Character set (MySQL only)
+    pub charset: Option<String>,
 }
 
 /// Information about a foreign key constraint.
@@ -281,6 +288,8 @@ struct MySqlIndexAccumulator {
 pub struct Introspector {
     /// Database type for dialect-specific queries
     dialect: Dialect,
+    /// Table prefix and schema to introspect, if any
+    namespace: Option<TableNamespace>,
 }
 
 /// Supported database dialects.
@@ -295,10 +304,89 @@ pub enum Dialect {
     Mysql,
 }
 
+impl From<sqlmodel_core::Dialect> for Dialect {
+    fn from(dialect: sqlmodel_core::Dialect) -> Self {
+        match dialect {
+            sqlmodel_core::Dialect::Sqlite => Dialect::Sqlite,
+            sqlmodel_core::Dialect::Postgres => Dialect::Postgres,
+            sqlmodel_core::Dialect::Mysql => Dialect::Mysql,
+        }
+    }
+}
+
+impl From<Dialect> for sqlmodel_core::Dialect {
+    fn from(dialect: Dialect) -> Self {
+        match dialect {
+            Dialect::Sqlite => sqlmodel_core::Dialect::Sqlite,
+            Dialect::Postgres => sqlmodel_core::Dialect::Postgres,
+            Dialect::Mysql => sqlmodel_core::Dialect::Mysql,
+        }
+    }
+}
+
+impl Dialect {
+    /// The built-in [`SqlDialect`] for this dialect.
+    #[must_use]
+    pub fn as_dyn(self) -> &'static dyn SqlDialect {
+        sqlmodel_core::Dialect::from(self).as_dyn()
+    }
+}
+
+impl IntoSqlDialect for Dialect {
+    fn into_sql_dialect(self) -> &'static dyn SqlDialect {
+        self.as_dyn()
+    }
+}
+
 impl Introspector {
     /// Create a new introspector for the given dialect.
     pub fn new(dialect: Dialect) -> Self {
-        Self { dialect }
+        Self {
+            dialect,
+            namespace: None,
+        }
+    }
+
+    /// Introspect the tables of `namespace` instead of the current schema.
+    ///
+    /// Tables are reported under their model names: only tables carrying the
+    /// namespace's prefix are listed, with the prefix stripped, and
+    /// [`table_info`](Self::table_info) takes unprefixed names. The schema, if
+    /// set, replaces the connection's current schema (Postgres) or database
+    /// (MySQL); SQLite ignores it.
+    pub fn with_namespace(mut self, namespace: TableNamespace) -> Self {
+        self.namespace = Some(namespace);
+        self
+    }
+
+    /// `sql` with the current schema replaced by the namespace's schema.
+    fn scoped<'a>(&self, sql: &'a str) -> Cow<'a, str> {
+        let Some(schema) = self.namespace.as_ref().and_then(|ns| ns.schema_name()) else {
+            return Cow::Borrowed(sql);
+        };
+        let literal = format!("'{}'", schema.replace('\'', "''"));
+        match self.dialect {
+            Dialect::Postgres => Cow::Owned(sql.replace("current_schema()", &literal)),
+            Dialect::Mysql => Cow::Owned(sql.replace("DATABASE()", &literal)),
+            Dialect::Sqlite => Cow::Borrowed(sql),
+        }
+    }
+
+    /// The physical name of model table `name`.
+    fn physical_name(&self, name: &str) -> String {
+        match &self.namespace {
+            Some(ns) => ns.table_name(name),
+            None => name.to_string(),
+        }
+    }
+
+    /// The model name of physical table `name`, or `None` if it is outside
+    /// the namespace.
+    fn logical_name(&self, name: &str) -> Option<String> {
+        match &self.namespace {
+            Some(ns) => ns.logical_name(name).map(String::from),
+            None => Some(name.to_string()),
+        }
     }
 
     /// List all table names in the database.
@@ -320,7 +408,7 @@ impl Introspector {
             Dialect::Mysql => "SHOW TABLES",
         };
 
-        let rows = match conn.query(cx, sql, &[]).await {
+        let rows = match conn.query(cx, &self.scoped(sql), &[]).await {
             Outcome::Ok(rows) => rows,
             Outcome::Err(e) => return Outcome::Err(e),
             Outcome::Cancelled(r) => return Outcome::Cancelled(r),
@@ -329,7 +417,8 @@ impl Introspector {
 
         let names: Vec<String> = rows
             .iter()
-            .filter_map(|row| row.get(0).and_then(|v| v.as_str().map(String::from)))
+            .filter_map(|row| row.get(0).and_then(|v| v.as_str()))
+            .filter_map(|name| self.logical_name(name))
             .collect();
 
         Outcome::Ok(names)
@@ -342,6 +431,10 @@ impl Introspector {
         conn: &C,
         table_name: &str,
     ) -> Outcome<TableInfo, Error> {
+        let name = table_name;
+        let physical = self.physical_name(name);
+        let table_name = physical.as_str();
+
         let columns = match self.columns(cx, conn, table_name).await {
             Outcome::Ok(cols) => cols,
             Outcome::Err(e) => return Outcome::Err(e),
@@ -355,20 +448,34 @@ impl Introspector {
             .map(|c| c.name.clone())
             .collect();
 
-        let foreign_keys = match self.foreign_keys(cx, conn, table_name).await {
+        let mut foreign_keys = match self.foreign_keys(cx, conn, table_name).await {
             Outcome::Ok(fks) => fks,
             Outcome::Err(e) => return Outcome::Err(e),
             Outcome::Cancelled(r) => return Outcome::Cancelled(r),
             Outcome::Panicked(p) => return Outcome::Panicked(p),
         };
 
-        let indexes = match self.indexes(cx, conn, table_name).await {
+        let mut indexes = match self.indexes(cx, conn, table_name).await {
             Outcome::Ok(idxs) => idxs,
             Outcome::Err(e) => return Outcome::Err(e),
             Outcome::Cancelled(r) => return Outcome::Cancelled(r),
             Outcome::Panicked(p) => return Outcome::Panicked(p),
         };
 
+        // Report namespaced tables and indexes under their model names.
+        if self.namespace.is_some() {
+            for fk in &mut foreign_keys {
+                if let Some(logical) = self.logical_name(&fk.foreign_table) {
+                    fk.foreign_table = logical;
+                }
+            }
+            for idx in &mut indexes {
+                if let Some(logical) = self.logical_name(&idx.name) {
+                    idx.name = logical;
+                }
+            }
+        }
+
         let unique_constraints = match self.dialect {
             Dialect::Postgres => match self.postgres_unique_constraints(cx, conn, table_name).await
             {
@@ -422,7 +529,7 @@ impl Introspector {
         };
 
         Outcome::Ok(TableInfo {
-            name: table_name.to_string(),
+            name: name.to_string(),
             columns,
             primary_key,
             foreign_keys,
@@ -439,7 +546,7 @@ impl Introspector {
         conn: &C,
         table_name: &str,
     ) -> Outcome<Vec<UniqueConstraintInfo>, Error> {
-        debug_assert!(self.dialect == Dialect::Postgres);
+        debug_assert_eq!(self.dialect, Dialect::Postgres);
 
         let sql = "SELECT
                        c.conname AS constraint_name,
@@ -458,7 +565,7 @@ impl Introspector {
         let rows = match conn
             .query(
                 cx,
-                sql,
+                &self.scoped(sql),
                 &[sqlmodel_core::Value::Text(table_name.to_string())],
             )
             .await
@@ -571,6 +678,8 @@ impl Introspector {
                     primary_key: pk > 0,
                     auto_increment: false, // SQLite doesn't report this via PRAGMA
                     comment: None,         // SQLite doesn't support column comments
+                    collation: None,       // Not reported by PRAGMA table_info
+                    charset: None,
                 })
             })
             .collect();
@@ -594,6 +703,7 @@ impl Introspector {
                        c.numeric_scale,
                        c.is_nullable,
                        c.column_default,
+                       c.collation_name,
                        COALESCE(d.description, '') as column_comment
                    FROM information_schema.columns c
                    LEFT JOIN pg_catalog.pg_statio_all_tables st
@@ -606,7 +716,7 @@ impl Introspector {
         let rows = match conn
             .query(
                 cx,
-                sql,
+                &self.scoped(sql),
                 &[sqlmodel_core::Value::Text(table_name.to_string())],
             )
             .await
@@ -629,6 +739,7 @@ impl Introspector {
                 let nullable_str = row.get_named::<String>("is_nullable").ok()?;
                 let default = row.get_named::<String>("column_default").ok();
                 let comment = row.get_named::<String>("column_comment").ok();
+                let collation = row.get_named::<String>("collation_name").ok();
 
                 // Build a complete SQL type string
                 let sql_type =
@@ -647,6 +758,8 @@ impl Introspector {
                     primary_key: false, // Determined via separate index query
                     auto_increment,
                     comment: comment.filter(|s| !s.is_empty()),
+                    collation,
+                    charset: None,
                 })
             })
             .collect();
@@ -682,6 +795,12 @@ impl Introspector {
                 let default = row.get_named::<String>("Default").ok();
                 let extra = row.get_named::<String>("Extra").ok().unwrap_or_default();
                 let comment = row.get_named::<String>("Comment").ok();
+                // NULL for non-text columns; a collation name starts with its character set
+                let collation = row.get_named::<String>("Collation").ok();
+                let charset = collation
+                    .as_deref()
+                    .and_then(|c| c.split('_').next())
+                    .map(String::from);
                 let parsed_type = ParsedSqlType::parse(&sql_type);
 
                 Some(ColumnInfo {
@@ -693,6 +812,8 @@ impl Introspector {
                     primary_key: key == "PRI",
                     auto_increment: extra.contains("auto_increment"),
                     comment: comment.filter(|s| !s.is_empty()),
+                    collation,
+                    charset,
                 })
             })
             .collect();
@@ -727,7 +848,7 @@ impl Introspector {
         let rows = match conn
             .query(
                 cx,
-                sql,
+                &self.scoped(sql),
                 &[sqlmodel_core::Value::Text(table_name.to_string())],
             )
             .await
@@ -771,7 +892,7 @@ impl Introspector {
         let rows = match conn
             .query(
                 cx,
-                sql,
+                &self.scoped(sql),
                 &[sqlmodel_core::Value::Text(table_name.to_string())],
             )
             .await
@@ -824,7 +945,7 @@ impl Introspector {
         let rows = match conn
             .query(
                 cx,
-                sql,
+                &self.scoped(sql),
                 &[sqlmodel_core::Value::Text(table_name.to_string())],
             )
             .await
@@ -886,7 +1007,7 @@ impl Introspector {
         let rows = match conn
             .query(
                 cx,
-                sql,
+                &self.scoped(sql),
                 &[sqlmodel_core::Value::Text(table_name.to_string())],
             )
             .await
@@ -920,7 +1041,7 @@ impl Introspector {
         let rows = match conn
             .query(
                 cx,
-                sql,
+                &self.scoped(sql),
                 &[sqlmodel_core::Value::Text(table_name.to_string())],
             )
             .await
@@ -1023,7 +1144,7 @@ impl Introspector {
         let rows = match conn
             .query(
                 cx,
-                sql,
+                &self.scoped(sql),
                 &[sqlmodel_core::Value::Text(table_name.to_string())],
             )
             .await
@@ -1082,7 +1203,7 @@ impl Introspector {
         let rows = match conn
             .query(
                 cx,
-                sql,
+                &self.scoped(sql),
                 &[sqlmodel_core::Value::Text(table_name.to_string())],
             )
             .await
@@ -1223,7 +1344,7 @@ impl Introspector {
         let rows = match conn
             .query(
                 cx,
-                sql,
+                &self.scoped(sql),
                 &[sqlmodel_core::Value::Text(table_name.to_string())],
             )
             .await
@@ -1351,6 +1472,11 @@ fn build_postgres_type(
         return format!("{}[]", udt_name.trim_start_matches('_'));
     }
 
+    // Extension and enum types (e.g. citext) are reported by name
+    if data_type == "USER-DEFINED" {
+        return udt_name.to_uppercase();
+    }
+
     // For character types with length
     if let Some(len) = char_len {
         return format!("{}({})", data_type.to_uppercase(), len);
@@ -1864,6 +1990,8 @@ mod tests {
                 primary_key: true,
                 auto_increment: true,
                 comment: None,
+                collation: None,
+                charset: None,
             }],
             primary_key: vec!["id".to_string()],
             foreign_keys: Vec::new(),
@@ -1884,6 +2012,12 @@ mod tests {
         assert_eq!(result, "text[]");
     }
 
+    #[test]
+    fn test_build_postgres_type_user_defined() {
+        let result = build_postgres_type("USER-DEFINED", "citext", None, None, None);
+        assert_eq!(result, "CITEXT");
+    }
+
     #[test]
     fn test_build_postgres_type_varchar() {
         let result = build_postgres_type("character varying", "", Some(100), None, None);
@@ -1896,6 +2030,32 @@ mod tests {
         assert_eq!(result, "NUMERIC(10,2)");
     }
 
+    #[test]
+    fn test_namespace_scopes_schema_and_maps_names() {
+        let ns = TableNamespace::new().prefix("t1_").schema("an'alytics");
+        let pg = Introspector::new(Dialect::Postgres).with_namespace(ns.clone());
+        assert_eq!(
+            pg.scoped("WHERE table_schema = current_schema()"),
+            "WHERE table_schema = 'an''alytics'"
+        );
+        assert_eq!(pg.physical_name("heroes"), "t1_heroes");
+        assert_eq!(pg.logical_name("t1_heroes").as_deref(), Some("heroes"));
+        assert_eq!(pg.logical_name("heroes"), None);
+
+        let mysql = Introspector::new(Dialect::Mysql).with_namespace(ns);
+        assert_eq!(
+            mysql.scoped("WHERE TABLE_SCHEMA = DATABASE()"),
+            "WHERE TABLE_SCHEMA = 'an''alytics'"
+        );
+
+        let plain = Introspector::new(Dialect::Postgres);
+        assert_eq!(
+            plain.scoped("WHERE table_schema = current_schema()"),
+            "WHERE table_schema = current_schema()"
+        );
+        assert_eq!(plain.logical_name("heroes").as_deref(), Some("heroes"));
+    }
+
     #[test]
     fn test_sanitize_identifier_normal() {
         assert_eq!(sanitize_identifier("users"), "users");
@@ -8,17 +8,27 @@
 //! - **Schema extraction**: derive expected tables/columns from `Model` definitions.
 //! - **Diff engine**: compare desired vs. actual schema for migration planning.
 //! - **DDL generation**: emit dialect-specific SQL for SQLite, MySQL, and Postgres.
+//! - **Triggers**: build BEFORE/AFTER row triggers for each dialect.
 //! - **Migration runner**: track, apply, and validate migrations.
+//! - **Export/import**: dump tables to CSV or JSON Lines and load them back.
+//! - **OpenAPI**: JSON Schema components for models and their Create/Update/Read shapes.
 //!
 //! Applications typically use this via `sqlmodel::SchemaBuilder`, but it can also be
 //! embedded in custom tooling or CI migration checks.
 
+pub mod admin;
+pub mod codegen;
 pub mod create;
 pub mod ddl;
 pub mod diff;
 pub mod expected;
+pub mod export;
+pub mod index;
 pub mod introspect;
 pub mod migrate;
+pub mod openapi;
+pub mod safe_ddl;
+pub mod trigger;
 
 pub use create::{CreateTable, SchemaBuilder};
 pub use ddl::{
@@ -26,17 +36,22 @@ pub use ddl::{
     generator_for_dialect,
 };
 pub use expected::{
-    ModelSchema, ModelTuple, expected_schema, normalize_sql_type, table_schema_from_fields,
+    ModelSchema, ModelTuple, expected_schema, normalize_sql_type, registry_schema,
+    registry_table_schemas, table_schema_from_descriptor, table_schema_from_fields,
     table_schema_from_model,
 };
+pub use index::CreateIndex;
 pub use introspect::{
     CheckConstraintInfo, ColumnInfo, DatabaseSchema, Dialect, ForeignKeyInfo, IndexInfo,
     Introspector, ParsedSqlType, TableInfo, UniqueConstraintInfo,
 };
 pub use migrate::{Migration, MigrationFormat, MigrationRunner, MigrationStatus, MigrationWriter};
+pub use safe_ddl::{Backfill, DdlFinding, DdlSeverity, SafeDdl};
+pub use sqlmodel_macros::embed_migrations;
+pub use trigger::{CreateTrigger, TriggerEvent, TriggerTiming};
 
 use asupersync::{Cx, Outcome};
-use sqlmodel_core::{Connection, Model, quote_ident};
+use sqlmodel_core::{Connection, Model, ModelRegistry, quote_ident};
 
 /// Create a table for a model type.
 ///
@@ -78,6 +93,19 @@ pub async fn create_all<C: Connection>(
     Outcome::Ok(())
 }
 
+/// Create the tables of every `#[sqlmodel(register)]` model that do not
+/// exist yet, parents first, and return their names.
+///
+/// Models are discovered through [`ModelRegistry::global`], so no model list
+/// has to be kept in sync by hand. Use [`admin::create_registered`] for a
+/// registry built at runtime.
+pub async fn create_all_registered<C: Connection>(
+    cx: &Cx,
+    conn: &C,
+) -> Outcome<Vec<String>, sqlmodel_core::Error> {
+    admin::create_registered(cx, conn, ModelRegistry::global()).await
+}
+
 /// Drop a table.
 pub async fn drop_table<C: Connection>(
     cx: &Cx,
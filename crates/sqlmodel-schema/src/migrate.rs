@@ -3,6 +3,8 @@
 //! This module provides:
 //! - Migration file generation from schema diffs
 //! - Writing migrations to disk (SQL or Rust format)
+//! - Loading migrations from a directory, or embedding them in the binary with
+//!   `embed_migrations!("migrations")`
 //! - Running migrations against a database
 //! - Tracking applied migrations
 
@@ -20,9 +22,9 @@ pub struct Migration {
     pub id: String,
     /// Human-readable description
     pub description: String,
-    /// SQL to apply the migration
+    /// SQL to apply the migration (may hold several statements)
     pub up: String,
-    /// SQL to revert the migration
+    /// SQL to revert the migration (may hold several statements)
     pub down: String,
 }
 
@@ -137,6 +139,165 @@ impl Migration {
     }
 }
 
+impl Migration {
+    /// Build a migration from the contents of a `.sql` migration file.
+    ///
+    /// Files written by [`MigrationWriter`] are split at their `UP` / `DOWN`
+    /// markers, and their `-- Migration:` header replaces `description`. A
+    /// file without markers is all `up`. The writer comments the `DOWN`
+    /// section out under a `-- Uncomment to enable rollback:` line; a section
+    /// that still starts with that line is uncommented here, so rolling the
+    /// migration back runs the statements the writer generated.
+    pub fn from_sql(id: impl Into<String>, description: impl Into<String>, content: &str) -> Self {
+        let description = content
+            .lines()
+            .find_map(|line| line.strip_prefix("-- Migration:"))
+            .map_or_else(|| description.into(), |d| d.trim().to_string());
+        let (up, down) = match content.split_once(UP_MARKER) {
+            Some((_, rest)) => rest.split_once(DOWN_MARKER).unwrap_or((rest, "")),
+            None => (content, ""),
+        };
+        let down = match down.trim().strip_prefix(ROLLBACK_HINT) {
+            Some(commented) => commented
+                .lines()
+                .map(|line| {
+                    line.strip_prefix("-- ")
+                        .or_else(|| line.strip_prefix("--"))
+                        .unwrap_or(line)
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            None => down.to_string(),
+        };
+        Self::new(id, description, up.trim(), down.trim())
+    }
+
+    /// Load the migrations in `dir`, ordered by version.
+    ///
+    /// Files are named `<version>_<description>.sql` or `.rs`, where the
+    /// version is all digits (as written by [`MigrationWriter`]). `.sql` files
+    /// are read with [`Migration::from_sql`]. `.rs` files can't be compiled at
+    /// runtime, so only the literal arguments of their `Migration::new(...)`
+    /// call are read; use `embed_migrations!` for Rust migrations that compute
+    /// their SQL. A `mod.rs` and files with other extensions are ignored.
+    #[allow(clippy::result_large_err)]
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Vec<Self>, Error> {
+        let dir = dir.as_ref();
+        let mut migrations = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let Some((stem, ext)) = name.rsplit_once('.') else {
+                continue;
+            };
+            if !matches!(ext, "sql" | "rs") || name == "mod.rs" {
+                continue;
+            }
+            let Some((version, description)) = parse_file_stem(stem) else {
+                return Err(Error::Custom(format!(
+                    "migration file {} does not start with a version number",
+                    path.display()
+                )));
+            };
+            let content = std::fs::read_to_string(&path)?;
+            let migration = if ext == "sql" {
+                Self::from_sql(version, description, &content)
+            } else {
+                parse_rust_migration(&content).ok_or_else(|| {
+                    Error::Custom(format!(
+                        "{}: expected `Migration::new(...)` with literal arguments; \
+                         embed Rust migrations with embed_migrations! instead",
+                        path.display()
+                    ))
+                })?
+            };
+            migrations.push(migration);
+        }
+
+        migrations.sort_by(|a, b| a.id.cmp(&b.id));
+        if let Some(pair) = migrations.windows(2).find(|w| w[0].id == w[1].id) {
+            return Err(Error::Custom(format!(
+                "duplicate migration version {} in {}",
+                pair[0].id,
+                dir.display()
+            )));
+        }
+        Ok(migrations)
+    }
+}
+
+/// Split a migration file stem into its version and description:
+/// `20250101120000_create_users` -> (`20250101120000`, `create users`).
+fn parse_file_stem(stem: &str) -> Option<(&str, String)> {
+    let digits = stem.bytes().take_while(u8::is_ascii_digit).count();
+    if digits == 0 {
+        return None;
+    }
+    let (version, rest) = stem.split_at(digits);
+    Some((version, rest.trim_start_matches('_').replace('_', " ")))
+}
+
+/// Read a Rust migration of the shape [`MigrationWriter`] writes: the four
+/// string-literal arguments of its `Migration::new(...)` call.
+fn parse_rust_migration(source: &str) -> Option<Migration> {
+    let (_, mut rest) = source.split_once("Migration::new(")?;
+    let mut args = Vec::with_capacity(4);
+    while args.len() < 4 {
+        let (literal, tail) =
+            string_literal(rest.trim_start_matches(|c: char| c.is_whitespace() || c == ','))?;
+        args.push(literal);
+        rest = tail;
+    }
+    let [id, description, up, down] = <[String; 4]>::try_from(args).ok()?;
+    Some(Migration::new(id, description, up.trim(), down.trim()))
+}
+
+/// Parse a leading Rust string literal (`"..."` or `r#"..."#`).
+fn string_literal(s: &str) -> Option<(String, &str)> {
+    if let Some(raw) = s.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let body = raw[hashes..].strip_prefix('"')?;
+        let close = format!("\"{}", "#".repeat(hashes));
+        let end = body.find(&close)?;
+        return Some((body[..end].to_string(), &body[end + close.len()..]));
+    }
+
+    let body = s.strip_prefix('"')?;
+    let mut out = String::new();
+    let mut chars = body.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((out, &body[i + 1..])),
+            '\\' => match chars.next()?.1 {
+                'n' => out.push('\n'),
+                'r' => out.push('\r'),
+                't' => out.push('\t'),
+                '0' => out.push('\0'),
+                '\n' => {
+                    // Line continuation: skip the newline and leading whitespace
+                    while chars.clone().next().is_some_and(|(_, c)| c.is_whitespace()) {
+                        chars.next();
+                    }
+                }
+                'u' => {
+                    let hex: String = chars
+                        .by_ref()
+                        .map(|(_, c)| c)
+                        .skip_while(|&c| c == '{')
+                        .take_while(|&c| c != '}')
+                        .collect();
+                    out.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                }
+                other => out.push(other),
+            },
+            c => out.push(c),
+        }
+    }
+    None
+}
+
 /// Check if a year is a leap year.
 fn is_leap_year(year: i64) -> bool {
     (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
@@ -146,6 +307,13 @@ fn is_leap_year(year: i64) -> bool {
 // Migration Writer
 // ============================================================================
 
+/// Line that starts the `up` section of a `.sql` migration file.
+const UP_MARKER: &str = "-- ========== UP ==========";
+/// Line that starts the `down` section of a `.sql` migration file.
+const DOWN_MARKER: &str = "-- ========== DOWN ==========";
+/// Line the writer puts above the commented-out `down` section.
+const ROLLBACK_HINT: &str = "-- Uncomment to enable rollback:";
+
 /// Format for migration files.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum MigrationFormat {
@@ -272,13 +440,16 @@ impl MigrationWriter {
         ));
 
         // UP migration
-        content.push_str("-- ========== UP ==========\n\n");
+        content.push_str(UP_MARKER);
+        content.push_str("\n\n");
         content.push_str(&m.up);
         content.push_str("\n\n");
 
         // DOWN migration (commented out by default for safety)
-        content.push_str("-- ========== DOWN ==========\n");
-        content.push_str("-- Uncomment to enable rollback:\n\n");
+        content.push_str(DOWN_MARKER);
+        content.push('\n');
+        content.push_str(ROLLBACK_HINT);
+        content.push_str("\n\n");
         for line in m.down.lines() {
             content.push_str("-- ");
             content.push_str(line);
@@ -360,6 +531,12 @@ impl MigrationRunner {
         }
     }
 
+    /// Create a runner for the migrations in `dir` (see [`Migration::load_dir`]).
+    #[allow(clippy::result_large_err)]
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Self, Error> {
+        Migration::load_dir(dir).map(Self::new)
+    }
+
     /// Set a custom migrations tracking table name.
     ///
     /// The name is sanitized to only allow alphanumeric characters and underscores
@@ -433,6 +610,9 @@ impl MigrationRunner {
     }
 
     /// Apply all pending migrations.
+    ///
+    /// Each migration's SQL runs as a script via `Connection::execute_script`,
+    /// so it may contain several statements, triggers and routine bodies.
     pub async fn migrate<C: Connection>(&self, cx: &Cx, conn: &C) -> Outcome<Vec<String>, Error> {
         let status = match self.status(cx, conn).await {
             Outcome::Ok(s) => s,
@@ -451,7 +631,7 @@ impl MigrationRunner {
                 };
 
                 // Execute the up migration
-                match conn.execute(cx, &migration.up, &[]).await {
+                match conn.execute_script(cx, &migration.up).await {
                     Outcome::Ok(_) => {}
                     Outcome::Err(e) => return Outcome::Err(e),
                     Outcome::Cancelled(r) => return Outcome::Cancelled(r),
@@ -459,9 +639,13 @@ impl MigrationRunner {
                 }
 
                 // Record the migration
+                let dialect = conn.sql_dialect();
                 let record_sql = format!(
-                    "INSERT INTO {} (id, description, applied_at) VALUES ($1, $2, $3)",
-                    self.table_name
+                    "INSERT INTO {} (id, description, applied_at) VALUES ({}, {}, {})",
+                    self.table_name,
+                    dialect.placeholder(1),
+                    dialect.placeholder(2),
+                    dialect.placeholder(3)
                 );
                 let now = std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
@@ -530,7 +714,7 @@ impl MigrationRunner {
         };
 
         // Execute the down migration
-        match conn.execute(cx, &migration.down, &[]).await {
+        match conn.execute_script(cx, &migration.down).await {
             Outcome::Ok(_) => {}
             Outcome::Err(e) => return Outcome::Err(e),
             Outcome::Cancelled(r) => return Outcome::Cancelled(r),
@@ -538,7 +722,11 @@ impl MigrationRunner {
         }
 
         // Remove the migration record
-        let delete_sql = format!("DELETE FROM {} WHERE id = $1", self.table_name);
+        let delete_sql = format!(
+            "DELETE FROM {} WHERE id = {}",
+            self.table_name,
+            conn.sql_dialect().placeholder(1)
+        );
         match conn
             .execute(cx, &delete_sql, &[Value::Text(id.clone())])
             .await
@@ -620,6 +808,8 @@ mod tests {
                     primary_key: true,
                     auto_increment: true,
                     comment: None,
+                    collation: None,
+                    charset: None,
                 },
                 ColumnInfo {
                     name: "name".to_string(),
@@ -630,6 +820,8 @@ mod tests {
                     primary_key: false,
                     auto_increment: false,
                     comment: None,
+                    collation: None,
+                    charset: None,
                 },
             ],
             primary_key: vec!["id".to_string()],
@@ -801,4 +993,92 @@ mod tests {
         let runner = MigrationRunner::new(vec![]).table_name("custom_migrations");
         assert_eq!(runner.table_name, "custom_migrations");
     }
+
+    #[test]
+    fn test_from_sql_reads_written_sections() {
+        let writer = MigrationWriter::new("/tmp");
+        let m = Migration::new(
+            "20260127120000",
+            "Create users",
+            "CREATE TABLE users (id INT);\nCREATE INDEX ix ON users (id);",
+            "DROP TABLE users;",
+        );
+        let parsed = Migration::from_sql("20260127120000", "create users", &writer.format_sql(&m));
+        assert_eq!(parsed.description, "Create users");
+        assert_eq!(parsed.up, m.up);
+        // The writer comments the DOWN section out; loading uncomments it
+        assert_eq!(parsed.down, m.down);
+
+        let hand_edited = "-- ========== UP ==========\nCREATE TABLE t (x INT);\n\
+                           -- ========== DOWN ==========\n-- nothing to undo\n";
+        let kept = Migration::from_sql("2", "kept", hand_edited);
+        assert_eq!(kept.down, "-- nothing to undo");
+
+        let bare = Migration::from_sql("1", "bare", "CREATE TABLE t (x INT);");
+        assert_eq!(bare.up, "CREATE TABLE t (x INT);");
+        assert_eq!(bare.down, "");
+    }
+
+    #[test]
+    fn test_parse_rust_migration_round_trip() {
+        let writer = MigrationWriter::new("/tmp").with_format(MigrationFormat::Rust);
+        let m = Migration::new(
+            "20260127120000",
+            "Say \"hi\"\tnow",
+            "CREATE TABLE t (s TEXT DEFAULT 'a\\b');",
+            "DROP TABLE t;",
+        );
+        let parsed = parse_rust_migration(&writer.format_rust(&m)).unwrap();
+        assert_eq!(parsed.id, m.id);
+        assert_eq!(parsed.description, m.description);
+        assert_eq!(parsed.up, m.up);
+        assert_eq!(parsed.down, m.down);
+
+        assert!(parse_rust_migration("pub fn migration() -> Migration { build() }").is_none());
+    }
+
+    #[test]
+    fn test_load_dir_orders_by_version() {
+        let dir = std::env::temp_dir().join(format!("sqlmodel_load_dir_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let rust = MigrationWriter::new(&dir).with_format(MigrationFormat::Rust);
+        rust.write(&Migration::new(
+            "20250102000000",
+            "Add email",
+            "ALTER TABLE users ADD email TEXT",
+            "",
+        ))
+        .unwrap();
+        MigrationWriter::new(&dir)
+            .write(&Migration::new(
+                "20250101000000",
+                "Create users",
+                "CREATE TABLE users (id INT)",
+                "",
+            ))
+            .unwrap();
+        std::fs::write(dir.join("mod.rs"), "pub mod m;").unwrap();
+        std::fs::write(dir.join("README.md"), "notes").unwrap();
+
+        let loaded = Migration::load_dir(&dir);
+        std::fs::write(dir.join("oops.sql"), "SELECT 1;").unwrap();
+        let unversioned = Migration::load_dir(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let loaded = loaded.unwrap();
+        let ids: Vec<_> = loaded.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["20250101000000", "20250102000000"]);
+        assert_eq!(loaded[0].description, "Create users");
+        assert_eq!(loaded[1].up, "ALTER TABLE users ADD email TEXT");
+        assert!(unversioned.is_err());
+    }
+
+    #[test]
+    fn test_parse_file_stem() {
+        assert_eq!(
+            parse_file_stem("20250101000000_create_users"),
+            Some(("20250101000000", "create users".to_string()))
+        );
+        assert_eq!(parse_file_stem("create_users"), None);
+    }
 }
@@ -0,0 +1,448 @@
+//! OpenAPI / JSON Schema generation from `Model` metadata.
+//!
+//! Each model yields the four shapes SQLModel applications expose through
+//! FastAPI-style APIs:
+//!
+//! | Component | Contents |
+//! |-----------|----------|
+//! | `Hero` | every stored column, as the table model |
+//! | `HeroCreate` | request body for inserts: no auto-increment keys or computed fields |
+//! | `HeroUpdate` | request body for partial updates: the `Create` fields, all optional |
+//! | `HeroRead` | response body: stored and computed fields, minus `exclude`d ones |
+//!
+//! Field metadata is honored throughout: input shapes name properties by
+//! `validation_alias`/`alias`, `Read` by `serialization_alias`/`alias`;
+//! `title`, `description`, `default_json` and `schema_extra` are copied onto the
+//! property; nullable columns become `anyOf: [T, null]` (OpenAPI 3.1). Fields
+//! marked `api_exclude` or `db_only` are left out of every shape. Properties
+//! are listed in field declaration order.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use sqlmodel_schema::openapi::OpenApiBuilder;
+//!
+//! let doc = OpenApiBuilder::new("Hero API", "1.0.0")
+//!     .model::<Hero>()
+//!     .model::<Team>()
+//!     .build();
+//! println!("{}", serde_json::to_string_pretty(&doc)?);
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value as Json, json};
+use sqlmodel_core::{FieldInfo, Model, SqlType};
+
+/// OpenAPI version emitted by [`OpenApiBuilder::build`].
+pub const OPENAPI_VERSION: &str = "3.1.0";
+
+/// Which shape of a model a schema describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SchemaVariant {
+    /// The table model itself: every stored column.
+    Table,
+    /// Insert request body.
+    Create,
+    /// Partial update request body.
+    Update,
+    /// Response body.
+    Read,
+}
+
+impl SchemaVariant {
+    /// All variants, in the order [`OpenApiBuilder::model`] registers them.
+    pub const ALL: [SchemaVariant; 4] = [
+        SchemaVariant::Table,
+        SchemaVariant::Create,
+        SchemaVariant::Update,
+        SchemaVariant::Read,
+    ];
+
+    /// Suffix appended to the model name for this variant's component.
+    pub const fn suffix(self) -> &'static str {
+        match self {
+            SchemaVariant::Table => "",
+            SchemaVariant::Create => "Create",
+            SchemaVariant::Update => "Update",
+            SchemaVariant::Read => "Read",
+        }
+    }
+
+    const fn is_input(self) -> bool {
+        matches!(self, SchemaVariant::Create | SchemaVariant::Update)
+    }
+
+    /// Whether `field` appears in this variant.
+    fn includes(self, field: &FieldInfo) -> bool {
+        if !field.in_api() {
+            return false;
+        }
+        match self {
+            SchemaVariant::Table => !field.computed,
+            SchemaVariant::Create | SchemaVariant::Update => {
+                !field.computed && (!field.primary_key || !field.auto_increment)
+            }
+            SchemaVariant::Read => !field.exclude,
+        }
+    }
+
+    /// Whether `field` must be present in this variant.
+    fn requires(self, field: &FieldInfo) -> bool {
+        match self {
+            SchemaVariant::Update => false,
+            SchemaVariant::Read => !field.nullable,
+            SchemaVariant::Table | SchemaVariant::Create => {
+                !field.nullable && !field.has_default && field.default.is_none()
+            }
+        }
+    }
+}
+
+/// Name of the component for `M`: the Rust type name without its module path.
+pub fn model_name<M: Model>() -> &'static str {
+    let name = std::any::type_name::<M>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// JSON Schema for the `variant` shape of `M`.
+pub fn model_json_schema<M: Model>(variant: SchemaVariant) -> Json {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for field in M::fields().iter().filter(|f| variant.includes(f)) {
+        let name = property_name(field, variant);
+        let mut schema = field_json_schema(field);
+        if variant == SchemaVariant::Update && !field.nullable {
+            schema = nullable(schema);
+        }
+        if variant == SchemaVariant::Read && field.computed {
+            schema["readOnly"] = Json::Bool(true);
+        }
+        if variant.requires(field) {
+            required.push(Json::from(name));
+        }
+        properties.insert(name.to_string(), schema);
+    }
+
+    let config = M::model_config();
+    let title = config.title.unwrap_or_else(model_name::<M>);
+    let mut schema = json!({
+        "title": format!("{title}{}", variant.suffix()),
+        "type": "object",
+        "properties": properties,
+    });
+    if !required.is_empty() {
+        schema["required"] = Json::Array(required);
+    }
+    if let Some(extra) = config.json_schema_extra {
+        merge_extra(&mut schema, extra);
+    }
+    schema
+}
+
+/// JSON Schema for the values of one field, including its annotations.
+pub fn field_json_schema(field: &FieldInfo) -> Json {
+    let mut schema = sql_type_schema(&field.sql_type);
+    if field.nullable {
+        schema = nullable(schema);
+    }
+    if let Some(title) = field.title {
+        schema["title"] = Json::from(title);
+    }
+    if let Some(description) = field.description {
+        schema["description"] = Json::from(description);
+    }
+    if let Some(default) = field
+        .default_json
+        .and_then(|d| serde_json::from_str(d).ok())
+    {
+        schema["default"] = default;
+    }
+    if let Some(extra) = field.schema_extra {
+        merge_extra(&mut schema, extra);
+    }
+    schema
+}
+
+/// JSON Schema for values of `sql_type`.
+pub fn sql_type_schema(sql_type: &SqlType) -> Json {
+    match sql_type {
+        SqlType::Boolean => json!({"type": "boolean"}),
+        SqlType::TinyInt | SqlType::SmallInt => json!({"type": "integer"}),
+        SqlType::Integer => json!({"type": "integer", "format": "int32"}),
+        SqlType::BigInt => json!({"type": "integer", "format": "int64"}),
+        SqlType::Real => json!({"type": "number", "format": "float"}),
+        SqlType::Double => json!({"type": "number", "format": "double"}),
+        SqlType::Numeric { .. } | SqlType::Decimal { .. } => {
+            json!({"type": "string", "format": "decimal"})
+        }
+        SqlType::Char(len) | SqlType::VarChar(len) => json!({"type": "string", "maxLength": len}),
+        SqlType::Text | SqlType::CiText => json!({"type": "string"}),
+        SqlType::Binary(_) | SqlType::VarBinary(_) | SqlType::Blob => {
+            json!({"type": "string", "format": "binary"})
+        }
+        SqlType::Date => json!({"type": "string", "format": "date"}),
+        SqlType::Time => json!({"type": "string", "format": "time"}),
+        SqlType::DateTime | SqlType::Timestamp | SqlType::TimestampTz => {
+            json!({"type": "string", "format": "date-time"})
+        }
+        SqlType::Uuid => json!({"type": "string", "format": "uuid"}),
+        SqlType::Json | SqlType::JsonB | SqlType::Custom(_) => json!({}),
+        SqlType::Array(inner) => json!({"type": "array", "items": sql_type_schema(inner)}),
+        SqlType::Enum(variants) => json!({"type": "string", "enum": variants}),
+    }
+}
+
+/// Collects component schemas for registered models and renders an OpenAPI document.
+#[derive(Debug, Clone)]
+pub struct OpenApiBuilder {
+    title: String,
+    version: String,
+    schemas: BTreeMap<String, Json>,
+}
+
+impl OpenApiBuilder {
+    /// Create a builder for an API with the given `info.title` and `info.version`.
+    pub fn new(title: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            version: version.into(),
+            schemas: BTreeMap::new(),
+        }
+    }
+
+    /// Register `M`, adding its table, `Create`, `Update` and `Read` schemas.
+    pub fn model<M: Model>(mut self) -> Self {
+        for variant in SchemaVariant::ALL {
+            self = self.variant::<M>(variant);
+        }
+        self
+    }
+
+    /// Register a single shape of `M`.
+    pub fn variant<M: Model>(mut self, variant: SchemaVariant) -> Self {
+        let name = format!("{}{}", model_name::<M>(), variant.suffix());
+        self.schemas.insert(name, model_json_schema::<M>(variant));
+        self
+    }
+
+    /// Component schemas registered so far, keyed by component name.
+    pub fn schemas(&self) -> &BTreeMap<String, Json> {
+        &self.schemas
+    }
+
+    /// The `components` object: `{"schemas": {...}}`.
+    pub fn components(&self) -> Json {
+        json!({ "schemas": self.schemas })
+    }
+
+    /// A complete OpenAPI document with the registered components and no paths.
+    pub fn build(&self) -> Json {
+        json!({
+            "openapi": OPENAPI_VERSION,
+            "info": { "title": self.title, "version": self.version },
+            "paths": {},
+            "components": self.components(),
+        })
+    }
+}
+
+/// Property name for `field` in `variant`.
+fn property_name(field: &FieldInfo, variant: SchemaVariant) -> &'static str {
+    if variant.is_input() {
+        field.validation_alias.or(field.alias).unwrap_or(field.name)
+    } else {
+        field.output_name()
+    }
+}
+
+fn nullable(schema: Json) -> Json {
+    let mut any_of = serde_json::Map::new();
+    any_of.insert(
+        "anyOf".to_string(),
+        Json::Array(vec![schema, json!({"type": "null"})]),
+    );
+    Json::Object(any_of)
+}
+
+/// Merge the keys of a JSON object literal into `schema`; other JSON is ignored.
+fn merge_extra(schema: &mut Json, extra: &str) {
+    if let (Some(target), Ok(Json::Object(extra))) =
+        (schema.as_object_mut(), serde_json::from_str::<Json>(extra))
+    {
+        target.extend(extra);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlmodel_core::{ModelConfig, Row, Value};
+
+    struct Hero;
+
+    impl Model for Hero {
+        const TABLE_NAME: &'static str = "heroes";
+        const PRIMARY_KEY: &'static [&'static str] = &["id"];
+
+        fn fields() -> &'static [FieldInfo] {
+            static FIELDS: &[FieldInfo] = &[
+                FieldInfo::new("id", "id", SqlType::BigInt)
+                    .nullable(true)
+                    .primary_key(true)
+                    .auto_increment(true),
+                FieldInfo::new("name", "name", SqlType::VarChar(100))
+                    .title("Hero name")
+                    .description("Public name"),
+                FieldInfo::new("secret_name", "secret_name", SqlType::Text)
+                    .alias("secretName")
+                    .exclude(true),
+                FieldInfo::new("age", "age", SqlType::Integer)
+                    .nullable(true)
+                    .schema_extra("{\"minimum\": 0}"),
+                FieldInfo::new("rank", "rank", SqlType::Text)
+                    .default("'rookie'")
+                    .default_json("\"rookie\"")
+                    .has_default(true),
+                FieldInfo::new("power_level", "power_level", SqlType::Double)
+                    .serialization_alias("powerLevel")
+                    .computed(true),
+                // Neither appears in any variant
+                FieldInfo::new("password_hash", "password_hash", SqlType::Text).db_only(true),
+                FieldInfo::new("threat_score", "threat_score", SqlType::Integer).api_exclude(true),
+            ];
+            FIELDS
+        }
+
+        fn to_row(&self) -> Vec<(&'static str, Value)> {
+            vec![]
+        }
+
+        fn from_row(_row: &Row) -> sqlmodel_core::Result<Self> {
+            Ok(Hero)
+        }
+
+        fn primary_key_value(&self) -> Vec<Value> {
+            vec![]
+        }
+
+        fn is_new(&self) -> bool {
+            true
+        }
+
+        fn model_config() -> ModelConfig {
+            ModelConfig {
+                json_schema_extra: Some("{\"examples\": [{\"name\": \"Deadpond\"}]}"),
+                ..ModelConfig::table()
+            }
+        }
+    }
+
+    fn property_names(schema: &Json) -> Vec<&str> {
+        schema["properties"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect()
+    }
+
+    #[test]
+    fn test_model_name_strips_module_path() {
+        assert_eq!(model_name::<Hero>(), "Hero");
+    }
+
+    #[test]
+    fn test_table_schema() {
+        let schema = model_json_schema::<Hero>(SchemaVariant::Table);
+        assert_eq!(schema["title"], "Hero");
+        assert_eq!(
+            property_names(&schema),
+            vec!["id", "name", "secretName", "age", "rank"]
+        );
+        assert_eq!(schema["required"], json!(["name", "secretName"]));
+        assert_eq!(
+            schema["properties"]["name"],
+            json!({
+                "type": "string",
+                "maxLength": 100,
+                "title": "Hero name",
+                "description": "Public name"
+            })
+        );
+        assert_eq!(
+            schema["properties"]["age"],
+            json!({
+                "anyOf": [{"type": "integer", "format": "int32"}, {"type": "null"}],
+                "minimum": 0
+            })
+        );
+        assert_eq!(schema["properties"]["rank"]["default"], "rookie");
+        assert_eq!(schema["examples"], json!([{"name": "Deadpond"}]));
+    }
+
+    #[test]
+    fn test_create_and_update_schemas() {
+        let create = model_json_schema::<Hero>(SchemaVariant::Create);
+        assert_eq!(create["title"], "HeroCreate");
+        assert_eq!(
+            property_names(&create),
+            vec!["name", "secretName", "age", "rank"]
+        );
+        assert_eq!(create["required"], json!(["name", "secretName"]));
+
+        let update = model_json_schema::<Hero>(SchemaVariant::Update);
+        assert_eq!(
+            property_names(&update),
+            vec!["name", "secretName", "age", "rank"]
+        );
+        assert!(update.get("required").is_none());
+        assert_eq!(
+            update["properties"]["name"]["anyOf"][1],
+            json!({"type": "null"})
+        );
+    }
+
+    #[test]
+    fn test_read_schema() {
+        let read = model_json_schema::<Hero>(SchemaVariant::Read);
+        assert_eq!(
+            property_names(&read),
+            vec!["id", "name", "age", "rank", "powerLevel"]
+        );
+        assert_eq!(read["properties"]["powerLevel"]["readOnly"], true);
+        assert_eq!(read["required"], json!(["name", "rank", "powerLevel"]));
+    }
+
+    #[test]
+    fn test_openapi_document() {
+        let doc = OpenApiBuilder::new("Hero API", "1.0.0")
+            .model::<Hero>()
+            .build();
+        assert_eq!(doc["openapi"], OPENAPI_VERSION);
+        assert_eq!(doc["info"]["title"], "Hero API");
+        let schemas: Vec<&str> = doc["components"]["schemas"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(
+            schemas,
+            vec!["Hero", "HeroCreate", "HeroRead", "HeroUpdate"]
+        );
+    }
+
+    #[test]
+    fn test_sql_type_schema() {
+        assert_eq!(
+            sql_type_schema(&SqlType::Array(Box::new(SqlType::Uuid))),
+            json!({"type": "array", "items": {"type": "string", "format": "uuid"}})
+        );
+        assert_eq!(
+            sql_type_schema(&SqlType::Enum(vec!["a", "b"])),
+            json!({"type": "string", "enum": ["a", "b"]})
+        );
+    }
+}
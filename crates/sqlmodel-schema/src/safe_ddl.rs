@@ -0,0 +1,425 @@
+//! Lints for migrations that lock busy tables, and helpers for avoiding them.
+//!
+//! [`SafeDdl`] inspects the operations of a generated migration and flags
+//! patterns that rewrite or lock an existing table for the whole duration of
+//! the statement:
+//!
+//! - adding a NOT NULL column without a default (fails on a non-empty table),
+//! - adding a column with a default on PostgreSQL before 11 (table rewrite),
+//! - changing a column's type (table rewrite),
+//! - tightening a column to NOT NULL (full scan under an exclusive lock),
+//! - creating an index without `CONCURRENTLY` on PostgreSQL.
+//!
+//! Tables created earlier in the same migration are empty, so operations on
+//! them are never flagged.
+//!
+//! The usual fix is expand/backfill/contract: add the column as nullable,
+//! fill it in small batches with [`Backfill`], then add the constraint.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use sqlmodel_schema::diff::schema_diff;
+//! use sqlmodel_schema::{Dialect, SafeDdl};
+//!
+//! let diff = schema_diff(&current, &expected);
+//! let findings = SafeDdl::new(Dialect::Postgres)
+//!     .postgres_version(10)
+//!     .check(&diff.operations)?;
+//! for finding in &findings {
+//!     eprintln!("warning: {finding}");
+//! }
+//! ```
+
+use std::collections::HashSet;
+use std::fmt;
+
+use asupersync::{CancelReason, Cx, Outcome};
+use sqlmodel_core::{Connection, Error};
+
+use crate::diff::SchemaOperation;
+use crate::introspect::Dialect;
+
+/// How serious a [`DdlFinding`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DdlSeverity {
+    /// Locks or scans the table; review before running on a busy database.
+    Warning,
+    /// Rewrites the table or fails on existing rows.
+    Error,
+}
+
+/// A lock-heavy pattern found by [`SafeDdl`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DdlFinding {
+    /// Severity of the finding.
+    pub severity: DdlSeverity,
+    /// Index of the offending operation in the analyzed slice.
+    pub operation: usize,
+    /// Table the operation touches.
+    pub table: String,
+    /// What the operation does to the table.
+    pub message: String,
+    /// How to get the same result without the lock.
+    pub suggestion: String,
+}
+
+impl fmt::Display for DdlFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "operation {} on {}: {} ({})",
+            self.operation + 1,
+            self.table,
+            self.message,
+            self.suggestion
+        )
+    }
+}
+
+/// Analyzer for lock-heavy migration operations.
+#[derive(Debug, Clone, Copy)]
+pub struct SafeDdl {
+    dialect: Dialect,
+    postgres_major: Option<u32>,
+}
+
+impl SafeDdl {
+    /// Create an analyzer for `dialect`, assuming a current server version.
+    pub fn new(dialect: Dialect) -> Self {
+        Self {
+            dialect,
+            postgres_major: None,
+        }
+    }
+
+    /// Target a PostgreSQL server of the given major version.
+    ///
+    /// Before PostgreSQL 11, adding a column with a default rewrites the table.
+    #[must_use]
+    pub fn postgres_version(mut self, major: u32) -> Self {
+        self.postgres_major = Some(major);
+        self
+    }
+
+    /// Inspect `ops` and return every finding, in operation order.
+    pub fn analyze(&self, ops: &[SchemaOperation]) -> Vec<DdlFinding> {
+        let mut created = HashSet::new();
+        let mut findings = Vec::new();
+
+        for (i, op) in ops.iter().enumerate() {
+            let mut flag = |severity, table: &str, message: String, suggestion: &str| {
+                findings.push(DdlFinding {
+                    severity,
+                    operation: i,
+                    table: table.to_string(),
+                    message,
+                    suggestion: suggestion.to_string(),
+                });
+            };
+
+            match op {
+                SchemaOperation::CreateTable(table) => {
+                    created.insert(table.name.as_str());
+                }
+                SchemaOperation::AddColumn { table, column }
+                    if !created.contains(table.as_str()) =>
+                {
+                    if !column.nullable && column.default.is_none() && !column.auto_increment {
+                        flag(
+                            DdlSeverity::Error,
+                            table,
+                            format!(
+                                "adding NOT NULL column {} without a default fails on existing rows",
+                                column.name
+                            ),
+                            "add it as nullable, backfill in batches, then set NOT NULL",
+                        );
+                    } else if column.default.is_some()
+                        && self.dialect == Dialect::Postgres
+                        && self.postgres_major.is_some_and(|v| v < 11)
+                    {
+                        flag(
+                            DdlSeverity::Warning,
+                            table,
+                            format!(
+                                "adding column {} with a default rewrites the table before PostgreSQL 11",
+                                column.name
+                            ),
+                            "add it without a default, set the default, then backfill in batches",
+                        );
+                    }
+                }
+                SchemaOperation::AlterColumnType {
+                    table,
+                    column,
+                    from_type,
+                    to_type,
+                    ..
+                } if !created.contains(table.as_str()) => flag(
+                    DdlSeverity::Error,
+                    table,
+                    format!("changing {column} from {from_type} to {to_type} rewrites the table"),
+                    "add a new column, backfill it in batches, then swap the columns",
+                ),
+                SchemaOperation::AlterColumnNullable {
+                    table,
+                    column,
+                    to_nullable: false,
+                    ..
+                } if !created.contains(table.as_str()) => flag(
+                    DdlSeverity::Warning,
+                    table,
+                    format!(
+                        "setting {} NOT NULL scans the table under an exclusive lock",
+                        column.name
+                    ),
+                    "on PostgreSQL, add a CHECK (... IS NOT NULL) NOT VALID constraint and validate it first",
+                ),
+                SchemaOperation::CreateIndex { table, index }
+                    if self.dialect == Dialect::Postgres && !created.contains(table.as_str()) =>
+                {
+                    flag(
+                        DdlSeverity::Warning,
+                        table,
+                        format!("creating index {} blocks writes to the table", index.name),
+                        "use CreateIndex::from_info(..).concurrently() in a migration of its own",
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        findings
+    }
+
+    /// Like [`analyze`](Self::analyze), but fail if any finding is an error.
+    ///
+    /// Warnings are returned for the caller to report.
+    #[allow(clippy::result_large_err)]
+    pub fn check(&self, ops: &[SchemaOperation]) -> Result<Vec<DdlFinding>, Error> {
+        let findings = self.analyze(ops);
+        let errors: Vec<String> = findings
+            .iter()
+            .filter(|f| f.severity == DdlSeverity::Error)
+            .map(ToString::to_string)
+            .collect();
+        if errors.is_empty() {
+            Ok(findings)
+        } else {
+            Err(Error::Custom(format!(
+                "unsafe migration: {}",
+                errors.join("; ")
+            )))
+        }
+    }
+}
+
+/// Batched UPDATE for filling a column without holding a long lock.
+///
+/// Each batch updates at most `batch_size` rows matching the `pending`
+/// predicate and commits on its own, so row locks are short-lived. `set` must
+/// make the rows stop matching `pending`, or the backfill never finishes.
+///
+/// ```ignore
+/// let updated = Backfill::new("orders", "status = 'open'", "status IS NULL")
+///     .batch_size(500)
+///     .run(&cx, &conn)
+///     .await;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backfill {
+    table: String,
+    set: String,
+    pending: String,
+    key: String,
+    batch_size: u64,
+}
+
+impl Backfill {
+    /// Backfill `table` with the `set` assignments wherever `pending` holds.
+    pub fn new(
+        table: impl Into<String>,
+        set: impl Into<String>,
+        pending: impl Into<String>,
+    ) -> Self {
+        Self {
+            table: table.into(),
+            set: set.into(),
+            pending: pending.into(),
+            key: "id".to_string(),
+            batch_size: 1000,
+        }
+    }
+
+    /// Column used to select each batch (default: `id`).
+    #[must_use]
+    pub fn key(mut self, column: impl Into<String>) -> Self {
+        self.key = column.into();
+        self
+    }
+
+    /// Maximum rows per batch (default: 1000).
+    #[must_use]
+    pub fn batch_size(mut self, rows: u64) -> Self {
+        self.batch_size = rows.max(1);
+        self
+    }
+
+    /// The UPDATE statement run for each batch.
+    pub fn batch_sql(&self, dialect: Dialect) -> String {
+        match dialect {
+            Dialect::Mysql => format!(
+                "UPDATE {} SET {} WHERE {} LIMIT {}",
+                self.table, self.set, self.pending, self.batch_size
+            ),
+            Dialect::Postgres | Dialect::Sqlite => format!(
+                "UPDATE {table} SET {} WHERE {key} IN (SELECT {key} FROM {table} WHERE {} LIMIT {})",
+                self.set,
+                self.pending,
+                self.batch_size,
+                table = self.table,
+                key = self.key,
+            ),
+        }
+    }
+
+    /// Run batches until one updates fewer than `batch_size` rows.
+    ///
+    /// Returns the total number of rows updated. Cancellation is checked
+    /// between batches, so completed batches stay committed.
+    pub async fn run<C: Connection>(&self, cx: &Cx, conn: &C) -> Outcome<u64, Error> {
+        let sql = self.batch_sql(conn.dialect().into());
+        let mut total = 0;
+        loop {
+            if cx.is_cancel_requested() {
+                return Outcome::Cancelled(CancelReason::user("backfill cancelled"));
+            }
+            let updated = match conn.execute(cx, &sql, &[]).await {
+                Outcome::Ok(n) => n,
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            };
+            total += updated;
+            if updated < self.batch_size {
+                return Outcome::Ok(total);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::introspect::{ColumnInfo, IndexInfo, ParsedSqlType, TableInfo};
+
+    fn column(name: &str, nullable: bool, default: Option<&str>) -> ColumnInfo {
+        ColumnInfo {
+            name: name.to_string(),
+            sql_type: "INTEGER".to_string(),
+            parsed_type: ParsedSqlType::parse("INTEGER"),
+            nullable,
+            default: default.map(str::to_string),
+            primary_key: false,
+            auto_increment: false,
+            comment: None,
+            collation: None,
+            charset: None,
+        }
+    }
+
+    fn add_column(table: &str, column: ColumnInfo) -> SchemaOperation {
+        SchemaOperation::AddColumn {
+            table: table.to_string(),
+            column,
+        }
+    }
+
+    fn create_index(table: &str) -> SchemaOperation {
+        SchemaOperation::CreateIndex {
+            table: table.to_string(),
+            index: IndexInfo {
+                name: format!("idx_{table}_total"),
+                columns: vec!["total".to_string()],
+                unique: false,
+                index_type: None,
+                primary: false,
+            },
+        }
+    }
+
+    #[test]
+    fn test_flags_lock_heavy_operations() {
+        let ops = vec![
+            add_column("orders", column("total", false, None)),
+            add_column("orders", column("status", false, Some("0"))),
+            SchemaOperation::AlterColumnType {
+                table: "orders".to_string(),
+                column: "total".to_string(),
+                from_type: "INTEGER".to_string(),
+                to_type: "BIGINT".to_string(),
+                table_info: None,
+            },
+            create_index("orders"),
+        ];
+
+        let findings = SafeDdl::new(Dialect::Postgres)
+            .postgres_version(10)
+            .analyze(&ops);
+        let summary: Vec<(DdlSeverity, usize)> =
+            findings.iter().map(|f| (f.severity, f.operation)).collect();
+        assert_eq!(
+            summary,
+            [
+                (DdlSeverity::Error, 0),
+                (DdlSeverity::Warning, 1),
+                (DdlSeverity::Error, 2),
+                (DdlSeverity::Warning, 3),
+            ]
+        );
+
+        // Defaults are cheap from PostgreSQL 11 on, and SQLite has no CONCURRENTLY.
+        let findings = SafeDdl::new(Dialect::Sqlite).analyze(&ops);
+        assert_eq!(
+            findings.iter().map(|f| f.operation).collect::<Vec<_>>(),
+            [0, 2]
+        );
+        assert!(SafeDdl::new(Dialect::Postgres).check(&ops).is_err());
+    }
+
+    #[test]
+    fn test_skips_tables_created_in_same_migration() {
+        let table = TableInfo {
+            name: "invoices".to_string(),
+            columns: vec![column("id", false, None)],
+            primary_key: vec!["id".to_string()],
+            foreign_keys: Vec::new(),
+            unique_constraints: Vec::new(),
+            check_constraints: Vec::new(),
+            indexes: Vec::new(),
+            comment: None,
+        };
+        let ops = vec![
+            SchemaOperation::CreateTable(table),
+            add_column("invoices", column("total", false, None)),
+            create_index("invoices"),
+        ];
+        let findings = SafeDdl::new(Dialect::Postgres).check(&ops).unwrap();
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_backfill_batch_sql() {
+        let backfill = Backfill::new("orders", "status = 'open'", "status IS NULL").batch_size(500);
+        assert_eq!(
+            backfill.batch_sql(Dialect::Postgres),
+            "UPDATE orders SET status = 'open' WHERE id IN \
+             (SELECT id FROM orders WHERE status IS NULL LIMIT 500)"
+        );
+        assert_eq!(
+            backfill.key("order_id").batch_sql(Dialect::Mysql),
+            "UPDATE orders SET status = 'open' WHERE status IS NULL LIMIT 500"
+        );
+    }
+}
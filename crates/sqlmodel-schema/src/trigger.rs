@@ -0,0 +1,442 @@
+//! CREATE TRIGGER statement builder.
+//!
+//! Triggers are written once and rendered for each dialect:
+//!
+//! - **PostgreSQL**: the body becomes a PL/pgSQL trigger function
+//!   (`<name>_fn`) and the trigger executes it.
+//! - **SQLite / MySQL**: the body is inlined in the trigger. Both allow a
+//!   single event per trigger, so a trigger on several events is emitted as one
+//!   trigger per event, named `<name>_<event>`.
+//!
+//! Statements reference the affected row through `NEW` / `OLD` in every
+//! dialect. Use [`CreateTrigger::statement_for`] where the syntax differs.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use sqlmodel_schema::{CreateTrigger, Dialect, TriggerEvent};
+//!
+//! let trigger = CreateTrigger::before("heroes_touch", "heroes", TriggerEvent::Update)
+//!     .statement_for(Dialect::Postgres, "NEW.updated_at := now()")
+//!     .statement_for(Dialect::Mysql, "SET NEW.updated_at = NOW()")
+//!     .or_replace();
+//! let statements = trigger.build(Dialect::Postgres);
+//! ```
+
+use sqlmodel_core::dialect::{IntoSqlDialect, SqlDialect};
+use std::fmt::Write as _;
+
+use crate::introspect::Dialect;
+
+/// When a trigger fires relative to the triggering statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerTiming {
+    /// Fire before the row is written (can modify `NEW`).
+    Before,
+    /// Fire after the row is written.
+    After,
+}
+
+impl TriggerTiming {
+    /// SQL keyword for this timing.
+    pub const fn as_sql(self) -> &'static str {
+        match self {
+            TriggerTiming::Before => "BEFORE",
+            TriggerTiming::After => "AFTER",
+        }
+    }
+}
+
+/// Row event a trigger fires on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerEvent {
+    /// INSERT
+    Insert,
+    /// UPDATE
+    Update,
+    /// DELETE
+    Delete,
+}
+
+impl TriggerEvent {
+    /// SQL keyword for this event.
+    pub const fn as_sql(self) -> &'static str {
+        match self {
+            TriggerEvent::Insert => "INSERT",
+            TriggerEvent::Update => "UPDATE",
+            TriggerEvent::Delete => "DELETE",
+        }
+    }
+
+    /// Suffix used for per-event trigger names.
+    const fn suffix(self) -> &'static str {
+        match self {
+            TriggerEvent::Insert => "insert",
+            TriggerEvent::Update => "update",
+            TriggerEvent::Delete => "delete",
+        }
+    }
+}
+
+/// Builder for row-level CREATE TRIGGER statements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateTrigger {
+    name: String,
+    table: String,
+    timing: TriggerTiming,
+    events: Vec<TriggerEvent>,
+    when: Option<String>,
+    /// Body statements, optionally restricted to one dialect.
+    body: Vec<(Option<Dialect>, String)>,
+    replace: bool,
+}
+
+impl CreateTrigger {
+    /// Create a trigger firing with `timing` on `event` for each row of `table`.
+    pub fn new(
+        name: impl Into<String>,
+        table: impl Into<String>,
+        timing: TriggerTiming,
+        event: TriggerEvent,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            table: table.into(),
+            timing,
+            events: vec![event],
+            when: None,
+            body: Vec::new(),
+            replace: false,
+        }
+    }
+
+    /// Create a BEFORE trigger.
+    pub fn before(name: impl Into<String>, table: impl Into<String>, event: TriggerEvent) -> Self {
+        Self::new(name, table, TriggerTiming::Before, event)
+    }
+
+    /// Create an AFTER trigger.
+    pub fn after(name: impl Into<String>, table: impl Into<String>, event: TriggerEvent) -> Self {
+        Self::new(name, table, TriggerTiming::After, event)
+    }
+
+    /// Also fire on `event`.
+    #[must_use]
+    pub fn or(mut self, event: TriggerEvent) -> Self {
+        if !self.events.contains(&event) {
+            self.events.push(event);
+        }
+        self
+    }
+
+    /// Only fire when `condition` holds (may reference `NEW` / `OLD`).
+    ///
+    /// MySQL has no WHEN clause; the body is wrapped in `IF ... THEN` instead.
+    #[must_use]
+    pub fn when(mut self, condition: impl Into<String>) -> Self {
+        self.when = Some(condition.into());
+        self
+    }
+
+    /// Append a body statement used for every dialect.
+    #[must_use]
+    pub fn statement(mut self, sql: impl Into<String>) -> Self {
+        self.body.push((None, sql.into()));
+        self
+    }
+
+    /// Append a body statement used only when rendering for `dialect`.
+    #[must_use]
+    pub fn statement_for(mut self, dialect: Dialect, sql: impl Into<String>) -> Self {
+        self.body.push((Some(dialect), sql.into()));
+        self
+    }
+
+    /// Drop any existing trigger of the same name before creating it.
+    #[must_use]
+    pub fn or_replace(mut self) -> Self {
+        self.replace = true;
+        self
+    }
+
+    /// Trigger name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Table the trigger is attached to.
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    /// Timing of the trigger.
+    pub fn timing(&self) -> TriggerTiming {
+        self.timing
+    }
+
+    /// Events the trigger fires on.
+    pub fn events(&self) -> &[TriggerEvent] {
+        &self.events
+    }
+
+    /// Name of the PostgreSQL trigger function.
+    pub fn function_name(&self) -> String {
+        format!("{}_fn", self.name)
+    }
+
+    /// Build the statements creating this trigger in `dialect`.
+    pub fn build(&self, dialect: impl IntoSqlDialect) -> Vec<String> {
+        let sql_dialect = dialect.into_sql_dialect();
+        let quote = |name: &str| sql_dialect.quote_identifier(name);
+        let dialect = Dialect::from(sql_dialect.base_dialect());
+        let mut statements = Vec::new();
+        if self.replace {
+            statements.extend(self.drop_triggers(sql_dialect));
+        }
+
+        match dialect {
+            Dialect::Postgres => {
+                statements.push(self.postgres_function(sql_dialect));
+                let events: Vec<&str> = self.events.iter().map(|e| e.as_sql()).collect();
+                let mut sql = format!(
+                    "CREATE TRIGGER {} {} {} ON {} FOR EACH ROW",
+                    quote(&self.name),
+                    self.timing.as_sql(),
+                    events.join(" OR "),
+                    quote(&self.table)
+                );
+                if let Some(condition) = &self.when {
+                    sql.push_str(&format!(" WHEN ({condition})"));
+                }
+                sql.push_str(&format!(
+                    " EXECUTE FUNCTION {}()",
+                    quote(&self.function_name())
+                ));
+                statements.push(sql);
+            }
+            Dialect::Sqlite | Dialect::Mysql => {
+                for (event, name) in self.per_event_names() {
+                    let mut sql = format!(
+                        "CREATE TRIGGER {} {} {} ON {} FOR EACH ROW",
+                        quote(&name),
+                        self.timing.as_sql(),
+                        event.as_sql(),
+                        quote(&self.table)
+                    );
+                    let mut body = self.body_statements(dialect);
+                    match (&self.when, dialect) {
+                        (Some(condition), Dialect::Mysql) => {
+                            body = format!("  IF {condition} THEN\n{}  END IF;\n", indent(&body));
+                        }
+                        (Some(condition), _) => sql.push_str(&format!(" WHEN {condition}")),
+                        (None, _) => {}
+                    }
+                    sql.push_str(&format!(" BEGIN\n{body}END"));
+                    statements.push(sql);
+                }
+            }
+        }
+
+        statements
+    }
+
+    /// Build the statements dropping this trigger (and its function) in `dialect`.
+    pub fn build_drop(&self, dialect: impl IntoSqlDialect) -> Vec<String> {
+        let dialect = dialect.into_sql_dialect();
+        let mut statements = self.drop_triggers(dialect);
+        if Dialect::from(dialect.base_dialect()) == Dialect::Postgres {
+            statements.push(format!(
+                "DROP FUNCTION IF EXISTS {}()",
+                dialect.quote_identifier(&self.function_name())
+            ));
+        }
+        statements
+    }
+
+    fn drop_triggers(&self, dialect: &dyn SqlDialect) -> Vec<String> {
+        match Dialect::from(dialect.base_dialect()) {
+            Dialect::Postgres => vec![format!(
+                "DROP TRIGGER IF EXISTS {} ON {}",
+                dialect.quote_identifier(&self.name),
+                dialect.quote_identifier(&self.table)
+            )],
+            Dialect::Sqlite | Dialect::Mysql => self
+                .per_event_names()
+                .into_iter()
+                .map(|(_, name)| {
+                    format!("DROP TRIGGER IF EXISTS {}", dialect.quote_identifier(&name))
+                })
+                .collect(),
+        }
+    }
+
+    /// Trigger names for dialects allowing one event per trigger.
+    fn per_event_names(&self) -> Vec<(TriggerEvent, String)> {
+        if let [event] = self.events.as_slice() {
+            return vec![(*event, self.name.clone())];
+        }
+        self.events
+            .iter()
+            .map(|event| (*event, format!("{}_{}", self.name, event.suffix())))
+            .collect()
+    }
+
+    /// Body statements for `dialect`, one per line and `;`-terminated.
+    fn body_statements(&self, dialect: Dialect) -> String {
+        let mut out = String::new();
+        for (_, sql) in self
+            .body
+            .iter()
+            .filter(|(only, _)| only.is_none_or(|d| d == dialect))
+        {
+            let _ = writeln!(out, "  {};", sql.trim().trim_end_matches(';'));
+        }
+        out
+    }
+
+    /// PL/pgSQL function running the body and returning the row to keep.
+    fn postgres_function(&self, dialect: &dyn SqlDialect) -> String {
+        let returns = match self.timing {
+            // Return values of AFTER row triggers are ignored
+            TriggerTiming::After => "  RETURN NULL;\n".to_string(),
+            TriggerTiming::Before if self.events == [TriggerEvent::Delete] => {
+                "  RETURN OLD;\n".to_string()
+            }
+            TriggerTiming::Before if self.events.contains(&TriggerEvent::Delete) => {
+                "  IF TG_OP = 'DELETE' THEN\n    RETURN OLD;\n  END IF;\n  RETURN NEW;\n"
+                    .to_string()
+            }
+            TriggerTiming::Before => "  RETURN NEW;\n".to_string(),
+        };
+
+        format!(
+            "CREATE {}FUNCTION {}() RETURNS trigger AS $$\nBEGIN\n{}{}END;\n$$ LANGUAGE plpgsql",
+            if self.replace { "OR REPLACE " } else { "" },
+            dialect.quote_identifier(&self.function_name()),
+            self.body_statements(Dialect::Postgres),
+            returns
+        )
+    }
+}
+
+fn indent(body: &str) -> String {
+    let mut out = String::new();
+    for line in body.lines() {
+        let _ = writeln!(out, "  {line}");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ddl::PostgresDdlGenerator;
+    use crate::diff::SchemaOperation;
+    use crate::migrate::Migration;
+
+    fn touch_trigger() -> CreateTrigger {
+        CreateTrigger::before("heroes_touch", "heroes", TriggerEvent::Update)
+            .statement_for(Dialect::Postgres, "NEW.updated_at := now()")
+            .statement_for(Dialect::Mysql, "SET NEW.updated_at = NOW();")
+            .statement_for(
+                Dialect::Sqlite,
+                "UPDATE heroes SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id",
+            )
+    }
+
+    #[test]
+    fn test_postgres_trigger_uses_function() {
+        let statements = touch_trigger().build(Dialect::Postgres);
+        assert_eq!(
+            statements,
+            vec![
+                "CREATE FUNCTION \"heroes_touch_fn\"() RETURNS trigger AS $$\nBEGIN\n  NEW.updated_at := now();\n  RETURN NEW;\nEND;\n$$ LANGUAGE plpgsql",
+                "CREATE TRIGGER \"heroes_touch\" BEFORE UPDATE ON \"heroes\" FOR EACH ROW EXECUTE FUNCTION \"heroes_touch_fn\"()",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_postgres_replace_and_drop() {
+        let trigger = CreateTrigger::after("audit", "heroes", TriggerEvent::Insert)
+            .or(TriggerEvent::Delete)
+            .when("pg_trigger_depth() = 0")
+            .statement("INSERT INTO audit_log (tbl) VALUES (TG_TABLE_NAME)")
+            .or_replace();
+        let statements = trigger.build(Dialect::Postgres);
+        assert_eq!(statements.len(), 3);
+        assert_eq!(
+            statements[0],
+            "DROP TRIGGER IF EXISTS \"audit\" ON \"heroes\""
+        );
+        assert!(statements[1].starts_with("CREATE OR REPLACE FUNCTION \"audit_fn\"()"));
+        assert!(statements[1].contains("  RETURN NULL;\n"));
+        assert_eq!(
+            statements[2],
+            "CREATE TRIGGER \"audit\" AFTER INSERT OR DELETE ON \"heroes\" FOR EACH ROW WHEN (pg_trigger_depth() = 0) EXECUTE FUNCTION \"audit_fn\"()"
+        );
+
+        assert_eq!(
+            trigger.build_drop(Dialect::Postgres),
+            vec![
+                "DROP TRIGGER IF EXISTS \"audit\" ON \"heroes\"",
+                "DROP FUNCTION IF EXISTS \"audit_fn\"()",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sqlite_trigger_per_event() {
+        let trigger = CreateTrigger::after("heroes_log", "heroes", TriggerEvent::Insert)
+            .or(TriggerEvent::Update)
+            .when("NEW.name IS NOT NULL")
+            .statement("INSERT INTO log (hero_id) VALUES (NEW.id)");
+        let statements = trigger.build(Dialect::Sqlite);
+        assert_eq!(
+            statements,
+            vec![
+                "CREATE TRIGGER \"heroes_log_insert\" AFTER INSERT ON \"heroes\" FOR EACH ROW WHEN NEW.name IS NOT NULL BEGIN\n  INSERT INTO log (hero_id) VALUES (NEW.id);\nEND",
+                "CREATE TRIGGER \"heroes_log_update\" AFTER UPDATE ON \"heroes\" FOR EACH ROW WHEN NEW.name IS NOT NULL BEGIN\n  INSERT INTO log (hero_id) VALUES (NEW.id);\nEND",
+            ]
+        );
+        assert_eq!(
+            trigger.build_drop(Dialect::Sqlite),
+            vec![
+                "DROP TRIGGER IF EXISTS \"heroes_log_insert\"",
+                "DROP TRIGGER IF EXISTS \"heroes_log_update\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mysql_trigger_wraps_condition() {
+        let statements = touch_trigger()
+            .when("NEW.name <> OLD.name")
+            .or_replace()
+            .build(Dialect::Mysql);
+        assert_eq!(
+            statements,
+            vec![
+                "DROP TRIGGER IF EXISTS `heroes_touch`",
+                "CREATE TRIGGER `heroes_touch` BEFORE UPDATE ON `heroes` FOR EACH ROW BEGIN\n  IF NEW.name <> OLD.name THEN\n    SET NEW.updated_at = NOW();\n  END IF;\nEND",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trigger_operations_in_migrations() {
+        let op = SchemaOperation::CreateTrigger(touch_trigger());
+        assert_eq!(op.table(), Some("heroes"));
+
+        let migration = Migration::from_operations(&[op], &PostgresDdlGenerator, "touch heroes");
+        assert!(
+            migration
+                .up
+                .contains("CREATE TRIGGER \"heroes_touch\" BEFORE UPDATE ON \"heroes\"")
+        );
+        assert_eq!(
+            migration.down,
+            "DROP TRIGGER IF EXISTS \"heroes_touch\" ON \"heroes\";\n\nDROP FUNCTION IF EXISTS \"heroes_touch_fn\"();"
+        );
+    }
+}
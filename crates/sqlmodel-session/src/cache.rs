@@ -0,0 +1,670 @@
+//! Read-through entity cache for [`Session::get`](crate::Session::get).
+//!
+//! A session with an [`EntityCache`] looks up models that opt in with
+//! `#[sqlmodel(cache)]` (or `#[sqlmodel(cache(ttl = "60s"))]`) in the cache
+//! after the identity map and before the database. Rows loaded from the
+//! database are written back with the model's TTL.
+//!
+//! Entries are keyed by table and primary key ([`CacheKey`]) and hold the
+//! model serialized as JSON, so one cache can be shared by every session and,
+//! with a networked backend, every process. Flush invalidates the keys of the
+//! objects it writes, and commit invalidates them again so a reader that
+//! refilled the cache from the pre-commit row does not keep serving it. Rows
+//! changed outside a session (raw SQL, database-side cascades) are only
+//! refreshed when their TTL runs out.
+//!
+//! Backends:
+//!
+//! - [`MokaCache`] - in-process, bounded, per-entry TTL (`moka` feature).
+//! - [`RedisCache`] - shared across processes; a small RESP client on
+//!   asupersync sockets with connect and command timeouts (`redis` feature).
+//!
+//! Cache failures never fail a query: a backend that cannot be reached is
+//! logged and treated as a miss.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use asupersync::Cx;
+use sqlmodel_core::Value;
+
+/// Cache key for one row: table name plus primary key values.
+///
+/// Integer primary keys are normalized, so `get::<Hero>(1_i32)` and a row
+/// whose key was read back as `BigInt(1)` share an entry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey(String);
+
+impl CacheKey {
+    /// The key for the row of `table` with primary key `pk`.
+    pub fn new(table: &str, pk: &[Value]) -> Self {
+        let mut key = String::with_capacity(table.len() + 16);
+        key.push_str(table);
+        for value in pk {
+            key.push(':');
+            match value {
+                Value::TinyInt(_) | Value::SmallInt(_) | Value::Int(_) | Value::BigInt(_) => {
+                    key.push_str(&value.as_i64().unwrap_or_default().to_string());
+                }
+                Value::Text(s) => {
+                    key.push_str(&serde_json::to_string(s).unwrap_or_default());
+                }
+                other => key.push_str(&serde_json::to_string(other).unwrap_or_default()),
+            }
+        }
+        Self(key)
+    }
+
+    /// The key as a string, e.g. `heroes:1`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for CacheKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Future returned by [`EntityCache`] methods.
+pub type CacheFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A store for serialized entities, shared between sessions.
+///
+/// Methods are async so networked backends can wait on I/O without blocking
+/// the executor; in-process backends return ready futures.
+pub trait EntityCache: Send + Sync {
+    /// The cached bytes for `key`, if present and unexpired.
+    fn get<'a>(&'a self, cx: &'a Cx, key: &'a CacheKey) -> CacheFuture<'a, Option<Vec<u8>>>;
+
+    /// Store `value` under `key`, expiring after `ttl` if given.
+    fn put<'a>(
+        &'a self,
+        cx: &'a Cx,
+        key: &'a CacheKey,
+        value: Vec<u8>,
+        ttl: Option<Duration>,
+    ) -> CacheFuture<'a, ()>;
+
+    /// Drop `key` if present.
+    fn invalidate<'a>(&'a self, cx: &'a Cx, key: &'a CacheKey) -> CacheFuture<'a, ()>;
+}
+
+#[cfg(feature = "moka")]
+pub use self::moka_cache::MokaCache;
+
+#[cfg(feature = "moka")]
+mod moka_cache {
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    use asupersync::Cx;
+    use moka::Expiry;
+    use moka::sync::Cache;
+
+    use super::{CacheFuture, CacheKey, EntityCache};
+
+    type Entry = (Arc<[u8]>, Option<Duration>);
+
+    /// Expires each entry after the TTL it was stored with.
+    struct PerEntryTtl;
+
+    impl Expiry<String, Entry> for PerEntryTtl {
+        fn expire_after_create(
+            &self,
+            _key: &String,
+            value: &Entry,
+            _created_at: Instant,
+        ) -> Option<Duration> {
+            value.1
+        }
+    }
+
+    /// In-process LRU entity cache backed by [`moka`].
+    #[derive(Clone)]
+    pub struct MokaCache {
+        inner: Cache<String, Entry>,
+    }
+
+    impl std::fmt::Debug for MokaCache {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("MokaCache")
+                .field("entries", &self.inner.entry_count())
+                .finish()
+        }
+    }
+
+    impl MokaCache {
+        /// A cache holding at most `max_entries` rows.
+        pub fn new(max_entries: u64) -> Self {
+            Self {
+                inner: Cache::builder()
+                    .max_capacity(max_entries)
+                    .expire_after(PerEntryTtl)
+                    .build(),
+            }
+        }
+
+        /// Number of cached rows (approximate until pending maintenance runs).
+        pub fn entry_count(&self) -> u64 {
+            self.inner.entry_count()
+        }
+    }
+
+    impl EntityCache for MokaCache {
+        fn get<'a>(&'a self, _cx: &'a Cx, key: &'a CacheKey) -> CacheFuture<'a, Option<Vec<u8>>> {
+            let value = self
+                .inner
+                .get(key.as_str())
+                .map(|(bytes, _)| bytes.to_vec());
+            Box::pin(std::future::ready(value))
+        }
+
+        fn put<'a>(
+            &'a self,
+            _cx: &'a Cx,
+            key: &'a CacheKey,
+            value: Vec<u8>,
+            ttl: Option<Duration>,
+        ) -> CacheFuture<'a, ()> {
+            self.inner
+                .insert(key.as_str().to_string(), (Arc::from(value), ttl));
+            Box::pin(std::future::ready(()))
+        }
+
+        fn invalidate<'a>(&'a self, _cx: &'a Cx, key: &'a CacheKey) -> CacheFuture<'a, ()> {
+            self.inner.invalidate(key.as_str());
+            Box::pin(std::future::ready(()))
+        }
+    }
+}
+
+#[cfg(feature = "redis")]
+pub use self::redis_cache::RedisCache;
+
+#[cfg(feature = "redis")]
+mod redis_cache {
+    use std::io;
+    use std::sync::{Mutex, PoisonError};
+    use std::time::Duration;
+
+    use asupersync::Cx;
+    use asupersync::io::{AsyncRead, AsyncWriteExt, ReadBuf};
+    use asupersync::net::TcpStream;
+    use asupersync::time::{timeout, wall_now};
+    use sqlmodel_core::Error;
+    use sqlmodel_core::error::ConfigError;
+
+    use super::{CacheFuture, CacheKey, EntityCache};
+
+    /// Entity cache shared across processes through Redis.
+    ///
+    /// Speaks RESP over asupersync sockets, so waiting on the server never
+    /// blocks the executor. Connecting and each command are bounded by
+    /// timeouts (250ms by default); a failed or timed-out command is logged
+    /// and treated as a miss. Idle connections are kept for reuse, and
+    /// concurrent sessions each use their own. Keys are stored as
+    /// `{prefix}{table}:{pk}`.
+    pub struct RedisCache {
+        host: String,
+        port: u16,
+        /// `AUTH` arguments: optional user, then password
+        auth: Option<(Option<String>, String)>,
+        db: u32,
+        prefix: String,
+        connect_timeout: Duration,
+        command_timeout: Duration,
+        idle: Mutex<Vec<RedisConnection>>,
+    }
+
+    impl std::fmt::Debug for RedisCache {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("RedisCache")
+                .field("host", &self.host)
+                .field("port", &self.port)
+                .field("db", &self.db)
+                .field("prefix", &self.prefix)
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl RedisCache {
+        /// Connect lazily to the server at `url`
+        /// (`redis://[[user]:password@]host[:port][/db]`), prefixing keys with
+        /// `sqlmodel:`.
+        #[allow(clippy::result_large_err)]
+        pub fn open(url: &str) -> Result<Self, Error> {
+            let invalid = |message: String| {
+                Error::Config(ConfigError {
+                    message,
+                    source: None,
+                })
+            };
+            let rest = url
+                .strip_prefix("redis://")
+                .ok_or_else(|| invalid(format!("redis URL must start with redis://: {url}")))?;
+            let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+            let (userinfo, hostport) = match authority.rsplit_once('@') {
+                Some((userinfo, hostport)) => (Some(userinfo), hostport),
+                None => (None, authority),
+            };
+            let auth = userinfo.map(|userinfo| match userinfo.split_once(':') {
+                Some((user, password)) => {
+                    let user = (!user.is_empty()).then(|| user.to_string());
+                    (user, password.to_string())
+                }
+                None => (None, userinfo.to_string()),
+            });
+            let (host, port) = match hostport.strip_prefix('[') {
+                Some(bracketed) => {
+                    let (host, port) = bracketed
+                        .split_once(']')
+                        .ok_or_else(|| invalid(format!("invalid redis host in {url}")))?;
+                    (host, port.strip_prefix(':'))
+                }
+                None => match hostport.rsplit_once(':') {
+                    Some((host, port)) => (host, Some(port)),
+                    None => (hostport, None),
+                },
+            };
+            let port = match port {
+                Some(port) => port
+                    .parse()
+                    .map_err(|_| invalid(format!("invalid redis port in {url}")))?,
+                None => 6379,
+            };
+            if host.is_empty() {
+                return Err(invalid(format!("redis URL has no host: {url}")));
+            }
+            let db = if path.is_empty() {
+                0
+            } else {
+                path.parse()
+                    .map_err(|_| invalid(format!("invalid redis database in {url}")))?
+            };
+            Ok(Self {
+                host: host.to_string(),
+                port,
+                auth,
+                db,
+                prefix: "sqlmodel:".to_string(),
+                connect_timeout: Duration::from_millis(250),
+                command_timeout: Duration::from_millis(250),
+                idle: Mutex::new(Vec::new()),
+            })
+        }
+
+        /// Use `prefix` instead of `sqlmodel:` in front of every key.
+        #[must_use]
+        pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+            self.prefix = prefix.into();
+            self
+        }
+
+        /// Give up connecting after `timeout`.
+        #[must_use]
+        pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+            self.connect_timeout = timeout;
+            self
+        }
+
+        /// Give up on a command after `timeout`.
+        #[must_use]
+        pub fn with_command_timeout(mut self, timeout: Duration) -> Self {
+            self.command_timeout = timeout;
+            self
+        }
+
+        fn redis_key(&self, key: &CacheKey) -> String {
+            format!("{}{}", self.prefix, key.as_str())
+        }
+
+        /// Open a connection, authenticated and on the configured database.
+        async fn connect(&self) -> io::Result<RedisConnection> {
+            let stream = TcpStream::connect((self.host.clone(), self.port)).await?;
+            let mut conn = RedisConnection {
+                stream,
+                buf: Vec::new(),
+            };
+            if let Some((user, password)) = &self.auth {
+                let mut args: Vec<&[u8]> = vec![b"AUTH"];
+                if let Some(user) = user {
+                    args.push(user.as_bytes());
+                }
+                args.push(password.as_bytes());
+                conn.command(&args).await?.ok()?;
+            }
+            if self.db != 0 {
+                let db = self.db.to_string();
+                conn.command(&[b"SELECT", db.as_bytes()]).await?.ok()?;
+            }
+            Ok(conn)
+        }
+
+        /// Run one command on an idle or new connection, treating any
+        /// failure or timeout as `None`. Connections that fail are dropped.
+        async fn run(&self, cx: &Cx, args: &[&[u8]]) -> Option<Reply> {
+            let idle = self
+                .idle
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .pop();
+            let mut conn = if let Some(conn) = idle {
+                conn
+            } else {
+                let now = cx
+                    .timer_driver()
+                    .map_or_else(wall_now, |driver| driver.now());
+                let connect = std::pin::pin!(self.connect());
+                match timeout(now, self.connect_timeout, connect).await {
+                    Ok(Ok(conn)) => conn,
+                    Ok(Err(e)) => {
+                        tracing::warn!(error = %e, "entity cache: redis connect failed");
+                        return None;
+                    }
+                    Err(_) => {
+                        tracing::warn!("entity cache: redis connect timed out");
+                        return None;
+                    }
+                }
+            };
+
+            let now = cx
+                .timer_driver()
+                .map_or_else(wall_now, |driver| driver.now());
+            let reply = {
+                let command = std::pin::pin!(conn.command(args));
+                timeout(now, self.command_timeout, command).await
+            };
+            match reply {
+                Ok(Ok(Reply::Error(message))) => {
+                    tracing::warn!(error = %message, "entity cache: redis command failed");
+                    None
+                }
+                Ok(Ok(reply)) => {
+                    self.idle
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .push(conn);
+                    Some(reply)
+                }
+                Ok(Err(e)) => {
+                    tracing::warn!(error = %e, "entity cache: redis command failed");
+                    None
+                }
+                Err(_) => {
+                    tracing::warn!("entity cache: redis command timed out");
+                    None
+                }
+            }
+        }
+    }
+
+    impl EntityCache for RedisCache {
+        fn get<'a>(&'a self, cx: &'a Cx, key: &'a CacheKey) -> CacheFuture<'a, Option<Vec<u8>>> {
+            Box::pin(async move {
+                let key = self.redis_key(key);
+                match self.run(cx, &[b"GET", key.as_bytes()]).await? {
+                    Reply::Bulk(value) => value,
+                    _ => None,
+                }
+            })
+        }
+
+        fn put<'a>(
+            &'a self,
+            cx: &'a Cx,
+            key: &'a CacheKey,
+            value: Vec<u8>,
+            ttl: Option<Duration>,
+        ) -> CacheFuture<'a, ()> {
+            Box::pin(async move {
+                let key = self.redis_key(key);
+                let mut args: Vec<&[u8]> = vec![b"SET", key.as_bytes(), &value];
+                let millis = ttl.map(|ttl| {
+                    u64::try_from(ttl.as_millis())
+                        .unwrap_or(u64::MAX)
+                        .max(1)
+                        .to_string()
+                });
+                if let Some(millis) = &millis {
+                    args.push(b"PX");
+                    args.push(millis.as_bytes());
+                }
+                let _ = self.run(cx, &args).await;
+            })
+        }
+
+        fn invalidate<'a>(&'a self, cx: &'a Cx, key: &'a CacheKey) -> CacheFuture<'a, ()> {
+            Box::pin(async move {
+                let key = self.redis_key(key);
+                let _ = self.run(cx, &[b"DEL", key.as_bytes()]).await;
+            })
+        }
+    }
+
+    /// One connection to the server, with the bytes read past the last reply.
+    struct RedisConnection {
+        stream: TcpStream,
+        buf: Vec<u8>,
+    }
+
+    impl RedisConnection {
+        async fn command(&mut self, args: &[&[u8]]) -> io::Result<Reply> {
+            self.stream.write_all(&encode_command(args)).await?;
+            loop {
+                if let Some((reply, used)) = parse_reply(&self.buf)? {
+                    self.buf.drain(..used);
+                    return Ok(reply);
+                }
+                let mut chunk = [0u8; 4096];
+                let mut read_buf = ReadBuf::new(&mut chunk);
+                std::future::poll_fn(|task| {
+                    std::pin::Pin::new(&mut self.stream).poll_read(task, &mut read_buf)
+                })
+                .await?;
+                let n = read_buf.filled().len();
+                if n == 0 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "redis closed the connection",
+                    ));
+                }
+                self.buf.extend_from_slice(&chunk[..n]);
+            }
+        }
+    }
+
+    /// A RESP2 reply, minus arrays, which the cache never asks for.
+    #[derive(Debug, PartialEq, Eq)]
+    pub(super) enum Reply {
+        Status,
+        Integer,
+        Bulk(Option<Vec<u8>>),
+        Error(String),
+    }
+
+    impl Reply {
+        /// `Ok` unless the server answered with an error.
+        fn ok(self) -> io::Result<Self> {
+            match self {
+                Reply::Error(message) => Err(io::Error::other(message)),
+                reply => Ok(reply),
+            }
+        }
+    }
+
+    /// `args` as a RESP array of bulk strings.
+    pub(super) fn encode_command(args: &[&[u8]]) -> Vec<u8> {
+        let mut out = format!("*{}\r\n", args.len()).into_bytes();
+        for arg in args {
+            out.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+            out.extend_from_slice(arg);
+            out.extend_from_slice(b"\r\n");
+        }
+        out
+    }
+
+    /// The first reply in `buf` and the bytes it spans, or `None` if `buf`
+    /// does not hold a whole reply yet.
+    pub(super) fn parse_reply(buf: &[u8]) -> io::Result<Option<(Reply, usize)>> {
+        let invalid = |what: &str| io::Error::new(io::ErrorKind::InvalidData, what.to_string());
+        let Some(end) = buf.windows(2).position(|w| w == b"\r\n") else {
+            return Ok(None);
+        };
+        if end == 0 {
+            return Err(invalid("empty redis reply line"));
+        }
+        let line =
+            std::str::from_utf8(&buf[1..end]).map_err(|_| invalid("non-UTF-8 redis reply"))?;
+        let next = end + 2;
+        let reply = match buf[0] {
+            b'+' => Reply::Status,
+            b'-' => Reply::Error(line.to_string()),
+            b':' => {
+                line.parse::<i64>()
+                    .map_err(|_| invalid("invalid redis integer"))?;
+                Reply::Integer
+            }
+            b'$' => {
+                let len: i64 = line
+                    .parse()
+                    .map_err(|_| invalid("invalid redis bulk length"))?;
+                let Ok(len) = usize::try_from(len) else {
+                    return Ok(Some((Reply::Bulk(None), next)));
+                };
+                let stop = next + len;
+                if buf.len() < stop + 2 {
+                    return Ok(None);
+                }
+                return Ok(Some((
+                    Reply::Bulk(Some(buf[next..stop].to_vec())),
+                    stop + 2,
+                )));
+            }
+            _ => return Err(invalid("unexpected redis reply type")),
+        };
+        Ok(Some((reply, next)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_key_normalizes_integer_widths() {
+        assert_eq!(
+            CacheKey::new("heroes", &[Value::Int(7)]),
+            CacheKey::new("heroes", &[Value::BigInt(7)])
+        );
+        assert_eq!(
+            CacheKey::new("heroes", &[Value::SmallInt(7)]).as_str(),
+            "heroes:7"
+        );
+    }
+
+    #[test]
+    fn cache_key_separates_tables_and_composite_keys() {
+        assert_ne!(
+            CacheKey::new("heroes", &[Value::BigInt(1)]),
+            CacheKey::new("teams", &[Value::BigInt(1)])
+        );
+        assert_eq!(
+            CacheKey::new(
+                "order_items",
+                &[Value::BigInt(1), Value::Text("a:b".into())]
+            )
+            .as_str(),
+            "order_items:1:\"a:b\""
+        );
+        assert_ne!(
+            CacheKey::new("t", &[Value::Text("1".into())]),
+            CacheKey::new("t", &[Value::BigInt(1)])
+        );
+    }
+
+    #[cfg(feature = "moka")]
+    fn block_on<F: std::future::Future>(f: F) -> F::Output {
+        asupersync::runtime::RuntimeBuilder::current_thread()
+            .build()
+            .expect("create asupersync runtime")
+            .block_on(f)
+    }
+
+    #[cfg(feature = "moka")]
+    #[test]
+    fn moka_cache_round_trips_and_invalidates() {
+        let cx = Cx::for_testing();
+        let cache = MokaCache::new(100);
+        let key = CacheKey::new("heroes", &[Value::BigInt(1)]);
+        block_on(async {
+            assert_eq!(cache.get(&cx, &key).await, None);
+            cache.put(&cx, &key, b"{}".to_vec(), None).await;
+            assert_eq!(cache.get(&cx, &key).await, Some(b"{}".to_vec()));
+            cache.invalidate(&cx, &key).await;
+            assert_eq!(cache.get(&cx, &key).await, None);
+        });
+    }
+
+    #[cfg(feature = "moka")]
+    #[test]
+    fn moka_cache_honors_entry_ttl() {
+        let cx = Cx::for_testing();
+        let cache = MokaCache::new(100);
+        let key = CacheKey::new("heroes", &[Value::BigInt(1)]);
+        block_on(async {
+            cache
+                .put(&cx, &key, b"{}".to_vec(), Some(Duration::from_millis(20)))
+                .await;
+            assert!(cache.get(&cx, &key).await.is_some());
+        });
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(block_on(cache.get(&cx, &key)), None);
+    }
+
+    #[cfg(feature = "redis")]
+    #[test]
+    fn redis_command_is_encoded_as_bulk_strings() {
+        assert_eq!(
+            redis_cache::encode_command(&[b"GET", b"sqlmodel:heroes:1"]),
+            b"*2\r\n$3\r\nGET\r\n$17\r\nsqlmodel:heroes:1\r\n".to_vec()
+        );
+    }
+
+    #[cfg(feature = "redis")]
+    #[test]
+    fn redis_replies_parse_incrementally() {
+        use redis_cache::{Reply, parse_reply};
+
+        assert_eq!(parse_reply(b"+OK\r\n").unwrap(), Some((Reply::Status, 5)));
+        assert_eq!(parse_reply(b":1\r\n").unwrap(), Some((Reply::Integer, 4)));
+        assert_eq!(
+            parse_reply(b"-ERR wrong\r\n").unwrap(),
+            Some((Reply::Error("ERR wrong".into()), 12))
+        );
+        assert_eq!(
+            parse_reply(b"$-1\r\n").unwrap(),
+            Some((Reply::Bulk(None), 5))
+        );
+        assert_eq!(
+            parse_reply(b"$2\r\n{}\r\n+OK").unwrap(),
+            Some((Reply::Bulk(Some(b"{}".to_vec())), 8))
+        );
+        assert_eq!(parse_reply(b"$2\r\n{").unwrap(), None);
+        assert_eq!(parse_reply(b"+O").unwrap(), None);
+        assert!(parse_reply(b"?x\r\n").is_err());
+    }
+
+    #[cfg(feature = "redis")]
+    #[test]
+    fn redis_url_parsing() {
+        assert!(RedisCache::open("redis://127.0.0.1/").is_ok());
+        assert!(RedisCache::open("redis://:secret@cache.local:6380/2").is_ok());
+        assert!(RedisCache::open("http://127.0.0.1/").is_err());
+        assert!(RedisCache::open("redis://127.0.0.1/db").is_err());
+        assert!(RedisCache::open("redis://host:port").is_err());
+    }
+}
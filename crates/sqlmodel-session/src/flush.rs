@@ -9,6 +9,7 @@
 
 use crate::ObjectKey;
 use asupersync::{Cx, Outcome};
+use sqlmodel_core::dialect::SqlDialect;
 use sqlmodel_core::{Connection, Error, Model, Value, quote_ident};
 use std::collections::HashMap;
 
@@ -218,7 +219,7 @@ impl LinkTableOp {
     /// Execute this link table operation.
     #[tracing::instrument(level = "debug", skip(cx, conn))]
     pub async fn execute<C: Connection>(&self, cx: &Cx, conn: &C) -> Outcome<(), Error> {
-        let dialect = conn.dialect();
+        let dialect = conn.sql_dialect();
         match self {
             LinkTableOp::Link {
                 table,
@@ -665,7 +666,7 @@ impl FlushPlan {
 
     #[allow(clippy::result_large_err)]
     fn build_insert_batch_sql(
-        dialect: sqlmodel_core::Dialect,
+        dialect: &'static dyn SqlDialect,
         ops: &[&PendingOp],
     ) -> Result<(String, Vec<Value>), Error> {
         let table = ops[0].table();
@@ -733,7 +734,7 @@ impl FlushPlan {
 
     #[allow(clippy::result_large_err)]
     fn build_delete_batch_sql(
-        dialect: sqlmodel_core::Dialect,
+        dialect: &'static dyn SqlDialect,
         ops: &[&PendingOp],
     ) -> Result<Option<(String, Vec<Value>, usize)>, Error> {
         let table = ops[0].table();
@@ -797,7 +798,7 @@ impl FlushPlan {
 
     #[allow(clippy::result_large_err)]
     fn build_update_sql(
-        dialect: sqlmodel_core::Dialect,
+        dialect: &'static dyn SqlDialect,
         op: &PendingOp,
     ) -> Result<Option<(String, Vec<Value>)>, Error> {
         let PendingOp::Update {
@@ -898,7 +899,7 @@ impl FlushPlan {
         };
 
         tracing::debug!(table = table, count = ops.len(), "Executing insert batch");
-        let dialect = conn.dialect();
+        let dialect = conn.sql_dialect();
         let (sql, params) = match Self::build_insert_batch_sql(dialect, ops) {
             Ok(v) => v,
             Err(e) => return Outcome::Err(e),
@@ -939,7 +940,7 @@ impl FlushPlan {
         }
 
         tracing::debug!(table = table, count = ops.len(), "Executing delete batch");
-        let dialect = conn.dialect();
+        let dialect = conn.sql_dialect();
 
         // For simple single-column PK, use IN clause
         // DELETE FROM table WHERE pk IN ($1, $2, $3, ...)
@@ -1027,7 +1028,7 @@ impl FlushPlan {
         };
 
         tracing::debug!(table = *table, "Executing update");
-        let dialect = conn.dialect();
+        let dialect = conn.sql_dialect();
         let (sql, params) = match Self::build_update_sql(dialect, op) {
             Ok(Some(v)) => v,
             Ok(None) => return Outcome::Ok(()),
@@ -2004,8 +2005,9 @@ mod tests {
     fn test_build_insert_batch_sql_mysql_dialect() {
         let ops = [make_insert("teams", 1), make_insert("teams", 2)];
         let refs: Vec<&PendingOp> = ops.iter().collect();
-        let (sql, params) = FlushPlan::build_insert_batch_sql(sqlmodel_core::Dialect::Mysql, &refs)
-            .expect("build insert batch sql");
+        let (sql, params) =
+            FlushPlan::build_insert_batch_sql(sqlmodel_core::Dialect::Mysql.as_dyn(), &refs)
+                .expect("build insert batch sql");
 
         assert_eq!(
             sql,
@@ -2018,9 +2020,10 @@ mod tests {
     fn test_build_delete_batch_sql_sqlite_dialect() {
         let ops = [make_delete("heroes", 1), make_delete("heroes", 2)];
         let refs: Vec<&PendingOp> = ops.iter().collect();
-        let built = FlushPlan::build_delete_batch_sql(sqlmodel_core::Dialect::Sqlite, &refs)
-            .expect("build delete batch sql")
-            .expect("non-empty delete sql");
+        let built =
+            FlushPlan::build_delete_batch_sql(sqlmodel_core::Dialect::Sqlite.as_dyn(), &refs)
+                .expect("build delete batch sql")
+                .expect("non-empty delete sql");
 
         assert_eq!(built.0, "DELETE FROM \"heroes\" WHERE \"id\" IN (?1, ?2)");
         assert_eq!(built.1.len(), 2);
@@ -2030,9 +2033,10 @@ mod tests {
     #[test]
     fn test_build_update_sql_mysql_dialect() {
         let op = make_update("teams", 42);
-        let (sql, params) = FlushPlan::build_update_sql(sqlmodel_core::Dialect::Mysql, &op)
-            .expect("build update sql")
-            .expect("non-empty update sql");
+        let (sql, params) =
+            FlushPlan::build_update_sql(sqlmodel_core::Dialect::Mysql.as_dyn(), &op)
+                .expect("build update sql")
+                .expect("non-empty update sql");
 
         assert_eq!(sql, "UPDATE `teams` SET `name` = ? WHERE `id` = ?");
         assert_eq!(params.len(), 2);
@@ -2052,7 +2056,7 @@ mod tests {
             set_values: vec![Value::Text("A".to_string())],
         };
 
-        let err = FlushPlan::build_update_sql(sqlmodel_core::Dialect::Postgres, &op)
+        let err = FlushPlan::build_update_sql(sqlmodel_core::Dialect::Postgres.as_dyn(), &op)
             .expect_err("expected set mismatch error");
         assert!(
             err.to_string()
@@ -37,101 +37,6 @@ use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock, Weak};
 
-/// Hash a slice of values for use as a primary key identifier.
-fn hash_pk_values(values: &[Value]) -> u64 {
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::Hasher;
-
-    let mut hasher = DefaultHasher::new();
-    for v in values {
-        hash_single_value(v, &mut hasher);
-    }
-    hasher.finish()
-}
-
-/// Hash a single Value into the hasher.
-fn hash_single_value(v: &Value, hasher: &mut impl std::hash::Hasher) {
-    use std::hash::Hash;
-
-    match v {
-        Value::Null => 0u8.hash(hasher),
-        Value::Bool(b) => {
-            1u8.hash(hasher);
-            b.hash(hasher);
-        }
-        Value::TinyInt(i) => {
-            2u8.hash(hasher);
-            i.hash(hasher);
-        }
-        Value::SmallInt(i) => {
-            3u8.hash(hasher);
-            i.hash(hasher);
-        }
-        Value::Int(i) => {
-            4u8.hash(hasher);
-            i.hash(hasher);
-        }
-        Value::BigInt(i) => {
-            5u8.hash(hasher);
-            i.hash(hasher);
-        }
-        Value::Float(f) => {
-            6u8.hash(hasher);
-            f.to_bits().hash(hasher);
-        }
-        Value::Double(f) => {
-            7u8.hash(hasher);
-            f.to_bits().hash(hasher);
-        }
-        Value::Decimal(s) => {
-            8u8.hash(hasher);
-            s.hash(hasher);
-        }
-        Value::Text(s) => {
-            9u8.hash(hasher);
-            s.hash(hasher);
-        }
-        Value::Bytes(b) => {
-            10u8.hash(hasher);
-            b.hash(hasher);
-        }
-        Value::Date(d) => {
-            11u8.hash(hasher);
-            d.hash(hasher);
-        }
-        Value::Time(t) => {
-            12u8.hash(hasher);
-            t.hash(hasher);
-        }
-        Value::Timestamp(ts) => {
-            13u8.hash(hasher);
-            ts.hash(hasher);
-        }
-        Value::TimestampTz(ts) => {
-            14u8.hash(hasher);
-            ts.hash(hasher);
-        }
-        Value::Uuid(u) => {
-            15u8.hash(hasher);
-            u.hash(hasher);
-        }
-        Value::Json(j) => {
-            16u8.hash(hasher);
-            j.to_string().hash(hasher);
-        }
-        Value::Array(arr) => {
-            17u8.hash(hasher);
-            arr.len().hash(hasher);
-            for item in arr {
-                hash_single_value(item, hasher);
-            }
-        }
-        Value::Default => {
-            18u8.hash(hasher);
-        }
-    }
-}
-
 /// A type-erased entry in the identity map.
 ///
 /// This wrapper holds a type-erased `Arc<RwLock<M>>` which can be downcast
@@ -175,7 +80,7 @@ impl IdentityMap {
     /// An `Arc<RwLock<M>>` pointing to the object in the map.
     pub fn insert<M: Model + Send + Sync + 'static>(&mut self, model: M) -> Arc<RwLock<M>> {
         let pk_values = model.primary_key_value();
-        let pk_hash = hash_pk_values(&pk_values);
+        let pk_hash = Value::stable_hash_slice(&pk_values);
         let type_id = TypeId::of::<M>();
         let key = (type_id, pk_hash);
 
@@ -212,7 +117,7 @@ impl IdentityMap {
         &self,
         pk_values: &[Value],
     ) -> Option<Arc<RwLock<M>>> {
-        let pk_hash = hash_pk_values(pk_values);
+        let pk_hash = Value::stable_hash_slice(pk_values);
         let type_id = TypeId::of::<M>();
         let key = (type_id, pk_hash);
 
@@ -225,7 +130,7 @@ impl IdentityMap {
 
     /// Check if an object with the given PK exists in the map.
     pub fn contains<M: Model + 'static>(&self, pk_values: &[Value]) -> bool {
-        let pk_hash = hash_pk_values(pk_values);
+        let pk_hash = Value::stable_hash_slice(pk_values);
         let type_id = TypeId::of::<M>();
         self.entries.contains_key(&(type_id, pk_hash))
     }
@@ -242,7 +147,7 @@ impl IdentityMap {
     ///
     /// `true` if the object was removed, `false` if it wasn't in the map.
     pub fn remove<M: Model + 'static>(&mut self, pk_values: &[Value]) -> bool {
-        let pk_hash = hash_pk_values(pk_values);
+        let pk_hash = Value::stable_hash_slice(pk_values);
         let type_id = TypeId::of::<M>();
         self.entries.remove(&(type_id, pk_hash)).is_some()
     }
@@ -298,7 +203,7 @@ impl IdentityMap {
     /// If it doesn't exist, returns false.
     pub fn update<M: Model + Clone + Send + Sync + 'static>(&mut self, model: &M) -> bool {
         let pk_values = model.primary_key_value();
-        let pk_hash = hash_pk_values(&pk_values);
+        let pk_hash = Value::stable_hash_slice(&pk_values);
         let type_id = TypeId::of::<M>();
         let key = (type_id, pk_hash);
 
@@ -347,7 +252,7 @@ impl WeakIdentityMap {
         arc: &Arc<RwLock<Box<dyn Any + Send + Sync>>>,
         pk_values: &[Value],
     ) {
-        let pk_hash = hash_pk_values(pk_values);
+        let pk_hash = Value::stable_hash_slice(pk_values);
         let type_id = TypeId::of::<M>();
         let key = (type_id, pk_hash);
         self.entries.insert(key, Arc::downgrade(arc));
@@ -360,7 +265,7 @@ impl WeakIdentityMap {
         &self,
         pk_values: &[Value],
     ) -> Option<Arc<RwLock<Box<dyn Any + Send + Sync>>>> {
-        let pk_hash = hash_pk_values(pk_values);
+        let pk_hash = Value::stable_hash_slice(pk_values);
         let type_id = TypeId::of::<M>();
         let key = (type_id, pk_hash);
 
@@ -591,8 +496,14 @@ mod tests {
         let pk2 = vec![Value::BigInt(1), Value::Text("a".to_string())];
         let pk3 = vec![Value::BigInt(1), Value::Text("b".to_string())];
 
-        assert_eq!(hash_pk_values(&pk1), hash_pk_values(&pk2));
-        assert_ne!(hash_pk_values(&pk1), hash_pk_values(&pk3));
+        assert_eq!(
+            Value::stable_hash_slice(&pk1),
+            Value::stable_hash_slice(&pk2)
+        );
+        assert_ne!(
+            Value::stable_hash_slice(&pk1),
+            Value::stable_hash_slice(&pk3)
+        );
     }
 
     #[test]
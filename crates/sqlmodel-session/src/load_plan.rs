@@ -0,0 +1,349 @@
+//! Declarative eager-load graphs.
+//!
+//! [`load!`](crate::load) describes which relationships of a model to load,
+//! nested as deep as needed:
+//!
+//! ```ignore
+//! let plan = load!(Hero => { team, powers => { category } });
+//!
+//! let mut heroes = session.query::<Hero>().all().await?;
+//! session.load_graph(&cx, &mut heroes, &plan).await?;
+//!
+//! for hero in &heroes {
+//!     for power in &hero.powers {
+//!         println!("{}: {:?}", power.name, power.category.get());
+//!     }
+//! }
+//! ```
+//!
+//! Executing a plan runs one batched query per edge however many parents
+//! there are: the heroes' teams, then their powers, then the categories of
+//! every power loaded by the previous step. It is the equivalent of chained
+//! `selectinload()` options in SQLAlchemy.
+
+use std::future::Future;
+use std::pin::Pin;
+
+use asupersync::{Cx, Outcome};
+use serde::{Deserialize, Serialize};
+use sqlmodel_core::{Connection, Error, Lazy, Model, Related, RelatedMany, Value};
+
+use crate::Session;
+
+/// Future returned when loading one edge of a [`LoadPlan`].
+pub type EdgeFuture<'a> = Pin<Box<dyn Future<Output = Outcome<(), Error>> + 'a>>;
+
+/// A relationship container an eager-load edge can fill: [`Lazy`],
+/// [`Related`] or [`RelatedMany`].
+pub trait EagerRelation: Sized {
+    /// The model on the other side of the relationship.
+    type Target: Model + Clone + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static;
+
+    /// Load this relationship for every parent with one batched query.
+    fn load_batch<'a, P, C>(
+        session: &'a mut Session<C>,
+        cx: &'a Cx,
+        parents: &'a mut [P],
+        get: fn(&P) -> &Self,
+        get_mut: fn(&mut P) -> &mut Self,
+    ) -> EdgeFuture<'a>
+    where
+        P: Model + 'static,
+        C: Connection;
+
+    /// Copy or move the loaded targets out so their own relationships can be
+    /// loaded.
+    fn take_loaded(&mut self) -> Vec<Self::Target>;
+
+    /// Put back what [`take_loaded`](Self::take_loaded) returned.
+    fn restore_loaded(&mut self, loaded: Vec<Self::Target>);
+}
+
+impl<T> EagerRelation for Lazy<T>
+where
+    T: Model + Clone + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    type Target = T;
+
+    fn load_batch<'a, P, C>(
+        session: &'a mut Session<C>,
+        cx: &'a Cx,
+        parents: &'a mut [P],
+        get: fn(&P) -> &Self,
+        _get_mut: fn(&mut P) -> &mut Self,
+    ) -> EdgeFuture<'a>
+    where
+        P: Model + 'static,
+        C: Connection,
+    {
+        Box::pin(async move {
+            discard_count(session.load_to_one::<P, T, Self, _>(cx, parents, get).await)
+        })
+    }
+
+    fn take_loaded(&mut self) -> Vec<T> {
+        self.get().cloned().into_iter().collect()
+    }
+
+    fn restore_loaded(&mut self, mut loaded: Vec<T>) {
+        if let (Some(slot), Some(target)) = (self.get_mut(), loaded.pop()) {
+            *slot = target;
+        }
+    }
+}
+
+impl<T> EagerRelation for Related<T>
+where
+    T: Model + Clone + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    type Target = T;
+
+    fn load_batch<'a, P, C>(
+        session: &'a mut Session<C>,
+        cx: &'a Cx,
+        parents: &'a mut [P],
+        get: fn(&P) -> &Self,
+        _get_mut: fn(&mut P) -> &mut Self,
+    ) -> EdgeFuture<'a>
+    where
+        P: Model + 'static,
+        C: Connection,
+    {
+        Box::pin(async move {
+            discard_count(session.load_to_one::<P, T, Self, _>(cx, parents, get).await)
+        })
+    }
+
+    fn take_loaded(&mut self) -> Vec<T> {
+        self.get().cloned().into_iter().collect()
+    }
+
+    fn restore_loaded(&mut self, mut loaded: Vec<T>) {
+        if let (Some(slot), Some(target)) = (self.get_mut(), loaded.pop()) {
+            *slot = target;
+        }
+    }
+}
+
+impl<T> EagerRelation for RelatedMany<T>
+where
+    T: Model + Clone + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    type Target = T;
+
+    fn load_batch<'a, P, C>(
+        session: &'a mut Session<C>,
+        cx: &'a Cx,
+        parents: &'a mut [P],
+        get: fn(&P) -> &Self,
+        get_mut: fn(&mut P) -> &mut Self,
+    ) -> EdgeFuture<'a>
+    where
+        P: Model + 'static,
+        C: Connection,
+    {
+        Box::pin(async move {
+            let Some(first) = parents.first() else {
+                return Outcome::Ok(());
+            };
+            let outcome = match get(first).link_table().copied() {
+                Some(link_table) => {
+                    session
+                        .load_many_to_many_pk(
+                            cx,
+                            parents,
+                            get_mut,
+                            Model::primary_key_value,
+                            &link_table,
+                        )
+                        .await
+                }
+                None => {
+                    session
+                        .load_one_to_many(cx, parents, get_mut, |parent| {
+                            parent
+                                .primary_key_value()
+                                .into_iter()
+                                .next()
+                                .unwrap_or(Value::Null)
+                        })
+                        .await
+                }
+            };
+            discard_count(outcome)
+        })
+    }
+
+    fn take_loaded(&mut self) -> Vec<T> {
+        self.get_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    fn restore_loaded(&mut self, loaded: Vec<T>) {
+        if let Some(slot) = self.get_mut() {
+            *slot = loaded;
+        }
+    }
+}
+
+fn discard_count(outcome: Outcome<usize, Error>) -> Outcome<(), Error> {
+    match outcome {
+        Outcome::Ok(_) => Outcome::Ok(()),
+        Outcome::Err(e) => Outcome::Err(e),
+        Outcome::Cancelled(r) => Outcome::Cancelled(r),
+        Outcome::Panicked(p) => Outcome::Panicked(p),
+    }
+}
+
+/// One edge of a plan, type-erased over the relationship container.
+trait LoadEdge<P, C: Connection> {
+    fn load<'a>(
+        &'a self,
+        session: &'a mut Session<C>,
+        cx: &'a Cx,
+        parents: &'a mut [P],
+    ) -> EdgeFuture<'a>;
+}
+
+struct Edge<P, R: EagerRelation, C: Connection> {
+    get: fn(&P) -> &R,
+    get_mut: fn(&mut P) -> &mut R,
+    nested: LoadPlan<R::Target, C>,
+}
+
+impl<P, R, C> LoadEdge<P, C> for Edge<P, R, C>
+where
+    P: Model + 'static,
+    R: EagerRelation,
+    C: Connection + 'static,
+{
+    fn load<'a>(
+        &'a self,
+        session: &'a mut Session<C>,
+        cx: &'a Cx,
+        parents: &'a mut [P],
+    ) -> EdgeFuture<'a> {
+        Box::pin(async move {
+            match R::load_batch(session, cx, parents, self.get, self.get_mut).await {
+                Outcome::Ok(()) => {}
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            }
+            if self.nested.is_empty() {
+                return Outcome::Ok(());
+            }
+
+            // Gather every parent's targets into one slice so each nested
+            // edge is still a single query, then hand them back.
+            let mut counts = Vec::with_capacity(parents.len());
+            let mut targets = Vec::new();
+            for parent in parents.iter_mut() {
+                let loaded = (self.get_mut)(parent).take_loaded();
+                counts.push(loaded.len());
+                targets.extend(loaded);
+            }
+            let outcome = self.nested.execute(session, cx, &mut targets).await;
+            let mut targets = targets.into_iter();
+            for (parent, count) in parents.iter_mut().zip(counts) {
+                (self.get_mut)(parent).restore_loaded(targets.by_ref().take(count).collect());
+            }
+            outcome
+        })
+    }
+}
+
+/// A typed eager-load graph rooted at `M`, usually built with
+/// [`load!`](crate::load) and run with [`Session::load_graph`].
+pub struct LoadPlan<M, C: Connection> {
+    edges: Vec<Box<dyn LoadEdge<M, C>>>,
+}
+
+impl<M: Model + 'static, C: Connection + 'static> LoadPlan<M, C> {
+    /// Create a plan that loads nothing.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { edges: Vec::new() }
+    }
+
+    /// Load the relationship `get`/`get_mut` point at, then whatever
+    /// `nested` adds to the plan for its targets.
+    #[must_use]
+    pub fn edge<R>(
+        mut self,
+        get: fn(&M) -> &R,
+        get_mut: fn(&mut M) -> &mut R,
+        nested: impl FnOnce(LoadPlan<R::Target, C>) -> LoadPlan<R::Target, C>,
+    ) -> Self
+    where
+        R: EagerRelation + 'static,
+    {
+        self.edges.push(Box::new(Edge {
+            get,
+            get_mut,
+            nested: nested(LoadPlan::new()),
+        }));
+        self
+    }
+
+    /// Whether the plan has no edges.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    pub(crate) async fn execute(
+        &self,
+        session: &mut Session<C>,
+        cx: &Cx,
+        objects: &mut [M],
+    ) -> Outcome<(), Error> {
+        for edge in &self.edges {
+            match edge.load(session, cx, objects).await {
+                Outcome::Ok(()) => {}
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            }
+        }
+        Outcome::Ok(())
+    }
+}
+
+impl<M: Model + 'static, C: Connection + 'static> Default for LoadPlan<M, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Build a [`LoadPlan`] from the relationship fields to eager-load.
+///
+/// Each field is a [`Lazy`], [`Related`] or [`RelatedMany`]; `field => { .. }`
+/// also loads relationships of the field's targets.
+///
+/// ```ignore
+/// let plan = load!(Hero => { team, powers => { category } });
+/// session.load_graph(&cx, &mut heroes, &plan).await?;
+/// ```
+#[macro_export]
+macro_rules! load {
+    (@edges $plan:expr;) => {
+        $plan
+    };
+    (@edges $plan:expr; $field:ident => { $($nested:tt)* } $(, $($rest:tt)*)?) => {
+        $crate::load!(@edges $plan.edge(
+            |parent| &parent.$field,
+            |parent| &mut parent.$field,
+            |plan| $crate::load!(@edges plan; $($nested)*),
+        ); $($($rest)*)?)
+    };
+    (@edges $plan:expr; $field:ident $(, $($rest:tt)*)?) => {
+        $crate::load!(@edges $plan.edge(
+            |parent| &parent.$field,
+            |parent| &mut parent.$field,
+            |plan| plan,
+        ); $($($rest)*)?)
+    };
+    ($model:ty => { $($edges:tt)* }) => {
+        $crate::load!(@edges $crate::LoadPlan::<$model, _>::new(); $($edges)*)
+    };
+}
@@ -0,0 +1,328 @@
+//! Request-scoped batching of primary-key loads.
+//!
+//! A [`Loader`] coalesces `load(pk)` calls issued concurrently, typically by
+//! GraphQL resolvers serving one request, into a single `IN` query through
+//! [`Session::get_many`], in the style of DataLoader:
+//!
+//! ```ignore
+//! let teams = Loader::<i64, Team, _>::new(&mut session).max_batch_size(500);
+//!
+//! // Both resolvers run concurrently; one query fetches teams 1 and 2.
+//! let (a, b) = join!(teams.load(&cx, 1), teams.load(&cx, 2));
+//! ```
+//!
+//! Results, misses included, are cached for the life of the loader, so each
+//! key is fetched at most once per request; a batch that fails fails every
+//! load of its keys.
+
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::{Arc, PoisonError};
+use std::task::{Poll, Waker};
+use std::time::{Duration, Instant};
+
+use asupersync::sync::Mutex;
+use asupersync::{Cx, Outcome};
+use serde::{Deserialize, Serialize};
+use sqlmodel_core::{Connection, Error, Model, Value};
+
+use crate::Session;
+
+/// Default cap on the number of keys fetched by one batch.
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 1000;
+
+/// Where one key is in its life cycle.
+enum Slot<M> {
+    Queued,
+    InFlight,
+    Loaded(Option<M>),
+    Failed(String),
+}
+
+struct LoaderState<M> {
+    slots: HashMap<u64, Slot<M>>,
+    queue: Vec<(u64, Value)>,
+    window_start: Instant,
+    dispatching: bool,
+    /// Loads parked until the batch in flight finishes.
+    waiters: Vec<Waker>,
+}
+
+impl<M> LoaderState<M> {
+    fn enqueue(&mut self, hash: u64, key: Value) {
+        if self.queue.is_empty() {
+            self.window_start = Instant::now();
+        }
+        self.slots.insert(hash, Slot::Queued);
+        self.queue.push((hash, key));
+    }
+
+    fn end_dispatch(&mut self) {
+        self.dispatching = false;
+        for waiter in self.waiters.drain(..) {
+            waiter.wake();
+        }
+    }
+}
+
+/// A batch claimed by one waiter for fetching.
+///
+/// Dropping it unfinished, because the dispatching load was cancelled, puts
+/// the keys back in the queue for another waiter to fetch.
+struct Claim<'a, M> {
+    state: &'a std::sync::Mutex<LoaderState<M>>,
+    batch: Option<Vec<(u64, Value)>>,
+}
+
+impl<'a, M> Claim<'a, M> {
+    fn keys(&self) -> Vec<Value> {
+        self.batch
+            .iter()
+            .flatten()
+            .map(|(_, key)| key.clone())
+            .collect()
+    }
+
+    fn finish(mut self) -> (std::sync::MutexGuard<'a, LoaderState<M>>, Vec<(u64, Value)>) {
+        let batch = self.batch.take().unwrap_or_default();
+        let state = self.state;
+        let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.end_dispatch();
+        (state, batch)
+    }
+}
+
+impl<M> Drop for Claim<'_, M> {
+    fn drop(&mut self) {
+        if let Some(batch) = self.batch.take() {
+            let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+            for (hash, key) in batch {
+                state.enqueue(hash, key);
+            }
+            state.end_dispatch();
+        }
+    }
+}
+
+/// The session a [`Loader`] fetches through.
+enum SessionHandle<'s, C: Connection> {
+    Borrowed(Mutex<&'s mut Session<C>>),
+    Shared(Arc<Mutex<Session<C>>>),
+}
+
+/// Batches and caches primary-key loads of `M` against one [`Session`].
+///
+/// `K` is the key type callers pass to [`load`](Self::load). `M` must have
+/// a single-column primary key.
+pub struct Loader<'s, K, M, C: Connection> {
+    session: SessionHandle<'s, C>,
+    state: std::sync::Mutex<LoaderState<M>>,
+    max_batch_size: usize,
+    delay: Duration,
+    _key: PhantomData<fn(K)>,
+}
+
+impl<'s, K, M, C> Loader<'s, K, M, C>
+where
+    K: Into<Value>,
+    M: Model + Clone + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
+    C: Connection,
+{
+    /// Create a loader that batches through `session` until it is dropped.
+    pub fn new(session: &'s mut Session<C>) -> Self {
+        Self::with_session(SessionHandle::Borrowed(Mutex::new(session)))
+    }
+
+    /// Create a loader over a session shared with other loaders, e.g. one
+    /// per model stored in a GraphQL request's context.
+    pub fn shared(session: Arc<Mutex<Session<C>>>) -> Self {
+        Self::with_session(SessionHandle::Shared(session))
+    }
+
+    fn with_session(session: SessionHandle<'s, C>) -> Self {
+        Self {
+            session,
+            state: std::sync::Mutex::new(LoaderState {
+                slots: HashMap::new(),
+                queue: Vec::new(),
+                window_start: Instant::now(),
+                dispatching: false,
+                waiters: Vec::new(),
+            }),
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            delay: Duration::ZERO,
+            _key: PhantomData,
+        }
+    }
+
+    /// Dispatch a batch as soon as it holds `size` keys (default
+    /// [`DEFAULT_MAX_BATCH_SIZE`]).
+    #[must_use]
+    pub fn max_batch_size(mut self, size: usize) -> Self {
+        self.max_batch_size = size.max(1);
+        self
+    }
+
+    /// Keep a batch open for `delay` after its first key so later callers
+    /// can join it.
+    ///
+    /// With the default of zero, a batch collects every key queued before
+    /// the waiting loads next yield.
+    #[must_use]
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Load the object with primary key `key`, batched with every other
+    /// pending load. `None` if no such row exists.
+    pub async fn load(&self, cx: &Cx, key: K) -> Outcome<Option<M>, Error> {
+        match self.load_many(cx, [key]).await {
+            Outcome::Ok(mut found) => Outcome::Ok(found.pop().flatten()),
+            Outcome::Err(e) => Outcome::Err(e),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
+    /// Load several objects at once, in the order of `keys`.
+    pub async fn load_many(
+        &self,
+        cx: &Cx,
+        keys: impl IntoIterator<Item = K>,
+    ) -> Outcome<Vec<Option<M>>, Error> {
+        let hashes: Vec<u64> = {
+            let mut state = self.lock_state();
+            keys.into_iter()
+                .map(|key| {
+                    let key: Value = key.into();
+                    let hash = key.stable_hash();
+                    if !state.slots.contains_key(&hash) {
+                        state.enqueue(hash, key);
+                    }
+                    hash
+                })
+                .collect()
+        };
+
+        loop {
+            if let Some(found) = self.resolved(&hashes) {
+                return found;
+            }
+            if let Some(reason) = cx.cancel_reason() {
+                return Outcome::Cancelled(reason);
+            }
+            self.next_turn().await;
+
+            let Some(claim) = self.claim_due_batch() else {
+                continue;
+            };
+            match self.fetch(cx, &claim.keys()).await {
+                Outcome::Ok(found) => {
+                    let (mut state, batch) = claim.finish();
+                    for ((hash, _), model) in batch.into_iter().zip(found) {
+                        state.slots.insert(hash, Slot::Loaded(model));
+                    }
+                }
+                Outcome::Err(e) => {
+                    let (mut state, batch) = claim.finish();
+                    let message = e.to_string();
+                    let mine = batch.iter().any(|(hash, _)| hashes.contains(hash));
+                    for (hash, _) in batch {
+                        state.slots.insert(hash, Slot::Failed(message.clone()));
+                    }
+                    if mine {
+                        return Outcome::Err(e);
+                    }
+                }
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            }
+        }
+    }
+
+    async fn fetch(&self, cx: &Cx, keys: &[Value]) -> Outcome<Vec<Option<M>>, Error> {
+        let locked = "failed to acquire the loader's session";
+        match &self.session {
+            SessionHandle::Borrowed(session) => {
+                let Ok(mut session) = session.lock(cx).await else {
+                    return Outcome::Err(Error::Custom(locked.to_string()));
+                };
+                session.get_many::<M>(cx, keys).await
+            }
+            SessionHandle::Shared(session) => {
+                let Ok(mut session) = session.lock(cx).await else {
+                    return Outcome::Err(Error::Custom(locked.to_string()));
+                };
+                session.get_many::<M>(cx, keys).await
+            }
+        }
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, LoaderState<M>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The results for `hashes` once every one of them has been fetched.
+    fn resolved(&self, hashes: &[u64]) -> Option<Outcome<Vec<Option<M>>, Error>> {
+        let state = self.lock_state();
+        let mut found = Vec::with_capacity(hashes.len());
+        for hash in hashes {
+            match state.slots.get(hash)? {
+                Slot::Loaded(model) => found.push(model.clone()),
+                Slot::Failed(message) => {
+                    return Some(Outcome::Err(Error::Custom(format!(
+                        "batched load of {} failed: {message}",
+                        M::TABLE_NAME
+                    ))));
+                }
+                Slot::Queued | Slot::InFlight => return None,
+            }
+        }
+        Some(Outcome::Ok(found))
+    }
+
+    /// Yield once so sibling loads can queue their keys, parking until the
+    /// batch in flight finishes if there is one.
+    async fn next_turn(&self) {
+        let mut parked = false;
+        std::future::poll_fn(|task| {
+            if parked {
+                return Poll::Ready(());
+            }
+            parked = true;
+            let mut state = self.lock_state();
+            if state.dispatching {
+                state.waiters.push(task.waker().clone());
+            } else {
+                task.waker().wake_by_ref();
+            }
+            Poll::Pending
+        })
+        .await;
+    }
+
+    /// Claim the next batch if no other load is fetching one and it is
+    /// either full or past its delay.
+    fn claim_due_batch(&self) -> Option<Claim<'_, M>> {
+        let mut state = self.lock_state();
+        if state.dispatching || state.queue.is_empty() {
+            return None;
+        }
+        let full = state.queue.len() >= self.max_batch_size;
+        if !full && state.window_start.elapsed() < self.delay {
+            return None;
+        }
+        let take = state.queue.len().min(self.max_batch_size);
+        let batch: Vec<(u64, Value)> = state.queue.drain(..take).collect();
+        for (hash, _) in &batch {
+            state.slots.insert(*hash, Slot::InFlight);
+        }
+        state.window_start = Instant::now();
+        state.dispatching = true;
+        Some(Claim {
+            state: &self.state,
+            batch: Some(batch),
+        })
+    }
+}
@@ -18,8 +18,23 @@
 //! // This is the fix:
 //! session.load_many(&mut heroes, |h| &mut h.team).await?;  // 1 query
 //! ```
+//!
+//! Once detection is enabled, `Session::get` cache misses, `Session::load_lazy`
+//! and `Lazy::load` are recorded automatically, keyed by the loaded model's
+//! table and `"get"` or `"lazy"`. The recorded call site is the caller of
+//! those methods, i.e. the loop that issues the queries.
+//!
+//! # CI Assertions
+//!
+//! ```ignore
+//! let scope = N1DetectionScope::from_tracker(session.n1_tracker_mut().unwrap());
+//! render_dashboard(&mut session).await?;
+//! scope.assert_max_queries(&session.n1_stats().unwrap(), 2)?;
+//! ```
 
+use sqlmodel_core::Error;
 use std::collections::HashMap;
+use std::panic::Location;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// Tracks lazy load queries for N+1 detection.
@@ -118,6 +133,19 @@ impl N1QueryTracker {
     /// the threshold, a warning is emitted.
     #[track_caller]
     pub fn record_load(&mut self, parent_type: &'static str, relationship: &'static str) {
+        self.record_load_at(parent_type, relationship, Location::caller());
+    }
+
+    /// Record a lazy load triggered at `caller`.
+    ///
+    /// Used by async loaders, which capture the caller's location before
+    /// awaiting (`#[track_caller]` does not reach into async bodies).
+    pub fn record_load_at(
+        &mut self,
+        parent_type: &'static str,
+        relationship: &'static str,
+        caller: &'static Location<'static>,
+    ) {
         if !self.enabled {
             return;
         }
@@ -131,7 +159,6 @@ impl N1QueryTracker {
             + 1;
 
         // Capture call site
-        let caller = std::panic::Location::caller();
         self.call_sites.push(CallSite {
             parent_type,
             relationship,
@@ -148,13 +175,21 @@ impl N1QueryTracker {
 
     /// Emit a warning about potential N+1 query pattern.
     fn emit_warning(&self, parent_type: &'static str, relationship: &'static str, count: usize) {
+        let location = self
+            .call_sites
+            .iter()
+            .rev()
+            .find(|s| s.parent_type == parent_type && s.relationship == relationship)
+            .map(|s| format!("{}:{}", s.file, s.line))
+            .unwrap_or_default();
         tracing::warn!(
             target: "sqlmodel::n1",
             parent = parent_type,
             relationship = relationship,
             queries = count,
             threshold = self.threshold,
-            "N+1 QUERY PATTERN DETECTED! Consider using Session::load_many() for batch loading."
+            location = location.as_str(),
+            "N+1 QUERY PATTERN DETECTED at {location}! Consider using Session::load_many() for batch loading."
         );
 
         // Log recent call sites for this relationship
@@ -338,6 +373,30 @@ impl N1DetectionScope {
             );
         }
     }
+
+    /// Fail if more than `max` lazy loads happened since the scope started.
+    ///
+    /// Intended for tests and CI: wrap the code under test in a scope and
+    /// assert its query budget with the session's final stats.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Custom`] describing the overrun when the budget is
+    /// exceeded.
+    #[allow(clippy::result_large_err)]
+    pub fn assert_max_queries(&self, final_stats: &N1Stats, max: usize) -> Result<(), Error> {
+        let loads = final_stats
+            .total_loads
+            .saturating_sub(self.initial_stats.total_loads);
+        if loads > max {
+            self.log_summary(final_stats);
+            return Err(Error::Custom(format!(
+                "N+1 query budget exceeded: {loads} lazy loads in scope (max {max}); \
+                 use Session::load_many() to batch them"
+            )));
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -431,6 +490,17 @@ mod tests {
         assert!(site.line > 0);
     }
 
+    #[test]
+    fn test_record_load_at_uses_given_location() {
+        let mut tracker = N1QueryTracker::new();
+        let here = Location::caller();
+        tracker.record_load_at("teams", "lazy", here);
+
+        let site = &tracker.call_sites()[0];
+        assert_eq!(site.file, here.file());
+        assert_eq!(site.line, here.line());
+    }
+
     #[test]
     fn test_callsite_timestamp_monotonic() {
         let mut tracker = N1QueryTracker::new();
@@ -551,6 +621,21 @@ mod tests {
         scope.log_summary(&final_stats);
     }
 
+    #[test]
+    fn test_scope_assert_max_queries() {
+        let mut tracker = N1QueryTracker::new().with_threshold(10);
+        tracker.record_load("Hero", "team");
+        let scope = N1DetectionScope::from_tracker(&tracker);
+
+        tracker.record_load("Hero", "team");
+        tracker.record_load("Hero", "team");
+        assert!(scope.assert_max_queries(&tracker.stats(), 2).is_ok());
+
+        tracker.record_load("Hero", "team");
+        let err = scope.assert_max_queries(&tracker.stats(), 2).unwrap_err();
+        assert!(err.to_string().contains("3 lazy loads"));
+    }
+
     #[test]
     fn test_scope_calculates_delta() {
         let initial = N1Stats {
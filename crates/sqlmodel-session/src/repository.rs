@@ -0,0 +1,252 @@
+//! Repository pattern over [`Session`].
+//!
+//! [`Repository<M>`] is a narrow, per-model persistence interface. Every
+//! method has a default implementation in terms of [`Repository::session`],
+//! so an implementation only says where its session lives; services depend
+//! on the trait and tests substitute an in-memory mock that overrides the
+//! methods it needs.
+//!
+//! ```ignore
+//! async fn rename<R: Repository<Hero>>(cx: &Cx, repo: &mut R, id: i64, name: &str) -> Outcome<(), Error> {
+//!     let Some(mut hero) = repo.find(cx, id).await? else { return Outcome::Ok(()) };
+//!     hero.name = name.to_string();
+//!     repo.save(cx, &hero).await
+//! }
+//!
+//! rename(&cx, &mut session.repository::<Hero>(), 1, "Deadpond").await?;
+//! ```
+//!
+//! `#[derive(Repository)]` (from `sqlmodel-macros`) implements the trait for
+//! a struct holding a session and adds `find_by_<field>` finders.
+
+use std::future::Future;
+use std::marker::PhantomData;
+
+use serde::{Deserialize, Serialize};
+use sqlmodel_core::{Connection, Cx, Error, Model, Outcome, Value};
+use sqlmodel_query::{Expr, OrderBy, Select};
+
+use crate::Session;
+
+/// One page of [`Repository::paginate`] results.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page<M> {
+    /// Rows on this page.
+    pub items: Vec<M>,
+    /// Page number, starting at 1.
+    pub page: u64,
+    /// Maximum rows per page.
+    pub per_page: u64,
+    /// Rows across all pages.
+    pub total: u64,
+}
+
+impl<M> Page<M> {
+    /// Number of pages needed for `total` rows.
+    pub fn total_pages(&self) -> u64 {
+        self.total.div_ceil(self.per_page.max(1))
+    }
+
+    /// Whether a later page has rows.
+    pub fn has_next(&self) -> bool {
+        self.page < self.total_pages()
+    }
+
+    /// Whether this is not the first page.
+    pub fn has_prev(&self) -> bool {
+        self.page > 1
+    }
+}
+
+/// Per-model persistence operations with default implementations over a
+/// [`Session`].
+///
+/// `save` and `delete` flush immediately; the surrounding transaction (if
+/// any) is still committed by the caller.
+pub trait Repository<M>
+where
+    M: Model + Clone + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    /// Connection type of the underlying session.
+    type Conn: Connection;
+
+    /// The session the default methods run on.
+    fn session(&mut self) -> &mut Session<Self::Conn>;
+
+    /// Load the row with primary key `pk`.
+    fn find(
+        &mut self,
+        cx: &Cx,
+        pk: impl Into<Value>,
+    ) -> impl Future<Output = Outcome<Option<M>, Error>> {
+        let pk = pk.into();
+        async move { self.session().get_by_pk::<M>(cx, &[pk]).await }
+    }
+
+    /// Load every row of the table.
+    fn find_all(&mut self, cx: &Cx) -> impl Future<Output = Outcome<Vec<M>, Error>> {
+        async move { self.session().exec(cx, Select::<M>::new()).await }
+    }
+
+    /// Load the rows matching `filter`.
+    fn find_by(&mut self, cx: &Cx, filter: Expr) -> impl Future<Output = Outcome<Vec<M>, Error>> {
+        async move {
+            self.session()
+                .exec(cx, Select::<M>::new().filter(filter))
+                .await
+        }
+    }
+
+    /// Load the first row matching `filter`.
+    fn find_one_by(
+        &mut self,
+        cx: &Cx,
+        filter: Expr,
+    ) -> impl Future<Output = Outcome<Option<M>, Error>> {
+        async move {
+            let query = Select::<M>::new().filter(filter).limit(1);
+            self.session()
+                .exec(cx, query)
+                .await
+                .map(|rows| rows.into_iter().next())
+        }
+    }
+
+    /// Insert `obj` if it is new, otherwise write its current values, and
+    /// flush.
+    fn save(&mut self, cx: &Cx, obj: &M) -> impl Future<Output = Outcome<(), Error>> {
+        async move {
+            let session = self.session();
+            if session.object_state(obj).is_none() && obj.is_new() {
+                session.add(obj);
+            } else {
+                match session.merge(cx, obj.clone(), true).await {
+                    Outcome::Ok(_) => {}
+                    Outcome::Err(e) => return Outcome::Err(e),
+                    Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                    Outcome::Panicked(p) => return Outcome::Panicked(p),
+                }
+            }
+            session.flush(cx).await
+        }
+    }
+
+    /// Delete `obj`'s row and flush.
+    fn delete(&mut self, cx: &Cx, obj: &M) -> impl Future<Output = Outcome<(), Error>> {
+        async move {
+            let session = self.session();
+            if session.object_state(obj).is_none() {
+                // Attach first so the session knows the row exists.
+                match session.merge(cx, obj.clone(), true).await {
+                    Outcome::Ok(_) => {}
+                    Outcome::Err(e) => return Outcome::Err(e),
+                    Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                    Outcome::Panicked(p) => return Outcome::Panicked(p),
+                }
+            }
+            session.delete(obj);
+            session.flush(cx).await
+        }
+    }
+
+    /// Load page `page` (starting at 1) of `per_page` rows.
+    ///
+    /// Rows follow the model's default ordering, or the primary key when it
+    /// has none, so pages do not overlap.
+    fn paginate(
+        &mut self,
+        cx: &Cx,
+        page: u64,
+        per_page: u64,
+    ) -> impl Future<Output = Outcome<Page<M>, Error>> {
+        async move {
+            let page = page.max(1);
+            let per_page = per_page.max(1);
+            let session = self.session();
+
+            let total = match Select::<M>::new().count(cx, session.connection()).await {
+                Outcome::Ok(total) => total,
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            };
+
+            let mut query = Select::<M>::new()
+                .limit(per_page)
+                .offset((page - 1) * per_page);
+            if M::DEFAULT_ORDER_BY.is_empty() {
+                for column in M::PRIMARY_KEY {
+                    query = query.order_by(OrderBy::asc(Expr::col(*column)));
+                }
+            }
+            session.exec(cx, query).await.map(|items| Page {
+                items,
+                page,
+                per_page,
+                total,
+            })
+        }
+    }
+}
+
+/// [`Repository`] borrowing a [`Session`], from [`Session::repository`].
+pub struct SessionRepository<'s, M, C: Connection> {
+    session: &'s mut Session<C>,
+    model: PhantomData<fn() -> M>,
+}
+
+impl<C: Connection> Session<C> {
+    /// A [`Repository`] for `M` backed by this session.
+    pub fn repository<M>(&mut self) -> SessionRepository<'_, M, C> {
+        SessionRepository {
+            session: self,
+            model: PhantomData,
+        }
+    }
+}
+
+impl<M, C> Repository<M> for SessionRepository<'_, M, C>
+where
+    M: Model + Clone + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
+    C: Connection,
+{
+    type Conn = C;
+
+    fn session(&mut self) -> &mut Session<C> {
+        self.session
+    }
+}
+
+impl<M, C: Connection> std::fmt::Debug for SessionRepository<'_, M, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionRepository")
+            .field("model", &std::any::type_name::<M>())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(page: u64, per_page: u64, total: u64) -> Page<()> {
+        Page {
+            items: Vec::new(),
+            page,
+            per_page,
+            total,
+        }
+    }
+
+    #[test]
+    fn test_page_navigation() {
+        assert_eq!(page(1, 10, 25).total_pages(), 3);
+        assert!(page(1, 10, 25).has_next());
+        assert!(!page(1, 10, 25).has_prev());
+        assert!(page(3, 10, 25).has_prev());
+        assert!(!page(3, 10, 25).has_next());
+        assert_eq!(page(1, 10, 0).total_pages(), 0);
+        assert!(!page(1, 10, 0).has_next());
+        assert_eq!(page(1, 0, 5).total_pages(), 5);
+    }
+}
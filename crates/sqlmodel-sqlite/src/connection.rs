@@ -32,16 +32,20 @@
 use crate::ffi;
 use crate::types;
 use sqlmodel_core::{
-    Connection, Cx, Error, IsolationLevel, Outcome, PreparedStatement, Row, TransactionOps, Value,
+    Connection, Cx, Error, IsolationLevel, Outcome, PreparedStatement, QueryOptions, QueryResult,
+    ResultColumn, Row, ToParams, TransactionOps, Value, ValueRef,
     error::{ConnectionError, ConnectionErrorKind, QueryError, QueryErrorKind},
     row::ColumnInfo,
 };
-use std::ffi::{CStr, CString, c_int};
+use std::ffi::{CStr, CString, c_int, c_void};
 use std::future::Future;
+use std::marker::PhantomData;
 use std::ptr;
-use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 
+#[cfg(feature = "load-extension")]
+use asupersync::CancelReason;
 #[cfg(feature = "console")]
 use sqlmodel_console::{ConsoleAware, SqlModelConsole};
 
@@ -54,6 +58,9 @@ pub struct SqliteConfig {
     pub flags: OpenFlags,
     /// Busy timeout in milliseconds.
     pub busy_timeout_ms: u32,
+    /// Native extensions loaded right after the database opens.
+    #[cfg(feature = "load-extension")]
+    pub extensions: Vec<SqliteExtension>,
 }
 
 /// Flags controlling how the database is opened.
@@ -146,6 +153,8 @@ impl Default for SqliteConfig {
             path: ":memory:".to_string(),
             flags: OpenFlags::create_read_write(),
             busy_timeout_ms: 5000,
+            #[cfg(feature = "load-extension")]
+            extensions: Vec::new(),
         }
     }
 }
@@ -157,6 +166,8 @@ impl SqliteConfig {
             path: path.into(),
             flags: OpenFlags::create_read_write(),
             busy_timeout_ms: 5000,
+            #[cfg(feature = "load-extension")]
+            extensions: Vec::new(),
         }
     }
 
@@ -176,6 +187,170 @@ impl SqliteConfig {
         self.busy_timeout_ms = ms;
         self
     }
+
+    /// Load native extensions, in order, every time a connection opens.
+    ///
+    /// Opening fails if any extension cannot be loaded.
+    ///
+    /// # Safety
+    ///
+    /// Extensions are native libraries that run with the full privileges of
+    /// this process. Only list libraries you trust.
+    #[cfg(feature = "load-extension")]
+    pub unsafe fn extensions<I>(mut self, extensions: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<SqliteExtension>,
+    {
+        self.extensions
+            .extend(extensions.into_iter().map(Into::into));
+        self
+    }
+}
+
+/// A native SQLite extension library, such as `sqlite-vss`.
+#[cfg(feature = "load-extension")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqliteExtension {
+    /// Path to the shared library; SQLite tries platform suffixes if omitted.
+    pub path: String,
+    /// Entry point symbol; SQLite derives one from the file name if `None`.
+    pub entry_point: Option<String>,
+}
+
+#[cfg(feature = "load-extension")]
+impl SqliteExtension {
+    /// An extension loaded through its default entry point.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            entry_point: None,
+        }
+    }
+
+    /// Use `symbol` as the entry point.
+    pub fn entry_point(mut self, symbol: impl Into<String>) -> Self {
+        self.entry_point = Some(symbol.into());
+        self
+    }
+}
+
+#[cfg(feature = "load-extension")]
+impl From<&str> for SqliteExtension {
+    fn from(path: &str) -> Self {
+        Self::new(path)
+    }
+}
+
+#[cfg(feature = "load-extension")]
+impl From<String> for SqliteExtension {
+    fn from(path: String) -> Self {
+        Self::new(path)
+    }
+}
+
+/// How an online backup copies pages.
+///
+/// The default copies 100 pages per step without pausing. An incremental
+/// backup copies a few pages per step and sleeps in between, releasing the
+/// connection so queries and writers keep running while the snapshot is taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackupOptions {
+    /// Pages copied per step; `-1` copies the whole database in one step.
+    pub pages_per_step: i32,
+    /// Pause between steps.
+    pub pause: Duration,
+}
+
+impl Default for BackupOptions {
+    fn default() -> Self {
+        Self {
+            pages_per_step: 100,
+            pause: Duration::ZERO,
+        }
+    }
+}
+
+impl BackupOptions {
+    /// Copy `pages_per_step` pages at a time, sleeping `pause` between steps.
+    pub fn incremental(pages_per_step: i32, pause: Duration) -> Self {
+        Self {
+            pages_per_step: pages_per_step.max(1),
+            pause,
+        }
+    }
+}
+
+/// Progress of an online backup, reported after every step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackupProgress {
+    /// Pages still to copy.
+    pub remaining: u32,
+    /// Pages in the source database.
+    pub page_count: u32,
+}
+
+impl BackupProgress {
+    /// Pages copied so far.
+    pub fn copied(&self) -> u32 {
+        self.page_count.saturating_sub(self.remaining)
+    }
+}
+
+/// Virtual machine instructions SQLite runs between interruption checks.
+const INTERRUPT_CHECK_OPS: c_int = 1000;
+
+/// When to interrupt the connection's running statement.
+struct Interrupt<'a> {
+    cx: &'a Cx,
+    deadline: Option<Instant>,
+}
+
+impl Interrupt<'_> {
+    fn due(&self) -> bool {
+        self.cx.is_cancel_requested()
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+unsafe extern "C" fn interrupt_due(interrupt: *mut c_void) -> c_int {
+    // SAFETY: installed by `InterruptGuard`, which outlives the statements run
+    let interrupt = unsafe { &*interrupt.cast::<Interrupt<'_>>() };
+    c_int::from(interrupt.due())
+}
+
+/// Interrupts the connection's statements once an [`Interrupt`] is due,
+/// until dropped.
+struct InterruptGuard<'a> {
+    conn: &'a SqliteConnection,
+    _interrupt: PhantomData<&'a Interrupt<'a>>,
+}
+
+impl<'a> InterruptGuard<'a> {
+    fn install(conn: &'a SqliteConnection, interrupt: &'a Interrupt<'a>) -> Self {
+        let arg = ptr::from_ref(interrupt).cast_mut().cast::<c_void>();
+        conn.with_db(|db| {
+            // SAFETY: db is valid and `interrupt` outlives the guard
+            unsafe {
+                ffi::sqlite3_progress_handler(db, INTERRUPT_CHECK_OPS, Some(interrupt_due), arg);
+            }
+        });
+        Self {
+            conn,
+            _interrupt: PhantomData,
+        }
+    }
+}
+
+impl Drop for InterruptGuard<'_> {
+    fn drop(&mut self) {
+        self.conn.with_db(|db| {
+            // SAFETY: db is valid
+            unsafe { ffi::sqlite3_progress_handler(db, 0, None, ptr::null_mut()) };
+        });
+    }
 }
 
 /// Inner state of the SQLite connection, protected by a mutex for thread safety.
@@ -194,6 +369,9 @@ unsafe impl Send for SqliteInner {}
 /// This is a thread-safe wrapper around a SQLite database handle.
 pub struct SqliteConnection {
     inner: Mutex<SqliteInner>,
+    /// The database handle, for [`interrupt`](Self::interrupt), which SQLite
+    /// allows from any thread while a statement runs. Null once closed.
+    handle: Mutex<*mut ffi::sqlite3>,
     path: String,
     /// Optional console for rich output
     #[cfg(feature = "console")]
@@ -249,15 +427,111 @@ impl SqliteConnection {
             }
         }
 
-        Ok(Self {
+        let conn = Self {
             inner: Mutex::new(SqliteInner {
                 db,
                 in_transaction: false,
             }),
+            handle: Mutex::new(db),
             path: config.path.clone(),
             #[cfg(feature = "console")]
             console: None,
-        })
+        };
+
+        #[cfg(feature = "load-extension")]
+        for extension in &config.extensions {
+            conn.load_extension_now(&extension.path, extension.entry_point.as_deref())?;
+        }
+
+        Ok(conn)
+    }
+
+    /// Load a native extension into this connection.
+    ///
+    /// `entry_point` names the initialization symbol; with `None`, SQLite
+    /// derives it from the file name. Loading is enabled only for the
+    /// duration of this call and only through the C API, so SQL can never
+    /// call `load_extension()` itself.
+    ///
+    /// Built-in modules such as FTS5 and JSON need no loading; check them
+    /// with [`ffi::compile_option_used`].
+    ///
+    /// # Safety
+    ///
+    /// The extension is a native library that runs with the full privileges
+    /// of this process. Only load libraries you trust.
+    #[cfg(feature = "load-extension")]
+    pub unsafe fn load_extension(
+        &self,
+        cx: &Cx,
+        path: &str,
+        entry_point: Option<&str>,
+    ) -> Outcome<(), Error> {
+        if cx.is_cancel_requested() {
+            return Outcome::Cancelled(CancelReason::user("load_extension cancelled"));
+        }
+        self.load_extension_now(path, entry_point)
+            .map_or_else(Outcome::Err, Outcome::Ok)
+    }
+
+    #[cfg(feature = "load-extension")]
+    fn load_extension_now(&self, path: &str, entry_point: Option<&str>) -> Result<(), Error> {
+        let invalid = |what: &str| {
+            Error::Connection(ConnectionError {
+                kind: ConnectionErrorKind::Connect,
+                message: format!("Invalid extension {what}: contains null byte"),
+                source: None,
+            })
+        };
+        let c_path = CString::new(path).map_err(|_| invalid("path"))?;
+        let c_entry = entry_point
+            .map(CString::new)
+            .transpose()
+            .map_err(|_| invalid("entry point"))?;
+
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let mut errmsg: *mut std::ffi::c_char = ptr::null_mut();
+
+        // SAFETY: db is valid and locked; the strings outlive the calls.
+        let rc = unsafe {
+            ffi::sqlite3_db_config(
+                inner.db,
+                ffi::SQLITE_DBCONFIG_ENABLE_LOAD_EXTENSION,
+                1 as c_int,
+                ptr::null_mut::<c_int>(),
+            );
+            let rc = ffi::sqlite3_load_extension(
+                inner.db,
+                c_path.as_ptr(),
+                c_entry.as_ref().map_or(ptr::null(), |entry| entry.as_ptr()),
+                &mut errmsg,
+            );
+            ffi::sqlite3_db_config(
+                inner.db,
+                ffi::SQLITE_DBCONFIG_ENABLE_LOAD_EXTENSION,
+                0 as c_int,
+                ptr::null_mut::<c_int>(),
+            );
+            rc
+        };
+
+        if rc != ffi::SQLITE_OK {
+            let msg = if !errmsg.is_null() {
+                // SAFETY: errmsg is valid
+                let msg = unsafe { CStr::from_ptr(errmsg).to_string_lossy().into_owned() };
+                unsafe { ffi::sqlite3_free(errmsg.cast()) };
+                msg
+            } else {
+                ffi::error_string(rc).to_string()
+            };
+            return Err(Error::Connection(ConnectionError {
+                kind: ConnectionErrorKind::Connect,
+                message: format!("Failed to load extension {path}: {msg}"),
+                source: None,
+            }));
+        }
+
+        Ok(())
     }
 
     /// Open an in-memory database.
@@ -308,7 +582,7 @@ impl SqliteConnection {
                 ffi::error_string(rc).to_string()
             };
 
-            return Err(Error::Query(QueryError {
+            return Err(Error::from_query_error(QueryError {
                 kind: error_code_to_kind(rc),
                 sql: Some(sql.to_string()),
                 sqlstate: None,
@@ -336,84 +610,194 @@ impl SqliteConnection {
 
     /// Backup the current database to another open SQLite connection.
     pub fn backup_to_connection(&self, dest: &SqliteConnection) -> Result<(), Error> {
-        let self_first = (std::ptr::from_ref(self) as usize) <= (std::ptr::from_ref(dest) as usize);
-        let (source_guard, dest_guard) = if self_first {
-            let source_guard = self.inner.lock().unwrap_or_else(|e| e.into_inner());
-            let dest_guard = dest.inner.lock().unwrap_or_else(|e| e.into_inner());
-            (source_guard, dest_guard)
-        } else {
-            let dest_guard = dest.inner.lock().unwrap_or_else(|e| e.into_inner());
-            let source_guard = self.inner.lock().unwrap_or_else(|e| e.into_inner());
-            (source_guard, dest_guard)
-        };
+        self.backup_to_connection_with(dest, &BackupOptions::default(), |_| {})
+    }
 
-        let source_db = source_guard.db;
-        let dest_db = dest_guard.db;
+    /// Snapshot the live database to `dest_path`, reporting progress after
+    /// every step.
+    ///
+    /// The destination is created if needed and overwritten otherwise.
+    pub fn backup_to(
+        &self,
+        dest_path: impl AsRef<str>,
+        progress: impl FnMut(BackupProgress),
+    ) -> Result<(), Error> {
+        self.backup_to_with(dest_path, &BackupOptions::default(), progress)
+    }
 
-        let main = CString::new("main").expect("static sqlite db name");
+    /// Like [`backup_to`](Self::backup_to), copying pages as `options` says.
+    ///
+    /// Use [`BackupOptions::incremental`] to keep the connection available
+    /// to other tasks while a large database is copied.
+    pub fn backup_to_with(
+        &self,
+        dest_path: impl AsRef<str>,
+        options: &BackupOptions,
+        progress: impl FnMut(BackupProgress),
+    ) -> Result<(), Error> {
+        let dest = SqliteConnection::open(
+            &SqliteConfig::file(dest_path.as_ref()).flags(OpenFlags::create_read_write()),
+        )?;
+        self.backup_to_connection_with(&dest, options, progress)
+    }
 
-        // SAFETY: We hold locks on both connections; db pointers are valid.
-        let backup =
-            unsafe { ffi::sqlite3_backup_init(dest_db, main.as_ptr(), source_db, main.as_ptr()) };
-        if backup.is_null() {
-            let msg = unsafe { CStr::from_ptr(ffi::sqlite3_errmsg(dest_db)) }
-                .to_string_lossy()
-                .into_owned();
-            return Err(Error::Connection(ConnectionError {
-                kind: ConnectionErrorKind::Connect,
-                message: format!("SQLite backup init failed: {msg}"),
-                source: None,
-            }));
+    /// Backup to another open connection, copying pages as `options` says.
+    ///
+    /// Both connections are locked only while a step runs, so other tasks
+    /// can use them between steps. If the source is written through another
+    /// connection mid-backup, SQLite restarts the copy automatically.
+    pub fn backup_to_connection_with(
+        &self,
+        dest: &SqliteConnection,
+        options: &BackupOptions,
+        mut progress: impl FnMut(BackupProgress),
+    ) -> Result<(), Error> {
+        if ptr::eq(self, dest) {
+            return Err(backup_error(
+                "SQLite backup failed: source and destination are the same connection".to_string(),
+            ));
         }
 
-        let mut rc = unsafe { ffi::sqlite3_backup_step(backup, 100) };
-        loop {
-            if rc == ffi::SQLITE_DONE {
-                break;
-            }
-            if rc == ffi::SQLITE_OK {
-                rc = unsafe { ffi::sqlite3_backup_step(backup, 100) };
-                continue;
+        let main = CString::new("main").expect("static sqlite db name");
+
+        let backup = {
+            let (source_guard, dest_guard) = self.lock_pair(dest);
+            // SAFETY: We hold locks on both connections; db pointers are valid.
+            let backup = unsafe {
+                ffi::sqlite3_backup_init(
+                    dest_guard.db,
+                    main.as_ptr(),
+                    source_guard.db,
+                    main.as_ptr(),
+                )
+            };
+            if backup.is_null() {
+                return Err(backup_error(format!(
+                    "SQLite backup init failed: {}",
+                    errmsg(dest_guard.db)
+                )));
             }
-            if rc == ffi::SQLITE_BUSY || rc == ffi::SQLITE_LOCKED {
-                std::thread::sleep(Duration::from_millis(50));
-                rc = unsafe { ffi::sqlite3_backup_step(backup, 100) };
-                continue;
+            backup
+        };
+
+        let rc = loop {
+            let (rc, step_progress) = {
+                let _guards = self.lock_pair(dest);
+                // SAFETY: backup is live and both connections are locked.
+                unsafe {
+                    let rc = ffi::sqlite3_backup_step(backup, options.pages_per_step);
+                    let step_progress = BackupProgress {
+                        remaining: ffi::sqlite3_backup_remaining(backup) as u32,
+                        page_count: ffi::sqlite3_backup_pagecount(backup) as u32,
+                    };
+                    (rc, step_progress)
+                }
+            };
+            match rc {
+                ffi::SQLITE_DONE => {
+                    progress(step_progress);
+                    break rc;
+                }
+                ffi::SQLITE_OK => {
+                    progress(step_progress);
+                    if !options.pause.is_zero() {
+                        std::thread::sleep(options.pause);
+                    }
+                }
+                ffi::SQLITE_BUSY | ffi::SQLITE_LOCKED => {
+                    std::thread::sleep(options.pause.max(Duration::from_millis(50)));
+                }
+                _ => break rc,
             }
-            break;
-        }
+        };
 
+        let (_source_guard, dest_guard) = self.lock_pair(dest);
+        // SAFETY: backup is live and both connections are locked.
         let finish_rc = unsafe { ffi::sqlite3_backup_finish(backup) };
 
-        if rc != ffi::SQLITE_DONE && rc != ffi::SQLITE_OK {
-            let msg = unsafe { CStr::from_ptr(ffi::sqlite3_errmsg(dest_db)) }
-                .to_string_lossy()
-                .into_owned();
-            return Err(Error::Connection(ConnectionError {
-                kind: ConnectionErrorKind::Connect,
-                message: format!("SQLite backup failed: {} ({})", msg, ffi::error_string(rc)),
-                source: None,
-            }));
+        if rc != ffi::SQLITE_DONE {
+            return Err(backup_error(format!(
+                "SQLite backup failed: {} ({})",
+                errmsg(dest_guard.db),
+                ffi::error_string(rc)
+            )));
         }
 
         if finish_rc != ffi::SQLITE_OK {
-            let msg = unsafe { CStr::from_ptr(ffi::sqlite3_errmsg(dest_db)) }
-                .to_string_lossy()
-                .into_owned();
-            return Err(Error::Connection(ConnectionError {
-                kind: ConnectionErrorKind::Connect,
-                message: format!(
-                    "SQLite backup finish failed: {} ({})",
-                    msg,
-                    ffi::error_string(finish_rc)
-                ),
-                source: None,
-            }));
+            return Err(backup_error(format!(
+                "SQLite backup finish failed: {} ({})",
+                errmsg(dest_guard.db),
+                ffi::error_string(finish_rc)
+            )));
         }
 
         Ok(())
     }
 
+    /// Write a compacted copy of the database to `dest_path` with
+    /// `VACUUM INTO`.
+    ///
+    /// Unlike a backup, the copy omits free pages. The destination must not
+    /// already exist as a non-empty file.
+    pub fn vacuum_into(&self, dest_path: impl AsRef<str>) -> Result<(), Error> {
+        let path = dest_path.as_ref().replace('\'', "''");
+        self.execute_raw(&format!("VACUUM INTO '{path}'"))
+    }
+
+    /// Attach the database file at `path` under the name `schema`.
+    ///
+    /// Its tables are then addressed as `schema.table`, for example with
+    /// `Select::from_schema`. `path` may be `:memory:`; a missing file is
+    /// created. SQLite does not allow attaching inside a transaction.
+    pub fn attach(&self, path: impl AsRef<str>, schema: &str) -> Result<(), Error> {
+        self.execute_sync(
+            "ATTACH DATABASE ? AS ?",
+            &[
+                Value::Text(path.as_ref().to_string()),
+                Value::Text(schema.to_string()),
+            ],
+        )
+        .map(|_| ())
+    }
+
+    /// Detach a database attached with [`attach`](Self::attach).
+    pub fn detach(&self, schema: &str) -> Result<(), Error> {
+        self.execute_sync("DETACH DATABASE ?", &[Value::Text(schema.to_string())])
+            .map(|_| ())
+    }
+
+    /// `(schema, file)` pairs for every database on this connection, `main`
+    /// first. In-memory databases report an empty file.
+    pub fn databases(&self) -> Result<Vec<(String, String)>, Error> {
+        let rows = self.query_sync("PRAGMA database_list", &[])?;
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let text = |idx| match row.get(idx) {
+                    Some(Value::Text(s)) => s.clone(),
+                    _ => String::new(),
+                };
+                (text(1), text(2))
+            })
+            .collect())
+    }
+
+    /// Lock this connection and `other` in a consistent order.
+    fn lock_pair<'a>(
+        &'a self,
+        other: &'a SqliteConnection,
+    ) -> (MutexGuard<'a, SqliteInner>, MutexGuard<'a, SqliteInner>) {
+        if (std::ptr::from_ref(self) as usize) <= (std::ptr::from_ref(other) as usize) {
+            let own = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+            let theirs = other.inner.lock().unwrap_or_else(|e| e.into_inner());
+            (own, theirs)
+        } else {
+            let theirs = other.inner.lock().unwrap_or_else(|e| e.into_inner());
+            let own = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+            (own, theirs)
+        }
+    }
+
     /// Get the last insert rowid.
     pub fn last_insert_rowid(&self) -> i64 {
         let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
@@ -428,11 +812,76 @@ impl SqliteConnection {
         unsafe { ffi::sqlite3_changes(inner.db) }
     }
 
+    /// Interrupt the statement running on this connection, from any thread.
+    ///
+    /// The statement fails with an interrupted error. Statements run through
+    /// the [`Connection`] methods are interrupted automatically when their
+    /// `Cx` is cancelled, and return `Outcome::Cancelled`.
+    pub fn interrupt(&self) {
+        let handle = self.handle.lock().unwrap_or_else(|e| e.into_inner());
+        if !handle.is_null() {
+            // SAFETY: the handle is not closed while its lock is held
+            unsafe { ffi::sqlite3_interrupt(*handle) };
+        }
+    }
+
+    /// Run `f`, interrupting its statements once `cx` is cancelled.
+    fn cancellable<T>(&self, cx: &Cx, f: impl FnOnce() -> Result<T, Error>) -> Outcome<T, Error> {
+        self.interruptible(cx, None, f)
+    }
+
+    /// Run `f`, interrupting its statements once `cx` is cancelled or
+    /// `timeout` has passed.
+    fn interruptible<T>(
+        &self,
+        cx: &Cx,
+        timeout: Option<Duration>,
+        f: impl FnOnce() -> Result<T, Error>,
+    ) -> Outcome<T, Error> {
+        if let Some(reason) = cx.cancel_reason() {
+            return Outcome::Cancelled(reason);
+        }
+        let interrupt = Interrupt {
+            cx,
+            deadline: timeout.map(|timeout| Instant::now() + timeout),
+        };
+        let guard = InterruptGuard::install(self, &interrupt);
+        let result = f();
+        drop(guard);
+        match result {
+            Ok(value) => Outcome::Ok(value),
+            Err(e) => match cx.cancel_reason() {
+                Some(reason) => Outcome::Cancelled(reason),
+                None if interrupt.due() => Outcome::Err(Error::Timeout),
+                None => Outcome::Err(e),
+            },
+        }
+    }
+
+    /// Run `f` with the raw database handle while holding the connection lock.
+    pub(crate) fn with_db<R>(&self, f: impl FnOnce(*mut ffi::sqlite3) -> R) -> R {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        f(inner.db)
+    }
+
     /// Prepare and execute a query synchronously, returning all rows.
     ///
     /// This is a blocking operation suitable for simple use cases.
     /// For async usage, use the `Connection` trait methods instead.
     pub fn query_sync(&self, sql: &str, params: &[Value]) -> Result<Vec<Row>, Error> {
+        self.query_raw_sync(sql, params).map(|result| result.rows)
+    }
+
+    /// Like [`query_sync`](Self::query_sync), but also returns column metadata.
+    ///
+    /// Each column reports its declared type, if it has one; SQLite does not
+    /// expose nullability or source tables without extra compile options.
+    pub fn query_raw_sync(&self, sql: &str, params: &[Value]) -> Result<QueryResult, Error> {
+        self.query_refs_sync(sql, &params.to_params())
+    }
+
+    /// Like [`query_raw_sync`](Self::query_raw_sync), binding borrowed parameters.
+    fn query_refs_sync(&self, sql: &str, params: &[ValueRef<'_>]) -> Result<QueryResult, Error> {
         #[cfg(feature = "console")]
         let start = std::time::Instant::now();
 
@@ -442,7 +891,7 @@ impl SqliteConnection {
         // Bind parameters
         for (i, param) in params.iter().enumerate() {
             // SAFETY: stmt is valid, index is 1-based
-            let rc = unsafe { types::bind_value(stmt, (i + 1) as c_int, param) };
+            let rc = unsafe { types::bind_value_ref(stmt, (i + 1) as c_int, *param) };
             if rc != ffi::SQLITE_OK {
                 // SAFETY: stmt is valid
                 unsafe { ffi::sqlite3_finalize(stmt) };
@@ -460,6 +909,15 @@ impl SqliteConnection {
             col_names.push(name);
         }
         let columns = Arc::new(ColumnInfo::new(col_names.clone()));
+        let result_columns = col_names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| ResultColumn {
+                // SAFETY: stmt is valid, i < col_count
+                sql_type: unsafe { types::column_decltype(stmt, i as c_int) },
+                ..ResultColumn::named(name.clone())
+            })
+            .collect();
 
         // Fetch rows
         let mut rows = Vec::new();
@@ -495,7 +953,10 @@ impl SqliteConnection {
             self.emit_query_result(sql, &col_names, &rows, elapsed_ms);
         }
 
-        Ok(rows)
+        Ok(QueryResult {
+            columns: result_columns,
+            rows,
+        })
     }
 
     /// Prepare and execute a statement synchronously, returning rows affected.
@@ -503,6 +964,11 @@ impl SqliteConnection {
     /// This is a blocking operation suitable for simple use cases.
     /// For async usage, use the `Connection` trait methods instead.
     pub fn execute_sync(&self, sql: &str, params: &[Value]) -> Result<u64, Error> {
+        self.execute_refs_sync(sql, &params.to_params())
+    }
+
+    /// Like [`execute_sync`](Self::execute_sync), binding borrowed parameters.
+    fn execute_refs_sync(&self, sql: &str, params: &[ValueRef<'_>]) -> Result<u64, Error> {
         #[cfg(feature = "console")]
         let start = std::time::Instant::now();
 
@@ -512,7 +978,7 @@ impl SqliteConnection {
         // Bind parameters
         for (i, param) in params.iter().enumerate() {
             // SAFETY: stmt is valid
-            let rc = unsafe { types::bind_value(stmt, (i + 1) as c_int, param) };
+            let rc = unsafe { types::bind_value_ref(stmt, (i + 1) as c_int, *param) };
             if rc != ffi::SQLITE_OK {
                 // SAFETY: stmt is valid
                 unsafe { ffi::sqlite3_finalize(stmt) };
@@ -636,6 +1102,7 @@ impl SqliteConnection {
 
 impl Drop for SqliteConnection {
     fn drop(&mut self) {
+        *self.handle.lock().unwrap_or_else(|e| e.into_inner()) = ptr::null_mut();
         if let Ok(inner) = self.inner.lock() {
             if !inner.db.is_null() {
                 // SAFETY: db is valid
@@ -684,68 +1151,115 @@ impl Connection for SqliteConnection {
 
     fn query(
         &self,
-        _cx: &Cx,
+        cx: &Cx,
         sql: &str,
         params: &[Value],
     ) -> impl Future<Output = Outcome<Vec<Row>, Error>> + Send {
-        let result = self.query_sync(sql, params);
-        async move { result.map_or_else(Outcome::Err, Outcome::Ok) }
+        let result = self.cancellable(cx, || self.query_sync(sql, params));
+        async move { result }
+    }
+
+    fn query_raw(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<QueryResult, Error>> + Send {
+        let result = self.cancellable(cx, || self.query_raw_sync(sql, params));
+        async move { result }
     }
 
     fn query_one(
         &self,
-        _cx: &Cx,
+        cx: &Cx,
         sql: &str,
         params: &[Value],
     ) -> impl Future<Output = Outcome<Option<Row>, Error>> + Send {
-        let result = self.query_sync(sql, params).map(|mut rows| rows.pop());
-        async move { result.map_or_else(Outcome::Err, Outcome::Ok) }
+        let result = self.cancellable(cx, || {
+            self.query_sync(sql, params).map(|mut rows| rows.pop())
+        });
+        async move { result }
     }
 
     fn execute(
         &self,
-        _cx: &Cx,
+        cx: &Cx,
         sql: &str,
         params: &[Value],
     ) -> impl Future<Output = Outcome<u64, Error>> + Send {
-        let result = self.execute_sync(sql, params);
-        async move { result.map_or_else(Outcome::Err, Outcome::Ok) }
+        let result = self.cancellable(cx, || self.execute_sync(sql, params));
+        async move { result }
+    }
+
+    fn query_params<P: ToParams + ?Sized>(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &P,
+    ) -> impl Future<Output = Outcome<Vec<Row>, Error>> + Send {
+        let result = self.cancellable(cx, || {
+            self.query_refs_sync(sql, &params.to_params())
+                .map(|result| result.rows)
+        });
+        async move { result }
+    }
+
+    fn execute_params<P: ToParams + ?Sized>(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &P,
+    ) -> impl Future<Output = Outcome<u64, Error>> + Send {
+        let result = self.cancellable(cx, || self.execute_refs_sync(sql, &params.to_params()));
+        async move { result }
+    }
+
+    fn query_with(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+        options: &QueryOptions,
+    ) -> impl Future<Output = Outcome<Vec<Row>, Error>> + Send {
+        let sql = options.statement_sql(sqlmodel_core::Dialect::Sqlite, sql);
+        let result = self.interruptible(cx, options.timeout, || self.query_sync(&sql, params));
+        async move { result }
+    }
+
+    fn execute_with(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+        options: &QueryOptions,
+    ) -> impl Future<Output = Outcome<u64, Error>> + Send {
+        let sql = options.statement_sql(sqlmodel_core::Dialect::Sqlite, sql);
+        let result = self.interruptible(cx, options.timeout, || self.execute_sync(&sql, params));
+        async move { result }
     }
 
     fn insert(
         &self,
-        _cx: &Cx,
+        cx: &Cx,
         sql: &str,
         params: &[Value],
     ) -> impl Future<Output = Outcome<i64, Error>> + Send {
-        let result = self.insert_sync(sql, params);
-        async move { result.map_or_else(Outcome::Err, Outcome::Ok) }
+        let result = self.cancellable(cx, || self.insert_sync(sql, params));
+        async move { result }
     }
 
     fn batch(
         &self,
-        _cx: &Cx,
+        cx: &Cx,
         statements: &[(String, Vec<Value>)],
     ) -> impl Future<Output = Outcome<Vec<u64>, Error>> + Send {
-        let mut results = Vec::with_capacity(statements.len());
-        let mut error = None;
-
-        for (sql, params) in statements {
-            match self.execute_sync(sql, params) {
-                Ok(n) => results.push(n),
-                Err(e) => {
-                    error = Some(e);
-                    break;
-                }
-            }
-        }
-
-        async move {
-            match error {
-                Some(e) => Outcome::Err(e),
-                None => Outcome::Ok(results),
-            }
-        }
+        let result = self.cancellable(cx, || {
+            statements
+                .iter()
+                .map(|(sql, params)| self.execute_sync(sql, params))
+                .collect()
+        });
+        async move { result }
     }
 
     fn begin(&self, cx: &Cx) -> impl Future<Output = Outcome<Self::Tx<'_>, Error>> + Send {
@@ -828,32 +1342,38 @@ impl Connection for SqliteConnection {
 impl TransactionOps for SqliteTransaction<'_> {
     fn query(
         &self,
-        _cx: &Cx,
+        cx: &Cx,
         sql: &str,
         params: &[Value],
     ) -> impl Future<Output = Outcome<Vec<Row>, Error>> + Send {
-        let result = self.conn.query_sync(sql, params);
-        async move { result.map_or_else(Outcome::Err, Outcome::Ok) }
+        let result = self
+            .conn
+            .cancellable(cx, || self.conn.query_sync(sql, params));
+        async move { result }
     }
 
     fn query_one(
         &self,
-        _cx: &Cx,
+        cx: &Cx,
         sql: &str,
         params: &[Value],
     ) -> impl Future<Output = Outcome<Option<Row>, Error>> + Send {
-        let result = self.conn.query_sync(sql, params).map(|mut rows| rows.pop());
-        async move { result.map_or_else(Outcome::Err, Outcome::Ok) }
+        let result = self.conn.cancellable(cx, || {
+            self.conn.query_sync(sql, params).map(|mut rows| rows.pop())
+        });
+        async move { result }
     }
 
     fn execute(
         &self,
-        _cx: &Cx,
+        cx: &Cx,
         sql: &str,
         params: &[Value],
     ) -> impl Future<Output = Outcome<u64, Error>> + Send {
-        let result = self.conn.execute_sync(sql, params);
-        async move { result.map_or_else(Outcome::Err, Outcome::Ok) }
+        let result = self
+            .conn
+            .cancellable(cx, || self.conn.execute_sync(sql, params));
+        async move { result }
     }
 
     fn savepoint(&self, _cx: &Cx, name: &str) -> impl Future<Output = Outcome<(), Error>> + Send {
@@ -939,7 +1459,7 @@ fn prepare_error(db: *mut ffi::sqlite3, sql: &str) -> Error {
     };
     let code = unsafe { ffi::sqlite3_errcode(db) };
 
-    Error::Query(QueryError {
+    Error::from_query_error(QueryError {
         kind: error_code_to_kind(code),
         sql: Some(sql.to_string()),
         sqlstate: None,
@@ -978,7 +1498,7 @@ fn step_error(db: *mut ffi::sqlite3, sql: &str) -> Error {
     };
     let code = unsafe { ffi::sqlite3_errcode(db) };
 
-    Error::Query(QueryError {
+    Error::from_query_error(QueryError {
         kind: error_code_to_kind(code),
         sql: Some(sql.to_string()),
         sqlstate: None,
@@ -990,6 +1510,21 @@ fn step_error(db: *mut ffi::sqlite3, sql: &str) -> Error {
     })
 }
 
+fn errmsg(db: *mut ffi::sqlite3) -> String {
+    // SAFETY: db is valid, errmsg returns a valid C string
+    unsafe { CStr::from_ptr(ffi::sqlite3_errmsg(db)) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn backup_error(message: String) -> Error {
+    Error::Connection(ConnectionError {
+        kind: ConnectionErrorKind::Connect,
+        message,
+        source: None,
+    })
+}
+
 fn error_code_to_kind(code: c_int) -> QueryErrorKind {
     match code {
         ffi::SQLITE_CONSTRAINT => QueryErrorKind::Constraint,
@@ -1361,6 +1896,36 @@ mod tests {
         assert_eq!(conn.path(), ":memory:");
     }
 
+    #[test]
+    fn test_interrupt_stops_running_statement() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let conn = Arc::new(SqliteConnection::open_memory().unwrap());
+        let done = Arc::new(AtomicBool::new(false));
+        let interrupter = {
+            let conn = Arc::clone(&conn);
+            let done = Arc::clone(&done);
+            std::thread::spawn(move || {
+                // Interrupts with no statement running are no-ops, so retry.
+                while !done.load(Ordering::Relaxed) {
+                    std::thread::sleep(Duration::from_millis(10));
+                    conn.interrupt();
+                }
+            })
+        };
+        let result = conn.query_sync(
+            "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c) \
+             SELECT count(*) FROM c",
+            &[],
+        );
+        done.store(true, Ordering::Relaxed);
+        interrupter.join().unwrap();
+        assert!(result.is_err());
+
+        // The connection stays usable.
+        assert_eq!(conn.query_sync("SELECT 1", &[]).unwrap().len(), 1);
+    }
+
     #[test]
     fn test_execute_raw() {
         let conn = SqliteConnection::open_memory().unwrap();
@@ -1372,6 +1937,43 @@ mod tests {
         assert_eq!(conn.last_insert_rowid(), 1);
     }
 
+    #[test]
+    fn test_constraint_violations_are_structured() {
+        let conn = SqliteConnection::open_memory().unwrap();
+        conn.execute_raw(
+            "CREATE TABLE members (id INTEGER PRIMARY KEY, org_id INTEGER NOT NULL, \
+             email TEXT NOT NULL, UNIQUE (org_id, email))",
+        )
+        .unwrap();
+        conn.execute_sync(
+            "INSERT INTO members (org_id, email) VALUES (?, ?)",
+            &[Value::Int(1), Value::Text("a@b.c".to_string())],
+        )
+        .unwrap();
+
+        let err = conn
+            .execute_sync(
+                "INSERT INTO members (org_id, email) VALUES (?, ?)",
+                &[Value::Int(1), Value::Text("a@b.c".to_string())],
+            )
+            .unwrap_err();
+        assert!(
+            matches!(&err, Error::UniqueViolation { columns, .. } if columns == &["org_id", "email"]),
+            "expected unique violation, got {err:?}"
+        );
+
+        let err = conn
+            .execute_sync(
+                "INSERT INTO members (org_id, email) VALUES (?, NULL)",
+                &[Value::Int(2)],
+            )
+            .unwrap_err();
+        assert!(
+            matches!(&err, Error::NotNullViolation { columns, .. } if columns == &["email"]),
+            "expected not-null violation, got {err:?}"
+        );
+    }
+
     #[test]
     fn test_query_sync() {
         let conn = SqliteConnection::open_memory().unwrap();
@@ -1391,6 +1993,25 @@ mod tests {
         assert_eq!(rows[1].get_named::<String>("name").unwrap(), "Bob");
     }
 
+    #[test]
+    fn test_query_raw_sync_reports_columns_for_empty_result() {
+        let conn = SqliteConnection::open_memory().unwrap();
+        conn.execute_raw("CREATE TABLE test (id INTEGER PRIMARY KEY, name VARCHAR(40))")
+            .unwrap();
+
+        let result = conn
+            .query_raw_sync("SELECT id, name, 1 + 1 AS two FROM test", &[])
+            .unwrap();
+        assert!(result.is_empty());
+        assert_eq!(
+            result.column_names().collect::<Vec<_>>(),
+            ["id", "name", "two"]
+        );
+        assert_eq!(result.columns[0].sql_type.as_deref(), Some("INTEGER"));
+        assert_eq!(result.columns[1].sql_type.as_deref(), Some("VARCHAR(40)"));
+        assert_eq!(result.columns[2].sql_type, None);
+    }
+
     #[test]
     fn test_parameterized_query() {
         let conn = SqliteConnection::open_memory().unwrap();
@@ -1415,6 +2036,92 @@ mod tests {
         assert_eq!(rows[0].get_named::<i32>("age").unwrap(), 30);
     }
 
+    #[test]
+    fn test_query_with_timeout_interrupts_statement() {
+        let rt = asupersync::runtime::RuntimeBuilder::current_thread()
+            .build()
+            .unwrap();
+        let cx = Cx::for_testing();
+        let conn = SqliteConnection::open_memory().unwrap();
+        let options = QueryOptions::new()
+            .timeout(Duration::from_millis(50))
+            .tag("endless");
+
+        rt.block_on(async {
+            let outcome = conn
+                .query_with(
+                    &cx,
+                    "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c) \
+                     SELECT count(*) FROM c",
+                    &[],
+                    &options,
+                )
+                .await;
+            assert!(matches!(outcome, Outcome::Err(Error::Timeout)));
+
+            let outcome = conn.query_with(&cx, "SELECT 1 AS n", &[], &options).await;
+            let Outcome::Ok(rows) = outcome else {
+                panic!("query failed");
+            };
+            assert_eq!(rows[0].get_named::<i64>("n").unwrap(), 1);
+        });
+    }
+
+    #[test]
+    fn test_borrowed_params() {
+        let rt = asupersync::runtime::RuntimeBuilder::current_thread()
+            .build()
+            .unwrap();
+        let cx = Cx::for_testing();
+        let conn = SqliteConnection::open_memory().unwrap();
+        conn.execute_raw("CREATE TABLE docs (id INTEGER PRIMARY KEY, body TEXT, data BLOB)")
+            .unwrap();
+        let body = "x".repeat(64 * 1024);
+        let data = vec![7_u8; 1024];
+
+        rt.block_on(async {
+            let inserted = conn
+                .execute_params(
+                    &cx,
+                    "INSERT INTO docs (id, body, data) VALUES (?, ?, ?)",
+                    &[
+                        ValueRef::from(1_i64),
+                        ValueRef::from(&body),
+                        ValueRef::from(&data),
+                    ],
+                )
+                .await;
+            assert!(matches!(inserted, Outcome::Ok(1)));
+
+            let Outcome::Ok(rows) = conn
+                .query_params(
+                    &cx,
+                    "SELECT body, data FROM docs WHERE body = ?",
+                    &[ValueRef::from(body.as_str())],
+                )
+                .await
+            else {
+                panic!("borrowed query failed");
+            };
+            assert_eq!(rows.len(), 1);
+            assert_eq!(rows[0].get_named::<String>("body").unwrap(), body);
+            assert_eq!(rows[0].get_named::<Vec<u8>>("data").unwrap(), data);
+
+            // Owned parameter lists are accepted too
+            let Outcome::Ok(rows) = conn
+                .query_params(
+                    &cx,
+                    "SELECT id FROM docs WHERE id = ?",
+                    &vec![Value::Int(1)],
+                )
+                .await
+            else {
+                panic!("owned query failed");
+            };
+            assert_eq!(rows.len(), 1);
+        });
+    }
+
     #[test]
     fn test_null_handling() {
         let conn = SqliteConnection::open_memory().unwrap();
@@ -1562,6 +2269,133 @@ mod tests {
         let _ = std::fs::remove_file(&tmp);
     }
 
+    fn seeded(rows: i64) -> SqliteConnection {
+        let conn = SqliteConnection::open_memory().unwrap();
+        conn.execute_raw("CREATE TABLE items (id INTEGER PRIMARY KEY, payload TEXT)")
+            .unwrap();
+        for id in 0..rows {
+            conn.execute_sync(
+                "INSERT INTO items VALUES (?, ?)",
+                &[Value::BigInt(id), Value::Text("x".repeat(512))],
+            )
+            .unwrap();
+        }
+        conn
+    }
+
+    fn item_count(conn: &SqliteConnection) -> i64 {
+        let rows = conn.query_sync("SELECT COUNT(*) FROM items", &[]).unwrap();
+        rows[0].get_as(0).unwrap()
+    }
+
+    #[test]
+    fn test_backup_to_reports_progress() {
+        let tmp = std::env::temp_dir().join("sqlmodel_backup_to.db");
+        let _ = std::fs::remove_file(&tmp);
+        let conn = seeded(50);
+
+        let mut reports = Vec::new();
+        conn.backup_to(tmp.to_string_lossy(), |p| reports.push(p))
+            .unwrap();
+        let last = reports.last().unwrap();
+        assert_eq!(last.remaining, 0);
+        assert_eq!(last.copied(), last.page_count);
+
+        let copy = SqliteConnection::open_file(tmp.to_string_lossy().to_string()).unwrap();
+        assert_eq!(item_count(&copy), 50);
+        drop(copy);
+        let _ = std::fs::remove_file(&tmp);
+    }
+
+    #[test]
+    fn test_incremental_backup_steps_through_pages() {
+        let source = seeded(200);
+        let dest = SqliteConnection::open_memory().unwrap();
+
+        let mut reports = Vec::new();
+        source
+            .backup_to_connection_with(&dest, &BackupOptions::incremental(2, Duration::ZERO), |p| {
+                reports.push(p);
+            })
+            .unwrap();
+        assert!(reports.len() > 2, "{reports:?}");
+        assert!(reports.windows(2).all(|w| w[0].remaining >= w[1].remaining));
+        assert_eq!(reports.last().unwrap().remaining, 0);
+        assert_eq!(item_count(&dest), 200);
+
+        assert!(source.backup_to_connection(&source).is_err());
+    }
+
+    #[test]
+    fn test_vacuum_into_writes_compacted_copy() {
+        let tmp = std::env::temp_dir().join("sqlmodel_vacuum_it's.db");
+        let _ = std::fs::remove_file(&tmp);
+        let conn = seeded(20);
+
+        conn.vacuum_into(tmp.to_string_lossy()).unwrap();
+        let copy = SqliteConnection::open_file(tmp.to_string_lossy().to_string()).unwrap();
+        assert_eq!(item_count(&copy), 20);
+        drop(copy);
+
+        // VACUUM INTO refuses to overwrite an existing database.
+        assert!(conn.vacuum_into(tmp.to_string_lossy()).is_err());
+        let _ = std::fs::remove_file(&tmp);
+    }
+
+    #[cfg(feature = "load-extension")]
+    #[test]
+    fn test_load_extension_reports_failures() {
+        let cx = Cx::for_testing();
+        let conn = SqliteConnection::open_memory().unwrap();
+
+        // SAFETY: the library does not exist, so no code is loaded.
+        let outcome = unsafe { conn.load_extension(&cx, "/nonexistent/ext", None) };
+        let Outcome::Err(e) = outcome else {
+            panic!("expected loading a missing extension to fail");
+        };
+        assert!(e.to_string().contains("/nonexistent/ext"), "{e}");
+
+        // Loading stays disabled for SQL between calls.
+        assert!(
+            conn.query_sync("SELECT load_extension('/nonexistent/ext')", &[])
+                .is_err()
+        );
+
+        // SAFETY: as above.
+        let config = unsafe {
+            SqliteConfig::memory()
+                .extensions([SqliteExtension::new("/nonexistent/ext").entry_point("init")])
+        };
+        assert_eq!(config.extensions[0].entry_point.as_deref(), Some("init"));
+        assert!(SqliteConnection::open(&config).is_err());
+    }
+
+    #[test]
+    fn test_attach_and_query_across_databases() {
+        let conn = seeded(3);
+        conn.attach(":memory:", "archive").unwrap();
+        conn.execute_raw(
+            "CREATE TABLE archive.items (id INTEGER PRIMARY KEY, payload TEXT);
+             INSERT INTO archive.items VALUES (10, 'old'), (1, 'old');",
+        )
+        .unwrap();
+
+        let names: Vec<String> = conn.databases().unwrap().into_iter().map(|d| d.0).collect();
+        assert_eq!(names, vec!["main", "archive"]);
+
+        let rows = conn
+            .query_sync(
+                "SELECT COUNT(*) FROM items JOIN archive.items AS old ON old.id = items.id",
+                &[],
+            )
+            .unwrap();
+        assert_eq!(rows[0].get_as::<i64>(0).unwrap(), 1);
+
+        conn.detach("archive").unwrap();
+        assert_eq!(conn.databases().unwrap().len(), 1);
+        assert!(conn.query_sync("SELECT * FROM archive.items", &[]).is_err());
+    }
+
     // ==================== Console Integration Tests ====================
 
     #[cfg(feature = "console")]
@@ -27,6 +27,18 @@ pub struct sqlite3_backup {
     _private: [u8; 0],
 }
 
+/// Opaque sqlite3_context handle passed to user-defined functions.
+#[repr(C)]
+pub struct sqlite3_context {
+    _private: [u8; 0],
+}
+
+/// Opaque sqlite3_value handle for user-defined function arguments.
+#[repr(C)]
+pub struct sqlite3_value {
+    _private: [u8; 0],
+}
+
 // SQLite result codes
 pub const SQLITE_OK: c_int = 0;
 pub const SQLITE_ERROR: c_int = 1;
@@ -78,6 +90,20 @@ pub const SQLITE_TEXT: c_int = 3;
 pub const SQLITE_BLOB: c_int = 4;
 pub const SQLITE_NULL: c_int = 5;
 
+// Database configuration options
+pub const SQLITE_DBCONFIG_ENABLE_LOAD_EXTENSION: c_int = 1005;
+
+// Text encodings and function flags
+pub const SQLITE_UTF8: c_int = 1;
+pub const SQLITE_DETERMINISTIC: c_int = 0x000000800;
+
+// Callback types for user-defined functions and collations
+pub type sqlite3_function_type =
+    Option<unsafe extern "C" fn(*mut sqlite3_context, c_int, *mut *mut sqlite3_value)>;
+pub type sqlite3_final_type = Option<unsafe extern "C" fn(*mut sqlite3_context)>;
+pub type sqlite3_compare_type =
+    Option<unsafe extern "C" fn(*mut c_void, c_int, *const c_void, c_int, *const c_void) -> c_int>;
+
 // Type alias for destructor callback
 pub type sqlite3_destructor_type = Option<unsafe extern "C" fn(*mut c_void)>;
 
@@ -206,12 +232,82 @@ unsafe extern "C" {
     pub fn sqlite3_total_changes(db: *mut sqlite3) -> c_int;
     pub fn sqlite3_last_insert_rowid(db: *mut sqlite3) -> i64;
 
+    // User-defined functions
+    pub fn sqlite3_create_function_v2(
+        db: *mut sqlite3,
+        zFunctionName: *const c_char,
+        nArg: c_int,
+        eTextRep: c_int,
+        pApp: *mut c_void,
+        xFunc: sqlite3_function_type,
+        xStep: sqlite3_function_type,
+        xFinal: sqlite3_final_type,
+        xDestroy: sqlite3_destructor_type,
+    ) -> c_int;
+    pub fn sqlite3_user_data(ctx: *mut sqlite3_context) -> *mut c_void;
+
+    // Function argument values
+    pub fn sqlite3_value_type(value: *mut sqlite3_value) -> c_int;
+    pub fn sqlite3_value_int64(value: *mut sqlite3_value) -> i64;
+    pub fn sqlite3_value_double(value: *mut sqlite3_value) -> c_double;
+    pub fn sqlite3_value_text(value: *mut sqlite3_value) -> *const c_char;
+    pub fn sqlite3_value_blob(value: *mut sqlite3_value) -> *const c_void;
+    pub fn sqlite3_value_bytes(value: *mut sqlite3_value) -> c_int;
+
+    // Function results
+    pub fn sqlite3_result_null(ctx: *mut sqlite3_context);
+    pub fn sqlite3_result_int64(ctx: *mut sqlite3_context, value: i64);
+    pub fn sqlite3_result_double(ctx: *mut sqlite3_context, value: c_double);
+    pub fn sqlite3_result_text(
+        ctx: *mut sqlite3_context,
+        value: *const c_char,
+        len: c_int,
+        destructor: sqlite3_destructor_type,
+    );
+    pub fn sqlite3_result_blob(
+        ctx: *mut sqlite3_context,
+        value: *const c_void,
+        len: c_int,
+        destructor: sqlite3_destructor_type,
+    );
+    pub fn sqlite3_result_error(ctx: *mut sqlite3_context, msg: *const c_char, len: c_int);
+
+    // Collations
+    pub fn sqlite3_create_collation_v2(
+        db: *mut sqlite3,
+        zName: *const c_char,
+        eTextRep: c_int,
+        pArg: *mut c_void,
+        xCompare: sqlite3_compare_type,
+        xDestroy: sqlite3_destructor_type,
+    ) -> c_int;
+
+    // Interruption
+    pub fn sqlite3_interrupt(db: *mut sqlite3);
+    pub fn sqlite3_progress_handler(
+        db: *mut sqlite3,
+        nOps: c_int,
+        xProgress: Option<unsafe extern "C" fn(*mut c_void) -> c_int>,
+        pArg: *mut c_void,
+    );
+
     // Configuration
     pub fn sqlite3_busy_timeout(db: *mut sqlite3, ms: c_int) -> c_int;
+    pub fn sqlite3_db_config(db: *mut sqlite3, op: c_int, ...) -> c_int;
+
+    // Extensions
+    #[cfg(feature = "load-extension")]
+    pub fn sqlite3_load_extension(
+        db: *mut sqlite3,
+        zFile: *const c_char,
+        zProc: *const c_char,
+        pzErrMsg: *mut *mut c_char,
+    ) -> c_int;
 
     // Version info
     pub fn sqlite3_libversion() -> *const c_char;
     pub fn sqlite3_libversion_number() -> c_int;
+    pub fn sqlite3_compileoption_used(zOptName: *const c_char) -> c_int;
 }
 
 /// Get the SQLite library version as a string.
@@ -229,6 +325,18 @@ pub fn version_number() -> i32 {
     unsafe { sqlite3_libversion_number() }
 }
 
+/// Check whether the SQLite library was built with a compile-time option.
+///
+/// The `SQLITE_` prefix is optional, so `compile_option_used("ENABLE_FTS5")`
+/// reports whether full-text search is built in.
+pub fn compile_option_used(name: &str) -> bool {
+    let Ok(name) = std::ffi::CString::new(name) else {
+        return false;
+    };
+    // SAFETY: name is a valid nul-terminated string
+    unsafe { sqlite3_compileoption_used(name.as_ptr()) != 0 }
+}
+
 /// Convert an SQLite result code to a human-readable string.
 pub fn error_string(code: c_int) -> &'static str {
     // SAFETY: sqlite3_errstr returns a static string
@@ -268,6 +376,13 @@ mod tests {
         assert_eq!(error_string(SQLITE_CONSTRAINT), "constraint failed");
     }
 
+    #[test]
+    fn test_compile_option_used() {
+        assert!(compile_option_used("THREADSAFE"));
+        assert!(!compile_option_used("NOT_A_REAL_OPTION"));
+        assert!(!compile_option_used("BAD\0NAME"));
+    }
+
     #[test]
     fn test_result_codes() {
         // Verify result code constants match expected values
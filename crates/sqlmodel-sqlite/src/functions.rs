@@ -0,0 +1,439 @@
+//! User-defined SQL functions and collations.
+//!
+//! Rust closures registered here run inside SQLite while it evaluates a
+//! query, so normalization or custom ordering can live in `WHERE`,
+//! `ORDER BY`, indexes and views instead of being applied after the fact.
+//!
+//! SQLite owns each registered closure: it is dropped when the name is
+//! registered again, removed, or when the connection closes, so nothing
+//! the closure captures can outlive the connection or be freed early.
+//!
+//! ```rust,ignore
+//! use sqlmodel_core::Value;
+//! use sqlmodel_sqlite::SqliteConnection;
+//!
+//! let conn = SqliteConnection::open_memory()?;
+//! conn.create_deterministic_function("normalize", |args| match &args[0] {
+//!     Value::Text(s) => Ok(Value::Text(s.trim().to_lowercase())),
+//!     other => Ok(other.clone()),
+//! })?;
+//! conn.create_collation("natural", |a, b| natural_cmp(a, b))?;
+//!
+//! conn.query_sync(
+//!     "SELECT name FROM heroes WHERE normalize(name) = ? ORDER BY name COLLATE natural",
+//!     &[Value::Text("thor".into())],
+//! )?;
+//! ```
+
+// Allow casts in FFI code where we need to match C types exactly
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_sign_loss)]
+#![allow(clippy::result_large_err)] // Error type is defined in sqlmodel-core
+
+use crate::connection::SqliteConnection;
+use crate::ffi;
+use crate::types;
+use sqlmodel_core::error::{QueryError, QueryErrorKind};
+use sqlmodel_core::{Error, Value};
+use std::cmp::Ordering;
+use std::ffi::{CStr, CString, c_int, c_void};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::ptr;
+
+/// Boxed scalar function stored as SQLite user data.
+type ScalarFn = Box<dyn Fn(&[Value]) -> Result<Value, Error> + Send>;
+
+/// Boxed collation stored as SQLite user data.
+type CollationFn = Box<dyn Fn(&str, &str) -> Ordering + Send>;
+
+impl SqliteConnection {
+    /// Register a scalar SQL function implemented by `f`.
+    ///
+    /// The function accepts any number of arguments; check `args.len()`
+    /// inside `f`. Returning `Err` fails the statement with the error's
+    /// message, and a panic inside `f` is reported the same way.
+    ///
+    /// Registering an existing name replaces the previous function.
+    pub fn create_scalar_function<F>(&self, name: &str, f: F) -> Result<(), Error>
+    where
+        F: Fn(&[Value]) -> Result<Value, Error> + Send + 'static,
+    {
+        self.register_function(name, ffi::SQLITE_UTF8, Box::new(f))
+    }
+
+    /// Like [`create_scalar_function`](Self::create_scalar_function), but
+    /// promises that `f` always returns the same result for the same
+    /// arguments.
+    ///
+    /// SQLite may then evaluate constant calls once per statement, and the
+    /// function can be used in indexes, `CHECK` constraints and generated
+    /// columns.
+    pub fn create_deterministic_function<F>(&self, name: &str, f: F) -> Result<(), Error>
+    where
+        F: Fn(&[Value]) -> Result<Value, Error> + Send + 'static,
+    {
+        self.register_function(
+            name,
+            ffi::SQLITE_UTF8 | ffi::SQLITE_DETERMINISTIC,
+            Box::new(f),
+        )
+    }
+
+    /// Remove a function registered with
+    /// [`create_scalar_function`](Self::create_scalar_function).
+    pub fn remove_function(&self, name: &str) -> Result<(), Error> {
+        let c_name = c_name(name)?;
+        self.with_db(|db| {
+            // SAFETY: db is valid while the lock is held; null callbacks delete
+            // the function and SQLite drops the old closure.
+            let rc = unsafe {
+                ffi::sqlite3_create_function_v2(
+                    db,
+                    c_name.as_ptr(),
+                    -1,
+                    ffi::SQLITE_UTF8,
+                    ptr::null_mut(),
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+            };
+            check(db, rc, "remove function", name)
+        })
+    }
+
+    /// Register a collating sequence for `COLLATE name` clauses.
+    ///
+    /// `f` compares two text values. A panic inside `f` cannot be reported
+    /// to SQLite, so the values compare as equal instead.
+    ///
+    /// Registering an existing name replaces the previous collation.
+    pub fn create_collation<F>(&self, name: &str, f: F) -> Result<(), Error>
+    where
+        F: Fn(&str, &str) -> Ordering + Send + 'static,
+    {
+        let c_name = c_name(name)?;
+        let data = Box::into_raw(Box::new(Box::new(f) as CollationFn));
+        self.with_db(|db| {
+            // SAFETY: db is valid while the lock is held; `data` is released
+            // by `destroy_collation` once SQLite no longer needs it.
+            let rc = unsafe {
+                ffi::sqlite3_create_collation_v2(
+                    db,
+                    c_name.as_ptr(),
+                    ffi::SQLITE_UTF8,
+                    data.cast(),
+                    Some(compare_trampoline),
+                    Some(destroy_collation),
+                )
+            };
+            if rc != ffi::SQLITE_OK {
+                // SQLite does not call the destructor when registration fails.
+                // SAFETY: `data` came from Box::into_raw above and was not kept.
+                drop(unsafe { Box::from_raw(data) });
+            }
+            check(db, rc, "create collation", name)
+        })
+    }
+
+    /// Remove a collation registered with
+    /// [`create_collation`](Self::create_collation).
+    pub fn remove_collation(&self, name: &str) -> Result<(), Error> {
+        let c_name = c_name(name)?;
+        self.with_db(|db| {
+            // SAFETY: db is valid while the lock is held; a null comparator
+            // deletes the collation and SQLite drops the old closure.
+            let rc = unsafe {
+                ffi::sqlite3_create_collation_v2(
+                    db,
+                    c_name.as_ptr(),
+                    ffi::SQLITE_UTF8,
+                    ptr::null_mut(),
+                    None,
+                    None,
+                )
+            };
+            check(db, rc, "remove collation", name)
+        })
+    }
+
+    fn register_function(&self, name: &str, flags: c_int, f: ScalarFn) -> Result<(), Error> {
+        let c_name = c_name(name)?;
+        let data = Box::into_raw(Box::new(f));
+        self.with_db(|db| {
+            // SAFETY: db is valid while the lock is held. SQLite calls
+            // `destroy_function` on `data` when the function is replaced or
+            // removed, when the connection closes, or if registration fails.
+            let rc = unsafe {
+                ffi::sqlite3_create_function_v2(
+                    db,
+                    c_name.as_ptr(),
+                    -1,
+                    flags,
+                    data.cast(),
+                    Some(function_trampoline),
+                    None,
+                    None,
+                    Some(destroy_function),
+                )
+            };
+            check(db, rc, "create function", name)
+        })
+    }
+}
+
+unsafe extern "C" fn function_trampoline(
+    ctx: *mut ffi::sqlite3_context,
+    n_values: c_int,
+    values: *mut *mut ffi::sqlite3_value,
+) {
+    // SAFETY: user data is the ScalarFn registered in `register_function`,
+    // and `values` holds `n_values` valid handles for the duration of this call.
+    unsafe {
+        let f = &*ffi::sqlite3_user_data(ctx).cast::<ScalarFn>();
+        let args: Vec<Value> = if n_values > 0 {
+            std::slice::from_raw_parts(values, n_values as usize)
+                .iter()
+                .map(|&value| types::read_value(value))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        match catch_unwind(AssertUnwindSafe(|| f(&args))) {
+            Ok(Ok(value)) => types::set_result(ctx, &value),
+            Ok(Err(e)) => set_error(ctx, &e.to_string()),
+            Err(_) => set_error(ctx, "user-defined function panicked"),
+        }
+    }
+}
+
+unsafe extern "C" fn compare_trampoline(
+    data: *mut c_void,
+    left_len: c_int,
+    left: *const c_void,
+    right_len: c_int,
+    right: *const c_void,
+) -> c_int {
+    // SAFETY: data is the CollationFn registered in `create_collation`, and
+    // both strings are valid for the given lengths during this call.
+    unsafe {
+        let f = &*data.cast::<CollationFn>();
+        let left = text(left, left_len);
+        let right = text(right, right_len);
+        match catch_unwind(AssertUnwindSafe(|| f(&left, &right))) {
+            Ok(Ordering::Less) => -1,
+            Ok(Ordering::Greater) => 1,
+            Ok(Ordering::Equal) | Err(_) => 0,
+        }
+    }
+}
+
+unsafe extern "C" fn destroy_function(data: *mut c_void) {
+    // SAFETY: data came from Box::into_raw in `register_function`.
+    drop(unsafe { Box::from_raw(data.cast::<ScalarFn>()) });
+}
+
+unsafe extern "C" fn destroy_collation(data: *mut c_void) {
+    // SAFETY: data came from Box::into_raw in `create_collation`.
+    drop(unsafe { Box::from_raw(data.cast::<CollationFn>()) });
+}
+
+/// Decode collation input, which SQLite does not nul-terminate.
+unsafe fn text<'a>(ptr: *const c_void, len: c_int) -> std::borrow::Cow<'a, str> {
+    if ptr.is_null() || len <= 0 {
+        return std::borrow::Cow::Borrowed("");
+    }
+    // SAFETY: caller guarantees ptr is valid for len bytes.
+    let bytes = unsafe { std::slice::from_raw_parts(ptr.cast::<u8>(), len as usize) };
+    String::from_utf8_lossy(bytes)
+}
+
+unsafe fn set_error(ctx: *mut ffi::sqlite3_context, message: &str) {
+    // SAFETY: ctx is the running function's context; SQLite copies the message.
+    unsafe { ffi::sqlite3_result_error(ctx, message.as_ptr().cast(), message.len() as c_int) };
+}
+
+fn c_name(name: &str) -> Result<CString, Error> {
+    CString::new(name)
+        .map_err(|_| Error::Custom(format!("SQLite name {name:?} contains a null byte")))
+}
+
+fn check(db: *mut ffi::sqlite3, rc: c_int, action: &str, name: &str) -> Result<(), Error> {
+    if rc == ffi::SQLITE_OK {
+        return Ok(());
+    }
+    // SAFETY: db is valid
+    let msg = unsafe { CStr::from_ptr(ffi::sqlite3_errmsg(db)) }
+        .to_string_lossy()
+        .into_owned();
+    Err(Error::Query(QueryError {
+        kind: QueryErrorKind::Database,
+        sql: None,
+        sqlstate: None,
+        message: format!("Failed to {action} {name}: {msg}"),
+        detail: None,
+        hint: None,
+        position: None,
+        source: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+
+    fn texts(conn: &SqliteConnection, sql: &str) -> Vec<String> {
+        conn.query_sync(sql, &[])
+            .unwrap()
+            .iter()
+            .map(|row| match row.get(0) {
+                Some(Value::Text(s)) => s.clone(),
+                other => panic!("expected text, got {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_scalar_function_runs_in_queries() {
+        let conn = SqliteConnection::open_memory().unwrap();
+        conn.create_deterministic_function("normalize", |args| match &args[0] {
+            Value::Text(s) => Ok(Value::Text(s.trim().to_lowercase())),
+            other => Ok(other.clone()),
+        })
+        .unwrap();
+        conn.create_scalar_function("add_all", |args| {
+            let mut total = 0_i64;
+            for arg in args {
+                match arg {
+                    Value::Int(v) => total += i64::from(*v),
+                    Value::BigInt(v) => total += v,
+                    other => return Err(Error::Custom(format!("not a number: {other:?}"))),
+                }
+            }
+            Ok(Value::BigInt(total))
+        })
+        .unwrap();
+
+        conn.execute_raw(
+            "CREATE TABLE heroes (name TEXT);
+             INSERT INTO heroes VALUES ('  Thor '), ('LOKI'), ('Hulk');
+             CREATE INDEX heroes_normalized ON heroes (normalize(name));",
+        )
+        .unwrap();
+        assert_eq!(
+            texts(
+                &conn,
+                "SELECT normalize(name) FROM heroes WHERE normalize(name) <> 'hulk' ORDER BY 1"
+            ),
+            vec!["loki", "thor"]
+        );
+
+        let rows = conn
+            .query_sync(
+                "SELECT add_all(1, 2, ?), normalize(NULL)",
+                &[Value::Int(39)],
+            )
+            .unwrap();
+        assert_eq!(rows[0].get(0), Some(&Value::Int(42)));
+        assert_eq!(rows[0].get(1), Some(&Value::Null));
+    }
+
+    #[test]
+    fn test_scalar_function_errors_and_panics_fail_the_statement() {
+        let conn = SqliteConnection::open_memory().unwrap();
+        conn.create_scalar_function("fail", |_| Err(Error::Custom("nope".to_string())))
+            .unwrap();
+        conn.create_scalar_function("boom", |_| panic!("boom"))
+            .unwrap();
+
+        let err = conn.query_sync("SELECT fail()", &[]).unwrap_err();
+        assert!(err.to_string().contains("nope"), "{err}");
+        let err = conn.query_sync("SELECT boom()", &[]).unwrap_err();
+        assert!(err.to_string().contains("panicked"), "{err}");
+
+        // The connection is still usable afterwards.
+        assert_eq!(texts(&conn, "SELECT 'ok'"), vec!["ok"]);
+    }
+
+    #[test]
+    fn test_collation_orders_and_compares() {
+        let conn = SqliteConnection::open_memory().unwrap();
+        conn.create_collation("by_length", |a, b| a.len().cmp(&b.len()).then(a.cmp(b)))
+            .unwrap();
+        conn.execute_raw(
+            "CREATE TABLE words (w TEXT);
+             INSERT INTO words VALUES ('ccc'), ('a'), ('bb'), ('aa');",
+        )
+        .unwrap();
+
+        assert_eq!(
+            texts(&conn, "SELECT w FROM words ORDER BY w COLLATE by_length"),
+            vec!["a", "aa", "bb", "ccc"]
+        );
+
+        conn.create_collation("nocase_trim", |a, b| {
+            a.trim().to_lowercase().cmp(&b.trim().to_lowercase())
+        })
+        .unwrap();
+        assert_eq!(
+            texts(
+                &conn,
+                "SELECT 'match' WHERE ' Thor ' = 'THOR' COLLATE nocase_trim"
+            ),
+            vec!["match"]
+        );
+    }
+
+    #[test]
+    fn test_closures_are_dropped_when_replaced_removed_or_closed() {
+        struct Tracked(Arc<AtomicUsize>);
+        impl Drop for Tracked {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, AtomicOrdering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let conn = SqliteConnection::open_memory().unwrap();
+        let register = |name: &str| {
+            let tracked = Tracked(Arc::clone(&dropped));
+            conn.create_scalar_function(name, move |_| {
+                let _ = &tracked;
+                Ok(Value::Null)
+            })
+            .unwrap();
+        };
+
+        register("f");
+        register("f");
+        assert_eq!(dropped.load(AtomicOrdering::SeqCst), 1);
+
+        conn.remove_function("f").unwrap();
+        assert_eq!(dropped.load(AtomicOrdering::SeqCst), 2);
+        assert!(conn.query_sync("SELECT f()", &[]).is_err());
+
+        register("g");
+        let tracked = Tracked(Arc::clone(&dropped));
+        conn.create_collation("c", move |a, b| {
+            let _ = &tracked;
+            a.cmp(b)
+        })
+        .unwrap();
+        drop(conn);
+        assert_eq!(dropped.load(AtomicOrdering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_names_with_null_bytes_are_rejected() {
+        let conn = SqliteConnection::open_memory().unwrap();
+        assert!(
+            conn.create_scalar_function("bad\0name", |_| Ok(Value::Null))
+                .is_err()
+        );
+        assert!(conn.create_collation("bad\0name", |a, b| a.cmp(b)).is_err());
+    }
+}
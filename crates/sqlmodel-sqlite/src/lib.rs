@@ -24,6 +24,9 @@
 //! - Type-safe parameter binding
 //! - In-memory and file-based databases
 //! - Configurable open flags and busy timeout
+//! - Online and incremental backups, and `VACUUM INTO` snapshots
+//! - Native extension loading behind the `load-extension` feature
+//! - Rust scalar functions and collations callable from SQL
 //!
 //! # Example
 //!
@@ -69,9 +72,14 @@
 
 pub mod connection;
 pub mod ffi;
+pub mod functions;
 pub mod types;
 
-pub use connection::{OpenFlags, SqliteConfig, SqliteConnection, SqliteTransaction};
+#[cfg(feature = "load-extension")]
+pub use connection::SqliteExtension;
+pub use connection::{
+    BackupOptions, BackupProgress, OpenFlags, SqliteConfig, SqliteConnection, SqliteTransaction,
+};
 
 // Console integration (feature-gated)
 #[cfg(feature = "console")]
@@ -16,7 +16,7 @@
 #![allow(clippy::checked_conversions)]
 
 use crate::ffi;
-use sqlmodel_core::Value;
+use sqlmodel_core::{Value, ValueRef};
 use std::ffi::{CStr, c_int};
 
 /// Bind a Value to a prepared statement parameter.
@@ -25,26 +25,43 @@ use std::ffi::{CStr, c_int};
 /// - `stmt` must be a valid, non-null prepared statement handle
 /// - `index` must be a valid 1-based parameter index
 pub unsafe fn bind_value(stmt: *mut ffi::sqlite3_stmt, index: c_int, value: &Value) -> c_int {
+    // SAFETY: same contract as this function
+    unsafe { bind_value_ref(stmt, index, value.as_value_ref()) }
+}
+
+/// Bind a borrowed value to a prepared statement parameter.
+///
+/// Text and blobs are copied by SQLite itself (`SQLITE_TRANSIENT`), so callers
+/// never need to clone their data into an owned [`Value`].
+///
+/// # Safety
+/// - `stmt` must be a valid, non-null prepared statement handle
+/// - `index` must be a valid 1-based parameter index
+pub unsafe fn bind_value_ref(
+    stmt: *mut ffi::sqlite3_stmt,
+    index: c_int,
+    value: ValueRef<'_>,
+) -> c_int {
     // SAFETY: All FFI calls require unsafe in Rust 2024
     unsafe {
         match value {
-            Value::Null => ffi::sqlite3_bind_null(stmt, index),
+            ValueRef::Null => ffi::sqlite3_bind_null(stmt, index),
 
-            Value::Bool(b) => ffi::sqlite3_bind_int(stmt, index, if *b { 1 } else { 0 }),
+            ValueRef::Bool(b) => ffi::sqlite3_bind_int(stmt, index, if b { 1 } else { 0 }),
 
-            Value::TinyInt(v) => ffi::sqlite3_bind_int(stmt, index, i32::from(*v)),
+            ValueRef::TinyInt(v) => ffi::sqlite3_bind_int(stmt, index, i32::from(v)),
 
-            Value::SmallInt(v) => ffi::sqlite3_bind_int(stmt, index, i32::from(*v)),
+            ValueRef::SmallInt(v) => ffi::sqlite3_bind_int(stmt, index, i32::from(v)),
 
-            Value::Int(v) => ffi::sqlite3_bind_int(stmt, index, *v),
+            ValueRef::Int(v) => ffi::sqlite3_bind_int(stmt, index, v),
 
-            Value::BigInt(v) => ffi::sqlite3_bind_int64(stmt, index, *v),
+            ValueRef::BigInt(v) => ffi::sqlite3_bind_int64(stmt, index, v),
 
-            Value::Float(v) => ffi::sqlite3_bind_double(stmt, index, f64::from(*v)),
+            ValueRef::Float(v) => ffi::sqlite3_bind_double(stmt, index, f64::from(v)),
 
-            Value::Double(v) => ffi::sqlite3_bind_double(stmt, index, *v),
+            ValueRef::Double(v) => ffi::sqlite3_bind_double(stmt, index, v),
 
-            Value::Decimal(s) => {
+            ValueRef::Decimal(s) => {
                 let bytes = s.as_bytes();
                 ffi::sqlite3_bind_text(
                     stmt,
@@ -55,7 +72,7 @@ pub unsafe fn bind_value(stmt: *mut ffi::sqlite3_stmt, index: c_int, value: &Val
                 )
             }
 
-            Value::Text(s) => {
+            ValueRef::Text(s) => {
                 let bytes = s.as_bytes();
                 ffi::sqlite3_bind_text(
                     stmt,
@@ -66,7 +83,7 @@ pub unsafe fn bind_value(stmt: *mut ffi::sqlite3_stmt, index: c_int, value: &Val
                 )
             }
 
-            Value::Bytes(b) => ffi::sqlite3_bind_blob(
+            ValueRef::Bytes(b) => ffi::sqlite3_bind_blob(
                 stmt,
                 index,
                 b.as_ptr().cast(),
@@ -75,8 +92,8 @@ pub unsafe fn bind_value(stmt: *mut ffi::sqlite3_stmt, index: c_int, value: &Val
             ),
 
             // Date stored as ISO-8601 text (YYYY-MM-DD)
-            Value::Date(days) => {
-                let date = days_to_date(*days);
+            ValueRef::Date(days) => {
+                let date = days_to_date(days);
                 let bytes = date.as_bytes();
                 ffi::sqlite3_bind_text(
                     stmt,
@@ -88,8 +105,8 @@ pub unsafe fn bind_value(stmt: *mut ffi::sqlite3_stmt, index: c_int, value: &Val
             }
 
             // Time stored as ISO-8601 text (HH:MM:SS.sss)
-            Value::Time(micros) => {
-                let time = micros_to_time(*micros);
+            ValueRef::Time(micros) => {
+                let time = micros_to_time(micros);
                 let bytes = time.as_bytes();
                 ffi::sqlite3_bind_text(
                     stmt,
@@ -101,8 +118,8 @@ pub unsafe fn bind_value(stmt: *mut ffi::sqlite3_stmt, index: c_int, value: &Val
             }
 
             // Timestamp stored as ISO-8601 text
-            Value::Timestamp(micros) | Value::TimestampTz(micros) => {
-                let ts = micros_to_timestamp(*micros);
+            ValueRef::Timestamp(micros) | ValueRef::TimestampTz(micros) => {
+                let ts = micros_to_timestamp(micros);
                 let bytes = ts.as_bytes();
                 ffi::sqlite3_bind_text(
                     stmt,
@@ -114,7 +131,7 @@ pub unsafe fn bind_value(stmt: *mut ffi::sqlite3_stmt, index: c_int, value: &Val
             }
 
             // UUID stored as 16-byte blob
-            Value::Uuid(bytes) => ffi::sqlite3_bind_blob(
+            ValueRef::Uuid(bytes) => ffi::sqlite3_bind_blob(
                 stmt,
                 index,
                 bytes.as_ptr().cast(),
@@ -123,7 +140,7 @@ pub unsafe fn bind_value(stmt: *mut ffi::sqlite3_stmt, index: c_int, value: &Val
             ),
 
             // JSON stored as text
-            Value::Json(json) => {
+            ValueRef::Json(json) => {
                 let s = json.to_string();
                 let bytes = s.as_bytes();
                 ffi::sqlite3_bind_text(
@@ -136,7 +153,7 @@ pub unsafe fn bind_value(stmt: *mut ffi::sqlite3_stmt, index: c_int, value: &Val
             }
 
             // Arrays stored as JSON text
-            Value::Array(arr) => {
+            ValueRef::Array(arr) => {
                 let json = serde_json::Value::Array(arr.iter().map(value_to_json).collect());
                 let s = json.to_string();
                 let bytes = s.as_bytes();
@@ -151,7 +168,7 @@ pub unsafe fn bind_value(stmt: *mut ffi::sqlite3_stmt, index: c_int, value: &Val
 
             // Default should never reach bind_value - query builder puts "DEFAULT"
             // directly in SQL text. Bind NULL as defensive fallback.
-            Value::Default => ffi::sqlite3_bind_null(stmt, index),
+            ValueRef::Default => ffi::sqlite3_bind_null(stmt, index),
         }
     }
 }
@@ -212,6 +229,104 @@ pub unsafe fn read_column(stmt: *mut ffi::sqlite3_stmt, index: c_int) -> Value {
     }
 }
 
+/// Read a user-defined function argument.
+///
+/// # Safety
+/// - `value` must be a valid argument handle passed to the running function
+pub unsafe fn read_value(value: *mut ffi::sqlite3_value) -> Value {
+    // SAFETY: All FFI calls require unsafe in Rust 2024
+    unsafe {
+        match ffi::sqlite3_value_type(value) {
+            ffi::SQLITE_INTEGER => {
+                let v = ffi::sqlite3_value_int64(value);
+                if v >= i32::MIN as i64 && v <= i32::MAX as i64 {
+                    Value::Int(v as i32)
+                } else {
+                    Value::BigInt(v)
+                }
+            }
+
+            ffi::SQLITE_FLOAT => Value::Double(ffi::sqlite3_value_double(value)),
+
+            ffi::SQLITE_TEXT => {
+                let ptr = ffi::sqlite3_value_text(value);
+                let len = ffi::sqlite3_value_bytes(value);
+                if ptr.is_null() {
+                    Value::Null
+                } else {
+                    let slice = std::slice::from_raw_parts(ptr.cast::<u8>(), len as usize);
+                    Value::Text(String::from_utf8_lossy(slice).into_owned())
+                }
+            }
+
+            ffi::SQLITE_BLOB => {
+                let ptr = ffi::sqlite3_value_blob(value);
+                let len = ffi::sqlite3_value_bytes(value);
+                if ptr.is_null() || len == 0 {
+                    Value::Bytes(Vec::new())
+                } else {
+                    let slice = std::slice::from_raw_parts(ptr.cast::<u8>(), len as usize);
+                    Value::Bytes(slice.to_vec())
+                }
+            }
+
+            _ => Value::Null,
+        }
+    }
+}
+
+/// Set the result of a user-defined function.
+///
+/// Values are stored the same way [`bind_value`] stores parameters.
+///
+/// # Safety
+/// - `ctx` must be the context handle of the running function
+pub unsafe fn set_result(ctx: *mut ffi::sqlite3_context, value: &Value) {
+    // SAFETY: All FFI calls require unsafe in Rust 2024
+    unsafe {
+        let text = match value {
+            Value::Null | Value::Default => return ffi::sqlite3_result_null(ctx),
+            Value::Bool(b) => return ffi::sqlite3_result_int64(ctx, i64::from(*b)),
+            Value::TinyInt(v) => return ffi::sqlite3_result_int64(ctx, i64::from(*v)),
+            Value::SmallInt(v) => return ffi::sqlite3_result_int64(ctx, i64::from(*v)),
+            Value::Int(v) => return ffi::sqlite3_result_int64(ctx, i64::from(*v)),
+            Value::BigInt(v) => return ffi::sqlite3_result_int64(ctx, *v),
+            Value::Float(v) => return ffi::sqlite3_result_double(ctx, f64::from(*v)),
+            Value::Double(v) => return ffi::sqlite3_result_double(ctx, *v),
+            Value::Bytes(b) => {
+                return ffi::sqlite3_result_blob(
+                    ctx,
+                    b.as_ptr().cast(),
+                    b.len() as c_int,
+                    ffi::sqlite_transient(),
+                );
+            }
+            Value::Uuid(bytes) => {
+                return ffi::sqlite3_result_blob(
+                    ctx,
+                    bytes.as_ptr().cast(),
+                    16,
+                    ffi::sqlite_transient(),
+                );
+            }
+            Value::Decimal(s) | Value::Text(s) => s.clone(),
+            Value::Date(days) => days_to_date(*days),
+            Value::Time(micros) => micros_to_time(*micros),
+            Value::Timestamp(micros) | Value::TimestampTz(micros) => micros_to_timestamp(*micros),
+            Value::Json(json) => json.to_string(),
+            Value::Array(arr) => {
+                serde_json::Value::Array(arr.iter().map(value_to_json).collect()).to_string()
+            }
+        };
+        ffi::sqlite3_result_text(
+            ctx,
+            text.as_ptr().cast(),
+            text.len() as c_int,
+            ffi::sqlite_transient(),
+        );
+    }
+}
+
 /// Get the column name from a result.
 ///
 /// # Safety
@@ -229,6 +344,25 @@ pub unsafe fn column_name(stmt: *mut ffi::sqlite3_stmt, index: c_int) -> Option<
     }
 }
 
+/// Get the declared type of a result column (e.g. `INTEGER`, `VARCHAR(40)`).
+///
+/// Returns `None` for expressions and other columns not taken directly from a table.
+///
+/// # Safety
+/// - `stmt` must be a valid prepared statement
+/// - `index` must be a valid 0-based column index
+pub unsafe fn column_decltype(stmt: *mut ffi::sqlite3_stmt, index: c_int) -> Option<String> {
+    // SAFETY: All FFI calls require unsafe in Rust 2024
+    unsafe {
+        let ptr = ffi::sqlite3_column_decltype(stmt, index);
+        if ptr.is_null() {
+            None
+        } else {
+            CStr::from_ptr(ptr).to_str().ok().map(String::from)
+        }
+    }
+}
+
 /// Convert days since Unix epoch to ISO-8601 date string.
 fn days_to_date(days: i32) -> String {
     // Simple calculation - for a proper implementation, use a date library
@@ -0,0 +1,625 @@
+//! Deterministic fake data for models.
+//!
+//! A [`Factory`] fills every required column of a model with a fake value
+//! derived from [`Model::fields`], the column name, and a sequence number, so
+//! the same factory always produces the same rows. Overrides registered with
+//! [`Factory::with`] run on each instance before it is inserted.
+//!
+//! Foreign keys are satisfied by registering a factory for the referenced
+//! table with [`Factory::parent`]: one parent row is inserted before each
+//! [`create`](Factory::create) / [`create_batch`](Factory::create_batch) call
+//! and its key is written into every child. Parents may have parents of their
+//! own; each table is inserted once per call, dependencies first.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let heroes = Factory::<Hero>::new()
+//!     .parent(Factory::<Team>::new())
+//!     .with(|h| h.age = Some(30))
+//!     .create_batch(cx, &mut session, 50)
+//!     .await?;
+//! ```
+//!
+//! Nullable columns stay NULL unless an override sets them, so a nullable
+//! auto-increment primary key is generated by the database.
+
+#![allow(clippy::result_large_err)] // Error type is defined in sqlmodel-core
+
+use std::collections::HashMap;
+
+use sqlmodel_core::{
+    Connection, Cx, Dialect, Error, FieldInfo, Model, Outcome, Row, SqlType, Value,
+};
+use sqlmodel_session::Session;
+
+/// 2024-01-01, the first fake date, in days since the Unix epoch.
+const FAKE_EPOCH_DAYS: i32 = 19_723;
+
+/// 2024-01-01T00:00:00Z, the first fake timestamp, in seconds since the Unix epoch.
+const FAKE_EPOCH_SECS: i64 = 1_704_067_200;
+
+/// Closure applied to each built instance.
+type Override<M> = Box<dyn Fn(&mut M) + Send + Sync>;
+
+/// Builds and inserts fake instances of `M`.
+///
+/// Sequence numbers start at 1 and advance with every instance created, so
+/// values in text and integer columns stay unique across calls on the same
+/// factory.
+pub struct Factory<M: Model> {
+    overrides: Vec<Override<M>>,
+    parents: Vec<Box<dyn ParentFactory>>,
+    next: u64,
+}
+
+impl<M: Model + 'static> Factory<M> {
+    /// A factory with no overrides or parents.
+    pub fn new() -> Self {
+        Self {
+            overrides: Vec::new(),
+            parents: Vec::new(),
+            next: 1,
+        }
+    }
+
+    /// Modify every instance after the fake values are filled in.
+    ///
+    /// Overrides run in registration order.
+    #[must_use]
+    pub fn with(mut self, f: impl Fn(&mut M) + Send + Sync + 'static) -> Self {
+        self.overrides.push(Box::new(f));
+        self
+    }
+
+    /// Create rows for the table `P` belongs to before creating `M`.
+    ///
+    /// Columns of `M` with a foreign key into that table get the parent's
+    /// referenced value, nullable or not.
+    #[must_use]
+    pub fn parent<P: Model + 'static>(mut self, factory: Factory<P>) -> Self {
+        self.parents.push(Box::new(factory));
+        self
+    }
+
+    /// Start the sequence at `seq` instead of 1.
+    ///
+    /// Useful when seed rows made by another factory already use the low
+    /// numbers of a unique column.
+    #[must_use]
+    pub fn sequence_start(mut self, seq: u64) -> Self {
+        self.next = seq;
+        self
+    }
+
+    /// Build the next instance without inserting it.
+    ///
+    /// Foreign keys get fake values like any other column.
+    pub fn build(&mut self) -> Result<M, Error> {
+        let seq = self.advance(1);
+        self.build_at(seq, &HashMap::new())
+    }
+
+    /// Insert one instance (and its parents) through `session`'s connection.
+    ///
+    /// The returned model carries database-generated values such as its
+    /// primary key. It is not tracked by the session.
+    pub async fn create<C: Connection>(
+        &mut self,
+        cx: &Cx,
+        session: &mut Session<C>,
+    ) -> Outcome<M, Error> {
+        match self.create_batch(cx, session, 1).await {
+            Outcome::Ok(mut created) => match created.pop() {
+                Some(model) => Outcome::Ok(model),
+                None => Outcome::Err(Error::Custom(format!(
+                    "factory for {} created no rows",
+                    M::TABLE_NAME
+                ))),
+            },
+            Outcome::Err(e) => Outcome::Err(e),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
+    /// Insert `count` instances sharing one set of parents.
+    pub async fn create_batch<C: Connection>(
+        &mut self,
+        cx: &Cx,
+        session: &mut Session<C>,
+        count: usize,
+    ) -> Outcome<Vec<M>, Error> {
+        let first = self.advance(count);
+        self.insert_rows(cx, session.connection(), first, count)
+            .await
+    }
+
+    /// The next sequence number.
+    pub(crate) fn sequence(&self) -> u64 {
+        self.next
+    }
+
+    /// Reserve `count` sequence numbers, returning the first.
+    fn advance(&mut self, count: usize) -> u64 {
+        let first = self.next;
+        self.next += count as u64;
+        first
+    }
+
+    /// Build the instance for `seq`, taking foreign keys from `parents`.
+    fn build_at(&self, seq: u64, parents: &HashMap<&'static str, Row>) -> Result<M, Error> {
+        let mut model = M::from_row(&fake_row(M::TABLE_NAME, M::fields(), seq, parents))?;
+        for f in &self.overrides {
+            f(&mut model);
+        }
+        Ok(model)
+    }
+
+    /// Insert parents, then `count` instances numbered from `first`.
+    pub(crate) async fn insert_rows<C: Connection>(
+        &self,
+        cx: &Cx,
+        conn: &C,
+        first: u64,
+        count: usize,
+    ) -> Outcome<Vec<M>, Error> {
+        let mut order = Vec::new();
+        dependency_order(&self.parents, &mut order);
+
+        // Parents share the first child's sequence number, so repeated calls
+        // never reuse a parent's unique values.
+        let mut inserted: HashMap<&'static str, Row> = HashMap::new();
+        for parent in order {
+            let row = match parent.build_row(first, &inserted) {
+                Ok(row) => row,
+                Err(e) => return Outcome::Err(e),
+            };
+            match insert_row(cx, conn, parent.table(), parent.fields(), row).await {
+                Outcome::Ok(row) => {
+                    inserted.insert(parent.table(), row);
+                }
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            }
+        }
+
+        let mut created = Vec::with_capacity(count);
+        for seq in first..first + count as u64 {
+            let model = match self.build_at(seq, &inserted) {
+                Ok(model) => model,
+                Err(e) => return Outcome::Err(e),
+            };
+            let row = match insert_row(cx, conn, M::TABLE_NAME, M::fields(), model.to_row()).await {
+                Outcome::Ok(row) => row,
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            };
+            match M::from_row(&row) {
+                Ok(model) => created.push(model),
+                Err(e) => return Outcome::Err(e),
+            }
+        }
+        Outcome::Ok(created)
+    }
+}
+
+impl<M: Model + 'static> Default for Factory<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M: Model> std::fmt::Debug for Factory<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Factory")
+            .field("table", &M::TABLE_NAME)
+            .field("overrides", &self.overrides.len())
+            .field(
+                "parents",
+                &self.parents.iter().map(|p| p.table()).collect::<Vec<_>>(),
+            )
+            .field("next", &self.next)
+            .finish()
+    }
+}
+
+/// A parent factory with its model type erased.
+trait ParentFactory: Send + Sync {
+    fn table(&self) -> &'static str;
+    fn fields(&self) -> &'static [FieldInfo];
+    fn parents(&self) -> &[Box<dyn ParentFactory>];
+    fn build_row(
+        &self,
+        seq: u64,
+        parents: &HashMap<&'static str, Row>,
+    ) -> Result<Vec<(&'static str, Value)>, Error>;
+}
+
+impl<M: Model + 'static> ParentFactory for Factory<M> {
+    fn table(&self) -> &'static str {
+        M::TABLE_NAME
+    }
+
+    fn fields(&self) -> &'static [FieldInfo] {
+        M::fields()
+    }
+
+    fn parents(&self) -> &[Box<dyn ParentFactory>] {
+        &self.parents
+    }
+
+    fn build_row(
+        &self,
+        seq: u64,
+        parents: &HashMap<&'static str, Row>,
+    ) -> Result<Vec<(&'static str, Value)>, Error> {
+        self.build_at(seq, parents).map(|model| model.to_row())
+    }
+}
+
+/// Flatten a parent tree so every table comes after the tables it references.
+fn dependency_order<'a>(
+    parents: &'a [Box<dyn ParentFactory>],
+    out: &mut Vec<&'a dyn ParentFactory>,
+) {
+    for parent in parents {
+        dependency_order(parent.parents(), out);
+        if !out.iter().any(|p| p.table() == parent.table()) {
+            out.push(parent.as_ref());
+        }
+    }
+}
+
+/// A row with a value for every stored column of `table`.
+///
+/// Columns referencing an inserted parent get the parent's value, other
+/// nullable columns NULL, and everything else [`fake_value`].
+fn fake_row(
+    table: &str,
+    fields: &[FieldInfo],
+    seq: u64,
+    parents: &HashMap<&'static str, Row>,
+) -> Row {
+    let (names, values) = fields
+        .iter()
+        .filter(|f| !f.computed)
+        .map(|field| {
+            let parent_value = field
+                .foreign_key
+                .and_then(|fk| fk.split_once('.'))
+                .and_then(|(parent, column)| parents.get(parent)?.get_by_name(column).cloned());
+            let value = match parent_value {
+                Some(value) => value,
+                None if field.nullable => Value::Null,
+                None => fake_value(table, field, seq),
+            };
+            (field.column_name.to_string(), value)
+        })
+        .unzip();
+    Row::new(names, values)
+}
+
+/// The deterministic fake for `field` in row number `seq`.
+fn fake_value(table: &str, field: &FieldInfo, seq: u64) -> Value {
+    let column = field.column_name;
+    match &field.sql_type {
+        SqlType::Boolean => Value::Bool(seq % 2 == 0),
+        SqlType::TinyInt => Value::TinyInt(i8::try_from(seq % 127).unwrap_or_default()),
+        SqlType::SmallInt => Value::SmallInt(i16::try_from(seq % 32_767).unwrap_or_default()),
+        SqlType::Integer => Value::Int(i32::try_from(seq).unwrap_or(i32::MAX)),
+        SqlType::BigInt => Value::BigInt(i64::try_from(seq).unwrap_or(i64::MAX)),
+        SqlType::Real => Value::Float(seq as f32 + 0.5),
+        SqlType::Double => Value::Double(seq as f64 + 0.5),
+        SqlType::Numeric { scale, .. } | SqlType::Decimal { scale, .. } => {
+            if *scale == 0 {
+                Value::Decimal(seq.to_string())
+            } else {
+                Value::Decimal(format!("{seq}.{}", "0".repeat(usize::from(*scale))))
+            }
+        }
+        SqlType::Char(len) | SqlType::VarChar(len) => {
+            Value::Text(fit(fake_text(column, seq), *len))
+        }
+        SqlType::Text | SqlType::CiText | SqlType::Custom(_) => Value::Text(fake_text(column, seq)),
+        SqlType::Enum(variants) => match variants.len() {
+            0 => Value::Text(fake_text(column, seq)),
+            n => {
+                let index = usize::try_from(seq.saturating_sub(1) % n as u64).unwrap_or_default();
+                Value::Text(variants[index].to_string())
+            }
+        },
+        SqlType::Binary(len) | SqlType::VarBinary(len) => {
+            Value::Bytes(fit(fake_text(column, seq), *len).into_bytes())
+        }
+        SqlType::Blob => Value::Bytes(fake_text(column, seq).into_bytes()),
+        SqlType::Date => {
+            Value::Date(FAKE_EPOCH_DAYS + i32::try_from(seq % 36_500).unwrap_or_default())
+        }
+        SqlType::Time => Value::Time(i64::try_from(seq % 86_400).unwrap_or_default() * 1_000_000),
+        SqlType::DateTime | SqlType::Timestamp => Value::Timestamp(fake_micros(seq)),
+        SqlType::TimestampTz => Value::TimestampTz(fake_micros(seq)),
+        SqlType::Uuid => Value::Uuid(fake_uuid(table, column, seq)),
+        SqlType::Json | SqlType::JsonB => Value::Json(serde_json::json!({ column: seq })),
+        SqlType::Array(_) => Value::Array(Vec::new()),
+    }
+}
+
+fn fake_text(column: &str, seq: u64) -> String {
+    format!("{column}-{seq}")
+}
+
+/// Keep the last `len` characters so the sequence number survives (0 = unbounded).
+fn fit(text: String, len: u32) -> String {
+    let len = usize::try_from(len).unwrap_or(usize::MAX);
+    let count = text.chars().count();
+    if len == 0 || count <= len {
+        return text;
+    }
+    text.chars().skip(count - len).collect()
+}
+
+/// One hour apart from 2024-01-01T00:00:00Z, in microseconds.
+fn fake_micros(seq: u64) -> i64 {
+    let hours = i64::try_from(seq).unwrap_or_default();
+    (FAKE_EPOCH_SECS + hours * 3600) * 1_000_000
+}
+
+/// A version-4 shaped UUID hashed from the table, column and sequence number.
+fn fake_uuid(table: &str, column: &str, seq: u64) -> [u8; 16] {
+    let key = format!("{table}.{column}:{seq}");
+    let mut bytes = [0u8; 16];
+    bytes[..8].copy_from_slice(&fnv1a(key.as_bytes(), 0xcbf2_9ce4_8422_2325).to_be_bytes());
+    bytes[8..].copy_from_slice(&fnv1a(key.as_bytes(), 0x8422_2325_cbf2_9ce4).to_be_bytes());
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    bytes
+}
+
+fn fnv1a(data: &[u8], seed: u64) -> u64 {
+    data.iter().fold(seed, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Insert `row` into `table` and return the stored row.
+///
+/// NULL auto-increment columns are left out so the database generates them.
+/// Postgres and SQLite read the row back with `RETURNING *`; MySQL fills in
+/// the generated id from `LAST_INSERT_ID()`.
+async fn insert_row<C: Connection>(
+    cx: &Cx,
+    conn: &C,
+    table: &str,
+    fields: &[FieldInfo],
+    row: Vec<(&'static str, Value)>,
+) -> Outcome<Row, Error> {
+    let generated = |name: &str, value: &Value| {
+        value.is_null()
+            && fields
+                .iter()
+                .any(|f| f.column_name == name && f.auto_increment)
+    };
+
+    let dialect = conn.sql_dialect();
+    let (columns, params): (Vec<&'static str>, Vec<Value>) = row
+        .iter()
+        .filter(|(name, value)| !generated(name, value))
+        .cloned()
+        .unzip();
+    let placeholders: Vec<String> = (1..=columns.len())
+        .map(|i| dialect.placeholder(i))
+        .collect();
+    let table_sql = dialect.quote_identifier(table);
+    let mut sql = if columns.is_empty() {
+        match dialect.base_dialect() {
+            Dialect::Mysql => format!("INSERT INTO {table_sql} () VALUES ()"),
+            Dialect::Postgres | Dialect::Sqlite => {
+                format!("INSERT INTO {table_sql} DEFAULT VALUES")
+            }
+        }
+    } else {
+        let columns_sql: Vec<String> = columns
+            .iter()
+            .map(|c| dialect.quote_identifier(c))
+            .collect();
+        format!(
+            "INSERT INTO {table_sql} ({}) VALUES ({})",
+            columns_sql.join(", "),
+            placeholders.join(", ")
+        )
+    };
+
+    if dialect.base_dialect() == Dialect::Mysql {
+        let id = match conn.insert(cx, &sql, &params).await {
+            Outcome::Ok(id) => id,
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        };
+        let (names, values) = row
+            .into_iter()
+            .map(|(name, value)| {
+                let value = if generated(name, &value) {
+                    Value::BigInt(id)
+                } else {
+                    value
+                };
+                (name.to_string(), value)
+            })
+            .unzip();
+        return Outcome::Ok(Row::new(names, values));
+    }
+
+    sql.push_str(" RETURNING *");
+    match conn.query_one(cx, &sql, &params).await {
+        Outcome::Ok(Some(row)) => Outcome::Ok(row),
+        Outcome::Ok(None) => Outcome::Err(Error::Custom(format!(
+            "factory insert into {table} returned no row"
+        ))),
+        Outcome::Err(e) => Outcome::Err(e),
+        Outcome::Cancelled(r) => Outcome::Cancelled(r),
+        Outcome::Panicked(p) => Outcome::Panicked(p),
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use crate::{TestDatabase, block_on, expect_outcome};
+    use sqlmodel_macros::Model;
+
+    #[derive(Model, Debug, Clone, PartialEq)]
+    #[sqlmodel(table = "leagues")]
+    struct League {
+        #[sqlmodel(primary_key, auto_increment)]
+        id: Option<i64>,
+        #[sqlmodel(unique)]
+        name: String,
+    }
+
+    #[derive(Model, Debug, Clone, PartialEq)]
+    #[sqlmodel(table = "teams")]
+    struct Team {
+        #[sqlmodel(primary_key, auto_increment)]
+        id: Option<i64>,
+        name: String,
+        #[sqlmodel(foreign_key = "leagues.id")]
+        league_id: i64,
+    }
+
+    #[derive(Model, Debug, Clone, PartialEq)]
+    #[sqlmodel(table = "heroes")]
+    struct Hero {
+        #[sqlmodel(primary_key, auto_increment)]
+        id: Option<i64>,
+        #[sqlmodel(unique)]
+        name: String,
+        #[sqlmodel(nullable)]
+        age: Option<i32>,
+        #[sqlmodel(foreign_key = "teams.id")]
+        team_id: Option<i64>,
+    }
+
+    #[test]
+    fn test_build_is_deterministic() {
+        let mut first = Factory::<Hero>::new();
+        let mut second = Factory::<Hero>::new();
+        let a = first.build().unwrap();
+        let b = second.build().unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.id, None);
+        assert_eq!(a.name, "name-1");
+        assert_eq!(a.age, None);
+        assert_eq!(first.build().unwrap().name, "name-2");
+    }
+
+    #[test]
+    fn test_with_overrides_run_in_order() {
+        let mut factory = Factory::<Hero>::new()
+            .with(|h| h.age = Some(30))
+            .with(|h| h.name.push_str("-x"));
+        let hero = factory.build().unwrap();
+        assert_eq!(hero.age, Some(30));
+        assert_eq!(hero.name, "name-1-x");
+    }
+
+    #[test]
+    fn test_fake_values() {
+        let field = |sql_type| FieldInfo::new("code", "code", sql_type);
+        assert_eq!(
+            fake_value("t", &field(SqlType::VarChar(4)), 12),
+            Value::Text("e-12".to_string())
+        );
+        assert_eq!(
+            fake_value(
+                "t",
+                &field(SqlType::Decimal {
+                    precision: 10,
+                    scale: 2
+                }),
+                3
+            ),
+            Value::Decimal("3.00".to_string())
+        );
+        assert_eq!(
+            fake_value("t", &field(SqlType::Enum(vec!["a", "b"])), 2),
+            Value::Text("b".to_string())
+        );
+        assert_eq!(
+            fake_value("t", &field(SqlType::Date), 1),
+            Value::Date(19_724)
+        );
+        let uuid = fake_value("t", &field(SqlType::Uuid), 1);
+        assert_eq!(uuid, fake_value("t", &field(SqlType::Uuid), 1));
+        assert_ne!(uuid, fake_value("t", &field(SqlType::Uuid), 2));
+    }
+
+    #[test]
+    fn test_create_batch_inserts_parents_first() {
+        let db = TestDatabase::sqlite()
+            .model::<League>()
+            .model::<Team>()
+            .model::<Hero>();
+        block_on(async {
+            let cx = Cx::for_testing();
+            let mut session = expect_outcome(db.session(&cx).await, "open session");
+
+            let mut factory = Factory::<Hero>::new()
+                .parent(Factory::<Team>::new().parent(Factory::<League>::new()))
+                .with(|h| h.age = Some(30));
+            let heroes = expect_outcome(
+                factory.create_batch(&cx, &mut session, 50).await,
+                "create heroes",
+            );
+            let more = expect_outcome(
+                factory.create_batch(&cx, &mut session, 2).await,
+                "create more heroes",
+            );
+
+            assert_eq!(heroes.len(), 50);
+            assert!(heroes.iter().all(|h| h.id.is_some() && h.age == Some(30)));
+            assert_eq!(heroes[49].name, "name-50");
+            assert_eq!(more[0].name, "name-51");
+            assert!(heroes.iter().all(|h| h.team_id == heroes[0].team_id));
+            assert_ne!(more[0].team_id, heroes[0].team_id);
+
+            let rows = expect_outcome(
+                session
+                    .connection()
+                    .query(&cx, "SELECT COUNT(*) FROM teams", &[])
+                    .await,
+                "count teams",
+            );
+            assert_eq!(rows[0].get_as::<i64>(0).unwrap(), 2);
+
+            expect_outcome(session.finish(&cx).await, "rollback");
+        });
+    }
+
+    #[test]
+    fn test_factory_seed_data() {
+        let db = TestDatabase::sqlite()
+            .model::<League>()
+            .model::<Team>()
+            .factory(Factory::<Team>::new().parent(Factory::<League>::new()), 3);
+        block_on(async {
+            let cx = Cx::for_testing();
+            for _ in 0..2 {
+                let session = expect_outcome(db.session(&cx).await, "open session");
+                let rows = expect_outcome(
+                    session
+                        .connection()
+                        .query(&cx, "SELECT name FROM teams ORDER BY id", &[])
+                        .await,
+                    "select teams",
+                );
+                let names: Vec<String> = rows.iter().map(|r| r.get_as(0).unwrap()).collect();
+                assert_eq!(names, ["name-1", "name-2", "name-3"]);
+                expect_outcome(session.finish(&cx).await, "rollback");
+            }
+        });
+    }
+}
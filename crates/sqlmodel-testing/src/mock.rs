@@ -0,0 +1,780 @@
+//! Scriptable [`Connection`] for unit tests without a database.
+//!
+//! A [`MockConnection`] answers statements from a list of expectations and
+//! records every call. Expectations are matched in order by default, or in
+//! any order after [`MockConnection::unordered`]. When the last clone of the
+//! connection is dropped it panics if an expectation was never satisfied or a
+//! statement matched none of them.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use sqlmodel_testing::{MockConnection, matching, rows};
+//!
+//! let conn = MockConnection::new();
+//! conn.expect_query(matching("SELECT * FROM heroes%"))
+//!     .returning(rows![{"id" => 1_i64, "name" => "Deadpond"}]);
+//! conn.expect_execute(matching("UPDATE heroes SET%"))
+//!     .with_params(vec![Value::from("Rusty-Man"), Value::from(1_i64)])
+//!     .affecting(1);
+//!
+//! rename_first_hero(&cx, &conn).await?;
+//! ```
+//!
+//! Transactions opened with `begin` are not expectations themselves; the
+//! statements run inside them are matched like any other.
+
+#![allow(clippy::result_large_err)] // Error type is defined in sqlmodel-core
+
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use sqlmodel_core::connection::{IsolationLevel, PreparedStatement, TransactionOps};
+use sqlmodel_core::{Connection, Cx, Dialect, Error, Outcome, Row, Value};
+
+/// Build a `Vec<Row>` from column/value pairs.
+///
+/// ```ignore
+/// let rows = rows![
+///     {"id" => 1_i64, "name" => "Deadpond"},
+///     {"id" => 2_i64, "name" => "Spider-Boy"},
+/// ];
+/// ```
+#[macro_export]
+macro_rules! rows {
+    ($({$($column:expr => $value:expr),* $(,)?}),* $(,)?) => {
+        vec![$(
+            $crate::Row::new(
+                vec![$(::std::string::String::from($column)),*],
+                vec![$($crate::Value::from($value)),*],
+            )
+        ),*]
+    };
+}
+
+/// How a statement reached the connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallKind {
+    /// `query`, `query_one` or `query_prepared`.
+    Query,
+    /// `execute`, `execute_prepared` or one statement of a `batch`.
+    Execute,
+    /// `insert`.
+    Insert,
+}
+
+impl fmt::Display for CallKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CallKind::Query => "query",
+            CallKind::Execute => "execute",
+            CallKind::Insert => "insert",
+        })
+    }
+}
+
+/// Which SQL an expectation accepts.
+///
+/// Whitespace runs are collapsed on both sides before comparing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SqlMatcher {
+    /// The statement must equal this text.
+    Exact(String),
+    /// `LIKE`-style pattern: `%` matches any run of characters, `_` one.
+    Pattern(String),
+    /// Any statement.
+    Any,
+}
+
+impl SqlMatcher {
+    /// Whether `sql` satisfies this matcher.
+    pub fn matches(&self, sql: &str) -> bool {
+        match self {
+            SqlMatcher::Exact(expected) => normalize(expected) == normalize(sql),
+            SqlMatcher::Pattern(pattern) => like(&normalize(pattern), &normalize(sql)),
+            SqlMatcher::Any => true,
+        }
+    }
+}
+
+impl fmt::Display for SqlMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SqlMatcher::Exact(sql) => write!(f, "`{sql}`"),
+            SqlMatcher::Pattern(pattern) => write!(f, "pattern `{pattern}`"),
+            SqlMatcher::Any => f.write_str("any statement"),
+        }
+    }
+}
+
+/// Match SQL against a `LIKE`-style pattern (`%` and `_` wildcards).
+pub fn matching(pattern: impl Into<String>) -> SqlMatcher {
+    SqlMatcher::Pattern(pattern.into())
+}
+
+/// Match SQL exactly (modulo whitespace).
+pub fn exact(sql: impl Into<String>) -> SqlMatcher {
+    SqlMatcher::Exact(sql.into())
+}
+
+/// Match any SQL.
+pub fn any_sql() -> SqlMatcher {
+    SqlMatcher::Any
+}
+
+fn normalize(sql: &str) -> String {
+    sql.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn like(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `%` and the text index it is currently absorbing up to.
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '_' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '%' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, absorbed)) = backtrack {
+            p = star + 1;
+            t = absorbed + 1;
+            backtrack = Some((star, t));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '%')
+}
+
+/// One statement the connection received.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockCall {
+    /// How it was issued.
+    pub kind: CallKind,
+    /// The SQL text.
+    pub sql: String,
+    /// Bound parameters.
+    pub params: Vec<Value>,
+}
+
+#[derive(Debug)]
+struct ExpectedCall {
+    kind: CallKind,
+    matcher: SqlMatcher,
+    params: Option<Vec<Value>>,
+    rows: Vec<Row>,
+    affected: u64,
+    id: i64,
+    error: Option<String>,
+    times: usize,
+    calls: usize,
+}
+
+impl ExpectedCall {
+    fn pending(&self) -> bool {
+        self.calls < self.times
+    }
+
+    fn accepts(&self, kind: CallKind, sql: &str, params: &[Value]) -> bool {
+        self.kind == kind
+            && self.matcher.matches(sql)
+            && self
+                .params
+                .as_deref()
+                .is_none_or(|expected| expected == params)
+    }
+
+    fn describe(&self) -> String {
+        let mut text = format!("{} matching {}", self.kind, self.matcher);
+        if let Some(params) = &self.params {
+            text.push_str(&format!(" with params {params:?}"));
+        }
+        text
+    }
+}
+
+/// What a matched expectation answers with.
+enum Reply {
+    Rows(Vec<Row>),
+    Affected(u64),
+    Id(i64),
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    expectations: Vec<ExpectedCall>,
+    unordered: bool,
+    calls: Vec<MockCall>,
+    failures: Vec<String>,
+}
+
+impl MockState {
+    fn dispatch(&mut self, kind: CallKind, sql: &str, params: &[Value]) -> Result<Reply, Error> {
+        self.calls.push(MockCall {
+            kind,
+            sql: sql.to_string(),
+            params: params.to_vec(),
+        });
+
+        let found = if self.unordered {
+            self.expectations
+                .iter()
+                .position(|e| e.pending() && e.accepts(kind, sql, params))
+        } else {
+            self.expectations
+                .iter()
+                .position(ExpectedCall::pending)
+                .filter(|&i| self.expectations[i].accepts(kind, sql, params))
+        };
+
+        let Some(index) = found else {
+            let next = if self.unordered {
+                None
+            } else {
+                self.expectations.iter().find(|e| e.pending())
+            };
+            let message = match next {
+                Some(next) => format!(
+                    "unexpected {kind} `{sql}` with params {params:?}; expected {}",
+                    next.describe()
+                ),
+                None => format!("unexpected {kind} `{sql}` with params {params:?}"),
+            };
+            self.failures.push(message.clone());
+            return Err(Error::Custom(message));
+        };
+
+        let expectation = &mut self.expectations[index];
+        expectation.calls += 1;
+        if let Some(message) = &expectation.error {
+            return Err(Error::Custom(message.clone()));
+        }
+        Ok(match kind {
+            CallKind::Query => Reply::Rows(expectation.rows.clone()),
+            CallKind::Execute => Reply::Affected(expectation.affected),
+            CallKind::Insert => Reply::Id(expectation.id),
+        })
+    }
+
+    fn problems(&self) -> Vec<String> {
+        let mut problems = self.failures.clone();
+        for e in self.expectations.iter().filter(|e| e.pending()) {
+            problems.push(format!(
+                "expected {} {} time(s), got {}",
+                e.describe(),
+                e.times,
+                e.calls
+            ));
+        }
+        problems
+    }
+}
+
+/// Verifies expectations when the last handle goes away.
+#[derive(Debug, Default)]
+struct Shared {
+    state: Mutex<MockState>,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn dispatch(&self, kind: CallKind, sql: &str, params: &[Value]) -> Result<Reply, Error> {
+        self.lock().dispatch(kind, sql, params)
+    }
+}
+
+impl Drop for Shared {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            return;
+        }
+        let problems = self.lock().problems();
+        assert!(
+            problems.is_empty(),
+            "MockConnection expectations not met:\n  {}",
+            problems.join("\n  ")
+        );
+    }
+}
+
+/// A [`Connection`] that answers from scripted expectations.
+///
+/// Clones share expectations and the call log.
+#[derive(Debug, Clone)]
+pub struct MockConnection {
+    shared: Arc<Shared>,
+    dialect: Dialect,
+}
+
+impl MockConnection {
+    /// A mock speaking the Postgres dialect, matching expectations in order.
+    pub fn new() -> Self {
+        Self {
+            shared: Arc::new(Shared::default()),
+            dialect: Dialect::Postgres,
+        }
+    }
+
+    /// Report `dialect` from [`Connection::dialect`].
+    #[must_use]
+    pub fn with_dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Let statements satisfy any pending expectation, not just the next one.
+    #[must_use]
+    pub fn unordered(self) -> Self {
+        self.shared.lock().unordered = true;
+        self
+    }
+
+    /// Expect a `query`; it returns no rows unless configured.
+    pub fn expect_query(&self, matcher: SqlMatcher) -> Expectation {
+        self.expect(CallKind::Query, matcher)
+    }
+
+    /// Expect an `execute`; it affects 0 rows unless configured.
+    pub fn expect_execute(&self, matcher: SqlMatcher) -> Expectation {
+        self.expect(CallKind::Execute, matcher)
+    }
+
+    /// Expect an `insert`; it returns id 0 unless configured.
+    pub fn expect_insert(&self, matcher: SqlMatcher) -> Expectation {
+        self.expect(CallKind::Insert, matcher)
+    }
+
+    fn expect(&self, kind: CallKind, matcher: SqlMatcher) -> Expectation {
+        let mut state = self.shared.lock();
+        state.expectations.push(ExpectedCall {
+            kind,
+            matcher,
+            params: None,
+            rows: Vec::new(),
+            affected: 0,
+            id: 0,
+            error: None,
+            times: 1,
+            calls: 0,
+        });
+        Expectation {
+            shared: Arc::clone(&self.shared),
+            index: state.expectations.len() - 1,
+        }
+    }
+
+    /// Every statement received so far, in order.
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.shared.lock().calls.clone()
+    }
+
+    /// Panic unless every expectation is satisfied and no statement went
+    /// unmatched.
+    ///
+    /// Dropping the last clone does the same check; call this to fail at a
+    /// specific point instead.
+    #[track_caller]
+    pub fn verify(&self) {
+        let problems = self.shared.lock().problems();
+        assert!(
+            problems.is_empty(),
+            "MockConnection expectations not met:\n  {}",
+            problems.join("\n  ")
+        );
+    }
+
+    fn reply(&self, kind: CallKind, sql: &str, params: &[Value]) -> Result<Reply, Error> {
+        self.shared.dispatch(kind, sql, params)
+    }
+}
+
+impl Default for MockConnection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Handle for configuring an expectation added to a [`MockConnection`].
+#[derive(Debug)]
+pub struct Expectation {
+    shared: Arc<Shared>,
+    index: usize,
+}
+
+impl Expectation {
+    fn update(self, f: impl FnOnce(&mut ExpectedCall)) -> Self {
+        f(&mut self.shared.lock().expectations[self.index]);
+        self
+    }
+
+    /// Only accept these bound parameters.
+    pub fn with_params(self, params: Vec<Value>) -> Self {
+        self.update(|e| e.params = Some(params))
+    }
+
+    /// Rows returned by a query (`query_one` returns the first).
+    pub fn returning(self, rows: Vec<Row>) -> Self {
+        self.update(|e| e.rows = rows)
+    }
+
+    /// Rows affected reported by an execute.
+    pub fn affecting(self, rows: u64) -> Self {
+        self.update(|e| e.affected = rows)
+    }
+
+    /// Id returned by an insert.
+    pub fn returning_id(self, id: i64) -> Self {
+        self.update(|e| e.id = id)
+    }
+
+    /// Answer with `Error::Custom(message)` instead.
+    pub fn failing(self, message: impl Into<String>) -> Self {
+        let message = message.into();
+        self.update(|e| e.error = Some(message))
+    }
+
+    /// Expect exactly `n` matching calls (default 1).
+    pub fn times(self, n: usize) -> Self {
+        self.update(|e| e.times = n)
+    }
+}
+
+fn rows_outcome(reply: Result<Reply, Error>) -> Outcome<Vec<Row>, Error> {
+    match reply {
+        Ok(Reply::Rows(rows)) => Outcome::Ok(rows),
+        Ok(Reply::Affected(_) | Reply::Id(_)) => Outcome::Ok(Vec::new()),
+        Err(e) => Outcome::Err(e),
+    }
+}
+
+fn affected_outcome(reply: Result<Reply, Error>) -> Outcome<u64, Error> {
+    match reply {
+        Ok(Reply::Affected(rows)) => Outcome::Ok(rows),
+        Ok(Reply::Rows(_) | Reply::Id(_)) => Outcome::Ok(0),
+        Err(e) => Outcome::Err(e),
+    }
+}
+
+#[allow(clippy::manual_async_fn)] // Trait methods return `impl Future + Send`
+impl Connection for MockConnection {
+    type Tx<'conn>
+        = MockTransaction
+    where
+        Self: 'conn;
+
+    fn dialect(&self) -> Dialect {
+        self.dialect
+    }
+
+    fn query(
+        &self,
+        _cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<Vec<Row>, Error>> + Send {
+        let outcome = rows_outcome(self.reply(CallKind::Query, sql, params));
+        async move { outcome }
+    }
+
+    fn query_one(
+        &self,
+        _cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<Option<Row>, Error>> + Send {
+        let outcome = rows_outcome(self.reply(CallKind::Query, sql, params))
+            .map(|rows| rows.into_iter().next());
+        async move { outcome }
+    }
+
+    fn execute(
+        &self,
+        _cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<u64, Error>> + Send {
+        let outcome = affected_outcome(self.reply(CallKind::Execute, sql, params));
+        async move { outcome }
+    }
+
+    fn insert(
+        &self,
+        _cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<i64, Error>> + Send {
+        let outcome = match self.reply(CallKind::Insert, sql, params) {
+            Ok(Reply::Id(id)) => Outcome::Ok(id),
+            Ok(Reply::Rows(_) | Reply::Affected(_)) => Outcome::Ok(0),
+            Err(e) => Outcome::Err(e),
+        };
+        async move { outcome }
+    }
+
+    fn batch(
+        &self,
+        _cx: &Cx,
+        statements: &[(String, Vec<Value>)],
+    ) -> impl Future<Output = Outcome<Vec<u64>, Error>> + Send {
+        let mut outcome = Outcome::Ok(Vec::with_capacity(statements.len()));
+        for (sql, params) in statements {
+            match affected_outcome(self.reply(CallKind::Execute, sql, params)) {
+                Outcome::Ok(rows) => {
+                    if let Outcome::Ok(results) = &mut outcome {
+                        results.push(rows);
+                    }
+                }
+                failed => {
+                    outcome = failed.map(|_| Vec::new());
+                    break;
+                }
+            }
+        }
+        async move { outcome }
+    }
+
+    fn begin(&self, _cx: &Cx) -> impl Future<Output = Outcome<Self::Tx<'_>, Error>> + Send {
+        let tx = MockTransaction { conn: self.clone() };
+        async move { Outcome::Ok(tx) }
+    }
+
+    fn begin_with(
+        &self,
+        cx: &Cx,
+        _isolation: IsolationLevel,
+    ) -> impl Future<Output = Outcome<Self::Tx<'_>, Error>> + Send {
+        self.begin(cx)
+    }
+
+    fn prepare(
+        &self,
+        _cx: &Cx,
+        sql: &str,
+    ) -> impl Future<Output = Outcome<PreparedStatement, Error>> + Send {
+        let stmt = PreparedStatement::new(0, sql.to_string(), 0);
+        async move { Outcome::Ok(stmt) }
+    }
+
+    fn query_prepared(
+        &self,
+        cx: &Cx,
+        stmt: &PreparedStatement,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<Vec<Row>, Error>> + Send {
+        self.query(cx, stmt.sql(), params)
+    }
+
+    fn execute_prepared(
+        &self,
+        cx: &Cx,
+        stmt: &PreparedStatement,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<u64, Error>> + Send {
+        self.execute(cx, stmt.sql(), params)
+    }
+
+    fn ping(&self, _cx: &Cx) -> impl Future<Output = Outcome<(), Error>> + Send {
+        async { Outcome::Ok(()) }
+    }
+
+    fn close(self, _cx: &Cx) -> impl Future<Output = sqlmodel_core::Result<()>> + Send {
+        async { Ok(()) }
+    }
+}
+
+/// Transaction handed out by [`MockConnection::begin`].
+///
+/// Statements go through the connection's expectations; savepoints, commit
+/// and rollback always succeed.
+#[derive(Debug)]
+pub struct MockTransaction {
+    conn: MockConnection,
+}
+
+#[allow(clippy::manual_async_fn)] // Trait methods return `impl Future + Send`
+impl TransactionOps for MockTransaction {
+    fn query(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<Vec<Row>, Error>> + Send {
+        self.conn.query(cx, sql, params)
+    }
+
+    fn query_one(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<Option<Row>, Error>> + Send {
+        self.conn.query_one(cx, sql, params)
+    }
+
+    fn execute(
+        &self,
+        cx: &Cx,
+        sql: &str,
+        params: &[Value],
+    ) -> impl Future<Output = Outcome<u64, Error>> + Send {
+        self.conn.execute(cx, sql, params)
+    }
+
+    fn savepoint(&self, _cx: &Cx, _name: &str) -> impl Future<Output = Outcome<(), Error>> + Send {
+        async { Outcome::Ok(()) }
+    }
+
+    fn rollback_to(
+        &self,
+        _cx: &Cx,
+        _name: &str,
+    ) -> impl Future<Output = Outcome<(), Error>> + Send {
+        async { Outcome::Ok(()) }
+    }
+
+    fn release(&self, _cx: &Cx, _name: &str) -> impl Future<Output = Outcome<(), Error>> + Send {
+        async { Outcome::Ok(()) }
+    }
+
+    fn commit(self, _cx: &Cx) -> impl Future<Output = Outcome<(), Error>> + Send {
+        async { Outcome::Ok(()) }
+    }
+
+    fn rollback(self, _cx: &Cx) -> impl Future<Output = Outcome<(), Error>> + Send {
+        async { Outcome::Ok(()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block_on;
+
+    #[test]
+    fn test_like_patterns() {
+        assert!(matching("SELECT * FROM heroes%").matches("SELECT * FROM heroes WHERE id = $1"));
+        assert!(matching("SELECT * FROM heroes%").matches("SELECT *\n  FROM   heroes"));
+        assert!(matching("%FROM _eroes%").matches("SELECT id FROM heroes LIMIT 1"));
+        assert!(!matching("SELECT * FROM heroes%").matches("SELECT * FROM teams"));
+        assert!(exact("DELETE FROM heroes").matches(" DELETE  FROM heroes "));
+        assert!(!exact("DELETE FROM heroes").matches("DELETE FROM heroes WHERE id = 1"));
+        assert!(any_sql().matches("VACUUM"));
+    }
+
+    #[test]
+    fn test_scripted_replies_and_call_log() {
+        let conn = MockConnection::new();
+        conn.expect_query(matching("SELECT * FROM heroes%"))
+            .returning(rows![{"id" => 1_i64, "name" => "Deadpond"}]);
+        conn.expect_execute(matching("UPDATE heroes%"))
+            .with_params(vec![Value::from("Rusty-Man")])
+            .affecting(1);
+        conn.expect_insert(any_sql()).returning_id(7);
+
+        block_on(async {
+            let cx = Cx::for_testing();
+            let rows = conn
+                .query(
+                    &cx,
+                    "SELECT * FROM heroes WHERE id = $1",
+                    &[Value::from(1_i64)],
+                )
+                .await;
+            let Outcome::Ok(rows) = rows else {
+                panic!("query failed: {rows:?}")
+            };
+            assert_eq!(rows[0].get_named::<String>("name").unwrap(), "Deadpond");
+
+            let updated = conn
+                .execute(
+                    &cx,
+                    "UPDATE heroes SET name = $1",
+                    &[Value::from("Rusty-Man")],
+                )
+                .await;
+            assert!(matches!(updated, Outcome::Ok(1)));
+            assert!(matches!(
+                conn.insert(&cx, "INSERT INTO heroes", &[]).await,
+                Outcome::Ok(7)
+            ));
+        });
+
+        assert_eq!(conn.calls().len(), 3);
+        assert_eq!(conn.calls()[1].kind, CallKind::Execute);
+        conn.verify();
+    }
+
+    #[test]
+    fn test_ordered_and_unordered_modes() {
+        let ordered = MockConnection::new();
+        ordered.expect_execute(exact("DELETE FROM a"));
+        ordered.expect_execute(exact("DELETE FROM b"));
+
+        let unordered = MockConnection::new().unordered();
+        unordered.expect_execute(exact("DELETE FROM a"));
+        unordered.expect_execute(exact("DELETE FROM b"));
+
+        block_on(async {
+            let cx = Cx::for_testing();
+            let out_of_order = ordered.execute(&cx, "DELETE FROM b", &[]).await;
+            assert!(
+                matches!(out_of_order, Outcome::Err(Error::Custom(ref m)) if m.contains("expected execute matching `DELETE FROM a`"))
+            );
+
+            assert!(matches!(
+                unordered.execute(&cx, "DELETE FROM b", &[]).await,
+                Outcome::Ok(0)
+            ));
+            assert!(matches!(
+                unordered.execute(&cx, "DELETE FROM a", &[]).await,
+                Outcome::Ok(0)
+            ));
+        });
+        unordered.verify();
+
+        let problems = ordered.shared.lock().problems();
+        assert_eq!(problems.len(), 3);
+        // Satisfy the remaining expectations so the drop check stays quiet.
+        ordered.shared.lock().expectations.clear();
+        ordered.shared.lock().failures.clear();
+    }
+
+    #[test]
+    #[should_panic(expected = "expected query matching pattern `SELECT%` 1 time(s), got 0")]
+    fn test_verify_on_drop() {
+        let conn = MockConnection::new();
+        conn.expect_query(matching("SELECT%"));
+        drop(conn);
+    }
+
+    #[test]
+    fn test_failing_and_times() {
+        let conn = MockConnection::new();
+        conn.expect_execute(matching("INSERT%"))
+            .failing("duplicate key")
+            .times(2);
+
+        block_on(async {
+            let cx = Cx::for_testing();
+            for _ in 0..2 {
+                let outcome = conn
+                    .execute(&cx, "INSERT INTO heroes VALUES (1)", &[])
+                    .await;
+                assert!(
+                    matches!(outcome, Outcome::Err(Error::Custom(ref m)) if m == "duplicate key")
+                );
+            }
+        });
+    }
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "sqlite")]
+
+use std::sync::LazyLock;
+
+use sqlmodel_core::{Connection, Error, Outcome, Value};
+use sqlmodel_session::Session;
+use sqlmodel_sqlite::SqliteConnection;
+use sqlmodel_testing::{Cx, SqliteBackend, TestDatabase, expect_outcome};
+
+static HEROES: LazyLock<TestDatabase<SqliteBackend>> = LazyLock::new(|| {
+    TestDatabase::sqlite()
+        .schema("CREATE TABLE heroes (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+        .seed(
+            "INSERT INTO heroes (name) VALUES ($1)",
+            vec![Value::Text("Deadpond".to_string())],
+        )
+});
+
+async fn hero_names(cx: &Cx, session: &Session<SqliteConnection>) -> Vec<String> {
+    let rows = expect_outcome(
+        session
+            .connection()
+            .query(cx, "SELECT name FROM heroes ORDER BY id", &[])
+            .await,
+        "select heroes",
+    );
+    rows.iter()
+        .map(|row| row.get_as::<String>(0).expect("name column"))
+        .collect()
+}
+
+#[sqlmodel_macros::test(db = HEROES)]
+async fn sees_seed_rows(cx: &Cx, session: &mut Session<SqliteConnection>) {
+    assert_eq!(hero_names(cx, session).await, vec!["Deadpond".to_string()]);
+}
+
+#[sqlmodel_macros::test(db = HEROES)]
+async fn writes_inside_the_test_transaction(
+    cx: &Cx,
+    session: &mut Session<SqliteConnection>,
+) -> Outcome<(), Error> {
+    session
+        .connection()
+        .execute(
+            cx,
+            "INSERT INTO heroes (name) VALUES ($1)",
+            &[Value::Text("Rusty-Man".to_string())],
+        )
+        .await
+        .map(|_| ())
+}
+
+#[sqlmodel_macros::test]
+async fn default_database_is_empty(cx: &Cx, session: &mut Session<SqliteConnection>) {
+    let rows = expect_outcome(
+        session
+            .connection()
+            .query(
+                cx,
+                "SELECT name FROM sqlite_master WHERE type = 'table'",
+                &[],
+            )
+            .await,
+        "list tables",
+    );
+    assert!(rows.is_empty());
+}
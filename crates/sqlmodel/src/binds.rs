@@ -0,0 +1,376 @@
+//! Multiple database binds with per-model routing.
+//!
+//! [`Binds`] names a set of connection pools ("binds") and routes each model's
+//! table to one of them, e.g. user data to `"primary"` and event tables to
+//! `"analytics"`. A [`RoutedSession`] then keeps one ORM [`Session`] per bind,
+//! checked out lazily, and sends each operation to the session of the model's
+//! bind.
+//!
+//! There is no distributed transaction coordinator, so a unit of work must
+//! stay on one bind: adding or deleting objects on a second bind while the
+//! first has uncommitted changes fails, as does a query joining tables that
+//! live on different binds. Commit one bind before writing to the next.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use sqlmodel::binds::{Binds, RoutedSession};
+//!
+//! let binds = Arc::new(
+//!     Binds::new()
+//!         .bind("primary", primary_pool, || connect(&primary_url))
+//!         .bind("analytics", analytics_pool, || connect(&analytics_url))
+//!         .default_bind("primary")
+//!         .route::<PageView>("analytics")
+//!         .route::<Click>("analytics"),
+//! );
+//!
+//! let mut session = RoutedSession::new(binds);
+//! let user = session.get::<User>(&cx, 1_i64).await?;      // primary
+//! session.add(&cx, &PageView::new(user.id)).await?;       // analytics
+//! session.commit(&cx).await?;
+//! ```
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use asupersync::{Cx, Outcome};
+use serde::{Deserialize, Serialize};
+use sqlmodel_core::{Connection, Error, Model, Value};
+use sqlmodel_pool::{Pool, PooledConnection};
+use sqlmodel_query::Select;
+use sqlmodel_session::Session;
+
+type ConnectFuture<C> = Pin<Box<dyn Future<Output = Outcome<C, Error>> + Send>>;
+type ConnectFn<C> = Arc<dyn Fn() -> ConnectFuture<C> + Send + Sync>;
+
+struct Bind<C: Connection> {
+    pool: Pool<C>,
+    connect: ConnectFn<C>,
+}
+
+/// Named connection pools and the tables routed to each.
+pub struct Binds<C: Connection> {
+    pools: HashMap<String, Bind<C>>,
+    routes: HashMap<String, String>,
+    default: Option<String>,
+}
+
+impl<C: Connection> Default for Binds<C> {
+    fn default() -> Self {
+        Self {
+            pools: HashMap::new(),
+            routes: HashMap::new(),
+            default: None,
+        }
+    }
+}
+
+impl<C: Connection> std::fmt::Debug for Binds<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names: Vec<&String> = self.pools.keys().collect();
+        names.sort();
+        f.debug_struct("Binds")
+            .field("binds", &names)
+            .field("routes", &self.routes)
+            .field("default", &self.default)
+            .finish()
+    }
+}
+
+impl<C: Connection> Binds<C> {
+    /// An empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register bind `name`: connections come from `pool`, which opens new
+    /// ones with `connect`.
+    pub fn bind<F, Fut>(mut self, name: impl Into<String>, pool: Pool<C>, connect: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Outcome<C, Error>> + Send + 'static,
+    {
+        let connect: ConnectFn<C> = Arc::new(move || Box::pin(connect()));
+        self.pools.insert(name.into(), Bind { pool, connect });
+        self
+    }
+
+    /// Route tables without an explicit route to bind `name`.
+    pub fn default_bind(mut self, name: impl Into<String>) -> Self {
+        self.default = Some(name.into());
+        self
+    }
+
+    /// Route the table of `M` to bind `name`.
+    pub fn route<M: Model>(self, name: impl Into<String>) -> Self {
+        self.route_table(M::TABLE_NAME, name)
+    }
+
+    /// Route `table` to bind `name`, for tables without a model or to route a
+    /// whole group with a loop.
+    pub fn route_table(mut self, table: impl Into<String>, name: impl Into<String>) -> Self {
+        self.routes.insert(table.into(), name.into());
+        self
+    }
+
+    /// Registered bind names, sorted.
+    pub fn bind_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.pools.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// The pool of bind `name`.
+    pub fn pool(&self, name: &str) -> Option<&Pool<C>> {
+        self.pools.get(name).map(|bind| &bind.pool)
+    }
+
+    /// The bind `table` is routed to.
+    #[allow(clippy::result_large_err)]
+    pub fn bind_for_table(&self, table: &str) -> Result<&str, Error> {
+        let name = self
+            .routes
+            .get(table)
+            .or(self.default.as_ref())
+            .ok_or_else(|| {
+                Error::Custom(format!(
+                    "table '{table}' is not routed to a bind and there is no default bind"
+                ))
+            })?;
+        if !self.pools.contains_key(name) {
+            return Err(Error::Custom(format!(
+                "table '{table}' is routed to unknown bind '{name}'"
+            )));
+        }
+        Ok(name.as_str())
+    }
+
+    /// The bind the table of `M` is routed to.
+    #[allow(clippy::result_large_err)]
+    pub fn bind_for<M: Model>(&self) -> Result<&str, Error> {
+        self.bind_for_table(M::TABLE_NAME)
+    }
+
+    /// Check out a connection from bind `name`.
+    pub async fn acquire(&self, cx: &Cx, name: &str) -> Outcome<PooledConnection<C>, Error> {
+        let Some(bind) = self.pools.get(name) else {
+            return Outcome::Err(Error::Custom(format!("unknown bind '{name}'")));
+        };
+        bind.pool.acquire(cx, || (bind.connect)()).await
+    }
+}
+
+/// An ORM session spanning several binds.
+///
+/// Each bind gets its own [`Session`] over a connection checked out on first
+/// use and held until the routed session is dropped.
+pub struct RoutedSession<C: Connection> {
+    binds: Arc<Binds<C>>,
+    sessions: HashMap<String, Session<PooledConnection<C>>>,
+}
+
+impl<C: Connection> std::fmt::Debug for RoutedSession<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut open: Vec<&String> = self.sessions.keys().collect();
+        open.sort();
+        f.debug_struct("RoutedSession")
+            .field("binds", &self.binds)
+            .field("open", &open)
+            .finish()
+    }
+}
+
+impl<C: Connection> RoutedSession<C> {
+    /// A session routing through `binds`.
+    pub fn new(binds: Arc<Binds<C>>) -> Self {
+        Self {
+            binds,
+            sessions: HashMap::new(),
+        }
+    }
+
+    /// The bind registry.
+    pub fn binds(&self) -> &Binds<C> {
+        &self.binds
+    }
+
+    /// The session of bind `name`, checking out its connection on first use.
+    pub async fn session(
+        &mut self,
+        cx: &Cx,
+        name: &str,
+    ) -> Outcome<&mut Session<PooledConnection<C>>, Error> {
+        if !self.sessions.contains_key(name) {
+            let conn = match self.binds.acquire(cx, name).await {
+                Outcome::Ok(conn) => conn,
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            };
+            self.sessions.insert(name.to_string(), Session::new(conn));
+        }
+        Outcome::Ok(self.sessions.get_mut(name).expect("session inserted above"))
+    }
+
+    /// The session of the bind `M` is routed to.
+    pub async fn session_for<M: Model>(
+        &mut self,
+        cx: &Cx,
+    ) -> Outcome<&mut Session<PooledConnection<C>>, Error> {
+        let name = match self.binds.bind_for::<M>() {
+            Ok(name) => name.to_string(),
+            Err(e) => return Outcome::Err(e),
+        };
+        self.session(cx, &name).await
+    }
+
+    /// The bind with uncommitted changes, if any.
+    pub fn writing_bind(&self) -> Option<&str> {
+        self.sessions
+            .iter()
+            .find(|(_, session)| has_changes(session))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Fail if a bind other than `name` has uncommitted changes.
+    #[allow(clippy::result_large_err)]
+    fn check_single_writer(&self, name: &str) -> Result<(), Error> {
+        match self.writing_bind() {
+            Some(writing) if writing != name => Err(Error::Custom(format!(
+                "bind '{name}' cannot join a unit of work on bind '{writing}'; \
+                 commit or roll back '{writing}' first"
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Get `M` by primary key from its bind.
+    pub async fn get<M>(&mut self, cx: &Cx, pk: impl Into<Value>) -> Outcome<Option<M>, Error>
+    where
+        M: Model + Clone + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
+    {
+        let pk = pk.into();
+        match self.session_for::<M>(cx).await {
+            Outcome::Ok(session) => session.get::<M>(cx, pk).await,
+            Outcome::Err(e) => Outcome::Err(e),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
+    /// Run `query` on the bind of `M`.
+    ///
+    /// Fails without running the query if it joins a table routed to another
+    /// bind.
+    pub async fn all<M: Model>(&mut self, cx: &Cx, query: Select<M>) -> Outcome<Vec<M>, Error> {
+        let name = match self.binds.bind_for::<M>() {
+            Ok(name) => name.to_string(),
+            Err(e) => return Outcome::Err(e),
+        };
+        for table in query.joined_tables() {
+            match self.binds.bind_for_table(table) {
+                Ok(other) if other == name => {}
+                Ok(other) => {
+                    return Outcome::Err(Error::Custom(format!(
+                        "cannot join '{table}' (bind '{other}') to '{}' (bind '{name}')",
+                        M::TABLE_NAME
+                    )));
+                }
+                Err(e) => return Outcome::Err(e),
+            }
+        }
+        match self.session(cx, &name).await {
+            Outcome::Ok(session) => query.all(cx, session.connection()).await,
+            Outcome::Err(e) => Outcome::Err(e),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
+    /// Add `obj` to the session of its bind.
+    pub async fn add<M>(&mut self, cx: &Cx, obj: &M) -> Outcome<(), Error>
+    where
+        M: Model + Clone + Send + Sync + Serialize + 'static,
+    {
+        match self.writable_session::<M>(cx).await {
+            Outcome::Ok(session) => {
+                session.add(obj);
+                Outcome::Ok(())
+            }
+            Outcome::Err(e) => Outcome::Err(e),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
+    /// Mark `obj` for deletion in the session of its bind.
+    pub async fn delete<M: Model + 'static>(&mut self, cx: &Cx, obj: &M) -> Outcome<(), Error> {
+        match self.writable_session::<M>(cx).await {
+            Outcome::Ok(session) => {
+                session.delete(obj);
+                Outcome::Ok(())
+            }
+            Outcome::Err(e) => Outcome::Err(e),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
+    async fn writable_session<M: Model>(
+        &mut self,
+        cx: &Cx,
+    ) -> Outcome<&mut Session<PooledConnection<C>>, Error> {
+        let name = match self.binds.bind_for::<M>() {
+            Ok(name) => name.to_string(),
+            Err(e) => return Outcome::Err(e),
+        };
+        if let Err(e) = self.check_single_writer(&name) {
+            return Outcome::Err(e);
+        }
+        self.session(cx, &name).await
+    }
+
+    /// Flush the bind with pending changes.
+    pub async fn flush(&mut self, cx: &Cx) -> Outcome<(), Error> {
+        let Some(name) = self.writing_bind().map(String::from) else {
+            return Outcome::Ok(());
+        };
+        match self.sessions.get_mut(&name) {
+            Some(session) => session.flush(cx).await,
+            None => Outcome::Ok(()),
+        }
+    }
+
+    /// Commit the bind with uncommitted changes.
+    pub async fn commit(&mut self, cx: &Cx) -> Outcome<(), Error> {
+        let Some(name) = self.writing_bind().map(String::from) else {
+            return Outcome::Ok(());
+        };
+        match self.sessions.get_mut(&name) {
+            Some(session) => session.commit(cx).await,
+            None => Outcome::Ok(()),
+        }
+    }
+
+    /// Roll back the bind with uncommitted changes.
+    pub async fn rollback(&mut self, cx: &Cx) -> Outcome<(), Error> {
+        let Some(name) = self.writing_bind().map(String::from) else {
+            return Outcome::Ok(());
+        };
+        match self.sessions.get_mut(&name) {
+            Some(session) => session.rollback(cx).await,
+            None => Outcome::Ok(()),
+        }
+    }
+}
+
+fn has_changes<C: Connection>(session: &Session<C>) -> bool {
+    session.in_transaction()
+        || session.pending_new_count() > 0
+        || session.pending_dirty_count() > 0
+        || session.pending_delete_count() > 0
+}
@@ -0,0 +1,211 @@
+//! Running independent queries concurrently.
+//!
+//! [`join_queries!`](crate::join_queries) checks out one connection per
+//! query, runs the queries concurrently and returns their results as a
+//! tuple. The first query to fail, be cancelled or panic ends the join: the
+//! other queries are dropped, returning their connections to the pool, and
+//! its outcome is returned.
+//!
+//! ```rust,ignore
+//! use sqlmodel::join_queries;
+//!
+//! // `cx: &Cx`, copied into each query
+//! let (users, orders, total) = join_queries!(cx,
+//!     |conn| async move { conn.query(cx, "SELECT * FROM users", &[]).await } => (&pool, connect),
+//!     |conn| async move { conn.query(cx, "SELECT * FROM orders", &[]).await } => (&pool, connect),
+//!     |conn| async move { conn.query_one(cx, "SELECT sum(total) FROM orders", &[]).await } => (&binds, "analytics"),
+//! )
+//! .await?;
+//! ```
+//!
+//! Each query receives its own [`PooledConnection`] by value, so no query
+//! ever waits on a connection held by another part of the same join. The
+//! queries are polled by the calling task, inside its region: nothing
+//! outlives the join.
+//!
+//! Once the task holds every connection of a pool, further queries fail with
+//! `PoolErrorKind::ReentrantAcquire` rather than waiting on themselves;
+//! queries that find the pool busy with other tasks wait as usual.
+
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use asupersync::{Cx, Outcome};
+use sqlmodel_core::{Connection, Error};
+use sqlmodel_pool::{Pool, PooledConnection};
+
+use crate::binds::Binds;
+
+/// Somewhere [`join_queries!`](crate::join_queries) can check out a
+/// connection.
+///
+/// Implemented for `(&Pool<C>, factory)`, with the factory used to open new
+/// connections, and for `(&Binds<C>, bind_name)`.
+pub trait ConnectionSource {
+    /// The pooled connection type.
+    type Connection: Connection;
+
+    /// Check out a connection.
+    fn acquire(
+        &self,
+        cx: &Cx,
+    ) -> impl Future<Output = Outcome<PooledConnection<Self::Connection>, Error>> + Send;
+}
+
+impl<C, F, Fut> ConnectionSource for (&Pool<C>, F)
+where
+    C: Connection,
+    F: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = Outcome<C, Error>> + Send,
+{
+    type Connection = C;
+
+    fn acquire(&self, cx: &Cx) -> impl Future<Output = Outcome<PooledConnection<C>, Error>> + Send {
+        self.0.acquire(cx, &self.1)
+    }
+}
+
+impl<C: Connection> ConnectionSource for (&Binds<C>, &str) {
+    type Connection = C;
+
+    fn acquire(&self, cx: &Cx) -> impl Future<Output = Outcome<PooledConnection<C>, Error>> + Send {
+        self.0.acquire(cx, self.1)
+    }
+}
+
+/// Check out a connection from `source` and run `query` on it.
+#[doc(hidden)]
+pub async fn run_on<S, Q, Fut, T>(cx: &Cx, source: S, query: Q) -> Outcome<T, Error>
+where
+    S: ConnectionSource,
+    Q: FnOnce(PooledConnection<S::Connection>) -> Fut,
+    Fut: Future<Output = Outcome<T, Error>>,
+{
+    let conn = match source.acquire(cx).await {
+        Outcome::Ok(conn) => conn,
+        Outcome::Err(e) => return Outcome::Err(e),
+        Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+        Outcome::Panicked(p) => return Outcome::Panicked(p),
+    };
+    query(conn).await
+}
+
+/// One query of a join: its future until it completes, then its result.
+#[doc(hidden)]
+pub struct Branch<F: Future> {
+    future: Option<Pin<Box<F>>>,
+    output: Option<F::Output>,
+}
+
+impl<T, F> Branch<F>
+where
+    F: Future<Output = Outcome<T, Error>>,
+{
+    /// A branch running `future`.
+    #[must_use]
+    pub fn new(future: F) -> Self {
+        Self {
+            future: Some(Box::pin(future)),
+            output: None,
+        }
+    }
+
+    /// Poll the query. Ready with `None` once it succeeded, with `Some` if
+    /// its failure ends the join.
+    pub fn poll_branch(
+        &mut self,
+        task: &mut Context<'_>,
+    ) -> Poll<Option<Outcome<Infallible, Error>>> {
+        let Some(future) = self.future.as_mut() else {
+            return Poll::Ready(None);
+        };
+        let outcome = match future.as_mut().poll(task) {
+            Poll::Ready(outcome) => outcome,
+            Poll::Pending => return Poll::Pending,
+        };
+        self.future = None;
+        match outcome {
+            Outcome::Ok(value) => {
+                self.output = Some(Outcome::Ok(value));
+                Poll::Ready(None)
+            }
+            Outcome::Err(e) => Poll::Ready(Some(Outcome::Err(e))),
+            Outcome::Cancelled(r) => Poll::Ready(Some(Outcome::Cancelled(r))),
+            Outcome::Panicked(p) => Poll::Ready(Some(Outcome::Panicked(p))),
+        }
+    }
+
+    /// The result of a query that succeeded.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless [`poll_branch`](Self::poll_branch) reported success.
+    pub fn take(&mut self) -> T {
+        match self.output.take() {
+            Some(Outcome::Ok(value)) => value,
+            _ => panic!("join branch polled to completion before its result is taken"),
+        }
+    }
+}
+
+/// Turn a branch failure into the join's outcome.
+#[doc(hidden)]
+#[must_use]
+pub fn fail<T>(failure: Outcome<Infallible, Error>) -> Outcome<T, Error> {
+    match failure {
+        Outcome::Ok(never) => match never {},
+        Outcome::Err(e) => Outcome::Err(e),
+        Outcome::Cancelled(r) => Outcome::Cancelled(r),
+        Outcome::Panicked(p) => Outcome::Panicked(p),
+    }
+}
+
+/// Run queries concurrently, each on its own pooled connection.
+///
+/// Takes the `&Cx` and one `query => source` pair per query, where `query`
+/// is a closure from a [`PooledConnection`] to a future of
+/// `Outcome<T, Error>` and `source` is a
+/// [`ConnectionSource`](crate::concurrent::ConnectionSource). Evaluates to a
+/// future of `Outcome<(T1, T2, ...), Error>`; see the
+/// [module docs](crate::concurrent).
+#[macro_export]
+macro_rules! join_queries {
+    (@{ $cx:expr; ($($count:tt)*) $( ( $($skip:tt)* ) $query:expr => $source:expr , )* }) => {{
+        let cx: &$crate::Cx = $cx;
+        let mut branches = ($(
+            $crate::concurrent::Branch::new(
+                $crate::concurrent::run_on(cx, $source, $query),
+            ),
+        )*);
+        ::std::future::poll_fn(move |task| {
+            let mut pending = false;
+            $(
+                let ($($skip,)* branch, ..) = &mut branches;
+                match branch.poll_branch(task) {
+                    ::std::task::Poll::Ready(Some(failure)) => {
+                        return ::std::task::Poll::Ready($crate::concurrent::fail(failure));
+                    }
+                    ::std::task::Poll::Ready(None) => {}
+                    ::std::task::Poll::Pending => pending = true,
+                }
+            )*
+            if pending {
+                return ::std::task::Poll::Pending;
+            }
+            ::std::task::Poll::Ready($crate::Outcome::Ok(($({
+                let ($($skip,)* branch, ..) = &mut branches;
+                branch.take()
+            },)*)))
+        })
+    }};
+    (@{ $cx:expr; ($($count:tt)*) $($done:tt)* } $query:expr => $source:expr , $($rest:tt)*) => {
+        $crate::join_queries!(
+            @{ $cx; ($($count)* _) $($done)* ($($count)*) $query => $source , } $($rest)*
+        )
+    };
+    ($cx:expr, $($query:expr => $source:expr),+ $(,)?) => {
+        $crate::join_queries!(@{ $cx; () } $($query => $source ,)+)
+    };
+}
@@ -0,0 +1,536 @@
+//! Background job queue backed by a database table.
+//!
+//! Jobs are ordinary rows of [`Job`], so enqueueing one is part of whatever
+//! transaction the caller's [`Session`] is in: if the business write rolls
+//! back, the job never existed. Workers claim jobs with
+//! `SELECT ... FOR UPDATE SKIP LOCKED`, so any number of them can poll the
+//! same queue without handing the same job out twice.
+//!
+//! A failed job is retried with exponential backoff until it reaches its
+//! `max_attempts`, after which it is marked dead and kept as a dead letter
+//! (see [`JobQueue::dead_letters`]).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use sqlmodel::jobs::{Job, JobHandler, JobQueue, Worker};
+//!
+//! // Producer: enqueue alongside the write that caused it.
+//! let queue = JobQueue::new("email");
+//! session.add(&order);
+//! queue.enqueue(&cx, &mut session, Job::new("send_receipt", &order.id)?).await;
+//! session.commit(&cx).await;
+//!
+//! // Consumer.
+//! struct SendReceipt;
+//! impl JobHandler for SendReceipt {
+//!     async fn handle(&self, cx: &Cx, job: &Job) -> Outcome<(), Error> {
+//!         let order_id: i64 = job.payload()?;
+//!         // ...
+//!         Outcome::Ok(())
+//!     }
+//! }
+//! Worker::new(queue, SendReceipt).run(&cx, &pool, || connect(&cx)).await;
+//! ```
+//!
+//! SQLite has no row locks; there the claim query runs without the lock
+//! clause and relies on the database-level write lock instead.
+
+use std::future::Future;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use asupersync::{CancelReason, Cx, Outcome};
+use serde::{Deserialize, Serialize};
+use sqlmodel_core::dialect::SqlDialect;
+use sqlmodel_core::{Connection, Dialect, Error, Model, TransactionOps, Value};
+use sqlmodel_pool::Pool;
+use sqlmodel_session::Session;
+
+/// Where a [`Job`] is in its lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JobStatus {
+    /// Waiting to run (including retries scheduled for later).
+    Pending,
+    /// Ran successfully.
+    Done,
+    /// Failed `max_attempts` times; kept as a dead letter.
+    Dead,
+}
+
+impl JobStatus {
+    /// The value stored in the `status` column.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Done => "done",
+            Self::Dead => "dead",
+        }
+    }
+
+    /// Parse a `status` column value.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(Self::Pending),
+            "done" => Some(Self::Done),
+            "dead" => Some(Self::Dead),
+            _ => None,
+        }
+    }
+}
+
+/// A queued unit of work, stored in the `sqlmodel_jobs` table.
+///
+/// Create the table like any other model, e.g. with
+/// `SchemaBuilder::new().create_table::<Job>()`.
+#[derive(crate::Model, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[sqlmodel(table = "sqlmodel_jobs")]
+pub struct Job {
+    /// Row id, assigned by the database.
+    #[sqlmodel(primary_key, auto_increment)]
+    pub id: Option<i64>,
+    /// Queue name; set by [`JobQueue::enqueue`].
+    pub queue: String,
+    /// Application-defined job type, used by handlers to dispatch.
+    pub kind: String,
+    /// JSON-encoded payload.
+    pub payload: String,
+    /// One of [`JobStatus::as_str`].
+    pub status: String,
+    /// Number of times a worker has run this job.
+    pub attempts: i32,
+    /// Attempts allowed before the job is marked dead.
+    pub max_attempts: i32,
+    /// Earliest time the job may run, in Unix milliseconds.
+    pub run_at: i64,
+    /// Error from the most recent failed attempt.
+    #[sqlmodel(nullable)]
+    pub last_error: Option<String>,
+    /// Enqueue time, in Unix milliseconds.
+    pub created_at: i64,
+}
+
+/// Default number of attempts before a job is marked dead.
+pub const DEFAULT_MAX_ATTEMPTS: i32 = 5;
+
+impl Job {
+    /// Build a job of type `kind` with a JSON-serialized payload.
+    ///
+    /// The job runs as soon as it is enqueued, up to
+    /// [`DEFAULT_MAX_ATTEMPTS`] times.
+    #[allow(clippy::result_large_err)]
+    pub fn new(kind: impl Into<String>, payload: &impl Serialize) -> Result<Self, Error> {
+        let payload = serde_json::to_string(payload).map_err(|e| Error::Serde(e.to_string()))?;
+        Ok(Self {
+            id: None,
+            queue: String::new(),
+            kind: kind.into(),
+            payload,
+            status: JobStatus::Pending.as_str().to_string(),
+            attempts: 0,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            run_at: 0,
+            last_error: None,
+            created_at: 0,
+        })
+    }
+
+    /// Do not run the job before `at`.
+    #[must_use]
+    pub fn run_at(mut self, at: SystemTime) -> Self {
+        self.run_at = unix_ms(at);
+        self
+    }
+
+    /// Allow up to `n` attempts before marking the job dead.
+    #[must_use]
+    pub fn max_attempts(mut self, n: i32) -> Self {
+        self.max_attempts = n.max(1);
+        self
+    }
+
+    /// Decode the JSON payload.
+    #[allow(clippy::result_large_err)]
+    pub fn payload<T: for<'de> Deserialize<'de>>(&self) -> Result<T, Error> {
+        serde_json::from_str(&self.payload).map_err(|e| Error::Serde(e.to_string()))
+    }
+
+    /// The parsed `status` column, if it holds a known value.
+    pub fn status(&self) -> Option<JobStatus> {
+        JobStatus::parse(&self.status)
+    }
+}
+
+/// A named queue of [`Job`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobQueue {
+    name: String,
+}
+
+impl JobQueue {
+    /// Refer to the queue called `name`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+
+    /// The queue name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Add `job` to this queue inside the session's current transaction.
+    ///
+    /// The job is flushed immediately but only becomes visible to workers
+    /// when the caller commits.
+    pub async fn enqueue<C: Connection>(
+        &self,
+        cx: &Cx,
+        session: &mut Session<C>,
+        mut job: Job,
+    ) -> Outcome<(), Error> {
+        let now = unix_ms(SystemTime::now());
+        job.id = None;
+        job.queue.clone_from(&self.name);
+        job.status = JobStatus::Pending.as_str().to_string();
+        job.created_at = now;
+        if job.run_at == 0 {
+            job.run_at = now;
+        }
+        session.add(&job);
+        session.flush(cx).await
+    }
+
+    /// Jobs on this queue that exhausted their attempts, oldest first.
+    pub async fn dead_letters<C: Connection>(&self, cx: &Cx, conn: &C) -> Outcome<Vec<Job>, Error> {
+        let dialect = conn.sql_dialect();
+        let sql = format!(
+            "SELECT * FROM {} WHERE {} = {} AND {} = {} ORDER BY {}",
+            dialect.quote_identifier(Job::TABLE_NAME),
+            dialect.quote_identifier("queue"),
+            dialect.placeholder(1),
+            dialect.quote_identifier("status"),
+            dialect.placeholder(2),
+            dialect.quote_identifier("id"),
+        );
+        let params = [
+            Value::Text(self.name.clone()),
+            Value::Text(JobStatus::Dead.as_str().to_string()),
+        ];
+        let rows = match conn.query(cx, &sql, &params).await {
+            Outcome::Ok(rows) => rows,
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        };
+        match rows.iter().map(Job::from_row).collect() {
+            Ok(jobs) => Outcome::Ok(jobs),
+            Err(e) => Outcome::Err(e),
+        }
+    }
+}
+
+/// Runs claimed jobs for a [`Worker`].
+pub trait JobHandler: Send + Sync {
+    /// Process `job`. An `Err` schedules a retry (or marks the job dead);
+    /// cancellation rolls back the claim so another worker can pick it up.
+    fn handle(&self, cx: &Cx, job: &Job) -> impl Future<Output = Outcome<(), Error>> + Send;
+}
+
+/// Claims jobs from a [`JobQueue`] and hands them to a [`JobHandler`].
+#[derive(Debug)]
+pub struct Worker<H> {
+    queue: JobQueue,
+    handler: H,
+    backoff_base: Duration,
+    backoff_max: Duration,
+    poll_interval: Duration,
+}
+
+impl<H: JobHandler> Worker<H> {
+    /// Work `queue` with `handler`.
+    ///
+    /// Defaults: 1s backoff doubling up to 1h, polling every 500ms when idle.
+    pub fn new(queue: JobQueue, handler: H) -> Self {
+        Self {
+            queue,
+            handler,
+            backoff_base: Duration::from_secs(1),
+            backoff_max: Duration::from_secs(3600),
+            poll_interval: Duration::from_millis(500),
+        }
+    }
+
+    /// Retry after `base * 2^(attempts - 1)`, capped at `max`.
+    #[must_use]
+    pub fn backoff(mut self, base: Duration, max: Duration) -> Self {
+        self.backoff_base = base;
+        self.backoff_max = max;
+        self
+    }
+
+    /// How long [`Worker::run`] waits before polling an empty queue again.
+    #[must_use]
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Delay before the retry that follows the `attempts`-th failure.
+    pub fn retry_delay(&self, attempts: i32) -> Duration {
+        let exponent = u32::try_from(attempts.saturating_sub(1))
+            .unwrap_or(0)
+            .min(31);
+        self.backoff_base
+            .saturating_mul(1 << exponent)
+            .min(self.backoff_max)
+    }
+
+    /// Claim and run at most one ready job.
+    ///
+    /// The job row stays locked for the duration of the handler and is
+    /// updated in the same transaction. Returns the job's new status, or
+    /// `None` if nothing was ready.
+    pub async fn run_once<C: Connection>(
+        &self,
+        cx: &Cx,
+        conn: &C,
+    ) -> Outcome<Option<JobStatus>, Error> {
+        let dialect = conn.sql_dialect();
+        let tx = match conn.begin(cx).await {
+            Outcome::Ok(tx) => tx,
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        };
+
+        let now = unix_ms(SystemTime::now());
+        let params = [
+            Value::Text(self.queue.name.clone()),
+            Value::Text(JobStatus::Pending.as_str().to_string()),
+            Value::BigInt(now),
+        ];
+        let row = match tx.query_one(cx, &claim_sql(dialect), &params).await {
+            Outcome::Ok(row) => row,
+            Outcome::Err(e) => return rollback_with(cx, tx, Outcome::Err(e)).await,
+            Outcome::Cancelled(r) => return rollback_with(cx, tx, Outcome::Cancelled(r)).await,
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        };
+        let Some(row) = row else {
+            return rollback_with(cx, tx, Outcome::Ok(None)).await;
+        };
+        let job = match Job::from_row(&row) {
+            Ok(job) => job,
+            Err(e) => return rollback_with(cx, tx, Outcome::Err(e)).await,
+        };
+
+        let attempts = job.attempts.saturating_add(1);
+        let (status, run_at, last_error) = match self.handler.handle(cx, &job).await {
+            Outcome::Ok(()) => (JobStatus::Done, job.run_at, job.last_error.clone()),
+            Outcome::Err(e) if attempts >= job.max_attempts => {
+                (JobStatus::Dead, job.run_at, Some(e.to_string()))
+            }
+            Outcome::Err(e) => {
+                let delay =
+                    i64::try_from(self.retry_delay(attempts).as_millis()).unwrap_or(i64::MAX);
+                (
+                    JobStatus::Pending,
+                    now.saturating_add(delay),
+                    Some(e.to_string()),
+                )
+            }
+            Outcome::Cancelled(r) => return rollback_with(cx, tx, Outcome::Cancelled(r)).await,
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        };
+
+        let params = [
+            Value::Text(status.as_str().to_string()),
+            Value::Int(attempts),
+            Value::BigInt(run_at),
+            last_error.map_or(Value::Null, Value::Text),
+            Value::BigInt(job.id.unwrap_or_default()),
+        ];
+        match tx.execute(cx, &update_sql(dialect), &params).await {
+            Outcome::Ok(_) => {}
+            Outcome::Err(e) => return rollback_with(cx, tx, Outcome::Err(e)).await,
+            Outcome::Cancelled(r) => return rollback_with(cx, tx, Outcome::Cancelled(r)).await,
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        }
+        match tx.commit(cx).await {
+            Outcome::Ok(()) => Outcome::Ok(Some(status)),
+            Outcome::Err(e) => Outcome::Err(e),
+            Outcome::Cancelled(r) => Outcome::Cancelled(r),
+            Outcome::Panicked(p) => Outcome::Panicked(p),
+        }
+    }
+
+    /// Run ready jobs until none are left, returning how many ran.
+    pub async fn drain<C, F, Fut>(&self, cx: &Cx, pool: &Pool<C>, factory: F) -> Outcome<u64, Error>
+    where
+        C: Connection,
+        F: Fn() -> Fut,
+        Fut: Future<Output = Outcome<C, Error>>,
+    {
+        let mut processed = 0;
+        loop {
+            if cx.is_cancel_requested() {
+                return Outcome::Cancelled(CancelReason::user("job worker cancelled"));
+            }
+            let conn = match pool.acquire(cx, &factory).await {
+                Outcome::Ok(conn) => conn,
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            };
+            match self.run_once(cx, &conn).await {
+                Outcome::Ok(Some(_)) => processed += 1,
+                Outcome::Ok(None) => return Outcome::Ok(processed),
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            }
+        }
+    }
+
+    /// Work the queue until `cx` is cancelled.
+    ///
+    /// Errors from a single pass are logged and the worker keeps going
+    /// after the poll interval; the outcome is always `Cancelled` or
+    /// `Panicked`.
+    pub async fn run<C, F, Fut>(&self, cx: &Cx, pool: &Pool<C>, factory: F) -> Outcome<(), Error>
+    where
+        C: Connection,
+        F: Fn() -> Fut,
+        Fut: Future<Output = Outcome<C, Error>>,
+    {
+        loop {
+            match self.drain(cx, pool, &factory).await {
+                Outcome::Ok(_) => {}
+                Outcome::Err(e) => {
+                    tracing::warn!(queue = %self.queue.name, error = %e, "job worker pass failed");
+                }
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            }
+            if cx.is_cancel_requested() {
+                return Outcome::Cancelled(CancelReason::user("job worker cancelled"));
+            }
+            std::thread::sleep(self.poll_interval);
+        }
+    }
+}
+
+/// Roll back `tx` and return `outcome`, unless the rollback itself fails.
+async fn rollback_with<T, Tx: TransactionOps>(
+    cx: &Cx,
+    tx: Tx,
+    outcome: Outcome<T, Error>,
+) -> Outcome<T, Error> {
+    match tx.rollback(cx).await {
+        Outcome::Ok(()) => outcome,
+        Outcome::Err(e) => match outcome {
+            Outcome::Err(original) => Outcome::Err(original),
+            _ => Outcome::Err(e),
+        },
+        Outcome::Cancelled(r) => Outcome::Cancelled(r),
+        Outcome::Panicked(p) => Outcome::Panicked(p),
+    }
+}
+
+/// The next ready job on a queue, locked so concurrent workers skip it.
+fn claim_sql(dialect: &'static dyn SqlDialect) -> String {
+    let q = |name| dialect.quote_identifier(name);
+    let lock = if dialect.base_dialect() == Dialect::Sqlite {
+        ""
+    } else {
+        " FOR UPDATE SKIP LOCKED"
+    };
+    format!(
+        "SELECT * FROM {} WHERE {} = {} AND {} = {} AND {} <= {} ORDER BY {}, {} LIMIT 1{lock}",
+        q(Job::TABLE_NAME),
+        q("queue"),
+        dialect.placeholder(1),
+        q("status"),
+        dialect.placeholder(2),
+        q("run_at"),
+        dialect.placeholder(3),
+        q("run_at"),
+        q("id"),
+    )
+}
+
+/// Record the result of an attempt.
+fn update_sql(dialect: &'static dyn SqlDialect) -> String {
+    let q = |name| dialect.quote_identifier(name);
+    format!(
+        "UPDATE {} SET {} = {}, {} = {}, {} = {}, {} = {} WHERE {} = {}",
+        q(Job::TABLE_NAME),
+        q("status"),
+        dialect.placeholder(1),
+        q("attempts"),
+        dialect.placeholder(2),
+        q("run_at"),
+        dialect.placeholder(3),
+        q("last_error"),
+        dialect.placeholder(4),
+        q("id"),
+        dialect.placeholder(5),
+    )
+}
+
+pub(crate) fn unix_ms(at: SystemTime) -> i64 {
+    at.duration_since(UNIX_EPOCH)
+        .map_or(0, |d| i64::try_from(d.as_millis()).unwrap_or(i64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Noop;
+
+    impl JobHandler for Noop {
+        async fn handle(&self, _cx: &Cx, _job: &Job) -> Outcome<(), Error> {
+            Outcome::Ok(())
+        }
+    }
+
+    #[test]
+    fn claim_sql_locks_rows_except_on_sqlite() {
+        assert_eq!(
+            claim_sql(Dialect::Postgres.as_dyn()),
+            "SELECT * FROM \"sqlmodel_jobs\" WHERE \"queue\" = $1 AND \"status\" = $2 \
+             AND \"run_at\" <= $3 ORDER BY \"run_at\", \"id\" LIMIT 1 FOR UPDATE SKIP LOCKED"
+        );
+        assert!(claim_sql(Dialect::Mysql.as_dyn()).ends_with("LIMIT 1 FOR UPDATE SKIP LOCKED"));
+        assert!(claim_sql(Dialect::Sqlite.as_dyn()).ends_with("LIMIT 1"));
+    }
+
+    #[test]
+    fn update_sql_sets_attempt_columns() {
+        assert_eq!(
+            update_sql(Dialect::Sqlite.as_dyn()),
+            "UPDATE \"sqlmodel_jobs\" SET \"status\" = ?1, \"attempts\" = ?2, \
+             \"run_at\" = ?3, \"last_error\" = ?4 WHERE \"id\" = ?5"
+        );
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_max() {
+        let worker = Worker::new(JobQueue::new("q"), Noop)
+            .backoff(Duration::from_millis(100), Duration::from_secs(1));
+        assert_eq!(worker.retry_delay(1), Duration::from_millis(100));
+        assert_eq!(worker.retry_delay(2), Duration::from_millis(200));
+        assert_eq!(worker.retry_delay(4), Duration::from_millis(800));
+        assert_eq!(worker.retry_delay(5), Duration::from_secs(1));
+        assert_eq!(worker.retry_delay(1000), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn job_payload_round_trips() {
+        let job = Job::new("greet", &("ada", 36)).unwrap().max_attempts(0);
+        assert_eq!(job.status(), Some(JobStatus::Pending));
+        assert_eq!(job.max_attempts, 1);
+        let (name, age): (String, i32) = job.payload().unwrap();
+        assert_eq!((name.as_str(), age), ("ada", 36));
+        assert!(job.payload::<bool>().is_err());
+    }
+}
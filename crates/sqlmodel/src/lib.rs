@@ -10,7 +10,8 @@
 //!   `Expr`, and the query macros.
 //! - **Facade over sub-crates**: wraps `sqlmodel-core`, `sqlmodel-macros`, `sqlmodel-query`,
 //!   `sqlmodel-schema`, `sqlmodel-session`, and `sqlmodel-pool`.
-//! - **Optional console**: feature-gated integration with `sqlmodel-console` for rich output.
+//! - **Optional console**: feature-gated integration with `sqlmodel-console` for rich output,
+//!   including an interactive query REPL (`sqlmodel::repl`).
 //!
 //! # When To Use This Crate
 //!
@@ -95,16 +96,28 @@
 //! - **`console` feature**: Enable rich terminal output via `sqlmodel-console`
 
 // Re-export all public types from sub-crates
-pub use sqlmodel_core::connection::{ConnectionConfig, SslMode, Transaction};
+pub use sqlmodel_core::connection::{
+    ConnectionConfig, QueryResult, ResultColumn, SslMode, Transaction,
+};
 pub use sqlmodel_core::{
     // asupersync re-exports
     Budget,
+    // Budget-aware admission
+    BudgetedConnection,
     // Core types
+    CachePolicy,
+    // Dynamic credentials
+    CachedCredentials,
+    CommentInjector,
     Connection,
+    Credentials,
+    CredentialsProvider,
     Cx,
+    Dialect,
     DumpMode,
     DumpOptions,
     DumpResult,
+    EnvCredentials,
     Error,
     Field,
     FieldInfo,
@@ -113,45 +126,88 @@ pub use sqlmodel_core::{
     // Inheritance types
     InheritanceInfo,
     InheritanceStrategy,
+    // Statement interceptors
+    InterceptedConnection,
+    InterceptorChain,
+    // Query logging
+    JsonLinesQueryLogger,
+    LoggedConnection,
     Model,
+    ModelDescriptor,
     ModelDump,
+    ModelRegistry,
+    OrderByColumn,
     Outcome,
+    QueryCost,
+    QueryLogEntry,
+    QueryLogger,
+    QueryOptions,
+    QueryStatus,
+    RedactionPolicy,
     RegionId,
+    RejectUnfilteredWrites,
     Result,
     Row,
+    SqlCommenter,
     SqlEnum,
     SqlModelDump,
     SqlModelValidate,
+    SqlScalar,
     SqlType,
+    Statement,
+    StatementInterceptor,
+    StaticCredentials,
+    TableNamespace,
     TaskId,
+    ToParams,
+    TracingQueryLogger,
     TrackedModel,
     TypeInfo,
+    Validate,
     ValidateInput,
     ValidateOptions,
     ValidateResult,
+    ValidationError,
+    ValidationErrorKind,
+    ValidationErrors,
     Value,
+    ValueRef,
 };
 
-pub use sqlmodel_macros::{Model, SqlEnum, Validate};
+// Pluggable dialects, kept under a module so `SqlDialect` does not clash with
+// the console's `renderables::SqlDialect` in glob imports
+pub use sqlmodel_core::dialect;
+// `ModelDyn` stays out of the root so `use sqlmodel::*` keeps `Model` calls unambiguous
+pub use sqlmodel_core::registry;
+
+pub use sqlmodel_macros::{Model, Repository, SqlEnum, Validate, embed_migrations};
 
 pub use sqlmodel_query::{
-    BinaryOp, Expr, Join, JoinType, Limit, Offset, OrderBy, PolymorphicJoined, PolymorphicJoined2,
-    PolymorphicJoined3, PolymorphicJoinedSelect, PolymorphicJoinedSelect2,
-    PolymorphicJoinedSelect3, QueryBuilder, Select, UnaryOp, Where, delete, insert, raw_execute,
-    raw_query, select, update,
+    BatchProgress, BinaryOp, Cursor, Expr, FieldSet, FilterSpec, Join, JoinType, Limit,
+    MergeBuilder, Offset, OrderBy, PolymorphicJoined, PolymorphicJoined2, PolymorphicJoined3,
+    PolymorphicJoinedSelect, PolymorphicJoinedSelect2, PolymorphicJoinedSelect3, PreparedQuery,
+    Query, QueryBuilder, QueryParams, RawQuery, Select, SortSpec, UnaryOp, Where, delete, insert,
+    merge, query, raw_execute, raw_query, select, update,
 };
 
 pub use sqlmodel_schema::{
     CreateTable, Migration, MigrationRunner, MigrationStatus, SchemaBuilder, create_all,
-    create_table, drop_table,
+    create_all_registered, create_table, drop_table,
 };
 
 pub use sqlmodel_pool::{
-    Pool, PoolConfig, PoolStats, PooledConnection, ReplicaPool, ReplicaStrategy,
+    CircuitState, ConnectionHook, DrainPolicy, HealthReport, HealthStatus, HookFuture, Pool,
+    PoolConfig, PoolStats, PooledConnection, QueryLimits, ReplicaPool, ReplicaStrategy,
 };
 
+#[cfg(feature = "moka")]
+pub use sqlmodel_session::MokaCache;
+#[cfg(feature = "redis")]
+pub use sqlmodel_session::RedisCache;
 pub use sqlmodel_session::{
-    GetOptions, ObjectKey, ObjectState, Session, SessionConfig, SessionDebugInfo,
+    CacheFuture, CacheKey, EagerRelation, EntityCache, FlushTarget, GetOptions, LoadPlan, Loader,
+    ObjectKey, ObjectState, Page, Repository, Session, SessionConfig, SessionDebugInfo,
+    SessionRepository, TruncateOptions, UpsertCounts, load,
 };
 
 /// Wrap a model struct literal and track which fields were explicitly provided.
@@ -189,10 +245,49 @@ pub mod connection_session;
 pub mod session;
 pub use connection_session::{ConnectionSession, ConnectionSessionBuilder};
 
+// Background job queue (SKIP LOCKED workers)
+pub mod jobs;
+
+// Periodic tasks with per-task leader election
+pub mod scheduler;
+
+// Multiple database binds with per-model routing
+pub mod binds;
+
+// Concurrent queries on separate pooled connections
+pub mod concurrent;
+
 // Console-enabled session extension trait
 #[cfg(feature = "console")]
 pub use connection_session::ConnectionBuilderExt;
 
+// Interactive REPL (feature-gated)
+#[cfg(feature = "console")]
+pub mod repl;
+
+// Console query log sink (feature-gated)
+#[cfg(feature = "console")]
+pub mod query_log;
+#[cfg(feature = "console")]
+pub use query_log::ConsoleQueryLogger;
+
+// Test harness: fixtures, factories, mock connections and the test
+// attribute (feature-gated)
+#[cfg(feature = "testing")]
+pub mod testing {
+    //! Re-export of `sqlmodel-testing`, plus the `#[sqlmodel::testing::test]`
+    //! attribute. The attribute lives here rather than at the crate root so
+    //! that `use sqlmodel::*` does not shadow the built-in `#[test]`.
+    pub use sqlmodel_macros::test;
+    pub use sqlmodel_testing::*;
+}
+
+// Apache Arrow interop (feature-gated)
+#[cfg(feature = "arrow")]
+pub use sqlmodel_arrow as arrow;
+#[cfg(feature = "graphql")]
+pub use sqlmodel_graphql as graphql;
+
 // Global console support (feature-gated)
 #[cfg(feature = "console")]
 mod global_console;
@@ -647,6 +742,7 @@ pub mod prelude {
         Hybrid,
         Join,
         JoinType,
+        LoadPlan,
         Migration,
         MigrationRunner,
         Model,
@@ -664,7 +760,9 @@ pub mod prelude {
         // Pool
         Pool,
         PoolConfig,
+        Query,
         RegionId,
+        Repository,
         Result,
         Row,
         Select,
@@ -673,8 +771,10 @@ pub mod prelude {
         SessionConfig,
         SqlModelDump,
         SqlModelValidate,
+        SqlScalar,
         TaskId,
         TrackedModel,
+        TruncateOptions,
         ValidateInput,
         ValidateOptions,
         ValidateResult,
@@ -684,12 +784,18 @@ pub mod prelude {
         // Macros
         delete,
         insert,
+        load,
+        query,
         select,
         update,
     };
     // Derive macros (re-export only Validate/SqlEnum since Model trait conflicts)
     pub use sqlmodel_macros::{SqlEnum, Validate};
 
+    // Arrow query results when feature enabled
+    #[cfg(feature = "arrow")]
+    pub use sqlmodel_arrow::SelectArrowExt;
+
     // Console types when feature enabled
     #[cfg(feature = "console")]
     pub use crate::{
@@ -0,0 +1,118 @@
+//! Console sink for structured query logging (`console` feature).
+//!
+//! [`ConsoleQueryLogger`] prints each statement to stderr through a
+//! [`SqlModelConsole`], alongside the tracing and JSON-lines sinks in
+//! [`sqlmodel_core::query_log`].
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use sqlmodel::prelude::*;
+//! use sqlmodel::{ConsoleQueryLogger, RedactionPolicy};
+//!
+//! let console = Arc::new(SqlModelConsole::new());
+//! let pool = Pool::new(PoolConfig::new(10))
+//!     .with_query_logger(Arc::new(ConsoleQueryLogger::new(console, RedactionPolicy::Hashed)));
+//! ```
+
+use std::sync::Arc;
+
+use sqlmodel_console::SqlModelConsole;
+use sqlmodel_core::query_log::{QueryLogEntry, QueryLogger, QueryStatus, RedactionPolicy};
+
+/// Prints executed statements to the console.
+///
+/// Plain and rich modes print one readable line per statement; JSON mode
+/// prints the same object the JSON-lines sink writes. Failures go through
+/// [`SqlModelConsole::error`], everything else through
+/// [`SqlModelConsole::status`], so stdout stays clean either way.
+#[derive(Debug, Clone)]
+pub struct ConsoleQueryLogger {
+    console: Arc<SqlModelConsole>,
+    policy: RedactionPolicy,
+}
+
+impl ConsoleQueryLogger {
+    /// Log to `console` with the given redaction policy.
+    pub fn new(console: Arc<SqlModelConsole>, policy: RedactionPolicy) -> Self {
+        Self { console, policy }
+    }
+
+    /// Log to the global console, if one has been set.
+    pub fn global(policy: RedactionPolicy) -> Option<Self> {
+        crate::global_console().map(|console| Self::new(console, policy))
+    }
+
+    fn format(&self, entry: &QueryLogEntry<'_>) -> String {
+        if self.console.is_json() {
+            return entry.to_json(self.policy).to_string();
+        }
+
+        let ms = entry.duration.as_secs_f64() * 1000.0;
+        let mut line = match &entry.status {
+            QueryStatus::Ok { rows } => format!("{ms:.2}ms  {rows} rows  {}", entry.sql),
+            QueryStatus::Error(error) => format!("{ms:.2}ms  {}  -- {error}", entry.sql),
+            QueryStatus::Cancelled | QueryStatus::Panicked => {
+                format!("{ms:.2}ms  {}  -- {}", entry.sql, entry.status.as_str())
+            }
+        };
+        if let Some(params) = self.policy.redact(entry.params) {
+            line.push_str("  params=");
+            line.push_str(&serde_json::Value::Array(params).to_string());
+        }
+        line
+    }
+}
+
+impl QueryLogger for ConsoleQueryLogger {
+    fn log(&self, entry: &QueryLogEntry<'_>) {
+        let line = self.format(entry);
+        match entry.status {
+            QueryStatus::Ok { .. } => self.console.status(&line),
+            QueryStatus::Error(_) | QueryStatus::Cancelled | QueryStatus::Panicked => {
+                self.console.error(&line);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlmodel_console::OutputMode;
+    use sqlmodel_core::Value;
+    use std::time::Duration;
+
+    fn entry(params: &[Value], status: QueryStatus) -> QueryLogEntry<'_> {
+        QueryLogEntry {
+            sql: "SELECT * FROM users WHERE email = $1",
+            params,
+            duration: Duration::from_millis(4),
+            status,
+        }
+    }
+
+    #[test]
+    fn test_console_logger_format() {
+        let params = [Value::Text("alice@example.com".to_string())];
+
+        let plain = ConsoleQueryLogger::new(
+            Arc::new(SqlModelConsole::with_mode(OutputMode::Plain)),
+            RedactionPolicy::Hashed,
+        );
+        let line = plain.format(&entry(&params, QueryStatus::Ok { rows: 1 }));
+        assert!(line.starts_with("4.00ms  1 rows  SELECT"));
+        assert!(line.contains("params=[\"fnv1a:"));
+        assert!(!line.contains("alice"));
+
+        let json = ConsoleQueryLogger::new(
+            Arc::new(SqlModelConsole::with_mode(OutputMode::Json)),
+            RedactionPolicy::Omitted,
+        );
+        let line = json.format(&entry(&params, QueryStatus::Error("boom".to_string())));
+        let parsed: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed["error"], "boom");
+        assert!(parsed.get("params").is_none());
+    }
+}
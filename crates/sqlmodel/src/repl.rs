@@ -0,0 +1,308 @@
+//! Interactive REPL for ad-hoc queries (`console` feature).
+//!
+//! Runs SQL and `\d`-style inspection commands against any [`Connection`] —
+//! typically a connection checked out of a [`Pool`](crate::Pool) — and renders
+//! results with the console's output modes. Input parsing and rendering live
+//! in [`sqlmodel_console::repl`]; this module executes the commands.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use sqlmodel::prelude::*;
+//!
+//! let conn = pool.acquire(&cx, connect).await?;
+//! let stdin = std::io::stdin();
+//! sqlmodel::repl::run_repl(&cx, &*conn, stdin.lock(), &mut std::io::stdout(), OutputMode::detect())
+//!     .await?;
+//! ```
+//!
+//! Pass a [`ModelRegistry`] to [`run_repl_with_models`] to make `\m` list the
+//! application's models and show the schema each one expects.
+
+use std::io::{BufRead, Write};
+use std::time::Instant;
+
+use sqlmodel_console::OutputMode;
+use sqlmodel_console::renderables::{ColumnData, ForeignKeyData, IndexData, TableInfo};
+use sqlmodel_console::repl::{HELP, ReplCommand, ReplInput, ReplRenderer};
+use sqlmodel_core::{Connection, Cx, Dialect, Error, ModelRegistry, Outcome, Value};
+use sqlmodel_schema::introspect::{self, Introspector};
+use sqlmodel_schema::table_schema_from_descriptor;
+
+/// Run an interactive session reading commands from `input` until `\q` or EOF.
+///
+/// Statement errors are printed and the session continues; only I/O failures,
+/// cancellation, and panics end it early. Prompts are not written in JSON mode
+/// so the output stays machine-readable.
+pub async fn run_repl<C, R, W>(
+    cx: &Cx,
+    conn: &C,
+    input: R,
+    output: &mut W,
+    mode: OutputMode,
+) -> Outcome<(), Error>
+where
+    C: Connection,
+    R: BufRead,
+    W: Write,
+{
+    run_repl_with_models(cx, conn, input, output, mode, &ModelRegistry::new()).await
+}
+
+/// [`run_repl`] with `models` available to the `\m` commands.
+pub async fn run_repl_with_models<C, R, W>(
+    cx: &Cx,
+    conn: &C,
+    input: R,
+    output: &mut W,
+    mode: OutputMode,
+    models: &ModelRegistry,
+) -> Outcome<(), Error>
+where
+    C: Connection,
+    R: BufRead,
+    W: Write,
+{
+    let mut renderer = ReplRenderer::new(mode);
+    let mut repl_input = ReplInput::new();
+    let introspector = Introspector::new(schema_dialect(conn.dialect()));
+    let mut lines = input.lines();
+
+    loop {
+        if renderer.mode() != OutputMode::Json
+            && let Err(e) = write!(output, "{}", repl_input.prompt()).and_then(|()| output.flush())
+        {
+            return Outcome::Err(Error::Io(e));
+        }
+
+        let command = match lines.next() {
+            Some(Ok(line)) => match repl_input.push_line(&line) {
+                Some(command) => command,
+                None => continue,
+            },
+            Some(Err(e)) => return Outcome::Err(Error::Io(e)),
+            None => match repl_input.take_pending() {
+                Some(command) => command,
+                None => break,
+            },
+        };
+
+        let outcome = match command {
+            ReplCommand::Quit => break,
+            ReplCommand::Help => Outcome::Ok(HELP.to_string()),
+            ReplCommand::Invalid(message) => Outcome::Ok(renderer.render_error(&message)),
+            ReplCommand::Mode(mode) => {
+                renderer.set_mode(mode);
+                Outcome::Ok(format!("output mode: {mode}"))
+            }
+            ReplCommand::Sql(sql) => run_sql(cx, conn, &renderer, &sql).await,
+            ReplCommand::ListTables => introspector
+                .table_names(cx, conn)
+                .await
+                .map(|names| renderer.render_table_names(&names)),
+            ReplCommand::Describe(table) => introspector
+                .table_info(cx, conn, &table)
+                .await
+                .map(|info| renderer.render_table_info(table_info_display(info))),
+            ReplCommand::ListModels => {
+                let names: Vec<(&str, &str)> =
+                    models.iter().map(|m| (m.table_name, m.type_name)).collect();
+                Outcome::Ok(renderer.render_model_names(&names))
+            }
+            ReplCommand::DescribeModel(table) => Outcome::Ok(match models.get(&table) {
+                Some(model) => renderer
+                    .render_table_info(table_info_display(table_schema_from_descriptor(model))),
+                None => renderer.render_error(&format!(
+                    "no registered model for table '{table}' (see \\m)"
+                )),
+            }),
+        };
+
+        let rendered = match outcome {
+            Outcome::Ok(text) => text,
+            Outcome::Err(e) => renderer.render_error(&e.to_string()),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        };
+        if let Err(e) = writeln!(output, "{rendered}") {
+            return Outcome::Err(Error::Io(e));
+        }
+    }
+
+    Outcome::Ok(())
+}
+
+/// Execute one SQL statement and render its rows or affected count.
+async fn run_sql<C: Connection>(
+    cx: &Cx,
+    conn: &C,
+    renderer: &ReplRenderer,
+    sql: &str,
+) -> Outcome<String, Error> {
+    let started = Instant::now();
+    if returns_rows(sql) {
+        conn.query(cx, sql, &[]).await.map(|rows| {
+            let columns = rows
+                .first()
+                .map(|row| row.column_names().map(str::to_string).collect())
+                .unwrap_or_default();
+            let data = rows
+                .iter()
+                .map(|row| row.values().map(display_value).collect())
+                .collect();
+            renderer.render_rows(columns, data, started.elapsed())
+        })
+    } else {
+        conn.execute(cx, sql, &[])
+            .await
+            .map(|affected| renderer.render_affected(affected, started.elapsed()))
+    }
+}
+
+/// Whether `sql` produces a result set (and so must go through `query`).
+fn returns_rows(sql: &str) -> bool {
+    let first = sql
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_ascii_uppercase();
+    matches!(
+        first.as_str(),
+        "SELECT"
+            | "WITH"
+            | "VALUES"
+            | "TABLE"
+            | "SHOW"
+            | "EXPLAIN"
+            | "PRAGMA"
+            | "DESCRIBE"
+            | "DESC"
+    ) || sql.to_ascii_uppercase().contains("RETURNING")
+}
+
+/// Text shown for a value in a result table (`None` renders as NULL).
+fn display_value(value: &Value) -> Option<String> {
+    let text = match value {
+        Value::Null => return None,
+        Value::Default => "DEFAULT".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::TinyInt(n) => n.to_string(),
+        Value::SmallInt(n) => n.to_string(),
+        Value::Int(n) => n.to_string(),
+        Value::BigInt(n) => n.to_string(),
+        Value::Float(n) => n.to_string(),
+        Value::Double(n) => n.to_string(),
+        Value::Decimal(s) | Value::Text(s) => s.clone(),
+        Value::Bytes(b) => {
+            let hex: String = b.iter().map(|byte| format!("{byte:02x}")).collect();
+            format!("\\x{hex}")
+        }
+        Value::Date(d) => d.to_string(),
+        Value::Time(t) | Value::Timestamp(t) | Value::TimestampTz(t) => t.to_string(),
+        Value::Uuid(u) => {
+            let hex: String = u.iter().map(|byte| format!("{byte:02x}")).collect();
+            format!(
+                "{}-{}-{}-{}-{}",
+                &hex[0..8],
+                &hex[8..12],
+                &hex[12..16],
+                &hex[16..20],
+                &hex[20..32]
+            )
+        }
+        Value::Json(j) => j.to_string(),
+        Value::Array(items) => {
+            let items: Vec<String> = items
+                .iter()
+                .map(|v| display_value(v).unwrap_or_else(|| "NULL".to_string()))
+                .collect();
+            format!("{{{}}}", items.join(","))
+        }
+    };
+    Some(text)
+}
+
+fn schema_dialect(dialect: Dialect) -> introspect::Dialect {
+    match dialect {
+        Dialect::Postgres => introspect::Dialect::Postgres,
+        Dialect::Sqlite => introspect::Dialect::Sqlite,
+        Dialect::Mysql => introspect::Dialect::Mysql,
+    }
+}
+
+/// Convert introspected table metadata into the console's table panel.
+fn table_info_display(info: introspect::TableInfo) -> TableInfo {
+    let columns = info
+        .columns
+        .into_iter()
+        .map(|c| ColumnData {
+            name: c.name,
+            sql_type: c.sql_type,
+            nullable: c.nullable,
+            default: c.default,
+            primary_key: c.primary_key,
+            auto_increment: c.auto_increment,
+        })
+        .collect();
+    let indexes = info
+        .indexes
+        .into_iter()
+        .filter(|i| !i.primary)
+        .map(|i| IndexData {
+            name: i.name,
+            columns: i.columns,
+            unique: i.unique,
+        })
+        .collect();
+    let foreign_keys = info
+        .foreign_keys
+        .into_iter()
+        .map(|fk| ForeignKeyData {
+            name: fk.name,
+            column: fk.column,
+            foreign_table: fk.foreign_table,
+            foreign_column: fk.foreign_column,
+            on_delete: fk.on_delete,
+            on_update: fk.on_update,
+        })
+        .collect();
+
+    TableInfo::new(info.name, columns)
+        .with_primary_key(info.primary_key)
+        .with_indexes(indexes)
+        .with_foreign_keys(foreign_keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_returns_rows() {
+        assert!(returns_rows("select * from heroes"));
+        assert!(returns_rows("  WITH t AS (SELECT 1) SELECT * FROM t"));
+        assert!(returns_rows(
+            "INSERT INTO heroes (name) VALUES ('x') RETURNING id"
+        ));
+        assert!(!returns_rows("UPDATE heroes SET name = 'x'"));
+        assert!(!returns_rows("CREATE TABLE t (id INTEGER)"));
+    }
+
+    #[test]
+    fn test_display_value() {
+        assert_eq!(display_value(&Value::Null), None);
+        assert_eq!(display_value(&Value::BigInt(7)).as_deref(), Some("7"));
+        assert_eq!(
+            display_value(&Value::Bytes(vec![0xde, 0xad])).as_deref(),
+            Some("\\xdead")
+        );
+        assert_eq!(
+            display_value(&Value::Array(vec![Value::Int(1), Value::Null])).as_deref(),
+            Some("{1,NULL}")
+        );
+        assert_eq!(
+            display_value(&Value::Uuid([0x11; 16])).as_deref(),
+            Some("11111111-1111-1111-1111-111111111111")
+        );
+    }
+}
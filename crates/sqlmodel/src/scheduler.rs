@@ -0,0 +1,565 @@
+//! Periodic tasks with one leader per task across processes.
+//!
+//! Every process registers the same named tasks on a [`Scheduler`] and calls
+//! [`Scheduler::tick`] (or [`Scheduler::run`]) against the shared database.
+//! For each due task, the scheduler opens a transaction and takes an advisory
+//! lock keyed by the task name ([`lock_key`]); whoever gets the lock is that
+//! run's leader and everyone else skips it. Bookkeeping lives in the
+//! `sqlmodel_scheduled_tasks` table ([`TaskRecord`]) and is updated in the same
+//! transaction, so a crash mid-run leaves the task due for the next leader.
+//!
+//! | Dialect | Lock |
+//! |---------|------|
+//! | PostgreSQL | `pg_try_advisory_xact_lock(key)`, released at commit |
+//! | MySQL | `GET_LOCK(name, 0)`, released right after the transaction |
+//! | SQLite | none needed; the database write lock serializes runs |
+//!
+//! Tasks run one after another inside the call that claimed them, so none
+//! outlives the scheduler's [`Cx`]. Each task can carry a time budget; a run
+//! that exceeds it is recorded as failed.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//! use sqlmodel::scheduler::{Schedule, ScheduledTask, Scheduler};
+//!
+//! struct PurgeSessions;
+//! impl ScheduledTask for PurgeSessions {
+//!     async fn run(&self, cx: &Cx) -> Outcome<(), Error> {
+//!         // ...
+//!         Outcome::Ok(())
+//!     }
+//! }
+//!
+//! let scheduler = Scheduler::new().register(
+//!     "purge_sessions",
+//!     Schedule::every(Duration::from_secs(300)).budget(Duration::from_secs(30)),
+//!     PurgeSessions,
+//! );
+//! scheduler.run(&cx, &pool, || connect(&cx)).await;
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::{Duration, Instant, SystemTime};
+
+use asupersync::{CancelReason, Cx, Outcome};
+use serde::{Deserialize, Serialize};
+use sqlmodel_core::{Connection, Dialect, Error, Model, SqlDialect, TransactionOps, Value};
+use sqlmodel_pool::Pool;
+
+use crate::jobs::unix_ms;
+
+/// Bookkeeping for one scheduled task, stored in `sqlmodel_scheduled_tasks`.
+///
+/// Rows are created on a task's first run. Create the table like any other
+/// model, e.g. with `SchemaBuilder::new().create_table::<TaskRecord>()`.
+#[derive(crate::Model, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[sqlmodel(table = "sqlmodel_scheduled_tasks")]
+pub struct TaskRecord {
+    /// Task name, as passed to [`Scheduler::register`].
+    #[sqlmodel(primary_key)]
+    pub name: String,
+    /// Earliest time of the next run, in Unix milliseconds.
+    pub next_run_at: i64,
+    /// Start of the most recent run, in Unix milliseconds.
+    #[sqlmodel(nullable)]
+    pub last_run_at: Option<i64>,
+    /// How long the most recent run took.
+    #[sqlmodel(nullable)]
+    pub last_duration_ms: Option<i64>,
+    /// Error from the most recent run, if it failed.
+    #[sqlmodel(nullable)]
+    pub last_error: Option<String>,
+    /// Total number of runs.
+    pub runs: i64,
+    /// Number of failed runs.
+    pub failures: i64,
+}
+
+/// When a task runs and how long it may take.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Schedule {
+    interval: Duration,
+    budget: Option<Duration>,
+}
+
+impl Schedule {
+    /// Run at most once per `interval`, measured from the start of the last run.
+    pub fn every(interval: Duration) -> Self {
+        Self {
+            interval,
+            budget: None,
+        }
+    }
+
+    /// Record runs that take longer than `budget` as failed.
+    #[must_use]
+    pub fn budget(mut self, budget: Duration) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// The run interval.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+}
+
+/// A periodic task.
+pub trait ScheduledTask: Send + Sync {
+    /// Perform one run. An `Err` is recorded and the task is tried again at
+    /// its next interval.
+    fn run(&self, cx: &Cx) -> impl Future<Output = Outcome<(), Error>> + Send;
+}
+
+/// A [`ScheduledTask`] with its type erased.
+trait DynTask: Send + Sync {
+    fn run_boxed<'a>(
+        &'a self,
+        cx: &'a Cx,
+    ) -> Pin<Box<dyn Future<Output = Outcome<(), Error>> + Send + 'a>>;
+}
+
+impl<T: ScheduledTask> DynTask for T {
+    fn run_boxed<'a>(
+        &'a self,
+        cx: &'a Cx,
+    ) -> Pin<Box<dyn Future<Output = Outcome<(), Error>> + Send + 'a>> {
+        Box::pin(self.run(cx))
+    }
+}
+
+struct Entry {
+    name: String,
+    schedule: Schedule,
+    task: Box<dyn DynTask>,
+}
+
+impl std::fmt::Debug for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Entry")
+            .field("name", &self.name)
+            .field("schedule", &self.schedule)
+            .finish_non_exhaustive()
+    }
+}
+
+/// What happened to a task during [`Scheduler::tick`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunStatus {
+    /// This process was the leader and the task succeeded.
+    Succeeded,
+    /// This process was the leader and the task failed or overran its budget.
+    Failed(String),
+    /// The task's next run is still in the future.
+    NotDue,
+    /// Another process holds the task's lock.
+    NotLeader,
+}
+
+/// The result of one task in a [`Scheduler::tick`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskRun {
+    /// Task name.
+    pub name: String,
+    /// What happened.
+    pub status: RunStatus,
+}
+
+/// A set of named periodic tasks.
+#[derive(Debug)]
+pub struct Scheduler {
+    entries: Vec<Entry>,
+    poll_interval: Duration,
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scheduler {
+    /// An empty scheduler that polls once per second.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+
+    /// Add a task. Names must be unique across every process sharing the
+    /// database, since they key both the lock and the bookkeeping row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a task with the same name is already registered.
+    #[must_use]
+    pub fn register(
+        mut self,
+        name: impl Into<String>,
+        schedule: Schedule,
+        task: impl ScheduledTask + 'static,
+    ) -> Self {
+        let name = name.into();
+        assert!(
+            self.entries.iter().all(|e| e.name != name),
+            "scheduled task {name:?} registered twice"
+        );
+        self.entries.push(Entry {
+            name,
+            schedule,
+            task: Box::new(task),
+        });
+        self
+    }
+
+    /// How long [`Scheduler::run`] waits between ticks.
+    #[must_use]
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Names of the registered tasks, in registration order.
+    pub fn task_names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|e| e.name.as_str())
+    }
+
+    /// Try each registered task once, running the ones that are due and
+    /// whose lock this process wins.
+    pub async fn tick<C: Connection>(&self, cx: &Cx, conn: &C) -> Outcome<Vec<TaskRun>, Error> {
+        let mut runs = Vec::with_capacity(self.entries.len());
+        for entry in &self.entries {
+            if cx.is_cancel_requested() {
+                return Outcome::Cancelled(CancelReason::user("scheduler cancelled"));
+            }
+            let status = match run_entry(cx, conn, entry).await {
+                Outcome::Ok(status) => status,
+                Outcome::Err(e) => return Outcome::Err(e),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            };
+            runs.push(TaskRun {
+                name: entry.name.clone(),
+                status,
+            });
+        }
+        Outcome::Ok(runs)
+    }
+
+    /// Tick until `cx` is cancelled.
+    ///
+    /// A failed tick is logged and retried after the poll interval; the
+    /// outcome is always `Cancelled` or `Panicked`.
+    pub async fn run<C, F, Fut>(&self, cx: &Cx, pool: &Pool<C>, factory: F) -> Outcome<(), Error>
+    where
+        C: Connection,
+        F: Fn() -> Fut,
+        Fut: Future<Output = Outcome<C, Error>>,
+    {
+        loop {
+            if cx.is_cancel_requested() {
+                return Outcome::Cancelled(CancelReason::user("scheduler cancelled"));
+            }
+            let result = match pool.acquire(cx, &factory).await {
+                Outcome::Ok(conn) => self.tick(cx, &conn).await.map(|_| ()),
+                Outcome::Err(e) => Outcome::Err(e),
+                Outcome::Cancelled(r) => Outcome::Cancelled(r),
+                Outcome::Panicked(p) => Outcome::Panicked(p),
+            };
+            match result {
+                Outcome::Ok(()) => {}
+                Outcome::Err(e) => tracing::warn!(error = %e, "scheduler tick failed"),
+                Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                Outcome::Panicked(p) => return Outcome::Panicked(p),
+            }
+            std::thread::sleep(self.poll_interval);
+        }
+    }
+}
+
+/// The advisory lock key for a task name (64-bit FNV-1a).
+pub fn lock_key(name: &str) -> i64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in "sqlmodel:scheduler:".bytes().chain(name.bytes()) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    i64::from_ne_bytes(hash.to_ne_bytes())
+}
+
+/// MySQL lock names are strings of at most 64 characters.
+fn mysql_lock_name(name: &str) -> String {
+    format!("sqlmodel_scheduler_{:016x}", lock_key(name))
+}
+
+/// Claim, run and record one task.
+async fn run_entry<C: Connection>(cx: &Cx, conn: &C, entry: &Entry) -> Outcome<RunStatus, Error> {
+    let dialect = conn.sql_dialect();
+    let tx = match conn.begin(cx).await {
+        Outcome::Ok(tx) => tx,
+        Outcome::Err(e) => return Outcome::Err(e),
+        Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+        Outcome::Panicked(p) => return Outcome::Panicked(p),
+    };
+
+    let (lock_sql, lock_param) = match dialect.base_dialect() {
+        Dialect::Postgres => (
+            "SELECT pg_try_advisory_xact_lock($1)",
+            Value::BigInt(lock_key(&entry.name)),
+        ),
+        Dialect::Mysql => (
+            "SELECT GET_LOCK(?, 0)",
+            Value::Text(mysql_lock_name(&entry.name)),
+        ),
+        Dialect::Sqlite => ("", Value::Null),
+    };
+    if !lock_sql.is_empty() {
+        let acquired = match tx
+            .query_one(cx, lock_sql, std::slice::from_ref(&lock_param))
+            .await
+        {
+            Outcome::Ok(row) => row
+                .and_then(|row| row.get(0).and_then(Value::as_bool))
+                .unwrap_or(false),
+            Outcome::Err(e) => return finish(cx, conn, tx, None, Outcome::Err(e)).await,
+            Outcome::Cancelled(r) => {
+                return finish(cx, conn, tx, None, Outcome::Cancelled(r)).await;
+            }
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        };
+        if !acquired {
+            return finish(cx, conn, tx, None, Outcome::Ok(RunStatus::NotLeader)).await;
+        }
+    }
+    // From here on a MySQL lock must be released whatever happens.
+    let held = (dialect.base_dialect() == Dialect::Mysql).then_some(&lock_param);
+
+    let now = unix_ms(SystemTime::now());
+    let select = format!(
+        "SELECT * FROM {} WHERE {} = {}",
+        dialect.quote_identifier(TaskRecord::TABLE_NAME),
+        dialect.quote_identifier("name"),
+        dialect.placeholder(1),
+    );
+    let name_param = [Value::Text(entry.name.clone())];
+    let existing = match tx.query_one(cx, &select, &name_param).await {
+        Outcome::Ok(row) => row,
+        Outcome::Err(e) => return finish(cx, conn, tx, held, Outcome::Err(e)).await,
+        Outcome::Cancelled(r) => {
+            return finish(cx, conn, tx, held, Outcome::Cancelled(r)).await;
+        }
+        Outcome::Panicked(p) => return Outcome::Panicked(p),
+    };
+    let record = match existing.as_ref().map(TaskRecord::from_row) {
+        Some(Ok(record)) => Some(record),
+        Some(Err(e)) => return finish(cx, conn, tx, held, Outcome::Err(e)).await,
+        None => None,
+    };
+    if record.as_ref().is_some_and(|r| r.next_run_at > now) {
+        return finish(cx, conn, tx, held, Outcome::Ok(RunStatus::NotDue)).await;
+    }
+
+    let started = Instant::now();
+    let result = entry.task.run_boxed(cx).await;
+    let elapsed = started.elapsed();
+    let error = match result {
+        Outcome::Ok(()) => match entry.schedule.budget {
+            Some(budget) if elapsed > budget => Some(format!(
+                "exceeded budget of {}ms (took {}ms)",
+                budget.as_millis(),
+                elapsed.as_millis()
+            )),
+            _ => None,
+        },
+        Outcome::Err(e) => Some(e.to_string()),
+        Outcome::Cancelled(r) => {
+            return finish(cx, conn, tx, held, Outcome::Cancelled(r)).await;
+        }
+        Outcome::Panicked(p) => return Outcome::Panicked(p),
+    };
+
+    let mut record = record.unwrap_or_else(|| TaskRecord {
+        name: entry.name.clone(),
+        next_run_at: now,
+        last_run_at: None,
+        last_duration_ms: None,
+        last_error: None,
+        runs: 0,
+        failures: 0,
+    });
+    let interval = i64::try_from(entry.schedule.interval.as_millis()).unwrap_or(i64::MAX);
+    record.next_run_at = now.saturating_add(interval);
+    record.last_run_at = Some(now);
+    record.last_duration_ms = Some(i64::try_from(elapsed.as_millis()).unwrap_or(i64::MAX));
+    record.runs += 1;
+    if error.is_some() {
+        record.failures += 1;
+    }
+    record.last_error.clone_from(&error);
+
+    let (sql, params) = upsert_statement(dialect, &record, existing.is_some());
+    match tx.execute(cx, &sql, &params).await {
+        Outcome::Ok(_) => {}
+        Outcome::Err(e) => return finish(cx, conn, tx, held, Outcome::Err(e)).await,
+        Outcome::Cancelled(r) => {
+            return finish(cx, conn, tx, held, Outcome::Cancelled(r)).await;
+        }
+        Outcome::Panicked(p) => return Outcome::Panicked(p),
+    }
+    let committed = match tx.commit(cx).await {
+        Outcome::Ok(()) => Outcome::Ok(error.map_or(RunStatus::Succeeded, RunStatus::Failed)),
+        Outcome::Err(e) => Outcome::Err(e),
+        Outcome::Cancelled(r) => Outcome::Cancelled(r),
+        Outcome::Panicked(p) => Outcome::Panicked(p),
+    };
+    release_mysql_lock(cx, conn, held, committed).await
+}
+
+/// Roll back `tx`, release any MySQL lock and return `outcome`.
+async fn finish<C: Connection, T>(
+    cx: &Cx,
+    conn: &C,
+    tx: C::Tx<'_>,
+    held: Option<&Value>,
+    outcome: Outcome<T, Error>,
+) -> Outcome<T, Error> {
+    let outcome = match tx.rollback(cx).await {
+        Outcome::Ok(()) => outcome,
+        Outcome::Err(e) => match outcome {
+            Outcome::Err(original) => Outcome::Err(original),
+            _ => Outcome::Err(e),
+        },
+        Outcome::Cancelled(r) => Outcome::Cancelled(r),
+        Outcome::Panicked(p) => return Outcome::Panicked(p),
+    };
+    release_mysql_lock(cx, conn, held, outcome).await
+}
+
+async fn release_mysql_lock<C: Connection, T>(
+    cx: &Cx,
+    conn: &C,
+    held: Option<&Value>,
+    outcome: Outcome<T, Error>,
+) -> Outcome<T, Error> {
+    let Some(lock) = held else {
+        return outcome;
+    };
+    match conn
+        .query(cx, "SELECT RELEASE_LOCK(?)", std::slice::from_ref(lock))
+        .await
+    {
+        Outcome::Ok(_) => outcome,
+        Outcome::Err(e) => match outcome {
+            Outcome::Ok(_) => Outcome::Err(e),
+            other => other,
+        },
+        Outcome::Cancelled(r) => Outcome::Cancelled(r),
+        Outcome::Panicked(p) => Outcome::Panicked(p),
+    }
+}
+
+/// Insert a first-run record or update an existing one.
+fn upsert_statement(
+    dialect: &'static dyn SqlDialect,
+    record: &TaskRecord,
+    exists: bool,
+) -> (String, Vec<Value>) {
+    let q = |name: &str| dialect.quote_identifier(name);
+    let row = record.to_row();
+    let (names, mut params): (Vec<&str>, Vec<Value>) = row.into_iter().unzip();
+    let sql = if exists {
+        let assignments: Vec<String> = names
+            .iter()
+            .skip(1)
+            .enumerate()
+            .map(|(i, &name)| format!("{} = {}", q(name), dialect.placeholder(i + 1)))
+            .collect();
+        // The primary key moves from the front to the WHERE clause.
+        let key = params.remove(0);
+        params.push(key);
+        format!(
+            "UPDATE {} SET {} WHERE {} = {}",
+            q(TaskRecord::TABLE_NAME),
+            assignments.join(", "),
+            q(names[0]),
+            dialect.placeholder(params.len()),
+        )
+    } else {
+        let columns: Vec<String> = names.iter().map(|&name| q(name)).collect();
+        let placeholders: Vec<String> = (1..=names.len()).map(|i| dialect.placeholder(i)).collect();
+        format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            q(TaskRecord::TABLE_NAME),
+            columns.join(", "),
+            placeholders.join(", "),
+        )
+    };
+    (sql, params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Noop;
+
+    impl ScheduledTask for Noop {
+        async fn run(&self, _cx: &Cx) -> Outcome<(), Error> {
+            Outcome::Ok(())
+        }
+    }
+
+    fn record() -> TaskRecord {
+        TaskRecord {
+            name: "purge".to_string(),
+            next_run_at: 2_000,
+            last_run_at: Some(1_000),
+            last_duration_ms: Some(5),
+            last_error: None,
+            runs: 1,
+            failures: 0,
+        }
+    }
+
+    #[test]
+    fn lock_key_is_stable_and_distinct() {
+        assert_eq!(lock_key("purge"), lock_key("purge"));
+        assert_ne!(lock_key("purge"), lock_key("report"));
+        assert!(mysql_lock_name("purge").len() <= 64);
+    }
+
+    #[test]
+    fn upsert_statement_updates_by_name() {
+        let (sql, params) = upsert_statement(Dialect::Postgres.as_dyn(), &record(), true);
+        assert_eq!(
+            sql,
+            "UPDATE \"sqlmodel_scheduled_tasks\" SET \"next_run_at\" = $1, \"last_run_at\" = $2, \
+             \"last_duration_ms\" = $3, \"last_error\" = $4, \"runs\" = $5, \"failures\" = $6 \
+             WHERE \"name\" = $7"
+        );
+        assert_eq!(params.len(), 7);
+        assert_eq!(params[6], Value::Text("purge".to_string()));
+        assert_eq!(params[0], Value::BigInt(2_000));
+    }
+
+    #[test]
+    fn upsert_statement_inserts_first_run() {
+        let (sql, params) = upsert_statement(Dialect::Sqlite.as_dyn(), &record(), false);
+        assert!(sql.starts_with("INSERT INTO \"sqlmodel_scheduled_tasks\" (\"name\", "));
+        assert!(sql.ends_with("VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"));
+        assert_eq!(params[0], Value::Text("purge".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "registered twice")]
+    fn register_rejects_duplicate_names() {
+        let every = Schedule::every(Duration::from_secs(60));
+        let _ = Scheduler::new()
+            .register("purge", every, Noop)
+            .register("purge", every, Noop);
+    }
+}
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+use sqlmodel::prelude::*;
+use sqlmodel_schema::openapi::{SchemaVariant, model_json_schema};
+
+#[derive(Model, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[sqlmodel(table = "users", generate = "UserCreate(exclude(id)), UserRead")]
+struct User {
+    #[sqlmodel(primary_key, auto_increment)]
+    id: Option<i64>,
+    email: String,
+    #[sqlmodel(db_only)]
+    password_hash: String,
+    #[sqlmodel(api_exclude)]
+    risk_score: i32,
+}
+
+fn user() -> User {
+    User {
+        id: Some(1),
+        email: "ada@example.com".to_string(),
+        password_hash: "$argon2id$...".to_string(),
+        risk_score: 7,
+    }
+}
+
+#[test]
+fn db_only_columns_are_still_stored() {
+    let columns: Vec<&str> = user().to_row().into_iter().map(|(c, _)| c).collect();
+    assert_eq!(columns, ["id", "email", "password_hash", "risk_score"]);
+}
+
+#[test]
+fn generated_dtos_leave_out_hidden_fields() {
+    // Neither hidden field is a DTO member; converting back fills defaults
+    let create = UserCreate {
+        email: "grace@example.com".to_string(),
+    };
+    let model: User = create.into();
+    assert_eq!(model.password_hash, "");
+    assert_eq!(model.risk_score, 0);
+
+    let read = UserRead::from(user());
+    assert_eq!(read.id, Some(1));
+    assert_eq!(read.email, "ada@example.com");
+}
+
+#[test]
+fn json_schema_leaves_out_hidden_fields() {
+    for variant in SchemaVariant::ALL {
+        let schema = model_json_schema::<User>(variant);
+        let properties = schema["properties"].as_object().unwrap();
+        assert!(!properties.contains_key("password_hash"), "{variant:?}");
+        assert!(!properties.contains_key("risk_score"), "{variant:?}");
+    }
+}
+
+#[test]
+fn model_dump_drops_db_only_but_keeps_api_exclude() {
+    let dumped = user().sql_model_dump(DumpOptions::default()).unwrap();
+    assert!(dumped.get("password_hash").is_none());
+    assert_eq!(dumped["risk_score"], 7);
+}
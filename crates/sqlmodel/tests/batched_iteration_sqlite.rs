@@ -0,0 +1,109 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use std::sync::Mutex;
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+
+use sqlmodel::BatchProgress;
+use sqlmodel::prelude::*;
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> T {
+    match outcome {
+        Outcome::Ok(v) => v,
+        Outcome::Err(e) => panic!("unexpected error: {e}"),
+        Outcome::Cancelled(r) => panic!("cancelled: {r:?}"),
+        Outcome::Panicked(p) => panic!("panicked: {p:?}"),
+    }
+}
+
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(table = "events")]
+struct Event {
+    #[sqlmodel(primary_key)]
+    id: i64,
+    processed: bool,
+}
+
+#[test]
+fn sqlite_for_each_batch_walks_table_in_pk_order() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        unwrap_outcome(
+            conn.execute_script(
+                &cx,
+                "CREATE TABLE events (id INTEGER PRIMARY KEY, processed INTEGER NOT NULL);
+                 INSERT INTO events VALUES
+                     (9, 0), (2, 0), (7, 1), (4, 0), (1, 0), (12, 0), (5, 0);",
+            )
+            .await,
+        );
+
+        let seen = Mutex::new(Vec::new());
+        let mut reports = Vec::new();
+        let done = unwrap_outcome(
+            select!(Event)
+                .filter(Expr::col("processed").eq(false))
+                .for_each_batch_with(
+                    &cx,
+                    &conn,
+                    2,
+                    |progress| reports.push(progress),
+                    |batch| {
+                        let seen = &seen;
+                        let conn = &conn;
+                        let cx = &cx;
+                        async move {
+                            let ids: Vec<i64> = batch.iter().map(|event| event.id).collect();
+                            seen.lock().unwrap().push(ids.clone());
+                            for id in ids {
+                                match conn
+                                    .execute(
+                                        cx,
+                                        "UPDATE events SET processed = 1 WHERE id = ?1",
+                                        &[Value::BigInt(id)],
+                                    )
+                                    .await
+                                {
+                                    Outcome::Ok(_) => {}
+                                    Outcome::Err(e) => return Outcome::Err(e),
+                                    Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+                                    Outcome::Panicked(p) => return Outcome::Panicked(p),
+                                }
+                            }
+                            Outcome::Ok(())
+                        }
+                    },
+                )
+                .await,
+        );
+
+        assert_eq!(
+            seen.into_inner().unwrap(),
+            [vec![1, 2], vec![4, 5], vec![9, 12]]
+        );
+        assert_eq!(
+            done,
+            BatchProgress {
+                batches: 3,
+                rows: 6
+            }
+        );
+        assert_eq!(reports.last(), Some(&done));
+        assert_eq!(reports.len(), 3);
+
+        let remaining = unwrap_outcome(
+            select!(Event)
+                .filter(Expr::col("processed").eq(false))
+                .count(&cx, &conn)
+                .await,
+        );
+        assert_eq!(remaining, 0);
+    });
+}
@@ -0,0 +1,161 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use std::sync::Arc;
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+use serde::{Deserialize, Serialize};
+
+use sqlmodel::binds::{Binds, RoutedSession};
+use sqlmodel::prelude::*;
+use sqlmodel::{Join, Pool, PoolConfig, SchemaBuilder};
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> std::result::Result<T, String> {
+    match outcome {
+        Outcome::Ok(v) => Ok(v),
+        Outcome::Err(e) => Err(format!("unexpected error: {e}")),
+        Outcome::Cancelled(r) => Err(format!("cancelled: {r:?}")),
+        Outcome::Panicked(p) => Err(format!("panicked: {p:?}")),
+    }
+}
+
+#[derive(Model, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[sqlmodel(table = "users")]
+struct User {
+    #[sqlmodel(primary_key)]
+    id: i64,
+    name: String,
+}
+
+#[derive(Model, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[sqlmodel(table = "page_views")]
+struct PageView {
+    #[sqlmodel(primary_key)]
+    id: i64,
+    user_id: i64,
+    path: String,
+}
+
+async fn open_memory() -> Outcome<SqliteConnection, Error> {
+    match SqliteConnection::open_memory() {
+        Ok(conn) => Outcome::Ok(conn),
+        Err(e) => Outcome::Err(e),
+    }
+}
+
+fn binds() -> Binds<SqliteConnection> {
+    Binds::new()
+        .bind("primary", Pool::new(PoolConfig::new(1)), open_memory)
+        .bind("analytics", Pool::new(PoolConfig::new(1)), open_memory)
+        .default_bind("primary")
+        .route::<PageView>("analytics")
+}
+
+async fn create_tables(cx: &Cx, binds: &Binds<SqliteConnection>) {
+    for (bind, ddl) in [
+        (
+            "primary",
+            SchemaBuilder::new().create_table::<User>().build(),
+        ),
+        (
+            "analytics",
+            SchemaBuilder::new().create_table::<PageView>().build(),
+        ),
+    ] {
+        let conn = unwrap_outcome(binds.acquire(cx, bind).await).expect("acquire");
+        for stmt in ddl {
+            unwrap_outcome(conn.execute(cx, &stmt, &[]).await).expect("execute ddl");
+        }
+    }
+}
+
+#[test]
+fn sqlite_routed_session_writes_each_model_to_its_bind() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let binds = Arc::new(binds());
+        create_tables(&cx, &binds).await;
+        assert_eq!(binds.bind_for::<User>().unwrap(), "primary");
+        assert_eq!(binds.bind_for::<PageView>().unwrap(), "analytics");
+
+        let mut session = RoutedSession::new(Arc::clone(&binds));
+        let user = User {
+            id: 1,
+            name: "Deadpond".to_string(),
+        };
+        unwrap_outcome(session.add(&cx, &user).await).expect("add user");
+        unwrap_outcome(session.commit(&cx).await).expect("commit primary");
+
+        let view = PageView {
+            id: 1,
+            user_id: 1,
+            path: "/heroes".to_string(),
+        };
+        unwrap_outcome(session.add(&cx, &view).await).expect("add page view");
+        unwrap_outcome(session.commit(&cx).await).expect("commit analytics");
+
+        let found = unwrap_outcome(session.get::<User>(&cx, 1_i64).await).expect("get user");
+        assert_eq!(found, Some(user));
+        let views = unwrap_outcome(session.all(&cx, select!(PageView)).await).expect("views");
+        assert_eq!(views, vec![view]);
+        drop(session);
+
+        let primary = unwrap_outcome(binds.acquire(&cx, "primary").await).expect("acquire");
+        let missing = primary
+            .query(&cx, "SELECT COUNT(*) FROM page_views", &[])
+            .await;
+        assert!(
+            matches!(missing, Outcome::Err(_)),
+            "page_views lives on the analytics bind only"
+        );
+    });
+}
+
+#[test]
+fn sqlite_routed_session_rejects_multi_bind_writes_and_joins() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let binds = Arc::new(binds());
+        create_tables(&cx, &binds).await;
+        let mut session = RoutedSession::new(binds);
+
+        let user = User {
+            id: 1,
+            name: "Deadpond".to_string(),
+        };
+        unwrap_outcome(session.add(&cx, &user).await).expect("add user");
+        assert_eq!(session.writing_bind(), Some("primary"));
+
+        let view = PageView {
+            id: 1,
+            user_id: 1,
+            path: "/".to_string(),
+        };
+        let Outcome::Err(err) = session.add(&cx, &view).await else {
+            panic!("second bind must not join the unit of work");
+        };
+        assert!(err.to_string().contains("commit or roll back 'primary'"));
+
+        unwrap_outcome(session.rollback(&cx).await).expect("rollback");
+        assert_eq!(session.writing_bind(), None);
+        unwrap_outcome(session.add(&cx, &view).await).expect("add after rollback");
+
+        let joined = select!(User).join(Join::inner(
+            "page_views",
+            Expr::qualified("page_views", "user_id").eq(Expr::qualified("users", "id")),
+        ));
+        let Outcome::Err(err) = session.all(&cx, joined).await else {
+            panic!("cross-bind join must fail");
+        };
+        assert!(err.to_string().contains("cannot join 'page_views'"));
+    });
+}
@@ -0,0 +1,144 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+
+use sqlmodel::prelude::*;
+use sqlmodel::{SchemaBuilder, Session};
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> T {
+    match outcome {
+        Outcome::Ok(v) => v,
+        Outcome::Err(e) => panic!("unexpected error: {e}"),
+        Outcome::Cancelled(r) => panic!("cancelled: {r:?}"),
+        Outcome::Panicked(p) => panic!("panicked: {p:?}"),
+    }
+}
+
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(table = "readings")]
+struct Reading {
+    #[sqlmodel(primary_key)]
+    id: i64,
+    sensor: String,
+    value: i64,
+}
+
+fn readings(n: i64) -> Vec<Reading> {
+    (1..=n)
+        .map(|id| Reading {
+            id,
+            sensor: format!("sensor-{}", id % 7),
+            value: id * 10,
+        })
+        .collect()
+}
+
+async fn count_rows(cx: &Cx, session: &Session<SqliteConnection>) -> i64 {
+    let rows = unwrap_outcome(
+        session
+            .connection()
+            .query(cx, "SELECT COUNT(*) FROM readings", &[])
+            .await,
+    );
+    rows[0].get_as(0).expect("count")
+}
+
+#[test]
+fn sqlite_bulk_insert_splits_statements_at_parameter_limit() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        for stmt in SchemaBuilder::new().create_table::<Reading>().build() {
+            unwrap_outcome(conn.execute(&cx, &stmt, &[]).await);
+        }
+        let mut session = Session::new(conn);
+
+        // Three columns per row: 333 rows fit under SQLite's 999 placeholders.
+        let counts = unwrap_outcome(
+            session
+                .bulk_insert_chunks(&cx, &readings(700), 1000, None)
+                .await,
+        );
+        assert_eq!(counts, vec![333, 333, 34]);
+        assert_eq!(count_rows(&cx, &session).await, 700);
+
+        let inserted = unwrap_outcome(session.bulk_insert(&cx, &readings(1500)[700..]).await);
+        assert_eq!(inserted, 800);
+        assert_eq!(count_rows(&cx, &session).await, 1500);
+    });
+}
+
+#[test]
+fn sqlite_bulk_insert_honours_custom_parameter_limit() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        for stmt in SchemaBuilder::new().create_table::<Reading>().build() {
+            unwrap_outcome(conn.execute(&cx, &stmt, &[]).await);
+        }
+        let mut session = Session::new(conn);
+
+        let counts = unwrap_outcome(
+            session
+                .bulk_insert_chunks(&cx, &readings(10), 4, Some(6))
+                .await,
+        );
+        assert_eq!(counts, vec![2, 2, 2, 2, 2]);
+        assert_eq!(count_rows(&cx, &session).await, 10);
+    });
+}
+
+#[test]
+fn sqlite_bulk_upsert_updates_existing_rows() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        for stmt in SchemaBuilder::new().create_table::<Reading>().build() {
+            unwrap_outcome(conn.execute(&cx, &stmt, &[]).await);
+        }
+        let mut session = Session::new(conn);
+
+        let counts = unwrap_outcome(session.bulk_upsert(&cx, &readings(5), &["id"]).await);
+        assert_eq!(counts.affected, 5);
+        assert_eq!(counts.inserted, None);
+
+        let mut changed = readings(8);
+        for reading in &mut changed {
+            reading.value += 1;
+        }
+        unwrap_outcome(session.bulk_upsert(&cx, &changed, &["id"]).await);
+        assert_eq!(count_rows(&cx, &session).await, 8);
+
+        // Only `sensor` is updated; `value` keeps the first upsert's data.
+        changed[0].sensor = "moved".to_string();
+        changed[0].value = 999;
+        unwrap_outcome(
+            session
+                .bulk_upsert_columns(&cx, &changed[..1], &["id"], &["sensor"])
+                .await,
+        );
+
+        let rows = unwrap_outcome(
+            session
+                .connection()
+                .query(&cx, "SELECT sensor, value FROM readings WHERE id = 1", &[])
+                .await,
+        );
+        assert_eq!(rows[0].get_as::<String>(0).unwrap(), "moved");
+        assert_eq!(rows[0].get_as::<i64>(1).unwrap(), 11);
+    });
+}
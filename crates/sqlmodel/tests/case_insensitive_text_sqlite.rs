@@ -0,0 +1,94 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+
+use sqlmodel::SqlType;
+use sqlmodel::prelude::*;
+use sqlmodel_schema::diff::SchemaOperation;
+use sqlmodel_schema::{Dialect, ModelSchema, generator_for_dialect};
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> T {
+    match outcome {
+        Outcome::Ok(v) => v,
+        Outcome::Err(e) => panic!("unexpected error: {e}"),
+        Outcome::Cancelled(r) => panic!("cancelled: {r:?}"),
+        Outcome::Panicked(p) => panic!("panicked: {p:?}"),
+    }
+}
+
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(table = "accounts")]
+struct Account {
+    #[sqlmodel(primary_key)]
+    id: i64,
+    #[sqlmodel(case_insensitive, unique)]
+    email: String,
+}
+
+#[test]
+fn derive_selects_citext_per_dialect() {
+    let email = Account::fields()
+        .iter()
+        .find(|f| f.column_name == "email")
+        .unwrap();
+    assert_eq!(email.sql_type, SqlType::CiText);
+
+    let create = SchemaOperation::CreateTable(Account::table_schema());
+    let postgres = generator_for_dialect(Dialect::Postgres).generate(&create);
+    assert_eq!(postgres[0], "CREATE EXTENSION IF NOT EXISTS citext");
+    assert!(postgres[1].contains("\"email\" CITEXT NOT NULL"));
+    let mysql = generator_for_dialect(Dialect::Mysql).generate(&create);
+    assert!(mysql[0].contains("`email` TEXT COLLATE `utf8mb4_unicode_ci` NOT NULL"));
+}
+
+#[test]
+fn sqlite_eq_on_case_insensitive_column_ignores_case() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        let ddl = generator_for_dialect(Dialect::Sqlite)
+            .generate(&SchemaOperation::CreateTable(Account::table_schema()));
+        for sql in &ddl {
+            unwrap_outcome(conn.execute(&cx, sql, &[]).await);
+        }
+        unwrap_outcome(
+            conn.execute(
+                &cx,
+                "INSERT INTO accounts (id, email) VALUES (1, 'Ann@Example.com')",
+                &[],
+            )
+            .await,
+        );
+
+        // A plain `=` matches regardless of case; no LOWER() wrapper needed
+        let found = unwrap_outcome(
+            select!(Account)
+                .filter(Expr::col("email").eq("ann@example.COM"))
+                .all(&cx, &conn)
+                .await,
+        );
+        assert_eq!(
+            found,
+            [Account {
+                id: 1,
+                email: "Ann@Example.com".to_string(),
+            }]
+        );
+
+        // ...and uniqueness is case-insensitive too
+        let duplicate = conn
+            .execute(
+                &cx,
+                "INSERT INTO accounts (id, email) VALUES (2, 'ANN@example.com')",
+                &[],
+            )
+            .await;
+        assert!(matches!(duplicate, Outcome::Err(_)));
+    });
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+
+use sqlmodel::prelude::*;
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> T {
+    match outcome {
+        Outcome::Ok(v) => v,
+        Outcome::Err(e) => panic!("unexpected error: {e}"),
+        Outcome::Cancelled(r) => panic!("cancelled: {r:?}"),
+        Outcome::Panicked(p) => panic!("panicked: {p:?}"),
+    }
+}
+
+/// Legacy schema stores tag lists as comma-joined TEXT.
+mod codec {
+    use sqlmodel::{Error, Value};
+
+    pub fn join_tags(tags: &[String]) -> Value {
+        Value::Text(tags.join(","))
+    }
+
+    pub fn split_tags(value: &Value) -> Result<Vec<String>, Error> {
+        let Value::Text(joined) = value else {
+            return Err(Error::Custom(format!(
+                "expected comma-joined TEXT, got {value:?}"
+            )));
+        };
+        Ok(joined
+            .split(',')
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+}
+
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(table = "posts", order_by = "id")]
+struct Post {
+    #[sqlmodel(primary_key)]
+    id: i64,
+    #[sqlmodel(
+        serialize_with = "codec::join_tags",
+        deserialize_with = "codec::split_tags"
+    )]
+    tags: Vec<String>,
+}
+
+#[test]
+fn serialize_with_controls_stored_value() {
+    let post = Post {
+        id: 1,
+        tags: vec!["rust".to_string(), "orm".to_string()],
+    };
+    assert_eq!(
+        post.to_row(),
+        vec![
+            ("id", Value::BigInt(1)),
+            ("tags", Value::Text("rust,orm".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn sqlite_custom_column_codecs_round_trip() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        unwrap_outcome(
+            conn.execute_script(
+                &cx,
+                "CREATE TABLE posts (
+                     id INTEGER PRIMARY KEY,
+                     tags TEXT NOT NULL
+                 );
+                 INSERT INTO posts VALUES (1, 'legacy,import');",
+            )
+            .await,
+        );
+
+        let post = Post {
+            id: 2,
+            tags: vec!["rust".to_string(), "orm".to_string()],
+        };
+        unwrap_outcome(insert!(&post).execute(&cx, &conn).await);
+
+        let posts = unwrap_outcome(select!(Post).all(&cx, &conn).await);
+        assert_eq!(posts[0].tags, ["legacy", "import"]);
+        assert_eq!(posts[1], post);
+    });
+}
@@ -0,0 +1,66 @@
+use sqlmodel::CreateTable;
+use sqlmodel::Dialect as QueryDialect;
+use sqlmodel::prelude::*;
+use sqlmodel_schema::diff::{SchemaOperation, schema_diff};
+use sqlmodel_schema::{DatabaseSchema, Dialect, ModelSchema, generator_for_dialect};
+
+#[derive(Model, Debug, Clone)]
+#[sqlmodel(table = "customers")]
+struct Customer {
+    #[sqlmodel(primary_key, auto_increment)]
+    id: Option<i64>,
+    #[sqlmodel(collation = "utf8mb4_bin", charset = "utf8mb4")]
+    email: String,
+    name: String,
+}
+
+#[test]
+fn derive_carries_collation_and_charset() {
+    let email = Customer::table_schema().column("email").unwrap().clone();
+    assert_eq!(email.collation.as_deref(), Some("utf8mb4_bin"));
+    assert_eq!(email.charset.as_deref(), Some("utf8mb4"));
+
+    assert!(
+        CreateTable::<Customer>::new()
+            .build()
+            .contains("\"email\" TEXT COLLATE \"utf8mb4_bin\" NOT NULL")
+    );
+    let create = generator_for_dialect(Dialect::Mysql)
+        .generate(&SchemaOperation::CreateTable(Customer::table_schema()));
+    assert!(create[0].contains("CHARACTER SET utf8mb4 COLLATE `utf8mb4_bin`"));
+}
+
+#[test]
+fn drifted_collation_is_altered() {
+    let mut current = DatabaseSchema::new(Dialect::Mysql);
+    let mut reflected = Customer::table_schema();
+    for column in &mut reflected.columns {
+        column.collation = Some("utf8mb4_0900_ai_ci".to_string());
+        column.charset = Some("utf8mb4".to_string());
+    }
+    current.tables.insert(reflected.name.clone(), reflected);
+
+    let mut expected = DatabaseSchema::new(Dialect::Mysql);
+    expected
+        .tables
+        .insert("customers".to_string(), Customer::table_schema());
+
+    // Only the column with a declared collation is touched
+    let diff = schema_diff(&current, &expected);
+    assert_eq!(diff.operations.len(), 1);
+    let ddl = generator_for_dialect(Dialect::Mysql).generate_all(&diff.operations);
+    assert_eq!(ddl.len(), 1);
+    assert!(ddl[0].starts_with("ALTER TABLE `customers` MODIFY COLUMN `email`"));
+    assert!(ddl[0].contains("COLLATE `utf8mb4_bin`"));
+}
+
+#[test]
+fn collate_overrides_query_collation() {
+    let mut params = Vec::new();
+    let sql =
+        Expr::col("name")
+            .collate("und-x-icu")
+            .asc()
+            .build(QueryDialect::Postgres, &mut params, 0);
+    assert_eq!(sql, "\"name\" COLLATE \"und-x-icu\" ASC");
+}
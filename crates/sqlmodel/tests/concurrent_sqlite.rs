@@ -0,0 +1,64 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+
+use sqlmodel::join_queries;
+use sqlmodel::prelude::*;
+use sqlmodel::{Pool, PoolConfig};
+use sqlmodel_sqlite::SqliteConnection;
+
+async fn open_memory() -> Outcome<SqliteConnection, Error> {
+    match SqliteConnection::open_memory() {
+        Ok(conn) => Outcome::Ok(conn),
+        Err(e) => Outcome::Err(e),
+    }
+}
+
+#[test]
+fn sqlite_join_queries_returns_typed_tuple() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+    let pool: Pool<SqliteConnection> = Pool::new(PoolConfig::new(3));
+
+    rt.block_on(async {
+        let cx = &cx;
+        let outcome = join_queries!(cx,
+            |conn| async move { conn.query(cx, "SELECT 1 AS n", &[]).await } => (&pool, open_memory),
+            |conn| async move { conn.query_one(cx, "SELECT 'two' AS s", &[]).await } => (&pool, open_memory),
+            |conn| async move { conn.execute(cx, "CREATE TABLE t (id INTEGER)", &[]).await } => (&pool, open_memory),
+        )
+        .await;
+        let Outcome::Ok((rows, row, created)) = outcome else {
+            panic!("join failed");
+        };
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get_named::<i64>("n").unwrap(), 1);
+        assert_eq!(row.unwrap().get_named::<String>("s").unwrap(), "two");
+        assert_eq!(created, 0);
+    });
+    assert_eq!(pool.total_count(), 3);
+    assert_eq!(pool.idle_count(), 3);
+}
+
+#[test]
+fn sqlite_join_queries_stops_at_first_error() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+    let pool: Pool<SqliteConnection> = Pool::new(PoolConfig::new(2));
+
+    rt.block_on(async {
+        let cx = &cx;
+        let outcome = join_queries!(cx,
+            |conn| async move { conn.query(cx, "SELECT 1", &[]).await } => (&pool, open_memory),
+            |conn| async move { conn.query(cx, "SELECT * FROM missing", &[]).await } => (&pool, open_memory),
+        )
+        .await;
+        assert!(matches!(outcome, Outcome::Err(_)));
+    });
+    assert_eq!(pool.active_count(), 0);
+}
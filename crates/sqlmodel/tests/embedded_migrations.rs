@@ -0,0 +1,50 @@
+use sqlmodel::{Migration, embed_migrations};
+
+fn summary(migrations: &[Migration]) -> Vec<(&str, &str, &str, &str)> {
+    migrations
+        .iter()
+        .map(|m| {
+            (
+                m.id.as_str(),
+                m.description.as_str(),
+                m.up.trim(),
+                m.down.trim(),
+            )
+        })
+        .collect()
+}
+
+/// Embed the test migrations once; each expansion includes the `.rs` files as modules.
+fn embedded() -> Vec<Migration> {
+    embed_migrations!("tests/migrations")
+}
+
+#[test]
+fn embedded_migrations_are_ordered_and_parsed() {
+    let migrations = embedded();
+    assert_eq!(
+        summary(&migrations),
+        [
+            (
+                "20250101000000",
+                "Create heroes",
+                "CREATE TABLE heroes (id INTEGER PRIMARY KEY, name TEXT NOT NULL);\n\
+                 CREATE INDEX idx_heroes_name ON heroes (name);",
+                "DROP TABLE heroes;",
+            ),
+            (
+                "20250102000000",
+                "Add hero age",
+                "ALTER TABLE heroes ADD COLUMN age INTEGER;",
+                "ALTER TABLE heroes DROP COLUMN age;",
+            ),
+        ]
+    );
+}
+
+#[test]
+fn embedded_migrations_match_directory_load() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/migrations");
+    let loaded = Migration::load_dir(dir).expect("load migrations directory");
+    assert_eq!(summary(&loaded), summary(&embedded()));
+}
@@ -0,0 +1,58 @@
+use sqlmodel::prelude::*;
+
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(
+    table = "heroes",
+    generate = "HeroCreate(exclude(id)), HeroUpdate(exclude(id), all_optional), HeroRead"
+)]
+struct Hero {
+    #[sqlmodel(primary_key, auto_increment)]
+    id: Option<i64>,
+    name: String,
+    #[sqlmodel(nullable)]
+    age: Option<i32>,
+}
+
+fn hero() -> Hero {
+    Hero {
+        id: Some(1),
+        name: "Deadpond".to_string(),
+        age: Some(30),
+    }
+}
+
+#[test]
+fn create_dto_converts_into_model_with_defaulted_excluded_fields() {
+    let create = HeroCreate {
+        name: "Spider-Boy".to_string(),
+        age: None,
+    };
+    let model: Hero = create.into();
+    assert_eq!(model.id, None);
+    assert_eq!(model.name, "Spider-Boy");
+    assert_eq!(model.age, None);
+}
+
+#[test]
+fn read_dto_round_trips_every_field() {
+    let read = HeroRead::from(hero());
+    assert_eq!(read.id, Some(1));
+    assert_eq!(Hero::from(read), hero());
+}
+
+#[test]
+fn update_dto_patches_only_provided_fields() {
+    let mut model = hero();
+    HeroUpdate {
+        name: Some("Deadpool".to_string()),
+        ..Default::default()
+    }
+    .apply_to(&mut model);
+    assert_eq!(model.name, "Deadpool");
+    assert_eq!(model.age, Some(30));
+    assert_eq!(model.id, Some(1));
+
+    let update = HeroUpdate::from(hero());
+    assert_eq!(update.name.as_deref(), Some("Deadpond"));
+    assert_eq!(update.age, Some(30));
+}
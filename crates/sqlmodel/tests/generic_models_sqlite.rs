@@ -0,0 +1,106 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+
+use sqlmodel::prelude::*;
+use sqlmodel::{SchemaBuilder, SqlType};
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> T {
+    match outcome {
+        Outcome::Ok(v) => v,
+        Outcome::Err(e) => panic!("unexpected error: {e}"),
+        Outcome::Cancelled(r) => panic!("cancelled: {r:?}"),
+        Outcome::Panicked(p) => panic!("panicked: {p:?}"),
+    }
+}
+
+/// One audit table definition reused with different key types.
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(table = "audit_events", order_by = "id")]
+struct AuditEvent<K>
+where
+    K: SqlScalar,
+{
+    #[sqlmodel(primary_key)]
+    id: K,
+    action: String,
+    parent_id: Option<K>,
+}
+
+fn event<K: SqlScalar>(id: K, action: &str, parent_id: Option<K>) -> AuditEvent<K> {
+    AuditEvent {
+        id,
+        action: action.to_string(),
+        parent_id,
+    }
+}
+
+fn column_types<M: Model>() -> Vec<(&'static str, SqlType, bool)> {
+    M::fields()
+        .iter()
+        .map(|f| (f.column_name, f.sql_type.clone(), f.nullable))
+        .collect()
+}
+
+#[test]
+fn generic_model_columns_follow_type_parameter() {
+    assert_eq!(
+        column_types::<AuditEvent<i64>>(),
+        [
+            ("id", SqlType::BigInt, false),
+            ("action", SqlType::Text, false),
+            ("parent_id", SqlType::BigInt, true),
+        ]
+    );
+    assert_eq!(
+        column_types::<AuditEvent<String>>()[0],
+        ("id", SqlType::Text, false)
+    );
+    // `Option<K>` makes the column nullable through `TypeInfo`.
+    assert!(AuditEvent::<Option<i32>>::fields()[0].nullable);
+}
+
+#[test]
+fn sqlite_generic_model_round_trips_each_key_type() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        for stmt in SchemaBuilder::new()
+            .create_table::<AuditEvent<i64>>()
+            .build()
+        {
+            unwrap_outcome(conn.execute(&cx, &stmt, &[]).await);
+        }
+        let created = event(1_i64, "created", None);
+        let renamed = event(2, "renamed", Some(1));
+        for e in [&created, &renamed] {
+            unwrap_outcome(insert!(e).execute(&cx, &conn).await);
+        }
+        let events = unwrap_outcome(select!(AuditEvent<i64>).all(&cx, &conn).await);
+        assert_eq!(events, [created, renamed]);
+
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        for stmt in SchemaBuilder::new()
+            .create_table::<AuditEvent<String>>()
+            .build()
+        {
+            unwrap_outcome(conn.execute(&cx, &stmt, &[]).await);
+        }
+        let created = event("evt-a".to_string(), "created", None);
+        unwrap_outcome(insert!(&created).execute(&cx, &conn).await);
+        let found = unwrap_outcome(
+            select!(AuditEvent<String>)
+                .filter(Expr::col("id").eq("evt-a"))
+                .one(&cx, &conn)
+                .await,
+        );
+        assert_eq!(found, created);
+        assert_eq!(found.primary_key_value(), vec![Value::Text("evt-a".into())]);
+    });
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use std::collections::HashMap;
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+
+use sqlmodel::prelude::*;
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> T {
+    match outcome {
+        Outcome::Ok(v) => v,
+        Outcome::Err(e) => panic!("unexpected error: {e}"),
+        Outcome::Cancelled(r) => panic!("cancelled: {r:?}"),
+        Outcome::Panicked(p) => panic!("panicked: {p:?}"),
+    }
+}
+
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(table = "heroes", order_by = "id")]
+struct Hero {
+    #[sqlmodel(primary_key)]
+    id: i64,
+    name: String,
+    team_id: Option<i64>,
+}
+
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(table = "memberships")]
+struct Membership {
+    #[sqlmodel(primary_key)]
+    hero_id: i64,
+    #[sqlmodel(primary_key)]
+    team_id: i64,
+}
+
+#[test]
+fn sqlite_select_groups_and_indexes_results() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        unwrap_outcome(
+            conn.execute_script(
+                &cx,
+                "CREATE TABLE heroes (
+                     id INTEGER PRIMARY KEY,
+                     name TEXT NOT NULL,
+                     team_id INTEGER
+                 );
+                 INSERT INTO heroes VALUES
+                     (1, 'Deadpond', 1),
+                     (2, 'Spider-Boy', NULL),
+                     (3, 'Rusty-Man', 1);
+                 CREATE TABLE memberships (
+                     hero_id INTEGER NOT NULL,
+                     team_id INTEGER NOT NULL,
+                     PRIMARY KEY (hero_id, team_id)
+                 );",
+            )
+            .await,
+        );
+
+        let by_team = unwrap_outcome(
+            select!(Hero)
+                .all_grouped_by(&cx, &conn, |hero| hero.team_id)
+                .await,
+        );
+        let names = |team: Option<i64>| -> Vec<&str> {
+            by_team[&team]
+                .iter()
+                .map(|hero| hero.name.as_str())
+                .collect()
+        };
+        assert_eq!(by_team.len(), 2);
+        assert_eq!(names(Some(1)), ["Deadpond", "Rusty-Man"]);
+        assert_eq!(names(None), ["Spider-Boy"]);
+
+        let by_id: HashMap<i64, Hero> =
+            unwrap_outcome(select!(Hero).all_indexed_by_pk(&cx, &conn).await);
+        assert_eq!(by_id.len(), 3);
+        assert_eq!(by_id[&2].name, "Spider-Boy");
+
+        let composite = select!(Membership)
+            .all_indexed_by_pk::<i64, _>(&cx, &conn)
+            .await;
+        assert!(matches!(composite, Outcome::Err(_)));
+    });
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+use serde::{Deserialize, Serialize};
+
+use sqlmodel::SchemaBuilder;
+use sqlmodel::prelude::*;
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> T {
+    match outcome {
+        Outcome::Ok(v) => v,
+        Outcome::Err(e) => panic!("unexpected error: {e}"),
+        Outcome::Cancelled(r) => panic!("cancelled: {r:?}"),
+        Outcome::Panicked(p) => panic!("panicked: {p:?}"),
+    }
+}
+
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(table = "heroes")]
+struct Hero {
+    #[sqlmodel(primary_key, auto_increment)]
+    id: Option<i64>,
+    name: String,
+}
+
+#[derive(sqlmodel::Model, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[sqlmodel(table, inheritance = "joined")]
+struct Person {
+    #[sqlmodel(primary_key)]
+    id: i64,
+    name: String,
+}
+
+#[derive(sqlmodel::Model, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[sqlmodel(table, inherits = "Person")]
+struct Student {
+    #[sqlmodel(parent)]
+    person: Person,
+
+    #[sqlmodel(primary_key)]
+    id: i64,
+
+    grade: String,
+}
+
+#[test]
+fn sqlite_insert_returning_model_hydrates_generated_key() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        for stmt in SchemaBuilder::new().create_table::<Hero>().build() {
+            unwrap_outcome(conn.execute(&cx, &stmt, &[]).await);
+        }
+
+        let hero = Hero {
+            id: None,
+            name: "Deadpond".to_string(),
+        };
+        let stored = unwrap_outcome(insert!(&hero).returning_model().execute(&cx, &conn).await);
+        assert_eq!(
+            stored,
+            Some(Hero {
+                id: Some(1),
+                name: "Deadpond".to_string(),
+            })
+        );
+
+        let skipped = unwrap_outcome(
+            insert!(stored.as_ref().expect("inserted"))
+                .on_conflict_do_nothing()
+                .returning_model()
+                .execute(&cx, &conn)
+                .await,
+        );
+        assert_eq!(skipped, None);
+    });
+}
+
+#[test]
+fn sqlite_insert_returning_model_rereads_joined_inheritance_child() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        let stmts = SchemaBuilder::new()
+            .create_table::<Person>()
+            .create_table::<Student>()
+            .build();
+        for stmt in stmts {
+            unwrap_outcome(conn.execute(&cx, &stmt, &[]).await);
+        }
+
+        let student = Student {
+            person: Person {
+                id: 1,
+                name: "Alice".to_string(),
+            },
+            id: 1,
+            grade: "A".to_string(),
+        };
+        let stored = unwrap_outcome(
+            insert!(&student)
+                .returning_model()
+                .execute(&cx, &conn)
+                .await,
+        );
+        assert_eq!(stored, Some(student));
+    });
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use std::time::Duration;
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+
+use sqlmodel::jobs::{Job, JobHandler, JobQueue, JobStatus, Worker};
+use sqlmodel::prelude::*;
+use sqlmodel::{SchemaBuilder, Session};
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> std::result::Result<T, String> {
+    match outcome {
+        Outcome::Ok(v) => Ok(v),
+        Outcome::Err(e) => Err(format!("unexpected error: {e}")),
+        Outcome::Cancelled(r) => Err(format!("cancelled: {r:?}")),
+        Outcome::Panicked(p) => Err(format!("panicked: {p:?}")),
+    }
+}
+
+/// Succeeds for every job kind except `"fail"`.
+struct FailOnKind;
+
+impl JobHandler for FailOnKind {
+    async fn handle(&self, _cx: &Cx, job: &Job) -> Outcome<(), Error> {
+        if job.kind == "fail" {
+            Outcome::Err(Error::Custom("boom".to_string()))
+        } else {
+            Outcome::Ok(())
+        }
+    }
+}
+
+#[test]
+fn sqlite_jobs_retry_then_dead_letter() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        for stmt in SchemaBuilder::new().create_table::<Job>().build() {
+            unwrap_outcome(conn.execute(&cx, &stmt, &[]).await).expect("execute ddl");
+        }
+
+        let queue = JobQueue::new("mail");
+        let mut session = Session::new(conn);
+        unwrap_outcome(session.begin(&cx).await).expect("begin");
+        let ok = Job::new("send", &"hello").expect("serialize payload");
+        let bad = Job::new("fail", &42)
+            .expect("serialize payload")
+            .max_attempts(2);
+        unwrap_outcome(queue.enqueue(&cx, &mut session, ok).await).expect("enqueue ok");
+        unwrap_outcome(queue.enqueue(&cx, &mut session, bad).await).expect("enqueue bad");
+        unwrap_outcome(session.commit(&cx).await).expect("commit");
+
+        let conn = session.connection();
+        let worker = Worker::new(queue.clone(), FailOnKind).backoff(Duration::ZERO, Duration::ZERO);
+        let other = Worker::new(JobQueue::new("other"), FailOnKind);
+        assert_eq!(
+            unwrap_outcome(other.run_once(&cx, conn).await).expect("other queue"),
+            None
+        );
+
+        let mut statuses = Vec::new();
+        while let Some(status) = unwrap_outcome(worker.run_once(&cx, conn).await).expect("run_once")
+        {
+            statuses.push(status);
+        }
+        assert_eq!(
+            statuses,
+            vec![JobStatus::Done, JobStatus::Pending, JobStatus::Dead]
+        );
+
+        let dead = unwrap_outcome(queue.dead_letters(&cx, conn).await).expect("dead letters");
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].kind, "fail");
+        assert_eq!(dead[0].attempts, 2);
+        assert_eq!(dead[0].last_error.as_deref(), Some("boom"));
+        assert_eq!(dead[0].payload::<i32>().expect("decode payload"), 42);
+    });
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+
+use sqlmodel::prelude::*;
+use sqlmodel::{Migration, MigrationRunner};
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> std::result::Result<T, String> {
+    match outcome {
+        Outcome::Ok(v) => Ok(v),
+        Outcome::Err(e) => Err(format!("unexpected error: {e}")),
+        Outcome::Cancelled(r) => Err(format!("cancelled: {r:?}")),
+        Outcome::Panicked(p) => Err(format!("panicked: {p:?}")),
+    }
+}
+
+const UP: &str = "
+-- counters bumped by a trigger
+CREATE TABLE counters (name TEXT PRIMARY KEY, hits INTEGER NOT NULL DEFAULT 0);
+CREATE TABLE visits (page TEXT NOT NULL);
+
+CREATE TRIGGER count_visit AFTER INSERT ON visits
+BEGIN
+    INSERT OR IGNORE INTO counters (name) VALUES (NEW.page);
+    UPDATE counters SET hits = hits + 1 WHERE name = NEW.page;
+END;
+
+INSERT INTO counters (name) VALUES ('home; index');
+";
+
+#[test]
+fn sqlite_migration_runs_multi_statement_script_with_trigger() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        let runner = MigrationRunner::new(vec![Migration::new(
+            "001",
+            "counters",
+            UP,
+            "DROP TRIGGER count_visit; DROP TABLE visits; DROP TABLE counters;",
+        )]);
+        let applied = unwrap_outcome(runner.migrate(&cx, &conn).await).expect("migrate");
+        assert_eq!(applied, ["001"]);
+
+        unwrap_outcome(
+            conn.execute_script(
+                &cx,
+                "INSERT INTO visits VALUES ('home; index'); INSERT INTO visits VALUES ('about');",
+            )
+            .await,
+        )
+        .expect("insert visits");
+        let rows = unwrap_outcome(
+            conn.query(&cx, "SELECT name, hits FROM counters ORDER BY name", &[])
+                .await,
+        )
+        .expect("query counters");
+        let hits: Vec<(String, i64)> = rows
+            .iter()
+            .map(|r| (r.get_as(0).unwrap(), r.get_as(1).unwrap()))
+            .collect();
+        assert_eq!(
+            hits,
+            [("about".to_string(), 1), ("home; index".to_string(), 1)]
+        );
+
+        let rolled_back = unwrap_outcome(runner.rollback(&cx, &conn).await).expect("rollback");
+        assert_eq!(rolled_back.as_deref(), Some("001"));
+    });
+}
+
+#[test]
+fn sqlite_script_error_names_failing_statement() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        let script = "CREATE TABLE a (x INTEGER);\n\nINSERT INTO missing VALUES (1);\n";
+        let Outcome::Err(e) = conn.execute_script(&cx, script).await else {
+            panic!("expected the second statement to fail");
+        };
+        let query = e.query_error().expect("query error");
+        assert_eq!(query.sql.as_deref(), Some("INSERT INTO missing VALUES (1)"));
+        assert!(
+            query
+                .detail
+                .as_deref()
+                .is_some_and(|d| d.contains("statement 2 of 2 in script, line 3")),
+            "detail: {:?}",
+            query.detail
+        );
+    });
+}
@@ -0,0 +1,11 @@
+-- Migration: Create heroes
+-- Version: 20250101000000
+
+-- ========== UP ==========
+
+CREATE TABLE heroes (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+CREATE INDEX idx_heroes_name ON heroes (name);
+
+-- ========== DOWN ==========
+
+DROP TABLE heroes;
@@ -0,0 +1,19 @@
+//! Auto-generated migration.
+//! Description: Add hero age
+//! Version: 20250102000000
+
+use sqlmodel_schema::Migration;
+
+/// Returns this migration.
+pub fn migration() -> Migration {
+    Migration::new(
+        "20250102000000",
+        "Add hero age",
+        r#"
+ALTER TABLE heroes ADD COLUMN age INTEGER;
+"#,
+        r#"
+ALTER TABLE heroes DROP COLUMN age;
+"#,
+    )
+}
@@ -0,0 +1,54 @@
+use sqlmodel::prelude::*;
+
+#[derive(Model, Debug, Clone)]
+#[sqlmodel(
+    table = "posts",
+    order_by = "published_on DESC, id",
+    scope(published = "published_on IS NOT NULL"),
+    scope(visible = "hidden = 0")
+)]
+struct Post {
+    #[sqlmodel(primary_key, auto_increment)]
+    id: Option<i64>,
+    title: String,
+    #[sqlmodel(column = "published_at", nullable)]
+    published_on: Option<i64>,
+    hidden: bool,
+}
+
+#[test]
+fn default_order_uses_column_names() {
+    assert_eq!(
+        Post::DEFAULT_ORDER_BY
+            .iter()
+            .map(|o| (o.column, o.descending))
+            .collect::<Vec<_>>(),
+        vec![("published_at", true), ("id", false)]
+    );
+}
+
+#[test]
+fn select_applies_default_order_until_overridden() {
+    let (sql, _) = select!(Post).build();
+    assert_eq!(
+        sql,
+        "SELECT * FROM posts ORDER BY \"published_at\" DESC, \"id\" ASC"
+    );
+
+    let (sql, _) = select!(Post)
+        .order_by(OrderBy::asc(Expr::col("title")))
+        .build();
+    assert_eq!(sql, "SELECT * FROM posts ORDER BY \"title\" ASC");
+}
+
+#[test]
+fn scopes_compose_as_filters() {
+    let (sql, _) = select!(Post)
+        .filter(Post::scope_published().and(Post::scope_visible()))
+        .unordered()
+        .build();
+    assert_eq!(
+        sql,
+        "SELECT * FROM posts WHERE (published_on IS NOT NULL) AND (hidden = 0)"
+    );
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+
+use sqlmodel::prelude::*;
+use sqlmodel::registry::ModelDyn;
+use sqlmodel::{ModelRegistry, create_all_registered};
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> T {
+    match outcome {
+        Outcome::Ok(v) => v,
+        Outcome::Err(e) => panic!("unexpected error: {e}"),
+        Outcome::Cancelled(r) => panic!("cancelled: {r:?}"),
+        Outcome::Panicked(p) => panic!("panicked: {p:?}"),
+    }
+}
+
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(table = "guild_members", register)]
+struct Member {
+    #[sqlmodel(primary_key)]
+    id: i64,
+    name: String,
+    #[sqlmodel(foreign_key = "guilds.id")]
+    guild_id: i64,
+}
+
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(table = "guilds", register)]
+struct Guild {
+    #[sqlmodel(primary_key)]
+    id: i64,
+    name: String,
+}
+
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(table = "unregistered")]
+struct Unregistered {
+    #[sqlmodel(primary_key)]
+    id: i64,
+}
+
+#[test]
+fn sqlite_create_all_registered_discovers_models() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    let registry = ModelRegistry::global();
+    assert_eq!(registry.table_names(), ["guild_members", "guilds"]);
+    assert!(!registry.contains(Unregistered::TABLE_NAME));
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        unwrap_outcome(conn.execute(&cx, "PRAGMA foreign_keys = ON", &[]).await);
+
+        // Members reference guilds, so guilds must be created first.
+        let created = unwrap_outcome(create_all_registered(&cx, &conn).await);
+        assert_eq!(created, ["guild_members", "guilds"]);
+        assert!(unwrap_outcome(create_all_registered(&cx, &conn).await).is_empty());
+
+        unwrap_outcome(
+            conn.execute_script(
+                &cx,
+                "INSERT INTO guilds VALUES (1, 'Mages');
+                 INSERT INTO guild_members VALUES (1, 'Merlin', 1);",
+            )
+            .await,
+        );
+
+        let mut models: Vec<Box<dyn ModelDyn>> = Vec::new();
+        for table in ["guilds", "guild_members"] {
+            let descriptor = registry.get(table).expect("registered");
+            let rows = unwrap_outcome(
+                conn.query(&cx, &format!("SELECT * FROM {table}"), &[])
+                    .await,
+            );
+            models.push(descriptor.from_row(&rows[0]).expect("hydrate"));
+        }
+        assert_eq!(models[0].pk_values(), vec![Value::BigInt(1)]);
+        assert_eq!(
+            models[1].downcast_ref::<Member>(),
+            Some(&Member {
+                id: 1,
+                name: "Merlin".to_string(),
+                guild_id: 1
+            })
+        );
+    });
+}
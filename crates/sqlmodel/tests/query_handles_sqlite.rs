@@ -0,0 +1,86 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+
+use sqlmodel::prelude::*;
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> T {
+    match outcome {
+        Outcome::Ok(v) => v,
+        Outcome::Err(e) => panic!("unexpected error: {e}"),
+        Outcome::Cancelled(r) => panic!("cancelled: {r:?}"),
+        Outcome::Panicked(p) => panic!("panicked: {p:?}"),
+    }
+}
+
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(table = "heroes", order_by = "id")]
+struct Hero {
+    #[sqlmodel(primary_key)]
+    id: i64,
+    name: String,
+    email: String,
+    active: bool,
+}
+
+static HERO_BY_EMAIL: Query<Hero, (String,)> =
+    query!(select!(Hero).filter(Expr::col("email").eq(Expr::placeholder(1))));
+
+static ACTIVE_HEROES_AFTER: Query<Hero, (i64,)> = query!(
+    select!(Hero)
+        .filter(Expr::col("active").eq(true))
+        .filter(Expr::col("id").gt(Expr::placeholder(1)))
+);
+
+#[test]
+fn sqlite_static_query_handles_bind_typed_params() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        unwrap_outcome(
+            conn.execute_script(
+                &cx,
+                "CREATE TABLE heroes (
+                     id INTEGER PRIMARY KEY,
+                     name TEXT NOT NULL,
+                     email TEXT NOT NULL,
+                     active INTEGER NOT NULL
+                 );
+                 INSERT INTO heroes VALUES
+                     (1, 'Deadpond', 'dp@example.com', 1),
+                     (2, 'Spider-Boy', 'sb@example.com', 0),
+                     (3, 'Rusty-Man', 'rm@example.com', 1);",
+            )
+            .await,
+        );
+
+        let hero = unwrap_outcome(
+            HERO_BY_EMAIL
+                .one(&cx, &conn, ("sb@example.com".to_string(),))
+                .await,
+        );
+        assert_eq!(hero.name, "Spider-Boy");
+
+        let ids: Vec<i64> = unwrap_outcome(ACTIVE_HEROES_AFTER.all(&cx, &conn, (0,)).await)
+            .into_iter()
+            .map(|hero| hero.id)
+            .collect();
+        assert_eq!(ids, [1, 3]);
+
+        let by_email = unwrap_outcome(HERO_BY_EMAIL.prepare(&cx, &conn).await);
+        for (email, name) in [
+            ("dp@example.com", Some("Deadpond")),
+            ("rm@example.com", Some("Rusty-Man")),
+            ("nobody@example.com", None),
+        ] {
+            let hero = unwrap_outcome(by_email.first(&cx, (email.to_string(),)).await);
+            assert_eq!(hero.map(|hero| hero.name).as_deref(), name);
+        }
+    });
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+
+use sqlmodel::prelude::*;
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> T {
+    match outcome {
+        Outcome::Ok(v) => v,
+        Outcome::Err(e) => panic!("unexpected error: {e}"),
+        Outcome::Cancelled(r) => panic!("cancelled: {r:?}"),
+        Outcome::Panicked(p) => panic!("panicked: {p:?}"),
+    }
+}
+
+/// Maps onto a legacy table without annotating each field.
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(
+    table = "LEGACY_ACCOUNTS",
+    rename_all = "SCREAMING_SNAKE",
+    order_by = "display_name"
+)]
+struct Account {
+    #[sqlmodel(primary_key)]
+    account_id: i64,
+    display_name: String,
+    #[sqlmodel(column = "email")]
+    email: String,
+}
+
+#[test]
+fn sqlite_rename_all_reads_and_writes_legacy_columns() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    assert_eq!(
+        Account::fields()
+            .iter()
+            .map(|f| f.column_name)
+            .collect::<Vec<_>>(),
+        ["ACCOUNT_ID", "DISPLAY_NAME", "email"]
+    );
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        unwrap_outcome(
+            conn.execute_script(
+                &cx,
+                "CREATE TABLE LEGACY_ACCOUNTS (
+                     ACCOUNT_ID INTEGER PRIMARY KEY,
+                     DISPLAY_NAME TEXT NOT NULL,
+                     email TEXT NOT NULL
+                 );
+                 INSERT INTO LEGACY_ACCOUNTS VALUES (1, 'Zed', 'zed@example.com');",
+            )
+            .await,
+        );
+
+        let ada = Account {
+            account_id: 2,
+            display_name: "Ada".to_string(),
+            email: "ada@example.com".to_string(),
+        };
+        unwrap_outcome(insert!(&ada).execute(&cx, &conn).await);
+
+        let accounts = unwrap_outcome(select!(Account).all(&cx, &conn).await);
+        assert_eq!(accounts.len(), 2);
+        assert_eq!(accounts[0], ada);
+        assert_eq!(accounts[1].display_name, "Zed");
+    });
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+use serde::{Deserialize, Serialize};
+
+use sqlmodel::prelude::*;
+use sqlmodel::{Page, SchemaBuilder};
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> T {
+    match outcome {
+        Outcome::Ok(v) => v,
+        Outcome::Err(e) => panic!("unexpected error: {e}"),
+        Outcome::Cancelled(r) => panic!("cancelled: {r:?}"),
+        Outcome::Panicked(p) => panic!("panicked: {p:?}"),
+    }
+}
+
+#[derive(Model, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[sqlmodel(table = "accounts")]
+struct Account {
+    #[sqlmodel(primary_key, auto_increment)]
+    id: Option<i64>,
+    email: String,
+    name: String,
+}
+
+#[derive(Repository)]
+#[repository(model = Account, find_by(email, name))]
+struct AccountRepo<'s> {
+    session: &'s mut Session<SqliteConnection>,
+}
+
+/// A service that only sees the narrow repository interface.
+async fn rename<R: Repository<Account>>(
+    cx: &Cx,
+    repo: &mut R,
+    id: i64,
+    name: &str,
+) -> Outcome<bool, Error> {
+    let mut account = match repo.find(cx, id).await {
+        Outcome::Ok(Some(account)) => account,
+        Outcome::Ok(None) => return Outcome::Ok(false),
+        Outcome::Err(e) => return Outcome::Err(e),
+        Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+        Outcome::Panicked(p) => return Outcome::Panicked(p),
+    };
+    account.name = name.to_string();
+    repo.save(cx, &account).await.map(|()| true)
+}
+
+fn account(id: i64, email: &str, name: &str) -> Account {
+    Account {
+        id: Some(id),
+        email: email.to_string(),
+        name: name.to_string(),
+    }
+}
+
+#[test]
+fn sqlite_repository_crud_pagination_and_finders() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        for stmt in SchemaBuilder::new().create_table::<Account>().build() {
+            unwrap_outcome(conn.execute(&cx, &stmt, &[]).await);
+        }
+        let mut session = Session::new(conn);
+
+        let mut repo = AccountRepo {
+            session: &mut session,
+        };
+        for (id, email, name) in [
+            (1, "ada@example.com", "Ada"),
+            (2, "bob@example.com", "Bob"),
+            (3, "cy@example.com", "Bob"),
+        ] {
+            unwrap_outcome(repo.save(&cx, &account(id, email, name)).await);
+        }
+
+        let ada = unwrap_outcome(repo.find_one_by_email(&cx, "ada@example.com").await)
+            .expect("ada saved");
+        assert_eq!(ada.name, "Ada");
+        assert_eq!(unwrap_outcome(repo.find_by_name(&cx, "Bob").await).len(), 2);
+        assert!(unwrap_outcome(repo.find_one_by_email(&cx, "nobody@example.com").await).is_none());
+        assert_eq!(unwrap_outcome(repo.find_all(&cx).await).len(), 3);
+
+        let first: Page<Account> = unwrap_outcome(repo.paginate(&cx, 1, 2).await);
+        assert_eq!(first.total, 3);
+        assert_eq!(first.items.len(), 2);
+        assert_eq!(first.items[0].email, "ada@example.com");
+        assert!(first.has_next());
+        let second = unwrap_outcome(repo.paginate(&cx, 2, 2).await);
+        assert_eq!(second.items.len(), 1);
+        assert!(!second.has_next());
+
+        let ada_id = ada.id.expect("ada has an id");
+        assert!(unwrap_outcome(
+            rename(&cx, &mut repo, ada_id, "Countess").await
+        ));
+        assert!(!unwrap_outcome(rename(&cx, &mut repo, 999, "Nobody").await));
+
+        unwrap_outcome(repo.delete(&cx, &ada).await);
+        assert!(unwrap_outcome(repo.find(&cx, ada_id).await).is_none());
+
+        // The session's own repository view sees the same rows.
+        let mut accounts = session.repository::<Account>();
+        let rows = unwrap_outcome(accounts.find_all(&cx).await);
+        assert_eq!(rows.len(), 2);
+    });
+}
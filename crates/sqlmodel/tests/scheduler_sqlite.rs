@@ -0,0 +1,103 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use std::time::Duration;
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+
+use sqlmodel::SchemaBuilder;
+use sqlmodel::prelude::*;
+use sqlmodel::scheduler::{RunStatus, Schedule, ScheduledTask, Scheduler, TaskRecord};
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> std::result::Result<T, String> {
+    match outcome {
+        Outcome::Ok(v) => Ok(v),
+        Outcome::Err(e) => Err(format!("unexpected error: {e}")),
+        Outcome::Cancelled(r) => Err(format!("cancelled: {r:?}")),
+        Outcome::Panicked(p) => Err(format!("panicked: {p:?}")),
+    }
+}
+
+struct Succeed;
+
+impl ScheduledTask for Succeed {
+    async fn run(&self, _cx: &Cx) -> Outcome<(), Error> {
+        Outcome::Ok(())
+    }
+}
+
+struct Fail;
+
+impl ScheduledTask for Fail {
+    async fn run(&self, _cx: &Cx) -> Outcome<(), Error> {
+        Outcome::Err(Error::Custom("disk full".to_string()))
+    }
+}
+
+fn statuses(runs: &[sqlmodel::scheduler::TaskRun]) -> Vec<(&str, RunStatus)> {
+    runs.iter()
+        .map(|run| (run.name.as_str(), run.status.clone()))
+        .collect()
+}
+
+#[test]
+fn sqlite_scheduler_records_runs_and_waits_for_interval() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        for stmt in SchemaBuilder::new().create_table::<TaskRecord>().build() {
+            unwrap_outcome(conn.execute(&cx, &stmt, &[]).await).expect("execute ddl");
+        }
+
+        let hourly = Schedule::every(Duration::from_secs(3600));
+        let scheduler = Scheduler::new()
+            .register("report", hourly, Succeed)
+            .register("backup", hourly, Fail)
+            .register("always", Schedule::every(Duration::ZERO), Succeed);
+
+        let first = unwrap_outcome(scheduler.tick(&cx, &conn).await).expect("first tick");
+        assert_eq!(
+            statuses(&first),
+            vec![
+                ("report", RunStatus::Succeeded),
+                ("backup", RunStatus::Failed("disk full".to_string())),
+                ("always", RunStatus::Succeeded),
+            ]
+        );
+
+        let second = unwrap_outcome(scheduler.tick(&cx, &conn).await).expect("second tick");
+        assert_eq!(
+            statuses(&second),
+            vec![
+                ("report", RunStatus::NotDue),
+                ("backup", RunStatus::NotDue),
+                ("always", RunStatus::Succeeded),
+            ]
+        );
+
+        let records = unwrap_outcome(
+            select!(TaskRecord)
+                .order_by(OrderBy::asc(Expr::col("name")))
+                .all(&cx, &conn)
+                .await,
+        )
+        .expect("load records");
+        let summary: Vec<(&str, i64, i64, Option<&str>)> = records
+            .iter()
+            .map(|r| (r.name.as_str(), r.runs, r.failures, r.last_error.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("always", 2, 0, None),
+                ("backup", 1, 1, Some("disk full")),
+                ("report", 1, 0, None),
+            ]
+        );
+    });
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+
+use sqlmodel::prelude::*;
+use sqlmodel_schema::admin;
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> T {
+    match outcome {
+        Outcome::Ok(v) => v,
+        Outcome::Err(e) => panic!("unexpected error: {e}"),
+        Outcome::Cancelled(r) => panic!("cancelled: {r:?}"),
+        Outcome::Panicked(p) => panic!("panicked: {p:?}"),
+    }
+}
+
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(table = "teams")]
+struct Team {
+    #[sqlmodel(primary_key)]
+    id: i64,
+    name: String,
+}
+
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(table = "heroes")]
+struct Hero {
+    #[sqlmodel(primary_key)]
+    id: i64,
+    name: String,
+    #[sqlmodel(foreign_key = "teams.id")]
+    team_id: i64,
+}
+
+async fn count(cx: &Cx, conn: &SqliteConnection, table: &str) -> i64 {
+    let rows = unwrap_outcome(
+        conn.query(cx, &format!("SELECT COUNT(*) FROM {table}"), &[])
+            .await,
+    );
+    rows[0].get_as(0).expect("count")
+}
+
+#[test]
+fn sqlite_reset_creates_and_empties_model_tables() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        unwrap_outcome(conn.execute(&cx, "PRAGMA foreign_keys = ON", &[]).await);
+
+        unwrap_outcome(admin::reset::<(Team, Hero), _>(&cx, &conn).await);
+        unwrap_outcome(
+            conn.execute_script(
+                &cx,
+                "INSERT INTO teams VALUES (1, 'Avengers');
+                 INSERT INTO heroes VALUES (1, 'Thor', 1);",
+            )
+            .await,
+        );
+        assert_eq!(count(&cx, &conn, "heroes").await, 1);
+
+        // Teams come first in the tuple, but heroes must be emptied first.
+        unwrap_outcome(admin::reset::<(Team, Hero), _>(&cx, &conn).await);
+        assert_eq!(count(&cx, &conn, "teams").await, 0);
+        assert_eq!(count(&cx, &conn, "heroes").await, 0);
+    });
+}
+
+#[test]
+fn sqlite_create_database_is_unsupported() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        let Outcome::Err(e) = admin::create_database(&cx, &conn, "app_test").await else {
+            panic!("expected SQLite to reject create_database");
+        };
+        assert!(e.to_string().contains("not supported on SQLite"), "{e}");
+    });
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+
+use sqlmodel::prelude::*;
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> T {
+    match outcome {
+        Outcome::Ok(v) => v,
+        Outcome::Err(e) => panic!("unexpected error: {e}"),
+        Outcome::Cancelled(r) => panic!("cancelled: {r:?}"),
+        Outcome::Panicked(p) => panic!("panicked: {p:?}"),
+    }
+}
+
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(table = "events")]
+struct Event {
+    #[sqlmodel(primary_key)]
+    id: i64,
+    kind: String,
+}
+
+#[test]
+fn sqlite_select_from_attached_month_databases() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        for (month, rows) in [
+            ("m2024_01", "(1, 'login'), (2, 'logout')"),
+            ("m2024_02", "(3, 'login')"),
+        ] {
+            conn.attach(":memory:", month).expect("attach month");
+            unwrap_outcome(
+                conn.execute_script(
+                    &cx,
+                    &format!(
+                        "CREATE TABLE {month}.events (id INTEGER PRIMARY KEY, kind TEXT NOT NULL);
+                         INSERT INTO {month}.events VALUES {rows};"
+                    ),
+                )
+                .await,
+            );
+        }
+
+        let january = unwrap_outcome(
+            select!(Event)
+                .from_schema("m2024_01")
+                .filter(Expr::qualified("events", "kind").eq("login"))
+                .all(&cx, &conn)
+                .await,
+        );
+        assert_eq!(
+            january,
+            vec![Event {
+                id: 1,
+                kind: "login".to_string()
+            }]
+        );
+
+        let february = unwrap_outcome(select!(Event).from_schema("m2024_02").all(&cx, &conn).await);
+        assert_eq!(february.len(), 1);
+        assert_eq!(february[0].id, 3);
+
+        // The main database has no events table of its own.
+        assert!(matches!(
+            select!(Event).all(&cx, &conn).await,
+            Outcome::Err(_)
+        ));
+    });
+}
@@ -0,0 +1,66 @@
+use sqlmodel::CreateTable;
+use sqlmodel::prelude::*;
+use sqlmodel_schema::diff::{SchemaOperation, schema_diff};
+use sqlmodel_schema::{DatabaseSchema, Dialect, ModelSchema, generator_for_dialect};
+
+#[derive(Model, Debug, Clone)]
+#[sqlmodel(table = "invoices", comment = "Issued customer invoices")]
+struct Invoice {
+    #[sqlmodel(primary_key, auto_increment)]
+    id: Option<i64>,
+    #[sqlmodel(column_comment = "Total in cents")]
+    amount: i64,
+    memo: Option<String>,
+}
+
+#[test]
+fn derive_carries_table_and_column_comments() {
+    assert_eq!(Invoice::TABLE_COMMENT, Some("Issued customer invoices"));
+
+    let table = Invoice::table_schema();
+    assert_eq!(table.comment.as_deref(), Some("Issued customer invoices"));
+    assert_eq!(
+        table.column("amount").unwrap().comment.as_deref(),
+        Some("Total in cents")
+    );
+    assert_eq!(table.column("memo").unwrap().comment, None);
+}
+
+#[test]
+fn create_emits_comment_ddl_per_dialect() {
+    assert_eq!(
+        CreateTable::<Invoice>::new().build_comments(Dialect::Postgres),
+        [
+            "COMMENT ON TABLE \"invoices\" IS 'Issued customer invoices'",
+            "COMMENT ON COLUMN \"invoices\".\"amount\" IS 'Total in cents'",
+        ]
+    );
+
+    let create = generator_for_dialect(Dialect::Mysql)
+        .generate(&SchemaOperation::CreateTable(Invoice::table_schema()));
+    assert!(create[0].contains("COMMENT 'Total in cents'"));
+    assert!(create[0].ends_with(" COMMENT='Issued customer invoices'"));
+}
+
+#[test]
+fn introspected_comments_round_trip_through_diff() {
+    let mut current = DatabaseSchema::new(Dialect::Postgres);
+    let mut reflected = Invoice::table_schema();
+    reflected.comment = Some("Invoices".to_string());
+    current.tables.insert(reflected.name.clone(), reflected);
+
+    let mut expected = DatabaseSchema::new(Dialect::Postgres);
+    expected
+        .tables
+        .insert("invoices".to_string(), Invoice::table_schema());
+
+    let diff = schema_diff(&current, &expected);
+    let ddl = generator_for_dialect(Dialect::Postgres).generate_all(&diff.operations);
+    assert_eq!(
+        ddl,
+        ["COMMENT ON TABLE \"invoices\" IS 'Issued customer invoices'"]
+    );
+
+    let in_sync = schema_diff(&expected, &expected);
+    assert!(in_sync.is_empty());
+}
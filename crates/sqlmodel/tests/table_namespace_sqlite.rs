@@ -0,0 +1,92 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use std::sync::Arc;
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+
+use sqlmodel::prelude::*;
+use sqlmodel::{InterceptedConnection, InterceptorChain, SchemaBuilder, TableNamespace};
+use sqlmodel_schema::introspect::{Dialect, Introspector};
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> std::result::Result<T, String> {
+    match outcome {
+        Outcome::Ok(v) => Ok(v),
+        Outcome::Err(e) => Err(format!("unexpected error: {e}")),
+        Outcome::Cancelled(r) => Err(format!("cancelled: {r:?}")),
+        Outcome::Panicked(p) => Err(format!("panicked: {p:?}")),
+    }
+}
+
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(table = "heroes", index(columns("name")))]
+struct Hero {
+    #[sqlmodel(primary_key, auto_increment)]
+    id: Option<i64>,
+    name: String,
+}
+
+fn namespace(prefix: &str) -> TableNamespace {
+    TableNamespace::new().prefix(prefix).model::<Hero>()
+}
+
+fn intercepted(conn: SqliteConnection, prefix: &str) -> InterceptedConnection<SqliteConnection> {
+    InterceptedConnection::new(
+        conn,
+        InterceptorChain::new().with(Arc::new(namespace(prefix))),
+    )
+}
+
+#[test]
+fn sqlite_prefixed_namespaces_are_isolated() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        for prefix in ["t1_", "t2_"] {
+            let ddl = SchemaBuilder::new()
+                .create_table::<Hero>()
+                .namespace(namespace(prefix))
+                .build();
+            for stmt in ddl {
+                unwrap_outcome(conn.execute(&cx, &stmt, &[]).await).expect("execute ddl");
+            }
+        }
+
+        let t1 = intercepted(conn, "t1_");
+        for name in ["Deadpond", "Rusty-Man"] {
+            let hero = Hero {
+                id: None,
+                name: name.to_string(),
+            };
+            unwrap_outcome(insert!(&hero).execute(&cx, &t1).await).expect("insert into t1");
+        }
+        let t2 = intercepted(t1.into_inner(), "t2_");
+        let hero = Hero {
+            id: None,
+            name: "Spider-Boy".to_string(),
+        };
+        unwrap_outcome(insert!(&hero).execute(&cx, &t2).await).expect("insert into t2");
+
+        let in_t2 = unwrap_outcome(select!(Hero).all(&cx, &t2).await).expect("select t2");
+        assert_eq!(in_t2.len(), 1);
+        assert_eq!(in_t2[0].name, "Spider-Boy");
+
+        let conn = t2.into_inner();
+        let rows = unwrap_outcome(conn.query(&cx, "SELECT COUNT(*) FROM t1_heroes", &[]).await)
+            .expect("count t1");
+        assert_eq!(rows[0].get(0).and_then(|v| v.as_i64()), Some(2));
+
+        let introspector = Introspector::new(Dialect::Sqlite).with_namespace(namespace("t1_"));
+        let names = unwrap_outcome(introspector.table_names(&cx, &conn).await).expect("tables");
+        assert_eq!(names, vec!["heroes".to_string()]);
+        let info = unwrap_outcome(introspector.table_info(&cx, &conn, "heroes").await)
+            .expect("table info");
+        assert_eq!(info.name, "heroes");
+        assert!(info.indexes.iter().any(|idx| idx.name == "idx_heroes_name"));
+    });
+}
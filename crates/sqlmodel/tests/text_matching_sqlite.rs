@@ -0,0 +1,82 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+
+use sqlmodel::prelude::*;
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> T {
+    match outcome {
+        Outcome::Ok(v) => v,
+        Outcome::Err(e) => panic!("unexpected error: {e}"),
+        Outcome::Cancelled(r) => panic!("cancelled: {r:?}"),
+        Outcome::Panicked(p) => panic!("panicked: {p:?}"),
+    }
+}
+
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(table = "products", order_by = "id")]
+struct Product {
+    #[sqlmodel(primary_key)]
+    id: i64,
+    sku: String,
+}
+
+#[test]
+fn sqlite_regex_and_unsupported_fuzzy_matching() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        // `x REGEXP y` calls regexp(y, x); a prefix matcher is enough here
+        conn.create_deterministic_function("regexp", |args| match (&args[0], &args[1]) {
+            (Value::Text(pattern), Value::Text(text)) => Ok(Value::Bool(
+                text.starts_with(pattern.trim_start_matches('^')),
+            )),
+            _ => Ok(Value::Null),
+        })
+        .expect("register regexp");
+        unwrap_outcome(
+            conn.execute_script(
+                &cx,
+                "CREATE TABLE products (id INTEGER PRIMARY KEY, sku TEXT NOT NULL);
+                 INSERT INTO products VALUES (1, 'ABC-1'), (2, 'XYZ-2'), (3, 'ABC-3');",
+            )
+            .await,
+        );
+
+        let abc = unwrap_outcome(
+            select!(Product)
+                .filter(Expr::col("sku").regex_match("^ABC"))
+                .all(&cx, &conn)
+                .await,
+        );
+        assert_eq!(abc.iter().map(|p| p.id).collect::<Vec<_>>(), [1, 3]);
+
+        let others = unwrap_outcome(
+            select!(Product)
+                .filter(Expr::col("sku").not_regex_match("^ABC"))
+                .all(&cx, &conn)
+                .await,
+        );
+        assert_eq!(others.iter().map(|p| p.id).collect::<Vec<_>>(), [2]);
+
+        // PostgreSQL-only operators fail with an error that names them
+        let Outcome::Err(err) = select!(Product)
+            .filter(Expr::col("sku").similar_to("ABC-%"))
+            .all(&cx, &conn)
+            .await
+        else {
+            panic!("SIMILAR TO should be rejected on SQLite");
+        };
+        assert!(
+            err.to_string()
+                .contains("sqlmodel_similar_to_requires_postgres"),
+            "{err}"
+        );
+    });
+}
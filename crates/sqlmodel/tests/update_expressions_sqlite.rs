@@ -0,0 +1,74 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+
+use sqlmodel::prelude::*;
+use sqlmodel_query::UpdateBuilder;
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> T {
+    match outcome {
+        Outcome::Ok(v) => v,
+        Outcome::Err(e) => panic!("unexpected error: {e}"),
+        Outcome::Cancelled(r) => panic!("cancelled: {r:?}"),
+        Outcome::Panicked(p) => panic!("panicked: {p:?}"),
+    }
+}
+
+#[derive(Model, Debug, Clone, PartialEq)]
+#[sqlmodel(table = "pages", order_by = "id")]
+struct Page {
+    #[sqlmodel(primary_key)]
+    id: i64,
+    visits: i64,
+    touched_at: Option<String>,
+}
+
+#[test]
+fn sqlite_update_with_expression_assignments() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        unwrap_outcome(
+            conn.execute_script(
+                &cx,
+                "CREATE TABLE pages (
+                     id INTEGER PRIMARY KEY,
+                     visits INTEGER NOT NULL,
+                     touched_at TEXT
+                 );
+                 INSERT INTO pages VALUES (1, 10, NULL), (2, 20, NULL);",
+            )
+            .await,
+        );
+
+        for _ in 0..2 {
+            let affected = unwrap_outcome(
+                UpdateBuilder::<Page>::empty()
+                    .set("visits", Expr::col("visits").add(1))
+                    .set_now("touched_at")
+                    .filter(Expr::col("id").eq(1))
+                    .execute(&cx, &conn)
+                    .await,
+            );
+            assert_eq!(affected, 1);
+        }
+
+        let pages = unwrap_outcome(select!(Page).all(&cx, &conn).await);
+        assert_eq!(pages[0].visits, 12);
+        assert!(pages[0].touched_at.is_some());
+        assert_eq!(
+            pages[1],
+            Page {
+                id: 2,
+                visits: 20,
+                touched_at: None,
+            }
+        );
+    });
+}
@@ -0,0 +1,119 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+
+use sqlmodel::prelude::*;
+use sqlmodel::{ValidationError, ValidationErrorKind, ValidationErrors};
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> T {
+    match outcome {
+        Outcome::Ok(v) => v,
+        Outcome::Err(e) => panic!("unexpected error: {e}"),
+        Outcome::Cancelled(r) => panic!("cancelled: {r:?}"),
+        Outcome::Panicked(p) => panic!("panicked: {p:?}"),
+    }
+}
+
+#[derive(Validate, Debug)]
+#[validate(schema = "check_dates")]
+struct EventForm {
+    #[validate(min_length = 1, custom_async = "slug_is_unique")]
+    slug: String,
+    start_day: Option<i64>,
+    #[validate(required_if = "start_day")]
+    end_day: Option<i64>,
+}
+
+impl EventForm {
+    fn check_dates(&self) -> Result<(), ValidationError> {
+        let mut errors = ValidationError::new();
+        match (self.start_day, self.end_day) {
+            (Some(start), Some(end)) if end < start => {
+                errors.add_custom("end_day", "must not be before start_day");
+            }
+            _ => {}
+        }
+        errors.into_result()
+    }
+
+    async fn slug_is_unique<C: Connection>(
+        &self,
+        cx: &Cx,
+        conn: &C,
+        slug: &str,
+    ) -> Outcome<Result<(), String>, Error> {
+        let rows = match conn
+            .query(
+                cx,
+                "SELECT 1 FROM events WHERE slug = ?1",
+                &[Value::Text(slug.to_string())],
+            )
+            .await
+        {
+            Outcome::Ok(rows) => rows,
+            Outcome::Err(e) => return Outcome::Err(e),
+            Outcome::Cancelled(r) => return Outcome::Cancelled(r),
+            Outcome::Panicked(p) => return Outcome::Panicked(p),
+        };
+        Outcome::Ok(if rows.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("'{slug}' is already taken"))
+        })
+    }
+}
+
+fn form(slug: &str, start_day: Option<i64>, end_day: Option<i64>) -> EventForm {
+    EventForm {
+        slug: slug.to_string(),
+        start_day,
+        end_day,
+    }
+}
+
+#[test]
+fn sqlite_validate_async_aggregates_sync_and_async_failures() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        unwrap_outcome(
+            conn.execute_script(
+                &cx,
+                "CREATE TABLE events (slug TEXT PRIMARY KEY);
+                 INSERT INTO events VALUES ('launch');",
+            )
+            .await,
+        );
+
+        assert!(
+            unwrap_outcome(
+                form("meetup", Some(1), Some(2))
+                    .validate_async(&cx, &conn)
+                    .await
+            )
+            .is_ok()
+        );
+
+        // The sync checks alone do not know the slug is taken.
+        let taken = form("launch", Some(3), None);
+        let sync_errors = taken.validate().unwrap_err();
+        assert_eq!(sync_errors.errors.len(), 1);
+        assert_eq!(sync_errors.errors[0].kind, ValidationErrorKind::RequiredIf);
+
+        let errors: ValidationErrors =
+            unwrap_outcome(taken.validate_async(&cx, &conn).await).unwrap_err();
+        assert_eq!(errors.paths().collect::<Vec<_>>(), ["end_day", "slug"]);
+        assert_eq!(errors.get("slug")[0].message, "'launch' is already taken");
+
+        let backwards = form("retro", Some(5), Some(4));
+        let errors = unwrap_outcome(backwards.validate_async(&cx, &conn).await).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors.get("end_day")[0].kind, ValidationErrorKind::Custom);
+    });
+}
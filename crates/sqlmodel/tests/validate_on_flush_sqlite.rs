@@ -0,0 +1,86 @@
+#![cfg(feature = "c-sqlite-tests")]
+
+use asupersync::runtime::RuntimeBuilder;
+use asupersync::{Cx, Outcome};
+use serde::{Deserialize, Serialize};
+
+use sqlmodel::prelude::*;
+use sqlmodel::{SchemaBuilder, ValidationErrorKind};
+use sqlmodel_sqlite::SqliteConnection;
+
+fn unwrap_outcome<T>(outcome: Outcome<T, Error>) -> T {
+    match outcome {
+        Outcome::Ok(v) => v,
+        Outcome::Err(e) => panic!("unexpected error: {e}"),
+        Outcome::Cancelled(r) => panic!("cancelled: {r:?}"),
+        Outcome::Panicked(p) => panic!("panicked: {p:?}"),
+    }
+}
+
+#[derive(Model, Validate, Debug, Clone, Serialize, Deserialize)]
+#[sqlmodel(table = "products")]
+struct Product {
+    #[sqlmodel(primary_key)]
+    id: i64,
+    #[validate(min_length = 1)]
+    name: String,
+    #[validate(min = 0)]
+    price_cents: i64,
+}
+
+#[test]
+fn sqlite_validate_on_flush_blocks_invalid_inserts_and_updates() {
+    let rt = RuntimeBuilder::current_thread()
+        .build()
+        .expect("create asupersync runtime");
+    let cx = Cx::for_testing();
+
+    rt.block_on(async {
+        let conn = SqliteConnection::open_memory().expect("open sqlite memory db");
+        for stmt in SchemaBuilder::new().create_table::<Product>().build() {
+            unwrap_outcome(conn.execute(&cx, &stmt, &[]).await);
+        }
+        let mut session = Session::with_config(
+            conn,
+            SessionConfig {
+                validate_on_flush: true,
+                ..Default::default()
+            },
+        );
+
+        let mut lamp = Product {
+            id: 1,
+            name: "Lamp".to_string(),
+            price_cents: -5,
+        };
+        session.add(&lamp);
+        let Outcome::Err(Error::InvalidObject {
+            table,
+            primary_key,
+            errors,
+        }) = session.flush(&cx).await
+        else {
+            panic!("expected an invalid object error");
+        };
+        assert_eq!(table, "products");
+        assert_eq!(primary_key, vec![Value::BigInt(1)]);
+        assert_eq!(errors.errors[0].kind, ValidationErrorKind::Min);
+        let rows = unwrap_outcome(
+            session
+                .connection()
+                .query(&cx, "SELECT id FROM products", &[])
+                .await,
+        );
+        assert!(rows.is_empty());
+
+        lamp.price_cents = 1999;
+        session.add(&lamp);
+        unwrap_outcome(session.flush(&cx).await);
+
+        // Dirty objects are checked too.
+        lamp.name.clear();
+        session.mark_dirty(&lamp);
+        let outcome = session.flush(&cx).await;
+        assert!(matches!(outcome, Outcome::Err(Error::InvalidObject { .. })));
+    });
+}